@@ -0,0 +1,108 @@
+@tool
+extends VBoxContainer
+
+## MCP Activity Dock
+## Shows activity items (operations, pending approvals, recent diffs) queued by the MCP server.
+## The server appends items to res://.godot-mcp/activity.json; this dock polls the file.
+
+const ACTIVITY_PATH = "res://.godot-mcp/activity.json"
+const POLL_INTERVAL = 1.0
+const MAX_VISIBLE_ITEMS = 100
+
+const LEVEL_COLORS = {
+	"info": Color(0.8, 0.8, 0.8),
+	"warning": Color(1.0, 0.8, 0.3),
+	"error": Color(1.0, 0.4, 0.4),
+}
+
+const KIND_LABELS = {
+	"message": "",
+	"operation": "[Operation] ",
+	"approval": "[Approval] ",
+	"diff": "[Diff] ",
+}
+
+var _last_id: int = 0
+var _item_list: ItemList
+var _detail_view: TextEdit
+var _timer: Timer
+
+func _ready() -> void:
+	name = "MCP Activity"
+	custom_minimum_size = Vector2(200, 200)
+
+	var header = HBoxContainer.new()
+	var title = Label.new()
+	title.text = "MCP Activity"
+	title.size_flags_horizontal = Control.SIZE_EXPAND_FILL
+	header.add_child(title)
+	var clear_button = Button.new()
+	clear_button.text = "Clear"
+	clear_button.pressed.connect(_on_clear_pressed)
+	header.add_child(clear_button)
+	add_child(header)
+
+	_item_list = ItemList.new()
+	_item_list.size_flags_vertical = Control.SIZE_EXPAND_FILL
+	_item_list.item_selected.connect(_on_item_selected)
+	add_child(_item_list)
+
+	_detail_view = TextEdit.new()
+	_detail_view.editable = false
+	_detail_view.custom_minimum_size = Vector2(0, 80)
+	_detail_view.visible = false
+	add_child(_detail_view)
+
+	_timer = Timer.new()
+	_timer.wait_time = POLL_INTERVAL
+	_timer.autostart = true
+	_timer.timeout.connect(_poll)
+	add_child(_timer)
+
+func _poll() -> void:
+	if not FileAccess.file_exists(ACTIVITY_PATH):
+		return
+
+	var text = FileAccess.get_file_as_string(ACTIVITY_PATH)
+	var json = JSON.new()
+	if json.parse(text) != OK or not json.data is Dictionary:
+		return
+
+	# Queue was recreated (ids restarted)
+	if int(json.data.get("nextId", 0)) <= _last_id:
+		_last_id = 0
+
+	for item in json.data.get("items", []):
+		var id = int(item.get("id", 0))
+		if id <= _last_id:
+			continue
+		_last_id = id
+		_add_item(item)
+
+func _add_item(item: Dictionary) -> void:
+	var level = item.get("level", "info")
+	var kind = item.get("kind", "message")
+	var text = KIND_LABELS.get(kind, "") + str(item.get("message", ""))
+
+	var index = _item_list.add_item(text)
+	_item_list.set_item_custom_fg_color(index, LEVEL_COLORS.get(level, LEVEL_COLORS["info"]))
+	_item_list.set_item_metadata(index, item.get("detail", null))
+	_item_list.set_item_tooltip(index, text)
+
+	while _item_list.item_count > MAX_VISIBLE_ITEMS:
+		_item_list.remove_item(0)
+
+	_item_list.ensure_current_is_visible()
+
+	if level == "error" or kind == "approval":
+		print("[MCP] %s" % text)
+
+func _on_item_selected(index: int) -> void:
+	var detail = _item_list.get_item_metadata(index)
+	_detail_view.visible = detail != null and str(detail) != ""
+	_detail_view.text = str(detail) if _detail_view.visible else ""
+
+func _on_clear_pressed() -> void:
+	_item_list.clear()
+	_detail_view.text = ""
+	_detail_view.visible = false
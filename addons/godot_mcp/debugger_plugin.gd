@@ -8,6 +8,9 @@ var sessions: Array[EditorDebuggerSession] = []
 var captured_errors: Array = []
 var log_buffer: Array = []
 var max_errors = 50
+## Answers of the running game to eval_expression, by id
+var eval_results: Dictionary = {}
+var _last_eval_id = 0
 
 func _setup_session(session_id: int) -> void:
 	var session = get_session(session_id)
@@ -33,6 +36,10 @@ func _capture(message, data, session_id) -> bool:
 	if message == "mcp_debug:error":
 		_add_error(data)
 		return true
+	if message == "mcp_debug:eval_result":
+		if data.size() >= 2 and data[1] is Dictionary:
+			eval_results[int(data[0])] = data[1]
+		return true
 	return false
 
 func _add_error(data):
@@ -50,3 +57,15 @@ func get_errors() -> Array:
 
 func clear_errors():
 	captured_errors.clear()
+
+func next_eval_id() -> int:
+	_last_eval_id += 1
+	return _last_eval_id
+
+## The game's answer for an evaluation, or null while it is pending
+func take_eval_result(id: int) -> Variant:
+	if not eval_results.has(id):
+		return null
+	var result = eval_results[id]
+	eval_results.erase(id)
+	return result
@@ -1,7 +1,7 @@
 @tool
 extends RefCounted
 ## Animation Handler
-## Handles animation operations: create, add_track, add_key, play, stop, list
+## Handles animation operations: create, add_track, add_key, play, stop, list, remap_tracks
 
 var plugin: EditorPlugin
 
@@ -22,6 +22,8 @@ func handle(command: String, params: Dictionary) -> Dictionary:
 			return _handle_stop_animation(params)
 		"list_animations":
 			return _handle_list_animations(params)
+		"remap_animation_tracks":
+			return _handle_remap_animation_tracks(params)
 		_:
 			return {"error": "Unknown animation command: " + command}
 
@@ -159,3 +161,72 @@ func _handle_list_animations(params: Dictionary) -> Dictionary:
 		})
 	
 	return {"success": true, "animations": anims}
+
+func _handle_remap_animation_tracks(params: Dictionary) -> Dictionary:
+	var root = EditorInterface.get_edited_scene_root()
+	if not root:
+		return {"error": "No scene is open"}
+	
+	var player_path = params.get("player", "AnimationPlayer")
+	var mapping = params.get("mapping", [])
+	var only = params.get("animations", null)
+	var dry_run = params.get("dry_run", false)
+	
+	var player = root.get_node_or_null(player_path)
+	if not player or not player is AnimationPlayer:
+		return {"error": "'" + player_path + "' is not an AnimationPlayer"}
+	
+	var names = player.get_animation_list()
+	if only != null:
+		for name in only:
+			if not names.has(name):
+				return {"error": "Animation not found: " + name}
+	
+	var remapped = []
+	for name in names:
+		if only != null and not only.has(name):
+			continue
+		var anim = player.get_animation(name)
+		for track in anim.get_track_count():
+			var from = str(anim.track_get_path(track))
+			var to = _remap_track_path(from, mapping)
+			if to == "":
+				continue
+			if not dry_run:
+				anim.track_set_path(track, NodePath(to))
+			remapped.append({"animation": name, "track": track, "from": from, "to": to})
+	
+	return {"success": true, "remapped": remapped}
+
+## New path for a track, or "" when no mapping applies (longest matching "from" wins)
+func _remap_track_path(path: String, mapping: Array) -> String:
+	var node = path
+	var property = ""
+	var colon = path.find(":")
+	if colon != -1:
+		node = path.substr(0, colon)
+		property = path.substr(colon)
+	
+	var best = ""
+	var best_len = -1
+	for m in mapping:
+		var from: String = m.get("from", "")
+		var to: String = m.get("to", "")
+		var candidate = ""
+		if from.contains(":"):
+			if from != path:
+				continue
+			candidate = to
+		else:
+			if node != from and not node.begins_with(from + "/"):
+				continue
+			var rest = node.substr(from.length())
+			if to == "." and rest != "":
+				candidate = rest.substr(1) + property
+			else:
+				candidate = to + rest + property
+		if from.length() > best_len:
+			best = candidate
+			best_len = from.length()
+	
+	return "" if best == path else best
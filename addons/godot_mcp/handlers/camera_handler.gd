@@ -0,0 +1,114 @@
+@tool
+extends RefCounted
+## Camera Handler
+## Points the editor at what was just changed, for screenshots and review:
+## frame_node, set_editor_camera, set_main_screen
+## The 3D camera keeps the placement until the user navigates the viewport.
+
+const MAIN_SCREENS = ["2D", "3D", "Script", "AssetLib"]
+## Camera distance as a multiple of the framed node's largest extent
+const FRAME_DISTANCE = 1.5
+
+var plugin: EditorPlugin
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	match command:
+		"frame_node":
+			return _handle_frame_node(params)
+		"set_editor_camera":
+			return _handle_set_editor_camera(params)
+		"set_main_screen":
+			return _handle_set_main_screen(params)
+		_:
+			return {"error": "Unknown camera command: " + command}
+
+func _handle_frame_node(params: Dictionary) -> Dictionary:
+	var root = EditorInterface.get_edited_scene_root()
+	if not root:
+		return {"error": "No scene is open"}
+
+	var node_path = params.get("node_path", ".")
+	var node = root.get_node_or_null(node_path) if node_path != "." else root
+	if not node:
+		return {"error": "Node not found: " + node_path}
+
+	var selection = EditorInterface.get_selection()
+	selection.clear()
+	selection.add_node(node)
+
+	if node is Node3D:
+		EditorInterface.set_main_screen_editor("3D")
+		var camera = EditorInterface.get_editor_viewport_3d(0).get_camera_3d()
+		if not camera:
+			return {"error": "3D editor camera not available"}
+		var bounds = _bounds_3d(node)
+		var target = bounds.get_center()
+		# Keep the current viewing direction; only the distance changes
+		var distance = max(bounds.get_longest_axis_size(), 1.0) * FRAME_DISTANCE
+		var position = target + camera.global_transform.basis.z * distance
+		_place_camera(camera, position, target)
+		return {
+			"success": true,
+			"screen": "3D",
+			"position": [position.x, position.y, position.z],
+			"look_at": [target.x, target.y, target.z],
+		}
+	elif node is CanvasItem:
+		EditorInterface.set_main_screen_editor("2D")
+		# Pan the 2D view so the node is centered, keeping the zoom
+		var viewport = EditorInterface.get_editor_viewport_2d()
+		var center = node.get_global_rect().get_center() if node is Control else node.global_position
+		var zoom = viewport.global_canvas_transform.get_scale()
+		var offset = Vector2(viewport.size) / 2.0 - center * zoom
+		viewport.global_canvas_transform = Transform2D(0.0, zoom, 0.0, offset)
+		return {"success": true, "screen": "2D", "center": [center.x, center.y]}
+
+	return {"success": true, "message": "Selected; the node has no position to frame"}
+
+func _handle_set_editor_camera(params: Dictionary) -> Dictionary:
+	var position = _vector3(params.get("position"))
+	var target = _vector3(params.get("look_at"))
+	if position == null or target == null:
+		return {"error": "position and look_at must be [x, y, z]"}
+	if position.is_equal_approx(target):
+		return {"error": "position and look_at must differ"}
+
+	EditorInterface.set_main_screen_editor("3D")
+	var camera = EditorInterface.get_editor_viewport_3d(0).get_camera_3d()
+	if not camera:
+		return {"error": "3D editor camera not available"}
+	_place_camera(camera, position, target)
+	return {"success": true}
+
+func _handle_set_main_screen(params: Dictionary) -> Dictionary:
+	var screen = params.get("screen", "")
+	if not screen in MAIN_SCREENS:
+		return {"error": "Unknown main screen: " + str(screen)}
+
+	EditorInterface.set_main_screen_editor(screen)
+	return {"success": true, "screen": screen}
+
+func _place_camera(camera: Camera3D, position: Vector3, target: Vector3) -> void:
+	# look_at needs an up vector that is not parallel to the view direction
+	var up = Vector3.UP
+	if abs((target - position).normalized().dot(up)) > 0.99:
+		up = Vector3.FORWARD
+	camera.look_at_from_position(position, target, up)
+
+## World-space bounds of a node and its visible descendants
+func _bounds_3d(node: Node3D) -> AABB:
+	var bounds = AABB(node.global_position, Vector3.ZERO)
+	var visuals: Array = node.find_children("*", "VisualInstance3D", true, false)
+	if node is VisualInstance3D:
+		visuals.append(node)
+	for visual in visuals:
+		bounds = bounds.merge(visual.global_transform * visual.get_aabb())
+	return bounds
+
+func _vector3(value) -> Variant:
+	if value is Array and value.size() == 3:
+		return Vector3(float(value[0]), float(value[1]), float(value[2]))
+	return null
@@ -2,6 +2,8 @@
 extends RefCounted
 ## Debug Handler
 ## Handles debug operations: get_editor_log, get_logs, pause, resume, step, breakpoint
+## Expressions evaluated in the running game: eval_expression, get_eval_result
+## One reading of the editor's performance monitors: get_performance_metrics
 
 var plugin: EditorPlugin
 
@@ -35,6 +37,12 @@ func handle(command: String, params: Dictionary) -> Dictionary:
 			return _handle_get_parse_errors(params)
 		"get_stack_frame_vars":
 			return _handle_get_stack_frame_vars(params)
+		"get_performance_metrics":
+			return _handle_get_performance_metrics(params)
+		"eval_expression":
+			return _handle_eval_expression(params)
+		"get_eval_result":
+			return _handle_get_eval_result(params)
 		_:
 			return {"error": "Unknown debug command: " + command}
 
@@ -71,10 +79,14 @@ func _handle_get_logs(params: Dictionary) -> Dictionary:
 	if plugin.log_buffer:
 		var buffer = plugin.log_buffer
 		var start = max(0, buffer.size() - limit)
+		# since: only lines logged after that sequence number (editorLog polling)
+		if params.has("since"):
+			var new_lines = plugin.log_seq - int(params["since"])
+			start = max(start, buffer.size() - max(0, new_lines))
 		for i in range(start, buffer.size()):
 			logs.append(buffer[i])
 	
-	return {"success": true, "logs": logs, "count": logs.size()}
+	return {"success": true, "logs": logs, "count": logs.size(), "last_seq": plugin.log_seq}
 
 func _handle_get_object_by_id(params: Dictionary) -> Dictionary:
 	var object_id = params.get("object_id", "")
@@ -233,3 +245,42 @@ func _handle_get_stack_frame_vars(params: Dictionary) -> Dictionary:
 	
 	return {"success": true, "variables": variables, "frame_index": frame_index}
 
+## Raw monitor values; the server averages repeated readings
+func _handle_get_performance_metrics(_params: Dictionary) -> Dictionary:
+	return {
+		"success": true,
+		"monitors": {
+			"fps": Performance.get_monitor(Performance.TIME_FPS),
+			"process_time": Performance.get_monitor(Performance.TIME_PROCESS),
+			"physics_process_time": Performance.get_monitor(Performance.TIME_PHYSICS_PROCESS),
+			"draw_calls": Performance.get_monitor(Performance.RENDER_TOTAL_DRAW_CALLS_IN_FRAME),
+			"objects_in_frame": Performance.get_monitor(Performance.RENDER_TOTAL_OBJECTS_IN_FRAME),
+			"static_memory": Performance.get_monitor(Performance.MEMORY_STATIC),
+			"video_memory": Performance.get_monitor(Performance.RENDER_VIDEO_MEM_USED),
+			"object_count": Performance.get_monitor(Performance.OBJECT_COUNT),
+			"node_count": Performance.get_monitor(Performance.OBJECT_NODE_COUNT),
+		}
+	}
+
+func _handle_eval_expression(params: Dictionary) -> Dictionary:
+	var expression = params.get("expression", "")
+	if expression.strip_edges().is_empty():
+		return {"error": "expression required"}
+	if not plugin.debugger_plugin:
+		return {"error": "Debugger plugin not initialized"}
+	var session = plugin.debugger_plugin.get_active_session()
+	if not session or not session.is_active():
+		return {"error": "No game is running from the editor"}
+
+	# The game answers with an "mcp_debug:eval_result" message (game_bridge.gd)
+	var id = plugin.debugger_plugin.next_eval_id()
+	session.send_message("mcp:eval", [id, params.get("node_path", "."), expression])
+	return {"success": true, "pending": true, "id": id}
+
+func _handle_get_eval_result(params: Dictionary) -> Dictionary:
+	if not plugin.debugger_plugin:
+		return {"error": "Debugger plugin not initialized"}
+	var result = plugin.debugger_plugin.take_eval_result(int(params.get("id", -1)))
+	if result == null:
+		return {"success": true, "pending": true}
+	return result
@@ -0,0 +1,66 @@
+@tool
+extends RefCounted
+## Editor Settings Handler
+## Reads and writes the editor's own settings (Editor > Editor Settings):
+## get_editor_settings, set_editor_setting
+
+var plugin: EditorPlugin
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	match command:
+		"get_editor_settings":
+			return _handle_get_editor_settings(params)
+		"set_editor_setting":
+			return _handle_set_editor_setting(params)
+		_:
+			return {"error": "Unknown editor settings command: " + command}
+
+func _handle_get_editor_settings(params: Dictionary) -> Dictionary:
+	var settings = EditorInterface.get_editor_settings()
+	var names: Array = params.get("names", [])
+	var prefix: String = params.get("prefix", "")
+	
+	if prefix != "":
+		for property in settings.get_property_list():
+			var name: String = property["name"]
+			if name.begins_with(prefix) and settings.has_setting(name) and not names.has(name):
+				names.append(name)
+	
+	var result = []
+	for name in names:
+		if not settings.has_setting(name):
+			continue
+		var value = settings.get_setting(name)
+		result.append({
+			"name": name,
+			"value": var_to_str(value),
+			"type": type_string(typeof(value)),
+		})
+	return {"success": true, "settings": result}
+
+func _handle_set_editor_setting(params: Dictionary) -> Dictionary:
+	var settings = EditorInterface.get_editor_settings()
+	var name: String = params.get("name", "")
+	if not settings.has_setting(name):
+		return {"error": "Unknown editor setting: " + name}
+	
+	var previous = settings.get_setting(name)
+	var text: String = params.get("value", "")
+	var value = str_to_var(text)
+	# Paths and flags may be given without quotes
+	if value == null and typeof(previous) == TYPE_STRING:
+		value = text
+	
+	var numeric = [TYPE_INT, TYPE_FLOAT]
+	if typeof(value) != typeof(previous) and not (typeof(value) in numeric and typeof(previous) in numeric):
+		return {"error": "Expected a %s value for %s, got: %s" % [type_string(typeof(previous)), name, text]}
+	if typeof(previous) == TYPE_INT:
+		value = int(value)
+	elif typeof(previous) == TYPE_FLOAT:
+		value = float(value)
+	
+	settings.set_setting(name, value)
+	return {"success": true, "previous": var_to_str(previous), "value": var_to_str(value)}
@@ -2,6 +2,9 @@
 extends RefCounted
 ## Scene Handler
 ## Handles scene operations: save, open, instantiate, get_tree
+## get_tree with serialize: true also returns the edited scene as .tscn text,
+## as saving it would write it (for comparing unsaved edits with the file)
+## Scene open/close events recorded by the plugin: get_scene_events
 
 var plugin: EditorPlugin
 
@@ -18,9 +21,27 @@ func handle(command: String, params: Dictionary) -> Dictionary:
 			return _handle_instantiate_scene(params)
 		"get_tree":
 			return _handle_get_tree(params)
+		"get_scene_events":
+			return _handle_get_scene_events(params)
 		_:
 			return {"error": "Unknown scene command: " + command}
 
+## since: only events after that sequence number; current_scene lets a server
+## that has just connected catch up with the scene already being edited
+func _handle_get_scene_events(params: Dictionary) -> Dictionary:
+	var since = int(params.get("since", 0))
+	var events = []
+	for event in plugin.scene_events:
+		if event["seq"] > since:
+			events.append(event)
+	var root = EditorInterface.get_edited_scene_root()
+	return {
+		"success": true,
+		"events": events,
+		"last_seq": plugin.scene_event_seq,
+		"current_scene": root.scene_file_path if root else "",
+	}
+
 func _handle_save_scene(params: Dictionary) -> Dictionary:
 	var root = EditorInterface.get_edited_scene_root()
 	if not root:
@@ -108,7 +129,30 @@ func _handle_get_tree(params: Dictionary) -> Dictionary:
 		return {"error": "No scene is open"}
 	
 	var tree = _build_tree(root, 0)
-	return {"success": true, "tree": tree, "root_name": root.name}
+	var result = {
+		"success": true,
+		"tree": tree,
+		"root_name": root.name,
+		"scene_path": root.scene_file_path,
+	}
+	if params.get("serialize", false):
+		var text = _serialize_scene(root)
+		if text == "":
+			return {"error": "Failed to serialize the edited scene"}
+		result["tscn"] = text
+	return result
+
+## Pack the scene and save it to a scratch file, leaving the scene's own file alone
+func _serialize_scene(root: Node) -> String:
+	var packed = PackedScene.new()
+	if packed.pack(root) != OK:
+		return ""
+	var scratch = "user://mcp_scene_snapshot.tscn"
+	if ResourceSaver.save(packed, scratch) != OK:
+		return ""
+	var text = FileAccess.get_file_as_string(scratch)
+	DirAccess.remove_absolute(ProjectSettings.globalize_path(scratch))
+	return text
 
 func _build_tree(node: Node, indent: int) -> Array:
 	var result = []
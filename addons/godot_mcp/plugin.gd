@@ -8,12 +8,21 @@ extends EditorPlugin
 
 const PORT = 6060
 const MAX_LOG_LINES = 1000
+const MAX_SCENE_EVENTS = 100
+## Autoload that answers requests sent to the running game (e.g. captureViewport)
+const GAME_BRIDGE = "MCPGameBridge"
 
 var tcp_server: TCPServer
 var command_handler: Node
 var websocket_server: Node
 var debugger_plugin: EditorDebuggerPlugin
+var activity_dock: Control
 var log_buffer: Array = []
+## Sequence number of the latest line in log_buffer (counts dropped lines too)
+var log_seq: int = 0
+## Scenes opened/closed in the editor, polled by the server to warm its caches
+var scene_events: Array = []
+var scene_event_seq: int = 0
 
 func _enter_tree():
 	# Load debugger plugin
@@ -21,6 +30,14 @@ func _enter_tree():
 	debugger_plugin = debugger_script.new()
 	add_debugger_plugin(debugger_plugin)
 
+	# Activity dock (polls the queue written by notifyEditor)
+	var dock_script = load("res://addons/godot_mcp/activity_dock.gd")
+	activity_dock = dock_script.new()
+	add_control_to_dock(DOCK_SLOT_RIGHT_UL, activity_dock)
+
+	scene_changed.connect(_on_scene_changed)
+	scene_closed.connect(_on_scene_closed)
+
 	# Load command handler
 	var handler_script = load("res://addons/godot_mcp/command_handler.gd")
 	command_handler = handler_script.new()
@@ -52,8 +69,17 @@ func _exit_tree():
 		websocket_server.queue_free()
 	if debugger_plugin:
 		remove_debugger_plugin(debugger_plugin)
+	if activity_dock:
+		remove_control_from_docks(activity_dock)
+		activity_dock.queue_free()
 	print("Godot MCP: Servers stopped")
 
+func _enable_plugin():
+	add_autoload_singleton(GAME_BRIDGE, "res://addons/godot_mcp/game_bridge.gd")
+
+func _disable_plugin():
+	remove_autoload_singleton(GAME_BRIDGE)
+
 func _process(_delta):
 	if tcp_server and tcp_server.is_connection_available():
 		var peer = tcp_server.take_connection()
@@ -106,11 +132,27 @@ func _handle_connection(peer: StreamPeerTCP):
 	peer.put_data(response.to_utf8_buffer())
 	peer.disconnect_from_host()
 
+# === Scene Events ===
+
+func _on_scene_changed(scene_root: Node) -> void:
+	if scene_root and scene_root.scene_file_path != "":
+		_add_scene_event("opened", scene_root.scene_file_path)
+
+func _on_scene_closed(path: String) -> void:
+	_add_scene_event("closed", path)
+
+func _add_scene_event(event: String, path: String) -> void:
+	scene_event_seq += 1
+	scene_events.append({"seq": scene_event_seq, "event": event, "path": path})
+	if scene_events.size() > MAX_SCENE_EVENTS:
+		scene_events.pop_front()
+
 # === Log Buffer Methods ===
 
 func add_log(message: String) -> void:
 	var timestamp = Time.get_datetime_string_from_system()
 	log_buffer.append("[%s] %s" % [timestamp, message])
+	log_seq += 1
 	if log_buffer.size() > MAX_LOG_LINES:
 		log_buffer.pop_front()
 
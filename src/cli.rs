@@ -8,6 +8,7 @@ use crate::godot::commands::{
 };
 use crate::tools::GodotTools;
 use clap::{Parser, Subcommand};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
 use std::path::PathBuf;
 
 /// Godot MCP Server CLI
@@ -15,6 +16,14 @@ use std::path::PathBuf;
 #[command(name = "godot-mcp-rs")]
 #[command(about = "MCP server for Godot - CLI mode for direct tool execution")]
 pub struct Cli {
+    /// Threads for project indexing (0: one per CPU; default: [index].jobs in the config file)
+    #[arg(long, global = true)]
+    pub jobs: Option<usize>,
+
+    /// Godot executable (default: GODOT, PATH, then common install locations)
+    #[arg(long, global = true)]
+    pub godot: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -24,6 +33,45 @@ pub enum Commands {
     /// Start MCP server (default mode)
     Serve,
 
+    /// Serve GraphQL over HTTP for local tools and in-editor agents
+    Http {
+        #[arg(short, long, default_value = ".")]
+        project: PathBuf,
+        /// Listen address (default: [http].bind in the config file)
+        #[arg(long)]
+        bind: Option<String>,
+        /// Accepted bearer token (write scope), in addition to [http].tokens
+        #[arg(long)]
+        token: Option<String>,
+        /// Godot editor plugin port for live queries
+        #[arg(long, default_value = "6060")]
+        godot_port: u16,
+        /// Serve an in-memory project loaded from a JSON fixture instead of
+        /// `--project`; edits are discarded when the server stops
+        #[arg(long, conflicts_with = "project")]
+        sandbox: Option<PathBuf>,
+    },
+
+    /// Re-run the tool calls of a replay file (see [replay] in the config
+    /// file) against a copy of the project and report results that changed
+    Replay {
+        /// Replay file (JSON Lines, one tool call per line)
+        file: PathBuf,
+        /// Project to replay against; a copy is modified, never the original
+        #[arg(short, long, default_value = ".")]
+        project: PathBuf,
+        /// Replay against an in-memory project loaded from a JSON fixture
+        /// instead of a copy of `--project`
+        #[arg(long, conflicts_with = "project")]
+        sandbox: Option<PathBuf>,
+        /// Keep the project copy instead of deleting it afterwards
+        #[arg(long)]
+        keep: bool,
+        /// Fail when any result differs from the recording (regression suites)
+        #[arg(long)]
+        check: bool,
+    },
+
     /// Execute a tool directly via CLI
     #[command(subcommand)]
     Tool(ToolCommands),
@@ -83,6 +131,21 @@ pub enum ToolCommands {
         project: PathBuf,
     },
 
+    /// Check scene contracts (.godot-mcp/contracts.toml); exits non-zero on violations
+    CheckContracts {
+        #[arg(short, long)]
+        project: PathBuf,
+    },
+
+    /// Print a Markdown changelog of a session's file edits from the audit log
+    Report {
+        #[arg(short, long)]
+        project: PathBuf,
+        /// Session id (default: the latest session in the audit log)
+        #[arg(short, long)]
+        session: Option<String>,
+    },
+
     /// Read Godot's log file for a project
     ReadGodotLog {
         #[arg(short, long)]
@@ -148,6 +211,31 @@ pub enum ToolCommands {
         node_path: String,
     },
 
+    /// Rename a node, updating child paths, NodePaths and connections
+    RenameNode {
+        #[arg(short, long)]
+        project: PathBuf,
+        #[arg(long)]
+        scene: String,
+        #[arg(long)]
+        node_path: String,
+        #[arg(long)]
+        new_name: String,
+    },
+
+    /// Move a node (and its children) under a different parent
+    ReparentNode {
+        #[arg(short, long)]
+        project: PathBuf,
+        #[arg(long)]
+        scene: String,
+        #[arg(long)]
+        node_path: String,
+        /// New parent node path ("." for root)
+        #[arg(long)]
+        new_parent: String,
+    },
+
     /// Set a node property
     SetNodeProperty {
         #[arg(short, long)]
@@ -168,6 +256,24 @@ pub enum ToolCommands {
         project: PathBuf,
         #[arg(long)]
         scene: String,
+        /// indent, ascii or markdown
+        #[arg(long, default_value = "indent")]
+        style: String,
+        /// Hide node types
+        #[arg(long)]
+        no_types: bool,
+        /// Show attached scripts
+        #[arg(long)]
+        scripts: bool,
+        /// Show groups
+        #[arg(long)]
+        groups: bool,
+        /// Levels shown below the subtree root
+        #[arg(long)]
+        max_depth: Option<u32>,
+        /// Node path to start from
+        #[arg(long)]
+        root: Option<String>,
     },
 
     /// Validate a scene file
@@ -357,6 +463,12 @@ pub enum ToolCommands {
         project: PathBuf,
         #[arg(long)]
         scene: Option<String>,
+        /// Kill the game after this many seconds
+        #[arg(long)]
+        timeout_seconds: Option<u64>,
+        /// Wait for the game and stop it when the CLI exits
+        #[arg(long)]
+        auto_stop: bool,
     },
 
     /// Stop the running project
@@ -717,6 +829,12 @@ pub async fn run_cli(cmd: ToolCommands) -> anyhow::Result<()> {
             let tools = GodotTools::with_project(project);
             tools.handle_validate_project(None).await
         }
+        ToolCommands::CheckContracts { project } => {
+            return run_check_contracts(project).await;
+        }
+        ToolCommands::Report { project, session } => {
+            return run_report(project, session).await;
+        }
         ToolCommands::ReadGodotLog { project, lines } => {
             // Get project name from project.godot
             let project_godot = project.join("project.godot");
@@ -845,6 +963,41 @@ pub async fn run_cli(cmd: ToolCommands) -> anyhow::Result<()> {
             );
             tools.handle_remove_node(Some(map)).await
         }
+        ToolCommands::RenameNode {
+            project,
+            scene,
+            node_path,
+            new_name,
+        } => {
+            let tools = GodotTools::with_project(project);
+            let mut map = serde_json::Map::new();
+            map.insert("scene_path".to_string(), serde_json::Value::String(scene));
+            map.insert(
+                "node_path".to_string(),
+                serde_json::Value::String(node_path),
+            );
+            map.insert("new_name".to_string(), serde_json::Value::String(new_name));
+            tools.handle_rename_node(Some(map)).await
+        }
+        ToolCommands::ReparentNode {
+            project,
+            scene,
+            node_path,
+            new_parent,
+        } => {
+            let tools = GodotTools::with_project(project);
+            let mut map = serde_json::Map::new();
+            map.insert("scene_path".to_string(), serde_json::Value::String(scene));
+            map.insert(
+                "node_path".to_string(),
+                serde_json::Value::String(node_path),
+            );
+            map.insert(
+                "new_parent".to_string(),
+                serde_json::Value::String(new_parent),
+            );
+            tools.handle_reparent_node(Some(map)).await
+        }
         ToolCommands::SetNodeProperty {
             project,
             scene,
@@ -863,10 +1016,29 @@ pub async fn run_cli(cmd: ToolCommands) -> anyhow::Result<()> {
             map.insert("value".to_string(), serde_json::Value::String(value));
             tools.handle_set_node_property(Some(map)).await
         }
-        ToolCommands::GetNodeTree { project, scene } => {
+        ToolCommands::GetNodeTree {
+            project,
+            scene,
+            style,
+            no_types,
+            scripts,
+            groups,
+            max_depth,
+            root,
+        } => {
             let tools = GodotTools::with_project(project);
             let mut map = serde_json::Map::new();
             map.insert("path".to_string(), serde_json::Value::String(scene));
+            map.insert("style".to_string(), serde_json::Value::String(style));
+            map.insert("include_types".to_string(), serde_json::json!(!no_types));
+            map.insert("include_scripts".to_string(), serde_json::json!(scripts));
+            map.insert("include_groups".to_string(), serde_json::json!(groups));
+            if let Some(max_depth) = max_depth {
+                map.insert("max_depth".to_string(), serde_json::json!(max_depth));
+            }
+            if let Some(root) = root {
+                map.insert("root".to_string(), serde_json::Value::String(root));
+            }
             tools.handle_get_node_tree(Some(map)).await
         }
         ToolCommands::ValidateTscn { project, path } => {
@@ -1099,14 +1271,27 @@ pub async fn run_cli(cmd: ToolCommands) -> anyhow::Result<()> {
             };
             tools.handle_get_godot_version(None).await
         }
-        ToolCommands::RunProject { project, scene } => {
+        ToolCommands::RunProject {
+            project,
+            scene,
+            timeout_seconds,
+            auto_stop,
+        } => {
             let tools = GodotTools::with_project(project);
-            let args = scene.map(|s| {
-                let mut map = serde_json::Map::new();
+            let mut map = serde_json::Map::new();
+            if let Some(s) = scene {
                 map.insert("scene".to_string(), serde_json::Value::String(s));
-                map
-            });
-            tools.handle_run_project(args).await
+            }
+            if let Some(t) = timeout_seconds {
+                map.insert("timeout_seconds".to_string(), t.into());
+            }
+            map.insert("auto_stop".to_string(), auto_stop.into());
+            let result = tools.handle_run_project(Some(map)).await;
+            // The watchdog lives in this process: stay until the game ends
+            if timeout_seconds.is_some() || auto_stop {
+                crate::graphql::run_report::wait_for_runs();
+            }
+            result
         }
         ToolCommands::StopProject { project } => {
             let tools = GodotTools::with_project(project);
@@ -1441,6 +1626,81 @@ pub async fn run_cli(cmd: ToolCommands) -> anyhow::Result<()> {
     }
 }
 
+/// Print the session report as Markdown
+async fn run_report(project: PathBuf, session: Option<String>) -> anyhow::Result<()> {
+    let schema = build_schema_with_context(GqlContext::new(project));
+    let request = async_graphql::Request::new(
+        "query($session: String) { sessionReport(sessionId: $session) { markdown } }",
+    )
+    .variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "session": session }),
+    ));
+    let response = schema.execute(request).await;
+    let data = response.data.into_json()?;
+    print!(
+        "{}",
+        data["sessionReport"]["markdown"]
+            .as_str()
+            .unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// Check scene contracts and report violations, failing for CI
+async fn run_check_contracts(project: PathBuf) -> anyhow::Result<()> {
+    let schema = build_schema_with_context(GqlContext::new(project));
+    let response = schema
+        .execute(
+            "{ checkContracts { passed contractsChecked scenesChecked \
+             violations { contract scene nodePaths message } \
+             error { code message location { file line } } } }",
+        )
+        .await;
+    let data = response.data.into_json()?;
+    let result = &data["checkContracts"];
+
+    if let Some(message) = result["error"]["message"].as_str() {
+        let line = result["error"]["location"]["line"]
+            .as_i64()
+            .map(|l| format!(" (line {})", l))
+            .unwrap_or_default();
+        eprintln!("Error: {}{}", message, line);
+        std::process::exit(2);
+    }
+
+    for violation in result["violations"].as_array().into_iter().flatten() {
+        let nodes: Vec<&str> = violation["nodePaths"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|n| n.as_str())
+            .collect();
+        let nodes = if nodes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", nodes.join(", "))
+        };
+        println!(
+            "{}: [{}]{} {}",
+            violation["scene"].as_str().unwrap_or(""),
+            violation["contract"].as_str().unwrap_or(""),
+            nodes,
+            violation["message"].as_str().unwrap_or("")
+        );
+    }
+    println!(
+        "{} contract(s), {} scene(s) checked, {} violation(s)",
+        result["contractsChecked"],
+        result["scenesChecked"],
+        result["violations"].as_array().map_or(0, |v| v.len())
+    );
+
+    if result["passed"].as_bool() != Some(true) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 /// Execute a live command via HTTP to the Godot plugin
 async fn run_live_command(port: u16, command: GodotCommand) -> anyhow::Result<()> {
     let url = format!("http://localhost:{}", port);
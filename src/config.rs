@@ -0,0 +1,371 @@
+//! Server configuration
+//!
+//! Loaded from the file named by `GODOT_MCP_CONFIG`, or `.godot-mcp/config.toml`
+//! in the working directory. Every section is optional:
+//!
+//! ```toml
+//! [telemetry]
+//! enabled = true
+//! endpoint = "http://localhost:4318"
+//! service_name = "godot-mcp-rs"
+//! headers = { "x-api-key" = "..." }
+//!
+//! [http]
+//! bind = "127.0.0.1:8765"
+//! tokens = ["...", { token = "...", scope = "read" }]
+//! cors_origins = ["*"]
+//!
+//! [http.tls]
+//! cert = "certs/server.pem"
+//! key = "certs/server-key.pem"
+//!
+//! [index]
+//! jobs = 8
+//!
+//! [gdtoolkit]
+//! venv = ".venv"
+//!
+//! [replay]
+//! file = ".godot-mcp/replay.jsonl"
+//!
+//! [assets]
+//! allowed_domains = ["kenney.nl", "opengameart.org"]
+//! max_bytes = 52428800
+//!
+//! [[editors]]
+//! name = "client"
+//! project = "../client"
+//! port = 6070
+//! ws_port = 6071
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// Environment variable overriding the config file location
+pub const CONFIG_ENV: &str = "GODOT_MCP_CONFIG";
+
+/// Default config file, relative to the working directory
+pub const DEFAULT_CONFIG_PATH: &str = ".godot-mcp/config.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub telemetry: TelemetryConfig,
+    pub http: HttpConfig,
+    pub index: IndexConfig,
+    pub gdtoolkit: GdtoolkitConfig,
+    pub replay: ReplayConfig,
+    pub assets: AssetsConfig,
+    /// Further editors with the plugin running, for `liveInstancesQuery`
+    pub editors: Vec<EditorInstanceConfig>,
+}
+
+/// OTLP export of tool call traces and metrics
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    /// OTLP/HTTP base URL; `/v1/traces` and `/v1/metrics` are appended.
+    /// Falls back to the standard `OTEL_EXPORTER_OTLP_*` variables when unset.
+    pub endpoint: Option<String>,
+    pub service_name: String,
+    /// Extra headers sent with every export (e.g. API keys)
+    pub headers: HashMap<String, String>,
+    /// Export metrics alongside traces
+    pub metrics: bool,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            service_name: "godot-mcp-rs".to_string(),
+            headers: HashMap::new(),
+            metrics: true,
+        }
+    }
+}
+
+/// GraphQL over HTTP (`godot-mcp-rs http`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Listen address
+    pub bind: String,
+    /// Accepted bearer tokens; one write token is generated per run when empty
+    pub tokens: Vec<HttpToken>,
+    /// Origins allowed by CORS ("*" for any)
+    pub cors_origins: Vec<String>,
+    /// Serve HTTPS with these PEM files
+    pub tls: Option<TlsConfig>,
+    /// Allow plain HTTP on non-loopback addresses (tokens travel unencrypted)
+    pub allow_insecure: bool,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            bind: "127.0.0.1:8765".to_string(),
+            tokens: Vec::new(),
+            cors_origins: vec!["*".to_string()],
+            tls: None,
+            allow_insecure: false,
+        }
+    }
+}
+
+/// Project indexing (file listing and scene/script parsing)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexConfig {
+    /// Worker threads; 1 indexes serially, 0 uses one per CPU
+    pub jobs: usize,
+    /// Parse scenes opened in the editor, and their dependencies, in the background
+    pub warm_open_scenes: bool,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            warm_open_scenes: true,
+        }
+    }
+}
+
+/// gdtoolkit (`gdlint`/`gdformat`) lookup
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GdtoolkitConfig {
+    /// Virtualenv searched before `PATH`
+    pub venv: Option<PathBuf>,
+}
+
+/// Recording of MCP tool calls for `godot-mcp-rs replay`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReplayConfig {
+    /// JSON Lines file each tool call and its result is appended to
+    pub file: Option<PathBuf>,
+}
+
+/// Remote asset downloads (`fetchAsset`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AssetsConfig {
+    /// Hosts assets may be downloaded from; subdomains are included
+    pub allowed_domains: Vec<String>,
+    /// Largest file accepted, in bytes
+    pub max_bytes: u64,
+    /// Minimum delay between two requests to the same host
+    pub min_interval_ms: u64,
+    /// Refuse downloads into folders without a license file
+    pub require_license: bool,
+}
+
+impl Default for AssetsConfig {
+    fn default() -> Self {
+        Self {
+            // Common sources of CC0 game assets
+            allowed_domains: [
+                "kenney.nl",
+                "opengameart.org",
+                "ambientcg.com",
+                "polyhaven.com",
+                "polyhaven.org",
+            ]
+            .map(String::from)
+            .to_vec(),
+            max_bytes: 50 * 1024 * 1024,
+            min_interval_ms: 1000,
+            require_license: true,
+        }
+    }
+}
+
+/// Another editor with the plugin running (another project or window)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EditorInstanceConfig {
+    pub name: String,
+    /// Project the editor has open
+    pub project: PathBuf,
+    /// Plugin HTTP port
+    #[serde(default = "default_editor_port")]
+    pub port: u16,
+    /// Plugin WebSocket port; HTTP only when unset
+    pub ws_port: Option<u16>,
+}
+
+fn default_editor_port() -> u16 {
+    6060
+}
+
+/// Bearer token: a plain string grants write access
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum HttpToken {
+    Plain(String),
+    Scoped { token: String, scope: TokenScope },
+}
+
+impl HttpToken {
+    /// Token with write access
+    pub fn write(token: impl Into<String>) -> Self {
+        Self::Plain(token.into())
+    }
+
+    pub fn token(&self) -> &str {
+        match self {
+            Self::Plain(token) | Self::Scoped { token, .. } => token,
+        }
+    }
+
+    pub fn scope(&self) -> TokenScope {
+        match self {
+            Self::Plain(_) => TokenScope::Write,
+            Self::Scoped { scope, .. } => *scope,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenScope {
+    /// Queries without side effects only
+    Read,
+    /// Queries and mutations
+    Write,
+}
+
+/// Certificate chain and private key in PEM format
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl ServerConfig {
+    /// Load the config file; a missing default file yields the defaults
+    pub fn load() -> anyhow::Result<Self> {
+        match std::env::var_os(CONFIG_ENV) {
+            Some(path) => Self::from_file(&PathBuf::from(path)),
+            None => {
+                let path = Path::new(DEFAULT_CONFIG_PATH);
+                if path.exists() {
+                    Self::from_file(path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    /// Load a specific config file
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_telemetry_config() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+[telemetry]
+enabled = true
+endpoint = "http://collector:4318"
+headers = { "x-api-key" = "secret" }
+"#,
+        )
+        .unwrap();
+
+        assert!(config.telemetry.enabled);
+        assert_eq!(
+            config.telemetry.endpoint.as_deref(),
+            Some("http://collector:4318")
+        );
+        assert_eq!(config.telemetry.service_name, "godot-mcp-rs");
+        assert_eq!(config.telemetry.headers["x-api-key"], "secret");
+        assert!(config.telemetry.metrics);
+    }
+
+    #[test]
+    fn test_empty_config_disables_telemetry() {
+        let config: ServerConfig = toml::from_str("").unwrap();
+        assert!(!config.telemetry.enabled);
+        assert_eq!(config.http.bind, "127.0.0.1:8765");
+        assert!(config.http.tokens.is_empty());
+        assert_eq!(config.index.jobs, 1);
+        assert!(config.index.warm_open_scenes);
+        assert!(config.gdtoolkit.venv.is_none());
+        assert!(config.replay.file.is_none());
+        assert!(config.assets.require_license);
+        assert!(config.editors.is_empty());
+        assert!(config
+            .assets
+            .allowed_domains
+            .contains(&"kenney.nl".to_string()));
+    }
+
+    #[test]
+    fn test_parse_editor_instances() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+[[editors]]
+name = "client"
+project = "../client"
+port = 6070
+ws_port = 6071
+
+[[editors]]
+name = "server"
+project = "/work/server"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.editors.len(), 2);
+        assert_eq!(config.editors[0].project, PathBuf::from("../client"));
+        assert_eq!(config.editors[0].ws_port, Some(6071));
+        assert_eq!(config.editors[1].port, 6060);
+        assert_eq!(config.editors[1].ws_port, None);
+    }
+
+    #[test]
+    fn test_parse_http_config() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+[http]
+bind = "0.0.0.0:9000"
+tokens = ["abc", { token = "viewer", scope = "read" }]
+cors_origins = ["http://localhost:3000"]
+
+[http.tls]
+cert = "cert.pem"
+key = "key.pem"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.http.bind, "0.0.0.0:9000");
+        assert_eq!(config.http.tokens[0], HttpToken::write("abc"));
+        assert_eq!(config.http.tokens[0].scope(), TokenScope::Write);
+        assert_eq!(config.http.tokens[1].token(), "viewer");
+        assert_eq!(config.http.tokens[1].scope(), TokenScope::Read);
+        assert_eq!(config.http.cors_origins, vec!["http://localhost:3000"]);
+        assert_eq!(config.http.tls.unwrap().cert, PathBuf::from("cert.pem"));
+    }
+}
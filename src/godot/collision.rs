@@ -0,0 +1,623 @@
+//! Collision shape fitting
+//!
+//! Derives collision shapes for MeshInstance3D and Sprite2D nodes with plain
+//! bounding-box and hull math over primitive meshes, OBJ files and sprite
+//! textures, so no Godot instance is needed.
+
+use indexmap::IndexMap;
+use std::f64::consts::PI;
+
+use super::expression::{Value, VectorKind};
+use crate::screenshot::Canvas;
+
+/// Segments around the Y axis for round primitives
+const SEGMENTS: usize = 16;
+
+/// Rings per quarter circle for spheres and capsule caps
+const RINGS: usize = 4;
+
+/// Alpha at which a pixel counts as solid (Godot's BitMap default of 0.1)
+const ALPHA_THRESHOLD: u8 = 26;
+
+/// How a shape is fitted around the geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitStrategy {
+    /// Convex hull of the vertices / opaque pixels
+    Convex,
+    /// Exact triangles of a mesh
+    Trimesh,
+    /// Capsule around the bounding box
+    CapsuleFit,
+    /// Rectangle of the sprite frame
+    RectFromSprite,
+}
+
+/// Shape resource fitted to a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedShape {
+    pub resource_type: &'static str,
+    /// Sub-resource properties, as .tscn values
+    pub properties: Vec<(String, String)>,
+    /// Where the shape's origin sits in the source node's space
+    pub offset: Vec<f64>,
+}
+
+impl FittedShape {
+    fn new(resource_type: &'static str, properties: Vec<(&str, String)>, offset: Vec<f64>) -> Self {
+        Self {
+            resource_type,
+            properties: properties
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+            offset,
+        }
+    }
+}
+
+/// Triangle mesh in the MeshInstance3D's local space
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    pub vertices: Vec<[f64; 3]>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    /// Geometry of a PrimitiveMesh resource (BoxMesh, SphereMesh, ...);
+    /// round surfaces are approximated with `SEGMENTS` sides
+    pub fn primitive(resource_type: &str, properties: &IndexMap<String, String>) -> Option<Self> {
+        let number = |key: &str, default: f64| {
+            properties
+                .get(key)
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .unwrap_or(default)
+        };
+        let vector = |key: &str, default: &[f64]| {
+            properties
+                .get(key)
+                .and_then(|v| components(v))
+                .filter(|c| c.len() == default.len())
+                .unwrap_or_else(|| default.to_vec())
+        };
+
+        match resource_type {
+            "BoxMesh" => {
+                let size = vector("size", &[1.0, 1.0, 1.0]);
+                let half = [size[0] / 2.0, size[1] / 2.0, size[2] / 2.0];
+                Some(Self::cuboid([-half[0], -half[1], -half[2]], half))
+            }
+            "PrismMesh" => {
+                let size = vector("size", &[1.0, 1.0, 1.0]);
+                let (x, y, z) = (size[0] / 2.0, size[1] / 2.0, size[2] / 2.0);
+                let top = -x + number("left_to_right", 0.5) * size[0];
+                Some(Self {
+                    vertices: vec![
+                        [-x, -y, -z],
+                        [x, -y, -z],
+                        [x, -y, z],
+                        [-x, -y, z],
+                        [top, y, -z],
+                        [top, y, z],
+                    ],
+                    triangles: vec![
+                        [0, 1, 2],
+                        [0, 2, 3],
+                        [0, 4, 1],
+                        [3, 2, 5],
+                        [0, 3, 5],
+                        [0, 5, 4],
+                        [1, 4, 5],
+                        [1, 5, 2],
+                    ],
+                })
+            }
+            // QuadMesh is a PlaneMesh facing Z
+            "PlaneMesh" | "QuadMesh" => {
+                let (default_size, default_orientation) = if resource_type == "QuadMesh" {
+                    (1.0, 2.0)
+                } else {
+                    (2.0, 1.0)
+                };
+                let size = vector("size", &[default_size, default_size]);
+                let center = vector("center_offset", &[0.0, 0.0, 0.0]);
+                let (u, v) = (size[0] / 2.0, size[1] / 2.0);
+                let corner =
+                    |a: f64, b: f64| match number("orientation", default_orientation) as i64 {
+                        0 => [center[0], center[1] + b, center[2] + a],
+                        2 => [center[0] + a, center[1] + b, center[2]],
+                        _ => [center[0] + a, center[1], center[2] + b],
+                    };
+                Some(Self {
+                    vertices: vec![corner(-u, -v), corner(u, -v), corner(u, v), corner(-u, v)],
+                    triangles: vec![[0, 1, 2], [0, 2, 3]],
+                })
+            }
+            "SphereMesh" => {
+                let radius = number("radius", 0.5);
+                let half_height = number("height", 1.0) / 2.0;
+                let profile: Vec<(f64, f64)> = (0..=2 * RINGS)
+                    .map(|i| {
+                        let angle = PI * i as f64 / (2 * RINGS) as f64;
+                        (radius * angle.sin(), half_height * angle.cos())
+                    })
+                    .collect();
+                Some(Self::lathe(&profile))
+            }
+            "CapsuleMesh" => {
+                let radius = number("radius", 0.5);
+                let cap = (number("height", 2.0) / 2.0 - radius).max(0.0);
+                let mut profile = Vec::new();
+                for i in 0..=RINGS {
+                    let angle = PI / 2.0 * i as f64 / RINGS as f64;
+                    profile.push((radius * angle.sin(), cap + radius * angle.cos()));
+                }
+                for i in 0..=RINGS {
+                    let angle = PI / 2.0 * i as f64 / RINGS as f64;
+                    profile.push((radius * angle.cos(), -cap - radius * angle.sin()));
+                }
+                Some(Self::lathe(&profile))
+            }
+            "CylinderMesh" => {
+                let half_height = number("height", 2.0) / 2.0;
+                Some(Self::lathe(&[
+                    (0.0, half_height),
+                    (number("top_radius", 0.5), half_height),
+                    (number("bottom_radius", 0.5), -half_height),
+                    (0.0, -half_height),
+                ]))
+            }
+            _ => None,
+        }
+    }
+
+    /// Vertices and faces of a Wavefront OBJ; polygons are split into fans
+    pub fn from_obj(content: &str) -> Option<Self> {
+        let mut mesh = Self::default();
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("v") => {
+                    let coords: Vec<f64> = parts.take(3).filter_map(|p| p.parse().ok()).collect();
+                    mesh.vertices.push(coords.try_into().ok()?);
+                }
+                Some("f") => {
+                    let count = mesh.vertices.len() as i64;
+                    let indices: Vec<usize> = parts
+                        .map(|p| {
+                            let index: i64 = p.split('/').next()?.parse().ok()?;
+                            // Negative indices count back from the last vertex
+                            let index = if index < 0 { count + index } else { index - 1 };
+                            (0..count).contains(&index).then_some(index as usize)
+                        })
+                        .collect::<Option<_>>()?;
+                    for i in 1..indices.len().saturating_sub(1) {
+                        mesh.triangles
+                            .push([indices[0], indices[i], indices[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        (!mesh.vertices.is_empty()).then_some(mesh)
+    }
+
+    /// Axis-aligned bounding box as (min, max)
+    pub fn bounds(&self) -> Option<([f64; 3], [f64; 3])> {
+        let first = *self.vertices.first()?;
+        Some(self.vertices.iter().fold((first, first), |(min, max), v| {
+            (
+                [min[0].min(v[0]), min[1].min(v[1]), min[2].min(v[2])],
+                [max[0].max(v[0]), max[1].max(v[1]), max[2].max(v[2])],
+            )
+        }))
+    }
+
+    fn cuboid(min: [f64; 3], max: [f64; 3]) -> Self {
+        let vertices = (0..8)
+            .map(|i| {
+                [
+                    if i & 1 == 0 { min[0] } else { max[0] },
+                    if i & 2 == 0 { min[1] } else { max[1] },
+                    if i & 4 == 0 { min[2] } else { max[2] },
+                ]
+            })
+            .collect();
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let triangles = quads
+            .iter()
+            .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+            .collect();
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Surface of revolution around Y from top to bottom (radius, y) points;
+    /// zero-radius points become a single pole vertex
+    fn lathe(profile: &[(f64, f64)]) -> Self {
+        let mut mesh = Self::default();
+        let mut rings: Vec<Vec<usize>> = Vec::new();
+        for &(radius, y) in profile {
+            let ring = if radius.abs() < 1e-9 {
+                mesh.vertices.push([0.0, y, 0.0]);
+                vec![mesh.vertices.len() - 1; SEGMENTS]
+            } else {
+                (0..SEGMENTS)
+                    .map(|s| {
+                        let angle = 2.0 * PI * s as f64 / SEGMENTS as f64;
+                        mesh.vertices
+                            .push([radius * angle.cos(), y, radius * angle.sin()]);
+                        mesh.vertices.len() - 1
+                    })
+                    .collect()
+            };
+            rings.push(ring);
+        }
+        for pair in rings.windows(2) {
+            let (upper, lower) = (&pair[0], &pair[1]);
+            for s in 0..SEGMENTS {
+                let next = (s + 1) % SEGMENTS;
+                for triangle in [
+                    [upper[s], upper[next], lower[next]],
+                    [upper[s], lower[next], lower[s]],
+                ] {
+                    // Skip the slivers collapsed onto a pole
+                    if triangle[0] != triangle[1]
+                        && triangle[1] != triangle[2]
+                        && triangle[0] != triangle[2]
+                    {
+                        mesh.triangles.push(triangle);
+                    }
+                }
+            }
+        }
+        mesh
+    }
+}
+
+/// Fit a 3D shape around a mesh
+pub fn fit_mesh(mesh: &Mesh, strategy: FitStrategy) -> Result<FittedShape, String> {
+    let (min, max) = mesh.bounds().ok_or("Mesh has no vertices")?;
+    match strategy {
+        // Godot computes the hull of the points itself
+        FitStrategy::Convex => {
+            let mut points: Vec<[f64; 3]> = Vec::new();
+            for vertex in &mesh.vertices {
+                if !points.contains(vertex) {
+                    points.push(*vertex);
+                }
+            }
+            Ok(FittedShape::new(
+                "ConvexPolygonShape3D",
+                vec![(
+                    "points",
+                    packed_array("PackedVector3Array", points.iter().flatten().copied()),
+                )],
+                vec![0.0; 3],
+            ))
+        }
+        FitStrategy::Trimesh => {
+            if mesh.triangles.is_empty() {
+                return Err("Mesh has no faces".to_string());
+            }
+            let faces = mesh
+                .triangles
+                .iter()
+                .flat_map(|t| t.iter().flat_map(|&i| mesh.vertices[i]));
+            Ok(FittedShape::new(
+                "ConcavePolygonShape3D",
+                vec![("data", packed_array("PackedVector3Array", faces))],
+                vec![0.0; 3],
+            ))
+        }
+        // Upright capsule, as CapsuleShape3D is aligned with Y
+        FitStrategy::CapsuleFit => {
+            let radius = (max[0] - min[0]).max(max[2] - min[2]) / 2.0;
+            let height = (max[1] - min[1]).max(2.0 * radius);
+            Ok(FittedShape::new(
+                "CapsuleShape3D",
+                vec![("radius", number(radius)), ("height", number(height))],
+                (0..3).map(|i| (min[i] + max[i]) / 2.0).collect(),
+            ))
+        }
+        FitStrategy::RectFromSprite => Err(
+            "RECT_FROM_SPRITE fits Sprite2D nodes; use CONVEX, TRIMESH or CAPSULE_FIT for meshes"
+                .to_string(),
+        ),
+    }
+}
+
+/// Part of the texture a Sprite2D shows, and where it is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteFrame {
+    /// Texture pixel rect of the current frame (x, y, width, height)
+    pub region: [f64; 4],
+    /// Center of the drawn frame in the node's space
+    pub center: [f64; 2],
+    pub flip_h: bool,
+    pub flip_v: bool,
+}
+
+impl SpriteFrame {
+    /// Frame from Sprite2D properties (`region_*`, `hframes`/`vframes`/`frame`,
+    /// `centered`, `offset`, `flip_*`) and the texture size
+    pub fn new(properties: &IndexMap<String, String>, texture_size: (f64, f64)) -> Self {
+        let flag =
+            |key: &str, default: bool| properties.get(key).map_or(default, |v| v.trim() == "true");
+        let count = |key: &str, default: usize| {
+            properties
+                .get(key)
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(default)
+        };
+
+        let mut region = [0.0, 0.0, texture_size.0, texture_size.1];
+        if flag("region_enabled", false) {
+            if let Some(rect) = properties
+                .get("region_rect")
+                .and_then(|v| components(v))
+                .filter(|c| c.len() == 4)
+            {
+                region = [rect[0], rect[1], rect[2], rect[3]];
+            }
+        }
+        let (hframes, vframes) = (count("hframes", 1).max(1), count("vframes", 1).max(1));
+        let frame = count("frame", 0) % (hframes * vframes);
+        region[2] /= hframes as f64;
+        region[3] /= vframes as f64;
+        region[0] += (frame % hframes) as f64 * region[2];
+        region[1] += (frame / hframes) as f64 * region[3];
+
+        let offset = properties
+            .get("offset")
+            .and_then(|v| components(v))
+            .filter(|c| c.len() == 2)
+            .unwrap_or_else(|| vec![0.0, 0.0]);
+        let center = if flag("centered", true) {
+            [offset[0], offset[1]]
+        } else {
+            [offset[0] + region[2] / 2.0, offset[1] + region[3] / 2.0]
+        };
+
+        Self {
+            region,
+            center,
+            flip_h: flag("flip_h", false),
+            flip_v: flag("flip_v", false),
+        }
+    }
+}
+
+/// Fit a 2D shape to a sprite frame. CONVEX outlines the opaque pixels of
+/// `image` when given, and the frame rectangle otherwise.
+pub fn fit_sprite(
+    frame: &SpriteFrame,
+    image: Option<&Canvas>,
+    strategy: FitStrategy,
+) -> Result<FittedShape, String> {
+    let [_, _, width, height] = frame.region;
+    match strategy {
+        FitStrategy::RectFromSprite => Ok(FittedShape::new(
+            "RectangleShape2D",
+            vec![("size", vector(VectorKind::Vector2, &[width, height]))],
+            frame.center.to_vec(),
+        )),
+        FitStrategy::CapsuleFit => Ok(FittedShape::new(
+            "CapsuleShape2D",
+            vec![
+                ("radius", number(width / 2.0)),
+                ("height", number(height.max(width))),
+            ],
+            frame.center.to_vec(),
+        )),
+        FitStrategy::Convex => {
+            let outline = match image {
+                Some(image) => opaque_outline(image, frame.region)
+                    .ok_or("Sprite frame has no opaque pixels")?,
+                None => vec![[0.0, 0.0], [width, 0.0], [width, height], [0.0, height]],
+            };
+            // Frame pixels -> node space, mirrored like the drawn sprite
+            let points = outline.iter().flat_map(|&[x, y]| {
+                let x = if frame.flip_h { width - x } else { x };
+                let y = if frame.flip_v { height - y } else { y };
+                [
+                    frame.center[0] + x - width / 2.0,
+                    frame.center[1] + y - height / 2.0,
+                ]
+            });
+            Ok(FittedShape::new(
+                "ConvexPolygonShape2D",
+                vec![("points", packed_array("PackedVector2Array", points))],
+                vec![0.0; 2],
+            ))
+        }
+        FitStrategy::Trimesh => {
+            Err("TRIMESH fits MeshInstance3D nodes; use CONVEX to outline a sprite".to_string())
+        }
+    }
+}
+
+/// Node types whose CollisionShape children give them a shape
+pub fn is_collision_object(node_type: &str) -> bool {
+    matches!(
+        node_type,
+        "Area2D"
+            | "StaticBody2D"
+            | "AnimatableBody2D"
+            | "RigidBody2D"
+            | "CharacterBody2D"
+            | "PhysicalBone2D"
+            | "Area3D"
+            | "StaticBody3D"
+            | "AnimatableBody3D"
+            | "RigidBody3D"
+            | "CharacterBody3D"
+            | "VehicleBody3D"
+            | "PhysicalBone3D"
+    )
+}
+
+/// Numbers inside a constructor value such as `Vector3(1, 2, 3)` or `Rect2(0, 0, 16, 16)`
+pub fn components(value: &str) -> Option<Vec<f64>> {
+    let inner = value.trim().split_once('(')?.1.strip_suffix(')')?;
+    inner
+        .split(',')
+        .map(|part| part.trim().parse::<f64>().ok())
+        .collect()
+}
+
+/// Convex hull (counter-clockwise in image space) of the solid pixels of a
+/// texture region, relative to the region's top-left corner
+fn opaque_outline(image: &Canvas, region: [f64; 4]) -> Option<Vec<[f64; 2]>> {
+    let x0 = region[0].max(0.0) as u32;
+    let y0 = region[1].max(0.0) as u32;
+    let x1 = ((region[0] + region[2]) as u32).min(image.width);
+    let y1 = ((region[1] + region[3]) as u32).min(image.height);
+
+    // The hull only depends on the outermost solid pixel of each row
+    let mut points = Vec::new();
+    for y in y0..y1 {
+        let solid = |x: &u32| image.pixel(*x, y)[3] >= ALPHA_THRESHOLD;
+        let (Some(left), Some(right)) = ((x0..x1).find(solid), (x0..x1).rev().find(solid)) else {
+            continue;
+        };
+        let (top, bottom) = ((y - y0) as f64, (y - y0 + 1) as f64);
+        let (left, right) = ((left - x0) as f64, (right - x0 + 1) as f64);
+        points.extend([[left, top], [left, bottom], [right, top], [right, bottom]]);
+    }
+    (!points.is_empty()).then(|| convex_hull(points))
+}
+
+/// Andrew's monotone chain; collinear points are dropped
+fn convex_hull(mut points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| {
+        (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+    };
+    let mut hull: Vec<[f64; 2]> = Vec::new();
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        for point in pass {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point of each chain starts the next one
+        hull.pop();
+    }
+    hull
+}
+
+fn number(n: f64) -> String {
+    Value::Number(n).to_godot()
+}
+
+fn vector(kind: VectorKind, components: &[f64]) -> String {
+    Value::Vector(kind, components.to_vec()).to_godot()
+}
+
+fn packed_array(name: &str, values: impl IntoIterator<Item = f64>) -> String {
+    let values: Vec<String> = values.into_iter().map(number).collect();
+    format!("{}({})", name, values.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_primitive_meshes() {
+        let box_mesh = Mesh::primitive(
+            "BoxMesh",
+            &IndexMap::from([("size".to_string(), "Vector3(2, 4, 2)".to_string())]),
+        )
+        .unwrap();
+        assert_eq!(
+            box_mesh.bounds(),
+            Some(([-1.0, -2.0, -1.0], [1.0, 2.0, 1.0]))
+        );
+        assert_eq!(box_mesh.triangles.len(), 12);
+
+        let capsule = fit_mesh(&box_mesh, FitStrategy::CapsuleFit).unwrap();
+        assert_eq!(capsule.resource_type, "CapsuleShape3D");
+        assert_eq!(
+            capsule.properties,
+            vec![
+                ("radius".to_string(), "1".to_string()),
+                ("height".to_string(), "4".to_string())
+            ]
+        );
+
+        let convex = fit_mesh(&box_mesh, FitStrategy::Convex).unwrap();
+        assert!(convex.properties[0]
+            .1
+            .starts_with("PackedVector3Array(-1, -2, -1, 1, -2, -1,"));
+
+        let sphere = Mesh::primitive("SphereMesh", &IndexMap::new()).unwrap();
+        let (min, max) = sphere.bounds().unwrap();
+        assert!((max[1] - 0.5).abs() < 1e-9 && (min[1] + 0.5).abs() < 1e-9);
+        assert!(sphere
+            .triangles
+            .iter()
+            .all(|t| t[0] != t[1] && t[1] != t[2]));
+        assert!(fit_mesh(&sphere, FitStrategy::RectFromSprite).is_err());
+    }
+
+    #[test]
+    fn test_obj_mesh() {
+        let obj = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1/1 2/2 3/3 4/4\nf -4 -3 -2\n";
+        let mesh = Mesh::from_obj(obj).unwrap();
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [0, 2, 3], [0, 1, 2]]);
+        assert!(Mesh::from_obj("f 1 2 3\n").is_none());
+    }
+
+    #[test]
+    fn test_fit_sprite() {
+        let properties = IndexMap::from([
+            ("hframes".to_string(), "2".to_string()),
+            ("frame".to_string(), "1".to_string()),
+            ("centered".to_string(), "false".to_string()),
+        ]);
+        let frame = SpriteFrame::new(&properties, (8.0, 4.0));
+        assert_eq!(frame.region, [4.0, 0.0, 4.0, 4.0]);
+        assert_eq!(frame.center, [2.0, 2.0]);
+
+        let rect = fit_sprite(&frame, None, FitStrategy::RectFromSprite).unwrap();
+        assert_eq!(rect.properties[0].1, "Vector2(4, 4)");
+        assert_eq!(rect.offset, vec![2.0, 2.0]);
+
+        // Solid 2x2 block in the second frame
+        let mut image = Canvas::new(8, 4);
+        image.fill_rect(
+            crate::screenshot::Rect {
+                x: 5,
+                y: 1,
+                width: 2,
+                height: 2,
+            },
+            [255, 255, 255],
+        );
+        let convex = fit_sprite(&frame, Some(&image), FitStrategy::Convex).unwrap();
+        assert_eq!(
+            convex.properties[0].1,
+            "PackedVector2Array(1, 1, 3, 1, 3, 3, 1, 3)"
+        );
+        assert!(fit_sprite(&frame, None, FitStrategy::Trimesh).is_err());
+    }
+}
@@ -0,0 +1,1019 @@
+//! C# script scanner
+//!
+//! Summarizes Godot C# (Mono) scripts without a full C# parser: namespace,
+//! the script class and its base class, `[Export]` fields and properties,
+//! `[Signal]` delegates, methods, constants and enums. Comments, strings and
+//! preprocessor lines are tokenized away first, so braces inside them do not
+//! throw off the member scan. The first class in the file is the script
+//! class; members of nested and further types are skipped.
+
+/// Operators and punctuation, longest first. `>>` is left as two tokens so
+/// nested generic arguments (`List<List<int>>`) close one at a time.
+const PUNCTUATION: &[&str] = &[
+    "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "?.", "++", "--", "+=", "-=", "*=", "/=", "%=",
+    "&=", "|=", "^=", "::", "->", "+", "-", "*", "/", "%", "^", "&", "|", "~", "!", "<", ">", "=",
+    "(", ")", "[", "]", "{", "}", ",", ";", ":", ".", "?",
+];
+
+/// Member modifiers, skipped when reading a declaration's type
+const MODIFIERS: &[&str] = &[
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "static",
+    "readonly",
+    "partial",
+    "override",
+    "virtual",
+    "abstract",
+    "sealed",
+    "async",
+    "const",
+    "new",
+    "extern",
+    "unsafe",
+    "volatile",
+    "required",
+    "fixed",
+];
+
+/// Parameter modifiers
+const PARAMETER_MODIFIERS: &[&str] = &["this", "ref", "out", "in", "params", "scoped"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// Identifier or keyword (`@class` is the identifier `class`)
+    Identifier(String),
+    /// String or char literal with simple escapes resolved; interpolated
+    /// strings keep their `{...}` holes as written
+    String(String),
+    Number(String),
+    Punct(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Byte range in the source
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line of the first character
+    pub line: usize,
+    /// 0-based byte column of the first character
+    pub column: usize,
+}
+
+/// Summary of a C# script
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CSharpScript {
+    pub namespace: Option<String>,
+    /// Name of the script class
+    pub class_name: Option<String>,
+    /// First type after `:`, without a `Godot.` prefix
+    pub base_class: Option<String>,
+    /// Line of the `class` keyword (1-based)
+    pub class_line: usize,
+    /// `[Export]` fields and properties
+    pub exports: Vec<CSharpField>,
+    /// Other fields and properties
+    pub fields: Vec<CSharpField>,
+    pub constants: Vec<CSharpField>,
+    /// `[Signal]` delegates, named without the `EventHandler` suffix
+    pub signals: Vec<CSharpSignal>,
+    /// Methods (constructors and operators excluded)
+    pub methods: Vec<CSharpMethod>,
+    pub enums: Vec<CSharpEnum>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpField {
+    pub name: String,
+    pub field_type: String,
+    /// Initializer as written
+    pub default_value: Option<String>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpSignal {
+    pub name: String,
+    pub parameters: Vec<CSharpParameter>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpMethod {
+    pub name: String,
+    pub parameters: Vec<CSharpParameter>,
+    pub return_type: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpParameter {
+    pub name: String,
+    pub param_type: String,
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpEnum {
+    pub name: String,
+    pub values: Vec<CSharpEnumValue>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CSharpEnumValue {
+    pub name: String,
+    pub line: usize,
+}
+
+impl CSharpScript {
+    /// Scan C# source
+    pub fn parse(source: &str) -> Self {
+        let tokens = tokenize(source);
+        let scanner = Scanner {
+            source,
+            tokens: &tokens,
+        };
+        let mut script = CSharpScript::default();
+
+        let mut i = 0;
+        while i < tokens.len() {
+            match scanner.ident(i) {
+                Some("namespace") => {
+                    let (name, next) = scanner.qualified_name(i + 1);
+                    script.namespace = Some(name);
+                    // Block-scoped namespaces are entered, not skipped
+                    i = next;
+                }
+                Some("class") if script.class_name.is_none() => {
+                    i = scanner.class(i, &mut script);
+                }
+                Some("class" | "struct" | "interface" | "record" | "enum") => {
+                    i = scanner.skip_type(i);
+                }
+                _ => i += 1,
+            }
+        }
+        script
+    }
+
+    /// Lines declaring `symbol`: the class, its members, signals (by signal or
+    /// delegate name) and enum values
+    pub fn declaration_lines(&self, symbol: &str) -> Vec<usize> {
+        let mut lines = Vec::new();
+        if self.class_name.as_deref() == Some(symbol) {
+            lines.push(self.class_line);
+        }
+        for field in self
+            .exports
+            .iter()
+            .chain(&self.fields)
+            .chain(&self.constants)
+        {
+            if field.name == symbol {
+                lines.push(field.line);
+            }
+        }
+        for signal in &self.signals {
+            if signal.name == symbol || format!("{}EventHandler", signal.name) == symbol {
+                lines.push(signal.line);
+            }
+        }
+        for method in &self.methods {
+            if method.name == symbol {
+                lines.push(method.line);
+            }
+        }
+        for decl in &self.enums {
+            if decl.name == symbol {
+                lines.push(decl.line);
+            }
+            lines.extend(
+                decl.values
+                    .iter()
+                    .filter(|v| v.name == symbol)
+                    .map(|v| v.line),
+            );
+        }
+        lines.sort_unstable();
+        lines
+    }
+}
+
+/// Tokenize C# source. Comments and preprocessor lines (`#region`, `#if`) are dropped.
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    let mut line = 1;
+    let mut line_start = 0;
+    // Only whitespace so far on this line
+    let mut at_line_start = true;
+
+    let is_ident_start = |b: u8| b.is_ascii_alphabetic() || b == b'_' || b >= 0x80;
+    let is_ident_char = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let next = bytes.get(pos + 1).copied();
+        let start = pos;
+        let (start_line, column) = (line, pos - line_start);
+
+        if c == b'\n' {
+            pos += 1;
+            line += 1;
+            line_start = pos;
+            at_line_start = true;
+            continue;
+        }
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if (c == b'#' && at_line_start) || (c == b'/' && next == Some(b'/')) {
+            while pos < bytes.len() && bytes[pos] != b'\n' {
+                pos += 1;
+            }
+            continue;
+        }
+        if c == b'/' && next == Some(b'*') {
+            pos += 2;
+            while pos < bytes.len() && !(bytes[pos] == b'*' && bytes.get(pos + 1) == Some(&b'/')) {
+                if bytes[pos] == b'\n' {
+                    line += 1;
+                    line_start = pos + 1;
+                }
+                pos += 1;
+            }
+            pos = (pos + 2).min(bytes.len());
+            continue;
+        }
+        at_line_start = false;
+
+        // String prefixes: @"verbatim", $"interpolated", $@"..." / @$"..."
+        let prefix_len = bytes[pos..]
+            .iter()
+            .take(2)
+            .take_while(|b| **b == b'@' || **b == b'$')
+            .count();
+        let kind = if bytes.get(pos + prefix_len) == Some(&b'"') {
+            let verbatim = bytes[pos..pos + prefix_len].contains(&b'@');
+            let interpolated = bytes[pos..pos + prefix_len].contains(&b'$');
+            pos += prefix_len;
+            let (value, end) = scan_string(source, pos, verbatim, interpolated);
+            for (offset, b) in bytes[pos..end].iter().enumerate() {
+                if *b == b'\n' {
+                    line += 1;
+                    line_start = pos + offset + 1;
+                }
+            }
+            pos = end;
+            TokenKind::String(value)
+        } else if c == b'\'' {
+            let (value, end) = scan_string(source, pos, false, false);
+            pos = end;
+            TokenKind::String(value)
+        } else if c == b'@' && next.is_some_and(is_ident_start) {
+            // Verbatim identifier: the token covers the name only
+            pos += 1;
+            let name_start = pos;
+            while pos < bytes.len() && is_ident_char(bytes[pos]) {
+                pos += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Identifier(source[name_start..pos].to_string()),
+                start: name_start,
+                end: pos,
+                line: start_line,
+                column: column + 1,
+            });
+            continue;
+        } else if is_ident_start(c) {
+            while pos < bytes.len() && is_ident_char(bytes[pos]) {
+                pos += 1;
+            }
+            TokenKind::Identifier(source[start..pos].to_string())
+        } else if c.is_ascii_digit() || (c == b'.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            while pos < bytes.len()
+                && (bytes[pos].is_ascii_alphanumeric()
+                    || bytes[pos] == b'_'
+                    || (bytes[pos] == b'.'
+                        && bytes.get(pos + 1).is_some_and(|n| n.is_ascii_digit())))
+            {
+                pos += 1;
+            }
+            TokenKind::Number(source[start..pos].to_string())
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| source[pos..].starts_with(**p)) {
+            pos += punct.len();
+            TokenKind::Punct(punct)
+        } else {
+            // Anything else (a stray `\`` or non-ASCII symbol) is skipped
+            pos += source[pos..].chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            end: pos,
+            line: start_line,
+            column,
+        });
+    }
+    tokens
+}
+
+/// Scan a string or char literal starting at its opening quote; returns the
+/// value and the byte offset just past the closing quote
+fn scan_string(source: &str, open: usize, verbatim: bool, interpolated: bool) -> (String, usize) {
+    let bytes = source.as_bytes();
+    let quote = bytes[open];
+
+    // Raw string literal: three or more quotes
+    let quotes = bytes[open..].iter().take_while(|b| **b == b'"').count();
+    if quote == b'"' && quotes >= 3 {
+        let delimiter = &source[open..open + quotes];
+        let body_start = open + quotes;
+        return match source[body_start..].find(delimiter) {
+            Some(len) => (
+                source[body_start..body_start + len].to_string(),
+                body_start + len + quotes,
+            ),
+            None => (source[body_start..].to_string(), source.len()),
+        };
+    }
+
+    let mut value = String::new();
+    let mut pos = open + 1;
+    let mut holes = 0usize;
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        if c == quote && holes == 0 {
+            if verbatim && bytes.get(pos + 1) == Some(&quote) {
+                value.push(quote as char);
+                pos += 2;
+                continue;
+            }
+            return (value, pos + 1);
+        }
+        if c == b'\n' && !verbatim {
+            break;
+        }
+        if interpolated && c == b'{' {
+            if bytes.get(pos + 1) == Some(&b'{') && holes == 0 {
+                value.push('{');
+                pos += 2;
+                continue;
+            }
+            holes += 1;
+        } else if interpolated && c == b'}' && holes > 0 {
+            holes -= 1;
+        }
+        if c == b'\\' && !verbatim && holes == 0 {
+            let escaped = bytes.get(pos + 1).copied().unwrap_or(b'\\');
+            value.push(match escaped {
+                b'n' => '\n',
+                b't' => '\t',
+                b'r' => '\r',
+                b'0' => '\0',
+                other => other as char,
+            });
+            pos += 2;
+            continue;
+        }
+        let ch = source[pos..].chars().next().unwrap_or('\0');
+        value.push(ch);
+        pos += ch.len_utf8();
+    }
+    (value, pos.min(bytes.len()))
+}
+
+struct Scanner<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+}
+
+impl Scanner<'_> {
+    fn ident(&self, i: usize) -> Option<&str> {
+        match self.tokens.get(i).map(|t| &t.kind) {
+            Some(TokenKind::Identifier(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn is_punct(&self, i: usize, punct: &str) -> bool {
+        matches!(self.tokens.get(i).map(|t| &t.kind), Some(TokenKind::Punct(p)) if *p == punct)
+    }
+
+    /// Index just past the bracket closing the one opened at `open`
+    fn skip_group(&self, open: usize) -> usize {
+        let mut depth = 0usize;
+        let mut i = open;
+        while i < self.tokens.len() {
+            match self.tokens[i].kind {
+                TokenKind::Punct("(" | "[" | "{") => depth += 1,
+                TokenKind::Punct(")" | "]" | "}") => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        i
+    }
+
+    /// Index just past the `;` ending the statement that contains `i`
+    fn skip_statement(&self, mut i: usize, end: usize) -> usize {
+        while i < end {
+            match self.tokens[i].kind {
+                TokenKind::Punct(";") => return i + 1,
+                TokenKind::Punct("(" | "[" | "{") => i = self.skip_group(i),
+                _ => i += 1,
+            }
+        }
+        end
+    }
+
+    /// `A.B.C` starting at `i`; returns the name and the index after it
+    fn qualified_name(&self, mut i: usize) -> (String, usize) {
+        let mut parts = Vec::new();
+        while let Some(part) = self.ident(i) {
+            parts.push(part);
+            i += 1;
+            if !self.is_punct(i, ".") {
+                break;
+            }
+            i += 1;
+        }
+        (parts.join("."), i)
+    }
+
+    /// Source text from token `from` through token `to`, whitespace collapsed
+    fn text(&self, from: usize, to: usize) -> String {
+        let text = &self.source[self.tokens[from].start..self.tokens[to].end];
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Index of the first `{` or `;` at or after `i`, skipping parenthesized parts
+    fn find_body(&self, mut i: usize) -> usize {
+        while i < self.tokens.len() {
+            match self.tokens[i].kind {
+                TokenKind::Punct("{" | ";") => return i,
+                TokenKind::Punct("(" | "[") => i = self.skip_group(i),
+                _ => i += 1,
+            }
+        }
+        i
+    }
+
+    /// Skip a type declaration starting at its keyword
+    fn skip_type(&self, keyword: usize) -> usize {
+        let body = self.find_body(keyword + 1);
+        if self.is_punct(body, "{") {
+            self.skip_group(body)
+        } else {
+            body + 1
+        }
+    }
+
+    /// Read the script class header and members; returns the index after the class
+    fn class(&self, keyword: usize, script: &mut CSharpScript) -> usize {
+        let Some(name) = self.ident(keyword + 1) else {
+            return keyword + 1;
+        };
+        script.class_name = Some(name.to_string());
+        script.class_line = self.tokens[keyword].line;
+
+        let mut i = keyword + 2;
+        if self.is_punct(i, "<") {
+            i = self.skip_angles(i);
+        }
+        if self.is_punct(i, "(") {
+            // Primary constructor
+            i = self.skip_group(i);
+        }
+        if self.is_punct(i, ":") {
+            let (base, next) = self.qualified_name(i + 1);
+            if !base.is_empty() {
+                let base = base.strip_prefix("Godot.").unwrap_or(&base);
+                script.base_class = Some(base.to_string());
+            }
+            i = next;
+        }
+
+        let body = self.find_body(i);
+        if !self.is_punct(body, "{") {
+            return body + 1;
+        }
+        let end = self.skip_group(body);
+        self.members(body + 1, end.saturating_sub(1), script);
+        end
+    }
+
+    /// Index just past the `>` closing the `<` at `open`
+    fn skip_angles(&self, open: usize) -> usize {
+        let mut depth = 0usize;
+        let mut i = open;
+        while i < self.tokens.len() {
+            match self.tokens[i].kind {
+                TokenKind::Punct("<") => depth += 1,
+                TokenKind::Punct(">") => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                }
+                TokenKind::Punct("{" | ";") => return i,
+                _ => {}
+            }
+            i += 1;
+        }
+        i
+    }
+
+    /// Scan the member declarations between `start` and `end` (class body, exclusive)
+    fn members(&self, start: usize, end: usize, script: &mut CSharpScript) {
+        let mut attributes: Vec<String> = Vec::new();
+        let mut i = start;
+        while i < end {
+            if self.is_punct(i, "[") {
+                let close = self.skip_group(i);
+                attributes.extend(self.attribute_names(i, close));
+                i = close;
+                continue;
+            }
+            if self.is_punct(i, ";") || self.is_punct(i, "}") {
+                attributes.clear();
+                i += 1;
+                continue;
+            }
+
+            // Head of the declaration up to its first structural token
+            let head_start = i;
+            let mut j = i;
+            let mut angles = 0usize;
+            while j < end {
+                match self.tokens[j].kind {
+                    TokenKind::Punct("<") => angles += 1,
+                    TokenKind::Punct(">") if angles > 0 => angles -= 1,
+                    TokenKind::Punct("[") => {
+                        // Array rank in a type (`int[]`)
+                        j = self.skip_group(j);
+                        continue;
+                    }
+                    TokenKind::Punct("(" | "{" | ";" | "=>" | "=") if angles == 0 => break,
+                    _ => {}
+                }
+                j += 1;
+            }
+            let words: Vec<&str> = (head_start..j).filter_map(|k| self.ident(k)).collect();
+            let has = |word: &str| words.contains(&word);
+            let line = self.tokens[head_start].line;
+            let exported = attributes.iter().any(|a| is_export_attribute(a));
+
+            i = if ["class", "struct", "interface", "record"]
+                .iter()
+                .any(|w| has(w))
+            {
+                let body = self.find_body(j);
+                if self.is_punct(body, "{") {
+                    self.skip_group(body)
+                } else {
+                    body + 1
+                }
+            } else if has("enum") {
+                let body = self.find_body(j);
+                if self.is_punct(body, "{") {
+                    let close = self.skip_group(body);
+                    let name = self.last_ident(head_start, j).unwrap_or_default();
+                    script.enums.push(CSharpEnum {
+                        name: name.to_string(),
+                        values: self.enum_values(body, close),
+                        line,
+                    });
+                    close
+                } else {
+                    body + 1
+                }
+            } else if has("delegate") && self.is_punct(j, "(") {
+                if attributes.iter().any(|a| a == "Signal") {
+                    if let Some(name) = self.last_ident(head_start, j) {
+                        script.signals.push(CSharpSignal {
+                            name: name
+                                .strip_suffix("EventHandler")
+                                .unwrap_or(name)
+                                .to_string(),
+                            parameters: self.parameters(j),
+                            line,
+                        });
+                    }
+                }
+                self.skip_statement(j, end)
+            } else if self.is_punct(j, "(") {
+                let close = self.skip_group(j);
+                let name = self.last_ident(head_start, j);
+                let return_type = self.declared_type(head_start, j);
+                if let (Some(name), Some(return_type)) = (name, return_type) {
+                    if !has("operator") && !has("event") {
+                        script.methods.push(CSharpMethod {
+                            name: name.to_string(),
+                            parameters: self.parameters(j),
+                            return_type,
+                            line,
+                        });
+                    }
+                }
+                self.skip_member_body(close, end)
+            } else {
+                let mut default_value = None;
+                let mut initializer = |equals: usize| {
+                    let value_end = self.initializer_end(equals + 1, end);
+                    if value_end > equals + 1 {
+                        default_value = Some(self.text(equals + 1, value_end - 1));
+                    }
+                    self.skip_statement(value_end, end)
+                };
+                let next = if self.is_punct(j, "{") {
+                    // Property accessors, maybe followed by `= initializer;`
+                    let after = self.skip_group(j);
+                    if self.is_punct(after, "=") {
+                        initializer(after)
+                    } else {
+                        after
+                    }
+                } else if self.is_punct(j, "=") {
+                    initializer(j)
+                } else {
+                    // `;`, or `=>` of an expression-bodied property
+                    self.skip_statement(j, end)
+                };
+
+                if let (Some(name), Some(field_type)) = (
+                    self.last_ident(head_start, j),
+                    self.declared_type(head_start, j),
+                ) {
+                    if !has("event") {
+                        let field = CSharpField {
+                            name: name.to_string(),
+                            field_type,
+                            default_value,
+                            line,
+                        };
+                        if has("const") {
+                            script.constants.push(field);
+                        } else if exported {
+                            script.exports.push(field);
+                        } else {
+                            script.fields.push(field);
+                        }
+                    }
+                }
+                next
+            };
+            attributes.clear();
+        }
+    }
+
+    /// After a method's parameter list: constraints or a constructor
+    /// initializer, then a block, an expression body or `;`
+    fn skip_member_body(&self, mut i: usize, end: usize) -> usize {
+        while i < end {
+            match self.tokens[i].kind {
+                TokenKind::Punct("{") => return self.skip_group(i),
+                TokenKind::Punct("=>") => return self.skip_statement(i, end),
+                TokenKind::Punct(";") => return i + 1,
+                TokenKind::Punct("(" | "[") => i = self.skip_group(i),
+                _ => i += 1,
+            }
+        }
+        end
+    }
+
+    /// End (exclusive) of an initializer: the `;` or `,` at depth 0
+    fn initializer_end(&self, mut i: usize, end: usize) -> usize {
+        while i < end {
+            match self.tokens[i].kind {
+                TokenKind::Punct(";" | ",") => return i,
+                TokenKind::Punct("(" | "[" | "{") => i = self.skip_group(i),
+                _ => i += 1,
+            }
+        }
+        end
+    }
+
+    /// Last identifier in `[from, to)` (the declared name)
+    fn last_ident(&self, from: usize, to: usize) -> Option<&str> {
+        let last = to.checked_sub(1)?;
+        if last < from {
+            return None;
+        }
+        self.ident(last)
+    }
+
+    /// Type of the declaration in `[from, to)`: everything between the
+    /// modifiers and the name. `None` for constructors and destructors.
+    fn declared_type(&self, from: usize, to: usize) -> Option<String> {
+        let name = to.checked_sub(1)?;
+        let mut first = from;
+        while first < name
+            && self
+                .ident(first)
+                .is_some_and(|w| MODIFIERS.contains(&w) || w == "event")
+        {
+            first += 1;
+        }
+        while first < name && self.is_punct(first, "~") {
+            first += 1;
+        }
+        if first >= name {
+            return None;
+        }
+        Some(self.text(first, name - 1))
+    }
+
+    /// Attribute names in `[...]` at `open`, without `Godot.` or an `Attribute` suffix
+    fn attribute_names(&self, open: usize, close: usize) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut i = open + 1;
+        while i < close {
+            // Attribute target (`[field: Export]`)
+            if self.ident(i).is_some() && self.is_punct(i + 1, ":") {
+                i += 2;
+            }
+            let (name, next) = self.qualified_name(i);
+            if let Some(last) = name.rsplit('.').next().filter(|n| !n.is_empty()) {
+                names.push(last.strip_suffix("Attribute").unwrap_or(last).to_string());
+            }
+            // Skip arguments up to the next attribute in the list
+            i = next.max(i + 1);
+            while i < close && !self.is_punct(i, ",") {
+                if self.is_punct(i, "(") {
+                    i = self.skip_group(i);
+                } else {
+                    i += 1;
+                }
+            }
+            i += 1;
+        }
+        names
+    }
+
+    fn enum_values(&self, open: usize, close: usize) -> Vec<CSharpEnumValue> {
+        let mut values = Vec::new();
+        let mut expect_name = true;
+        let mut i = open + 1;
+        while i + 1 < close {
+            match &self.tokens[i].kind {
+                TokenKind::Punct("[") => {
+                    i = self.skip_group(i);
+                    continue;
+                }
+                TokenKind::Punct("(") => {
+                    i = self.skip_group(i);
+                    continue;
+                }
+                TokenKind::Punct(",") => expect_name = true,
+                TokenKind::Identifier(name) if expect_name => {
+                    values.push(CSharpEnumValue {
+                        name: name.clone(),
+                        line: self.tokens[i].line,
+                    });
+                    expect_name = false;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        values
+    }
+
+    /// Parameters of the list opened at `open`
+    fn parameters(&self, open: usize) -> Vec<CSharpParameter> {
+        let close = self.skip_group(open).saturating_sub(1);
+        let mut parameters = Vec::new();
+        let mut start = open + 1;
+        let mut i = start;
+        let mut angles = 0usize;
+        while i <= close {
+            match self.tokens[i].kind {
+                TokenKind::Punct("<") => angles += 1,
+                TokenKind::Punct(">") if angles > 0 => angles -= 1,
+                TokenKind::Punct("(" | "[" | "{") if i != close => {
+                    i = self.skip_group(i);
+                    continue;
+                }
+                _ => {}
+            }
+            if i == close || (angles == 0 && self.is_punct(i, ",")) {
+                if let Some(parameter) = self.parameter(start, i) {
+                    parameters.push(parameter);
+                }
+                start = i + 1;
+            }
+            i += 1;
+        }
+        parameters
+    }
+
+    /// One parameter in `[from, to)`
+    fn parameter(&self, mut from: usize, to: usize) -> Option<CSharpParameter> {
+        while from < to && self.is_punct(from, "[") {
+            from = self.skip_group(from);
+        }
+        while from < to
+            && self
+                .ident(from)
+                .is_some_and(|w| PARAMETER_MODIFIERS.contains(&w))
+        {
+            from += 1;
+        }
+        let equals = (from..to).find(|k| self.is_punct(*k, "=")).unwrap_or(to);
+        let name = self.last_ident(from, equals)?;
+        let param_type = if equals >= from + 2 {
+            self.text(from, equals - 2)
+        } else {
+            "var".to_string()
+        };
+        let default_value = (equals + 1 < to).then(|| self.text(equals + 1, to - 1));
+        Some(CSharpParameter {
+            name: name.to_string(),
+            param_type,
+            default_value,
+        })
+    }
+}
+
+/// `[Export]` and its variants (`[ExportRange]`, `[ExportFile]`...); group
+/// and category attributes only label the inspector
+fn is_export_attribute(name: &str) -> bool {
+    name.starts_with("Export")
+        && !matches!(name, "ExportGroup" | "ExportSubgroup" | "ExportCategory")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: &str = r#"using Godot;
+using System.Collections.Generic;
+
+namespace Game.Actors;
+
+/// <summary>The player { not a brace }</summary>
+[GlobalClass]
+public partial class Player : Godot.CharacterBody2D
+{
+    [Signal]
+    public delegate void HealthChangedEventHandler(int newValue, string reason = "hit");
+
+    #region Exports
+    [Export] public float Speed { get; set; } = 300.0f;
+    [Export(PropertyHint.Range, "0,100")]
+    public int MaxHealth = 100;
+    [ExportGroup("Loot")]
+    [Export] private PackedScene _dropScene;
+    #endregion
+
+    public const string Tag = "player";
+    private readonly Dictionary<string, List<int>> _inventory = new();
+    private int _health;
+
+    public enum State { Idle, Running = 2, Dead }
+
+    public Player() { _health = MaxHealth; }
+
+    public override void _Ready()
+    {
+        var text = $"{{ {Speed} }}";
+        EmitSignal(SignalName.HealthChanged, _health, @"say ""hi""");
+    }
+
+    public int TakeDamage(int amount, bool critical = false) => _health -= amount;
+
+    private class Inner
+    {
+        public void Hidden() { }
+    }
+}
+"#;
+
+    #[test]
+    fn test_parse_player() {
+        let script = CSharpScript::parse(PLAYER);
+
+        assert_eq!(script.namespace.as_deref(), Some("Game.Actors"));
+        assert_eq!(script.class_name.as_deref(), Some("Player"));
+        assert_eq!(script.base_class.as_deref(), Some("CharacterBody2D"));
+        assert_eq!(script.class_line, 8);
+
+        assert_eq!(script.signals.len(), 1);
+        assert_eq!(script.signals[0].name, "HealthChanged");
+        assert_eq!(
+            script.signals[0].parameters,
+            vec![
+                CSharpParameter {
+                    name: "newValue".to_string(),
+                    param_type: "int".to_string(),
+                    default_value: None,
+                },
+                CSharpParameter {
+                    name: "reason".to_string(),
+                    param_type: "string".to_string(),
+                    default_value: Some("\"hit\"".to_string()),
+                },
+            ]
+        );
+
+        let exports: Vec<_> = script
+            .exports
+            .iter()
+            .map(|e| {
+                (
+                    e.name.as_str(),
+                    e.field_type.as_str(),
+                    e.default_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                ("Speed", "float", Some("300.0f")),
+                ("MaxHealth", "int", Some("100")),
+                ("_dropScene", "PackedScene", None),
+            ]
+        );
+
+        assert_eq!(script.constants[0].name, "Tag");
+        assert_eq!(
+            script.constants[0].default_value.as_deref(),
+            Some("\"player\"")
+        );
+        let fields: Vec<_> = script
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.field_type.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("_inventory", "Dictionary<string, List<int>>"),
+                ("_health", "int")
+            ]
+        );
+
+        assert_eq!(script.enums[0].name, "State");
+        let values: Vec<_> = script.enums[0]
+            .values
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect();
+        assert_eq!(values, ["Idle", "Running", "Dead"]);
+
+        let methods: Vec<_> = script
+            .methods
+            .iter()
+            .map(|m| (m.name.as_str(), m.return_type.as_str(), m.parameters.len()))
+            .collect();
+        assert_eq!(
+            methods,
+            vec![("_Ready", "void", 0), ("TakeDamage", "int", 2)]
+        );
+        assert_eq!(
+            script.methods[1].parameters[1].default_value.as_deref(),
+            Some("false")
+        );
+
+        assert_eq!(script.declaration_lines("HealthChanged"), vec![11]);
+        assert_eq!(script.declaration_lines("Running"), vec![25]);
+    }
+
+    #[test]
+    fn test_tokenize_strings_and_comments() {
+        let tokens = tokenize("var a = @\"x\"\"y\"; // \"c\"\n/* { */ var @class = '\\n';");
+        let kinds: Vec<_> = tokens.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Identifier("var".to_string()),
+                TokenKind::Identifier("a".to_string()),
+                TokenKind::Punct("="),
+                TokenKind::String("x\"y".to_string()),
+                TokenKind::Punct(";"),
+                TokenKind::Identifier("var".to_string()),
+                TokenKind::Identifier("class".to_string()),
+                TokenKind::Punct("="),
+                TokenKind::String("\n".to_string()),
+                TokenKind::Punct(";"),
+            ]
+        );
+        assert_eq!((tokens[6].line, tokens[6].column), (2, 13));
+    }
+}
@@ -0,0 +1,254 @@
+//! export_presets.cfg editing
+//!
+//! Each export preset is a `[preset.N]` section with the preset's settings
+//! (name, platform, export path, features, filters), followed by a
+//! `[preset.N.options]` section with the platform exporter's options. The
+//! file has project.godot's syntax, so it goes through [`ProjectSettings`]
+//! and edits keep everything else as written. Values are raw Godot syntax
+//! (`"Windows Desktop"`, `true`).
+
+use super::project_settings::ProjectSettings;
+
+/// A platform new presets can be created for, with the settings Godot writes
+/// for a fresh preset. Options Godot adds itself when the editor loads the
+/// preset are left out.
+pub struct PlatformTemplate {
+    /// Platform name as written in `platform="..."`
+    pub platform: &'static str,
+    /// Extension of the exported file
+    pub extension: &'static str,
+    pub options: &'static [(&'static str, &'static str)],
+}
+
+pub const PLATFORM_TEMPLATES: &[PlatformTemplate] = &[
+    PlatformTemplate {
+        platform: "Windows Desktop",
+        extension: "exe",
+        options: &[
+            ("custom_template/debug", "\"\""),
+            ("custom_template/release", "\"\""),
+            ("debug/export_console_wrapper", "1"),
+            ("binary_format/embed_pck", "false"),
+            ("texture_format/s3tc_bptc", "true"),
+            ("texture_format/etc2_astc", "false"),
+            ("binary_format/architecture", "\"x86_64\""),
+        ],
+    },
+    PlatformTemplate {
+        platform: "Linux",
+        extension: "x86_64",
+        options: &[
+            ("custom_template/debug", "\"\""),
+            ("custom_template/release", "\"\""),
+            ("debug/export_console_wrapper", "1"),
+            ("binary_format/embed_pck", "false"),
+            ("texture_format/s3tc_bptc", "true"),
+            ("texture_format/etc2_astc", "false"),
+            ("binary_format/architecture", "\"x86_64\""),
+        ],
+    },
+    PlatformTemplate {
+        platform: "macOS",
+        extension: "zip",
+        options: &[
+            ("export/distribution_type", "1"),
+            ("binary_format/architecture", "\"universal\""),
+            ("custom_template/debug", "\"\""),
+            ("custom_template/release", "\"\""),
+            ("application/bundle_identifier", "\"\""),
+            ("codesign/codesign", "1"),
+        ],
+    },
+    PlatformTemplate {
+        platform: "Web",
+        extension: "html",
+        options: &[
+            ("custom_template/debug", "\"\""),
+            ("custom_template/release", "\"\""),
+            ("variant/extensions_support", "false"),
+            ("vram_texture_compression/for_desktop", "true"),
+            ("vram_texture_compression/for_mobile", "false"),
+            ("html/export_icon", "true"),
+            ("html/canvas_resize_policy", "2"),
+            ("progressive_web_app/enabled", "false"),
+        ],
+    },
+    PlatformTemplate {
+        platform: "Android",
+        extension: "apk",
+        options: &[
+            ("gradle_build/use_gradle_build", "false"),
+            ("architectures/armeabi-v7a", "false"),
+            ("architectures/arm64-v8a", "true"),
+            ("version/code", "1"),
+            ("version/name", "\"\""),
+            ("package/unique_name", "\"com.example.$genname\""),
+        ],
+    },
+    PlatformTemplate {
+        platform: "iOS",
+        extension: "ipa",
+        options: &[
+            ("application/app_store_team_id", "\"\""),
+            ("application/bundle_identifier", "\"\""),
+            ("architectures/arm64", "true"),
+        ],
+    },
+];
+
+/// Preset settings after name, platform and runnable, in the order Godot writes them
+pub const DEFAULT_SETTINGS: &[(&str, &str)] = &[
+    ("advanced_options", "false"),
+    ("dedicated_server", "false"),
+    ("custom_features", "\"\""),
+    ("export_filter", "\"all_resources\""),
+    ("include_filter", "\"\""),
+    ("exclude_filter", "\"\""),
+    ("export_path", "\"\""),
+    ("encryption_include_filters", "\"\""),
+    ("encryption_exclude_filters", "\"\""),
+    ("encrypt_pck", "false"),
+    ("encrypt_directory", "false"),
+    ("script_export_mode", "2"),
+];
+
+/// Template for a platform, matched case-insensitively
+pub fn platform_template(platform: &str) -> Option<&'static PlatformTemplate> {
+    PLATFORM_TEMPLATES
+        .iter()
+        .find(|t| t.platform.eq_ignore_ascii_case(platform))
+}
+
+/// A parsed export_presets.cfg
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportPresets {
+    settings: ProjectSettings,
+}
+
+impl ExportPresets {
+    pub fn parse(content: &str) -> Self {
+        Self {
+            settings: ProjectSettings::parse(content),
+        }
+    }
+
+    /// Preset indices in file order
+    pub fn indices(&self) -> Vec<usize> {
+        self.settings
+            .sections()
+            .into_iter()
+            .filter_map(|section| section.strip_prefix("preset.")?.parse().ok())
+            .collect()
+    }
+
+    /// `key=value` settings of a preset
+    pub fn settings(&self, index: usize) -> Vec<(&str, &str)> {
+        self.settings.entries(&format!("preset.{}", index))
+    }
+
+    /// Exporter options of a preset
+    pub fn options(&self, index: usize) -> Vec<(&str, &str)> {
+        self.settings.entries(&format!("preset.{}.options", index))
+    }
+
+    pub fn get(&self, index: usize, key: &str) -> Option<&str> {
+        self.settings.get(&format!("preset.{}/{}", index, key))
+    }
+
+    /// Set a preset setting; returns whether the file changed
+    pub fn set(&mut self, index: usize, key: &str, value: &str) -> bool {
+        self.settings
+            .set(&format!("preset.{}/{}", index, key), value)
+    }
+
+    /// Set an exporter option; returns whether the file changed
+    pub fn set_option(&mut self, index: usize, key: &str, value: &str) -> bool {
+        self.settings
+            .set(&format!("preset.{}.options/{}", index, key), value)
+    }
+
+    /// Append a preset after the last one; returns its index
+    pub fn add<K: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        settings: &[(K, V)],
+        options: &[(K, V)],
+    ) -> usize {
+        let index = self.indices().into_iter().max().map_or(0, |i| i + 1);
+        for (key, value) in settings {
+            self.set(index, key.as_ref(), value.as_ref());
+        }
+        // Written even when empty, as Godot does
+        self.settings
+            .add_section(&format!("preset.{}.options", index));
+        for (key, value) in options {
+            self.set_option(index, key.as_ref(), value.as_ref());
+        }
+        index
+    }
+
+    /// Convert back to export_presets.cfg format
+    pub fn to_cfg(&self) -> String {
+        self.settings.to_project_godot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRESETS: &str = r#"[preset.0]
+
+name="Windows Desktop"
+platform="Windows Desktop"
+runnable=true
+custom_features=""
+export_path="build/game.exe"
+
+[preset.0.options]
+
+custom_template/debug=""
+binary_format/embed_pck=false
+"#;
+
+    #[test]
+    fn test_parse_presets() {
+        let presets = ExportPresets::parse(PRESETS);
+        assert_eq!(presets.indices(), vec![0]);
+        assert_eq!(presets.get(0, "name"), Some("\"Windows Desktop\""));
+        assert_eq!(presets.settings(0).len(), 5);
+        assert_eq!(
+            presets.options(0),
+            vec![
+                ("custom_template/debug", "\"\""),
+                ("binary_format/embed_pck", "false")
+            ]
+        );
+        assert_eq!(presets.to_cfg(), PRESETS);
+    }
+
+    #[test]
+    fn test_add_preset() {
+        let mut presets = ExportPresets::parse(PRESETS);
+        let index = presets.add(
+            &[("name", "\"Web\""), ("platform", "\"Web\"")],
+            &[("html/export_icon", "true")],
+        );
+        assert_eq!(index, 1);
+        assert!(presets.to_cfg().ends_with(
+            "binary_format/embed_pck=false\n\n[preset.1]\n\nname=\"Web\"\nplatform=\"Web\"\n\n[preset.1.options]\n\nhtml/export_icon=true\n"
+        ));
+
+        let mut empty = ExportPresets::parse("");
+        assert_eq!(empty.add(&[("name", "\"Linux\"")], &[]), 0);
+        assert_eq!(
+            empty.to_cfg(),
+            "[preset.0]\n\nname=\"Linux\"\n\n[preset.0.options]\n\n"
+        );
+    }
+
+    #[test]
+    fn test_platform_template() {
+        assert_eq!(platform_template("web").unwrap().platform, "Web");
+        assert!(platform_template("Switch").is_none());
+    }
+}
@@ -0,0 +1,696 @@
+//! Property expressions
+//!
+//! A small, side-effect free evaluator for computing property values from
+//! other node properties, e.g. `$"../Door".position + Vector2(32, 0)`.
+//!
+//! - Literals: numbers, strings, `true`/`false`, `PI`, `TAU`
+//! - `Vector2`/`Vector2i`/`Vector3`/`Vector3i`/`Color` constructors and `.x/.y/.z`, `.r/.g/.b/.a`
+//! - `+ - * / %`, unary `-`, parentheses
+//! - Functions: `abs floor ceil round sqrt sin cos min max clamp lerp deg_to_rad rad_to_deg`
+//! - `$Path.property` / `$"../Path".property` read another node (path relative to
+//!   the computed node, like in GDScript); a bare identifier reads the node's own property
+//!
+//! Stored values (`Vector2(1, 2)`, `0.5`, `"text"`) are parsed with the same grammar.
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ExpressionError {
+    #[error("Syntax error at {pos}: {message}")]
+    Syntax { pos: usize, message: String },
+    #[error("{0}")]
+    Eval(String),
+}
+
+type Result<T> = std::result::Result<T, ExpressionError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorKind {
+    Vector2,
+    Vector2i,
+    Vector3,
+    Vector3i,
+    Color,
+}
+
+impl VectorKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Vector2" => Some(Self::Vector2),
+            "Vector2i" => Some(Self::Vector2i),
+            "Vector3" => Some(Self::Vector3),
+            "Vector3i" => Some(Self::Vector3i),
+            "Color" => Some(Self::Color),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Vector2 => "Vector2",
+            Self::Vector2i => "Vector2i",
+            Self::Vector3 => "Vector3",
+            Self::Vector3i => "Vector3i",
+            Self::Color => "Color",
+        }
+    }
+
+    fn len(self) -> usize {
+        match self {
+            Self::Vector2 | Self::Vector2i => 2,
+            Self::Vector3 | Self::Vector3i => 3,
+            Self::Color => 4,
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        matches!(self, Self::Vector2i | Self::Vector3i)
+    }
+
+    fn component(self, member: &str) -> Option<usize> {
+        let index = match (self, member) {
+            (Self::Color, "r") => 0,
+            (Self::Color, "g") => 1,
+            (Self::Color, "b") => 2,
+            (Self::Color, "a") => 3,
+            (Self::Color, _) => return None,
+            (_, "x") => 0,
+            (_, "y") => 1,
+            (_, "z") => 2,
+            _ => return None,
+        };
+        (index < self.len()).then_some(index)
+    }
+}
+
+/// Evaluated value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Bool(bool),
+    Str(String),
+    Vector(VectorKind, Vec<f64>),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "number",
+            Self::Bool(_) => "bool",
+            Self::Str(_) => "String",
+            Self::Vector(kind, _) => kind.name(),
+        }
+    }
+
+    /// Format as a .tscn property value
+    pub fn to_godot(&self) -> String {
+        match self {
+            Self::Number(n) => format_number(*n),
+            Self::Bool(b) => b.to_string(),
+            Self::Str(s) => format!(
+                "\"{}\"",
+                s.replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('\n', "\\n")
+            ),
+            Self::Vector(kind, components) => {
+                let parts: Vec<String> = components
+                    .iter()
+                    .map(|&c| format_number(if kind.is_integer() { c.round() } else { c }))
+                    .collect();
+                format!("{}({})", kind.name(), parts.join(", "))
+            }
+        }
+    }
+}
+
+/// Shortest decimal form, up to 6 fractional digits
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        return format!("{}", n as i64);
+    }
+    let s = format!("{:.6}", n);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Looks up `property` on the node at `node_path` (relative to the computed
+/// node; "." is the node itself) and returns its stored value string
+pub trait PropertySource {
+    fn property(&self, node_path: &str, property: &str) -> Result<Value>;
+}
+
+/// Source for literal-only evaluation
+struct NoProperties;
+
+impl PropertySource for NoProperties {
+    fn property(&self, _: &str, property: &str) -> Result<Value> {
+        Err(ExpressionError::Eval(format!(
+            "Unsupported value: {}",
+            property
+        )))
+    }
+}
+
+/// Evaluate an expression
+pub fn evaluate(expression: &str, source: &dyn PropertySource) -> Result<Value> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        source,
+    };
+    let value = parser.expression()?;
+    match parser.peek() {
+        Token::End => Ok(value),
+        _ => Err(parser.error("Unexpected token")),
+    }
+}
+
+/// Parse a stored property value (literals and constructors only)
+pub fn parse_value(value: &str) -> Result<Value> {
+    evaluate(value, &NoProperties)
+}
+
+/// Bare identifiers an expression reads as its own properties (`size` in
+/// `Vector2(size, size) * 2`), in order of first use. Only tokenizes: a
+/// syntax error further in is not reported.
+pub fn property_names(expression: &str) -> Result<Vec<String>> {
+    let tokens = tokenize(expression)?;
+    let mut names: Vec<String> = Vec::new();
+    for (i, (_, token)) in tokens.iter().enumerate() {
+        let Token::Ident(name) = token else {
+            continue;
+        };
+        let member = i > 0 && tokens[i - 1].1 == Token::Op('.');
+        let called = tokens.get(i + 1).is_some_and(|(_, t)| *t == Token::Op('('));
+        let constant = matches!(name.as_str(), "true" | "false" | "PI" | "TAU");
+        if !member && !called && !constant && !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+    Ok(names)
+}
+
+// ======================
+// Tokenizer
+// ======================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    /// `$Path` or `$"path"`
+    NodePath(String),
+    Op(char),
+    End,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()))
+        {
+            while i < chars.len()
+                && (chars[i].is_ascii_digit()
+                    || chars[i] == '.'
+                    || chars[i] == '_'
+                    || chars[i] == 'e'
+                    || ((chars[i] == '-' || chars[i] == '+') && chars[i - 1] == 'e'))
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+            let number = text.parse().map_err(|_| ExpressionError::Syntax {
+                pos: start,
+                message: format!("Invalid number '{}'", text),
+            })?;
+            tokens.push((start, Token::Number(number)));
+        } else if c == '"' {
+            let (text, end) = read_string(&chars, i)?;
+            tokens.push((start, Token::Str(text)));
+            i = end;
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((start, Token::Ident(chars[start..i].iter().collect())));
+        } else if c == '$' {
+            i += 1;
+            if chars.get(i) == Some(&'"') {
+                let (text, end) = read_string(&chars, i)?;
+                tokens.push((start, Token::NodePath(text)));
+                i = end;
+            } else {
+                let path_start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '/')
+                {
+                    i += 1;
+                }
+                if i == path_start {
+                    return Err(ExpressionError::Syntax {
+                        pos: start,
+                        message: "Expected node path after '$'".to_string(),
+                    });
+                }
+                tokens.push((
+                    start,
+                    Token::NodePath(chars[path_start..i].iter().collect()),
+                ));
+            }
+        } else if "+-*/%(),.".contains(c) {
+            tokens.push((start, Token::Op(c)));
+            i += 1;
+        } else {
+            return Err(ExpressionError::Syntax {
+                pos: start,
+                message: format!("Unexpected character '{}'", c),
+            });
+        }
+    }
+    tokens.push((chars.len(), Token::End));
+    Ok(tokens)
+}
+
+/// Read a double-quoted string starting at `start`; returns it and the index after it
+fn read_string(chars: &[char], start: usize) -> Result<(String, usize)> {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => return Ok((text, i + 1)),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                text.push(match chars[i] {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                });
+            }
+            other => text.push(other),
+        }
+        i += 1;
+    }
+    Err(ExpressionError::Syntax {
+        pos: start,
+        message: "Unterminated string".to_string(),
+    })
+}
+
+// ======================
+// Parser / Evaluator
+// ======================
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    source: &'a dyn PropertySource,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].1.clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error(&self, message: &str) -> ExpressionError {
+        ExpressionError::Syntax {
+            pos: self.tokens[self.pos].0,
+            message: message.to_string(),
+        }
+    }
+
+    fn expect(&mut self, op: char) -> Result<()> {
+        if self.peek() == &Token::Op(op) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'", op)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        let start = self.pos;
+        match self.next() {
+            Token::Ident(name) => Ok(name),
+            _ => {
+                self.pos = start;
+                Err(self.error("Expected identifier"))
+            }
+        }
+    }
+
+    fn expression(&mut self) -> Result<Value> {
+        let mut value = self.term()?;
+        while let Token::Op(op @ ('+' | '-')) = *self.peek() {
+            self.next();
+            let rhs = self.term()?;
+            value = binary(op, value, rhs)?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Value> {
+        let mut value = self.unary()?;
+        while let Token::Op(op @ ('*' | '/' | '%')) = *self.peek() {
+            self.next();
+            let rhs = self.unary()?;
+            value = binary(op, value, rhs)?;
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Value> {
+        if self.peek() == &Token::Op('-') {
+            self.next();
+            let value = self.unary()?;
+            return binary('*', value, Value::Number(-1.0));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Value> {
+        let mut value = self.primary()?;
+        while self.peek() == &Token::Op('.') {
+            self.next();
+            let member = self.ident()?;
+            value = match &value {
+                Value::Vector(kind, components) => kind
+                    .component(&member)
+                    .map(|i| Value::Number(components[i]))
+                    .ok_or_else(|| {
+                        ExpressionError::Eval(format!("{} has no member '{}'", kind.name(), member))
+                    })?,
+                other => {
+                    return Err(ExpressionError::Eval(format!(
+                        "{} has no member '{}'",
+                        other.type_name(),
+                        member
+                    )))
+                }
+            };
+        }
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> Result<Value> {
+        let start = self.pos;
+        match self.next() {
+            Token::Number(n) => Ok(Value::Number(n)),
+            Token::Str(s) => Ok(Value::Str(s)),
+            Token::NodePath(path) => {
+                if self.peek() != &Token::Op('.') {
+                    return Err(self.error("Expected '.property' after node path"));
+                }
+                self.next();
+                let property = self.ident()?;
+                self.source.property(&path, &property)
+            }
+            Token::Op('(') => {
+                let value = self.expression()?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Token::Ident(name) => {
+                if self.peek() == &Token::Op('(') {
+                    self.next();
+                    let mut args = Vec::new();
+                    if self.peek() != &Token::Op(')') {
+                        loop {
+                            args.push(self.expression()?);
+                            if self.peek() != &Token::Op(',') {
+                                break;
+                            }
+                            self.next();
+                        }
+                    }
+                    self.expect(')')?;
+                    return call(&name, args);
+                }
+                match name.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "PI" => Ok(Value::Number(std::f64::consts::PI)),
+                    "TAU" => Ok(Value::Number(std::f64::consts::TAU)),
+                    _ => self.source.property(".", &name),
+                }
+            }
+            _ => {
+                self.pos = start;
+                Err(self.error("Expected a value"))
+            }
+        }
+    }
+}
+
+fn binary(op: char, lhs: Value, rhs: Value) -> Result<Value> {
+    let apply = |a: f64, b: f64| match op {
+        '+' => a + b,
+        '-' => a - b,
+        '*' => a * b,
+        '/' => a / b,
+        _ => a % b,
+    };
+    if matches!(op, '/' | '%') && is_zero(&rhs) {
+        return Err(ExpressionError::Eval("Division by zero".to_string()));
+    }
+
+    match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => Ok(Value::Number(apply(a, b))),
+        (Value::Str(a), Value::Str(b)) if op == '+' => Ok(Value::Str(a + &b)),
+        (Value::Vector(kind, a), Value::Vector(other, b)) if a.len() == b.len() => {
+            // Mixing float and integer vectors yields the float kind
+            let kind = if kind.is_integer() { other } else { kind };
+            Ok(Value::Vector(
+                kind,
+                a.iter().zip(&b).map(|(&x, &y)| apply(x, y)).collect(),
+            ))
+        }
+        (Value::Vector(kind, a), Value::Number(b)) if op != '+' && op != '-' => Ok(Value::Vector(
+            kind,
+            a.iter().map(|&x| apply(x, b)).collect(),
+        )),
+        (Value::Number(a), Value::Vector(kind, b)) if op == '*' => Ok(Value::Vector(
+            kind,
+            b.iter().map(|&y| apply(a, y)).collect(),
+        )),
+        (lhs, rhs) => Err(ExpressionError::Eval(format!(
+            "Cannot apply '{}' to {} and {}",
+            op,
+            lhs.type_name(),
+            rhs.type_name()
+        ))),
+    }
+}
+
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => *n == 0.0,
+        Value::Vector(_, c) => c.contains(&0.0),
+        _ => false,
+    }
+}
+
+fn number(value: &Value, function: &str) -> Result<f64> {
+    match value {
+        Value::Number(n) => Ok(*n),
+        other => Err(ExpressionError::Eval(format!(
+            "{}() expects a number, got {}",
+            function,
+            other.type_name()
+        ))),
+    }
+}
+
+/// Apply `f` to a number or to each vector component
+fn map(value: Value, function: &str, f: impl Fn(f64) -> f64) -> Result<Value> {
+    match value {
+        Value::Number(n) => Ok(Value::Number(f(n))),
+        Value::Vector(kind, c) => Ok(Value::Vector(kind, c.into_iter().map(f).collect())),
+        other => Err(ExpressionError::Eval(format!(
+            "{}() expects a number or vector, got {}",
+            function,
+            other.type_name()
+        ))),
+    }
+}
+
+/// Combine two numbers or two same-sized vectors component-wise
+fn zip(a: Value, b: Value, function: &str, f: impl Fn(f64, f64) -> f64) -> Result<Value> {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => Ok(Value::Number(f(x, y))),
+        (Value::Vector(kind, x), Value::Vector(_, y)) if x.len() == y.len() => Ok(Value::Vector(
+            kind,
+            x.iter().zip(&y).map(|(&p, &q)| f(p, q)).collect(),
+        )),
+        (a, b) => Err(ExpressionError::Eval(format!(
+            "{}() cannot combine {} and {}",
+            function,
+            a.type_name(),
+            b.type_name()
+        ))),
+    }
+}
+
+fn call(name: &str, args: Vec<Value>) -> Result<Value> {
+    if let Some(kind) = VectorKind::from_name(name) {
+        let components = args
+            .iter()
+            .map(|a| number(a, name))
+            .collect::<Result<Vec<f64>>>()?;
+        let components = match (kind, components.len()) {
+            (_, n) if n == kind.len() => components,
+            // Color(r, g, b) is opaque
+            (VectorKind::Color, 3) => [components, vec![1.0]].concat(),
+            _ => {
+                return Err(ExpressionError::Eval(format!(
+                    "{}() expects {} arguments",
+                    name,
+                    kind.len()
+                )))
+            }
+        };
+        return Ok(Value::Vector(kind, components));
+    }
+
+    let arity = match name {
+        "abs" | "floor" | "ceil" | "round" | "sqrt" | "sin" | "cos" | "deg_to_rad"
+        | "rad_to_deg" => 1,
+        "min" | "max" => 2,
+        "clamp" | "lerp" => 3,
+        _ => return Err(ExpressionError::Eval(format!("Unknown function: {}", name))),
+    };
+    if args.len() != arity {
+        return Err(ExpressionError::Eval(format!(
+            "{}() expects {} arguments",
+            name, arity
+        )));
+    }
+
+    let mut args = args.into_iter();
+    let mut arg = || args.next().expect("arity checked");
+    match name {
+        "abs" => map(arg(), name, f64::abs),
+        "floor" => map(arg(), name, f64::floor),
+        "ceil" => map(arg(), name, f64::ceil),
+        "round" => map(arg(), name, f64::round),
+        "sqrt" => map(arg(), name, f64::sqrt),
+        "sin" => map(arg(), name, f64::sin),
+        "cos" => map(arg(), name, f64::cos),
+        "deg_to_rad" => map(arg(), name, f64::to_radians),
+        "rad_to_deg" => map(arg(), name, f64::to_degrees),
+        "min" => zip(arg(), arg(), name, f64::min),
+        "max" => zip(arg(), arg(), name, f64::max),
+        "clamp" => {
+            let value = arg();
+            let low = number(&arg(), name)?;
+            let high = number(&arg(), name)?;
+            map(value, name, |x| x.clamp(low, high.max(low)))
+        }
+        _ => {
+            let from = arg();
+            let to = arg();
+            let weight = number(&arg(), name)?;
+            zip(from, to, name, |a, b| a + (b - a) * weight)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct Props(HashMap<(&'static str, &'static str), &'static str>);
+
+    impl PropertySource for Props {
+        fn property(&self, node_path: &str, property: &str) -> Result<Value> {
+            let raw = self.0.get(&(node_path, property)).ok_or_else(|| {
+                ExpressionError::Eval(format!("{}:{} not set", node_path, property))
+            })?;
+            parse_value(raw)
+        }
+    }
+
+    fn props() -> Props {
+        Props(HashMap::from([
+            (("../Door", "position"), "Vector2(100, 40)"),
+            (("Sprite", "scale"), "Vector2(2, 2)"),
+            ((".", "speed"), "1.5"),
+        ]))
+    }
+
+    fn eval(expression: &str) -> String {
+        evaluate(expression, &props()).unwrap().to_godot()
+    }
+
+    #[test]
+    fn test_arithmetic_and_vectors() {
+        assert_eq!(eval("1 + 2 * 3"), "7");
+        assert_eq!(eval("(1 + 2) * -3"), "-9");
+        assert_eq!(eval("10 / 4"), "2.5");
+        assert_eq!(
+            eval("Vector2(1, 2) * 2 + Vector2(0.5, 0)"),
+            "Vector2(2.5, 4)"
+        );
+        assert_eq!(eval("Vector3i(1, 2, 3).z"), "3");
+        assert_eq!(eval("Color(1, 0, 0)"), "Color(1, 0, 0, 1)");
+        assert_eq!(eval("\"a\" + \"b\""), "\"ab\"");
+    }
+
+    #[test]
+    fn test_node_references() {
+        assert_eq!(
+            eval("$\"../Door\".position + Vector2(32, 0)"),
+            "Vector2(132, 40)"
+        );
+        assert_eq!(eval("$Sprite.scale.x * speed"), "3");
+        assert_eq!(eval("lerp(0, $\"../Door\".position.x, 0.25)"), "25");
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(eval("clamp(15, 0, 10)"), "10");
+        assert_eq!(eval("max(Vector2(1, 5), Vector2(3, 2))"), "Vector2(3, 5)");
+        assert_eq!(eval("round(deg_to_rad(180) * 1000)"), "3142");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            evaluate("1 +", &props()),
+            Err(ExpressionError::Syntax { pos: 3, .. })
+        ));
+        assert_eq!(
+            evaluate("1 / 0", &props()),
+            Err(ExpressionError::Eval("Division by zero".to_string()))
+        );
+        assert!(evaluate("Vector2(1, 2) + 1", &props()).is_err());
+        assert!(evaluate("OS.execute()", &props()).is_err());
+        assert!(evaluate("$Missing.position", &props()).is_err());
+    }
+
+    #[test]
+    fn test_property_names() {
+        assert_eq!(
+            property_names("Vector2(size, size * 2).x + max(gap, PI) + $Sprite.scale.y").unwrap(),
+            vec!["size", "gap"]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! GDScript syntax tree
+//!
+//! Produced by [`super::parser`]. A script is a [`ClassDecl`] without a name
+//! of its own (`class_name` is optional); inner classes nest the same type.
+//! Type hints and initializers keep their source text alongside the parsed
+//! expression so tools can report and rewrite them verbatim. Lines are 1-based.
+
+/// Class body: the script itself or an inner `class`
+#[derive(Debug, Clone, Default)]
+pub struct ClassDecl {
+    /// `class_name` for a script, the class name for an inner class
+    pub name: Option<String>,
+    /// `extends` target: class name, dotted inner class or `res://` path (unquoted)
+    pub extends: Option<String>,
+    /// Class-level annotations such as `@tool`, `@icon(...)`
+    pub annotations: Vec<Annotation>,
+    pub members: Vec<Member>,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+impl ClassDecl {
+    pub fn variables(&self) -> impl Iterator<Item = &VariableDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Variable(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = &ConstantDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Constant(c) => Some(c),
+            _ => None,
+        })
+    }
+
+    pub fn signals(&self) -> impl Iterator<Item = &SignalDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Signal(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    pub fn enums(&self) -> impl Iterator<Item = &EnumDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Enum(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &FunctionDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Function(f) => Some(f),
+            _ => None,
+        })
+    }
+
+    pub fn classes(&self) -> impl Iterator<Item = &ClassDecl> {
+        self.members.iter().filter_map(|m| match m {
+            Member::Class(c) => Some(c),
+            _ => None,
+        })
+    }
+
+    pub fn has_annotation(&self, name: &str) -> bool {
+        self.annotations.iter().any(|a| a.name == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Member {
+    Variable(VariableDecl),
+    Constant(ConstantDecl),
+    Signal(SignalDecl),
+    Enum(EnumDecl),
+    Function(FunctionDecl),
+    Class(ClassDecl),
+}
+
+impl Member {
+    /// Declared name (`None` for anonymous enums)
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Member::Variable(v) => Some(&v.name),
+            Member::Constant(c) => Some(&c.name),
+            Member::Signal(s) => Some(&s.name),
+            Member::Enum(e) => e.name.as_deref(),
+            Member::Function(f) => Some(&f.name),
+            Member::Class(c) => c.name.as_deref(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Member::Variable(v) => v.line,
+            Member::Constant(c) => c.line,
+            Member::Signal(s) => s.line,
+            Member::Enum(e) => e.line,
+            Member::Function(f) => f.line,
+            Member::Class(c) => c.line,
+        }
+    }
+
+    /// Last line of the declaration, including bodies
+    pub fn end_line(&self) -> usize {
+        match self {
+            Member::Variable(v) => v.end_line,
+            Member::Constant(c) => c.end_line,
+            Member::Signal(s) => s.end_line,
+            Member::Enum(e) => e.end_line,
+            Member::Function(f) => f.end_line,
+            Member::Class(c) => c.end_line,
+        }
+    }
+}
+
+/// `@name` or `@name(args)`; Godot 3 keywords (`export`, `onready`, `tool`,
+/// `remote`, ...) are recorded as annotations of the same name
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub name: String,
+    pub arguments: Vec<Expr>,
+    pub line: usize,
+}
+
+impl Annotation {
+    /// String literal arguments, e.g. the types of `@export_node_path("A", "B")`
+    pub fn string_arguments(&self) -> Vec<String> {
+        self.arguments
+            .iter()
+            .filter_map(|a| match a {
+                Expr::Literal(Literal::String(s)) => Some(s.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `var` declaration (member or local)
+#[derive(Debug, Clone)]
+pub struct VariableDecl {
+    pub name: String,
+    /// Declared type as written (`int`, `Array[Node]`); `None` for untyped or `:=`
+    pub type_hint: Option<String>,
+    /// Declared with `:=`
+    pub inferred: bool,
+    pub value: Option<Expr>,
+    /// Initializer source text
+    pub value_source: Option<String>,
+    pub annotations: Vec<Annotation>,
+    pub is_static: bool,
+    pub setter: Option<Accessor>,
+    pub getter: Option<Accessor>,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+impl VariableDecl {
+    pub fn has_annotation(&self, name: &str) -> bool {
+        self.annotations.iter().any(|a| a.name == name)
+    }
+
+    /// `@export` or one of the `@export_*` property hints
+    pub fn is_exported(&self) -> bool {
+        self.annotations.iter().any(|a| {
+            a.name == "export"
+                || (a.name.starts_with("export_")
+                    && !matches!(
+                        a.name.as_str(),
+                        "export_category" | "export_group" | "export_subgroup"
+                    ))
+        })
+    }
+}
+
+/// Property setter or getter
+#[derive(Debug, Clone)]
+pub enum Accessor {
+    /// `set = _set_value` / `get = _get_value` / Godot 3 `setget`
+    Function(String),
+    /// `set(value): ...` / `get: ...`
+    Inline {
+        parameter: Option<String>,
+        body: Vec<Statement>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstantDecl {
+    pub name: String,
+    pub type_hint: Option<String>,
+    pub value: Option<Expr>,
+    pub value_source: Option<String>,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalDecl {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumDecl {
+    /// `None` for `enum { A, B }`, whose values become class constants
+    pub name: Option<String>,
+    pub values: Vec<EnumValue>,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumValue {
+    pub name: String,
+    pub value_source: Option<String>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionDecl {
+    /// Empty for anonymous lambdas
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+    pub body: Vec<Statement>,
+    /// Body source text, dedented to column 0
+    pub body_source: String,
+    pub annotations: Vec<Annotation>,
+    pub is_static: bool,
+    /// Line of the `func` keyword
+    pub line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub name: String,
+    pub type_hint: Option<String>,
+    pub inferred: bool,
+    pub default: Option<Expr>,
+    pub default_source: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub line: usize,
+    pub kind: StatementKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum StatementKind {
+    Expression(Expr),
+    /// `target op value` for `=`, `+=`, `-=`, ...
+    Assign {
+        target: Expr,
+        op: &'static str,
+        value: Expr,
+    },
+    Variable(VariableDecl),
+    Constant(ConstantDecl),
+    If {
+        /// `if` and `elif` conditions with their bodies
+        branches: Vec<(Expr, Vec<Statement>)>,
+        otherwise: Option<Vec<Statement>>,
+    },
+    While {
+        condition: Expr,
+        body: Vec<Statement>,
+    },
+    For {
+        variable: String,
+        type_hint: Option<String>,
+        iterable: Expr,
+        body: Vec<Statement>,
+    },
+    Match {
+        subject: Expr,
+        branches: Vec<MatchBranch>,
+    },
+    Return(Option<Expr>),
+    Pass,
+    Break,
+    Continue,
+    Breakpoint,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchBranch {
+    pub patterns: Vec<Pattern>,
+    /// `when` guard
+    pub guard: Option<Expr>,
+    pub body: Vec<Statement>,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `_`
+    Wildcard,
+    /// `var name`
+    Binding(String),
+    /// `..` inside array and dictionary patterns
+    Rest,
+    /// Literal or constant expression (`1`, `"a"`, `State.IDLE`)
+    Expression(Expr),
+    Array(Vec<Pattern>),
+    /// Keys with optional value patterns
+    Dictionary(Vec<(Pattern, Option<Pattern>)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// Number as written
+    Number(String),
+    String(String),
+    StringName(String),
+    NodePath(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Literal(Literal),
+    Identifier {
+        name: String,
+        line: usize,
+    },
+    SelfRef,
+    /// `super` (as in `super()` or `super.method()`)
+    Super,
+    /// `$Path/To/Node`, `$"Path"`, `%Unique`
+    GetNode {
+        path: String,
+        unique: bool,
+    },
+    Array(Vec<Expr>),
+    Dictionary(Vec<(Expr, Expr)>),
+    Unary {
+        op: &'static str,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: &'static str,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    /// `value if condition else otherwise`
+    Ternary {
+        value: Box<Expr>,
+        condition: Box<Expr>,
+        otherwise: Box<Expr>,
+    },
+    /// `value is Type` / `value is not Type`
+    Is {
+        value: Box<Expr>,
+        type_name: String,
+        negated: bool,
+    },
+    /// `value as Type`
+    Cast {
+        value: Box<Expr>,
+        type_name: String,
+    },
+    Await(Box<Expr>),
+    Call {
+        callee: Box<Expr>,
+        arguments: Vec<Expr>,
+    },
+    Attribute {
+        base: Box<Expr>,
+        name: String,
+    },
+    Subscript {
+        base: Box<Expr>,
+        index: Box<Expr>,
+    },
+    Lambda(Box<FunctionDecl>),
+}
+
+impl Expr {
+    /// Call of a plain function by name, e.g. `preload(...)`
+    pub fn as_call_to(&self, function: &str) -> Option<&[Expr]> {
+        match self {
+            Expr::Call { callee, arguments } => match callee.as_ref() {
+                Expr::Identifier { name, .. } if name == function => Some(arguments),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Syntax error with the line it was found on
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub message: String,
+}
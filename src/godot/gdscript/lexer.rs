@@ -0,0 +1,520 @@
+//! GDScript tokenizer
+//!
+//! Turns source text into tokens with byte offsets and line numbers. Comments
+//! are dropped. Every logical line starts with a `Newline(indent)` token
+//! carrying its indentation (a tab counts as 4 columns); blank and
+//! comment-only lines and `\` continuations produce none. Whether a newline
+//! ends a statement (it does not inside brackets) is left to the parser.
+
+/// Keywords that can never be identifiers
+const KEYWORDS: &[&str] = &[
+    "if",
+    "elif",
+    "else",
+    "for",
+    "while",
+    "break",
+    "continue",
+    "pass",
+    "return",
+    "match",
+    "func",
+    "class",
+    "class_name",
+    "extends",
+    "is",
+    "in",
+    "as",
+    "self",
+    "super",
+    "signal",
+    "static",
+    "const",
+    "enum",
+    "var",
+    "breakpoint",
+    "await",
+    "and",
+    "or",
+    "not",
+    "true",
+    "false",
+    "null",
+];
+
+/// Operators and punctuation, longest first
+const PUNCTUATION: &[&str] = &[
+    "**=", "<<=", ">>=", "**", "<<", ">>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=",
+    "/=", "%=", "&=", "|=", "^=", "->", "..", "+", "-", "*", "/", "%", "^", "&", "|", "~", "!",
+    "<", ">", "=", "(", ")", "[", "]", "{", "}", ",", ";", ":", ".", "$", "?",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringKind {
+    /// `"text"`, `'text'`, `"""text"""`, `r"text"`
+    Plain,
+    /// `&"name"`
+    StringName,
+    /// `^"path"`
+    NodePath,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// Start of a logical line, with its indentation width
+    Newline(usize),
+    Identifier(String),
+    Keyword(&'static str),
+    /// `@name`
+    Annotation(String),
+    /// Number literal as written (`1_000`, `0xFF`, `1.5e3`)
+    Number(String),
+    /// String literal with escapes resolved
+    String {
+        value: String,
+        kind: StringKind,
+    },
+    Punct(&'static str),
+    Eof,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Byte range in the source
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line of the first character
+    pub line: usize,
+    /// 0-based byte column of the first character
+    pub column: usize,
+}
+
+impl Token {
+    /// Identifier text, if this is an identifier
+    pub fn identifier(&self) -> Option<&str> {
+        match &self.kind {
+            TokenKind::Identifier(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Lexical error (unterminated string, unknown character)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Tokenize GDScript source
+///
+/// Always yields a token list ending in `Eof`; malformed input is skipped
+/// and reported in the error list.
+pub fn tokenize(source: &str) -> (Vec<Token>, Vec<LexError>) {
+    let mut lexer = Lexer {
+        source,
+        bytes: source.as_bytes(),
+        pos: 0,
+        line: 1,
+        line_start: 0,
+        tokens: Vec::new(),
+        errors: Vec::new(),
+    };
+    lexer.run();
+    (lexer.tokens, lexer.errors)
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    tokens: Vec<Token>,
+    errors: Vec<LexError>,
+}
+
+impl Lexer<'_> {
+    fn run(&mut self) {
+        let mut at_line_start = true;
+
+        while self.pos < self.bytes.len() {
+            if at_line_start {
+                at_line_start = false;
+                if let Some(indent) = self.line_indent() {
+                    self.push(TokenKind::Newline(indent), self.pos);
+                }
+                continue;
+            }
+
+            let c = self.bytes[self.pos];
+            match c {
+                b'\n' => {
+                    self.newline();
+                    at_line_start = true;
+                }
+                b' ' | b'\t' | b'\r' => self.pos += 1,
+                b'#' => self.skip_comment(),
+                b'\\' if self.is_continuation() => {
+                    // Skip to the next line without starting a new logical line
+                    while self.bytes[self.pos] != b'\n' {
+                        self.pos += 1;
+                    }
+                    self.newline();
+                }
+                b'"' | b'\'' => self.string(self.pos, StringKind::Plain, false),
+                b'r' if matches!(self.peek_at(1), Some(b'"' | b'\'')) => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    self.string(start, StringKind::Plain, true);
+                }
+                b'&' if matches!(self.peek_at(1), Some(b'"' | b'\'')) => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    self.string(start, StringKind::StringName, false);
+                }
+                b'^' if matches!(self.peek_at(1), Some(b'"' | b'\'')) => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    self.string(start, StringKind::NodePath, false);
+                }
+                b'@' => {
+                    let start = self.pos;
+                    self.pos += 1;
+                    let name = self.word();
+                    if name.is_empty() {
+                        self.error("Expected annotation name after '@'");
+                    } else {
+                        self.push(TokenKind::Annotation(name), start);
+                    }
+                }
+                b'0'..=b'9' => self.number(),
+                b'.' if matches!(self.peek_at(1), Some(b'0'..=b'9')) => self.number(),
+                _ if c == b'_' || c.is_ascii_alphabetic() || c >= 0x80 => {
+                    let start = self.pos;
+                    let word = self.word();
+                    if word.is_empty() {
+                        // Non-identifier unicode character
+                        let len = self.char_len();
+                        self.error(format!(
+                            "Unexpected character '{}'",
+                            &self.source[start..start + len]
+                        ));
+                        self.pos += len;
+                    } else if let Some(keyword) = KEYWORDS.iter().find(|k| **k == word) {
+                        self.push(TokenKind::Keyword(keyword), start);
+                    } else {
+                        self.push(TokenKind::Identifier(word), start);
+                    }
+                }
+                _ => {
+                    let start = self.pos;
+                    match PUNCTUATION
+                        .iter()
+                        .find(|p| self.source[self.pos..].starts_with(**p))
+                    {
+                        Some(p) => {
+                            self.pos += p.len();
+                            self.push(TokenKind::Punct(p), start);
+                        }
+                        None => {
+                            let len = self.char_len();
+                            self.error(format!(
+                                "Unexpected character '{}'",
+                                &self.source[start..start + len]
+                            ));
+                            self.pos += len;
+                        }
+                    }
+                }
+            }
+        }
+
+        let end = self.bytes.len();
+        self.tokens.push(Token {
+            kind: TokenKind::Eof,
+            start: end,
+            end,
+            line: self.line,
+            column: end - self.line_start,
+        });
+    }
+
+    /// Measure the indentation at the start of a line and move past it;
+    /// `None` for blank and comment-only lines
+    fn line_indent(&mut self) -> Option<usize> {
+        let mut indent = 0;
+        while let Some(&c) = self.bytes.get(self.pos) {
+            match c {
+                b' ' => indent += 1,
+                b'\t' => indent += 4,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        match self.bytes.get(self.pos) {
+            None | Some(b'\n' | b'#') => None,
+            Some(b'\r') if self.peek_at(1) == Some(b'\n') => None,
+            _ => Some(indent),
+        }
+    }
+
+    fn newline(&mut self) {
+        self.pos += 1;
+        self.line += 1;
+        self.line_start = self.pos;
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.pos + offset).copied()
+    }
+
+    fn char_len(&self) -> usize {
+        self.source[self.pos..]
+            .chars()
+            .next()
+            .map(char::len_utf8)
+            .unwrap_or(1)
+    }
+
+    fn is_continuation(&self) -> bool {
+        self.source[self.pos + 1..]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .all(char::is_whitespace)
+            && self.source[self.pos + 1..].contains('\n')
+    }
+
+    fn skip_comment(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos] != b'\n' {
+            self.pos += 1;
+        }
+    }
+
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        for (i, c) in self.source[start..].char_indices() {
+            if !(c == '_' || c.is_alphanumeric()) {
+                self.pos = start + i;
+                return self.source[start..self.pos].to_string();
+            }
+        }
+        self.pos = self.bytes.len();
+        self.source[start..].to_string()
+    }
+
+    fn number(&mut self) {
+        let start = self.pos;
+        if self.bytes[self.pos] == b'0'
+            && matches!(self.peek_at(1), Some(b'x' | b'X' | b'b' | b'B'))
+        {
+            self.pos += 2;
+            while self
+                .bytes
+                .get(self.pos)
+                .is_some_and(|c| c.is_ascii_hexdigit() || *c == b'_')
+            {
+                self.pos += 1;
+            }
+        } else {
+            let digits = |lexer: &mut Self| {
+                while lexer
+                    .bytes
+                    .get(lexer.pos)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == b'_')
+                {
+                    lexer.pos += 1;
+                }
+            };
+            digits(self);
+            // `1.5` but not `1..2` or `1.method` (integer then attribute is not valid anyway)
+            if self.bytes.get(self.pos) == Some(&b'.') && self.peek_at(1) != Some(b'.') {
+                self.pos += 1;
+                digits(self);
+            }
+            if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+                let sign = usize::from(matches!(self.peek_at(1), Some(b'+' | b'-')));
+                if self
+                    .bytes
+                    .get(self.pos + 1 + sign)
+                    .is_some_and(u8::is_ascii_digit)
+                {
+                    self.pos += 1 + sign;
+                    digits(self);
+                }
+            }
+        }
+        let text = self.source[start..self.pos].to_string();
+        self.push(TokenKind::Number(text), start);
+    }
+
+    /// String literal starting at the quote under `pos`; `start` includes any prefix
+    fn string(&mut self, start: usize, kind: StringKind, raw: bool) {
+        let start_line = self.line;
+        let start_column = start - self.line_start;
+        let quote = self.bytes[self.pos];
+        let triple = self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote);
+        self.pos += if triple { 3 } else { 1 };
+
+        let mut value = String::new();
+        loop {
+            let Some(c) = self.source[self.pos..].chars().next() else {
+                self.errors.push(LexError {
+                    line: start_line,
+                    message: "Unterminated string".to_string(),
+                });
+                break;
+            };
+            if c as u32 == quote as u32 {
+                if !triple {
+                    self.pos += 1;
+                    break;
+                }
+                if self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote) {
+                    self.pos += 3;
+                    break;
+                }
+            }
+            if c == '\n' {
+                if !triple {
+                    self.errors.push(LexError {
+                        line: start_line,
+                        message: "Unterminated string".to_string(),
+                    });
+                    break;
+                }
+                value.push('\n');
+                self.newline();
+                continue;
+            }
+            if c == '\\' && !raw {
+                self.pos += 1;
+                let Some(escaped) = self.source[self.pos..].chars().next() else {
+                    continue;
+                };
+                self.pos += escaped.len_utf8();
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    '0' => value.push('\0'),
+                    '\n' => {
+                        // Escaped line break inside a string
+                        self.line += 1;
+                        self.line_start = self.pos;
+                    }
+                    'u' | 'U' => {
+                        let len = if escaped == 'u' { 4 } else { 6 };
+                        let hex: String = self.source[self.pos..].chars().take(len).collect();
+                        match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            Some(ch) => {
+                                value.push(ch);
+                                self.pos += hex.len();
+                            }
+                            None => value.push(escaped),
+                        }
+                    }
+                    other => value.push(other),
+                }
+                continue;
+            }
+            value.push(c);
+            self.pos += c.len_utf8();
+        }
+
+        self.tokens.push(Token {
+            kind: TokenKind::String { value, kind },
+            start,
+            end: self.pos,
+            line: start_line,
+            column: start_column,
+        });
+    }
+
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        self.tokens.push(Token {
+            kind,
+            start,
+            end: self.pos,
+            line: self.line,
+            column: start - self.line_start,
+        });
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(LexError {
+            line: self.line,
+            message: message.into(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source).0.into_iter().map(|t| t.kind).collect()
+    }
+
+    #[test]
+    fn test_indentation_and_comments() {
+        let kinds = kinds("func f():\n\t# note\n\n\tpass # trailing\n");
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Newline(0),
+                TokenKind::Keyword("func"),
+                TokenKind::Identifier("f".into()),
+                TokenKind::Punct("("),
+                TokenKind::Punct(")"),
+                TokenKind::Punct(":"),
+                TokenKind::Newline(4),
+                TokenKind::Keyword("pass"),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strings_and_numbers() {
+        let (tokens, errors) =
+            tokenize("x = &\"name\" + ^\"A/B\" + \"\"\"a\nb\"\"\" + 1_000.5e2 + 0xFF");
+        assert!(errors.is_empty());
+        let strings: Vec<_> = tokens
+            .iter()
+            .filter_map(|t| match &t.kind {
+                TokenKind::String { value, kind } => Some((value.as_str(), *kind)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            strings,
+            vec![
+                ("name", StringKind::StringName),
+                ("A/B", StringKind::NodePath),
+                ("a\nb", StringKind::Plain),
+            ]
+        );
+        assert!(tokens
+            .iter()
+            .any(|t| t.kind == TokenKind::Number("1_000.5e2".into())));
+        assert!(tokens
+            .iter()
+            .any(|t| t.kind == TokenKind::Number("0xFF".into())));
+    }
+
+    #[test]
+    fn test_line_continuation_and_positions() {
+        let (tokens, _) = tokenize("var a = 1 + \\\n\t2\nvar b");
+        let newlines = tokens
+            .iter()
+            .filter(|t| matches!(t.kind, TokenKind::Newline(_)))
+            .count();
+        assert_eq!(newlines, 2);
+
+        let b = tokens.iter().find(|t| t.identifier() == Some("b")).unwrap();
+        assert_eq!((b.line, b.column), (3, 4));
+    }
+}
@@ -0,0 +1,533 @@
+//! GDScript Parser and Generator
+//!
+//! Handles parsing of GDScript files and generating script templates.
+//! Parsing goes through a full lexer and parser ([`ast`]); the flat fields
+//! of [`GDScript`] summarize the script's top-level class.
+
+pub mod ast;
+pub mod lexer;
+mod parser;
+
+use ast::{ClassDecl, Parameter, SyntaxError};
+
+/// GDScript file structure
+#[derive(Debug, Clone)]
+pub struct GDScript {
+    /// extends declaration
+    pub extends: Option<String>,
+    /// class_name declaration
+    pub class_name: Option<String>,
+    /// exported variables
+    pub exports: Vec<ExportVar>,
+    /// regular variables
+    pub variables: Vec<Variable>,
+    /// functions
+    pub functions: Vec<Function>,
+    /// signals
+    pub signals: Vec<String>,
+    /// Full syntax tree (empty for scripts built with [`GDScript::new`])
+    pub ast: ClassDecl,
+    /// Syntax errors found while parsing
+    pub errors: Vec<SyntaxError>,
+}
+
+/// Exported variable
+#[derive(Debug, Clone)]
+pub struct ExportVar {
+    pub name: String,
+    pub var_type: Option<String>,
+    pub default_value: Option<String>,
+}
+
+/// Variable
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub var_type: Option<String>,
+    pub default_value: Option<String>,
+}
+
+/// Function
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<FunctionParam>,
+    pub return_type: Option<String>,
+    pub body: String,
+}
+
+/// Function parameter
+#[derive(Debug, Clone)]
+pub struct FunctionParam {
+    pub name: String,
+    pub param_type: Option<String>,
+    pub default_value: Option<String>,
+}
+
+impl GDScript {
+    /// Create a new script
+    pub fn new(extends: &str) -> Self {
+        Self {
+            extends: Some(extends.to_string()),
+            class_name: None,
+            exports: Vec::new(),
+            variables: Vec::new(),
+            functions: Vec::new(),
+            signals: Vec::new(),
+            ast: ClassDecl::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Parse GDScript content
+    pub fn parse(content: &str) -> Self {
+        let (ast, errors) = parser::parse(content);
+
+        let signals = ast
+            .signals()
+            .map(|s| {
+                if s.parameters.is_empty() {
+                    s.name.clone()
+                } else {
+                    let params: Vec<String> = s.parameters.iter().map(render_parameter).collect();
+                    format!("{}({})", s.name, params.join(", "))
+                }
+            })
+            .collect();
+
+        let mut exports = Vec::new();
+        let mut variables = Vec::new();
+        for var in ast.variables() {
+            if var.is_exported() {
+                exports.push(ExportVar {
+                    name: var.name.clone(),
+                    var_type: var.type_hint.clone(),
+                    default_value: var.value_source.clone(),
+                });
+            } else {
+                variables.push(Variable {
+                    name: var.name.clone(),
+                    var_type: var.type_hint.clone(),
+                    default_value: var.value_source.clone(),
+                });
+            }
+        }
+
+        let functions = ast
+            .functions()
+            .map(|f| Function {
+                name: f.name.clone(),
+                params: f
+                    .parameters
+                    .iter()
+                    .map(|p| FunctionParam {
+                        name: p.name.clone(),
+                        param_type: p.type_hint.clone(),
+                        default_value: p.default_source.clone(),
+                    })
+                    .collect(),
+                return_type: f.return_type.clone(),
+                body: f.body_source.trim_end().to_string(),
+            })
+            .collect();
+
+        GDScript {
+            extends: ast.extends.clone(),
+            class_name: ast.name.clone(),
+            exports,
+            variables,
+            functions,
+            signals,
+            ast,
+            errors,
+        }
+    }
+
+    /// Generate GDScript code
+    pub fn to_gdscript(&self) -> String {
+        let mut output = String::new();
+
+        // extends
+        if let Some(ref ext) = self.extends {
+            output.push_str(&format!("extends {}\n", ext));
+        }
+
+        // class_name
+        if let Some(ref name) = self.class_name {
+            output.push_str(&format!("class_name {}\n", name));
+        }
+
+        if self.extends.is_some() || self.class_name.is_some() {
+            output.push('\n');
+        }
+
+        // signals
+        for signal in &self.signals {
+            output.push_str(&format!("signal {}\n", signal));
+        }
+        if !self.signals.is_empty() {
+            output.push('\n');
+        }
+
+        // exports
+        for var in &self.exports {
+            output.push_str(&var.to_gdscript());
+        }
+        if !self.exports.is_empty() {
+            output.push('\n');
+        }
+
+        // variables
+        for var in &self.variables {
+            output.push_str(&var.to_gdscript());
+        }
+        if !self.variables.is_empty() {
+            output.push('\n');
+        }
+
+        // functions
+        for func in &self.functions {
+            output.push_str(&func.to_gdscript());
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Add a function
+    pub fn add_function(&mut self, func: Function) {
+        self.functions.push(func);
+    }
+
+    /// Add a variable
+    pub fn add_variable(&mut self, var: Variable) {
+        self.variables.push(var);
+    }
+
+    /// Add an exported variable
+    pub fn add_export(&mut self, var: ExportVar) {
+        self.exports.push(var);
+    }
+}
+
+impl ExportVar {
+    /// `@export var name: Type = value` line
+    pub fn to_gdscript(&self) -> String {
+        format!(
+            "@export var {}{}\n",
+            self.name,
+            type_and_default(&self.var_type, &self.default_value)
+        )
+    }
+}
+
+impl Variable {
+    /// `var name: Type = value` line
+    pub fn to_gdscript(&self) -> String {
+        format!(
+            "var {}{}\n",
+            self.name,
+            type_and_default(&self.var_type, &self.default_value)
+        )
+    }
+}
+
+impl Function {
+    /// `func` declaration with its tab-indented body
+    pub fn to_gdscript(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|p| {
+                format!(
+                    "{}{}",
+                    p.name,
+                    type_and_default(&p.param_type, &p.default_value)
+                )
+            })
+            .collect();
+
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map(|t| format!(" -> {}", t))
+            .unwrap_or_default();
+
+        let mut output = format!(
+            "func {}({}){}:\n",
+            self.name,
+            params.join(", "),
+            return_type
+        );
+        for line in self.body.lines() {
+            output.push_str(&format!("\t{}\n", line));
+        }
+        output
+    }
+}
+
+/// Append a function to script source, keeping the existing text intact
+pub fn append_function(content: &str, func: &Function) -> String {
+    let mut output = content.trim_end().to_string();
+    if !output.is_empty() {
+        output.push_str("\n\n");
+    }
+    output.push_str(&func.to_gdscript());
+    output
+}
+
+/// Insert an exported variable into script source after the last top-level
+/// export, else before the first variable or function, else at the end
+pub fn insert_export(content: &str, var: &ExportVar) -> String {
+    let (ast, _) = parser::parse(content);
+    let last_export = ast
+        .variables()
+        .filter(|v| v.is_exported())
+        .map(|v| v.end_line)
+        .max();
+    let first_member = || {
+        ast.members
+            .iter()
+            .find(|m| matches!(m, ast::Member::Variable(_) | ast::Member::Function(_)))
+            .map(|m| {
+                // Keep annotations such as `@onready` with their member
+                let annotated = match m {
+                    ast::Member::Variable(v) => v.annotations.first().map(|a| a.line),
+                    ast::Member::Function(f) => f.annotations.first().map(|a| a.line),
+                    _ => None,
+                };
+                annotated.unwrap_or(m.line()) - 1
+            })
+    };
+
+    let mut lines: Vec<&str> = content.lines().collect();
+    let text = var.to_gdscript();
+    let new_line = text.trim_end();
+    match last_export.or_else(first_member) {
+        Some(index) => lines.insert(index.min(lines.len()), new_line),
+        None => {
+            if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                lines.push("");
+            }
+            lines.push(new_line);
+        }
+    }
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+fn type_and_default(var_type: &Option<String>, default_value: &Option<String>) -> String {
+    let type_hint = var_type
+        .as_ref()
+        .map(|t| format!(": {}", t))
+        .unwrap_or_default();
+    let default = default_value
+        .as_ref()
+        .map(|v| format!(" = {}", v))
+        .unwrap_or_default();
+    format!("{}{}", type_hint, default)
+}
+
+/// Parameter as written in a signature: `name`, `name: Type`, `name := value`
+pub fn render_parameter(parameter: &Parameter) -> String {
+    let mut text = parameter.name.clone();
+    if let Some(ref type_hint) = parameter.type_hint {
+        text.push_str(&format!(": {}", type_hint));
+    }
+    match (&parameter.default_source, parameter.inferred) {
+        (Some(default), true) => text.push_str(&format!(" := {}", default)),
+        (Some(default), false) => text.push_str(&format!(" = {}", default)),
+        (None, _) => {}
+    }
+    text
+}
+
+/// Generate script from template
+pub fn generate_template(extends: &str) -> String {
+    match extends {
+        "CharacterBody3D" => TEMPLATE_CHARACTER_BODY_3D.to_string(),
+        "CharacterBody2D" => TEMPLATE_CHARACTER_BODY_2D.to_string(),
+        "Node3D" => TEMPLATE_NODE_3D.to_string(),
+        "Node2D" => TEMPLATE_NODE_2D.to_string(),
+        "RigidBody3D" => TEMPLATE_RIGID_BODY_3D.to_string(),
+        "Area3D" => TEMPLATE_AREA_3D.to_string(),
+        _ => format!("extends {}\n\nfunc _ready() -> void:\n\tpass\n", extends),
+    }
+}
+
+const TEMPLATE_CHARACTER_BODY_3D: &str = r#"extends CharacterBody3D
+
+@export var speed: float = 5.0
+@export var jump_velocity: float = 4.5
+
+var gravity: float = ProjectSettings.get_setting("physics/3d/default_gravity")
+
+func _physics_process(delta: float) -> void:
+	if not is_on_floor():
+		velocity.y -= gravity * delta
+
+	if Input.is_action_just_pressed("jump") and is_on_floor():
+		velocity.y = jump_velocity
+
+	var input_dir := Input.get_vector("left", "right", "forward", "back")
+	var direction := (transform.basis * Vector3(input_dir.x, 0, input_dir.y)).normalized()
+	
+	if direction:
+		velocity.x = direction.x * speed
+		velocity.z = direction.z * speed
+	else:
+		velocity.x = move_toward(velocity.x, 0, speed)
+		velocity.z = move_toward(velocity.z, 0, speed)
+
+	move_and_slide()
+"#;
+
+const TEMPLATE_CHARACTER_BODY_2D: &str = r#"extends CharacterBody2D
+
+@export var speed: float = 300.0
+@export var jump_velocity: float = -400.0
+
+var gravity: float = ProjectSettings.get_setting("physics/2d/default_gravity")
+
+func _physics_process(delta: float) -> void:
+	if not is_on_floor():
+		velocity.y += gravity * delta
+
+	if Input.is_action_just_pressed("jump") and is_on_floor():
+		velocity.y = jump_velocity
+
+	var direction := Input.get_axis("left", "right")
+	if direction:
+		velocity.x = direction * speed
+	else:
+		velocity.x = move_toward(velocity.x, 0, speed)
+
+	move_and_slide()
+"#;
+
+const TEMPLATE_NODE_3D: &str = r#"extends Node3D
+
+func _ready() -> void:
+	pass
+
+func _process(delta: float) -> void:
+	pass
+"#;
+
+const TEMPLATE_NODE_2D: &str = r#"extends Node2D
+
+func _ready() -> void:
+	pass
+
+func _process(delta: float) -> void:
+	pass
+"#;
+
+const TEMPLATE_RIGID_BODY_3D: &str = r#"extends RigidBody3D
+
+func _ready() -> void:
+	pass
+
+func _physics_process(delta: float) -> void:
+	pass
+"#;
+
+const TEMPLATE_AREA_3D: &str = r#"extends Area3D
+
+func _ready() -> void:
+	body_entered.connect(_on_body_entered)
+	body_exited.connect(_on_body_exited)
+
+func _on_body_entered(body: Node3D) -> void:
+	pass
+
+func _on_body_exited(body: Node3D) -> void:
+	pass
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_script() {
+        let content = r#"extends Node3D
+
+var health: int = 100
+
+func _ready() -> void:
+	pass
+"#;
+        let script = GDScript::parse(content);
+        assert_eq!(script.extends, Some("Node3D".to_string()));
+        assert_eq!(script.variables.len(), 1);
+        assert_eq!(script.functions.len(), 1);
+    }
+
+    #[test]
+    fn test_generate_script() {
+        let mut script = GDScript::new("Node3D");
+        script.add_variable(Variable {
+            name: "speed".to_string(),
+            var_type: Some("float".to_string()),
+            default_value: Some("5.0".to_string()),
+        });
+
+        let output = script.to_gdscript();
+        assert!(output.contains("extends Node3D"));
+        assert!(output.contains("var speed: float = 5.0"));
+    }
+
+    #[test]
+    fn test_templates_parse_cleanly() {
+        for extends in ["CharacterBody3D", "CharacterBody2D", "Area3D", "Control"] {
+            let script = GDScript::parse(&generate_template(extends));
+            assert!(script.errors.is_empty(), "{}: {:?}", extends, script.errors);
+            assert_eq!(script.extends.as_deref(), Some(extends));
+        }
+
+        let script = GDScript::parse(TEMPLATE_CHARACTER_BODY_2D);
+        assert_eq!(script.exports.len(), 2);
+        assert_eq!(script.variables.len(), 1);
+        let body = &script.functions[0].body;
+        assert!(body.starts_with("if not is_on_floor():\n\tvelocity.y += gravity * delta"));
+        assert!(body.ends_with("move_and_slide()"));
+    }
+
+    #[test]
+    fn test_insert_export_keeps_surrounding_code() {
+        let var = ExportVar {
+            name: "armor".to_string(),
+            var_type: Some("int".to_string()),
+            default_value: None,
+        };
+
+        let content = "extends Node\n\n# Stats\n@export var health: int = 100\n@onready var label = $Label\n\nfunc _ready():\n\tpass # keep me\n";
+        let output = insert_export(content, &var);
+        assert_eq!(
+            output,
+            "extends Node\n\n# Stats\n@export var health: int = 100\n@export var armor: int\n@onready var label = $Label\n\nfunc _ready():\n\tpass # keep me\n"
+        );
+
+        let output = insert_export("extends Node\n\n@onready\nvar label = $Label\n", &var);
+        assert_eq!(
+            output,
+            "extends Node\n\n@export var armor: int\n@onready\nvar label = $Label\n"
+        );
+
+        let output = insert_export("extends Node\n", &var);
+        assert_eq!(output, "extends Node\n\n@export var armor: int\n");
+    }
+
+    #[test]
+    fn test_parse_signals_and_inline_function() {
+        let script = GDScript::parse(
+            "signal hit(damage: int, source := null)\nfunc ping() -> int: return 1\n",
+        );
+        assert_eq!(script.signals, vec!["hit(damage: int, source := null)"]);
+        assert_eq!(script.functions[0].body, "return 1");
+        assert_eq!(script.functions[0].return_type.as_deref(), Some("int"));
+    }
+}
@@ -0,0 +1,1777 @@
+//! GDScript parser
+//!
+//! Recursive descent over the tokens from [`super::lexer`], producing the
+//! tree in [`super::ast`]. Blocks are delimited by the indentation carried on
+//! `Newline` tokens; newlines inside brackets are skipped, except in the body
+//! of a multi-line lambda. Errors are collected rather than fatal: a broken
+//! member or statement is skipped up to the next line at its indentation, so
+//! the rest of the script is still parsed.
+//!
+//! Covers GDScript 2 (Godot 4) plus the Godot 3 member keywords (`export`,
+//! `onready`, `tool`, `setget`, `remote`, ...).
+
+use super::ast::*;
+use super::lexer::{tokenize, StringKind, Token, TokenKind};
+
+type PResult<T> = Result<T, SyntaxError>;
+
+/// Annotations that apply to the script rather than the next member
+const CLASS_ANNOTATIONS: &[&str] = &["tool", "icon", "static_unload", "abstract"];
+
+/// Godot 3 keywords that prefix members, recorded as annotations
+const LEGACY_MEMBER_KEYWORDS: &[&str] = &[
+    "export",
+    "onready",
+    "tool",
+    "remote",
+    "master",
+    "puppet",
+    "remotesync",
+    "mastersync",
+    "puppetsync",
+    "sync",
+    "slave",
+];
+
+const ASSIGNMENT_OPERATORS: &[&str] = &[
+    "=", "+=", "-=", "*=", "/=", "%=", "**=", "&=", "|=", "^=", "<<=", ">>=",
+];
+
+/// Parse a script into its class tree and the syntax errors found
+pub fn parse(source: &str) -> (ClassDecl, Vec<SyntaxError>) {
+    let (tokens, lex_errors) = tokenize(source);
+    let mut parser = Parser {
+        source,
+        tokens,
+        pos: 0,
+        depth: 0,
+        indent: 0,
+        lambda_depth: 0,
+        prev_end: 0,
+        prev_line: 1,
+        errors: lex_errors
+            .into_iter()
+            .map(|e| SyntaxError {
+                line: e.line,
+                message: e.message,
+            })
+            .collect(),
+    };
+
+    let mut class = ClassDecl {
+        line: 1,
+        ..ClassDecl::default()
+    };
+    let indent = match parser.raw() {
+        TokenKind::Newline(indent) => *indent,
+        _ => 0,
+    };
+    parser.class_body(&mut class, indent);
+    class.end_line = parser.prev_line;
+
+    let mut errors = parser.errors;
+    errors.sort_by_key(|e| e.line);
+    (class, errors)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Open brackets; newlines inside them do not end statements
+    depth: usize,
+    /// Indentation of the block being parsed
+    indent: usize,
+    /// Lambda bodies being parsed inside brackets
+    lambda_depth: usize,
+    /// End offset and last line of the previous token
+    prev_end: usize,
+    prev_line: usize,
+    errors: Vec<SyntaxError>,
+}
+
+impl Parser<'_> {
+    // ======================
+    // Token access
+    // ======================
+
+    /// Current token kind, without skipping bracketed newlines
+    fn raw(&self) -> &TokenKind {
+        &self.tokens[self.pos].kind
+    }
+
+    fn peek(&mut self) -> &Token {
+        if self.depth > 0 {
+            while matches!(self.tokens[self.pos].kind, TokenKind::Newline(_)) {
+                self.pos += 1;
+            }
+        }
+        &self.tokens[self.pos]
+    }
+
+    /// Kind of the `n`-th token after the current one
+    fn peek_ahead(&mut self, n: usize) -> &TokenKind {
+        self.peek();
+        let mut pos = self.pos;
+        let mut remaining = n;
+        while remaining > 0 && pos + 1 < self.tokens.len() {
+            pos += 1;
+            if self.depth == 0 || !matches!(self.tokens[pos].kind, TokenKind::Newline(_)) {
+                remaining -= 1;
+            }
+        }
+        &self.tokens[pos].kind
+    }
+
+    fn bump(&mut self) -> Token {
+        self.peek();
+        let token = self.tokens[self.pos].clone();
+        if token.kind != TokenKind::Eof {
+            self.pos += 1;
+        }
+        match token.kind {
+            TokenKind::Punct("(" | "[" | "{") => self.depth += 1,
+            TokenKind::Punct(")" | "]" | "}") => self.depth = self.depth.saturating_sub(1),
+            TokenKind::Newline(_) => return token,
+            _ => {}
+        }
+        self.prev_end = token.end;
+        self.prev_line = token.line + self.source[token.start..token.end].matches('\n').count();
+        token
+    }
+
+    fn check_punct(&mut self, punct: &str) -> bool {
+        matches!(self.peek().kind, TokenKind::Punct(p) if p == punct)
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let found = self.check_punct(punct);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> PResult<()> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", punct)))
+        }
+    }
+
+    fn check_keyword(&mut self, keyword: &str) -> bool {
+        matches!(self.peek().kind, TokenKind::Keyword(k) if k == keyword)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.check_keyword(keyword);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> PResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{}'", keyword)))
+        }
+    }
+
+    fn check_identifier(&mut self, name: &str) -> bool {
+        self.peek().identifier() == Some(name)
+    }
+
+    fn expect_identifier(&mut self) -> PResult<String> {
+        match &self.peek().kind {
+            TokenKind::Identifier(name) => {
+                let name = name.clone();
+                self.bump();
+                Ok(name)
+            }
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    /// The current line (or bracketed expression) has ended
+    fn at_statement_end(&mut self) -> bool {
+        if self.depth == 0 && matches!(self.raw(), TokenKind::Newline(_) | TokenKind::Eof) {
+            return true;
+        }
+        match self.peek().kind {
+            TokenKind::Punct(";") | TokenKind::Eof => true,
+            TokenKind::Punct(")" | "]" | "}" | ",") => self.lambda_depth > 0,
+            _ => false,
+        }
+    }
+
+    fn unexpected(&mut self, expected: &str) -> SyntaxError {
+        let token = self.peek().clone();
+        let found = match &token.kind {
+            TokenKind::Newline(_) => "end of line".to_string(),
+            TokenKind::Eof => "end of file".to_string(),
+            _ => format!("'{}'", &self.source[token.start..token.end]),
+        };
+        SyntaxError {
+            line: token.line,
+            message: format!("Expected {}, found {}", expected, found),
+        }
+    }
+
+    /// Skip to the next line indented at most `indent`
+    fn recover(&mut self, indent: usize) {
+        self.depth = 0;
+        loop {
+            match self.raw() {
+                TokenKind::Eof => break,
+                TokenKind::Newline(n) if *n <= indent => break,
+                _ => {
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Source text from `start` to the end of the previous token
+    fn source_from(&self, start: usize) -> String {
+        self.source[start..self.prev_end.max(start)].to_string()
+    }
+
+    // ======================
+    // Members
+    // ======================
+
+    fn class_body(&mut self, class: &mut ClassDecl, indent: usize) {
+        let saved = self.indent;
+        self.indent = indent;
+        let mut pending = Vec::new();
+
+        loop {
+            match *self.raw() {
+                TokenKind::Newline(n) if n == indent => {
+                    self.pos += 1;
+                }
+                TokenKind::Newline(n) if n > indent => {
+                    let line = self.tokens[self.pos].line;
+                    self.errors.push(SyntaxError {
+                        line,
+                        message: "Unexpected indentation".to_string(),
+                    });
+                    self.pos += 1;
+                    self.recover(indent);
+                    continue;
+                }
+                _ => break,
+            }
+
+            if let Err(e) = self.member_line(class, &mut pending) {
+                self.errors.push(e);
+                self.recover(indent);
+                continue;
+            }
+            if !matches!(self.raw(), TokenKind::Newline(_) | TokenKind::Eof) {
+                let e = self.unexpected("end of line");
+                self.errors.push(e);
+                self.recover(indent);
+            }
+        }
+
+        // Annotations with nothing after them
+        class.annotations.append(&mut pending);
+        self.indent = saved;
+    }
+
+    /// One line of a class body: members separated by `;`
+    fn member_line(&mut self, class: &mut ClassDecl, pending: &mut Vec<Annotation>) -> PResult<()> {
+        loop {
+            self.member(class, pending)?;
+            if !self.eat_punct(";") || self.at_statement_end() {
+                return Ok(());
+            }
+        }
+    }
+
+    fn member(&mut self, class: &mut ClassDecl, pending: &mut Vec<Annotation>) -> PResult<()> {
+        let token = self.peek().clone();
+        match &token.kind {
+            TokenKind::Annotation(name) => {
+                self.bump();
+                let arguments = if self.check_punct("(") {
+                    self.bump();
+                    self.arguments()?
+                } else {
+                    Vec::new()
+                };
+                let annotation = Annotation {
+                    name: name.clone(),
+                    arguments,
+                    line: token.line,
+                };
+                if CLASS_ANNOTATIONS.contains(&name.as_str()) {
+                    class.annotations.push(annotation);
+                } else {
+                    pending.push(annotation);
+                }
+                // `@export var x` on one line, or the member on the next line
+                if !self.at_statement_end() {
+                    self.member(class, pending)?;
+                }
+            }
+            TokenKind::Identifier(name) if LEGACY_MEMBER_KEYWORDS.contains(&name.as_str()) => {
+                self.bump();
+                let arguments = if name == "export" && self.check_punct("(") {
+                    self.bump();
+                    self.arguments()?
+                } else {
+                    Vec::new()
+                };
+                let annotation = Annotation {
+                    name: name.clone(),
+                    arguments,
+                    line: token.line,
+                };
+                if name == "tool" {
+                    class.annotations.push(annotation);
+                } else {
+                    pending.push(annotation);
+                }
+                if !self.at_statement_end() {
+                    self.member(class, pending)?;
+                }
+            }
+            TokenKind::Keyword("extends") => {
+                self.bump();
+                class.extends = Some(self.extends_target()?);
+            }
+            TokenKind::Keyword("class_name") => {
+                self.bump();
+                class.name = Some(self.expect_identifier()?);
+                // Godot 3 icon path
+                if self.eat_punct(",") {
+                    self.expression()?;
+                }
+                if self.eat_keyword("extends") {
+                    class.extends = Some(self.extends_target()?);
+                }
+            }
+            TokenKind::Keyword("signal") => {
+                self.bump();
+                let name = self.expect_identifier()?;
+                let parameters = if self.check_punct("(") {
+                    self.parameters()?
+                } else {
+                    Vec::new()
+                };
+                pending.clear();
+                class.members.push(Member::Signal(SignalDecl {
+                    name,
+                    parameters,
+                    line: token.line,
+                    end_line: self.prev_line,
+                }));
+            }
+            TokenKind::Keyword("const") => {
+                self.bump();
+                pending.clear();
+                let constant = self.constant(token.line)?;
+                class.members.push(Member::Constant(constant));
+            }
+            TokenKind::Keyword("enum") => {
+                self.bump();
+                pending.clear();
+                let decl = self.enumeration(token.line)?;
+                class.members.push(Member::Enum(decl));
+            }
+            TokenKind::Keyword("var") => {
+                self.bump();
+                let annotations = std::mem::take(pending);
+                let variable = self.variable(annotations, false, token.line)?;
+                class.members.push(Member::Variable(variable));
+            }
+            TokenKind::Keyword("func") => {
+                self.bump();
+                let annotations = std::mem::take(pending);
+                let function = self.function(annotations, false, token.line)?;
+                class.members.push(Member::Function(function));
+            }
+            TokenKind::Keyword("static") => {
+                self.bump();
+                let annotations = std::mem::take(pending);
+                if self.eat_keyword("func") {
+                    let function = self.function(annotations, true, token.line)?;
+                    class.members.push(Member::Function(function));
+                } else if self.eat_keyword("var") {
+                    let variable = self.variable(annotations, true, token.line)?;
+                    class.members.push(Member::Variable(variable));
+                } else {
+                    return Err(self.unexpected("'func' or 'var' after 'static'"));
+                }
+            }
+            TokenKind::Keyword("class") => {
+                self.bump();
+                pending.clear();
+                let inner = self.inner_class(token.line)?;
+                class.members.push(Member::Class(inner));
+            }
+            TokenKind::Keyword("pass") => {
+                self.bump();
+            }
+            _ => return Err(self.unexpected("a class member")),
+        }
+        Ok(())
+    }
+
+    /// `Node`, `Base.Inner`, `"res://base.gd"` or `"res://base.gd".Inner`
+    fn extends_target(&mut self) -> PResult<String> {
+        let mut target = match &self.peek().kind {
+            TokenKind::String { value, .. } => {
+                let value = value.clone();
+                self.bump();
+                value
+            }
+            _ => self.expect_identifier()?,
+        };
+        while self.check_punct(".") {
+            self.bump();
+            target.push('.');
+            target.push_str(&self.expect_identifier()?);
+        }
+        Ok(target)
+    }
+
+    fn inner_class(&mut self, line: usize) -> PResult<ClassDecl> {
+        let mut class = ClassDecl {
+            name: Some(self.expect_identifier()?),
+            line,
+            ..ClassDecl::default()
+        };
+        if self.eat_keyword("extends") {
+            class.extends = Some(self.extends_target()?);
+        }
+        self.expect_punct(":")?;
+
+        match *self.raw() {
+            TokenKind::Newline(n) if n > self.indent => self.class_body(&mut class, n),
+            TokenKind::Newline(_) | TokenKind::Eof => {
+                return Err(self.unexpected("an indented class body"));
+            }
+            _ => {
+                let mut pending = Vec::new();
+                self.member_line(&mut class, &mut pending)?;
+            }
+        }
+        class.end_line = self.prev_line;
+        Ok(class)
+    }
+
+    /// `var` declaration after the keyword (members and locals)
+    fn variable(
+        &mut self,
+        annotations: Vec<Annotation>,
+        is_static: bool,
+        line: usize,
+    ) -> PResult<VariableDecl> {
+        let mut decl = VariableDecl {
+            name: self.expect_identifier()?,
+            type_hint: None,
+            inferred: false,
+            value: None,
+            value_source: None,
+            annotations,
+            is_static,
+            setter: None,
+            getter: None,
+            line,
+            end_line: line,
+        };
+
+        let mut accessors_parsed = false;
+        if self.eat_punct(":") {
+            if self.check_punct("=") {
+                decl.inferred = true;
+            } else if self.depth == 0 && matches!(self.raw(), TokenKind::Newline(_)) {
+                self.accessors(&mut decl)?;
+                accessors_parsed = true;
+            } else if self.at_inline_accessor() {
+                self.inline_accessors(&mut decl)?;
+                accessors_parsed = true;
+            } else {
+                decl.type_hint = Some(self.type_hint()?);
+            }
+        }
+
+        if !accessors_parsed {
+            if self.eat_punct("=") {
+                let start = self.peek().start;
+                decl.value = Some(self.expression()?);
+                decl.value_source = Some(self.source_from(start));
+            }
+
+            if self.check_identifier("setget") {
+                // Godot 3: `setget setter, getter` / `setget , getter`
+                self.bump();
+                if let TokenKind::Identifier(name) = &self.peek().kind {
+                    decl.setter = Some(Accessor::Function(name.clone()));
+                    self.bump();
+                }
+                if self.eat_punct(",") {
+                    decl.getter = Some(Accessor::Function(self.expect_identifier()?));
+                }
+            } else if self.eat_punct(":") {
+                if self.depth == 0 && matches!(self.raw(), TokenKind::Newline(_)) {
+                    self.accessors(&mut decl)?;
+                } else {
+                    self.inline_accessors(&mut decl)?;
+                }
+            }
+        }
+
+        decl.end_line = self.prev_line;
+        Ok(decl)
+    }
+
+    fn at_inline_accessor(&mut self) -> bool {
+        (self.check_identifier("set") || self.check_identifier("get"))
+            && matches!(self.peek_ahead(1), TokenKind::Punct("=" | "(" | ":"))
+    }
+
+    /// Indented `set(value): ...` / `get: ...` block after `var x:`
+    fn accessors(&mut self, decl: &mut VariableDecl) -> PResult<()> {
+        let TokenKind::Newline(indent) = *self.raw() else {
+            return Err(self.unexpected("an indented setter/getter block"));
+        };
+        if indent <= self.indent {
+            return Err(self.unexpected("an indented setter/getter block"));
+        }
+
+        let saved = self.indent;
+        self.indent = indent;
+        while matches!(*self.raw(), TokenKind::Newline(n) if n == indent) {
+            self.pos += 1;
+            if let Err(e) = self.accessor(decl) {
+                self.errors.push(e);
+                self.recover(indent);
+            }
+        }
+        self.indent = saved;
+        Ok(())
+    }
+
+    /// `set = f, get = g` on the declaration line
+    fn inline_accessors(&mut self, decl: &mut VariableDecl) -> PResult<()> {
+        self.accessor(decl)?;
+        while self.eat_punct(",") {
+            self.accessor(decl)?;
+        }
+        Ok(())
+    }
+
+    fn accessor(&mut self, decl: &mut VariableDecl) -> PResult<()> {
+        let kind = self.expect_identifier()?;
+        if kind != "set" && kind != "get" {
+            return Err(SyntaxError {
+                line: self.prev_line,
+                message: format!("Expected 'set' or 'get', found '{}'", kind),
+            });
+        }
+
+        let accessor = if self.eat_punct("=") {
+            Accessor::Function(self.expect_identifier()?)
+        } else if kind == "set" && self.eat_punct("(") {
+            let parameter = self.expect_identifier()?;
+            if self.eat_punct(":") {
+                self.type_hint()?;
+            }
+            self.expect_punct(")")?;
+            self.expect_punct(":")?;
+            Accessor::Inline {
+                parameter: Some(parameter),
+                body: self.block()?,
+            }
+        } else {
+            self.expect_punct(":")?;
+            Accessor::Inline {
+                parameter: None,
+                body: self.block()?,
+            }
+        };
+
+        if kind == "set" {
+            decl.setter = Some(accessor);
+        } else {
+            decl.getter = Some(accessor);
+        }
+        Ok(())
+    }
+
+    /// `const` declaration after the keyword
+    fn constant(&mut self, line: usize) -> PResult<ConstantDecl> {
+        let name = self.expect_identifier()?;
+        let mut type_hint = None;
+        if self.eat_punct(":") && !self.check_punct("=") {
+            type_hint = Some(self.type_hint()?);
+        }
+        self.expect_punct("=")?;
+        let start = self.peek().start;
+        let value = self.expression()?;
+        Ok(ConstantDecl {
+            name,
+            type_hint,
+            value: Some(value),
+            value_source: Some(self.source_from(start)),
+            line,
+            end_line: self.prev_line,
+        })
+    }
+
+    /// `enum` declaration after the keyword
+    fn enumeration(&mut self, line: usize) -> PResult<EnumDecl> {
+        let name = match &self.peek().kind {
+            TokenKind::Identifier(name) => {
+                let name = name.clone();
+                self.bump();
+                Some(name)
+            }
+            _ => None,
+        };
+        self.expect_punct("{")?;
+
+        let mut values = Vec::new();
+        while !self.check_punct("}") {
+            let value_line = self.peek().line;
+            let value_name = self.expect_identifier()?;
+            let mut value_source = None;
+            if self.eat_punct("=") {
+                let start = self.peek().start;
+                self.expression()?;
+                value_source = Some(self.source_from(start));
+            }
+            values.push(EnumValue {
+                name: value_name,
+                value_source,
+                line: value_line,
+            });
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        self.expect_punct("}")?;
+
+        Ok(EnumDecl {
+            name,
+            values,
+            line,
+            end_line: self.prev_line,
+        })
+    }
+
+    /// Function (or lambda) after the `func` keyword
+    fn function(
+        &mut self,
+        annotations: Vec<Annotation>,
+        is_static: bool,
+        line: usize,
+    ) -> PResult<FunctionDecl> {
+        let name = match &self.peek().kind {
+            TokenKind::Identifier(name) => {
+                let name = name.clone();
+                self.bump();
+                name
+            }
+            _ => String::new(),
+        };
+        if !self.check_punct("(") {
+            return Err(self.unexpected("'('"));
+        }
+        let parameters = self.parameters()?;
+        let return_type = if self.eat_punct("->") {
+            Some(self.type_hint()?)
+        } else {
+            None
+        };
+        self.expect_punct(":")?;
+        let (body, body_source) = self.function_body()?;
+
+        Ok(FunctionDecl {
+            name,
+            parameters,
+            return_type,
+            body,
+            body_source,
+            annotations,
+            is_static,
+            line,
+            end_line: self.prev_line,
+        })
+    }
+
+    /// Function body with its source text
+    fn function_body(&mut self) -> PResult<(Vec<Statement>, String)> {
+        let TokenKind::Newline(_) = self.raw() else {
+            let start = self.peek().start;
+            let body = self.block()?;
+            return Ok((body, self.source_from(start)));
+        };
+
+        let first_line = self.tokens[self.pos].line;
+        let body = self.block()?;
+        let lines: Vec<&str> = self
+            .source
+            .lines()
+            .skip(first_line - 1)
+            .take(self.prev_line + 1 - first_line)
+            .collect();
+        let prefix_len = lines
+            .first()
+            .map(|l| l.len() - l.trim_start().len())
+            .unwrap_or(0);
+        let source = lines
+            .iter()
+            .map(|l| {
+                let indent = l.len() - l.trim_start().len();
+                &l[indent.min(prefix_len)..]
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok((body, source))
+    }
+
+    /// `(a, b: int, c := 1, d: float = 2.0)`
+    fn parameters(&mut self) -> PResult<Vec<Parameter>> {
+        self.expect_punct("(")?;
+        let mut parameters = Vec::new();
+        while !self.check_punct(")") {
+            let mut parameter = Parameter {
+                name: self.expect_identifier()?,
+                type_hint: None,
+                inferred: false,
+                default: None,
+                default_source: None,
+            };
+            if self.eat_punct(":") {
+                if self.check_punct("=") {
+                    parameter.inferred = true;
+                } else {
+                    parameter.type_hint = Some(self.type_hint()?);
+                }
+            }
+            if self.eat_punct("=") {
+                let start = self.peek().start;
+                parameter.default = Some(self.expression()?);
+                parameter.default_source = Some(self.source_from(start));
+            }
+            parameters.push(parameter);
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        self.expect_punct(")")?;
+        Ok(parameters)
+    }
+
+    /// Type as written: `int`, `Node.Inner`, `Array[int]`, `Dictionary[String, int]`
+    fn type_hint(&mut self) -> PResult<String> {
+        let start = self.peek().start;
+        self.expect_identifier()?;
+        while self.eat_punct(".") {
+            self.expect_identifier()?;
+        }
+        if self.eat_punct("[") {
+            loop {
+                self.type_hint()?;
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct("]")?;
+        }
+        Ok(self.source_from(start))
+    }
+
+    // ======================
+    // Statements
+    // ======================
+
+    /// Body after `:` — statements on the same line, or an indented block
+    fn block(&mut self) -> PResult<Vec<Statement>> {
+        let indent = match *self.raw() {
+            TokenKind::Newline(n) if self.depth == 0 || self.lambda_depth > 0 => n,
+            TokenKind::Eof => return Err(self.unexpected("an indented block")),
+            _ => return self.statement_line(),
+        };
+        if indent <= self.indent {
+            return Err(SyntaxError {
+                line: self.tokens[self.pos].line,
+                message: "Expected an indented block".to_string(),
+            });
+        }
+
+        let saved = self.indent;
+        self.indent = indent;
+        let mut body = Vec::new();
+        loop {
+            match *self.raw() {
+                TokenKind::Newline(n) if n == indent => {
+                    self.pos += 1;
+                }
+                TokenKind::Newline(n) if n > indent => {
+                    let line = self.tokens[self.pos].line;
+                    self.errors.push(SyntaxError {
+                        line,
+                        message: "Unexpected indentation".to_string(),
+                    });
+                    self.pos += 1;
+                    self.recover(indent);
+                    continue;
+                }
+                _ => break,
+            }
+
+            match self.statement_line() {
+                Ok(mut statements) => body.append(&mut statements),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.recover(indent);
+                    continue;
+                }
+            }
+            match self.raw() {
+                TokenKind::Newline(_) | TokenKind::Eof => {}
+                // `)` closing the call a lambda was passed to
+                _ if self.lambda_depth > 0 => break,
+                _ => {
+                    let e = self.unexpected("end of statement");
+                    self.errors.push(e);
+                    self.recover(indent);
+                }
+            }
+        }
+        self.indent = saved;
+        Ok(body)
+    }
+
+    /// Statements separated by `;`, after any statement annotations
+    fn statement_line(&mut self) -> PResult<Vec<Statement>> {
+        let mut statements = Vec::new();
+        loop {
+            // `@warning_ignore(...)` and friends
+            while matches!(self.peek().kind, TokenKind::Annotation(_)) {
+                self.bump();
+                if self.check_punct("(") {
+                    self.bump();
+                    self.arguments()?;
+                }
+            }
+            if self.at_statement_end() {
+                return Ok(statements);
+            }
+            statements.push(self.statement()?);
+            if !self.eat_punct(";") || self.at_statement_end() {
+                return Ok(statements);
+            }
+        }
+    }
+
+    fn statement(&mut self) -> PResult<Statement> {
+        let token = self.peek().clone();
+        let line = token.line;
+        let kind = match token.kind {
+            TokenKind::Keyword("pass") => {
+                self.bump();
+                StatementKind::Pass
+            }
+            TokenKind::Keyword("break") => {
+                self.bump();
+                StatementKind::Break
+            }
+            TokenKind::Keyword("continue") => {
+                self.bump();
+                StatementKind::Continue
+            }
+            TokenKind::Keyword("breakpoint") => {
+                self.bump();
+                StatementKind::Breakpoint
+            }
+            TokenKind::Keyword("return") => {
+                self.bump();
+                if self.at_statement_end() {
+                    StatementKind::Return(None)
+                } else {
+                    StatementKind::Return(Some(self.expression()?))
+                }
+            }
+            TokenKind::Keyword("var") => {
+                self.bump();
+                StatementKind::Variable(self.variable(Vec::new(), false, line)?)
+            }
+            TokenKind::Keyword("const") => {
+                self.bump();
+                StatementKind::Constant(self.constant(line)?)
+            }
+            TokenKind::Keyword("if") => {
+                self.bump();
+                self.if_statement()?
+            }
+            TokenKind::Keyword("while") => {
+                self.bump();
+                let condition = self.expression()?;
+                self.expect_punct(":")?;
+                StatementKind::While {
+                    condition,
+                    body: self.block()?,
+                }
+            }
+            TokenKind::Keyword("for") => {
+                self.bump();
+                let variable = self.expect_identifier()?;
+                let type_hint = if self.eat_punct(":") {
+                    Some(self.type_hint()?)
+                } else {
+                    None
+                };
+                self.expect_keyword("in")?;
+                let iterable = self.expression()?;
+                self.expect_punct(":")?;
+                StatementKind::For {
+                    variable,
+                    type_hint,
+                    iterable,
+                    body: self.block()?,
+                }
+            }
+            TokenKind::Keyword("match") => {
+                self.bump();
+                let subject = self.expression()?;
+                self.expect_punct(":")?;
+                StatementKind::Match {
+                    subject,
+                    branches: self.match_branches()?,
+                }
+            }
+            _ => {
+                let target = self.expression()?;
+                let op = match self.peek().kind {
+                    TokenKind::Punct(p) if ASSIGNMENT_OPERATORS.contains(&p) => p,
+                    _ => {
+                        return Ok(Statement {
+                            line,
+                            kind: StatementKind::Expression(target),
+                        })
+                    }
+                };
+                self.bump();
+                StatementKind::Assign {
+                    target,
+                    op,
+                    value: self.expression()?,
+                }
+            }
+        };
+        Ok(Statement { line, kind })
+    }
+
+    /// `if` chain after the keyword, including `elif`/`else` lines
+    fn if_statement(&mut self) -> PResult<StatementKind> {
+        let condition = self.expression()?;
+        self.expect_punct(":")?;
+        let mut branches = vec![(condition, self.block()?)];
+        let mut otherwise = None;
+
+        loop {
+            // `elif`/`else` start a new line at the `if`'s indentation
+            let continues = matches!(*self.raw(), TokenKind::Newline(n) if n == self.indent)
+                && matches!(
+                    self.tokens.get(self.pos + 1).map(|t| &t.kind),
+                    Some(TokenKind::Keyword("elif" | "else"))
+                );
+            if continues {
+                self.pos += 1;
+            }
+
+            if self.eat_keyword("elif") {
+                let condition = self.expression()?;
+                self.expect_punct(":")?;
+                branches.push((condition, self.block()?));
+            } else if self.eat_keyword("else") {
+                self.expect_punct(":")?;
+                otherwise = Some(self.block()?);
+                break;
+            } else {
+                break;
+            }
+        }
+
+        Ok(StatementKind::If {
+            branches,
+            otherwise,
+        })
+    }
+
+    /// Indented `pattern, pattern when guard: body` lines of a `match`
+    fn match_branches(&mut self) -> PResult<Vec<MatchBranch>> {
+        let indent = match *self.raw() {
+            TokenKind::Newline(n) if n > self.indent => n,
+            _ => return Err(self.unexpected("an indented block of match branches")),
+        };
+
+        let saved = self.indent;
+        self.indent = indent;
+        let mut branches = Vec::new();
+        while matches!(*self.raw(), TokenKind::Newline(n) if n == indent) {
+            self.pos += 1;
+            match self.match_branch() {
+                Ok(branch) => branches.push(branch),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.recover(indent);
+                }
+            }
+        }
+        self.indent = saved;
+        Ok(branches)
+    }
+
+    fn match_branch(&mut self) -> PResult<MatchBranch> {
+        let line = self.peek().line;
+        let mut patterns = vec![self.pattern()?];
+        while self.eat_punct(",") {
+            patterns.push(self.pattern()?);
+        }
+        let guard = if self.check_identifier("when") {
+            self.bump();
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.expect_punct(":")?;
+        Ok(MatchBranch {
+            patterns,
+            guard,
+            body: self.block()?,
+            line,
+        })
+    }
+
+    fn pattern(&mut self) -> PResult<Pattern> {
+        if self.check_identifier("_") {
+            self.bump();
+            return Ok(Pattern::Wildcard);
+        }
+        if self.eat_keyword("var") {
+            return Ok(Pattern::Binding(self.expect_identifier()?));
+        }
+        if self.eat_punct("..") {
+            return Ok(Pattern::Rest);
+        }
+        if self.eat_punct("[") {
+            let mut items = Vec::new();
+            while !self.check_punct("]") {
+                items.push(self.pattern()?);
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct("]")?;
+            return Ok(Pattern::Array(items));
+        }
+        if self.eat_punct("{") {
+            let mut entries = Vec::new();
+            while !self.check_punct("}") {
+                let key = self.pattern()?;
+                let value = if self.eat_punct(":") {
+                    Some(self.pattern()?)
+                } else {
+                    None
+                };
+                entries.push((key, value));
+                if !self.eat_punct(",") {
+                    break;
+                }
+            }
+            self.expect_punct("}")?;
+            return Ok(Pattern::Dictionary(entries));
+        }
+        Ok(Pattern::Expression(self.expression()?))
+    }
+
+    // ======================
+    // Expressions
+    // ======================
+
+    fn expression(&mut self) -> PResult<Expr> {
+        let mut expr = self.ternary()?;
+        while self.eat_keyword("as") {
+            expr = Expr::Cast {
+                value: Box::new(expr),
+                type_name: self.type_hint()?,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn ternary(&mut self) -> PResult<Expr> {
+        let value = self.or()?;
+        if !self.check_keyword("if") {
+            return Ok(value);
+        }
+        self.bump();
+        let condition = self.or()?;
+        self.expect_keyword("else")?;
+        let otherwise = self.ternary()?;
+        Ok(Expr::Ternary {
+            value: Box::new(value),
+            condition: Box::new(condition),
+            otherwise: Box::new(otherwise),
+        })
+    }
+
+    fn or(&mut self) -> PResult<Expr> {
+        let mut left = self.and()?;
+        while self.eat_keyword("or") || self.eat_punct("||") {
+            let right = self.and()?;
+            left = binary("or", left, right);
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> PResult<Expr> {
+        let mut left = self.not()?;
+        while self.eat_keyword("and") || self.eat_punct("&&") {
+            let right = self.not()?;
+            left = binary("and", left, right);
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> PResult<Expr> {
+        if self.eat_keyword("not") || self.eat_punct("!") {
+            return Ok(Expr::Unary {
+                op: "not",
+                operand: Box::new(self.not()?),
+            });
+        }
+        self.membership()
+    }
+
+    fn membership(&mut self) -> PResult<Expr> {
+        let mut left = self.comparison()?;
+        loop {
+            if self.eat_keyword("in") {
+                let right = self.comparison()?;
+                left = binary("in", left, right);
+            } else if self.check_keyword("not")
+                && matches!(self.peek_ahead(1), TokenKind::Keyword("in"))
+            {
+                self.bump();
+                self.bump();
+                let right = self.comparison()?;
+                left = binary("not in", left, right);
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    fn comparison(&mut self) -> PResult<Expr> {
+        self.binary_level(&["==", "!=", "<", ">", "<=", ">="], Self::bit_or)
+    }
+
+    fn bit_or(&mut self) -> PResult<Expr> {
+        self.binary_level(&["|"], Self::bit_xor)
+    }
+
+    fn bit_xor(&mut self) -> PResult<Expr> {
+        self.binary_level(&["^"], Self::bit_and)
+    }
+
+    fn bit_and(&mut self) -> PResult<Expr> {
+        self.binary_level(&["&"], Self::shift)
+    }
+
+    fn shift(&mut self) -> PResult<Expr> {
+        self.binary_level(&["<<", ">>"], Self::additive)
+    }
+
+    fn additive(&mut self) -> PResult<Expr> {
+        self.binary_level(&["+", "-"], Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> PResult<Expr> {
+        self.binary_level(&["*", "/", "%"], Self::sign)
+    }
+
+    /// Left-associative binary operators of one precedence level
+    fn binary_level(
+        &mut self,
+        operators: &[&'static str],
+        operand: fn(&mut Self) -> PResult<Expr>,
+    ) -> PResult<Expr> {
+        let mut left = operand(self)?;
+        loop {
+            let op = match self.peek().kind {
+                TokenKind::Punct(p) if operators.contains(&p) => p,
+                _ => return Ok(left),
+            };
+            self.bump();
+            let right = operand(self)?;
+            left = binary(op, left, right);
+        }
+    }
+
+    fn sign(&mut self) -> PResult<Expr> {
+        for op in ["-", "+"] {
+            if self.eat_punct(op) {
+                return Ok(Expr::Unary {
+                    op,
+                    operand: Box::new(self.sign()?),
+                });
+            }
+        }
+        self.bit_not()
+    }
+
+    fn bit_not(&mut self) -> PResult<Expr> {
+        if self.eat_punct("~") {
+            return Ok(Expr::Unary {
+                op: "~",
+                operand: Box::new(self.bit_not()?),
+            });
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> PResult<Expr> {
+        let mut left = self.type_test()?;
+        while self.eat_punct("**") {
+            let right = self.type_test()?;
+            left = binary("**", left, right);
+        }
+        Ok(left)
+    }
+
+    fn type_test(&mut self) -> PResult<Expr> {
+        let mut value = self.awaited()?;
+        while self.eat_keyword("is") {
+            let negated = self.eat_keyword("not");
+            value = Expr::Is {
+                value: Box::new(value),
+                type_name: self.type_hint()?,
+                negated,
+            };
+        }
+        Ok(value)
+    }
+
+    fn awaited(&mut self) -> PResult<Expr> {
+        if self.eat_keyword("await") {
+            return Ok(Expr::Await(Box::new(self.awaited()?)));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> PResult<Expr> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.depth == 0 && matches!(self.raw(), TokenKind::Newline(_)) {
+                return Ok(expr);
+            }
+            if self.eat_punct(".") {
+                let token = self.bump();
+                let name = match token.kind {
+                    TokenKind::Identifier(name) => name,
+                    TokenKind::Keyword(keyword) => keyword.to_string(),
+                    _ => {
+                        return Err(SyntaxError {
+                            line: token.line,
+                            message: "Expected an attribute name after '.'".to_string(),
+                        })
+                    }
+                };
+                expr = Expr::Attribute {
+                    base: Box::new(expr),
+                    name,
+                };
+            } else if self.eat_punct("(") {
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    arguments: self.arguments()?,
+                };
+            } else if self.eat_punct("[") {
+                let index = self.expression()?;
+                self.expect_punct("]")?;
+                expr = Expr::Subscript {
+                    base: Box::new(expr),
+                    index: Box::new(index),
+                };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    /// Call arguments after the opening `(`
+    fn arguments(&mut self) -> PResult<Vec<Expr>> {
+        let mut arguments = Vec::new();
+        while !self.check_punct(")") {
+            arguments.push(self.expression()?);
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        self.expect_punct(")")?;
+        Ok(arguments)
+    }
+
+    fn primary(&mut self) -> PResult<Expr> {
+        let token = self.peek().clone();
+        let expr = match token.kind {
+            TokenKind::Number(text) => {
+                self.bump();
+                Expr::Literal(Literal::Number(text))
+            }
+            TokenKind::String { value, kind } => {
+                self.bump();
+                Expr::Literal(match kind {
+                    StringKind::Plain => Literal::String(value),
+                    StringKind::StringName => Literal::StringName(value),
+                    StringKind::NodePath => Literal::NodePath(value),
+                })
+            }
+            TokenKind::Keyword("true") => {
+                self.bump();
+                Expr::Literal(Literal::Bool(true))
+            }
+            TokenKind::Keyword("false") => {
+                self.bump();
+                Expr::Literal(Literal::Bool(false))
+            }
+            TokenKind::Keyword("null") => {
+                self.bump();
+                Expr::Literal(Literal::Null)
+            }
+            TokenKind::Keyword("self") => {
+                self.bump();
+                Expr::SelfRef
+            }
+            TokenKind::Keyword("super") => {
+                self.bump();
+                Expr::Super
+            }
+            TokenKind::Keyword("func") => {
+                self.bump();
+                self.lambda(token.line)?
+            }
+            TokenKind::Identifier(name) => {
+                self.bump();
+                Expr::Identifier {
+                    name,
+                    line: token.line,
+                }
+            }
+            TokenKind::Punct("(") => {
+                self.bump();
+                let inner = self.expression()?;
+                self.expect_punct(")")?;
+                inner
+            }
+            TokenKind::Punct("[") => {
+                self.bump();
+                let mut items = Vec::new();
+                while !self.check_punct("]") {
+                    items.push(self.expression()?);
+                    if !self.eat_punct(",") {
+                        break;
+                    }
+                }
+                self.expect_punct("]")?;
+                Expr::Array(items)
+            }
+            TokenKind::Punct("{") => {
+                self.bump();
+                self.dictionary()?
+            }
+            TokenKind::Punct("$") => {
+                self.bump();
+                Expr::GetNode {
+                    path: self.node_path()?,
+                    unique: false,
+                }
+            }
+            TokenKind::Punct("%") => {
+                self.bump();
+                Expr::GetNode {
+                    path: self.node_path()?,
+                    unique: true,
+                }
+            }
+            _ => return Err(self.unexpected("an expression")),
+        };
+        Ok(expr)
+    }
+
+    /// Dictionary after `{`: `key: value` or Lua-style `key = value` entries
+    fn dictionary(&mut self) -> PResult<Expr> {
+        let mut entries = Vec::new();
+        while !self.check_punct("}") {
+            let lua_key = match self.peek().kind.clone() {
+                TokenKind::Identifier(name)
+                    if matches!(self.peek_ahead(1), TokenKind::Punct("=")) =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            };
+            let key = match lua_key {
+                Some(name) => {
+                    self.bump();
+                    self.bump();
+                    Expr::Literal(Literal::String(name))
+                }
+                None => {
+                    let key = self.expression()?;
+                    self.expect_punct(":")?;
+                    key
+                }
+            };
+            entries.push((key, self.expression()?));
+            if !self.eat_punct(",") {
+                break;
+            }
+        }
+        self.expect_punct("}")?;
+        Ok(Expr::Dictionary(entries))
+    }
+
+    /// Path after `$` or `%`: `"quoted"`, or `Name/Child` / `%Unique/Child`
+    fn node_path(&mut self) -> PResult<String> {
+        if let TokenKind::String { value, .. } = &self.peek().kind {
+            let value = value.clone();
+            self.bump();
+            return Ok(value);
+        }
+
+        let mut path = String::new();
+        loop {
+            if self.eat_punct("%") {
+                path.push('%');
+            }
+            match self.peek().kind.clone() {
+                TokenKind::Identifier(name) => path.push_str(&name),
+                TokenKind::Keyword(keyword) => path.push_str(keyword),
+                _ => return Err(self.unexpected("a node path")),
+            }
+            self.bump();
+
+            let continues = self.check_punct("/")
+                && matches!(
+                    self.peek_ahead(1),
+                    TokenKind::Identifier(_) | TokenKind::Keyword(_) | TokenKind::Punct("%")
+                );
+            if !continues {
+                return Ok(path);
+            }
+            self.bump();
+            path.push('/');
+        }
+    }
+
+    /// Lambda after `func`; multi-line bodies inside brackets use indentation again
+    fn lambda(&mut self, line: usize) -> PResult<Expr> {
+        let saved_depth = self.depth;
+        let nested = saved_depth > 0;
+        if nested {
+            self.lambda_depth += 1;
+        }
+
+        // Parameters and return type are still bracketed; the body is not
+        let name = match &self.peek().kind {
+            TokenKind::Identifier(name) => {
+                let name = name.clone();
+                self.bump();
+                name
+            }
+            _ => String::new(),
+        };
+        let result = (|| {
+            let parameters = self.parameters()?;
+            let return_type = if self.eat_punct("->") {
+                Some(self.type_hint()?)
+            } else {
+                None
+            };
+            self.expect_punct(":")?;
+            self.depth = 0;
+            let (body, body_source) = self.function_body()?;
+            Ok(FunctionDecl {
+                name,
+                parameters,
+                return_type,
+                body,
+                body_source,
+                annotations: Vec::new(),
+                is_static: false,
+                line,
+                end_line: self.prev_line,
+            })
+        })();
+
+        self.depth = saved_depth;
+        if nested {
+            self.lambda_depth -= 1;
+        }
+        result.map(|function| Expr::Lambda(Box::new(function)))
+    }
+}
+
+fn binary(op: &'static str, left: Expr, right: Expr) -> Expr {
+    Expr::Binary {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_ok(source: &str) -> ClassDecl {
+        let (class, errors) = parse(source);
+        assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+        class
+    }
+
+    #[test]
+    fn test_members_and_annotations() {
+        let class = parse_ok(
+            r#"@tool
+class_name Player extends CharacterBody2D
+
+signal health_changed(old: int, new: int)
+signal died
+
+enum State { IDLE, RUN = 2,
+	JUMP }
+const SPEED := 300.0
+@export_range(0, 100) var health: int = 100
+@export
+var target: NodePath
+@onready var sprite := $Sprite2D as Sprite2D
+static var count = 0
+
+static func create() -> Player:
+	return null
+"#,
+        );
+
+        assert!(class.has_annotation("tool"));
+        assert_eq!(class.name.as_deref(), Some("Player"));
+        assert_eq!(class.extends.as_deref(), Some("CharacterBody2D"));
+
+        let signals: Vec<_> = class.signals().collect();
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].parameters[1].type_hint.as_deref(), Some("int"));
+
+        let state = class.enums().next().unwrap();
+        assert_eq!(state.name.as_deref(), Some("State"));
+        assert_eq!(state.values.len(), 3);
+        assert_eq!(state.values[1].value_source.as_deref(), Some("2"));
+
+        let speed = class.constants().next().unwrap();
+        assert_eq!(speed.value_source.as_deref(), Some("300.0"));
+
+        let vars: Vec<_> = class.variables().collect();
+        assert_eq!(vars.len(), 4);
+        assert!(vars[0].is_exported());
+        assert_eq!(vars[0].annotations[0].arguments.len(), 2);
+        assert!(vars[1].is_exported());
+        assert_eq!(vars[1].type_hint.as_deref(), Some("NodePath"));
+        assert!(vars[2].has_annotation("onready"));
+        assert!(vars[2].inferred);
+        assert_eq!(
+            vars[2].value_source.as_deref(),
+            Some("$Sprite2D as Sprite2D")
+        );
+        assert!(vars[3].is_static);
+
+        let create = class.functions().next().unwrap();
+        assert!(create.is_static);
+        assert_eq!(create.return_type.as_deref(), Some("Player"));
+        assert_eq!((create.line, create.end_line), (16, 17));
+    }
+
+    #[test]
+    fn test_inner_classes() {
+        let class = parse_ok(
+            r#"extends "res://base.gd"
+
+class Item extends Resource:
+	var id: int
+
+	class Stack:
+		var items: Array[Item] = []
+
+		func push(item: Item) -> void:
+			items.append(item)
+
+func after() -> void:
+	pass
+"#,
+        );
+
+        assert_eq!(class.extends.as_deref(), Some("res://base.gd"));
+        let item = class.classes().next().unwrap();
+        assert_eq!(item.name.as_deref(), Some("Item"));
+        assert_eq!(item.extends.as_deref(), Some("Resource"));
+        assert_eq!(item.variables().count(), 1);
+
+        let stack = item.classes().next().unwrap();
+        assert_eq!(
+            stack.variables().next().unwrap().type_hint.as_deref(),
+            Some("Array[Item]")
+        );
+        assert_eq!(stack.functions().next().unwrap().name, "push");
+
+        // Members after the inner class belong to the script again
+        assert_eq!(class.functions().next().unwrap().name, "after");
+    }
+
+    #[test]
+    fn test_match_and_control_flow() {
+        let class = parse_ok(
+            r#"func f(value):
+	match value:
+		1, 2:
+			print("small")
+		[var first, ..]:
+			print(first)
+		{"type": "enemy", ..}:
+			pass
+		State.IDLE when value > 0:
+			pass
+		_:
+			pass
+	if value is not int and not value in [1, 2]:
+		return
+	elif value:
+		value += 1
+	else:
+		for i: int in range(3):
+			while i < 3: i += 1
+"#,
+        );
+
+        let function = class.functions().next().unwrap();
+        assert_eq!(function.body.len(), 2);
+        let StatementKind::Match { branches, .. } = &function.body[0].kind else {
+            panic!("Expected match");
+        };
+        assert_eq!(branches.len(), 5);
+        assert_eq!(branches[0].patterns.len(), 2);
+        assert!(matches!(branches[1].patterns[0], Pattern::Array(_)));
+        assert!(matches!(branches[2].patterns[0], Pattern::Dictionary(_)));
+        assert!(branches[3].guard.is_some());
+        assert!(matches!(branches[4].patterns[0], Pattern::Wildcard));
+
+        let StatementKind::If {
+            branches,
+            otherwise,
+        } = &function.body[1].kind
+        else {
+            panic!("Expected if");
+        };
+        assert_eq!(branches.len(), 2);
+        assert!(otherwise.is_some());
+    }
+
+    #[test]
+    fn test_lambdas() {
+        let class = parse_ok(
+            r#"func _ready():
+	var double = func(x): return x * 2
+	button.pressed.connect(func():
+		print("pressed")
+		queue_free()
+	)
+	timer.timeout.connect(func(): print("done"), CONNECT_ONE_SHOT)
+	print("after")
+"#,
+        );
+
+        let body = &class.functions().next().unwrap().body;
+        assert_eq!(body.len(), 4);
+        let StatementKind::Expression(Expr::Call { arguments, .. }) = &body[1].kind else {
+            panic!("Expected call");
+        };
+        let Expr::Lambda(lambda) = &arguments[0] else {
+            panic!("Expected lambda");
+        };
+        assert_eq!(lambda.body.len(), 2);
+
+        let StatementKind::Expression(Expr::Call { arguments, .. }) = &body[2].kind else {
+            panic!("Expected call");
+        };
+        assert_eq!(arguments.len(), 2);
+    }
+
+    #[test]
+    fn test_properties_and_legacy_syntax() {
+        let class = parse_ok(
+            r#"tool
+extends Node
+
+export(int, 0, 10) var level = 1
+onready var label = get_node("Label")
+var hp = 10 setget set_hp, get_hp
+var speed: float = 1.0:
+	set(value):
+		speed = clamp(value, 0.0, 10.0)
+	get:
+		return speed
+var alias: int: set = _set_alias, get = _get_alias
+
+remote func sync_state(state):
+	pass
+"#,
+        );
+
+        assert!(class.has_annotation("tool"));
+        let vars: Vec<_> = class.variables().collect();
+        assert!(vars[0].is_exported());
+        assert!(vars[1].has_annotation("onready"));
+        assert!(matches!(vars[2].setter, Some(Accessor::Function(ref f)) if f == "set_hp"));
+        assert!(matches!(vars[3].setter, Some(Accessor::Inline { .. })));
+        assert!(matches!(vars[3].getter, Some(Accessor::Inline { .. })));
+        assert_eq!(vars[3].end_line, 11);
+        assert!(matches!(vars[4].getter, Some(Accessor::Function(ref f)) if f == "_get_alias"));
+
+        let function = class.functions().next().unwrap();
+        assert_eq!(function.annotations[0].name, "remote");
+    }
+
+    #[test]
+    fn test_error_recovery() {
+        let (class, errors) = parse(
+            r#"extends Node
+
+func broken(:
+	pass
+
+func ok():
+	var x = (1 +
+	print(x)
+	var y = 2
+
+var after = 1
+"#,
+        );
+
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(errors[0].line, 3);
+        let names: Vec<_> = class.functions().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["ok"]);
+        assert_eq!(class.variables().next().unwrap().name, "after");
+    }
+}
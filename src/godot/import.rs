@@ -0,0 +1,150 @@
+//! .import file editing
+//!
+//! Reads and updates the `key=value` settings of the `.import` sidecars Godot
+//! writes next to imported assets, keeping every other line as it was.
+
+/// A parsed `.import` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportFile {
+    lines: Vec<String>,
+}
+
+impl ImportFile {
+    pub fn parse(content: &str) -> Self {
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+        }
+    }
+
+    /// Raw value of `key` in `[section]`
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.entries(section)
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// `key=value` pairs of `[section]`, in file order
+    pub fn entries(&self, section: &str) -> Vec<(&str, &str)> {
+        let Some((start, end)) = self.section_range(section) else {
+            return Vec::new();
+        };
+        self.lines[start..end]
+            .iter()
+            .filter_map(|line| line.split_once('='))
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .collect()
+    }
+
+    /// Importer name from `[remap]` (e.g. "wav", "oggvorbisstr")
+    pub fn importer(&self) -> Option<&str> {
+        self.get("remap", "importer").map(|v| v.trim_matches('"'))
+    }
+
+    /// Set `key` in `[section]`, adding the key (or section) when missing.
+    /// Returns whether the file changed.
+    pub fn set(&mut self, section: &str, key: &str, value: &str) -> bool {
+        let entry = format!("{}={}", key, value);
+        let Some((start, end)) = self.section_range(section) else {
+            if self.lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                self.lines.push(String::new());
+            }
+            self.lines.push(format!("[{}]", section));
+            self.lines.push(String::new());
+            self.lines.push(entry);
+            return true;
+        };
+
+        let existing = (start..end).find(|&i| {
+            self.lines[i]
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key)
+        });
+        match existing {
+            Some(i) if self.lines[i] == entry => false,
+            Some(i) => {
+                self.lines[i] = entry;
+                true
+            }
+            None => {
+                // After the section's last entry, before trailing blank lines
+                let insert_at = (start..end)
+                    .rev()
+                    .find(|&i| !self.lines[i].trim().is_empty())
+                    .map_or(start, |i| i + 1);
+                self.lines.insert(insert_at, entry);
+                true
+            }
+        }
+    }
+
+    /// Convert back to .import format
+    pub fn to_import(&self) -> String {
+        let mut output = self.lines.join("\n");
+        output.push('\n');
+        output
+    }
+
+    /// Line range of a section's body (after the header, up to the next header)
+    fn section_range(&self, section: &str) -> Option<(usize, usize)> {
+        let header = format!("[{}]", section);
+        let start = self.lines.iter().position(|l| l.trim() == header)? + 1;
+        let end = self.lines[start..]
+            .iter()
+            .position(|l| l.trim_start().starts_with('['))
+            .map_or(self.lines.len(), |i| start + i);
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAV_IMPORT: &str = r#"[remap]
+
+importer="wav"
+type="AudioStreamWAV"
+uid="uid://b8x2m1"
+path="res://.godot/imported/hit.wav-1c2d.sample"
+
+[deps]
+
+source_file="res://sfx/hit.wav"
+dest_files=["res://.godot/imported/hit.wav-1c2d.sample"]
+
+[params]
+
+force/8_bit=false
+edit/loop_mode=0
+edit/normalize=false
+"#;
+
+    #[test]
+    fn test_get_and_set_keep_other_lines() {
+        let mut import = ImportFile::parse(WAV_IMPORT);
+        assert_eq!(import.importer(), Some("wav"));
+        assert_eq!(import.get("params", "edit/loop_mode"), Some("0"));
+        assert_eq!(import.entries("params").len(), 3);
+
+        assert!(import.set("params", "edit/loop_mode", "2"));
+        assert!(!import.set("params", "edit/loop_mode", "2"));
+        assert!(import.set("params", "edit/loop_begin", "100"));
+        let output = import.to_import();
+        assert!(output.starts_with("[remap]\n\nimporter=\"wav\""));
+        assert!(output.contains("edit/loop_mode=2\nedit/normalize=false\nedit/loop_begin=100\n"));
+        assert!(output
+            .contains("dest_files=[\"res://.godot/imported/hit.wav-1c2d.sample\"]\n\n[params]"));
+    }
+
+    #[test]
+    fn test_set_adds_missing_section() {
+        let mut import = ImportFile::parse("[remap]\n\nimporter=\"mp3\"\n");
+        assert!(import.set("params", "loop", "true"));
+        assert_eq!(
+            import.to_import(),
+            "[remap]\n\nimporter=\"mp3\"\n\n[params]\n\nloop=true\n"
+        );
+        assert_eq!(import.get("params", "loop"), Some("true"));
+    }
+}
@@ -0,0 +1,503 @@
+//! InputMap actions in project.godot
+//!
+//! Reads and writes the values of the `[input]` section: a dictionary with
+//! the action's deadzone and its events, each serialized as
+//! `Object(InputEventKey,"property":value,...)`. Key, mouse button and
+//! joypad events are decoded; other event classes are kept as written.
+
+/// Godot's `Key` enum: keys without a character start at `KEY_SPECIAL`
+const KEY_SPECIAL: i64 = 1 << 22;
+
+/// Named keys and their keycodes (letters and digits are their ASCII codes)
+const KEYS: &[(&str, i64)] = &[
+    ("Space", 32),
+    ("Apostrophe", 39),
+    ("Comma", 44),
+    ("Minus", 45),
+    ("Period", 46),
+    ("Slash", 47),
+    ("Semicolon", 59),
+    ("Equal", 61),
+    ("BracketLeft", 91),
+    ("Backslash", 92),
+    ("BracketRight", 93),
+    ("QuoteLeft", 96),
+    ("Escape", KEY_SPECIAL + 1),
+    ("Tab", KEY_SPECIAL + 2),
+    ("Backtab", KEY_SPECIAL + 3),
+    ("Backspace", KEY_SPECIAL + 4),
+    ("Enter", KEY_SPECIAL + 5),
+    ("Kp Enter", KEY_SPECIAL + 6),
+    ("Insert", KEY_SPECIAL + 7),
+    ("Delete", KEY_SPECIAL + 8),
+    ("Pause", KEY_SPECIAL + 9),
+    ("Print", KEY_SPECIAL + 10),
+    ("Home", KEY_SPECIAL + 13),
+    ("End", KEY_SPECIAL + 14),
+    ("Left", KEY_SPECIAL + 15),
+    ("Up", KEY_SPECIAL + 16),
+    ("Right", KEY_SPECIAL + 17),
+    ("Down", KEY_SPECIAL + 18),
+    ("PageUp", KEY_SPECIAL + 19),
+    ("PageDown", KEY_SPECIAL + 20),
+    ("Shift", KEY_SPECIAL + 21),
+    ("Ctrl", KEY_SPECIAL + 22),
+    ("Meta", KEY_SPECIAL + 23),
+    ("Alt", KEY_SPECIAL + 24),
+    ("CapsLock", KEY_SPECIAL + 25),
+    ("NumLock", KEY_SPECIAL + 26),
+    ("ScrollLock", KEY_SPECIAL + 27),
+    ("Menu", KEY_SPECIAL + 66),
+    ("Kp Multiply", KEY_SPECIAL + 129),
+    ("Kp Divide", KEY_SPECIAL + 130),
+    ("Kp Subtract", KEY_SPECIAL + 131),
+    ("Kp Period", KEY_SPECIAL + 132),
+    ("Kp Add", KEY_SPECIAL + 133),
+];
+
+/// Alternative spellings accepted for key names
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("Return", "Enter"),
+    ("Esc", "Escape"),
+    ("Control", "Ctrl"),
+    ("Cmd", "Meta"),
+    ("Command", "Meta"),
+    ("Del", "Delete"),
+    ("PgUp", "PageUp"),
+    ("PgDown", "PageDown"),
+];
+
+/// First function key (F1); F1-F35 are consecutive
+const KEY_F1: i64 = KEY_SPECIAL + 28;
+
+/// First keypad digit (Kp 0); Kp 0-9 are consecutive
+const KEY_KP_0: i64 = KEY_SPECIAL + 134;
+
+/// An event bound to an action
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    Key {
+        keycode: i64,
+        /// Bound by physical location (the editor's default) rather than by label
+        physical: bool,
+        ctrl: bool,
+        shift: bool,
+        alt: bool,
+        meta: bool,
+    },
+    MouseButton {
+        button: i64,
+    },
+    JoypadButton {
+        /// -1 for all devices
+        device: i64,
+        button: i64,
+    },
+    JoypadMotion {
+        device: i64,
+        axis: i64,
+        /// Direction: -1.0 or 1.0
+        axis_value: f64,
+    },
+    /// Another event class, as written
+    Other {
+        class: String,
+        raw: String,
+    },
+}
+
+/// An action's value in `[input]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputAction {
+    pub deadzone: f64,
+    pub events: Vec<InputEvent>,
+}
+
+impl InputAction {
+    /// Parse `{"deadzone": 0.5, "events": [Object(...), ...]}`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if !value.starts_with('{') {
+            return None;
+        }
+        let deadzone = value
+            .split_once("\"deadzone\":")
+            .and_then(|(_, rest)| {
+                let end = rest.find([',', '\n', '}']).unwrap_or(rest.len());
+                rest[..end].trim().parse::<f64>().ok()
+            })
+            .unwrap_or(0.5);
+        let events = objects(value)
+            .into_iter()
+            .map(|(class, properties, raw)| InputEvent::from_properties(class, &properties, raw))
+            .collect();
+        Some(Self { deadzone, events })
+    }
+
+    /// Serialize in the layout Godot writes
+    pub fn to_godot(&self) -> String {
+        let events: Vec<String> = self.events.iter().map(InputEvent::to_godot).collect();
+        format!(
+            "{{\n\"deadzone\": {},\n\"events\": [{}]\n}}",
+            format_float(self.deadzone),
+            events.join("\n, ")
+        )
+    }
+}
+
+impl InputEvent {
+    /// A key event from a name such as "Space", "W", "F5" or "Ctrl+Shift+S"
+    pub fn key(combo: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = combo.split('+').map(str::trim).collect();
+        let name = parts.pop().unwrap_or_default();
+        let keycode = key_code(name).ok_or_else(|| format!("Unknown key: '{}'", name))?;
+        let mut event = (false, false, false, false);
+        for modifier in parts {
+            match key_code(modifier) {
+                Some(code) if code == KEY_SPECIAL + 22 => event.0 = true,
+                Some(code) if code == KEY_SPECIAL + 21 => event.1 = true,
+                Some(code) if code == KEY_SPECIAL + 24 => event.2 = true,
+                Some(code) if code == KEY_SPECIAL + 23 => event.3 = true,
+                _ => return Err(format!("Unknown modifier: '{}'", modifier)),
+            }
+        }
+        let (ctrl, shift, alt, meta) = event;
+        Ok(Self::Key {
+            keycode,
+            physical: true,
+            ctrl,
+            shift,
+            alt,
+            meta,
+        })
+    }
+
+    fn from_properties(class: &str, properties: &[(&str, &str)], raw: &str) -> Self {
+        let get = |name: &str| {
+            properties
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        let int = |name: &str| get(name).and_then(|v| v.parse::<i64>().ok()).unwrap_or(0);
+        let flag = |name: &str| get(name) == Some("true");
+        match class {
+            "InputEventKey" => {
+                let physical = int("physical_keycode");
+                Self::Key {
+                    keycode: if physical != 0 {
+                        physical
+                    } else {
+                        int("keycode")
+                    },
+                    physical: physical != 0,
+                    ctrl: flag("ctrl_pressed"),
+                    shift: flag("shift_pressed"),
+                    alt: flag("alt_pressed"),
+                    meta: flag("meta_pressed"),
+                }
+            }
+            "InputEventMouseButton" => Self::MouseButton {
+                button: int("button_index"),
+            },
+            "InputEventJoypadButton" => Self::JoypadButton {
+                device: int("device"),
+                button: int("button_index"),
+            },
+            "InputEventJoypadMotion" => Self::JoypadMotion {
+                device: int("device"),
+                axis: int("axis"),
+                axis_value: get("axis_value")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1.0),
+            },
+            _ => Self::Other {
+                class: class.to_string(),
+                raw: raw.to_string(),
+            },
+        }
+    }
+
+    /// Godot class of the event
+    pub fn class(&self) -> &str {
+        match self {
+            Self::Key { .. } => "InputEventKey",
+            Self::MouseButton { .. } => "InputEventMouseButton",
+            Self::JoypadButton { .. } => "InputEventJoypadButton",
+            Self::JoypadMotion { .. } => "InputEventJoypadMotion",
+            Self::Other { class, .. } => class,
+        }
+    }
+
+    /// Human-readable binding, e.g. "Ctrl+S", "Mouse Button 1", "Joypad Axis 0-"
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Key {
+                keycode,
+                ctrl,
+                shift,
+                alt,
+                meta,
+                ..
+            } => {
+                let mut parts = Vec::new();
+                for (pressed, name) in [
+                    (ctrl, "Ctrl"),
+                    (shift, "Shift"),
+                    (alt, "Alt"),
+                    (meta, "Meta"),
+                ] {
+                    if *pressed {
+                        parts.push(name.to_string());
+                    }
+                }
+                parts.push(key_name(*keycode).unwrap_or_else(|| format!("Key {}", keycode)));
+                parts.join("+")
+            }
+            Self::MouseButton { button } => format!("Mouse Button {}", button),
+            Self::JoypadButton { button, .. } => format!("Joypad Button {}", button),
+            Self::JoypadMotion {
+                axis, axis_value, ..
+            } => format!(
+                "Joypad Axis {}{}",
+                axis,
+                if *axis_value < 0.0 { "-" } else { "+" }
+            ),
+            Self::Other { class, .. } => class.clone(),
+        }
+    }
+
+    /// Serialize as project.godot writes it
+    pub fn to_godot(&self) -> String {
+        const RESOURCE: &str = r#""resource_local_to_scene":false,"resource_name":"""#;
+        match self {
+            Self::Key {
+                keycode,
+                physical,
+                ctrl,
+                shift,
+                alt,
+                meta,
+            } => {
+                let (logical, physical_code) = if *physical {
+                    (0, *keycode)
+                } else {
+                    (*keycode, 0)
+                };
+                // Letters are stored as uppercase keycodes but type lowercase
+                let unicode = match *keycode {
+                    code @ 65..=90 if !shift => code + 32,
+                    code if code < KEY_SPECIAL => code,
+                    _ => 0,
+                };
+                format!(
+                    r#"Object(InputEventKey,{},"device":-1,"window_id":0,"alt_pressed":{},"shift_pressed":{},"ctrl_pressed":{},"meta_pressed":{},"pressed":false,"keycode":{},"physical_keycode":{},"key_label":0,"unicode":{},"location":0,"echo":false,"script":null)"#,
+                    RESOURCE, alt, shift, ctrl, meta, logical, physical_code, unicode
+                )
+            }
+            Self::MouseButton { button } => format!(
+                r#"Object(InputEventMouseButton,{},"device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"button_mask":0,"position":Vector2(0, 0),"global_position":Vector2(0, 0),"factor":1.0,"button_index":{},"canceled":false,"pressed":true,"double_click":false,"script":null)"#,
+                RESOURCE, button
+            ),
+            Self::JoypadButton { device, button } => format!(
+                r#"Object(InputEventJoypadButton,{},"device":{},"button_index":{},"pressure":0.0,"pressed":true,"script":null)"#,
+                RESOURCE, device, button
+            ),
+            Self::JoypadMotion {
+                device,
+                axis,
+                axis_value,
+            } => format!(
+                r#"Object(InputEventJoypadMotion,{},"device":{},"axis":{},"axis_value":{},"script":null)"#,
+                RESOURCE,
+                device,
+                axis,
+                format_float(*axis_value)
+            ),
+            Self::Other { raw, .. } => raw.clone(),
+        }
+    }
+}
+
+/// Keycode of a key name (case-insensitive): "A", "7", "F5", "Space", "Kp 3"
+pub fn key_code(name: &str) -> Option<i64> {
+    let name = name.trim();
+    let name = KEY_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, key)| key);
+    if let [c] = name.as_bytes() {
+        if c.is_ascii_alphanumeric() {
+            return Some(c.to_ascii_uppercase() as i64);
+        }
+    }
+    let upper = name.to_ascii_uppercase();
+    if let Some(n) = upper.strip_prefix('F').and_then(|n| n.parse::<i64>().ok()) {
+        return (1..=35).contains(&n).then_some(KEY_F1 + n - 1);
+    }
+    if let Some(n) = upper
+        .strip_prefix("KP")
+        .and_then(|n| n.trim().parse::<i64>().ok())
+    {
+        return (0..=9).contains(&n).then_some(KEY_KP_0 + n);
+    }
+    let compact = |s: &str| s.replace([' ', '_'], "").to_ascii_lowercase();
+    KEYS.iter()
+        .find(|(key, _)| compact(key) == compact(name))
+        .map(|(_, code)| *code)
+}
+
+/// Name of a keycode, in the spelling [`key_code`] accepts
+pub fn key_name(code: i64) -> Option<String> {
+    match code {
+        48..=57 | 65..=90 => Some((code as u8 as char).to_string()),
+        code if (KEY_F1..KEY_F1 + 35).contains(&code) => Some(format!("F{}", code - KEY_F1 + 1)),
+        code if (KEY_KP_0..KEY_KP_0 + 10).contains(&code) => {
+            Some(format!("Kp {}", code - KEY_KP_0))
+        }
+        _ => KEYS
+            .iter()
+            .find(|(_, c)| *c == code)
+            .map(|(name, _)| name.to_string()),
+    }
+}
+
+/// An `Object(...)` entry: class, properties and the raw text
+type Object<'a> = (&'a str, Vec<(&'a str, &'a str)>, &'a str);
+
+/// `Object(Class,"key":value,...)` entries of a value
+fn objects(value: &str) -> Vec<Object<'_>> {
+    let mut found = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("Object(") {
+        let body_start = start + "Object(".len();
+        let Some(len) = closing_paren(&rest[body_start..]) else {
+            break;
+        };
+        let body = &rest[body_start..body_start + len];
+        let raw = &rest[start..body_start + len + 1];
+        let (class, properties) = body.split_once(',').unwrap_or((body, ""));
+        let properties = split_top_level(properties)
+            .into_iter()
+            .filter_map(|pair| {
+                let (key, value) = pair.split_once(':')?;
+                Some((key.trim().trim_matches('"'), value.trim()))
+            })
+            .collect();
+        found.push((class.trim(), properties, raw));
+        rest = &rest[body_start + len + 1..];
+    }
+    found
+}
+
+/// Length of the text up to the parenthesis closing an already opened one
+fn closing_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' if !in_string && depth == 0 => return Some(i),
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas outside strings and brackets
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut in_string, mut escaped, mut start) = (0, false, false, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Float as Godot writes it: always with a decimal point
+fn format_float(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// As written by the Godot 4.4 editor
+    const JUMP: &str = r#"{
+"deadzone": 0.2,
+"events": [Object(InputEventKey,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":32,"key_label":0,"unicode":32,"location":0,"echo":false,"script":null)
+, Object(InputEventJoypadButton,"resource_local_to_scene":false,"resource_name":"","device":-1,"button_index":0,"pressure":0.0,"pressed":true,"script":null)
+, Object(InputEventMouseButton,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"button_mask":0,"position":Vector2(0, 0),"global_position":Vector2(0, 0),"factor":1.0,"button_index":1,"canceled":false,"pressed":true,"double_click":false,"script":null)
+, Object(InputEventJoypadMotion,"resource_local_to_scene":false,"resource_name":"","device":0,"axis":1,"axis_value":-1.0,"script":null)
+]
+}"#;
+
+    #[test]
+    fn test_parse_action() {
+        let action = InputAction::parse(JUMP).unwrap();
+        assert_eq!(action.deadzone, 0.2);
+        let described: Vec<String> = action.events.iter().map(InputEvent::describe).collect();
+        assert_eq!(
+            described,
+            vec![
+                "Space",
+                "Joypad Button 0",
+                "Mouse Button 1",
+                "Joypad Axis 1-"
+            ]
+        );
+        assert_eq!(
+            action.events[3],
+            InputEvent::JoypadMotion {
+                device: 0,
+                axis: 1,
+                axis_value: -1.0
+            }
+        );
+        // Round trip
+        assert_eq!(InputAction::parse(&action.to_godot()).unwrap(), action);
+    }
+
+    #[test]
+    fn test_key_names() {
+        assert_eq!(key_code("space"), Some(32));
+        assert_eq!(key_code("w"), Some(87));
+        assert_eq!(key_code("7"), Some(55));
+        assert_eq!(key_code("F12"), Some(KEY_F1 + 11));
+        assert_eq!(key_code("Return"), key_code("Enter"));
+        assert_eq!(key_code("page_up"), Some(KEY_SPECIAL + 19));
+        assert_eq!(key_code("Kp 3"), Some(KEY_KP_0 + 3));
+        assert_eq!(key_code("Left"), Some(4194319));
+        assert_eq!(key_code("Hyper"), None);
+        for code in [32, 65, KEY_F1 + 4, KEY_KP_0 + 9, KEY_SPECIAL + 5] {
+            assert_eq!(key_code(&key_name(code).unwrap()), Some(code));
+        }
+
+        let save = InputEvent::key("Ctrl+Shift+S").unwrap();
+        assert_eq!(save.describe(), "Ctrl+Shift+S");
+        assert!(save.to_godot().contains(r#""ctrl_pressed":true,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":83,"key_label":0,"unicode":83"#));
+        assert!(InputEvent::key("Hyper+S").is_err());
+    }
+}
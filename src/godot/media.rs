@@ -0,0 +1,437 @@
+//! Binary asset probing
+//!
+//! Reads just enough of image, audio and mesh files to report their
+//! dimensions, duration or vertex counts, without decoding them.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Bytes read from the start of a file when looking for headers
+const HEADER_LIMIT: u64 = 256 * 1024;
+
+/// Bytes read from the end of an Ogg file when looking for the last page
+const OGG_TAIL: u64 = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Image,
+    Audio,
+    Mesh,
+}
+
+/// Metadata derived from a binary asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    pub kind: MediaKind,
+    /// Container/codec, e.g. "PNG", "Ogg Vorbis"
+    pub format: String,
+    pub file_size: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub channels: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub duration_secs: Option<f64>,
+    pub vertex_count: Option<u64>,
+    pub face_count: Option<u64>,
+}
+
+impl MediaInfo {
+    fn new(kind: MediaKind, format: &str, file_size: u64) -> Self {
+        Self {
+            kind,
+            format: format.to_string(),
+            file_size,
+            width: None,
+            height: None,
+            channels: None,
+            sample_rate: None,
+            duration_secs: None,
+            vertex_count: None,
+            face_count: None,
+        }
+    }
+}
+
+/// Probe an asset by extension; `None` for unsupported or malformed files
+pub fn probe(path: &Path) -> Option<MediaInfo> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let mut file = File::open(path).ok()?;
+    let file_size = file.metadata().ok()?.len();
+
+    match ext.as_str() {
+        "png" => probe_png(&read_head(&mut file).ok()?, file_size),
+        "jpg" | "jpeg" => probe_jpeg(&read_head(&mut file).ok()?, file_size),
+        "webp" => probe_webp(&read_head(&mut file).ok()?, file_size),
+        "wav" => probe_wav(&read_head(&mut file).ok()?, file_size),
+        "ogg" => {
+            let head = read_head(&mut file).ok()?;
+            let tail = read_tail(&mut file, file_size).ok()?;
+            probe_ogg(&head, &tail, file_size)
+        }
+        "obj" => probe_obj(&std::fs::read_to_string(path).ok()?, file_size),
+        "gltf" => probe_gltf(&std::fs::read_to_string(path).ok()?, file_size),
+        "glb" => probe_glb(&std::fs::read(path).ok()?, file_size),
+        _ => None,
+    }
+}
+
+/// Probe an asset already read into memory (see [`probe`])
+pub fn probe_data(path: &Path, data: &[u8]) -> Option<MediaInfo> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let file_size = data.len() as u64;
+    let head = &data[..data.len().min(HEADER_LIMIT as usize)];
+
+    match ext.as_str() {
+        "png" => probe_png(head, file_size),
+        "jpg" | "jpeg" => probe_jpeg(head, file_size),
+        "webp" => probe_webp(head, file_size),
+        "wav" => probe_wav(head, file_size),
+        "ogg" => {
+            let tail = &data[data.len().saturating_sub(OGG_TAIL as usize)..];
+            probe_ogg(head, tail, file_size)
+        }
+        "obj" => probe_obj(std::str::from_utf8(data).ok()?, file_size),
+        "gltf" => probe_gltf(std::str::from_utf8(data).ok()?, file_size),
+        "glb" => probe_glb(data, file_size),
+        _ => None,
+    }
+}
+
+fn read_head(file: &mut File) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    file.take(HEADER_LIMIT).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+fn read_tail(file: &mut File, file_size: u64) -> io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(file_size.saturating_sub(OGG_TAIL)))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+fn u16_be(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_be(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u16_le(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_le(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn u24_le(data: &[u8], at: usize) -> Option<u32> {
+    let b = data.get(at..at + 3)?;
+    Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+}
+
+// ======================
+// Images
+// ======================
+
+fn probe_png(data: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if !data.starts_with(b"\x89PNG\r\n\x1a\n") || data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let mut info = MediaInfo::new(MediaKind::Image, "PNG", file_size);
+    info.width = u32_be(data, 16);
+    info.height = u32_be(data, 20);
+    info.channels = match data.get(25)? {
+        0 => Some(1),
+        2 => Some(3),
+        3 => Some(1), // palette
+        4 => Some(2),
+        6 => Some(4),
+        _ => None,
+    };
+    Some(info)
+}
+
+fn probe_jpeg(data: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        // Fill bytes and markers without a length
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if marker == 0xD8 || marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos += 2;
+            continue;
+        }
+        let length = u16_be(data, pos + 2)? as usize;
+        // SOF0..SOF15 except DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            let mut info = MediaInfo::new(MediaKind::Image, "JPEG", file_size);
+            info.height = u16_be(data, pos + 5).map(u32::from);
+            info.width = u16_be(data, pos + 7).map(u32::from);
+            info.channels = data.get(pos + 9).map(|&c| u32::from(c));
+            return Some(info);
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+fn probe_webp(data: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if data.get(0..4)? != b"RIFF" || data.get(8..12)? != b"WEBP" {
+        return None;
+    }
+    let mut info = MediaInfo::new(MediaKind::Image, "WebP", file_size);
+    match data.get(12..16)? {
+        b"VP8 " => {
+            info.width = u16_le(data, 26).map(|w| u32::from(w & 0x3FFF));
+            info.height = u16_le(data, 28).map(|h| u32::from(h & 0x3FFF));
+        }
+        b"VP8L" => {
+            let bits = u32_le(data, 21)?;
+            info.width = Some((bits & 0x3FFF) + 1);
+            info.height = Some(((bits >> 14) & 0x3FFF) + 1);
+        }
+        b"VP8X" => {
+            info.width = u24_le(data, 24).map(|w| w + 1);
+            info.height = u24_le(data, 27).map(|h| h + 1);
+        }
+        _ => return None,
+    }
+    Some(info)
+}
+
+// ======================
+// Audio
+// ======================
+
+fn probe_wav(data: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if data.get(0..4)? != b"RIFF" || data.get(8..12)? != b"WAVE" {
+        return None;
+    }
+    let mut info = MediaInfo::new(MediaKind::Audio, "WAV", file_size);
+    let mut byte_rate = None;
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32_le(data, pos + 4)?;
+        match id {
+            b"fmt " => {
+                info.channels = u16_le(data, pos + 10).map(u32::from);
+                info.sample_rate = u32_le(data, pos + 12);
+                byte_rate = u32_le(data, pos + 16).filter(|&r| r > 0);
+            }
+            b"data" => {
+                // The data chunk may extend past the bytes read
+                info.duration_secs = byte_rate.map(|rate| f64::from(size) / f64::from(rate));
+                break;
+            }
+            _ => {}
+        }
+        pos += 8 + size as usize + (size as usize & 1);
+    }
+    Some(info)
+}
+
+fn probe_ogg(head: &[u8], tail: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if !head.starts_with(b"OggS") {
+        return None;
+    }
+    // First packet starts after the page header and its segment table
+    let segments = *head.get(26)? as usize;
+    let packet = head.get(27 + segments..)?;
+
+    let (mut info, rate, pre_skip) = if packet.starts_with(b"\x01vorbis") {
+        let mut info = MediaInfo::new(MediaKind::Audio, "Ogg Vorbis", file_size);
+        info.channels = packet.get(11).map(|&c| u32::from(c));
+        info.sample_rate = u32_le(packet, 12);
+        let rate = info.sample_rate?;
+        (info, rate, 0)
+    } else if packet.starts_with(b"OpusHead") {
+        let mut info = MediaInfo::new(MediaKind::Audio, "Ogg Opus", file_size);
+        info.channels = packet.get(9).map(|&c| u32::from(c));
+        info.sample_rate = u32_le(packet, 12);
+        // Opus granule positions always count 48 kHz samples
+        (info, 48_000, u64::from(u16_le(packet, 10)?))
+    } else {
+        return None;
+    };
+
+    // Granule position of the last page gives the total sample count
+    let last_page = tail.windows(4).rposition(|w| w == b"OggS")?;
+    let granule = u64::from_le_bytes(tail.get(last_page + 6..last_page + 14)?.try_into().ok()?);
+    if rate > 0 && granule != u64::MAX {
+        info.duration_secs = Some(granule.saturating_sub(pre_skip) as f64 / f64::from(rate));
+    }
+    Some(info)
+}
+
+// ======================
+// Meshes
+// ======================
+
+fn probe_obj(content: &str, file_size: u64) -> Option<MediaInfo> {
+    let mut info = MediaInfo::new(MediaKind::Mesh, "OBJ", file_size);
+    let mut vertices = 0;
+    let mut faces = 0;
+    for line in content.lines() {
+        let line = line.trim_start();
+        if line.starts_with("v ") {
+            vertices += 1;
+        } else if line.starts_with("f ") {
+            faces += 1;
+        }
+    }
+    info.vertex_count = Some(vertices);
+    info.face_count = Some(faces);
+    Some(info)
+}
+
+fn probe_gltf(content: &str, file_size: u64) -> Option<MediaInfo> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    Some(gltf_counts(&json, "glTF", file_size))
+}
+
+fn probe_glb(data: &[u8], file_size: u64) -> Option<MediaInfo> {
+    if data.get(0..4)? != b"glTF" || data.get(16..20)? != b"JSON" {
+        return None;
+    }
+    let length = u32_le(data, 12)? as usize;
+    let json: serde_json::Value = serde_json::from_slice(data.get(20..20 + length)?).ok()?;
+    Some(gltf_counts(&json, "GLB", file_size))
+}
+
+/// Sum POSITION accessor counts (vertices) and index counts / 3 (triangles)
+fn gltf_counts(json: &serde_json::Value, format: &str, file_size: u64) -> MediaInfo {
+    let accessor_count = |index: &serde_json::Value| {
+        index
+            .as_u64()
+            .and_then(|i| json["accessors"].get(i as usize))
+            .and_then(|a| a["count"].as_u64())
+    };
+
+    let mut vertices = 0;
+    let mut faces = 0;
+    let primitives = json["meshes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|mesh| mesh["primitives"].as_array().into_iter().flatten());
+    for primitive in primitives {
+        let count = accessor_count(&primitive["attributes"]["POSITION"]).unwrap_or(0);
+        vertices += count;
+        faces += accessor_count(&primitive["indices"]).unwrap_or(count) / 3;
+    }
+
+    let mut info = MediaInfo::new(MediaKind::Mesh, format, file_size);
+    info.vertex_count = Some(vertices);
+    info.face_count = Some(faces);
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn test_probe_png() {
+        let info = probe_png(&png_header(64, 32), 100).unwrap();
+        assert_eq!(info.width, Some(64));
+        assert_eq!(info.height, Some(32));
+        assert_eq!(info.channels, Some(4));
+        assert!(probe_png(b"not a png", 9).is_none());
+    }
+
+    #[test]
+    fn test_probe_jpeg() {
+        let mut data = vec![0xFF, 0xD8];
+        // APP0 segment to skip
+        data.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00]);
+        // SOF0: length, precision, height, width, components
+        data.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0xE0, 0x02, 0x80, 0x03]);
+        let info = probe_jpeg(&data, 100).unwrap();
+        assert_eq!(info.width, Some(640));
+        assert_eq!(info.height, Some(480));
+        assert_eq!(info.channels, Some(3));
+    }
+
+    #[test]
+    fn test_probe_webp_lossless() {
+        let mut data = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f".to_vec();
+        let bits: u32 = 99 | (49 << 14);
+        data.extend_from_slice(&bits.to_le_bytes());
+        let info = probe_webp(&data, 100).unwrap();
+        assert_eq!(info.width, Some(100));
+        assert_eq!(info.height, Some(50));
+    }
+
+    #[test]
+    fn test_probe_wav() {
+        let mut data = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0".to_vec();
+        data.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        data.extend_from_slice(&2u16.to_le_bytes()); // channels
+        data.extend_from_slice(&44_100u32.to_le_bytes());
+        data.extend_from_slice(&176_400u32.to_le_bytes()); // byte rate
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&16u16.to_le_bytes());
+        data.extend_from_slice(b"data");
+        data.extend_from_slice(&352_800u32.to_le_bytes());
+
+        let info = probe_wav(&data, 100).unwrap();
+        assert_eq!(info.channels, Some(2));
+        assert_eq!(info.sample_rate, Some(44_100));
+        assert_eq!(info.duration_secs, Some(2.0));
+    }
+
+    #[test]
+    fn test_probe_ogg_vorbis() {
+        let mut head = b"OggS\0\x02".to_vec();
+        head.extend_from_slice(&[0; 20]);
+        head.push(1); // one segment
+        head.push(30);
+        head.extend_from_slice(b"\x01vorbis\0\0\0\0\x01");
+        head.extend_from_slice(&22_050u32.to_le_bytes());
+
+        let mut tail = b"OggS\0\x04".to_vec();
+        tail.extend_from_slice(&66_150u64.to_le_bytes());
+
+        let info = probe_ogg(&head, &tail, 100).unwrap();
+        assert_eq!(info.format, "Ogg Vorbis");
+        assert_eq!(info.channels, Some(1));
+        assert_eq!(info.duration_secs, Some(3.0));
+    }
+
+    #[test]
+    fn test_probe_meshes() {
+        let obj = probe_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n", 10).unwrap();
+        assert_eq!(obj.vertex_count, Some(3));
+        assert_eq!(obj.face_count, Some(1));
+
+        let gltf = probe_gltf(
+            r#"{"accessors":[{"count":24},{"count":36}],
+                "meshes":[{"primitives":[{"attributes":{"POSITION":0},"indices":1}]}]}"#,
+            10,
+        )
+        .unwrap();
+        assert_eq!(gltf.vertex_count, Some(24));
+        assert_eq!(gltf.face_count, Some(12));
+    }
+}
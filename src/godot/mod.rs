@@ -1,7 +1,17 @@
 //! Godot file parsers
 
+pub mod collision;
 pub mod commands;
+pub mod csharp;
+pub mod export_presets;
+pub mod expression;
 pub mod gdscript;
+pub mod import;
+pub mod input_map;
+pub mod media;
+pub mod project_settings;
+pub mod scene_patch;
+pub mod scene_template;
 pub mod tres;
 pub mod tscn;
 pub mod types;
@@ -0,0 +1,313 @@
+//! project.godot editing
+//!
+//! Parses project.godot into its sections and `key=value` settings and
+//! updates single settings, keeping comments, blank lines and the order of
+//! everything else as written. Values spanning several lines (input actions,
+//! dictionaries) are kept whole. Settings are addressed by their Godot path:
+//! the section, then the key (`application/config/name` is `config/name` in
+//! `[application]`); keys before the first section (`config_version`) have
+//! no section part.
+
+/// One logical line of project.godot
+#[derive(Debug, Clone, PartialEq)]
+enum Line {
+    Section(String),
+    /// `key=value`; the value may span lines
+    Setting {
+        key: String,
+        value: String,
+    },
+    /// Comments, blank lines and anything else, as written
+    Other(String),
+}
+
+impl Line {
+    fn is_blank(&self) -> bool {
+        matches!(self, Line::Other(text) if text.trim().is_empty())
+    }
+}
+
+/// A parsed project.godot
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSettings {
+    lines: Vec<Line>,
+}
+
+/// Split a setting path into its section ("" before the first section) and key
+pub fn split_path(path: &str) -> (&str, &str) {
+    path.split_once('/').unwrap_or(("", path))
+}
+
+impl ProjectSettings {
+    pub fn parse(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut source = content.lines();
+        while let Some(line) = source.next() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                lines.push(Line::Section(trimmed[1..trimmed.len() - 1].to_string()));
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=').filter(|_| !trimmed.starts_with(';'))
+            else {
+                lines.push(Line::Other(line.to_string()));
+                continue;
+            };
+            let mut value = value.to_string();
+            while !is_complete(&value) {
+                let Some(next) = source.next() else { break };
+                value.push('\n');
+                value.push_str(next);
+            }
+            lines.push(Line::Setting {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            });
+        }
+        Self { lines }
+    }
+
+    /// Section names in file order
+    pub fn sections(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Section(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `key=value` pairs of a section ("" for the keys before the first section), in file order
+    pub fn entries(&self, section: &str) -> Vec<(&str, &str)> {
+        let (start, end) = self.section_range(section).unwrap_or((0, 0));
+        self.lines[start..end]
+            .iter()
+            .filter_map(|line| match line {
+                Line::Setting { key, value } => Some((key.as_str(), value.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Raw value of a setting
+    pub fn get(&self, path: &str) -> Option<&str> {
+        let (section, key) = split_path(path);
+        self.entries(section)
+            .into_iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Set a setting, adding the key (or section) when missing.
+    /// Returns whether the file changed.
+    pub fn set(&mut self, path: &str, value: &str) -> bool {
+        let (section, key) = split_path(path);
+        let setting = Line::Setting {
+            key: key.to_string(),
+            value: value.trim().to_string(),
+        };
+        let Some((start, end)) = self.section_range(section) else {
+            self.add_section(section);
+            self.lines.push(setting);
+            return true;
+        };
+
+        match self.find(start, end, key) {
+            Some(i) if self.lines[i] == setting => false,
+            Some(i) => {
+                self.lines[i] = setting;
+                true
+            }
+            None => {
+                let last = (start..end).rev().find(|&i| !self.lines[i].is_blank());
+                let insert_at = match last {
+                    // After the section's last setting, before trailing blank lines
+                    Some(i) => i + 1,
+                    // Empty section: keep a blank line after the header
+                    None if start > 0 => {
+                        if start == end || !self.lines[start].is_blank() {
+                            self.lines.insert(start, Line::Other(String::new()));
+                        }
+                        start + 1
+                    }
+                    None => start,
+                };
+                self.lines.insert(insert_at, setting);
+                if matches!(self.lines.get(insert_at + 1), Some(Line::Section(_))) {
+                    self.lines.insert(insert_at + 1, Line::Other(String::new()));
+                }
+                true
+            }
+        }
+    }
+
+    /// Append an empty section at the end of the file; returns whether it was missing
+    pub fn add_section(&mut self, section: &str) -> bool {
+        if self.section_range(section).is_some() {
+            return false;
+        }
+        if self.lines.last().is_some_and(|l| !l.is_blank()) {
+            self.lines.push(Line::Other(String::new()));
+        }
+        self.lines.push(Line::Section(section.to_string()));
+        self.lines.push(Line::Other(String::new()));
+        true
+    }
+
+    /// Remove a setting; returns whether it existed
+    pub fn remove(&mut self, path: &str) -> bool {
+        let (section, key) = split_path(path);
+        let Some((start, end)) = self.section_range(section) else {
+            return false;
+        };
+        match self.find(start, end, key) {
+            Some(i) => {
+                self.lines.remove(i);
+                // Don't leave two blank lines where the setting was
+                if i > 0
+                    && self.lines[i - 1].is_blank()
+                    && self.lines.get(i).is_some_and(Line::is_blank)
+                {
+                    self.lines.remove(i);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Convert back to project.godot format
+    pub fn to_project_godot(&self) -> String {
+        let mut output = String::new();
+        for line in &self.lines {
+            match line {
+                Line::Section(name) => {
+                    output.push('[');
+                    output.push_str(name);
+                    output.push(']');
+                }
+                Line::Setting { key, value } => {
+                    output.push_str(key);
+                    output.push('=');
+                    output.push_str(value);
+                }
+                Line::Other(text) => output.push_str(text),
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    fn find(&self, start: usize, end: usize, key: &str) -> Option<usize> {
+        (start..end).find(|&i| matches!(&self.lines[i], Line::Setting { key: k, .. } if k == key))
+    }
+
+    /// Line range of a section's body (after the header, up to the next header)
+    fn section_range(&self, section: &str) -> Option<(usize, usize)> {
+        let start = if section.is_empty() {
+            0
+        } else {
+            self.lines
+                .iter()
+                .position(|l| matches!(l, Line::Section(name) if name == section))?
+                + 1
+        };
+        let end = self.lines[start..]
+            .iter()
+            .position(|l| matches!(l, Line::Section(_)))
+            .map_or(self.lines.len(), |i| start + i);
+        Some((start, end))
+    }
+}
+
+/// Whether a value's brackets and strings are all closed
+fn is_complete(value: &str) -> bool {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in value.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0 && !in_string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"; Engine configuration file.
+; It's best edited using the editor UI and not directly,
+
+config_version=5
+
+[application]
+
+config/name="Demo"
+run/main_scene="res://main.tscn"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": [Object(InputEventKey,"keycode":32,"unicode":32)]
+}
+
+[rendering]
+
+textures/canvas_textures/default_texture_filter=0
+"#;
+
+    #[test]
+    fn test_parse_sections_and_multiline_values() {
+        let settings = ProjectSettings::parse(PROJECT);
+        assert_eq!(
+            settings.sections(),
+            vec!["application", "input", "rendering"]
+        );
+        assert_eq!(settings.get("config_version"), Some("5"));
+        assert_eq!(settings.get("application/config/name"), Some("\"Demo\""));
+        assert!(settings
+            .get("input/jump")
+            .is_some_and(|v| v.starts_with('{') && v.ends_with('}') && v.contains("keycode")));
+        assert_eq!(settings.entries("input").len(), 1);
+        assert_eq!(settings.get("application/missing"), None);
+        // Unchanged files are written back as they were
+        assert_eq!(settings.to_project_godot(), PROJECT);
+    }
+
+    #[test]
+    fn test_set_and_remove() {
+        let mut settings = ProjectSettings::parse(PROJECT);
+        assert!(settings.set("application/config/name", "\"Renamed\""));
+        assert!(!settings.set("application/config/name", "\"Renamed\""));
+        assert!(settings.set("application/config/icon", "\"res://icon.svg\""));
+        assert!(settings.set("display/window/size/viewport_width", "1920"));
+        assert!(settings.remove("input/jump"));
+        assert!(!settings.remove("input/jump"));
+
+        let output = settings.to_project_godot();
+        assert!(output.contains(
+            "config/name=\"Renamed\"\nrun/main_scene=\"res://main.tscn\"\nconfig/icon=\"res://icon.svg\"\n\n[input]"
+        ));
+        assert!(output.ends_with("\n\n[display]\n\nwindow/size/viewport_width=1920\n"));
+        assert!(!output.contains("jump"));
+    }
+
+    #[test]
+    fn test_set_in_empty_section() {
+        let mut settings = ProjectSettings::parse("config_version=5\n\n[autoload]\n\n[editor]\n");
+        assert!(settings.set("autoload/Game", "\"*res://game.gd\""));
+        assert_eq!(
+            settings.to_project_godot(),
+            "config_version=5\n\n[autoload]\n\nGame=\"*res://game.gd\"\n\n[editor]\n"
+        );
+    }
+}
@@ -0,0 +1,763 @@
+//! Declarative scene patches
+//!
+//! A patch is a JSON list of operations on one scene that address nodes with
+//! selectors (path, type, group, subtree). Operations run on a copy of the
+//! scene in a fixed phase order — node additions, property and group changes,
+//! signal connections, renames and moves, removals — so every path in a patch
+//! refers to the scene before renames and moves, and operations that would
+//! depend on their order (two values for one property, renaming a removed
+//! node) are rejected as conflicts. The scene is only changed when every
+//! operation succeeds. The JSON schema is `docs/gql/scene_patch.schema.json`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::tscn::{is_within, node_path, Connection, GodotScene, SceneNode};
+
+/// JSON schema of the patch format
+pub const SCHEMA: &str = include_str!("../../docs/gql/scene_patch.schema.json");
+
+/// Operations on one scene
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenePatch {
+    pub operations: Vec<PatchOperation>,
+}
+
+/// Nodes an operation applies to; every given criterion must match
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Selector {
+    /// Node path relative to the root ("." for the root)
+    pub path: Option<String>,
+    /// Node class as written in the scene
+    #[serde(rename = "type")]
+    pub node_type: Option<String>,
+    pub group: Option<String>,
+    /// Descendants of this path, without the node itself
+    pub under: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "op",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    deny_unknown_fields
+)]
+pub enum PatchOperation {
+    AddNode {
+        parent: String,
+        name: String,
+        #[serde(rename = "type")]
+        node_type: String,
+        #[serde(default)]
+        properties: BTreeMap<String, Value>,
+        #[serde(default)]
+        groups: Vec<String>,
+    },
+    SetProperty {
+        select: Selector,
+        property: String,
+        value: Value,
+    },
+    RemoveProperty {
+        select: Selector,
+        property: String,
+    },
+    AddToGroup {
+        select: Selector,
+        group: String,
+    },
+    RemoveFromGroup {
+        select: Selector,
+        group: String,
+    },
+    ConnectSignal {
+        from: String,
+        signal: String,
+        to: String,
+        method: String,
+    },
+    DisconnectSignal {
+        from: String,
+        signal: String,
+        to: String,
+        method: String,
+    },
+    RenameNode {
+        select: Selector,
+        new_name: String,
+    },
+    ReparentNode {
+        select: Selector,
+        new_parent: String,
+    },
+    RemoveNode {
+        select: Selector,
+    },
+}
+
+/// Why a patch was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchError {
+    /// Index of the failing operation (None for a malformed patch)
+    pub operation_index: Option<usize>,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl PatchError {
+    fn new(operation_index: usize, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            operation_index: Some(operation_index),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl Selector {
+    fn is_empty(&self) -> bool {
+        self.path.is_none()
+            && self.node_type.is_none()
+            && self.group.is_none()
+            && self.under.is_none()
+    }
+
+    fn matches(&self, node: &SceneNode) -> bool {
+        let path = node_path(node);
+        self.path.as_ref().is_none_or(|p| *p == path)
+            && self.node_type.as_ref().is_none_or(|t| *t == node.node_type)
+            && self.group.as_ref().is_none_or(|g| node.groups.contains(g))
+            && self
+                .under
+                .as_ref()
+                .is_none_or(|under| path != *under && (under == "." || is_within(&path, under)))
+    }
+
+    fn describe(&self) -> String {
+        let criteria: Vec<String> = [
+            ("path", &self.path),
+            ("type", &self.node_type),
+            ("group", &self.group),
+            ("under", &self.under),
+        ]
+        .iter()
+        .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}={}", key, v)))
+        .collect();
+        format!("{{{}}}", criteria.join(", "))
+    }
+}
+
+impl PatchOperation {
+    fn selector(&self) -> Option<&Selector> {
+        match self {
+            PatchOperation::SetProperty { select, .. }
+            | PatchOperation::RemoveProperty { select, .. }
+            | PatchOperation::AddToGroup { select, .. }
+            | PatchOperation::RemoveFromGroup { select, .. }
+            | PatchOperation::RenameNode { select, .. }
+            | PatchOperation::ReparentNode { select, .. }
+            | PatchOperation::RemoveNode { select } => Some(select),
+            PatchOperation::AddNode { .. }
+            | PatchOperation::ConnectSignal { .. }
+            | PatchOperation::DisconnectSignal { .. } => None,
+        }
+    }
+}
+
+impl ScenePatch {
+    /// Parse a patch from its JSON form
+    pub fn from_json(value: &Value) -> Result<Self, PatchError> {
+        serde_json::from_value(value.clone()).map_err(|e| PatchError {
+            operation_index: None,
+            code: "INVALID_PATCH",
+            message: format!("Invalid scene patch: {}", e),
+        })
+    }
+
+    /// Apply every operation to `scene`, or none of them
+    pub fn apply(&self, scene: &mut GodotScene) -> Result<(), Vec<PatchError>> {
+        let mut patched = scene.clone();
+        let mut errors = Vec::new();
+
+        self.add_nodes(&mut patched, &mut errors);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let targets = self.select_nodes(&patched, &mut errors);
+        self.check_conflicts(&targets, &mut errors);
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.edit_nodes(&mut patched, &targets, &mut errors);
+        self.edit_connections(&mut patched, &mut errors);
+        if errors.is_empty() {
+            self.move_nodes(&mut patched, &targets, &mut errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        *scene = patched;
+        Ok(())
+    }
+
+    /// Add nodes whose parent exists, until none is left; a parent may be
+    /// added by the patch itself, in any order
+    fn add_nodes(&self, scene: &mut GodotScene, errors: &mut Vec<PatchError>) {
+        let mut pending = Vec::new();
+        for (index, op) in self.operations.iter().enumerate() {
+            let PatchOperation::AddNode {
+                name,
+                node_type,
+                properties,
+                ..
+            } = op
+            else {
+                continue;
+            };
+            if !is_valid_name(name) {
+                errors.push(PatchError::new(
+                    index,
+                    "INVALID_NAME",
+                    format!("Invalid node name: '{}'", name),
+                ));
+            } else if node_type.is_empty() {
+                errors.push(PatchError::new(index, "INVALID_TYPE", "Node type is empty"));
+            } else if let Some(error) = properties
+                .values()
+                .find_map(|value| godot_value(value).err())
+            {
+                errors.push(PatchError::new(index, "INVALID_VALUE", error));
+            } else {
+                pending.push(index);
+            }
+        }
+
+        loop {
+            let before = pending.len();
+            pending.retain(|&index| {
+                let PatchOperation::AddNode {
+                    parent,
+                    name,
+                    node_type,
+                    properties,
+                    groups,
+                } = &self.operations[index]
+                else {
+                    return false;
+                };
+                if !has_node(scene, parent) {
+                    return true;
+                }
+                let path = child_path(parent, name);
+                if has_node(scene, &path) {
+                    errors.push(PatchError::new(
+                        index,
+                        "NODE_EXISTS",
+                        format!("'{}' already exists", path),
+                    ));
+                    return false;
+                }
+                scene.add_node(SceneNode {
+                    name: name.clone(),
+                    node_type: node_type.clone(),
+                    parent: Some(parent.clone()),
+                    instance: None,
+                    groups: groups.clone(),
+                    extra_attrs: Vec::new(),
+                    attr_order: Vec::new(),
+                    properties: properties
+                        .iter()
+                        .filter_map(|(key, value)| Some((key.clone(), godot_value(value).ok()?)))
+                        .collect(),
+                });
+                false
+            });
+            if pending.len() == before {
+                break;
+            }
+        }
+        for index in pending {
+            if let PatchOperation::AddNode { parent, .. } = &self.operations[index] {
+                errors.push(PatchError::new(
+                    index,
+                    "PARENT_NOT_FOUND",
+                    format!("Parent not found: {}", parent),
+                ));
+            }
+        }
+    }
+
+    /// Paths of the nodes each operation selects (empty for operations without a selector)
+    fn select_nodes(&self, scene: &GodotScene, errors: &mut Vec<PatchError>) -> Vec<Vec<String>> {
+        let mut targets = Vec::with_capacity(self.operations.len());
+        for (index, op) in self.operations.iter().enumerate() {
+            let Some(selector) = op.selector() else {
+                targets.push(Vec::new());
+                continue;
+            };
+            if selector.is_empty() {
+                errors.push(PatchError::new(
+                    index,
+                    "INVALID_SELECTOR",
+                    "Selector needs at least one of path, type, group, under",
+                ));
+                targets.push(Vec::new());
+                continue;
+            }
+
+            let paths: Vec<String> = scene
+                .nodes
+                .iter()
+                .filter(|n| selector.matches(n))
+                .map(node_path)
+                .collect();
+            if paths.is_empty() {
+                errors.push(PatchError::new(
+                    index,
+                    "SELECTOR_NO_MATCH",
+                    format!("Selector matches no node: {}", selector.describe()),
+                ));
+            } else if matches!(op, PatchOperation::RenameNode { .. }) && paths.len() > 1 {
+                errors.push(PatchError::new(
+                    index,
+                    "SELECTOR_AMBIGUOUS",
+                    format!("Rename selects {} nodes: {}", paths.len(), paths.join(", ")),
+                ));
+            } else if matches!(
+                op,
+                PatchOperation::RemoveNode { .. } | PatchOperation::ReparentNode { .. }
+            ) && paths.iter().any(|p| p == ".")
+            {
+                errors.push(PatchError::new(
+                    index,
+                    "INVALID_OPERATION",
+                    "The root node cannot be removed or moved",
+                ));
+            }
+            targets.push(paths);
+        }
+        targets
+    }
+
+    /// Reject operations whose outcome would depend on their order
+    fn check_conflicts(&self, targets: &[Vec<String>], errors: &mut Vec<PatchError>) {
+        let mut properties: HashMap<(&str, &str), (usize, Option<String>)> = HashMap::new();
+        let mut groups: HashMap<(&str, &str), (usize, bool)> = HashMap::new();
+        let mut structure: HashMap<&str, (usize, &'static str)> = HashMap::new();
+        let mut connections: HashMap<[&str; 4], (usize, bool)> = HashMap::new();
+
+        let conflict = |index: usize, other: usize, what: String| {
+            PatchError::new(
+                index,
+                "CONFLICT",
+                format!("Conflicts with operation {}: {}", other, what),
+            )
+        };
+        for (index, op) in self.operations.iter().enumerate() {
+            match op {
+                PatchOperation::SetProperty { property, .. }
+                | PatchOperation::RemoveProperty { property, .. } => {
+                    let value = match op {
+                        PatchOperation::SetProperty { value, .. } => match godot_value(value) {
+                            Ok(value) => Some(value),
+                            Err(e) => {
+                                errors.push(PatchError::new(index, "INVALID_VALUE", e));
+                                continue;
+                            }
+                        },
+                        _ => None,
+                    };
+                    for path in &targets[index] {
+                        let key = (path.as_str(), property.as_str());
+                        match properties.get(&key) {
+                            Some((other, previous)) if *previous != value => errors.push(conflict(
+                                index,
+                                *other,
+                                format!("both change '{}' of {}", property, path),
+                            )),
+                            Some(_) => {}
+                            None => {
+                                properties.insert(key, (index, value.clone()));
+                            }
+                        }
+                    }
+                }
+                PatchOperation::AddToGroup { group, .. }
+                | PatchOperation::RemoveFromGroup { group, .. } => {
+                    let add = matches!(op, PatchOperation::AddToGroup { .. });
+                    for path in &targets[index] {
+                        let key = (path.as_str(), group.as_str());
+                        match groups.get(&key) {
+                            Some((other, previous)) if *previous != add => errors.push(conflict(
+                                index,
+                                *other,
+                                format!("both change group '{}' of {}", group, path),
+                            )),
+                            Some(_) => {}
+                            None => {
+                                groups.insert(key, (index, add));
+                            }
+                        }
+                    }
+                }
+                PatchOperation::ConnectSignal {
+                    from,
+                    signal,
+                    to,
+                    method,
+                }
+                | PatchOperation::DisconnectSignal {
+                    from,
+                    signal,
+                    to,
+                    method,
+                } => {
+                    let connect = matches!(op, PatchOperation::ConnectSignal { .. });
+                    let key = [from.as_str(), signal.as_str(), to.as_str(), method.as_str()];
+                    match connections.get(&key) {
+                        Some((other, previous)) if *previous != connect => errors.push(conflict(
+                            index,
+                            *other,
+                            format!("both change {}.{} -> {}::{}", from, signal, to, method),
+                        )),
+                        Some((other, _)) => errors.push(conflict(
+                            index,
+                            *other,
+                            format!("duplicate {}.{}", from, signal),
+                        )),
+                        None => {
+                            connections.insert(key, (index, connect));
+                        }
+                    }
+                }
+                PatchOperation::RenameNode { .. }
+                | PatchOperation::ReparentNode { .. }
+                | PatchOperation::RemoveNode { .. } => {
+                    let kind = match op {
+                        PatchOperation::RenameNode { .. } => "rename",
+                        PatchOperation::ReparentNode { .. } => "move",
+                        _ => "remove",
+                    };
+                    for path in &targets[index] {
+                        match structure.get(path.as_str()) {
+                            // A node may be both renamed and moved
+                            Some((_, other_kind))
+                                if kind != *other_kind
+                                    && kind != "remove"
+                                    && *other_kind != "remove" => {}
+                            Some((other, other_kind)) => errors.push(conflict(
+                                index,
+                                *other,
+                                format!("{} and {} of {}", other_kind, kind, path),
+                            )),
+                            None => {
+                                structure.insert(path, (index, kind));
+                            }
+                        }
+                    }
+                }
+                PatchOperation::AddNode { .. } => {}
+            }
+        }
+    }
+
+    /// Property and group changes
+    fn edit_nodes(
+        &self,
+        scene: &mut GodotScene,
+        targets: &[Vec<String>],
+        errors: &mut Vec<PatchError>,
+    ) {
+        for (index, op) in self.operations.iter().enumerate() {
+            if !matches!(
+                op,
+                PatchOperation::SetProperty { .. }
+                    | PatchOperation::RemoveProperty { .. }
+                    | PatchOperation::AddToGroup { .. }
+                    | PatchOperation::RemoveFromGroup { .. }
+            ) {
+                continue;
+            }
+            for path in &targets[index] {
+                let Some(node) = scene.nodes.iter_mut().find(|n| node_path(n) == *path) else {
+                    errors.push(PatchError::new(
+                        index,
+                        "NODE_NOT_FOUND",
+                        format!("Node not found: {}", path),
+                    ));
+                    continue;
+                };
+                match op {
+                    PatchOperation::SetProperty {
+                        property, value, ..
+                    } => {
+                        if let Ok(value) = godot_value(value) {
+                            node.properties.insert(property.clone(), value);
+                        }
+                    }
+                    PatchOperation::RemoveProperty { property, .. } => {
+                        node.properties.shift_remove(property);
+                    }
+                    PatchOperation::AddToGroup { group, .. } if !node.groups.contains(group) => {
+                        node.groups.push(group.clone());
+                    }
+                    PatchOperation::RemoveFromGroup { group, .. } => {
+                        node.groups.retain(|g| g != group);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn edit_connections(&self, scene: &mut GodotScene, errors: &mut Vec<PatchError>) {
+        for (index, op) in self.operations.iter().enumerate() {
+            match op {
+                PatchOperation::ConnectSignal {
+                    from,
+                    signal,
+                    to,
+                    method,
+                } => {
+                    let connection = Connection {
+                        signal: signal.clone(),
+                        from: from.clone(),
+                        to: to.clone(),
+                        method: method.clone(),
+                        flags: None,
+                        unbinds: None,
+                        binds: None,
+                    };
+                    if let Err(message) = scene.connect_signal(connection) {
+                        errors.push(PatchError::new(index, "CONNECT_FAILED", message));
+                    }
+                }
+                PatchOperation::DisconnectSignal {
+                    from,
+                    signal,
+                    to,
+                    method,
+                } => {
+                    let before = scene.connections.len();
+                    scene.connections.retain(|c| {
+                        !(c.from == *from
+                            && c.signal == *signal
+                            && c.to == *to
+                            && c.method == *method)
+                    });
+                    if scene.connections.len() == before {
+                        errors.push(PatchError::new(
+                            index,
+                            "CONNECTION_NOT_FOUND",
+                            format!("Not connected: {}.{} -> {}::{}", from, signal, to, method),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Renames, then moves, then removals; paths are followed through earlier renames and moves
+    fn move_nodes(
+        &self,
+        scene: &mut GodotScene,
+        targets: &[Vec<String>],
+        errors: &mut Vec<PatchError>,
+    ) {
+        let mut moves: Vec<(String, String)> = Vec::new();
+
+        for (index, op) in self.operations.iter().enumerate() {
+            let PatchOperation::RenameNode { new_name, .. } = op else {
+                continue;
+            };
+            for path in &targets[index] {
+                let current = follow(&moves, path);
+                match scene.rename_node(&current, new_name) {
+                    Ok(new_path) => moves.push((current, new_path)),
+                    Err(message) => errors.push(PatchError::new(index, "RENAME_FAILED", message)),
+                }
+            }
+        }
+
+        for (index, op) in self.operations.iter().enumerate() {
+            let PatchOperation::ReparentNode { new_parent, .. } = op else {
+                continue;
+            };
+            let new_parent = follow(&moves, new_parent);
+            for path in &targets[index] {
+                let current = follow(&moves, path);
+                match scene.reparent_node(&current, &new_parent) {
+                    Ok(new_path) => moves.push((current, new_path)),
+                    Err(message) => errors.push(PatchError::new(index, "REPARENT_FAILED", message)),
+                }
+            }
+        }
+
+        for (index, op) in self.operations.iter().enumerate() {
+            if !matches!(op, PatchOperation::RemoveNode { .. }) {
+                continue;
+            }
+            for path in &targets[index] {
+                let current = follow(&moves, path);
+                // Already gone with a removed ancestor
+                if has_node(scene, &current) {
+                    remove_subtree(scene, &current);
+                }
+            }
+        }
+    }
+}
+
+/// Godot literal for a JSON value: strings as written, numbers and booleans as JSON
+fn godot_value(value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Ok(s.clone()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(format!(
+            "Unsupported value {} (use a Godot literal string, a number or a boolean)",
+            value
+        )),
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['.', ':', '@', '/', '"', '%'])
+}
+
+fn has_node(scene: &GodotScene, path: &str) -> bool {
+    scene.nodes.iter().any(|n| node_path(n) == path)
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    if parent == "." {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+/// Where `path` ended up after the given renames and moves
+fn follow(moves: &[(String, String)], path: &str) -> String {
+    let mut path = path.to_string();
+    for (from, to) in moves {
+        if is_within(&path, from) {
+            path = format!("{}{}", to, &path[from.len()..]);
+        }
+    }
+    path
+}
+
+/// Remove a node, its descendants, their connections and `[editable]` sections
+fn remove_subtree(scene: &mut GodotScene, path: &str) {
+    scene.nodes.retain(|n| !is_within(&node_path(n), path));
+    scene
+        .connections
+        .retain(|c| !is_within(&c.from, path) && !is_within(&c.to, path));
+    scene.unparsed_sections.retain(|section| {
+        section
+            .header
+            .strip_prefix("[editable path=\"")
+            .and_then(|rest| rest.strip_suffix("\"]"))
+            .is_none_or(|editable| !is_within(editable, path))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SCENE: &str = r#"[gd_scene format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Enemies" type="Node2D" parent="."]
+
+[node name="Slime" type="CharacterBody2D" parent="Enemies" groups=["enemies"]]
+
+[node name="Bat" type="CharacterBody2D" parent="Enemies" groups=["enemies"]]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+
+[connection signal="body_entered" from="Enemies/Slime" to="." method="_on_hit"]
+"#;
+
+    fn apply(patch: Value) -> Result<GodotScene, Vec<PatchError>> {
+        let mut scene = GodotScene::parse(SCENE).unwrap();
+        ScenePatch::from_json(&patch)
+            .map_err(|e| vec![e])?
+            .apply(&mut scene)?;
+        Ok(scene)
+    }
+
+    fn paths(scene: &GodotScene) -> Vec<String> {
+        scene.nodes.iter().map(node_path).collect()
+    }
+
+    #[test]
+    fn test_patch_is_order_independent() {
+        // Children before their parent, removals and renames before edits
+        let scene = apply(json!({ "operations": [
+            { "op": "removeNode", "select": { "path": "Enemies/Slime" } },
+            { "op": "addNode", "parent": "UI/Hud", "name": "Score", "type": "Label",
+              "properties": { "text": "\"0\"" } },
+            { "op": "renameNode", "select": { "path": "Player" }, "newName": "Hero" },
+            { "op": "addNode", "parent": "UI", "name": "Hud", "type": "Control" },
+            { "op": "addNode", "parent": ".", "name": "UI", "type": "CanvasLayer" },
+            { "op": "setProperty", "select": { "group": "enemies" }, "property": "speed", "value": 40 },
+            { "op": "reparentNode", "select": { "path": "Player" }, "newParent": "UI" },
+        ]}))
+        .unwrap();
+
+        assert_eq!(
+            paths(&scene),
+            vec![
+                ".",
+                "Enemies",
+                "Enemies/Bat",
+                "UI",
+                "UI/Hud",
+                "UI/Hud/Score",
+                "UI/Hero"
+            ]
+        );
+        let bat = scene.nodes.iter().find(|n| n.name == "Bat").unwrap();
+        assert_eq!(bat.properties.get("speed").unwrap(), "40");
+        assert!(scene.connections.is_empty());
+    }
+
+    #[test]
+    fn test_patch_rejects_conflicts_and_missing_nodes() {
+        let errors = apply(json!({ "operations": [
+            { "op": "setProperty", "select": { "path": "Player" }, "property": "visible", "value": false },
+            { "op": "setProperty", "select": { "type": "CharacterBody2D" }, "property": "visible", "value": true },
+            { "op": "removeNode", "select": { "path": "Enemies/Bat" } },
+            { "op": "renameNode", "select": { "path": "Enemies/Bat" }, "newName": "Crow" },
+            { "op": "setProperty", "select": { "type": "Sprite2D" }, "property": "visible", "value": true },
+        ]}))
+        .unwrap_err();
+
+        let codes: Vec<(Option<usize>, &str)> =
+            errors.iter().map(|e| (e.operation_index, e.code)).collect();
+        assert_eq!(
+            codes,
+            vec![
+                (Some(4), "SELECTOR_NO_MATCH"),
+                (Some(1), "CONFLICT"),
+                (Some(3), "CONFLICT"),
+            ]
+        );
+
+        let error =
+            ScenePatch::from_json(&json!({ "operations": [{ "op": "explode" }] })).unwrap_err();
+        assert_eq!(error.code, "INVALID_PATCH");
+    }
+}
@@ -0,0 +1,449 @@
+//! Scene templates
+//!
+//! A template is .tscn text whose values may hold `{{expression}}`
+//! placeholders over the template's parameters, evaluated with the property
+//! expression grammar ([`super::expression`]) when the template is
+//! instantiated: `size = Vector2({{size * 2}}, {{size}})`. A placeholder
+//! inside a string literal inserts the text of a string value
+//! (`text = "Hello {{name}}"`). Filters follow the expression:
+//! `{{color|default:'Color(1, 1, 1, 1)'}}` uses the default (an expression,
+//! or raw Godot text such as `ExtResource("1")`) when a parameter it reads
+//! was not given.
+
+use std::collections::HashMap;
+
+use super::expression::{self, ExpressionError, PropertySource, Value, VectorKind};
+use super::tscn::GodotScene;
+
+/// Parameter types templates can declare
+pub const PARAM_TYPES: &[&str] = &[
+    "float", "int", "bool", "String", "Vector2", "Vector2i", "Vector3", "Vector3i", "Color",
+];
+
+/// A `{{...}}` placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// Byte range of `{{...}}` in the template
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line
+    pub line: usize,
+    pub expression: String,
+    /// `default:` filter argument
+    pub default: Option<String>,
+    /// Inside a string literal
+    pub in_string: bool,
+}
+
+/// Problem with a template, at a 1-based line
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Find the placeholders of a template
+pub fn placeholders(text: &str) -> Result<Vec<Placeholder>, TemplateError> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find("{{") {
+        let start = offset + open;
+        let line = text[..start].matches('\n').count() + 1;
+        let Some(close) = text[start + 2..].find("}}") else {
+            return Err(TemplateError {
+                line,
+                message: "Unterminated placeholder: missing '}}'".to_string(),
+            });
+        };
+        let end = start + 2 + close + 2;
+        let mut parts = split_filters(&text[start + 2..end - 2]).into_iter();
+        let expression = parts.next().unwrap_or_default().trim().to_string();
+        if expression.is_empty() {
+            return Err(TemplateError {
+                line,
+                message: "Empty placeholder".to_string(),
+            });
+        }
+        let mut default = None;
+        for filter in parts {
+            let (name, argument) = filter.split_once(':').unwrap_or((filter, ""));
+            match name.trim() {
+                "default" if !argument.trim().is_empty() => {
+                    default = Some(unquote(argument.trim()).to_string())
+                }
+                "default" => {
+                    return Err(TemplateError {
+                        line,
+                        message: "default filter needs a value (default:'...')".to_string(),
+                    })
+                }
+                other => {
+                    return Err(TemplateError {
+                        line,
+                        message: format!("Unknown filter '{}'", other),
+                    })
+                }
+            }
+        }
+        let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        found.push(Placeholder {
+            start,
+            end,
+            line,
+            expression,
+            default,
+            in_string: in_string(&text[line_start..start]),
+        });
+        offset = end;
+    }
+    Ok(found)
+}
+
+/// Instantiate a template with parameter values
+pub fn render(text: &str, params: &HashMap<String, Value>) -> Result<String, TemplateError> {
+    let mut output = String::with_capacity(text.len());
+    let mut offset = 0;
+    for placeholder in placeholders(text)? {
+        output.push_str(&text[offset..placeholder.start]);
+        output.push_str(&evaluate(&placeholder, params)?);
+        offset = placeholder.end;
+    }
+    output.push_str(&text[offset..]);
+    Ok(output)
+}
+
+/// Text a placeholder is replaced with
+fn evaluate(
+    placeholder: &Placeholder,
+    params: &HashMap<String, Value>,
+) -> Result<String, TemplateError> {
+    let error = |e: ExpressionError| TemplateError {
+        line: placeholder.line,
+        message: format!("{{{{{}}}}}: {}", placeholder.expression, e),
+    };
+    let missing = expression::property_names(&placeholder.expression)
+        .map_err(error)?
+        .into_iter()
+        .any(|name| !params.contains_key(&name));
+    let value = match (&placeholder.default, missing) {
+        // An expression, or raw Godot text the grammar doesn't cover
+        (Some(default), true) => match expression::evaluate(default, &Params(params)) {
+            Ok(value) => value,
+            Err(_) => return Ok(default.clone()),
+        },
+        _ => expression::evaluate(&placeholder.expression, &Params(params)).map_err(error)?,
+    };
+    Ok(match value {
+        Value::Str(s) if placeholder.in_string => s
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n"),
+        value => value.to_godot(),
+    })
+}
+
+/// Stand-in value of a parameter type ("float", "Vector2", ...) for linting;
+/// non-zero so divisions by a parameter check out
+pub fn sample_value(param_type: &str) -> Option<Value> {
+    let vector = |kind, len| Some(Value::Vector(kind, vec![1.0; len]));
+    match param_type {
+        "float" | "int" => Some(Value::Number(1.0)),
+        "bool" => Some(Value::Bool(true)),
+        "String" => Some(Value::Str("text".to_string())),
+        "Vector2" => vector(VectorKind::Vector2, 2),
+        "Vector2i" => vector(VectorKind::Vector2i, 2),
+        "Vector3" => vector(VectorKind::Vector3, 3),
+        "Vector3i" => vector(VectorKind::Vector3i, 3),
+        "Color" => vector(VectorKind::Color, 4),
+        _ => None,
+    }
+}
+
+/// Whether a value fits a parameter type
+pub fn matches_type(value: &Value, param_type: &str) -> bool {
+    match (value, sample_value(param_type)) {
+        (Value::Number(n), Some(Value::Number(_))) => param_type != "int" || n.fract() == 0.0,
+        (Value::Vector(kind, _), Some(Value::Vector(expected, _))) => *kind == expected,
+        (value, Some(sample)) => std::mem::discriminant(value) == std::mem::discriminant(&sample),
+        (_, None) => false,
+    }
+}
+
+/// Problem found by [`lint`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintIssue {
+    /// INVALID_PLACEHOLDER, UNDECLARED_PARAMETER, UNUSED_PARAMETER,
+    /// INVALID_EXPRESSION or INVALID_SCENE
+    pub code: &'static str,
+    /// 1-based line; None for issues with the whole template
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Check a template against its declared parameters, each with a sample
+/// value of its type (None when untyped): every parameter used must be
+/// declared, and every expression must evaluate with the samples. Unused
+/// parameters are reported too.
+pub fn lint(text: &str, declared: &HashMap<String, Option<Value>>) -> Vec<LintIssue> {
+    let found = match placeholders(text) {
+        Ok(found) => found,
+        Err(error) => {
+            return vec![LintIssue {
+                code: "INVALID_PLACEHOLDER",
+                line: Some(error.line),
+                message: error.message,
+            }]
+        }
+    };
+
+    let mut issues = Vec::new();
+    let mut used: Vec<String> = Vec::new();
+    for placeholder in &found {
+        let mut issue = |code, message| {
+            issues.push(LintIssue {
+                code,
+                line: Some(placeholder.line),
+                message,
+            })
+        };
+        let names = match expression::property_names(&placeholder.expression) {
+            Ok(names) => names,
+            Err(e) => {
+                issue(
+                    "INVALID_EXPRESSION",
+                    format!("{{{{{}}}}}: {}", placeholder.expression, e),
+                );
+                continue;
+            }
+        };
+        let undeclared: Vec<&String> = names
+            .iter()
+            .filter(|n| !declared.contains_key(*n))
+            .collect();
+        for name in &undeclared {
+            issue(
+                "UNDECLARED_PARAMETER",
+                format!("Parameter '{}' is used but not declared", name),
+            );
+        }
+        used.extend(names.iter().cloned());
+        if !undeclared.is_empty() {
+            continue;
+        }
+
+        // Untyped parameters only get a syntax check
+        let typed = names.iter().all(|n| declared[n].is_some());
+        let samples: HashMap<String, Value> = names
+            .iter()
+            .map(|n| (n.clone(), declared[n].clone().unwrap_or(Value::Number(1.0))))
+            .collect();
+        match expression::evaluate(&placeholder.expression, &Params(&samples)) {
+            Err(e @ ExpressionError::Syntax { .. }) => issue(
+                "INVALID_EXPRESSION",
+                format!("{{{{{}}}}}: {}", placeholder.expression, e),
+            ),
+            Err(e) if typed => issue(
+                "INVALID_EXPRESSION",
+                format!("{{{{{}}}}}: {}", placeholder.expression, e),
+            ),
+            _ => {}
+        }
+    }
+
+    let mut unused: Vec<&String> = declared
+        .keys()
+        .filter(|name| !used.contains(name))
+        .collect();
+    unused.sort();
+    for name in unused {
+        issues.push(LintIssue {
+            code: "UNUSED_PARAMETER",
+            line: None,
+            message: format!("Parameter '{}' is declared but never used", name),
+        });
+    }
+
+    // With the samples filled in, the template must be a valid scene
+    if issues.iter().all(|i| i.code == "UNUSED_PARAMETER") {
+        let samples: HashMap<String, Value> = declared
+            .iter()
+            .map(|(name, sample)| (name.clone(), sample.clone().unwrap_or(Value::Number(1.0))))
+            .collect();
+        if let Ok(rendered) = render(text, &samples) {
+            if let Err(e) = GodotScene::parse(&rendered) {
+                issues.push(LintIssue {
+                    code: "INVALID_SCENE",
+                    line: None,
+                    message: format!("Not a valid scene once instantiated: {}", e),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Parameters as the expression's own properties
+struct Params<'a>(&'a HashMap<String, Value>);
+
+impl PropertySource for Params<'_> {
+    fn property(&self, node_path: &str, property: &str) -> Result<Value, ExpressionError> {
+        if node_path != "." {
+            return Err(ExpressionError::Eval(
+                "Node references are not available in templates".to_string(),
+            ));
+        }
+        self.0
+            .get(property)
+            .cloned()
+            .ok_or_else(|| ExpressionError::Eval(format!("Missing parameter: {}", property)))
+    }
+}
+
+/// Split `expr|filter:arg|...` on the bars outside quotes
+fn split_filters(content: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in content.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '|') => {
+                parts.push(&content[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&content[start..]);
+    parts
+}
+
+fn unquote(argument: &str) -> &str {
+    ['\'', '"']
+        .iter()
+        .find_map(|&q| argument.strip_prefix(q)?.strip_suffix(q))
+        .unwrap_or(argument)
+}
+
+/// Whether the end of `line` is inside a string literal
+fn in_string(line: &str) -> bool {
+    let mut inside = false;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if inside => escaped = true,
+            '"' => inside = !inside,
+            _ => {}
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"[gd_scene format=3]
+
+[node name="Platform" type="Sprite2D"]
+scale = Vector2({{size * 2}}, {{size}})
+modulate = {{color|default:'Color(1, 1, 1, 1)'}}
+texture = {{texture | default:'ExtResource("1")'}}
+
+[node name="Label" type="Label" parent="."]
+text = "Size {{size}}: {{label}}"
+"#;
+
+    fn params(values: &[(&str, Value)]) -> HashMap<String, Value> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let found = placeholders(TEMPLATE).unwrap();
+        assert_eq!(found.len(), 6);
+        assert_eq!(found[0].expression, "size * 2");
+        assert_eq!(found[0].line, 4);
+        assert_eq!(found[2].default.as_deref(), Some("Color(1, 1, 1, 1)"));
+        assert_eq!(found[3].default.as_deref(), Some("ExtResource(\"1\")"));
+        assert!(!found[0].in_string);
+        assert!(found[5].in_string);
+
+        let error = placeholders("a = 1\nb = {{size|upper}}").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "Unknown filter 'upper'");
+        assert!(placeholders("a = {{size").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(
+            TEMPLATE,
+            &params(&[
+                ("size", Value::Number(16.0)),
+                ("label", Value::Str("Big \"one\"".to_string())),
+            ]),
+        )
+        .unwrap();
+        assert!(rendered.contains("scale = Vector2(32, 16)\n"));
+        assert!(rendered.contains("modulate = Color(1, 1, 1, 1)\n"));
+        assert!(rendered.contains("texture = ExtResource(\"1\")\n"));
+        assert!(rendered.contains("text = \"Size 16: Big \\\"one\\\"\"\n"));
+
+        let error = render(TEMPLATE, &params(&[("size", Value::Number(16.0))])).unwrap_err();
+        assert_eq!(error.line, 9);
+        assert!(error.message.contains("Missing parameter: label"));
+    }
+
+    #[test]
+    fn test_types() {
+        assert!(matches_type(&Value::Number(2.0), "int"));
+        assert!(!matches_type(&Value::Number(2.5), "int"));
+        assert!(matches_type(&sample_value("Color").unwrap(), "Color"));
+        assert!(!matches_type(&sample_value("Vector2").unwrap(), "Vector3"));
+        assert!(sample_value("Node").is_none());
+    }
+
+    #[test]
+    fn test_lint() {
+        let declared: HashMap<String, Option<Value>> = [
+            ("size", sample_value("float")),
+            ("texture", None),
+            ("speed", sample_value("float")),
+        ]
+        .into_iter()
+        .map(|(name, sample)| (name.to_string(), sample))
+        .collect();
+        let issues = lint(TEMPLATE, &declared);
+        let codes: Vec<(&str, Option<usize>)> = issues.iter().map(|i| (i.code, i.line)).collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("UNDECLARED_PARAMETER", Some(5)),
+                ("UNDECLARED_PARAMETER", Some(9)),
+                ("UNUSED_PARAMETER", None),
+            ]
+        );
+        assert!(issues[0].message.contains("'color'"));
+        assert!(issues[2].message.contains("'speed'"));
+
+        let declared: HashMap<String, Option<Value>> = [("flag".to_string(), sample_value("bool"))]
+            .into_iter()
+            .collect();
+        let issues = lint(
+            "[gd_scene format=3]\n\n[node name=\"A\" type=\"Node2D\"]\nx = {{flag * 2}}\n",
+            &declared,
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "INVALID_EXPRESSION");
+
+        let issues = lint("[node type=\"Node2D\"]\nx = {{flag}}\n", &declared);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "INVALID_SCENE");
+    }
+}
@@ -4,6 +4,8 @@
 
 use std::collections::HashMap;
 
+use super::tscn::is_property_key;
+
 /// Godot Resource (.tres)
 #[derive(Debug, Clone)]
 pub struct GodotResource {
@@ -13,6 +15,10 @@ pub struct GodotResource {
     pub load_steps: Option<i32>,
     /// format version
     pub format: Option<i32>,
+    /// `uid://...` of the resource itself
+    pub uid: Option<String>,
+    /// `class_name` of the resource's script, for custom resources
+    pub script_class: Option<String>,
     /// External resource referral
     pub ext_resources: Vec<ExtResourceRef>,
     /// Sub-resources
@@ -27,6 +33,8 @@ pub struct ExtResourceRef {
     pub id: String,
     pub resource_type: String,
     pub path: String,
+    /// `uid://...` of the referenced resource
+    pub uid: Option<String>,
 }
 
 /// Sub-resource definition
@@ -60,6 +68,8 @@ impl GodotResource {
             resource_type: resource_type.to_string(),
             load_steps: None,
             format: Some(3),
+            uid: None,
+            script_class: None,
             ext_resources: Vec::new(),
             sub_resources: Vec::new(),
             properties: HashMap::new(),
@@ -77,12 +87,14 @@ impl GodotResource {
             id: id.to_string(),
             resource_type: resource_type.to_string(),
             path: path.to_string(),
+            uid: None,
         });
     }
 
     /// Add a sub-resource and return a mutable reference to it
     pub fn add_sub_resource(&mut self, id: &str, resource_type: &str) -> &mut SubResourceDef {
-        self.sub_resources.push(SubResourceDef::new(id, resource_type));
+        self.sub_resources
+            .push(SubResourceDef::new(id, resource_type));
         self.sub_resources.last_mut().unwrap()
     }
 
@@ -94,19 +106,27 @@ impl GodotResource {
         let load_steps = 1 + self.ext_resources.len() + self.sub_resources.len();
 
         // Header
+        output.push_str(&format!("[gd_resource type=\"{}\"", self.resource_type));
+        if let Some(script_class) = &self.script_class {
+            output.push_str(&format!(" script_class=\"{}\"", script_class));
+        }
         output.push_str(&format!(
-            "[gd_resource type=\"{}\" load_steps={} format={}]\n",
-            self.resource_type,
+            " load_steps={} format={}",
             load_steps,
             self.format.unwrap_or(3)
         ));
+        if let Some(uid) = &self.uid {
+            output.push_str(&format!(" uid=\"{}\"", uid));
+        }
+        output.push_str("]\n");
 
         // External resources
         for ext in &self.ext_resources {
-            output.push_str(&format!(
-                "\n[ext_resource type=\"{}\" path=\"{}\" id=\"{}\"]\n",
-                ext.resource_type, ext.path, ext.id
-            ));
+            output.push_str(&format!("\n[ext_resource type=\"{}\"", ext.resource_type));
+            if let Some(uid) = &ext.uid {
+                output.push_str(&format!(" uid=\"{}\"", uid));
+            }
+            output.push_str(&format!(" path=\"{}\" id=\"{}\"]\n", ext.path, ext.id));
         }
 
         // Sub-resources
@@ -135,110 +155,107 @@ impl GodotResource {
             resource_type: String::new(),
             load_steps: None,
             format: None,
+            uid: None,
+            script_class: None,
             ext_resources: Vec::new(),
             sub_resources: Vec::new(),
             properties: HashMap::new(),
         };
 
-        let lines: Vec<&str> = content.lines().collect();
-        let mut i = 0;
-        let mut current_section: Option<String> = None;
+        let mut current_section: Option<&str> = None;
+        let mut current_sub: Option<SubResourceDef> = None;
         let mut current_props: HashMap<String, String> = HashMap::new();
-        let mut current_sub_id = String::new();
-        let mut current_sub_type = String::new();
+        // Key of the last property, for values spanning several lines
+        let mut last_key: Option<String> = None;
 
-        while i < lines.len() {
-            let line = lines[i].trim();
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
 
             // Skip empty lines and comments
             if line.is_empty() || line.starts_with(';') {
-                i += 1;
                 continue;
             }
 
-            // [gd_resource ...] header
-            if line.starts_with("[gd_resource") {
-                if let Some(rt) = extract_attr(line, "type") {
-                    resource.resource_type = rt;
+            if line.starts_with('[') && line.ends_with(']') {
+                // Save previous sub-resource
+                if let Some(mut sub) = current_sub.take() {
+                    sub.properties = std::mem::take(&mut current_props);
+                    resource.sub_resources.push(sub);
                 }
-                if let Some(ls) = extract_attr(line, "load_steps") {
-                    resource.load_steps = ls.parse().ok();
+                last_key = None;
+                current_section = None;
+
+                // [gd_resource ...] header
+                if line.starts_with("[gd_resource") {
+                    if let Some(rt) = extract_attr(line, "type") {
+                        resource.resource_type = rt;
+                    }
+                    if let Some(ls) = extract_attr(line, "load_steps") {
+                        resource.load_steps = ls.parse().ok();
+                    }
+                    if let Some(fmt) = extract_attr(line, "format") {
+                        resource.format = fmt.parse().ok();
+                    }
+                    resource.uid = extract_attr(line, "uid");
+                    resource.script_class = extract_attr(line, "script_class");
                 }
-                if let Some(fmt) = extract_attr(line, "format") {
-                    resource.format = fmt.parse().ok();
+                // [ext_resource ...]
+                else if line.starts_with("[ext_resource") {
+                    if let (Some(id), Some(rt), Some(path)) = (
+                        extract_attr(line, "id"),
+                        extract_attr(line, "type"),
+                        extract_attr(line, "path"),
+                    ) {
+                        resource.ext_resources.push(ExtResourceRef {
+                            id,
+                            resource_type: rt,
+                            path,
+                            uid: extract_attr(line, "uid"),
+                        });
+                    }
                 }
-                current_section = Some("header".to_string());
-            }
-            // [ext_resource ...]
-            else if line.starts_with("[ext_resource") {
-                if let (Some(id), Some(rt), Some(path)) = (
-                    extract_attr(line, "id"),
-                    extract_attr(line, "type"),
-                    extract_attr(line, "path"),
-                ) {
-                    resource.ext_resources.push(ExtResourceRef {
-                        id,
-                        resource_type: rt,
-                        path,
-                    });
+                // [sub_resource ...]
+                else if line.starts_with("[sub_resource") {
+                    current_sub = Some(SubResourceDef::new(
+                        &extract_attr(line, "id").unwrap_or_default(),
+                        &extract_attr(line, "type").unwrap_or_default(),
+                    ));
+                    current_section = Some("sub_resource");
                 }
-            }
-            // [sub_resource ...]
-            else if line.starts_with("[sub_resource") {
-                // Save previous sub-resource
-                if !current_sub_id.is_empty() {
-                    resource.sub_resources.push(SubResourceDef {
-                        id: current_sub_id.clone(),
-                        resource_type: current_sub_type.clone(),
-                        properties: current_props.clone(),
-                    });
-                    current_props.clear();
+                // [resource]
+                else if line.starts_with("[resource]") {
+                    current_section = Some("resource");
                 }
-
-                current_sub_id = extract_attr(line, "id").unwrap_or_default();
-                current_sub_type = extract_attr(line, "type").unwrap_or_default();
-                current_section = Some("sub_resource".to_string());
+                continue;
             }
-            // [resource]
-            else if line.starts_with("[resource]") {
-                // Save previous sub-resource
-                if !current_sub_id.is_empty() {
-                    resource.sub_resources.push(SubResourceDef {
-                        id: current_sub_id.clone(),
-                        resource_type: current_sub_type.clone(),
-                        properties: current_props.clone(),
-                    });
-                    current_props.clear();
-                    current_sub_id.clear();
+
+            // Property line, or the continuation of a multi-line value
+            let props = match current_section {
+                Some("resource") => &mut resource.properties,
+                Some("sub_resource") => &mut current_props,
+                _ => continue,
+            };
+            match line
+                .split_once(" = ")
+                .filter(|(key, _)| is_property_key(key))
+            {
+                Some((key, value)) => {
+                    props.insert(key.to_string(), value.trim().to_string());
+                    last_key = Some(key.to_string());
                 }
-                current_section = Some("resource".to_string());
-            }
-            // Property line
-            else if let Some(eq_pos) = line.find(" = ") {
-                let key = line[..eq_pos].trim().to_string();
-                let value = line[eq_pos + 3..].trim().to_string();
-
-                match current_section.as_deref() {
-                    Some("resource") => {
-                        resource.properties.insert(key, value);
-                    }
-                    Some("sub_resource") => {
-                        current_props.insert(key, value);
+                None => {
+                    if let Some(value) = last_key.as_ref().and_then(|key| props.get_mut(key)) {
+                        value.push('\n');
+                        value.push_str(raw_line);
                     }
-                    _ => {}
                 }
             }
-
-            i += 1;
         }
 
         // Save the last sub-resource
-        if !current_sub_id.is_empty() {
-            resource.sub_resources.push(SubResourceDef {
-                id: current_sub_id,
-                resource_type: current_sub_type,
-                properties: current_props,
-            });
+        if let Some(mut sub) = current_sub.take() {
+            sub.properties = current_props;
+            resource.sub_resources.push(sub);
         }
 
         if resource.resource_type.is_empty() {
@@ -254,10 +271,13 @@ impl GodotResource {
             "type": self.resource_type,
             "load_steps": self.load_steps,
             "format": self.format,
+            "uid": self.uid,
+            "script_class": self.script_class,
             "ext_resources": self.ext_resources.iter().map(|r| serde_json::json!({
                 "id": r.id,
                 "type": r.resource_type,
                 "path": r.path,
+                "uid": r.uid,
             })).collect::<Vec<_>>(),
             "sub_resources": self.sub_resources.iter().map(|s| serde_json::json!({
                 "id": s.id,
@@ -310,4 +330,37 @@ value = 42
             Some(&"\"TestResource\"".to_string())
         );
     }
+
+    #[test]
+    fn test_parse_tres_custom_resource() {
+        let content = r#"[gd_resource type="Resource" script_class="ItemData" load_steps=3 format=3 uid="uid://b1item"]
+
+[ext_resource type="Script" uid="uid://c2script" path="res://item_data.gd" id="1_item"]
+
+[sub_resource type="Gradient" id="Gradient_a"]
+offsets = PackedFloat32Array(0, 1)
+
+[resource]
+script = ExtResource("1_item")
+stats = {
+"attack": 5,
+"speed": 1.5
+}
+gradient = SubResource("Gradient_a")
+"#;
+        let res = GodotResource::parse(content).unwrap();
+        assert_eq!(res.uid.as_deref(), Some("uid://b1item"));
+        assert_eq!(res.script_class.as_deref(), Some("ItemData"));
+        assert_eq!(res.ext_resources[0].uid.as_deref(), Some("uid://c2script"));
+        assert_eq!(res.sub_resources.len(), 1);
+        assert_eq!(
+            res.sub_resources[0].properties.get("offsets"),
+            Some(&"PackedFloat32Array(0, 1)".to_string())
+        );
+        assert_eq!(
+            res.properties.get("stats"),
+            Some(&"{\n\"attack\": 5,\n\"speed\": 1.5\n}".to_string())
+        );
+        assert_eq!(res.properties.len(), 3);
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! Parses and generates Godot's text scene format
 
+use indexmap::IndexMap;
+use regex::Regex;
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -19,6 +21,9 @@ pub struct GodotScene {
     /// Scene header information
     pub format: u32,
     pub uid: Option<String>,
+    /// Whether the header carries `load_steps` (newer Godot versions omit
+    /// it); the count itself is recomputed on write
+    pub load_steps: bool,
 
     /// External resources
     pub ext_resources: Vec<ExtResource>,
@@ -28,6 +33,21 @@ pub struct GodotScene {
 
     /// List of nodes
     pub nodes: Vec<SceneNode>,
+
+    /// Signal connections (`[connection]` sections)
+    pub connections: Vec<Connection>,
+
+    /// Sections not modelled above (e.g. `[editable path="..."]`), kept verbatim
+    pub unparsed_sections: Vec<RawSection>,
+}
+
+/// Section preserved as written, so edits never drop editor data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawSection {
+    /// Header line including the brackets
+    pub header: String,
+    /// Body lines, without trailing blank lines
+    pub lines: Vec<String>,
 }
 
 /// External resource reference
@@ -36,6 +56,8 @@ pub struct ExtResource {
     pub id: String,
     pub resource_type: String,
     pub path: String,
+    /// `uid://...` of the referenced resource
+    pub uid: Option<String>,
 }
 
 /// Sub-resource
@@ -43,16 +65,90 @@ pub struct ExtResource {
 pub struct SubResource {
     pub id: String,
     pub resource_type: String,
-    pub properties: HashMap<String, String>,
+    pub properties: IndexMap<String, String>,
+}
+
+/// Signal connection; node paths are relative to the scene root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    pub signal: String,
+    pub from: String,
+    pub to: String,
+    pub method: String,
+    /// ConnectFlags bitmask
+    pub flags: Option<u32>,
+    /// Trailing signal arguments dropped before the call
+    pub unbinds: Option<u32>,
+    /// Extra call arguments, as written (e.g. `[1, "a"]`)
+    pub binds: Option<String>,
 }
 
 /// Scene node
 #[derive(Debug, Clone)]
 pub struct SceneNode {
     pub name: String,
+    /// Empty for overrides of a node inherited from a base scene
     pub node_type: String,
     pub parent: Option<String>,
-    pub properties: HashMap<String, String>,
+    /// ExtResource id of the instanced PackedScene (`instance=ExtResource("id")`)
+    pub instance: Option<String>,
+    pub groups: Vec<String>,
+    /// Other header attributes (`index`, `owner`, `node_paths`, `unique_id`...) as written
+    pub extra_attrs: Vec<(String, String)>,
+    /// Header attribute keys in the order they were read; new nodes and
+    /// added attributes follow Godot's order
+    pub attr_order: Vec<String>,
+    pub properties: IndexMap<String, String>,
+}
+
+/// Structural problem of the node tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StructureIssue {
+    /// No nodes, or the first node has a parent
+    MissingRoot,
+    /// Parentless node after the root
+    ExtraRoot { index: usize, name: String },
+    /// Node whose parent path matches no node
+    Orphan {
+        index: usize,
+        path: String,
+        parent: String,
+    },
+    /// Node with the same path as an earlier node
+    Duplicate { index: usize, path: String },
+}
+
+impl StructureIssue {
+    /// Path of the offending node, if any
+    pub fn node_path(&self) -> Option<&str> {
+        match self {
+            Self::MissingRoot => None,
+            Self::ExtraRoot { name, .. } => Some(name),
+            Self::Orphan { path, .. } | Self::Duplicate { path, .. } => Some(path),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::MissingRoot => "Root node missing".to_string(),
+            Self::ExtraRoot { name, .. } => format!("Node '{}' has no parent", name),
+            Self::Orphan { path, parent, .. } => {
+                format!("Node '{}' references missing parent '{}'", path, parent)
+            }
+            Self::Duplicate { path, .. } => format!("Duplicate node path '{}'", path),
+        }
+    }
+}
+
+/// Automatic fix for structural issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureFix {
+    /// Move orphans and extra roots under the root node
+    ReattachOrphans,
+    /// Remove orphans (with their children) and extra roots
+    DropOrphans,
+    /// Remove nodes duplicating an earlier node path
+    DropDuplicates,
 }
 
 impl GodotScene {
@@ -61,14 +157,21 @@ impl GodotScene {
         Self {
             format: 3,
             uid: None,
+            load_steps: true,
             ext_resources: Vec::new(),
             sub_resources: Vec::new(),
             nodes: vec![SceneNode {
                 name: root_name.to_string(),
                 node_type: root_type.to_string(),
                 parent: None,
-                properties: HashMap::new(),
+                instance: None,
+                groups: Vec::new(),
+                extra_attrs: Vec::new(),
+                attr_order: Vec::new(),
+                properties: IndexMap::new(),
             }],
+            connections: Vec::new(),
+            unparsed_sections: Vec::new(),
         }
     }
 
@@ -77,17 +180,52 @@ impl GodotScene {
         let mut scene = GodotScene {
             format: 3,
             uid: None,
+            load_steps: false,
             ext_resources: Vec::new(),
             sub_resources: Vec::new(),
             nodes: Vec::new(),
+            connections: Vec::new(),
+            unparsed_sections: Vec::new(),
         };
 
         let mut current_section: Option<&str> = None;
         let mut current_node: Option<SceneNode> = None;
-        let mut current_properties: HashMap<String, String> = HashMap::new();
+        let mut current_sub: Option<SubResource> = None;
+        let mut current_raw: Option<RawSection> = None;
+        let mut current_properties: IndexMap<String, String> = IndexMap::new();
+        // Key of the last property, for values spanning several lines
+        let mut last_key: Option<String> = None;
+        // Strings and brackets still open in the current value; until they
+        // close, blank and `[...]` lines belong to the value
+        let mut value_scan = ValueScan::default();
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+
+            if value_scan.is_open() {
+                value_scan.feed("\n");
+                value_scan.feed(raw_line);
+                if let Some(raw) = current_raw.as_mut() {
+                    raw.lines.push(raw_line.to_string());
+                } else if let Some(value) = last_key
+                    .as_ref()
+                    .and_then(|key| current_properties.get_mut(key))
+                {
+                    value.push('\n');
+                    value.push_str(raw_line);
+                }
+                continue;
+            }
 
-        for line in content.lines() {
-            let line = line.trim();
+            // Unknown sections keep their body verbatim, including blank lines
+            // inside multi-line values
+            if let Some(raw) = current_raw.as_mut() {
+                if !(line.starts_with('[') && line.ends_with(']')) {
+                    value_scan.feed(raw_line);
+                    raw.lines.push(raw_line.to_string());
+                    continue;
+                }
+            }
 
             if line.is_empty() {
                 continue;
@@ -95,12 +233,19 @@ impl GodotScene {
 
             // Section header
             if line.starts_with('[') && line.ends_with(']') {
-                // Save previous node
+                // Save previous node / sub-resource
                 if let Some(mut node) = current_node.take() {
-                    node.properties = current_properties.clone();
+                    node.properties = std::mem::take(&mut current_properties);
                     scene.nodes.push(node);
-                    current_properties.clear();
                 }
+                if let Some(mut sub) = current_sub.take() {
+                    sub.properties = std::mem::take(&mut current_properties);
+                    scene.sub_resources.push(sub);
+                }
+                if let Some(raw) = current_raw.take() {
+                    scene.unparsed_sections.push(finish_raw(raw));
+                }
+                last_key = None;
 
                 let section_content = &line[1..line.len() - 1];
 
@@ -112,31 +257,65 @@ impl GodotScene {
                     if let Some(uid) = extract_attr(section_content, "uid") {
                         scene.uid = Some(uid.to_string());
                     }
+                    scene.load_steps = extract_attr(section_content, "load_steps").is_some();
                     current_section = Some("gd_scene");
                 } else if section_content.starts_with("ext_resource") {
                     let res = parse_ext_resource(section_content)?;
                     scene.ext_resources.push(res);
                     current_section = Some("ext_resource");
                 } else if section_content.starts_with("sub_resource") {
+                    current_sub = Some(parse_sub_resource_header(section_content)?);
                     current_section = Some("sub_resource");
                 } else if section_content.starts_with("node") {
                     let node = parse_node_header(section_content)?;
                     current_node = Some(node);
                     current_section = Some("node");
+                } else if section_content.starts_with("connection") {
+                    scene.connections.push(parse_connection(section_content)?);
+                    current_section = Some("connection");
+                } else {
+                    current_raw = Some(RawSection {
+                        header: line.to_string(),
+                        lines: Vec::new(),
+                    });
+                    current_section = None;
                 }
-            } else if current_section == Some("node") {
-                // Property line
-                if let Some((key, value)) = line.split_once(" = ") {
-                    current_properties.insert(key.to_string(), value.to_string());
+            } else if matches!(current_section, Some("node" | "sub_resource")) {
+                // Property line, or the continuation of a multi-line value
+                match line
+                    .split_once(" = ")
+                    .filter(|(key, _)| is_property_key(key))
+                {
+                    Some((key, value)) => {
+                        value_scan.feed(value);
+                        current_properties.insert(key.to_string(), value.to_string());
+                        last_key = Some(key.to_string());
+                    }
+                    None => {
+                        if let Some(value) = last_key
+                            .as_ref()
+                            .and_then(|key| current_properties.get_mut(key))
+                        {
+                            value.push('\n');
+                            value.push_str(raw_line);
+                        }
+                    }
                 }
             }
         }
 
-        // Save the last node
+        // Save the last node / sub-resource
         if let Some(mut node) = current_node.take() {
-            node.properties = current_properties;
+            node.properties = std::mem::take(&mut current_properties);
             scene.nodes.push(node);
         }
+        if let Some(mut sub) = current_sub.take() {
+            sub.properties = current_properties;
+            scene.sub_resources.push(sub);
+        }
+        if let Some(raw) = current_raw.take() {
+            scene.unparsed_sections.push(finish_raw(raw));
+        }
 
         Ok(scene)
     }
@@ -146,11 +325,12 @@ impl GodotScene {
         let mut output = String::new();
 
         // Header
-        let load_steps = 1 + self.ext_resources.len() + self.sub_resources.len();
-        output.push_str(&format!(
-            "[gd_scene load_steps={} format={}",
-            load_steps, self.format
-        ));
+        output.push_str("[gd_scene");
+        if self.load_steps {
+            let load_steps = 1 + self.ext_resources.len() + self.sub_resources.len();
+            output.push_str(&format!(" load_steps={}", load_steps));
+        }
+        output.push_str(&format!(" format={}", self.format));
         if let Some(ref uid) = self.uid {
             output.push_str(&format!(" uid=\"{}\"", uid));
         }
@@ -158,23 +338,33 @@ impl GodotScene {
 
         // External resources
         for res in &self.ext_resources {
-            output.push_str(&format!(
-                "[ext_resource type=\"{}\" path=\"{}\" id=\"{}\"]\n",
-                res.resource_type, res.path, res.id
-            ));
+            output.push_str(&format!("[ext_resource type=\"{}\"", res.resource_type));
+            if let Some(ref uid) = res.uid {
+                output.push_str(&format!(" uid=\"{}\"", uid));
+            }
+            output.push_str(&format!(" path=\"{}\" id=\"{}\"]\n", res.path, res.id));
         }
         if !self.ext_resources.is_empty() {
             output.push('\n');
         }
 
-        // Nodes
-        for node in &self.nodes {
+        // Sub-resources
+        for sub in &self.sub_resources {
             output.push_str(&format!(
-                "[node name=\"{}\" type=\"{}\"",
-                node.name, node.node_type
+                "[sub_resource type=\"{}\" id=\"{}\"]\n",
+                sub.resource_type, sub.id
             ));
-            if let Some(ref parent) = node.parent {
-                output.push_str(&format!(" parent=\"{}\"", parent));
+            for (key, value) in &sub.properties {
+                output.push_str(&format!("{} = {}\n", key, value));
+            }
+            output.push('\n');
+        }
+
+        // Nodes
+        for node in &self.nodes {
+            output.push_str("[node");
+            for (key, value) in node_header_attrs(node) {
+                output.push_str(&format!(" {}={}", key, value));
             }
             output.push_str("]\n");
 
@@ -184,6 +374,37 @@ impl GodotScene {
             output.push('\n');
         }
 
+        // Signal connections
+        for connection in &self.connections {
+            output.push_str(&format!(
+                "[connection signal=\"{}\" from=\"{}\" to=\"{}\" method=\"{}\"",
+                connection.signal, connection.from, connection.to, connection.method
+            ));
+            if let Some(flags) = connection.flags {
+                output.push_str(&format!(" flags={}", flags));
+            }
+            if let Some(unbinds) = connection.unbinds {
+                output.push_str(&format!(" unbinds={}", unbinds));
+            }
+            if let Some(ref binds) = connection.binds {
+                output.push_str(&format!(" binds={}", binds));
+            }
+            output.push_str("]\n");
+        }
+
+        // Preserved sections (`[editable]` comes last in Godot's own output)
+        for section in &self.unparsed_sections {
+            if !output.ends_with("\n\n") {
+                output.push('\n');
+            }
+            output.push_str(&section.header);
+            output.push('\n');
+            for line in &section.lines {
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+
         output
     }
 
@@ -198,6 +419,7 @@ impl GodotScene {
             id: id.to_string(),
             resource_type: resource_type.to_string(),
             path: path.to_string(),
+            uid: None,
         });
     }
 
@@ -230,6 +452,104 @@ impl GodotScene {
         }
     }
 
+    /// Add a signal connection between existing nodes
+    pub fn connect_signal(&mut self, connection: Connection) -> Result<(), String> {
+        for path in [&connection.from, &connection.to] {
+            if !self.nodes.iter().any(|n| node_path(n) == *path) {
+                return Err(format!("Node not found: {}", path));
+            }
+        }
+        let duplicate = self.connections.iter().any(|c| {
+            c.signal == connection.signal
+                && c.from == connection.from
+                && c.to == connection.to
+                && c.method == connection.method
+        });
+        if duplicate {
+            return Err(format!(
+                "Already connected: {}.{} -> {}::{}",
+                connection.from, connection.signal, connection.to, connection.method
+            ));
+        }
+        self.connections.push(connection);
+        Ok(())
+    }
+
+    /// Connections whose source is the node at `node_path`
+    pub fn connections_from<'a>(
+        &'a self,
+        node_path: &'a str,
+    ) -> impl Iterator<Item = &'a Connection> + 'a {
+        self.connections.iter().filter(move |c| c.from == node_path)
+    }
+
+    /// Path of the base scene, for inherited scenes (root with `instance=...`)
+    pub fn inherits_from(&self) -> Option<&str> {
+        let root = self.nodes.first().filter(|n| n.parent.is_none())?;
+        self.instance_path(root)
+    }
+
+    /// The node at `path` as defined by the scene this one inherits from,
+    /// following the inheritance chain through `load` (scene path to scene)
+    pub fn inherited_node(
+        &self,
+        path: &str,
+        load: impl Fn(&str) -> Option<GodotScene>,
+    ) -> Option<SceneNode> {
+        let mut base_path = self.inherits_from().map(str::to_string);
+        let mut seen = Vec::new();
+        while let Some(current) = base_path.take() {
+            if seen.contains(&current) {
+                break;
+            }
+            let base = load(&current)?;
+            seen.push(current);
+            if let Some(node) = base.nodes.iter().find(|n| node_path(n) == path) {
+                if node.instance.is_none() && !node.node_type.is_empty() {
+                    return Some(node.clone());
+                }
+            }
+            base_path = base.inherits_from().map(str::to_string);
+        }
+        None
+    }
+
+    /// Add an empty override section (`[node name=.. parent=..]`, no type) for
+    /// a node inherited from the base scene; returns its index
+    pub fn add_inherited_override(&mut self, path: &str) -> Result<usize, String> {
+        if self.inherits_from().is_none() {
+            return Err("Scene does not inherit from another scene".to_string());
+        }
+        if self.nodes.iter().any(|n| node_path(n) == path) {
+            return Err(format!("Node already in scene: {}", path));
+        }
+        let (parent, name) = match path.rsplit_once('/') {
+            Some((parent, name)) => (parent, name),
+            None if path != "." && !path.is_empty() => (".", path),
+            None => return Err(format!("Invalid node path: {}", path)),
+        };
+        self.nodes.push(SceneNode {
+            name: name.to_string(),
+            node_type: String::new(),
+            parent: Some(parent.to_string()),
+            instance: None,
+            groups: Vec::new(),
+            extra_attrs: Vec::new(),
+            attr_order: Vec::new(),
+            properties: IndexMap::new(),
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Path of the scene `node` instances, resolved through its ext_resource
+    pub fn instance_path(&self, node: &SceneNode) -> Option<&str> {
+        let id = node.instance.as_deref()?;
+        self.ext_resources
+            .iter()
+            .find(|r| r.id == id)
+            .map(|r| r.path.as_str())
+    }
+
     /// Remove a node
     pub fn remove_node(&mut self, node_path: &str) -> Result<(), String> {
         if node_path == "." {
@@ -270,6 +590,637 @@ impl GodotScene {
         }
     }
 
+    /// Rename a node. Child parent paths, connections, `[editable]` sections
+    /// and NodePath properties follow the rename; returns the node's new path.
+    pub fn rename_node(&mut self, path: &str, new_name: &str) -> Result<String, String> {
+        if new_name.is_empty() || new_name.contains(['.', ':', '@', '/', '"', '%']) {
+            return Err(format!("Invalid node name: '{}'", new_name));
+        }
+        let index = self
+            .nodes
+            .iter()
+            .position(|n| node_path(n) == path)
+            .ok_or_else(|| format!("Node not found: {}", path))?;
+        let new_path = match self.nodes[index].parent.as_deref() {
+            // Paths are relative to the root, so its name appears in none of them
+            None => {
+                self.nodes[index].name = new_name.to_string();
+                return Ok(path.to_string());
+            }
+            Some(".") => new_name.to_string(),
+            Some(parent) => format!("{}/{}", parent, new_name),
+        };
+        if new_path == path {
+            return Ok(new_path);
+        }
+        if self.nodes.iter().any(|n| node_path(n) == new_path) {
+            return Err(format!("'{}' already exists", new_path));
+        }
+
+        self.rebase_references(path, &new_path);
+        self.nodes[index].name = new_name.to_string();
+        self.rename_subtree(path, &new_path);
+        Ok(new_path)
+    }
+
+    /// Deep-copy a node and its descendants as a sibling placed right after the
+    /// original subtree. Without `new_name` the copy is named like Godot does
+    /// ("Enemy" -> "Enemy2", "Enemy2" -> "Enemy3"). Connections from the
+    /// subtree and `[editable]` sections are copied too; returns the copy's path.
+    pub fn duplicate_node(&mut self, path: &str, new_name: Option<&str>) -> Result<String, String> {
+        if path == "." {
+            return Err("Cannot duplicate the root node".to_string());
+        }
+        let index = self
+            .nodes
+            .iter()
+            .position(|n| n.parent.is_some() && node_path(n) == path)
+            .ok_or_else(|| format!("Node not found: {}", path))?;
+        let parent = self.nodes[index].parent.clone().unwrap_or_default();
+        let sibling_path = |name: &str| {
+            if parent == "." {
+                name.to_string()
+            } else {
+                format!("{}/{}", parent, name)
+            }
+        };
+        let taken = |name: &str| {
+            self.nodes
+                .iter()
+                .any(|n| node_path(n) == sibling_path(name))
+        };
+
+        let name = match new_name {
+            Some(name) => {
+                if name.is_empty() || name.contains(['.', ':', '@', '/', '"', '%']) {
+                    return Err(format!("Invalid node name: '{}'", name));
+                }
+                if taken(name) {
+                    return Err(format!("'{}' already exists", sibling_path(name)));
+                }
+                name.to_string()
+            }
+            None => next_free_name(&self.nodes[index].name, taken),
+        };
+        let new_path = sibling_path(&name);
+        let rebase = |p: &str| rebase_path(p, path, &new_path);
+
+        let subtree: Vec<usize> = (index..self.nodes.len())
+            .filter(|&i| {
+                i == index
+                    || self.nodes[i]
+                        .parent
+                        .as_deref()
+                        .is_some_and(|p| is_within(p, path))
+            })
+            .collect();
+        let mut copies: Vec<SceneNode> = subtree.iter().map(|&i| self.nodes[i].clone()).collect();
+        copies[0].name = name;
+        for copy in &mut copies[1..] {
+            copy.parent = copy.parent.as_deref().and_then(rebase);
+        }
+        let end = subtree.last().map_or(index, |i| i + 1);
+        self.nodes.splice(end..end, copies);
+
+        let connections: Vec<Connection> = self
+            .connections
+            .iter()
+            .filter_map(|c| {
+                let from = rebase(&c.from)?;
+                let to = rebase(&c.to).unwrap_or_else(|| c.to.clone());
+                Some(Connection {
+                    from,
+                    to,
+                    ..c.clone()
+                })
+            })
+            .collect();
+        self.connections.extend(connections);
+
+        let editable: Vec<RawSection> = self
+            .unparsed_sections
+            .iter()
+            .filter_map(|section| {
+                let header = section.header.strip_prefix('[')?.strip_suffix(']')?;
+                if !header.starts_with("editable ") {
+                    return None;
+                }
+                let copied = rebase(extract_attr(header, "path")?)?;
+                Some(RawSection {
+                    header: format!("[editable path=\"{}\"]", copied),
+                    lines: section.lines.clone(),
+                })
+            })
+            .collect();
+        self.unparsed_sections.extend(editable);
+
+        Ok(new_path)
+    }
+
+    /// Move a node (with its descendants) to the end of `new_parent`'s children.
+    /// Parent paths, connections, `[editable]` sections and NodePath properties
+    /// follow the move; returns the node's new path.
+    pub fn reparent_node(&mut self, path: &str, new_parent: &str) -> Result<String, String> {
+        if path == "." {
+            return Err("Cannot reparent the root node".to_string());
+        }
+        let index = self
+            .nodes
+            .iter()
+            .position(|n| n.parent.is_some() && node_path(n) == path)
+            .ok_or_else(|| format!("Node not found: {}", path))?;
+        if new_parent != "." && !self.nodes.iter().any(|n| node_path(n) == new_parent) {
+            return Err(format!("Parent not found: {}", new_parent));
+        }
+        if is_within(new_parent, path) {
+            return Err(format!("Cannot move '{}' below itself", path));
+        }
+        if self.nodes[index].parent.as_deref() == Some(new_parent) {
+            return Ok(path.to_string());
+        }
+
+        let name = self.nodes[index].name.clone();
+        let new_path = if new_parent == "." {
+            name.clone()
+        } else {
+            format!("{}/{}", new_parent, name)
+        };
+        if self.nodes.iter().any(|n| node_path(n) == new_path) {
+            return Err(format!("'{}' already exists", new_path));
+        }
+
+        self.rebase_references(path, &new_path);
+
+        // Take the subtree out and put it after the new parent's subtree, so
+        // parents keep preceding their children in the file
+        let moved: Vec<bool> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| i == index || n.parent.as_deref().is_some_and(|p| is_within(p, path)))
+            .collect();
+        let mut subtree = Vec::new();
+        let mut rest = Vec::new();
+        for (node, moved) in self.nodes.drain(..).zip(moved) {
+            if moved {
+                subtree.push(node);
+            } else {
+                rest.push(node);
+            }
+        }
+        let insert_at = rest
+            .iter()
+            .rposition(|n| new_parent == "." || is_within(&node_path(n), new_parent))
+            .map_or(rest.len(), |i| i + 1);
+        self.nodes = rest;
+
+        subtree[0].parent = Some(new_parent.to_string());
+        self.nodes.splice(insert_at..insert_at, subtree);
+        self.rename_subtree(path, &new_path);
+        Ok(new_path)
+    }
+
+    /// Standalone scene whose root is a copy of the node at `path`, holding its
+    /// descendants, the connections between them, their `[editable]` sections
+    /// and the ext/sub resources they use (sub-resources transitively).
+    pub fn extract_subtree(&self, path: &str) -> Result<GodotScene, String> {
+        let index = self
+            .nodes
+            .iter()
+            .position(|n| node_path(n) == path)
+            .ok_or_else(|| format!("Node not found: {}", path))?;
+        let relative = |p: &str| match path {
+            "." => Some(p.to_string()),
+            _ => rebase_path(p, path, ".").map(|p| relative_to_root(&p)),
+        };
+
+        let mut nodes: Vec<SceneNode> = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate().skip(index) {
+            if i == index {
+                let mut root = node.clone();
+                root.parent = None;
+                root.extra_attrs.retain(|(key, _)| key != "index");
+                nodes.push(root);
+            } else if let Some(parent) = node.parent.as_deref().and_then(relative) {
+                nodes.push(SceneNode {
+                    parent: Some(parent),
+                    ..node.clone()
+                });
+            }
+        }
+
+        // Resources used by the nodes, then by the sub-resources they pull in
+        let mut ext_ids: Vec<String> = Vec::new();
+        let mut sub_ids: Vec<String> = Vec::new();
+        let mut pending: Vec<&String> = Vec::new();
+        for node in &nodes {
+            ext_ids.extend(node.instance.iter().cloned());
+            pending.extend(node.properties.values());
+        }
+        while let Some(value) = pending.pop() {
+            for (kind, id) in resource_refs(value) {
+                if kind == "ExtResource" {
+                    if !ext_ids.contains(&id) {
+                        ext_ids.push(id);
+                    }
+                } else if !sub_ids.contains(&id) {
+                    if let Some(sub) = self.sub_resources.iter().find(|s| s.id == id) {
+                        pending.extend(sub.properties.values());
+                    }
+                    sub_ids.push(id);
+                }
+            }
+        }
+
+        let connections = self
+            .connections
+            .iter()
+            .filter_map(|c| {
+                Some(Connection {
+                    from: relative(&c.from)?,
+                    to: relative(&c.to)?,
+                    ..c.clone()
+                })
+            })
+            .collect();
+        let unparsed_sections = self
+            .unparsed_sections
+            .iter()
+            .filter_map(|section| {
+                let header = section.header.strip_prefix('[')?.strip_suffix(']')?;
+                if !header.starts_with("editable ") {
+                    return None;
+                }
+                let copied = relative(extract_attr(header, "path")?)?;
+                Some(RawSection {
+                    header: format!("[editable path=\"{}\"]", copied),
+                    lines: section.lines.clone(),
+                })
+            })
+            .collect();
+
+        Ok(GodotScene {
+            format: self.format,
+            uid: None,
+            load_steps: self.load_steps,
+            ext_resources: self
+                .ext_resources
+                .iter()
+                .filter(|r| ext_ids.contains(&r.id))
+                .cloned()
+                .collect(),
+            sub_resources: self
+                .sub_resources
+                .iter()
+                .filter(|s| sub_ids.contains(&s.id))
+                .cloned()
+                .collect(),
+            nodes,
+            connections,
+            unparsed_sections,
+        })
+    }
+
+    /// Add the nodes of `subtree` (as returned by `extract_subtree`) as the
+    /// last child of `parent`. Ext resources already loaded from the same
+    /// path are reused; other resources get ids not taken in this scene. The
+    /// root keeps its name unless a sibling has it ("Enemy" -> "Enemy2").
+    /// Returns the path of the inserted root.
+    pub fn insert_subtree(&mut self, subtree: &GodotScene, parent: &str) -> Result<String, String> {
+        let root = subtree
+            .nodes
+            .first()
+            .filter(|n| n.parent.is_none())
+            .ok_or("Subtree has no root node")?;
+        if parent != "." && !self.nodes.iter().any(|n| node_path(n) == parent) {
+            return Err(format!("Parent not found: {}", parent));
+        }
+        let child_path = |name: &str| {
+            if parent == "." {
+                name.to_string()
+            } else {
+                format!("{}/{}", parent, name)
+            }
+        };
+        let taken = |name: &str| self.nodes.iter().any(|n| node_path(n) == child_path(name));
+        let name = if taken(&root.name) {
+            next_free_name(&root.name, taken)
+        } else {
+            root.name.clone()
+        };
+        let new_path = child_path(&name);
+        let place = |p: &str| {
+            if p == "." {
+                new_path.clone()
+            } else {
+                format!("{}/{}", new_path, p)
+            }
+        };
+
+        let mut ids: HashMap<(&str, &str), String> = HashMap::new();
+        for res in &subtree.ext_resources {
+            let id = match self
+                .ext_resources
+                .iter()
+                .find(|r| r.path == res.path && r.resource_type == res.resource_type)
+            {
+                Some(existing) => existing.id.clone(),
+                None => {
+                    let id = free_id(&res.id, |id| self.ext_resources.iter().any(|r| r.id == id));
+                    self.ext_resources.push(ExtResource {
+                        id: id.clone(),
+                        ..res.clone()
+                    });
+                    id
+                }
+            };
+            ids.insert(("ExtResource", &res.id), id);
+        }
+        for sub in &subtree.sub_resources {
+            let id = free_id(&sub.id, |id| self.sub_resources.iter().any(|s| s.id == id));
+            ids.insert(("SubResource", &sub.id), id);
+        }
+        let reference = Regex::new(RESOURCE_REF).unwrap();
+        let remap = |value: &str| {
+            reference
+                .replace_all(value, |c: &regex::Captures| {
+                    match ids.get(&(&c[1], &c[2])) {
+                        Some(id) => format!("{}(\"{}\")", &c[1], id),
+                        None => c[0].to_string(),
+                    }
+                })
+                .into_owned()
+        };
+        let remap_properties = |properties: &IndexMap<String, String>| {
+            properties
+                .iter()
+                .map(|(key, value)| (key.clone(), remap(value)))
+                .collect::<IndexMap<_, _>>()
+        };
+
+        // Sub-resources precede their users, so keep the subtree's order
+        for sub in &subtree.sub_resources {
+            self.sub_resources.push(SubResource {
+                id: ids[&("SubResource", sub.id.as_str())].clone(),
+                resource_type: sub.resource_type.clone(),
+                properties: remap_properties(&sub.properties),
+            });
+        }
+
+        let nodes: Vec<SceneNode> = subtree
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| SceneNode {
+                name: if i == 0 {
+                    name.clone()
+                } else {
+                    node.name.clone()
+                },
+                parent: Some(match node.parent.as_deref() {
+                    None => parent.to_string(),
+                    Some(p) => place(p),
+                }),
+                instance: node
+                    .instance
+                    .as_ref()
+                    .map(|id| ids.get(&("ExtResource", id)).unwrap_or(id).clone()),
+                properties: remap_properties(&node.properties),
+                ..node.clone()
+            })
+            .collect();
+        let insert_at = self
+            .nodes
+            .iter()
+            .rposition(|n| parent == "." || is_within(&node_path(n), parent))
+            .map_or(self.nodes.len(), |i| i + 1);
+        self.nodes.splice(insert_at..insert_at, nodes);
+
+        self.connections
+            .extend(subtree.connections.iter().map(|c| Connection {
+                from: place(&c.from),
+                to: place(&c.to),
+                ..c.clone()
+            }));
+        self.unparsed_sections
+            .extend(subtree.unparsed_sections.iter().filter_map(|section| {
+                let header = section.header.strip_prefix('[')?.strip_suffix(']')?;
+                let path =
+                    extract_attr(header, "path").filter(|_| header.starts_with("editable "))?;
+                Some(RawSection {
+                    header: format!("[editable path=\"{}\"]", place(path)),
+                    lines: section.lines.clone(),
+                })
+            }));
+
+        Ok(new_path)
+    }
+
+    /// Animations embedded in an AnimationPlayer's libraries, as
+    /// (name, sub_resource id). Names outside the default library get a
+    /// "library/" prefix, as in `AnimationPlayer.get_animation_list()`;
+    /// libraries saved as external resources are not included.
+    pub fn player_animations(&self, player_path: &str) -> Result<Vec<(String, String)>, String> {
+        let player = self
+            .nodes
+            .iter()
+            .find(|n| node_path(n) == player_path)
+            .ok_or_else(|| format!("Node not found: {}", player_path))?;
+        if player.node_type != "AnimationPlayer" {
+            return Err(format!("'{}' is not an AnimationPlayer", player_path));
+        }
+
+        let mut animations = Vec::new();
+        let Some(libraries) = player.properties.get("libraries") else {
+            return Ok(animations);
+        };
+        for (library, library_id) in sub_resource_entries(libraries) {
+            let Some(data) = self
+                .sub_resources
+                .iter()
+                .find(|s| s.id == library_id)
+                .and_then(|s| s.properties.get("_data"))
+            else {
+                continue;
+            };
+            for (name, animation_id) in sub_resource_entries(data) {
+                let name = if library.is_empty() {
+                    name
+                } else {
+                    format!("{}/{}", library, name)
+                };
+                animations.push((name, animation_id));
+            }
+        }
+        Ok(animations)
+    }
+
+    /// Detect multiple roots, orphaned nodes and duplicate node paths
+    pub fn structure_issues(&self) -> Vec<StructureIssue> {
+        let mut issues = Vec::new();
+        if self.nodes.first().is_none_or(|root| root.parent.is_some()) {
+            issues.push(StructureIssue::MissingRoot);
+        }
+
+        let paths: Vec<String> = self.nodes.iter().map(node_path).collect();
+        for (index, node) in self.nodes.iter().enumerate().skip(1) {
+            match node.parent.as_deref() {
+                None => issues.push(StructureIssue::ExtraRoot {
+                    index,
+                    name: node.name.clone(),
+                }),
+                Some(".") => {}
+                Some(parent) if !paths.iter().any(|p| p == parent) => {
+                    issues.push(StructureIssue::Orphan {
+                        index,
+                        path: paths[index].clone(),
+                        parent: parent.to_string(),
+                    })
+                }
+                Some(_) => {}
+            }
+            if node.parent.is_some() && paths[..index].contains(&paths[index]) {
+                issues.push(StructureIssue::Duplicate {
+                    index,
+                    path: paths[index].clone(),
+                });
+            }
+        }
+        issues
+    }
+
+    /// Apply structural fixes; returns a description of every change made.
+    /// Reattaching takes precedence over dropping orphans.
+    pub fn repair_structure(&mut self, fixes: &[StructureFix]) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if fixes.contains(&StructureFix::ReattachOrphans) {
+            for issue in self.structure_issues() {
+                match issue {
+                    StructureIssue::ExtraRoot { index, name } => {
+                        self.nodes[index].parent = Some(".".to_string());
+                        changes.push(format!("Attached '{}' to the root node", name));
+                    }
+                    StructureIssue::Orphan { index, path, .. } => {
+                        let name = self.nodes[index].name.clone();
+                        self.nodes[index].parent = Some(".".to_string());
+                        self.rename_subtree(&path, &name);
+                        changes.push(format!("Reattached '{}' to the root node", path));
+                    }
+                    _ => {}
+                }
+            }
+        } else if fixes.contains(&StructureFix::DropOrphans) {
+            let mut drop = vec![false; self.nodes.len()];
+            for issue in self.structure_issues() {
+                match issue {
+                    StructureIssue::ExtraRoot { index, name } => {
+                        drop[index] = true;
+                        changes.push(format!("Removed parentless node '{}'", name));
+                    }
+                    StructureIssue::Orphan { index, path, .. } => {
+                        drop[index] = true;
+                        for (i, node) in self.nodes.iter().enumerate() {
+                            if node.parent.as_deref().is_some_and(|p| is_within(p, &path)) {
+                                drop[i] = true;
+                            }
+                        }
+                        changes.push(format!("Removed orphaned node '{}'", path));
+                    }
+                    _ => {}
+                }
+            }
+            self.retain_indices(&drop);
+        }
+
+        if fixes.contains(&StructureFix::DropDuplicates) {
+            let mut drop = vec![false; self.nodes.len()];
+            for issue in self.structure_issues() {
+                if let StructureIssue::Duplicate { index, path } = issue {
+                    drop[index] = true;
+                    changes.push(format!("Removed duplicate node '{}'", path));
+                }
+            }
+            self.retain_indices(&drop);
+        }
+
+        changes
+    }
+
+    /// Rewrite parent paths below `old_path` after the node moved to `new_path`
+    fn rename_subtree(&mut self, old_path: &str, new_path: &str) {
+        for node in &mut self.nodes {
+            if let Some(parent) = &node.parent {
+                if is_within(parent, old_path) {
+                    node.parent = Some(format!("{}{}", new_path, &parent[old_path.len()..]));
+                }
+            }
+        }
+    }
+
+    /// Point connections, `[editable]` sections and NodePath properties at
+    /// `new_path` for the subtree at `path`. Runs before the nodes move, while
+    /// node_path() still gives each owner's old location.
+    fn rebase_references(&mut self, path: &str, new_path: &str) {
+        let rebase = |p: &str| rebase_path(p, path, new_path);
+        for connection in &mut self.connections {
+            if let Some(from) = rebase(&connection.from) {
+                connection.from = from;
+            }
+            if let Some(to) = rebase(&connection.to) {
+                connection.to = to;
+            }
+        }
+        for section in &mut self.unparsed_sections {
+            let editable = section
+                .header
+                .strip_prefix('[')
+                .and_then(|h| h.strip_suffix(']'))
+                .filter(|h| h.starts_with("editable "))
+                .and_then(|h| extract_attr(h, "path"))
+                .and_then(rebase);
+            if let Some(editable) = editable {
+                section.header = format!("[editable path=\"{}\"]", editable);
+            }
+        }
+
+        // NodePaths are relative to their owner, which may itself be moving
+        let literal = Regex::new(r#"NodePath\("([^"]*)"\)"#).unwrap();
+        for node in &mut self.nodes {
+            let owner = node_path(node);
+            let new_owner = rebase(&owner).unwrap_or_else(|| owner.clone());
+            for value in node.properties.values_mut() {
+                if !value.contains("NodePath(") {
+                    continue;
+                }
+                let rewritten = literal.replace_all(value, |c: &regex::Captures| {
+                    let (target, property) = c[1].split_at(c[1].find(':').unwrap_or(c[1].len()));
+                    let Some(old_target) = resolve_node_path(&owner, target) else {
+                        return c[0].to_string();
+                    };
+                    let new_target = rebase(&old_target).unwrap_or(old_target);
+                    if resolve_node_path(&new_owner, target).as_ref() == Some(&new_target) {
+                        return c[0].to_string();
+                    }
+                    format!(
+                        "NodePath(\"{}{}\")",
+                        relative_node_path(&new_owner, &new_target),
+                        property
+                    )
+                });
+                *value = rewritten.into_owned();
+            }
+        }
+    }
+
+    fn retain_indices(&mut self, drop: &[bool]) {
+        let mut index = 0;
+        self.nodes.retain(|_| {
+            index += 1;
+            !drop[index - 1]
+        });
+    }
+
     /// Format version
     pub fn format_version(&self) -> u32 {
         self.format
@@ -295,14 +1246,140 @@ impl GodotScene {
     }
 }
 
-/// Extract attribute value
-fn extract_attr<'a>(content: &'a str, attr: &str) -> Option<&'a str> {
-    let pattern = format!("{}=", attr);
-    content.find(&pattern).map(|start| {
-        let rest = &content[start + pattern.len()..];
-        if rest.starts_with('"') {
-            let rest = &rest[1..];
-            rest.find('"').map(|end| &rest[..end]).unwrap_or(rest)
+/// Path of a node relative to the scene root ("." for the root)
+pub(crate) fn node_path(node: &SceneNode) -> String {
+    match node.parent.as_deref() {
+        None => ".".to_string(),
+        Some(".") => node.name.clone(),
+        Some(parent) => format!("{}/{}", parent, node.name),
+    }
+}
+
+/// Whether `path` is `ancestor` or lies below it
+pub(crate) fn is_within(path: &str, ancestor: &str) -> bool {
+    path == ancestor
+        || path
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// `"key": SubResource("id")` entries of a dictionary value (keys may be
+/// StringNames, `&"key"`)
+fn sub_resource_entries(value: &str) -> Vec<(String, String)> {
+    let entry =
+        Regex::new(r#"&?"((?:[^"\\]|\\.)*)"\s*:\s*SubResource\(\s*"([^"]+)"\s*\)"#).unwrap();
+    entry
+        .captures_iter(value)
+        .map(|c| (c[1].to_string(), c[2].to_string()))
+        .collect()
+}
+
+/// `ExtResource("id")` / `SubResource("id")`, capturing the kind and the id
+const RESOURCE_REF: &str = r#"(ExtResource|SubResource)\(\s*"([^"]+)"\s*\)"#;
+
+/// Resource references in a value, as (kind, id)
+fn resource_refs(value: &str) -> Vec<(String, String)> {
+    let reference = Regex::new(RESOURCE_REF).unwrap();
+    reference
+        .captures_iter(value)
+        .map(|c| (c[1].to_string(), c[2].to_string()))
+        .collect()
+}
+
+/// Name numbered like Godot does ("Enemy" -> "Enemy2", "Enemy2" -> "Enemy3")
+/// that `taken` does not reject
+fn next_free_name(original: &str, taken: impl Fn(&str) -> bool) -> String {
+    let base = original.trim_end_matches(|c: char| c.is_ascii_digit());
+    let start = original[base.len()..].parse::<u64>().map_or(2, |n| n + 1);
+    (start..)
+        .map(|n| format!("{}{}", base, n))
+        .find(|name| !taken(name))
+        .unwrap_or_default()
+}
+
+/// `id`, or `id_2`, `id_3`... when `taken`
+fn free_id(id: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(id) {
+        return id.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", id, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+/// "./A/B" (as produced by rebasing onto ".") -> "A/B"
+fn relative_to_root(p: &str) -> String {
+    p.strip_prefix("./").unwrap_or(p).to_string()
+}
+
+/// `p` moved along with the subtree at `old`, if it lies within it
+fn rebase_path(p: &str, old: &str, new: &str) -> Option<String> {
+    is_within(p, old).then(|| format!("{}{}", new, &p[old.len()..]))
+}
+
+/// Scene-relative path of a relative NodePath seen from the node at `owner`.
+/// None for empty, absolute and unique-name (`%Name`) paths, and for paths
+/// leaving the scene.
+fn resolve_node_path(owner: &str, target: &str) -> Option<String> {
+    if target.is_empty() || target.starts_with(['/', '%']) {
+        return None;
+    }
+    let mut parts: Vec<&str> = owner.split('/').filter(|s| *s != ".").collect();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            name => parts.push(name),
+        }
+    }
+    Some(if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    })
+}
+
+/// Relative NodePath from the node at `owner` to the node at `target`
+fn relative_node_path(owner: &str, target: &str) -> String {
+    let split = |p: &str| -> Vec<String> {
+        p.split('/')
+            .filter(|s| *s != ".")
+            .map(str::to_string)
+            .collect()
+    };
+    let (owner, target) = (split(owner), split(target));
+    let common = owner
+        .iter()
+        .zip(&target)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts = vec!["..".to_string(); owner.len() - common];
+    parts.extend(target[common..].iter().cloned());
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Extract attribute value
+fn extract_attr<'a>(content: &'a str, attr: &str) -> Option<&'a str> {
+    let pattern = format!("{}=", attr);
+    // Whole attribute names only (`id=` must not match inside `uid=`)
+    let start = content.match_indices(&pattern).map(|(i, _)| i).find(|&i| {
+        content[..i]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace)
+    });
+    start.map(|start| {
+        let rest = &content[start + pattern.len()..];
+        if rest.starts_with('"') {
+            let rest = &rest[1..];
+            rest.find('"').map(|end| &rest[..end]).unwrap_or(rest)
         } else {
             rest.split_whitespace().next().unwrap_or("")
         }
@@ -322,28 +1399,865 @@ fn parse_ext_resource(content: &str) -> Result<ExtResource, TscnError> {
         id: id.to_string(),
         resource_type: resource_type.to_string(),
         path: path.to_string(),
+        uid: extract_attr(content, "uid").map(str::to_string),
     })
 }
 
+/// Parse a `[connection ...]` header
+fn parse_connection(content: &str) -> Result<Connection, TscnError> {
+    let attr = |name: &str| {
+        extract_attr(content, name)
+            .map(str::to_string)
+            .ok_or_else(|| TscnError::ParseError(format!("Missing {} in connection", name)))
+    };
+    // `binds=[...]` may contain spaces; `unbinds=` must not match it
+    let binds = content.find(" binds=[").map(|start| {
+        let rest = &content[start + " binds=".len()..];
+        rest.rfind(']')
+            .map(|end| &rest[..=end])
+            .unwrap_or(rest)
+            .to_string()
+    });
+
+    Ok(Connection {
+        signal: attr("signal")?,
+        from: attr("from")?,
+        to: attr("to")?,
+        method: attr("method")?,
+        flags: extract_attr(content, "flags").and_then(|f| f.parse().ok()),
+        unbinds: extract_attr(content, "unbinds").and_then(|u| u.parse().ok()),
+        binds,
+    })
+}
+
+/// Parse a `[sub_resource type="..." id="..."]` header
+fn parse_sub_resource_header(content: &str) -> Result<SubResource, TscnError> {
+    let resource_type = extract_attr(content, "type")
+        .ok_or_else(|| TscnError::ParseError("Missing type in sub_resource".into()))?;
+    let id = extract_attr(content, "id")
+        .ok_or_else(|| TscnError::ParseError("Missing id in sub_resource".into()))?;
+
+    Ok(SubResource {
+        id: id.to_string(),
+        resource_type: resource_type.to_string(),
+        properties: IndexMap::new(),
+    })
+}
+
+/// Whether `key` looks like a property name (`position`, `metadata/x`, `tracks/0/keys`)
+pub(crate) fn is_property_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '/' | ':' | '.' | '@' | '-'))
+}
+
 /// Parse node header
 fn parse_node_header(content: &str) -> Result<SceneNode, TscnError> {
+    let attrs = split_attrs(content);
     let name = extract_attr(content, "name")
         .ok_or_else(|| TscnError::ParseError("Missing name in node".into()))?;
-    let node_type = extract_attr(content, "type").unwrap_or("Node");
     let parent = extract_attr(content, "parent");
+    let instance = extract_attr(content, "instance").and_then(|value| {
+        value
+            .strip_prefix("ExtResource(\"")
+            .and_then(|v| v.strip_suffix("\")"))
+            .map(str::to_string)
+    });
+    // Without a type or instance the node overrides one of an inherited scene
+    let node_type = match extract_attr(content, "type") {
+        Some(node_type) => node_type,
+        None if instance.is_some() => "Node",
+        None => "",
+    };
 
     Ok(SceneNode {
         name: name.to_string(),
         node_type: node_type.to_string(),
         parent: parent.map(|s| s.to_string()),
-        properties: HashMap::new(),
+        instance,
+        groups: extract_groups(content),
+        extra_attrs: attrs
+            .iter()
+            .filter(|(key, _)| !MODELLED_NODE_ATTRS.contains(&key.as_str()))
+            .cloned()
+            .collect(),
+        attr_order: attrs.into_iter().map(|(key, _)| key).collect(),
+        properties: IndexMap::new(),
     })
 }
 
+/// Node header attributes with dedicated `SceneNode` fields
+const MODELLED_NODE_ATTRS: &[&str] = &["name", "type", "parent", "instance", "groups"];
+
+/// Node header attributes in the order Godot writes them
+const NODE_ATTR_ORDER: &[&str] = &[
+    "name",
+    "type",
+    "parent",
+    "owner",
+    "index",
+    "unique_id",
+    "node_paths",
+    "groups",
+    "instance_placeholder",
+    "instance",
+];
+
+/// Header attributes of a node as `key=value` pairs: the ones read from the
+/// file keep their order, others go where Godot would put them
+fn node_header_attrs(node: &SceneNode) -> Vec<(String, String)> {
+    let mut attrs = vec![("name".to_string(), format!("\"{}\"", node.name))];
+    // Instanced scenes and overrides of inherited nodes take their type from
+    // the PackedScene
+    if node.instance.is_none() && !node.node_type.is_empty() {
+        attrs.push(("type".to_string(), format!("\"{}\"", node.node_type)));
+    }
+    if let Some(ref parent) = node.parent {
+        attrs.push(("parent".to_string(), format!("\"{}\"", parent)));
+    }
+    if let Some(ref id) = node.instance {
+        attrs.push(("instance".to_string(), format!("ExtResource(\"{}\")", id)));
+    }
+    if !node.groups.is_empty() {
+        let groups: Vec<String> = node.groups.iter().map(|g| format!("\"{}\"", g)).collect();
+        attrs.push(("groups".to_string(), format!("[{}]", groups.join(", "))));
+    }
+    attrs.extend(node.extra_attrs.iter().cloned());
+
+    let rank = |key: &str| {
+        NODE_ATTR_ORDER
+            .iter()
+            .position(|k| *k == key)
+            .unwrap_or(NODE_ATTR_ORDER.len())
+    };
+    let (mut ordered, added): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|(key, _)| node.attr_order.contains(key));
+    ordered.sort_by_key(|(key, _)| node.attr_order.iter().position(|k| k == key));
+    for attr in added {
+        let at = ordered
+            .iter()
+            .position(|(key, _)| rank(key) > rank(&attr.0))
+            .unwrap_or(ordered.len());
+        ordered.insert(at, attr);
+    }
+    ordered
+}
+
+/// Tracks strings and brackets left open by a property value, so a value
+/// spanning several lines is read to its end
+#[derive(Debug, Default)]
+struct ValueScan {
+    depth: i32,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl ValueScan {
+    fn feed(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.in_string {
+                match c {
+                    _ if self.escaped => self.escaped = false,
+                    '\\' => self.escaped = true,
+                    '"' => self.in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => self.in_string = true,
+                '(' | '[' | '{' => self.depth += 1,
+                ')' | ']' | '}' => self.depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.in_string || self.depth > 0
+    }
+}
+
+/// Split a section header (without brackets) into `key=value` pairs, values as
+/// written; quotes, brackets and parentheses may contain spaces
+fn split_attrs(content: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    // Skip the section name
+    let mut rest = content
+        .split_once(char::is_whitespace)
+        .map(|(_, rest)| rest)
+        .unwrap_or("");
+
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let value_start = eq + 1;
+        let mut depth = 0i32;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut end = rest.len();
+        for (i, c) in rest[value_start..].char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                c if c.is_whitespace() && depth <= 0 => {
+                    end = value_start + i;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        if !key.is_empty() {
+            attrs.push((key.to_string(), rest[value_start..end].to_string()));
+        }
+        rest = rest[end..].trim_start();
+    }
+    attrs
+}
+
+/// Drop trailing blank lines of a preserved section
+fn finish_raw(mut raw: RawSection) -> RawSection {
+    while raw.lines.last().is_some_and(|l| l.trim().is_empty()) {
+        raw.lines.pop();
+    }
+    raw
+}
+
+/// Extract the `groups=[...]` list of a node header
+fn extract_groups(content: &str) -> Vec<String> {
+    let Some(start) = content.find("groups=[") else {
+        return Vec::new();
+    };
+    let rest = &content[start + "groups=[".len()..];
+    let list = rest.find(']').map(|end| &rest[..end]).unwrap_or(rest);
+    list.split(',')
+        .map(|g| g.trim().trim_matches('"').to_string())
+        .filter(|g| !g.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_instanced_node_round_trip() {
+        let content = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://coin.tscn" id="1_coin"]
+
+[node name="Level" type="Node2D"]
+
+[node name="Coin" parent="." instance=ExtResource("1_coin")]
+"#;
+        let scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.nodes[1].instance.as_deref(), Some("1_coin"));
+        assert_eq!(
+            scene.instance_path(&scene.nodes[1]),
+            Some("res://coin.tscn")
+        );
+        assert_eq!(scene.instance_path(&scene.nodes[0]), None);
+
+        let tscn = scene.to_tscn();
+        assert!(tscn.contains("[node name=\"Coin\" parent=\".\" instance=ExtResource(\"1_coin\")]"));
+    }
+
+    #[test]
+    fn test_inherited_scene() {
+        let base = r#"[gd_scene format=3]
+
+[node name="Enemy" type="CharacterBody2D"]
+
+[node name="Sprite" type="Sprite2D" parent="."]
+"#;
+        let content = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://enemy.tscn" id="1_base"]
+
+[node name="Boss" instance=ExtResource("1_base")]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.inherits_from(), Some("res://enemy.tscn"));
+        let load =
+            |path: &str| (path == "res://enemy.tscn").then(|| GodotScene::parse(base).unwrap());
+        assert_eq!(
+            scene.inherited_node("Sprite", load).map(|n| n.node_type),
+            Some("Sprite2D".to_string())
+        );
+        assert!(scene.inherited_node("Missing", load).is_none());
+
+        let index = scene.add_inherited_override("Sprite").unwrap();
+        scene.nodes[index]
+            .properties
+            .insert("scale".to_string(), "Vector2(2, 2)".to_string());
+        let tscn = scene.to_tscn();
+        assert!(tscn.contains("[node name=\"Sprite\" parent=\".\"]\nscale = Vector2(2, 2)"));
+
+        // Overrides keep their type-less header through a round trip
+        let reparsed = GodotScene::parse(&tscn).unwrap();
+        assert_eq!(reparsed.nodes[1].node_type, "");
+        assert_eq!(reparsed.to_tscn(), tscn);
+        assert!(scene.add_inherited_override("Sprite").is_err());
+    }
+
+    #[test]
+    fn test_connections_round_trip() {
+        let content = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Button" type="Button" parent="."]
+
+[node name="Timer" type="Timer" parent="."]
+
+[connection signal="pressed" from="Button" to="." method="_on_button_pressed"]
+[connection signal="timeout" from="Timer" to="." method="_on_tick" flags=3 unbinds=1 binds=[1, "a"]]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.connections.len(), 2);
+        assert_eq!(scene.connections[1].flags, Some(3));
+        assert_eq!(scene.connections[1].unbinds, Some(1));
+        assert_eq!(scene.connections[1].binds.as_deref(), Some("[1, \"a\"]"));
+        assert_eq!(scene.connections_from("Button").count(), 1);
+
+        let tscn = scene.to_tscn();
+        assert_eq!(
+            GodotScene::parse(&tscn).unwrap().connections,
+            scene.connections
+        );
+
+        let connection = Connection {
+            signal: "timeout".to_string(),
+            from: "Timer".to_string(),
+            to: "Button".to_string(),
+            method: "hide".to_string(),
+            flags: None,
+            unbinds: None,
+            binds: None,
+        };
+        assert!(scene.connect_signal(connection.clone()).is_ok());
+        assert!(scene.connect_signal(connection.clone()).is_err());
+        assert!(scene
+            .connect_signal(Connection {
+                from: "Missing".to_string(),
+                ..connection
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_sub_resources_round_trip() {
+        let content = r#"[gd_scene load_steps=3 format=3]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_a1"]
+radius = 12.0
+
+[sub_resource type="Curve2D" id="Curve2D_b2"]
+_data = {
+"points": PackedVector2Array(0, 0, 0, 0, 10, 20)
+}
+point_count = 1
+
+[node name="Main" type="Node2D"]
+
+[node name="Shape" type="CollisionShape2D" parent="."]
+shape = SubResource("CircleShape2D_a1")
+"#;
+        let scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.sub_resources.len(), 2);
+        assert_eq!(scene.sub_resources[0].properties["radius"], "12.0");
+        assert_eq!(
+            scene.sub_resources[1].properties["_data"],
+            "{\n\"points\": PackedVector2Array(0, 0, 0, 0, 10, 20)\n}"
+        );
+        assert_eq!(scene.sub_resources[1].properties["point_count"], "1");
+        assert_eq!(scene.nodes.len(), 2);
+
+        let tscn = scene.to_tscn();
+        assert!(tscn.contains("[sub_resource type=\"CircleShape2D\" id=\"CircleShape2D_a1\"]"));
+        let reparsed = GodotScene::parse(&tscn).unwrap();
+        assert_eq!(reparsed.sub_resources.len(), 2);
+        assert_eq!(
+            reparsed.sub_resources[1].properties,
+            scene.sub_resources[1].properties
+        );
+        assert!(tscn.find("[sub_resource").unwrap() < tscn.find("[node").unwrap());
+    }
+
+    #[test]
+    fn test_property_order_round_trip() {
+        let content = r#"[gd_scene load_steps=3 format=3 uid="uid://level"]
+
+[ext_resource type="Texture2D" uid="uid://icon" path="res://icon.svg" id="1_icon"]
+
+[sub_resource type="RectangleShape2D" id="RectangleShape2D_a1"]
+size = Vector2(32, 16)
+custom_solver_bias = 0.5
+
+[node name="Level" type="Node2D"]
+y_sort_enabled = true
+position = Vector2(10, 20)
+
+[node name="Player" type="CharacterBody2D" parent="."]
+position = Vector2(100, 200)
+rotation = 0.5
+scale = Vector2(2, 2)
+visible = false
+z_index = 3
+collision_mask = 5
+
+[node name="Sprite" type="Sprite2D" parent="Player"]
+texture = ExtResource("1_icon")
+offset = Vector2(0, -8)
+flip_h = true
+modulate = Color(1, 0.5, 0.5, 1)
+
+[node name="Shape" type="CollisionShape2D" parent="Player"]
+shape = SubResource("RectangleShape2D_a1")
+disabled = true
+
+"#;
+        let scene = GodotScene::parse(content).unwrap();
+        let keys: Vec<&str> = scene.nodes[1]
+            .properties
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "position",
+                "rotation",
+                "scale",
+                "visible",
+                "z_index",
+                "collision_mask"
+            ]
+        );
+        assert_eq!(scene.to_tscn(), content);
+        assert_eq!(
+            GodotScene::parse(&scene.to_tscn()).unwrap().to_tscn(),
+            content
+        );
+    }
+
+    #[test]
+    fn test_multi_line_value_round_trip() {
+        let content = r#"[gd_scene format=3 uid="uid://tool"]
+
+[sub_resource type="GDScript" id="GDScript_x1"]
+script/source = "extends Node
+
+var grid = [
+[1]
+]
+
+
+func _ready():
+	print(\"[done]\")
+"
+
+[node name="Tool" type="Node"]
+script = SubResource("GDScript_x1")
+points = PackedVector2Array(0, 0,
+
+1, 1)
+
+[future_section key="value"]
+text = "a
+
+[b]"
+"#;
+        let scene = GodotScene::parse(content).unwrap();
+        assert!(!scene.load_steps);
+        assert_eq!(scene.sub_resources.len(), 1);
+        assert_eq!(scene.nodes.len(), 1);
+        assert!(scene.sub_resources[0].properties["script/source"].contains("\n[1]\n]\n\n\nfunc"));
+        assert_eq!(scene.unparsed_sections.len(), 1);
+        assert_eq!(scene.to_tscn(), content);
+    }
+
+    #[test]
+    fn test_node_header_order_round_trip() {
+        let content = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://enemy.tscn" id="1_enemy"]
+
+[node name="Main" type="Node2D"]
+
+[node name="Enemy" parent="." groups=["enemies"] instance=ExtResource("1_enemy")]
+
+[node name="Camera" type="Camera2D" parent="." node_paths=PackedStringArray("target") groups=["cameras"]]
+target = NodePath("../Enemy")
+
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert!(scene.load_steps);
+        assert_eq!(scene.to_tscn(), content);
+
+        // Added attributes go where Godot puts them
+        scene.nodes[0].groups.push("levels".to_string());
+        scene.add_node(SceneNode {
+            name: "Spawner".to_string(),
+            node_type: "Node2D".to_string(),
+            parent: Some(".".to_string()),
+            instance: None,
+            groups: vec!["spawners".to_string()],
+            extra_attrs: vec![(
+                "node_paths".to_string(),
+                "PackedStringArray(\"target\")".to_string(),
+            )],
+            attr_order: Vec::new(),
+            properties: IndexMap::new(),
+        });
+        let tscn = scene.to_tscn();
+        assert!(tscn.contains("[node name=\"Main\" type=\"Node2D\" groups=[\"levels\"]]"));
+        assert!(tscn.contains(
+            "[node name=\"Spawner\" type=\"Node2D\" parent=\".\" node_paths=PackedStringArray(\"target\") groups=[\"spawners\"]]"
+        ));
+    }
+
+    #[test]
+    fn test_unmodelled_data_round_trip() {
+        let content = r#"[gd_scene load_steps=2 format=3 uid="uid://main"]
+
+[ext_resource type="PackedScene" uid="uid://player" path="res://player.tscn" id="1_player"]
+
+[node name="Main" type="Node2D"]
+
+[node name="Player" parent="." instance=ExtResource("1_player")]
+
+[node name="Camera" type="Camera2D" parent="." node_paths=PackedStringArray("target", "limit node") index="0" unique_id=1234]
+target = NodePath("../Player")
+
+[connection signal="ready" from="." to="." method="_on_ready"]
+
+[editable path="Player"]
+
+[future_section key="value"]
+data = [1,
+
+2]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.ext_resources[0].uid.as_deref(), Some("uid://player"));
+        assert_eq!(scene.ext_resources[0].id, "1_player");
+        assert_eq!(
+            scene.nodes[2].extra_attrs,
+            vec![
+                (
+                    "node_paths".to_string(),
+                    "PackedStringArray(\"target\", \"limit node\")".to_string()
+                ),
+                ("index".to_string(), "\"0\"".to_string()),
+                ("unique_id".to_string(), "1234".to_string()),
+            ]
+        );
+        assert_eq!(scene.nodes[2].properties.len(), 1);
+        assert_eq!(scene.unparsed_sections.len(), 2);
+        assert_eq!(
+            scene.unparsed_sections[1].lines,
+            vec!["data = [1,", "", "2]"]
+        );
+
+        // An edit keeps everything the model doesn't know about
+        scene.add_node(SceneNode {
+            name: "Enemy".to_string(),
+            node_type: "Node2D".to_string(),
+            parent: Some(".".to_string()),
+            instance: None,
+            groups: Vec::new(),
+            extra_attrs: Vec::new(),
+            attr_order: Vec::new(),
+            properties: IndexMap::new(),
+        });
+        let tscn = scene.to_tscn();
+        assert!(tscn.contains(
+            "[ext_resource type=\"PackedScene\" uid=\"uid://player\" path=\"res://player.tscn\" id=\"1_player\"]"
+        ));
+        assert!(tscn.contains(
+            "[node name=\"Camera\" type=\"Camera2D\" parent=\".\" node_paths=PackedStringArray(\"target\", \"limit node\") index=\"0\" unique_id=1234]"
+        ));
+        assert!(tscn.ends_with(
+            "[editable path=\"Player\"]\n\n[future_section key=\"value\"]\ndata = [1,\n\n2]\n"
+        ));
+
+        let reparsed = GodotScene::parse(&tscn).unwrap();
+        assert_eq!(reparsed.unparsed_sections, scene.unparsed_sections);
+        assert_eq!(reparsed.nodes[2].extra_attrs, scene.nodes[2].extra_attrs);
+    }
+
+    #[test]
+    fn test_reparent_node_moves_subtree() {
+        let content = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+speed = 200
+
+[node name="Sprite" type="Sprite2D" parent="Player"]
+
+[node name="Eyes" type="Sprite2D" parent="Player/Sprite"]
+
+[node name="World" type="Node2D" parent="."]
+
+[node name="Ground" type="StaticBody2D" parent="World"]
+
+[connection signal="ready" from="Player/Sprite" to="." method="_on_ready"]
+
+[editable path="Player/Sprite"]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(
+            scene.reparent_node("Player", "World"),
+            Ok("World/Player".to_string())
+        );
+
+        let paths: Vec<String> = scene.nodes.iter().map(node_path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".",
+                "World",
+                "World/Ground",
+                "World/Player",
+                "World/Player/Sprite",
+                "World/Player/Sprite/Eyes"
+            ]
+        );
+        assert_eq!(scene.nodes[3].properties["speed"], "200");
+        assert_eq!(scene.connections[0].from, "World/Player/Sprite");
+        assert_eq!(
+            scene.unparsed_sections[0].header,
+            "[editable path=\"World/Player/Sprite\"]"
+        );
+        assert!(scene.structure_issues().is_empty());
+
+        assert!(scene.reparent_node("World", "World/Player").is_err());
+        assert!(scene.reparent_node(".", "World").is_err());
+        assert!(scene.reparent_node("Missing", ".").is_err());
+        assert_eq!(
+            scene.reparent_node("World/Player/Sprite", "."),
+            Ok("Sprite".to_string())
+        );
+        assert_eq!(node_path(scene.nodes.last().unwrap()), "Sprite/Eyes");
+    }
+
+    #[test]
+    fn test_duplicate_node_copies_subtree() {
+        let content = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Enemy" type="CharacterBody2D" parent="."]
+speed = 80
+
+[node name="Sprite" type="Sprite2D" parent="Enemy"]
+
+[node name="Enemy2" type="CharacterBody2D" parent="."]
+
+[node name="World" type="Node2D" parent="."]
+
+[connection signal="ready" from="Enemy/Sprite" to="Enemy" method="_on_ready"]
+[connection signal="ready" from="World" to="Enemy" method="_on_world_ready"]
+
+[editable path="Enemy/Sprite"]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(
+            scene.duplicate_node("Enemy", None),
+            Ok("Enemy3".to_string())
+        );
+
+        let paths: Vec<String> = scene.nodes.iter().map(node_path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".",
+                "Enemy",
+                "Enemy/Sprite",
+                "Enemy3",
+                "Enemy3/Sprite",
+                "Enemy2",
+                "World"
+            ]
+        );
+        assert_eq!(scene.nodes[3].properties["speed"], "80");
+        assert_eq!(scene.connections.len(), 3);
+        assert_eq!(scene.connections[2].from, "Enemy3/Sprite");
+        assert_eq!(scene.connections[2].to, "Enemy3");
+        assert_eq!(
+            scene.unparsed_sections[1].header,
+            "[editable path=\"Enemy3/Sprite\"]"
+        );
+
+        assert_eq!(
+            scene.duplicate_node("Enemy3", None),
+            Ok("Enemy4".to_string())
+        );
+        assert_eq!(
+            scene.duplicate_node("Enemy/Sprite", Some("Shadow")),
+            Ok("Enemy/Shadow".to_string())
+        );
+        assert!(scene.duplicate_node("Enemy", Some("World")).is_err());
+        assert!(scene.duplicate_node(".", None).is_err());
+        assert!(scene.structure_issues().is_empty());
+    }
+
+    #[test]
+    fn test_subtree_extract_and_insert() {
+        let source = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Texture2D" path="res://enemy.png" id="1_tex"]
+[ext_resource type="Script" path="res://main.gd" id="2_main"]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_a"]
+radius = 8.0
+
+[node name="Main" type="Node2D"]
+script = ExtResource("2_main")
+
+[node name="Enemy" type="Area2D" parent="."]
+
+[node name="Sprite" type="Sprite2D" parent="Enemy"]
+texture = ExtResource("1_tex")
+
+[node name="Shape" type="CollisionShape2D" parent="Enemy"]
+shape = SubResource("CircleShape2D_a")
+
+[connection signal="body_entered" from="Enemy" to="Enemy/Sprite" method="_on_hit"]
+[connection signal="body_entered" from="Enemy" to="." method="_on_enemy_hit"]
+"#;
+        let subtree = GodotScene::parse(source)
+            .unwrap()
+            .extract_subtree("Enemy")
+            .unwrap();
+        let paths: Vec<String> = subtree.nodes.iter().map(node_path).collect();
+        assert_eq!(paths, vec![".", "Sprite", "Shape"]);
+        assert_eq!(subtree.ext_resources.len(), 1);
+        assert_eq!(subtree.ext_resources[0].id, "1_tex");
+        assert_eq!(subtree.sub_resources.len(), 1);
+        assert_eq!(subtree.connections.len(), 1);
+        assert_eq!(subtree.connections[0].from, ".");
+        assert_eq!(subtree.connections[0].to, "Sprite");
+
+        let target = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Texture2D" path="res://other.png" id="1_tex"]
+
+[sub_resource type="RectangleShape2D" id="CircleShape2D_a"]
+
+[node name="Level" type="Node2D"]
+
+[node name="Enemies" type="Node2D" parent="."]
+
+[node name="Enemy" type="Area2D" parent="Enemies"]
+
+[node name="Ground" type="StaticBody2D" parent="."]
+"#;
+        let mut scene = GodotScene::parse(target).unwrap();
+        assert_eq!(
+            scene.insert_subtree(&subtree, "Enemies"),
+            Ok("Enemies/Enemy2".to_string())
+        );
+
+        let paths: Vec<String> = scene.nodes.iter().map(node_path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".",
+                "Enemies",
+                "Enemies/Enemy",
+                "Enemies/Enemy2",
+                "Enemies/Enemy2/Sprite",
+                "Enemies/Enemy2/Shape",
+                "Ground"
+            ]
+        );
+        assert_eq!(scene.ext_resources[1].id, "1_tex_2");
+        assert_eq!(scene.ext_resources[1].path, "res://enemy.png");
+        assert_eq!(
+            scene.nodes[4].properties["texture"],
+            "ExtResource(\"1_tex_2\")"
+        );
+        assert_eq!(scene.sub_resources[1].id, "CircleShape2D_a_2");
+        assert_eq!(
+            scene.nodes[5].properties["shape"],
+            "SubResource(\"CircleShape2D_a_2\")"
+        );
+        assert_eq!(scene.connections[0].from, "Enemies/Enemy2");
+        assert_eq!(scene.connections[0].to, "Enemies/Enemy2/Sprite");
+        assert!(scene.structure_issues().is_empty());
+
+        // Pasting again reuses the texture registered by the first paste
+        scene.insert_subtree(&subtree, ".").unwrap();
+        assert_eq!(scene.ext_resources.len(), 2);
+        assert!(scene.insert_subtree(&subtree, "Missing").is_err());
+    }
+
+    #[test]
+    fn test_rename_node_updates_references() {
+        let content = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+target = NodePath("Player/Sprite")
+
+[node name="Player" type="CharacterBody2D" parent="."]
+sprite = NodePath("Sprite")
+camera = NodePath("../Camera")
+
+[node name="Sprite" type="Sprite2D" parent="Player"]
+follow = NodePath("../../Player:position")
+
+[node name="Camera" type="Camera2D" parent="."]
+targets = Array[NodePath]([NodePath("../Player"), NodePath("../Player/Sprite:frame"), NodePath("%Hud")])
+
+[connection signal="ready" from="Player/Sprite" to="Player" method="_on_ready"]
+"#;
+        let mut scene = GodotScene::parse(content).unwrap();
+        assert_eq!(scene.rename_node("Player", "Hero"), Ok("Hero".to_string()));
+
+        let paths: Vec<String> = scene.nodes.iter().map(node_path).collect();
+        assert_eq!(paths, vec![".", "Hero", "Hero/Sprite", "Camera"]);
+        assert_eq!(
+            scene.nodes[0].properties["target"],
+            "NodePath(\"Hero/Sprite\")"
+        );
+        // Paths from inside the renamed subtree that don't cross its name stay as written
+        assert_eq!(scene.nodes[1].properties["sprite"], "NodePath(\"Sprite\")");
+        assert_eq!(
+            scene.nodes[1].properties["camera"],
+            "NodePath(\"../Camera\")"
+        );
+        assert_eq!(
+            scene.nodes[2].properties["follow"],
+            "NodePath(\"..:position\")"
+        );
+        assert_eq!(
+            scene.nodes[3].properties["targets"],
+            "Array[NodePath]([NodePath(\"../Hero\"), NodePath(\"../Hero/Sprite:frame\"), NodePath(\"%Hud\")])"
+        );
+        assert_eq!(scene.connections[0].from, "Hero/Sprite");
+        assert_eq!(scene.connections[0].to, "Hero");
+
+        assert!(scene.rename_node("Hero", "Camera").is_err());
+        assert!(scene.rename_node("Hero", "Bad/Name").is_err());
+        assert_eq!(scene.rename_node(".", "Level"), Ok(".".to_string()));
+        assert_eq!(scene.nodes[0].name, "Level");
+    }
+
     #[test]
     fn test_create_scene() {
         let scene = GodotScene::new("Player", "CharacterBody3D");
@@ -368,4 +2282,87 @@ mod tests {
         assert_eq!(scene.nodes[0].name, "Root");
         assert_eq!(scene.nodes[1].name, "Player");
     }
+
+    #[test]
+    fn test_parse_groups() {
+        let content = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Root" type="Node3D"]
+
+[node name="Spawn" type="Marker3D" parent="." groups=["spawn", "markers"]]
+"#;
+        let scene = GodotScene::parse(content).unwrap();
+
+        assert!(scene.nodes[0].groups.is_empty());
+        assert_eq!(scene.nodes[1].groups, vec!["spawn", "markers"]);
+        assert!(scene
+            .to_tscn()
+            .contains(r#"parent="." groups=["spawn", "markers"]]"#));
+    }
+
+    const MALFORMED: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Root" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+
+[node name="Player" type="Node2D" parent="."]
+
+[node name="Enemy" type="Node2D" parent="Missing/Group"]
+
+[node name="Sprite" type="Sprite2D" parent="Missing/Group/Enemy"]
+
+[node name="Stray" type="Node"]
+"#;
+
+    #[test]
+    fn test_structure_issues() {
+        let scene = GodotScene::parse(MALFORMED).unwrap();
+        let issues = scene.structure_issues();
+
+        assert_eq!(
+            issues,
+            vec![
+                StructureIssue::Duplicate {
+                    index: 2,
+                    path: "Player".to_string()
+                },
+                StructureIssue::Orphan {
+                    index: 3,
+                    path: "Missing/Group/Enemy".to_string(),
+                    parent: "Missing/Group".to_string()
+                },
+                StructureIssue::ExtraRoot {
+                    index: 5,
+                    name: "Stray".to_string()
+                },
+            ]
+        );
+        assert!(GodotScene::new("Root", "Node")
+            .structure_issues()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_repair_reattach_orphans() {
+        let mut scene = GodotScene::parse(MALFORMED).unwrap();
+        let changes =
+            scene.repair_structure(&[StructureFix::ReattachOrphans, StructureFix::DropDuplicates]);
+
+        assert_eq!(changes.len(), 3);
+        assert!(scene.structure_issues().is_empty());
+        assert_eq!(scene.nodes.len(), 5);
+        assert_eq!(scene.nodes[2].parent.as_deref(), Some("."));
+        assert_eq!(scene.nodes[3].parent.as_deref(), Some("Enemy"));
+        assert_eq!(scene.nodes[4].parent.as_deref(), Some("."));
+    }
+
+    #[test]
+    fn test_repair_drop_orphans() {
+        let mut scene = GodotScene::parse(MALFORMED).unwrap();
+        scene.repair_structure(&[StructureFix::DropOrphans]);
+
+        let names: Vec<&str> = scene.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["Root", "Player", "Player"]);
+    }
 }
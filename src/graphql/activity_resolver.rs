@@ -0,0 +1,103 @@
+//! Activity Resolver
+//!
+//! Maintains the activity queue displayed by the editor dock panel. The server
+//! appends items to `res://.godot-mcp/activity.json`; the plugin polls the file
+//! and shows everything above the last id it has seen.
+
+use serde::{Deserialize, Serialize};
+
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+use super::types::*;
+
+/// Queue file polled by the editor dock
+const ACTIVITY_PATH: &str = "res://.godot-mcp/activity.json";
+
+/// Oldest items are dropped beyond this size
+const MAX_ACTIVITY_ITEMS: usize = 200;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActivityQueue {
+    next_id: i32,
+    items: Vec<ActivityItem>,
+}
+
+/// Resolve notifyEditor mutation
+pub fn resolve_notify_editor(
+    ctx: &GqlContext,
+    message: &str,
+    level: NotifyLevel,
+    kind: ActivityKind,
+    detail: Option<String>,
+) -> NotifyEditorResult {
+    if message.trim().is_empty() {
+        return NotifyEditorResult {
+            success: false,
+            item: None,
+            message: Some("Message must not be empty".to_string()),
+        };
+    }
+
+    let mut queue = read_queue(ctx);
+    let item = ActivityItem {
+        id: queue.next_id.max(1),
+        timestamp: now_millis().to_string(),
+        level,
+        kind,
+        message: message.to_string(),
+        detail,
+    };
+    queue.next_id = item.id + 1;
+    queue.items.push(item.clone());
+    if queue.items.len() > MAX_ACTIVITY_ITEMS {
+        let overflow = queue.items.len() - MAX_ACTIVITY_ITEMS;
+        queue.items.drain(..overflow);
+    }
+
+    match write_queue(ctx, &queue) {
+        Ok(()) => NotifyEditorResult {
+            success: true,
+            item: Some(item),
+            message: None,
+        },
+        Err(e) => NotifyEditorResult {
+            success: false,
+            item: None,
+            message: Some(format!("Failed to write activity queue: {}", e)),
+        },
+    }
+}
+
+/// Resolve editorActivity query (items with id greater than `since`)
+pub fn resolve_editor_activity(ctx: &GqlContext, since: i32) -> Vec<ActivityItem> {
+    read_queue(ctx)
+        .items
+        .into_iter()
+        .filter(|item| item.id > since)
+        .collect()
+}
+
+fn read_queue(ctx: &GqlContext) -> ActivityQueue {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, ACTIVITY_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_queue(ctx: &GqlContext, queue: &ActivityQueue) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, ACTIVITY_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(queue)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
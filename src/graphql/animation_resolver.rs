@@ -0,0 +1,253 @@
+//! Animation Resolver
+//!
+//! Rewrites animation track paths in bulk, after nodes were renamed or
+//! reparented or when animations are copied between rigs with a similar
+//! layout. Scene files are edited directly (animations embedded in the
+//! player's libraries); without a scene path the scene open in the editor is
+//! remapped live through the plugin.
+
+use crate::godot::tscn::GodotScene;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::scene_lock;
+use super::types::*;
+
+/// Resolve remapAnimationTracks mutation
+pub async fn resolve_remap_animation_tracks(
+    ctx: &GqlContext,
+    input: &RemapAnimationTracksInput,
+) -> RemapAnimationTracksResult {
+    let Some(scene_path) = input.scene_path.as_deref() else {
+        return remap_live(ctx, input).await;
+    };
+    if input.dry_run {
+        return remap_scene_tracks(ctx, scene_path, input);
+    }
+
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return failure(input.dry_run, e),
+    };
+    let result = remap_scene_tracks(ctx, scene_path, input);
+    scene_lock::unlock_scene(ctx, lock, result.success && !result.remapped.is_empty()).await;
+    result
+}
+
+fn remap_scene_tracks(
+    ctx: &GqlContext,
+    scene_path: &str,
+    input: &RemapAnimationTracksInput,
+) -> RemapAnimationTracksResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return remap_failure(input, format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return remap_failure(input, format!("Failed to parse scene: {}", e)),
+    };
+    let animations = match scene.player_animations(&input.player) {
+        Ok(animations) => animations,
+        Err(message) => return remap_failure(input, message),
+    };
+    if let Some(missing) = input
+        .animations
+        .iter()
+        .flatten()
+        .find(|name| !animations.iter().any(|(a, _)| a == *name))
+    {
+        return remap_failure(input, format!("Animation not found: {}", missing));
+    }
+
+    let mut remapped = Vec::new();
+    for (name, id) in &animations {
+        if input
+            .animations
+            .as_ref()
+            .is_some_and(|only| !only.contains(name))
+        {
+            continue;
+        }
+        let Some(animation) = scene.sub_resources.iter_mut().find(|s| &s.id == id) else {
+            continue;
+        };
+
+        let mut tracks: Vec<(i32, String)> = animation
+            .properties
+            .keys()
+            .filter_map(|key| {
+                let index = key.strip_prefix("tracks/")?.strip_suffix("/path")?;
+                Some((index.parse().ok()?, key.clone()))
+            })
+            .collect();
+        tracks.sort();
+        for (track, key) in tracks {
+            let value = &animation.properties[&key];
+            let Some(from) = node_path_literal(value) else {
+                continue;
+            };
+            let Some(to) = remap_track_path(&from, &input.mapping) else {
+                continue;
+            };
+            if !input.dry_run {
+                animation
+                    .properties
+                    .insert(key, format!("NodePath(\"{}\")", to));
+            }
+            remapped.push(RemappedTrack {
+                animation: name.clone(),
+                track,
+                from,
+                to,
+            });
+        }
+    }
+
+    if !input.dry_run && !remapped.is_empty() {
+        if let Err(e) = audit::write_file(ctx, "remapAnimationTracks", &file_path, scene.to_tscn())
+        {
+            return remap_failure(input, format!("Failed to write scene: {}", e));
+        }
+        telemetry::record_files_written(1);
+    }
+
+    RemapAnimationTracksResult {
+        success: true,
+        dry_run: input.dry_run,
+        remapped,
+        error: None,
+    }
+}
+
+/// Remap the tracks of the scene open in the editor through the plugin
+async fn remap_live(
+    ctx: &GqlContext,
+    input: &RemapAnimationTracksInput,
+) -> RemapAnimationTracksResult {
+    let command = GodotLiveCommand::RemapAnimationTracks {
+        player: input.player.clone(),
+        mapping: input.mapping.clone(),
+        animations: input.animations.clone(),
+        dry_run: input.dry_run,
+    };
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return failure(input.dry_run, e.to_structured_error()),
+    };
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return remap_failure(input, message.to_string());
+    }
+
+    RemapAnimationTracksResult {
+        success: true,
+        dry_run: input.dry_run,
+        remapped: serde_json::from_value(value["remapped"].clone()).unwrap_or_default(),
+        error: None,
+    }
+}
+
+/// New path for a track, or None when no mapping applies. A `from` containing
+/// ':' matches the whole track path; otherwise it matches the node part or an
+/// ancestor of it, keeping the rest. The longest matching `from` wins.
+fn remap_track_path(path: &str, mapping: &[TrackPathMappingInput]) -> Option<String> {
+    let (node, property) = match path.split_once(':') {
+        Some((node, property)) => (node, Some(property)),
+        None => (path, None),
+    };
+    mapping
+        .iter()
+        .filter_map(|m| {
+            if m.from.contains(':') {
+                return (m.from == path).then(|| (m.from.len(), m.to.clone()));
+            }
+            let rest = node
+                .strip_prefix(m.from.as_str())
+                .filter(|rest| rest.is_empty() || rest.starts_with('/'))?;
+            let node = match (m.to.as_str(), rest) {
+                (".", rest) if !rest.is_empty() => rest[1..].to_string(),
+                (to, rest) => format!("{}{}", to, rest),
+            };
+            let path = match property {
+                Some(property) => format!("{}:{}", node, property),
+                None => node,
+            };
+            Some((m.from.len(), path))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, new_path)| new_path)
+        .filter(|new_path| new_path != path)
+}
+
+/// The string inside `NodePath("...")`
+fn node_path_literal(value: &str) -> Option<String> {
+    value
+        .trim()
+        .strip_prefix("NodePath(\"")
+        .and_then(|v| v.strip_suffix("\")"))
+        .map(str::to_string)
+}
+
+fn remap_failure(input: &RemapAnimationTracksInput, message: String) -> RemapAnimationTracksResult {
+    failure(
+        input.dry_run,
+        GqlStructuredError::new("TRACK_REMAP_FAILED", GqlErrorCategory::Validation, message)
+            .with_suggestion(
+                "player には AnimationPlayer のパスを、animations には既存のアニメーション名を指定してください",
+            ),
+    )
+}
+
+fn failure(dry_run: bool, error: GqlStructuredError) -> RemapAnimationTracksResult {
+    RemapAnimationTracksResult {
+        success: false,
+        dry_run,
+        remapped: Vec::new(),
+        error: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(pairs: &[(&str, &str)]) -> Vec<TrackPathMappingInput> {
+        pairs
+            .iter()
+            .map(|(from, to)| TrackPathMappingInput {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_remap_track_path() {
+        let mapping = mapping(&[
+            ("Body", "Rig/Body"),
+            ("Body/Arm", "Rig/LeftArm"),
+            ("Head:modulate", "Rig/Head:self_modulate"),
+            ("Old", "."),
+        ]);
+        let remap = |path| remap_track_path(path, &mapping);
+
+        assert_eq!(remap("Body:position").as_deref(), Some("Rig/Body:position"));
+        assert_eq!(
+            remap("Body/Arm/Hand:rotation").as_deref(),
+            Some("Rig/LeftArm/Hand:rotation")
+        );
+        assert_eq!(remap("Body/Leg").as_deref(), Some("Rig/Body/Leg"));
+        assert_eq!(
+            remap("Head:modulate").as_deref(),
+            Some("Rig/Head:self_modulate")
+        );
+        assert_eq!(remap("Old/Sprite:frame").as_deref(), Some("Sprite:frame"));
+        assert_eq!(remap("Head:position"), None);
+        assert_eq!(remap("Bodyguard:position"), None);
+    }
+}
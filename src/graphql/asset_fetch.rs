@@ -0,0 +1,227 @@
+//! Remote asset downloads
+//!
+//! A deliberately narrow HTTP downloader for `fetchAsset`: only hosts on the
+//! allowlist (`[assets] allowed_domains` in the config file, see
+//! [`set_config`]) are contacted, redirects must stay on the allowlist, files
+//! over `max_bytes` are refused (by `Content-Length` and again while
+//! streaming), and requests to the same host are spaced `min_interval_ms`
+//! apart so a feature setup fetching many files stays polite.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use reqwest::{redirect, Client, Url};
+
+use crate::config::AssetsConfig;
+
+use super::types::{GqlErrorCategory, GqlStructuredError};
+
+/// Limits applied to every download
+static CONFIG: RwLock<Option<AssetsConfig>> = RwLock::new(None);
+
+/// Give up on a download after this long
+const TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Redirects followed before giving up
+const MAX_REDIRECTS: usize = 5;
+
+/// Set the download limits (the config file's `[assets]` section)
+pub fn set_config(config: AssetsConfig) {
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(config);
+}
+
+/// Download limits currently in effect
+pub fn config() -> AssetsConfig {
+    CONFIG
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// A downloaded file
+#[derive(Debug, Clone)]
+pub struct Download {
+    pub data: Vec<u8>,
+    pub content_type: Option<String>,
+    /// URL after redirects
+    pub url: String,
+}
+
+/// Download `url` within the configured limits
+pub async fn download(url: &str) -> Result<Download, Box<GqlStructuredError>> {
+    let config = config();
+    let url = Url::parse(url)
+        .map_err(|e| fetch_error("INVALID_INPUT", format!("Invalid URL {}: {}", url, e)))?;
+    check_url(&config, &url)?;
+    throttle(&config, url.host_str().unwrap_or_default()).await;
+
+    let allowlist = config.clone();
+    let client = Client::builder()
+        .timeout(TIMEOUT)
+        .user_agent(concat!("godot-mcp-rs/", env!("CARGO_PKG_VERSION")))
+        .redirect(redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if let Err(error) = check_url(&allowlist, attempt.url()) {
+                attempt.error(error.message)
+            } else {
+                attempt.follow()
+            }
+        }))
+        .build()
+        .map_err(|e| fetch_error("ASSET_FETCH_FAILED", e.to_string()))?;
+
+    let mut response = client.get(url.clone()).send().await.map_err(|e| {
+        fetch_error(
+            "ASSET_FETCH_FAILED",
+            format!("Request to {} failed: {}", url, with_causes(&e)),
+        )
+    })?;
+    if !response.status().is_success() {
+        return Err(fetch_error(
+            "ASSET_FETCH_FAILED",
+            format!("{} returned {}", url, response.status()),
+        ));
+    }
+    let too_large = |size: u64| {
+        Box::new(
+            GqlStructuredError::new(
+                "ASSET_TOO_LARGE",
+                GqlErrorCategory::Validation,
+                format!(
+                    "{} is larger than the {} byte limit ({} bytes)",
+                    url, config.max_bytes, size
+                ),
+            )
+            .with_suggestion("設定ファイルの [assets] max_bytes を引き上げてください"),
+        )
+    };
+    if let Some(size) = response.content_length() {
+        if size > config.max_bytes {
+            return Err(too_large(size));
+        }
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let final_url = response.url().to_string();
+    // The length header may be missing or wrong: count while reading
+    let mut data = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| {
+        fetch_error(
+            "ASSET_FETCH_FAILED",
+            format!("Download of {} failed: {}", url, e),
+        )
+    })? {
+        data.extend_from_slice(&chunk);
+        if data.len() as u64 > config.max_bytes {
+            return Err(too_large(data.len() as u64));
+        }
+    }
+
+    Ok(Download {
+        data,
+        content_type,
+        url: final_url,
+    })
+}
+
+/// HTTPS to an allowed host (plain HTTP only on loopback, for local mirrors)
+fn check_url(config: &AssetsConfig, url: &Url) -> Result<(), Box<GqlStructuredError>> {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let loopback = host == "localhost"
+        || host
+            .trim_matches(['[', ']'])
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    if url.scheme() != "https" && !(url.scheme() == "http" && loopback) {
+        return Err(fetch_error(
+            "ASSET_URL_NOT_ALLOWED",
+            format!("Only https:// URLs can be fetched: {}", url),
+        ));
+    }
+
+    let allowed = config.allowed_domains.iter().any(|domain| {
+        let domain = domain.trim_start_matches("*.").to_ascii_lowercase();
+        host == domain || host.ends_with(&format!(".{}", domain))
+    });
+    if !allowed {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "ASSET_URL_NOT_ALLOWED",
+                GqlErrorCategory::Validation,
+                format!("{} is not on the asset domain allowlist", host),
+            )
+            .with_suggestion(
+                "設定ファイルの [assets] allowed_domains にドメインを追加してください",
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Wait until `min_interval_ms` has passed since the last request to `host`
+async fn throttle(config: &AssetsConfig, host: &str) {
+    static NEXT_REQUEST: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+    let interval = Duration::from_millis(config.min_interval_ms);
+    let start = {
+        let mut next = NEXT_REQUEST
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let slot = next.get(host).copied().filter(|t| *t > now).unwrap_or(now);
+        // Reserve the slot before waiting so concurrent requests queue up
+        next.insert(host.to_string(), slot + interval);
+        slot
+    };
+    tokio::time::sleep_until(start.into()).await;
+}
+
+/// An error and its sources, so redirect refusals explain themselves
+fn with_causes(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message = format!("{}: {}", message, cause);
+        source = cause.source();
+    }
+    message
+}
+
+fn fetch_error(code: &str, message: String) -> Box<GqlStructuredError> {
+    let category = if code == "ASSET_FETCH_FAILED" {
+        GqlErrorCategory::Connection
+    } else {
+        GqlErrorCategory::Validation
+    };
+    Box::new(GqlStructuredError::new(code, category, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_url() {
+        let config = AssetsConfig {
+            allowed_domains: vec!["kenney.nl".to_string(), "127.0.0.1".to_string()],
+            ..AssetsConfig::default()
+        };
+        let check = |url: &str| check_url(&config, &Url::parse(url).unwrap()).is_ok();
+        assert!(check("https://kenney.nl/media/pages/assets/pack.zip"));
+        assert!(check("https://www.kenney.nl/tiles.png"));
+        assert!(check("http://127.0.0.1:8000/tiles.png"));
+        // Plain HTTP off loopback, lookalike domains, other hosts
+        assert!(!check("http://kenney.nl/tiles.png"));
+        assert!(!check("https://evilkenney.nl/tiles.png"));
+        assert!(!check("https://kenney.nl.example.com/tiles.png"));
+        assert!(!check("https://example.com/tiles.png"));
+        assert!(!check("file:///etc/passwd"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Asset Resolver
+//!
+//! `fetchAsset(url, destination)` downloads a remote asset (texture, sound,
+//! model, font) into the project through [`asset_fetch`], so a feature setup
+//! that needs CC0 art can finish without the user fetching files by hand.
+//! Only asset file types are accepted, and unless disabled in the config the
+//! asset's folder must carry a license file: one already there, or one
+//! downloaded alongside from `licenseUrl`.
+//!
+//! [`asset_fetch`]: super::asset_fetch
+
+use std::path::Path;
+
+use reqwest::Url;
+
+use crate::path_utils::ResPath;
+
+use super::asset_fetch;
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::to_res_path;
+use super::types::*;
+
+/// File types fetchAsset writes (never scripts or scenes)
+const ASSET_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "webp", "svg", "bmp", "tga", "exr", "hdr", "ktx", "ogg", "wav", "mp3",
+    "glb", "gltf", "bin", "obj", "mtl", "fbx", "ttf", "otf", "woff", "woff2", "fnt",
+];
+
+/// Name given to a downloaded license whose URL doesn't look like one
+const DEFAULT_LICENSE_NAME: &str = "LICENSE.txt";
+
+/// Resolve fetchAsset mutation
+pub async fn resolve_fetch_asset(ctx: &GqlContext, input: &FetchAssetInput) -> FetchAssetResult {
+    match fetch(ctx, input).await {
+        Ok(result) => result,
+        Err(error) => FetchAssetResult {
+            success: false,
+            path: None,
+            size: 0,
+            content_type: None,
+            license_path: None,
+            error: Some(*error),
+        },
+    }
+}
+
+async fn fetch(
+    ctx: &GqlContext,
+    input: &FetchAssetInput,
+) -> Result<FetchAssetResult, Box<GqlStructuredError>> {
+    let invalid = |message: String| {
+        Box::new(GqlStructuredError::new(
+            "INVALID_INPUT",
+            GqlErrorCategory::Validation,
+            message,
+        ))
+    };
+
+    let mut destination = input.destination.trim().to_string();
+    if destination.ends_with('/') || destination == "res:/" {
+        let name = url_file_name(&input.url)
+            .ok_or_else(|| invalid(format!("No file name in URL {}", input.url)))?;
+        destination = format!("{}/{}", destination.trim_end_matches('/'), name);
+    }
+    let destination =
+        ResPath::new(&destination).map_err(|e| invalid(format!("Invalid destination: {}", e)))?;
+    let extension = Path::new(destination.relative())
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if !ASSET_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(Box::new(GqlStructuredError::new(
+            "ASSET_TYPE_NOT_ALLOWED",
+            GqlErrorCategory::Validation,
+            format!(
+                "{} is not an asset file type ({})",
+                destination.as_res_path(),
+                ASSET_EXTENSIONS.join(", ")
+            ),
+        )));
+    }
+
+    let fs_path = destination.to_fs_path(&ctx.project_path);
+    if ctx.fs.exists(&fs_path) && !input.overwrite {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "ASSET_EXISTS",
+                GqlErrorCategory::Validation,
+                format!("{} already exists", destination.as_res_path()),
+            )
+            .with_suggestion("上書きする場合は overwrite: true を指定してください"),
+        ));
+    }
+    let folder = fs_path.parent().unwrap_or(&ctx.project_path).to_path_buf();
+
+    // The license is checked (and fetched) before the asset is downloaded
+    let license = match &input.license_url {
+        Some(url) => {
+            let name = url_file_name(url)
+                .filter(|name| is_license_file(name))
+                .unwrap_or_else(|| DEFAULT_LICENSE_NAME.to_string());
+            Some((folder.join(name), asset_fetch::download(url).await?))
+        }
+        None => None,
+    };
+    let existing_license = find_license(ctx, &folder);
+    if license.is_none() && existing_license.is_none() && asset_fetch::config().require_license {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "ASSET_LICENSE_MISSING",
+                GqlErrorCategory::Validation,
+                format!(
+                    "No license file in {} for {}",
+                    to_res_path(&ctx.project_path, &folder),
+                    input.url
+                ),
+            )
+            .with_suggestion(
+                "licenseUrl でライセンスファイルを指定するか、保存先フォルダに LICENSE ファイルを置いてください",
+            ),
+        ));
+    }
+
+    let asset = asset_fetch::download(&input.url).await?;
+
+    let write_error = |path: &Path, e: std::io::Error| {
+        Box::new(GqlStructuredError::new(
+            "ASSET_WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to write {}: {}", path.display(), e),
+        ))
+    };
+    ctx.fs
+        .create_dir_all(&folder)
+        .map_err(|e| write_error(&folder, e))?;
+    let mut written = 1;
+    if let Some((path, download)) = &license {
+        audit::write_binary_file(ctx, "fetchAsset", path, &download.data)
+            .map_err(|e| write_error(path, e))?;
+        written += 1;
+    }
+    audit::write_binary_file(ctx, "fetchAsset", &fs_path, &asset.data)
+        .map_err(|e| write_error(&fs_path, e))?;
+    crate::telemetry::record_files_written(written);
+
+    let license_path = license
+        .map(|(path, _)| path)
+        .or(existing_license)
+        .map(|path| to_res_path(&ctx.project_path, &path));
+    Ok(FetchAssetResult {
+        success: true,
+        path: Some(destination.as_res_path()),
+        size: asset.data.len() as i32,
+        content_type: asset.content_type,
+        license_path,
+        error: None,
+    })
+}
+
+/// Last path segment of a URL, if any
+fn url_file_name(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let name = url.path_segments()?.next_back()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// LICENSE, License.txt, licence.md, COPYING, ...
+fn is_license_file(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    ["license", "licence", "copying"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// License file already in the folder
+fn find_license(ctx: &GqlContext, folder: &Path) -> Option<std::path::PathBuf> {
+    ctx.fs.read_dir(folder).ok()?.into_iter().find(|path| {
+        ctx.fs.is_file(path)
+            && path
+                .file_name()
+                .is_some_and(|name| is_license_file(&name.to_string_lossy()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_file_names() {
+        assert!(is_license_file("LICENSE"));
+        assert!(is_license_file("License.txt"));
+        assert!(is_license_file("licence-cc0.md"));
+        assert!(is_license_file("COPYING"));
+        assert!(!is_license_file("tiles.png"));
+        assert!(!is_license_file("readme.txt"));
+    }
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(
+            url_file_name("https://kenney.nl/assets/tiles.png?v=2").as_deref(),
+            Some("tiles.png")
+        );
+        assert_eq!(url_file_name("https://kenney.nl/"), None);
+    }
+}
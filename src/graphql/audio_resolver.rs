@@ -0,0 +1,405 @@
+//! Audio Resolver
+//!
+//! Lists audio assets with the settings from their `.import` sidecars and
+//! edits those settings: loop mode and loop points for music, and presets
+//! (compression, normalization, ...) applied to a whole folder of sounds.
+//! Godot picks up the changed settings the next time it reimports the files.
+
+use std::path::{Path, PathBuf};
+
+use crate::godot::import::ImportFile;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::probe_media;
+use super::types::*;
+use super::vfs::ProjectFs;
+
+/// Audio extensions Godot imports
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "ogg", "mp3"];
+
+/// Resolve audioAssets query
+pub fn resolve_audio_assets(ctx: &GqlContext, folder: Option<&str>) -> Vec<AudioAsset> {
+    let Ok(dir) = path_utils::to_fs_path(&ctx.project_path, folder.unwrap_or("res://")) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    collect_audio_files(ctx.fs.as_ref(), &dir, true, &mut files);
+    files.iter().map(|file| audio_asset(ctx, file)).collect()
+}
+
+/// Resolve setAudioLoop mutation
+pub fn resolve_set_audio_loop(ctx: &GqlContext, input: &SetAudioLoopInput) -> AudioImportResult {
+    let file_path = match path_utils::to_fs_path(&ctx.project_path, &input.path) {
+        Ok(path) if ctx.fs.is_file(&path) && extension(&path).is_some() => path,
+        _ => {
+            return failure(
+                false,
+                GqlStructuredError::new(
+                    "AUDIO_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Audio file not found: {}", input.path),
+                )
+                .with_suggestion(
+                    "audioAssets クエリで WAV/Ogg Vorbis/MP3 ファイルのパスを確認してください",
+                ),
+            )
+        }
+    };
+    let Some(mut import) = read_import(ctx.fs.as_ref(), &file_path) else {
+        return failure(
+            false,
+            GqlStructuredError::new(
+                "AUDIO_NOT_IMPORTED",
+                GqlErrorCategory::FileSystem,
+                format!("{} has no .import file", input.path),
+            )
+            .with_suggestion(
+                "Godot エディターでプロジェクトを開き、ファイルをインポートしてください",
+            ),
+        );
+    };
+
+    let params = match loop_params(&file_path, input) {
+        Ok(params) => params,
+        Err(message) => {
+            return failure(
+                false,
+                GqlStructuredError::new(
+                    "AUDIO_LOOP_UNSUPPORTED",
+                    GqlErrorCategory::Validation,
+                    message,
+                )
+                .with_suggestion(
+                    "Ogg Vorbis/MP3 は DISABLED/FORWARD と loopOffset、WAV は全モードと loopBegin/loopEnd を指定できます",
+                ),
+            )
+        }
+    };
+
+    let changed = apply_params(&mut import, &params);
+    let mut changes = Vec::new();
+    if !changed.is_empty() {
+        if let Err(e) = write_import(ctx, "setAudioLoop", &file_path, &import) {
+            return failure(false, write_error(&input.path, e));
+        }
+        changes.push(AudioImportChange {
+            path: input.path.clone(),
+            changed,
+        });
+    }
+
+    AudioImportResult {
+        success: true,
+        dry_run: false,
+        changes,
+        skipped: Vec::new(),
+        error: None,
+    }
+}
+
+/// Resolve applyAudioImportPreset mutation
+pub fn resolve_apply_audio_import_preset(
+    ctx: &GqlContext,
+    input: &ApplyAudioImportPresetInput,
+) -> AudioImportResult {
+    let dir = match path_utils::to_fs_path(&ctx.project_path, &input.folder) {
+        Ok(dir) if ctx.fs.is_dir(&dir) => dir,
+        _ => {
+            return failure(
+                input.dry_run,
+                GqlStructuredError::new(
+                    "AUDIO_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Folder not found: {}", input.folder),
+                ),
+            )
+        }
+    };
+    let mut files = Vec::new();
+    collect_audio_files(ctx.fs.as_ref(), &dir, input.recursive, &mut files);
+
+    let mut changes = Vec::new();
+    let mut skipped = Vec::new();
+    for file in files {
+        let res_path = res_path(&ctx.project_path, &file);
+        let Some(mut import) = read_import(ctx.fs.as_ref(), &file) else {
+            skipped.push(res_path);
+            continue;
+        };
+        let changed = apply_params(&mut import, &preset_params(&file, &input.preset));
+        if changed.is_empty() {
+            continue;
+        }
+        if !input.dry_run {
+            if let Err(e) = write_import(ctx, "applyAudioImportPreset", &file, &import) {
+                return failure(input.dry_run, write_error(&res_path, e));
+            }
+        }
+        changes.push(AudioImportChange {
+            path: res_path,
+            changed,
+        });
+    }
+
+    AudioImportResult {
+        success: true,
+        dry_run: input.dry_run,
+        changes,
+        skipped,
+        error: None,
+    }
+}
+
+fn audio_asset(ctx: &GqlContext, file: &Path) -> AudioAsset {
+    let import = read_import(ctx.fs.as_ref(), file);
+    let param = |key: &str| {
+        import
+            .as_ref()
+            .and_then(|i| i.get("params", key))
+            .map(str::to_string)
+    };
+    let flag = |key: &str| param(key).map(|v| v == "true");
+    let is_wav = extension(file) == Some("wav");
+
+    let loop_mode = if is_wav {
+        param("edit/loop_mode")
+            .and_then(|v| v.parse().ok())
+            .and_then(wav_loop_mode)
+    } else {
+        flag("loop").map(|looped| {
+            if looped {
+                AudioLoopMode::Forward
+            } else {
+                AudioLoopMode::Disabled
+            }
+        })
+    };
+    let frames = |key: &str| param(key).and_then(|v| v.parse().ok());
+
+    AudioAsset {
+        path: res_path(&ctx.project_path, file),
+        format: match extension(file) {
+            Some("wav") => "WAV",
+            Some("ogg") => "Ogg Vorbis",
+            _ => "MP3",
+        }
+        .to_string(),
+        importer: import
+            .as_ref()
+            .and_then(|i| i.importer())
+            .map(str::to_string),
+        imported: import.is_some(),
+        loop_mode,
+        loop_offset: param("loop_offset")
+            .filter(|_| !is_wav)
+            .and_then(|v| v.parse().ok()),
+        loop_begin: frames("edit/loop_begin").filter(|_| is_wav),
+        loop_end: frames("edit/loop_end").filter(|_| is_wav),
+        compression: param("compress/mode").and_then(|v| match v.as_str() {
+            "0" => Some(AudioCompression::Pcm),
+            "1" => Some(AudioCompression::ImaAdpcm),
+            "2" => Some(AudioCompression::Qoa),
+            _ => None,
+        }),
+        normalize: flag("edit/normalize"),
+        force_mono: flag("force/mono"),
+        duration_sec: probe_media(ctx, file).and_then(|info| info.duration_secs),
+        params: import
+            .as_ref()
+            .map(|i| {
+                i.entries("params")
+                    .into_iter()
+                    .map(|(key, value)| ImportParam {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// [params] entries for a setAudioLoop request
+fn loop_params(file: &Path, input: &SetAudioLoopInput) -> Result<Vec<(String, String)>, String> {
+    let mut params = Vec::new();
+    if extension(file) == Some("wav") {
+        if input.loop_offset.is_some() {
+            return Err("loopOffset applies to Ogg Vorbis and MP3; use loopBegin for WAV".into());
+        }
+        params.push(wav_loop_param(input.mode));
+        if let Some(begin) = input.loop_begin {
+            params.push(("edit/loop_begin".to_string(), begin.to_string()));
+        }
+        if let Some(end) = input.loop_end {
+            params.push(("edit/loop_end".to_string(), end.to_string()));
+        }
+    } else {
+        if input.loop_begin.is_some() || input.loop_end.is_some() {
+            return Err(
+                "loopBegin/loopEnd apply to WAV; use loopOffset for Ogg Vorbis and MP3".into(),
+            );
+        }
+        let Some(param) = stream_loop_param(input.mode) else {
+            return Err(format!(
+                "{:?} looping is only supported for WAV files",
+                input.mode
+            ));
+        };
+        params.push(param);
+        if let Some(offset) = input.loop_offset {
+            params.push(("loop_offset".to_string(), offset.to_string()));
+        }
+    }
+    Ok(params)
+}
+
+/// [params] entries of a preset that apply to this file
+fn preset_params(file: &Path, preset: &AudioImportPresetInput) -> Vec<(String, String)> {
+    if extension(file) != Some("wav") {
+        return preset
+            .loop_mode
+            .and_then(stream_loop_param)
+            .into_iter()
+            .collect();
+    }
+
+    let mut params = Vec::new();
+    if let Some(compression) = preset.compression {
+        let mode = match compression {
+            AudioCompression::Pcm => "0",
+            AudioCompression::ImaAdpcm => "1",
+            AudioCompression::Qoa => "2",
+        };
+        params.push(("compress/mode".to_string(), mode.to_string()));
+    }
+    for (key, value) in [
+        ("edit/normalize", preset.normalize),
+        ("force/mono", preset.force_mono),
+        ("edit/trim", preset.trim),
+    ] {
+        if let Some(value) = value {
+            params.push((key.to_string(), value.to_string()));
+        }
+    }
+    if let Some(mode) = preset.loop_mode {
+        params.push(wav_loop_param(mode));
+    }
+    params
+}
+
+fn wav_loop_param(mode: AudioLoopMode) -> (String, String) {
+    let value = match mode {
+        AudioLoopMode::Detect => 0,
+        AudioLoopMode::Disabled => 1,
+        AudioLoopMode::Forward => 2,
+        AudioLoopMode::PingPong => 3,
+        AudioLoopMode::Backward => 4,
+    };
+    ("edit/loop_mode".to_string(), value.to_string())
+}
+
+fn wav_loop_mode(value: i32) -> Option<AudioLoopMode> {
+    match value {
+        0 => Some(AudioLoopMode::Detect),
+        1 => Some(AudioLoopMode::Disabled),
+        2 => Some(AudioLoopMode::Forward),
+        3 => Some(AudioLoopMode::PingPong),
+        4 => Some(AudioLoopMode::Backward),
+        _ => None,
+    }
+}
+
+/// `loop` entry for Ogg Vorbis / MP3, which only loop forward
+fn stream_loop_param(mode: AudioLoopMode) -> Option<(String, String)> {
+    let looped = match mode {
+        AudioLoopMode::Forward => true,
+        AudioLoopMode::Disabled => false,
+        _ => return None,
+    };
+    Some(("loop".to_string(), looped.to_string()))
+}
+
+/// Set the entries in [params], returning the ones that changed
+fn apply_params(import: &mut ImportFile, params: &[(String, String)]) -> Vec<ImportParam> {
+    params
+        .iter()
+        .filter(|(key, value)| import.set("params", key, value))
+        .map(|(key, value)| ImportParam {
+            key: key.clone(),
+            value: value.clone(),
+        })
+        .collect()
+}
+
+fn read_import(fs: &dyn ProjectFs, file: &Path) -> Option<ImportFile> {
+    fs.read_to_string(&import_path(file))
+        .ok()
+        .map(|content| ImportFile::parse(&content))
+}
+
+fn write_import(
+    ctx: &GqlContext,
+    operation: &str,
+    file: &Path,
+    import: &ImportFile,
+) -> std::io::Result<()> {
+    audit::write_file(ctx, operation, &import_path(file), import.to_import())?;
+    telemetry::record_files_written(1);
+    Ok(())
+}
+
+fn write_error(path: &str, e: std::io::Error) -> GqlStructuredError {
+    GqlStructuredError::new(
+        "AUDIO_IMPORT_WRITE_FAILED",
+        GqlErrorCategory::FileSystem,
+        format!("Failed to write {}.import: {}", path, e),
+    )
+}
+
+fn import_path(file: &Path) -> PathBuf {
+    let mut path = file.as_os_str().to_owned();
+    path.push(".import");
+    PathBuf::from(path)
+}
+
+fn res_path(project_path: &Path, file: &Path) -> String {
+    path_utils::to_res_path(project_path, file)
+        .unwrap_or_else(|_| file.to_string_lossy().into_owned())
+}
+
+/// Lowercase audio extension, None for other files
+fn extension(file: &Path) -> Option<&'static str> {
+    let ext = file.extension()?.to_str()?.to_ascii_lowercase();
+    AUDIO_EXTENSIONS.iter().copied().find(|e| *e == ext)
+}
+
+/// Audio files under `dir`, sorted, skipping hidden folders and addons
+fn collect_audio_files(fs: &dyn ProjectFs, dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) {
+    let Ok(mut entries) = fs.read_dir(dir) else {
+        return;
+    };
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if fs.is_dir(&path) {
+            if recursive && !name.starts_with('.') && name != "addons" {
+                collect_audio_files(fs, &path, recursive, files);
+            }
+        } else if extension(&path).is_some() {
+            files.push(path);
+        }
+    }
+}
+
+fn failure(dry_run: bool, error: GqlStructuredError) -> AudioImportResult {
+    AudioImportResult {
+        success: false,
+        dry_run,
+        changes: Vec::new(),
+        skipped: Vec::new(),
+        error: Some(error),
+    }
+}
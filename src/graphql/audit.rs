@@ -0,0 +1,176 @@
+//! Audit Log
+//!
+//! Every project file the server writes or moves is recorded in
+//! `res://.godot-mcp/audit.jsonl` (one JSON entry per line) with the session,
+//! the GraphQL operation and a unified diff, so a session's edits can be
+//! summarized afterwards (see `sessionReport`). Overwritten files are backed
+//! up first (see `backup`). Failing to append to the log
+//! never fails the write itself. Written and moved files are also queued for
+//! the cached dependency graph, ahead of the file watcher.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::backup;
+use super::context::GqlContext;
+use super::dependency_cache;
+use super::project_resolver::to_res_path;
+
+/// Append-only log of file changes
+pub const AUDIT_LOG_PATH: &str = "res://.godot-mcp/audit.jsonl";
+
+/// One recorded file change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub session_id: String,
+    /// Unix epoch milliseconds
+    pub timestamp: u64,
+    /// GraphQL field that caused the change (e.g. `addNode`)
+    pub operation: String,
+    pub path: String,
+    /// The file did not exist before
+    #[serde(default)]
+    pub created: bool,
+    /// Previous path when the file was moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<String>,
+    /// Unified diff of the change (empty for moves)
+    #[serde(default)]
+    pub diff: String,
+}
+
+/// Write a project file and record the change
+pub fn write_file(
+    ctx: &GqlContext,
+    operation: &str,
+    path: &Path,
+    content: impl AsRef<str>,
+) -> std::io::Result<()> {
+    let content = content.as_ref();
+    let previous_bytes = ctx.fs.read(path).ok();
+    let previous = previous_bytes
+        .as_deref()
+        .and_then(|bytes| std::str::from_utf8(bytes).ok());
+    if previous != Some(content) {
+        backup::snapshot(ctx, operation, path, previous_bytes.as_deref());
+    }
+    ctx.fs.write(path, content.as_bytes())?;
+
+    let res_path = to_res_path(&ctx.project_path, path);
+    let before = previous.unwrap_or("");
+    if previous.is_some() && before == content {
+        return Ok(());
+    }
+    let diff = TextDiff::from_lines(before, content)
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            if previous.is_some() {
+                &res_path
+            } else {
+                "/dev/null"
+            },
+            &res_path,
+        )
+        .to_string();
+
+    record(
+        ctx,
+        AuditEntry {
+            session_id: ctx.session_id.clone(),
+            timestamp: now_millis(),
+            operation: operation.to_string(),
+            created: previous.is_none(),
+            path: res_path,
+            moved_from: None,
+            diff,
+        },
+    );
+    Ok(())
+}
+
+/// Write a binary project file (texture, sound, ...) and record it without a diff
+pub fn write_binary_file(
+    ctx: &GqlContext,
+    operation: &str,
+    path: &Path,
+    content: &[u8],
+) -> std::io::Result<()> {
+    let previous = ctx.fs.read(path).ok();
+    if previous.as_deref() != Some(content) {
+        backup::snapshot(ctx, operation, path, previous.as_deref());
+    }
+    ctx.fs.write(path, content)?;
+    if previous.as_deref() == Some(content) {
+        return Ok(());
+    }
+
+    record(
+        ctx,
+        AuditEntry {
+            session_id: ctx.session_id.clone(),
+            timestamp: now_millis(),
+            operation: operation.to_string(),
+            path: to_res_path(&ctx.project_path, path),
+            created: previous.is_none(),
+            moved_from: None,
+            diff: String::new(),
+        },
+    );
+    Ok(())
+}
+
+/// Record a file move (the caller performs the rename)
+pub fn record_move(ctx: &GqlContext, operation: &str, from: &Path, to: &Path) {
+    record(
+        ctx,
+        AuditEntry {
+            session_id: ctx.session_id.clone(),
+            timestamp: now_millis(),
+            operation: operation.to_string(),
+            path: to_res_path(&ctx.project_path, to),
+            created: false,
+            moved_from: Some(to_res_path(&ctx.project_path, from)),
+            diff: String::new(),
+        },
+    );
+}
+
+/// All entries in log order; unreadable lines are skipped
+pub fn read_entries(ctx: &GqlContext) -> Vec<AuditEntry> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, AUDIT_LOG_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn record(ctx: &GqlContext, entry: AuditEntry) {
+    dependency_cache::mark_changed(&ctx.project_path, &entry.path);
+    if let Some(from) = &entry.moved_from {
+        dependency_cache::mark_changed(&ctx.project_path, from);
+    }
+    if let Err(e) = append(ctx, &entry) {
+        tracing::warn!("Failed to append to audit log: {}", e);
+    }
+}
+
+fn append(ctx: &GqlContext, entry: &AuditEntry) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, AUDIT_LOG_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    ctx.fs.append(&path, format!("{}\n", line).as_bytes())
+}
@@ -0,0 +1,156 @@
+//! Backups
+//!
+//! Before a project file is overwritten, its previous content is copied into
+//! `res://.godot-mcp/backups/<id>/` (mirroring the project layout) and listed
+//! in the backup index, so `restoreBackup` can undo an operation. Consecutive
+//! writes of one operation in the same session share a backup; a file written
+//! twice starts a new one, so every backup holds the state right before its
+//! first write. Newly created files are recorded without content and are
+//! deleted on restore. Files under `res://.godot-mcp/` are never backed up, and
+//! failing to take a backup never fails the write itself.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+use super::project_resolver::to_res_path;
+use super::types::{Backup, BackupFile};
+
+/// Where backups and their index are stored
+pub const BACKUP_DIR: &str = "res://.godot-mcp/backups";
+
+/// Backup list, oldest first
+const INDEX_PATH: &str = "res://.godot-mcp/backups/index.json";
+
+/// Oldest backups are deleted beyond this count
+const MAX_BACKUPS: usize = 50;
+
+/// Writes of the same operation further apart than this start a new backup
+const GROUP_WINDOW_MS: u64 = 2000;
+
+/// Serializes index updates of concurrent writes
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// Back up `path` before it is written; `previous` is its current content
+/// (None when the file does not exist yet)
+pub fn snapshot(ctx: &GqlContext, operation: &str, path: &Path, previous: Option<&[u8]>) {
+    let res_path = to_res_path(&ctx.project_path, path);
+    if res_path.starts_with("res://.godot-mcp/") {
+        return;
+    }
+    if let Err(e) = add_to_backup(ctx, operation, &res_path, previous) {
+        tracing::warn!("Failed to back up {}: {}", res_path, e);
+    }
+}
+
+/// All backups, oldest first
+pub fn list(ctx: &GqlContext) -> Vec<Backup> {
+    read_index(ctx)
+}
+
+/// Directory holding a backup's file copies
+pub fn backup_path(id: &str) -> String {
+    format!("{}/{}", BACKUP_DIR, id)
+}
+
+/// Where a backup keeps its copy of `res_path`
+pub fn copy_path(ctx: &GqlContext, id: &str, res_path: &str) -> PathBuf {
+    let relative = res_path.strip_prefix("res://").unwrap_or(res_path);
+    path_utils::to_fs_path_unchecked(&ctx.project_path, &backup_path(id)).join(relative)
+}
+
+/// Set the description of the given backups (e.g. `applyMutation`'s `backupDescription`)
+pub fn describe(ctx: &GqlContext, ids: &[String], description: &str) -> std::io::Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut index = read_index(ctx);
+    for backup in index.iter_mut().filter(|b| ids.contains(&b.id)) {
+        backup.description = Some(description.to_string());
+    }
+    write_index(ctx, &index)
+}
+
+fn add_to_backup(
+    ctx: &GqlContext,
+    operation: &str,
+    res_path: &str,
+    previous: Option<&[u8]>,
+) -> std::io::Result<()> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut index = read_index(ctx);
+    let millis = now_millis();
+
+    let reuse = index.last().is_some_and(|last| {
+        last.session_id == ctx.session_id
+            && last.operation == operation
+            && !last.files.iter().any(|f| f.path == res_path)
+            && last
+                .timestamp
+                .parse::<u64>()
+                .is_ok_and(|started| millis.saturating_sub(started) <= GROUP_WINDOW_MS)
+    });
+    if !reuse {
+        let mut id = format!("bk-{}", millis);
+        // Several backups within the same millisecond
+        while index.iter().any(|b| b.id == id) {
+            id.push('x');
+        }
+        index.push(Backup {
+            id,
+            timestamp: millis.to_string(),
+            session_id: ctx.session_id.clone(),
+            operation: operation.to_string(),
+            description: None,
+            files: Vec::new(),
+        });
+    }
+    let backup = index.last_mut().expect("backup was just added");
+
+    if let Some(content) = previous {
+        let copy = copy_path(ctx, &backup.id, res_path);
+        if let Some(parent) = copy.parent() {
+            ctx.fs.create_dir_all(parent)?;
+        }
+        ctx.fs.write(&copy, content)?;
+    }
+    backup.files.push(BackupFile {
+        path: res_path.to_string(),
+        created: previous.is_none(),
+    });
+
+    if index.len() > MAX_BACKUPS {
+        let overflow = index.len() - MAX_BACKUPS;
+        for old in index.drain(..overflow) {
+            let dir = path_utils::to_fs_path_unchecked(&ctx.project_path, &backup_path(&old.id));
+            let _ = ctx.fs.remove_dir_all(&dir);
+        }
+    }
+    write_index(ctx, &index)
+}
+
+fn read_index(ctx: &GqlContext) -> Vec<Backup> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INDEX_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(ctx: &GqlContext, index: &[Backup]) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INDEX_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(index)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
@@ -0,0 +1,82 @@
+//! Backup Resolver
+//!
+//! Lists the backups taken before file writes (see `backup`) and restores
+//! one: backed up files get their previous content back and files the
+//! operation created are deleted. The restore's own writes are backed up
+//! again, so a restore can be undone as well.
+
+use crate::path_utils;
+
+use super::audit;
+use super::backup;
+use super::context::GqlContext;
+use super::dependency_cache;
+use super::types::*;
+
+/// Resolve listBackups query
+pub fn resolve_list_backups(ctx: &GqlContext) -> Vec<Backup> {
+    backup::list(ctx)
+}
+
+/// Resolve restoreBackup mutation
+pub fn resolve_restore_backup(ctx: &GqlContext, id: &str) -> RestoreBackupResult {
+    let Some(found) = backup::list(ctx).into_iter().find(|b| b.id == id) else {
+        return RestoreBackupResult {
+            success: false,
+            backup: None,
+            restored: Vec::new(),
+            deleted: Vec::new(),
+            error: Some(
+                GqlStructuredError::new(
+                    "BACKUP_NOT_FOUND",
+                    GqlErrorCategory::Validation,
+                    format!("Backup not found: {}", id),
+                )
+                .with_suggestion("listBackups で利用可能なバックアップ ID を確認してください"),
+            ),
+        };
+    };
+
+    let mut result = RestoreBackupResult {
+        success: true,
+        backup: None,
+        restored: Vec::new(),
+        deleted: Vec::new(),
+        error: None,
+    };
+    for file in &found.files {
+        let path = path_utils::to_fs_path_unchecked(&ctx.project_path, &file.path);
+        let outcome = if file.created {
+            if ctx.fs.exists(&path) {
+                ctx.fs.remove_file(&path).map(|_| {
+                    dependency_cache::mark_changed(&ctx.project_path, &file.path);
+                    result.deleted.push(file.path.clone());
+                })
+            } else {
+                Ok(())
+            }
+        } else {
+            ctx.fs
+                .read(&backup::copy_path(ctx, &found.id, &file.path))
+                .and_then(|content| match String::from_utf8(content) {
+                    Ok(text) => audit::write_file(ctx, "restoreBackup", &path, text),
+                    Err(e) => audit::write_binary_file(ctx, "restoreBackup", &path, e.as_bytes()),
+                })
+                .map(|_| result.restored.push(file.path.clone()))
+        };
+        if let Err(e) = outcome {
+            result.success = false;
+            result.error = Some(
+                GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to restore {}: {}", file.path, e),
+                )
+                .with_suggestion("ファイルが他のプロセスで使用されていないか確認してください"),
+            );
+            break;
+        }
+    }
+    result.backup = Some(found);
+    result
+}
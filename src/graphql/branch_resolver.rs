@@ -0,0 +1,268 @@
+//! Branch Resolver
+//!
+//! Reads the scenes, scripts and resources of another git branch (or any
+//! commit) into an in-memory project with git plumbing (`rev-parse`,
+//! `ls-tree`, `cat-file --batch`), without a checkout: the working directory
+//! and index are never touched. `branchView(gitRef)` runs the regular
+//! project/scene/script resolvers against that copy (read-only), so agents
+//! can compare the scene they are editing with, say, `main`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::path_utils;
+
+use super::checkpoint_resolver::SNAPSHOT_EXTENSIONS;
+use super::context::GqlContext;
+use super::types::*;
+use super::vfs::{MemoryFs, ProjectFs};
+
+/// Virtual location of materialized commits (nothing is written there)
+const BRANCH_VIEW_DIR: &str = "res://.godot-mcp/branches";
+
+/// Most recently used views kept in memory
+const MAX_CACHED_VIEWS: usize = 4;
+
+/// A materialized commit; immutable, so it is shared across queries
+#[derive(Clone)]
+struct CachedView {
+    project_path: PathBuf,
+    commit: String,
+    file_count: usize,
+    context: GqlContext,
+}
+
+fn views() -> &'static Mutex<Vec<CachedView>> {
+    static VIEWS: OnceLock<Mutex<Vec<CachedView>>> = OnceLock::new();
+    VIEWS.get_or_init(Default::default)
+}
+
+/// Resolve branchView query
+pub fn resolve_branch_view(ctx: &GqlContext, git_ref: &str) -> BranchView {
+    match materialize(ctx, git_ref) {
+        Ok(view) => BranchView {
+            git_ref: git_ref.to_string(),
+            commit: Some(view.commit),
+            file_count: view.file_count as i32,
+            error: None,
+            context: Some(view.context),
+        },
+        Err(error) => BranchView {
+            git_ref: git_ref.to_string(),
+            commit: None,
+            file_count: 0,
+            error: Some(*error),
+            context: None,
+        },
+    }
+}
+
+fn materialize(ctx: &GqlContext, git_ref: &str) -> Result<CachedView, Box<GqlStructuredError>> {
+    // Refs are passed as arguments; a leading dash would be read as an option
+    if git_ref.trim().is_empty() || git_ref.starts_with('-') {
+        return Err(Box::new(GqlStructuredError::new(
+            "INVALID_INPUT",
+            GqlErrorCategory::Validation,
+            format!("Invalid git ref: '{}'", git_ref),
+        )));
+    }
+    if ctx.fs.is_virtual() {
+        return Err(Box::new(GqlStructuredError::new(
+            "GIT_FAILED",
+            GqlErrorCategory::FileSystem,
+            "In-memory projects have no git history",
+        )));
+    }
+
+    let commit = resolve_commit(&ctx.project_path, git_ref)?;
+    {
+        let mut views = views().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(index) = views
+            .iter()
+            .position(|v| v.commit == commit && v.project_path == ctx.project_path)
+        {
+            let view = views.remove(index);
+            views.push(view.clone());
+            return Ok(view);
+        }
+    }
+
+    let files = tracked_files(&ctx.project_path, &commit)?;
+    let contents = read_blobs(
+        &ctx.project_path,
+        files.iter().map(|(object, _)| object.as_str()),
+    )?;
+
+    let root = path_utils::to_fs_path_unchecked(
+        &ctx.project_path,
+        &format!("{}/{}", BRANCH_VIEW_DIR, commit),
+    );
+    let fs = MemoryFs::new(&root);
+    for ((_, path), content) in files.iter().zip(contents) {
+        let target = root.join(path);
+        let written = target
+            .parent()
+            .map_or(Ok(()), |parent| fs.create_dir_all(parent))
+            .and_then(|_| fs.write(&target, &content));
+        if let Err(e) = written {
+            return Err(Box::new(GqlStructuredError::new(
+                "GIT_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to materialize {}: {}", path, e),
+            )));
+        }
+    }
+
+    let view = CachedView {
+        project_path: ctx.project_path.clone(),
+        commit,
+        file_count: files.len(),
+        context: GqlContext {
+            project_path: root.clone(),
+            ..ctx.clone()
+        }
+        .with_fs(Arc::new(fs)),
+    };
+    let mut views = views().lock().unwrap_or_else(|e| e.into_inner());
+    views.push(view.clone());
+    if views.len() > MAX_CACHED_VIEWS {
+        views.remove(0);
+    }
+    Ok(view)
+}
+
+/// Commit id a ref points to
+fn resolve_commit(project: &Path, git_ref: &str) -> Result<String, Box<GqlStructuredError>> {
+    let spec = format!("{}^{{commit}}", git_ref);
+    let output = git(project, &["rev-parse", "--verify", "--quiet", &spec], None)?;
+    let commit = String::from_utf8_lossy(&output).trim().to_string();
+    if commit.is_empty() {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "GIT_REF_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Git ref not found: {}", git_ref),
+            )
+            .with_suggestion("ブランチ名・タグ・コミットIDを確認してください (git branch -a)"),
+        ));
+    }
+    Ok(commit)
+}
+
+/// Blob ids and project-relative paths of the snapshot file types in a commit
+///
+/// Runs in the project folder, so the listing is limited to it and paths are
+/// relative to it even when the repository root is further up.
+fn tracked_files(
+    project: &Path,
+    commit: &str,
+) -> Result<Vec<(String, String)>, Box<GqlStructuredError>> {
+    let output = git(project, &["ls-tree", "-r", "-z", commit], None)?;
+    let files = output
+        .split(|b| *b == 0)
+        .filter_map(|entry| {
+            // <mode> SP <type> SP <object> TAB <path>
+            let entry = String::from_utf8_lossy(entry);
+            let (meta, path) = entry.split_once('\t')?;
+            let mut meta = meta.split(' ');
+            let (kind, object) = (meta.nth(1)?, meta.next()?);
+            let included = kind == "blob"
+                // .godot (import cache), .godot-mcp (server state)
+                && !path.split('/').any(|part| part.starts_with('.'))
+                && Path::new(path)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| SNAPSHOT_EXTENSIONS.contains(&e));
+            included.then(|| (object.to_string(), path.to_string()))
+        })
+        .collect();
+    Ok(files)
+}
+
+/// Contents of the given blobs, in order, read in one `cat-file --batch` call
+fn read_blobs<'a>(
+    project: &Path,
+    objects: impl Iterator<Item = &'a str>,
+) -> Result<Vec<Vec<u8>>, Box<GqlStructuredError>> {
+    let input: String = objects.map(|object| format!("{}\n", object)).collect();
+    let output = git(project, &["cat-file", "--batch"], Some(input.as_bytes()))?;
+
+    // <object> SP <type> SP <size> LF <contents> LF, per object
+    let mut contents = Vec::new();
+    let mut rest = output.as_slice();
+    while let Some(header_end) = rest.iter().position(|b| *b == b'\n') {
+        let header = String::from_utf8_lossy(&rest[..header_end]);
+        let size = header
+            .rsplit(' ')
+            .next()
+            .and_then(|size| size.parse::<usize>().ok())
+            .filter(|_| !header.ends_with(" missing"))
+            .ok_or_else(|| {
+                Box::new(GqlStructuredError::new(
+                    "GIT_FAILED",
+                    GqlErrorCategory::FileSystem,
+                    format!("Unexpected git cat-file output: {}", header),
+                ))
+            })?;
+        let start = header_end + 1;
+        let end = (start + size).min(rest.len());
+        contents.push(rest[start..end].to_vec());
+        rest = &rest[(end + 1).min(rest.len())..];
+    }
+    Ok(contents)
+}
+
+/// Run git in the project folder and return its stdout
+fn git(
+    project: &Path,
+    args: &[&str],
+    stdin: Option<&[u8]>,
+) -> Result<Vec<u8>, Box<GqlStructuredError>> {
+    let spawn_failed = |e: std::io::Error| {
+        Box::new(
+            GqlStructuredError::new(
+                "GIT_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to run git: {}", e),
+            )
+            .with_suggestion("git をインストールして PATH に追加してください"),
+        )
+    };
+
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(project)
+        // Fail instead of waiting for credentials nobody can type
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_failed)?;
+    // Write from a thread so a full stdout pipe cannot block the writer
+    let writer = child.stdin.take().map(|mut pipe| {
+        let input = stdin.unwrap_or_default().to_vec();
+        std::thread::spawn(move || pipe.write_all(&input))
+    });
+    let output = child.wait_with_output().map_err(spawn_failed)?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+
+    // rev-parse --verify --quiet exits with 1 and no output for unknown refs
+    if output.status.success() || (args[0] == "rev-parse" && output.stderr.is_empty()) {
+        return Ok(output.stdout);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut error = GqlStructuredError::new(
+        "GIT_FAILED",
+        GqlErrorCategory::FileSystem,
+        format!("git {} failed: {}", args[0], stderr.trim()),
+    );
+    if stderr.contains("not a git repository") {
+        error = error.with_suggestion("プロジェクトを git リポジトリで管理してください");
+    }
+    Err(Box::new(error))
+}
@@ -0,0 +1,187 @@
+//! Checkpoint Resolver
+//!
+//! Snapshots the project's scenes, scripts and resources under
+//! `res://.godot-mcp/checkpoints/<id>/`, mirroring the project layout, so
+//! `projectAt(checkpointId)` can run the regular scene/script resolvers against
+//! a past state (read-only) and agents can compare before and after their edits.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::types::*;
+use super::vfs::ProjectFs;
+
+/// Where snapshots and their index are stored
+pub const CHECKPOINT_DIR: &str = "res://.godot-mcp/checkpoints";
+
+/// Checkpoint list, oldest first
+const INDEX_PATH: &str = "res://.godot-mcp/checkpoints/index.json";
+
+/// Oldest checkpoints are deleted beyond this count
+const MAX_CHECKPOINTS: usize = 20;
+
+/// File types captured in a snapshot
+pub(super) const SNAPSHOT_EXTENSIONS: &[&str] =
+    &["tscn", "tres", "gd", "cs", "gdshader", "godot", "cfg"];
+
+/// Resolve checkpoints query
+pub fn resolve_checkpoints(ctx: &GqlContext) -> Vec<Checkpoint> {
+    read_index(ctx)
+}
+
+/// Resolve projectAt query; None for unknown checkpoints
+pub fn resolve_project_at(ctx: &GqlContext, checkpoint_id: &str) -> Option<ProjectSnapshot> {
+    let checkpoint = read_index(ctx)
+        .into_iter()
+        .find(|c| c.id == checkpoint_id)?;
+    let dir = path_utils::to_fs_path_unchecked(
+        &ctx.project_path,
+        &format!("{}/{}", CHECKPOINT_DIR, checkpoint.id),
+    );
+    if !ctx.fs.is_dir(&dir) {
+        return None;
+    }
+
+    Some(ProjectSnapshot {
+        checkpoint,
+        context: GqlContext {
+            project_path: dir,
+            ..ctx.clone()
+        },
+    })
+}
+
+/// Resolve createCheckpoint mutation
+pub fn resolve_create_checkpoint(
+    ctx: &GqlContext,
+    label: Option<String>,
+) -> CreateCheckpointResult {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let mut index = read_index(ctx);
+    let mut id = format!("cp-{}", millis);
+    // Several checkpoints within the same millisecond
+    while index.iter().any(|c| c.id == id) {
+        id.push('x');
+    }
+
+    let snapshot_dir =
+        path_utils::to_fs_path_unchecked(&ctx.project_path, &format!("{}/{}", CHECKPOINT_DIR, id));
+    let file_count = match copy_project_files(
+        ctx.fs.as_ref(),
+        &ctx.project_path,
+        &ctx.project_path,
+        &snapshot_dir,
+    ) {
+        Ok(count) => count,
+        Err(e) => {
+            let _ = ctx.fs.remove_dir_all(&snapshot_dir);
+            return CreateCheckpointResult {
+                success: false,
+                checkpoint: None,
+                error: Some(GqlStructuredError::new(
+                    "CHECKPOINT_FAILED",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to snapshot project: {}", e),
+                )),
+            };
+        }
+    };
+
+    let checkpoint = Checkpoint {
+        id,
+        label,
+        timestamp: millis.to_string(),
+        file_count: file_count as i32,
+    };
+    index.push(checkpoint.clone());
+    if index.len() > MAX_CHECKPOINTS {
+        let overflow = index.len() - MAX_CHECKPOINTS;
+        for old in index.drain(..overflow) {
+            let dir = path_utils::to_fs_path_unchecked(
+                &ctx.project_path,
+                &format!("{}/{}", CHECKPOINT_DIR, old.id),
+            );
+            let _ = ctx.fs.remove_dir_all(&dir);
+        }
+    }
+
+    if let Err(e) = write_index(ctx, &index) {
+        return CreateCheckpointResult {
+            success: false,
+            checkpoint: None,
+            error: Some(GqlStructuredError::new(
+                "CHECKPOINT_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to write checkpoint index: {}", e),
+            )),
+        };
+    }
+
+    CreateCheckpointResult {
+        success: true,
+        checkpoint: Some(checkpoint),
+        error: None,
+    }
+}
+
+/// Copy snapshot file types below `dir` into `target`, keeping relative paths
+fn copy_project_files(
+    fs: &dyn ProjectFs,
+    root: &Path,
+    dir: &Path,
+    target: &Path,
+) -> std::io::Result<usize> {
+    let mut count = 0;
+    for path in fs.read_dir(dir)? {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        // .godot (import cache), .godot-mcp (state incl. snapshots), .git, addons
+        if name.starts_with('.') || name == "addons" {
+            continue;
+        }
+
+        if fs.is_dir(&path) {
+            count += copy_project_files(fs, root, &path, target)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SNAPSHOT_EXTENSIONS.contains(&e))
+        {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let destination = target.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs.create_dir_all(parent)?;
+            }
+            fs.copy(&path, &destination)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn read_index(ctx: &GqlContext) -> Vec<Checkpoint> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INDEX_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(ctx: &GqlContext, index: &[Checkpoint]) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INDEX_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(index)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
@@ -0,0 +1,248 @@
+//! Class Icon Resolver
+//!
+//! Editor icons and categories of node classes, so GUI clients can draw scene
+//! trees the way Godot's Scene dock does. Icons come from the editor theme
+//! through the plugin (including `@icon` of script classes); without an editor,
+//! a generic icon in the category color is returned from a bundled class list.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::Value;
+
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::types::*;
+
+/// Parent of common built-in node classes
+const CLASS_PARENTS: &[(&str, &str)] = &[
+    ("CanvasItem", "Node"),
+    ("Node2D", "CanvasItem"),
+    ("Node3D", "Node"),
+    ("Control", "CanvasItem"),
+    // 2D
+    ("Sprite2D", "Node2D"),
+    ("AnimatedSprite2D", "Node2D"),
+    ("Camera2D", "Node2D"),
+    ("Marker2D", "Node2D"),
+    ("Path2D", "Node2D"),
+    ("PathFollow2D", "Node2D"),
+    ("Line2D", "Node2D"),
+    ("Polygon2D", "Node2D"),
+    ("TileMap", "Node2D"),
+    ("TileMapLayer", "Node2D"),
+    ("GPUParticles2D", "Node2D"),
+    ("CPUParticles2D", "Node2D"),
+    ("PointLight2D", "Light2D"),
+    ("DirectionalLight2D", "Light2D"),
+    ("Light2D", "Node2D"),
+    ("AudioStreamPlayer2D", "Node2D"),
+    ("RayCast2D", "Node2D"),
+    ("NavigationAgent2D", "Node"),
+    ("CollisionObject2D", "Node2D"),
+    ("PhysicsBody2D", "CollisionObject2D"),
+    ("Area2D", "CollisionObject2D"),
+    ("StaticBody2D", "PhysicsBody2D"),
+    ("AnimatableBody2D", "StaticBody2D"),
+    ("CharacterBody2D", "PhysicsBody2D"),
+    ("RigidBody2D", "PhysicsBody2D"),
+    ("CollisionShape2D", "Node2D"),
+    ("CollisionPolygon2D", "Node2D"),
+    // 3D
+    ("MeshInstance3D", "GeometryInstance3D"),
+    ("GeometryInstance3D", "VisualInstance3D"),
+    ("VisualInstance3D", "Node3D"),
+    ("Camera3D", "Node3D"),
+    ("Marker3D", "Node3D"),
+    ("Path3D", "Node3D"),
+    ("PathFollow3D", "Node3D"),
+    ("Light3D", "VisualInstance3D"),
+    ("DirectionalLight3D", "Light3D"),
+    ("OmniLight3D", "Light3D"),
+    ("SpotLight3D", "Light3D"),
+    ("GPUParticles3D", "GeometryInstance3D"),
+    ("CPUParticles3D", "GeometryInstance3D"),
+    ("AudioStreamPlayer3D", "Node3D"),
+    ("RayCast3D", "Node3D"),
+    ("NavigationAgent3D", "Node"),
+    ("CollisionObject3D", "Node3D"),
+    ("PhysicsBody3D", "CollisionObject3D"),
+    ("Area3D", "CollisionObject3D"),
+    ("StaticBody3D", "PhysicsBody3D"),
+    ("AnimatableBody3D", "StaticBody3D"),
+    ("CharacterBody3D", "PhysicsBody3D"),
+    ("RigidBody3D", "PhysicsBody3D"),
+    ("CollisionShape3D", "Node3D"),
+    ("WorldEnvironment", "Node"),
+    // Control
+    ("Label", "Control"),
+    ("RichTextLabel", "Control"),
+    ("BaseButton", "Control"),
+    ("Button", "BaseButton"),
+    ("CheckBox", "Button"),
+    ("TextureButton", "BaseButton"),
+    ("LineEdit", "Control"),
+    ("TextEdit", "Control"),
+    ("TextureRect", "Control"),
+    ("ColorRect", "Control"),
+    ("NinePatchRect", "Control"),
+    ("Panel", "Control"),
+    ("Range", "Control"),
+    ("ProgressBar", "Range"),
+    ("TextureProgressBar", "Range"),
+    ("Container", "Control"),
+    ("BoxContainer", "Container"),
+    ("VBoxContainer", "BoxContainer"),
+    ("HBoxContainer", "BoxContainer"),
+    ("GridContainer", "Container"),
+    ("MarginContainer", "Container"),
+    ("CenterContainer", "Container"),
+    ("PanelContainer", "Container"),
+    ("ScrollContainer", "Container"),
+    ("TabContainer", "Container"),
+    // Other nodes
+    ("CanvasLayer", "Node"),
+    ("Timer", "Node"),
+    ("AnimationPlayer", "AnimationMixer"),
+    ("AnimationTree", "AnimationMixer"),
+    ("AnimationMixer", "Node"),
+    ("AudioStreamPlayer", "Node"),
+    ("HTTPRequest", "Node"),
+    ("Viewport", "Node"),
+    ("SubViewport", "Viewport"),
+];
+
+/// Resolve nodeClassIcons query
+pub async fn resolve_node_class_icons(ctx: &GqlContext, types: Vec<String>) -> NodeClassIcons {
+    let command = GodotLiveCommand::GetClassIcons {
+        types: types.clone(),
+    };
+    match execute_live_command(ctx, command).await {
+        Ok(value) if value.get("error").is_none() => NodeClassIcons {
+            editor_connected: true,
+            icons: types
+                .iter()
+                .map(|type_name| editor_icon(type_name, &value["icons"][type_name]))
+                .collect(),
+        },
+        _ => NodeClassIcons {
+            editor_connected: false,
+            icons: types
+                .iter()
+                .map(|type_name| bundled_icon(type_name))
+                .collect(),
+        },
+    }
+}
+
+/// Icon and metadata reported by the plugin; classes the editor does not
+/// know fall back to the bundled list
+fn editor_icon(type_name: &str, entry: &Value) -> NodeClassIcon {
+    if entry["known"].as_bool() != Some(true) {
+        return bundled_icon(type_name);
+    }
+
+    let category = entry["category"]
+        .as_str()
+        .and_then(parse_category)
+        .unwrap_or(NodeCategory::Node);
+    let (mime_type, icon_base64, source) = match entry["icon"].as_str() {
+        Some(icon) if !icon.is_empty() => ("image/png", icon.to_string(), IconSource::Editor),
+        _ => (
+            "image/svg+xml",
+            BASE64.encode(generic_svg(category)),
+            IconSource::Bundled,
+        ),
+    };
+    NodeClassIcon {
+        type_name: type_name.to_string(),
+        base_class: entry["base"].as_str().map(str::to_string),
+        category,
+        known: true,
+        color: category_color(category).to_string(),
+        mime_type: mime_type.to_string(),
+        icon_base64,
+        source,
+    }
+}
+
+/// Generic icon from the bundled class list
+fn bundled_icon(type_name: &str) -> NodeClassIcon {
+    let known = type_name == "Node" || parent_class(type_name).is_some();
+    let category = if known {
+        static_category(type_name)
+    } else {
+        NodeCategory::Node
+    };
+    NodeClassIcon {
+        type_name: type_name.to_string(),
+        base_class: parent_class(type_name).map(str::to_string),
+        category,
+        known,
+        color: category_color(category).to_string(),
+        mime_type: "image/svg+xml".to_string(),
+        icon_base64: BASE64.encode(generic_svg(category)),
+        source: IconSource::Bundled,
+    }
+}
+
+fn parent_class(type_name: &str) -> Option<&'static str> {
+    CLASS_PARENTS
+        .iter()
+        .find(|(class, _)| *class == type_name)
+        .map(|(_, parent)| *parent)
+}
+
+/// Category from the bundled class list, walking up to Node2D/Node3D/Control
+fn static_category(type_name: &str) -> NodeCategory {
+    let mut class = type_name;
+    loop {
+        match class {
+            "Node2D" => return NodeCategory::Node2D,
+            "Node3D" => return NodeCategory::Node3D,
+            "Control" => return NodeCategory::Control,
+            _ => match parent_class(class) {
+                Some(parent) => class = parent,
+                None => return NodeCategory::Node,
+            },
+        }
+    }
+}
+
+fn parse_category(name: &str) -> Option<NodeCategory> {
+    match name {
+        "2d" => Some(NodeCategory::Node2D),
+        "3d" => Some(NodeCategory::Node3D),
+        "control" => Some(NodeCategory::Control),
+        "node" => Some(NodeCategory::Node),
+        _ => None,
+    }
+}
+
+/// Icon colors of the default editor theme
+fn category_color(category: NodeCategory) -> &'static str {
+    match category {
+        NodeCategory::Node2D => "#8da5f3",
+        NodeCategory::Node3D => "#fc7f7f",
+        NodeCategory::Control => "#8eef97",
+        NodeCategory::Node => "#e0e0e0",
+    }
+}
+
+/// 16x16 generic icon: a ring for nodes, a frame for controls
+fn generic_svg(category: NodeCategory) -> String {
+    let color = category_color(category);
+    let shape = match category {
+        NodeCategory::Control => format!(
+            r#"<rect x="2" y="3" width="12" height="10" rx="1" fill="none" stroke="{}" stroke-width="2"/>"#,
+            color
+        ),
+        _ => format!(
+            r#"<circle cx="8" cy="8" r="5" fill="none" stroke="{}" stroke-width="2"/>"#,
+            color
+        ),
+    };
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16">{}</svg>"#,
+        shape
+    )
+}
@@ -0,0 +1,109 @@
+//! Cleanup Resolver
+//!
+//! Finds `.import` / `.uid` sidecars left behind when their source asset was
+//! moved or deleted outside the editor, and moves them to a trash folder under
+//! `res://.godot-mcp/trash/` instead of deleting them. Source assets Godot
+//! imports but that have no `.import` file are reported for a reimport.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::path_utils;
+
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::to_res_path;
+use super::types::*;
+
+/// Folder the stale sidecars are moved to, one sub folder per cleanup
+pub const TRASH_DIR: &str = "res://.godot-mcp/trash";
+
+/// Sidecar extensions, each naming its source file without the extension
+const SIDECAR_EXTENSIONS: &[&str] = &["import", "uid"];
+
+/// Asset extensions Godot imports (and writes a `.import` file for)
+const IMPORTED_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "webp", "svg", "bmp", "tga", "exr", "hdr", "ktx", "dds", "wav", "ogg",
+    "mp3", "glb", "gltf", "obj", "fbx", "blend", "dae", "ttf", "otf", "woff", "woff2", "fnt",
+    "csv",
+];
+
+/// Resolve cleanImportArtifacts mutation
+pub fn resolve_clean_import_artifacts(
+    ctx: &GqlContext,
+    dry_run: bool,
+) -> CleanImportArtifactsResult {
+    let files = ctx.index.files(&ctx.project_path);
+
+    let mut stale: Vec<PathBuf> = Vec::new();
+    let mut missing_imports = Vec::new();
+    for file in &files {
+        let extension = file.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if SIDECAR_EXTENSIONS.contains(&extension) {
+            let source = file.with_extension("");
+            if !ctx.fs.exists(&source) {
+                stale.push(file.clone());
+            }
+        } else if IMPORTED_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            && !ctx.fs.is_file(&sidecar(file, "import"))
+        {
+            missing_imports.push(to_res_path(&ctx.project_path, file));
+        }
+    }
+
+    let mut result = CleanImportArtifactsResult {
+        success: true,
+        dry_run,
+        removed: stale
+            .iter()
+            .map(|path| to_res_path(&ctx.project_path, path))
+            .collect(),
+        missing_imports,
+        trash_path: None,
+        error: None,
+    };
+    if dry_run || stale.is_empty() {
+        return result;
+    }
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let trash = format!("{}/{}", TRASH_DIR, millis);
+    let trash_dir = path_utils::to_fs_path_unchecked(&ctx.project_path, &trash);
+    for (path, res_path) in stale.iter().zip(&result.removed) {
+        let relative = res_path.strip_prefix("res://").unwrap_or(res_path);
+        let target = trash_dir.join(relative);
+        if let Err(e) = move_to(ctx, path, &target) {
+            result.success = false;
+            result.error = Some(
+                GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to move {} to the trash: {}", res_path, e),
+                )
+                .with_suggestion("ファイルが他のプロセスで使用されていないか確認してください"),
+            );
+            break;
+        }
+        audit::record_move(ctx, "cleanImportArtifacts", path, &target);
+    }
+    result.trash_path = Some(trash);
+    result
+}
+
+/// `path` with `.extension` appended (`icon.png` -> `icon.png.import`)
+fn sidecar(path: &Path, extension: &str) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".");
+    sidecar.push(extension);
+    PathBuf::from(sidecar)
+}
+
+fn move_to(ctx: &GqlContext, from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.rename(from, to)
+}
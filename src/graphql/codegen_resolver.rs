@@ -2,11 +2,11 @@
 //!
 //! Handles code generation for input handlers, state machines, and test scripts.
 
-use std::fs;
-
 use crate::godot::gdscript::GDScript;
 use crate::path_utils;
+use crate::telemetry;
 
+use super::audit;
 use super::context::GqlContext;
 use super::types::*;
 
@@ -18,7 +18,7 @@ pub fn resolve_generate_input_handler(
     let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.script_path);
 
     // Read existing script or create new one
-    let existing_content = fs::read_to_string(&file_path).unwrap_or_default();
+    let existing_content = ctx.fs.read_to_string(&file_path).unwrap_or_default();
     let has_content = !existing_content.trim().is_empty();
 
     // Determine handler function name
@@ -76,13 +76,14 @@ pub fn resolve_generate_input_handler(
         format!("extends Node\n{}", new_function)
     };
 
-    if let Err(e) = fs::write(&file_path, &new_content) {
+    if let Err(e) = audit::write_file(ctx, "generateInputHandler", &file_path, &new_content) {
         return CodeGenerationResult {
             success: false,
             path: input.script_path.clone(),
             message: Some(format!("Failed to write: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     CodeGenerationResult {
         success: true,
@@ -241,16 +242,17 @@ pub fn resolve_generate_state_machine(
 
     // Ensure directory exists
     if let Some(parent) = file_path.parent() {
-        let _ = fs::create_dir_all(parent);
+        let _ = ctx.fs.create_dir_all(parent);
     }
 
-    if let Err(e) = fs::write(&file_path, &content) {
+    if let Err(e) = audit::write_file(ctx, "generateStateMachine", &file_path, &content) {
         return CodeGenerationResult {
             success: false,
             path: input.script_path.clone(),
             message: Some(format!("Failed to write: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     CodeGenerationResult {
         success: true,
@@ -270,7 +272,7 @@ pub fn resolve_generate_test_script(
     let target_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.target_script);
 
     // Parse target script
-    let content = match fs::read_to_string(&target_path) {
+    let content = match ctx.fs.read_to_string(&target_path) {
         Ok(c) => c,
         Err(e) => {
             return CodeGenerationResult {
@@ -305,16 +307,17 @@ pub fn resolve_generate_test_script(
 
     // Ensure directory exists
     if let Some(parent) = test_file_path.parent() {
-        let _ = fs::create_dir_all(parent);
+        let _ = ctx.fs.create_dir_all(parent);
     }
 
-    if let Err(e) = fs::write(&test_file_path, &test_content) {
+    if let Err(e) = audit::write_file(ctx, "generateTestScript", &test_file_path, &test_content) {
         return CodeGenerationResult {
             success: false,
             path: output_path,
             message: Some(format!("Failed to write test: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     CodeGenerationResult {
         success: true,
@@ -0,0 +1,383 @@
+//! Collision Resolver
+//!
+//! Adds a CollisionShape2D/3D next to a Sprite2D or MeshInstance3D in a scene
+//! file, with a shape sub-resource fitted to the sprite texture or the mesh
+//! (primitive meshes and OBJ files), using the node's transform.
+
+use indexmap::IndexMap;
+
+use crate::godot::collision::{self, FitStrategy, FittedShape, Mesh, SpriteFrame};
+use crate::godot::expression::{Value, VectorKind};
+use crate::godot::tscn::{node_path, GodotScene, SceneNode, SubResource};
+use crate::path_utils;
+use crate::screenshot::Canvas;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::probe_media;
+use super::scene_lock;
+use super::types::*;
+
+/// Resolve generateCollisionShape mutation
+pub async fn generate_collision_shape(
+    ctx: &GqlContext,
+    input: &GenerateCollisionShapeInput,
+) -> GenerateCollisionShapeResult {
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return failure(e),
+    };
+    let result = match write_collision_shape(ctx, input) {
+        Ok(result) => result,
+        Err(e) => failure(*e),
+    };
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_collision_shape(
+    ctx: &GqlContext,
+    input: &GenerateCollisionShapeInput,
+) -> Result<GenerateCollisionShapeResult, Box<GqlStructuredError>> {
+    let file_path = path_utils::to_fs_path(&ctx.project_path, &input.scene_path)
+        .map_err(|e| error("INVALID_PATH", e.to_string()))?;
+    let content = ctx.fs.read_to_string(&file_path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read scene {}: {}", input.scene_path, e),
+        ))
+    })?;
+    let mut scene = GodotScene::parse(&content)
+        .map_err(|e| error("SCENE_PARSE_ERROR", format!("Failed to parse scene: {}", e)))?;
+    let node = scene
+        .nodes
+        .iter()
+        .find(|n| node_path(n) == input.node_path)
+        .ok_or_else(|| {
+            Box::new(
+                GqlStructuredError::new(
+                    "NODE_NOT_FOUND",
+                    GqlErrorCategory::Validation,
+                    format!("Node not found: {}", input.node_path),
+                )
+                .with_suggestion("scene クエリでノードパスを確認してください"),
+            )
+        })?;
+
+    let strategy = match input.strategy {
+        CollisionShapeStrategy::Convex => FitStrategy::Convex,
+        CollisionShapeStrategy::Trimesh => FitStrategy::Trimesh,
+        CollisionShapeStrategy::CapsuleFit => FitStrategy::CapsuleFit,
+        CollisionShapeStrategy::RectFromSprite => FitStrategy::RectFromSprite,
+    };
+    let (shape, shape_node_type) = match node.node_type.as_str() {
+        "MeshInstance3D" => (
+            fit_mesh_node(ctx, &scene, node, strategy)?,
+            "CollisionShape3D",
+        ),
+        "Sprite2D" => (
+            fit_sprite_node(ctx, &scene, node, strategy)?,
+            "CollisionShape2D",
+        ),
+        other => {
+            return Err(Box::new(
+                GqlStructuredError::new(
+                    "UNSUPPORTED_NODE_TYPE",
+                    GqlErrorCategory::Validation,
+                    format!("Cannot derive a collision shape from a {}", other),
+                )
+                .with_suggestion("MeshInstance3D か Sprite2D のノードを指定してください"),
+            ))
+        }
+    };
+
+    // The shape goes next to the node, under the body or area holding it;
+    // for a root node it becomes its child
+    let (parent, placement) = match node.parent.as_deref() {
+        Some(parent) => (
+            parent.to_string(),
+            placement(&node.properties, &shape.offset),
+        ),
+        None => (".".to_string(), placement(&IndexMap::new(), &shape.offset)),
+    };
+    let mut warnings = Vec::new();
+    let parent_type = scene
+        .nodes
+        .iter()
+        .find(|n| node_path(n) == parent)
+        .map(|n| n.node_type.clone())
+        .unwrap_or_default();
+    if !collision::is_collision_object(&parent_type) {
+        warnings.push(format!(
+            "'{}' is a {}, not a physics body or area; move the {} under one",
+            parent, parent_type, shape_node_type
+        ));
+    }
+
+    let mut fragment = GodotScene::new(shape_node_type, shape_node_type);
+    let id = format!("{}_1", shape.resource_type);
+    fragment.sub_resources.push(SubResource {
+        id: id.clone(),
+        resource_type: shape.resource_type.to_string(),
+        properties: shape.properties.into_iter().collect(),
+    });
+    let properties = &mut fragment.nodes[0].properties;
+    properties.extend(placement);
+    properties.insert("shape".to_string(), format!("SubResource(\"{}\")", id));
+
+    let node_path = scene
+        .insert_subtree(&fragment, &parent)
+        .map_err(|message| error("NODE_INSERT_FAILED", message))?;
+    audit::write_file(ctx, "generateCollisionShape", &file_path, scene.to_tscn()).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to write scene: {}", e),
+        ))
+    })?;
+    telemetry::record_files_written(1);
+
+    Ok(GenerateCollisionShapeResult {
+        success: true,
+        node_path: Some(node_path),
+        shape_type: Some(shape.resource_type.to_string()),
+        warnings,
+        error: None,
+    })
+}
+
+/// Fit a shape to the `mesh` of a MeshInstance3D
+fn fit_mesh_node(
+    ctx: &GqlContext,
+    scene: &GodotScene,
+    node: &SceneNode,
+    strategy: FitStrategy,
+) -> Result<FittedShape, Box<GqlStructuredError>> {
+    let unsupported = |message: String| {
+        Box::new(
+            GqlStructuredError::new("UNSUPPORTED_MESH", GqlErrorCategory::Validation, message)
+                .with_suggestion(
+                    "プリミティブメッシュ（BoxMesh, SphereMesh など）か .obj のメッシュに対応しています",
+                ),
+        )
+    };
+    let mesh = match node.properties.get("mesh").and_then(|v| resource_ref(v)) {
+        Some(("SubResource", id)) => {
+            let sub = scene
+                .sub_resources
+                .iter()
+                .find(|s| s.id == id)
+                .ok_or_else(|| error("MISSING_RESOURCE", format!("Missing sub-resource {}", id)))?;
+            Mesh::primitive(&sub.resource_type, &sub.properties).ok_or_else(|| {
+                unsupported(format!(
+                    "Cannot read the geometry of a {}",
+                    sub.resource_type
+                ))
+            })?
+        }
+        Some((_, id)) => {
+            let path = ext_resource_path(scene, id)?;
+            if !path.ends_with(".obj") {
+                return Err(unsupported(format!("Cannot read the geometry of {}", path)));
+            }
+            let fs_path = path_utils::to_fs_path(&ctx.project_path, path)
+                .map_err(|e| error("INVALID_PATH", e.to_string()))?;
+            ctx.fs
+                .read_to_string(&fs_path)
+                .ok()
+                .and_then(|content| Mesh::from_obj(&content))
+                .ok_or_else(|| unsupported(format!("Cannot read the geometry of {}", path)))?
+        }
+        None => {
+            return Err(error(
+                "NO_MESH",
+                format!("'{}' has no mesh", node_path(node)),
+            ))
+        }
+    };
+    collision::fit_mesh(&mesh, strategy).map_err(|message| error("INVALID_STRATEGY", message))
+}
+
+/// Fit a shape to the current frame of a Sprite2D's texture (file or AtlasTexture)
+fn fit_sprite_node(
+    ctx: &GqlContext,
+    scene: &GodotScene,
+    node: &SceneNode,
+    strategy: FitStrategy,
+) -> Result<FittedShape, Box<GqlStructuredError>> {
+    let no_texture = || {
+        error(
+            "NO_TEXTURE",
+            format!("'{}' has no texture with a readable size", node_path(node)),
+        )
+    };
+    let (texture_id, atlas_region) =
+        match node.properties.get("texture").and_then(|v| resource_ref(v)) {
+            Some(("ExtResource", id)) => (id, None),
+            Some((_, id)) => {
+                // AtlasTexture: a region of another texture
+                let atlas = scene
+                    .sub_resources
+                    .iter()
+                    .find(|s| s.id == id && s.resource_type == "AtlasTexture")
+                    .ok_or_else(no_texture)?;
+                let texture_id = atlas
+                    .properties
+                    .get("atlas")
+                    .and_then(|v| resource_ref(v))
+                    .filter(|(kind, _)| *kind == "ExtResource")
+                    .ok_or_else(no_texture)?
+                    .1;
+                let region = atlas
+                    .properties
+                    .get("region")
+                    .and_then(|v| collision::components(v))
+                    .filter(|c| c.len() == 4);
+                (texture_id, region)
+            }
+            None => return Err(no_texture()),
+        };
+
+    let path = ext_resource_path(scene, texture_id)?;
+    let fs_path = path_utils::to_fs_path(&ctx.project_path, path)
+        .map_err(|e| error("INVALID_PATH", e.to_string()))?;
+    let info = probe_media(ctx, &fs_path).ok_or_else(no_texture)?;
+    let (width, height) = (
+        info.width.ok_or_else(no_texture)? as f64,
+        info.height.ok_or_else(no_texture)? as f64,
+    );
+    let (origin, size) = match &atlas_region {
+        Some(region) => ((region[0], region[1]), (region[2], region[3])),
+        None => ((0.0, 0.0), (width, height)),
+    };
+    let mut frame = SpriteFrame::new(&node.properties, size);
+    frame.region[0] += origin.0;
+    frame.region[1] += origin.1;
+
+    // Only the convex outline needs the pixels
+    let image = if strategy == FitStrategy::Convex && path.ends_with(".png") {
+        ctx.fs
+            .read(&fs_path)
+            .ok()
+            .and_then(|data| Canvas::decode_png(&data).ok())
+    } else {
+        None
+    };
+    collision::fit_sprite(&frame, image.as_ref(), strategy)
+        .map_err(|message| error("INVALID_STRATEGY", message))
+}
+
+/// Transform properties of the shape node: the source node's own, moved by
+/// the shape's offset in the node's space
+fn placement(source: &IndexMap<String, String>, offset: &[f64]) -> IndexMap<String, String> {
+    let mut properties = IndexMap::new();
+    let at_origin = offset.iter().all(|&c| c == 0.0);
+
+    if offset.len() == 3 {
+        if at_origin {
+            if let Some(value) = source.get("transform") {
+                properties.insert("transform".to_string(), value.clone());
+            }
+            return properties;
+        }
+        let mut transform = source
+            .get("transform")
+            .and_then(|v| collision::components(v))
+            .filter(|c| c.len() == 12)
+            .unwrap_or_else(|| vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+        // Basis rows come first, then the origin
+        for row in 0..3 {
+            transform[9 + row] += (0..3)
+                .map(|col| transform[row * 3 + col] * offset[col])
+                .sum::<f64>();
+        }
+        let values: Vec<String> = transform
+            .iter()
+            .map(|&n| Value::Number(n).to_godot())
+            .collect();
+        properties.insert(
+            "transform".to_string(),
+            format!("Transform3D({})", values.join(", ")),
+        );
+        return properties;
+    }
+
+    for key in ["rotation", "scale", "skew"] {
+        if let Some(value) = source.get(key) {
+            properties.insert(key.to_string(), value.clone());
+        }
+    }
+    if at_origin {
+        if let Some(value) = source.get("position") {
+            properties.insert("position".to_string(), value.clone());
+        }
+        return properties;
+    }
+    let vector = |key: &str, default: [f64; 2]| {
+        source
+            .get(key)
+            .and_then(|v| collision::components(v))
+            .filter(|c| c.len() == 2)
+            .map_or(default, |c| [c[0], c[1]])
+    };
+    let position = vector("position", [0.0, 0.0]);
+    let scale = vector("scale", [1.0, 1.0]);
+    let rotation = source
+        .get("rotation")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .unwrap_or(0.0);
+    let (x, y) = (offset[0] * scale[0], offset[1] * scale[1]);
+    let (sin, cos) = rotation.sin_cos();
+    properties.insert(
+        "position".to_string(),
+        Value::Vector(
+            VectorKind::Vector2,
+            vec![
+                position[0] + x * cos - y * sin,
+                position[1] + x * sin + y * cos,
+            ],
+        )
+        .to_godot(),
+    );
+    properties
+}
+
+/// `("ExtResource", "id")` for `ExtResource("id")`, likewise for SubResource
+fn resource_ref(value: &str) -> Option<(&str, &str)> {
+    let (kind, rest) = value.trim().split_once('(')?;
+    let id = rest.strip_suffix(')')?.trim().trim_matches('"');
+    matches!(kind, "ExtResource" | "SubResource").then_some((kind, id))
+}
+
+fn ext_resource_path<'a>(
+    scene: &'a GodotScene,
+    id: &str,
+) -> Result<&'a str, Box<GqlStructuredError>> {
+    scene
+        .ext_resources
+        .iter()
+        .find(|r| r.id == id)
+        .map(|r| r.path.as_str())
+        .ok_or_else(|| error("MISSING_RESOURCE", format!("Missing ext_resource {}", id)))
+}
+
+fn error(code: &str, message: String) -> Box<GqlStructuredError> {
+    Box::new(GqlStructuredError::new(
+        code,
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
+
+fn failure(error: GqlStructuredError) -> GenerateCollisionShapeResult {
+    GenerateCollisionShapeResult {
+        success: false,
+        node_path: None,
+        shape_type: None,
+        warnings: vec![],
+        error: Some(error),
+    }
+}
@@ -3,6 +3,12 @@
 //! Provides context data (e.g., project path) to resolvers.
 
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::project_index::ProjectIndex;
+use super::transaction::TransactionFs;
+use super::vfs::ProjectFs;
 
 /// Context for GraphQL resolvers
 #[derive(Debug, Clone)]
@@ -11,16 +17,45 @@ pub struct GqlContext {
     pub project_path: PathBuf,
     /// Port for Godot editor plugin HTTP API (default: 6060)
     pub godot_port: u16,
-    /// HTTP request timeout in milliseconds (default: 5000)
+    /// Port of the plugin's WebSocket server, tried before HTTP (default: 6061);
+    /// None sends live commands over HTTP only
+    pub ws_port: Option<u16>,
+    /// Live command timeout in milliseconds (default: 5000)
     pub timeout_ms: u64,
+    /// Agent session that file writes are attributed to in the audit log
+    /// (default: one per server process)
+    pub session_id: String,
+    /// Cached file listings and parses, shared by every context of the project
+    pub index: ProjectIndex,
+    /// Filesystem project files are read from and written to
+    pub fs: Arc<dyn ProjectFs>,
+    /// Layer of `fs` that stages writes while a transaction is open
+    pub transaction: Arc<TransactionFs>,
+    /// Images resolvers attach to the response; MCP tools return them as
+    /// image content blocks next to the JSON
+    pub images: Arc<Mutex<Vec<ImageAttachment>>>,
+}
+
+/// Image attached to a GraphQL response (see [`GqlContext::attach_image`])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageAttachment {
+    pub mime_type: String,
+    pub data_base64: String,
 }
 
 impl GqlContext {
     pub fn new(project_path: PathBuf) -> Self {
+        let transaction = TransactionFs::shared(&project_path);
         Self {
+            index: ProjectIndex::shared(&project_path),
             project_path,
             godot_port: 6060,
+            ws_port: Some(6061),
             timeout_ms: 5000,
+            session_id: process_session_id().to_string(),
+            fs: transaction.clone(),
+            transaction,
+            images: Arc::default(),
         }
     }
 
@@ -30,9 +65,58 @@ impl GqlContext {
         self
     }
 
+    /// Create context with a custom WebSocket port (None: HTTP only)
+    pub fn with_ws_port(mut self, port: Option<u16>) -> Self {
+        self.ws_port = port;
+        self
+    }
+
     /// Create context with custom timeout
     pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
         self.timeout_ms = timeout_ms;
         self
     }
+
+    /// Create context attributing writes to a specific session
+    pub fn with_session(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = session_id.into();
+        self
+    }
+
+    /// Create context over another filesystem (e.g. an in-memory sandbox),
+    /// with an index of its own instead of the one shared by the project path
+    pub fn with_fs(mut self, fs: Arc<dyn ProjectFs>) -> Self {
+        self.transaction = Arc::new(TransactionFs::new(fs));
+        self.index = ProjectIndex::with_fs(self.transaction.clone());
+        self.fs = self.transaction.clone();
+        self
+    }
+
+    /// Attach an image for the MCP tool result
+    pub fn attach_image(&self, mime_type: &str, data_base64: String) {
+        self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(ImageAttachment {
+                mime_type: mime_type.to_string(),
+                data_base64,
+            });
+    }
+
+    /// Take the images attached so far
+    pub fn take_images(&self) -> Vec<ImageAttachment> {
+        std::mem::take(&mut *self.images.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Session id shared by all contexts of this process (`s-<start millis>`)
+fn process_session_id() -> &'static str {
+    static SESSION_ID: OnceLock<String> = OnceLock::new();
+    SESSION_ID.get_or_init(|| {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        format!("s-{}", millis)
+    })
 }
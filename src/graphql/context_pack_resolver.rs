@@ -0,0 +1,444 @@
+//! Context Pack Resolver
+//!
+//! Packs the files gatherContext finds around some entry points into one
+//! Markdown bundle that fits a token budget. Every file starts at full
+//! detail; while the bundle is over budget, files are lowered one step at a
+//! time — comments and blank lines stripped, long function bodies collapsed,
+//! then a summary (declarations referenced by the other files for scripts,
+//! the node tree for scenes) — and finally left out. The strategy decides
+//! which file is lowered next. Tokens are estimated at 4 characters each.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::godot::gdscript::ast::Member;
+use crate::godot::gdscript::GDScript;
+use crate::godot::tscn::GodotScene;
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::dependency_resolver::resolve_gather_context;
+use super::project_resolver::is_script_path;
+use super::types::*;
+
+/// Function bodies longer than this are collapsed at COLLAPSED detail
+const COLLAPSE_BODY_LINES: usize = 4;
+
+/// One file of the bundle and its sections, most detailed first
+struct Candidate {
+    path: String,
+    file_type: FileType,
+    role: ContextRole,
+    /// Detail, bundle section and its length in characters
+    levels: Vec<(PackDetail, String, usize)>,
+    /// Index into `levels`; `levels.len()` when omitted
+    level: usize,
+}
+
+impl Candidate {
+    fn detail(&self) -> PackDetail {
+        self.levels
+            .get(self.level)
+            .map_or(PackDetail::Omitted, |(detail, _, _)| *detail)
+    }
+
+    fn chars(&self) -> usize {
+        self.levels
+            .get(self.level)
+            .map_or(0, |(_, _, chars)| *chars)
+    }
+
+    /// The next step leaves the file out
+    fn last_level(&self) -> bool {
+        self.level + 1 >= self.levels.len()
+    }
+}
+
+/// Resolve packContext query
+pub fn resolve_pack_context(
+    ctx: &GqlContext,
+    entry_points: Vec<String>,
+    token_budget: i32,
+    strategy: Option<PackStrategy>,
+) -> PackedContext {
+    let strategy = strategy.unwrap_or(PackStrategy::Balanced);
+    let failure = |error: GqlStructuredError| PackedContext {
+        bundle: String::new(),
+        estimated_tokens: 0,
+        token_budget,
+        strategy,
+        files: Vec::new(),
+        omitted: Vec::new(),
+        error: Some(error),
+    };
+    if token_budget <= 0 {
+        return failure(GqlStructuredError::new(
+            "INVALID_BUDGET",
+            GqlErrorCategory::Validation,
+            format!("tokenBudget must be positive, got {}", token_budget),
+        ));
+    }
+    if let Some(missing) = entry_points.iter().find(|path| {
+        !ctx.fs
+            .is_file(&path_utils::to_fs_path_unchecked(&ctx.project_path, path))
+    }) {
+        return failure(
+            GqlStructuredError::new(
+                "FILE_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Entry point not found: {}", missing),
+            )
+            .with_suggestion(
+                "res:// から始まる既存のシーンまたはスクリプトのパスを指定してください",
+            ),
+        );
+    }
+
+    let mut candidates = collect_candidates(ctx, &entry_points);
+    let budget = token_budget as usize;
+    while tokens_of(candidates.iter().map(Candidate::chars).sum()) > budget {
+        let Some(next) = next_to_lower(&candidates, strategy) else {
+            break;
+        };
+        candidates[next].level += 1;
+    }
+
+    let bundle: String = candidates
+        .iter()
+        .filter_map(|c| c.levels.get(c.level))
+        .map(|(_, section, _)| section.as_str())
+        .collect();
+    PackedContext {
+        estimated_tokens: tokens_of(bundle.chars().count()) as i32,
+        bundle,
+        token_budget,
+        strategy,
+        omitted: candidates
+            .iter()
+            .filter(|c| c.detail() == PackDetail::Omitted)
+            .map(|c| c.path.clone())
+            .collect(),
+        files: candidates
+            .iter()
+            .map(|c| PackedFile {
+                path: c.path.clone(),
+                file_type: c.file_type,
+                role: c.role,
+                detail: c.detail(),
+                estimated_tokens: tokens_of(c.chars()) as i32,
+                full_tokens: c.levels.first().map_or(0, |l| tokens_of(l.2)) as i32,
+            })
+            .collect(),
+        error: None,
+    }
+}
+
+/// Entry points, then their dependencies, then their dependents
+fn collect_candidates(ctx: &GqlContext, entry_points: &[String]) -> Vec<Candidate> {
+    let mut seen = HashSet::new();
+    let mut files: Vec<(String, FileType, ContextRole)> = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut dependents = Vec::new();
+    for entry_point in entry_points {
+        let gathered = resolve_gather_context(
+            ctx,
+            GatherContextInput {
+                entry_point: entry_point.clone(),
+                depth: None,
+                include: None,
+            },
+        );
+        if seen.insert(gathered.main.path.clone()) {
+            files.push((
+                gathered.main.path,
+                gathered.main.file_type,
+                ContextRole::EntryPoint,
+            ));
+        }
+        dependencies.extend(gathered.dependencies);
+        dependents.extend(gathered.dependents);
+    }
+    for item in dependencies {
+        if seen.insert(item.path.clone()) {
+            files.push((item.path, item.file_type, ContextRole::Dependency));
+        }
+    }
+    for item in dependents {
+        if seen.insert(item.path.clone()) {
+            files.push((item.path, item.file_type, ContextRole::Dependent));
+        }
+    }
+
+    let contents: Vec<String> = files
+        .iter()
+        .map(|(path, _, _)| {
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
+            ctx.index
+                .read_to_string(&fs_path)
+                .map(|text| text.to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // Number of files each name appears in, for summaries of referenced members
+    let file_words: Vec<HashSet<&str>> =
+        contents.iter().map(|text| words(text).collect()).collect();
+    let mut word_files: HashMap<&str, usize> = HashMap::new();
+    for word in file_words.iter().flatten() {
+        *word_files.entry(word).or_default() += 1;
+    }
+
+    files
+        .into_iter()
+        .enumerate()
+        .map(|(i, (path, file_type, role))| {
+            let content = &contents[i];
+            let levels = if path.ends_with(".gd") {
+                // Used by one of the other files
+                let referenced = |name: &str| {
+                    word_files.get(name).copied().unwrap_or(0)
+                        > usize::from(file_words[i].contains(name))
+                };
+                let only_referenced: Option<&dyn Fn(&str) -> bool> =
+                    (role != ContextRole::EntryPoint).then_some(&referenced);
+                gdscript_levels(content, only_referenced)
+            } else if path.ends_with(".tscn") {
+                scene_levels(content)
+            } else if is_script_path(&path) {
+                vec![
+                    (PackDetail::Full, content.clone()),
+                    (PackDetail::Stripped, strip_lines(content, "//")),
+                ]
+            } else {
+                vec![(PackDetail::Full, content.clone())]
+            };
+            let levels = dedup_levels(levels)
+                .into_iter()
+                .map(|(detail, text)| {
+                    let section = render_section(&path, detail, &text);
+                    let chars = section.chars().count();
+                    (detail, section, chars)
+                })
+                .collect();
+            Candidate {
+                path,
+                file_type,
+                role,
+                levels,
+                level: 0,
+            }
+        })
+        .collect()
+}
+
+/// Drop renderings that are no smaller than the one before
+fn dedup_levels(levels: Vec<(PackDetail, String)>) -> Vec<(PackDetail, String)> {
+    let mut kept: Vec<(PackDetail, String)> = Vec::new();
+    for (detail, text) in levels {
+        if kept
+            .last()
+            .is_some_and(|(_, last)| text.len() >= last.len())
+        {
+            continue;
+        }
+        kept.push((detail, text));
+    }
+    kept
+}
+
+fn gdscript_levels(
+    content: &str,
+    only_referenced: Option<&dyn Fn(&str) -> bool>,
+) -> Vec<(PackDetail, String)> {
+    let script = GDScript::parse(content);
+    let lines: Vec<&str> = content.lines().collect();
+
+    // Bodies of long top-level functions, as (first, last) line indexes
+    let collapsed: Vec<(usize, usize)> = script
+        .ast
+        .functions()
+        .filter(|f| f.end_line > f.line + COLLAPSE_BODY_LINES)
+        .map(|f| (f.line, f.end_line.min(lines.len()) - 1))
+        .collect();
+    let mut collapsed_text = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if let Some(&(first, last)) = collapsed.iter().find(|(first, _)| *first == i) {
+            let indent: String = lines[first]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .collect();
+            collapsed_text.push(format!(
+                "{}...  # {} lines collapsed",
+                if indent.is_empty() { "\t" } else { &indent },
+                last + 1 - first
+            ));
+            i = last + 1;
+            continue;
+        }
+        collapsed_text.push(lines[i].to_string());
+        i += 1;
+    }
+
+    let mut summary = Vec::new();
+    if let Some(class_name) = &script.class_name {
+        summary.push(format!("class_name {}", class_name));
+    }
+    if let Some(extends) = &script.extends {
+        summary.push(format!("extends {}", extends));
+    }
+    for member in &script.ast.members {
+        let name = member.name().unwrap_or_default();
+        if only_referenced.is_some_and(|referenced| !referenced(name)) {
+            continue;
+        }
+        let first = member.line().saturating_sub(1);
+        match member {
+            // Enum values are part of the declaration
+            Member::Enum(_) => {
+                let last = member.end_line().min(lines.len());
+                summary.extend(lines[first.min(last)..last].iter().map(|l| l.to_string()));
+            }
+            _ => {
+                if let Some(line) = lines.get(first) {
+                    summary.push(line.trim_end().to_string());
+                }
+            }
+        }
+    }
+
+    vec![
+        (PackDetail::Full, content.to_string()),
+        (PackDetail::Stripped, strip_lines(content, "#")),
+        (
+            PackDetail::Collapsed,
+            strip_lines(&collapsed_text.join("\n"), "#"),
+        ),
+        (PackDetail::Summary, summary.join("\n")),
+    ]
+}
+
+fn scene_levels(content: &str) -> Vec<(PackDetail, String)> {
+    let mut levels = vec![(PackDetail::Full, content.to_string())];
+    let Ok(scene) = GodotScene::parse(content) else {
+        return levels;
+    };
+
+    let resource_path = |value: &str| {
+        let id = value.strip_prefix("ExtResource(\"")?.strip_suffix("\")")?;
+        scene
+            .ext_resources
+            .iter()
+            .find(|r| r.id == id)
+            .map(|r| r.path.clone())
+    };
+    let mut outline = Vec::new();
+    for node in &scene.nodes {
+        let depth = match node.parent.as_deref() {
+            None => 0,
+            Some(".") => 1,
+            Some(parent) => parent.split('/').count() + 1,
+        };
+        let kind = match &node.instance {
+            Some(id) => scene
+                .ext_resources
+                .iter()
+                .find(|r| &r.id == id)
+                .map_or_else(|| "instance".to_string(), |r| r.path.clone()),
+            None => node.node_type.clone(),
+        };
+        let mut line = format!("{}{} ({})", "  ".repeat(depth), node.name, kind);
+        if let Some(script) = node.properties.get("script").and_then(|v| resource_path(v)) {
+            line.push_str(&format!(" script={}", script));
+        }
+        if !node.groups.is_empty() {
+            line.push_str(&format!(" groups=[{}]", node.groups.join(", ")));
+        }
+        outline.push(line);
+    }
+    for connection in &scene.connections {
+        outline.push(format!(
+            "{}.{} -> {}.{}",
+            connection.from, connection.signal, connection.to, connection.method
+        ));
+    }
+    levels.push((PackDetail::Summary, outline.join("\n")));
+    levels
+}
+
+/// Text without blank lines and lines that hold only a comment
+fn strip_lines(content: &str, comment: &str) -> String {
+    content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with(comment)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+}
+
+/// File to lower next, or None when every file is left out
+fn next_to_lower(candidates: &[Candidate], strategy: PackStrategy) -> Option<usize> {
+    let lowerable = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.level < c.levels.len());
+    // Smallest key goes first; later (lower priority) files win ties
+    match strategy {
+        // Summarize everything before leaving anything out, entry points last
+        PackStrategy::Balanced => lowerable
+            .min_by_key(|(i, c)| {
+                (
+                    c.last_level(),
+                    c.role == ContextRole::EntryPoint,
+                    c.level,
+                    std::cmp::Reverse(*i),
+                )
+            })
+            .map(|(i, _)| i),
+        // Keep as many files as possible, lowering all of them evenly
+        PackStrategy::Breadth => lowerable
+            .min_by_key(|(i, c)| (c.last_level(), c.level, std::cmp::Reverse(*i)))
+            .map(|(i, _)| i),
+        // Keep the closest files whole, dropping the farthest first
+        PackStrategy::Depth => lowerable
+            .min_by_key(|(i, c)| (c.role == ContextRole::EntryPoint, std::cmp::Reverse(*i)))
+            .map(|(i, _)| i),
+    }
+}
+
+/// Markdown section of one file
+fn render_section(path: &str, detail: PackDetail, text: &str) -> String {
+    let language = match path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("gd") => "gdscript",
+        Some("cs") => "csharp",
+        _ => "",
+    };
+    format!(
+        "## {} ({})\n```{}\n{}\n```\n\n",
+        path,
+        detail_label(detail),
+        language,
+        text.trim_end()
+    )
+}
+
+fn detail_label(detail: PackDetail) -> &'static str {
+    match detail {
+        PackDetail::Full => "full",
+        PackDetail::Stripped => "comments stripped",
+        PackDetail::Collapsed => "long functions collapsed",
+        PackDetail::Summary => "summary",
+        PackDetail::Omitted => "omitted",
+    }
+}
+
+/// Rough token count of a text: 4 characters per token, rounded up
+fn tokens_of(chars: usize) -> usize {
+    chars.div_ceil(4)
+}
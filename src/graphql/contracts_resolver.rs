@@ -0,0 +1,347 @@
+//! Contracts Resolver
+//!
+//! Validates game design invariants declared in `res://.godot-mcp/contracts.toml`
+//! against the project's scenes, e.g.:
+//!
+//! ```toml
+//! [[contract]]
+//! name = "level-spawn"
+//! scenes = "levels/**/*.tscn"
+//!
+//! [[contract.nodes]]
+//! name = "PlayerSpawn"
+//! group = "spawn"
+//! count = 1
+//!
+//! [[contract]]
+//! name = "ui-root"
+//! scenes = "ui/**/*.tscn"
+//! root_type = ["Control", "CanvasLayer"]
+//! ```
+
+use serde::Deserialize;
+
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::project_resolver::collect_project_files;
+use super::types::*;
+
+/// Contracts file checked by checkContracts
+pub const CONTRACTS_PATH: &str = "res://.godot-mcp/contracts.toml";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContractsFile {
+    #[serde(default)]
+    contract: Vec<Contract>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Contract {
+    name: String,
+    /// Glob over res:// paths ("levels/**/*.tscn")
+    #[serde(default = "default_scenes")]
+    scenes: String,
+    /// Allowed root node types
+    root_type: Option<OneOrMany>,
+    #[serde(default)]
+    nodes: Vec<NodeRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value),
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// Node count rule; selector fields are combined with AND
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeRule {
+    /// Node name (`*` and `?` wildcards)
+    name: Option<String>,
+    #[serde(rename = "type")]
+    node_type: Option<String>,
+    group: Option<String>,
+    count: Option<usize>,
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+impl NodeRule {
+    fn matches(&self, node: &SceneNode) -> bool {
+        self.name
+            .as_ref()
+            .is_none_or(|name| wildcard_match(name, &node.name))
+            && self.node_type.as_ref().is_none_or(|t| *t == node.node_type)
+            && self.group.as_ref().is_none_or(|g| node.groups.contains(g))
+    }
+
+    /// Inclusive bounds; a rule without counts requires at least one node
+    fn bounds(&self) -> (usize, Option<usize>) {
+        match self.count {
+            Some(count) => (count, Some(count)),
+            None if self.min.is_none() && self.max.is_none() => (1, None),
+            None => (self.min.unwrap_or(0), self.max),
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(name) = &self.name {
+            parts.push(format!("name={}", name));
+        }
+        if let Some(node_type) = &self.node_type {
+            parts.push(format!("type={}", node_type));
+        }
+        if let Some(group) = &self.group {
+            parts.push(format!("group={}", group));
+        }
+        if parts.is_empty() {
+            "any node".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+fn default_scenes() -> String {
+    "**/*.tscn".to_string()
+}
+
+/// Resolve checkContracts query
+pub fn resolve_check_contracts(ctx: &GqlContext) -> ContractCheckResult {
+    let contracts = match load_contracts(ctx) {
+        Ok(contracts) => contracts,
+        Err(error) => {
+            return ContractCheckResult {
+                passed: false,
+                contracts_checked: 0,
+                scenes_checked: 0,
+                violations: vec![],
+                error: Some(*error),
+            }
+        }
+    };
+
+    let (scene_files, _) = collect_project_files(ctx);
+    let mut checked_scenes = std::collections::BTreeSet::new();
+    let mut violations = Vec::new();
+
+    for scene_file in &scene_files {
+        let targets: Vec<&Contract> = contracts
+            .iter()
+            .filter(|c| {
+                glob_match(
+                    path_utils::strip_res_prefix(&c.scenes),
+                    path_utils::strip_res_prefix(&scene_file.path),
+                )
+            })
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+        checked_scenes.insert(scene_file.path.clone());
+
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &scene_file.path);
+        let scene = match ctx.index.scene(&file_path) {
+            Ok(scene) => scene,
+            Err(e) => {
+                for contract in targets {
+                    violations.push(ContractViolation {
+                        contract: contract.name.clone(),
+                        scene: scene_file.path.clone(),
+                        node_paths: vec![],
+                        message: format!("Failed to read scene: {}", e),
+                    });
+                }
+                continue;
+            }
+        };
+
+        for contract in targets {
+            check_scene(contract, &scene, &scene_file.path, &mut violations);
+        }
+    }
+
+    ContractCheckResult {
+        passed: violations.is_empty(),
+        contracts_checked: contracts.len() as i32,
+        scenes_checked: checked_scenes.len() as i32,
+        violations,
+        error: None,
+    }
+}
+
+fn load_contracts(ctx: &GqlContext) -> Result<Vec<Contract>, Box<GqlStructuredError>> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, CONTRACTS_PATH);
+    let content = ctx.fs.read_to_string(&path).map_err(|_| {
+        let error = GqlStructuredError::new(
+            "CONTRACTS_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Contracts file not found: {}", CONTRACTS_PATH),
+        )
+        .with_suggestion(
+            "[[contract]] エントリを記述した .godot-mcp/contracts.toml を作成してください",
+        );
+        Box::new(error)
+    })?;
+
+    let file: ContractsFile = toml::from_str(&content).map_err(|e| {
+        let mut error = GqlStructuredError::new(
+            "CONTRACTS_INVALID",
+            GqlErrorCategory::Validation,
+            e.message().to_string(),
+        );
+        error.location = Some(GqlErrorLocation {
+            file: Some(CONTRACTS_PATH.to_string()),
+            line: e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() as i32 + 1),
+            column: None,
+        });
+        Box::new(error)
+    })?;
+
+    Ok(file.contract)
+}
+
+fn check_scene(
+    contract: &Contract,
+    scene: &GodotScene,
+    scene_path: &str,
+    violations: &mut Vec<ContractViolation>,
+) {
+    let mut violation = |node_paths: Vec<String>, message: String| {
+        violations.push(ContractViolation {
+            contract: contract.name.clone(),
+            scene: scene_path.to_string(),
+            node_paths,
+            message,
+        });
+    };
+
+    if let (Some(allowed), Some(root)) = (&contract.root_type, scene.nodes.first()) {
+        let allowed = allowed.as_slice();
+        if !allowed.contains(&root.node_type) {
+            violation(
+                vec![".".to_string()],
+                format!(
+                    "Root node '{}' is {}, expected {}",
+                    root.name,
+                    root.node_type,
+                    allowed.join(" or ")
+                ),
+            );
+        }
+    }
+
+    for rule in &contract.nodes {
+        let matched: Vec<String> = scene
+            .nodes
+            .iter()
+            .filter(|n| rule.matches(n))
+            .map(node_path)
+            .collect();
+        let (min, max) = rule.bounds();
+        let found = matched.len();
+
+        let expected = match max {
+            Some(max) if max == min => format!("exactly {}", min),
+            Some(max) if found > max => format!("at most {}", max),
+            _ => format!("at least {}", min),
+        };
+        if found < min || max.is_some_and(|max| found > max) {
+            violation(
+                matched,
+                format!(
+                    "Expected {} node(s) matching {}, found {}",
+                    expected,
+                    rule.describe(),
+                    found
+                ),
+            );
+        }
+    }
+}
+
+/// Match a res:// relative path against a glob (`**` spans folders)
+pub(super) fn glob_match(pattern: &str, path: &str) -> bool {
+    fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => path.is_empty(),
+            Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+            Some((segment, rest)) => {
+                !path.is_empty()
+                    && wildcard_match(segment, path[0])
+                    && match_segments(rest, &path[1..])
+            }
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match_segments(&pattern, &path)
+}
+
+/// Match a single name against `*` / `?` wildcards
+pub(super) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("levels/**/*.tscn", "levels/world1/level_1.tscn"));
+        assert!(glob_match("levels/**/*.tscn", "levels/level_1.tscn"));
+        assert!(!glob_match("levels/**/*.tscn", "ui/menu.tscn"));
+        assert!(glob_match("**/*.tscn", "main.tscn"));
+        assert!(glob_match("ui/*_menu.tscn", "ui/main_menu.tscn"));
+        assert!(!glob_match("ui/*.tscn", "ui/sub/menu.tscn"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("Spawn*", "SpawnPoint"));
+        assert!(wildcard_match("Enemy?", "Enemy1"));
+        assert!(!wildcard_match("Enemy?", "Enemy12"));
+        assert!(wildcard_match("*", ""));
+    }
+}
@@ -0,0 +1,239 @@
+//! GDScript line coverage
+//!
+//! GUT runs a pre-run hook before loading the test scripts and a post-run
+//! hook after the last one. The pre-run hook swaps the source of every
+//! measured script for an instrumented copy ([`instrument`]) with a probe
+//! line before each executable statement ([`executable_lines`]); the probes
+//! mark their line in a dictionary kept as Engine metadata, which the
+//! post-run hook writes to [`HITS_PATH`].
+//!
+//! Probes are inserted on lines of their own, so line numbers in errors
+//! raised during a coverage run point into the instrumented copy.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::godot::gdscript::ast::{ClassDecl, Member, Statement, StatementKind};
+use crate::godot::gdscript::GDScript;
+
+/// Pre-run hook swapping in the instrumented sources
+pub const PRE_RUN_HOOK_PATH: &str = "res://.godot-mcp/tools/coverage_pre_run.gd";
+
+/// Post-run hook writing the lines hit
+pub const POST_RUN_HOOK_PATH: &str = "res://.godot-mcp/tools/coverage_post_run.gd";
+
+/// Scripts to instrument: `[{"path", "source"}]`, in probe id order
+pub const PLAN_PATH: &str = "res://.godot-mcp/coverage_plan.json";
+
+/// Lines hit per probe id: `{"0": [12, 15]}`
+pub const HITS_PATH: &str = "res://.godot-mcp/coverage_hits.json";
+
+/// Engine metadata holding the hits while the tests run
+const HITS_META: &str = "godot_mcp_coverage";
+
+pub const PRE_RUN_HOOK: &str = r#"extends "res://addons/gut/hook_script.gd"
+## Written by godot-mcp-rs: replaces the scripts listed in
+## res://.godot-mcp/coverage_plan.json with their instrumented sources.
+
+
+func run() -> void:
+	var hits := {}
+	Engine.set_meta("godot_mcp_coverage", hits)
+	var plan = JSON.parse_string(FileAccess.get_file_as_string("res://.godot-mcp/coverage_plan.json"))
+	if not plan is Array:
+		printerr("COVERAGE_ERROR: Cannot read res://.godot-mcp/coverage_plan.json")
+		return
+	for id in plan.size():
+		hits[id] = {}
+		var script = load(plan[id]["path"])
+		if not script is GDScript:
+			printerr("COVERAGE_ERROR: Cannot load ", plan[id]["path"])
+			continue
+		script.source_code = plan[id]["source"]
+		if script.reload(true) != OK:
+			printerr("COVERAGE_ERROR: Cannot instrument ", plan[id]["path"])
+"#;
+
+pub const POST_RUN_HOOK: &str = r#"extends "res://addons/gut/hook_script.gd"
+## Written by godot-mcp-rs: saves the lines the instrumented scripts hit to
+## res://.godot-mcp/coverage_hits.json.
+
+
+func run() -> void:
+	var hits: Dictionary = Engine.get_meta("godot_mcp_coverage", {})
+	var lines := {}
+	for id in hits:
+		lines[str(id)] = hits[id].keys()
+	var file := FileAccess.open("res://.godot-mcp/coverage_hits.json", FileAccess.WRITE)
+	if file == null:
+		printerr("COVERAGE_ERROR: Cannot write res://.godot-mcp/coverage_hits.json")
+		return
+	file.store_string(JSON.stringify(lines))
+"#;
+
+/// Lines a probe can precede: those starting a statement inside a function
+/// body. Statements sharing a line with the `func`, `elif`, `else` or match
+/// pattern that opens their block are left out, since a probe placed before
+/// them would split the header from its body.
+pub fn executable_lines(script: &GDScript, source: &str) -> Vec<usize> {
+    let mut lines = BTreeSet::new();
+    let mut headers = BTreeSet::new();
+    collect_class(&script.ast, &mut lines, &mut headers);
+    let text: Vec<&str> = source.lines().collect();
+    lines
+        .into_iter()
+        .filter(|line| !headers.contains(line))
+        .filter(|line| {
+            text.get(line - 1).is_some_and(|text| {
+                let text = text.trim_start();
+                !text.starts_with("elif ") && !text.starts_with("else")
+            })
+        })
+        .collect()
+}
+
+fn collect_class(class: &ClassDecl, lines: &mut BTreeSet<usize>, headers: &mut BTreeSet<usize>) {
+    for member in &class.members {
+        match member {
+            Member::Function(function) => {
+                headers.insert(function.line);
+                collect_block(&function.body, lines, headers);
+            }
+            Member::Class(class) => collect_class(class, lines, headers),
+            _ => {}
+        }
+    }
+}
+
+fn collect_block(body: &[Statement], lines: &mut BTreeSet<usize>, headers: &mut BTreeSet<usize>) {
+    for statement in body {
+        lines.insert(statement.line);
+        match &statement.kind {
+            StatementKind::If {
+                branches,
+                otherwise,
+            } => {
+                for (_, body) in branches {
+                    collect_block(body, lines, headers);
+                }
+                if let Some(body) = otherwise {
+                    collect_block(body, lines, headers);
+                }
+            }
+            StatementKind::While { body, .. } | StatementKind::For { body, .. } => {
+                collect_block(body, lines, headers)
+            }
+            StatementKind::Match { branches, .. } => {
+                for branch in branches {
+                    headers.insert(branch.line);
+                    collect_block(&branch.body, lines, headers);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `source` with a probe for script `id` before each of `lines` (sorted),
+/// indented like the line it precedes
+pub fn instrument(source: &str, id: usize, lines: &[usize]) -> String {
+    let mut instrumented = String::with_capacity(source.len() + lines.len() * 64);
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        if lines.binary_search(&number).is_ok() {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            instrumented.push_str(&format!(
+                "{}Engine.get_meta(\"{}\")[{}][{}] = true\n",
+                indent, HITS_META, id, number
+            ));
+        }
+        instrumented.push_str(line);
+        instrumented.push('\n');
+    }
+    instrumented
+}
+
+/// Lines hit per probe id, from the post-run hook's JSON
+pub fn parse_hits(json: &str) -> Result<BTreeMap<usize, BTreeSet<usize>>, String> {
+    let raw: BTreeMap<String, Vec<f64>> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid coverage hits: {}", e))?;
+    raw.into_iter()
+        .map(|(id, lines)| {
+            let id = id
+                .parse()
+                .map_err(|_| format!("Invalid coverage script id {:?}", id))?;
+            Ok((id, lines.into_iter().map(|line| line as usize).collect()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "extends Node
+
+var speed := 10
+
+
+func move(delta: float) -> float:
+\tvar step := speed * delta
+\tif step > 5:
+\t\tstep = 5
+\telif step < 0: step = 0
+\telse:
+\t\tpass
+\tfor i in 3:
+\t\tstep += i
+\tmatch step:
+\t\t1: return 1
+\t\t_:
+\t\t\treturn step
+\treturn step
+
+
+func quick() -> int: return 1
+
+
+class Inner:
+\tfunc run():
+\t\tprint(\"run\")
+";
+
+    #[test]
+    fn test_executable_lines() {
+        let script = GDScript::parse(SOURCE);
+        assert_eq!(
+            executable_lines(&script, SOURCE),
+            vec![7, 8, 9, 12, 13, 14, 15, 18, 19, 27]
+        );
+    }
+
+    #[test]
+    fn test_instrument() {
+        let instrumented = instrument(SOURCE, 3, &[7, 27]);
+        let lines: Vec<&str> = instrumented.lines().collect();
+        assert_eq!(
+            lines[6],
+            "\tEngine.get_meta(\"godot_mcp_coverage\")[3][7] = true"
+        );
+        assert_eq!(lines[7], "\tvar step := speed * delta");
+        assert_eq!(
+            lines[27],
+            "\t\tEngine.get_meta(\"godot_mcp_coverage\")[3][27] = true"
+        );
+        assert_eq!(lines.len(), SOURCE.lines().count() + 2);
+
+        // Still a valid script with the same functions
+        let script = GDScript::parse(&instrumented);
+        assert!(script.errors.is_empty(), "{:?}", script.errors);
+        assert_eq!(script.functions.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_hits() {
+        let hits = parse_hits(r#"{"0": [7, 9.0], "2": []}"#).unwrap();
+        assert_eq!(hits[&0], BTreeSet::from([7, 9]));
+        assert!(hits[&2].is_empty());
+        assert!(parse_hits("[]").is_err());
+    }
+}
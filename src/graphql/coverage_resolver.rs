@@ -0,0 +1,226 @@
+//! Test Coverage Resolver
+//!
+//! runTestCoverage: runs the GUT tests with every measured script instrumented
+//! (see `coverage`) and reports covered and total executable lines per
+//! script, with the lines no test reached.
+
+use std::collections::BTreeSet;
+
+use serde_json::json;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::coverage;
+use super::project_resolver::collect_project_files;
+use super::test_resolver::{run_gut, DEFAULT_TEST_PATH};
+use super::types::*;
+
+/// Resolve runTestCoverage mutation
+pub fn resolve_run_test_coverage(
+    ctx: &GqlContext,
+    input: &TestCoverageInput,
+) -> TestCoverageResult {
+    let failure =
+        |tests: Option<TestExecutionResult>, error: GqlStructuredError| TestCoverageResult {
+            success: false,
+            covered_lines: 0,
+            total_lines: 0,
+            percent: 0.0,
+            scripts: Vec::new(),
+            tests,
+            error: Some(error),
+        };
+    if ctx.fs.is_virtual() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "runTestCoverage needs the project on disk",
+            ),
+        );
+    }
+    let fs_path = |res_path: &str| path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+
+    // Scripts to measure and their executable lines, in probe id order
+    let test_folder = folder(input.test_path.as_deref().unwrap_or(DEFAULT_TEST_PATH));
+    let scope = input.path.as_deref().map(|path| (path, folder(path)));
+    let mut targets: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut plan = Vec::new();
+    for script in collect_project_files(ctx).1 {
+        let path = script.path;
+        if !path.ends_with(".gd") || path.starts_with(&test_folder) {
+            continue;
+        }
+        if let Some((scope, scope_folder)) = &scope {
+            if path != *scope && !path.starts_with(scope_folder.as_str()) {
+                continue;
+            }
+        }
+        let (Some(parsed), Ok(source)) = (
+            ctx.index.script(&fs_path(&path)),
+            ctx.fs.read_to_string(&fs_path(&path)),
+        ) else {
+            continue;
+        };
+        if !parsed.errors.is_empty() || is_test_script(&path, parsed.extends.as_deref()) {
+            continue;
+        }
+        let lines = coverage::executable_lines(&parsed, &source);
+        if lines.is_empty() {
+            continue;
+        }
+        plan.push(json!({
+            "path": path,
+            "source": coverage::instrument(&source, targets.len(), &lines),
+        }));
+        targets.push((path, lines));
+    }
+    if targets.is_empty() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NO_SCRIPTS",
+                GqlErrorCategory::Validation,
+                format!(
+                    "No scripts with executable lines in {}",
+                    input.path.as_deref().unwrap_or("the project")
+                ),
+            ),
+        );
+    }
+
+    let hits_path = fs_path(coverage::HITS_PATH);
+    let prepared = [
+        (
+            coverage::PLAN_PATH,
+            serde_json::Value::Array(plan).to_string(),
+        ),
+        (
+            coverage::PRE_RUN_HOOK_PATH,
+            coverage::PRE_RUN_HOOK.to_string(),
+        ),
+        (
+            coverage::POST_RUN_HOOK_PATH,
+            coverage::POST_RUN_HOOK.to_string(),
+        ),
+    ]
+    .into_iter()
+    .try_for_each(|(res_path, content)| {
+        let path = fs_path(res_path);
+        if let Some(parent) = path.parent() {
+            ctx.fs.create_dir_all(parent)?;
+        }
+        ctx.fs.write(&path, content.as_bytes())
+    })
+    .and_then(|_| match ctx.fs.exists(&hits_path) {
+        true => ctx.fs.remove_file(&hits_path),
+        false => Ok(()),
+    });
+    if let Err(e) = prepared {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "WRITE_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Cannot prepare the coverage run: {}", e),
+            ),
+        );
+    }
+
+    let pre_run = format!("-gpre_run_script={}", coverage::PRE_RUN_HOOK_PATH);
+    let post_run = format!("-gpost_run_script={}", coverage::POST_RUN_HOOK_PATH);
+    let tests = run_gut(
+        ctx,
+        &RunTestsInput {
+            test_path: input.test_path.clone(),
+            retries: None,
+            framework: Some(TestFramework::Gut),
+            script_name: input.script_name.clone(),
+            test_name: input.test_name.clone(),
+        },
+        &[&pre_run, &post_run],
+    );
+    let _ = ctx.fs.remove_file(&fs_path(coverage::PLAN_PATH));
+    if let Some(error) = tests.error.clone() {
+        return failure(Some(tests), error);
+    }
+    let hits = match ctx
+        .fs
+        .read_to_string(&hits_path)
+        .map_err(|e| format!("GUT wrote no {} ({})", coverage::HITS_PATH, e))
+        .and_then(|json| coverage::parse_hits(&json))
+    {
+        Ok(hits) => hits,
+        Err(message) => {
+            return failure(
+                Some(tests),
+                GqlStructuredError::new("COVERAGE_FAILED", GqlErrorCategory::Godot, message)
+                    .with_suggestion(
+                        "GUT のバージョンが pre_run_script / post_run_script に対応しているか確認してください",
+                    ),
+            )
+        }
+    };
+
+    let empty = BTreeSet::new();
+    let mut scripts: Vec<ScriptCoverage> = targets
+        .into_iter()
+        .enumerate()
+        .map(|(id, (path, lines))| {
+            let hit = hits.get(&id).unwrap_or(&empty);
+            let uncovered: Vec<i32> = lines
+                .iter()
+                .filter(|line| !hit.contains(line))
+                .map(|line| *line as i32)
+                .collect();
+            let total = lines.len() as i32;
+            let covered = total - uncovered.len() as i32;
+            ScriptCoverage {
+                path,
+                covered_lines: covered,
+                total_lines: total,
+                percent: percent(covered, total),
+                uncovered_lines: uncovered,
+            }
+        })
+        .collect();
+    scripts.sort_by(|a, b| {
+        a.percent
+            .total_cmp(&b.percent)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    let covered_lines = scripts.iter().map(|s| s.covered_lines).sum();
+    let total_lines = scripts.iter().map(|s| s.total_lines).sum();
+
+    TestCoverageResult {
+        success: true,
+        covered_lines,
+        total_lines,
+        percent: percent(covered_lines, total_lines),
+        scripts,
+        tests: Some(tests),
+        error: None,
+    }
+}
+
+fn folder(path: &str) -> String {
+    format!("{}/", path.trim_end_matches('/'))
+}
+
+/// GUT test scripts outside the test folder are not measured either
+fn is_test_script(path: &str, extends: Option<&str>) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name.starts_with("test_")
+        || file_name.ends_with("_test.gd")
+        || extends
+            .is_some_and(|extends| extends == "GutTest" || extends.starts_with("res://addons/gut/"))
+}
+
+fn percent(covered: i32, total: i32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (covered as f64 * 1000.0 / total as f64).round() / 10.0
+}
@@ -0,0 +1,543 @@
+//! Custom Resource Resolver
+//!
+//! Writes instances of custom Resource scripts (`extends Resource` with a
+//! `class_name`, e.g. items or abilities) as .tres files. The script's
+//! exported vars, including inherited ones, are the valid fields; values are
+//! given as JSON and checked against the declared (or default-inferred) type
+//! before being written as Godot literals.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::godot::gdscript::ast::EnumDecl;
+use crate::godot::gdscript::GDScript;
+use crate::godot::tres::GodotResource;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::collect_project_files;
+use super::scene_resolver::convert_godot_resource_to_gql;
+use super::types::*;
+
+/// Value types written as literals; any other type names an object (resource) class
+const VECTOR_TYPES: &[(&str, usize, bool)] = &[
+    ("Vector2", 2, false),
+    ("Vector2i", 2, true),
+    ("Vector3", 3, false),
+    ("Vector3i", 3, true),
+    ("Vector4", 4, false),
+    ("Vector4i", 4, true),
+    ("Rect2", 4, false),
+    ("Rect2i", 4, true),
+    ("Quaternion", 4, false),
+    ("Color", 4, false),
+];
+
+const PACKED_ARRAY_TYPES: &[(&str, &str)] = &[
+    ("PackedStringArray", "String"),
+    ("PackedInt32Array", "int"),
+    ("PackedInt64Array", "int"),
+    ("PackedFloat32Array", "float"),
+    ("PackedFloat64Array", "float"),
+];
+
+/// Resolve createCustomResource mutation
+pub fn resolve_create_custom_resource(
+    ctx: &GqlContext,
+    input: &CreateCustomResourceInput,
+) -> CustomResourceResult {
+    let class = match ResourceClass::load(ctx, &input.script_path) {
+        Ok(class) => class,
+        Err(error) => return CustomResourceResult::err(vec![], *error),
+    };
+    let fields = class.fields();
+
+    let output_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.output_path);
+    if !input.output_path.ends_with(".tres") {
+        return CustomResourceResult::err(
+            fields,
+            GqlStructuredError::new(
+                "INVALID_OUTPUT_PATH",
+                GqlErrorCategory::Validation,
+                format!("Output path must be a .tres file: {}", input.output_path),
+            ),
+        );
+    }
+    if ctx.fs.exists(&output_path) {
+        return CustomResourceResult::err(
+            fields,
+            GqlStructuredError::new(
+                "RESOURCE_EXISTS",
+                GqlErrorCategory::Validation,
+                format!("Resource already exists: {}", input.output_path),
+            )
+            .with_suggestion("別の outputPath を指定するか、既存ファイルを削除してください"),
+        );
+    }
+
+    let Some(values) = input.values.0.as_object() else {
+        return CustomResourceResult::err(
+            fields,
+            GqlStructuredError::new(
+                "INVALID_VALUES",
+                GqlErrorCategory::Validation,
+                "values must be an object of field names to values",
+            ),
+        );
+    };
+
+    let mut resource = GodotResource::new("Resource");
+    resource.script_class = class.class_name.clone();
+    resource.add_ext_resource("1", "Script", &input.script_path);
+    resource.set_property("script", "ExtResource(\"1\")");
+    let mut writer = LiteralWriter {
+        ctx,
+        class: &class,
+        resource: &mut resource,
+    };
+    let mut invalid: HashMap<String, String> = HashMap::new();
+    for (name, value) in values {
+        let Some(field) = fields.iter().find(|f| &f.name == name) else {
+            let valid: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+            invalid.insert(
+                name.clone(),
+                format!("Unknown field (valid: {})", valid.join(", ")),
+            );
+            continue;
+        };
+        match writer.literal(value, &field.field_type) {
+            Ok(literal) => writer.resource.set_property(name, &literal),
+            Err(message) => {
+                invalid.insert(name.clone(), message);
+            }
+        }
+    }
+    if !invalid.is_empty() {
+        let mut names: Vec<&String> = invalid.keys().collect();
+        names.sort();
+        let message = names
+            .iter()
+            .map(|name| format!("{}: {}", name, invalid[*name]))
+            .collect::<Vec<_>>()
+            .join("; ");
+        return CustomResourceResult::err(
+            fields,
+            GqlStructuredError::new("INVALID_VALUES", GqlErrorCategory::Validation, message)
+                .with_suggestion("fields に列挙された名前と型に合わせて values を指定してください")
+                .with_context(invalid),
+        );
+    }
+
+    if let Some(parent) = output_path.parent() {
+        if let Err(e) = ctx.fs.create_dir_all(parent) {
+            return CustomResourceResult::err(
+                fields,
+                GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to create directory: {}", e),
+                ),
+            );
+        }
+    }
+    if let Err(e) = audit::write_file(
+        ctx,
+        "createCustomResource",
+        &output_path,
+        resource.to_tres(),
+    ) {
+        return CustomResourceResult::err(
+            fields,
+            GqlStructuredError::new(
+                "FILE_WRITE_ERROR",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to write resource: {}", e),
+            ),
+        );
+    }
+    telemetry::record_files_written(1);
+
+    CustomResourceResult {
+        success: true,
+        path: Some(input.output_path.clone()),
+        resource: Some(convert_godot_resource_to_gql(&resource, &input.output_path)),
+        fields,
+        error: None,
+    }
+}
+
+impl CustomResourceResult {
+    fn err(fields: Vec<CustomResourceField>, error: GqlStructuredError) -> Self {
+        Self {
+            success: false,
+            path: None,
+            resource: None,
+            fields,
+            error: Some(error),
+        }
+    }
+}
+
+/// A Resource script and the scripts it extends, most derived first
+pub(super) struct ResourceClass {
+    pub(super) class_name: Option<String>,
+    chain: Vec<Arc<GDScript>>,
+    /// class_name -> script path of every project script, for typed references
+    classes: HashMap<String, String>,
+}
+
+impl ResourceClass {
+    pub(super) fn load(
+        ctx: &GqlContext,
+        script_path: &str,
+    ) -> Result<Self, Box<GqlStructuredError>> {
+        let script_at = |path: &str| {
+            ctx.index
+                .script(&path_utils::to_fs_path_unchecked(&ctx.project_path, path))
+        };
+        let Some(script) = script_at(script_path) else {
+            return Err(Box::new(GqlStructuredError::new(
+                "SCRIPT_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Script not found: {}", script_path),
+            )));
+        };
+
+        let (_, scripts) = collect_project_files(ctx);
+        let classes: HashMap<String, String> = scripts
+            .into_iter()
+            .filter(|s| s.path.ends_with(".gd"))
+            .filter_map(|s| Some((script_at(&s.path)?.class_name.clone()?, s.path)))
+            .collect();
+
+        let class_name = script.class_name.clone();
+        let mut chain = vec![script];
+        loop {
+            let extends = chain.last().and_then(|s| s.extends.clone());
+            let base = match extends.as_deref() {
+                Some(path) if path.starts_with("res://") => script_at(path),
+                Some(name) => classes.get(name).and_then(|path| script_at(path)),
+                None => None,
+            };
+            match base {
+                Some(base) if chain.len() < 32 => chain.push(base),
+                _ if extends.as_deref() == Some("Resource") => break,
+                _ => {
+                    return Err(Box::new(
+                        GqlStructuredError::new(
+                            "NOT_A_RESOURCE",
+                            GqlErrorCategory::Validation,
+                            format!(
+                                "{} does not extend Resource (extends {})",
+                                script_path,
+                                extends.as_deref().unwrap_or("RefCounted")
+                            ),
+                        )
+                        .with_suggestion("extends Resource のスクリプトを指定してください"),
+                    ))
+                }
+            }
+        }
+
+        Ok(Self {
+            class_name,
+            chain,
+            classes,
+        })
+    }
+
+    /// Exported vars, inherited ones first
+    pub(super) fn fields(&self) -> Vec<CustomResourceField> {
+        self.chain
+            .iter()
+            .rev()
+            .flat_map(|script| &script.exports)
+            .map(|export| CustomResourceField {
+                name: export.name.clone(),
+                field_type: export
+                    .var_type
+                    .clone()
+                    .unwrap_or_else(|| inferred_type(export.default_value.as_deref())),
+                default_value: export.default_value.clone(),
+            })
+            .collect()
+    }
+
+    /// Enum declared in the class chain under `type_name` (`Rarity` or `Item.Rarity`)
+    pub(super) fn find_enum(&self, type_name: &str) -> Option<&EnumDecl> {
+        let name = type_name.rsplit('.').next()?;
+        self.chain
+            .iter()
+            .flat_map(|script| script.ast.enums())
+            .find(|e| e.name.as_deref() == Some(name))
+    }
+}
+
+/// Type of an untyped export from its default value
+fn inferred_type(default: Option<&str>) -> String {
+    let Some(default) = default.map(str::trim) else {
+        return "Variant".to_string();
+    };
+    let type_name = if default == "true" || default == "false" {
+        "bool"
+    } else if default.parse::<i64>().is_ok() {
+        "int"
+    } else if default.parse::<f64>().is_ok() {
+        "float"
+    } else if default.starts_with('"') {
+        "String"
+    } else if default.starts_with('[') {
+        "Array"
+    } else if default.starts_with('{') {
+        "Dictionary"
+    } else if let Some((name, _)) = default.split_once('(') {
+        return name.trim().to_string();
+    } else {
+        "Variant"
+    };
+    type_name.to_string()
+}
+
+/// Turns JSON values into Godot literals, adding ext_resources for references
+pub(super) struct LiteralWriter<'a> {
+    pub(super) ctx: &'a GqlContext,
+    pub(super) class: &'a ResourceClass,
+    pub(super) resource: &'a mut GodotResource,
+}
+
+impl LiteralWriter<'_> {
+    pub(super) fn literal(&mut self, value: &Value, type_name: &str) -> Result<String, String> {
+        let mismatch = || format!("expected {}, got {}", type_name, value);
+        match type_name {
+            "Variant" => self.variant(value),
+            "bool" => match value {
+                Value::Bool(b) => Ok(b.to_string()),
+                _ => Err(mismatch()),
+            },
+            "int" => value.as_i64().map(|n| n.to_string()).ok_or_else(mismatch),
+            "float" => value.as_f64().map(float_literal).ok_or_else(mismatch),
+            "String" => value.as_str().map(string_literal).ok_or_else(mismatch),
+            "StringName" => value
+                .as_str()
+                .map(|s| format!("&{}", string_literal(s)))
+                .ok_or_else(mismatch),
+            "NodePath" => value
+                .as_str()
+                .map(|s| format!("NodePath({})", string_literal(s)))
+                .ok_or_else(mismatch),
+            "Dictionary" => match value {
+                Value::Object(map) => {
+                    let entries = map
+                        .iter()
+                        .map(|(k, v)| Ok(format!("{}: {}", string_literal(k), self.variant(v)?)))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    Ok(format!("{{{}}}", entries.join(", ")))
+                }
+                _ => Err(mismatch()),
+            },
+            "Array" => self.array(value, "Variant").ok_or_else(mismatch)?,
+            _ => {
+                if let Some(element) = type_name
+                    .strip_prefix("Array[")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                {
+                    let items = self.array(value, element).ok_or_else(mismatch)??;
+                    let element_type = match self.class.classes.get(element) {
+                        Some(script_path) => {
+                            let id = self.ext_resource("Script", script_path);
+                            format!("ExtResource(\"{}\")", id)
+                        }
+                        None => element.to_string(),
+                    };
+                    return Ok(format!("Array[{}]({})", element_type, items));
+                }
+                if let Some((_, element)) = PACKED_ARRAY_TYPES.iter().find(|(t, _)| *t == type_name)
+                {
+                    let items = value
+                        .as_array()
+                        .ok_or_else(mismatch)?
+                        .iter()
+                        .map(|item| self.literal(item, element))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    return Ok(format!("{}({})", type_name, items.join(", ")));
+                }
+                if let Some(&(_, size, integer)) =
+                    VECTOR_TYPES.iter().find(|(t, _, _)| *t == type_name)
+                {
+                    return vector_literal(value, type_name, size, integer).ok_or_else(mismatch);
+                }
+                if let Some(decl) = self.class.find_enum(type_name) {
+                    return enum_literal(value, decl).ok_or_else(|| {
+                        let names: Vec<&str> =
+                            decl.values.iter().map(|v| v.name.as_str()).collect();
+                        format!(
+                            "expected one of {} or an int, got {}",
+                            names.join(", "),
+                            value
+                        )
+                    });
+                }
+                self.reference(value, type_name)
+            }
+        }
+    }
+
+    /// `[...]` with each item checked against `element`
+    fn array(&mut self, value: &Value, element: &str) -> Option<Result<String, String>> {
+        let items = value.as_array()?;
+        Some(
+            items
+                .iter()
+                .map(|item| self.literal(item, element))
+                .collect::<Result<Vec<_>, String>>()
+                .map(|items| format!("[{}]", items.join(", "))),
+        )
+    }
+
+    /// Untyped value: the JSON type decides
+    fn variant(&mut self, value: &Value) -> Result<String, String> {
+        match value {
+            Value::Null => Ok("null".to_string()),
+            Value::Bool(_) => self.literal(value, "bool"),
+            Value::Number(n) if n.is_i64() => self.literal(value, "int"),
+            Value::Number(_) => self.literal(value, "float"),
+            Value::String(_) => self.literal(value, "String"),
+            Value::Array(_) => self.literal(value, "Array"),
+            Value::Object(_) => self.literal(value, "Dictionary"),
+        }
+    }
+
+    /// Object field: a `res://` path to an existing file, or null
+    fn reference(&mut self, value: &Value, type_name: &str) -> Result<String, String> {
+        let path = match value {
+            Value::Null => return Ok("null".to_string()),
+            Value::String(path) if path.starts_with("res://") => path,
+            _ => {
+                return Err(format!(
+                    "expected a res:// path to a {}, got {}",
+                    type_name, value
+                ))
+            }
+        };
+        let file_path = path_utils::to_fs_path_unchecked(&self.ctx.project_path, path);
+        if !self.ctx.fs.exists(&file_path) {
+            return Err(format!("{} not found", path));
+        }
+        let resource_type = match path.rsplit('.').next() {
+            Some("tscn" | "scn") => "PackedScene",
+            Some("gd") => "Script",
+            Some("tres" | "res") => "Resource",
+            _ => type_name,
+        };
+        let id = self.ext_resource(resource_type, path);
+        Ok(format!("ExtResource(\"{}\")", id))
+    }
+
+    /// Id of the ext_resource for `path`, added when missing
+    fn ext_resource(&mut self, resource_type: &str, path: &str) -> String {
+        if let Some(existing) = self.resource.ext_resources.iter().find(|r| r.path == path) {
+            return existing.id.clone();
+        }
+        let ids = self.resource.ext_resources.len() + 1..;
+        let id = ids
+            .map(|n| n.to_string())
+            .find(|id| self.resource.ext_resources.iter().all(|r| &r.id != id))
+            .unwrap_or_default();
+        self.resource.add_ext_resource(&id, resource_type, path);
+        id
+    }
+}
+
+fn string_literal(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Floats keep a decimal point, as Godot writes them
+fn float_literal(f: f64) -> String {
+    if f.fract() == 0.0 && f.abs() < 1e15 {
+        format!("{:.1}", f)
+    } else {
+        f.to_string()
+    }
+}
+
+/// `Type(a, b, ...)` from `[a, b]`, `{"x": a, "y": b}` (or r/g/b/a, x/y/w/h),
+/// a literal already written as `Type(...)`, or `#rrggbb[aa]` for colors
+fn vector_literal(value: &Value, type_name: &str, size: usize, integer: bool) -> Option<String> {
+    let components: Vec<f64> = match value {
+        Value::Array(items) => items.iter().map(Value::as_f64).collect::<Option<_>>()?,
+        Value::Object(map) => {
+            let keys: &[&str] = match type_name {
+                "Color" => &["r", "g", "b", "a"],
+                "Rect2" | "Rect2i" => &["x", "y", "w", "h"],
+                _ => &["x", "y", "z", "w"],
+            };
+            keys.iter()
+                .take(size)
+                .map_while(|key| map.get(*key).and_then(Value::as_f64))
+                .collect()
+        }
+        Value::String(s) if s.starts_with(&format!("{}(", type_name)) && s.ends_with(')') => {
+            return Some(s.clone());
+        }
+        Value::String(s) if type_name == "Color" => hex_color(s)?,
+        _ => return None,
+    };
+    let mut components = components;
+    // Colors may leave alpha out
+    if type_name == "Color" && components.len() == 3 {
+        components.push(1.0);
+    }
+    if components.len() != size || (integer && components.iter().any(|c| c.fract() != 0.0)) {
+        return None;
+    }
+    let components: Vec<String> = components.iter().map(|c| c.to_string()).collect();
+    Some(format!("{}({})", type_name, components.join(", ")))
+}
+
+fn hex_color(s: &str) -> Option<Vec<f64>> {
+    let hex = s.strip_prefix('#')?;
+    if !matches!(hex.len(), 6 | 8) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            let byte = u8::from_str_radix(hex.get(i..i + 2)?, 16).ok()?;
+            // Four decimals keep all 256 byte values apart
+            Some((byte as f64 / 255.0 * 1e4).round() / 1e4)
+        })
+        .collect()
+}
+
+/// Enum member value from its name or an int that is one of the values
+fn enum_literal(value: &Value, decl: &EnumDecl) -> Option<String> {
+    let members = enum_members(decl);
+    let found = match value {
+        Value::String(name) => members.iter().find(|(n, _)| n == name),
+        Value::Number(n) => members.iter().find(|(_, v)| Some(*v) == n.as_i64()),
+        _ => None,
+    };
+    found.map(|(_, v)| v.to_string())
+}
+
+/// Member names and values, counting up from the last explicit value
+pub(super) fn enum_members(decl: &EnumDecl) -> Vec<(&str, i64)> {
+    let mut next = 0;
+    decl.values
+        .iter()
+        .map(|member| {
+            let value = member
+                .value_source
+                .as_deref()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(next);
+            next = value + 1;
+            (member.name.as_str(), value)
+        })
+        .collect()
+}
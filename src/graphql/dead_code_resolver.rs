@@ -0,0 +1,367 @@
+//! Dead Code Resolver
+//!
+//! Finds GDScript functions nothing calls and signals nothing emits or
+//! connects. Every script (C# included), scene and resource of the project is
+//! scanned for uses of each name: identifiers outside the function's own
+//! body, `.emit()` / `emit_signal("...")`, `.connect()` / `connect("...")` /
+//! `await`, string literals (`call("name")`, `has_method`, animation method
+//! tracks) and scene `[connection]`s. Engine callbacks (`_ready`, `_process`,
+//! ...) are never reported, and addons are left out as in the other project
+//! scans. Names are matched without types, so a call on an unrelated object
+//! keeps a function alive: results err on the side of missing dead code
+//! rather than flagging live code.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::godot::csharp;
+use crate::godot::gdscript::ast::ClassDecl;
+use crate::godot::gdscript::lexer::{tokenize, StringKind, Token, TokenKind};
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::project_index::parallel_map;
+use super::project_resolver::collect_project_files;
+use super::types::*;
+
+/// Virtual methods the engine calls; never reported
+const ENGINE_CALLBACKS: &[&str] = &[
+    "_init",
+    "_static_init",
+    "_ready",
+    "_enter_tree",
+    "_exit_tree",
+    "_process",
+    "_physics_process",
+    "_input",
+    "_shortcut_input",
+    "_unhandled_input",
+    "_unhandled_key_input",
+    "_gui_input",
+    "_notification",
+    "_draw",
+    "_to_string",
+    "_get",
+    "_set",
+    "_get_property_list",
+    "_validate_property",
+    "_property_can_revert",
+    "_property_get_revert",
+    "_get_configuration_warnings",
+    "_integrate_forces",
+    "_has_point",
+    "_can_drop_data",
+    "_drop_data",
+    "_get_drag_data",
+    "_make_custom_tooltip",
+    "_structured_text_parser",
+    "_get_minimum_size",
+    "_run",
+    "_handles",
+    "_edit",
+    "_make_visible",
+    "_has_main_screen",
+    "_get_plugin_name",
+    "_get_plugin_icon",
+    "_forward_canvas_gui_input",
+    "_forward_3d_gui_input",
+    "_enable_plugin",
+    "_disable_plugin",
+    "_save_external_data",
+    "_apply_changes",
+    "_build",
+];
+
+/// Where a name is used across the project
+#[derive(Debug, Default)]
+struct Usage {
+    /// Identifier occurrences (res path, line), declarations excluded
+    uses: Vec<(String, usize)>,
+    /// `name.emit(` or `emit_signal("name"`
+    emits: usize,
+    /// `name.connect(`, `connect("name"`, `await name` or a scene connection
+    connects: usize,
+    /// Other identifier uses (called, passed around)
+    other: usize,
+    /// Named in a string literal (dynamic calls, animation method tracks) or as
+    /// a scene connection's method
+    named: usize,
+}
+
+/// Resolve deadCode query
+pub fn resolve_dead_code(
+    ctx: &GqlContext,
+    scope: Option<&str>,
+    min_confidence: DeadCodeConfidence,
+) -> DeadCodeReport {
+    let (scenes, scripts) = collect_project_files(ctx);
+    let mut usage: HashMap<String, Usage> = HashMap::new();
+
+    let scanned = parallel_map(&scripts, |script| {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &script.path);
+        let content = ctx.index.read_to_string(&fs_path)?;
+        let tokens = if script.path.ends_with(".cs") {
+            csharp_tokens(&content)
+        } else {
+            tokenize(&content).0
+        };
+        Some((script.path.clone(), tokens))
+    });
+    for (path, tokens) in scanned.iter().flatten() {
+        record_script_usage(&mut usage, path, tokens);
+    }
+    for scene in &scenes {
+        record_scene_usage(ctx, &mut usage, &scene.path);
+    }
+    for path in ctx.index.files(&ctx.project_path) {
+        if path.extension().is_some_and(|e| e == "tres") {
+            if let Some(content) = ctx.index.read_to_string(&path) {
+                record_string_names(&mut usage, &content);
+            }
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut scripts_scanned = 0;
+    for script in &scripts {
+        let in_scope = scope.is_none_or(|scope| script.path.starts_with(scope));
+        if !script.path.ends_with(".gd") || !in_scope {
+            continue;
+        }
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &script.path);
+        let Some(parsed) = ctx.index.script(&fs_path) else {
+            continue;
+        };
+        scripts_scanned += 1;
+        check_class(&parsed.ast, &script.path, &usage, &mut items);
+    }
+
+    items.retain(|item| item.confidence >= min_confidence);
+    items.sort_by(|a, b| {
+        b.confidence
+            .cmp(&a.confidence)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    DeadCodeReport {
+        items,
+        scripts_scanned,
+    }
+}
+
+/// Report unused functions and signals of a class and its inner classes
+fn check_class(
+    class: &ClassDecl,
+    file: &str,
+    usage: &HashMap<String, Usage>,
+    items: &mut Vec<DeadCodeItem>,
+) {
+    let empty = Usage::default();
+    for function in class.functions() {
+        if ENGINE_CALLBACKS.contains(&function.name.as_str()) {
+            continue;
+        }
+        let used = usage.get(&function.name).unwrap_or(&empty);
+        // Recursive calls don't keep a function alive
+        let outside_body = used
+            .uses
+            .iter()
+            .any(|(path, line)| path != file || *line < function.line || *line > function.end_line);
+        if outside_body || used.named > 0 {
+            continue;
+        }
+        // Other underscore names may override a virtual method not listed above
+        let (confidence, reason) =
+            if function.name.starts_with('_') && !function.name.starts_with("_on_") {
+                (
+                    DeadCodeConfidence::Medium,
+                    "Never called; may override an engine or addon virtual method",
+                )
+            } else {
+                (
+                    DeadCodeConfidence::High,
+                    "Never called, connected or referenced by name",
+                )
+            };
+        items.push(item(
+            DeadCodeKind::UnusedFunction,
+            &function.name,
+            file,
+            function.line,
+            confidence,
+            reason,
+        ));
+    }
+
+    for signal in class.signals() {
+        let used = usage.get(&signal.name).unwrap_or(&empty);
+        // Passed around or named in a string: may be emitted or connected indirectly
+        let other_uses = used.other + used.named > 0;
+        let (kind, confidence, reason) = match (used.emits > 0, used.connects > 0) {
+            (true, true) => continue,
+            (false, false) if other_uses => (
+                DeadCodeKind::UnusedSignal,
+                DeadCodeConfidence::Low,
+                "Never emitted or connected directly, but referenced by name",
+            ),
+            (false, false) => (
+                DeadCodeKind::UnusedSignal,
+                DeadCodeConfidence::High,
+                "Never emitted or connected",
+            ),
+            (false, true) => (
+                DeadCodeKind::SignalNeverEmitted,
+                if other_uses {
+                    DeadCodeConfidence::Low
+                } else {
+                    DeadCodeConfidence::High
+                },
+                "Connected, but never emitted: its handlers never run",
+            ),
+            (true, false) => (
+                DeadCodeKind::SignalNeverConnected,
+                if other_uses {
+                    DeadCodeConfidence::Low
+                } else {
+                    DeadCodeConfidence::Medium
+                },
+                "Emitted, but nothing in the project connects to it",
+            ),
+        };
+        items.push(item(
+            kind,
+            &signal.name,
+            file,
+            signal.line,
+            confidence,
+            reason,
+        ));
+    }
+
+    for inner in class.classes() {
+        check_class(inner, file, usage, items);
+    }
+}
+
+fn item(
+    kind: DeadCodeKind,
+    name: &str,
+    file: &str,
+    line: usize,
+    confidence: DeadCodeConfidence,
+    reason: &str,
+) -> DeadCodeItem {
+    DeadCodeItem {
+        kind,
+        name: name.to_string(),
+        file: file.to_string(),
+        line: line as i32,
+        confidence,
+        reason: reason.to_string(),
+    }
+}
+
+/// Record the names a script uses
+fn record_script_usage(usage: &mut HashMap<String, Usage>, path: &str, tokens: &[Token]) {
+    for (i, token) in tokens.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| &tokens[i].kind);
+        match &token.kind {
+            TokenKind::Identifier(name) => {
+                // Declarations are not uses
+                if matches!(previous, Some(TokenKind::Keyword("func" | "signal"))) {
+                    continue;
+                }
+                let entry = usage.entry(name.clone()).or_default();
+                entry.uses.push((path.to_string(), token.line));
+                let method = match tokens.get(i + 1) {
+                    Some(dot) if dot.kind == TokenKind::Punct(".") => {
+                        tokens.get(i + 2).and_then(Token::identifier)
+                    }
+                    _ => None,
+                };
+                match method {
+                    Some("emit") => entry.emits += 1,
+                    Some("connect") => entry.connects += 1,
+                    None if is_awaited(tokens, i) => entry.connects += 1,
+                    _ => entry.other += 1,
+                }
+            }
+            TokenKind::String { value, .. } => {
+                let entry = usage.entry(value.clone()).or_default();
+                // Godot 3 style: emit_signal("name"), connect("name", ...)
+                let call = i
+                    .checked_sub(2)
+                    .filter(|_| previous == Some(&TokenKind::Punct("(")))
+                    .and_then(|i| tokens[i].identifier());
+                match call {
+                    Some("emit_signal" | "EmitSignal") => entry.emits += 1,
+                    Some("connect" | "Connect") => entry.connects += 1,
+                    _ => entry.named += 1,
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether the identifier at `i` ends an `await a.b.name` / `await $Node.name`
+/// expression
+fn is_awaited(tokens: &[Token], mut i: usize) -> bool {
+    while i > 0 {
+        match &tokens[i - 1].kind {
+            TokenKind::Keyword("await") => return true,
+            TokenKind::Punct("$" | "%") => i -= 1,
+            TokenKind::Punct(".") if i >= 2 && tokens[i - 2].identifier().is_some() => i -= 2,
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Record signal connections and method names of a scene
+fn record_scene_usage(ctx: &GqlContext, usage: &mut HashMap<String, Usage>, res_path: &str) {
+    let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    if let Ok(scene) = ctx.index.scene(&fs_path) {
+        for connection in &scene.connections {
+            usage.entry(connection.signal.clone()).or_default().connects += 1;
+            usage.entry(connection.method.clone()).or_default().named += 1;
+        }
+    }
+    if let Some(content) = ctx.index.read_to_string(&fs_path) {
+        record_string_names(usage, &content);
+    }
+}
+
+/// `&"name"` values in scene and resource text
+fn record_string_names(usage: &mut HashMap<String, Usage>, content: &str) {
+    static STRING_NAME: OnceLock<Regex> = OnceLock::new();
+    let pattern = STRING_NAME.get_or_init(|| Regex::new(r#"&"([A-Za-z_]\w*)""#).unwrap());
+    for capture in pattern.captures_iter(content) {
+        usage.entry(capture[1].to_string()).or_default().named += 1;
+    }
+}
+
+/// C# tokens in GDScript form, so the same scan finds `EmitSignal("name")`,
+/// `Connect("name", ...)` and calls into GDScript
+fn csharp_tokens(content: &str) -> Vec<Token> {
+    csharp::tokenize(content)
+        .into_iter()
+        .map(|token| Token {
+            kind: match token.kind {
+                csharp::TokenKind::Identifier(name) => TokenKind::Identifier(name),
+                csharp::TokenKind::String(value) => TokenKind::String {
+                    value,
+                    kind: StringKind::Plain,
+                },
+                csharp::TokenKind::Number(number) => TokenKind::Number(number),
+                csharp::TokenKind::Punct(punct) => TokenKind::Punct(punct),
+            },
+            start: token.start,
+            end: token.end,
+            line: token.line,
+            column: token.column,
+        })
+        .collect()
+}
@@ -0,0 +1,176 @@
+//! Decision Resolver
+//!
+//! A log of design decisions attached to project files and folders ("collision
+//! layer 3 is reserved for projectiles"), kept in
+//! `res://.godot-mcp/decisions.json` so later sessions can look them up
+//! instead of re-deriving or contradicting them. A decision on a folder (or on
+//! `res://`, the whole project) applies to everything inside it. Recording a
+//! decision that supersedes an earlier one keeps both, with the earlier one
+//! hidden from `decisions` by default.
+
+use crate::path_utils::{self, ResPath};
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+use super::types::*;
+
+/// Recorded decisions, oldest first
+pub const DECISIONS_PATH: &str = "res://.godot-mcp/decisions.json";
+
+/// Resolve decisions query
+pub fn resolve_decisions(
+    ctx: &GqlContext,
+    path: Option<&str>,
+    include_superseded: bool,
+) -> Vec<Decision> {
+    let path = path.map(normalize);
+    read_decisions(ctx)
+        .into_iter()
+        .filter(|d| include_superseded || d.superseded_by.is_none())
+        .filter(|d| path.as_deref().is_none_or(|path| applies_to(&d.path, path)))
+        .collect()
+}
+
+/// Resolve recordDecision mutation
+pub fn resolve_record_decision(
+    ctx: &GqlContext,
+    path: &str,
+    decision: &str,
+    rationale: &str,
+    supersedes: Option<&str>,
+) -> RecordDecisionResult {
+    match record(ctx, path, decision, rationale, supersedes) {
+        Ok(decision) => RecordDecisionResult {
+            success: true,
+            decision: Some(decision),
+            error: None,
+        },
+        Err(error) => RecordDecisionResult {
+            success: false,
+            decision: None,
+            error: Some(*error),
+        },
+    }
+}
+
+fn record(
+    ctx: &GqlContext,
+    path: &str,
+    decision: &str,
+    rationale: &str,
+    supersedes: Option<&str>,
+) -> Result<Decision, Box<GqlStructuredError>> {
+    if decision.trim().is_empty() {
+        return Err(invalid("Decision cannot be empty".to_string()));
+    }
+    let res_path =
+        ResPath::new(relative(path)).map_err(|e| invalid(format!("Invalid path: {}", e)))?;
+    if !res_path.relative().is_empty() && !ctx.fs.exists(&res_path.to_fs_path(&ctx.project_path)) {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "FILE_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("File or folder not found: {}", path),
+            )
+            .with_suggestion("プロジェクト全体の決定は path に \"res://\" を指定してください"),
+        ));
+    }
+
+    let mut decisions = read_decisions(ctx);
+    let id = next_id(&decisions);
+    if let Some(previous) = supersedes {
+        let Some(earlier) = decisions.iter_mut().find(|d| d.id == previous) else {
+            return Err(Box::new(
+                GqlStructuredError::new(
+                    "DECISION_NOT_FOUND",
+                    GqlErrorCategory::Validation,
+                    format!("Decision '{}' not found", previous),
+                )
+                .with_suggestion("decisions(includeSuperseded: true) で id を確認してください"),
+            ));
+        };
+        earlier.superseded_by = Some(id.clone());
+    }
+
+    let decision = Decision {
+        id,
+        path: res_path.as_res_path(),
+        decision: decision.trim().to_string(),
+        rationale: rationale.trim().to_string(),
+        session_id: ctx.session_id.clone(),
+        recorded_at: now_millis().to_string(),
+        supersedes: supersedes.map(str::to_string),
+        superseded_by: None,
+    };
+    decisions.push(decision.clone());
+    write_decisions(ctx, &decisions).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "DECISION_WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to write {}: {}", DECISIONS_PATH, e),
+        ))
+    })?;
+    Ok(decision)
+}
+
+/// `res://` path without a trailing slash ("res://" stays as is)
+fn normalize(path: &str) -> String {
+    ResPath::new_unchecked(relative(path)).as_res_path()
+}
+
+fn relative(path: &str) -> &str {
+    path.strip_prefix("res://")
+        .unwrap_or(path)
+        .trim_end_matches('/')
+}
+
+/// Whether a decision recorded on `scope` applies to `path`
+fn applies_to(scope: &str, path: &str) -> bool {
+    scope == "res://"
+        || scope == path
+        || path
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// `dec-<epoch millis>`, made unique within the log
+fn next_id(decisions: &[Decision]) -> String {
+    let base = format!("dec-{}", now_millis());
+    let mut id = base.clone();
+    let mut n = 1;
+    while decisions.iter().any(|d| d.id == id) {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    id
+}
+
+fn read_decisions(ctx: &GqlContext) -> Vec<Decision> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, DECISIONS_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_decisions(ctx: &GqlContext, decisions: &[Decision]) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, DECISIONS_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(decisions)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
+
+fn invalid(message: String) -> Box<GqlStructuredError> {
+    Box::new(GqlStructuredError::new(
+        "INVALID_INPUT",
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
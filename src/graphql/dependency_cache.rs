@@ -0,0 +1,145 @@
+//! Dependency Graph Cache
+//!
+//! Keeps each project's dependency graph in memory and updates it per file:
+//! changes reported by the project file watcher (`crate::watch`) and files the
+//! server writes itself (via `audit`) are re-parsed on the next graph request,
+//! and only their nodes and edges are replaced. The version changes whenever
+//! the graph does, so agents can tell that a cached copy is stale without
+//! fetching the graph again.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+use crate::path_utils;
+use crate::project_registry::ProjectRegistry;
+use crate::watch::{self, FileEvent};
+
+use super::context::GqlContext;
+use super::dependency_resolver::{
+    assemble_graph, file_dependencies, is_graph_path, scan_dependencies, FileDependencies,
+};
+use super::types::{GraphEdge, GraphNode};
+
+struct GraphCache {
+    files: BTreeMap<String, FileDependencies>,
+    /// Files to re-parse before the graph is served again
+    changed: BTreeSet<String>,
+    /// Watcher events; None when no watcher could be started, in which case
+    /// every request rescans the project
+    events: Option<broadcast::Receiver<FileEvent>>,
+    /// When the cache was created, so versions differ across server restarts
+    epoch: u128,
+    revision: u64,
+}
+
+impl GraphCache {
+    fn new(ctx: &GqlContext) -> Self {
+        // Subscribe before scanning so changes made during the scan are seen
+        let events = watch::subscribe(&ctx.project_path)
+            .inspect_err(|e| tracing::warn!("Dependency graph falls back to full rescans: {}", e))
+            .ok();
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        Self {
+            files: scan_dependencies(ctx).into_iter().collect(),
+            changed: BTreeSet::new(),
+            events,
+            epoch,
+            revision: 1,
+        }
+    }
+
+    fn version(&self) -> String {
+        format!("{}.{}", self.epoch, self.revision)
+    }
+
+    /// Apply pending changes; returns whether the graph changed
+    fn refresh(&mut self, ctx: &GqlContext) -> bool {
+        let mut rescan = self.events.is_none();
+        if let Some(events) = &mut self.events {
+            loop {
+                match events.try_recv() {
+                    Ok(event) => {
+                        self.changed.insert(event.path);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Lagged(_)) => rescan = true,
+                    Err(TryRecvError::Closed) => {
+                        self.events = None;
+                        rescan = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if rescan {
+            self.changed.clear();
+            let files: BTreeMap<_, _> = scan_dependencies(ctx).into_iter().collect();
+            if files == self.files {
+                return false;
+            }
+            self.files = files;
+            return true;
+        }
+
+        let mut updated = false;
+        for res_path in std::mem::take(&mut self.changed) {
+            if !is_graph_path(&res_path) {
+                continue;
+            }
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &res_path);
+            let deps = file_dependencies(&ctx.index, &fs_path);
+            if self.files.get(&res_path) == deps.as_ref() {
+                continue;
+            }
+            match deps {
+                Some(deps) => self.files.insert(res_path, deps),
+                None => self.files.remove(&res_path),
+            };
+            updated = true;
+        }
+        updated
+    }
+}
+
+static CACHES: ProjectRegistry<GraphCache> = ProjectRegistry::new();
+
+/// Current graph and its version, updating the cache first
+pub(super) fn graph(ctx: &GqlContext) -> (Vec<GraphNode>, Vec<GraphEdge>, String) {
+    // In-memory projects have no watcher and may share a path with each
+    // other, so they are scanned every time and versioned by content
+    if ctx.fs.is_virtual() {
+        let files: BTreeMap<_, _> = scan_dependencies(ctx).into_iter().collect();
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", files).hash(&mut hasher);
+        let (nodes, edges) = assemble_graph(files.iter());
+        return (nodes, edges, format!("0.{:x}", hasher.finish()));
+    }
+
+    let mut caches = CACHES.lock();
+
+    let cache = caches
+        .entry(ctx.project_path.clone())
+        .or_insert_with(|| GraphCache::new(ctx));
+    if cache.refresh(ctx) {
+        cache.revision += 1;
+    }
+    let (nodes, edges) = assemble_graph(cache.files.iter());
+    (nodes, edges, cache.version())
+}
+
+/// Queue a file the server wrote, moved or deleted for re-parsing, so the next
+/// graph reflects it without waiting for the watcher
+pub(super) fn mark_changed(project_path: &Path, res_path: &str) {
+    let mut caches = CACHES.lock();
+    if let Some(cache) = caches.get_mut(project_path) {
+        cache.changed.insert(res_path.to_string());
+    }
+}
@@ -2,17 +2,21 @@
 //!
 //! Analyzes dependencies between scenes, scripts, and resources.
 
-use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::path::Path;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 
 use regex::Regex;
 
+use crate::godot::tres::GodotResource;
 use crate::godot::tscn::GodotScene;
 use crate::path_utils;
 
 use super::context::GqlContext;
+use super::dependency_cache;
+use super::project_index::{parallel_map, ProjectIndex, EXCLUDED_DIRS};
+use super::project_resolver::{is_script_path, parse_main_scene};
 use super::resolver::{resolve_scene, resolve_script};
+use super::tags_resolver::TagScope;
 use super::types::*;
 
 // ======================
@@ -28,81 +32,171 @@ pub struct DependencyEdge {
 }
 
 /// Build the complete dependency graph for the project
+///
+/// Served from a per-project cache that only re-parses files changed since the
+/// last call (see `dependency_cache`).
 pub fn build_dependency_graph(ctx: &GqlContext) -> (Vec<GraphNode>, Vec<GraphEdge>) {
-    let mut nodes: HashMap<String, GraphNode> = HashMap::new();
-    let mut edges: Vec<GraphEdge> = Vec::new();
+    let (nodes, edges, _) = dependency_cache::graph(ctx);
+    (nodes, edges)
+}
 
-    // Collect all files
-    let (scenes, scripts) = collect_files(&ctx.project_path);
+/// Version of the cached dependency graph; changes whenever the graph does
+pub fn resolve_dependency_graph_version(ctx: &GqlContext) -> String {
+    dependency_cache::graph(ctx).2
+}
 
-    // Add scene nodes
-    for scene_path in &scenes {
-        let res_path = path_utils::to_res_path(&ctx.project_path, scene_path)
-            .unwrap_or_else(|_| scene_path.to_string_lossy().to_string());
-        nodes.insert(
-            res_path.clone(),
-            GraphNode {
-                id: res_path.clone(),
-                label: scene_path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| res_path.clone()),
-                node_type: FileType::Scene,
-                in_degree: 0,
-                out_degree: 0,
-                is_unused: true,
-            },
-        );
+/// A graph file with its outgoing references
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct FileDependencies {
+    pub label: String,
+    pub node_type: FileType,
+    pub references: Vec<(String, ReferenceType)>,
+}
+
+/// Parse every graph file in the project (in parallel, see `project_index::set_jobs`)
+pub(super) fn scan_dependencies(ctx: &GqlContext) -> HashMap<String, FileDependencies> {
+    let files: Vec<PathBuf> = ctx
+        .index
+        .files(&ctx.project_path)
+        .into_iter()
+        .filter(|path| graph_file_type(path.extension().and_then(|e| e.to_str())).is_some())
+        .collect();
+    parallel_map(&files, |path| {
+        let res_path = path_utils::to_res_path(&ctx.project_path, path)
+            .unwrap_or_else(|_| path.to_string_lossy().to_string());
+        file_dependencies(&ctx.index, path).map(|deps| (res_path, deps))
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Node type of a graph file by extension: scenes, scripts, text resources
+/// and shaders (with their includes)
+fn graph_file_type(extension: Option<&str>) -> Option<FileType> {
+    match extension {
+        Some("tscn") | Some("scn") => Some(FileType::Scene),
+        Some("gd") | Some("cs") => Some(FileType::Script),
+        Some("tres") => Some(FileType::Resource),
+        Some("gdshader") | Some("gdshaderinc") => Some(FileType::Shader),
+        _ => None,
+    }
+}
+
+/// Node and outgoing references of one graph file; None when the file is
+/// gone or not part of the graph
+pub(super) fn file_dependencies(index: &ProjectIndex, path: &Path) -> Option<FileDependencies> {
+    let node_type = graph_file_type(path.extension().and_then(|e| e.to_str()))?;
+    if !index.fs().is_file(path) {
+        return None;
+    }
+    let label = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
 
+    let mut references = Vec::new();
+    if node_type == FileType::Scene {
         // Parse scene and extract dependencies
-        if let Ok(content) = fs::read_to_string(scene_path) {
-            if let Ok(scene) = GodotScene::parse(&content) {
-                for ext_res in &scene.ext_resources {
-                    let ref_type = match ext_res.resource_type.as_str() {
-                        "Script" | "GDScript" => ReferenceType::AttachesScript,
-                        "PackedScene" => ReferenceType::Instantiates,
-                        _ => ReferenceType::UsesResource,
-                    };
-
-                    edges.push(GraphEdge {
-                        from: res_path.clone(),
-                        to: ext_res.path.clone(),
-                        reference_type: ref_type,
-                    });
-                }
-            }
+        if let Ok(scene) = index.scene(path) {
+            references = scene_references(&scene);
         }
+    } else if let Some(content) = index.read_to_string(path) {
+        references = match node_type {
+            FileType::Resource => GodotResource::parse(&content)
+                .map(|resource| resource_references(&resource))
+                .unwrap_or_default(),
+            FileType::Shader => extract_shader_dependencies(&content),
+            // Parse script and extract preload/load dependencies
+            _ => extract_script_dependencies(&content),
+        };
     }
 
-    // Add script nodes
-    for script_path in &scripts {
-        let res_path = path_utils::to_res_path(&ctx.project_path, script_path)
-            .unwrap_or_else(|_| script_path.to_string_lossy().to_string());
+    Some(FileDependencies {
+        label,
+        node_type,
+        references,
+    })
+}
+
+/// References of a scene's ext_resources. Scenes are Instantiates edges only
+/// when a node instances them (`instance=ExtResource(...)`); a PackedScene held
+/// in a property (e.g. a bullet to spawn) is a plain resource use
+fn scene_references(scene: &GodotScene) -> Vec<(String, ReferenceType)> {
+    let instanced: HashSet<&str> = scene
+        .nodes
+        .iter()
+        .filter_map(|node| scene.instance_path(node))
+        .collect();
+
+    scene
+        .ext_resources
+        .iter()
+        .map(|ext_res| {
+            let ref_type = if instanced.contains(ext_res.path.as_str()) {
+                ReferenceType::Instantiates
+            } else {
+                match ext_res.resource_type.as_str() {
+                    "Script" | "GDScript" => ReferenceType::AttachesScript,
+                    _ => ReferenceType::UsesResource,
+                }
+            };
+            (ext_res.path.clone(), ref_type)
+        })
+        .collect()
+}
+
+/// References of a text resource's ext_resources (e.g. a material's shader
+/// and textures, a custom resource's script)
+fn resource_references(resource: &GodotResource) -> Vec<(String, ReferenceType)> {
+    resource
+        .ext_resources
+        .iter()
+        .map(|ext_res| {
+            let ref_type = match ext_res.resource_type.as_str() {
+                "Script" | "GDScript" => ReferenceType::AttachesScript,
+                _ => ReferenceType::UsesResource,
+            };
+            (ext_res.path.clone(), ref_type)
+        })
+        .collect()
+}
+
+/// Whether a res:// path belongs in the graph (same rules as the full scan)
+pub(super) fn is_graph_path(res_path: &str) -> bool {
+    let relative = path_utils::strip_res_prefix(res_path);
+    let excluded = relative
+        .split('/')
+        .any(|part| EXCLUDED_DIRS.contains(&part));
+    let ext = relative.rsplit_once('.').map(|(_, ext)| ext);
+    !excluded && graph_file_type(ext).is_some()
+}
+
+/// Assemble nodes and edges, computing degrees and unused status
+pub(super) fn assemble_graph<'a>(
+    files: impl Iterator<Item = (&'a String, &'a FileDependencies)>,
+) -> (Vec<GraphNode>, Vec<GraphEdge>) {
+    let mut nodes: HashMap<String, GraphNode> = HashMap::new();
+    let mut edges: Vec<GraphEdge> = Vec::new();
+
+    for (res_path, deps) in files {
         nodes.insert(
             res_path.clone(),
             GraphNode {
                 id: res_path.clone(),
-                label: script_path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| res_path.clone()),
-                node_type: FileType::Script,
+                label: deps.label.clone(),
+                node_type: deps.node_type,
                 in_degree: 0,
                 out_degree: 0,
                 is_unused: true,
             },
         );
-
-        // Parse script and extract preload/load dependencies
-        if let Ok(content) = fs::read_to_string(script_path) {
-            let deps = extract_script_dependencies(&content);
-            for (dep_path, ref_type) in deps {
-                edges.push(GraphEdge {
-                    from: res_path.clone(),
-                    to: dep_path,
-                    reference_type: ref_type,
-                });
-            }
+        for (to, reference_type) in &deps.references {
+            edges.push(GraphEdge {
+                from: res_path.clone(),
+                to: to.clone(),
+                reference_type: *reference_type,
+            });
         }
     }
 
@@ -117,16 +211,20 @@ pub fn build_dependency_graph(ctx: &GqlContext) -> (Vec<GraphNode>, Vec<GraphEdg
         }
     }
 
-    (nodes.into_values().collect(), edges)
+    let mut nodes: Vec<GraphNode> = nodes.into_values().collect();
+    nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    (nodes, edges)
 }
 
-/// Extract dependencies from script content (preload/load calls)
+/// Extract dependencies from script content (preload/load calls, and
+/// `GD.Load<T>(...)` / `ResourceLoader.Load(...)` in C#)
 fn extract_script_dependencies(content: &str) -> Vec<(String, ReferenceType)> {
     let mut deps = Vec::new();
 
     // Match preload("res://...") and load("res://...")
     let preload_re = Regex::new(r#"preload\s*\(\s*"(res://[^"]+)"\s*\)"#).unwrap();
-    let load_re = Regex::new(r#"load\s*\(\s*"(res://[^"]+)"\s*\)"#).unwrap();
+    let load_re =
+        Regex::new(r#"\b(?:load|Load\s*(?:<[^>()]*>)?)\s*\(\s*"(res://[^"]+)"\s*[,)]"#).unwrap();
 
     for cap in preload_re.captures_iter(content) {
         if let Some(path) = cap.get(1) {
@@ -143,45 +241,27 @@ fn extract_script_dependencies(content: &str) -> Vec<(String, ReferenceType)> {
     deps
 }
 
+/// Extract `#include "res://..."` dependencies from shader code
+fn extract_shader_dependencies(content: &str) -> Vec<(String, ReferenceType)> {
+    let include_re = Regex::new(r#"(?m)^\s*#include\s+"(res://[^"]+)""#).unwrap();
+    include_re
+        .captures_iter(content)
+        .map(|cap| (cap[1].to_string(), ReferenceType::UsesResource))
+        .collect()
+}
+
 /// Collect scene and script files from project
-fn collect_files(project_path: &Path) -> (Vec<std::path::PathBuf>, Vec<std::path::PathBuf>) {
+fn collect_files(ctx: &GqlContext) -> (Vec<PathBuf>, Vec<PathBuf>) {
     let mut scenes = Vec::new();
     let mut scripts = Vec::new();
-    collect_files_recursive(project_path, &mut scenes, &mut scripts);
-    (scenes, scripts)
-}
-
-fn collect_files_recursive(
-    dir: &Path,
-    scenes: &mut Vec<std::path::PathBuf>,
-    scripts: &mut Vec<std::path::PathBuf>,
-) {
-    let Ok(entries) = fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        // Skip .godot and addons directories
-        if path
-            .file_name()
-            .map(|n| n == ".godot" || n == "addons")
-            .unwrap_or(false)
-        {
-            continue;
-        }
-
-        if path.is_dir() {
-            collect_files_recursive(&path, scenes, scripts);
-        } else if let Some(ext) = path.extension() {
-            match ext.to_str() {
-                Some("tscn") | Some("scn") => scenes.push(path),
-                Some("gd") => scripts.push(path),
-                _ => {}
-            }
+    for path in ctx.index.files(&ctx.project_path) {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tscn") | Some("scn") => scenes.push(path),
+            Some("gd") | Some("cs") => scripts.push(path),
+            _ => {}
         }
     }
+    (scenes, scripts)
 }
 
 // ======================
@@ -189,7 +269,7 @@ fn collect_files_recursive(
 // ======================
 
 /// Detect cycles in the dependency graph
-fn detect_cycles(nodes: &[GraphNode], edges: &[GraphEdge]) -> (bool, Vec<Vec<String>>) {
+pub(super) fn detect_cycles(nodes: &[GraphNode], edges: &[GraphEdge]) -> (bool, Vec<Vec<String>>) {
     let mut adj: HashMap<&str, Vec<&str>> = HashMap::new();
 
     for node in nodes {
@@ -247,70 +327,342 @@ fn dfs_cycle<'a>(
     false
 }
 
+// ======================
+// Cycle Breaking
+// ======================
+
+/// Rank the references that make up the detected cycles: those shared by the
+/// most cycles first, then the least invasive refactor
+pub(super) fn suggest_cycle_breaks(
+    ctx: &GqlContext,
+    edges: &[GraphEdge],
+    cycle_paths: &[Vec<String>],
+) -> Vec<CycleBreakSuggestion> {
+    let mut counts: HashMap<(&str, &str), i32> = HashMap::new();
+    for path in cycle_paths {
+        // A DFS path may lead into the cycle; keep only the loop itself
+        let Some(last) = path.last() else {
+            continue;
+        };
+        let start = path.iter().position(|node| node == last).unwrap_or(0);
+        for pair in path[start..].windows(2) {
+            *counts.entry((&pair[0], &pair[1])).or_insert(0) += 1;
+        }
+    }
+
+    let mut suggestions: Vec<CycleBreakSuggestion> = counts
+        .into_iter()
+        .filter_map(|((from, to), cycles_broken)| {
+            // Of several references between the same files, the cheapest wins
+            let reference_type = edges
+                .iter()
+                .filter(|e| e.from == from && e.to == to)
+                .map(|e| e.reference_type)
+                .min_by_key(|r| cycle_break_plan(*r).1)?;
+            let (strategy, impact) = cycle_break_plan(reference_type);
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, from);
+            Some(CycleBreakSuggestion {
+                from: from.to_string(),
+                to: to.to_string(),
+                reference_type,
+                line: reference_line(&ctx.index, &fs_path, to),
+                strategy,
+                cycles_broken,
+                impact,
+                description: cycle_break_description(reference_type, from, to),
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.cycles_broken
+            .cmp(&a.cycles_broken)
+            .then(a.impact.cmp(&b.impact))
+            .then_with(|| a.from.cmp(&b.from))
+            .then_with(|| a.to.cmp(&b.to))
+    });
+    suggestions
+}
+
+/// Strategy and relative effort for breaking a reference of the given type
+fn cycle_break_plan(reference_type: ReferenceType) -> (CycleBreakStrategy, i32) {
+    match reference_type {
+        ReferenceType::Preloads => (CycleBreakStrategy::PreloadToLoad, 1),
+        ReferenceType::Loads => (CycleBreakStrategy::SignalDecoupling, 2),
+        ReferenceType::UsesResource => (CycleBreakStrategy::RuntimeLoad, 2),
+        ReferenceType::Instantiates => (CycleBreakStrategy::RuntimeInstantiation, 3),
+        ReferenceType::AttachesScript => (CycleBreakStrategy::SignalDecoupling, 4),
+    }
+}
+
+fn cycle_break_description(reference_type: ReferenceType, from: &str, to: &str) -> String {
+    match reference_type {
+        ReferenceType::Preloads => format!(
+            "Replace preload(\"{to}\") with load(\"{to}\") where it is used in {from}, so it is resolved at runtime instead of when {from} is loaded"
+        ),
+        ReferenceType::Loads => format!(
+            "Have {from} emit a signal (or go through an event bus autoload) instead of loading {to} directly"
+        ),
+        ReferenceType::UsesResource => format!(
+            "Assign {to} from a script with load(\"{to}\") instead of referencing it in {from}"
+        ),
+        ReferenceType::Instantiates => format!(
+            "Remove the {to} instance from {from} and add it at runtime with load(\"{to}\").instantiate()"
+        ),
+        ReferenceType::AttachesScript => format!(
+            "Detach {to} from {from} and let them communicate through signals or an event bus autoload"
+        ),
+    }
+}
+
+/// First line of the file that mentions `target` as a quoted path
+fn reference_line(index: &ProjectIndex, fs_path: &Path, target: &str) -> Option<i32> {
+    let content = index.read_to_string(fs_path)?;
+    let quoted = format!("\"{}\"", target);
+    content
+        .lines()
+        .position(|line| line.contains(&quoted))
+        .map(|i| i as i32 + 1)
+}
+
 // ======================
 // Export Formats
 // ======================
 
+/// Vertex shape in exported diagrams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DiagramShape {
+    Box,
+    Ellipse,
+    Diamond,
+}
+
+/// Edge style in exported diagrams
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DiagramStyle {
+    Solid,
+    Dashed,
+    Bold,
+    Dotted,
+}
+
+/// Vertex of a graph exported as MERMAID or DOT
+pub(super) struct DiagramVertex<'a> {
+    pub id: &'a str,
+    pub label: &'a str,
+    pub shape: DiagramShape,
+}
+
+/// Edge of a graph exported as MERMAID or DOT
+pub(super) struct DiagramEdge<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub label: Option<&'a str>,
+    pub style: DiagramStyle,
+}
+
 /// Export graph to MERMAID format
 fn export_to_mermaid(nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
-    let mut output = String::from("graph LR\n");
+    let (vertices, edges) = dependency_diagram(nodes, edges);
+    render_mermaid(&vertices, &edges)
+}
+
+/// Export graph to DOT format
+fn export_to_dot(nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
+    let (vertices, edges) = dependency_diagram(nodes, edges);
+    render_dot("Dependencies", &vertices, &edges)
+}
+
+/// Export graph to GraphML format
+///
+/// Node and edge types are GraphML attributes, so Gephi and yEd can color
+/// and filter by them. Files referenced but not analysed (textures, audio,
+/// missing files) get a node with only a label, so every edge has both ends.
+fn export_to_graphml(nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    for (id, target, attr_type) in [
+        ("label", "node", "string"),
+        ("type", "node", "string"),
+        ("inDegree", "node", "int"),
+        ("outDegree", "node", "int"),
+        ("isUnused", "node", "boolean"),
+        ("referenceType", "edge", "string"),
+    ] {
+        output.push_str(&format!(
+            "  <key id=\"{0}\" for=\"{1}\" attr.name=\"{0}\" attr.type=\"{2}\"/>\n",
+            id, target, attr_type
+        ));
+    }
+    output.push_str("  <graph id=\"Dependencies\" edgedefault=\"directed\">\n");
 
     for node in nodes {
-        let shape = match node.node_type {
-            FileType::Scene => format!("{}[{}]", sanitize_id(&node.id), node.label),
-            FileType::Script => format!("{}(({}))", sanitize_id(&node.id), node.label),
-            _ => format!("{}>{}>]", sanitize_id(&node.id), node.label),
+        output.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+        output.push_str(&format!(
+            "      <data key=\"label\">{}</data>\n",
+            xml_escape(&node.label)
+        ));
+        output.push_str(&format!(
+            "      <data key=\"type\">{}</data>\n",
+            enum_name(&node.node_type)
+        ));
+        output.push_str(&format!(
+            "      <data key=\"inDegree\">{}</data>\n",
+            node.in_degree
+        ));
+        output.push_str(&format!(
+            "      <data key=\"outDegree\">{}</data>\n",
+            node.out_degree
+        ));
+        output.push_str(&format!(
+            "      <data key=\"isUnused\">{}</data>\n",
+            node.is_unused
+        ));
+        output.push_str("    </node>\n");
+    }
+
+    let known: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+    let mut external: Vec<&str> = edges
+        .iter()
+        .map(|edge| edge.to.as_str())
+        .filter(|to| !known.contains(to))
+        .collect();
+    external.sort_unstable();
+    external.dedup();
+    for id in external {
+        output.push_str(&format!("    <node id=\"{}\">\n", xml_escape(id)));
+        output.push_str(&format!(
+            "      <data key=\"label\">{}</data>\n",
+            xml_escape(id.rsplit('/').next().unwrap_or(id))
+        ));
+        output.push_str("    </node>\n");
+    }
+
+    for (i, edge) in edges.iter().enumerate() {
+        output.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            i,
+            xml_escape(&edge.from),
+            xml_escape(&edge.to)
+        ));
+        output.push_str(&format!(
+            "      <data key=\"referenceType\">{}</data>\n",
+            enum_name(&edge.reference_type)
+        ));
+        output.push_str("    </edge>\n");
+    }
+
+    output.push_str("  </graph>\n</graphml>\n");
+    output
+}
+
+fn dependency_diagram<'a>(
+    nodes: &'a [GraphNode],
+    edges: &'a [GraphEdge],
+) -> (Vec<DiagramVertex<'a>>, Vec<DiagramEdge<'a>>) {
+    let vertices = nodes
+        .iter()
+        .map(|node| DiagramVertex {
+            id: &node.id,
+            label: &node.label,
+            shape: match node.node_type {
+                FileType::Scene => DiagramShape::Box,
+                FileType::Script => DiagramShape::Ellipse,
+                _ => DiagramShape::Diamond,
+            },
+        })
+        .collect();
+    let edges = edges
+        .iter()
+        .map(|edge| DiagramEdge {
+            from: &edge.from,
+            to: &edge.to,
+            label: None,
+            style: match edge.reference_type {
+                ReferenceType::Instantiates => DiagramStyle::Solid,
+                ReferenceType::AttachesScript => DiagramStyle::Dashed,
+                ReferenceType::Preloads => DiagramStyle::Bold,
+                ReferenceType::Loads => DiagramStyle::Solid,
+                ReferenceType::UsesResource => DiagramStyle::Dotted,
+            },
+        })
+        .collect();
+    (vertices, edges)
+}
+
+/// Render vertices and edges as a left-to-right MERMAID flowchart
+pub(super) fn render_mermaid(vertices: &[DiagramVertex], edges: &[DiagramEdge]) -> String {
+    let mut output = String::from("graph LR\n");
+
+    for vertex in vertices {
+        let id = sanitize_id(vertex.id);
+        let label = mermaid_label(vertex.label);
+        let shape = match vertex.shape {
+            DiagramShape::Box => format!("{}[{}]", id, label),
+            DiagramShape::Ellipse => format!("{}(({}))", id, label),
+            DiagramShape::Diamond => format!("{}{{{}}}", id, label),
         };
         output.push_str(&format!("    {}\n", shape));
     }
 
     for edge in edges {
-        let arrow = match edge.reference_type {
-            ReferenceType::Instantiates => "-->",
-            ReferenceType::AttachesScript => "-.->",
-            ReferenceType::Preloads => "==>",
-            ReferenceType::Loads => "-->",
-            ReferenceType::UsesResource => "-.->",
+        let arrow = match edge.style {
+            DiagramStyle::Solid => "-->",
+            DiagramStyle::Dashed | DiagramStyle::Dotted => "-.->",
+            DiagramStyle::Bold => "==>",
         };
+        let label = edge
+            .label
+            .map(|l| format!("|{}|", mermaid_label(l)))
+            .unwrap_or_default();
         output.push_str(&format!(
-            "    {} {} {}\n",
-            sanitize_id(&edge.from),
+            "    {} {}{} {}\n",
+            sanitize_id(edge.from),
             arrow,
-            sanitize_id(&edge.to)
+            label,
+            sanitize_id(edge.to)
         ));
     }
 
     output
 }
 
-/// Export graph to DOT format
-fn export_to_dot(nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
-    let mut output = String::from("digraph Dependencies {\n");
+/// Render vertices and edges as a left-to-right DOT digraph
+pub(super) fn render_dot(name: &str, vertices: &[DiagramVertex], edges: &[DiagramEdge]) -> String {
+    let mut output = format!("digraph {} {{\n", name);
     output.push_str("    rankdir=LR;\n");
 
-    for node in nodes {
-        let shape = match node.node_type {
-            FileType::Scene => "box",
-            FileType::Script => "ellipse",
-            _ => "diamond",
+    for vertex in vertices {
+        let shape = match vertex.shape {
+            DiagramShape::Box => "box",
+            DiagramShape::Ellipse => "ellipse",
+            DiagramShape::Diamond => "diamond",
         };
         output.push_str(&format!(
             "    \"{}\" [label=\"{}\" shape={}];\n",
-            node.id, node.label, shape
+            dot_escape(vertex.id),
+            dot_escape(vertex.label),
+            shape
         ));
     }
 
     for edge in edges {
-        let style = match edge.reference_type {
-            ReferenceType::Instantiates => "solid",
-            ReferenceType::AttachesScript => "dashed",
-            ReferenceType::Preloads => "bold",
-            ReferenceType::Loads => "solid",
-            ReferenceType::UsesResource => "dotted",
+        let style = match edge.style {
+            DiagramStyle::Solid => "solid",
+            DiagramStyle::Dashed => "dashed",
+            DiagramStyle::Bold => "bold",
+            DiagramStyle::Dotted => "dotted",
         };
+        let label = edge
+            .label
+            .map(|l| format!("label=\"{}\" ", dot_escape(l)))
+            .unwrap_or_default();
         output.push_str(&format!(
-            "    \"{}\" -> \"{}\" [style={}];\n",
-            edge.from, edge.to, style
+            "    \"{}\" -> \"{}\" [{}style={}];\n",
+            dot_escape(edge.from),
+            dot_escape(edge.to),
+            label,
+            style
         ));
     }
 
@@ -321,9 +673,35 @@ fn export_to_dot(nodes: &[GraphNode], edges: &[GraphEdge]) -> String {
 /// Sanitize node ID for MERMAID (replace special chars)
 fn sanitize_id(id: &str) -> String {
     id.replace("res://", "")
-        .replace('/', "_")
-        .replace('.', "_")
-        .replace('-', "_")
+        .replace(['/', '.', '-', ':', ' ', '@'], "_")
+}
+
+/// Labels with characters MERMAID treats as syntax are quoted
+fn mermaid_label(label: &str) -> String {
+    let plain = label
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-' | ' ' | '/'));
+    if plain {
+        label.to_string()
+    } else {
+        format!("\"{}\"", label.replace('"', "#quot;"))
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// GraphQL name of an enum value, e.g. `ATTACHES_SCRIPT`
+pub(super) fn enum_name<T: async_graphql::InputType>(value: &T) -> String {
+    value.to_value().to_string()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 // ======================
@@ -341,7 +719,7 @@ pub fn resolve_gather_context(ctx: &GqlContext, input: GatherContextInput) -> Ga
     let entry_type = if input.entry_point.ends_with(".tscn") || input.entry_point.ends_with(".scn")
     {
         FileType::Scene
-    } else if input.entry_point.ends_with(".gd") {
+    } else if is_script_path(&input.entry_point) {
         FileType::Script
     } else {
         FileType::Resource
@@ -424,9 +802,9 @@ fn collect_dependencies(
 
     let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
     let deps = if path.ends_with(".tscn") || path.ends_with(".scn") {
-        extract_scene_dependencies(&fs_path)
-    } else if path.ends_with(".gd") {
-        extract_script_deps(&fs_path)
+        extract_scene_dependencies(&ctx.index, &fs_path)
+    } else if is_script_path(path) {
+        extract_script_deps(&ctx.index, &fs_path)
     } else {
         Vec::new()
     };
@@ -439,7 +817,7 @@ fn collect_dependencies(
 
         let file_type = if dep_path.ends_with(".tscn") || dep_path.ends_with(".scn") {
             FileType::Scene
-        } else if dep_path.ends_with(".gd") {
+        } else if is_script_path(&dep_path) {
             FileType::Script
         } else {
             FileType::Resource
@@ -473,14 +851,12 @@ fn collect_dependencies(
 }
 
 /// Extract dependencies from a scene file
-fn extract_scene_dependencies(path: &Path) -> Vec<String> {
+fn extract_scene_dependencies(index: &ProjectIndex, path: &Path) -> Vec<String> {
     let mut deps = Vec::new();
 
-    if let Ok(content) = fs::read_to_string(path) {
-        if let Ok(scene) = GodotScene::parse(&content) {
-            for ext_res in scene.ext_resources {
-                deps.push(ext_res.path);
-            }
+    if let Ok(scene) = index.scene(path) {
+        for ext_res in &scene.ext_resources {
+            deps.push(ext_res.path.clone());
         }
     }
 
@@ -488,10 +864,10 @@ fn extract_scene_dependencies(path: &Path) -> Vec<String> {
 }
 
 /// Extract dependencies from a script file
-fn extract_script_deps(path: &Path) -> Vec<String> {
+fn extract_script_deps(index: &ProjectIndex, path: &Path) -> Vec<String> {
     let mut deps = Vec::new();
 
-    if let Ok(content) = fs::read_to_string(path) {
+    if let Some(content) = index.read_to_string(path) {
         for (dep_path, _) in extract_script_dependencies(&content) {
             deps.push(dep_path);
         }
@@ -503,7 +879,7 @@ fn extract_script_deps(path: &Path) -> Vec<String> {
 /// Find files that depend on the given path
 fn find_dependents(ctx: &GqlContext, target_path: &str) -> Vec<ContextItem> {
     let mut dependents = Vec::new();
-    let (scenes, scripts) = collect_files(&ctx.project_path);
+    let (scenes, scripts) = collect_files(ctx);
 
     // Check scenes
     for scene_path in scenes {
@@ -513,7 +889,7 @@ fn find_dependents(ctx: &GqlContext, target_path: &str) -> Vec<ContextItem> {
             continue;
         }
 
-        let deps = extract_scene_dependencies(&scene_path);
+        let deps = extract_scene_dependencies(&ctx.index, &scene_path);
         if deps.contains(&target_path.to_string()) {
             dependents.push(ContextItem {
                 path: res_path.clone(),
@@ -532,7 +908,7 @@ fn find_dependents(ctx: &GqlContext, target_path: &str) -> Vec<ContextItem> {
             continue;
         }
 
-        let deps = extract_script_deps(&script_path);
+        let deps = extract_script_deps(&ctx.index, &script_path);
         if deps.contains(&target_path.to_string()) {
             dependents.push(ContextItem {
                 path: res_path.clone(),
@@ -552,19 +928,17 @@ fn collect_resources(ctx: &GqlContext, path: &str) -> Vec<ResourceInfo> {
     let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
 
     if path.ends_with(".tscn") || path.ends_with(".scn") {
-        if let Ok(content) = fs::read_to_string(&fs_path) {
-            if let Ok(scene) = GodotScene::parse(&content) {
-                for ext_res in scene.ext_resources {
-                    // Only include non-scene, non-script resources
-                    if ext_res.resource_type != "Script"
-                        && ext_res.resource_type != "GDScript"
-                        && ext_res.resource_type != "PackedScene"
-                    {
-                        resources.push(ResourceInfo {
-                            path: ext_res.path,
-                            resource_type: Some(ext_res.resource_type),
-                        });
-                    }
+        if let Ok(scene) = ctx.index.scene(&fs_path) {
+            for ext_res in &scene.ext_resources {
+                // Only include non-scene, non-script resources
+                if ext_res.resource_type != "Script"
+                    && ext_res.resource_type != "GDScript"
+                    && ext_res.resource_type != "PackedScene"
+                {
+                    resources.push(ResourceInfo {
+                        path: ext_res.path.clone(),
+                        resource_type: Some(ext_res.resource_type.clone()),
+                    });
                 }
             }
         }
@@ -578,10 +952,23 @@ pub fn resolve_dependency_graph(
     ctx: &GqlContext,
     input: Option<DependencyGraphInput>,
 ) -> DependencyGraph {
-    let (nodes, edges) = build_dependency_graph(ctx);
+    let (mut nodes, mut edges, version) = dependency_cache::graph(ctx);
+
+    // Scope to tagged files, keeping their outgoing edges
+    let tags = input.as_ref().and_then(|i| i.tags.as_ref());
+    match TagScope::new(ctx, tags) {
+        Ok(Some(scope)) => {
+            nodes.retain(|n| scope.includes(ctx, &n.id));
+            let kept: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+            edges.retain(|e| kept.contains(e.from.as_str()));
+        }
+        Ok(None) => {}
+        Err(error) => tracing::warn!("Ignoring tag filter: {}", error.message),
+    }
 
     // Detect cycles
     let (has_cycles, cycle_paths) = detect_cycles(&nodes, &edges);
+    let cycle_break_suggestions = suggest_cycle_breaks(ctx, &edges, &cycle_paths);
 
     // Calculate stats
     let unused_count = nodes.iter().filter(|n| n.is_unused).count() as i32;
@@ -595,8 +982,10 @@ pub fn resolve_dependency_graph(
     };
 
     // Export data if format requested
-    let exported_data = input.as_ref().and_then(|i| i.format).map(|format| {
-        match format {
+    let exported_data = input
+        .as_ref()
+        .and_then(|i| i.format)
+        .map(|format| match format {
             GraphFormat::Mermaid => export_to_mermaid(&nodes, &edges),
             GraphFormat::Dot => export_to_dot(&nodes, &edges),
             GraphFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
@@ -615,15 +1004,175 @@ pub fn resolve_dependency_graph(
                 })).collect::<Vec<_>>(),
             }))
             .unwrap_or_default(),
-            GraphFormat::Graphml => String::new(), // TODO: Implement GraphML export
-        }
-    });
+            GraphFormat::Graphml => export_to_graphml(&nodes, &edges),
+        });
 
     DependencyGraph {
         nodes,
         edges,
         stats,
         exported_data,
+        version,
+        cycle_break_suggestions,
+    }
+}
+
+// ======================
+// Preload Analysis
+// ======================
+
+/// Resolve preloadAnalysis query
+///
+/// Scene ext_resources and script preload() targets are resolved as soon as
+/// the owning file is loaded, so they form the startup closure of the entry
+/// scene. load() targets are resolved at runtime and are only reported per
+/// script.
+pub fn resolve_preload_analysis(ctx: &GqlContext, entry_point: Option<String>) -> PreloadAnalysis {
+    let (_, script_files) = collect_files(ctx);
+
+    let mut scripts = Vec::new();
+    for script_path in &script_files {
+        let Some(content) = ctx.index.read_to_string(script_path) else {
+            continue;
+        };
+        let mut preloads = Vec::new();
+        let mut loads = Vec::new();
+        for (dep_path, ref_type) in extract_script_dependencies(&content) {
+            match ref_type {
+                ReferenceType::Preloads => preloads.push(dep_path),
+                _ => loads.push(dep_path),
+            }
+        }
+        if preloads.is_empty() && loads.is_empty() {
+            continue;
+        }
+        scripts.push(ScriptLoadReferences {
+            path: path_utils::to_res_path(&ctx.project_path, script_path)
+                .unwrap_or_else(|_| script_path.to_string_lossy().to_string()),
+            preloads,
+            loads,
+        });
+    }
+    scripts.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let preload_count = scripts.iter().map(|s| s.preloads.len() as i32).sum();
+    let load_count = scripts.iter().map(|s| s.loads.len() as i32).sum();
+
+    let entry_point = entry_point
+        .or_else(|| parse_main_scene(ctx.fs.as_ref(), &ctx.project_path.join("project.godot")))
+        .and_then(|entry| resolve_uid_path(ctx, &entry));
+
+    let startup_files = entry_point
+        .as_deref()
+        .map(|entry| collect_startup_closure(ctx, entry))
+        .unwrap_or_default();
+    let total_startup_bytes = startup_files.iter().map(|f| f.size_bytes).sum();
+
+    PreloadAnalysis {
+        entry_point,
+        scripts,
+        startup_files,
+        total_startup_bytes,
+        preload_count,
+        load_count,
+    }
+}
+
+/// Breadth-first walk over load-time references starting at the entry point
+fn collect_startup_closure(ctx: &GqlContext, entry_point: &str) -> Vec<StartupFile> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue: VecDeque<(String, i32, Option<String>, Option<ReferenceType>)> = VecDeque::new();
+
+    visited.insert(entry_point.to_string());
+    queue.push_back((entry_point.to_string(), 0, None, None));
+
+    while let Some((path, depth, loaded_by, reference_type)) = queue.pop_front() {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &path);
+        let size_bytes = ctx
+            .fs
+            .metadata(&fs_path)
+            .map(|m| m.len.min(i32::MAX as u64) as i32)
+            .unwrap_or(0);
+
+        for (dep_path, ref_type) in extract_load_time_dependencies(&ctx.index, &fs_path, &path) {
+            let dep_path = resolve_uid_path(ctx, &dep_path).unwrap_or(dep_path);
+            if visited.insert(dep_path.clone()) {
+                queue.push_back((dep_path, depth + 1, Some(path.clone()), Some(ref_type)));
+            }
+        }
+
+        files.push(StartupFile {
+            file_type: file_type_of(&path),
+            path,
+            size_bytes,
+            depth,
+            loaded_by,
+            reference_type,
+        });
+    }
+
+    files
+}
+
+/// Extract references that are resolved when the file itself is loaded
+fn extract_load_time_dependencies(
+    index: &ProjectIndex,
+    fs_path: &Path,
+    res_path: &str,
+) -> Vec<(String, ReferenceType)> {
+    let Some(content) = index.read_to_string(fs_path) else {
+        return Vec::new();
+    };
+
+    match file_type_of(res_path) {
+        FileType::Scene => index
+            .scene(fs_path)
+            .map(|scene| scene_references(&scene))
+            .unwrap_or_default(),
+        FileType::Script => extract_script_dependencies(&content)
+            .into_iter()
+            .filter(|(_, ref_type)| *ref_type == ReferenceType::Preloads)
+            .collect(),
+        _ if res_path.ends_with(".tres") => GodotResource::parse(&content)
+            .map(|resource| {
+                resource
+                    .ext_resources
+                    .into_iter()
+                    .map(|ext_res| (ext_res.path, ReferenceType::UsesResource))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Map a `uid://` reference to its res:// path by scanning scene headers
+fn resolve_uid_path(ctx: &GqlContext, path: &str) -> Option<String> {
+    if !path.starts_with("uid://") {
+        return Some(path.to_string());
+    }
+
+    let (scenes, _) = collect_files(ctx);
+    scenes.into_iter().find_map(|scene_path| {
+        let scene = ctx.index.scene(&scene_path).ok()?;
+        if scene.uid.as_deref() == Some(path) {
+            path_utils::to_res_path(&ctx.project_path, &scene_path).ok()
+        } else {
+            None
+        }
+    })
+}
+
+fn file_type_of(path: &str) -> FileType {
+    if path.ends_with(".tscn") || path.ends_with(".scn") {
+        FileType::Scene
+    } else if is_script_path(path) {
+        FileType::Script
+    } else if path.ends_with(".gdshader") || path.ends_with(".gdshaderinc") {
+        FileType::Shader
+    } else {
+        FileType::Resource
     }
 }
 
@@ -655,6 +1204,31 @@ func _ready():
         );
     }
 
+    #[test]
+    fn test_extract_csharp_script_dependencies() {
+        let content = r#"public partial class Spawner : Node
+{
+    private PackedScene _enemy = GD.Load<PackedScene>("res://scenes/enemy.tscn");
+
+    public override void _Ready()
+    {
+        var item = ResourceLoader.Load("res://resources/item.tres", "Resource");
+    }
+}
+"#;
+        let deps = extract_script_dependencies(content);
+        assert_eq!(
+            deps,
+            vec![
+                ("res://scenes/enemy.tscn".to_string(), ReferenceType::Loads),
+                (
+                    "res://resources/item.tres".to_string(),
+                    ReferenceType::Loads
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_sanitize_id() {
         assert_eq!(
@@ -680,4 +1254,73 @@ func _ready():
         assert!(mermaid.contains("graph LR"));
         assert!(mermaid.contains("player.tscn"));
     }
+
+    #[test]
+    fn test_export_to_graphml() {
+        let nodes = vec![
+            GraphNode {
+                id: "res://scenes/player.tscn".to_string(),
+                label: "player.tscn".to_string(),
+                node_type: FileType::Scene,
+                in_degree: 0,
+                out_degree: 1,
+                is_unused: true,
+            },
+            GraphNode {
+                id: "res://scripts/a&b.gd".to_string(),
+                label: "a&b.gd".to_string(),
+                node_type: FileType::Script,
+                in_degree: 1,
+                out_degree: 0,
+                is_unused: false,
+            },
+        ];
+        let edges = vec![GraphEdge {
+            from: "res://scenes/player.tscn".to_string(),
+            to: "res://scripts/a&b.gd".to_string(),
+            reference_type: ReferenceType::AttachesScript,
+        }];
+
+        let graphml = export_to_graphml(&nodes, &edges);
+        assert!(graphml.contains("<graph id=\"Dependencies\" edgedefault=\"directed\">"));
+        assert!(graphml.contains("<node id=\"res://scripts/a&amp;b.gd\">"));
+        assert!(graphml.contains("<data key=\"type\">SCENE</data>"));
+        assert!(graphml.contains("<data key=\"isUnused\">true</data>"));
+        assert!(graphml.contains(
+            "<edge id=\"e0\" source=\"res://scenes/player.tscn\" target=\"res://scripts/a&amp;b.gd\">"
+        ));
+        assert!(graphml.contains("<data key=\"referenceType\">ATTACHES_SCRIPT</data>"));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+
+    #[test]
+    fn test_export_to_graphml_declares_external_targets() {
+        let nodes = vec![GraphNode {
+            id: "res://scenes/player.tscn".to_string(),
+            label: "player.tscn".to_string(),
+            node_type: FileType::Scene,
+            in_degree: 0,
+            out_degree: 2,
+            is_unused: true,
+        }];
+        let edges: Vec<GraphEdge> = ["res://art/player.png", "res://art/player.png"]
+            .into_iter()
+            .map(|to| GraphEdge {
+                from: "res://scenes/player.tscn".to_string(),
+                to: to.to_string(),
+                reference_type: ReferenceType::UsesResource,
+            })
+            .collect();
+
+        let graphml = export_to_graphml(&nodes, &edges);
+        assert_eq!(
+            graphml
+                .matches("<node id=\"res://art/player.png\">\n      <data key=\"label\">player.png</data>\n    </node>")
+                .count(),
+            1
+        );
+        assert!(graphml.contains(
+            "<edge id=\"e1\" source=\"res://scenes/player.tscn\" target=\"res://art/player.png\">"
+        ));
+    }
 }
@@ -0,0 +1,37 @@
+//! Diagnostics Resolver
+//!
+//! serverDiagnostics: the server process and what it recovered from the
+//! write-ahead journal (see `journal`) when it opened the project.
+
+use super::context::GqlContext;
+use super::journal::{self, RecoveryOutcome};
+use super::project_resolver::to_res_path;
+use super::types::*;
+
+/// Resolve serverDiagnostics query
+pub fn resolve_server_diagnostics(ctx: &GqlContext) -> ServerDiagnostics {
+    ServerDiagnostics {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        session_id: ctx.session_id.clone(),
+        journal_recoveries: journal::recoveries(&ctx.project_path)
+            .into_iter()
+            .map(|recovery| JournalRecovery {
+                id: recovery.id,
+                operation: recovery.operation,
+                started_at: recovery.started_at.map(|millis| millis.to_string()),
+                outcome: match recovery.outcome {
+                    RecoveryOutcome::RolledForward => JournalRecoveryOutcome::RolledForward,
+                    RecoveryOutcome::Restored => JournalRecoveryOutcome::Restored,
+                    RecoveryOutcome::Discarded => JournalRecoveryOutcome::Discarded,
+                    RecoveryOutcome::Failed => JournalRecoveryOutcome::Failed,
+                },
+                files: recovery
+                    .paths
+                    .iter()
+                    .map(|path| to_res_path(&ctx.project_path, path))
+                    .collect(),
+                message: recovery.message,
+            })
+            .collect(),
+    }
+}
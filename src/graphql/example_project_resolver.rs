@@ -0,0 +1,991 @@
+//! Example Project Resolver
+//!
+//! generateExampleProject: a small playable project (2D platformer, 3D
+//! first-person shooter or UI demo) built with the server's own resolvers —
+//! createScene, applyMutation (scripts, nodes, script attachments, signal
+//! connections), createCustomResource, addInputAction and setProjectSetting —
+//! then checked with projectHealth. It doubles as an onboarding demo and as
+//! an end-to-end test of those subsystems working together.
+
+use std::path::{Path, PathBuf};
+
+use async_graphql::Json;
+use serde_json::{json, Value};
+
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::custom_resource_resolver::resolve_create_custom_resource;
+use super::health_resolver::resolve_project_health;
+use super::input_map_resolver::resolve_add_input_action;
+use super::mutation_resolver::apply_mutation;
+use super::project_resolver::resolve_set_project_setting;
+use super::scene_resolver::create_scene;
+use super::types::*;
+
+const PROJECT_GODOT: &str = r#"; Engine configuration file.
+; It's best edited using the editor UI and not directly,
+; since the parameters that go here are not all obvious.
+
+config_version=5
+
+[application]
+
+config/features=PackedStringArray("4.3")
+"#;
+
+/// Everything an example project is made of
+struct Blueprint {
+    name: &'static str,
+    main_scene: &'static str,
+    /// Settings besides the name and the main scene
+    settings: Vec<(&'static str, &'static str)>,
+    actions: Vec<(&'static str, Vec<InputEventInput>)>,
+    scripts: Vec<ExampleScript>,
+    /// Custom Resource instance: script, .tres path and values
+    resource: (&'static str, &'static str, Value),
+    scenes: Vec<ExampleScene>,
+}
+
+struct ExampleScript {
+    path: &'static str,
+    extends: &'static str,
+    class_name: Option<&'static str>,
+    /// Everything after the `extends` line
+    body: &'static str,
+}
+
+struct ExampleScene {
+    path: &'static str,
+    root_name: &'static str,
+    root_type: &'static str,
+    /// applyMutation operations, without the scene path
+    operations: Vec<(OperationType, Value)>,
+}
+
+/// Resolve generateExampleProject mutation
+pub async fn resolve_generate_example_project(
+    ctx: &GqlContext,
+    kind: ExampleProjectKind,
+    path: &str,
+) -> ExampleProjectResult {
+    let root = target_dir(ctx, path);
+    let mut result = ExampleProjectResult {
+        success: false,
+        kind,
+        path: root.display().to_string(),
+        main_scene: None,
+        files: Vec::new(),
+        input_actions: Vec::new(),
+        steps: Vec::new(),
+        health: None,
+        error: None,
+    };
+
+    if ctx.fs.is_virtual() {
+        result.error = Some(GqlStructuredError::new(
+            "NOT_SUPPORTED",
+            GqlErrorCategory::Validation,
+            "generateExampleProject needs the project on disk",
+        ));
+        return result;
+    }
+    let is_empty =
+        std::fs::read_dir(&root).map_or(!root.exists(), |mut entries| entries.next().is_none());
+    if !is_empty {
+        result.error = Some(
+            GqlStructuredError::new(
+                "TARGET_NOT_EMPTY",
+                GqlErrorCategory::Validation,
+                format!("{} exists and is not an empty folder", root.display()),
+            )
+            .with_suggestion("存在しないフォルダか空のフォルダを指定してください"),
+        );
+        return result;
+    }
+    if let Err(e) = std::fs::create_dir_all(&root) {
+        result.error = Some(GqlStructuredError::new(
+            "WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to create {}: {}", root.display(), e),
+        ));
+        return result;
+    }
+
+    let example = GqlContext::new(root).with_session(ctx.session_id.clone());
+    let blueprint = match kind {
+        ExampleProjectKind::Platformer2d => platformer_2d(),
+        ExampleProjectKind::Fps3d => fps_3d(),
+        ExampleProjectKind::UiDemo => ui_demo(),
+    };
+    if let Err(e) = generate(&example, blueprint, &mut result).await {
+        result.error = Some(*e);
+        return result;
+    }
+
+    result.health = Some(resolve_project_health(&example, false));
+    result.success = true;
+    result
+}
+
+/// Absolute folders as given; relative ones next to the current project
+fn target_dir(ctx: &GqlContext, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    ctx.project_path
+        .parent()
+        .unwrap_or(&ctx.project_path)
+        .join(path)
+}
+
+async fn generate(
+    ctx: &GqlContext,
+    blueprint: Blueprint,
+    result: &mut ExampleProjectResult,
+) -> Result<(), Box<GqlStructuredError>> {
+    let project_godot = ctx.project_path.join("project.godot");
+    let written = audit::write_file(ctx, "generateExampleProject", &project_godot, PROJECT_GODOT);
+    step(
+        result,
+        "writeFile",
+        "res://project.godot",
+        written.map_err(|e| e.to_string()),
+    )?;
+    telemetry::record_files_written(1);
+    result.files.push("res://project.godot".to_string());
+
+    // Scenes, then every script and node in one mutation
+    let mut operations = Vec::new();
+    for scene in &blueprint.scenes {
+        let created = create_scene(
+            ctx,
+            &CreateSceneInput {
+                path: scene.path.to_string(),
+                root_name: scene.root_name.to_string(),
+                root_type: scene.root_type.to_string(),
+            },
+        );
+        step(
+            result,
+            "createScene",
+            scene.path,
+            ok_or(created.success, created.message),
+        )?;
+        result.files.push(scene.path.to_string());
+    }
+    for script in &blueprint.scripts {
+        operations.push(PlannedOperation {
+            operation_type: OperationType::CreateScript,
+            args: Json(json!({
+                "path": script.path,
+                "extends": script.extends,
+                "className": script.class_name,
+            })),
+        });
+    }
+    for scene in blueprint.scenes {
+        for (operation_type, mut args) in scene.operations {
+            args["scenePath"] = json!(scene.path);
+            operations.push(PlannedOperation {
+                operation_type,
+                args: Json(args),
+            });
+        }
+    }
+    let operation_count = operations.len();
+    let applied = apply_mutation(
+        ctx,
+        &ApplyMutationInput {
+            operations,
+            create_backup: None,
+            backup_description: None,
+        },
+    )
+    .await;
+    let message = applied
+        .errors
+        .iter()
+        .map(|e| format!("operation {}: {}", e.operation_index, e.message))
+        .collect::<Vec<_>>()
+        .join("; ");
+    step(
+        result,
+        "applyMutation",
+        &format!("{} operation(s)", operation_count),
+        ok_or(applied.success, Some(message)),
+    )?;
+
+    // createScript writes a skeleton; the gameplay code replaces its functions
+    for script in &blueprint.scripts {
+        let written = write_script_body(ctx, script.path, script.body);
+        step(result, "writeScript", script.path, written)?;
+        result.files.push(script.path.to_string());
+    }
+
+    let (script_path, output_path, values) = blueprint.resource;
+    let created = resolve_create_custom_resource(
+        ctx,
+        &CreateCustomResourceInput {
+            script_path: script_path.to_string(),
+            output_path: output_path.to_string(),
+            values: Json(values),
+        },
+    );
+    let message = created.error.map(|e| e.message);
+    step(
+        result,
+        "createCustomResource",
+        output_path,
+        ok_or(created.success, message),
+    )?;
+    result.files.push(output_path.to_string());
+
+    for (action_name, events) in blueprint.actions {
+        let added = resolve_add_input_action(
+            ctx,
+            &AddInputActionInput {
+                action_name: action_name.to_string(),
+                events,
+                deadzone: None,
+            },
+        );
+        step(result, "addInputAction", action_name, operation(added))?;
+        result.input_actions.push(action_name.to_string());
+    }
+
+    let name = format!("\"{}\"", blueprint.name);
+    let main_scene = format!("\"{}\"", blueprint.main_scene);
+    let settings = [
+        ("application/config/name", name.as_str()),
+        ("application/run/main_scene", main_scene.as_str()),
+    ];
+    for (path, value) in settings.into_iter().chain(blueprint.settings) {
+        let set = resolve_set_project_setting(
+            ctx,
+            &SetProjectSettingInput {
+                path: path.to_string(),
+                value: value.to_string(),
+                value_type: None,
+            },
+        );
+        step(result, "setProjectSetting", path, operation(set))?;
+    }
+    result.main_scene = Some(blueprint.main_scene.to_string());
+    Ok(())
+}
+
+/// Record a step; a failed one stops the generation
+fn step(
+    result: &mut ExampleProjectResult,
+    name: &str,
+    target: &str,
+    outcome: Result<(), String>,
+) -> Result<(), Box<GqlStructuredError>> {
+    result.steps.push(ExampleProjectStep {
+        name: name.to_string(),
+        target: target.to_string(),
+        success: outcome.is_ok(),
+        message: outcome.as_ref().err().cloned(),
+    });
+    outcome.map_err(|message| {
+        Box::new(GqlStructuredError::new(
+            "STEP_FAILED",
+            GqlErrorCategory::Validation,
+            format!("{} {} failed: {}", name, target, message),
+        ))
+    })
+}
+
+fn ok_or(success: bool, message: Option<String>) -> Result<(), String> {
+    if success {
+        Ok(())
+    } else {
+        Err(message.unwrap_or_default())
+    }
+}
+
+fn operation(result: OperationResult) -> Result<(), String> {
+    let message = result.error.map(|e| e.message);
+    ok_or(result.success, message)
+}
+
+/// Keep the `class_name` and `extends` lines createScript wrote and replace the rest
+fn write_script_body(ctx: &GqlContext, res_path: &str, body: &str) -> Result<(), String> {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    let skeleton = ctx
+        .fs
+        .read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read {}: {}", res_path, e))?;
+    let header: Vec<&str> = skeleton
+        .lines()
+        .take_while(|line| !line.starts_with("func "))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let content = format!("{}\n\n{}", header.join("\n"), body);
+    audit::write_file(ctx, "generateExampleProject", &file_path, content)
+        .map_err(|e| format!("Failed to write {}: {}", res_path, e))?;
+    telemetry::record_files_written(1);
+    Ok(())
+}
+
+fn key(name: &str) -> InputEventInput {
+    InputEventInput {
+        event_type: InputEventType::Key,
+        key: Some(name.to_string()),
+        button: None,
+        axis_value: None,
+        device: None,
+    }
+}
+
+fn mouse_button(button: i32) -> InputEventInput {
+    InputEventInput {
+        event_type: InputEventType::MouseButton,
+        key: None,
+        button: Some(button),
+        axis_value: None,
+        device: None,
+    }
+}
+
+fn add_node(
+    parent: &str,
+    name: &str,
+    node_type: &str,
+    properties: Value,
+) -> (OperationType, Value) {
+    (
+        OperationType::AddNode,
+        json!({ "parent": parent, "name": name, "type": node_type, "properties": properties }),
+    )
+}
+
+fn set_properties(node_path: &str, properties: Value) -> (OperationType, Value) {
+    (
+        OperationType::SetProperties,
+        json!({ "nodePath": node_path, "properties": properties }),
+    )
+}
+
+fn attach_script(node_path: &str, script_path: &str) -> (OperationType, Value) {
+    (
+        OperationType::AttachScript,
+        json!({ "nodePath": node_path, "scriptPath": script_path }),
+    )
+}
+
+fn connect_signal(from: &str, signal: &str, to: &str, method: &str) -> (OperationType, Value) {
+    (
+        OperationType::ConnectSignal,
+        json!({ "from": from, "signal": signal, "to": to, "method": method }),
+    )
+}
+
+/// A rectangle as a polygon literal centered on the origin
+fn box_polygon(width: f64, height: f64) -> String {
+    let (x, y) = (width / 2.0, height / 2.0);
+    format!(
+        "PackedVector2Array({}, {}, {}, {}, {}, {}, {}, {})",
+        -x, -y, x, -y, x, y, -x, y
+    )
+}
+
+/// Full-rect anchors of a Control
+fn full_rect() -> Value {
+    json!({
+        "anchors_preset": 15,
+        "anchor_right": 1.0,
+        "anchor_bottom": 1.0,
+        "grow_horizontal": 2,
+        "grow_vertical": 2,
+    })
+}
+
+/// Anchors of a Control centered in its parent, `width` x `height`
+fn centered(width: i32, height: i32) -> Value {
+    json!({
+        "anchors_preset": 8,
+        "anchor_left": 0.5,
+        "anchor_top": 0.5,
+        "anchor_right": 0.5,
+        "anchor_bottom": 0.5,
+        "offset_left": -width / 2,
+        "offset_top": -height / 2,
+        "offset_right": width / 2,
+        "offset_bottom": height / 2,
+        "grow_horizontal": 2,
+        "grow_vertical": 2,
+    })
+}
+
+/// A solid 2D block: a static body drawn by a polygon
+fn block_2d(name: &str, position: &str, width: f64, height: f64) -> Vec<(OperationType, Value)> {
+    let polygon = box_polygon(width, height);
+    vec![
+        add_node(".", name, "StaticBody2D", json!({ "position": position })),
+        add_node(
+            name,
+            "Body",
+            "Polygon2D",
+            json!({ "color": "Color(0.3, 0.3, 0.35, 1)", "polygon": polygon }),
+        ),
+        add_node(
+            name,
+            "Collision",
+            "CollisionPolygon2D",
+            json!({ "polygon": polygon }),
+        ),
+    ]
+}
+
+fn platformer_2d() -> Blueprint {
+    let player = box_polygon(32.0, 48.0);
+    let coin = "PackedVector2Array(0, -12, 12, 0, 0, 12, -12, 0)";
+    let mut main = vec![
+        add_node(
+            ".",
+            "Player",
+            "CharacterBody2D",
+            json!({ "position": "Vector2(160, 520)" }),
+        ),
+        add_node(
+            "Player",
+            "Body",
+            "Polygon2D",
+            json!({ "color": "Color(0.25, 0.55, 1, 1)", "polygon": player }),
+        ),
+        add_node(
+            "Player",
+            "Collision",
+            "CollisionPolygon2D",
+            json!({ "polygon": player }),
+        ),
+        add_node(".", "HUD", "CanvasLayer", json!({})),
+        add_node(
+            "HUD",
+            "ScoreLabel",
+            "Label",
+            json!({ "offset_left": 16, "offset_top": 16, "text": "\"Coins: 0\"" }),
+        ),
+        attach_script(".", "res://main.gd"),
+        attach_script("Player", "res://player.gd"),
+    ];
+    main.extend(block_2d("Ground", "Vector2(576, 624)", 1152.0, 48.0));
+    main.extend(block_2d("Platform", "Vector2(520, 440)", 192.0, 24.0));
+    main.extend(block_2d("Ledge", "Vector2(860, 320)", 192.0, 24.0));
+
+    Blueprint {
+        name: "Example Platformer",
+        main_scene: "res://main.tscn",
+        settings: vec![],
+        actions: vec![
+            ("move_left", vec![key("A"), key("Left")]),
+            ("move_right", vec![key("D"), key("Right")]),
+            ("jump", vec![key("Space"), key("W"), key("Up")]),
+        ],
+        scripts: vec![
+            ExampleScript {
+                path: "res://player_stats.gd",
+                extends: "Resource",
+                class_name: Some("PlayerStats"),
+                body: PLAYER_STATS_GD,
+            },
+            ExampleScript {
+                path: "res://player.gd",
+                extends: "CharacterBody2D",
+                class_name: None,
+                body: PLATFORMER_PLAYER_GD,
+            },
+            ExampleScript {
+                path: "res://coin.gd",
+                extends: "Area2D",
+                class_name: Some("Coin"),
+                body: COIN_GD,
+            },
+            ExampleScript {
+                path: "res://main.gd",
+                extends: "Node2D",
+                class_name: None,
+                body: PLATFORMER_MAIN_GD,
+            },
+        ],
+        resource: (
+            "res://player_stats.gd",
+            "res://player_stats.tres",
+            json!({ "speed": 240.0, "jump_velocity": -460.0 }),
+        ),
+        scenes: vec![
+            ExampleScene {
+                path: "res://main.tscn",
+                root_name: "Main",
+                root_type: "Node2D",
+                operations: main,
+            },
+            ExampleScene {
+                path: "res://coin.tscn",
+                root_name: "Coin",
+                root_type: "Area2D",
+                operations: vec![
+                    add_node(
+                        ".",
+                        "Body",
+                        "Polygon2D",
+                        json!({ "color": "Color(1, 0.8, 0.2, 1)", "polygon": coin }),
+                    ),
+                    add_node(
+                        ".",
+                        "Collision",
+                        "CollisionPolygon2D",
+                        json!({ "polygon": coin }),
+                    ),
+                    attach_script(".", "res://coin.gd"),
+                    connect_signal(".", "body_entered", ".", "_on_body_entered"),
+                ],
+            },
+        ],
+    }
+}
+
+fn fps_3d() -> Blueprint {
+    // CollisionPolygon3D extrudes the polygon along Z, `depth` deep
+    let player = box_polygon(0.8, 1.8);
+    let target = box_polygon(1.0, 1.0);
+    Blueprint {
+        name: "Example FPS",
+        main_scene: "res://main.tscn",
+        settings: vec![],
+        actions: vec![
+            ("move_forward", vec![key("W"), key("Up")]),
+            ("move_back", vec![key("S"), key("Down")]),
+            ("move_left", vec![key("A"), key("Left")]),
+            ("move_right", vec![key("D"), key("Right")]),
+            ("jump", vec![key("Space")]),
+            ("shoot", vec![mouse_button(1)]),
+        ],
+        scripts: vec![
+            ExampleScript {
+                path: "res://weapon_stats.gd",
+                extends: "Resource",
+                class_name: Some("WeaponStats"),
+                body: WEAPON_STATS_GD,
+            },
+            ExampleScript {
+                path: "res://player.gd",
+                extends: "CharacterBody3D",
+                class_name: None,
+                body: FPS_PLAYER_GD,
+            },
+            ExampleScript {
+                path: "res://target.gd",
+                extends: "StaticBody3D",
+                class_name: Some("Target"),
+                body: TARGET_GD,
+            },
+            ExampleScript {
+                path: "res://main.gd",
+                extends: "Node3D",
+                class_name: None,
+                body: FPS_MAIN_GD,
+            },
+        ],
+        resource: (
+            "res://weapon_stats.gd",
+            "res://pistol.tres",
+            json!({ "damage": 1, "fire_rate": 4.0 }),
+        ),
+        scenes: vec![
+            ExampleScene {
+                path: "res://main.tscn",
+                root_name: "Main",
+                root_type: "Node3D",
+                operations: vec![
+                    add_node(
+                        ".",
+                        "Sun",
+                        "DirectionalLight3D",
+                        json!({
+                            "transform": "Transform3D(1, 0, 0, 0, 0.5, 0.866025, 0, -0.866025, 0.5, 0, 10, 0)",
+                            "shadow_enabled": true,
+                        }),
+                    ),
+                    add_node(
+                        ".",
+                        "Floor",
+                        "CSGBox3D",
+                        json!({
+                            "position": "Vector3(0, -0.5, 0)",
+                            "size": "Vector3(40, 1, 40)",
+                            "use_collision": true,
+                        }),
+                    ),
+                    add_node(
+                        ".",
+                        "Player",
+                        "CharacterBody3D",
+                        json!({ "position": "Vector3(0, 0.9, 6)" }),
+                    ),
+                    add_node(
+                        "Player",
+                        "Collision",
+                        "CollisionPolygon3D",
+                        json!({ "polygon": player, "depth": 0.8 }),
+                    ),
+                    add_node(
+                        "Player",
+                        "Camera",
+                        "Camera3D",
+                        json!({ "position": "Vector3(0, 0.7, 0)" }),
+                    ),
+                    add_node(
+                        "Player/Camera",
+                        "Aim",
+                        "RayCast3D",
+                        json!({ "target_position": "Vector3(0, 0, -50)" }),
+                    ),
+                    add_node(".", "HUD", "CanvasLayer", json!({})),
+                    add_node(
+                        "HUD",
+                        "Crosshair",
+                        "Label",
+                        json!({
+                            "anchors_preset": 8,
+                            "anchor_left": 0.5,
+                            "anchor_top": 0.5,
+                            "anchor_right": 0.5,
+                            "anchor_bottom": 0.5,
+                            "text": "\"+\"",
+                        }),
+                    ),
+                    add_node(
+                        "HUD",
+                        "StatusLabel",
+                        "Label",
+                        json!({ "offset_left": 16, "offset_top": 16 }),
+                    ),
+                    attach_script(".", "res://main.gd"),
+                    attach_script("Player", "res://player.gd"),
+                ],
+            },
+            ExampleScene {
+                path: "res://target.tscn",
+                root_name: "Target",
+                root_type: "StaticBody3D",
+                operations: vec![
+                    add_node(".", "Body", "CSGSphere3D", json!({ "radius": 0.5 })),
+                    add_node(
+                        ".",
+                        "Collision",
+                        "CollisionPolygon3D",
+                        json!({ "polygon": target, "depth": 1.0 }),
+                    ),
+                    attach_script(".", "res://target.gd"),
+                ],
+            },
+        ],
+    }
+}
+
+fn ui_demo() -> Blueprint {
+    let button = json!({ "custom_minimum_size": "Vector2(0, 40)" });
+    Blueprint {
+        name: "Example UI",
+        main_scene: "res://main.tscn",
+        settings: vec![("display/window/stretch/mode", "\"canvas_items\"")],
+        actions: vec![("toggle_fullscreen", vec![key("F11")])],
+        scripts: vec![
+            ExampleScript {
+                path: "res://menu_texts.gd",
+                extends: "Resource",
+                class_name: Some("MenuTexts"),
+                body: MENU_TEXTS_GD,
+            },
+            ExampleScript {
+                path: "res://dialog.gd",
+                extends: "PanelContainer",
+                class_name: Some("InfoDialog"),
+                body: DIALOG_GD,
+            },
+            ExampleScript {
+                path: "res://main.gd",
+                extends: "Control",
+                class_name: None,
+                body: UI_MAIN_GD,
+            },
+        ],
+        resource: (
+            "res://menu_texts.gd",
+            "res://menu_texts.tres",
+            json!({
+                "title": "Example UI",
+                "start_text": "Open dialog",
+                "quit_text": "Quit",
+            }),
+        ),
+        scenes: vec![
+            ExampleScene {
+                path: "res://main.tscn",
+                root_name: "Main",
+                root_type: "Control",
+                operations: vec![
+                    set_properties(".", full_rect()),
+                    add_node(".", "Menu", "VBoxContainer", centered(280, 240)),
+                    add_node(
+                        "Menu",
+                        "Title",
+                        "Label",
+                        json!({ "horizontal_alignment": 1, "theme_override_font_sizes/font_size": 32 }),
+                    ),
+                    add_node("Menu", "Start", "Button", button.clone()),
+                    add_node("Menu", "VolumeLabel", "Label", json!({})),
+                    add_node(
+                        "Menu",
+                        "Volume",
+                        "HSlider",
+                        json!({ "max_value": 1.0, "step": 0.05, "value": 0.8 }),
+                    ),
+                    add_node("Menu", "Quit", "Button", button),
+                    attach_script(".", "res://main.gd"),
+                    connect_signal("Menu/Start", "pressed", ".", "_on_start_pressed"),
+                    connect_signal("Menu/Volume", "value_changed", ".", "_on_volume_changed"),
+                    connect_signal("Menu/Quit", "pressed", ".", "_on_quit_pressed"),
+                ],
+            },
+            ExampleScene {
+                path: "res://dialog.tscn",
+                root_name: "Dialog",
+                root_type: "PanelContainer",
+                operations: vec![
+                    set_properties(".", centered(360, 160)),
+                    add_node(".", "Content", "VBoxContainer", json!({})),
+                    add_node(
+                        "Content",
+                        "Message",
+                        "Label",
+                        json!({
+                            "text": "\"This dialog is a scene of its own, instantiated by main.gd.\"",
+                            "autowrap_mode": 3,
+                            "size_flags_vertical": 3,
+                        }),
+                    ),
+                    add_node("Content", "Close", "Button", json!({ "text": "\"Close\"" })),
+                    attach_script(".", "res://dialog.gd"),
+                    connect_signal("Content/Close", "pressed", ".", "_on_close_pressed"),
+                ],
+            },
+        ],
+    }
+}
+
+const PLAYER_STATS_GD: &str = r#"@export var speed: float = 200.0
+@export var jump_velocity: float = -400.0
+"#;
+
+const PLATFORMER_PLAYER_GD: &str = r#"const STATS: PlayerStats = preload("res://player_stats.tres")
+
+var gravity: float = ProjectSettings.get_setting("physics/2d/default_gravity")
+
+
+func _physics_process(delta: float) -> void:
+	if not is_on_floor():
+		velocity.y += gravity * delta
+	if Input.is_action_just_pressed("jump") and is_on_floor():
+		velocity.y = STATS.jump_velocity
+	velocity.x = Input.get_axis("move_left", "move_right") * STATS.speed
+	move_and_slide()
+"#;
+
+const COIN_GD: &str = r#"signal collected
+
+
+func _on_body_entered(body: Node2D) -> void:
+	if body is CharacterBody2D:
+		collected.emit()
+		queue_free()
+"#;
+
+const PLATFORMER_MAIN_GD: &str = r#"const COIN_SCENE: PackedScene = preload("res://coin.tscn")
+const COIN_POSITIONS := [Vector2(520, 400), Vector2(860, 280), Vector2(1000, 580)]
+
+var score: int = 0
+
+@onready var score_label: Label = $HUD/ScoreLabel
+
+
+func _ready() -> void:
+	for at in COIN_POSITIONS:
+		var coin: Coin = COIN_SCENE.instantiate()
+		coin.position = at
+		coin.collected.connect(_on_coin_collected)
+		add_child(coin)
+
+
+func _on_coin_collected() -> void:
+	score += 1
+	score_label.text = "Coins: %d" % score
+"#;
+
+const WEAPON_STATS_GD: &str = r#"@export var damage: int = 1
+@export var fire_rate: float = 2.0
+"#;
+
+const FPS_PLAYER_GD: &str = r#"const WEAPON: WeaponStats = preload("res://pistol.tres")
+const SPEED: float = 5.0
+const JUMP_VELOCITY: float = 4.5
+const MOUSE_SENSITIVITY: float = 0.003
+
+var gravity: float = ProjectSettings.get_setting("physics/3d/default_gravity")
+var cooldown: float = 0.0
+
+@onready var camera: Camera3D = $Camera
+@onready var aim: RayCast3D = $Camera/Aim
+
+
+func _ready() -> void:
+	Input.mouse_mode = Input.MOUSE_MODE_CAPTURED
+
+
+func _unhandled_input(event: InputEvent) -> void:
+	var motion := event as InputEventMouseMotion
+	if motion and Input.mouse_mode == Input.MOUSE_MODE_CAPTURED:
+		rotate_y(-motion.relative.x * MOUSE_SENSITIVITY)
+		camera.rotate_x(-motion.relative.y * MOUSE_SENSITIVITY)
+		camera.rotation.x = clampf(camera.rotation.x, -1.4, 1.4)
+	elif event.is_action_pressed("ui_cancel"):
+		Input.mouse_mode = Input.MOUSE_MODE_VISIBLE
+	elif event.is_action_pressed("shoot"):
+		Input.mouse_mode = Input.MOUSE_MODE_CAPTURED
+
+
+func _physics_process(delta: float) -> void:
+	cooldown -= delta
+	if not is_on_floor():
+		velocity.y -= gravity * delta
+	if Input.is_action_just_pressed("jump") and is_on_floor():
+		velocity.y = JUMP_VELOCITY
+	var input := Input.get_vector("move_left", "move_right", "move_forward", "move_back")
+	var direction := (transform.basis * Vector3(input.x, 0, input.y)).normalized()
+	velocity.x = direction.x * SPEED
+	velocity.z = direction.z * SPEED
+	move_and_slide()
+	if Input.is_action_pressed("shoot") and cooldown <= 0.0:
+		shoot()
+
+
+func shoot() -> void:
+	cooldown = 1.0 / WEAPON.fire_rate
+	var target := aim.get_collider() as Target
+	if target:
+		target.hit(WEAPON.damage)
+"#;
+
+const TARGET_GD: &str = r#"signal destroyed
+
+@export var health: int = 3
+
+
+func hit(damage: int) -> void:
+	health -= damage
+	if health <= 0:
+		destroyed.emit()
+		queue_free()
+"#;
+
+const FPS_MAIN_GD: &str = r#"const TARGET_SCENE: PackedScene = preload("res://target.tscn")
+const TARGET_POSITIONS := [Vector3(-4, 1, -8), Vector3(0, 1.5, -12), Vector3(4, 1, -8)]
+
+var remaining: int = 0
+
+@onready var status_label: Label = $HUD/StatusLabel
+
+
+func _ready() -> void:
+	for at in TARGET_POSITIONS:
+		var target: Target = TARGET_SCENE.instantiate()
+		target.position = at
+		target.destroyed.connect(_on_target_destroyed)
+		add_child(target)
+		remaining += 1
+	_update_status()
+
+
+func _on_target_destroyed() -> void:
+	remaining -= 1
+	_update_status()
+
+
+func _update_status() -> void:
+	if remaining > 0:
+		status_label.text = "Targets left: %d" % remaining
+	else:
+		status_label.text = "All targets down!"
+"#;
+
+const MENU_TEXTS_GD: &str = r#"@export var title: String = "Title"
+@export var start_text: String = "Start"
+@export var quit_text: String = "Quit"
+"#;
+
+const DIALOG_GD: &str = r#"signal closed
+
+@onready var close_button: Button = $Content/Close
+
+
+func _ready() -> void:
+	close_button.grab_focus()
+
+
+func _unhandled_input(event: InputEvent) -> void:
+	if event.is_action_pressed("ui_cancel"):
+		get_viewport().set_input_as_handled()
+		_on_close_pressed()
+
+
+func _on_close_pressed() -> void:
+	closed.emit()
+	queue_free()
+"#;
+
+const UI_MAIN_GD: &str = r#"const DIALOG_SCENE: PackedScene = preload("res://dialog.tscn")
+const TEXTS: MenuTexts = preload("res://menu_texts.tres")
+
+@onready var title_label: Label = $Menu/Title
+@onready var start_button: Button = $Menu/Start
+@onready var quit_button: Button = $Menu/Quit
+@onready var volume_label: Label = $Menu/VolumeLabel
+@onready var volume_slider: HSlider = $Menu/Volume
+
+
+func _ready() -> void:
+	title_label.text = TEXTS.title
+	start_button.text = TEXTS.start_text
+	quit_button.text = TEXTS.quit_text
+	_on_volume_changed(volume_slider.value)
+	start_button.grab_focus()
+
+
+func _unhandled_input(event: InputEvent) -> void:
+	if event.is_action_pressed("toggle_fullscreen"):
+		if DisplayServer.window_get_mode() == DisplayServer.WINDOW_MODE_FULLSCREEN:
+			DisplayServer.window_set_mode(DisplayServer.WINDOW_MODE_WINDOWED)
+		else:
+			DisplayServer.window_set_mode(DisplayServer.WINDOW_MODE_FULLSCREEN)
+
+
+func _on_start_pressed() -> void:
+	var dialog: InfoDialog = DIALOG_SCENE.instantiate()
+	dialog.closed.connect(start_button.grab_focus)
+	add_child(dialog)
+
+
+func _on_volume_changed(value: float) -> void:
+	volume_label.text = "Volume: %d%%" % roundi(value * 100)
+	AudioServer.set_bus_volume_db(0, linear_to_db(value))
+
+
+func _on_quit_pressed() -> void:
+	get_tree().quit()
+"#;
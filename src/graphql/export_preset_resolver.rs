@@ -0,0 +1,371 @@
+//! Export Preset Resolver
+//!
+//! Lists and edits the export presets in export_presets.cfg, so builds can be
+//! prepared without opening the editor's Export dialog. New presets start from
+//! a built-in template for their platform or as a copy of an existing preset;
+//! exporter options the templates leave out are filled in with their defaults
+//! by Godot.
+
+use crate::godot::export_presets::{self, ExportPresets};
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::scene_resolver::{quote_godot_string, unquote_godot_string};
+use super::types::*;
+
+const EXPORT_PRESETS_FILE: &str = "export_presets.cfg";
+
+/// `key=value` pairs with raw Godot values
+type Settings = Vec<(String, String)>;
+
+/// Resolve exportPresets query
+pub fn resolve_export_presets(ctx: &GqlContext) -> ExportPresetsResult {
+    match read_presets(ctx) {
+        Ok(presets) => ExportPresetsResult {
+            presets: presets
+                .indices()
+                .into_iter()
+                .map(|index| preset_info(&presets, index))
+                .collect(),
+            error: None,
+        },
+        Err(error) => ExportPresetsResult {
+            presets: Vec::new(),
+            error: Some(*error),
+        },
+    }
+}
+
+/// Resolve createExportPreset mutation
+pub fn resolve_create_export_preset(
+    ctx: &GqlContext,
+    input: &CreateExportPresetInput,
+) -> ExportPresetResult {
+    result(ctx, "createExportPreset", |presets| create(presets, input))
+}
+
+/// Resolve updateExportPreset mutation
+pub fn resolve_update_export_preset(
+    ctx: &GqlContext,
+    input: &UpdateExportPresetInput,
+) -> ExportPresetResult {
+    result(ctx, "updateExportPreset", |presets| update(presets, input))
+}
+
+/// Apply an edit to export_presets.cfg and write it back
+fn result(
+    ctx: &GqlContext,
+    operation: &str,
+    edit: impl FnOnce(&mut ExportPresets) -> Result<usize, Box<GqlStructuredError>>,
+) -> ExportPresetResult {
+    let edited = read_presets(ctx).and_then(|mut presets| {
+        let before = presets.to_cfg();
+        let index = edit(&mut presets)?;
+        let content = presets.to_cfg();
+        if content != before {
+            let path = ctx.project_path.join(EXPORT_PRESETS_FILE);
+            audit::write_file(ctx, operation, &path, content).map_err(|e| {
+                Box::new(GqlStructuredError::new(
+                    "EXPORT_PRESETS_WRITE_FAILED",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to write {}: {}", EXPORT_PRESETS_FILE, e),
+                ))
+            })?;
+            telemetry::record_files_written(1);
+        }
+        Ok(preset_info(&presets, index))
+    });
+    match edited {
+        Ok(preset) => ExportPresetResult {
+            success: true,
+            preset: Some(preset),
+            error: None,
+        },
+        Err(error) => ExportPresetResult {
+            success: false,
+            preset: None,
+            error: Some(*error),
+        },
+    }
+}
+
+fn create(
+    presets: &mut ExportPresets,
+    input: &CreateExportPresetInput,
+) -> Result<usize, Box<GqlStructuredError>> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(invalid("Preset name cannot be empty".to_string()));
+    }
+    if find(presets, name).is_some() {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "EXPORT_PRESET_EXISTS",
+                GqlErrorCategory::Validation,
+                format!("Export preset '{}' already exists", name),
+            )
+            .with_suggestion("updateExportPreset で既存のプリセットを変更してください"),
+        ));
+    }
+
+    let (platform, mut settings, options) = starting_point(presets, input)?;
+    // The first preset of a platform is the one run by one-click deploy
+    let runnable = input
+        .runnable
+        .unwrap_or_else(|| runnable_preset(presets, &platform).is_none());
+    let export_path = match (
+        &input.export_path,
+        export_presets::platform_template(&platform),
+    ) {
+        (Some(path), _) => path.clone(),
+        (None, Some(template)) => format!("build/{}.{}", file_stem(name), template.extension),
+        (None, None) => String::new(),
+    };
+    let mut overrides = vec![
+        ("name", quote_godot_string(name)),
+        ("runnable", runnable.to_string()),
+        ("export_path", quote_godot_string(&export_path)),
+    ];
+    if let Some(features) = &input.custom_features {
+        overrides.push(("custom_features", features_value(features)));
+    }
+    for (key, value) in overrides {
+        match settings.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => settings.push((key.to_string(), value)),
+        }
+    }
+
+    if runnable {
+        clear_runnable(presets, &platform);
+    }
+    Ok(presets.add(&settings, &options))
+}
+
+/// Platform, settings and options a new preset starts from: a copy of
+/// `copyFrom` or the platform's template
+fn starting_point(
+    presets: &ExportPresets,
+    input: &CreateExportPresetInput,
+) -> Result<(String, Settings, Settings), Box<GqlStructuredError>> {
+    if let Some(source) = &input.copy_from {
+        let index = find(presets, source).ok_or_else(|| not_found(source))?;
+        return Ok((
+            string_setting(presets, index, "platform"),
+            owned(&presets.settings(index)),
+            owned(&presets.options(index)),
+        ));
+    }
+    let Some(platform) = &input.platform else {
+        return Err(invalid(
+            "Either platform or copyFrom is required".to_string(),
+        ));
+    };
+    let Some(template) = export_presets::platform_template(platform) else {
+        let platforms: Vec<&str> = export_presets::PLATFORM_TEMPLATES
+            .iter()
+            .map(|t| t.platform)
+            .collect();
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "UNKNOWN_PLATFORM",
+                GqlErrorCategory::Validation,
+                format!("No preset template for platform '{}'", platform),
+            )
+            .with_suggestion(format!(
+                "platform は {} のいずれかを指定するか、copyFrom で既存のプリセットを複製してください",
+                platforms.join(", ")
+            )),
+        ));
+    };
+    // Name and platform first, as Godot writes them
+    let mut settings = vec![
+        ("name".to_string(), String::new()),
+        (
+            "platform".to_string(),
+            quote_godot_string(template.platform),
+        ),
+        ("runnable".to_string(), "false".to_string()),
+    ];
+    settings.extend(owned(export_presets::DEFAULT_SETTINGS));
+    Ok((
+        template.platform.to_string(),
+        settings,
+        owned(template.options),
+    ))
+}
+
+fn update(
+    presets: &mut ExportPresets,
+    input: &UpdateExportPresetInput,
+) -> Result<usize, Box<GqlStructuredError>> {
+    let index = find(presets, &input.name).ok_or_else(|| not_found(&input.name))?;
+    if let Some(new_name) = &input.new_name {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(invalid("Preset name cannot be empty".to_string()));
+        }
+        if find(presets, new_name).is_some_and(|other| other != index) {
+            return Err(Box::new(GqlStructuredError::new(
+                "EXPORT_PRESET_EXISTS",
+                GqlErrorCategory::Validation,
+                format!("Export preset '{}' already exists", new_name),
+            )));
+        }
+        presets.set(index, "name", &quote_godot_string(new_name));
+    }
+    if let Some(export_path) = &input.export_path {
+        presets.set(index, "export_path", &quote_godot_string(export_path));
+    }
+    if let Some(features) = &input.custom_features {
+        presets.set(index, "custom_features", &features_value(features));
+    }
+    for (key, filter) in [
+        ("include_filter", &input.include_filter),
+        ("exclude_filter", &input.exclude_filter),
+    ] {
+        if let Some(filter) = filter {
+            presets.set(index, key, &quote_godot_string(filter));
+        }
+    }
+    if let Some(runnable) = input.runnable {
+        if runnable {
+            let platform = string_setting(presets, index, "platform");
+            clear_runnable(presets, &platform);
+        }
+        presets.set(index, "runnable", &runnable.to_string());
+    }
+    for option in input.options.iter().flatten() {
+        if option.key.trim().is_empty() || option.value.trim().is_empty() {
+            return Err(invalid(format!(
+                "Option '{}' needs a key and a value",
+                option.key
+            )));
+        }
+        presets.set_option(index, option.key.trim(), &option.value);
+    }
+    Ok(index)
+}
+
+fn read_presets(ctx: &GqlContext) -> Result<ExportPresets, Box<GqlStructuredError>> {
+    let path = ctx.project_path.join(EXPORT_PRESETS_FILE);
+    if !ctx.fs.exists(&path) {
+        return Ok(ExportPresets::parse(""));
+    }
+    let content = ctx.fs.read_to_string(&path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FILE_READ_ERROR",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read {}: {}", EXPORT_PRESETS_FILE, e),
+        ))
+    })?;
+    Ok(ExportPresets::parse(&content))
+}
+
+fn preset_info(presets: &ExportPresets, index: usize) -> ExportPreset {
+    let features = string_setting(presets, index, "custom_features");
+    ExportPreset {
+        index: index as i32,
+        name: string_setting(presets, index, "name"),
+        platform: string_setting(presets, index, "platform"),
+        runnable: presets.get(index, "runnable") == Some("true"),
+        export_path: string_setting(presets, index, "export_path"),
+        custom_features: features
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
+            .collect(),
+        export_filter: string_setting(presets, index, "export_filter"),
+        include_filter: string_setting(presets, index, "include_filter"),
+        exclude_filter: string_setting(presets, index, "exclude_filter"),
+        options: presets
+            .options(index)
+            .into_iter()
+            .map(|(key, value)| ExportPresetOption {
+                key: key.to_string(),
+                value: value.to_string(),
+            })
+            .collect(),
+    }
+}
+
+/// Index of the preset with this name
+fn find(presets: &ExportPresets, name: &str) -> Option<usize> {
+    presets
+        .indices()
+        .into_iter()
+        .find(|&index| string_setting(presets, index, "name") == name.trim())
+}
+
+/// Runnable preset of a platform
+fn runnable_preset(presets: &ExportPresets, platform: &str) -> Option<usize> {
+    presets.indices().into_iter().find(|&index| {
+        presets.get(index, "runnable") == Some("true")
+            && string_setting(presets, index, "platform") == platform
+    })
+}
+
+/// Only one preset per platform is runnable
+fn clear_runnable(presets: &mut ExportPresets, platform: &str) {
+    while let Some(index) = runnable_preset(presets, platform) {
+        presets.set(index, "runnable", "false");
+    }
+}
+
+fn string_setting(presets: &ExportPresets, index: usize, key: &str) -> String {
+    presets
+        .get(index, key)
+        .map(unquote_godot_string)
+        .unwrap_or_default()
+}
+
+/// `custom_features` value: features separated by commas
+fn features_value(features: &[String]) -> String {
+    let features: Vec<&str> = features
+        .iter()
+        .map(|f| f.trim())
+        .filter(|f| !f.is_empty())
+        .collect();
+    quote_godot_string(&features.join(","))
+}
+
+/// Preset name as a file name ("Windows Desktop" -> "windows_desktop")
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn owned(pairs: &[(&str, &str)]) -> Settings {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn not_found(name: &str) -> Box<GqlStructuredError> {
+    Box::new(
+        GqlStructuredError::new(
+            "EXPORT_PRESET_NOT_FOUND",
+            GqlErrorCategory::Validation,
+            format!("Export preset '{}' not found", name),
+        )
+        .with_suggestion("exportPresets でプリセット名を確認してください"),
+    )
+}
+
+fn invalid(message: String) -> Box<GqlStructuredError> {
+    Box::new(GqlStructuredError::new(
+        "INVALID_INPUT",
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
@@ -0,0 +1,527 @@
+//! Feature Pack Resolver
+//!
+//! Installs community "feature packs" — a `feature_pack.toml` manifest plus
+//! scenes, scripts and resources authored as a small Godot project — from a
+//! local directory or a git URL. The pack's files are copied under an install
+//! prefix with their `res://` references rewritten to match, existing project
+//! files are never overwritten unless asked, and every install is recorded in
+//! `res://.godot-mcp/feature_packs.json` so reinstalling a newer version
+//! updates the same files and drops the ones the pack no longer ships.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::path_utils::{self, ResPath};
+use crate::time_utils::now_millis;
+
+use super::audit;
+use super::context::GqlContext;
+use super::types::*;
+
+/// Manifest at the root of every pack
+pub const MANIFEST_FILE: &str = "feature_pack.toml";
+
+/// Installed packs, in install order
+pub const INSTALLED_PACKS_PATH: &str = "res://.godot-mcp/feature_packs.json";
+
+/// Text files whose `res://` references are rewritten on install
+const TEXT_EXTENSIONS: &[&str] = &["tscn", "tres", "gd", "cs", "gdshader", "cfg", "json"];
+
+/// Files Godot regenerates per project; never copied
+const SKIPPED_EXTENSIONS: &[&str] = &["import", "uid"];
+
+#[derive(Debug, Deserialize)]
+struct PackManifest {
+    name: String,
+    version: String,
+    description: Option<String>,
+    /// Default install folder (res:// path)
+    prefix: Option<String>,
+}
+
+/// Resolve featurePacks query
+pub fn resolve_feature_packs(ctx: &GqlContext) -> Vec<InstalledFeaturePack> {
+    read_installed(ctx)
+}
+
+/// Resolve installFeaturePack mutation
+pub fn resolve_install_feature_pack(
+    ctx: &GqlContext,
+    input: &InstallFeaturePackInput,
+) -> InstallFeaturePackResult {
+    match install(ctx, input) {
+        Ok(result) => result,
+        Err(error) => InstallFeaturePackResult {
+            success: false,
+            dry_run: input.dry_run,
+            pack: None,
+            files: Vec::new(),
+            conflicts: Vec::new(),
+            error: Some(*error),
+        },
+    }
+}
+
+fn install(
+    ctx: &GqlContext,
+    input: &InstallFeaturePackInput,
+) -> Result<InstallFeaturePackResult, Box<GqlStructuredError>> {
+    let checkout = fetch_source(ctx, &input.source, input.git_ref.as_deref())?;
+    let pack_dir = checkout.path();
+    let manifest = read_manifest(pack_dir)?;
+
+    let mut installed = read_installed(ctx);
+    let previous = installed.iter().position(|p| p.name == manifest.name);
+    let prefix = input
+        .prefix
+        .clone()
+        .or_else(|| previous.map(|i| installed[i].prefix.clone()))
+        .or_else(|| manifest.prefix.clone())
+        .unwrap_or_else(|| format!("res://features/{}", manifest.name));
+    let prefix = ResPath::new(prefix.trim_end_matches('/')).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FEATURE_PACK_INVALID",
+            GqlErrorCategory::Validation,
+            format!("Invalid install prefix: {}", e),
+        ))
+    })?;
+
+    let mut sources = Vec::new();
+    collect_pack_files(pack_dir, pack_dir, &mut sources).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FEATURE_PACK_READ_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read pack files: {}", e),
+        ))
+    })?;
+    let relative: BTreeSet<String> = sources.iter().map(|(rel, _)| rel.clone()).collect();
+
+    // Target res:// path -> content to write
+    let mut targets = BTreeMap::new();
+    for (rel, path) in &sources {
+        let bytes = fs::read(path).map_err(|e| {
+            Box::new(GqlStructuredError::new(
+                "FEATURE_PACK_READ_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to read {}: {}", rel, e),
+            ))
+        })?;
+        let content = match (is_text_file(rel), String::from_utf8(bytes)) {
+            (true, Ok(text)) => {
+                rewrite_references(&text, &relative, prefix.relative()).into_bytes()
+            }
+            (_, Ok(text)) => text.into_bytes(),
+            (_, Err(e)) => e.into_bytes(),
+        };
+        targets.insert(format!("{}/{}", prefix.as_res_path(), rel), content);
+    }
+
+    let owned: BTreeSet<&str> = previous
+        .map(|i| installed[i].files.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    let mut files = Vec::new();
+    let mut conflicts = Vec::new();
+    for (res_path, content) in &targets {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+        let action = match ctx.fs.read(&fs_path) {
+            Err(_) => FeaturePackFileAction::Create,
+            Ok(existing) if existing == *content => FeaturePackFileAction::Unchanged,
+            Ok(_) => {
+                if !owned.contains(res_path.as_str()) && !input.overwrite {
+                    conflicts.push(res_path.clone());
+                }
+                FeaturePackFileAction::Update
+            }
+        };
+        files.push(FeaturePackFileChange {
+            path: res_path.clone(),
+            action,
+        });
+    }
+    for stale in owned.iter().filter(|p| !targets.contains_key(**p)) {
+        // feature_packs.json may have been edited; never delete outside the project
+        path_utils::to_fs_path(&ctx.project_path, stale).map_err(|e| {
+            Box::new(GqlStructuredError::new(
+                "FEATURE_PACK_INVALID",
+                GqlErrorCategory::Validation,
+                format!("Invalid installed file path {}: {}", stale, e),
+            ))
+        })?;
+        files.push(FeaturePackFileChange {
+            path: stale.to_string(),
+            action: FeaturePackFileAction::Remove,
+        });
+    }
+
+    let pack = InstalledFeaturePack {
+        name: manifest.name,
+        version: manifest.version,
+        description: manifest.description,
+        source: input.source.clone(),
+        prefix: prefix.as_res_path(),
+        installed_at: now_millis().to_string(),
+        files: targets.keys().cloned().collect(),
+    };
+
+    if !conflicts.is_empty() {
+        let error = GqlStructuredError::new(
+            "FEATURE_PACK_CONFLICT",
+            GqlErrorCategory::Validation,
+            format!(
+                "{} existing file(s) would be overwritten: {}",
+                conflicts.len(),
+                conflicts.join(", ")
+            ),
+        )
+        .with_suggestion(
+            "別の prefix を指定するか、上書きする場合は overwrite: true を指定してください",
+        );
+        return Ok(InstallFeaturePackResult {
+            success: false,
+            dry_run: input.dry_run,
+            pack: Some(pack),
+            files,
+            conflicts,
+            error: Some(error),
+        });
+    }
+
+    if !input.dry_run {
+        write_pack_files(ctx, &targets, &files)?;
+        match previous {
+            Some(i) => installed[i] = pack.clone(),
+            None => installed.push(pack.clone()),
+        }
+        write_installed(ctx, &installed).map_err(|e| {
+            Box::new(GqlStructuredError::new(
+                "FEATURE_PACK_WRITE_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to record installed pack: {}", e),
+            ))
+        })?;
+    }
+
+    Ok(InstallFeaturePackResult {
+        success: true,
+        dry_run: input.dry_run,
+        pack: Some(pack),
+        files,
+        conflicts,
+        error: None,
+    })
+}
+
+fn write_pack_files(
+    ctx: &GqlContext,
+    targets: &BTreeMap<String, Vec<u8>>,
+    files: &[FeaturePackFileChange],
+) -> Result<(), Box<GqlStructuredError>> {
+    let write_error = |path: &str, e: std::io::Error| {
+        Box::new(GqlStructuredError::new(
+            "FEATURE_PACK_WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to write {}: {}", path, e),
+        ))
+    };
+
+    let mut written = 0;
+    for file in files {
+        let fs_path = path_utils::to_fs_path(&ctx.project_path, &file.path).map_err(|e| {
+            write_error(
+                &file.path,
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()),
+            )
+        })?;
+        match file.action {
+            FeaturePackFileAction::Unchanged => {}
+            FeaturePackFileAction::Remove => {
+                if ctx.fs.exists(&fs_path) {
+                    ctx.fs
+                        .remove_file(&fs_path)
+                        .map_err(|e| write_error(&file.path, e))?;
+                }
+            }
+            FeaturePackFileAction::Create | FeaturePackFileAction::Update => {
+                if let Some(parent) = fs_path.parent() {
+                    ctx.fs
+                        .create_dir_all(parent)
+                        .map_err(|e| write_error(&file.path, e))?;
+                }
+                let content = &targets[&file.path];
+                match std::str::from_utf8(content) {
+                    Ok(text) if is_text_file(&file.path) => {
+                        audit::write_file(ctx, "installFeaturePack", &fs_path, text)
+                    }
+                    _ => audit::write_binary_file(ctx, "installFeaturePack", &fs_path, content),
+                }
+                .map_err(|e| write_error(&file.path, e))?;
+                written += 1;
+            }
+        }
+    }
+    crate::telemetry::record_files_written(written);
+    Ok(())
+}
+
+/// Pack checkout; cloned sources are deleted when dropped
+enum PackSource {
+    Local(PathBuf),
+    Cloned(PathBuf),
+}
+
+impl PackSource {
+    fn path(&self) -> &Path {
+        match self {
+            PackSource::Local(path) | PackSource::Cloned(path) => path,
+        }
+    }
+}
+
+impl Drop for PackSource {
+    fn drop(&mut self) {
+        if let PackSource::Cloned(path) = self {
+            let _ = fs::remove_dir_all(path);
+        }
+    }
+}
+
+fn fetch_source(
+    ctx: &GqlContext,
+    source: &str,
+    git_ref: Option<&str>,
+) -> Result<PackSource, Box<GqlStructuredError>> {
+    // Both are passed to git as arguments; a leading dash would be read as an option
+    if let Some(value) = std::iter::once(source)
+        .chain(git_ref)
+        .find(|value| value.starts_with('-'))
+    {
+        return Err(Box::new(GqlStructuredError::new(
+            "INVALID_INPUT",
+            GqlErrorCategory::Validation,
+            format!("Invalid pack source or git ref: '{}'", value),
+        )));
+    }
+    if !is_git_url(source) {
+        let path = ctx.project_path.join(source);
+        if !path.is_dir() {
+            return Err(Box::new(GqlStructuredError::new(
+                "FEATURE_PACK_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Pack directory not found: {}", path.display()),
+            )));
+        }
+        return Ok(PackSource::Local(path));
+    }
+
+    let checkout = std::env::temp_dir().join(format!(
+        "godot-mcp-pack-{}-{}",
+        std::process::id(),
+        now_millis()
+    ));
+    let mut command = Command::new("git");
+    // Fail instead of waiting for credentials nobody can type
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    command.args(["clone", "--depth", "1", "--quiet"]);
+    if let Some(git_ref) = git_ref {
+        command.args(["--branch", git_ref]);
+    }
+    let output = command.arg("--").arg(source).arg(&checkout).output();
+    // Owns the directory from here on, so failed clones are cleaned up too
+    let checkout = PackSource::Cloned(checkout);
+    match output {
+        Ok(output) if output.status.success() => Ok(checkout),
+        Ok(output) => Err(Box::new(GqlStructuredError::new(
+            "FEATURE_PACK_FETCH_FAILED",
+            GqlErrorCategory::FileSystem,
+            format!(
+                "git clone {} failed: {}",
+                source,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ))),
+        Err(e) => Err(Box::new(
+            GqlStructuredError::new(
+                "FEATURE_PACK_FETCH_FAILED",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to run git: {}", e),
+            )
+            .with_suggestion("git をインストールして PATH に追加してください"),
+        )),
+    }
+}
+
+fn is_git_url(source: &str) -> bool {
+    ["https://", "http://", "ssh://", "git://", "git@"]
+        .iter()
+        .any(|scheme| source.starts_with(scheme))
+        || source.ends_with(".git")
+}
+
+fn read_manifest(pack_dir: &Path) -> Result<PackManifest, Box<GqlStructuredError>> {
+    let invalid = |message: String| {
+        Box::new(GqlStructuredError::new(
+            "FEATURE_PACK_INVALID",
+            GqlErrorCategory::Validation,
+            message,
+        ))
+    };
+    let content = fs::read_to_string(pack_dir.join(MANIFEST_FILE))
+        .map_err(|e| invalid(format!("Cannot read {}: {}", MANIFEST_FILE, e)))?;
+    let manifest: PackManifest = toml::from_str(&content)
+        .map_err(|e| invalid(format!("Invalid {}: {}", MANIFEST_FILE, e)))?;
+
+    let valid_name = !manifest.name.is_empty()
+        && manifest
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid_name {
+        return Err(invalid(format!(
+            "Pack name '{}' must be non-empty and use only letters, digits, '_' or '-'",
+            manifest.name
+        )));
+    }
+    if manifest.version.trim().is_empty() {
+        return Err(invalid("Pack version must not be empty".to_string()));
+    }
+    Ok(manifest)
+}
+
+/// Pack files as (relative path with '/', filesystem path); skips the manifest,
+/// hidden entries (.git, .godot) and per-project files
+fn collect_pack_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, PathBuf)>,
+) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        // Links could pull in files from outside the pack, or loop forever
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.file_type().is_symlink() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{} is a symbolic link", relative),
+            ));
+        }
+        if metadata.is_dir() {
+            collect_pack_files(root, &path, files)?;
+            continue;
+        }
+        let skipped = relative == MANIFEST_FILE
+            || relative == "project.godot"
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| SKIPPED_EXTENSIONS.contains(&e));
+        if !skipped {
+            files.push((relative, path));
+        }
+    }
+    Ok(())
+}
+
+fn is_text_file(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| TEXT_EXTENSIONS.contains(&ext))
+}
+
+/// Point `res://` references to pack files (or folders) at their installed
+/// location, and drop `uid="uid://..."` attributes so the copies don't clash
+/// with UIDs already in the project (Godot assigns new ones on import)
+fn rewrite_references(content: &str, files: &BTreeSet<String>, prefix: &str) -> String {
+    let in_pack = |path: &str| {
+        let dir = format!("{}/", path.trim_end_matches('/'));
+        files.contains(path) || files.iter().any(|f| f.starts_with(&dir))
+    };
+    let res_path = Regex::new(r#"res://([^"'\s)\]]*)"#).unwrap();
+    let rewritten = res_path.replace_all(content, |c: &regex::Captures| {
+        let path = &c[1];
+        if !path.is_empty() && in_pack(path) {
+            format!("res://{}/{}", prefix, path)
+        } else {
+            c[0].to_string()
+        }
+    });
+    let uid = Regex::new(r#" uid="uid://[^"]*""#).unwrap();
+    uid.replace_all(&rewritten, "").into_owned()
+}
+
+fn read_installed(ctx: &GqlContext) -> Vec<InstalledFeaturePack> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INSTALLED_PACKS_PATH);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_installed(ctx: &GqlContext, packs: &[InstalledFeaturePack]) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, INSTALLED_PACKS_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(packs)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_references() {
+        let files: BTreeSet<String> = ["scenes/box.tscn", "scripts/box.gd", "art/box.png"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let scene = r#"[gd_scene load_steps=3 format=3 uid="uid://abc"]
+
+[ext_resource type="Script" uid="uid://def" path="res://scripts/box.gd" id="1"]
+[ext_resource type="Texture2D" path="res://art/box.png" id="2"]
+[ext_resource type="Theme" path="res://ui/theme.tres" id="3"]
+"#;
+        let rewritten = rewrite_references(scene, &files, "features/box");
+        assert!(rewritten.starts_with("[gd_scene load_steps=3 format=3]"));
+        assert!(rewritten.contains(
+            r#"[ext_resource type="Script" path="res://features/box/scripts/box.gd" id="1"]"#
+        ));
+        assert!(rewritten.contains(r#"path="res://features/box/art/box.png""#));
+        // Not part of the pack: left to the project
+        assert!(rewritten.contains(r#"path="res://ui/theme.tres""#));
+
+        let script = "var dir = \"res://art/\"\nvar scene = preload(\"res://scenes/box.tscn\")\n";
+        assert_eq!(
+            rewrite_references(script, &files, "features/box"),
+            "var dir = \"res://features/box/art/\"\nvar scene = preload(\"res://features/box/scenes/box.tscn\")\n"
+        );
+    }
+
+    #[test]
+    fn test_is_git_url() {
+        assert!(is_git_url("https://github.com/user/pack"));
+        assert!(is_git_url("git@github.com:user/pack.git"));
+        assert!(is_git_url("../packs/dialogue.git"));
+        assert!(!is_git_url("../packs/dialogue"));
+        assert!(!is_git_url("/home/user/packs/dialogue"));
+    }
+}
@@ -0,0 +1,166 @@
+//! gdtoolkit integration
+//!
+//! Locates `gdlint` and `gdformat` from [gdtoolkit](https://github.com/Scony/godot-gdscript-toolkit)
+//! in a configured virtualenv (`[gdtoolkit] venv` in the config file, see
+//! [`set_venv`]) or on `PATH`, runs them and parses their output. Every
+//! caller treats a missing tool as "use the built-in behavior".
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::RwLock;
+
+/// Virtualenv searched before `PATH`
+static VENV: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Set the virtualenv gdtoolkit is installed in (`None` searches `PATH` only)
+pub fn set_venv(venv: Option<PathBuf>) {
+    *VENV.write().unwrap_or_else(|e| e.into_inner()) = venv;
+}
+
+/// Path of a gdtoolkit executable (`gdlint`, `gdformat`), if installed
+pub fn find_tool(name: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+
+    let venv = VENV.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let venv_dirs = venv
+        .into_iter()
+        .flat_map(|venv| [venv.join("bin"), venv.join("Scripts")]);
+    let path_dirs = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    venv_dirs
+        .chain(path_dirs)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// One problem reported by gdlint
+#[derive(Debug, Clone, PartialEq)]
+pub struct GdlintProblem {
+    pub file: PathBuf,
+    pub line: usize,
+    /// `true` for `Error:`, `false` for `Warning:`
+    pub is_error: bool,
+    /// Check name, e.g. `function-name`
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Run gdlint on the given files
+///
+/// gdlint exits non-zero when it finds problems, so the exit status is only
+/// an error when nothing could be parsed from the output.
+pub fn run_gdlint(tool: &Path, files: &[PathBuf]) -> Result<Vec<GdlintProblem>, String> {
+    let output = Command::new(tool)
+        .args(files)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", tool.display(), e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let problems: Vec<GdlintProblem> = stderr
+        .lines()
+        .chain(stdout.lines())
+        .filter_map(parse_gdlint_line)
+        .collect();
+
+    if problems.is_empty() && !output.status.success() {
+        let message = stderr.trim();
+        return Err(if message.is_empty() {
+            format!("gdlint exited with {}", output.status)
+        } else {
+            message.to_string()
+        });
+    }
+    Ok(problems)
+}
+
+/// `path/to/file.gd:12: Error: Function name "Foo" is not valid (function-name)`
+fn parse_gdlint_line(line: &str) -> Option<GdlintProblem> {
+    let (location, rest) = line
+        .split_once(": Error: ")
+        .map(|(l, r)| (l, (true, r)))
+        .or_else(|| line.split_once(": Warning: ").map(|(l, r)| (l, (false, r))))?;
+    let (file, line_number) = location.rsplit_once(':')?;
+    let line_number = line_number.trim().parse().ok()?;
+    let (is_error, message) = rest;
+
+    let (message, code) = match message.trim_end().strip_suffix(')') {
+        Some(body) => match body.rsplit_once(" (") {
+            Some((text, code)) if !code.is_empty() && !code.contains(' ') => {
+                (text.to_string(), Some(code.to_string()))
+            }
+            _ => (message.trim_end().to_string(), None),
+        },
+        None => (message.trim_end().to_string(), None),
+    };
+
+    Some(GdlintProblem {
+        file: PathBuf::from(file),
+        line: line_number,
+        is_error,
+        code,
+        message,
+    })
+}
+
+/// Format GDScript source with `gdformat -` (stdin to stdout)
+pub fn run_gdformat(tool: &Path, source: &str) -> Result<String, String> {
+    let mut child = Command::new(tool)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", tool.display(), e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(source.as_bytes())
+            .map_err(|e| format!("Failed to write to gdformat: {}", e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run {}: {}", tool.display(), e))?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if message.is_empty() {
+            format!("gdformat exited with {}", output.status)
+        } else {
+            message
+        });
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("gdformat output is not UTF-8: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gdlint_line() {
+        let problem = parse_gdlint_line(
+            "/p/player.gd:12: Error: Function name \"Foo\" is not valid (function-name)",
+        )
+        .unwrap();
+        assert_eq!(problem.file, PathBuf::from("/p/player.gd"));
+        assert_eq!(problem.line, 12);
+        assert!(problem.is_error);
+        assert_eq!(problem.code.as_deref(), Some("function-name"));
+        assert_eq!(problem.message, "Function name \"Foo\" is not valid");
+
+        let problem = parse_gdlint_line("C:\\p\\a.gd:3: Warning: Unused argument").unwrap();
+        assert_eq!(problem.file, PathBuf::from("C:\\p\\a.gd"));
+        assert!(!problem.is_error);
+        assert_eq!(problem.code, None);
+
+        assert!(parse_gdlint_line("Failure: 2 problems found").is_none());
+    }
+}
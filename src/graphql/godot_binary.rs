@@ -0,0 +1,641 @@
+//! Godot executable discovery
+//!
+//! Finds the Godot binary editor tools run, trying in order: a path set for
+//! the session ([`set_override`], from `setGodotPath` or `--godot`), the
+//! `GODOT` environment variable (`GODOT_PATH` and `GODOT_BIN` are read too),
+//! `godot`/`godot4` on `PATH`, then common install locations. A
+//! `.godot-version` file in the project root (`4.3`, `4.3.stable`) pins the
+//! version: discovered binaries whose `--version` doesn't match are skipped.
+//! A path set for the session is used as is.
+//!
+//! [`check_script`] runs a found binary's own parser over a script, and
+//! [`run_headless`] runs it headless for other one-off jobs.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Version pin file in the project root
+pub const VERSION_FILE: &str = ".godot-version";
+
+/// Environment variables naming the Godot executable, in order of precedence
+pub const ENV_VARS: &[&str] = &["GODOT", "GODOT_PATH", "GODOT_BIN"];
+
+/// Executable names looked up on `PATH`
+const PATH_NAMES: &[&str] = &["godot", "godot4", "Godot"];
+
+/// Path set for this session, tried before anything else
+static OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Where a Godot binary was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinarySource {
+    /// Set with `setGodotPath` or `--godot`
+    Override,
+    /// One of [`ENV_VARS`]
+    Environment,
+    /// On `PATH`
+    Path,
+    /// A common install location
+    InstallDir,
+}
+
+/// A Godot binary and how it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct GodotBinary {
+    pub path: PathBuf,
+    pub source: BinarySource,
+    /// `--version` output (e.g., "4.3.stable.official.77dcf97d8"); only
+    /// queried when a version is pinned or the caller asks
+    pub version: Option<String>,
+}
+
+/// Set the Godot executable for this session (`None` goes back to discovery)
+pub fn set_override(path: Option<PathBuf>) {
+    *OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Path set with [`set_override`]
+pub fn override_path() -> Option<PathBuf> {
+    OVERRIDE.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Version pinned in the project's `.godot-version`, if any
+pub fn pinned_version(project_root: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(project_root.join(VERSION_FILE)).ok()?;
+    let pin = content.lines().next()?.trim();
+    let pin = pin.strip_prefix('v').unwrap_or(pin);
+    (!pin.is_empty()).then(|| pin.to_string())
+}
+
+/// Whether a `--version` string satisfies a pin: the pin's dot-separated
+/// parts are a prefix of the version's ("4.3" matches "4.3.1.stable.official")
+pub fn version_matches(version: &str, pin: &str) -> bool {
+    let version: Vec<&str> = version.trim().split('.').collect();
+    let pin: Vec<&str> = pin.trim().split('.').collect();
+    pin.len() <= version.len()
+        && pin
+            .iter()
+            .zip(&version)
+            .all(|(p, v)| p.eq_ignore_ascii_case(v))
+}
+
+/// Pin written for a `--version` string: the numbers and status, without
+/// the build ("4.3.stable.official.77dcf97d8" -> "4.3.stable")
+pub fn pin_for_version(version: &str) -> String {
+    let parts: Vec<&str> = version.trim().split('.').collect();
+    let end = parts
+        .iter()
+        .position(|part| part.parse::<u32>().is_err())
+        .map_or(parts.len(), |status| status + 1);
+    parts[..end].join(".")
+}
+
+/// `--version` output of a Godot binary
+pub fn query_version(path: &Path) -> Result<String, String> {
+    let output = Command::new(path)
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", path.display(), e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Godot may print warnings before the version
+    let version = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string);
+    match version {
+        Some(version) if output.status.success() => Ok(version),
+        _ => Err(format!(
+            "{} --version did not print a Godot version",
+            path.display()
+        )),
+    }
+}
+
+/// Every Godot binary discovery can see, in order of precedence
+pub fn candidates() -> Vec<(PathBuf, BinarySource)> {
+    let mut found: Vec<(PathBuf, BinarySource)> = Vec::new();
+    let mut push = |path: PathBuf, source| {
+        if !found.iter().any(|(p, _)| *p == path) {
+            found.push((path, source));
+        }
+    };
+
+    if let Some(path) = override_path().filter(|path| path.is_file()) {
+        push(path, BinarySource::Override);
+    }
+    for var in ENV_VARS {
+        let Some(value) = std::env::var_os(var).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        // A bare name ("godot4") is looked up on PATH
+        let path = PathBuf::from(&value);
+        if path.is_file() {
+            push(path, BinarySource::Environment);
+        } else if let Some(path) = find_on_path(&value.to_string_lossy()) {
+            push(path, BinarySource::Environment);
+        }
+    }
+    for name in PATH_NAMES {
+        if let Some(path) = find_on_path(name) {
+            push(path, BinarySource::Path);
+        }
+    }
+    for dir in install_dirs() {
+        for path in executables_in(&dir) {
+            push(path, BinarySource::InstallDir);
+        }
+    }
+    found
+}
+
+/// The Godot binary to run for a project. With a pinned version, the first
+/// discovered binary of that version.
+pub fn discover(project_root: Option<&Path>) -> Result<GodotBinary, String> {
+    let pin = project_root.and_then(pinned_version);
+    let candidates = candidates();
+    let Some(pin) = pin else {
+        return candidates
+            .into_iter()
+            .next()
+            .map(|(path, source)| GodotBinary {
+                path,
+                source,
+                version: None,
+            })
+            .ok_or_else(|| {
+                "Godot executable not found. Set the GODOT environment variable, add Godot to PATH or use setGodotPath (or --godot)."
+                    .to_string()
+            });
+    };
+
+    let mut seen = Vec::new();
+    for (path, source) in candidates {
+        let version = query_version(&path).ok();
+        if source == BinarySource::Override
+            || version.as_deref().is_some_and(|v| version_matches(v, &pin))
+        {
+            return Ok(GodotBinary {
+                path,
+                source,
+                version,
+            });
+        }
+        seen.push(format!(
+            "{} ({})",
+            path.display(),
+            version.as_deref().unwrap_or("unknown version")
+        ));
+    }
+    Err(if seen.is_empty() {
+        format!(
+            "Godot {} (pinned in {}) not found: no Godot executable found",
+            pin, VERSION_FILE
+        )
+    } else {
+        format!(
+            "Godot {} (pinned in {}) not found; found {}",
+            pin,
+            VERSION_FILE,
+            seen.join(", ")
+        )
+    })
+}
+
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(windows) && !name.ends_with(".exe") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Folders Godot is commonly installed or unpacked to
+fn install_dirs() -> Vec<PathBuf> {
+    let home =
+        std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).map(PathBuf::from);
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if cfg!(windows) {
+        dirs.extend(
+            [
+                r"C:\Program Files\Godot",
+                r"C:\Program Files (x86)\Godot",
+                r"C:\Godot",
+                r"C:\Program Files (x86)\Steam\steamapps\common\Godot Engine",
+            ]
+            .map(PathBuf::from),
+        );
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(local).join("Godot"));
+        }
+        dirs.extend(home.iter().flat_map(|home| {
+            [
+                home.join("Godot"),
+                home.join("Desktop"),
+                home.join("Downloads"),
+                home.join(r"scoop\shims"),
+            ]
+        }));
+    } else if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/Applications"));
+        dirs.extend(home.iter().map(|home| home.join("Applications")));
+        dirs.push(PathBuf::from("/opt/homebrew/bin"));
+        dirs.push(PathBuf::from("/usr/local/bin"));
+    } else {
+        dirs.extend(home.iter().flat_map(|home| {
+            [
+                home.join(".local/bin"),
+                home.join("Applications"),
+                home.join("godot"),
+                home.join(".local/share/flatpak/exports/bin"),
+                home.join(".steam/steam/steamapps/common/Godot Engine"),
+            ]
+        }));
+        dirs.extend(
+            [
+                "/usr/local/bin",
+                "/usr/bin",
+                "/opt/godot",
+                "/snap/bin",
+                "/var/lib/flatpak/exports/bin",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+    dirs
+}
+
+/// Godot executables directly in a folder (`godot4`,
+/// `Godot_v4.3-stable_linux.x86_64`, `Godot.exe`, `Godot.app`,
+/// `org.godotengine.Godot`), newest name first
+fn executables_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<PathBuf> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+            if name.starts_with("godot") && name.ends_with(".app") {
+                let binary = path.join("Contents/MacOS/Godot");
+                return binary.is_file().then_some(binary);
+            }
+            (is_godot_name(&name) && path.is_file()).then_some(path)
+        })
+        .collect();
+    found.sort();
+    found.reverse();
+    found
+}
+
+/// Whether a lowercase file name is a Godot executable's. Other tools
+/// named godot-something (including this server) don't count.
+fn is_godot_name(name: &str) -> bool {
+    let name = if cfg!(windows) {
+        match name.strip_suffix(".exe") {
+            Some(stem) if !stem.ends_with("_console") => stem,
+            _ => return false,
+        }
+    } else {
+        name
+    };
+    if name == "org.godotengine.godot" {
+        return true;
+    }
+    let Some(rest) = name.strip_prefix("godot") else {
+        return false;
+    };
+    // "godot", "godot4", "godot4.3", or a release download "Godot_v4.3-stable_..."
+    let release = rest.starts_with("_v")
+        && ![".zip", ".tar.xz", ".txt"]
+            .iter()
+            .any(|ext| rest.ends_with(ext));
+    release || rest.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// A problem Godot reported while parsing a script
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptProblem {
+    /// `res://` path, when Godot named one
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    /// `true` for errors, `false` for warnings
+    pub is_error: bool,
+    pub message: String,
+}
+
+/// Parse a script with `godot --headless --check-only --script`, killing
+/// Godot after `timeout`
+pub fn check_script(
+    binary: &Path,
+    project_root: &Path,
+    res_path: &str,
+    timeout: Duration,
+) -> Result<Vec<ScriptProblem>, String> {
+    let (status, output) = run_headless(
+        binary,
+        project_root,
+        &["--check-only", "--script", res_path],
+        timeout,
+        &format!("checking {}", res_path),
+    )?;
+
+    let mut problems = parse_check_output(&output);
+    if problems.is_empty() && !status.success() {
+        // Not a parse error: the script could not be loaded at all
+        let message = output
+            .lines()
+            .map(str::trim)
+            .find(|line| line.starts_with("ERROR:") || line.starts_with("SCRIPT ERROR:"))
+            .map(|line| {
+                line.split_once(':')
+                    .map_or(line, |(_, m)| m)
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_else(|| format!("Godot exited with {}", status));
+        problems.push(ScriptProblem {
+            file: Some(res_path.to_string()),
+            line: None,
+            is_error: true,
+            message,
+        });
+    }
+    Ok(problems)
+}
+
+/// Run `godot --headless --path <project_root> <args>`, killing Godot after
+/// `timeout`; returns its exit status and stdout followed by stderr. `task`
+/// names what Godot was doing in the timeout error.
+pub fn run_headless(
+    binary: &Path,
+    project_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+    task: &str,
+) -> Result<(ExitStatus, String), String> {
+    run(binary, &["--headless"], project_root, args, timeout, task)
+}
+
+/// Like [`run_headless`], but with a small window off screen: headless Godot
+/// draws with a dummy renderer, so anything read back from a viewport needs
+/// a display.
+pub fn run_rendering(
+    binary: &Path,
+    project_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+    task: &str,
+) -> Result<(ExitStatus, String), String> {
+    run(
+        binary,
+        &[
+            "--windowed",
+            "--resolution",
+            "64x64",
+            "--position",
+            "-10000,-10000",
+        ],
+        project_root,
+        args,
+        timeout,
+        task,
+    )
+}
+
+fn run(
+    binary: &Path,
+    mode: &[&str],
+    project_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+    task: &str,
+) -> Result<(ExitStatus, String), String> {
+    let mut child = Command::new(binary)
+        .args(mode)
+        .arg("--path")
+        .arg(project_root)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", binary.display(), e))?;
+
+    // Read both pipes while waiting so a chatty Godot can't block on a full pipe
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            let _ = pipe.read_to_string(&mut output);
+            output
+        })
+    })
+    .collect();
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "Godot did not finish {} within {}s",
+                    task,
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("Failed to wait for Godot: {}", e)),
+        }
+    };
+    let output: String = readers
+        .into_iter()
+        .map(|reader| reader.join().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok((status, output))
+}
+
+/// Problems in Godot's output. Godot 4 prints
+///
+/// ```text
+/// SCRIPT ERROR: Parse Error: Identifier "foo" not declared in the current scope.
+///           at: GDScript::reload (res://player.gd:5)
+/// ```
+///
+/// and older builds `res://player.gd:5 - Parse Error: ...`. The
+/// "Failed to load script" error that follows a parse error is skipped.
+pub fn parse_check_output(output: &str) -> Vec<ScriptProblem> {
+    let lines: Vec<&str> = output.lines().map(str::trim).collect();
+    let mut problems = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let (location, rest) = match line.split_once(" - ") {
+            Some((location, rest)) if location.starts_with("res://") => (Some(location), rest),
+            _ => (None, *line),
+        };
+        let (is_error, message) = if let Some(m) = rest.strip_prefix("SCRIPT ERROR:") {
+            (true, m)
+        } else if let Some(m) = rest.strip_prefix("SCRIPT WARNING:") {
+            (false, m)
+        } else if location.is_some() || rest.starts_with("Parse Error:") {
+            (true, rest)
+        } else {
+            continue;
+        };
+        let message = message.trim();
+        let message = message
+            .strip_prefix("Parse Error:")
+            .unwrap_or(message)
+            .trim();
+
+        // The location is on the line itself or on the `at:` line after it
+        let location = location.or_else(|| {
+            lines
+                .get(i + 1)
+                .filter(|next| next.to_ascii_lowercase().starts_with("at:"))
+                .and_then(|next| next.find("res://").map(|start| &next[start..]))
+                .map(|at| at.trim_end_matches(['.', ')']))
+        });
+        let (file, line) = match location.and_then(|l| l.rsplit_once(':')) {
+            Some((file, line)) => (Some(file.to_string()), line.trim().parse().ok()),
+            None => (location.map(str::to_string), None),
+        };
+        problems.push(ScriptProblem {
+            file,
+            line,
+            is_error,
+            message: message.to_string(),
+        });
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches("4.3.stable.official.77dcf97d8", "4.3"));
+        assert!(version_matches(
+            "4.3.stable.official.77dcf97d8",
+            "4.3.stable"
+        ));
+        assert!(version_matches("4.3.1.stable.official", "4.3"));
+        assert!(!version_matches("4.3.stable.official", "4.3.1"));
+        assert!(!version_matches("4.2.2.stable.official", "4.3"));
+        assert!(!version_matches("4.30.stable", "4.3"));
+    }
+
+    #[test]
+    fn test_pin_for_version() {
+        assert_eq!(
+            pin_for_version("4.3.stable.official.77dcf97d8"),
+            "4.3.stable"
+        );
+        assert_eq!(pin_for_version("4.2.2.rc1.mono.official"), "4.2.2.rc1");
+        assert_eq!(pin_for_version("4.3"), "4.3");
+    }
+
+    #[test]
+    fn test_pinned_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(pinned_version(dir.path()), None);
+        std::fs::write(dir.path().join(VERSION_FILE), "v4.3.stable\n").unwrap();
+        assert_eq!(pinned_version(dir.path()).as_deref(), Some("4.3.stable"));
+    }
+
+    #[test]
+    fn test_executables_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let names: &[&str] = if cfg!(windows) {
+            &[
+                "Godot_v4.2-stable_win64.exe",
+                "Godot_v4.3-stable_win64.exe",
+                "Godot_v4.3-stable_win64_console.exe",
+                "readme.txt",
+            ]
+        } else {
+            &[
+                "Godot_v4.2-stable_linux.x86_64",
+                "Godot_v4.3-stable_linux.x86_64",
+                "Godot_v4.3-stable_linux.x86_64.zip",
+                "godot-mcp-rs",
+                "readme.txt",
+            ]
+        };
+        for name in names {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        let found: Vec<String> = executables_in(dir.path())
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(found, vec![names[1].to_string(), names[0].to_string()]);
+    }
+
+    #[test]
+    fn test_parse_check_output() {
+        let output = r#"Godot Engine v4.3.stable.official.77dcf97d8 - https://godotengine.org
+
+SCRIPT ERROR: Parse Error: Identifier "foo" not declared in the current scope.
+          at: GDScript::reload (res://player.gd:5)
+SCRIPT WARNING: The local variable "unused" is declared but never used in the block.
+          at: GDScript::reload (res://player.gd:9)
+ERROR: Failed to load script "res://player.gd" with error "Parse error".
+   at: load (modules/gdscript/gdscript.cpp:2936)
+"#;
+        let problems = parse_check_output(output);
+        assert_eq!(
+            problems,
+            vec![
+                ScriptProblem {
+                    file: Some("res://player.gd".to_string()),
+                    line: Some(5),
+                    is_error: true,
+                    message: "Identifier \"foo\" not declared in the current scope.".to_string(),
+                },
+                ScriptProblem {
+                    file: Some("res://player.gd".to_string()),
+                    line: Some(9),
+                    is_error: false,
+                    message:
+                        "The local variable \"unused\" is declared but never used in the block."
+                            .to_string(),
+                },
+            ]
+        );
+
+        let problems = parse_check_output(
+            "res://enemy.gd:12 - Parse Error: Expected \":\" after function declaration.",
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file.as_deref(), Some("res://enemy.gd"));
+        assert_eq!(problems[0].line, Some(12));
+        assert_eq!(
+            problems[0].message,
+            "Expected \":\" after function declaration."
+        );
+    }
+}
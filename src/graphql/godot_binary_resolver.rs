@@ -0,0 +1,145 @@
+//! Godot Binary Resolver
+//!
+//! Reports which Godot executable editor tools will run (see
+//! [`super::godot_binary`]) and sets it for the session, optionally pinning
+//! its version in the project's `.godot-version`.
+
+use std::path::{Path, PathBuf};
+
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::godot_binary::{self, BinarySource, VERSION_FILE};
+use super::types::*;
+
+/// Resolve godotBinary query
+pub fn resolve_godot_binary(ctx: &GqlContext) -> GodotBinaryResult {
+    report(ctx, None)
+}
+
+/// Resolve setGodotPath mutation
+pub fn resolve_set_godot_path(ctx: &GqlContext, input: &SetGodotPathInput) -> GodotBinaryResult {
+    let path = input
+        .path
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let Some(path) = path else {
+        godot_binary::set_override(None);
+        return report(ctx, None);
+    };
+
+    let path = PathBuf::from(path);
+    if !path.is_file() {
+        return report(
+            ctx,
+            Some(
+                GqlStructuredError::new(
+                    "GODOT_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Godot executable not found: {}", path.display()),
+                )
+                .with_suggestion("Godot 実行ファイルの絶対パスを指定してください"),
+            ),
+        );
+    }
+    let version = match godot_binary::query_version(&path) {
+        Ok(version) => version,
+        Err(message) => {
+            return report(
+                ctx,
+                Some(GqlStructuredError::new(
+                    "INVALID_GODOT_BINARY",
+                    GqlErrorCategory::Validation,
+                    message,
+                )),
+            )
+        }
+    };
+    let path = std::path::absolute(&path).unwrap_or(path);
+    godot_binary::set_override(Some(path));
+
+    if input.pin.unwrap_or(false) {
+        let pin = godot_binary::pin_for_version(&version);
+        let pin_path = ctx.project_path.join(VERSION_FILE);
+        if let Err(e) = audit::write_file(ctx, "setGodotPath", &pin_path, format!("{}\n", pin)) {
+            return report(
+                ctx,
+                Some(GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to write {}: {}", VERSION_FILE, e),
+                )),
+            );
+        }
+        telemetry::record_files_written(1);
+    }
+    report(ctx, None)
+}
+
+/// The binary discovery picks for the project, every candidate it saw and
+/// the pinned version
+fn report(ctx: &GqlContext, error: Option<GqlStructuredError>) -> GodotBinaryResult {
+    let pinned_version = godot_binary::pinned_version(&ctx.project_path);
+    let candidates: Vec<GodotBinaryInfo> = godot_binary::candidates()
+        .into_iter()
+        .map(|(path, source)| {
+            let version = godot_binary::query_version(&path).ok();
+            binary_info(path, source, version, pinned_version.as_deref())
+        })
+        .collect();
+    let (binary, error) = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(found) => (
+            candidates
+                .iter()
+                .find(|c| found.path.as_path() == Path::new(&c.path))
+                .cloned(),
+            error,
+        ),
+        Err(message) => (
+            None,
+            error.or_else(|| {
+                Some(
+                    GqlStructuredError::new(
+                        "GODOT_NOT_FOUND",
+                        GqlErrorCategory::FileSystem,
+                        message,
+                    )
+                    .with_suggestion(
+                        "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+                    ),
+                )
+            }),
+        ),
+    };
+    GodotBinaryResult {
+        binary,
+        pinned_version,
+        candidates,
+        error,
+    }
+}
+
+fn binary_info(
+    path: PathBuf,
+    source: BinarySource,
+    version: Option<String>,
+    pin: Option<&str>,
+) -> GodotBinaryInfo {
+    GodotBinaryInfo {
+        path: path.display().to_string(),
+        source: match source {
+            BinarySource::Override => GodotBinarySource::Override,
+            BinarySource::Environment => GodotBinarySource::Environment,
+            BinarySource::Path => GodotBinarySource::Path,
+            BinarySource::InstallDir => GodotBinarySource::InstallDir,
+        },
+        matches_pin: pin.map(|pin| {
+            version
+                .as_deref()
+                .is_some_and(|v| godot_binary::version_matches(v, pin))
+        }),
+        version,
+    }
+}
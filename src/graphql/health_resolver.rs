@@ -0,0 +1,589 @@
+//! Health Resolver
+//!
+//! Aggregates validation, lint, dependency cycles, unused files, the last test
+//! run, scene contracts and asset budgets into a scored report. Each call can
+//! record a checkpoint in `res://.godot-mcp/health_history.json` so the score
+//! can be tracked over time.
+//!
+//! Asset budgets are declared in `res://.godot-mcp/budgets.toml`:
+//!
+//! ```toml
+//! [[budget]]
+//! name = "textures"
+//! files = "**/*.png"
+//! max_file_kb = 1024
+//! max_total_kb = 51200
+//! ```
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+use super::contracts_resolver::{glob_match, resolve_check_contracts, CONTRACTS_PATH};
+use super::dependency_resolver::{build_dependency_graph, detect_cycles};
+use super::lint_resolver::lint_scripts;
+use super::project_resolver::{collect_project_files, parse_main_scene, to_res_path};
+use super::refactoring_resolver::resolve_autoloads;
+use super::types::*;
+use super::vfs::ProjectFs;
+
+const HISTORY_PATH: &str = "res://.godot-mcp/health_history.json";
+const TEST_RESULTS_PATH: &str = "res://.godot-mcp/test_results.json";
+const BUDGETS_PATH: &str = "res://.godot-mcp/budgets.toml";
+
+/// Oldest checkpoints are dropped beyond this size
+const MAX_CHECKPOINTS: usize = 50;
+
+/// Issues listed per category
+const MAX_ISSUES: usize = 20;
+
+/// Summary of the last runTests call
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestRunSummary {
+    timestamp: String,
+    total_count: i32,
+    passed_count: i32,
+    failed_count: i32,
+    error_count: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BudgetsFile {
+    #[serde(default)]
+    budget: Vec<Budget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Budget {
+    name: String,
+    /// Glob over res:// paths ("**/*.png")
+    files: String,
+    max_file_kb: Option<u64>,
+    max_total_kb: Option<u64>,
+    max_count: Option<usize>,
+}
+
+/// Resolve projectHealth query
+pub fn resolve_project_health(ctx: &GqlContext, record: bool) -> ProjectHealth {
+    let categories = vec![
+        check_validation(ctx),
+        check_lint(ctx),
+        check_dependencies(ctx),
+        check_unused(ctx),
+        check_tests(ctx),
+        check_contracts(ctx),
+        check_budgets(ctx),
+    ];
+
+    let scores: Vec<i32> = categories.iter().filter_map(|c| c.score).collect();
+    let score = if scores.is_empty() {
+        100
+    } else {
+        scores.iter().sum::<i32>() / scores.len() as i32
+    };
+
+    let focus = categories
+        .iter()
+        .filter(|c| c.issue_count > 0 && c.score.is_some())
+        .min_by_key(|c| c.score)
+        .map(|c| c.name.clone());
+
+    let mut history = read_history(ctx);
+    let previous_score = history.last().map(|c| c.score);
+    if record {
+        history.push(HealthCheckpoint {
+            timestamp: now_millis().to_string(),
+            score,
+        });
+        if history.len() > MAX_CHECKPOINTS {
+            let overflow = history.len() - MAX_CHECKPOINTS;
+            history.drain(..overflow);
+        }
+        if let Err(e) = write_json(ctx, HISTORY_PATH, &history) {
+            tracing::warn!("Failed to record health checkpoint: {}", e);
+        }
+    }
+
+    ProjectHealth {
+        score,
+        categories,
+        focus,
+        previous_score,
+        delta: previous_score.map(|previous| score - previous),
+        history,
+    }
+}
+
+/// Remember the outcome of a test run for projectHealth
+pub fn record_test_run(ctx: &GqlContext, result: &TestExecutionResult) {
+    let summary = TestRunSummary {
+        timestamp: now_millis().to_string(),
+        total_count: result.total_count,
+        passed_count: result.passed_count,
+        failed_count: result.failed_count,
+        error_count: result.error_count,
+    };
+    if let Err(e) = write_json(ctx, TEST_RESULTS_PATH, &summary) {
+        tracing::warn!("Failed to record test results: {}", e);
+    }
+}
+
+// ======================
+// Categories
+// ======================
+
+/// Scene parse and structure errors, empty scenes and references to missing files
+fn check_validation(ctx: &GqlContext) -> HealthCategory {
+    let (scenes, _) = collect_project_files(ctx);
+    let mut issues = Vec::new();
+
+    for scene in &scenes {
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &scene.path);
+        if !ctx.fs.is_file(&file_path) {
+            continue;
+        }
+        match ctx.index.scene(&file_path) {
+            Ok(parsed) if parsed.nodes.is_empty() => issues.push(issue(
+                HealthSeverity::Warning,
+                &scene.path,
+                "Scene has no nodes",
+            )),
+            Ok(parsed) => {
+                for structure in parsed.structure_issues() {
+                    issues.push(issue(
+                        HealthSeverity::Error,
+                        &scene.path,
+                        &structure.message(),
+                    ));
+                }
+            }
+            Err(e) => issues.push(issue(
+                HealthSeverity::Error,
+                &scene.path,
+                &format!("Failed to parse scene: {}", e),
+            )),
+        }
+    }
+
+    let (_, edges) = build_dependency_graph(ctx);
+    for edge in &edges {
+        if !edge.to.starts_with("res://") {
+            continue;
+        }
+        let target = path_utils::to_fs_path_unchecked(&ctx.project_path, &edge.to);
+        if !ctx.fs.exists(&target) {
+            issues.push(issue(
+                HealthSeverity::Error,
+                &edge.from,
+                &format!("References missing file {}", edge.to),
+            ));
+        }
+    }
+
+    category(
+        "validation",
+        issues,
+        format!("{} scene(s) checked", scenes.len()),
+    )
+}
+
+/// Script lint: gdlint when installed, else syntax errors, mixed indentation
+/// and leftover TODO/FIXME markers
+fn check_lint(ctx: &GqlContext) -> HealthCategory {
+    let (_, scripts) = collect_project_files(ctx);
+    let paths: Vec<String> = scripts
+        .into_iter()
+        .map(|s| s.path)
+        .filter(|p| p.ends_with(".gd"))
+        .collect();
+    let report = lint_scripts(ctx, &paths);
+
+    let issues = report
+        .diagnostics
+        .into_iter()
+        .map(|d| HealthIssue {
+            severity: d.severity,
+            message: match d.line {
+                Some(line) => format!("Line {}: {}", line, d.message),
+                None => d.message,
+            },
+            file: Some(d.file),
+        })
+        .collect();
+
+    category(
+        "lint",
+        issues,
+        format!("{} script(s) checked ({})", paths.len(), report.tool),
+    )
+}
+
+fn check_dependencies(ctx: &GqlContext) -> HealthCategory {
+    let (nodes, edges) = build_dependency_graph(ctx);
+    let (_, cycles) = detect_cycles(&nodes, &edges);
+
+    let issues = cycles
+        .iter()
+        .map(|cycle| HealthIssue {
+            severity: HealthSeverity::Error,
+            file: cycle.first().cloned(),
+            message: format!("Dependency cycle: {}", cycle.join(" -> ")),
+        })
+        .collect();
+
+    category(
+        "dependencies",
+        issues,
+        format!("{} file(s), {} reference(s)", nodes.len(), edges.len()),
+    )
+}
+
+/// Graph files nothing references, except entry points, files named in
+/// project settings (e.g. the default theme or environment) and global classes
+fn check_unused(ctx: &GqlContext) -> HealthCategory {
+    let (nodes, _) = build_dependency_graph(ctx);
+
+    let mut entry_points: HashSet<String> = resolve_autoloads(ctx)
+        .autoloads
+        .into_iter()
+        .map(|a| a.path)
+        .collect();
+    let project_godot = ctx.project_path.join("project.godot");
+    if let Some(main_scene) = parse_main_scene(ctx.fs.as_ref(), &project_godot) {
+        entry_points.insert(main_scene);
+    }
+    if let Ok(settings) = ctx.fs.read_to_string(&project_godot) {
+        entry_points.extend(
+            settings
+                .split('"')
+                .filter(|value| value.starts_with("res://"))
+                .map(str::to_string),
+        );
+    }
+
+    let mut issues: Vec<HealthIssue> = nodes
+        .iter()
+        .filter(|n| n.is_unused && !entry_points.contains(&n.id))
+        .filter(|n| !is_global_class(ctx, &n.id))
+        .map(|n| issue(HealthSeverity::Warning, &n.id, "Not referenced by any file"))
+        .collect();
+    issues.sort_by(|a, b| a.file.cmp(&b.file));
+
+    category(
+        "unused",
+        issues,
+        format!("{} file(s) in the dependency graph", nodes.len()),
+    )
+}
+
+fn check_tests(ctx: &GqlContext) -> HealthCategory {
+    let Some(run) = read_json::<TestRunSummary>(ctx, TEST_RESULTS_PATH) else {
+        return skipped("tests", "No recorded test run (use runTests)");
+    };
+
+    let failed = run.failed_count + run.error_count;
+    let score = if run.total_count > 0 {
+        run.passed_count * 100 / run.total_count
+    } else {
+        0
+    };
+    let issues = if failed > 0 {
+        vec![HealthIssue {
+            severity: HealthSeverity::Error,
+            file: None,
+            message: format!("{} of {} test(s) failing", failed, run.total_count),
+        }]
+    } else if run.total_count == 0 {
+        vec![HealthIssue {
+            severity: HealthSeverity::Warning,
+            file: None,
+            message: "Last test run executed no tests".to_string(),
+        }]
+    } else {
+        vec![]
+    };
+
+    HealthCategory {
+        name: "tests".to_string(),
+        status: status_for(&issues),
+        score: Some(score),
+        summary: format!(
+            "{}/{} passed (recorded {})",
+            run.passed_count, run.total_count, run.timestamp
+        ),
+        issue_count: issues.len() as i32,
+        issues,
+    }
+}
+
+fn check_contracts(ctx: &GqlContext) -> HealthCategory {
+    if !ctx.fs.exists(&path_utils::to_fs_path_unchecked(
+        &ctx.project_path,
+        CONTRACTS_PATH,
+    )) {
+        return skipped("contracts", "No contracts file");
+    }
+
+    let result = resolve_check_contracts(ctx);
+    if let Some(error) = result.error {
+        let issues = vec![HealthIssue {
+            severity: HealthSeverity::Error,
+            file: Some(CONTRACTS_PATH.to_string()),
+            message: error.message,
+        }];
+        return category("contracts", issues, "Invalid contracts file".to_string());
+    }
+
+    let issues = result
+        .violations
+        .into_iter()
+        .map(|v| HealthIssue {
+            severity: HealthSeverity::Error,
+            file: Some(v.scene),
+            message: format!("[{}] {}", v.contract, v.message),
+        })
+        .collect();
+    category(
+        "contracts",
+        issues,
+        format!(
+            "{} contract(s) over {} scene(s)",
+            result.contracts_checked, result.scenes_checked
+        ),
+    )
+}
+
+fn check_budgets(ctx: &GqlContext) -> HealthCategory {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, BUDGETS_PATH);
+    let Ok(content) = ctx.fs.read_to_string(&path) else {
+        return skipped("budgets", "No budgets file");
+    };
+    let budgets = match toml::from_str::<BudgetsFile>(&content) {
+        Ok(file) => file.budget,
+        Err(e) => {
+            let issues = vec![HealthIssue {
+                severity: HealthSeverity::Error,
+                file: Some(BUDGETS_PATH.to_string()),
+                message: e.message().to_string(),
+            }];
+            return category("budgets", issues, "Invalid budgets file".to_string());
+        }
+    };
+
+    let mut files = Vec::new();
+    collect_sized_files(
+        ctx.fs.as_ref(),
+        &ctx.project_path,
+        &ctx.project_path,
+        &mut files,
+    );
+
+    let mut issues = Vec::new();
+    for budget in &budgets {
+        let pattern = path_utils::strip_res_prefix(&budget.files);
+        let matched: Vec<&(String, u64)> = files
+            .iter()
+            .filter(|(res_path, _)| glob_match(pattern, path_utils::strip_res_prefix(res_path)))
+            .collect();
+
+        if let Some(max_kb) = budget.max_file_kb {
+            for (res_path, size) in &matched {
+                if *size > max_kb * 1024 {
+                    issues.push(issue(
+                        HealthSeverity::Warning,
+                        res_path,
+                        &format!(
+                            "[{}] {} KB exceeds the {} KB per-file budget",
+                            budget.name,
+                            size.div_ceil(1024),
+                            max_kb
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(max_kb) = budget.max_total_kb {
+            let total: u64 = matched.iter().map(|(_, size)| size).sum();
+            if total > max_kb * 1024 {
+                issues.push(HealthIssue {
+                    severity: HealthSeverity::Error,
+                    file: None,
+                    message: format!(
+                        "[{}] {} KB total exceeds the {} KB budget",
+                        budget.name,
+                        total.div_ceil(1024),
+                        max_kb
+                    ),
+                });
+            }
+        }
+        if let Some(max_count) = budget.max_count {
+            if matched.len() > max_count {
+                issues.push(HealthIssue {
+                    severity: HealthSeverity::Error,
+                    file: None,
+                    message: format!(
+                        "[{}] {} files exceed the budget of {}",
+                        budget.name,
+                        matched.len(),
+                        max_count
+                    ),
+                });
+            }
+        }
+    }
+
+    category("budgets", issues, format!("{} budget(s)", budgets.len()))
+}
+
+// ======================
+// Helpers
+// ======================
+
+fn issue(severity: HealthSeverity, file: &str, message: &str) -> HealthIssue {
+    HealthIssue {
+        severity,
+        file: Some(file.to_string()),
+        message: message.to_string(),
+    }
+}
+
+/// Score a category from its issues: errors cost 20 points, warnings 5, info 1
+fn category(name: &str, mut issues: Vec<HealthIssue>, summary: String) -> HealthCategory {
+    let penalty: i32 = issues
+        .iter()
+        .map(|i| match i.severity {
+            HealthSeverity::Error => 20,
+            HealthSeverity::Warning => 5,
+            HealthSeverity::Info => 1,
+        })
+        .sum();
+
+    let status = status_for(&issues);
+    let issue_count = issues.len() as i32;
+    issues.sort_by_key(|i| severity_rank(i.severity));
+    issues.truncate(MAX_ISSUES);
+
+    HealthCategory {
+        name: name.to_string(),
+        status,
+        score: Some((100 - penalty).max(0)),
+        summary,
+        issue_count,
+        issues,
+    }
+}
+
+fn skipped(name: &str, summary: &str) -> HealthCategory {
+    HealthCategory {
+        name: name.to_string(),
+        status: HealthStatus::Skipped,
+        score: None,
+        summary: summary.to_string(),
+        issue_count: 0,
+        issues: vec![],
+    }
+}
+
+fn status_for(issues: &[HealthIssue]) -> HealthStatus {
+    if issues.iter().any(|i| i.severity == HealthSeverity::Error) {
+        HealthStatus::Fail
+    } else if issues.iter().any(|i| i.severity == HealthSeverity::Warning) {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Pass
+    }
+}
+
+fn severity_rank(severity: HealthSeverity) -> u8 {
+    match severity {
+        HealthSeverity::Error => 0,
+        HealthSeverity::Warning => 1,
+        HealthSeverity::Info => 2,
+    }
+}
+
+/// Scripts with `class_name` are reachable without an explicit reference, as
+/// are C# classes, which other scripts use by type name
+fn is_global_class(ctx: &GqlContext, res_path: &str) -> bool {
+    if res_path.ends_with(".cs") {
+        return true;
+    }
+    if !res_path.ends_with(".gd") {
+        return false;
+    }
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    ctx.fs
+        .read_to_string(&file_path)
+        .map(|content| {
+            content
+                .lines()
+                .any(|l| l.trim_start().starts_with("class_name "))
+        })
+        .unwrap_or(false)
+}
+
+/// Collect (res:// path, size in bytes), skipping hidden folders and addons
+fn collect_sized_files(
+    fs: &dyn ProjectFs,
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, u64)>,
+) {
+    let Ok(entries) = fs.read_dir(dir) else {
+        return;
+    };
+
+    for path in entries {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if name.starts_with('.') || name == "addons" {
+            continue;
+        }
+
+        let Ok(metadata) = fs.metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir {
+            collect_sized_files(fs, root, &path, files);
+        } else if !name.ends_with(".import") && !name.ends_with(".uid") {
+            let size = metadata.len;
+            files.push((to_res_path(root, &path), size));
+        }
+    }
+}
+
+fn read_history(ctx: &GqlContext) -> Vec<HealthCheckpoint> {
+    read_json(ctx, HISTORY_PATH).unwrap_or_default()
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(ctx: &GqlContext, res_path: &str) -> Option<T> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    ctx.fs
+        .read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn write_json<T: Serialize>(ctx: &GqlContext, res_path: &str, value: &T) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(value)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
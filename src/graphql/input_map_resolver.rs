@@ -0,0 +1,201 @@
+//! Input Map Resolver
+//!
+//! Lists, adds and removes the project's InputMap actions by rewriting the
+//! `[input]` section of project.godot, so actions can be set up without the
+//! editor. Only actions saved in the project are seen: Godot's built-in
+//! `ui_*` actions appear once they were edited in the Project Settings.
+
+use crate::godot::input_map::{self, InputEvent};
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::project_resolver::read_project_settings;
+use super::types::*;
+
+/// Resolve listInputActions query
+pub fn resolve_list_input_actions(ctx: &GqlContext) -> Vec<InputAction> {
+    let Ok((_, settings)) = read_project_settings(ctx) else {
+        return Vec::new();
+    };
+    settings
+        .entries("input")
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let action = input_map::InputAction::parse(value)?;
+            Some(InputAction {
+                name: name.to_string(),
+                deadzone: action.deadzone,
+                events: action.events.iter().map(event_info).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Add an input action to the project's InputMap
+pub fn resolve_add_input_action(ctx: &GqlContext, input: &AddInputActionInput) -> OperationResult {
+    // Validate action name
+    if input.action_name.is_empty() {
+        return OperationResult::err_msg("Action name cannot be empty");
+    }
+    if input.action_name.contains(['/', '=', '"'])
+        || input.action_name.contains(char::is_whitespace)
+    {
+        return invalid(format!(
+            "Invalid action name '{}': spaces, '/', '=' and '\"' are not allowed",
+            input.action_name
+        ));
+    }
+    let events = match input
+        .events
+        .iter()
+        .map(to_event)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(events) => events,
+        Err(message) => return invalid(message),
+    };
+
+    let (project_godot, mut settings) = match read_project_settings(ctx) {
+        Ok(read) => read,
+        Err(error) => return OperationResult::err(*error),
+    };
+    let path = format!("input/{}", input.action_name);
+    if settings.get(&path).is_some() {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "INPUT_ACTION_EXISTS",
+                GqlErrorCategory::Validation,
+                format!("Input action '{}' already exists", input.action_name),
+            )
+            .with_suggestion("removeInputAction で削除してから追加してください"),
+        );
+    }
+
+    let action = input_map::InputAction {
+        deadzone: input.deadzone.unwrap_or(0.5),
+        events,
+    };
+    settings.set(&path, &action.to_godot());
+    write_project(
+        ctx,
+        "addInputAction",
+        &project_godot,
+        settings.to_project_godot(),
+    )
+}
+
+/// Remove an input action from the project's InputMap
+pub fn resolve_remove_input_action(ctx: &GqlContext, action_name: &str) -> OperationResult {
+    let (project_godot, mut settings) = match read_project_settings(ctx) {
+        Ok(read) => read,
+        Err(error) => return OperationResult::err(*error),
+    };
+    if !settings.remove(&format!("input/{}", action_name)) {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "INPUT_ACTION_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Input action '{}' not found", action_name),
+            )
+            .with_suggestion("listInputActions で登録済みのアクションを確認してください"),
+        );
+    }
+    write_project(
+        ctx,
+        "removeInputAction",
+        &project_godot,
+        settings.to_project_godot(),
+    )
+}
+
+fn write_project(
+    ctx: &GqlContext,
+    operation: &str,
+    project_godot: &std::path::Path,
+    content: String,
+) -> OperationResult {
+    if let Err(e) = audit::write_file(ctx, operation, project_godot, content) {
+        return OperationResult::err_msg(format!("Failed to write project.godot: {}", e));
+    }
+    telemetry::record_files_written(1);
+    OperationResult::ok()
+}
+
+/// Event to write for an InputEventInput
+fn to_event(input: &InputEventInput) -> Result<InputEvent, String> {
+    // Gamepad events apply to every device unless one is given
+    let device = input.device.unwrap_or(-1) as i64;
+    match input.event_type {
+        InputEventType::Key => {
+            let key = input
+                .key
+                .as_deref()
+                .ok_or("KEY events need a key (e.g. \"Space\", \"Ctrl+S\")")?;
+            InputEvent::key(key)
+        }
+        InputEventType::MouseButton => Ok(InputEvent::MouseButton {
+            button: input.button.unwrap_or(1) as i64,
+        }),
+        InputEventType::JoyButton => Ok(InputEvent::JoypadButton {
+            device,
+            button: input.button.unwrap_or(0) as i64,
+        }),
+        InputEventType::JoyAxis => Ok(InputEvent::JoypadMotion {
+            device,
+            axis: input.button.unwrap_or(0) as i64,
+            axis_value: if input.axis_value.unwrap_or(1.0) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            },
+        }),
+    }
+}
+
+fn event_info(event: &InputEvent) -> InputActionEvent {
+    let mut info = InputActionEvent {
+        event_type: None,
+        class_name: event.class().to_string(),
+        key: None,
+        button: None,
+        axis_value: None,
+        device: -1,
+        description: event.describe(),
+    };
+    match event {
+        InputEvent::Key { .. } => {
+            info.event_type = Some(InputEventType::Key);
+            info.key = Some(info.description.clone());
+        }
+        InputEvent::MouseButton { button } => {
+            info.event_type = Some(InputEventType::MouseButton);
+            info.button = Some(*button as i32);
+        }
+        InputEvent::JoypadButton { device, button } => {
+            info.event_type = Some(InputEventType::JoyButton);
+            info.button = Some(*button as i32);
+            info.device = *device as i32;
+        }
+        InputEvent::JoypadMotion {
+            device,
+            axis,
+            axis_value,
+        } => {
+            info.event_type = Some(InputEventType::JoyAxis);
+            info.button = Some(*axis as i32);
+            info.axis_value = Some(*axis_value);
+            info.device = *device as i32;
+        }
+        InputEvent::Other { .. } => {}
+    }
+    info
+}
+
+fn invalid(message: String) -> OperationResult {
+    OperationResult::err(GqlStructuredError::new(
+        "INVALID_INPUT",
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
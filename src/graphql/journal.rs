@@ -0,0 +1,594 @@
+//! Write-Ahead Journal
+//!
+//! Operations that change several files at once (`commitTransaction`,
+//! `renameSymbol`, `moveFile`) record their intended changes in
+//! `res://.godot-mcp/journal/<id>/` before touching the project: the new
+//! content of every file they write, a copy of every file they overwrite or
+//! delete, and last the manifest `journal.json` listing the changes in order.
+//! The entry is deleted once the operation has finished (or been undone).
+//!
+//! An entry still present when the server first opens a project belongs to an
+//! operation interrupted by a crash. With a manifest, its changes are rolled
+//! forward from the staged copies; if that fails, the files are restored from
+//! the original copies instead. Without a manifest nothing had been changed
+//! yet, so the entry is discarded. Recoveries are reported by
+//! `serverDiagnostics`.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+
+/// Where journal entries are stored
+pub const JOURNAL_DIR: &str = "res://.godot-mcp/journal";
+
+/// Written last: an entry without it was never applied
+const MANIFEST: &str = "journal.json";
+
+/// Recoveries of every project opened by this process
+static RECOVERIES: Mutex<Vec<(PathBuf, Recovery)>> = Mutex::new(Vec::new());
+
+/// Change an operation is about to make
+#[derive(Debug, Clone, Copy)]
+pub enum JournalChange<'a> {
+    Write { path: &'a Path, data: &'a [u8] },
+    Remove { path: &'a Path },
+    Rename { from: &'a Path, to: &'a Path },
+    CreateDir { path: &'a Path },
+}
+
+/// What recovery did with an interrupted operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryOutcome {
+    /// Every change was applied from the staged copies
+    RolledForward,
+    /// Rolling forward failed; the previous files were put back
+    Restored,
+    /// The journal was incomplete, so nothing had been changed
+    Discarded,
+    /// Neither worked; the entry is kept for the next start
+    Failed,
+}
+
+/// Interrupted operation found when the project was opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recovery {
+    pub id: String,
+    /// None when the manifest was never written
+    pub operation: Option<String>,
+    /// Unix epoch milliseconds
+    pub started_at: Option<u64>,
+    pub outcome: RecoveryOutcome,
+    /// Files and folders the operation changes
+    pub paths: Vec<PathBuf>,
+    pub message: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    operation: String,
+    started_at: u64,
+    entries: Vec<Entry>,
+}
+
+/// Change in the manifest; paths are relative to the project root
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum Entry {
+    Write {
+        path: PathBuf,
+        /// File in the journal holding the new content
+        staged: String,
+        original: Option<Original>,
+    },
+    Remove {
+        path: PathBuf,
+        original: Option<Original>,
+    },
+    Rename {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    CreateDir {
+        path: PathBuf,
+    },
+}
+
+/// Content a changed file had before the operation
+#[derive(Serialize, Deserialize)]
+struct Original {
+    /// Where the file was, before earlier renames of the operation moved it
+    path: PathBuf,
+    /// File in the journal holding the content
+    copy: String,
+}
+
+/// Journal entry of an operation in progress
+#[derive(Debug)]
+pub struct Journal {
+    dir: PathBuf,
+}
+
+impl Journal {
+    /// Record `changes` before they are applied
+    pub fn begin(
+        project_root: &Path,
+        operation: &str,
+        changes: &[JournalChange],
+    ) -> io::Result<Self> {
+        let root = path_utils::to_fs_path_unchecked(project_root, JOURNAL_DIR);
+        fs::create_dir_all(&root)?;
+        let started_at = now_millis();
+        let mut id = format!("wal-{}", started_at);
+        let dir = loop {
+            let dir = root.join(&id);
+            match fs::create_dir(&dir) {
+                Ok(()) => break dir,
+                // Several operations within the same millisecond
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => id.push('x'),
+                Err(e) => return Err(e),
+            }
+        };
+        let journal = Self { dir };
+        match journal.prepare(project_root, operation, started_at, changes) {
+            Ok(()) => Ok(journal),
+            Err(e) => {
+                journal.finish();
+                Err(e)
+            }
+        }
+    }
+
+    /// Journal for an operation of `ctx`, or None when its writes don't reach
+    /// the disk directly (in-memory projects, or an open transaction, whose
+    /// commit is journaled instead)
+    pub fn for_context(
+        ctx: &GqlContext,
+        operation: &str,
+        changes: &[JournalChange],
+    ) -> io::Result<Option<Self>> {
+        if ctx.fs.is_virtual() {
+            return Ok(None);
+        }
+        Self::begin(&ctx.project_path, operation, changes).map(Some)
+    }
+
+    /// The operation has finished or been undone: drop the entry
+    pub fn finish(self) {
+        if let Err(e) = fs::remove_dir_all(&self.dir) {
+            tracing::warn!("Failed to remove journal {}: {}", self.dir.display(), e);
+        }
+    }
+
+    fn prepare(
+        &self,
+        project_root: &Path,
+        operation: &str,
+        started_at: u64,
+        changes: &[JournalChange],
+    ) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for (index, change) in changes.iter().enumerate() {
+            let earlier = &changes[..index];
+            entries.push(match *change {
+                JournalChange::Write { path, data } => {
+                    let staged = format!("{}.staged", index);
+                    write_synced(&self.dir.join(&staged), data)?;
+                    Entry::Write {
+                        path: relative(project_root, path),
+                        staged,
+                        original: self.keep_original(project_root, index, path, earlier)?,
+                    }
+                }
+                JournalChange::Remove { path } => Entry::Remove {
+                    path: relative(project_root, path),
+                    original: self.keep_original(project_root, index, path, earlier)?,
+                },
+                JournalChange::Rename { from, to } => Entry::Rename {
+                    from: relative(project_root, from),
+                    to: relative(project_root, to),
+                },
+                JournalChange::CreateDir { path } => Entry::CreateDir {
+                    path: relative(project_root, path),
+                },
+            });
+        }
+
+        let manifest = Manifest {
+            operation: operation.to_string(),
+            started_at,
+            entries,
+        };
+        // Renamed into place, so the manifest is either complete or absent
+        let partial = self.dir.join(format!("{}.partial", MANIFEST));
+        write_synced(&partial, &serde_json::to_vec_pretty(&manifest)?)?;
+        fs::rename(&partial, self.dir.join(MANIFEST))?;
+        // Persist the rename itself (not possible on every platform)
+        if let Ok(dir) = File::open(&self.dir) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    }
+
+    /// Copy the current content of the file the change at `index` replaces
+    fn keep_original(
+        &self,
+        project_root: &Path,
+        index: usize,
+        path: &Path,
+        earlier: &[JournalChange],
+    ) -> io::Result<Option<Original>> {
+        let source = source_path(path, earlier);
+        match fs::read(&source) {
+            Ok(data) => {
+                let copy = format!("{}.original", index);
+                write_synced(&self.dir.join(&copy), &data)?;
+                Ok(Some(Original {
+                    path: relative(project_root, &source),
+                    copy,
+                }))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Recover the interrupted operations of a project; called once per project
+/// when the server first opens it
+pub fn recover(project_root: &Path) -> Vec<Recovery> {
+    let root = path_utils::to_fs_path_unchecked(project_root, JOURNAL_DIR);
+    let Ok(read_dir) = fs::read_dir(&root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    let recoveries: Vec<Recovery> = dirs
+        .iter()
+        .map(|dir| recover_entry(project_root, dir))
+        .collect();
+    for recovery in &recoveries {
+        tracing::warn!(
+            "Recovered interrupted operation {} ({:?}): {:?}",
+            recovery.id,
+            recovery.operation,
+            recovery.outcome
+        );
+    }
+    RECOVERIES.lock().unwrap_or_else(|e| e.into_inner()).extend(
+        recoveries
+            .iter()
+            .map(|recovery| (project_root.to_path_buf(), recovery.clone())),
+    );
+    recoveries
+}
+
+/// Recoveries made when this process opened `project_root`
+pub fn recoveries(project_root: &Path) -> Vec<Recovery> {
+    RECOVERIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|(root, _)| root == project_root)
+        .map(|(_, recovery)| recovery.clone())
+        .collect()
+}
+
+fn recover_entry(project_root: &Path, dir: &Path) -> Recovery {
+    let id = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let manifest: Option<Manifest> = fs::read(dir.join(MANIFEST))
+        .ok()
+        .and_then(|data| serde_json::from_slice(&data).ok());
+    let Some(manifest) = manifest else {
+        let _ = fs::remove_dir_all(dir);
+        return Recovery {
+            id,
+            operation: None,
+            started_at: None,
+            outcome: RecoveryOutcome::Discarded,
+            paths: Vec::new(),
+            message: Some("The journal was incomplete; no file had been changed".to_string()),
+        };
+    };
+
+    let (outcome, message) = match roll_forward(project_root, dir, &manifest.entries) {
+        Ok(()) => (RecoveryOutcome::RolledForward, None),
+        Err(forward) => match restore(project_root, dir, &manifest.entries) {
+            Ok(()) => (
+                RecoveryOutcome::Restored,
+                Some(format!(
+                    "Rolling forward failed ({}); the previous files were restored",
+                    forward
+                )),
+            ),
+            Err(e) => (
+                RecoveryOutcome::Failed,
+                Some(format!(
+                    "Rolling forward failed ({}) and restoring failed ({}); the journal is kept in {}",
+                    forward,
+                    e,
+                    dir.display()
+                )),
+            ),
+        },
+    };
+    if outcome != RecoveryOutcome::Failed {
+        let _ = fs::remove_dir_all(dir);
+    }
+    Recovery {
+        id,
+        operation: Some(manifest.operation),
+        started_at: Some(manifest.started_at),
+        outcome,
+        paths: manifest
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Write { path, .. }
+                | Entry::Remove { path, .. }
+                | Entry::CreateDir { path } => project_root.join(path),
+                Entry::Rename { to, .. } => project_root.join(to),
+            })
+            .collect(),
+        message,
+    }
+}
+
+/// Apply every change; changes applied before the crash are skipped
+fn roll_forward(project_root: &Path, dir: &Path, entries: &[Entry]) -> io::Result<()> {
+    for entry in entries {
+        match entry {
+            Entry::CreateDir { path } => fs::create_dir_all(project_root.join(path))?,
+            Entry::Rename { from, to } => {
+                let (from, to) = (project_root.join(from), project_root.join(to));
+                match (from.exists(), to.exists()) {
+                    (true, false) => fs::rename(&from, &to)?,
+                    (false, true) => {}
+                    (true, true) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::AlreadyExists,
+                            format!("Both {} and {} exist", from.display(), to.display()),
+                        ))
+                    }
+                    (false, false) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("Neither {} nor {} exists", from.display(), to.display()),
+                        ))
+                    }
+                }
+            }
+            Entry::Write { path, staged, .. } => {
+                fs::write(project_root.join(path), fs::read(dir.join(staged))?)?
+            }
+            Entry::Remove { path, .. } => remove_if_present(&project_root.join(path))?,
+        }
+    }
+    Ok(())
+}
+
+/// Put back the project as it was before the operation
+fn restore(project_root: &Path, dir: &Path, entries: &[Entry]) -> io::Result<()> {
+    // Files the operation created
+    for entry in entries.iter().rev() {
+        if let Entry::Write {
+            path,
+            original: None,
+            ..
+        } = entry
+        {
+            remove_if_present(&project_root.join(path))?;
+        }
+    }
+    for entry in entries.iter().rev() {
+        if let Entry::Rename { from, to } = entry {
+            let (from, to) = (project_root.join(from), project_root.join(to));
+            if to.exists() && !from.exists() {
+                fs::rename(&to, &from)?;
+            }
+        }
+    }
+    // Originals are kept under their path from before the renames
+    for entry in entries {
+        if let Entry::Write {
+            original: Some(original),
+            ..
+        }
+        | Entry::Remove {
+            original: Some(original),
+            ..
+        } = entry
+        {
+            fs::write(
+                project_root.join(&original.path),
+                fs::read(dir.join(&original.copy))?,
+            )?;
+        }
+    }
+    for entry in entries.iter().rev() {
+        if let Entry::CreateDir { path } = entry {
+            // Only succeeds while empty, i.e. when it didn't exist before
+            let _ = fs::remove_dir(project_root.join(path));
+        }
+    }
+    Ok(())
+}
+
+/// Where `path` is before the earlier changes of the operation move it there
+fn source_path(path: &Path, earlier: &[JournalChange]) -> PathBuf {
+    let mut source = path.to_path_buf();
+    for change in earlier.iter().rev() {
+        if let JournalChange::Rename { from, to } = *change {
+            if let Ok(rest) = source.strip_prefix(to) {
+                source = if rest.as_os_str().is_empty() {
+                    from.to_path_buf()
+                } else {
+                    from.join(rest)
+                };
+            }
+        }
+    }
+    source
+}
+
+fn relative(project_root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(project_root)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn remove_if_present(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/player.gd"), "extends Node\n").unwrap();
+        fs::write(
+            dir.path().join("main.tscn"),
+            "[ext_resource path=\"res://scripts/player.gd\"]\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    fn journals(root: &Path) -> usize {
+        fs::read_dir(path_utils::to_fs_path_unchecked(root, JOURNAL_DIR))
+            .map(|entries| entries.count())
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_interrupted_move_is_rolled_forward() {
+        let dir = project();
+        let root = dir.path();
+        let (from, to) = (root.join("scripts"), root.join("src/scripts"));
+        let (scene, moved) = (root.join("main.tscn"), root.join("src/scripts/player.gd"));
+        let scene_data = b"[ext_resource path=\"res://src/scripts/player.gd\"]\n";
+        Journal::begin(
+            root,
+            "moveFile",
+            &[
+                JournalChange::CreateDir {
+                    path: &root.join("src"),
+                },
+                JournalChange::Rename {
+                    from: &from,
+                    to: &to,
+                },
+                JournalChange::Write {
+                    path: &moved,
+                    data: b"extends Node2D\n",
+                },
+                JournalChange::Write {
+                    path: &scene,
+                    data: scene_data,
+                },
+            ],
+        )
+        .unwrap();
+        // Crash after the move, before the rewrites
+        fs::create_dir(root.join("src")).unwrap();
+        fs::rename(&from, &to).unwrap();
+
+        let recovered = recover(root);
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].outcome, RecoveryOutcome::RolledForward);
+        assert_eq!(recovered[0].operation.as_deref(), Some("moveFile"));
+        assert_eq!(fs::read_to_string(&moved).unwrap(), "extends Node2D\n");
+        assert_eq!(fs::read(&scene).unwrap(), scene_data);
+        assert_eq!(journals(root), 0);
+        assert_eq!(recoveries(root), recovered);
+    }
+
+    #[test]
+    fn test_failed_roll_forward_restores_originals() {
+        let dir = project();
+        let root = dir.path();
+        let (from, to) = (root.join("scripts"), root.join("src/scripts"));
+        let moved = root.join("src/scripts/player.gd");
+        let journal = Journal::begin(
+            root,
+            "moveFile",
+            &[
+                JournalChange::CreateDir {
+                    path: &root.join("src"),
+                },
+                JournalChange::Rename {
+                    from: &from,
+                    to: &to,
+                },
+                JournalChange::Write {
+                    path: &moved,
+                    data: b"extends Node2D\n",
+                },
+                JournalChange::Write {
+                    path: &root.join("src/scripts/new.gd"),
+                    data: b"",
+                },
+            ],
+        )
+        .unwrap();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::rename(&from, &to).unwrap();
+        fs::write(&moved, "extends Node2D\n").unwrap();
+        // A staged copy lost to the crash
+        fs::remove_file(journal.dir.join("3.staged")).unwrap();
+
+        let recovered = recover(root);
+        assert_eq!(recovered[0].outcome, RecoveryOutcome::Restored);
+        assert_eq!(
+            fs::read_to_string(root.join("scripts/player.gd")).unwrap(),
+            "extends Node\n"
+        );
+        assert!(!root.join("src").exists());
+        assert_eq!(journals(root), 0);
+    }
+
+    #[test]
+    fn test_journal_without_manifest_is_discarded() {
+        let dir = project();
+        let root = dir.path();
+        let journal_dir = path_utils::to_fs_path_unchecked(root, JOURNAL_DIR).join("wal-1");
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("0.staged"), "extends Control\n").unwrap();
+
+        let recovered = recover(root);
+        assert_eq!(recovered[0].outcome, RecoveryOutcome::Discarded);
+        assert_eq!(
+            fs::read_to_string(root.join("scripts/player.gd")).unwrap(),
+            "extends Node\n"
+        );
+        assert_eq!(journals(root), 0);
+        assert!(recover(root).is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+//! JUnit XML test reports
+//!
+//! Reads the `<testsuite>`/`<testcase>` elements test runners such as
+//! GdUnit4 write, with the `<failure>`, `<error>` and `<skipped>` outcomes of
+//! each case. Only the subset of XML these reports use is understood:
+//! elements, quoted attributes, text, CDATA, comments and the predefined and
+//! numeric entities.
+
+/// A `<testsuite>` element
+#[derive(Debug, Clone, PartialEq)]
+pub struct JUnitSuite {
+    pub name: String,
+    pub package: Option<String>,
+    /// Seconds
+    pub time: Option<f64>,
+    pub cases: Vec<JUnitCase>,
+}
+
+/// A `<testcase>` element
+#[derive(Debug, Clone, PartialEq)]
+pub struct JUnitCase {
+    pub name: String,
+    pub classname: Option<String>,
+    /// Seconds
+    pub time: Option<f64>,
+    pub outcome: Outcome,
+    /// `message` attribute of the outcome element
+    pub message: Option<String>,
+    /// Text of the outcome element (assertion details, stack trace)
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Error,
+    Skipped,
+}
+
+/// A parsed report: its suites, in document order (nested suites flattened),
+/// and the `time` of the root `<testsuites>` element
+#[derive(Debug, Clone, PartialEq)]
+pub struct JUnitReport {
+    pub time: Option<f64>,
+    pub suites: Vec<JUnitSuite>,
+}
+
+enum Token<'a> {
+    Open {
+        name: &'a str,
+        attributes: Vec<(&'a str, String)>,
+        empty: bool,
+    },
+    Close(&'a str),
+    Text(String),
+}
+
+/// Parse a JUnit XML report
+pub fn parse(xml: &str) -> Result<JUnitReport, String> {
+    let mut report = JUnitReport {
+        time: None,
+        suites: Vec::new(),
+    };
+    // Open suites (nesting is allowed) and the case and outcome being read
+    let mut suites: Vec<JUnitSuite> = Vec::new();
+    let mut case: Option<JUnitCase> = None;
+    let mut in_outcome = false;
+
+    for token in tokenize(xml)? {
+        match token {
+            Token::Open {
+                name,
+                attributes,
+                empty,
+            } => {
+                let attribute = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|(k, _)| *k == key)
+                        .map(|(_, v)| v.clone())
+                };
+                let time = attribute("time").and_then(|t| t.trim().parse().ok());
+                match name {
+                    "testsuites" => report.time = time,
+                    "testsuite" => {
+                        let suite = JUnitSuite {
+                            name: attribute("name").unwrap_or_default(),
+                            package: attribute("package"),
+                            time,
+                            cases: Vec::new(),
+                        };
+                        if empty {
+                            report.suites.push(suite);
+                        } else {
+                            suites.push(suite);
+                        }
+                    }
+                    "testcase" => {
+                        let new_case = JUnitCase {
+                            name: attribute("name").unwrap_or_default(),
+                            classname: attribute("classname"),
+                            time,
+                            outcome: Outcome::Passed,
+                            message: None,
+                            details: None,
+                        };
+                        if empty {
+                            push_case(&mut suites, new_case)?;
+                        } else {
+                            case = Some(new_case);
+                        }
+                    }
+                    "failure" | "error" | "skipped" => {
+                        let Some(case) = case.as_mut() else {
+                            return Err(format!("<{}> outside a <testcase>", name));
+                        };
+                        let outcome = match name {
+                            "failure" => Outcome::Failed,
+                            "error" => Outcome::Error,
+                            _ => Outcome::Skipped,
+                        };
+                        // An error outweighs a failure, both outweigh a skip
+                        if case.outcome == Outcome::Passed
+                            || (case.outcome == Outcome::Skipped && outcome != Outcome::Skipped)
+                            || (case.outcome == Outcome::Failed && outcome == Outcome::Error)
+                        {
+                            case.outcome = outcome;
+                        }
+                        if case.message.is_none() {
+                            case.message = attribute("message");
+                        }
+                        in_outcome = !empty;
+                    }
+                    _ => {}
+                }
+            }
+            Token::Close(name) => match name {
+                "testsuite" => {
+                    let suite = suites.pop().ok_or("Unbalanced </testsuite>")?;
+                    report.suites.push(suite);
+                }
+                "testcase" => {
+                    if let Some(case) = case.take() {
+                        push_case(&mut suites, case)?;
+                    }
+                }
+                "failure" | "error" | "skipped" => in_outcome = false,
+                _ => {}
+            },
+            Token::Text(text) => {
+                if let (true, Some(case)) = (in_outcome, case.as_mut()) {
+                    let text = text.trim();
+                    if !text.is_empty() {
+                        case.details = Some(match case.details.take() {
+                            Some(details) => format!("{}\n{}", details, text),
+                            None => text.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    if !suites.is_empty() {
+        return Err("Unclosed <testsuite>".to_string());
+    }
+    Ok(report)
+}
+
+fn push_case(suites: &mut [JUnitSuite], case: JUnitCase) -> Result<(), String> {
+    suites
+        .last_mut()
+        .ok_or_else(|| "<testcase> outside a <testsuite>".to_string())?
+        .cases
+        .push(case);
+    Ok(())
+}
+
+fn tokenize(xml: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = xml;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(unescape(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(unescape(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        let (end_marker, skip) = if rest.starts_with("<![CDATA[") {
+            ("]]>", true)
+        } else if rest.starts_with("<!--") {
+            ("-->", true)
+        } else if rest.starts_with("<?") {
+            ("?>", true)
+        } else if rest.starts_with("<!") {
+            (">", true)
+        } else {
+            (">", false)
+        };
+        let end = rest
+            .find(end_marker)
+            .ok_or_else(|| format!("Unterminated markup: {}", preview(rest)))?;
+        let markup = &rest[..end];
+        rest = &rest[end + end_marker.len()..];
+        if skip {
+            if let Some(cdata) = markup.strip_prefix("<![CDATA[") {
+                tokens.push(Token::Text(cdata.to_string()));
+            }
+            continue;
+        }
+
+        let markup = &markup[1..];
+        if let Some(name) = markup.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim()));
+            continue;
+        }
+        let (markup, empty) = match markup.strip_suffix('/') {
+            Some(markup) => (markup, true),
+            None => (markup, false),
+        };
+        let name_end = markup
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(markup.len());
+        tokens.push(Token::Open {
+            name: &markup[..name_end],
+            attributes: attributes(&markup[name_end..])?,
+            empty,
+        });
+    }
+    Ok(tokens)
+}
+
+/// `key="value"` pairs (either quote), values unescaped
+fn attributes(markup: &str) -> Result<Vec<(&str, String)>, String> {
+    let mut attributes = Vec::new();
+    let mut rest = markup.trim_start();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| format!("Malformed attribute: {}", preview(rest)))?;
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| format!("Unquoted attribute value: {}", preview(rest)))?;
+        let close = value[1..]
+            .find(quote)
+            .ok_or_else(|| format!("Unterminated attribute value: {}", preview(rest)))?;
+        attributes.push((key, unescape(&value[1..close + 1])));
+        rest = value[close + 2..].trim_start();
+    }
+    Ok(attributes)
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn preview(text: &str) -> String {
+    text.chars().take(40).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GDUNIT4_REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<testsuites id="2026-10-17" name="report_1" tests="4" failures="1" skipped="1" flaky="0" time="0.412">
+	<testsuite id="0" name="PlayerTest" package="tests" timestamp="2026-10-17T10:00:00" hostname="localhost" tests="4" failures="1" errors="1" skipped="1" flaky="0" time="0.4">
+		<properties/>
+		<testcase name="test_moves" classname="PlayerTest" time="0.021"/>
+		<testcase name="test_jumps" classname="PlayerTest" time="0.035">
+			<failure message="FAILED: res://tests/player_test.gd:14" type="FAILURE"><![CDATA[
+line 14: Expecting:
+ '-460'
+ but was
+ '-400'
+]]></failure>
+		</testcase>
+		<testcase name="test_dash" classname="PlayerTest" time="0.001">
+			<skipped message="SKIPPED: res://tests/player_test.gd:20"/>
+		</testcase>
+		<testcase name="test_land" classname="PlayerTest" time="0.010">
+			<error message="ERROR: res://tests/player_test.gd:31" type="ABORT">Invalid call &lt;Nil&gt; &amp; more</error>
+		</testcase>
+	</testsuite>
+</testsuites>
+"#;
+
+    #[test]
+    fn test_parse_gdunit4_report() {
+        let report = parse(GDUNIT4_REPORT).unwrap();
+        assert_eq!(report.time, Some(0.412));
+        assert_eq!(report.suites.len(), 1);
+        let suite = &report.suites[0];
+        assert_eq!(suite.name, "PlayerTest");
+        assert_eq!(suite.package.as_deref(), Some("tests"));
+        let outcomes: Vec<Outcome> = suite.cases.iter().map(|c| c.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Passed,
+                Outcome::Failed,
+                Outcome::Skipped,
+                Outcome::Error
+            ]
+        );
+        let failed = &suite.cases[1];
+        assert_eq!(
+            failed.message.as_deref(),
+            Some("FAILED: res://tests/player_test.gd:14")
+        );
+        assert!(failed
+            .details
+            .as_deref()
+            .unwrap()
+            .starts_with("line 14: Expecting:"));
+        assert_eq!(
+            suite.cases[3].details.as_deref(),
+            Some("Invalid call <Nil> & more")
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_suites_and_entities() {
+        let report = parse(
+            "<testsuites><testsuite name='outer'><testsuite name=\"inner\"><testcase name=\"a&#x41;&#66;\"/></testsuite></testsuite></testsuites>",
+        )
+        .unwrap();
+        let names: Vec<&str> = report.suites.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["inner", "outer"]);
+        assert_eq!(report.suites[0].cases[0].name, "aAB");
+    }
+
+    #[test]
+    fn test_parse_malformed_report() {
+        assert!(parse("<testsuites><testsuite name=\"x\">").is_err());
+        assert!(parse("<testcase name=\"orphan\"/>").is_err());
+        assert!(parse("<testsuite name=unquoted>").is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Layout Resolver
+//!
+//! Proposes a folder structure (by feature or by type) from the current file
+//! distribution and dependency graph, and applies it through the
+//! reference-updating move machinery.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::dependency_resolver::build_dependency_graph;
+use super::project_resolver::to_res_path;
+use super::refactoring_resolver::resolve_move_file;
+use super::types::*;
+use super::vfs::ProjectFs;
+
+/// Checkpoint log written after every applied batch
+const CHECKPOINT_PATH: &str = "res://.godot-mcp/layout_checkpoint.json";
+
+/// Root files Godot looks up by path without an explicit reference
+const PINNED_FILES: &[&str] = &[
+    "res://project.godot",
+    "res://export_presets.cfg",
+    "res://default_bus_layout.tres",
+    "res://default_env.tres",
+];
+
+/// Resolve suggestProjectLayout query
+pub fn resolve_suggest_project_layout(
+    ctx: &GqlContext,
+    convention: LayoutConvention,
+) -> ProjectLayoutPlan {
+    let files = collect_layout_files(ctx.fs.as_ref(), &ctx.project_path);
+    let owners = match convention {
+        LayoutConvention::ByFeature => scene_owners(ctx),
+        LayoutConvention::ByType => HashMap::new(),
+    };
+
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut moves = Vec::new();
+    let mut unchanged_count = 0;
+
+    for file in &files {
+        let Some((target_dir, reason)) = target_dir_for(file, convention, &owners) else {
+            unchanged_count += 1;
+            continue;
+        };
+
+        // Already in place (sub folders inside the target folder are kept)
+        if file.starts_with(&format!("res://{}/", target_dir)) {
+            unchanged_count += 1;
+            continue;
+        }
+
+        let file_name = file.rsplit('/').next().unwrap_or(file);
+        let mut target = format!("res://{}/{}", target_dir, file_name);
+
+        // Same file name from different folders: keep the original sub path
+        if taken.contains(&target) || occupied(ctx, &target) {
+            let relative = file.strip_prefix("res://").unwrap_or(file);
+            target = format!("res://{}/{}", target_dir, relative);
+        }
+        if taken.contains(&target) || occupied(ctx, &target) {
+            unchanged_count += 1;
+            continue;
+        }
+
+        taken.insert(target.clone());
+        moves.push(PlannedMove {
+            from: file.clone(),
+            to: target,
+            reason,
+        });
+    }
+
+    let folders: BTreeSet<String> = moves
+        .iter()
+        .filter_map(|m| m.to.rsplit_once('/').map(|(dir, _)| dir.to_string()))
+        .collect();
+
+    ProjectLayoutPlan {
+        convention,
+        moves,
+        folders: folders.into_iter().collect(),
+        unchanged_count,
+    }
+}
+
+/// Apply a layout plan in batches, writing a checkpoint after each batch
+pub fn resolve_apply_project_layout(
+    ctx: &GqlContext,
+    input: &ApplyProjectLayoutInput,
+) -> ApplyProjectLayoutResult {
+    let plan = resolve_suggest_project_layout(ctx, input.convention);
+    let batch_size = input.batch_size.max(1) as usize;
+    let total_batches = plan.moves.len().div_ceil(batch_size) as i32;
+
+    let mut result = ApplyProjectLayoutResult {
+        success: true,
+        dry_run: input.dry_run,
+        moves_applied: 0,
+        batches_completed: 0,
+        total_batches,
+        references_updated: 0,
+        checkpoint_path: None,
+        failed_move: None,
+        message: None,
+    };
+    let mut completed: Vec<&PlannedMove> = Vec::new();
+
+    for batch in plan.moves.chunks(batch_size) {
+        for planned in batch {
+            let moved = resolve_move_file(
+                ctx,
+                &MoveFileInput {
+                    from: planned.from.clone(),
+                    to: planned.to.clone(),
+                    dry_run: input.dry_run,
+                },
+            );
+            if !moved.success {
+                result.success = false;
+                result.failed_move = Some(planned.clone());
+                result.message = moved.message;
+                break;
+            }
+            result.moves_applied += 1;
+            result.references_updated += moved.references_updated;
+            completed.push(planned);
+        }
+
+        if !input.dry_run && !completed.is_empty() {
+            if let Err(e) = write_checkpoint(ctx, input.convention, &completed) {
+                result.success = false;
+                result.message = Some(format!("Failed to write checkpoint: {}", e));
+                break;
+            }
+            result.checkpoint_path = Some(CHECKPOINT_PATH.to_string());
+        }
+        if !result.success {
+            break;
+        }
+        result.batches_completed += 1;
+    }
+
+    if !input.dry_run {
+        for planned in &completed {
+            remove_empty_parents(ctx, &planned.from);
+        }
+    }
+
+    result
+}
+
+fn write_checkpoint(
+    ctx: &GqlContext,
+    convention: LayoutConvention,
+    completed: &[&PlannedMove],
+) -> std::io::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, CHECKPOINT_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs.create_dir_all(parent)?;
+    }
+    let checkpoint = json!({
+        "convention": format!("{:?}", convention),
+        "moves": completed
+            .iter()
+            .map(|m| json!({ "from": m.from, "to": m.to }))
+            .collect::<Vec<_>>(),
+    });
+    ctx.fs.write(
+        &path,
+        serde_json::to_string_pretty(&checkpoint)
+            .unwrap_or_default()
+            .as_bytes(),
+    )
+}
+
+/// Folder a file belongs in under the given convention, with a human-readable reason
+fn target_dir_for(
+    file: &str,
+    convention: LayoutConvention,
+    owners: &HashMap<String, BTreeSet<String>>,
+) -> Option<(String, String)> {
+    let type_dir = type_dir_for(file)?;
+
+    match convention {
+        LayoutConvention::ByType => Some((type_dir.to_string(), format!("{} file", type_dir))),
+        LayoutConvention::ByFeature => {
+            if type_dir == "scenes" {
+                let stem = file_stem(file);
+                return Some((format!("features/{}", stem), format!("scene '{}'", stem)));
+            }
+            let scenes = owners.get(file)?;
+            if scenes.len() == 1 {
+                let scene = scenes.iter().next()?;
+                Some((
+                    format!("features/{}", file_stem(scene)),
+                    format!("used only by {}", scene),
+                ))
+            } else {
+                Some((
+                    format!("common/{}", type_dir),
+                    format!("shared by {} scenes", scenes.len()),
+                ))
+            }
+        }
+    }
+}
+
+fn type_dir_for(file: &str) -> Option<&'static str> {
+    let ext = file.rsplit_once('.')?.1.to_ascii_lowercase();
+    match ext.as_str() {
+        "tscn" | "scn" => Some("scenes"),
+        "gd" | "cs" => Some("scripts"),
+        "tres" | "res" => Some("resources"),
+        "gdshader" => Some("shaders"),
+        "png" | "jpg" | "jpeg" | "webp" | "svg" => Some("assets/textures"),
+        "wav" | "ogg" | "mp3" => Some("assets/audio"),
+        "glb" | "gltf" | "obj" | "fbx" | "blend" => Some("assets/models"),
+        "ttf" | "otf" | "woff" | "woff2" => Some("assets/fonts"),
+        _ => None,
+    }
+}
+
+fn file_stem(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// For every non-scene file, the scenes whose load closure reaches it
+/// (without crossing into instanced sub-scenes)
+fn scene_owners(ctx: &GqlContext) -> HashMap<String, BTreeSet<String>> {
+    let (nodes, edges) = build_dependency_graph(ctx);
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &edges {
+        adjacency
+            .entry(edge.from.as_str())
+            .or_default()
+            .push(edge.to.as_str());
+    }
+
+    let mut owners: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for scene in nodes.iter().filter(|n| n.node_type == FileType::Scene) {
+        let mut stack = vec![scene.id.as_str()];
+        let mut visited = HashSet::new();
+        while let Some(current) = stack.pop() {
+            for next in adjacency.get(current).into_iter().flatten() {
+                if type_dir_for(next) == Some("scenes") || !visited.insert(*next) {
+                    continue;
+                }
+                owners
+                    .entry(next.to_string())
+                    .or_default()
+                    .insert(scene.id.clone());
+                stack.push(next);
+            }
+        }
+    }
+
+    owners
+}
+
+/// Existing files are never overwritten, even if they are moved later in the plan
+fn occupied(ctx: &GqlContext, target: &str) -> bool {
+    ctx.fs
+        .exists(&path_utils::to_fs_path_unchecked(&ctx.project_path, target))
+}
+
+fn remove_empty_parents(ctx: &GqlContext, res_path: &str) {
+    let mut dir = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path)
+        .parent()
+        .map(Path::to_path_buf);
+    while let Some(current) = dir {
+        if current == ctx.project_path || ctx.fs.remove_dir(&current).is_err() {
+            break;
+        }
+        dir = current.parent().map(Path::to_path_buf);
+    }
+}
+
+/// Collect movable files (res:// paths), skipping engine and tool folders
+fn collect_layout_files(fs: &dyn ProjectFs, project_path: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    collect_layout_files_recursive(fs, project_path, project_path, &mut files);
+    files.sort();
+    files
+}
+
+fn collect_layout_files_recursive(
+    fs: &dyn ProjectFs,
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<String>,
+) {
+    let Ok(entries) = fs.read_dir(dir) else {
+        return;
+    };
+
+    for path in entries {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Skip hidden folders (.godot, .godot-mcp, .git) and addons
+        if name.starts_with('.') || name == "addons" {
+            continue;
+        }
+
+        if fs.is_dir(&path) {
+            collect_layout_files_recursive(fs, root, &path, files);
+        } else if !name.ends_with(".import") && !name.ends_with(".uid") {
+            let res_path = to_res_path(root, &path);
+            if !PINNED_FILES.contains(&res_path.as_str()) {
+                files.push(res_path);
+            }
+        }
+    }
+}
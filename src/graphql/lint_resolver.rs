@@ -0,0 +1,391 @@
+//! Lint Resolver
+//!
+//! `gdlint` and `gdformat` over project scripts. gdtoolkit's tools are used
+//! when installed (see [`super::gdtoolkit`]); otherwise linting falls back to
+//! the built-in checks (syntax errors, mixed indentation, TODO markers) and
+//! formatting reports that no formatter is available. `checkScript` parses
+//! scripts with Godot itself, the ground truth for what the built-in parser
+//! only approximates.
+
+use std::time::Duration;
+
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::gdtoolkit;
+use super::godot_binary;
+use super::project_resolver::{collect_project_files, to_res_path};
+use super::tags_resolver::TagScope;
+use super::types::*;
+
+/// Lint scripts under a `res://` folder or a single script (whole project when `None`),
+/// optionally only those matching a tag filter
+pub fn resolve_gdlint(
+    ctx: &GqlContext,
+    path: Option<&str>,
+    tags: Option<&FileTagFilter>,
+) -> LintResult {
+    let scripts = match target_scripts(ctx, path, tags) {
+        Ok(scripts) => scripts,
+        Err(error) => {
+            return LintResult {
+                success: false,
+                tool: "builtin".to_string(),
+                files_checked: 0,
+                diagnostics: Vec::new(),
+                message: None,
+                error: Some(*error),
+            }
+        }
+    };
+
+    let report = lint_scripts(ctx, &scripts);
+    LintResult {
+        success: true,
+        tool: report.tool.to_string(),
+        files_checked: scripts.len() as i32,
+        diagnostics: report.diagnostics,
+        message: report.fallback_reason,
+        error: None,
+    }
+}
+
+/// Diagnostics for a set of scripts and the linter that produced them
+pub struct LintReport {
+    /// `gdlint` or `builtin`
+    pub tool: &'static str,
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// Why gdlint was installed but not used
+    pub fallback_reason: Option<String>,
+}
+
+/// Lint `res://` scripts with gdlint when installed, else the built-in checks
+pub fn lint_scripts(ctx: &GqlContext, scripts: &[String]) -> LintReport {
+    let mut fallback_reason = None;
+
+    // gdlint reads the files itself, so an in-memory project uses the built-in checks
+    let gdlint = gdtoolkit::find_tool("gdlint").filter(|_| !ctx.fs.is_virtual());
+    if let Some(tool) = gdlint {
+        if scripts.is_empty() {
+            return LintReport {
+                tool: "gdlint",
+                diagnostics: Vec::new(),
+                fallback_reason: None,
+            };
+        }
+
+        let files: Vec<_> = scripts
+            .iter()
+            .map(|s| path_utils::to_fs_path_unchecked(&ctx.project_path, s))
+            .collect();
+        match gdtoolkit::run_gdlint(&tool, &files) {
+            Ok(problems) => {
+                let diagnostics = problems
+                    .into_iter()
+                    .map(|p| LintDiagnostic {
+                        file: to_res_path(&ctx.project_path, &p.file),
+                        line: Some(p.line as i32),
+                        severity: if p.is_error {
+                            HealthSeverity::Error
+                        } else {
+                            HealthSeverity::Warning
+                        },
+                        code: p.code,
+                        message: p.message,
+                    })
+                    .collect();
+                return LintReport {
+                    tool: "gdlint",
+                    diagnostics,
+                    fallback_reason: None,
+                };
+            }
+            Err(e) => fallback_reason = Some(format!("gdlint failed, used built-in lint: {}", e)),
+        }
+    }
+
+    let diagnostics = scripts
+        .iter()
+        .flat_map(|script| builtin_lint(ctx, script))
+        .collect();
+    LintReport {
+        tool: "builtin",
+        diagnostics,
+        fallback_reason,
+    }
+}
+
+/// Syntax errors, mixed tab/space indentation and leftover TODO/FIXME markers
+fn builtin_lint(ctx: &GqlContext, res_path: &str) -> Vec<LintDiagnostic> {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    let Some(content) = ctx.index.read_to_string(&file_path) else {
+        return Vec::new();
+    };
+    let diagnostic = |line: Option<usize>, severity, code: &str, message: String| LintDiagnostic {
+        file: res_path.to_string(),
+        line: line.map(|l| l as i32),
+        severity,
+        code: Some(code.to_string()),
+        message,
+    };
+
+    let mut diagnostics: Vec<LintDiagnostic> = ctx
+        .index
+        .script(&file_path)
+        .map(|script| script.errors.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| {
+            diagnostic(
+                Some(e.line),
+                HealthSeverity::Error,
+                "syntax-error",
+                e.message,
+            )
+        })
+        .collect();
+
+    let mut tabs = false;
+    let mut spaces = false;
+    for (index, line) in content.lines().enumerate() {
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        if line.trim().is_empty() {
+            continue;
+        }
+        tabs |= indent.contains('\t');
+        spaces |= indent.contains(' ');
+        if line.contains("TODO") || line.contains("FIXME") {
+            diagnostics.push(diagnostic(
+                Some(index + 1),
+                HealthSeverity::Info,
+                "todo-comment",
+                line.trim().to_string(),
+            ));
+        }
+    }
+    if tabs && spaces {
+        diagnostics.push(diagnostic(
+            None,
+            HealthSeverity::Error,
+            "mixed-indentation",
+            "Mixed tab and space indentation".to_string(),
+        ));
+    }
+    diagnostics
+}
+
+/// Format scripts with gdformat, or with `check` only list the ones it would change
+pub fn resolve_gdformat(ctx: &GqlContext, input: &GdformatInput) -> GdformatResult {
+    let check = input.check.unwrap_or(false);
+    let failure = |error: GqlStructuredError| GdformatResult {
+        success: false,
+        check,
+        files_checked: 0,
+        changed_files: Vec::new(),
+        failed_files: Vec::new(),
+        error: Some(error),
+    };
+
+    let Some(tool) = gdtoolkit::find_tool("gdformat") else {
+        return failure(
+            GqlStructuredError::new(
+                "GDFORMAT_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                "gdformat is not installed",
+            )
+            .with_suggestion(
+                "Install gdtoolkit (pip install gdtoolkit) or set [gdtoolkit] venv in the config file",
+            ),
+        );
+    };
+    let scripts = match target_scripts(ctx, input.path.as_deref(), None) {
+        Ok(scripts) => scripts,
+        Err(error) => return failure(*error),
+    };
+
+    let mut changed_files = Vec::new();
+    let mut failed_files = Vec::new();
+    for script in &scripts {
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, script);
+        let Ok(content) = ctx.fs.read_to_string(&file_path) else {
+            continue;
+        };
+
+        let formatted = match gdtoolkit::run_gdformat(&tool, &content) {
+            Ok(formatted) => formatted,
+            Err(message) => {
+                failed_files.push(FormatFailure {
+                    path: script.clone(),
+                    message,
+                });
+                continue;
+            }
+        };
+        if formatted == content {
+            continue;
+        }
+
+        if !check {
+            if let Err(e) = audit::write_file(ctx, "gdformat", &file_path, &formatted) {
+                failed_files.push(FormatFailure {
+                    path: script.clone(),
+                    message: format!("Failed to write: {}", e),
+                });
+                continue;
+            }
+            telemetry::record_files_written(1);
+        }
+        changed_files.push(script.clone());
+    }
+
+    GdformatResult {
+        success: failed_files.is_empty(),
+        check,
+        files_checked: scripts.len() as i32,
+        changed_files,
+        failed_files,
+        error: None,
+    }
+}
+
+/// How long Godot may take to check one script (the first run may import the project)
+const CHECK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Parse scripts with `godot --headless --check-only`
+pub fn resolve_check_script(ctx: &GqlContext, path: &str) -> ScriptCheckResult {
+    let failure = |godot: Option<String>, error: GqlStructuredError| ScriptCheckResult {
+        success: false,
+        valid: false,
+        godot,
+        scripts_checked: 0,
+        diagnostics: Vec::new(),
+        error: Some(error),
+    };
+
+    if ctx.fs.is_virtual() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "checkScript needs the project on disk",
+            ),
+        );
+    }
+    let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(binary) => binary,
+        Err(message) => return failure(
+            None,
+            GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                .with_suggestion(
+                "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+            ),
+        ),
+    };
+    let godot = Some(binary.path.display().to_string());
+    let scripts = match target_scripts(ctx, Some(path), None) {
+        Ok(scripts) => scripts,
+        Err(error) => return failure(godot, *error),
+    };
+
+    let mut diagnostics = Vec::new();
+    for (checked, script) in scripts.iter().enumerate() {
+        let problems = match godot_binary::check_script(
+            &binary.path,
+            &ctx.project_path,
+            script,
+            CHECK_TIMEOUT,
+        ) {
+            Ok(problems) => problems,
+            Err(message) => {
+                return ScriptCheckResult {
+                    success: false,
+                    valid: false,
+                    godot,
+                    scripts_checked: checked as i32,
+                    diagnostics,
+                    error: Some(GqlStructuredError::new(
+                        "GODOT_CHECK_FAILED",
+                        GqlErrorCategory::Godot,
+                        message,
+                    )),
+                }
+            }
+        };
+        diagnostics.extend(problems.into_iter().map(|p| {
+            LintDiagnostic {
+                file: p.file.unwrap_or_else(|| script.clone()),
+                line: p.line.map(|l| l as i32),
+                severity: if p.is_error {
+                    HealthSeverity::Error
+                } else {
+                    HealthSeverity::Warning
+                },
+                code: Some(
+                    if p.is_error {
+                        "parse-error"
+                    } else {
+                        "parse-warning"
+                    }
+                    .to_string(),
+                ),
+                message: p.message,
+            }
+        }));
+    }
+
+    ScriptCheckResult {
+        success: true,
+        valid: diagnostics
+            .iter()
+            .all(|d| d.severity != HealthSeverity::Error),
+        godot,
+        scripts_checked: scripts.len() as i32,
+        diagnostics,
+        error: None,
+    }
+}
+
+/// `res://` GDScript files selected by a script path, a folder, or the whole project,
+/// limited to files matching the tag filter
+fn target_scripts(
+    ctx: &GqlContext,
+    path: Option<&str>,
+    tags: Option<&FileTagFilter>,
+) -> Result<Vec<String>, Box<GqlStructuredError>> {
+    let scope = TagScope::new(ctx, tags)?;
+    let in_scope = |script: &String| scope.as_ref().is_none_or(|s| s.includes(ctx, script));
+    let path = path.unwrap_or("res://");
+    if path.ends_with(".gd") {
+        let file_path = path_utils::to_fs_path(&ctx.project_path, path).map_err(|e| {
+            Box::new(GqlStructuredError::new(
+                "INVALID_PATH",
+                GqlErrorCategory::Validation,
+                e.to_string(),
+            ))
+        })?;
+        if !ctx.fs.is_file(&file_path) {
+            return Err(Box::new(GqlStructuredError::new(
+                "SCRIPT_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Script not found: {}", path),
+            )));
+        }
+        return Ok(vec![path.to_string()]
+            .into_iter()
+            .filter(in_scope)
+            .collect());
+    }
+
+    let folder = format!("{}/", path.trim_end_matches('/'));
+    let (_, scripts) = collect_project_files(ctx);
+    Ok(scripts
+        .into_iter()
+        .map(|s| s.path)
+        .filter(|p| p.ends_with(".gd"))
+        .filter(|p| path == "res://" || p.starts_with(&folder))
+        .filter(in_scope)
+        .collect())
+}
@@ -0,0 +1,167 @@
+//! Live Instances Resolver
+//!
+//! Runs one read-only GraphQL query against every editor instance at once —
+//! the context's own editor plus those registered in the config file's
+//! `[[editors]]` (see `live_resolver::set_editor_instances`) — and returns the
+//! result per instance, so dashboards and agents can watch several open
+//! projects or windows with one call. Each instance is queried with a context
+//! of its own (project path and plugin ports); an editor that doesn't answer
+//! only fails its own entry.
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use async_graphql::parser::types::OperationType;
+use futures_util::future::join_all;
+
+use super::context::GqlContext;
+use super::live_resolver::editor_instances;
+use super::schema::{build_schema, GqlSchema};
+use super::types::*;
+
+/// Name of the context's own editor
+pub const CURRENT_INSTANCE: &str = "current";
+
+/// Resolve editorInstances query
+pub fn resolve_editor_instances(ctx: &GqlContext) -> Vec<EditorInstance> {
+    instances(ctx)
+        .into_iter()
+        .map(|(instance, _)| instance)
+        .collect()
+}
+
+/// Resolve liveInstancesQuery
+pub async fn resolve_live_instances_query(
+    ctx: &GqlContext,
+    query: &str,
+    names: Option<&[String]>,
+) -> LiveInstancesResult {
+    if let Err(error) = check_read_only(query) {
+        return LiveInstancesResult {
+            success: false,
+            results: Vec::new(),
+            error: Some(*error),
+        };
+    }
+
+    let mut targets = instances(ctx);
+    if let Some(names) = names {
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !targets.iter().any(|(i, _)| &i.name == *name))
+        {
+            return LiveInstancesResult {
+                success: false,
+                results: Vec::new(),
+                error: Some(
+                    GqlStructuredError::new(
+                        "INSTANCE_NOT_FOUND",
+                        GqlErrorCategory::Validation,
+                        format!("Editor instance '{}' is not registered", unknown),
+                    )
+                    .with_suggestion(
+                        "editorInstances で登録済みのインスタンス名を確認してください",
+                    ),
+                ),
+            };
+        }
+        targets.retain(|(instance, _)| names.contains(&instance.name));
+    }
+
+    let schema = schema();
+    let results = join_all(targets.into_iter().map(|(instance, instance_ctx)| {
+        let request = async_graphql::Request::new(query).data(instance_ctx);
+        async move {
+            let start = Instant::now();
+            let response = schema.execute(request).await;
+            InstanceQueryResult {
+                instance,
+                success: response.errors.is_empty(),
+                data: response
+                    .data
+                    .into_json()
+                    .ok()
+                    .filter(|data| !data.is_null())
+                    .map(async_graphql::Json),
+                errors: response.errors.into_iter().map(|e| e.message).collect(),
+                duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            }
+        }
+    }))
+    .await;
+
+    LiveInstancesResult {
+        success: results.iter().all(|r| r.success),
+        results,
+        error: None,
+    }
+}
+
+/// The context's own editor first, then the registered ones, each with the
+/// context to query it with (editors on a port already listed are skipped)
+fn instances(ctx: &GqlContext) -> Vec<(EditorInstance, GqlContext)> {
+    let mut instances = vec![(
+        EditorInstance {
+            name: CURRENT_INSTANCE.to_string(),
+            project_path: ctx.project_path.display().to_string(),
+            port: ctx.godot_port as i32,
+            ws_port: ctx.ws_port.map(i32::from),
+        },
+        ctx.clone(),
+    )];
+    for editor in editor_instances() {
+        if instances
+            .iter()
+            .any(|(i, _)| i.port == editor.port as i32 || i.name == editor.name)
+        {
+            continue;
+        }
+        let instance_ctx = GqlContext::new(editor.project.clone())
+            .with_port(editor.port)
+            .with_ws_port(editor.ws_port)
+            .with_timeout(ctx.timeout_ms)
+            .with_session(ctx.session_id.clone());
+        instances.push((
+            EditorInstance {
+                name: editor.name,
+                project_path: editor.project.display().to_string(),
+                port: editor.port as i32,
+                ws_port: editor.ws_port.map(i32::from),
+            },
+            instance_ctx,
+        ));
+    }
+    instances
+}
+
+/// Only queries may be fanned out, and not into another fan-out
+fn check_read_only(query: &str) -> Result<(), Box<GqlStructuredError>> {
+    let document = async_graphql::parser::parse_query(query).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "INVALID_INPUT",
+            GqlErrorCategory::Validation,
+            format!("Invalid query: {}", e),
+        ))
+    })?;
+    let read_only = document
+        .operations
+        .iter()
+        .all(|(_, operation)| operation.node.ty == OperationType::Query);
+    if !read_only || query.contains("liveInstancesQuery") {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "READ_ONLY_QUERY_REQUIRED",
+                GqlErrorCategory::Validation,
+                "liveInstancesQuery only runs queries (no mutations, subscriptions or nested liveInstancesQuery)",
+            )
+            .with_suggestion("変更は対象のプロジェクトごとに個別に実行してください"),
+        ));
+    }
+    Ok(())
+}
+
+/// Schema the instance queries run on; each request carries its own context
+fn schema() -> &'static GqlSchema {
+    static SCHEMA: OnceLock<GqlSchema> = OnceLock::new();
+    SCHEMA.get_or_init(build_schema)
+}
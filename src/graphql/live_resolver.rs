@@ -1,18 +1,27 @@
 //! Live operation resolvers
 //!
-//! Connects GraphQL queries/mutations to Godot editor plugin via HTTP.
+//! Connects GraphQL queries/mutations to Godot editor plugin over its
+//! persistent WebSocket channel, falling back to HTTP when the plugin's
+//! WebSocket server cannot be reached.
 
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
+use futures_util::stream::{self, Stream};
+use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::config::EditorInstanceConfig;
+use crate::ws::{LiveChannel, WsError};
+
 use super::context::GqlContext;
 use super::types::*;
 
 // ======================
-// HTTP Client
+// Transport
 // ======================
 
 /// Execute a command to the Godot editor plugin
@@ -20,9 +29,53 @@ pub async fn execute_live_command(
     ctx: &GqlContext,
     command: GodotLiveCommand,
 ) -> Result<Value, LiveError> {
-    let url = format!("http://localhost:{}", ctx.godot_port);
+    let payload =
+        serde_json::to_value(&command).map_err(|e| LiveError::Connection(e.to_string()))?;
+    let name = payload
+        .get("command")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
     let timeout = Duration::from_millis(ctx.timeout_ms);
 
+    if let Some(ws_port) = ctx.ws_port {
+        let start = Instant::now();
+        match LiveChannel::shared(ws_port)
+            .request(&payload, timeout)
+            .await
+        {
+            Ok(value) => {
+                record_latency(ctx, &name, LiveTransport::Websocket, start, true);
+                return Ok(value);
+            }
+            // The command never reached the plugin: HTTP may still get through
+            Err(e @ (WsError::ConnectionFailed(_) | WsError::SendFailed(_))) => {
+                tracing::debug!("WebSocket unavailable ({}), falling back to HTTP", e);
+            }
+            Err(e) => {
+                record_latency(ctx, &name, LiveTransport::Websocket, start, false);
+                return Err(match e {
+                    WsError::Timeout => LiveError::Timeout,
+                    e => LiveError::Connection(e.to_string()),
+                });
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let result = execute_http_command(ctx, &payload, timeout).await;
+    record_latency(ctx, &name, LiveTransport::Http, start, result.is_ok());
+    result
+}
+
+/// Send a command in its own HTTP request
+async fn execute_http_command(
+    ctx: &GqlContext,
+    payload: &Value,
+    timeout: Duration,
+) -> Result<Value, LiveError> {
+    let url = format!("http://localhost:{}", ctx.godot_port);
+
     let client = Client::builder()
         .timeout(timeout)
         .build()
@@ -31,7 +84,7 @@ pub async fn execute_live_command(
     let response = client
         .post(&url)
         .header("Content-Type", "application/json")
-        .json(&command)
+        .json(payload)
         .send()
         .await
         .map_err(|e| {
@@ -59,6 +112,94 @@ pub async fn execute_live_command(
     }
 }
 
+/// Editors besides each context's own, for liveInstancesQuery
+static EDITOR_INSTANCES: RwLock<Vec<EditorInstanceConfig>> = RwLock::new(Vec::new());
+
+/// Register further editors (the config file's `[[editors]]`)
+pub fn set_editor_instances(editors: Vec<EditorInstanceConfig>) {
+    *EDITOR_INSTANCES.write().unwrap_or_else(|e| e.into_inner()) = editors;
+}
+
+/// Editors registered with [`set_editor_instances`]
+pub fn editor_instances() -> Vec<EditorInstanceConfig> {
+    EDITOR_INSTANCES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[derive(Default)]
+struct LatencyStats {
+    calls: i32,
+    errors: i32,
+    total_ms: f64,
+    max_ms: f64,
+    last_ms: f64,
+}
+
+type LatencyTable = BTreeMap<(String, LiveTransport), LatencyStats>;
+
+/// Latency per command and transport, per plugin (HTTP port)
+fn latencies() -> &'static Mutex<HashMap<u16, LatencyTable>> {
+    static LATENCIES: OnceLock<Mutex<HashMap<u16, LatencyTable>>> = OnceLock::new();
+    LATENCIES.get_or_init(Default::default)
+}
+
+fn record_latency(
+    ctx: &GqlContext,
+    command: &str,
+    transport: LiveTransport,
+    start: Instant,
+    ok: bool,
+) {
+    let ms = start.elapsed().as_secs_f64() * 1000.0;
+    tracing::debug!(command, ?transport, ms, ok, "live command");
+    let mut latencies = latencies().lock().unwrap_or_else(|e| e.into_inner());
+    let stats = latencies
+        .entry(ctx.godot_port)
+        .or_default()
+        .entry((command.to_string(), transport))
+        .or_default();
+    stats.calls += 1;
+    if !ok {
+        stats.errors += 1;
+    }
+    stats.total_ms += ms;
+    stats.max_ms = stats.max_ms.max(ms);
+    stats.last_ms = ms;
+}
+
+/// Resolve liveChannel query
+pub async fn resolve_live_channel(ctx: &GqlContext) -> LiveChannelStatus {
+    let channel = match ctx.ws_port {
+        Some(port) => Some(LiveChannel::shared(port).status().await),
+        None => None,
+    };
+    let latencies = latencies().lock().unwrap_or_else(|e| e.into_inner());
+    let commands = latencies
+        .get(&ctx.godot_port)
+        .into_iter()
+        .flatten()
+        .map(|((command, transport), stats)| LiveCommandLatency {
+            command: command.clone(),
+            transport: *transport,
+            calls: stats.calls,
+            errors: stats.errors,
+            avg_ms: stats.total_ms / stats.calls as f64,
+            max_ms: stats.max_ms,
+            last_ms: stats.last_ms,
+        })
+        .collect();
+
+    LiveChannelStatus {
+        websocket_url: channel.as_ref().map(|c| c.url.clone()),
+        connected: channel.as_ref().is_some_and(|c| c.connected),
+        reconnects: channel.as_ref().map_or(0, |c| c.reconnects as i32),
+        in_flight: channel.as_ref().map_or(0, |c| c.in_flight as i32),
+        commands,
+    }
+}
+
 /// Helper trait for pipe syntax
 trait Pipe: Sized {
     fn pipe<F, R>(self, f: F) -> R
@@ -129,13 +270,25 @@ pub enum GodotLiveCommand {
     #[serde(rename = "ping")]
     Ping,
     #[serde(rename = "get_tree")]
-    GetTree,
+    GetTree {
+        /// Also return the edited scene as .tscn text (`tscn`)
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        serialize: bool,
+    },
     #[serde(rename = "add_node")]
     AddNode {
         parent: String,
         name: String,
         node_type: String,
     },
+    #[serde(rename = "add_node_at_cursor")]
+    AddNodeAtCursor {
+        parent: String,
+        name: Option<String>,
+        node_type: Option<String>,
+        scene_path: Option<String>,
+        distance: Option<f64>,
+    },
     #[serde(rename = "remove_node")]
     RemoveNode { node_path: String },
     #[serde(rename = "set_property")]
@@ -164,6 +317,12 @@ pub enum GodotLiveCommand {
     RemoveFromGroup { node_path: String, group: String },
     #[serde(rename = "save_scene")]
     SaveScene,
+    #[serde(rename = "get_scene_events")]
+    GetSceneEvents {
+        /// Only events after this sequence number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        since: Option<i64>,
+    },
     #[serde(rename = "open_scene")]
     OpenScene { scene_path: String },
 
@@ -171,7 +330,12 @@ pub enum GodotLiveCommand {
     #[serde(rename = "get_debugger_errors")]
     GetDebuggerErrors,
     #[serde(rename = "get_logs")]
-    GetLogs { limit: i32 },
+    GetLogs {
+        limit: i32,
+        /// Only lines logged after this sequence number
+        #[serde(skip_serializing_if = "Option::is_none")]
+        since: Option<i64>,
+    },
     #[serde(rename = "get_object_by_id")]
     GetObjectById { object_id: String },
     #[serde(rename = "pause")]
@@ -194,6 +358,8 @@ pub enum GodotLiveCommand {
     GetParseErrors { script_path: String },
     #[serde(rename = "get_stack_frame_vars")]
     GetStackFrameVars { frame_index: i32 },
+    #[serde(rename = "get_performance_metrics")]
+    GetPerformanceMetrics,
 
     // Phase 1: Dynamic Type Discovery Commands
     #[serde(rename = "get_type_info")]
@@ -208,6 +374,90 @@ pub enum GodotLiveCommand {
     CommitTransaction,
     #[serde(rename = "rollback_transaction")]
     RollbackTransaction,
+
+    // Scene Lock Commands
+    #[serde(rename = "get_scene_status")]
+    GetSceneStatus { scene_path: String },
+    #[serde(rename = "lock_scene")]
+    LockScene { scene_path: String, policy: String },
+    #[serde(rename = "unlock_scene")]
+    UnlockScene { scene_path: String, reload: bool },
+
+    // Play-in-editor Commands
+    #[serde(rename = "get_play_state")]
+    GetPlayState,
+    #[serde(rename = "play_main_scene")]
+    PlayMainScene,
+    #[serde(rename = "play_current_scene")]
+    PlayCurrentScene,
+    #[serde(rename = "stop_playing_scene")]
+    StopPlayingScene,
+
+    // Input Simulation Commands (forwarded to the running game; `pressed: None` taps)
+    #[serde(rename = "send_input_action")]
+    SendInputAction {
+        action: String,
+        pressed: Option<bool>,
+        strength: f64,
+    },
+    #[serde(rename = "send_key")]
+    SendKey { key: String, pressed: Option<bool> },
+    #[serde(rename = "send_mouse_click")]
+    SendMouseClick { x: f64, y: f64, button: i32 },
+
+    // Remote Evaluation Commands (the game answers asynchronously; poll by id)
+    #[serde(rename = "eval_expression")]
+    EvalExpression {
+        node_path: String,
+        expression: String,
+    },
+    #[serde(rename = "get_eval_result")]
+    GetEvalResult { id: i64 },
+
+    // Script Profiler Commands (the editor's Profiler panel)
+    #[serde(rename = "start_profiling")]
+    StartProfiling,
+    #[serde(rename = "stop_profiling")]
+    StopProfiling,
+    #[serde(rename = "get_profile_report")]
+    GetProfileReport,
+
+    // Editor Camera Commands
+    #[serde(rename = "frame_node")]
+    FrameNode { node_path: String },
+    #[serde(rename = "set_editor_camera")]
+    SetEditorCamera {
+        position: [f64; 3],
+        look_at: [f64; 3],
+    },
+    #[serde(rename = "set_main_screen")]
+    SetMainScreen { screen: String },
+
+    // Editor Settings Commands
+    #[serde(rename = "get_editor_settings")]
+    GetEditorSettings { names: Vec<String>, prefix: String },
+    #[serde(rename = "set_editor_setting")]
+    SetEditorSetting { name: String, value: String },
+    #[serde(rename = "get_class_icons")]
+    GetClassIcons { types: Vec<String> },
+
+    // Screenshot Commands
+    #[serde(rename = "capture_screenshot")]
+    CaptureScreenshot {
+        viewport: String,
+        node_paths: Vec<String>,
+    },
+    #[serde(rename = "capture_viewport")]
+    CaptureViewport { viewport: String, path: String },
+
+    // Animation Commands
+    #[serde(rename = "remap_animation_tracks")]
+    RemapAnimationTracks {
+        player: String,
+        mapping: Vec<TrackPathMappingInput>,
+        animations: Option<Vec<String>>,
+        dry_run: bool,
+    },
 }
 
 // ======================
@@ -216,7 +466,7 @@ pub enum GodotLiveCommand {
 
 /// Resolve currentScene query
 pub async fn resolve_current_scene(ctx: &GqlContext) -> Option<LiveScene> {
-    let result = execute_live_command(ctx, GodotLiveCommand::GetTree).await;
+    let result = execute_live_command(ctx, GodotLiveCommand::GetTree { serialize: false }).await;
 
     match result {
         Ok(value) => parse_live_scene_from_tree(&value),
@@ -226,7 +476,7 @@ pub async fn resolve_current_scene(ctx: &GqlContext) -> Option<LiveScene> {
 
 /// Resolve node query
 pub async fn resolve_node(ctx: &GqlContext, path: String) -> Option<LiveNode> {
-    let result = execute_live_command(ctx, GodotLiveCommand::GetTree).await;
+    let result = execute_live_command(ctx, GodotLiveCommand::GetTree { serialize: false }).await;
 
     match result {
         Ok(value) => find_node_in_tree(&value, &path),
@@ -271,6 +521,65 @@ pub async fn resolve_add_node(ctx: &GqlContext, input: AddNodeInput) -> NodeResu
     }
 }
 
+/// Resolve addNodeAtCursor mutation - the plugin places the node where the
+/// editor is looking (in front of the 3D camera, or the 2D viewport center)
+pub async fn resolve_add_node_at_cursor(
+    ctx: &GqlContext,
+    input: AddNodeAtCursorInput,
+) -> NodeResult {
+    if input.node_type.is_some() == input.scene_path.is_some() {
+        return NodeResult::err(
+            GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                "Specify exactly one of type or scenePath",
+            )
+            .with_suggestion("type（ノードクラス）か scenePath（インスタンス化するシーン）のどちらか一方を指定してください"),
+        );
+    }
+
+    let command = GodotLiveCommand::AddNodeAtCursor {
+        parent: input.parent,
+        name: input.name,
+        node_type: input.node_type,
+        scene_path: input.scene_path,
+        distance: input.distance,
+    };
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return NodeResult::err(e.to_structured_error()),
+    };
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return NodeResult::err(GqlStructuredError::new(
+            "NODE_ADD_FAILED",
+            GqlErrorCategory::Godot,
+            message,
+        ));
+    }
+
+    let position: Vec<f64> = value["position"]
+        .as_array()
+        .map(|p| p.iter().filter_map(Value::as_f64).collect())
+        .unwrap_or_default();
+    NodeResult::ok(LiveNode {
+        name: value["name"].as_str().unwrap_or_default().to_string(),
+        r#type: value["type"].as_str().unwrap_or_default().to_string(),
+        path: value["node_path"].as_str().unwrap_or_default().to_string(),
+        global_position: match position[..] {
+            [x, y, z] => Some(Vector3 { x, y, z }),
+            _ => None,
+        },
+        global_position_2d: match position[..] {
+            [x, y] => Some(Vector2 { x, y }),
+            _ => None,
+        },
+        properties: vec![],
+        children: vec![],
+        available_signals: vec![],
+        connected_signals: vec![],
+    })
+}
+
 /// Resolve removeNode mutation
 pub async fn resolve_remove_node(ctx: &GqlContext, path: String) -> OperationResult {
     let command = GodotLiveCommand::RemoveNode { node_path: path };
@@ -392,13 +701,134 @@ pub async fn resolve_debugger_errors(ctx: &GqlContext) -> Vec<DebuggerError> {
 }
 
 pub async fn resolve_logs(ctx: &GqlContext, limit: i32) -> Vec<LogEntry> {
-    let result = execute_live_command(ctx, GodotLiveCommand::GetLogs { limit }).await;
-    match result {
-        Ok(val) => serde_json::from_value(val).unwrap_or_default(),
+    let command = GodotLiveCommand::GetLogs { limit, since: None };
+    match execute_live_command(ctx, command).await {
+        Ok(val) => parse_log_lines(&log_lines(&val)),
         Err(_) => vec![],
     }
 }
 
+/// Lines the plugin logged after `since`, and the sequence number of its
+/// latest line; None when the plugin cannot be reached
+async fn fetch_new_logs(ctx: &GqlContext, since: Option<i64>) -> Option<(Vec<String>, i64)> {
+    // The plugin keeps at most this many lines; older ones are gone anyway
+    const MAX_LOG_LINES: i32 = 1000;
+    let command = GodotLiveCommand::GetLogs {
+        limit: MAX_LOG_LINES,
+        since,
+    };
+    let value = execute_live_command(ctx, command).await.ok()?;
+    let last_seq = value.get("last_seq").and_then(Value::as_i64)?;
+    Some((log_lines(&value), last_seq))
+}
+
+fn log_lines(value: &Value) -> Vec<String> {
+    value
+        .get("logs")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|line| line.as_str().map(String::from))
+        .collect()
+}
+
+/// Resolve editorLog subscription
+///
+/// Polls the plugin every `interval_ms` and yields the lines logged since the
+/// previous poll; lines logged before the subscription started are skipped.
+/// While the editor is unreachable the stream stays open and keeps polling.
+pub fn resolve_editor_log(ctx: &GqlContext, interval_ms: u64) -> impl Stream<Item = LogEntry> {
+    struct Poll {
+        ctx: GqlContext,
+        since: Option<i64>,
+        pending: VecDeque<LogEntry>,
+        first: bool,
+    }
+
+    let interval = Duration::from_millis(interval_ms.max(10));
+    let state = Poll {
+        ctx: ctx.clone(),
+        since: None,
+        pending: VecDeque::new(),
+        first: true,
+    };
+    stream::unfold(state, move |mut state| async move {
+        loop {
+            if let Some(entry) = state.pending.pop_front() {
+                return Some((entry, state));
+            }
+            if !std::mem::take(&mut state.first) {
+                tokio::time::sleep(interval).await;
+            }
+            let Some((lines, last_seq)) = fetch_new_logs(&state.ctx, state.since).await else {
+                continue;
+            };
+            // The first answer (and one after the editor restarted) only sets the baseline
+            let restarted = state.since.is_some_and(|since| last_seq < since);
+            if state.since.is_some() && !restarted {
+                state.pending.extend(parse_log_lines(&lines));
+            }
+            state.since = Some(last_seq);
+        }
+    })
+}
+
+/// Parse plugin log lines (`[timestamp] message`) into entries
+///
+/// Severity comes from Godot's message prefixes (`ERROR:`, `SCRIPT ERROR:`,
+/// `USER WARNING:`, ...); anything else is `INFO`. An indented `at:` line
+/// gives the location of the entry before it and is not an entry itself.
+pub fn parse_log_lines(lines: &[String]) -> Vec<LogEntry> {
+    let location = Regex::new(r"(res://[^\s:()]+):(\d+)").expect("valid regex");
+    let mut entries: Vec<LogEntry> = Vec::new();
+
+    for line in lines {
+        let (timestamp, message) = match line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+        {
+            Some((timestamp, message)) => (timestamp.to_string(), message),
+            None => (String::new(), line.as_str()),
+        };
+        let source = location
+            .captures(message)
+            .map(|c| (c[1].to_string(), c[2].parse::<i32>().ok()));
+
+        if message.trim_start().starts_with("at:") {
+            if let Some(previous) = entries.last_mut().filter(|e| e.file.is_none()) {
+                if let Some((file, line)) = source {
+                    previous.file = Some(file);
+                    previous.line = line;
+                }
+                continue;
+            }
+        }
+
+        let (file, line) = source.map_or((None, None), |(file, line)| (Some(file), line));
+        entries.push(LogEntry {
+            message: message.to_string(),
+            severity: log_severity(message).to_string(),
+            timestamp,
+            file,
+            line,
+        });
+    }
+    entries
+}
+
+fn log_severity(message: &str) -> &'static str {
+    let message = message.trim_start();
+    let message = message.strip_prefix("USER ").unwrap_or(message);
+    let message = message.strip_prefix("SCRIPT ").unwrap_or(message);
+    if message.starts_with("ERROR:") || message.starts_with("Parse Error:") {
+        "ERROR"
+    } else if message.starts_with("WARNING:") {
+        "WARNING"
+    } else {
+        "INFO"
+    }
+}
+
 pub async fn resolve_object_by_id(ctx: &GqlContext, object_id: String) -> Option<GodotObject> {
     let result = execute_live_command(ctx, GodotLiveCommand::GetObjectById { object_id }).await;
     match result {
@@ -446,6 +876,605 @@ async fn execute_simple_command(ctx: &GqlContext, command: GodotLiveCommand) ->
     }
 }
 
+// ======================
+// Play-in-editor Resolvers
+// ======================
+
+/// Resolve playState query - whether the editor is running the game (F5/F6)
+pub async fn resolve_play_state(ctx: &GqlContext) -> PlayState {
+    match execute_live_command(ctx, GodotLiveCommand::GetPlayState).await {
+        Ok(value) if value.get("error").is_none() => PlayState {
+            editor_connected: true,
+            playing: value["playing"].as_bool().unwrap_or(false),
+            scene: value["scene"]
+                .as_str()
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        },
+        _ => PlayState {
+            editor_connected: false,
+            playing: false,
+            scene: None,
+        },
+    }
+}
+
+/// Resolve playMainScene mutation - the editor's Play button (F5)
+pub async fn resolve_play_main_scene(ctx: &GqlContext) -> OperationResult {
+    execute_play_command(ctx, GodotLiveCommand::PlayMainScene).await
+}
+
+/// Resolve playCurrentScene mutation - the editor's Play Scene button (F6)
+pub async fn resolve_play_current_scene(ctx: &GqlContext) -> OperationResult {
+    execute_play_command(ctx, GodotLiveCommand::PlayCurrentScene).await
+}
+
+/// Resolve stopPlayingScene mutation - the editor's Stop button (F8)
+pub async fn resolve_stop_playing_scene(ctx: &GqlContext) -> OperationResult {
+    execute_play_command(ctx, GodotLiveCommand::StopPlayingScene).await
+}
+
+/// Run a play command, surfacing errors reported by the plugin
+async fn execute_play_command(ctx: &GqlContext, command: GodotLiveCommand) -> OperationResult {
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return OperationResult::err(e.to_structured_error()),
+    };
+
+    match value.get("error").and_then(|e| e.as_str()) {
+        Some(message) => OperationResult::err(
+            GqlStructuredError::new("PLAY_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion(
+                    "メインシーン（run/main_scene）が設定されているか、シーンが開かれているか確認してください",
+                ),
+        ),
+        None => OperationResult::ok(),
+    }
+}
+
+// ======================
+// Input Simulation Resolvers
+// ======================
+
+/// Longest wait allowed after one step of playInputSequence
+const MAX_INPUT_WAIT_MS: i32 = 60_000;
+
+/// Resolve sendInputAction mutation - press, release or tap an InputMap action in the game
+pub async fn resolve_send_input_action(
+    ctx: &GqlContext,
+    action: String,
+    pressed: Option<bool>,
+    strength: f64,
+) -> OperationResult {
+    let command = GodotLiveCommand::SendInputAction {
+        action,
+        pressed,
+        strength,
+    };
+    send_input(ctx, command).await
+}
+
+/// Resolve sendKey mutation - press, release or tap a key in the game
+pub async fn resolve_send_key(
+    ctx: &GqlContext,
+    key: String,
+    pressed: Option<bool>,
+) -> OperationResult {
+    send_input(ctx, GodotLiveCommand::SendKey { key, pressed }).await
+}
+
+/// Resolve sendMouseClick mutation - click at a position of the game's viewport
+pub async fn resolve_send_mouse_click(
+    ctx: &GqlContext,
+    x: f64,
+    y: f64,
+    button: i32,
+) -> OperationResult {
+    send_input(ctx, GodotLiveCommand::SendMouseClick { x, y, button }).await
+}
+
+/// Resolve playInputSequence mutation - send input steps to the game in order,
+/// waiting `waitMs` after each. Every step is checked before the first is sent.
+pub async fn resolve_play_input_sequence(
+    ctx: &GqlContext,
+    steps: Vec<InputStepInput>,
+) -> InputSequenceResult {
+    let failure = |steps_sent: i32, error: Box<GqlStructuredError>| InputSequenceResult {
+        success: false,
+        steps_sent,
+        error: Some(*error),
+    };
+
+    let mut commands = Vec::with_capacity(steps.len());
+    for (index, step) in steps.into_iter().enumerate() {
+        let wait_ms = step.wait_ms;
+        let command = if (0..=MAX_INPUT_WAIT_MS).contains(&wait_ms) {
+            input_step_command(step)
+        } else {
+            Err(invalid_input(format!(
+                "waitMs must be between 0 and {}",
+                MAX_INPUT_WAIT_MS
+            )))
+        };
+        match command {
+            Ok(command) => commands.push((command, wait_ms as u64)),
+            Err(mut error) => {
+                error.message = format!("steps[{}]: {}", index, error.message);
+                return failure(0, error);
+            }
+        }
+    }
+
+    let total = commands.len() as i32;
+    for (sent, (command, wait_ms)) in commands.into_iter().enumerate() {
+        if let Err(error) = execute_input_command(ctx, command).await {
+            return failure(sent as i32, error);
+        }
+        if wait_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+        }
+    }
+    InputSequenceResult {
+        success: true,
+        steps_sent: total,
+        error: None,
+    }
+}
+
+/// Check and send a single input command
+async fn send_input(ctx: &GqlContext, command: GodotLiveCommand) -> OperationResult {
+    let sent = match validate_input_command(&command) {
+        Ok(()) => execute_input_command(ctx, command).await,
+        Err(error) => Err(error),
+    };
+    match sent {
+        Ok(()) => OperationResult::ok(),
+        Err(error) => OperationResult::err(*error),
+    }
+}
+
+/// Live command of one sequence step, which must set exactly one input
+fn input_step_command(step: InputStepInput) -> Result<GodotLiveCommand, Box<GqlStructuredError>> {
+    let command = match (step.action, step.key, step.mouse_click) {
+        (Some(action), None, None) => GodotLiveCommand::SendInputAction {
+            action,
+            pressed: step.pressed,
+            strength: 1.0,
+        },
+        (None, Some(key), None) => GodotLiveCommand::SendKey {
+            key,
+            pressed: step.pressed,
+        },
+        (None, None, Some(click)) => GodotLiveCommand::SendMouseClick {
+            x: click.x,
+            y: click.y,
+            button: click.button,
+        },
+        _ => {
+            return Err(invalid_input(
+                "set exactly one of action, key and mouseClick",
+            ))
+        }
+    };
+    validate_input_command(&command)?;
+    Ok(command)
+}
+
+fn validate_input_command(command: &GodotLiveCommand) -> Result<(), Box<GqlStructuredError>> {
+    match command {
+        GodotLiveCommand::SendInputAction { action, .. } if action.trim().is_empty() => {
+            Err(invalid_input("action must not be empty"))
+        }
+        GodotLiveCommand::SendInputAction { strength, .. } if !(0.0..=1.0).contains(strength) => {
+            Err(invalid_input("strength must be between 0 and 1"))
+        }
+        GodotLiveCommand::SendKey { key, .. } if key.trim().is_empty() => {
+            Err(invalid_input("key must not be empty"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> Box<GqlStructuredError> {
+    Box::new(GqlStructuredError::new(
+        "INVALID_INPUT",
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
+
+/// Send an input command, surfacing errors reported by the plugin or the game
+async fn execute_input_command(
+    ctx: &GqlContext,
+    command: GodotLiveCommand,
+) -> Result<(), Box<GqlStructuredError>> {
+    let value = execute_live_command(ctx, command)
+        .await
+        .map_err(|e| Box::new(e.to_structured_error()))?;
+
+    match value.get("error").and_then(|e| e.as_str()) {
+        Some(message) => Err(Box::new(
+            GqlStructuredError::new("INPUT_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion(
+                    "playMainScene / playCurrentScene でゲームを実行中か、アクション名・キー名が正しいか確認してください",
+                ),
+        )),
+        None => Ok(()),
+    }
+}
+
+// ======================
+// Script Profiler Resolvers
+// ======================
+
+/// Resolve startProfiling mutation - clear and start the editor's script profiler
+pub async fn resolve_start_profiling(ctx: &GqlContext) -> OperationResult {
+    match execute_profiler_command(ctx, GodotLiveCommand::StartProfiling).await {
+        Ok(value) if value["game_running"].as_bool() == Some(false) => OperationResult {
+            success: true,
+            message: Some("Profiling starts when the game is run from the editor".to_string()),
+            error: None,
+        },
+        Ok(_) => OperationResult::ok(),
+        Err(error) => OperationResult::err(*error),
+    }
+}
+
+/// Resolve stopProfiling mutation - stop recording; the report keeps the data
+pub async fn resolve_stop_profiling(ctx: &GqlContext) -> OperationResult {
+    match execute_profiler_command(ctx, GodotLiveCommand::StopProfiling).await {
+        Ok(_) => OperationResult::ok(),
+        Err(error) => OperationResult::err(*error),
+    }
+}
+
+/// Resolve profileReport query - script functions by average self time, slowest first
+pub async fn resolve_profile_report(
+    ctx: &GqlContext,
+    limit: i32,
+    include_addons: bool,
+) -> ProfileReport {
+    let report = |editor_connected: bool, error: Option<GqlStructuredError>| ProfileReport {
+        editor_connected,
+        profiling: false,
+        functions: vec![],
+        error,
+    };
+    let value = match execute_live_command(ctx, GodotLiveCommand::GetProfileReport).await {
+        Ok(value) => value,
+        Err(_) => return report(false, None),
+    };
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return report(true, Some(profiler_error(message)));
+    }
+
+    let mut functions: Vec<ProfiledFunction> = value["functions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(profiled_function)
+        .filter(|f| include_addons || !f.script.starts_with("res://addons/"))
+        .collect();
+    functions.sort_by(|a, b| {
+        b.time_ms
+            .partial_cmp(&a.time_ms)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.calls.cmp(&a.calls))
+    });
+    functions.truncate(limit.max(0) as usize);
+
+    ProfileReport {
+        editor_connected: true,
+        profiling: value["profiling"].as_bool().unwrap_or(false),
+        functions,
+        error: None,
+    }
+}
+
+/// Script function row of the Profiler panel; other rows (engine categories) are skipped
+fn profiled_function(row: &Value) -> Option<ProfiledFunction> {
+    let script = row["script"].as_str().filter(|s| !s.is_empty())?;
+    // Shown as "1.25 ms" (times) or "3.50 %" (frame percentages)
+    let time = row["time"].as_str().unwrap_or("");
+    let (number, unit) = time.trim().split_once(' ').unwrap_or((time, ""));
+    let number = number.parse::<f64>().ok();
+    Some(ProfiledFunction {
+        name: row["name"].as_str().unwrap_or("").to_string(),
+        script: script.to_string(),
+        line: row["line"].as_i64().filter(|l| *l > 0).map(|l| l as i32),
+        time_ms: number.filter(|_| unit == "ms").unwrap_or(0.0),
+        frame_percent: number.filter(|_| unit == "%"),
+        calls: row["calls"].as_i64().unwrap_or(0) as i32,
+    })
+}
+
+/// Send a profiler command, surfacing errors reported by the plugin
+async fn execute_profiler_command(
+    ctx: &GqlContext,
+    command: GodotLiveCommand,
+) -> Result<Value, Box<GqlStructuredError>> {
+    let value = execute_live_command(ctx, command)
+        .await
+        .map_err(|e| Box::new(e.to_structured_error()))?;
+
+    match value.get("error").and_then(|e| e.as_str()) {
+        Some(message) => Err(Box::new(profiler_error(message))),
+        None => Ok(value),
+    }
+}
+
+fn profiler_error(message: &str) -> GqlStructuredError {
+    GqlStructuredError::new("PROFILER_FAILED", GqlErrorCategory::Godot, message).with_suggestion(
+        "エディターの「デバッガー > プロファイラー」パネルが利用できるか確認してください",
+    )
+}
+
+// ======================
+// Remote Evaluation Resolvers
+// ======================
+
+/// Resolve evalExpression mutation - the game evaluates the expression with the
+/// node as `self`, and the server polls the plugin until the answer arrives
+pub async fn resolve_eval_expression(
+    ctx: &GqlContext,
+    node_path: String,
+    expression: String,
+) -> EvalResult {
+    let failure = |error: Box<GqlStructuredError>| EvalResult {
+        success: false,
+        value: None,
+        value_type: None,
+        json: None,
+        error: Some(*error),
+    };
+    if expression.trim().is_empty() {
+        return failure(invalid_input("expression must not be empty"));
+    }
+    let node_path = if node_path.is_empty() {
+        ".".to_string()
+    } else {
+        node_path
+    };
+
+    match evaluate_in_game(ctx, node_path, expression).await {
+        Ok(result) => EvalResult {
+            success: true,
+            value: result["value"].as_str().map(str::to_string),
+            value_type: result["type"].as_str().map(str::to_string),
+            json: result["json"]
+                .as_str()
+                .and_then(|json| serde_json::from_str(json).ok())
+                .map(async_graphql::Json),
+            error: None,
+        },
+        Err(error) => failure(error),
+    }
+}
+
+async fn evaluate_in_game(
+    ctx: &GqlContext,
+    node_path: String,
+    expression: String,
+) -> Result<Value, Box<GqlStructuredError>> {
+    let command = GodotLiveCommand::EvalExpression {
+        node_path,
+        expression,
+    };
+    let started = execute_eval_command(ctx, command).await?;
+    let id = started["id"].as_i64().ok_or_else(|| {
+        Box::new(GqlStructuredError::new(
+            "EVAL_FAILED",
+            GqlErrorCategory::Godot,
+            "The plugin did not return an evaluation id",
+        ))
+    })?;
+
+    let deadline = Instant::now() + Duration::from_millis(ctx.timeout_ms);
+    loop {
+        let result = execute_eval_command(ctx, GodotLiveCommand::GetEvalResult { id }).await?;
+        if result["pending"].as_bool() != Some(true) {
+            return Ok(result);
+        }
+        if Instant::now() >= deadline {
+            return Err(Box::new(
+                GqlStructuredError::new(
+                    "EVAL_TIMEOUT",
+                    GqlErrorCategory::Godot,
+                    format!("The game did not answer within {} ms", ctx.timeout_ms),
+                )
+                .with_suggestion("ゲームがブレークポイントで停止していないか確認してください"),
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Send an evaluation command, surfacing errors reported by the plugin or the game
+async fn execute_eval_command(
+    ctx: &GqlContext,
+    command: GodotLiveCommand,
+) -> Result<Value, Box<GqlStructuredError>> {
+    let value = execute_live_command(ctx, command)
+        .await
+        .map_err(|e| Box::new(e.to_structured_error()))?;
+
+    match value.get("error").and_then(|e| e.as_str()) {
+        Some(message) => Err(Box::new(
+            GqlStructuredError::new("EVAL_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion(
+                    "ゲームを実行中か、ノードパス（現在のシーンからの相対パスまたは /root/...）と式が正しいか確認してください",
+                ),
+        )),
+        None => Ok(value),
+    }
+}
+
+// ======================
+// Editor Settings Resolvers
+// ======================
+
+/// Settings reported by `editorSettings` when neither names nor a prefix are given:
+/// external editor, script editor behavior, autosave and editor FPS limits
+const DEFAULT_EDITOR_SETTINGS: &[&str] = &[
+    "text_editor/external/use_external_editor",
+    "text_editor/external/exec_path",
+    "text_editor/external/exec_flags",
+    "text_editor/behavior/indent/type",
+    "text_editor/behavior/indent/size",
+    "text_editor/behavior/files/autosave_interval_secs",
+    "text_editor/behavior/files/trim_trailing_whitespace_on_save",
+    "run/auto_save/save_before_running",
+    "interface/editor/low_processor_mode_sleep_usec",
+    "interface/editor/unfocused_low_processor_mode_sleep_usec",
+    "interface/editor/update_continuously",
+];
+
+/// Resolve editorSettings query - editor settings by name or path prefix (live)
+pub async fn resolve_editor_settings(
+    ctx: &GqlContext,
+    names: Option<Vec<String>>,
+    prefix: Option<String>,
+) -> EditorSettings {
+    let prefix = prefix.unwrap_or_default();
+    let names = match names {
+        Some(names) => names,
+        None if prefix.is_empty() => DEFAULT_EDITOR_SETTINGS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    match execute_live_command(ctx, GodotLiveCommand::GetEditorSettings { names, prefix }).await {
+        Ok(value) if value.get("error").is_none() => EditorSettings {
+            editor_connected: true,
+            settings: value["settings"]
+                .as_array()
+                .map(|settings| {
+                    settings
+                        .iter()
+                        .map(|s| EditorSetting {
+                            name: s["name"].as_str().unwrap_or("").to_string(),
+                            value: s["value"].as_str().unwrap_or("").to_string(),
+                            value_type: s["type"].as_str().unwrap_or("").to_string(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        },
+        _ => EditorSettings {
+            editor_connected: false,
+            settings: vec![],
+        },
+    }
+}
+
+/// Resolve setEditorSetting mutation - change an editor setting (live)
+pub async fn resolve_set_editor_setting(
+    ctx: &GqlContext,
+    input: SetEditorSettingInput,
+) -> OperationResult {
+    let command = GodotLiveCommand::SetEditorSetting {
+        name: input.name.clone(),
+        value: input.value,
+    };
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return OperationResult::err(e.to_structured_error()),
+    };
+
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return OperationResult::err(
+            GqlStructuredError::new("EDITOR_SETTING_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion(
+                    "editorSettings(prefix: ...) で設定名と現在の値の型を確認してください",
+                ),
+        );
+    }
+    OperationResult {
+        success: true,
+        message: Some(format!(
+            "{} = {} (was {})",
+            input.name,
+            value["value"].as_str().unwrap_or(""),
+            value["previous"].as_str().unwrap_or("")
+        )),
+        error: None,
+    }
+}
+
+// ======================
+// Editor Camera Resolvers
+// ======================
+
+/// Main screens of the editor, as named by `EditorInterface.set_main_screen_editor`
+const MAIN_SCREENS: [&str; 4] = ["2D", "3D", "Script", "AssetLib"];
+
+/// Resolve frameNode mutation - select a node and point the 2D/3D editor view at it
+pub async fn resolve_frame_node(ctx: &GqlContext, node_path: String) -> OperationResult {
+    execute_camera_command(ctx, GodotLiveCommand::FrameNode { node_path }).await
+}
+
+/// Resolve setEditorCamera mutation - place the 3D editor camera
+pub async fn resolve_set_editor_camera(
+    ctx: &GqlContext,
+    input: SetEditorCameraInput,
+) -> OperationResult {
+    let position = [input.position.x, input.position.y, input.position.z];
+    let look_at = [input.look_at.x, input.look_at.y, input.look_at.z];
+    if position == look_at {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                "position and lookAt must differ",
+            )
+            .with_suggestion("lookAt にはカメラ位置と異なる注視点を指定してください"),
+        );
+    }
+    execute_camera_command(ctx, GodotLiveCommand::SetEditorCamera { position, look_at }).await
+}
+
+/// Resolve setMainScreen mutation - switch the editor's main screen (2D/3D/Script/AssetLib)
+pub async fn resolve_set_main_screen(ctx: &GqlContext, screen: String) -> OperationResult {
+    let Some(screen) = MAIN_SCREENS
+        .iter()
+        .find(|s| s.eq_ignore_ascii_case(&screen))
+    else {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                format!("Unknown main screen: {}", screen),
+            )
+            .with_suggestion(format!(
+                "{} のいずれかを指定してください",
+                MAIN_SCREENS.join(" / ")
+            )),
+        );
+    };
+    let command = GodotLiveCommand::SetMainScreen {
+        screen: screen.to_string(),
+    };
+    execute_camera_command(ctx, command).await
+}
+
+/// Run a camera command, surfacing errors reported by the plugin
+async fn execute_camera_command(ctx: &GqlContext, command: GodotLiveCommand) -> OperationResult {
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return OperationResult::err(e.to_structured_error()),
+    };
+
+    match value.get("error").and_then(|e| e.as_str()) {
+        Some(message) => OperationResult::err(
+            GqlStructuredError::new("CAMERA_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion("シーンが開かれているか、ノードパスが正しいか確認してください"),
+        ),
+        None => OperationResult::ok(),
+    }
+}
+
 // ======================
 // Phase 3: Debug Enhanced Resolvers
 // ======================
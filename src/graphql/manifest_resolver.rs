@@ -0,0 +1,258 @@
+//! Manifest Resolver
+//!
+//! Builds a compact, machine-readable project manifest that clients can cache
+//! at session start and refresh incrementally with `manifestDelta`.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde_json::{json, Value};
+
+use crate::godot::project_settings::ProjectSettings;
+use crate::path_utils;
+use crate::time_utils::now_millis;
+
+use super::context::GqlContext;
+use super::dependency_resolver::{build_dependency_graph, enum_name};
+use super::project_resolver::{collect_project_files, read_project_settings};
+use super::refactoring_resolver::resolve_autoloads;
+use super::types::*;
+
+/// Manifest format version, bumped on incompatible layout changes
+const MANIFEST_VERSION: i32 = 1;
+
+/// project.godot settings worth exposing up front
+const SETTINGS_OF_INTEREST: &[&str] = &[
+    "application/config/name",
+    "application/config/features",
+    "application/run/main_scene",
+    "display/window/size/viewport_width",
+    "display/window/size/viewport_height",
+    "display/window/stretch/mode",
+    "rendering/renderer/rendering_method",
+    "physics/common/physics_ticks_per_second",
+];
+
+/// Resolve projectManifest query
+pub fn resolve_project_manifest(ctx: &GqlContext) -> ProjectManifest {
+    let generated_at = now_millis();
+    let (scenes, scripts) = collect_project_files(ctx);
+    let (_, edges) = build_dependency_graph(ctx);
+
+    let mut manifest = project_section(ctx);
+    manifest["version"] = json!(MANIFEST_VERSION);
+    manifest["generatedAt"] = json!(generated_at);
+    manifest["scenes"] = Value::Array(
+        scenes
+            .iter()
+            .filter_map(|s| scene_entry(ctx, &s.path))
+            .collect(),
+    );
+    manifest["scripts"] = Value::Array(
+        scripts
+            .iter()
+            .filter_map(|s| script_entry(ctx, &s.path))
+            .collect(),
+    );
+    manifest["dependencies"] = Value::Array(edges.iter().map(edge_entry).collect());
+
+    ProjectManifest {
+        generated_at: generated_at.to_string(),
+        scene_count: scenes.len() as i32,
+        script_count: scripts.len() as i32,
+        json: serde_json::to_string(&manifest).unwrap_or_default(),
+    }
+}
+
+/// Resolve manifestDelta query
+///
+/// Only files modified after `since` (the `generatedAt` of a previous manifest
+/// or delta) are re-parsed. Project-level sections are included when
+/// project.godot itself changed. Removed files are detected against
+/// `known_paths`, the scene/script paths the client currently holds.
+pub fn resolve_manifest_delta(
+    ctx: &GqlContext,
+    since: &str,
+    known_paths: Option<Vec<String>>,
+) -> ManifestDelta {
+    let generated_at = now_millis();
+    let Ok(since_millis) = since.parse::<u64>() else {
+        return ManifestDelta {
+            since: since.to_string(),
+            generated_at: generated_at.to_string(),
+            changed_files: Vec::new(),
+            removed_files: Vec::new(),
+            project_changed: false,
+            json: None,
+            message: Some(format!("Invalid 'since' timestamp: {}", since)),
+        };
+    };
+
+    let (scenes, scripts) = collect_project_files(ctx);
+    let is_changed = |res_path: &str| {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+        modified_millis(ctx, &fs_path)
+            .map(|m| m >= since_millis)
+            .unwrap_or(true)
+    };
+
+    let changed_scenes: Vec<&str> = scenes
+        .iter()
+        .map(|s| s.path.as_str())
+        .filter(|p| is_changed(p))
+        .collect();
+    let changed_scripts: Vec<&str> = scripts
+        .iter()
+        .map(|s| s.path.as_str())
+        .filter(|p| is_changed(p))
+        .collect();
+    let project_changed = modified_millis(ctx, &ctx.project_path.join("project.godot"))
+        .map(|m| m >= since_millis)
+        .unwrap_or(false);
+
+    let current: HashSet<&str> = scenes
+        .iter()
+        .map(|s| s.path.as_str())
+        .chain(scripts.iter().map(|s| s.path.as_str()))
+        .collect();
+    let removed_files: Vec<String> = known_paths
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| !current.contains(p.as_str()))
+        .collect();
+
+    let changed_files: Vec<String> = changed_scenes
+        .iter()
+        .chain(changed_scripts.iter())
+        .map(|p| p.to_string())
+        .collect();
+
+    let mut delta = if project_changed {
+        project_section(ctx)
+    } else {
+        json!({})
+    };
+    delta["version"] = json!(MANIFEST_VERSION);
+    delta["generatedAt"] = json!(generated_at);
+    delta["scenes"] = Value::Array(
+        changed_scenes
+            .iter()
+            .filter_map(|p| scene_entry(ctx, p))
+            .collect(),
+    );
+    delta["scripts"] = Value::Array(
+        changed_scripts
+            .iter()
+            .filter_map(|p| script_entry(ctx, p))
+            .collect(),
+    );
+    delta["removed"] = json!(removed_files);
+
+    // Outgoing edges of changed files replace the client's previous edges for them
+    if !changed_files.is_empty() {
+        let changed: HashSet<&str> = changed_files.iter().map(|p| p.as_str()).collect();
+        let (_, edges) = build_dependency_graph(ctx);
+        delta["dependencies"] = Value::Array(
+            edges
+                .iter()
+                .filter(|e| changed.contains(e.from.as_str()))
+                .map(edge_entry)
+                .collect(),
+        );
+    } else {
+        delta["dependencies"] = json!([]);
+    }
+
+    ManifestDelta {
+        since: since.to_string(),
+        generated_at: generated_at.to_string(),
+        changed_files,
+        removed_files,
+        project_changed,
+        json: Some(serde_json::to_string(&delta).unwrap_or_default()),
+        message: None,
+    }
+}
+
+/// Project-level sections derived from project.godot
+fn project_section(ctx: &GqlContext) -> Value {
+    let settings = read_project_settings(ctx)
+        .map(|(_, settings)| settings)
+        .unwrap_or_else(|_| ProjectSettings::parse(""));
+
+    let autoloads: Vec<Value> = resolve_autoloads(ctx)
+        .autoloads
+        .into_iter()
+        .map(|a| json!({ "name": a.name, "path": a.path, "isSingleton": a.is_singleton }))
+        .collect();
+
+    let settings_of_interest: serde_json::Map<String, Value> = SETTINGS_OF_INTEREST
+        .iter()
+        .filter_map(|key| {
+            settings
+                .get(key)
+                .map(|v| (key.to_string(), json!(v.trim_matches('"'))))
+        })
+        .collect();
+
+    json!({
+        "project": {
+            "name": settings.get("application/config/name").map(|v| v.trim_matches('"')),
+            "mainScene": settings.get("application/run/main_scene").map(|v| v.trim_matches('"')),
+        },
+        "autoloads": autoloads,
+        "inputActions": settings
+            .entries("input")
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>(),
+        "settings": settings_of_interest,
+    })
+}
+
+fn scene_entry(ctx: &GqlContext, res_path: &str) -> Option<Value> {
+    let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    let scene = ctx.index.scene(&fs_path).ok()?;
+    let root = scene.nodes.iter().find(|n| n.parent.is_none());
+
+    Some(json!({
+        "path": res_path,
+        "rootName": root.map(|n| n.name.as_str()),
+        "rootType": root.map(|n| n.node_type.as_str()),
+        "nodeCount": scene.nodes.len(),
+    }))
+}
+
+fn script_entry(ctx: &GqlContext, res_path: &str) -> Option<Value> {
+    let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    let (class_name, extends) = if res_path.ends_with(".cs") {
+        let script = ctx.index.csharp_script(&fs_path)?;
+        (script.class_name.clone(), script.base_class.clone())
+    } else {
+        let script = ctx.index.script(&fs_path)?;
+        (script.class_name.clone(), script.extends.clone())
+    };
+
+    Some(json!({
+        "path": res_path,
+        "className": class_name,
+        "extends": extends,
+    }))
+}
+
+fn edge_entry(edge: &GraphEdge) -> Value {
+    json!({
+        "from": edge.from,
+        "to": edge.to,
+        "type": enum_name(&edge.reference_type),
+    })
+}
+
+fn modified_millis(ctx: &GqlContext, path: &Path) -> Option<u64> {
+    ctx.fs
+        .metadata(path)
+        .ok()
+        .and_then(|m| m.modified.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
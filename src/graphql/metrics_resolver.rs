@@ -0,0 +1,115 @@
+//! Metrics Resolver
+//!
+//! Runtime performance metrics read from `Performance.get_monitor` by the
+//! editor plugin: FPS, frame and physics time, draw calls and memory. With a
+//! sampling duration, the server polls the plugin and averages the samples.
+
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::types::*;
+
+/// Longest sampling duration
+const MAX_DURATION_MS: i32 = 10_000;
+/// Shortest interval between samples
+const MIN_INTERVAL_MS: i32 = 16;
+
+/// Resolve performanceMetrics query
+pub async fn resolve_performance_metrics(
+    ctx: &GqlContext,
+    duration_ms: i32,
+    interval_ms: i32,
+) -> PerformanceMetrics {
+    let duration_ms = duration_ms.clamp(0, MAX_DURATION_MS) as u64;
+    let interval_ms = interval_ms.max(MIN_INTERVAL_MS) as u64;
+
+    // One reading at the start and one every interval until the duration is up
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    for index in 0..=duration_ms / interval_ms {
+        tokio::time::sleep_until((start + Duration::from_millis(index * interval_ms)).into()).await;
+        match execute_live_command(ctx, GodotLiveCommand::GetPerformanceMetrics).await {
+            Ok(value) if value.get("error").is_none() => samples.push(Sample::from_value(&value)),
+            // Keep what was sampled before the editor went away
+            _ => break,
+        }
+    }
+
+    average(&samples)
+}
+
+/// One reading of the monitors
+#[derive(Debug, Default, Clone, Copy)]
+struct Sample {
+    fps: f64,
+    frame_time_ms: f64,
+    physics_time_ms: f64,
+    draw_calls: f64,
+    objects_in_frame: f64,
+    static_memory_mb: f64,
+    video_memory_mb: f64,
+    object_count: f64,
+    node_count: f64,
+}
+
+impl Sample {
+    fn from_value(value: &Value) -> Self {
+        let monitors = &value["monitors"];
+        let get = |name: &str| monitors[name].as_f64().unwrap_or(0.0);
+        let mb = |name: &str| get(name) / (1024.0 * 1024.0);
+        Self {
+            fps: get("fps"),
+            frame_time_ms: get("process_time") * 1000.0,
+            physics_time_ms: get("physics_process_time") * 1000.0,
+            draw_calls: get("draw_calls"),
+            objects_in_frame: get("objects_in_frame"),
+            static_memory_mb: mb("static_memory"),
+            video_memory_mb: mb("video_memory"),
+            object_count: get("object_count"),
+            node_count: get("node_count"),
+        }
+    }
+}
+
+fn average(samples: &[Sample]) -> PerformanceMetrics {
+    if samples.is_empty() {
+        return PerformanceMetrics {
+            editor_connected: false,
+            sample_count: 0,
+            fps: 0.0,
+            min_fps: 0.0,
+            frame_time_ms: 0.0,
+            max_frame_time_ms: 0.0,
+            physics_time_ms: 0.0,
+            draw_calls: 0.0,
+            objects_in_frame: 0.0,
+            static_memory_mb: 0.0,
+            video_memory_mb: 0.0,
+            object_count: 0,
+            node_count: 0,
+        };
+    }
+
+    let count = samples.len() as f64;
+    let mean = |field: fn(&Sample) -> f64| samples.iter().map(field).sum::<f64>() / count;
+    let last = samples[samples.len() - 1];
+    PerformanceMetrics {
+        editor_connected: true,
+        sample_count: samples.len() as i32,
+        fps: mean(|s| s.fps),
+        min_fps: samples.iter().map(|s| s.fps).fold(f64::INFINITY, f64::min),
+        frame_time_ms: mean(|s| s.frame_time_ms),
+        max_frame_time_ms: samples.iter().map(|s| s.frame_time_ms).fold(0.0, f64::max),
+        physics_time_ms: mean(|s| s.physics_time_ms),
+        draw_calls: mean(|s| s.draw_calls),
+        objects_in_frame: mean(|s| s.objects_in_frame),
+        // Memory and object counts are levels, not rates: report the latest
+        static_memory_mb: last.static_memory_mb,
+        video_memory_mb: last.video_memory_mb,
+        object_count: last.object_count as i32,
+        node_count: last.node_count as i32,
+    }
+}
@@ -3,27 +3,94 @@
 //! Single source of truth: `docs/gql/schema.graphql`
 //! This module implements the schema in Rust using async-graphql.
 
+pub mod asset_fetch;
+pub mod audit;
+pub mod backup;
 pub mod context;
+pub mod coverage;
 pub mod dependency_resolver;
 pub mod error;
+pub mod gdtoolkit;
+pub mod godot_binary;
+pub mod journal;
+pub mod junit;
 pub mod live_resolver;
+pub mod node_bounds;
+pub mod project_index;
+pub mod run_report;
+pub mod scene_lock;
+pub mod scene_warmup;
+pub mod thumbnails;
+pub mod transaction;
+pub mod vfs;
 
 // Domain-specific resolvers (decomposed from monolithic resolver.rs)
+mod activity_resolver;
+mod animation_resolver;
+mod asset_resolver;
+mod audio_resolver;
+mod backup_resolver;
+mod branch_resolver;
+mod checkpoint_resolver;
+mod class_icon_resolver;
+mod cleanup_resolver;
 mod codegen_resolver;
+mod collision_resolver;
+mod context_pack_resolver;
+mod contracts_resolver;
+mod coverage_resolver;
+mod custom_resource_resolver;
+mod dead_code_resolver;
+mod decision_resolver;
+mod dependency_cache;
+mod diagnostics_resolver;
+mod example_project_resolver;
+mod export_preset_resolver;
+mod feature_pack_resolver;
+mod godot_binary_resolver;
+mod health_resolver;
+mod input_map_resolver;
+mod layout_resolver;
+mod lint_resolver;
+mod live_instances_resolver;
+mod manifest_resolver;
+mod metrics_resolver;
 mod mutation_resolver;
+mod naming_resolver;
+mod node_bounds_resolver;
 mod node_type_resolver;
+mod performance_resolver;
+mod physics_layer_resolver;
+mod populate_resolver;
 mod project_resolver;
+mod property_search_resolver;
 mod refactoring_resolver;
+mod report_resolver;
+mod resource_table_resolver;
+mod run_report_resolver;
+mod scene_drift_resolver;
+mod scene_patch_resolver;
 mod scene_resolver;
+mod scene_template_resolver;
+mod schema_version_resolver;
+mod screenshot_resolver;
 mod script_resolver;
 mod shader_resolver;
+mod signal_flow_resolver;
+mod tags_resolver;
 mod test_resolver;
+mod thumbnail_resolver;
+mod transaction_resolver;
+mod watch_resolver;
+mod wiring_resolver;
 
 // Facade module re-exporting all resolvers
 mod resolver;
 mod schema;
 mod types;
 
-pub use context::GqlContext;
-pub use schema::{build_schema, build_schema_with_context, GqlSchema, MutationRoot, QueryRoot};
+pub use context::{GqlContext, ImageAttachment};
+pub use schema::{
+    build_schema, build_schema_with_context, GqlSchema, MutationRoot, QueryRoot, SubscriptionRoot,
+};
 pub use types::*;
@@ -1,20 +1,62 @@
 //! Mutation Resolver
 //!
-//! Handles mutation validation, preview, and application.
+//! Handles mutation validation, preview, and application, plus node renames,
+//! duplication and cross-scene copy/paste in scene files.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Instant;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::json;
+use similar::TextDiff;
+
+use crate::godot::scene_patch::ScenePatch;
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::backup;
 use super::context::GqlContext;
+use super::node_type_resolver::property_type;
+use super::scene_lock;
+use super::script_resolver::{create_script, script_content};
 use super::types::*;
 
 /// Validate a mutation plan
 pub fn validate_mutation(ctx: &GqlContext, input: &MutationPlanInput) -> MutationValidationResult {
     let start = Instant::now();
     let mut errors = Vec::new();
-    let warnings = Vec::new();
+    let mut warnings = Vec::new();
+    // Operations are checked against the scenes as the earlier ones left them
+    let mut scenes: BTreeMap<String, EditedScene> = BTreeMap::new();
+    // Nodes added to the edited scene (no scenePath) by earlier operations
+    let mut planned_nodes: HashSet<String> = HashSet::new();
 
     for (index, op) in input.operations.iter().enumerate() {
-        let op_errors = validate_operation(ctx, index as i32, op);
+        let mut op_errors = validate_operation(ctx, index as i32, op, &planned_nodes);
+        if op.operation_type == OperationType::AddNode && args_scene_path(op).is_none() {
+            let arg = |key: &str| op.args.0.get(key).and_then(|v| v.as_str());
+            if let (Some(parent), Some(name)) = (arg("parent"), arg("name")) {
+                planned_nodes.insert(if parent == "." {
+                    name.to_string()
+                } else {
+                    format!("{}/{}", parent, name)
+                });
+            }
+        }
+        if op.operation_type != OperationType::CreateScript {
+            if args_scene_path(op).is_none() {
+                warnings.push(MutationValidationWarning {
+                    operation_index: index as i32,
+                    message: "No scenePath: node paths are not checked against a scene file"
+                        .to_string(),
+                });
+            } else if op_errors.is_empty() {
+                op_errors = validate_against_scene(ctx, &mut scenes, index, op);
+            }
+        }
         errors.extend(op_errors);
     }
 
@@ -33,6 +75,7 @@ fn validate_operation(
     _ctx: &GqlContext,
     index: i32,
     op: &PlannedOperation,
+    planned_nodes: &HashSet<String>,
 ) -> Vec<MutationValidationError> {
     let mut errors = Vec::new();
     let args = &op.args.0;
@@ -65,11 +108,12 @@ fn validate_operation(
                 });
             }
 
-            // Check if node path exists (skip for "." which always exists)
-            if let Some(node_path) = args.get("nodePath").and_then(|v| v.as_str()) {
-                if node_path != "." {
-                    // For now, assume non-root nodes might not exist
-                    // In real implementation, we would check against the scene tree
+            // Without a scene file only the root and planned nodes are known to exist
+            if let (Some(node_path), None) = (
+                args.get("nodePath").and_then(|v| v.as_str()),
+                args.get("scenePath"),
+            ) {
+                if node_path != "." && !planned_nodes.contains(node_path) {
                     errors.push(MutationValidationError {
                         operation_index: index,
                         code: "NODE_NOT_FOUND".to_string(),
@@ -106,6 +150,26 @@ fn validate_operation(
                 });
             }
         }
+        OperationType::DuplicateNode => {
+            for key in ["scenePath", "nodePath"] {
+                if args.get(key).is_none() {
+                    errors.push(MutationValidationError {
+                        operation_index: index,
+                        code: "MISSING_REQUIRED_ARG".to_string(),
+                        message: format!("Missing required argument: {}", key),
+                        suggestion: None,
+                    });
+                }
+            }
+            if args.get("nodePath").and_then(|v| v.as_str()) == Some(".") {
+                errors.push(MutationValidationError {
+                    operation_index: index,
+                    code: "CANNOT_DUPLICATE_ROOT".to_string(),
+                    message: "Cannot duplicate root node".to_string(),
+                    suggestion: None,
+                });
+            }
+        }
         OperationType::RemoveNode => {
             // Check required args
             if let Some(path) = args.get("path").and_then(|v| v.as_str()) {
@@ -134,75 +198,312 @@ fn validate_operation(
     errors
 }
 
-/// Preview a mutation (generate diff)
-pub fn preview_mutation(_ctx: &GqlContext, input: &MutationPlanInput) -> PreviewResult {
-    let mut nodes_added = 0;
-    let mut nodes_removed = 0;
-    let mut properties_changed = 0;
-    let mut signals_connected = 0;
-    let mut diff_lines = Vec::new();
-    let affected_files = Vec::new();
+fn args_scene_path(op: &PlannedOperation) -> Option<&str> {
+    op.args.0.get("scenePath").and_then(|v| v.as_str())
+}
 
-    for op in &input.operations {
-        let args = &op.args.0;
+/// Check node paths, names and property types of an operation against its
+/// scene, then apply it there so later operations see its changes
+fn validate_against_scene(
+    ctx: &GqlContext,
+    scenes: &mut BTreeMap<String, EditedScene>,
+    index: usize,
+    op: &PlannedOperation,
+) -> Vec<MutationValidationError> {
+    let error = |code: &str, message: String, suggestion: Option<String>| MutationValidationError {
+        operation_index: index as i32,
+        code: code.to_string(),
+        message,
+        suggestion,
+    };
+    let args = &op.args.0;
+    let arg = |key: &str| args.get(key).and_then(|v| v.as_str());
+    let scene = match load_scene(ctx, scenes, args_scene_path(op).unwrap_or_default()) {
+        Ok(edited) => &edited.scene,
+        Err(message) => return vec![error("SCENE_NOT_FOUND", message, None)],
+    };
+    let has_node = |path: &str| scene.nodes.iter().any(|n| node_path(n) == path);
+    let mut errors = Vec::new();
 
-        match op.operation_type {
-            OperationType::SetProperty => {
-                properties_changed += 1;
-                if let (Some(node_path), Some(property), Some(value)) = (
-                    args.get("nodePath").and_then(|v| v.as_str()),
-                    args.get("property").and_then(|v| v.as_str()),
-                    args.get("value").and_then(|v| v.as_str()),
-                ) {
-                    diff_lines.push(format!("+ {}:{} = {}", node_path, property, value));
-                }
-            }
-            OperationType::AddNode => {
-                nodes_added += 1;
-                if let (Some(parent), Some(name), Some(node_type)) = (
-                    args.get("parent").and_then(|v| v.as_str()),
-                    args.get("name").and_then(|v| v.as_str()),
-                    args.get("type").and_then(|v| v.as_str()),
-                ) {
-                    diff_lines.push(format!(
-                        "+ [node name=\"{}\" type=\"{}\" parent=\"{}\"]",
-                        name, node_type, parent
+    let node_args: &[&str] = match op.operation_type {
+        OperationType::AddNode => &["parent"],
+        OperationType::RemoveNode => &["path"],
+        OperationType::ConnectSignal | OperationType::DisconnectSignal => &["from", "to"],
+        OperationType::ReparentNode => &["nodePath", "newParent"],
+        OperationType::CreateScript => &[],
+        _ => &["nodePath"],
+    };
+    for key in node_args {
+        let Some(path) = arg(key) else { continue };
+        if !has_node(path) {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            let suggestion = scene
+                .nodes
+                .iter()
+                .find(|n| n.name == name)
+                .map(|n| format!("Did you mean '{}'?", node_path(n)))
+                .unwrap_or_else(|| "Check the node path is correct".to_string());
+            errors.push(error(
+                "NODE_NOT_FOUND",
+                format!("Node not found: {}", path),
+                Some(suggestion),
+            ));
+        }
+    }
+
+    // A node may not share its name with a sibling
+    let taken = match op.operation_type {
+        OperationType::AddNode => arg("parent").zip(arg("name")),
+        OperationType::ReparentNode => {
+            arg("newParent").zip(arg("nodePath").and_then(|path| path.rsplit('/').next()))
+        }
+        OperationType::DuplicateNode => arg("nodePath")
+            .map(|path| path.rsplit_once('/').map_or(".", |(parent, _)| parent))
+            .zip(arg("newName")),
+        _ => None,
+    };
+    if let Some((parent, name)) = taken {
+        let path = if parent == "." {
+            name.to_string()
+        } else {
+            format!("{}/{}", parent, name)
+        };
+        if errors.is_empty() && has_node(&path) {
+            errors.push(error(
+                "DUPLICATE_NAME",
+                format!("'{}' already has a child named '{}'", parent, name),
+                Some("Choose a name not used by a sibling".to_string()),
+            ));
+        }
+    }
+
+    let properties: Vec<(&str, &serde_json::Value)> = match op.operation_type {
+        OperationType::SetProperty => arg("property").zip(args.get("value")).into_iter().collect(),
+        OperationType::SetProperties => args
+            .get("properties")
+            .and_then(|v| v.as_object())
+            .map(|map| map.iter().map(|(k, v)| (k.as_str(), v)).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    let target = arg("nodePath").and_then(|path| scene.nodes.iter().find(|n| node_path(n) == path));
+    if let Some(node) = target {
+        for (property, value) in properties {
+            let expected = property_type(&node.node_type, property).or_else(|| {
+                node.properties
+                    .get(property)
+                    .and_then(|current| literal_type(current))
+                    .map(str::to_string)
+            });
+            let actual = match value {
+                serde_json::Value::String(literal) => literal_type(literal),
+                other => literal_type(&other.to_string()),
+            };
+            if let (Some(expected), Some(actual)) = (expected, actual) {
+                if !types_compatible(&expected, actual) {
+                    errors.push(error(
+                        "TYPE_MISMATCH",
+                        format!(
+                            "{}.{} is {}, got {} value {}",
+                            node.name, property, expected, actual, value
+                        ),
+                        Some(format!("Write the value as a {} literal", expected)),
                     ));
                 }
             }
-            OperationType::RemoveNode => {
-                nodes_removed += 1;
-                if let Some(path) = args.get("path").and_then(|v| v.as_str()) {
-                    diff_lines.push(format!("- [node at \"{}\"]", path));
-                }
+        }
+    }
+
+    if errors.is_empty() {
+        let mut summary = ChangeSummary::default();
+        if let Err(message) = edit_scene_operation(ctx, scenes, index, op, &mut summary) {
+            errors.push(error("OPERATION_FAILED", message, None));
+        }
+    }
+    errors
+}
+
+/// Value types checked by validateMutation; other property types (enums,
+/// resources) accept any value
+const VALUE_TYPES: &[&str] = &[
+    "bool",
+    "int",
+    "float",
+    "String",
+    "StringName",
+    "NodePath",
+    "Vector2",
+    "Vector2i",
+    "Vector3",
+    "Vector3i",
+    "Vector4",
+    "Vector4i",
+    "Color",
+    "Rect2",
+    "Rect2i",
+    "Transform2D",
+    "Transform3D",
+    "Basis",
+    "Quaternion",
+    "AABB",
+    "Plane",
+];
+
+/// Type of a Godot literal as written in a scene (`Vector2(1, 2)`, `3.5`, `"a"`)
+fn literal_type(literal: &str) -> Option<&'static str> {
+    let literal = literal.trim();
+    let type_name = if literal == "true" || literal == "false" {
+        "bool"
+    } else if literal.parse::<i64>().is_ok() {
+        "int"
+    } else if literal.parse::<f64>().is_ok() {
+        "float"
+    } else if literal.starts_with('"') {
+        "String"
+    } else if literal.starts_with("&\"") {
+        "StringName"
+    } else {
+        let (name, _) = literal.split_once('(')?;
+        return VALUE_TYPES.iter().find(|t| **t == name.trim()).copied();
+    };
+    Some(type_name)
+}
+
+fn types_compatible(expected: &str, actual: &str) -> bool {
+    let numeric = |t: &str| t == "int" || t == "float";
+    let text = |t: &str| t == "String" || t == "StringName" || t == "NodePath";
+    !VALUE_TYPES.contains(&expected)
+        || expected == actual
+        || (numeric(expected) && numeric(actual))
+        || (text(expected) && text(actual))
+}
+
+/// Preview a mutation: apply it to in-memory copies of the files and diff them
+pub fn preview_mutation(ctx: &GqlContext, input: &MutationPlanInput) -> PreviewResult {
+    let mut summary = ChangeSummary::default();
+    let mut errors: Vec<ApplyError> = Vec::new();
+    let mut scenes: BTreeMap<String, EditedScene> = BTreeMap::new();
+    let mut scripts: Vec<(String, String)> = Vec::new();
+    // Operations without a file (edited scene) are described and counted from the plan
+    let mut described = Vec::new();
+
+    for (index, op) in input.operations.iter().enumerate() {
+        let args = &op.args.0;
+        let result = match op.operation_type {
+            OperationType::CreateScript => {
+                preview_create_script(ctx, args, &scripts).map(|script| scripts.push(script))
             }
-            OperationType::ConnectSignal => {
-                signals_connected += 1;
+            _ if args.get("scenePath").is_none() => {
+                described.extend(describe_operation(op, &mut summary));
+                Ok(())
             }
-            _ => {}
+            _ => edit_scene_operation(ctx, &mut scenes, index, op, &mut summary),
+        };
+        if let Err(message) = result {
+            errors.push(ApplyError {
+                operation_index: index as i32,
+                message,
+            });
         }
     }
 
-    // For now, we don't know the actual affected files without context
-    // In a real implementation, we'd analyze which scenes are affected
+    let mut diffs = Vec::new();
+    let mut affected_files = Vec::new();
+    for (scene_path, edited) in &scenes {
+        let edited_content = edited.scene.to_tscn();
+        if edited_content == edited.original {
+            continue;
+        }
+        diffs.push(unified_diff(scene_path, &edited.original, &edited_content));
+        affected_files.push(AffectedFile {
+            path: scene_path.clone(),
+            change_type: FileChangeType::Modified,
+        });
+    }
+    for (path, content) in &scripts {
+        diffs.push(unified_diff(path, "", content));
+        affected_files.push(AffectedFile {
+            path: path.clone(),
+            change_type: FileChangeType::Created,
+        });
+    }
+    diffs.extend(described);
 
     PreviewResult {
-        success: true,
-        diff: diff_lines.join("\n"),
+        success: errors.is_empty(),
+        diff: diffs.join("\n"),
         affected_files,
-        summary: ChangeSummary {
-            nodes_added,
-            nodes_removed,
-            properties_changed,
-            signals_connected,
-        },
+        summary,
+        errors,
+    }
+}
+
+fn unified_diff(path: &str, original: &str, edited: &str) -> String {
+    TextDiff::from_lines(original, edited)
+        .unified_diff()
+        .context_radius(3)
+        .header(path, path)
+        .to_string()
+}
+
+/// Path and content of the script a CREATE_SCRIPT operation would write
+fn preview_create_script(
+    ctx: &GqlContext,
+    args: &serde_json::Value,
+    staged: &[(String, String)],
+) -> Result<(String, String), String> {
+    let input = create_script_input(args)?;
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.path);
+    if ctx.fs.exists(&file_path) || staged.iter().any(|(path, _)| *path == input.path) {
+        return Err(format!("Script already exists: {}", input.path));
+    }
+    Ok((input.path.clone(), script_content(&input)))
+}
+
+/// Description line of an operation on the edited scene, which has no file to diff
+fn describe_operation(op: &PlannedOperation, summary: &mut ChangeSummary) -> Option<String> {
+    let args = &op.args.0;
+    let arg = |key: &str| args.get(key).and_then(|v| v.as_str());
+    match op.operation_type {
+        OperationType::SetProperty => {
+            summary.properties_changed += 1;
+            Some(format!(
+                "+ {}:{} = {}",
+                arg("nodePath")?,
+                arg("property")?,
+                arg("value")?
+            ))
+        }
+        OperationType::AddNode => {
+            summary.nodes_added += 1;
+            Some(format!(
+                "+ [node name=\"{}\" type=\"{}\" parent=\"{}\"]",
+                arg("name")?,
+                arg("type")?,
+                arg("parent")?
+            ))
+        }
+        OperationType::RemoveNode => {
+            summary.nodes_removed += 1;
+            Some(format!("- [node at \"{}\"]", arg("path")?))
+        }
+        OperationType::DuplicateNode => {
+            summary.nodes_added += 1;
+            Some(format!("+ [duplicate of \"{}\"]", arg("nodePath")?))
+        }
+        OperationType::ConnectSignal => {
+            summary.signals_connected += 1;
+            None
+        }
+        _ => None,
     }
 }
 
 /// Apply a mutation
-pub fn apply_mutation(_ctx: &GqlContext, input: &ApplyMutationInput) -> ApplyResult {
+pub async fn apply_mutation(ctx: &GqlContext, input: &ApplyMutationInput) -> ApplyResult {
     let mut applied_count = 0;
-    let errors: Vec<ApplyError> = Vec::new();
+    let mut errors: Vec<ApplyError> = Vec::new();
+    let backups_before: Vec<String> = backup::list(ctx).into_iter().map(|b| b.id).collect();
 
     // Generate undo action ID using system time
     let undo_action_id = if !input.operations.is_empty() {
@@ -215,26 +516,569 @@ pub fn apply_mutation(_ctx: &GqlContext, input: &ApplyMutationInput) -> ApplyRes
         None
     };
 
-    // Process each operation
-    for (_index, _op) in input.operations.iter().enumerate() {
-        // In a real implementation, we would:
-        // 1. Load the affected scene file
-        // 2. Apply the modification
-        // 3. Save the file (or accumulate changes for atomic write)
+    // Scene operations edit the parsed scenes; each scene is written once at the end
+    let mut scenes: BTreeMap<String, EditedScene> = BTreeMap::new();
+    let mut summary = ChangeSummary::default();
+    for (index, op) in input.operations.iter().enumerate() {
+        let result = match op.operation_type {
+            OperationType::CreateScript => create_script_operation(ctx, &op.args.0),
+            _ => edit_scene_operation(ctx, &mut scenes, index, op, &mut summary),
+        };
+        match result {
+            Ok(()) => applied_count += 1,
+            Err(message) => errors.push(ApplyError {
+                operation_index: index as i32,
+                message,
+            }),
+        }
+    }
+
+    for (scene_path, edited) in scenes {
+        if edited.operations.is_empty() {
+            continue;
+        }
+        let lock = match scene_lock::lock_scene(ctx, &scene_path, SceneWritePolicy::Refuse).await {
+            Ok(lock) => lock,
+            Err(e) => {
+                unapply(
+                    &mut applied_count,
+                    &mut errors,
+                    &edited.operations,
+                    &e.message,
+                );
+                continue;
+            }
+        };
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &scene_path);
+        let written = audit::write_file(ctx, "applyMutation", &file_path, edited.scene.to_tscn());
+        scene_lock::unlock_scene(ctx, lock, written.is_ok()).await;
+        match written {
+            Ok(()) => telemetry::record_files_written(1),
+            Err(e) => unapply(
+                &mut applied_count,
+                &mut errors,
+                &edited.operations,
+                &format!("Failed to write scene {}: {}", scene_path, e),
+            ),
+        }
+    }
+    errors.sort_by_key(|e| e.operation_index);
 
-        // For now, just count as applied
-        applied_count += 1;
+    // Every write is backed up; createBackup reports (and describes) this apply's backups
+    let mut backup_path = None;
+    if input.create_backup.unwrap_or(false) {
+        let new_backups: Vec<String> = backup::list(ctx)
+            .into_iter()
+            .map(|b| b.id)
+            .filter(|id| !backups_before.contains(id))
+            .collect();
+        if let Some(description) = &input.backup_description {
+            if let Err(e) = backup::describe(ctx, &new_backups, description) {
+                tracing::warn!("Failed to describe backups: {}", e);
+            }
+        }
+        backup_path = new_backups.first().map(|id| backup::backup_path(id));
     }
 
     ApplyResult {
         success: errors.is_empty(),
         applied_count,
-        backup_path: if input.create_backup.unwrap_or(false) {
-            Some("backup/".to_string())
-        } else {
-            None
-        },
+        backup_path,
         errors,
         undo_action_id,
     }
 }
+
+/// Scene loaded by previewMutation / applyMutation, with the operations applied to it
+struct EditedScene {
+    /// File content before the operations
+    original: String,
+    scene: GodotScene,
+    /// Indexes of the operations that changed the scene
+    operations: Vec<usize>,
+}
+
+/// Operations already counted as applied whose scene could not be written
+fn unapply(
+    applied_count: &mut i32,
+    errors: &mut Vec<ApplyError>,
+    operations: &[usize],
+    message: &str,
+) {
+    for &index in operations {
+        *applied_count -= 1;
+        errors.push(ApplyError {
+            operation_index: index as i32,
+            message: message.to_string(),
+        });
+    }
+}
+
+fn create_script_operation(ctx: &GqlContext, args: &serde_json::Value) -> Result<(), String> {
+    let result = create_script(ctx, &create_script_input(args)?);
+    if result.success {
+        Ok(())
+    } else {
+        Err(result.message.unwrap_or_default())
+    }
+}
+
+fn create_script_input(args: &serde_json::Value) -> Result<CreateScriptInput, String> {
+    Ok(CreateScriptInput {
+        path: required_arg(args, "path")?.to_string(),
+        extends: args
+            .get("extends")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Node")
+            .to_string(),
+        class_name: args
+            .get("className")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    })
+}
+
+/// In-memory copy of a scene, read on first use
+fn load_scene<'a>(
+    ctx: &GqlContext,
+    scenes: &'a mut BTreeMap<String, EditedScene>,
+    scene_path: &str,
+) -> Result<&'a mut EditedScene, String> {
+    if !scenes.contains_key(scene_path) {
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+        let content = ctx
+            .fs
+            .read_to_string(&file_path)
+            .map_err(|e| format!("Failed to read scene {}: {}", scene_path, e))?;
+        let scene = GodotScene::parse(&content)
+            .map_err(|e| format!("Failed to parse scene {}: {}", scene_path, e))?;
+        scenes.insert(
+            scene_path.to_string(),
+            EditedScene {
+                original: content,
+                scene,
+                operations: Vec::new(),
+            },
+        );
+    }
+    Ok(scenes.get_mut(scene_path).expect("scene loaded above"))
+}
+
+/// Apply one operation to the in-memory copy of its scene (`args.scenePath`),
+/// adding what it changed to `summary`
+fn edit_scene_operation(
+    ctx: &GqlContext,
+    scenes: &mut BTreeMap<String, EditedScene>,
+    index: usize,
+    op: &PlannedOperation,
+    summary: &mut ChangeSummary,
+) -> Result<(), String> {
+    let args = &op.args.0;
+    let edited = load_scene(ctx, scenes, required_arg(args, "scenePath")?)?;
+
+    // Edit a copy so a failing operation leaves the scene as it was
+    let mut scene = edited.scene.clone();
+    match op.operation_type {
+        OperationType::DuplicateNode => {
+            scene.duplicate_node(
+                required_arg(args, "nodePath")?,
+                args.get("newName").and_then(|v| v.as_str()),
+            )?;
+        }
+        OperationType::AttachScript => {
+            let node_path = required_arg(args, "nodePath")?;
+            let script_path = required_arg(args, "scriptPath")?;
+            let id = match scene.ext_resources.iter().find(|r| r.path == script_path) {
+                Some(resource) => resource.id.clone(),
+                None => {
+                    let id = next_ext_resource_id(&scene);
+                    scene.add_ext_resource(&id, "Script", script_path);
+                    id
+                }
+            };
+            apply_patch_operations(
+                &mut scene,
+                vec![json!({
+                    "op": "setProperty",
+                    "select": { "path": node_path },
+                    "property": "script",
+                    "value": format!("ExtResource(\"{}\")", id),
+                })],
+            )?;
+        }
+        _ => apply_patch_operations(&mut scene, patch_operations(op.operation_type, args)?)?,
+    }
+    summarize_changes(&edited.scene, &scene, summary);
+    edited.scene = scene;
+    edited.operations.push(index);
+    Ok(())
+}
+
+/// Count the nodes, properties and connections one operation changed
+fn summarize_changes(before: &GodotScene, after: &GodotScene, summary: &mut ChangeSummary) {
+    fn nodes(scene: &GodotScene) -> HashMap<String, &SceneNode> {
+        scene.nodes.iter().map(|n| (node_path(n), n)).collect()
+    }
+    let (before_nodes, after_nodes) = (nodes(before), nodes(after));
+    let added = after.nodes.len().saturating_sub(before.nodes.len());
+    let removed = before.nodes.len().saturating_sub(after.nodes.len());
+    summary.nodes_added += added as i32;
+    summary.nodes_removed += removed as i32;
+    // Moved or renamed nodes keep their properties; only nodes still at their path are compared
+    summary.properties_changed += after_nodes
+        .iter()
+        .filter_map(|(path, node)| Some((before_nodes.get(path)?, node)))
+        .map(|(old, new)| {
+            let keys: HashSet<&String> =
+                old.properties.keys().chain(new.properties.keys()).collect();
+            keys.into_iter()
+                .filter(|key| old.properties.get(*key) != new.properties.get(*key))
+                .count() as i32
+        })
+        .sum::<i32>();
+    summary.signals_connected += after
+        .connections
+        .len()
+        .saturating_sub(before.connections.len()) as i32;
+}
+
+/// Scene patch operations (see `godot::scene_patch`) of a planned operation
+fn patch_operations(
+    operation_type: OperationType,
+    args: &serde_json::Value,
+) -> Result<Vec<serde_json::Value>, String> {
+    let select = || -> Result<serde_json::Value, String> {
+        Ok(json!({ "path": required_arg(args, "nodePath")? }))
+    };
+    // Arguments named as in the patch, without the scene path
+    let renamed = |op: &str| {
+        let mut operation = args.clone();
+        if let Some(object) = operation.as_object_mut() {
+            object.remove("scenePath");
+            object.insert("op".to_string(), json!(op));
+        }
+        operation
+    };
+    Ok(match operation_type {
+        OperationType::AddNode => vec![renamed("addNode")],
+        OperationType::ConnectSignal => vec![renamed("connectSignal")],
+        OperationType::DisconnectSignal => vec![renamed("disconnectSignal")],
+        OperationType::RemoveNode => {
+            vec![json!({ "op": "removeNode", "select": { "path": required_arg(args, "path")? } })]
+        }
+        OperationType::SetProperty => vec![json!({
+            "op": "setProperty",
+            "select": select()?,
+            "property": required_arg(args, "property")?,
+            "value": args.get("value").cloned().unwrap_or_default(),
+        })],
+        OperationType::SetProperties => {
+            let properties = args
+                .get("properties")
+                .and_then(|v| v.as_object())
+                .ok_or("Missing required argument: properties")?;
+            properties
+                .iter()
+                .map(|(property, value)| {
+                    Ok(json!({
+                        "op": "setProperty",
+                        "select": select()?,
+                        "property": property,
+                        "value": value,
+                    }))
+                })
+                .collect::<Result<_, String>>()?
+        }
+        OperationType::AddToGroup | OperationType::RemoveFromGroup => vec![json!({
+            "op": if operation_type == OperationType::AddToGroup { "addToGroup" } else { "removeFromGroup" },
+            "select": select()?,
+            "group": required_arg(args, "group")?,
+        })],
+        OperationType::ReparentNode => vec![json!({
+            "op": "reparentNode",
+            "select": select()?,
+            "newParent": required_arg(args, "newParent")?,
+        })],
+        OperationType::DuplicateNode
+        | OperationType::CreateScript
+        | OperationType::AttachScript => unreachable!("applied without a scene patch"),
+    })
+}
+
+fn apply_patch_operations(
+    scene: &mut GodotScene,
+    operations: Vec<serde_json::Value>,
+) -> Result<(), String> {
+    let patch =
+        ScenePatch::from_json(&json!({ "operations": operations })).map_err(|e| e.message)?;
+    patch.apply(scene).map_err(|errors| {
+        errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect::<Vec<_>>()
+            .join("; ")
+    })
+}
+
+/// Smallest unused numeric ext_resource id
+fn next_ext_resource_id(scene: &GodotScene) -> String {
+    (1..)
+        .map(|n: usize| n.to_string())
+        .find(|id| scene.ext_resources.iter().all(|r| &r.id != id))
+        .expect("unbounded range")
+}
+
+fn required_arg<'a>(args: &'a serde_json::Value, key: &str) -> Result<&'a str, String> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("Missing required argument: {}", key))
+}
+
+/// Rename a node in a scene file, following it in every internal reference
+pub async fn rename_node(ctx: &GqlContext, input: &RenameNodeInput) -> OperationResult {
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_renamed_node(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_renamed_node(ctx: &GqlContext, input: &RenameNodeInput) -> OperationResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let new_path = match scene.rename_node(&input.node_path, &input.new_name) {
+        Ok(path) => path,
+        Err(message) => {
+            return OperationResult::err(
+                GqlStructuredError::new("NODE_RENAME_FAILED", GqlErrorCategory::Validation, message)
+                    .with_suggestion(
+                        "scene クエリでノードパスを確認してください（兄弟ノードと同じ名前や . : @ / \" % を含む名前は使えません）",
+                    ),
+            )
+        }
+    };
+
+    if let Err(e) = audit::write_file(ctx, "renameNode", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult {
+        message: Some(format!("Renamed to '{}'", new_path)),
+        ..OperationResult::ok()
+    }
+}
+
+/// Duplicate a node and its descendants in a scene file
+pub async fn duplicate_node_in_scene(
+    ctx: &GqlContext,
+    input: &DuplicateNodeInSceneInput,
+) -> OperationResult {
+    duplicate_in_scene(
+        ctx,
+        &input.scene_path,
+        &input.node_path,
+        input.new_name.as_deref(),
+        input.policy.unwrap_or(SceneWritePolicy::Refuse),
+    )
+    .await
+}
+
+async fn duplicate_in_scene(
+    ctx: &GqlContext,
+    scene_path: &str,
+    node_path: &str,
+    new_name: Option<&str>,
+    policy: SceneWritePolicy,
+) -> OperationResult {
+    let lock = match scene_lock::lock_scene(ctx, scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_duplicated_node(ctx, scene_path, node_path, new_name);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_duplicated_node(
+    ctx: &GqlContext,
+    scene_path: &str,
+    node_path: &str,
+    new_name: Option<&str>,
+) -> OperationResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let new_path =
+        match scene.duplicate_node(node_path, new_name) {
+            Ok(path) => path,
+            Err(message) => return OperationResult::err(
+                GqlStructuredError::new(
+                    "NODE_DUPLICATE_FAILED",
+                    GqlErrorCategory::Validation,
+                    message,
+                )
+                .with_suggestion(
+                    "scene クエリでノードパスを確認してください（ルートノードは複製できません）",
+                ),
+            ),
+        };
+
+    if let Err(e) = audit::write_file(ctx, "duplicateNodeInScene", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult {
+        message: Some(format!("Duplicated to '{}'", new_path)),
+        ..OperationResult::ok()
+    }
+}
+
+/// Copy a node and its descendants out of a scene file. The token is the
+/// subtree saved as a standalone scene (with the resources it uses), base64
+/// encoded, so it can be pasted into any scene of the project.
+pub fn copy_subtree(ctx: &GqlContext, scene_path: &str, node_path: &str) -> CopiedSubtree {
+    let failure = |error: GqlStructuredError| CopiedSubtree {
+        token: String::new(),
+        node_count: 0,
+        ext_resources: vec![],
+        sub_resource_count: 0,
+        error: Some(error),
+    };
+    let file_path = match path_utils::to_fs_path(&ctx.project_path, scene_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return failure(GqlStructuredError::new(
+                "INVALID_PATH",
+                GqlErrorCategory::Validation,
+                e.to_string(),
+            ))
+        }
+    };
+    if !ctx.fs.is_file(&file_path) {
+        return failure(GqlStructuredError::new(
+            "SCENE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Scene not found: {}", scene_path),
+        ));
+    }
+    let scene = match ctx.index.scene(&file_path) {
+        Ok(scene) => scene,
+        Err(e) => {
+            return failure(GqlStructuredError::new(
+                "SCENE_PARSE_ERROR",
+                GqlErrorCategory::Validation,
+                format!("Failed to parse scene: {}", e),
+            ))
+        }
+    };
+    let subtree = match scene.extract_subtree(node_path) {
+        Ok(subtree) => subtree,
+        Err(message) => {
+            return failure(
+                GqlStructuredError::new("NODE_NOT_FOUND", GqlErrorCategory::Validation, message)
+                    .with_suggestion("scene クエリでノードパスを確認してください"),
+            )
+        }
+    };
+
+    CopiedSubtree {
+        token: BASE64.encode(subtree.to_tscn()),
+        node_count: subtree.nodes.len() as i32,
+        ext_resources: subtree
+            .ext_resources
+            .iter()
+            .map(|r| r.path.clone())
+            .collect(),
+        sub_resource_count: subtree.sub_resources.len() as i32,
+        error: None,
+    }
+}
+
+/// Paste a subtree copied by copySubtree under a node of a scene file
+pub async fn paste_subtree(ctx: &GqlContext, input: &PasteSubtreeInput) -> OperationResult {
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.target_scene, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_pasted_subtree(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_pasted_subtree(ctx: &GqlContext, input: &PasteSubtreeInput) -> OperationResult {
+    let subtree = match decode_subtree(&input.token) {
+        Some(subtree) => subtree,
+        None => {
+            return OperationResult::err(
+                GqlStructuredError::new(
+                    "INVALID_SUBTREE_TOKEN",
+                    GqlErrorCategory::Validation,
+                    "Token is not a subtree returned by copySubtree",
+                )
+                .with_suggestion("copySubtree の token をそのまま渡してください"),
+            )
+        }
+    };
+
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.target_scene);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let new_path = match scene.insert_subtree(&subtree, &input.parent) {
+        Ok(path) => path,
+        Err(message) => {
+            return OperationResult::err(
+                GqlStructuredError::new("NODE_PASTE_FAILED", GqlErrorCategory::Validation, message)
+                    .with_suggestion("scene クエリで貼り付け先の親ノードパスを確認してください"),
+            )
+        }
+    };
+
+    if let Err(e) = audit::write_file(ctx, "pasteSubtree", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult {
+        message: Some(format!("Pasted to '{}'", new_path)),
+        ..OperationResult::ok()
+    }
+}
+
+fn decode_subtree(token: &str) -> Option<GodotScene> {
+    let bytes = BASE64.decode(token.trim()).ok()?;
+    let content = String::from_utf8(bytes).ok()?;
+    if !content.starts_with("[gd_scene") {
+        return None;
+    }
+    GodotScene::parse(&content)
+        .ok()
+        .filter(|scene| scene.nodes.first().is_some_and(|n| n.parent.is_none()))
+}
@@ -0,0 +1,417 @@
+//! Naming Collision Resolver
+//!
+//! Validation checks for names that collide without an obvious error:
+//! - signals connected to autoload methods that do not exist
+//!   (`died.connect(Events.on_died)`, `Callable(Events, "on_died")`)
+//! - autoloads named like a script's `class_name`, which hides the singleton
+//! - group names that differ only by case (`"Enemies"` vs `"enemies"`)
+//!
+//! Issues point at the file and line of each use, with a suggested fix.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::godot::gdscript::lexer::{tokenize, Token, TokenKind};
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::refactoring_resolver::resolve_autoloads;
+use super::types::*;
+use super::wiring_resolver::attached_script;
+
+/// Calls whose first argument is a group name
+const GROUP_METHODS: &[&str] = &[
+    "add_to_group",
+    "remove_from_group",
+    "is_in_group",
+    "get_nodes_in_group",
+    "get_first_node_in_group",
+    "has_group",
+    "call_group",
+    "set_group",
+    "notify_group",
+];
+
+/// Built-in methods commonly used as handlers on autoloads that extend Node
+const BUILTIN_HANDLERS: &[&str] = &[
+    "queue_free",
+    "free",
+    "show",
+    "hide",
+    "emit_signal",
+    "call_deferred",
+    "set_process",
+    "set_physics_process",
+    "add_child",
+    "remove_child",
+];
+
+/// Naming collisions found in the project, as validation errors and warnings
+pub(super) fn check_naming_collisions(
+    ctx: &GqlContext,
+    scenes: &[SceneFile],
+    scripts: &[ScriptFile],
+) -> (Vec<ProjectValidationError>, Vec<ProjectValidationWarning>) {
+    let sources: Vec<(String, String)> = scripts
+        .iter()
+        .filter(|s| s.path.ends_with(".gd"))
+        .filter_map(|s| {
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &s.path);
+            let source = ctx.index.read_to_string(&fs_path)?;
+            Some((s.path.clone(), source.to_string()))
+        })
+        .collect();
+    let classes = class_names(ctx, &sources);
+
+    let mut errors = Vec::new();
+    let autoloads = resolve_autoloads(ctx).autoloads;
+    let project_godot = ctx
+        .fs
+        .read_to_string(&ctx.project_path.join("project.godot"))
+        .unwrap_or_default();
+
+    for autoload in &autoloads {
+        if let Some(script) = classes.get(&autoload.name) {
+            errors.push(ProjectValidationError {
+                file: script.clone(),
+                line: line_of(&sources, script, |line| {
+                    line.trim_start().starts_with("class_name")
+                }),
+                message: format!(
+                    "class_name {} hides the autoload {} (project.godot line {})",
+                    autoload.name,
+                    autoload.name,
+                    autoload_line(&project_godot, &autoload.name).unwrap_or(0)
+                ),
+                severity: Some("error".to_string()),
+                suggestion: Some(format!(
+                    "Rename the class_name or the autoload; Godot will not load {} while both are named {}",
+                    script, autoload.name
+                )),
+            });
+        }
+    }
+
+    let members: HashMap<&str, HashSet<String>> = autoloads
+        .iter()
+        .filter_map(|a| Some((a.name.as_str(), autoload_members(ctx, &a.path, &classes)?)))
+        .collect();
+    for (path, source) in &sources {
+        for handler in find_autoload_handlers(source, &members) {
+            let known = &members[handler.autoload.as_str()];
+            if known.contains(&handler.method)
+                || BUILTIN_HANDLERS.contains(&handler.method.as_str())
+            {
+                continue;
+            }
+            let autoload = autoloads
+                .iter()
+                .find(|a| a.name == handler.autoload)
+                .map(|a| a.path.as_str())
+                .unwrap_or_default();
+            let near = known
+                .iter()
+                .find(|m| m.eq_ignore_ascii_case(&handler.method))
+                .map(|m| format!("Did you mean {}.{}?", handler.autoload, m));
+            errors.push(ProjectValidationError {
+                file: path.clone(),
+                line: Some(handler.line as i32),
+                message: format!(
+                    "{}.{} is connected as a signal handler, but autoload {} ({}) has no method {}",
+                    handler.autoload, handler.method, handler.autoload, autoload, handler.method
+                ),
+                severity: Some("error".to_string()),
+                suggestion: Some(near.unwrap_or_else(|| {
+                    format!(
+                        "Add func {}() to {} or fix the name",
+                        handler.method, autoload
+                    )
+                })),
+            });
+        }
+    }
+
+    let warnings = group_case_warnings(group_uses(ctx, scenes, &sources));
+    (errors, warnings)
+}
+
+/// class_name -> res:// path of the declaring script
+fn class_names(ctx: &GqlContext, sources: &[(String, String)]) -> HashMap<String, String> {
+    sources
+        .iter()
+        .filter_map(|(path, _)| {
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
+            let class_name = ctx.index.script(&fs_path)?.class_name.clone()?;
+            Some((class_name, path.clone()))
+        })
+        .collect()
+}
+
+/// Methods, variables and signals of an autoload's script and its project base
+/// scripts; None when the autoload has no GDScript to check
+fn autoload_members(
+    ctx: &GqlContext,
+    autoload_path: &str,
+    classes: &HashMap<String, String>,
+) -> Option<HashSet<String>> {
+    let mut script_path = if autoload_path.ends_with(".tscn") || autoload_path.ends_with(".scn") {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, autoload_path);
+        let scene = ctx.index.scene(&fs_path).ok()?;
+        attached_script(&scene, scene.nodes.first()?)?
+    } else {
+        autoload_path.to_string()
+    };
+
+    let mut members = HashSet::new();
+    let mut visited = HashSet::new();
+    while script_path.ends_with(".gd") && visited.insert(script_path.clone()) {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &script_path);
+        let script = ctx.index.script(&fs_path)?;
+        members.extend(script.functions.iter().map(|f| f.name.clone()));
+        members.extend(script.variables.iter().map(|v| v.name.clone()));
+        members.extend(script.exports.iter().map(|v| v.name.clone()));
+        members.extend(script.signals.iter().cloned());
+
+        let Some(extends) = script.extends.as_deref() else {
+            break;
+        };
+        let extends = extends.trim_matches('"');
+        script_path = match classes.get(extends) {
+            Some(path) => path.clone(),
+            None => extends.to_string(),
+        };
+    }
+    Some(members)
+}
+
+/// `Autoload.method` passed to a `connect(...)` call
+#[derive(Debug, PartialEq)]
+struct AutoloadHandler {
+    autoload: String,
+    method: String,
+    line: usize,
+}
+
+/// Autoload methods used as handlers in `connect(...)` arguments:
+/// `Events.on_died`, `Events.on_died.bind(1)` and `Callable(Events, "on_died")`
+fn find_autoload_handlers<T>(source: &str, autoloads: &HashMap<&str, T>) -> Vec<AutoloadHandler> {
+    let (tokens, _) = tokenize(source);
+    let ident = |i: usize| match tokens.get(i).map(|t| &t.kind) {
+        Some(TokenKind::Identifier(name)) => Some(name.as_str()),
+        _ => None,
+    };
+    let punct = |i: usize, p: &str| matches!(tokens.get(i).map(|t| &t.kind), Some(TokenKind::Punct(q)) if *q == p);
+    let is_autoload = |i: usize| ident(i).is_some_and(|name| autoloads.contains_key(name));
+
+    let mut handlers = Vec::new();
+    for i in 0..tokens.len() {
+        if ident(i) != Some("connect") || !punct(i + 1, "(") {
+            continue;
+        }
+        for j in i + 2..call_end(&tokens, i + 1) {
+            let handler = if is_autoload(j) && !punct(j - 1, ".") && punct(j + 1, ".") {
+                // A reference, not a call, optionally bound: `Events.on_died.bind(1)`
+                let method = ident(j + 2);
+                let bound = !punct(j + 3, ".") || matches!(ident(j + 4), Some("bind" | "unbind"));
+                method.filter(|_| !punct(j + 3, "(") && bound)
+            } else if ident(j) == Some("Callable")
+                && punct(j + 1, "(")
+                && is_autoload(j + 2)
+                && punct(j + 3, ",")
+            {
+                match tokens.get(j + 4).map(|t| &t.kind) {
+                    Some(TokenKind::String { value, .. }) => Some(value.as_str()),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            if let Some(method) = handler {
+                let autoload_index = if ident(j) == Some("Callable") {
+                    j + 2
+                } else {
+                    j
+                };
+                handlers.push(AutoloadHandler {
+                    autoload: ident(autoload_index).unwrap_or_default().to_string(),
+                    method: method.to_string(),
+                    line: tokens[j].line,
+                });
+            }
+        }
+    }
+    handlers
+}
+
+/// Index of the `)` closing the parenthesis at `open`
+fn call_end(tokens: &[Token], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.kind {
+            TokenKind::Punct("(") | TokenKind::Punct("[") | TokenKind::Punct("{") => depth += 1,
+            TokenKind::Punct(")") | TokenKind::Punct("]") | TokenKind::Punct("}") => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// A group name as written in a scene or script
+struct GroupUse {
+    group: String,
+    file: String,
+    line: Option<i32>,
+}
+
+/// Group names from node `groups=[...]` in scenes and group calls in scripts
+fn group_uses(
+    ctx: &GqlContext,
+    scenes: &[SceneFile],
+    sources: &[(String, String)],
+) -> Vec<GroupUse> {
+    let mut uses = Vec::new();
+    for scene_file in scenes {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &scene_file.path);
+        let (Ok(scene), Some(text)) = (
+            ctx.index.scene(&fs_path),
+            ctx.index.read_to_string(&fs_path),
+        ) else {
+            continue;
+        };
+        for node in &scene.nodes {
+            for group in &node.groups {
+                let header = format!("name=\"{}\"", node.name);
+                let quoted = format!("\"{}\"", group);
+                let line = text.lines().position(|line| {
+                    line.starts_with("[node ") && line.contains(&header) && line.contains(&quoted)
+                });
+                uses.push(GroupUse {
+                    group: group.clone(),
+                    file: scene_file.path.clone(),
+                    line: line.map(|l| l as i32 + 1),
+                });
+            }
+        }
+    }
+
+    for (path, source) in sources {
+        let (tokens, _) = tokenize(source);
+        for window in tokens.windows(3) {
+            let (
+                TokenKind::Identifier(method),
+                TokenKind::Punct("("),
+                TokenKind::String { value, .. },
+            ) = (&window[0].kind, &window[1].kind, &window[2].kind)
+            else {
+                continue;
+            };
+            if GROUP_METHODS.contains(&method.as_str()) {
+                uses.push(GroupUse {
+                    group: value.clone(),
+                    file: path.clone(),
+                    line: Some(window[2].line as i32),
+                });
+            }
+        }
+    }
+    uses
+}
+
+/// One warning per use of a spelling other than the most used one
+fn group_case_warnings(uses: Vec<GroupUse>) -> Vec<ProjectValidationWarning> {
+    let mut by_name: BTreeMap<String, Vec<GroupUse>> = BTreeMap::new();
+    for group_use in uses {
+        by_name
+            .entry(group_use.group.to_lowercase())
+            .or_default()
+            .push(group_use);
+    }
+
+    let mut warnings = Vec::new();
+    for uses in by_name.values() {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for group_use in uses {
+            *counts.entry(group_use.group.as_str()).or_default() += 1;
+        }
+        if counts.len() < 2 {
+            continue;
+        }
+        // Most used spelling; ties go to the first in sort order
+        let (canonical, count) =
+            counts.iter().fold(
+                ("", 0),
+                |best, (name, n)| if *n > best.1 { (name, *n) } else { best },
+            );
+        for group_use in uses.iter().filter(|u| u.group != canonical) {
+            warnings.push(ProjectValidationWarning {
+                file: Some(group_use.file.clone()),
+                line: group_use.line,
+                message: format!(
+                    "Group \"{}\" differs only by case from \"{}\" (used {} time(s)); group names are case-sensitive, so these are different groups",
+                    group_use.group, canonical, count
+                ),
+                suggestion: Some(format!("Use \"{}\"", canonical)),
+            });
+        }
+    }
+    warnings
+}
+
+/// Line (1-based) of `[autoload]` entry `name` in project.godot
+fn autoload_line(project_godot: &str, name: &str) -> Option<usize> {
+    let prefix = format!("{}=", name);
+    let mut in_autoload = false;
+    for (index, line) in project_godot.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_autoload = trimmed == "[autoload]";
+        } else if in_autoload && trimmed.starts_with(&prefix) {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+/// Line (1-based) of the first line of a script matching `pred`
+fn line_of(sources: &[(String, String)], path: &str, pred: impl Fn(&str) -> bool) -> Option<i32> {
+    let (_, source) = sources.iter().find(|(p, _)| p == path)?;
+    source.lines().position(pred).map(|index| index as i32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_autoload_handlers() {
+        let source = r#"extends Node
+
+func _ready():
+    died.connect(Events.on_died)
+    hurt.connect(Events.on_hurt.bind(2))
+    healed.connect(Callable(Events, "on_healed"))
+    Events.player_died.connect(_on_player_died)
+    spawned.connect(Events.make_handler())
+    other.connect(Other.method)
+"#;
+        let autoloads: HashMap<&str, ()> = [("Events", ())].into_iter().collect();
+        let handler = |method: &str, line: usize| AutoloadHandler {
+            autoload: "Events".to_string(),
+            method: method.to_string(),
+            line,
+        };
+
+        assert_eq!(
+            find_autoload_handlers(source, &autoloads),
+            vec![
+                handler("on_died", 4),
+                handler("on_hurt", 5),
+                handler("on_healed", 6),
+            ]
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Node bounds measured by Godot
+//!
+//! Instantiates a scene in a short-lived headless Godot running an embedded
+//! SceneTree script, which prints the global bounds of every node it can
+//! measure: `Rect2` for controls (after layout, with their theme), sprites,
+//! 2D shapes and polygons, `AABB` for visual instances and 3D shapes. Unlike
+//! the parser's estimates, textures, meshes and themes are Godot's own.
+//!
+//! Results are cached in [`CACHE_PATH`] under a key over the scene, the files
+//! it references directly and the Godot binary, so a scene is measured again
+//! only after one of them changed.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::godot::tscn::GodotScene;
+use crate::path_utils;
+
+use super::godot_binary;
+
+/// Where the measuring script is written for Godot to run
+pub const SCRIPT_PATH: &str = "res://.godot-mcp/tools/node_bounds.gd";
+
+/// Measured bounds per scene, with the key they were measured under
+pub const CACHE_PATH: &str = "res://.godot-mcp/node_bounds.json";
+
+const BEGIN_MARKER: &str = "NODE_BOUNDS_BEGIN";
+const END_MARKER: &str = "NODE_BOUNDS_END";
+const ERROR_MARKER: &str = "NODE_BOUNDS_ERROR:";
+
+/// Prints the global bounds of the scene given after `--` between the markers
+const SCRIPT: &str = r#"extends SceneTree
+## Written by godot-mcp-rs: prints the global bounds of every node of the
+## scene given after `--` as JSON between NODE_BOUNDS markers.
+
+
+func _initialize() -> void:
+	var args := OS.get_cmdline_user_args()
+	var scene: PackedScene = load(args[0]) if args.size() > 0 else null
+	if scene == null:
+		printerr("NODE_BOUNDS_ERROR: Cannot load scene ", args)
+		quit(1)
+		return
+	var root := scene.instantiate()
+	get_root().add_child(root)
+	# Containers and themed controls settle over the first frames
+	await process_frame
+	await process_frame
+	var nodes := []
+	_collect(root, root, nodes)
+	print("NODE_BOUNDS_BEGIN")
+	print(JSON.stringify(nodes))
+	print("NODE_BOUNDS_END")
+	quit()
+
+
+func _collect(node: Node, root: Node, nodes: Array) -> void:
+	var entry := _bounds(node)
+	if not entry.is_empty():
+		entry["path"] = str(root.get_path_to(node))
+		entry["type"] = node.get_class()
+		nodes.append(entry)
+	for child in node.get_children():
+		_collect(child, root, nodes)
+
+
+func _bounds(node) -> Dictionary:
+	if node is Control:
+		return _rect(node.get_global_rect())
+	if node is Node2D:
+		var local = _local_rect(node)
+		if local == null:
+			return {}
+		return _rect(node.get_global_transform() * local)
+	if node is VisualInstance3D:
+		return _aabb(node.get_global_transform() * node.get_aabb())
+	if node is CollisionShape3D and node.shape != null:
+		return _aabb(node.get_global_transform() * node.shape.get_debug_mesh().get_aabb())
+	return {}
+
+
+func _local_rect(node):
+	if node is Sprite2D:
+		return node.get_rect()
+	if node is AnimatedSprite2D and node.sprite_frames != null:
+		var texture: Texture2D = node.sprite_frames.get_frame_texture(node.animation, node.frame)
+		if texture == null:
+			return null
+		var size := texture.get_size()
+		var origin: Vector2 = node.offset - size / 2 if node.centered else node.offset
+		return Rect2(origin, size)
+	if node is CollisionShape2D and node.shape != null:
+		return node.shape.get_rect()
+	if node is Polygon2D or node is CollisionPolygon2D:
+		var points: PackedVector2Array = node.polygon
+		if points.is_empty():
+			return null
+		var rect := Rect2(points[0], Vector2.ZERO)
+		for point in points:
+			rect = rect.expand(point)
+		return rect
+	return null
+
+
+func _rect(rect: Rect2) -> Dictionary:
+	return {
+		"kind": "RECT2",
+		"position": [rect.position.x, rect.position.y],
+		"size": [rect.size.x, rect.size.y],
+	}
+
+
+func _aabb(aabb: AABB) -> Dictionary:
+	return {
+		"kind": "AABB",
+		"position": [aabb.position.x, aabb.position.y, aabb.position.z],
+		"size": [aabb.size.x, aabb.size.y, aabb.size.z],
+	}
+"#;
+
+/// Global bounds of one node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeBox {
+    /// Relative to the scene root (`.` for the root)
+    pub path: String,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub kind: BoxKind,
+    /// Two components for `Rect2`, three for `AABB`
+    pub position: Vec<f64>,
+    pub size: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BoxKind {
+    Rect2,
+    Aabb,
+}
+
+/// Two nodes whose bounds intersect
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap {
+    pub a: String,
+    pub b: String,
+    /// Area (`Rect2`) or volume (`AABB`) of the intersection
+    pub amount: f64,
+}
+
+/// Bounds of one scene as cached, with the key they were measured under
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    nodes: Vec<NodeBox>,
+}
+
+/// Measure every node of `res_path` with Godot
+pub fn measure(
+    binary: &Path,
+    project_root: &Path,
+    res_path: &str,
+    timeout: Duration,
+) -> Result<Vec<NodeBox>, String> {
+    let script = path_utils::to_fs_path_unchecked(project_root, SCRIPT_PATH);
+    if fs::read_to_string(&script).ok().as_deref() != Some(SCRIPT) {
+        if let Some(parent) = script.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&script, SCRIPT)
+            .map_err(|e| format!("Failed to write {}: {}", SCRIPT_PATH, e))?;
+    }
+    let (_, output) = godot_binary::run_headless(
+        binary,
+        project_root,
+        &["--script", SCRIPT_PATH, "--", res_path],
+        timeout,
+        &format!("measuring {}", res_path),
+    )?;
+    parse_output(&output)
+}
+
+/// The JSON between the markers, or the error Godot printed instead
+pub fn parse_output(output: &str) -> Result<Vec<NodeBox>, String> {
+    let lines: Vec<&str> = output.lines().map(str::trim).collect();
+    let begin = lines.iter().position(|line| *line == BEGIN_MARKER);
+    let end = lines.iter().position(|line| *line == END_MARKER);
+    if let (Some(begin), Some(end)) = (begin, end) {
+        let json = lines[begin + 1..end].join("\n");
+        return serde_json::from_str(&json).map_err(|e| format!("Invalid bounds output: {}", e));
+    }
+    let error = lines
+        .iter()
+        .find_map(|line| line.strip_prefix(ERROR_MARKER))
+        .or_else(|| {
+            lines
+                .iter()
+                .find(|line| line.starts_with("SCRIPT ERROR:") || line.starts_with("ERROR:"))
+                .copied()
+        })
+        .unwrap_or("Godot printed no bounds");
+    Err(error.trim().to_string())
+}
+
+/// Intersecting bounds of the same kind, skipping a node and its ancestors
+pub fn overlaps(boxes: &[NodeBox]) -> Vec<Overlap> {
+    let mut overlaps = Vec::new();
+    for (i, a) in boxes.iter().enumerate() {
+        for b in &boxes[i + 1..] {
+            if a.kind != b.kind || is_ancestor(&a.path, &b.path) || is_ancestor(&b.path, &a.path) {
+                continue;
+            }
+            let amount: f64 = a
+                .position
+                .iter()
+                .zip(&a.size)
+                .zip(b.position.iter().zip(&b.size))
+                .map(|((a_pos, a_size), (b_pos, b_size))| {
+                    ((a_pos + a_size).min(b_pos + b_size) - a_pos.max(*b_pos)).max(0.0)
+                })
+                .product();
+            if amount > 0.0 {
+                overlaps.push(Overlap {
+                    a: a.path.clone(),
+                    b: b.path.clone(),
+                    amount,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// Cache key of a scene: its content, the files it references directly and
+/// the Godot binary measuring it
+pub fn cache_key(project_root: &Path, scene_content: &str, binary: &Path) -> String {
+    let mut hasher = DefaultHasher::new();
+    scene_content.hash(&mut hasher);
+    binary.hash(&mut hasher);
+    if let Ok(scene) = GodotScene::parse(scene_content) {
+        for resource in &scene.ext_resources {
+            let path = path_utils::to_fs_path_unchecked(project_root, &resource.path);
+            fs::read(path).unwrap_or_default().hash(&mut hasher);
+        }
+    }
+    format!("{:x}", hasher.finish())
+}
+
+/// Cached bounds of `res_path`, if they were measured under `key`
+pub fn cached(project_root: &Path, res_path: &str, key: &str) -> Option<Vec<NodeBox>> {
+    read_cache(project_root)
+        .remove(res_path)
+        .filter(|entry| entry.key == key)
+        .map(|entry| entry.nodes)
+}
+
+/// Cache the bounds of `res_path` under `key`
+pub fn store(
+    project_root: &Path,
+    res_path: &str,
+    key: &str,
+    nodes: &[NodeBox],
+) -> Result<(), String> {
+    let mut cache = read_cache(project_root);
+    cache.insert(
+        res_path.to_string(),
+        CacheEntry {
+            key: key.to_string(),
+            nodes: nodes.to_vec(),
+        },
+    );
+    let path = path_utils::to_fs_path_unchecked(project_root, CACHE_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&cache).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", CACHE_PATH, e))
+}
+
+/// An unreadable cache is treated as empty
+fn read_cache(project_root: &Path) -> BTreeMap<String, CacheEntry> {
+    fs::read_to_string(path_utils::to_fs_path_unchecked(project_root, CACHE_PATH))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn is_ancestor(ancestor: &str, path: &str) -> bool {
+    ancestor == "." || path.starts_with(&format!("{}/", ancestor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(path: &str, x: f64, y: f64, w: f64, h: f64) -> NodeBox {
+        NodeBox {
+            path: path.to_string(),
+            node_type: "Sprite2D".to_string(),
+            kind: BoxKind::Rect2,
+            position: vec![x, y],
+            size: vec![w, h],
+        }
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let output = "Godot Engine v4.3.stable.official\nNODE_BOUNDS_BEGIN\n\
+            [{\"path\":\"Hud\",\"type\":\"Control\",\"kind\":\"RECT2\",\"position\":[0,0],\"size\":[1152,648]}]\n\
+            NODE_BOUNDS_END\n";
+        assert_eq!(
+            parse_output(output).unwrap(),
+            vec![NodeBox {
+                node_type: "Control".to_string(),
+                ..rect("Hud", 0.0, 0.0, 1152.0, 648.0)
+            }]
+        );
+        assert_eq!(
+            parse_output("NODE_BOUNDS_ERROR: Cannot load scene [\"res://x.tscn\"]\n").unwrap_err(),
+            "Cannot load scene [\"res://x.tscn\"]"
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let boxes = vec![
+            rect(".", 0.0, 0.0, 100.0, 100.0),
+            rect("Player", 0.0, 0.0, 10.0, 10.0),
+            rect("Player/Sword", 5.0, 5.0, 10.0, 10.0),
+            rect("Enemy", 8.0, 6.0, 10.0, 10.0),
+            rect("Coin", 50.0, 50.0, 4.0, 4.0),
+        ];
+        assert_eq!(
+            overlaps(&boxes),
+            vec![
+                Overlap {
+                    a: "Player".to_string(),
+                    b: "Enemy".to_string(),
+                    amount: 8.0,
+                },
+                Overlap {
+                    a: "Player/Sword".to_string(),
+                    b: "Enemy".to_string(),
+                    amount: 63.0,
+                },
+            ]
+        );
+    }
+}
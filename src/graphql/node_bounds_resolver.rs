@@ -0,0 +1,140 @@
+//! Node Bounds Resolver
+//!
+//! nodeBounds: global bounds of a scene's nodes measured by a headless Godot
+//! (see `node_bounds`), cached until the scene, its direct dependencies or
+//! the Godot executable change, with the pairs of nodes that overlap.
+
+use std::time::Duration;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::godot_binary;
+use super::node_bounds::{self, BoxKind, NodeBox};
+use super::types::*;
+
+/// Loading a scene can import its assets first
+const MEASURE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Resolve nodeBounds query
+pub fn resolve_node_bounds(ctx: &GqlContext, path: &str, refresh: bool) -> NodeBoundsResult {
+    let failure = |godot: Option<String>, error: GqlStructuredError| NodeBoundsResult {
+        success: false,
+        scene_path: path.to_string(),
+        godot,
+        cached: false,
+        nodes: Vec::new(),
+        overlaps: Vec::new(),
+        error: Some(error),
+    };
+
+    if ctx.fs.is_virtual() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "nodeBounds needs the project on disk",
+            ),
+        );
+    }
+    let file_path = match path_utils::to_fs_path(&ctx.project_path, path) {
+        Ok(file_path) => file_path,
+        Err(e) => {
+            return failure(
+                None,
+                GqlStructuredError::new(
+                    "INVALID_PATH",
+                    GqlErrorCategory::Validation,
+                    e.to_string(),
+                ),
+            )
+        }
+    };
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(content) => content,
+        Err(e) => {
+            return failure(
+                None,
+                GqlStructuredError::new(
+                    "SCENE_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to read {}: {}", path, e),
+                ),
+            )
+        }
+    };
+    let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(binary) => binary,
+        Err(message) => return failure(
+            None,
+            GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                .with_suggestion(
+                "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+            ),
+        ),
+    };
+    let godot = Some(binary.path.display().to_string());
+
+    let key = node_bounds::cache_key(&ctx.project_path, &content, &binary.path);
+    let cached = if refresh {
+        None
+    } else {
+        node_bounds::cached(&ctx.project_path, path, &key)
+    };
+    let is_cached = cached.is_some();
+    let boxes = match cached {
+        Some(boxes) => boxes,
+        None => {
+            let measured =
+                node_bounds::measure(&binary.path, &ctx.project_path, path, MEASURE_TIMEOUT)
+                    .and_then(|boxes| {
+                        node_bounds::store(&ctx.project_path, path, &key, &boxes)?;
+                        Ok(boxes)
+                    });
+            match measured {
+                Ok(boxes) => boxes,
+                Err(message) => {
+                    return failure(
+                        godot,
+                        GqlStructuredError::new(
+                            "GODOT_MEASURE_FAILED",
+                            GqlErrorCategory::Godot,
+                            message,
+                        ),
+                    )
+                }
+            }
+        }
+    };
+
+    NodeBoundsResult {
+        success: true,
+        scene_path: path.to_string(),
+        godot,
+        cached: is_cached,
+        overlaps: node_bounds::overlaps(&boxes)
+            .into_iter()
+            .map(|overlap| NodeBoundsOverlap {
+                a: overlap.a,
+                b: overlap.b,
+                amount: overlap.amount,
+            })
+            .collect(),
+        nodes: boxes.into_iter().map(to_node_bounds).collect(),
+        error: None,
+    }
+}
+
+fn to_node_bounds(node: NodeBox) -> NodeBounds {
+    NodeBounds {
+        path: node.path,
+        node_type: node.node_type,
+        kind: match node.kind {
+            BoxKind::Rect2 => BoundsKind::Rect2,
+            BoxKind::Aabb => BoundsKind::Aabb,
+        },
+        position: node.position,
+        size: node.size,
+    }
+}
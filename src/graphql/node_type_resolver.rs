@@ -36,6 +36,15 @@ pub fn resolve_node_type_info(type_name: &str) -> Option<NodeTypeInfo> {
     })
 }
 
+/// Declared type of a property in the static database
+pub(super) fn property_type(type_name: &str, property: &str) -> Option<String> {
+    get_node_type_data(type_name)?
+        .properties
+        .into_iter()
+        .find(|(name, _)| name == property)
+        .map(|(_, property_type)| property_type)
+}
+
 /// Internal node type data
 struct NodeTypeData {
     properties: Vec<(String, String)>,
@@ -0,0 +1,233 @@
+//! Performance Resolver
+//!
+//! 3D performance audit of scene files: meshes drawn at any distance (no
+//! visibility range), more shadow-casting lights than a budget, and GPU
+//! particle amounts per scene. Fixes are suggested as a mutation plan that
+//! can be passed to previewMutation / applyMutation as-is.
+
+use serde_json::json;
+
+use crate::godot::tscn::{node_path, SceneNode};
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::contracts_resolver::glob_match;
+use super::project_index::parallel_map;
+use super::project_resolver::collect_project_files;
+use super::types::*;
+
+const DEFAULT_MAX_SHADOW_LIGHTS: i32 = 4;
+const DEFAULT_MAX_GPU_PARTICLES: i32 = 10_000;
+const DEFAULT_VISIBILITY_RANGE_END: f64 = 150.0;
+
+/// `amount` of a GPUParticles node left at its default
+const DEFAULT_PARTICLE_AMOUNT: i64 = 8;
+
+const LIGHT_TYPES: &[&str] = &["DirectionalLight3D", "OmniLight3D", "SpotLight3D"];
+const PARTICLE_TYPES: &[&str] = &["GPUParticles3D", "GPUParticles2D"];
+
+/// Resolve performanceAudit query
+pub fn resolve_performance_audit(
+    ctx: &GqlContext,
+    scene_pattern: Option<&str>,
+    options: Option<&PerformanceAuditOptions>,
+) -> PerformanceAudit {
+    let max_shadow_lights = options
+        .and_then(|o| o.max_shadow_lights)
+        .unwrap_or(DEFAULT_MAX_SHADOW_LIGHTS);
+    let max_gpu_particles = options
+        .and_then(|o| o.max_gpu_particles)
+        .unwrap_or(DEFAULT_MAX_GPU_PARTICLES);
+    let visibility_range_end = options
+        .and_then(|o| o.visibility_range_end)
+        .unwrap_or(DEFAULT_VISIBILITY_RANGE_END);
+    if max_shadow_lights < 0 || max_gpu_particles < 0 || visibility_range_end <= 0.0 {
+        return PerformanceAudit {
+            success: false,
+            scenes: vec![],
+            issues: vec![],
+            suggested_plan: vec![],
+            error: Some(
+                GqlStructuredError::new(
+                    "INVALID_OPTIONS",
+                    GqlErrorCategory::Validation,
+                    "maxShadowLights and maxGpuParticles must be >= 0, visibilityRangeEnd > 0",
+                )
+                .with_suggestion("options の値を見直すか、省略して既定値を使ってください"),
+            ),
+        };
+    }
+
+    let (scene_files, _) = collect_project_files(ctx);
+    let scene_paths: Vec<String> = scene_files
+        .into_iter()
+        .map(|scene| scene.path)
+        .filter(|path| {
+            scene_pattern.is_none_or(|pattern| {
+                glob_match(
+                    path_utils::strip_res_prefix(pattern),
+                    path_utils::strip_res_prefix(path),
+                )
+            })
+        })
+        .collect();
+
+    let audits = parallel_map(&scene_paths, |scene_path| {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+        let scene = ctx.index.scene(&fs_path).ok()?;
+        let mut audit = SceneAudit::new(scene_path);
+        audit.check_meshes(&scene.nodes, visibility_range_end);
+        audit.check_shadows(&scene.nodes, max_shadow_lights);
+        audit.check_particles(&scene.nodes, max_gpu_particles);
+        (audit.stats.meshes + audit.stats.shadow_lights + audit.stats.particle_emitters > 0)
+            .then_some(audit)
+    });
+
+    let mut result = PerformanceAudit {
+        success: true,
+        scenes: vec![],
+        issues: vec![],
+        suggested_plan: vec![],
+        error: None,
+    };
+    for audit in audits.into_iter().flatten() {
+        result.scenes.push(audit.stats);
+        result.issues.extend(audit.issues);
+        result.suggested_plan.extend(audit.plan);
+    }
+    result
+}
+
+/// Findings of one scene
+struct SceneAudit {
+    stats: ScenePerformance,
+    issues: Vec<PerformanceIssue>,
+    plan: Vec<SuggestedOperation>,
+}
+
+impl SceneAudit {
+    fn new(scene_path: &str) -> Self {
+        Self {
+            stats: ScenePerformance {
+                scene_path: scene_path.to_string(),
+                meshes: 0,
+                meshes_without_visibility_range: 0,
+                shadow_lights: 0,
+                particle_emitters: 0,
+                gpu_particles: 0,
+            },
+            issues: vec![],
+            plan: vec![],
+        }
+    }
+
+    fn issue(&mut self, kind: PerformanceIssueKind, node: Option<&SceneNode>, message: String) {
+        self.issues.push(PerformanceIssue {
+            kind,
+            scene_path: self.stats.scene_path.clone(),
+            node_path: node.map(node_path),
+            message,
+        });
+    }
+
+    fn set_property(&mut self, node: &SceneNode, property: &str, value: String) {
+        self.plan.push(SuggestedOperation {
+            operation_type: OperationType::SetProperty,
+            args: async_graphql::Json(json!({
+                "scenePath": self.stats.scene_path,
+                "nodePath": node_path(node),
+                "property": property,
+                "value": value,
+            })),
+        });
+    }
+
+    /// Meshes without `visibility_range_end` are drawn at every distance
+    fn check_meshes(&mut self, nodes: &[SceneNode], visibility_range_end: f64) {
+        for node in nodes.iter().filter(|n| n.node_type == "MeshInstance3D") {
+            self.stats.meshes += 1;
+            let range = node
+                .properties
+                .get("visibility_range_end")
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .unwrap_or(0.0);
+            if range > 0.0 {
+                continue;
+            }
+            self.stats.meshes_without_visibility_range += 1;
+            self.issue(
+                PerformanceIssueKind::MissingVisibilityRange,
+                Some(node),
+                format!(
+                    "{} has no visibility range and is drawn at any distance",
+                    node.name
+                ),
+            );
+            self.set_property(
+                node,
+                "visibility_range_end",
+                format!("{:?}", visibility_range_end),
+            );
+        }
+    }
+
+    /// Shadows stay on for directional lights first, then in scene order
+    fn check_shadows(&mut self, nodes: &[SceneNode], max_shadow_lights: i32) {
+        let mut lights: Vec<&SceneNode> = nodes
+            .iter()
+            .filter(|n| LIGHT_TYPES.contains(&n.node_type.as_str()))
+            .filter(|n| n.properties.get("shadow_enabled").map(|v| v.trim()) == Some("true"))
+            .collect();
+        lights.sort_by_key(|n| n.node_type != "DirectionalLight3D");
+        self.stats.shadow_lights = lights.len() as i32;
+        for light in lights.into_iter().skip(max_shadow_lights as usize) {
+            self.issue(
+                PerformanceIssueKind::TooManyShadowLights,
+                Some(light),
+                format!(
+                    "{} casts shadows beyond the budget of {} shadow lights",
+                    light.name, max_shadow_lights
+                ),
+            );
+            self.set_property(light, "shadow_enabled", "false".to_string());
+        }
+    }
+
+    /// Over budget, every emitter is scaled down by the same ratio
+    fn check_particles(&mut self, nodes: &[SceneNode], max_gpu_particles: i32) {
+        let emitters: Vec<(&SceneNode, i64)> = nodes
+            .iter()
+            .filter(|n| PARTICLE_TYPES.contains(&n.node_type.as_str()))
+            .map(|n| {
+                let amount = n
+                    .properties
+                    .get("amount")
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(DEFAULT_PARTICLE_AMOUNT);
+                (n, amount)
+            })
+            .collect();
+        let total: i64 = emitters.iter().map(|(_, amount)| amount).sum();
+        self.stats.particle_emitters = emitters.len() as i32;
+        self.stats.gpu_particles = total as i32;
+        if total <= max_gpu_particles as i64 {
+            return;
+        }
+        self.issue(
+            PerformanceIssueKind::TooManyParticles,
+            None,
+            format!(
+                "{} GPU particles in {} emitters exceed the budget of {}",
+                total,
+                emitters.len(),
+                max_gpu_particles
+            ),
+        );
+        for (node, amount) in emitters {
+            let scaled = (amount * max_gpu_particles as i64 / total).max(1);
+            if scaled < amount {
+                self.set_property(node, "amount", scaled.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Physics Layer Resolver
+//!
+//! Godot has no project-wide collision matrix: each physics body or area
+//! carries a `collision_layer` (the layers it is on) and a `collision_mask`
+//! (the layers it scans), and two objects collide when either one's mask
+//! contains a layer of the other. `collisionMatrix` aggregates those bitmasks
+//! from every scene, with the layer names from project.godot's
+//! `[layer_names]`, so "why don't these collide" can be answered from data.
+//! `setLayerCollision` edits the masks of the objects on the two layers.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::contracts_resolver::glob_match;
+use super::project_index::parallel_map;
+use super::project_resolver::{collect_project_files, read_project_settings};
+use super::scene_lock;
+use super::types::*;
+
+/// Godot's layer count per physics engine
+const LAYER_COUNT: u32 = 32;
+
+/// Layer and mask of a new collision object: layer 1, scanning layer 1
+const DEFAULT_BITS: u32 = 1;
+
+/// Node classes with `collision_layer` / `collision_mask`
+const COLLISION_OBJECT_TYPES: &[&str] = &[
+    "Area2D",
+    "StaticBody2D",
+    "AnimatableBody2D",
+    "RigidBody2D",
+    "CharacterBody2D",
+    "PhysicalBone2D",
+    "Area3D",
+    "StaticBody3D",
+    "AnimatableBody3D",
+    "RigidBody3D",
+    "CharacterBody3D",
+    "PhysicalBone3D",
+    "VehicleBody3D",
+    "SoftBody3D",
+    "GridMap",
+];
+
+/// CSG shapes collide only with `use_collision = true`
+const CSG_TYPES: &[&str] = &[
+    "CSGBox3D",
+    "CSGCylinder3D",
+    "CSGMesh3D",
+    "CSGPolygon3D",
+    "CSGSphere3D",
+    "CSGTorus3D",
+    "CSGCombiner3D",
+];
+
+/// A collision object found in a scene, with its bitmasks
+struct Found {
+    scene_path: String,
+    node_path: String,
+    node_type: String,
+    space: PhysicsSpace,
+    layer: u32,
+    mask: u32,
+}
+
+impl Found {
+    fn to_object(&self) -> CollisionObject {
+        CollisionObject {
+            scene_path: self.scene_path.clone(),
+            node_path: self.node_path.clone(),
+            node_type: self.node_type.clone(),
+            space: self.space,
+            layers: bits(self.layer),
+            mask: bits(self.mask),
+        }
+    }
+}
+
+/// Resolve collisionMatrix query
+pub fn resolve_collision_matrix(
+    ctx: &GqlContext,
+    space: Option<PhysicsSpace>,
+    scene_pattern: Option<&str>,
+) -> CollisionMatrix {
+    let spaces: Vec<PhysicsSpace> = [PhysicsSpace::TwoD, PhysicsSpace::ThreeD]
+        .into_iter()
+        .filter(|s| space.is_none_or(|space| *s == space))
+        .collect();
+    let (found, scenes_scanned) = scan(ctx, scene_pattern);
+    let found: Vec<Found> = found
+        .into_iter()
+        .filter(|f| spaces.contains(&f.space))
+        .collect();
+
+    let mut layers = Vec::new();
+    let mut pairs = Vec::new();
+    for space in spaces {
+        let objects: Vec<&Found> = found.iter().filter(|f| f.space == space).collect();
+        let names = layer_names(ctx, space);
+        let name = |layer: u32| names.get(&layer).cloned();
+
+        for layer in 1..=LAYER_COUNT {
+            let bit = 1 << (layer - 1);
+            let objects_on = objects.iter().filter(|f| f.layer & bit != 0).count();
+            let scanning = objects.iter().filter(|f| f.mask & bit != 0).count();
+            if objects_on > 0 || scanning > 0 || names.contains_key(&layer) {
+                layers.push(PhysicsLayer {
+                    space,
+                    layer: layer as i32,
+                    name: name(layer),
+                    objects: objects_on as i32,
+                    scanned_by: scanning as i32,
+                });
+            }
+        }
+
+        // Some object on `a` scans `b`
+        let detects = |a: u32, b: u32| {
+            objects
+                .iter()
+                .any(|f| f.layer & (1 << (a - 1)) != 0 && f.mask & (1 << (b - 1)) != 0)
+        };
+        for a in 1..=LAYER_COUNT {
+            for b in a..=LAYER_COUNT {
+                let (a_detects_b, b_detects_a) = (detects(a, b), detects(b, a));
+                if a_detects_b || b_detects_a {
+                    pairs.push(LayerCollision {
+                        space,
+                        layer_a: a as i32,
+                        layer_b: b as i32,
+                        name_a: name(a),
+                        name_b: name(b),
+                        a_detects_b,
+                        b_detects_a,
+                    });
+                }
+            }
+        }
+    }
+
+    CollisionMatrix {
+        layers,
+        pairs,
+        objects: found.iter().map(Found::to_object).collect(),
+        scenes_scanned: scenes_scanned as i32,
+    }
+}
+
+/// Resolve setLayerCollision mutation
+///
+/// Enabling adds layer B to the mask of every object on layer A and layer A
+/// to the mask of every object on layer B; disabling removes them.
+pub async fn resolve_set_layer_collision(
+    ctx: &GqlContext,
+    input: &SetLayerCollisionInput,
+) -> SetLayerCollisionResult {
+    let mut result = SetLayerCollisionResult {
+        success: false,
+        changed: Vec::new(),
+        scenes_written: Vec::new(),
+        error: None,
+    };
+    let space = input.space.unwrap_or(PhysicsSpace::TwoD);
+    let names = layer_names(ctx, space);
+    let (layer_a, layer_b) = match (
+        parse_layer(&input.layer_a, &names),
+        parse_layer(&input.layer_b, &names),
+    ) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(error), _) | (_, Err(error)) => {
+            result.error = Some(*error);
+            return result;
+        }
+    };
+    let (bit_a, bit_b) = (1u32 << (layer_a - 1), 1u32 << (layer_b - 1));
+    let new_mask = |f: &Found| {
+        let mut mask = f.mask;
+        for (on, scans) in [(bit_a, bit_b), (bit_b, bit_a)] {
+            if f.layer & on != 0 {
+                if input.enabled {
+                    mask |= scans;
+                } else {
+                    mask &= !scans;
+                }
+            }
+        }
+        mask
+    };
+
+    // Scene path -> (node path, new mask)
+    let mut edits: BTreeMap<String, Vec<(String, u32)>> = BTreeMap::new();
+    let (found, _) = scan(ctx, input.scene_pattern.as_deref());
+    for mut f in found.into_iter().filter(|f| f.space == space) {
+        let mask = new_mask(&f);
+        if mask == f.mask {
+            continue;
+        }
+        edits
+            .entry(f.scene_path.clone())
+            .or_default()
+            .push((f.node_path.clone(), mask));
+        f.mask = mask;
+        result.changed.push(f.to_object());
+    }
+    if input.dry_run {
+        result.success = true;
+        return result;
+    }
+
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    for (scene_path, nodes) in edits {
+        let lock = match scene_lock::lock_scene(ctx, &scene_path, policy).await {
+            Ok(lock) => lock,
+            Err(e) => {
+                result.error = Some(e);
+                return result;
+            }
+        };
+        let written = write_masks(ctx, &scene_path, &nodes);
+        scene_lock::unlock_scene(ctx, lock, written.is_ok()).await;
+        if let Err(error) = written {
+            result.error = Some(*error);
+            return result;
+        }
+        telemetry::record_files_written(1);
+        result.scenes_written.push(scene_path);
+    }
+    result.success = true;
+    result
+}
+
+/// Set `collision_mask` of the given nodes, leaving the default unwritten as Godot does
+fn write_masks(
+    ctx: &GqlContext,
+    scene_path: &str,
+    nodes: &[(String, u32)],
+) -> Result<(), Box<GqlStructuredError>> {
+    let write_error = |message: String| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_WRITE_FAILED",
+            GqlErrorCategory::FileSystem,
+            message,
+        ))
+    };
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+    let content = ctx
+        .fs
+        .read_to_string(&file_path)
+        .map_err(|e| write_error(format!("Failed to read scene {}: {}", scene_path, e)))?;
+    let mut scene = GodotScene::parse(&content)
+        .map_err(|e| write_error(format!("Failed to parse scene {}: {}", scene_path, e)))?;
+    for (path, mask) in nodes {
+        let Some(node) = scene.nodes.iter_mut().find(|n| node_path(n) == *path) else {
+            continue;
+        };
+        if *mask == DEFAULT_BITS {
+            node.properties.shift_remove("collision_mask");
+        } else {
+            node.properties
+                .insert("collision_mask".to_string(), mask.to_string());
+        }
+    }
+    audit::write_file(ctx, "setLayerCollision", &file_path, scene.to_tscn())
+        .map_err(|e| write_error(format!("Failed to write scene {}: {}", scene_path, e)))
+}
+
+/// Collision objects of the matching scenes, and the number of scenes scanned
+fn scan(ctx: &GqlContext, scene_pattern: Option<&str>) -> (Vec<Found>, usize) {
+    let (scene_files, _) = collect_project_files(ctx);
+    let scene_paths: Vec<String> = scene_files
+        .into_iter()
+        .map(|scene| scene.path)
+        .filter(|path| {
+            scene_pattern.is_none_or(|pattern| {
+                glob_match(
+                    path_utils::strip_res_prefix(pattern),
+                    path_utils::strip_res_prefix(path),
+                )
+            })
+        })
+        .collect();
+
+    let per_scene = parallel_map(&scene_paths, |scene_path| {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+        let Ok(scene) = ctx.index.scene(&fs_path) else {
+            return Vec::new();
+        };
+        scene
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                let space = collision_space(node)?;
+                Some(Found {
+                    scene_path: scene_path.clone(),
+                    node_path: node_path(node),
+                    node_type: node.node_type.clone(),
+                    space,
+                    layer: bitmask(node, "collision_layer"),
+                    mask: bitmask(node, "collision_mask"),
+                })
+            })
+            .collect()
+    });
+    (per_scene.into_iter().flatten().collect(), scene_paths.len())
+}
+
+/// Physics engine of a node that takes part in collisions
+fn collision_space(node: &SceneNode) -> Option<PhysicsSpace> {
+    let node_type = node.node_type.as_str();
+    let colliding = COLLISION_OBJECT_TYPES.contains(&node_type)
+        || (CSG_TYPES.contains(&node_type)
+            && node.properties.get("use_collision").map(String::as_str) == Some("true"));
+    if !colliding {
+        return None;
+    }
+    Some(if node_type.ends_with("2D") {
+        PhysicsSpace::TwoD
+    } else {
+        PhysicsSpace::ThreeD
+    })
+}
+
+fn bitmask(node: &SceneNode, property: &str) -> u32 {
+    node.properties
+        .get(property)
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_BITS, |value| value as u32)
+}
+
+/// Layer numbers (1-based) of the set bits
+fn bits(value: u32) -> Vec<i32> {
+    (1..=LAYER_COUNT)
+        .filter(|layer| value & (1 << (layer - 1)) != 0)
+        .map(|layer| layer as i32)
+        .collect()
+}
+
+/// Named physics layers from project.godot (`2d_physics/layer_3="Enemies"`)
+fn layer_names(ctx: &GqlContext, space: PhysicsSpace) -> HashMap<u32, String> {
+    let prefix = match space {
+        PhysicsSpace::TwoD => "2d_physics/layer_",
+        PhysicsSpace::ThreeD => "3d_physics/layer_",
+    };
+    let Ok((_, settings)) = read_project_settings(ctx) else {
+        return HashMap::new();
+    };
+    settings
+        .entries("layer_names")
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let layer = key.strip_prefix(prefix)?.parse::<u32>().ok()?;
+            let name = value.trim_matches('"');
+            (!name.is_empty()).then(|| (layer, name.to_string()))
+        })
+        .collect()
+}
+
+/// A layer given by number ("3") or by name ("Enemies", case-insensitive)
+fn parse_layer(layer: &str, names: &HashMap<u32, String>) -> Result<u32, Box<GqlStructuredError>> {
+    let layer = layer.trim();
+    let number = layer.parse::<u32>().ok().or_else(|| {
+        names
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(layer))
+            .map(|(number, _)| *number)
+    });
+    match number {
+        Some(number) if (1..=LAYER_COUNT).contains(&number) => Ok(number),
+        _ => Err(Box::new(
+            GqlStructuredError::new(
+                "LAYER_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Unknown physics layer: '{}'", layer),
+            )
+            .with_suggestion(
+                "1〜32 のレイヤー番号か、project.godot の [layer_names] に登録された名前を指定してください",
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_and_layers() {
+        assert_eq!(bits(0b1011), vec![1, 2, 4]);
+        assert_eq!(bits(1 << 31), vec![32]);
+
+        let names = HashMap::from([(3, "Enemies".to_string())]);
+        assert_eq!(parse_layer("enemies", &names).unwrap(), 3);
+        assert_eq!(parse_layer(" 32 ", &names).unwrap(), 32);
+        assert!(parse_layer("0", &names).is_err());
+        assert!(parse_layer("33", &names).is_err());
+        assert!(parse_layer("Player", &names).is_err());
+    }
+}
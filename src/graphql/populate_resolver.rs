@@ -0,0 +1,399 @@
+//! Populate Resolver
+//!
+//! Data-driven level building: reads a CSV or JSON table (one row per item,
+//! e.g. `x,y,type` spawn lists) and adds one node per row to a scene, either an
+//! instance of a template scene or a plain node type. Properties are mapped from
+//! columns with property expressions, where bare identifiers name columns
+//! (`Vector2(x, y) * 16`).
+
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use crate::godot::expression::{self, ExpressionError, PropertySource, Value};
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::scene_lock;
+use super::types::*;
+
+/// One data row: column name to value
+type Row = HashMap<String, Value>;
+
+/// Resolve populateFromData mutation
+pub async fn populate_from_data(
+    ctx: &GqlContext,
+    input: &PopulateFromDataInput,
+) -> PopulateFromDataResult {
+    if input.dry_run {
+        return write_population(ctx, input);
+    }
+
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return failure(e),
+    };
+    let result = write_population(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.written).await;
+    result
+}
+
+fn write_population(ctx: &GqlContext, input: &PopulateFromDataInput) -> PopulateFromDataResult {
+    match build_population(ctx, input) {
+        Ok(result) => result,
+        Err(e) => failure(*e),
+    }
+}
+
+fn build_population(
+    ctx: &GqlContext,
+    input: &PopulateFromDataInput,
+) -> Result<PopulateFromDataResult, Box<GqlStructuredError>> {
+    let file_path = path_utils::to_fs_path(&ctx.project_path, &input.scene_path)
+        .map_err(|e| invalid_input(e.to_string()))?;
+    let content = ctx.fs.read_to_string(&file_path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read scene {}: {}", input.scene_path, e),
+        ))
+    })?;
+    let mut scene = GodotScene::parse(&content).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_PARSE_ERROR",
+            GqlErrorCategory::Validation,
+            format!("Failed to parse scene: {}", e),
+        ))
+    })?;
+
+    let parent = input.parent.clone().unwrap_or_else(|| ".".to_string());
+    if parent != "." && !scene.nodes.iter().any(|n| node_path(n) == parent) {
+        return Err(Box::new(
+            GqlStructuredError::new(
+                "VALIDATION_NODE_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Parent node not found: {}", parent),
+            )
+            .with_suggestion("scene クエリで有効なノードパスを確認してください"),
+        ));
+    }
+
+    // What each row becomes: a template instance or a plain node
+    let (node_type, instance, base_name) = match (&input.template_scene, &input.node_type) {
+        (Some(template), None) => {
+            let template_path = path_utils::to_fs_path(&ctx.project_path, template)
+                .map_err(|e| invalid_input(e.to_string()))?;
+            let template_root = ctx
+                .fs
+                .read_to_string(&template_path)
+                .ok()
+                .and_then(|c| GodotScene::parse(&c).ok())
+                .and_then(|s| s.nodes.into_iter().next())
+                .ok_or_else(|| invalid_input(format!("Cannot load template scene {}", template)))?;
+            let id = packed_scene_id(&mut scene, template);
+            ("Node".to_string(), Some(id), template_root.name)
+        }
+        (None, Some(node_type)) => (node_type.clone(), None, node_type.clone()),
+        _ => {
+            return Err(invalid_input(
+                "Specify exactly one of templateScene or nodeType".to_string(),
+            ))
+        }
+    };
+
+    let rows = load_rows(ctx, &input.data_file)?;
+
+    let mut taken: HashSet<String> = scene
+        .nodes
+        .iter()
+        .filter(|n| n.parent.as_deref() == Some(parent.as_str()))
+        .map(|n| n.name.clone())
+        .collect();
+    let mut created = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let row_error = |message: String| {
+            Box::new(
+                GqlStructuredError::new(
+                    "DATA_ROW_ERROR",
+                    GqlErrorCategory::Validation,
+                    format!("Row {}: {}", index + 1, message),
+                )
+                .with_suggestion(
+                    "mapping の式では列名を識別子として参照できます（例: Vector2(x, y) * 16）",
+                ),
+            )
+        };
+
+        let name = match &input.name_column {
+            Some(column) => match row.get(column) {
+                Some(Value::Str(s)) => sanitize_name(s),
+                Some(other) => sanitize_name(other.to_godot().trim_matches('"')),
+                None => return Err(row_error(format!("Missing column '{}'", column))),
+            },
+            None => format!("{}{}", base_name, index + 1),
+        };
+        let name = unique_name(&name, &mut taken);
+
+        let mut properties = IndexMap::new();
+        for mapping in &input.mapping {
+            let value = expression::evaluate(&mapping.expression, &RowColumns(row))
+                .map_err(|e| row_error(format!("{}: {}", mapping.property, e)))?;
+            properties.insert(mapping.property.clone(), value.to_godot());
+        }
+
+        created.push(if parent == "." {
+            name.clone()
+        } else {
+            format!("{}/{}", parent, name)
+        });
+        scene.add_node(SceneNode {
+            name,
+            node_type: node_type.clone(),
+            parent: Some(parent.clone()),
+            instance: instance.clone(),
+            groups: Vec::new(),
+            extra_attrs: Vec::new(),
+            attr_order: Vec::new(),
+            properties,
+        });
+    }
+
+    let written = !input.dry_run && !created.is_empty();
+    if written {
+        audit::write_file(ctx, "populateFromData", &file_path, scene.to_tscn()).map_err(|e| {
+            Box::new(GqlStructuredError::new(
+                "FILE_WRITE_ERROR",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to write scene: {}", e),
+            ))
+        })?;
+        telemetry::record_files_written(1);
+    }
+
+    Ok(PopulateFromDataResult {
+        success: true,
+        rows: rows.len() as i32,
+        created,
+        written,
+        error: None,
+    })
+}
+
+/// Columns of a data row as the expression's own properties
+struct RowColumns<'a>(&'a Row);
+
+impl PropertySource for RowColumns<'_> {
+    fn property(&self, node_path: &str, property: &str) -> Result<Value, ExpressionError> {
+        if node_path != "." {
+            return Err(ExpressionError::Eval(
+                "Node references are not available in data mappings".to_string(),
+            ));
+        }
+        self.0
+            .get(property)
+            .cloned()
+            .ok_or_else(|| ExpressionError::Eval(format!("Unknown column: {}", property)))
+    }
+}
+
+/// Read rows from a .csv (header line + rows) or .json (array of objects) file
+fn load_rows(ctx: &GqlContext, data_file: &str) -> Result<Vec<Row>, Box<GqlStructuredError>> {
+    let path = path_utils::to_fs_path(&ctx.project_path, data_file)
+        .map_err(|e| invalid_input(e.to_string()))?;
+    let content = ctx.fs.read_to_string(&path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FILE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read {}: {}", data_file, e),
+        ))
+    })?;
+
+    let rows = if data_file.to_lowercase().ends_with(".json") {
+        parse_json_rows(&content)
+    } else if data_file.to_lowercase().ends_with(".csv") {
+        Ok(parse_csv_rows(&content))
+    } else {
+        Err("Data file must be .csv or .json".to_string())
+    };
+    rows.map_err(|message| {
+        Box::new(
+            GqlStructuredError::new("DATA_PARSE_ERROR", GqlErrorCategory::Validation, message)
+                .with_suggestion(
+                    "CSV はヘッダー行付き、JSON はオブジェクトの配列で指定してください",
+                ),
+        )
+    })
+}
+
+fn parse_json_rows(content: &str) -> Result<Vec<Row>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = value
+        .as_array()
+        .ok_or_else(|| "JSON data must be an array of objects".to_string())?;
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let object = item
+                .as_object()
+                .ok_or_else(|| format!("Row {} is not an object", i + 1))?;
+            Ok(object
+                .iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::Number(n) => Value::Number(n.as_f64()?),
+                        serde_json::Value::Bool(b) => Value::Bool(*b),
+                        serde_json::Value::String(s) => Value::Str(s.clone()),
+                        _ => return None,
+                    };
+                    Some((key.clone(), value))
+                })
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_csv_rows(content: &str) -> Vec<Row> {
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .collect();
+
+    lines
+        .map(|line| {
+            columns
+                .iter()
+                .zip(split_csv_line(line))
+                .map(|(column, field)| (column.clone(), csv_value(field.trim())))
+                .collect()
+        })
+        .collect()
+}
+
+/// Split one CSV line, honouring double-quoted fields with `""` escapes
+pub(super) fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn csv_value(field: &str) -> Value {
+    match field {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => field
+            .parse::<f64>()
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::Str(field.to_string())),
+    }
+}
+
+/// Reuse or add the PackedScene ext_resource for `path`, returning its id
+fn packed_scene_id(scene: &mut GodotScene, path: &str) -> String {
+    if let Some(existing) = scene.ext_resources.iter().find(|r| r.path == path) {
+        return existing.id.clone();
+    }
+    let stem = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .trim_end_matches(".tscn")
+        .to_string();
+    let id = format!("{}_{}", scene.ext_resources.len() + 1, stem);
+    scene.add_ext_resource(&id, "PackedScene", path);
+    id
+}
+
+/// Node names cannot contain `. : @ / " %`
+fn sanitize_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '.' | ':' | '@' | '/' | '"' | '%'))
+        .collect();
+    if name.trim().is_empty() {
+        "Node".to_string()
+    } else {
+        name.trim().to_string()
+    }
+}
+
+/// Append a number until the name is free among its siblings
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{}{}", name, n);
+        n += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+fn invalid_input(message: String) -> Box<GqlStructuredError> {
+    Box::new(GqlStructuredError::new(
+        "INVALID_INPUT",
+        GqlErrorCategory::Validation,
+        message,
+    ))
+}
+
+fn failure(error: GqlStructuredError) -> PopulateFromDataResult {
+    PopulateFromDataResult {
+        success: false,
+        rows: 0,
+        created: vec![],
+        written: false,
+        error: Some(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_rows() {
+        let rows = parse_csv_rows(
+            "x, y, type,label\n10,20,coin,\"Big, shiny\"\n\n-5,0.5,gem,\"say \"\"hi\"\"\"\n",
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["x"], Value::Number(10.0));
+        assert_eq!(rows[0]["type"], Value::Str("coin".to_string()));
+        assert_eq!(rows[0]["label"], Value::Str("Big, shiny".to_string()));
+        assert_eq!(rows[1]["y"], Value::Number(0.5));
+        assert_eq!(rows[1]["label"], Value::Str("say \"hi\"".to_string()));
+    }
+
+    #[test]
+    fn test_unique_name() {
+        let mut taken = HashSet::from(["Coin".to_string()]);
+        assert_eq!(unique_name("Coin", &mut taken), "Coin2");
+        assert_eq!(unique_name("Coin", &mut taken), "Coin3");
+        assert_eq!(unique_name("Gem", &mut taken), "Gem");
+        assert_eq!(sanitize_name("a/b.c"), "abc");
+    }
+}
@@ -0,0 +1,349 @@
+//! Project Index
+//!
+//! In-memory cache of directory listings, parsed scenes and scripts, and file
+//! contents, shared by every clone of a `GqlContext`. Entries are checked
+//! against the filesystem on each use — listings by the directory's mtime,
+//! files by mtime and size — so edits made outside the server are picked up,
+//! while repeated queries on large projects neither re-walk unchanged folders
+//! nor re-parse unchanged files.
+//!
+//! The index reads through a [`ProjectFs`], so an in-memory project is
+//! indexed the same way as one on disk.
+//!
+//! Folder listing and bulk parsing can run on several threads (see
+//! [`set_jobs`]); the default of one job keeps indexing serial.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::SystemTime;
+
+use crate::godot::csharp::CSharpScript;
+use crate::godot::gdscript::GDScript;
+use crate::godot::tscn::GodotScene;
+use crate::project_registry::ProjectRegistry;
+
+use super::transaction::TransactionFs;
+use super::vfs::{OsFs, ProjectFs};
+
+/// Folders that are never part of the project listing: Godot's import cache,
+/// the server's own state (checkpoint snapshots) and third-party addons
+pub const EXCLUDED_DIRS: &[&str] = &[".godot", ".godot-mcp", "addons"];
+
+/// Worker threads used for listing and parsing
+static JOBS: AtomicUsize = AtomicUsize::new(1);
+
+/// Set the number of indexing threads; 0 uses one per CPU
+pub fn set_jobs(jobs: usize) {
+    let jobs = if jobs == 0 {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        jobs
+    };
+    JOBS.store(jobs, Ordering::Relaxed);
+}
+
+/// Number of indexing threads
+pub fn jobs() -> usize {
+    JOBS.load(Ordering::Relaxed)
+}
+
+/// Apply `f` to every item on up to [`jobs`] threads, keeping the input order
+pub fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let workers = jobs().min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    // Workers take the next item as they finish, so a few large files do
+    // not hold up a whole share of the list
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            break;
+                        };
+                        done.push((i, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("index worker panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Shared project index; cloning shares the cache
+#[derive(Clone)]
+pub struct ProjectIndex {
+    state: Arc<Mutex<IndexState>>,
+    fs: Arc<dyn ProjectFs>,
+}
+
+impl Default for ProjectIndex {
+    fn default() -> Self {
+        Self::with_fs(Arc::new(OsFs))
+    }
+}
+
+#[derive(Default)]
+struct IndexState {
+    dirs: HashMap<PathBuf, Arc<DirListing>>,
+    texts: HashMap<PathBuf, (FileStamp, Arc<str>)>,
+    scenes: HashMap<PathBuf, (FileStamp, Result<Arc<GodotScene>, String>)>,
+    scripts: HashMap<PathBuf, (FileStamp, Arc<GDScript>)>,
+    csharp_scripts: HashMap<PathBuf, (FileStamp, Arc<CSharpScript>)>,
+}
+
+struct DirListing {
+    modified: SystemTime,
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(fs: &dyn ProjectFs, path: &Path) -> Option<Self> {
+        let metadata = fs.metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified,
+            len: metadata.len,
+        })
+    }
+}
+
+impl ProjectIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of a project on another filesystem
+    pub fn with_fs(fs: Arc<dyn ProjectFs>) -> Self {
+        Self {
+            state: Default::default(),
+            fs,
+        }
+    }
+
+    /// Index shared by every context of `project_root`, so that requests
+    /// which each build their own context still reuse earlier work
+    pub fn shared(project_root: &Path) -> Self {
+        static INDEXES: ProjectRegistry<ProjectIndex> = ProjectRegistry::new();
+
+        INDEXES
+            .lock()
+            .entry(project_root.to_path_buf())
+            .or_insert_with(|| Self::with_fs(TransactionFs::shared(project_root)))
+            .clone()
+    }
+
+    /// Filesystem the index reads from
+    pub fn fs(&self) -> &dyn ProjectFs {
+        self.fs.as_ref()
+    }
+
+    /// Every file below `root` outside the excluded folders, sorted by path
+    pub fn files(&self, root: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        // One folder level at a time, its folders listed in parallel
+        let mut level = vec![root.to_path_buf()];
+        while !level.is_empty() {
+            let listings = parallel_map(&level, |dir| self.listing(dir));
+            level = Vec::new();
+            for listing in listings.into_iter().flatten() {
+                files.extend(listing.files.iter().cloned());
+                level.extend(listing.dirs.iter().cloned());
+            }
+        }
+        files.sort();
+        files
+    }
+
+    /// File content as text; None when missing or not UTF-8
+    pub fn read_to_string(&self, path: &Path) -> Option<Arc<str>> {
+        let stamp = FileStamp::of(self.fs.as_ref(), path)?;
+        let mut state = self.lock();
+        if let Some((cached, text)) = state.texts.get(path) {
+            if *cached == stamp {
+                return Some(text.clone());
+            }
+        }
+        let text: Arc<str> = self.fs.read_to_string(path).ok()?.into();
+        state
+            .texts
+            .insert(path.to_path_buf(), (stamp, text.clone()));
+        Some(text)
+    }
+
+    /// Parsed scene, or the read/parse error
+    pub fn scene(&self, path: &Path) -> Result<Arc<GodotScene>, String> {
+        let stamp = FileStamp::of(self.fs.as_ref(), path)
+            .ok_or_else(|| format!("Cannot read {}", path.display()))?;
+        if let Some((cached, scene)) = self.lock().scenes.get(path) {
+            if *cached == stamp {
+                return scene.clone();
+            }
+        }
+        let scene = self
+            .read_to_string(path)
+            .ok_or_else(|| format!("Cannot read {}", path.display()))
+            .and_then(|content| GodotScene::parse(&content).map_err(|e| e.to_string()))
+            .map(Arc::new);
+        self.lock()
+            .scenes
+            .insert(path.to_path_buf(), (stamp, scene.clone()));
+        scene
+    }
+
+    /// Parsed script; None when missing
+    pub fn script(&self, path: &Path) -> Option<Arc<GDScript>> {
+        let stamp = FileStamp::of(self.fs.as_ref(), path)?;
+        if let Some((cached, script)) = self.lock().scripts.get(path) {
+            if *cached == stamp {
+                return Some(script.clone());
+            }
+        }
+        let script = Arc::new(GDScript::parse(&self.read_to_string(path)?));
+        self.lock()
+            .scripts
+            .insert(path.to_path_buf(), (stamp, script.clone()));
+        Some(script)
+    }
+
+    /// Cached summary of a C# script, re-scanned when the file changed
+    pub fn csharp_script(&self, path: &Path) -> Option<Arc<CSharpScript>> {
+        let stamp = FileStamp::of(self.fs.as_ref(), path)?;
+        if let Some((cached, script)) = self.lock().csharp_scripts.get(path) {
+            if *cached == stamp {
+                return Some(script.clone());
+            }
+        }
+        let script = Arc::new(CSharpScript::parse(&self.read_to_string(path)?));
+        self.lock()
+            .csharp_scripts
+            .insert(path.to_path_buf(), (stamp, script.clone()));
+        Some(script)
+    }
+
+    /// Cached listing of `dir`, re-read when the directory changed
+    fn listing(&self, dir: &Path) -> Option<Arc<DirListing>> {
+        let Some(modified) = self.fs.metadata(dir).ok().map(|m| m.modified) else {
+            self.lock().dirs.remove(dir);
+            return None;
+        };
+        if let Some(listing) = self.lock().dirs.get(dir) {
+            if listing.modified == modified {
+                return Some(listing.clone());
+            }
+        }
+
+        let mut listing = DirListing {
+            modified,
+            files: Vec::new(),
+            dirs: Vec::new(),
+        };
+        for path in self.fs.read_dir(dir).ok()? {
+            let name = path.file_name().unwrap_or_default();
+            if EXCLUDED_DIRS.iter().any(|excluded| name == *excluded) {
+                continue;
+            }
+            if self.fs.is_dir(&path) {
+                listing.dirs.push(path);
+            } else {
+                listing.files.push(path);
+            }
+        }
+        let listing = Arc::new(listing);
+        self.lock().dirs.insert(dir.to_path_buf(), listing.clone());
+        Some(listing)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, IndexState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for ProjectIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ProjectIndex")
+            .field("dirs", &state.dirs.len())
+            .field("scenes", &state.scenes.len())
+            .field("scripts", &state.scripts.len())
+            .field("csharp_scripts", &state.csharp_scripts.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_index_follows_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("scenes")).unwrap();
+        fs::create_dir_all(root.join(".godot/imported")).unwrap();
+        fs::write(root.join(".godot/imported/cache.tscn"), "").unwrap();
+        fs::write(
+            root.join("scenes/main.tscn"),
+            "[node name=\"Main\" type=\"Node\"]\n",
+        )
+        .unwrap();
+
+        let index = ProjectIndex::new();
+        assert_eq!(index.files(root), vec![root.join("scenes/main.tscn")]);
+        let scene = index.scene(&root.join("scenes/main.tscn")).unwrap();
+        assert_eq!(scene.nodes[0].name, "Main");
+        // Unchanged: served from the cache
+        assert!(Arc::ptr_eq(
+            &scene,
+            &index.scene(&root.join("scenes/main.tscn")).unwrap()
+        ));
+
+        fs::write(
+            root.join("scenes/main.tscn"),
+            "[node name=\"Level\" type=\"Node2D\"]\n",
+        )
+        .unwrap();
+        fs::write(root.join("scenes/enemy.tscn"), "").unwrap();
+        assert_eq!(
+            index.scene(&root.join("scenes/main.tscn")).unwrap().nodes[0].name,
+            "Level"
+        );
+        assert_eq!(index.files(root).len(), 2);
+
+        fs::remove_file(root.join("scenes/main.tscn")).unwrap();
+        assert!(index.scene(&root.join("scenes/main.tscn")).is_err());
+        assert_eq!(index.files(root), vec![root.join("scenes/enemy.tscn")]);
+    }
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<usize> = (0..100).collect();
+        set_jobs(4);
+        let doubled = parallel_map(&items, |i| i * 2);
+        set_jobs(1);
+        assert_eq!(doubled, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+    }
+}
@@ -1,12 +1,20 @@
 //! Project Resolver
 //!
-//! Handles project information, file collection, and validation.
+//! Handles project information, file collection, validation, and asset previews.
 
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::godot::media::{self, MediaInfo, MediaKind};
+use crate::godot::project_settings::ProjectSettings;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
 use super::context::GqlContext;
+use super::naming_resolver::check_naming_collisions;
+use super::scene_resolver::quote_godot_string;
 use super::types::*;
+use super::vfs::ProjectFs;
 
 /// Resolve project information
 pub fn resolve_project(ctx: &GqlContext) -> Project {
@@ -14,8 +22,9 @@ pub fn resolve_project(ctx: &GqlContext) -> Project {
 
     // Read project.godot to get project name
     let project_godot_path = project_path.join("project.godot");
-    let name = if project_godot_path.exists() {
-        parse_project_name(&project_godot_path).unwrap_or_else(|| "Unknown".to_string())
+    let name = if ctx.fs.exists(&project_godot_path) {
+        parse_project_name(ctx.fs.as_ref(), &project_godot_path)
+            .unwrap_or_else(|| "Unknown".to_string())
     } else {
         project_path
             .file_name()
@@ -24,10 +33,10 @@ pub fn resolve_project(ctx: &GqlContext) -> Project {
     };
 
     // Collect scenes and scripts
-    let (scenes, scripts) = collect_project_files(project_path);
+    let (scenes, scripts) = collect_project_files(ctx);
 
     // Count resources (*.tres, *.res files)
-    let resource_count = count_resources(project_path);
+    let resource_count = count_resources(ctx);
 
     let stats = ProjectStats {
         scene_count: scenes.len() as i32,
@@ -36,7 +45,7 @@ pub fn resolve_project(ctx: &GqlContext) -> Project {
     };
 
     // Basic validation
-    let validation = validate_project(project_path, &scenes, &scripts);
+    let validation = validate_project(ctx, &scenes, &scripts);
 
     Project {
         name,
@@ -49,8 +58,8 @@ pub fn resolve_project(ctx: &GqlContext) -> Project {
 }
 
 /// Parse project name from project.godot
-pub fn parse_project_name(path: &Path) -> Option<String> {
-    let content = fs::read_to_string(path).ok()?;
+pub fn parse_project_name(fs: &dyn ProjectFs, path: &Path) -> Option<String> {
+    let content = fs.read_to_string(path).ok()?;
     for line in content.lines() {
         if line.starts_with("config/name=") {
             let value = line.strip_prefix("config/name=")?;
@@ -62,12 +71,43 @@ pub fn parse_project_name(path: &Path) -> Option<String> {
     None
 }
 
+/// Parse main scene (run/main_scene) from project.godot
+pub fn parse_main_scene(fs: &dyn ProjectFs, path: &Path) -> Option<String> {
+    let content = fs.read_to_string(path).ok()?;
+    for line in content.lines() {
+        if let Some(value) = line.strip_prefix("run/main_scene=") {
+            let trimmed = value.trim_matches('"');
+            if trimmed.is_empty() {
+                return None;
+            }
+            return Some(trimmed.to_string());
+        }
+    }
+    None
+}
+
+/// Script file extensions: GDScript and C# (Godot .NET)
+pub const SCRIPT_EXTENSIONS: &[&str] = &["gd", "cs"];
+
+/// Whether a path names a GDScript or C# script
+pub fn is_script_path(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, ext)| SCRIPT_EXTENSIONS.contains(&ext))
+}
+
 /// Collect scene and script files from project
-pub fn collect_project_files(project_path: &Path) -> (Vec<SceneFile>, Vec<ScriptFile>) {
+pub fn collect_project_files(ctx: &GqlContext) -> (Vec<SceneFile>, Vec<ScriptFile>) {
     let mut scenes = Vec::new();
     let mut scripts = Vec::new();
 
-    collect_files_recursive(project_path, project_path, &mut scenes, &mut scripts);
+    for path in ctx.index.files(&ctx.project_path) {
+        let res_path = to_res_path(&ctx.project_path, &path);
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("tscn") | Some("scn") => scenes.push(SceneFile { path: res_path }),
+            Some("gd") | Some("cs") => scripts.push(ScriptFile { path: res_path }),
+            _ => {}
+        }
+    }
 
     // Sort by path for consistent output
     scenes.sort_by(|a, b| a.path.cmp(&b.path));
@@ -76,80 +116,53 @@ pub fn collect_project_files(project_path: &Path) -> (Vec<SceneFile>, Vec<Script
     (scenes, scripts)
 }
 
-/// Recursively collect scene and script files
-fn collect_files_recursive(
-    root: &Path,
-    dir: &Path,
-    scenes: &mut Vec<SceneFile>,
-    scripts: &mut Vec<ScriptFile>,
-) {
-    let Ok(entries) = fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        // Skip .godot directory
-        if path
-            .file_name()
-            .map(|n| n == ".godot" || n == "addons")
-            .unwrap_or(false)
-        {
-            continue;
-        }
-
-        if path.is_dir() {
-            collect_files_recursive(root, &path, scenes, scripts);
-        } else if let Some(ext) = path.extension() {
-            let res_path = to_res_path(root, &path);
-            match ext.to_str() {
-                Some("tscn") | Some("scn") => {
-                    scenes.push(SceneFile { path: res_path });
-                }
-                Some("gd") => {
-                    scripts.push(ScriptFile { path: res_path });
-                }
-                _ => {}
-            }
-        }
+/// Header-derived metadata of a media file, streamed from disk when possible
+pub fn probe_media(ctx: &GqlContext, fs_path: &Path) -> Option<MediaInfo> {
+    if ctx.fs.is_virtual() {
+        media::probe_data(fs_path, &ctx.fs.read(fs_path).ok()?)
+    } else {
+        media::probe(fs_path)
     }
 }
 
-/// Count resource files
-pub fn count_resources(project_path: &Path) -> i32 {
-    let mut count = 0;
-    count_resources_recursive(project_path, &mut count);
-    count
+/// Resolve header-derived metadata of an image, audio or mesh file
+pub fn resolve_resource_preview(ctx: &GqlContext, res_path: &str) -> Option<ResourcePreview> {
+    let fs_path = path_utils::to_fs_path(&ctx.project_path, res_path).ok()?;
+    let info = probe_media(ctx, &fs_path)?;
+    let to_i32 = |v: u32| i32::try_from(v).ok();
+    let to_i64 = |v: u64| i64::try_from(v).ok();
+
+    Some(ResourcePreview {
+        path: res_path.to_string(),
+        kind: match info.kind {
+            MediaKind::Image => ResourcePreviewKind::Image,
+            MediaKind::Audio => ResourcePreviewKind::Audio,
+            MediaKind::Mesh => ResourcePreviewKind::Mesh,
+        },
+        format: info.format,
+        file_size: to_i64(info.file_size).unwrap_or(i64::MAX),
+        width: info.width.and_then(to_i32),
+        height: info.height.and_then(to_i32),
+        channels: info.channels.and_then(to_i32),
+        sample_rate: info.sample_rate.and_then(to_i32),
+        duration_sec: info.duration_secs,
+        vertex_count: info.vertex_count.and_then(to_i64),
+        face_count: info.face_count.and_then(to_i64),
+    })
 }
 
-fn count_resources_recursive(dir: &Path, count: &mut i32) {
-    let Ok(entries) = fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-
-        if path
-            .file_name()
-            .map(|n| n == ".godot" || n == "addons")
-            .unwrap_or(false)
-        {
-            continue;
-        }
-
-        if path.is_dir() {
-            count_resources_recursive(&path, count);
-        } else if let Some(ext) = path.extension() {
-            match ext.to_str() {
-                Some("tres") | Some("res") => {
-                    *count += 1;
-                }
-                _ => {}
-            }
-        }
-    }
+/// Count resource files
+pub fn count_resources(ctx: &GqlContext) -> i32 {
+    ctx.index
+        .files(&ctx.project_path)
+        .iter()
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("tres") | Some("res")
+            )
+        })
+        .count() as i32
 }
 
 /// Convert filesystem path to res:// path
@@ -162,174 +175,61 @@ pub fn to_res_path(project_root: &Path, file_path: &Path) -> String {
     format!("res://{}", relative)
 }
 
-/// Basic project validation
+/// Project validation: naming collisions between autoloads, classes, handlers and groups
 pub fn validate_project(
-    _project_path: &Path,
-    _scenes: &[SceneFile],
-    _scripts: &[ScriptFile],
+    ctx: &GqlContext,
+    scenes: &[SceneFile],
+    scripts: &[ScriptFile],
 ) -> ProjectValidationResult {
-    // For now, just return valid
-    // TODO: Add actual validation (missing references, etc.)
+    let (errors, warnings) = check_naming_collisions(ctx, scenes, scripts);
     ProjectValidationResult {
-        is_valid: true,
-        errors: vec![],
-        warnings: vec![],
+        is_valid: errors.is_empty(),
+        errors,
+        warnings,
     }
 }
 
 // ======================
-// Phase 2.2: Input Action & Project Settings
+// Phase 2.2: Project Settings
 // ======================
 
-/// Add an input action to the project's InputMap
-/// This modifies the project.godot file's [input] section
-pub fn resolve_add_input_action(ctx: &GqlContext, input: &AddInputActionInput) -> OperationResult {
-    // Validate action name
-    if input.action_name.is_empty() {
-        return OperationResult::err_msg("Action name cannot be empty");
-    }
-
-    let project_godot = ctx.project_path.join("project.godot");
-    if !project_godot.exists() {
-        return OperationResult::err_msg("project.godot not found");
-    }
-
-    // Read current project.godot
-    let content = match fs::read_to_string(&project_godot) {
-        Ok(c) => c,
-        Err(e) => return OperationResult::err_msg(format!("Failed to read project.godot: {}", e)),
-    };
-
-    // Build input action entry
-    let action_key = format!("input/{}", input.action_name);
-
-    // Check if action already exists
-    if content
-        .lines()
-        .any(|line| line.starts_with(&format!("{}=", action_key)))
-    {
-        return OperationResult::err_msg(format!(
-            "Input action '{}' already exists",
-            input.action_name
-        ));
-    }
-
-    // Build events string for Godot 4.x format
-    let events: Vec<String> = input.events.iter().map(|e| format_input_event(e)).collect();
-
-    let action_value = format!(
-        r#"{{
-"deadzone": 0.5,
-"events": [{}]
-}}"#,
-        events.join(", ")
-    );
-
-    // Find [input] section or create it
-    let mut new_content = String::new();
-    let mut found_input_section = false;
-    let mut in_input_section = false;
-    let mut inserted = false;
-
-    for line in content.lines() {
-        if line.trim() == "[input]" {
-            found_input_section = true;
-            in_input_section = true;
-            new_content.push_str(line);
-            new_content.push('\n');
-            continue;
-        }
-
-        if in_input_section && line.starts_with('[') && line != "[input]" {
-            // End of input section, insert new action before next section
-            if !inserted {
-                new_content.push_str(&format!("{}={}\n", action_key, action_value));
-                inserted = true;
+/// Resolve projectSettings query
+pub fn resolve_project_settings(ctx: &GqlContext, prefix: Option<&str>) -> ProjectSettingsResult {
+    let settings = match read_project_settings(ctx) {
+        Ok((_, settings)) => settings,
+        Err(error) => {
+            return ProjectSettingsResult {
+                sections: Vec::new(),
+                error: Some(*error),
             }
-            in_input_section = false;
-        }
-
-        new_content.push_str(line);
-        new_content.push('\n');
-    }
-
-    // If no [input] section found, add it at the end
-    if !found_input_section {
-        new_content.push_str("\n[input]\n");
-        new_content.push_str(&format!("{}={}\n", action_key, action_value));
-    } else if !inserted {
-        // [input] was the last section
-        new_content.push_str(&format!("{}={}\n", action_key, action_value));
-    }
-
-    // Write back
-    if let Err(e) = fs::write(&project_godot, new_content) {
-        return OperationResult::err_msg(format!("Failed to write project.godot: {}", e));
-    }
-
-    OperationResult::ok()
-}
-
-/// Format a single input event for project.godot
-fn format_input_event(event: &InputEventInput) -> String {
-    match event.event_type {
-        InputEventType::Key => {
-            let key = event.key.as_deref().unwrap_or("Unknown");
-            format!(
-                r#"Object(InputEventKey,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":{},"key_label":0,"unicode":0,"echo":false)"#,
-                key_name_to_godot_keycode(key)
-            )
-        }
-        InputEventType::MouseButton => {
-            let button = event.button.unwrap_or(1);
-            format!(
-                r#"Object(InputEventMouseButton,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"button_mask":0,"position":Vector2(0, 0),"global_position":Vector2(0, 0),"factor":1.0,"button_index":{},"canceled":false,"pressed":false,"double_click":false)"#,
-                button
-            )
-        }
-        InputEventType::JoyButton => {
-            let button = event.button.unwrap_or(0);
-            let device = event.device.unwrap_or(0);
-            format!(
-                r#"Object(InputEventJoypadButton,"resource_local_to_scene":false,"resource_name":"","device":{},"button_index":{},"pressure":0.0,"pressed":false)"#,
-                device, button
-            )
         }
-        InputEventType::JoyAxis => {
-            let button = event.button.unwrap_or(0);
-            let device = event.device.unwrap_or(0);
-            format!(
-                r#"Object(InputEventJoypadMotion,"resource_local_to_scene":false,"resource_name":"","device":{},"axis":{},"axis_value":0.0)"#,
-                device, button
-            )
-        }
-    }
-}
+    };
 
-/// Convert key name to Godot keycode
-fn key_name_to_godot_keycode(key: &str) -> i32 {
-    match key.to_uppercase().as_str() {
-        "SPACE" => 32,
-        "A" => 65,
-        "B" => 66,
-        "C" => 67,
-        "D" => 68,
-        "E" => 69,
-        "F" => 70,
-        "W" => 87,
-        "S" => 83,
-        "Q" => 81,
-        "LEFT" => 4194319,
-        "RIGHT" => 4194321,
-        "UP" => 4194320,
-        "DOWN" => 4194322,
-        "ENTER" | "RETURN" => 4194309,
-        "ESCAPE" | "ESC" => 4194305,
-        "TAB" => 4194306,
-        "SHIFT" => 4194325,
-        "CTRL" | "CONTROL" => 4194326,
-        "ALT" => 4194328,
-        _ => 0, // Unknown key
+    let prefix = prefix.unwrap_or_default();
+    let sections = std::iter::once("")
+        .chain(settings.sections())
+        .map(|section| ProjectSettingsSection {
+            name: section.to_string(),
+            settings: settings
+                .entries(section)
+                .into_iter()
+                .map(|(key, value)| ProjectSetting {
+                    path: if section.is_empty() {
+                        key.to_string()
+                    } else {
+                        format!("{}/{}", section, key)
+                    },
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })
+                .filter(|setting| setting.path.starts_with(prefix))
+                .collect(),
+        })
+        .filter(|section| !section.settings.is_empty())
+        .collect();
+    ProjectSettingsResult {
+        sections,
+        error: None,
     }
 }
 
@@ -342,67 +242,87 @@ pub fn resolve_set_project_setting(
     if input.path.is_empty() {
         return OperationResult::err_msg("Setting path cannot be empty");
     }
+    let value = match format_setting_value(&input.value, input.value_type.as_deref()) {
+        Ok(value) => value,
+        Err(message) => {
+            return OperationResult::err(GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                message,
+            ))
+        }
+    };
 
-    let project_godot = ctx.project_path.join("project.godot");
-    if !project_godot.exists() {
-        return OperationResult::err_msg("project.godot not found");
-    }
-
-    // Read current project.godot
-    let content = match fs::read_to_string(&project_godot) {
-        Ok(c) => c,
-        Err(e) => return OperationResult::err_msg(format!("Failed to read project.godot: {}", e)),
+    let (project_godot, mut settings) = match read_project_settings(ctx) {
+        Ok(read) => read,
+        Err(error) => return OperationResult::err(*error),
     };
+    if !settings.set(&input.path, &value) {
+        return OperationResult::ok();
+    }
 
-    // Parse the path to find section and key
-    let parts: Vec<&str> = input.path.split('/').collect();
-    if parts.len() < 2 {
-        return OperationResult::err_msg("Invalid setting path format (expected: section/key)");
+    // Write back
+    if let Err(e) = audit::write_file(
+        ctx,
+        "setProjectSetting",
+        &project_godot,
+        settings.to_project_godot(),
+    ) {
+        return OperationResult::err_msg(format!("Failed to write project.godot: {}", e));
     }
+    telemetry::record_files_written(1);
 
-    let section = format!("[{}]", parts[0]);
-    let key = parts[1..].join("/");
+    OperationResult::ok()
+}
 
-    let mut new_content = String::new();
-    let mut in_target_section = false;
-    let mut found_key = false;
+/// Path and parsed contents of project.godot
+pub(super) fn read_project_settings(
+    ctx: &GqlContext,
+) -> Result<(PathBuf, ProjectSettings), Box<GqlStructuredError>> {
+    let project_godot = ctx.project_path.join("project.godot");
+    let content = ctx.fs.read_to_string(&project_godot).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "PROJECT_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read project.godot: {}", e),
+        ))
+    })?;
+    Ok((project_godot, ProjectSettings::parse(&content)))
+}
 
-    for line in content.lines() {
-        // Check for section header
-        if line.trim().starts_with('[') && line.trim().ends_with(']') {
-            // If leaving target section without finding key, insert it
-            if in_target_section && !found_key {
-                new_content.push_str(&format!("{}={}\n", key, input.value));
-                found_key = true;
+/// A setting value in project.godot syntax, checked against the type hint
+/// (int, float, bool, string); without a hint the value is written as given
+fn format_setting_value(value: &str, value_type: Option<&str>) -> Result<String, String> {
+    let value = value.trim();
+    let invalid = |type_name: &str| format!("'{}' is not a valid {} value", value, type_name);
+    match value_type.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("") => Ok(value.to_string()),
+        Some("int") => value
+            .parse::<i64>()
+            .map(|v| v.to_string())
+            .map_err(|_| invalid("int")),
+        Some("float") => value
+            .parse::<f64>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|_| value.to_string())
+            .ok_or_else(|| invalid("float")),
+        Some("bool") => match value {
+            "true" | "false" => Ok(value.to_string()),
+            _ => Err(invalid("bool")),
+        },
+        Some("string") => {
+            if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+                Ok(value.to_string())
+            } else {
+                Ok(quote_godot_string(value))
             }
-            in_target_section = line.trim() == section;
         }
-
-        // Check for existing key in target section
-        if in_target_section && line.starts_with(&format!("{}=", key)) {
-            new_content.push_str(&format!("{}={}\n", key, input.value));
-            found_key = true;
-            continue;
-        }
-
-        new_content.push_str(line);
-        new_content.push('\n');
-    }
-
-    // If section not found, add it
-    if !found_key {
-        if !content.contains(&section) {
-            new_content.push_str(&format!("\n{}\n", section));
-        }
-        new_content.push_str(&format!("{}={}\n", key, input.value));
+        Some(other) => Err(format!(
+            "Unknown setting type '{}' (expected int, float, bool or string)",
+            other
+        )),
     }
-
-    // Write back
-    if let Err(e) = fs::write(&project_godot, new_content) {
-        return OperationResult::err_msg(format!("Failed to write project.godot: {}", e));
-    }
-
-    OperationResult::ok()
 }
 
 #[cfg(test)]
@@ -0,0 +1,179 @@
+//! Property Search Resolver
+//!
+//! Finds nodes across scenes by property value, e.g. every node written with
+//! `visible = false` or every light whose `light_energy` is above 5. Only
+//! values stored in the scene file are seen: Godot omits properties left at
+//! their default.
+
+use regex::Regex;
+
+use crate::godot::tscn::node_path;
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::contracts_resolver::{glob_match, wildcard_match};
+use super::project_index::parallel_map;
+use super::project_resolver::collect_project_files;
+use super::tags_resolver::TagScope;
+use super::types::*;
+
+/// How a property value is tested
+enum ValueFilter {
+    /// Property is set to anything
+    Any,
+    Regex(Regex),
+    Compare(Comparison, String),
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl ValueFilter {
+    /// `== x`, `!= x`, `> n`, `>= n`, `< n`, `<= n`, or a regex
+    fn parse(pattern: Option<&str>) -> Result<Self, String> {
+        let Some(pattern) = pattern.map(str::trim).filter(|p| !p.is_empty()) else {
+            return Ok(Self::Any);
+        };
+        const OPERATORS: &[(&str, Comparison)] = &[
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            ("!=", Comparison::Ne),
+            ("==", Comparison::Eq),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+        ];
+        for (operator, comparison) in OPERATORS {
+            if let Some(operand) = pattern.strip_prefix(operator) {
+                return Ok(Self::Compare(*comparison, operand.trim().to_string()));
+            }
+        }
+        Regex::new(pattern)
+            .map(Self::Regex)
+            .map_err(|e| format!("Invalid value pattern '{}': {}", pattern, e))
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Regex(regex) => regex.is_match(value),
+            Self::Compare(comparison, operand) => compare(value, *comparison, operand),
+        }
+    }
+}
+
+/// Numbers compare numerically; anything else only by (in)equality of the
+/// text without surrounding quotes
+fn compare(value: &str, comparison: Comparison, operand: &str) -> bool {
+    let value = value.trim();
+    if let (Ok(a), Ok(b)) = (value.parse::<f64>(), operand.parse::<f64>()) {
+        return match comparison {
+            Comparison::Eq => a == b,
+            Comparison::Ne => a != b,
+            Comparison::Gt => a > b,
+            Comparison::Ge => a >= b,
+            Comparison::Lt => a < b,
+            Comparison::Le => a <= b,
+        };
+    }
+    let unquote = |s: &str| s.trim_matches('"').to_string();
+    match comparison {
+        Comparison::Eq => unquote(value) == unquote(operand),
+        Comparison::Ne => unquote(value) != unquote(operand),
+        _ => false,
+    }
+}
+
+/// Resolve findPropertyValues query
+pub fn resolve_find_property_values(
+    ctx: &GqlContext,
+    property: &str,
+    value_pattern: Option<&str>,
+    scene_pattern: Option<&str>,
+    node_type: Option<&str>,
+    tags: Option<&FileTagFilter>,
+) -> PropertySearchResult {
+    let filter = match ValueFilter::parse(value_pattern) {
+        Ok(filter) => filter,
+        Err(message) => {
+            return PropertySearchResult {
+                success: false,
+                matches: vec![],
+                scenes_scanned: 0,
+                error: Some(
+                    GqlStructuredError::new(
+                        "VALUE_PATTERN_INVALID",
+                        GqlErrorCategory::Validation,
+                        message,
+                    )
+                    .with_suggestion(
+                        "valuePattern には正規表現か、== / != / > / >= / < / <= で始まる比較を指定してください",
+                    ),
+                ),
+            };
+        }
+    };
+
+    let scope = match TagScope::new(ctx, tags) {
+        Ok(scope) => scope,
+        Err(error) => {
+            return PropertySearchResult {
+                success: false,
+                matches: vec![],
+                scenes_scanned: 0,
+                error: Some(*error),
+            }
+        }
+    };
+
+    let (scene_files, _) = collect_project_files(ctx);
+    let scene_paths: Vec<String> = scene_files
+        .into_iter()
+        .map(|scene| scene.path)
+        .filter(|path| {
+            scene_pattern.is_none_or(|pattern| {
+                glob_match(
+                    path_utils::strip_res_prefix(pattern),
+                    path_utils::strip_res_prefix(path),
+                )
+            })
+        })
+        .filter(|path| scope.as_ref().is_none_or(|s| s.includes(ctx, path)))
+        .collect();
+
+    let per_scene = parallel_map(&scene_paths, |scene_path| {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+        let Ok(scene) = ctx.index.scene(&fs_path) else {
+            return Vec::new();
+        };
+        scene
+            .nodes
+            .iter()
+            .filter(|node| node_type.is_none_or(|pattern| wildcard_match(pattern, &node.node_type)))
+            .filter_map(|node| {
+                let value = node.properties.get(property)?;
+                filter.matches(value).then(|| PropertyMatch {
+                    scene_path: scene_path.clone(),
+                    node_path: node_path(node),
+                    node_name: node.name.clone(),
+                    node_type: node.node_type.clone(),
+                    property: property.to_string(),
+                    value: value.clone(),
+                })
+            })
+            .collect()
+    });
+
+    PropertySearchResult {
+        success: true,
+        matches: per_scene.into_iter().flatten().collect(),
+        scenes_scanned: scene_paths.len() as i32,
+        error: None,
+    }
+}
@@ -2,18 +2,28 @@
 //!
 //! Handles code understanding and refactoring operations.
 
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use crate::godot::csharp::{self, CSharpScript};
+use crate::godot::gdscript::ast::{ClassDecl, Member};
+use crate::godot::gdscript::lexer::{tokenize, TokenKind};
 use crate::godot::gdscript::GDScript;
 use crate::path_utils;
+use crate::telemetry;
 
+use super::audit;
 use super::context::GqlContext;
+use super::journal::{Journal, JournalChange};
 use super::project_resolver::{collect_project_files, to_res_path};
 use super::types::*;
+use super::vfs::ProjectFs;
 
 /// Get class hierarchy for a script
 pub fn resolve_class_hierarchy(ctx: &GqlContext, script_path: &str) -> ClassHierarchy {
+    if script_path.ends_with(".cs") {
+        return csharp_class_hierarchy(ctx, script_path);
+    }
     let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, script_path);
 
     let mut extends_chain = Vec::new();
@@ -23,7 +33,7 @@ pub fn resolve_class_hierarchy(ctx: &GqlContext, script_path: &str) -> ClassHier
 
     // Follow extends chain
     while let Some(ref path) = current_path {
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Ok(content) = ctx.fs.read_to_string(path) {
             let script = GDScript::parse(&content);
 
             // Get class_name from first script
@@ -79,6 +89,55 @@ pub fn resolve_class_hierarchy(ctx: &GqlContext, script_path: &str) -> ClassHier
     }
 }
 
+/// Class hierarchy of a C# script. Base classes are named, not pathed, so
+/// one declared by another project script is looked up by class name.
+fn csharp_class_hierarchy(ctx: &GqlContext, script_path: &str) -> ClassHierarchy {
+    let (_, scripts) = collect_project_files(ctx);
+    let classes: Vec<(String, Arc<CSharpScript>)> = scripts
+        .into_iter()
+        .filter(|s| s.path.ends_with(".cs"))
+        .filter_map(|s| {
+            let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &s.path);
+            ctx.index.csharp_script(&file_path).map(|c| (s.path, c))
+        })
+        .collect();
+
+    let mut current = classes
+        .iter()
+        .find(|(path, _)| path == script_path)
+        .map(|(_, class)| class.clone());
+    let class_name = current.as_ref().and_then(|c| c.class_name.clone());
+    let mut extends_chain = Vec::new();
+    let mut depth = 0;
+
+    while let Some(class) = current.take() {
+        depth += 1;
+        let Some(base) = &class.base_class else {
+            break;
+        };
+        let parent = classes
+            .iter()
+            .find(|(_, c)| c.class_name.as_deref() == Some(base.as_str()));
+        extends_chain.push(ClassInfo {
+            name: base.clone(),
+            script_path: parent.map(|(path, _)| path.clone()),
+            is_builtin: parent.is_none(),
+        });
+        current = parent.map(|(_, c)| c.clone());
+        // Safety limit
+        if depth > 50 {
+            break;
+        }
+    }
+
+    ClassHierarchy {
+        script_path: script_path.to_string(),
+        class_name,
+        extends_chain,
+        depth,
+    }
+}
+
 /// Find references to a symbol across the project
 pub fn resolve_find_references(
     ctx: &GqlContext,
@@ -89,11 +148,7 @@ pub fn resolve_find_references(
     let mut definition = None;
 
     // Collect all scripts
-    let (_, scripts) = collect_project_files(&ctx.project_path);
-
-    // Search pattern - word boundary match
-    let pattern = format!(r"\b{}\b", regex::escape(symbol));
-    let regex = regex::Regex::new(&pattern).unwrap_or_else(|_| regex::Regex::new(symbol).unwrap());
+    let (_, scripts) = collect_project_files(ctx);
 
     for script_file in &scripts {
         // Apply scope filter if provided
@@ -104,31 +159,28 @@ pub fn resolve_find_references(
         }
 
         let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &script_file.path);
-        if let Ok(content) = fs::read_to_string(&file_path) {
-            for (line_num, line) in content.lines().enumerate() {
-                if regex.is_match(line) {
-                    let line_num = (line_num + 1) as i32;
-
-                    // Check if this is a definition (var, func, signal, class_name)
-                    let trimmed = line.trim();
-                    let is_definition = trimmed.starts_with("func ")
-                        || trimmed.starts_with("var ")
-                        || trimmed.starts_with("signal ")
-                        || trimmed.starts_with("class_name ")
-                        || trimmed.starts_with("@export var ");
-
-                    let location = SymbolLocation {
-                        file: script_file.path.clone(),
-                        line: line_num,
-                        column: line.find(symbol).map(|c| c as i32),
-                        context: Some(trimmed.to_string()),
-                    };
-
-                    if is_definition && definition.is_none() {
-                        definition = Some(location);
-                    } else {
-                        references.push(location);
-                    }
+        if let Ok(content) = ctx.fs.read_to_string(&file_path) {
+            let csharp = script_file.path.ends_with(".cs");
+            let definition_lines = if csharp {
+                CSharpScript::parse(&content).declaration_lines(symbol)
+            } else {
+                declaration_lines(&GDScript::parse(&content).ast, symbol)
+            };
+            let lines: Vec<&str> = content.lines().collect();
+
+            for occurrence in symbol_occurrences(&content, symbol, csharp) {
+                let location = SymbolLocation {
+                    file: script_file.path.clone(),
+                    line: occurrence.line as i32,
+                    column: Some(occurrence.column as i32),
+                    context: lines.get(occurrence.line - 1).map(|l| l.trim().to_string()),
+                };
+
+                // The first occurrence on a declaration line is the definition
+                if definition.is_none() && definition_lines.contains(&occurrence.line) {
+                    definition = Some(location);
+                } else {
+                    references.push(location);
                 }
             }
         }
@@ -149,7 +201,7 @@ pub fn resolve_autoloads(ctx: &GqlContext) -> AutoloadsResult {
     let project_godot = ctx.project_path.join("project.godot");
     let mut autoloads = Vec::new();
 
-    if let Ok(content) = fs::read_to_string(&project_godot) {
+    if let Ok(content) = ctx.fs.read_to_string(&project_godot) {
         let mut in_autoload_section = false;
 
         for line in content.lines() {
@@ -189,29 +241,16 @@ pub fn resolve_autoloads(ctx: &GqlContext) -> AutoloadsResult {
 }
 
 /// Rename a symbol across the project
+///
+/// Every edit is planned (and journaled) before the first file is written.
 pub fn resolve_rename_symbol(ctx: &GqlContext, input: &RenameSymbolInput) -> RenameSymbolResult {
     let mut files_changed = Vec::new();
     let mut total_occurrences = 0;
 
     // Collect all scripts
-    let (_, scripts) = collect_project_files(&ctx.project_path);
-
-    // Search pattern - word boundary match
-    let pattern = format!(r"\b{}\b", regex::escape(&input.symbol));
-    let regex = match regex::Regex::new(&pattern) {
-        Ok(r) => r,
-        Err(e) => {
-            return RenameSymbolResult {
-                success: false,
-                old_name: input.symbol.clone(),
-                new_name: input.new_name.clone(),
-                files_changed: vec![],
-                occurrences_replaced: 0,
-                message: Some(format!("Invalid symbol pattern: {}", e)),
-            };
-        }
-    };
+    let (_, scripts) = collect_project_files(ctx);
 
+    let mut edits = Vec::new();
     for script_file in &scripts {
         // Apply scope filter if provided
         if let Some(ref scope) = input.scope {
@@ -221,31 +260,73 @@ pub fn resolve_rename_symbol(ctx: &GqlContext, input: &RenameSymbolInput) -> Ren
         }
 
         let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &script_file.path);
-        if let Ok(content) = fs::read_to_string(&file_path) {
-            let new_content = regex.replace_all(&content, input.new_name.as_str());
-
-            if new_content != content {
-                let count = regex.find_iter(&content).count();
-                total_occurrences += count as i32;
-
-                // Write the modified content
-                if let Err(e) = fs::write(&file_path, new_content.as_ref()) {
-                    return RenameSymbolResult {
-                        success: false,
-                        old_name: input.symbol.clone(),
-                        new_name: input.new_name.clone(),
-                        files_changed,
-                        occurrences_replaced: total_occurrences,
-                        message: Some(format!("Failed to write {}: {}", script_file.path, e)),
-                    };
+        if let Ok(content) = ctx.fs.read_to_string(&file_path) {
+            let csharp = script_file.path.ends_with(".cs");
+            let occurrences = symbol_occurrences(&content, &input.symbol, csharp);
+
+            if !occurrences.is_empty() {
+                let mut new_content = content.clone();
+                for occurrence in occurrences.iter().rev() {
+                    new_content.replace_range(occurrence.start..occurrence.end, &input.new_name);
                 }
+                edits.push((script_file, file_path, new_content, occurrences.len()));
+            }
+        }
+    }
+
+    let changes: Vec<JournalChange> = edits
+        .iter()
+        .map(|(_, path, content, _)| JournalChange::Write {
+            path,
+            data: content.as_bytes(),
+        })
+        .collect();
+    let journal = match Journal::for_context(ctx, "renameSymbol", &changes) {
+        Ok(journal) => journal,
+        Err(e) => {
+            return RenameSymbolResult {
+                success: false,
+                old_name: input.symbol.clone(),
+                new_name: input.new_name.clone(),
+                files_changed,
+                occurrences_replaced: 0,
+                message: Some(format!("Failed to journal the rename: {}", e)),
+            }
+        }
+    };
 
-                files_changed.push(FileChange {
-                    path: script_file.path.clone(),
-                    changes_count: count as i32,
-                });
+    let mut written: Vec<(&Path, Option<Vec<u8>>)> = Vec::new();
+    for (script_file, file_path, new_content, count) in &edits {
+        total_occurrences += *count as i32;
+
+        // Write the modified content
+        let previous = ctx.fs.read(file_path).ok();
+        if let Err(e) = audit::write_file(ctx, "renameSymbol", file_path, new_content) {
+            // Reported to the caller, so the scripts already renamed are put
+            // back rather than left for recovery to complete
+            restore_files(ctx, written);
+            if let Some(journal) = journal {
+                journal.finish();
             }
+            return RenameSymbolResult {
+                success: false,
+                old_name: input.symbol.clone(),
+                new_name: input.new_name.clone(),
+                files_changed,
+                occurrences_replaced: total_occurrences,
+                message: Some(format!("Failed to write {}: {}", script_file.path, e)),
+            };
         }
+        telemetry::record_files_written(1);
+        written.push((file_path, previous));
+
+        files_changed.push(FileChange {
+            path: script_file.path.clone(),
+            changes_count: *count as i32,
+        });
+    }
+    if let Some(journal) = journal {
+        journal.finish();
     }
 
     RenameSymbolResult {
@@ -258,6 +339,85 @@ pub fn resolve_rename_symbol(ctx: &GqlContext, input: &RenameSymbolInput) -> Ren
     }
 }
 
+/// A symbol occurrence in script source
+struct SymbolOccurrence {
+    /// Byte range of the symbol text
+    start: usize,
+    end: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Identifier tokens named `symbol`, plus string literals holding exactly the
+/// symbol (`connect("died", ...)`, `&"died"`). Comments never match.
+fn symbol_occurrences(content: &str, symbol: &str, csharp: bool) -> Vec<SymbolOccurrence> {
+    let occurrence = |start: usize, line: usize, column: usize, offset: usize| SymbolOccurrence {
+        start: start + offset,
+        end: start + offset + symbol.len(),
+        line,
+        column: column + offset,
+    };
+
+    if csharp {
+        return csharp::tokenize(content)
+            .iter()
+            .filter_map(|token| {
+                let offset = match &token.kind {
+                    csharp::TokenKind::Identifier(name) if name == symbol => 0,
+                    csharp::TokenKind::String(value) if value == symbol => {
+                        content[token.start..token.end].find(symbol)?
+                    }
+                    _ => return None,
+                };
+                Some(occurrence(token.start, token.line, token.column, offset))
+            })
+            .collect();
+    }
+
+    let (tokens, _) = tokenize(content);
+    tokens
+        .iter()
+        .filter_map(|token| {
+            let offset = match &token.kind {
+                TokenKind::Identifier(name) if name == symbol => 0,
+                TokenKind::String { value, .. } if value == symbol => {
+                    content[token.start..token.end].find(symbol)?
+                }
+                _ => return None,
+            };
+            Some(occurrence(token.start, token.line, token.column, offset))
+        })
+        .collect()
+}
+
+/// Lines declaring `symbol` in a class or its inner classes
+fn declaration_lines(class: &ClassDecl, symbol: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    if class.name.as_deref() == Some(symbol) {
+        lines.push(class.line);
+    }
+
+    for member in &class.members {
+        match member {
+            Member::Class(inner) => lines.extend(declaration_lines(inner, symbol)),
+            Member::Enum(decl) => {
+                if decl.name.as_deref() == Some(symbol) {
+                    lines.push(decl.line);
+                }
+                lines.extend(
+                    decl.values
+                        .iter()
+                        .filter(|v| v.name == symbol)
+                        .map(|v| v.line),
+                );
+            }
+            _ if member.name() == Some(symbol) => lines.push(member.line()),
+            _ => {}
+        }
+    }
+    lines
+}
+
 /// Extract code block into a new function
 pub fn resolve_extract_function(
     ctx: &GqlContext,
@@ -265,7 +425,7 @@ pub fn resolve_extract_function(
 ) -> ExtractFunctionResult {
     let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.script_path);
 
-    let content = match fs::read_to_string(&file_path) {
+    let content = match ctx.fs.read_to_string(&file_path) {
         Ok(c) => c,
         Err(e) => {
             return ExtractFunctionResult {
@@ -348,7 +508,7 @@ pub fn resolve_extract_function(
     new_content.push_str(&new_function);
 
     // Write back
-    if let Err(e) = fs::write(&file_path, &new_content) {
+    if let Err(e) = audit::write_file(ctx, "extractFunction", &file_path, &new_content) {
         return ExtractFunctionResult {
             success: false,
             function_name: input.function_name.clone(),
@@ -356,6 +516,7 @@ pub fn resolve_extract_function(
             message: Some(format!("Failed to write script: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     ExtractFunctionResult {
         success: true,
@@ -367,3 +528,327 @@ pub fn resolve_extract_function(
         )),
     }
 }
+
+/// Rename a file in place, keeping its directory
+pub fn resolve_rename_file(ctx: &GqlContext, input: &RenameFileInput) -> MoveFileResult {
+    if input.new_name.is_empty() || input.new_name.contains('/') || input.new_name.contains('\\') {
+        return move_file_error(
+            &input.path,
+            &input.new_name,
+            input.dry_run,
+            "New name must be a plain file name without directories",
+        );
+    }
+
+    let to = match input.path.rsplit_once('/') {
+        Some((dir, _)) if dir != "res:/" => format!("{}/{}", dir, input.new_name),
+        _ => format!("res://{}", input.new_name),
+    };
+
+    resolve_move_file(
+        ctx,
+        &MoveFileInput {
+            from: input.path.clone(),
+            to,
+            dry_run: input.dry_run,
+        },
+    )
+}
+
+/// Move a file or directory and rewrite every res:// reference to it
+///
+/// uid:// references keep working because the moved files (and their
+/// `.uid` / `.import` sidecars) carry their uid with them.
+pub fn resolve_move_file(ctx: &GqlContext, input: &MoveFileInput) -> MoveFileResult {
+    let project_path = &ctx.project_path;
+
+    let from_path = match path_utils::to_fs_path(project_path, &input.from) {
+        Ok(p) => p,
+        Err(e) => return move_file_error(&input.from, &input.to, input.dry_run, e.to_string()),
+    };
+    let to_path = match path_utils::to_fs_path(project_path, &input.to) {
+        Ok(p) => p,
+        Err(e) => return move_file_error(&input.from, &input.to, input.dry_run, e.to_string()),
+    };
+
+    if !ctx.fs.exists(&from_path) {
+        return move_file_error(
+            &input.from,
+            &input.to,
+            input.dry_run,
+            format!("Source not found: {}", input.from),
+        );
+    }
+    if ctx.fs.exists(&to_path) {
+        return move_file_error(
+            &input.from,
+            &input.to,
+            input.dry_run,
+            format!("Destination already exists: {}", input.to),
+        );
+    }
+
+    let from_res = format!("res://{}", path_utils::strip_res_prefix(&input.from));
+    let to_res = format!("res://{}", path_utils::strip_res_prefix(&input.to));
+    let is_dir = ctx.fs.is_dir(&from_path);
+
+    // Plan the on-disk moves (file plus sidecars)
+    let mut moves = vec![(from_path.clone(), to_path.clone())];
+    if !is_dir {
+        for sidecar in [".uid", ".import"] {
+            let side_from = append_extension(&from_path, sidecar);
+            if ctx.fs.exists(&side_from) {
+                moves.push((side_from, append_extension(&to_path, sidecar)));
+            }
+        }
+    }
+    let moved_files: Vec<String> = moves
+        .iter()
+        .map(|(from, _)| to_res_path(project_path, from))
+        .collect();
+
+    // Plan the reference rewrites, in double- or single-quoted strings
+    let pattern = if is_dir {
+        format!(
+            r#""(\*?){0}/([^"]*)"|'(\*?){0}/([^']*)'"#,
+            regex::escape(from_res.trim_end_matches('/'))
+        )
+    } else {
+        format!(r#""(\*?){0}"|'(\*?){0}'"#, regex::escape(&from_res))
+    };
+    let regex = match regex::Regex::new(&pattern) {
+        Ok(r) => r,
+        Err(e) => {
+            return move_file_error(
+                &input.from,
+                &input.to,
+                input.dry_run,
+                format!("Invalid path pattern: {}", e),
+            )
+        }
+    };
+    let rewrite = |caps: &regex::Captures| {
+        // Each quote style has its own groups; only the matched one is set
+        let groups: Vec<&str> = caps.iter().skip(1).flatten().map(|m| m.as_str()).collect();
+        let quote = &caps[0][..1];
+        if is_dir {
+            let dir = to_res.trim_end_matches('/');
+            format!("{0}{1}{2}/{3}{0}", quote, groups[0], dir, groups[1])
+        } else {
+            format!("{0}{1}{2}{0}", quote, groups[0], to_res)
+        }
+    };
+
+    let mut rewrites = Vec::new();
+    let mut files_changed = Vec::new();
+    let mut references_updated = 0;
+    for file in collect_reference_files(ctx.fs.as_ref(), project_path) {
+        let Ok(content) = ctx.fs.read_to_string(&file) else {
+            continue;
+        };
+        let count = regex.find_iter(&content).count();
+        if count == 0 {
+            continue;
+        }
+        references_updated += count as i32;
+
+        // Report changes under the file's post-move location
+        let final_path = moves
+            .iter()
+            .find_map(|(from, to)| {
+                file.strip_prefix(from).ok().map(|rest| {
+                    if rest.as_os_str().is_empty() {
+                        to.clone()
+                    } else {
+                        to.join(rest)
+                    }
+                })
+            })
+            .unwrap_or_else(|| file.clone());
+        files_changed.push(FileChange {
+            path: to_res_path(project_path, &final_path),
+            changes_count: count as i32,
+        });
+        rewrites.push((
+            final_path,
+            regex.replace_all(&content, &rewrite).into_owned(),
+        ));
+    }
+    files_changed.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if !input.dry_run {
+        let mut changes = Vec::new();
+        if let Some(parent) = to_path.parent() {
+            changes.push(JournalChange::CreateDir { path: parent });
+        }
+        changes.extend(
+            moves
+                .iter()
+                .map(|(from, to)| JournalChange::Rename { from, to }),
+        );
+        changes.extend(rewrites.iter().map(|(path, content)| JournalChange::Write {
+            path,
+            data: content.as_bytes(),
+        }));
+        let journal = match Journal::for_context(ctx, "moveFile", &changes) {
+            Ok(journal) => journal,
+            Err(e) => {
+                return move_file_error(
+                    &input.from,
+                    &input.to,
+                    false,
+                    format!("Failed to journal the move: {}", e),
+                )
+            }
+        };
+        // Failures are undone and reported to the caller, so not left for
+        // recovery to complete
+        let result = apply_move(ctx, &to_path, &moves, &rewrites);
+        if let Some(journal) = journal {
+            journal.finish();
+        }
+        if let Err(message) = result {
+            return move_file_error(&input.from, &input.to, false, message);
+        }
+    }
+
+    MoveFileResult {
+        success: true,
+        from: from_res,
+        to: to_res,
+        dry_run: input.dry_run,
+        moved_files,
+        files_changed,
+        references_updated,
+        message: None,
+    }
+}
+
+/// Move the files (creating the destination folder), then rewrite the
+/// references to them
+///
+/// On the first failure, every change already applied is undone.
+fn apply_move(
+    ctx: &GqlContext,
+    to_path: &Path,
+    moves: &[(PathBuf, PathBuf)],
+    rewrites: &[(PathBuf, String)],
+) -> Result<(), String> {
+    let mut created_dir = None;
+    if let Some(parent) = to_path.parent() {
+        if !ctx.fs.is_dir(parent) {
+            ctx.fs
+                .create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+            created_dir = Some(parent);
+        }
+    }
+
+    let mut moved: Vec<(&Path, &Path)> = Vec::new();
+    let mut written: Vec<(&Path, Option<Vec<u8>>)> = Vec::new();
+    let mut failure = None;
+    for (from, to) in moves {
+        if let Err(e) = ctx.fs.rename(from, to) {
+            failure = Some(format!("Failed to move {}: {}", from.display(), e));
+            break;
+        }
+        audit::record_move(ctx, "moveFile", from, to);
+        telemetry::record_files_written(1);
+        moved.push((from, to));
+    }
+    if failure.is_none() {
+        for (path, content) in rewrites {
+            let previous = ctx.fs.read(path).ok();
+            if let Err(e) = audit::write_file(ctx, "moveFile", path, content) {
+                failure = Some(format!("Failed to write {}: {}", path.display(), e));
+                break;
+            }
+            telemetry::record_files_written(1);
+            written.push((path, previous));
+        }
+    }
+
+    let Some(message) = failure else {
+        return Ok(());
+    };
+    restore_files(ctx, written);
+    for (from, to) in moved.into_iter().rev() {
+        let _ = ctx.fs.rename(to, from);
+    }
+    if let Some(dir) = created_dir {
+        let _ = ctx.fs.remove_dir(dir);
+    }
+    Err(message)
+}
+
+/// Best effort: put back the content files had before an operation wrote
+/// them, latest first
+fn restore_files(ctx: &GqlContext, written: Vec<(&Path, Option<Vec<u8>>)>) {
+    for (path, previous) in written.into_iter().rev() {
+        let _ = match previous {
+            Some(data) => ctx.fs.write(path, &data),
+            None => ctx.fs.remove_file(path),
+        };
+    }
+}
+
+fn move_file_error(
+    from: &str,
+    to: &str,
+    dry_run: bool,
+    message: impl Into<String>,
+) -> MoveFileResult {
+    MoveFileResult {
+        success: false,
+        from: from.to_string(),
+        to: to.to_string(),
+        dry_run,
+        moved_files: vec![],
+        files_changed: vec![],
+        references_updated: 0,
+        message: Some(message.into()),
+    }
+}
+
+fn append_extension(path: &Path, suffix: &str) -> std::path::PathBuf {
+    let mut os = path.as_os_str().to_owned();
+    os.push(suffix);
+    os.into()
+}
+
+/// Collect text files that may contain res:// references
+fn collect_reference_files(fs: &dyn ProjectFs, project_path: &Path) -> Vec<std::path::PathBuf> {
+    let mut files = Vec::new();
+    collect_reference_files_recursive(fs, project_path, &mut files);
+    files
+}
+
+fn collect_reference_files_recursive(
+    fs: &dyn ProjectFs,
+    dir: &Path,
+    files: &mut Vec<std::path::PathBuf>,
+) {
+    let Ok(entries) = fs.read_dir(dir) else {
+        return;
+    };
+
+    for path in entries {
+        if path
+            .file_name()
+            .map(|n| n == ".godot" || n == ".godot-mcp")
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        if fs.is_dir(&path) {
+            collect_reference_files_recursive(fs, &path, files);
+        } else if let Some(ext) = path.extension() {
+            match ext.to_str() {
+                Some("tscn") | Some("tres") | Some("gd") | Some("gdshader") | Some("cs")
+                | Some("godot") | Some("cfg") | Some("import") => files.push(path),
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Report Resolver
+//!
+//! Summarizes a session's entries in the audit log as a changelog grouped by
+//! operation and by file, with the diffs attached, plus a Markdown rendering
+//! for pasting into a PR description.
+
+use std::collections::BTreeMap;
+
+use super::audit::{self, AuditEntry};
+use super::context::GqlContext;
+use super::types::*;
+
+/// Resolve sessionReport query (latest recorded session when no id is given)
+pub fn resolve_session_report(ctx: &GqlContext, session_id: Option<&str>) -> SessionReport {
+    let entries = audit::read_entries(ctx);
+    let session_id = session_id
+        .map(str::to_string)
+        .or_else(|| entries.last().map(|e| e.session_id.clone()))
+        .unwrap_or_else(|| ctx.session_id.clone());
+    let entries: Vec<AuditEntry> = entries
+        .into_iter()
+        .filter(|e| e.session_id == session_id)
+        .collect();
+
+    // Operations in first-use order, files in path order
+    let mut features: Vec<SessionFeatureChange> = Vec::new();
+    let mut files: BTreeMap<String, SessionFileChange> = BTreeMap::new();
+    for entry in &entries {
+        match features.iter_mut().find(|f| f.operation == entry.operation) {
+            Some(feature) => {
+                if !feature.files.contains(&entry.path) {
+                    feature.files.push(entry.path.clone());
+                }
+            }
+            None => features.push(SessionFeatureChange {
+                operation: entry.operation.clone(),
+                files: vec![entry.path.clone()],
+            }),
+        }
+
+        let file = files
+            .entry(entry.path.clone())
+            .or_insert_with(|| SessionFileChange {
+                path: entry.path.clone(),
+                operations: Vec::new(),
+                created: entry.created,
+                moved_from: None,
+                change_count: 0,
+                diff: String::new(),
+            });
+        if !file.operations.contains(&entry.operation) {
+            file.operations.push(entry.operation.clone());
+        }
+        if entry.moved_from.is_some() {
+            file.moved_from = entry.moved_from.clone();
+        }
+        file.change_count += 1;
+        file.diff.push_str(&entry.diff);
+    }
+
+    let mut report = SessionReport {
+        session_id,
+        started_at: entries.first().map(|e| e.timestamp.to_string()),
+        ended_at: entries.last().map(|e| e.timestamp.to_string()),
+        change_count: entries.len() as i32,
+        features,
+        files: files.into_values().collect(),
+        markdown: String::new(),
+    };
+    report.markdown = render_markdown(&report);
+    report
+}
+
+fn render_markdown(report: &SessionReport) -> String {
+    let mut out = format!("## Session `{}`\n\n", report.session_id);
+    if report.files.is_empty() {
+        out.push_str("No file changes recorded.\n");
+        return out;
+    }
+    out.push_str(&format!(
+        "{} change(s) to {} file(s).\n\n### By operation\n\n",
+        report.change_count,
+        report.files.len()
+    ));
+    for feature in &report.features {
+        let files: Vec<String> = feature.files.iter().map(|f| format!("`{}`", f)).collect();
+        out.push_str(&format!(
+            "- **{}**: {}\n",
+            feature.operation,
+            files.join(", ")
+        ));
+    }
+
+    out.push_str("\n### By file\n");
+    for file in &report.files {
+        let status = if let Some(from) = &file.moved_from {
+            format!(" (moved from `{}`)", from)
+        } else if file.created {
+            " (new)".to_string()
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "\n#### `{}`{}\n\n{}\n",
+            file.path,
+            status,
+            file.operations.join(", ")
+        ));
+        if !file.diff.is_empty() {
+            out.push_str(&format!("\n```diff\n{}```\n", file.diff));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_groups_files() {
+        let report = SessionReport {
+            session_id: "s-1".to_string(),
+            started_at: Some("1".to_string()),
+            ended_at: Some("2".to_string()),
+            change_count: 2,
+            features: vec![SessionFeatureChange {
+                operation: "createScene".to_string(),
+                files: vec!["res://a.tscn".to_string()],
+            }],
+            files: vec![SessionFileChange {
+                path: "res://a.tscn".to_string(),
+                operations: vec!["createScene".to_string()],
+                created: true,
+                moved_from: None,
+                change_count: 1,
+                diff: "+[gd_scene format=3]\n".to_string(),
+            }],
+            markdown: String::new(),
+        };
+        let markdown = render_markdown(&report);
+        assert!(markdown.contains("- **createScene**: `res://a.tscn`"));
+        assert!(markdown.contains("#### `res://a.tscn` (new)"));
+        assert!(markdown.contains("```diff\n+[gd_scene format=3]\n```"));
+    }
+}
@@ -2,13 +2,62 @@
 //!
 //! This module re-exports all resolver functions from domain-specific modules.
 //! The implementation has been decomposed into:
-//! - project_resolver: Project information, file collection, validation
-//! - scene_resolver: Scene parsing, conversion, creation
+//! - project_resolver: Project information, file collection, validation, project.godot settings
+//! - schema_version_resolver: Schema version, changelog and deprecations
+//! - godot_binary_resolver: Godot executable discovery, session override and version pinning
+//! - node_bounds_resolver: Node bounds measured by headless Godot, cached, with overlaps
+//! - thumbnail_resolver: Scene PNG thumbnails rendered by Godot
+//! - input_map_resolver: InputMap actions in project.godot (list, add, remove)
+//! - export_preset_resolver: Export presets in export_presets.cfg (list, create from templates, update)
+//! - naming_resolver: Validation of autoload, class_name, signal handler and group name collisions
+//! - manifest_resolver: Compact project manifest and incremental deltas
+//! - layout_resolver: Folder layout suggestions and batched reorganization
+//! - cleanup_resolver: Stale .import/.uid sidecar cleanup
+//! - activity_resolver: Activity queue polled by the editor dock
+//! - contracts_resolver: Scene invariants declared in contracts.toml
+//! - context_pack_resolver: gatherContext output packed under a token budget
+//! - tags_resolver: File owners and area tags (owners.toml, scene root metadata) for query filters
+//! - decision_resolver: Design decisions recorded on files and folders for later sessions
+//! - property_search_resolver: Node search by property value across scenes
+//! - performance_resolver: 3D performance audit (visibility ranges, shadows, particles) with a fix plan
+//! - metrics_resolver: Runtime performance monitors (FPS, frame time, draw calls, memory) from the editor
+//! - health_resolver: Scored project health report and checkpoints
+//! - example_project_resolver: Playable example projects generated through the mutation pipeline
+//! - checkpoint_resolver: Project snapshots and read-only `projectAt` queries
+//! - branch_resolver: Read-only `branchView` of another git branch, materialized in memory
+//! - backup_resolver: Backups taken before file writes, and restoring them
+//! - report_resolver: Session changelogs from the audit log
+//! - transaction_resolver: File transactions staging writes until commit
+//! - diagnostics_resolver: Server diagnostics and write-ahead journal recoveries
+//! - scene_resolver: Scene and .tres resource parsing, conversion, creation, structural repair,
+//!   computed properties, signal connections, reparenting
+//! - scene_template_resolver: User-defined scene templates with parameter expressions, and their linting
+//! - scene_drift_resolver: Unsaved editor changes, compared with the scene file on disk
+//! - live_instances_resolver: Read-only queries fanned out to several editor instances
+//! - scene_patch_resolver: Declarative scene patches applied atomically
+//! - screenshot_resolver: Annotated editor viewport screenshots and plain viewport captures
 //! - script_resolver: Script parsing, conversion, creation
-//! - mutation_resolver: Mutation validation, preview, application
+//! - custom_resource_resolver: .tres instances of custom Resource scripts, checked against their exports
+//! - resource_table_resolver: custom resources of a directory round-tripped through a CSV/JSON table
+//! - wiring_resolver: Exported node reference audit and wiring
+//! - signal_flow_resolver: Signal connection and event bus diagrams
+//! - animation_resolver: Bulk animation track path remapping (file-based and live)
+//! - audio_resolver: Audio assets and their .import settings (loops, presets)
+//! - feature_pack_resolver: Feature pack installs from local folders or git
+//! - asset_resolver: Remote asset downloads (allowlisted hosts, size caps, license files)
+//! - watch_resolver: File and scene change streams for subscriptions
+//! - populate_resolver: Scene population from CSV/JSON data tables
+//! - collision_resolver: Collision shapes fitted to meshes and sprites
+//! - physics_layer_resolver: Collision layer/mask matrix across scenes and layer collision edits
+//! - mutation_resolver: Mutation validation, preview, application, node renames, duplication and subtree copy/paste
 //! - node_type_resolver: Node type information from static database
-//! - test_resolver: GdUnit4 test execution
+//! - class_icon_resolver: Editor icons and categories of node classes for GUI clients
+//! - test_resolver: GdUnit4 and GUT test execution
+//! - coverage_resolver: GDScript line coverage of instrumented GUT runs
+//! - run_report_resolver: Game runs with a watchdog, and the exit status, output and stack traces of the last one
 //! - refactoring_resolver: Code understanding, refactoring operations
+//! - dead_code_resolver: GDScript functions and signals never called, emitted or connected
+//! - lint_resolver: gdlint/gdformat over scripts, with a built-in lint fallback, and Godot --check-only
 //! - codegen_resolver: Code generation (input handlers, state machines, tests)
 //! - shader_resolver: Shader validation
 
@@ -19,33 +68,198 @@
 
 // Project operations
 pub use super::project_resolver::{
-    collect_project_files, count_resources, parse_project_name, resolve_add_input_action,
-    resolve_project, resolve_set_project_setting, to_res_path, validate_project,
+    collect_project_files, count_resources, parse_project_name, resolve_project,
+    resolve_project_settings, resolve_resource_preview, resolve_set_project_setting, to_res_path,
+    validate_project,
 };
 
+// Schema versioning
+pub use super::schema_version_resolver::{resolve_changelog, resolve_schema_version};
+
+// Godot executable
+pub use super::godot_binary_resolver::{resolve_godot_binary, resolve_set_godot_path};
+
+// Node bounds measured by Godot
+pub use super::node_bounds_resolver::resolve_node_bounds;
+pub use super::thumbnail_resolver::{resolve_generate_thumbnails, resolve_scene_thumbnail};
+
+// InputMap actions
+pub use super::input_map_resolver::{
+    resolve_add_input_action, resolve_list_input_actions, resolve_remove_input_action,
+};
+
+// Export presets
+pub use super::export_preset_resolver::{
+    resolve_create_export_preset, resolve_export_presets, resolve_update_export_preset,
+};
+
+// Project manifest
+pub use super::manifest_resolver::{resolve_manifest_delta, resolve_project_manifest};
+
+// Project layout
+pub use super::layout_resolver::{resolve_apply_project_layout, resolve_suggest_project_layout};
+
+// Import artifact cleanup
+pub use super::cleanup_resolver::{resolve_clean_import_artifacts, TRASH_DIR};
+
+// Editor activity
+pub use super::activity_resolver::{resolve_editor_activity, resolve_notify_editor};
+
+// Scene contracts
+pub use super::contracts_resolver::{resolve_check_contracts, CONTRACTS_PATH};
+
+// Property search
+pub use super::property_search_resolver::resolve_find_property_values;
+
+// Performance audit
+pub use super::performance_resolver::resolve_performance_audit;
+
+// Runtime performance metrics
+pub use super::metrics_resolver::resolve_performance_metrics;
+
+// Context packing
+pub use super::context_pack_resolver::resolve_pack_context;
+
+// File owners and tags
+pub use super::tags_resolver::{filter_project, resolve_file_tags, OWNERS_PATH};
+
+// Decision log
+pub use super::decision_resolver::{resolve_decisions, resolve_record_decision, DECISIONS_PATH};
+
+// Project health
+pub use super::health_resolver::{record_test_run, resolve_project_health};
+
+// Example projects
+pub use super::example_project_resolver::resolve_generate_example_project;
+
+// Project checkpoints
+pub use super::checkpoint_resolver::{
+    resolve_checkpoints, resolve_create_checkpoint, resolve_project_at, CHECKPOINT_DIR,
+};
+
+// Git branch views
+pub use super::branch_resolver::resolve_branch_view;
+
+// File backups
+pub use super::backup_resolver::{resolve_list_backups, resolve_restore_backup};
+
+// File transactions
+pub use super::transaction_resolver::{
+    resolve_begin_transaction, resolve_commit_transaction, resolve_rollback_transaction,
+};
+
+// Server diagnostics
+pub use super::diagnostics_resolver::resolve_server_diagnostics;
+
+// Session reports
+pub use super::report_resolver::resolve_session_report;
+
 // Scene operations
-pub use super::scene_resolver::{convert_godot_scene_to_gql, create_scene, resolve_scene};
+pub use super::scene_resolver::{
+    compute_property, connect_signal_in_scene, convert_godot_resource_to_gql,
+    convert_godot_scene_to_gql, create_scene, repair_scene, reparent_node_in_scene,
+    resolve_node_metadata, resolve_resource, resolve_scene, set_node_metadata,
+};
+
+// Unsaved editor changes
+pub use super::scene_drift_resolver::resolve_live_scene_drift;
+
+// Multiple editor instances
+pub use super::live_instances_resolver::{
+    resolve_editor_instances, resolve_live_instances_query, CURRENT_INSTANCE,
+};
+
+// Scene templates
+pub use super::scene_template_resolver::{
+    resolve_create_scene_from_template, resolve_lint_scene_templates, resolve_scene_templates,
+    TEMPLATES_DIR,
+};
+
+// Scene patches
+pub use super::scene_patch_resolver::{apply_scene_patch, resolve_scene_patch_schema};
+
+// Editor screenshots
+pub use super::screenshot_resolver::{
+    resolve_capture_viewport, resolve_editor_screenshot, SCREENSHOT_DIR,
+};
 
 // Script operations
 pub use super::script_resolver::{
     convert_gdscript_to_gql, create_script, parse_signal_definition, resolve_script,
 };
 
+// Custom resources
+pub use super::custom_resource_resolver::resolve_create_custom_resource;
+pub use super::resource_table_resolver::{
+    resolve_export_resources_to_table, resolve_import_resources_from_table,
+};
+
+// Exported node reference wiring
+pub use super::wiring_resolver::resolve_wire_exported_references;
+
+// Signal flow diagrams
+pub use super::signal_flow_resolver::resolve_signal_flow_graph;
+
+// Animation track remapping
+pub use super::animation_resolver::resolve_remap_animation_tracks;
+
+// Audio import settings
+pub use super::audio_resolver::{
+    resolve_apply_audio_import_preset, resolve_audio_assets, resolve_set_audio_loop,
+};
+
+// Feature packs
+pub use super::feature_pack_resolver::{
+    resolve_feature_packs, resolve_install_feature_pack, INSTALLED_PACKS_PATH,
+};
+
+// Remote assets
+pub use super::asset_resolver::resolve_fetch_asset;
+
+// File watch subscriptions
+pub use super::watch_resolver::{resolve_file_changed, resolve_scene_changed};
+
+// Data-driven scene population
+pub use super::populate_resolver::populate_from_data;
+
+// Collision shape generation
+pub use super::collision_resolver::generate_collision_shape;
+
+// Physics layer matrix
+pub use super::physics_layer_resolver::{resolve_collision_matrix, resolve_set_layer_collision};
+
 // Mutation operations
-pub use super::mutation_resolver::{apply_mutation, preview_mutation, validate_mutation};
+pub use super::mutation_resolver::{
+    apply_mutation, copy_subtree, duplicate_node_in_scene, paste_subtree, preview_mutation,
+    rename_node, validate_mutation,
+};
 
 // Node type info
 pub use super::node_type_resolver::resolve_node_type_info;
 
+// Node class icons
+pub use super::class_icon_resolver::resolve_node_class_icons;
+
 // Test execution
-pub use super::test_resolver::{parse_test_output, resolve_run_tests};
+pub use super::coverage_resolver::resolve_run_test_coverage;
+pub use super::test_resolver::{
+    parse_gut_output, parse_junit_xml, parse_test_output, resolve_run_tests,
+};
+
+// Game run reports
+pub use super::run_report_resolver::{resolve_last_run_report, resolve_run_project};
 
 // Refactoring operations
+pub use super::lint_resolver::{resolve_check_script, resolve_gdformat, resolve_gdlint};
+
 pub use super::refactoring_resolver::{
     resolve_autoloads, resolve_class_hierarchy, resolve_extract_function, resolve_find_references,
-    resolve_rename_symbol,
+    resolve_move_file, resolve_rename_file, resolve_rename_symbol,
 };
 
+// Dead code detection
+pub use super::dead_code_resolver::resolve_dead_code;
+
 // Code generation
 pub use super::codegen_resolver::{
     resolve_generate_input_handler, resolve_generate_state_machine, resolve_generate_test_script,
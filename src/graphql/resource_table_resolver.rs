@@ -0,0 +1,622 @@
+//! Resource Table Resolver
+//!
+//! Round-trips the custom Resource .tres files of a directory (items,
+//! enemies, ...) through one CSV or JSON table: one row per file, one column
+//! per exported field. Importing writes the rows back, creating files that do
+//! not exist yet; each row is validated on its own so one bad row does not
+//! hold back the others.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde_json::{Map, Value};
+
+use crate::godot::tres::GodotResource;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::custom_resource_resolver::{enum_members, LiteralWriter, ResourceClass};
+use super::populate_resolver::split_csv_line;
+use super::project_resolver::to_res_path;
+use super::types::*;
+
+const PATH_COLUMN: &str = "path";
+const SCRIPT_COLUMN: &str = "script";
+
+/// Resolve exportResourcesToTable query
+pub fn resolve_export_resources_to_table(
+    ctx: &GqlContext,
+    directory: &str,
+    format: ResourceTableFormat,
+) -> ResourceTable {
+    let prefix = directory_prefix(directory);
+    let mut paths: Vec<String> = ctx
+        .index
+        .files(&ctx.project_path)
+        .iter()
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("tres"))
+        .map(|path| to_res_path(&ctx.project_path, path))
+        .filter(|path| path.starts_with(&prefix))
+        .collect();
+    paths.sort();
+
+    let mut classes: HashMap<String, Option<ResourceClass>> = HashMap::new();
+    let mut columns = vec![PATH_COLUMN.to_string(), SCRIPT_COLUMN.to_string()];
+    let mut rows: Vec<Map<String, Value>> = vec![];
+    let mut skipped = vec![];
+    for path in paths {
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &path);
+        let resource = ctx
+            .fs
+            .read_to_string(&fs_path)
+            .ok()
+            .and_then(|content| GodotResource::parse(&content).ok());
+        let script = resource.as_ref().and_then(script_path);
+        let (Some(resource), Some(script)) = (resource, script) else {
+            skipped.push(path);
+            continue;
+        };
+        let class = classes
+            .entry(script.clone())
+            .or_insert_with(|| ResourceClass::load(ctx, &script).ok());
+        let Some(class) = class else {
+            skipped.push(path);
+            continue;
+        };
+
+        let mut row = Map::new();
+        row.insert(
+            PATH_COLUMN.to_string(),
+            Value::String(path[prefix.len()..].to_string()),
+        );
+        row.insert(SCRIPT_COLUMN.to_string(), Value::String(script.clone()));
+        for field in class.fields() {
+            if !columns.contains(&field.name) {
+                columns.push(field.name.clone());
+            }
+            let Some(literal) = resource.properties.get(&field.name) else {
+                continue;
+            };
+            let mut value = LiteralReader::new(literal, &resource).read();
+            // Enums read back as member names
+            if let (Some(decl), Some(n)) = (class.find_enum(&field.field_type), value.as_i64()) {
+                if let Some((name, _)) = enum_members(decl).into_iter().find(|(_, v)| *v == n) {
+                    value = Value::String(name.to_string());
+                }
+            }
+            row.insert(field.name, value);
+        }
+        rows.push(row);
+    }
+
+    let content = match format {
+        ResourceTableFormat::Json => serde_json::to_string_pretty(&Value::Array(
+            rows.iter().cloned().map(Value::Object).collect(),
+        ))
+        .unwrap_or_default(),
+        ResourceTableFormat::Csv => {
+            let mut lines = vec![columns
+                .iter()
+                .map(|c| csv_field(c))
+                .collect::<Vec<_>>()
+                .join(",")];
+            for row in &rows {
+                let cells: Vec<String> = columns
+                    .iter()
+                    .map(|column| match row.get(column) {
+                        None => String::new(),
+                        Some(Value::String(s)) => csv_field(s),
+                        Some(value) => csv_field(&value.to_string()),
+                    })
+                    .collect();
+                lines.push(cells.join(","));
+            }
+            lines.join("\n") + "\n"
+        }
+    };
+
+    ResourceTable {
+        success: true,
+        format,
+        columns,
+        row_count: rows.len() as i32,
+        content,
+        skipped,
+        error: None,
+    }
+}
+
+/// Resolve importResourcesFromTable mutation
+pub fn resolve_import_resources_from_table(
+    ctx: &GqlContext,
+    file: &str,
+    target_directory: &str,
+) -> ResourceTableImport {
+    let mut result = ResourceTableImport {
+        success: false,
+        created: vec![],
+        updated: vec![],
+        unchanged: vec![],
+        row_errors: vec![],
+        error: None,
+    };
+    let rows = match load_table(ctx, file) {
+        Ok(rows) => rows,
+        Err(error) => {
+            result.error = Some(*error);
+            return result;
+        }
+    };
+
+    let prefix = directory_prefix(target_directory);
+    let mut classes: HashMap<String, Result<ResourceClass, String>> = HashMap::new();
+    let mut seen = BTreeSet::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        // Rows count from 1, after the header
+        let row_number = index as i32 + 1;
+        let mut errors = RowErrors::new(row_number);
+        let path = match row.get(PATH_COLUMN) {
+            Some(Value::String(path)) if !path.trim().is_empty() => {
+                let path = path.trim();
+                if path.starts_with("res://") {
+                    path.to_string()
+                } else {
+                    format!("{}{}", prefix, path)
+                }
+            }
+            _ => {
+                errors.push(Some(PATH_COLUMN), "Missing path".to_string());
+                result.row_errors.extend(errors.errors);
+                continue;
+            }
+        };
+        errors.path = Some(path.clone());
+        if !path.ends_with(".tres") {
+            errors.push(Some(PATH_COLUMN), format!("Not a .tres path: {}", path));
+        } else if !seen.insert(path.clone()) {
+            errors.push(
+                Some(PATH_COLUMN),
+                "Path appears in an earlier row".to_string(),
+            );
+        }
+
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &path);
+        let existing = if ctx.fs.exists(&fs_path) {
+            match ctx
+                .fs
+                .read_to_string(&fs_path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| GodotResource::parse(&content))
+            {
+                Ok(resource) => Some(resource),
+                Err(e) => {
+                    errors.push(None, format!("Failed to read {}: {}", path, e));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let row_script = row
+            .get(SCRIPT_COLUMN)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+        let file_script = existing.as_ref().and_then(script_path);
+        let script = match (&row_script, &file_script) {
+            (Some(row_script), Some(file_script)) if row_script != file_script => {
+                errors.push(
+                    Some(SCRIPT_COLUMN),
+                    format!("{} uses {}, not {}", path, file_script, row_script),
+                );
+                None
+            }
+            (_, Some(script)) | (Some(script), None) => Some(script.clone()),
+            (None, None) => {
+                errors.push(
+                    Some(SCRIPT_COLUMN),
+                    "New resources need a script".to_string(),
+                );
+                None
+            }
+        };
+        if !errors.errors.is_empty() {
+            result.row_errors.extend(errors.errors);
+            continue;
+        }
+        let script = script.unwrap_or_default();
+        let class = classes.entry(script.clone()).or_insert_with(|| {
+            ResourceClass::load(ctx, &script).map_err(|error| error.message.clone())
+        });
+        let class = match class {
+            Ok(class) => class,
+            Err(message) => {
+                errors.push(Some(SCRIPT_COLUMN), message.clone());
+                result.row_errors.extend(errors.errors);
+                continue;
+            }
+        };
+
+        let is_new = existing.is_none();
+        let mut resource = existing.unwrap_or_else(|| {
+            let mut resource = GodotResource::new("Resource");
+            resource.script_class = class.class_name.clone();
+            resource.add_ext_resource("1", "Script", &script);
+            resource.set_property("script", "ExtResource(\"1\")");
+            resource
+        });
+        let fields = class.fields();
+        let mut writer = LiteralWriter {
+            ctx,
+            class,
+            resource: &mut resource,
+        };
+        let mut changed = is_new;
+        for (column, value) in &row {
+            if column == PATH_COLUMN || column == SCRIPT_COLUMN {
+                continue;
+            }
+            let Some(field) = fields.iter().find(|f| &f.name == column) else {
+                errors.push(Some(column), format!("Unknown field of {}", script));
+                continue;
+            };
+            // Empty cells, and literals exported as text, leave the field as it is
+            let current = writer.resource.properties.get(column).map(|l| l.trim());
+            let value = match value {
+                Value::String(cell) if cell.trim().is_empty() || current == Some(cell.trim()) => {
+                    continue
+                }
+                Value::String(cell) => cell_value(cell, &field.field_type),
+                value => value.clone(),
+            };
+            match writer.literal(&value, &field.field_type) {
+                Ok(literal) => {
+                    if writer.resource.properties.get(column) != Some(&literal) {
+                        writer.resource.set_property(column, &literal);
+                        changed = true;
+                    }
+                }
+                Err(message) => errors.push(Some(column), message),
+            }
+        }
+        if !errors.errors.is_empty() {
+            result.row_errors.extend(errors.errors);
+            continue;
+        }
+        if !changed {
+            result.unchanged.push(path);
+            continue;
+        }
+
+        let written = fs_path
+            .parent()
+            .map_or(Ok(()), |parent| ctx.fs.create_dir_all(parent))
+            .and_then(|_| {
+                audit::write_file(
+                    ctx,
+                    "importResourcesFromTable",
+                    &fs_path,
+                    resource.to_tres(),
+                )
+            });
+        match written {
+            Ok(()) => {
+                telemetry::record_files_written(1);
+                if is_new {
+                    result.created.push(path);
+                } else {
+                    result.updated.push(path);
+                }
+            }
+            Err(e) => {
+                errors.push(None, format!("Failed to write resource: {}", e));
+                result.row_errors.extend(errors.errors);
+            }
+        }
+    }
+
+    result.success = result.row_errors.is_empty();
+    result
+}
+
+/// Errors of one table row
+struct RowErrors {
+    row: i32,
+    path: Option<String>,
+    errors: Vec<ResourceTableRowError>,
+}
+
+impl RowErrors {
+    fn new(row: i32) -> Self {
+        Self {
+            row,
+            path: None,
+            errors: vec![],
+        }
+    }
+
+    fn push(&mut self, field: Option<&str>, message: String) {
+        self.errors.push(ResourceTableRowError {
+            row: self.row,
+            path: self.path.clone(),
+            field: field.map(str::to_string),
+            message,
+        });
+    }
+}
+
+/// `res://dir/` for `dir`, `res://dir` or `res://dir/`
+fn directory_prefix(directory: &str) -> String {
+    let directory = path_utils::strip_res_prefix(directory).trim_matches('/');
+    if directory.is_empty() {
+        "res://".to_string()
+    } else {
+        format!("res://{}/", directory)
+    }
+}
+
+/// Path of the script set on a resource's `script` property
+fn script_path(resource: &GodotResource) -> Option<String> {
+    let id = resource
+        .properties
+        .get("script")?
+        .trim()
+        .strip_prefix("ExtResource(\"")?
+        .strip_suffix("\")")?;
+    resource
+        .ext_resources
+        .iter()
+        .find(|r| r.id == id && r.path.ends_with(".gd"))
+        .map(|r| r.path.clone())
+}
+
+/// Rows of a .csv (header line first) or .json (array of objects) table
+fn load_table(
+    ctx: &GqlContext,
+    file: &str,
+) -> Result<Vec<Map<String, Value>>, Box<GqlStructuredError>> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, file);
+    let content = ctx.fs.read_to_string(&path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FILE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read {}: {}", file, e),
+        ))
+    })?;
+    let parse_error = |message: String| {
+        Box::new(
+            GqlStructuredError::new("DATA_PARSE_ERROR", GqlErrorCategory::Validation, message)
+                .with_suggestion(
+                    "exportResourcesToTable の出力と同じ形式 (CSV はヘッダー行付き、JSON はオブジェクトの配列) で指定してください",
+                ),
+        )
+    };
+
+    if file.to_lowercase().ends_with(".json") {
+        let rows: Vec<Value> = serde_json::from_str(&content)
+            .map_err(|e| parse_error(format!("Invalid JSON table: {}", e)))?;
+        rows.into_iter()
+            .map(|row| match row {
+                Value::Object(map) => Ok(map),
+                _ => Err(parse_error("Every JSON row must be an object".to_string())),
+            })
+            .collect()
+    } else if file.to_lowercase().ends_with(".csv") {
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+        let Some(header) = lines.next() else {
+            return Ok(vec![]);
+        };
+        let columns: Vec<String> = split_csv_line(header)
+            .into_iter()
+            .map(|c| c.trim().to_string())
+            .collect();
+        Ok(lines
+            .map(|line| {
+                columns
+                    .iter()
+                    .cloned()
+                    .zip(split_csv_line(line).into_iter().map(Value::String))
+                    .collect()
+            })
+            .collect())
+    } else {
+        Err(parse_error("Table file must be .csv or .json".to_string()))
+    }
+}
+
+/// JSON value of a CSV cell: text for string fields, otherwise JSON when it
+/// parses (numbers, bools, null, arrays) and the text itself when not (enum
+/// names, paths, `Vector2(...)`)
+fn cell_value(cell: &str, field_type: &str) -> Value {
+    match field_type {
+        "String" | "StringName" | "NodePath" => Value::String(cell.to_string()),
+        _ => serde_json::from_str(cell.trim()).unwrap_or_else(|_| Value::String(cell.to_string())),
+    }
+}
+
+/// Quote a CSV field when it holds a comma, quote or line break
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Reads Godot literals as JSON: strings, numbers, arrays and dictionaries as
+/// themselves, ExtResources as their paths, and other constructors such as
+/// `Vector2(1, 2)` as their source text
+struct LiteralReader<'a> {
+    source: &'a str,
+    pos: usize,
+    resource: &'a GodotResource,
+}
+
+impl<'a> LiteralReader<'a> {
+    fn new(source: &'a str, resource: &'a GodotResource) -> Self {
+        Self {
+            source: source.trim(),
+            pos: 0,
+            resource,
+        }
+    }
+
+    /// The whole literal, or its text when it cannot be read
+    fn read(mut self) -> Value {
+        match self.value() {
+            Some(value) if self.rest().trim().is_empty() => value,
+            _ => Value::String(self.source.to_string()),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.source.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        match self.peek()? {
+            '"' => self.string().map(Value::String),
+            '&' => {
+                self.pos += 1;
+                self.string().map(Value::String)
+            }
+            '[' => {
+                self.pos += 1;
+                self.list(']').map(Value::Array)
+            }
+            '{' => {
+                self.pos += 1;
+                self.dictionary()
+            }
+            c if c == '-' || c.is_ascii_digit() => self.number(),
+            c if c.is_alphabetic() || c == '_' => self.constructor(),
+            _ => None,
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Some(out);
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    c => out.push(c),
+                },
+                c => out.push(c),
+            }
+        }
+        None
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let rest = self.rest();
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+')))
+            .map_or(rest.len(), |(i, _)| i);
+        let text = &rest[..end];
+        self.pos += end;
+        text.parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| text.parse::<f64>().ok().map(Value::from))
+    }
+
+    /// Items up to `close`, separated by commas
+    fn list(&mut self, close: char) -> Option<Vec<Value>> {
+        let mut items = vec![];
+        while !self.eat(close) {
+            if !items.is_empty() && !self.eat(',') {
+                return None;
+            }
+            // Trailing comma
+            if self.eat(close) {
+                break;
+            }
+            items.push(self.value()?);
+        }
+        Some(items)
+    }
+
+    fn dictionary(&mut self) -> Option<Value> {
+        let mut map = Map::new();
+        while !self.eat('}') {
+            if !map.is_empty() && !self.eat(',') {
+                return None;
+            }
+            let key = match self.value()? {
+                Value::String(key) => key,
+                key => key.to_string(),
+            };
+            if !self.eat(':') {
+                return None;
+            }
+            map.insert(key, self.value()?);
+        }
+        Some(Value::Object(map))
+    }
+
+    fn constructor(&mut self) -> Option<Value> {
+        let start = self.pos;
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        self.pos += end;
+        match name {
+            "true" => return Some(Value::Bool(true)),
+            "false" => return Some(Value::Bool(false)),
+            "null" => return Some(Value::Null),
+            _ => {}
+        }
+        // Element type of `Array[T](...)`
+        if self.eat('[') {
+            self.list(']')?;
+        }
+        if !self.eat('(') {
+            return Some(Value::String(self.source[start..self.pos].to_string()));
+        }
+        let args = self.list(')')?;
+        let value = match name {
+            "ExtResource" => {
+                let id = args.first()?.as_str()?;
+                self.resource
+                    .ext_resources
+                    .iter()
+                    .find(|r| r.id == id)
+                    .map(|r| Value::String(r.path.clone()))?
+            }
+            "Array" => args.into_iter().next()?,
+            "NodePath" | "StringName" => args.into_iter().next()?,
+            _ if name.starts_with("Packed") && name.ends_with("Array") => Value::Array(args),
+            _ => Value::String(self.source[start..self.pos].to_string()),
+        };
+        Some(value)
+    }
+}
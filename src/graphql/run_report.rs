@@ -0,0 +1,702 @@
+//! Game Run Reports
+//!
+//! `run_project` hands the Godot process it starts to [`capture`], which
+//! streams stdout and stderr into `.godot_mcp_output` (stderr lines prefixed
+//! with `ERROR: `, as `get_debug_output` shows them) and records the run in
+//! `.godot_mcp_run.json`: pid, scene, start and end time, exit code and the
+//! signal that killed it. [`read`] turns both back into a [`RunReport`] with
+//! the GDScript stack traces and the native crash backtrace parsed out.
+//!
+//! [`start`] spawns the game for `run_project` and the `runProject`
+//! mutation. [`RunLimits`] put a watchdog on it: the game is killed once its
+//! timeout passes, and with `auto_stop` also when the process that started it
+//! calls [`stop_auto_stopped`] on its way out, so headless runs don't outlive
+//! the server or CLI that launched them.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::time_utils::now_millis;
+
+/// Combined output of the last run
+pub const OUTPUT_FILE: &str = ".godot_mcp_output";
+
+/// Pid of the running game, removed by `stop_project`
+pub const PID_FILE: &str = ".godot_mcp_pid";
+
+/// Record of the last run
+pub const RUN_FILE: &str = ".godot_mcp_run.json";
+
+/// Prefix of stderr lines in the combined output
+const STDERR_PREFIX: &str = "ERROR: ";
+
+/// Lines Godot's crash handler prints (Linux/macOS, Windows)
+const CRASH_MARKERS: &[&str] = &["Program crashed with signal", "CrashHandlerException"];
+
+/// How often the watchdog checks a run
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Watchdog threads of the runs this process started, with their `auto_stop`
+static RUNS: Mutex<Vec<(bool, JoinHandle<()>)>> = Mutex::new(Vec::new());
+
+/// Set by [`stop_auto_stopped`]
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Limits the watchdog enforces on a run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    /// Kill the game once it has run this long
+    pub timeout: Option<Duration>,
+    /// Stop the game when the process that started it exits
+    pub auto_stop: bool,
+}
+
+/// A game started by [`start`]
+#[derive(Debug, Clone, Copy)]
+pub struct StartedRun {
+    pub pid: u32,
+    /// A game started earlier was still running and was stopped first
+    pub restarted: bool,
+}
+
+/// CPU and memory use of a running process
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessUsage {
+    /// Not reported on Windows
+    pub cpu_percent: Option<f64>,
+    /// Resident set size (working set on Windows)
+    pub memory_bytes: u64,
+}
+
+/// The last run as recorded by [`capture`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunRecord {
+    pub pid: u32,
+    pub scene: Option<String>,
+    /// Unix epoch milliseconds
+    pub started_at: u64,
+    /// None while the game runs
+    pub finished_at: Option<u64>,
+    pub exit_code: Option<i32>,
+    /// Signal that terminated the process (Unix)
+    pub signal: Option<i32>,
+    /// Ended by `stop_project` rather than on its own
+    #[serde(default)]
+    pub stopped: bool,
+    /// Watchdog timeout the run was started with
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+    /// Killed by the watchdog when the timeout passed
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Stopped when the server or CLI that started it exits
+    #[serde(default)]
+    pub auto_stop: bool,
+}
+
+/// The last run with its output analyzed
+#[derive(Debug, Clone, Default)]
+pub struct RunReport {
+    pub record: RunRecord,
+    pub running: bool,
+    /// Why the run counts as a crash (None: it didn't crash)
+    pub crash: Option<String>,
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+    pub stack_traces: Vec<StackTrace>,
+    /// Engine backtrace printed by the crash handler
+    pub native_backtrace: Vec<String>,
+}
+
+/// Script error or warning with the GDScript calls that led to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackTrace {
+    pub message: String,
+    pub is_error: bool,
+    /// Most recent call first
+    pub frames: Vec<StackFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    pub function: String,
+    pub file: String,
+    pub line: Option<usize>,
+}
+
+/// Start the game (`scene`, or the main scene) with `--debug`, stopping
+/// the one started before if it still runs, and capture it under `limits`
+pub fn start(
+    binary: &Path,
+    project_root: &Path,
+    scene: Option<String>,
+    limits: RunLimits,
+) -> Result<StartedRun, String> {
+    let pid_file = project_root.join(PID_FILE);
+    let mut restarted = false;
+    if let Some(pid) = fs::read_to_string(&pid_file)
+        .ok()
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+    {
+        if is_running(pid) && kill(pid).is_ok() {
+            restarted = true;
+            // Brief wait for process to terminate
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        fs::remove_file(&pid_file).ok();
+    }
+
+    let mut cmd = Command::new(binary);
+    cmd.arg("--path").arg(project_root);
+    if let Some(ref scene) = scene {
+        cmd.arg(scene.strip_prefix("res://").unwrap_or(scene));
+    }
+    let child = cmd
+        .arg("--debug")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start Godot: {}", e))?;
+    let pid = child.id();
+    if let Err(e) = fs::write(&pid_file, pid.to_string()) {
+        let mut child = child;
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(format!("Failed to save PID: {}", e));
+    }
+
+    capture(project_root, child, scene, limits);
+    Ok(StartedRun { pid, restarted })
+}
+
+/// Record a started game and collect its output until it exits, killing it
+/// when `limits` say so
+pub fn capture(project_root: &Path, mut child: Child, scene: Option<String>, limits: RunLimits) {
+    let record = RunRecord {
+        pid: child.id(),
+        scene,
+        started_at: now_millis(),
+        timeout_seconds: limits.timeout.map(|timeout| timeout.as_secs()),
+        auto_stop: limits.auto_stop,
+        ..Default::default()
+    };
+    let output_file = project_root.join(OUTPUT_FILE);
+    let run_file = project_root.join(RUN_FILE);
+    let pid_file = project_root.join(PID_FILE);
+    fs::write(&output_file, "").ok();
+    write_record(&run_file, &record);
+
+    let output = Arc::new(Mutex::new(String::new()));
+    let readers = [
+        child
+            .stdout
+            .take()
+            .map(|stdout| stream(stdout, "", output.clone(), output_file.clone())),
+        child
+            .stderr
+            .take()
+            .map(|stderr| stream(stderr, STDERR_PREFIX, output.clone(), output_file.clone())),
+    ];
+
+    let deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+    let watchdog = std::thread::spawn(move || {
+        let mut timed_out = false;
+        let mut shut_down = false;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) => {}
+                Err(_) => break None,
+            }
+            timed_out = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+            shut_down = limits.auto_stop && SHUTTING_DOWN.load(Ordering::SeqCst);
+            if timed_out || shut_down {
+                let _ = child.kill();
+                break child.wait().ok();
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.join();
+        }
+        // stop_project removes the pid file before the process ends
+        let stopped = shut_down
+            || fs::read_to_string(&pid_file)
+                .map(|pid| pid.trim() != record.pid.to_string())
+                .unwrap_or(true);
+        let record = RunRecord {
+            finished_at: Some(now_millis()),
+            exit_code: status.and_then(|s| s.code()),
+            signal: status.and_then(exit_signal),
+            stopped,
+            timed_out,
+            ..record
+        };
+        write_record(&run_file, &record);
+        if !stopped || shut_down {
+            fs::remove_file(&pid_file).ok();
+        }
+    });
+    let mut runs = RUNS.lock().unwrap_or_else(|e| e.into_inner());
+    runs.retain(|(_, watchdog)| !watchdog.is_finished());
+    runs.push((limits.auto_stop, watchdog));
+}
+
+/// Block until every run this process started has ended
+pub fn wait_for_runs() {
+    let runs = std::mem::take(&mut *RUNS.lock().unwrap_or_else(|e| e.into_inner()));
+    for (_, watchdog) in runs {
+        let _ = watchdog.join();
+    }
+}
+
+/// Stop the runs started with `auto_stop`, waiting until they have ended;
+/// for the server and CLI to call before exiting
+pub fn stop_auto_stopped() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+    let runs = std::mem::take(&mut *RUNS.lock().unwrap_or_else(|e| e.into_inner()));
+    for (auto_stop, watchdog) in runs {
+        if auto_stop {
+            let _ = watchdog.join();
+        }
+    }
+}
+
+/// Whether a process with this pid exists
+pub fn is_running(pid: u32) -> bool {
+    #[cfg(windows)]
+    {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .output()
+            .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(&format!("\"{}\"", pid)))
+    }
+    #[cfg(not(windows))]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+}
+
+/// Forcefully end a process
+pub fn kill(pid: u32) -> Result<(), String> {
+    #[cfg(windows)]
+    let output = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .output();
+    #[cfg(not(windows))]
+    let output = Command::new("kill")
+        .args(["-KILL", &pid.to_string()])
+        .output();
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(String::from_utf8_lossy(&out.stderr).trim().to_string()),
+        Err(e) => Err(format!("Failed to kill process: {}", e)),
+    }
+}
+
+/// CPU and memory use of a process (None: not running)
+pub fn process_usage(pid: u32) -> Option<ProcessUsage> {
+    #[cfg(windows)]
+    {
+        let out = Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .output()
+            .ok()?;
+        parse_tasklist_usage(&String::from_utf8_lossy(&out.stdout), pid)
+    }
+    #[cfg(not(windows))]
+    {
+        let out = Command::new("ps")
+            .args(["-o", "%cpu=,rss=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        parse_ps_usage(&String::from_utf8_lossy(&out.stdout))
+    }
+}
+
+/// `ps -o %cpu=,rss=` output: percent of one core, resident KiB
+pub fn parse_ps_usage(output: &str) -> Option<ProcessUsage> {
+    let mut fields = output.split_whitespace();
+    let cpu_percent = fields.next()?.replace(',', ".").parse().ok()?;
+    let rss_kib: u64 = fields.next()?.parse().ok()?;
+    Some(ProcessUsage {
+        cpu_percent: Some(cpu_percent),
+        memory_bytes: rss_kib * 1024,
+    })
+}
+
+/// `tasklist /FO CSV /NH` row of `pid`: the last column is the working set,
+/// e.g. `"123,456 K"`
+pub fn parse_tasklist_usage(output: &str, pid: u32) -> Option<ProcessUsage> {
+    let row = output
+        .lines()
+        .find(|line| line.contains(&format!("\"{}\"", pid)))?;
+    let memory = row.rsplit("\",\"").next()?;
+    let kib: u64 = memory
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    Some(ProcessUsage {
+        cpu_percent: None,
+        memory_bytes: kib * 1024,
+    })
+}
+
+/// The last run of the project, with at most `tail` lines of each stream
+/// (None: the game was never run)
+pub fn read(project_root: &Path, tail: usize) -> Option<RunReport> {
+    let record: RunRecord = fs::read_to_string(project_root.join(RUN_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())?;
+    let output = fs::read_to_string(project_root.join(OUTPUT_FILE)).unwrap_or_default();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    for line in output.lines() {
+        match line.strip_prefix(STDERR_PREFIX) {
+            Some(line) => stderr.push(line),
+            None => stdout.push(line),
+        }
+    }
+    let all: Vec<&str> = output
+        .lines()
+        .map(|line| line.strip_prefix(STDERR_PREFIX).unwrap_or(line))
+        .collect();
+    let native_backtrace = parse_native_backtrace(&all);
+
+    // A process that outlived the server which captured it never gets an end
+    let running = record.finished_at.is_none()
+        && fs::read_to_string(project_root.join(PID_FILE))
+            .is_ok_and(|pid| pid.trim() == record.pid.to_string());
+    let crash = if running || record.stopped || record.timed_out {
+        None
+    } else {
+        crash_reason(&record, &all)
+    };
+
+    Some(RunReport {
+        running,
+        crash,
+        stack_traces: parse_stack_traces(&all),
+        native_backtrace,
+        stdout: tail_lines(&stdout, tail),
+        stderr: tail_lines(&stderr, tail),
+        record,
+    })
+}
+
+/// Script errors and warnings, each with the frames Godot printed for it:
+/// the `at:` line after `SCRIPT ERROR:`, or a `GDScript backtrace` block
+/// (which also turns a plain `ERROR:`, e.g. from `push_error`, into a trace)
+pub fn parse_stack_traces(lines: &[&str]) -> Vec<StackTrace> {
+    let mut traces: Vec<StackTrace> = Vec::new();
+    // Last plain `ERROR:`/`WARNING:`, a trace only if a backtrace follows
+    let mut plain: Option<(&str, bool)> = None;
+    let mut in_backtrace = false;
+    for line in lines.iter().map(|line| line.trim()) {
+        if let Some(message) = line.strip_prefix("SCRIPT ERROR:") {
+            traces.push(trace(message, true));
+            (plain, in_backtrace) = (None, false);
+        } else if let Some(message) = line.strip_prefix("SCRIPT WARNING:") {
+            traces.push(trace(message, false));
+            (plain, in_backtrace) = (None, false);
+        } else if let Some(message) = line.strip_prefix("ERROR:") {
+            (plain, in_backtrace) = (Some((message, true)), false);
+        } else if let Some(message) = line.strip_prefix("WARNING:") {
+            (plain, in_backtrace) = (Some((message, false)), false);
+        } else if line.starts_with("GDScript backtrace") {
+            in_backtrace = true;
+            match plain.take() {
+                Some((message, is_error)) => traces.push(trace(message, is_error)),
+                // Replaces the `at:` frame of the script error it follows
+                None => {
+                    if let Some(last) = traces.last_mut() {
+                        last.frames.clear();
+                    }
+                }
+            }
+        } else if let Some(at) = line.strip_prefix("at:") {
+            let last = traces
+                .last_mut()
+                .filter(|t| plain.is_none() && t.frames.is_empty());
+            if let (Some(last), Some(frame)) = (last, parse_frame(at)) {
+                last.frames.push(frame);
+            }
+        } else if in_backtrace && line.starts_with('[') {
+            let frame = line
+                .split_once(']')
+                .and_then(|(_, frame)| parse_frame(frame));
+            match (frame, traces.last_mut()) {
+                (Some(frame), Some(last)) => last.frames.push(frame),
+                _ => in_backtrace = false,
+            }
+        } else if !line.is_empty() {
+            in_backtrace = false;
+        }
+    }
+    traces
+}
+
+/// Lines between the crash handler's `Dumping the backtrace` and
+/// `-- END OF BACKTRACE --`
+pub fn parse_native_backtrace(lines: &[&str]) -> Vec<String> {
+    let Some(start) = lines
+        .iter()
+        .rposition(|line| line.contains("Dumping the backtrace"))
+    else {
+        return Vec::new();
+    };
+    lines[start + 1..]
+        .iter()
+        .map(|line| line.trim())
+        .take_while(|line| !line.starts_with("-- END OF BACKTRACE --"))
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Why a finished run counts as a crash: the crash handler ran, a signal
+/// killed it, or Windows reported an exception status
+fn crash_reason(record: &RunRecord, lines: &[&str]) -> Option<String> {
+    if let Some(line) = lines
+        .iter()
+        .find(|line| CRASH_MARKERS.iter().any(|marker| line.contains(marker)))
+    {
+        return Some(line.trim().to_string());
+    }
+    if let Some(signal) = record.signal {
+        return Some(format!("Terminated by signal {}", signal));
+    }
+    match record.exit_code {
+        // NTSTATUS error codes (e.g. 0xC0000005, access violation)
+        Some(code) if code as u32 >= 0xC000_0000 => {
+            Some(format!("Exited with exception code 0x{:08X}", code as u32))
+        }
+        _ => None,
+    }
+}
+
+/// `func (res://path.gd:12)`
+fn parse_frame(text: &str) -> Option<StackFrame> {
+    let text = text.trim();
+    let open = text.rfind("(res://")?;
+    let location = text[open + 1..].trim_end_matches(['.', ')']);
+    let (file, line) = match location.rsplit_once(':') {
+        Some((file, line)) => (file, line.parse().ok()),
+        None => (location, None),
+    };
+    Some(StackFrame {
+        function: text[..open].trim().to_string(),
+        file: file.to_string(),
+        line,
+    })
+}
+
+fn trace(message: &str, is_error: bool) -> StackTrace {
+    StackTrace {
+        message: message.trim().to_string(),
+        is_error,
+        frames: Vec::new(),
+    }
+}
+
+fn tail_lines(lines: &[&str], tail: usize) -> Vec<String> {
+    lines[lines.len().saturating_sub(tail)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Append every line of `reader` to the combined output file
+fn stream(
+    reader: impl Read + Send + 'static,
+    prefix: &'static str,
+    output: Arc<Mutex<String>>,
+    output_file: PathBuf,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            let mut output = output.lock().unwrap_or_else(|e| e.into_inner());
+            output.push_str(prefix);
+            output.push_str(&line);
+            output.push('\n');
+            fs::write(&output_file, output.as_bytes()).ok();
+        }
+    })
+}
+
+fn write_record(path: &Path, record: &RunRecord) {
+    if let Ok(json) = serde_json::to_string_pretty(record) {
+        fs::write(path, json).ok();
+    }
+}
+
+#[cfg(unix)]
+fn exit_signal(status: std::process::ExitStatus) -> Option<i32> {
+    std::os::unix::process::ExitStatusExt::signal(&status)
+}
+
+#[cfg(not(unix))]
+fn exit_signal(_status: std::process::ExitStatus) -> Option<i32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stack_traces() {
+        let output = "\
+SCRIPT ERROR: Invalid call. Nonexistent function 'jump' in base 'Node2D'.
+          at: _physics_process (res://scripts/player.gd:42)
+ERROR: Enemy has no target
+   at: push_error (core/variant/variant_utility.cpp:1024)
+   GDScript backtrace (most recent call first):
+       [0] find_target (res://scripts/enemy.gd:7)
+       [1] _ready (res://scripts/enemy.gd:3)
+SCRIPT WARNING: Integer division, decimal part will be discarded.
+          at: score (res://ui/hud.gd:12)";
+        let lines: Vec<&str> = output.lines().collect();
+        let traces = parse_stack_traces(&lines);
+        assert_eq!(traces.len(), 3);
+        assert_eq!(
+            traces[0].message,
+            "Invalid call. Nonexistent function 'jump' in base 'Node2D'."
+        );
+        assert_eq!(
+            traces[0].frames,
+            vec![StackFrame {
+                function: "_physics_process".to_string(),
+                file: "res://scripts/player.gd".to_string(),
+                line: Some(42),
+            }]
+        );
+        // A push_error with a backtrace
+        assert_eq!(traces[1].message, "Enemy has no target");
+        assert_eq!(traces[1].frames.len(), 2);
+        assert_eq!(traces[1].frames[1].function, "_ready");
+        assert_eq!(traces[1].frames[1].line, Some(3));
+        assert!(!traces[2].is_error);
+        assert_eq!(traces[2].frames[0].file, "res://ui/hud.gd");
+        assert_eq!(traces[2].frames[0].line, Some(12));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_records_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let child = std::process::Command::new("sh")
+            .args([
+                "-c",
+                "echo 'Godot Engine v4.3'; echo 'SCRIPT ERROR: boom' >&2; kill -SEGV $$",
+            ])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        fs::write(dir.path().join(PID_FILE), child.id().to_string()).unwrap();
+        capture(
+            dir.path(),
+            child,
+            Some("res://main.tscn".to_string()),
+            RunLimits::default(),
+        );
+
+        let report = (0..100)
+            .find_map(|_| {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                read(dir.path(), 10).filter(|report| !report.running)
+            })
+            .expect("run did not finish");
+        assert_eq!(report.record.signal, Some(11));
+        assert_eq!(report.crash.as_deref(), Some("Terminated by signal 11"));
+        assert!(!report.record.stopped);
+        assert_eq!(report.stdout, vec!["Godot Engine v4.3"]);
+        assert_eq!(report.stderr, vec!["SCRIPT ERROR: boom"]);
+        assert_eq!(report.stack_traces[0].message, "boom");
+        assert!(!dir.path().join(PID_FILE).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_kills_after_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let child = std::process::Command::new("sh")
+            .args(["-c", "echo started; exec sleep 30"])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        fs::write(dir.path().join(PID_FILE), pid.to_string()).unwrap();
+        assert!(process_usage(pid).is_some());
+        capture(
+            dir.path(),
+            child,
+            None,
+            RunLimits {
+                timeout: Some(Duration::from_millis(300)),
+                auto_stop: false,
+            },
+        );
+
+        let report = (0..100)
+            .find_map(|_| {
+                std::thread::sleep(Duration::from_millis(50));
+                read(dir.path(), 10).filter(|report| !report.running)
+            })
+            .expect("watchdog did not stop the run");
+        assert!(report.record.timed_out);
+        assert_eq!(report.record.signal, Some(9));
+        assert_eq!(report.crash, None);
+        assert_eq!(report.stdout, vec!["started"]);
+        assert!(!is_running(pid));
+        assert!(!dir.path().join(PID_FILE).exists());
+    }
+
+    #[test]
+    fn test_parse_process_usage() {
+        assert_eq!(
+            parse_ps_usage(" 12.5 204800\n"),
+            Some(ProcessUsage {
+                cpu_percent: Some(12.5),
+                memory_bytes: 204800 * 1024,
+            })
+        );
+        assert_eq!(parse_ps_usage(""), None);
+        assert_eq!(
+            parse_tasklist_usage(
+                "\"Godot_v4.3-stable_win64.exe\",\"4242\",\"Console\",\"1\",\"312,456 K\"\r\n",
+                4242
+            ),
+            Some(ProcessUsage {
+                cpu_percent: None,
+                memory_bytes: 312456 * 1024,
+            })
+        );
+        assert_eq!(
+            parse_tasklist_usage(
+                "INFO: No tasks are running which match the specified criteria.",
+                4242
+            ),
+            None
+        );
+    }
+}
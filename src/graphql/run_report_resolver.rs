@@ -0,0 +1,139 @@
+//! Run Report Resolver
+//!
+//! runProject: start the game outside the editor, like `run_project`, under
+//! an optional watchdog. lastRunReport: exit status, output tails and parsed
+//! stack traces of the last game run (see `run_report`).
+
+use std::time::Duration;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::godot_binary;
+use super::run_report::{self, RunLimits};
+use super::types::*;
+
+/// Resolve runProject mutation
+pub fn resolve_run_project(ctx: &GqlContext, input: &RunProjectInput) -> RunProjectResult {
+    let failure = |godot: Option<String>, error: GqlStructuredError| RunProjectResult {
+        success: false,
+        pid: None,
+        restarted: false,
+        godot,
+        error: Some(error),
+    };
+
+    if ctx.fs.is_virtual() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "runProject needs the project on disk",
+            ),
+        );
+    }
+    if input.timeout_seconds.is_some_and(|seconds| seconds <= 0) {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                "timeoutSeconds must be positive",
+            ),
+        );
+    }
+    if let Some(scene) = &input.scene {
+        let exists = path_utils::to_fs_path(&ctx.project_path, scene)
+            .is_ok_and(|path| ctx.fs.is_file(&path));
+        if !exists {
+            return failure(
+                None,
+                GqlStructuredError::new(
+                    "SCENE_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Scene not found: {}", scene),
+                ),
+            );
+        }
+    }
+    let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(binary) => binary,
+        Err(message) => return failure(
+            None,
+            GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                .with_suggestion(
+                "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+            ),
+        ),
+    };
+    let godot = Some(binary.path.display().to_string());
+
+    let limits = RunLimits {
+        timeout: input
+            .timeout_seconds
+            .map(|seconds| Duration::from_secs(seconds as u64)),
+        auto_stop: input.auto_stop,
+    };
+    match run_report::start(&binary.path, &ctx.project_path, input.scene.clone(), limits) {
+        Ok(run) => RunProjectResult {
+            success: true,
+            pid: Some(run.pid),
+            restarted: run.restarted,
+            godot,
+            error: None,
+        },
+        Err(message) => failure(
+            godot,
+            GqlStructuredError::new("GODOT_START_FAILED", GqlErrorCategory::Godot, message),
+        ),
+    }
+}
+
+/// Resolve lastRunReport query (null when the game was never run)
+pub fn resolve_last_run_report(ctx: &GqlContext, tail_lines: i32) -> Option<RunReport> {
+    let report = run_report::read(&ctx.project_path, tail_lines.max(0) as usize)?;
+    let record = report.record;
+    let usage = if report.running {
+        run_report::process_usage(record.pid)
+    } else {
+        None
+    };
+    Some(RunReport {
+        pid: record.pid,
+        scene: record.scene,
+        started_at: record.started_at.to_string(),
+        finished_at: record.finished_at.map(|millis| millis.to_string()),
+        running: report.running,
+        exit_code: record.exit_code,
+        signal: record.signal,
+        stopped: record.stopped,
+        timeout_seconds: record.timeout_seconds.map(|seconds| seconds as i32),
+        timed_out: record.timed_out,
+        auto_stop: record.auto_stop,
+        cpu_percent: usage.and_then(|usage| usage.cpu_percent),
+        memory_bytes: usage.map(|usage| usage.memory_bytes as i64),
+        crashed: report.crash.is_some(),
+        crash_reason: report.crash,
+        stdout_tail: report.stdout,
+        stderr_tail: report.stderr,
+        stack_traces: report
+            .stack_traces
+            .into_iter()
+            .map(|trace| RunStackTrace {
+                message: trace.message,
+                is_error: trace.is_error,
+                frames: trace
+                    .frames
+                    .into_iter()
+                    .map(|frame| StackFrame {
+                        file: frame.file,
+                        line: frame.line.map_or(0, |line| line as i32),
+                        function: frame.function,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        native_backtrace: report.native_backtrace,
+    })
+}
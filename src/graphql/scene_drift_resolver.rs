@@ -0,0 +1,329 @@
+//! Scene Drift Resolver
+//!
+//! Compares the scene being edited in the editor with its .tscn on disk, so an
+//! agent can tell whether the user has unsaved edits before it writes the
+//! file. The plugin serializes the edited scene the way saving it would
+//! (`get_tree` with `serialize: true`); both versions are parsed and compared
+//! node by node. Resource references are compared by path (ExtResource) or
+//! by content (SubResource), since ids differ between the two files, and
+//! numbers and whitespace are normalized (`1.0` and `1` are the same value).
+
+use std::collections::{BTreeSet, HashMap};
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils::ResPath;
+
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::types::*;
+
+/// Nested SubResource references followed when comparing by content
+const MAX_SUB_RESOURCE_DEPTH: usize = 4;
+
+/// Resolve liveSceneDrift query
+pub async fn resolve_live_scene_drift(ctx: &GqlContext, path: &str) -> LiveSceneDrift {
+    let mut drift = LiveSceneDrift {
+        path: path.to_string(),
+        editor_connected: false,
+        open: false,
+        edited_scene: None,
+        drifted: false,
+        added_nodes: Vec::new(),
+        removed_nodes: Vec::new(),
+        changed_properties: Vec::new(),
+        error: None,
+    };
+    let res_path = match ResPath::new(path) {
+        Ok(res_path) => res_path,
+        Err(e) => {
+            drift.error = Some(GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                format!("Invalid scene path: {}", e),
+            ));
+            return drift;
+        }
+    };
+    drift.path = res_path.as_res_path();
+
+    let disk = match read_scene(ctx, &res_path) {
+        Ok(scene) => scene,
+        Err(error) => {
+            drift.error = Some(*error);
+            return drift;
+        }
+    };
+
+    let command = GodotLiveCommand::GetTree { serialize: true };
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(_) => return drift,
+    };
+    drift.editor_connected = true;
+    if let Some(message) = value.get("error").and_then(Value::as_str) {
+        // Nothing being edited is not a failure: the scene simply isn't open
+        if !message.contains("No scene is open") {
+            drift.error = Some(drift_error(message.to_string()));
+        }
+        return drift;
+    }
+
+    let edited = value["scene_path"].as_str().unwrap_or_default();
+    if edited != drift.path {
+        drift.edited_scene = (!edited.is_empty()).then(|| edited.to_string());
+        return drift;
+    }
+    drift.open = true;
+
+    let live = match value["tscn"].as_str().map(GodotScene::parse) {
+        Some(Ok(scene)) => scene,
+        Some(Err(e)) => {
+            drift.error = Some(drift_error(format!(
+                "Cannot parse the editor's scene: {:?}",
+                e
+            )));
+            return drift;
+        }
+        None => {
+            drift.error = Some(drift_error(
+                "The plugin did not return the scene's contents".to_string(),
+            ));
+            return drift;
+        }
+    };
+
+    compare(&disk, &live, &mut drift);
+    drift.drifted = !drift.added_nodes.is_empty()
+        || !drift.removed_nodes.is_empty()
+        || !drift.changed_properties.is_empty();
+    drift
+}
+
+fn read_scene(ctx: &GqlContext, res_path: &ResPath) -> Result<GodotScene, Box<GqlStructuredError>> {
+    let fs_path = res_path.to_fs_path(&ctx.project_path);
+    let content = ctx.fs.read_to_string(&fs_path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read scene {}: {}", res_path.as_res_path(), e),
+        ))
+    })?;
+    GodotScene::parse(&content).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_PARSE_ERROR",
+            GqlErrorCategory::Validation,
+            format!("Failed to parse scene {}: {:?}", res_path.as_res_path(), e),
+        ))
+    })
+}
+
+/// Record the nodes and properties that differ between the file and the editor
+fn compare(disk: &GodotScene, live: &GodotScene, drift: &mut LiveSceneDrift) {
+    let disk_values = Normalizer::new(disk);
+    let live_values = Normalizer::new(live);
+    let disk_nodes: HashMap<String, &SceneNode> =
+        disk.nodes.iter().map(|n| (node_path(n), n)).collect();
+    let live_nodes: HashMap<String, &SceneNode> =
+        live.nodes.iter().map(|n| (node_path(n), n)).collect();
+
+    // A node whose type changed was replaced: removed and added again
+    for node in &live.nodes {
+        let path = node_path(node);
+        let kind = node_kind(live, node);
+        if disk_nodes.get(&path).map(|n| node_kind(disk, n)) != Some(kind.clone()) {
+            drift.added_nodes.push(DriftNode {
+                path,
+                node_type: kind,
+            });
+        }
+    }
+    for node in &disk.nodes {
+        let path = node_path(node);
+        let kind = node_kind(disk, node);
+        let Some(live_node) = live_nodes.get(&path) else {
+            drift.removed_nodes.push(DriftNode {
+                path,
+                node_type: kind,
+            });
+            continue;
+        };
+        if node_kind(live, live_node) != kind {
+            drift.removed_nodes.push(DriftNode {
+                path,
+                node_type: kind,
+            });
+            continue;
+        }
+
+        if node.parent.is_none() && node.name != live_node.name {
+            drift.changed_properties.push(PropertyDrift {
+                node_path: path.clone(),
+                property: "name".to_string(),
+                disk_value: Some(node.name.clone()),
+                live_value: Some(live_node.name.clone()),
+            });
+        }
+        let properties: BTreeSet<&String> = node
+            .properties
+            .keys()
+            .chain(live_node.properties.keys())
+            .collect();
+        for property in properties {
+            let disk_value = node.properties.get(property);
+            let live_value = live_node.properties.get(property);
+            let same = match (disk_value, live_value) {
+                (Some(d), Some(l)) => disk_values.normalize(d) == live_values.normalize(l),
+                _ => false,
+            };
+            if !same {
+                drift.changed_properties.push(PropertyDrift {
+                    node_path: path.clone(),
+                    property: property.clone(),
+                    disk_value: disk_value.cloned(),
+                    live_value: live_value.cloned(),
+                });
+            }
+        }
+    }
+}
+
+/// Node class, or the instanced scene's path for scene instances
+fn node_kind(scene: &GodotScene, node: &SceneNode) -> String {
+    if !node.node_type.is_empty() {
+        return node.node_type.clone();
+    }
+    scene.instance_path(node).unwrap_or_default().to_string()
+}
+
+/// Puts property values of one scene file into a comparable form
+struct Normalizer<'a> {
+    scene: &'a GodotScene,
+    ext_resource: Regex,
+    sub_resource: Regex,
+    decimal: Regex,
+}
+
+impl<'a> Normalizer<'a> {
+    fn new(scene: &'a GodotScene) -> Self {
+        Self {
+            scene,
+            ext_resource: Regex::new(r#"ExtResource\(\s*"([^"]*)"\s*\)"#).unwrap(),
+            sub_resource: Regex::new(r#"SubResource\(\s*"([^"]*)"\s*\)"#).unwrap(),
+            decimal: Regex::new(r"(^|[^\w.])(-?\d+\.\d+(?:e[-+]?\d+)?|-?\d+e[-+]?\d+)").unwrap(),
+        }
+    }
+
+    fn normalize(&self, value: &str) -> String {
+        self.normalize_nested(value, 0)
+    }
+
+    fn normalize_nested(&self, value: &str, depth: usize) -> String {
+        let value = self.ext_resource.replace_all(value, |c: &Captures| {
+            match self.scene.ext_resources.iter().find(|r| r.id == c[1]) {
+                Some(resource) => format!("ExtResource(\"{}\")", resource.path),
+                None => c[0].to_string(),
+            }
+        });
+        let value = self.sub_resource.replace_all(&value, |c: &Captures| {
+            let resource = self.scene.sub_resources.iter().find(|r| r.id == c[1]);
+            match resource {
+                Some(resource) if depth < MAX_SUB_RESOURCE_DEPTH => {
+                    let mut properties: Vec<String> = resource
+                        .properties
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, self.normalize_nested(v, depth + 1)))
+                        .collect();
+                    properties.sort();
+                    format!(
+                        "SubResource({}{{{}}})",
+                        resource.resource_type,
+                        properties.join(",")
+                    )
+                }
+                _ => c[0].to_string(),
+            }
+        });
+        map_outside_strings(&value, |code| {
+            let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+            self.decimal
+                .replace_all(&code, |c: &Captures| match c[2].parse::<f64>() {
+                    Ok(number) => format!("{}{}", &c[1], number),
+                    Err(_) => c[0].to_string(),
+                })
+                .into_owned()
+        })
+    }
+}
+
+/// Apply `f` to the parts of a value outside string literals
+fn map_outside_strings(value: &str, f: impl Fn(&str) -> String) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut code_start = 0;
+    let mut chars = value.char_indices();
+    while let Some((start, c)) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+        result.push_str(&f(&value[code_start..start]));
+        // Copy the literal, escapes included, up to its closing quote
+        let mut end = value.len();
+        let mut escaped = false;
+        for (i, c) in chars.by_ref() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    end = i + 1;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        result.push_str(&value[start..end]);
+        code_start = end;
+    }
+    result.push_str(&f(&value[code_start..]));
+    result
+}
+
+fn drift_error(message: String) -> GqlStructuredError {
+    GqlStructuredError::new("SCENE_DRIFT_FAILED", GqlErrorCategory::Godot, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_values() {
+        let scene = GodotScene::parse(
+            r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Texture2D" path="res://icon.svg" id="1_ab"]
+
+[sub_resource type="RectangleShape2D" id="RectangleShape2D_x"]
+size = Vector2(32, 16.0)
+
+[node name="Root" type="Node2D"]
+"#,
+        )
+        .unwrap();
+        let values = Normalizer::new(&scene);
+        assert_eq!(values.normalize("Vector2(1.0, 2.50)"), "Vector2(1,2.5)");
+        assert_eq!(values.normalize("Vector2(1,2.5)"), "Vector2(1,2.5)");
+        assert_eq!(
+            values.normalize(r#"ExtResource("1_ab")"#),
+            r#"ExtResource("res://icon.svg")"#
+        );
+        assert_eq!(
+            values.normalize(r#"SubResource("RectangleShape2D_x")"#),
+            "SubResource(RectangleShape2D{size=Vector2(32,16)})"
+        );
+        assert_eq!(values.normalize("1e-05"), "0.00001");
+        // Strings keep their spacing and digits
+        assert_eq!(values.normalize(r#""Level 2.0  ""#), r#""Level 2.0  ""#);
+    }
+}
@@ -0,0 +1,126 @@
+//! Scene Lock
+//!
+//! Coordinates file-based .tscn writes with the editor plugin so that the
+//! editor and the agent do not silently overwrite each other. Before writing,
+//! the server locks the scene in the plugin (which applies the write policy
+//! to unsaved editor changes); afterwards it unlocks and reloads the scene
+//! if it is open. Without a running editor, writes proceed unchecked.
+
+use serde_json::Value;
+
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::types::*;
+
+/// Lock held on a scene while the server writes it
+#[derive(Debug, Clone)]
+pub struct SceneLock {
+    path: String,
+    /// The plugin acknowledged the lock and must be told to release it
+    coordinated: bool,
+    /// The scene is open in the editor and needs a reload after the write
+    open: bool,
+}
+
+/// Resolve sceneLockStatus query
+pub async fn resolve_scene_lock_status(ctx: &GqlContext, path: &str) -> SceneLockStatus {
+    let command = GodotLiveCommand::GetSceneStatus {
+        scene_path: path.to_string(),
+    };
+
+    match execute_live_command(ctx, command).await {
+        Ok(value) if value.get("error").is_none() => SceneLockStatus {
+            path: path.to_string(),
+            editor_connected: true,
+            open: flag(&value, "open"),
+            unsaved: flag(&value, "unsaved"),
+            locked: flag(&value, "locked"),
+        },
+        _ => SceneLockStatus {
+            path: path.to_string(),
+            editor_connected: false,
+            open: false,
+            unsaved: false,
+            locked: false,
+        },
+    }
+}
+
+/// Lock a scene before writing it
+///
+/// Fails when the plugin refuses (unsaved changes under REFUSE, or another
+/// write in progress). An unreachable editor, or a plugin without lock
+/// support, yields an uncoordinated lock.
+pub async fn lock_scene(
+    ctx: &GqlContext,
+    path: &str,
+    policy: SceneWritePolicy,
+) -> Result<SceneLock, GqlStructuredError> {
+    let uncoordinated = SceneLock {
+        path: path.to_string(),
+        coordinated: false,
+        open: false,
+    };
+
+    let command = GodotLiveCommand::LockScene {
+        scene_path: path.to_string(),
+        policy: policy_name(policy).to_string(),
+    };
+
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        // No editor to coordinate with
+        Err(_) => return Ok(uncoordinated),
+    };
+
+    let Some(message) = value.get("error").and_then(|e| e.as_str()) else {
+        return Ok(SceneLock {
+            path: path.to_string(),
+            coordinated: true,
+            open: flag(&value, "open"),
+        });
+    };
+
+    // Older plugin without scene lock support
+    if message.starts_with("Unknown command") {
+        return Ok(uncoordinated);
+    }
+
+    let code = value
+        .get("code")
+        .and_then(|c| c.as_str())
+        .unwrap_or("SCENE_LOCKED");
+    let suggestion = if code == "SCENE_UNSAVED" {
+        "エディターでシーンを保存するか、policy に FORCE_SAVE / RELOAD_AFTER_WRITE を指定してください"
+    } else {
+        "しばらく待ってから再実行してください"
+    };
+    Err(GqlStructuredError::new(code, GqlErrorCategory::Godot, message).with_suggestion(suggestion))
+}
+
+/// Release a lock, reloading the scene in the editor if it was written
+pub async fn unlock_scene(ctx: &GqlContext, lock: SceneLock, written: bool) {
+    if !lock.coordinated {
+        return;
+    }
+
+    let command = GodotLiveCommand::UnlockScene {
+        scene_path: lock.path,
+        reload: written && lock.open,
+    };
+    if let Err(e) = execute_live_command(ctx, command).await {
+        tracing::warn!("Failed to unlock scene: {}", e);
+    }
+}
+
+fn policy_name(policy: SceneWritePolicy) -> &'static str {
+    match policy {
+        SceneWritePolicy::Refuse => "refuse",
+        SceneWritePolicy::ForceSave => "force_save",
+        SceneWritePolicy::ReloadAfterWrite => "reload_after_write",
+    }
+}
+
+fn flag(value: &Value, key: &str) -> bool {
+    value.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
@@ -0,0 +1,148 @@
+//! Scene Patch Resolver
+//!
+//! Applies declarative scene patches (see `godot::scene_patch`) to one scene
+//! file under the scene lock, or previews them as a diff with `dryRun`.
+
+use similar::TextDiff;
+
+use crate::godot::scene_patch::{PatchError, ScenePatch, SCHEMA};
+use crate::godot::tscn::GodotScene;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::scene_lock;
+use super::types::*;
+
+/// Resolve scenePatchSchema query
+pub fn resolve_scene_patch_schema() -> serde_json::Value {
+    serde_json::from_str(SCHEMA).unwrap_or_default()
+}
+
+/// Validate a patch and apply it to the scene atomically
+pub async fn apply_scene_patch(
+    ctx: &GqlContext,
+    path: &str,
+    patch: &serde_json::Value,
+    dry_run: bool,
+    policy: SceneWritePolicy,
+) -> ApplyScenePatchResult {
+    let patch = match ScenePatch::from_json(patch) {
+        Ok(patch) => patch,
+        Err(e) => return rejected(dry_run, 0, vec![e]),
+    };
+    if dry_run {
+        return write_scene_patch(ctx, path, &patch, true);
+    }
+
+    let lock = match scene_lock::lock_scene(ctx, path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return patch_failure(dry_run, e),
+    };
+    let result = write_scene_patch(ctx, path, &patch, false);
+    scene_lock::unlock_scene(ctx, lock, result.success && !result.diff.is_empty()).await;
+    result
+}
+
+fn write_scene_patch(
+    ctx: &GqlContext,
+    path: &str,
+    patch: &ScenePatch,
+    dry_run: bool,
+) -> ApplyScenePatchResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => {
+            return patch_failure(
+                dry_run,
+                GqlStructuredError::new(
+                    "SCENE_NOT_FOUND",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to read scene {}: {}", path, e),
+                ),
+            )
+        }
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => {
+            return patch_failure(
+                dry_run,
+                GqlStructuredError::new(
+                    "SCENE_PARSE_ERROR",
+                    GqlErrorCategory::Validation,
+                    format!("Failed to parse scene: {}", e),
+                ),
+            )
+        }
+    };
+
+    let operation_count = patch.operations.len() as i32;
+    if let Err(errors) = patch.apply(&mut scene) {
+        return rejected(dry_run, operation_count, errors);
+    }
+
+    let patched = scene.to_tscn();
+    let diff = if patched == content {
+        String::new()
+    } else {
+        TextDiff::from_lines(&content, &patched)
+            .unified_diff()
+            .context_radius(3)
+            .header(path, path)
+            .to_string()
+    };
+    if !dry_run && !diff.is_empty() {
+        if let Err(e) = audit::write_file(ctx, "applyScenePatch", &file_path, &patched) {
+            return patch_failure(
+                dry_run,
+                GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to write scene: {}", e),
+                ),
+            );
+        }
+        telemetry::record_files_written(1);
+    }
+
+    ApplyScenePatchResult {
+        success: true,
+        dry_run,
+        operation_count,
+        diff,
+        errors: Vec::new(),
+        error: None,
+    }
+}
+
+fn rejected(dry_run: bool, operation_count: i32, errors: Vec<PatchError>) -> ApplyScenePatchResult {
+    ApplyScenePatchResult {
+        success: false,
+        dry_run,
+        operation_count,
+        diff: String::new(),
+        errors: errors
+            .into_iter()
+            .map(|e| ScenePatchError {
+                operation_index: e.operation_index.map(|i| i as i32),
+                code: e.code.to_string(),
+                message: e.message,
+            })
+            .collect(),
+        error: None,
+    }
+}
+
+fn patch_failure(dry_run: bool, error: GqlStructuredError) -> ApplyScenePatchResult {
+    ApplyScenePatchResult {
+        success: false,
+        dry_run,
+        operation_count: 0,
+        diff: String::new(),
+        errors: Vec::new(),
+        error: Some(error),
+    }
+}
@@ -1,22 +1,52 @@
 //! Scene Resolver
 //!
-//! Handles scene parsing, conversion, and creation.
+//! Handles scene and resource parsing, conversion, creation, and structural repair.
 
-use std::fs;
+use std::collections::HashMap;
 
-use crate::godot::tscn::GodotScene;
+use crate::godot::expression::{self, ExpressionError, PropertySource, Value};
+use crate::godot::tres::GodotResource;
+use crate::godot::tscn::{node_path, Connection, GodotScene, StructureFix, StructureIssue};
 use crate::path_utils;
+use crate::telemetry;
 
+use super::audit;
 use super::context::GqlContext;
+use super::scene_lock;
 use super::types::*;
 
 /// Resolve scene from file path
 pub fn resolve_scene(ctx: &GqlContext, res_path: &str) -> Option<Scene> {
     let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
-    let content = fs::read_to_string(&file_path).ok()?;
-    let godot_scene = GodotScene::parse(&content).ok()?;
+    let godot_scene = ctx.index.scene(&file_path).ok()?;
+    let mut scene = convert_godot_scene_to_gql(&godot_scene, res_path);
 
-    Some(convert_godot_scene_to_gql(&godot_scene, res_path))
+    // Nodes of an inherited scene take their type from the base scene
+    if scene.inherits_from.is_some() {
+        for node in scene.all_nodes.iter_mut() {
+            if node.r#type.is_empty() || node.path == "." {
+                if let Some(base) = godot_scene.inherited_node(&node.path, scene_loader(ctx)) {
+                    node.r#type = base.node_type;
+                }
+            }
+        }
+        if let Some(root) = scene.all_nodes.first() {
+            scene.root = root.clone();
+        }
+    }
+
+    Some(scene)
+}
+
+/// Loads scenes by res:// path, for following scene inheritance
+pub(super) fn scene_loader(ctx: &GqlContext) -> impl Fn(&str) -> Option<GodotScene> + Copy + '_ {
+    move |res_path| {
+        let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+        ctx.index
+            .scene(&file_path)
+            .ok()
+            .map(|scene| (*scene).clone())
+    }
 }
 
 /// Convert GodotScene to GraphQL Scene
@@ -37,7 +67,7 @@ pub fn convert_godot_scene_to_gql(scene: &GodotScene, path: &str) -> Scene {
             SceneNode {
                 name: n.name.clone(),
                 r#type: n.node_type.clone(),
-                path: node_path,
+                path: node_path.clone(),
                 properties: n
                     .properties
                     .iter()
@@ -49,8 +79,17 @@ pub fn convert_godot_scene_to_gql(scene: &GodotScene, path: &str) -> Scene {
                     .collect(),
                 children: vec![], // Filled later if needed
                 script: None,     // TODO: Parse script reference
-                groups: vec![],   // TODO: Parse groups
-                signals: vec![],  // TODO: Parse signal connections
+                groups: n.groups.clone(),
+                signals: scene
+                    .connections_from(&node_path)
+                    .map(|c| SignalConnection {
+                        from_node: c.from.clone(),
+                        signal: c.signal.clone(),
+                        to_node: c.to.clone(),
+                        method: c.method.clone(),
+                    })
+                    .collect(),
+                instance_path: scene.instance_path(n).map(str::to_string),
             }
         })
         .collect();
@@ -65,6 +104,7 @@ pub fn convert_godot_scene_to_gql(scene: &GodotScene, path: &str) -> Scene {
         script: None,
         groups: vec![],
         signals: vec![],
+        instance_path: None,
     });
 
     // External resources
@@ -78,11 +118,83 @@ pub fn convert_godot_scene_to_gql(scene: &GodotScene, path: &str) -> Scene {
         })
         .collect();
 
+    let sub_resources = scene
+        .sub_resources
+        .iter()
+        .map(|r| SubResource {
+            id: r.id.clone(),
+            resource_type: r.resource_type.clone(),
+            properties: r
+                .properties
+                .iter()
+                .map(|(k, v)| Property {
+                    name: k.clone(),
+                    value: v.clone(),
+                    property_type: None,
+                })
+                .collect(),
+        })
+        .collect();
+
     Scene {
         path: path.to_string(),
         root,
         all_nodes,
         external_resources,
+        sub_resources,
+        inherits_from: scene.inherits_from().map(str::to_string),
+    }
+}
+
+/// Resolve a .tres resource from file path
+pub fn resolve_resource(ctx: &GqlContext, res_path: &str) -> Option<Resource> {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    let content = ctx.index.read_to_string(&file_path)?;
+    let resource = GodotResource::parse(&content).ok()?;
+
+    Some(convert_godot_resource_to_gql(&resource, res_path))
+}
+
+/// Convert GodotResource to GraphQL Resource
+pub fn convert_godot_resource_to_gql(resource: &GodotResource, path: &str) -> Resource {
+    // Properties are kept in a map, so list them by name for a stable order
+    let properties = |props: &HashMap<String, String>| {
+        let mut properties: Vec<Property> = props
+            .iter()
+            .map(|(k, v)| Property {
+                name: k.clone(),
+                value: v.clone(),
+                property_type: None,
+            })
+            .collect();
+        properties.sort_by(|a, b| a.name.cmp(&b.name));
+        properties
+    };
+
+    Resource {
+        path: path.to_string(),
+        resource_type: resource.resource_type.clone(),
+        uid: resource.uid.clone(),
+        script_class: resource.script_class.clone(),
+        external_resources: resource
+            .ext_resources
+            .iter()
+            .map(|r| ExternalResource {
+                id: r.id.parse().unwrap_or(0),
+                resource_type: r.resource_type.clone(),
+                path: r.path.clone(),
+            })
+            .collect(),
+        sub_resources: resource
+            .sub_resources
+            .iter()
+            .map(|r| SubResource {
+                id: r.id.clone(),
+                resource_type: r.resource_type.clone(),
+                properties: properties(&r.properties),
+            })
+            .collect(),
+        properties: properties(&resource.properties),
     }
 }
 
@@ -94,7 +206,7 @@ pub fn create_scene(ctx: &GqlContext, input: &CreateSceneInput) -> SceneResult {
     let file_path = path_utils::to_fs_path_unchecked(project_path, &input.path);
 
     // Check if file already exists
-    if file_path.exists() {
+    if ctx.fs.exists(&file_path) {
         return SceneResult {
             success: false,
             scene: None,
@@ -104,7 +216,7 @@ pub fn create_scene(ctx: &GqlContext, input: &CreateSceneInput) -> SceneResult {
 
     // Ensure parent directory exists
     if let Some(parent) = file_path.parent() {
-        if let Err(e) = fs::create_dir_all(parent) {
+        if let Err(e) = ctx.fs.create_dir_all(parent) {
             return SceneResult {
                 success: false,
                 scene: None,
@@ -123,13 +235,14 @@ pub fn create_scene(ctx: &GqlContext, input: &CreateSceneInput) -> SceneResult {
     );
 
     // Write file
-    if let Err(e) = fs::write(&file_path, tscn_content) {
+    if let Err(e) = audit::write_file(ctx, "createScene", &file_path, tscn_content) {
         return SceneResult {
             success: false,
             scene: None,
             message: Some(format!("Failed to write scene: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     SceneResult {
         success: true,
@@ -137,3 +250,562 @@ pub fn create_scene(ctx: &GqlContext, input: &CreateSceneInput) -> SceneResult {
         message: Some(format!("Created scene: {}", input.path)),
     }
 }
+
+// ======================
+// Node Metadata (agent annotations)
+// ======================
+
+/// Property prefix for agent annotations, visible under Metadata in the inspector
+pub(super) const MCP_METADATA_PREFIX: &str = "metadata/mcp_";
+
+/// Read agent metadata from a scene, optionally limited to one node
+pub fn resolve_node_metadata(
+    ctx: &GqlContext,
+    scene_path: &str,
+    node_path: Option<&str>,
+) -> Vec<NodeMetadataEntry> {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, scene_path);
+    let Ok(content) = ctx.fs.read_to_string(&file_path) else {
+        return Vec::new();
+    };
+    let Ok(scene) = GodotScene::parse(&content) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<NodeMetadataEntry> = scene
+        .nodes
+        .iter()
+        .map(|n| (crate::godot::tscn::node_path(n), n))
+        .filter(|(path, _)| node_path.map(|p| p == path).unwrap_or(true))
+        .flat_map(|(path, n)| {
+            n.properties.iter().filter_map(move |(k, v)| {
+                k.strip_prefix(MCP_METADATA_PREFIX)
+                    .map(|key| NodeMetadataEntry {
+                        node_path: path.clone(),
+                        key: key.to_string(),
+                        value: unquote_godot_string(v),
+                    })
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.node_path.cmp(&b.node_path).then(a.key.cmp(&b.key)));
+    entries
+}
+
+/// Set or remove an agent metadata entry on a scene node
+pub async fn set_node_metadata(ctx: &GqlContext, input: &SetNodeMetadataInput) -> OperationResult {
+    if input.key.is_empty()
+        || !input
+            .key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "VALIDATION_INVALID_KEY",
+                GqlErrorCategory::Validation,
+                format!("Invalid metadata key: {}", input.key),
+            )
+            .with_suggestion("キーには英数字とアンダースコアのみ使用できます"),
+        );
+    }
+
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_node_metadata(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_node_metadata(ctx: &GqlContext, input: &SetNodeMetadataInput) -> OperationResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let Some(node) = scene
+        .nodes
+        .iter_mut()
+        .find(|n| node_path(n) == input.node_path)
+    else {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "VALIDATION_NODE_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Node not found: {}", input.node_path),
+            )
+            .with_suggestion("scene クエリで有効なノードパスを確認してください"),
+        );
+    };
+
+    let property = format!("{}{}", MCP_METADATA_PREFIX, input.key);
+    match &input.value {
+        Some(value) => {
+            node.properties.insert(property, quote_godot_string(value));
+        }
+        None => {
+            node.properties.shift_remove(&property);
+        }
+    }
+
+    if let Err(e) = audit::write_file(ctx, "setNodeMetadata", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult::ok()
+}
+
+/// Add a `[connection]` to a scene file
+pub async fn connect_signal_in_scene(
+    ctx: &GqlContext,
+    input: &ConnectSignalInSceneInput,
+) -> OperationResult {
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_signal_connection(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_signal_connection(ctx: &GqlContext, input: &ConnectSignalInSceneInput) -> OperationResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let connection = Connection {
+        signal: input.signal.clone(),
+        from: input.from_node.clone(),
+        to: input.to_node.clone(),
+        method: input.method.clone(),
+        flags: input.flags.and_then(|f| u32::try_from(f).ok()),
+        unbinds: None,
+        binds: None,
+    };
+    if let Err(message) = scene.connect_signal(connection) {
+        return OperationResult::err(
+            GqlStructuredError::new(
+                "SIGNAL_CONNECT_FAILED",
+                GqlErrorCategory::Validation,
+                message,
+            )
+            .with_suggestion("scene クエリでノードパスと既存の接続（signals）を確認してください"),
+        );
+    }
+
+    if let Err(e) = audit::write_file(ctx, "connectSignalInScene", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult::ok()
+}
+
+/// Move a node and its descendants under a different parent in a scene file
+pub async fn reparent_node_in_scene(
+    ctx: &GqlContext,
+    input: &ReparentNodeInSceneInput,
+) -> OperationResult {
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return OperationResult::err(e),
+    };
+    let result = write_reparented_node(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_reparented_node(ctx: &GqlContext, input: &ReparentNodeInSceneInput) -> OperationResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => return OperationResult::err_msg(format!("Failed to read scene: {}", e)),
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => return OperationResult::err_msg(format!("Failed to parse scene: {}", e)),
+    };
+
+    let new_path = match scene.reparent_node(&input.node_path, &input.new_parent) {
+        Ok(path) => path,
+        Err(message) => {
+            return OperationResult::err(
+                GqlStructuredError::new(
+                    "NODE_REPARENT_FAILED",
+                    GqlErrorCategory::Validation,
+                    message,
+                )
+                .with_suggestion(
+                    "scene クエリでノードパスを確認してください（自身の子孫や同名ノードのある親には移動できません）",
+                ),
+            )
+        }
+    };
+
+    if let Err(e) = audit::write_file(ctx, "reparentNodeInScene", &file_path, scene.to_tscn()) {
+        return OperationResult::err_msg(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    OperationResult {
+        message: Some(format!("Moved to '{}'", new_path)),
+        ..OperationResult::ok()
+    }
+}
+
+/// Report structural issues of a scene and apply the requested fixes
+pub async fn repair_scene(
+    ctx: &GqlContext,
+    path: &str,
+    fixes: &[SceneRepairFix],
+    policy: SceneWritePolicy,
+) -> SceneRepairResult {
+    if fixes.is_empty() {
+        return write_scene_repair(ctx, path, fixes);
+    }
+
+    let lock = match scene_lock::lock_scene(ctx, path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return repair_failure(e),
+    };
+    let result = write_scene_repair(ctx, path, fixes);
+    scene_lock::unlock_scene(ctx, lock, result.written).await;
+    result
+}
+
+fn write_scene_repair(ctx: &GqlContext, path: &str, fixes: &[SceneRepairFix]) -> SceneRepairResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => {
+            return repair_failure(GqlStructuredError::new(
+                "SCENE_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to read scene {}: {}", path, e),
+            ))
+        }
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => {
+            return repair_failure(GqlStructuredError::new(
+                "SCENE_PARSE_ERROR",
+                GqlErrorCategory::Validation,
+                format!("Failed to parse scene: {}", e),
+            ))
+        }
+    };
+
+    let issues = convert_structure_issues(&scene.structure_issues());
+    let fixes: Vec<StructureFix> = fixes
+        .iter()
+        .map(|fix| match fix {
+            SceneRepairFix::ReattachOrphans => StructureFix::ReattachOrphans,
+            SceneRepairFix::DropOrphans => StructureFix::DropOrphans,
+            SceneRepairFix::DropDuplicates => StructureFix::DropDuplicates,
+        })
+        .collect();
+    let applied = scene.repair_structure(&fixes);
+    let remaining = convert_structure_issues(&scene.structure_issues());
+
+    let written = !applied.is_empty();
+    if written {
+        if let Err(e) = audit::write_file(ctx, "repairScene", &file_path, scene.to_tscn()) {
+            return repair_failure(GqlStructuredError::new(
+                "FILE_WRITE_ERROR",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to write scene: {}", e),
+            ));
+        }
+        telemetry::record_files_written(1);
+    }
+
+    SceneRepairResult {
+        success: true,
+        issues,
+        applied,
+        remaining,
+        written,
+        error: None,
+    }
+}
+
+fn convert_structure_issues(issues: &[StructureIssue]) -> Vec<SceneStructureIssue> {
+    issues
+        .iter()
+        .map(|issue| SceneStructureIssue {
+            kind: match issue {
+                StructureIssue::MissingRoot => SceneStructureIssueKind::MissingRoot,
+                StructureIssue::ExtraRoot { .. } => SceneStructureIssueKind::ExtraRoot,
+                StructureIssue::Orphan { .. } => SceneStructureIssueKind::Orphan,
+                StructureIssue::Duplicate { .. } => SceneStructureIssueKind::Duplicate,
+            },
+            node_path: issue.node_path().map(str::to_string),
+            message: issue.message(),
+        })
+        .collect()
+}
+
+fn repair_failure(error: GqlStructuredError) -> SceneRepairResult {
+    SceneRepairResult {
+        success: false,
+        issues: vec![],
+        applied: vec![],
+        remaining: vec![],
+        written: false,
+        error: Some(error),
+    }
+}
+
+/// Evaluate an expression over node properties and store the result on a node
+pub async fn compute_property(
+    ctx: &GqlContext,
+    input: &ComputePropertyInput,
+    policy: SceneWritePolicy,
+) -> ComputePropertyResult {
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return compute_failure(e),
+    };
+    let result = write_computed_property(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success).await;
+    result
+}
+
+fn write_computed_property(
+    ctx: &GqlContext,
+    input: &ComputePropertyInput,
+) -> ComputePropertyResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let content = match ctx.fs.read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(e) => {
+            return compute_failure(GqlStructuredError::new(
+                "SCENE_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Failed to read scene {}: {}", input.scene_path, e),
+            ))
+        }
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(s) => s,
+        Err(e) => {
+            return compute_failure(GqlStructuredError::new(
+                "SCENE_PARSE_ERROR",
+                GqlErrorCategory::Validation,
+                format!("Failed to parse scene: {}", e),
+            ))
+        }
+    };
+
+    // Nodes inherited from a base scene get an override section
+    let existing = scene
+        .nodes
+        .iter()
+        .position(|n| node_path(n) == input.node_path);
+    let Some(index) = existing.or_else(|| {
+        scene
+            .inherited_node(&input.node_path, scene_loader(ctx))
+            .and_then(|_| scene.add_inherited_override(&input.node_path).ok())
+    }) else {
+        return compute_failure(
+            GqlStructuredError::new(
+                "VALIDATION_NODE_NOT_FOUND",
+                GqlErrorCategory::Validation,
+                format!("Node not found: {}", input.node_path),
+            )
+            .with_suggestion("scene クエリで有効なノードパスを確認してください"),
+        );
+    };
+    let source = SceneProperties {
+        scene: &scene,
+        base: &input.node_path,
+    };
+    let value = match expression::evaluate(&input.expression, &source) {
+        Ok(value) => value.to_godot(),
+        Err(e) => {
+            return compute_failure(
+                GqlStructuredError::new(
+                    "EXPRESSION_ERROR",
+                    GqlErrorCategory::Validation,
+                    e.to_string(),
+                )
+                .with_suggestion(
+                    "$\"../Node\".position のようにノードパスとプロパティを指定し、四則演算・Vector2/Vector3・組み込み関数のみ使用してください",
+                ),
+            )
+        }
+    };
+
+    let previous = scene.nodes[index]
+        .properties
+        .insert(input.property.clone(), value.clone());
+
+    if let Err(e) = audit::write_file(ctx, "computeProperty", &file_path, scene.to_tscn()) {
+        return compute_failure(GqlStructuredError::new(
+            "FILE_WRITE_ERROR",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to write scene: {}", e),
+        ));
+    }
+    telemetry::record_files_written(1);
+
+    ComputePropertyResult {
+        success: true,
+        value: Some(value),
+        previous,
+        error: None,
+    }
+}
+
+/// Node properties of a parsed scene, addressed relative to `base`
+struct SceneProperties<'a> {
+    scene: &'a GodotScene,
+    base: &'a str,
+}
+
+impl SceneProperties<'_> {
+    fn node(&self, path: &str) -> Option<&crate::godot::tscn::SceneNode> {
+        self.scene.nodes.iter().find(|n| node_path(n) == path)
+    }
+}
+
+impl PropertySource for SceneProperties<'_> {
+    fn property(&self, node_path: &str, property: &str) -> Result<Value, ExpressionError> {
+        let path = resolve_relative_path(self.base, node_path).ok_or_else(|| {
+            ExpressionError::Eval(format!("Node path leaves the scene: {}", node_path))
+        })?;
+        let node = self
+            .node(&path)
+            .ok_or_else(|| ExpressionError::Eval(format!("Node not found: {}", path)))?;
+
+        let raw = match node.properties.get(property) {
+            Some(raw) => raw.as_str(),
+            None => default_property(&node.node_type, property).ok_or_else(|| {
+                ExpressionError::Eval(format!(
+                    "Property '{}' is not set on {} and has no known default",
+                    property, path
+                ))
+            })?,
+        };
+        expression::parse_value(raw).map_err(|_| {
+            ExpressionError::Eval(format!(
+                "Unsupported value for {}:{}: {}",
+                path, property, raw
+            ))
+        })
+    }
+}
+
+/// Resolve a GDScript-style relative node path (`Child`, `../Sibling`) against
+/// a scene node path; None when it climbs above the root
+fn resolve_relative_path(base: &str, relative: &str) -> Option<String> {
+    let mut segments: Vec<&str> = base.split('/').filter(|s| *s != ".").collect();
+    for segment in relative.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            name => segments.push(name),
+        }
+    }
+    Some(if segments.is_empty() {
+        ".".to_string()
+    } else {
+        segments.join("/")
+    })
+}
+
+/// Engine defaults of transform properties, which .tscn files omit when unchanged
+fn default_property(node_type: &str, property: &str) -> Option<&'static str> {
+    let is_3d = node_type.ends_with("3D");
+    match property {
+        "position" if is_3d => Some("Vector3(0, 0, 0)"),
+        "position" => Some("Vector2(0, 0)"),
+        "rotation" if is_3d => Some("Vector3(0, 0, 0)"),
+        "rotation" | "skew" => Some("0"),
+        "scale" if is_3d => Some("Vector3(1, 1, 1)"),
+        "scale" => Some("Vector2(1, 1)"),
+        "modulate" | "self_modulate" => Some("Color(1, 1, 1, 1)"),
+        "visible" => Some("true"),
+        "z_index" => Some("0"),
+        _ => None,
+    }
+}
+
+fn compute_failure(error: GqlStructuredError) -> ComputePropertyResult {
+    ComputePropertyResult {
+        success: false,
+        value: None,
+        previous: None,
+        error: Some(error),
+    }
+}
+
+/// Quote a value as a single-line Godot string literal
+pub(super) fn quote_godot_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+pub(super) fn unquote_godot_string(value: &str) -> String {
+    let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+        return value.to_string();
+    };
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_relative_path() {
+        assert_eq!(resolve_relative_path("Player", "../Door").unwrap(), "Door");
+        assert_eq!(
+            resolve_relative_path("Level/Player", "Sprite").unwrap(),
+            "Level/Player/Sprite"
+        );
+        assert_eq!(resolve_relative_path("Player", "..").unwrap(), ".");
+        assert_eq!(resolve_relative_path(".", "Player").unwrap(), "Player");
+        assert!(resolve_relative_path("Player", "../..").is_none());
+    }
+}
@@ -0,0 +1,419 @@
+//! Scene Template Resolver
+//!
+//! User-defined scene templates live in `res://.godot-mcp/templates` as
+//! `<name>.tscn`, with their parameters declared in a `<name>.toml` sidecar:
+//!
+//! ```toml
+//! description = "Moving platform"
+//!
+//! [params.size]
+//! type = "float"
+//! default = "32"
+//!
+//! [params.color]
+//! type = "Color"
+//! ```
+//!
+//! Property values use `{{expression}}` placeholders over the parameters
+//! (see [`crate::godot::scene_template`]). Parameters without a default are
+//! required when instantiating, unless every placeholder reading them has a
+//! `default:` filter. lintSceneTemplates checks that every parameter used is
+//! declared and that the expressions evaluate.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use crate::godot::expression::{self, Value};
+use crate::godot::scene_template::{self, LintIssue};
+use crate::godot::tscn::GodotScene;
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::types::*;
+
+/// Folder templates are read from
+pub const TEMPLATES_DIR: &str = "res://.godot-mcp/templates";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateFile {
+    description: Option<String>,
+    #[serde(default)]
+    params: BTreeMap<String, ParamDecl>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParamDecl {
+    /// One of [`scene_template::PARAM_TYPES`]; any value when left out
+    #[serde(rename = "type")]
+    param_type: Option<String>,
+    /// Godot value or expression ("Color(1, 1, 1, 1)", "32 * 2")
+    default: Option<String>,
+    description: Option<String>,
+}
+
+struct Template {
+    name: String,
+    /// res:// path of the .tscn
+    path: String,
+    text: String,
+}
+
+/// Resolve sceneTemplates query
+pub fn resolve_scene_templates(ctx: &GqlContext) -> SceneTemplatesResult {
+    let loaded = list_templates(ctx).and_then(|templates| {
+        templates
+            .into_iter()
+            .map(|template| {
+                let file = read_declarations(ctx, &template)?;
+                Ok(template_info(&template, &file))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    });
+    match loaded {
+        Ok(templates) => SceneTemplatesResult {
+            templates,
+            error: None,
+        },
+        Err(error) => SceneTemplatesResult {
+            templates: Vec::new(),
+            error: Some(*error),
+        },
+    }
+}
+
+/// Resolve createSceneFromTemplate mutation
+pub fn resolve_create_scene_from_template(
+    ctx: &GqlContext,
+    input: &TemplateSceneInput,
+) -> SceneResult {
+    let failed = |message: String| SceneResult {
+        success: false,
+        scene: None,
+        message: Some(message),
+    };
+
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.path);
+    if ctx.fs.exists(&file_path) {
+        return failed(format!("Scene already exists: {}", input.path));
+    }
+    let content = match instantiate(ctx, input) {
+        Ok(content) => content,
+        Err(message) => return failed(message),
+    };
+    if let Some(parent) = file_path.parent() {
+        if let Err(e) = ctx.fs.create_dir_all(parent) {
+            return failed(format!("Failed to create directory: {}", e));
+        }
+    }
+    if let Err(e) = audit::write_file(ctx, "createSceneFromTemplate", &file_path, content) {
+        return failed(format!("Failed to write scene: {}", e));
+    }
+    telemetry::record_files_written(1);
+
+    SceneResult {
+        success: true,
+        scene: None,
+        message: Some(format!(
+            "Created scene {} from template {}",
+            input.path, input.template
+        )),
+    }
+}
+
+/// Resolve lintSceneTemplates query
+pub fn resolve_lint_scene_templates(
+    ctx: &GqlContext,
+    name: Option<&str>,
+) -> SceneTemplateLintResult {
+    let templates = match list_templates(ctx) {
+        Ok(templates) => templates,
+        Err(error) => {
+            return SceneTemplateLintResult {
+                valid: false,
+                templates_checked: 0,
+                issues: Vec::new(),
+                error: Some(*error),
+            }
+        }
+    };
+    let templates: Vec<Template> = templates
+        .into_iter()
+        .filter(|t| name.is_none_or(|name| t.name == name))
+        .collect();
+    if let (Some(name), true) = (name, templates.is_empty()) {
+        return SceneTemplateLintResult {
+            valid: false,
+            templates_checked: 0,
+            issues: Vec::new(),
+            error: Some(*not_found(name)),
+        };
+    }
+
+    let mut issues = Vec::new();
+    for template in &templates {
+        lint_template(ctx, template, &mut issues);
+    }
+    SceneTemplateLintResult {
+        valid: issues
+            .iter()
+            .all(|issue| issue.severity != ErrorSeverity::Error),
+        templates_checked: templates.len() as i32,
+        issues,
+        error: None,
+    }
+}
+
+fn lint_template(ctx: &GqlContext, template: &Template, issues: &mut Vec<SceneTemplateLintIssue>) {
+    let mut issue = |line: Option<usize>, code: &str, message: String| {
+        issues.push(SceneTemplateLintIssue {
+            template: template.name.clone(),
+            line: line.map(|l| l as i32),
+            severity: if code == "UNUSED_PARAMETER" {
+                ErrorSeverity::Warning
+            } else {
+                ErrorSeverity::Error
+            },
+            code: code.to_string(),
+            message,
+        })
+    };
+    let file = match read_declarations(ctx, template) {
+        Ok(file) => file,
+        Err(error) => {
+            let line = error.location.as_ref().and_then(|l| l.line);
+            issue(
+                line.map(|l| l as usize),
+                "INVALID_DECLARATIONS",
+                error.message,
+            );
+            return;
+        }
+    };
+
+    // Sample value of each parameter: its default, or a stand-in of its type
+    let mut declared = HashMap::new();
+    for (name, param) in &file.params {
+        let param_type = param.param_type.as_deref();
+        if let Some(param_type) = param_type {
+            if !scene_template::PARAM_TYPES.contains(&param_type) {
+                issue(
+                    None,
+                    "UNKNOWN_TYPE",
+                    format!(
+                        "Parameter '{}' has unknown type '{}' (expected one of {})",
+                        name,
+                        param_type,
+                        scene_template::PARAM_TYPES.join(", ")
+                    ),
+                );
+                declared.insert(name.clone(), None);
+                continue;
+            }
+        }
+        let default = param
+            .default
+            .as_deref()
+            .map(|default| param_value(default, param_type));
+        let sample = match default {
+            Some(Ok(value)) => Some(value),
+            Some(Err(message)) => {
+                issue(
+                    None,
+                    "INVALID_DEFAULT",
+                    format!("Default of parameter '{}': {}", name, message),
+                );
+                param_type.and_then(scene_template::sample_value)
+            }
+            None => param_type.and_then(scene_template::sample_value),
+        };
+        declared.insert(name.clone(), sample);
+    }
+
+    for LintIssue {
+        code,
+        line,
+        message,
+    } in scene_template::lint(&template.text, &declared)
+    {
+        issue(line, code, message);
+    }
+}
+
+/// Instantiated template text for createSceneFromTemplate
+fn instantiate(ctx: &GqlContext, input: &TemplateSceneInput) -> Result<String, String> {
+    let template = list_templates(ctx)
+        .map_err(|e| e.message)?
+        .into_iter()
+        .find(|t| t.name == input.template)
+        .ok_or_else(|| not_found(&input.template).message)?;
+    let file = read_declarations(ctx, &template).map_err(|e| e.message)?;
+
+    let mut values = HashMap::new();
+    for param in input.params.iter().flatten() {
+        let Some(decl) = file.params.get(&param.name) else {
+            return Err(format!(
+                "Template '{}' has no parameter '{}'",
+                template.name, param.name
+            ));
+        };
+        let value = param_value(&param.value, decl.param_type.as_deref())
+            .map_err(|e| format!("Parameter '{}': {}", param.name, e))?;
+        values.insert(param.name.clone(), value);
+    }
+    for (name, decl) in &file.params {
+        if values.contains_key(name) {
+            continue;
+        }
+        let Some(default) = &decl.default else {
+            if is_required(&template, name, decl) {
+                return Err(format!(
+                    "Parameter '{}' of template '{}' is required",
+                    name, template.name
+                ));
+            }
+            continue;
+        };
+        let value = param_value(default, decl.param_type.as_deref())
+            .map_err(|e| format!("Default of parameter '{}': {}", name, e))?;
+        values.insert(name.clone(), value);
+    }
+
+    let content = scene_template::render(&template.text, &values)
+        .map_err(|e| format!("{} line {}: {}", template.path, e.line, e.message))?;
+    GodotScene::parse(&content).map_err(|e| {
+        format!(
+            "Template '{}' produced an invalid scene: {}",
+            template.name, e
+        )
+    })?;
+    Ok(content)
+}
+
+/// Parameter value from Godot text, checked against the declared type.
+/// Strings may be given without quotes.
+fn param_value(text: &str, param_type: Option<&str>) -> Result<Value, String> {
+    let parsed = expression::parse_value(text.trim());
+    let Some(param_type) = param_type else {
+        return parsed.map_err(|e| e.to_string());
+    };
+    match parsed {
+        Ok(value) if scene_template::matches_type(&value, param_type) => Ok(value),
+        _ if param_type == "String" => Ok(Value::Str(text.to_string())),
+        Ok(value) => Err(format!("{} is not a {}", value.to_godot(), param_type)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Templates in [`TEMPLATES_DIR`], by name
+fn list_templates(ctx: &GqlContext) -> Result<Vec<Template>, Box<GqlStructuredError>> {
+    let dir = path_utils::to_fs_path_unchecked(&ctx.project_path, TEMPLATES_DIR);
+    if !ctx.fs.is_dir(&dir) {
+        return Ok(Vec::new());
+    }
+    let read_error = |e: std::io::Error| {
+        Box::new(GqlStructuredError::new(
+            "FILE_READ_ERROR",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read {}: {}", TEMPLATES_DIR, e),
+        ))
+    };
+    let mut templates = Vec::new();
+    for path in ctx.fs.read_dir(&dir).map_err(read_error)? {
+        if path.extension().is_none_or(|ext| ext != "tscn") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        templates.push(Template {
+            name: name.to_string(),
+            path: format!("{}/{}.tscn", TEMPLATES_DIR, name),
+            text: ctx.fs.read_to_string(&path).map_err(read_error)?,
+        });
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// The template's `<name>.toml` sidecar; no parameters when there is none
+fn read_declarations(
+    ctx: &GqlContext,
+    template: &Template,
+) -> Result<TemplateFile, Box<GqlStructuredError>> {
+    let toml_path = format!("{}/{}.toml", TEMPLATES_DIR, template.name);
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, &toml_path);
+    if !ctx.fs.exists(&path) {
+        return Ok(TemplateFile::default());
+    }
+    let content = ctx.fs.read_to_string(&path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "FILE_READ_ERROR",
+            GqlErrorCategory::FileSystem,
+            format!("Failed to read {}: {}", toml_path, e),
+        ))
+    })?;
+    toml::from_str(&content).map_err(|e| {
+        let mut error = GqlStructuredError::new(
+            "TEMPLATE_DECLARATIONS_INVALID",
+            GqlErrorCategory::Validation,
+            e.message().to_string(),
+        );
+        error.location = Some(GqlErrorLocation {
+            file: Some(toml_path.clone()),
+            line: e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() as i32 + 1),
+            column: None,
+        });
+        Box::new(error)
+    })
+}
+
+fn template_info(template: &Template, file: &TemplateFile) -> SceneTemplate {
+    SceneTemplate {
+        name: template.name.clone(),
+        path: template.path.clone(),
+        description: file.description.clone(),
+        params: file
+            .params
+            .iter()
+            .map(|(name, param)| SceneTemplateParam {
+                name: name.clone(),
+                param_type: param.param_type.clone(),
+                default: param.default.clone(),
+                required: is_required(template, name, param),
+                description: param.description.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// Whether a parameter needs a value: no declared default, and read by a
+/// placeholder without a `default:` filter
+fn is_required(template: &Template, name: &str, param: &ParamDecl) -> bool {
+    param.default.is_none()
+        && scene_template::placeholders(&template.text)
+            .unwrap_or_default()
+            .iter()
+            .any(|placeholder| {
+                placeholder.default.is_none()
+                    && expression::property_names(&placeholder.expression)
+                        .is_ok_and(|names| names.iter().any(|n| n == name))
+            })
+}
+
+fn not_found(name: &str) -> Box<GqlStructuredError> {
+    Box::new(
+        GqlStructuredError::new(
+            "TEMPLATE_NOT_FOUND",
+            GqlErrorCategory::Validation,
+            format!("Scene template '{}' not found in {}", name, TEMPLATES_DIR),
+        )
+        .with_suggestion("sceneTemplates でテンプレート名を確認してください"),
+    )
+}
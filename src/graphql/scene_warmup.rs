@@ -0,0 +1,137 @@
+//! Scene Warm-up
+//!
+//! Follows the scenes opened in the editor and pre-warms the server's caches
+//! for them in the background: the scene, every scene and script it depends
+//! on (transitively) are parsed into the [`ProjectIndex`], and the dependency
+//! graph cache is brought up to date, so queries about the scene the user is
+//! editing are answered from memory.
+//!
+//! The plugin records scene open/close events with sequence numbers
+//! (`get_scene_events`); [`spawn`] polls them while the server runs.
+//!
+//! [`ProjectIndex`]: super::project_index::ProjectIndex
+
+use std::collections::{BTreeSet, VecDeque};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::dependency_cache;
+use super::dependency_resolver::file_dependencies;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+
+/// Delay between polls while the editor is reachable
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Delay between polls while it is not
+const OFFLINE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Files parsed while warming one scene
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarmedScene {
+    /// res:// path of the opened scene
+    pub path: String,
+    /// res:// paths of the scene and its scene/script dependencies
+    pub files: Vec<String>,
+}
+
+/// Position in the plugin's scene event stream
+#[derive(Debug, Default)]
+pub struct SceneEventCursor {
+    /// Sequence number of the last event seen; None before the first poll
+    since: Option<i64>,
+}
+
+/// Poll the plugin on a background task, warming each scene opened in the
+/// editor (and, on the first poll, the scene being edited)
+pub fn spawn(ctx: GqlContext) {
+    if ctx.fs.is_virtual() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut cursor = SceneEventCursor::default();
+        loop {
+            let interval = match warm_opened_scenes(&ctx, &mut cursor).await {
+                Some(warmed) => {
+                    for scene in warmed {
+                        tracing::debug!("Warmed {} ({} files)", scene.path, scene.files.len());
+                    }
+                    POLL_INTERVAL
+                }
+                None => OFFLINE_INTERVAL,
+            };
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+/// Fetch the scene events after `cursor` and warm the opened scenes; None
+/// when the plugin cannot be reached
+pub async fn warm_opened_scenes(
+    ctx: &GqlContext,
+    cursor: &mut SceneEventCursor,
+) -> Option<Vec<WarmedScene>> {
+    let command = GodotLiveCommand::GetSceneEvents {
+        since: cursor.since,
+    };
+    let value = execute_live_command(ctx, command).await.ok()?;
+    let last_seq = value.get("last_seq").and_then(Value::as_i64)?;
+
+    let mut opened = Vec::new();
+    if cursor.since.is_none() {
+        // Scenes opened before the server started
+        opened.extend(value["current_scene"].as_str().map(str::to_string));
+    }
+    for event in value["events"].as_array().into_iter().flatten() {
+        if event["event"] == "opened" {
+            opened.extend(event["path"].as_str().map(str::to_string));
+        }
+    }
+    cursor.since = Some(last_seq);
+
+    // Switching back and forth between tabs reports a scene more than once
+    let mut seen = BTreeSet::new();
+    opened.retain(|path| path.ends_with(".tscn") && seen.insert(path.clone()));
+    if opened.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let warm_ctx = ctx.clone();
+    tokio::task::spawn_blocking(move || {
+        let warmed = opened
+            .iter()
+            .map(|path| warm_scene(&warm_ctx, path))
+            .collect();
+        dependency_cache::graph(&warm_ctx);
+        warmed
+    })
+    .await
+    .ok()
+}
+
+/// Parse a scene and everything it depends on into the project index
+pub fn warm_scene(ctx: &GqlContext, res_path: &str) -> WarmedScene {
+    let mut files = Vec::new();
+    let mut visited = BTreeSet::new();
+    let mut queue = VecDeque::from([res_path.to_string()]);
+    while let Some(path) = queue.pop_front() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &path);
+        let Some(deps) = file_dependencies(&ctx.index, &fs_path) else {
+            continue;
+        };
+        if path.ends_with(".gd") {
+            ctx.index.script(&fs_path);
+        }
+        queue.extend(deps.references.into_iter().map(|(to, _)| to));
+        files.push(path);
+    }
+    WarmedScene {
+        path: res_path.to_string(),
+        files,
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Implements Query and Mutation roots as defined in `docs/gql/schema.graphql`.
 
-use async_graphql::{Context, EmptySubscription, Object, Schema};
+use async_graphql::{Context, Object, Schema, Subscription};
+use futures_util::Stream;
 
 use super::codegen_resolver;
 use super::context::GqlContext;
@@ -10,6 +11,7 @@ use super::dependency_resolver;
 use super::live_resolver;
 use super::refactoring_resolver;
 use super::resolver;
+use super::scene_lock;
 use super::shader_resolver;
 use super::types::*;
 
@@ -18,10 +20,81 @@ pub struct QueryRoot;
 
 #[Object]
 impl QueryRoot {
-    /// Get project information
-    async fn project(&self, ctx: &Context<'_>) -> Project {
+    /// Schema version (semver) and the deprecated fields still served
+    async fn schema_version(&self) -> SchemaVersion {
+        resolver::resolve_schema_version()
+    }
+
+    /// Schema changes released after `sinceVersion` (all when omitted), so
+    /// clients can detect breaking changes since the version they target
+    async fn changelog(&self, since_version: Option<String>) -> SchemaChangelog {
+        resolver::resolve_changelog(since_version.as_deref())
+    }
+
+    /// Get project information; `tags` limits the scene and script lists to matching files
+    async fn project(&self, ctx: &Context<'_>, tags: Option<FileTagFilter>) -> Project {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::filter_project(gql_ctx, resolver::resolve_project(gql_ctx), tags.as_ref())
+    }
+
+    /// project.godot settings by section; `prefix` limits them to paths
+    /// starting with it (e.g. "display/window")
+    async fn project_settings(
+        &self,
+        ctx: &Context<'_>,
+        prefix: Option<String>,
+    ) -> ProjectSettingsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_project_settings(gql_ctx, prefix.as_deref())
+    }
+
+    /// Input actions saved in project.godot, with their key, mouse and gamepad events
+    async fn list_input_actions(&self, ctx: &Context<'_>) -> Vec<InputAction> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_list_input_actions(gql_ctx)
+    }
+
+    /// Export presets in export_presets.cfg, with their exporter options
+    async fn export_presets(&self, ctx: &Context<'_>) -> ExportPresetsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_export_presets(gql_ctx)
+    }
+
+    /// Godot executable editor tools run, how it was found, and the version
+    /// pinned in .godot-version
+    async fn godot_binary(&self, ctx: &Context<'_>) -> GodotBinaryResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_godot_binary(gql_ctx)
+    }
+
+    /// Global bounds of a scene's nodes measured by a headless Godot (Rect2
+    /// for 2D and controls, AABB for 3D), cached until the scene or its
+    /// dependencies change, with the pairs of nodes that overlap
+    async fn node_bounds(
+        &self,
+        ctx: &Context<'_>,
+        path: String,
+        #[graphql(default = false)] refresh: bool,
+    ) -> NodeBoundsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_node_bounds(gql_ctx, &path, refresh)
+    }
+
+    /// User-defined scene templates in res://.godot-mcp/templates, with their parameters
+    async fn scene_templates(&self, ctx: &Context<'_>) -> SceneTemplatesResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_scene_templates(gql_ctx)
+    }
+
+    /// Check scene templates: parameters used but not declared (or declared
+    /// but unused), bad types and defaults, and expressions that don't evaluate
+    async fn lint_scene_templates(
+        &self,
+        ctx: &Context<'_>,
+        name: Option<String>,
+    ) -> SceneTemplateLintResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
-        resolver::resolve_project(gql_ctx)
+        resolver::resolve_lint_scene_templates(gql_ctx, name.as_deref())
     }
 
     /// Get scene file contents
@@ -36,12 +109,151 @@ impl QueryRoot {
         resolver::resolve_script(gql_ctx, &path)
     }
 
+    /// Get resource file (.tres) contents
+    async fn resource(&self, ctx: &Context<'_>, path: String) -> Option<Resource> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_resource(gql_ctx, &path)
+    }
+
+    /// JSON schema of the patches accepted by `applyScenePatch`
+    async fn scene_patch_schema(&self) -> async_graphql::Json<serde_json::Value> {
+        async_graphql::Json(resolver::resolve_scene_patch_schema())
+    }
+
+    /// Changelog of a session's file edits (default: the latest session in the audit log)
+    async fn session_report(&self, ctx: &Context<'_>, session_id: Option<String>) -> SessionReport {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_session_report(gql_ctx, session_id.as_deref())
+    }
+
+    /// List project checkpoints, oldest first
+    async fn checkpoints(&self, ctx: &Context<'_>) -> Vec<Checkpoint> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_checkpoints(gql_ctx)
+    }
+
+    /// List backups taken before file writes, oldest first
+    async fn list_backups(&self, ctx: &Context<'_>) -> Vec<Backup> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_list_backups(gql_ctx)
+    }
+
+    /// Server version, session and the interrupted multi-file operations
+    /// recovered from the write-ahead journal when the project was opened
+    async fn server_diagnostics(&self, ctx: &Context<'_>) -> ServerDiagnostics {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_server_diagnostics(gql_ctx)
+    }
+
+    /// Read-only project state at a checkpoint (null for unknown ids)
+    async fn project_at(
+        &self,
+        ctx: &Context<'_>,
+        checkpoint_id: String,
+    ) -> Option<ProjectSnapshot> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_project_at(gql_ctx, &checkpoint_id)
+    }
+
+    /// Read-only view of the project on another git branch, tag or commit,
+    /// read with git plumbing (the working directory is not touched)
+    async fn branch_view(&self, ctx: &Context<'_>, git_ref: String) -> BranchView {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_branch_view(gql_ctx, &git_ref)
+    }
+
+    /// Get agent metadata (metadata/mcp_*) stored on scene nodes
+    async fn node_metadata(
+        &self,
+        ctx: &Context<'_>,
+        scene_path: String,
+        node_path: Option<String>,
+    ) -> Vec<NodeMetadataEntry> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_node_metadata(gql_ctx, &scene_path, node_path.as_deref())
+    }
+
+    /// Copy a node and its descendants as a token for pasteSubtree
+    async fn copy_subtree(
+        &self,
+        ctx: &Context<'_>,
+        scene_path: String,
+        node_path: String,
+    ) -> CopiedSubtree {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::copy_subtree(gql_ctx, &scene_path, &node_path)
+    }
+
+    /// Get editor-side state (open/unsaved/locked) of a scene file (live)
+    async fn scene_lock_status(&self, ctx: &Context<'_>, path: String) -> SceneLockStatus {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        scene_lock::resolve_scene_lock_status(gql_ctx, &path).await
+    }
+
+    /// Compare the scene being edited with its file on disk (live)
+    async fn live_scene_drift(&self, ctx: &Context<'_>, path: String) -> LiveSceneDrift {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_live_scene_drift(gql_ctx, &path).await
+    }
+
+    /// Connection to the editor plugin and per-command latency (live)
+    async fn live_channel(&self, ctx: &Context<'_>) -> LiveChannelStatus {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_live_channel(gql_ctx).await
+    }
+
+    /// Editors liveInstancesQuery runs against: this server's own and the
+    /// config file's `[[editors]]`
+    async fn editor_instances(&self, ctx: &Context<'_>) -> Vec<EditorInstance> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_editor_instances(gql_ctx)
+    }
+
+    /// Run a read-only query against every editor instance (or the named ones)
+    /// in parallel, with the result per instance (live)
+    async fn live_instances_query(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        instances: Option<Vec<String>>,
+    ) -> LiveInstancesResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_live_instances_query(gql_ctx, &query, instances.as_deref()).await
+    }
+
+    /// Whether the editor is running the game (F5/F6) and which scene (live)
+    async fn play_state(&self, ctx: &Context<'_>) -> PlayState {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_play_state(gql_ctx).await
+    }
+
+    /// Editor settings by name or path prefix; common ones when neither is given (live)
+    async fn editor_settings(
+        &self,
+        ctx: &Context<'_>,
+        names: Option<Vec<String>>,
+        prefix: Option<String>,
+    ) -> EditorSettings {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_editor_settings(gql_ctx, names, prefix).await
+    }
+
     /// Get current scene in editor (live)
     async fn current_scene(&self, ctx: &Context<'_>) -> Option<LiveScene> {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         live_resolver::resolve_current_scene(gql_ctx).await
     }
 
+    /// Capture an editor viewport with highlighted nodes (live)
+    async fn editor_screenshot(
+        &self,
+        ctx: &Context<'_>,
+        input: EditorScreenshotInput,
+    ) -> EditorScreenshotResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_editor_screenshot(gql_ctx, &input).await
+    }
+
     /// Get node details (live)
     async fn node(&self, ctx: &Context<'_>, path: String) -> Option<LiveNode> {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
@@ -53,6 +265,24 @@ impl QueryRoot {
         resolver::resolve_node_type_info(&type_name)
     }
 
+    /// Script functions recorded by startProfiling, slowest first; addons are
+    /// left out unless `includeAddons` (live)
+    async fn profile_report(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i32,
+        #[graphql(default = false)] include_addons: bool,
+    ) -> ProfileReport {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_profile_report(gql_ctx, limit, include_addons).await
+    }
+
+    /// Editor icons and categories of node classes, for drawing scene trees
+    async fn node_class_icons(&self, ctx: &Context<'_>, types: Vec<String>) -> NodeClassIcons {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_node_class_icons(gql_ctx, types).await
+    }
+
     /// Gather context from entry point (index-chan inspired)
     async fn gather_context(
         &self,
@@ -63,6 +293,18 @@ impl QueryRoot {
         dependency_resolver::resolve_gather_context(gql_ctx, input)
     }
 
+    /// Pack the context around entry points into one bundle under a token budget
+    async fn pack_context(
+        &self,
+        ctx: &Context<'_>,
+        entry_points: Vec<String>,
+        token_budget: i32,
+        strategy: Option<PackStrategy>,
+    ) -> PackedContext {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_pack_context(gql_ctx, entry_points, token_budget, strategy)
+    }
+
     /// Get project dependency graph
     async fn dependency_graph(
         &self,
@@ -73,6 +315,192 @@ impl QueryRoot {
         dependency_resolver::resolve_dependency_graph(gql_ctx, input)
     }
 
+    /// Version of the dependency graph; changes whenever the graph does
+    async fn dependency_graph_version(&self, ctx: &Context<'_>) -> String {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        dependency_resolver::resolve_dependency_graph_version(gql_ctx)
+    }
+
+    /// Signal connections and event bus traffic as a DOT or MERMAID diagram
+    async fn signal_flow_graph(
+        &self,
+        ctx: &Context<'_>,
+        scene_path: Option<String>,
+        format: DiagramFormat,
+    ) -> SignalFlowGraph {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_signal_flow_graph(gql_ctx, scene_path.as_deref(), format)
+    }
+
+    /// Analyze preload vs load references and the startup closure of the main scene
+    async fn preload_analysis(
+        &self,
+        ctx: &Context<'_>,
+        entry_point: Option<String>,
+    ) -> PreloadAnalysis {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        dependency_resolver::resolve_preload_analysis(gql_ctx, entry_point)
+    }
+
+    /// Get a compact machine-readable project manifest
+    async fn project_manifest(&self, ctx: &Context<'_>) -> ProjectManifest {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_project_manifest(gql_ctx)
+    }
+
+    /// Get manifest changes since a previous manifest/delta
+    async fn manifest_delta(
+        &self,
+        ctx: &Context<'_>,
+        since: String,
+        known_paths: Option<Vec<String>>,
+    ) -> ManifestDelta {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_manifest_delta(gql_ctx, &since, known_paths)
+    }
+
+    /// Propose a folder layout for the project
+    async fn suggest_project_layout(
+        &self,
+        ctx: &Context<'_>,
+        convention: LayoutConvention,
+    ) -> ProjectLayoutPlan {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_suggest_project_layout(gql_ctx, convention)
+    }
+
+    /// Get activity items queued for the editor dock
+    async fn editor_activity(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] since: i32,
+    ) -> Vec<ActivityItem> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_editor_activity(gql_ctx, since)
+    }
+
+    /// Owners and area tags of files (.godot-mcp/owners.toml and scene root metadata)
+    async fn file_tags(&self, ctx: &Context<'_>, filter: Option<FileTagFilter>) -> FileTagsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_file_tags(gql_ctx, filter.as_ref())
+    }
+
+    /// Decisions recorded on a file or folder, including those on the folders
+    /// containing it (all decisions without a path)
+    async fn decisions(
+        &self,
+        ctx: &Context<'_>,
+        path: Option<String>,
+        #[graphql(default = false)] include_superseded: bool,
+    ) -> Vec<Decision> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_decisions(gql_ctx, path.as_deref(), include_superseded)
+    }
+
+    /// Check scene invariants declared in .godot-mcp/contracts.toml
+    async fn check_contracts(&self, ctx: &Context<'_>) -> ContractCheckResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_check_contracts(gql_ctx)
+    }
+
+    /// Nodes across scenes whose property matches a regex or a comparison
+    /// (`> 5`, `== false`); only values written in the scene are seen
+    async fn find_property_values(
+        &self,
+        ctx: &Context<'_>,
+        property: String,
+        value_pattern: Option<String>,
+        scene_pattern: Option<String>,
+        node_type: Option<String>,
+        tags: Option<FileTagFilter>,
+    ) -> PropertySearchResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_find_property_values(
+            gql_ctx,
+            &property,
+            value_pattern.as_deref(),
+            scene_pattern.as_deref(),
+            node_type.as_deref(),
+            tags.as_ref(),
+        )
+    }
+
+    /// Which physics layers collide with which, from the collision layers and
+    /// masks of the bodies and areas in every scene
+    async fn collision_matrix(
+        &self,
+        ctx: &Context<'_>,
+        space: Option<PhysicsSpace>,
+        scene_pattern: Option<String>,
+    ) -> CollisionMatrix {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_collision_matrix(gql_ctx, space, scene_pattern.as_deref())
+    }
+
+    /// Meshes without visibility ranges, shadow lights and GPU particles over
+    /// budget per scene, with a mutation plan fixing them
+    async fn performance_audit(
+        &self,
+        ctx: &Context<'_>,
+        scene_pattern: Option<String>,
+        options: Option<PerformanceAuditOptions>,
+    ) -> PerformanceAudit {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_performance_audit(gql_ctx, scene_pattern.as_deref(), options.as_ref())
+    }
+
+    /// FPS, frame time, draw calls and memory from the editor's performance
+    /// monitors, averaged over `durationMs` when given (live)
+    async fn performance_metrics(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] duration_ms: i32,
+        #[graphql(default = 100)] interval_ms: i32,
+    ) -> PerformanceMetrics {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_performance_metrics(gql_ctx, duration_ms, interval_ms).await
+    }
+
+    /// Custom resources under a directory as one CSV or JSON table
+    async fn export_resources_to_table(
+        &self,
+        ctx: &Context<'_>,
+        directory: String,
+        format: Option<ResourceTableFormat>,
+    ) -> ResourceTable {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        let format = format.unwrap_or(ResourceTableFormat::Csv);
+        resolver::resolve_export_resources_to_table(gql_ctx, &directory, format)
+    }
+
+    /// Scored project health report; `record: true` appends a checkpoint for trend tracking
+    async fn project_health(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] record: bool,
+    ) -> ProjectHealth {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_project_health(gql_ctx, record)
+    }
+
+    /// Image dimensions, audio duration or mesh vertex counts of a binary asset
+    async fn resource_preview(&self, ctx: &Context<'_>, path: String) -> Option<ResourcePreview> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_resource_preview(gql_ctx, &path)
+    }
+
+    /// Audio files (WAV, Ogg Vorbis, MP3) with their import settings
+    async fn audio_assets(&self, ctx: &Context<'_>, folder: Option<String>) -> Vec<AudioAsset> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_audio_assets(gql_ctx, folder.as_deref())
+    }
+
+    /// Feature packs installed into the project
+    async fn feature_packs(&self, ctx: &Context<'_>) -> Vec<InstalledFeaturePack> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_feature_packs(gql_ctx)
+    }
+
     // ========== Debugging (Phase 2) ==========
 
     /// Get debugger errors
@@ -82,11 +510,23 @@ impl QueryRoot {
     }
 
     /// Get logs
+    #[graphql(deprecation = "Use the editorLog subscription")]
     async fn logs(&self, ctx: &Context<'_>, #[graphql(default = 100)] limit: i32) -> Vec<LogEntry> {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         live_resolver::resolve_logs(gql_ctx, limit).await
     }
 
+    /// Exit code, output tails, GDScript stack traces and crash detection of
+    /// the last game run started by run_project (null if never run)
+    async fn last_run_report(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 50)] tail_lines: i32,
+    ) -> Option<RunReport> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_last_run_report(gql_ctx, tail_lines)
+    }
+
     /// Get object by ID
     async fn object_by_id(&self, ctx: &Context<'_>, object_id: String) -> Option<GodotObject> {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
@@ -96,6 +536,7 @@ impl QueryRoot {
     // ========== Phase 3: Debug Enhanced ==========
 
     /// Get parse errors from a script (live)
+    #[graphql(deprecation = "Use the checkScript mutation")]
     async fn parse_errors(&self, ctx: &Context<'_>, script_path: String) -> Vec<ParseError> {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         live_resolver::resolve_parse_errors(gql_ctx, script_path).await
@@ -135,6 +576,29 @@ impl QueryRoot {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         refactoring_resolver::resolve_autoloads(gql_ctx)
     }
+
+    /// GDScript functions never called and signals never emitted or connected,
+    /// across scripts and scene connections (engine callbacks excluded)
+    async fn dead_code(
+        &self,
+        ctx: &Context<'_>,
+        scope: Option<String>,
+        #[graphql(default_with = "DeadCodeConfidence::Low")] min_confidence: DeadCodeConfidence,
+    ) -> DeadCodeReport {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_dead_code(gql_ctx, scope.as_deref(), min_confidence)
+    }
+
+    /// Lint scripts with gdlint, or the built-in checks when it is not installed
+    async fn gdlint(
+        &self,
+        ctx: &Context<'_>,
+        path: Option<String>,
+        tags: Option<FileTagFilter>,
+    ) -> LintResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_gdlint(gql_ctx, path.as_deref(), tags.as_ref())
+    }
 }
 
 /// GraphQL Mutation Root
@@ -149,13 +613,53 @@ impl MutationRoot {
         resolver::create_scene(gql_ctx, &input)
     }
 
-    async fn create_scene_from_template(&self, _input: TemplateSceneInput) -> SceneResult {
-        // TODO: Implement resolver
-        SceneResult {
-            success: false,
-            scene: None,
-            message: Some("Not implemented".to_string()),
-        }
+    /// Create a scene from a user-defined template, evaluating its
+    /// `{{expression}}` placeholders with the given parameters
+    async fn create_scene_from_template(
+        &self,
+        ctx: &Context<'_>,
+        input: TemplateSceneInput,
+    ) -> SceneResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_create_scene_from_template(gql_ctx, &input)
+    }
+
+    /// Generate a small playable project (scenes, scripts, a custom resource,
+    /// input map, main scene) in an empty folder through the same resolvers
+    /// as the other mutations, and report its projectHealth; a relative path
+    /// is resolved next to the current project
+    async fn generate_example_project(
+        &self,
+        ctx: &Context<'_>,
+        kind: ExampleProjectKind,
+        path: String,
+    ) -> ExampleProjectResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_generate_example_project(gql_ctx, kind, &path).await
+    }
+
+    /// Render scenes (all of the project's when omitted) to PNG thumbnails
+    /// of at most `size` pixels under res://.godot-mcp/thumbnails with Godot;
+    /// Scene.thumbnail returns them
+    async fn generate_thumbnails(
+        &self,
+        ctx: &Context<'_>,
+        scenes: Option<Vec<String>>,
+        #[graphql(default = 256)] size: i32,
+    ) -> GenerateThumbnailsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_generate_thumbnails(gql_ctx, scenes.as_deref(), size)
+    }
+
+    /// Set the Godot executable for this session, optionally pinning its
+    /// version in .godot-version
+    async fn set_godot_path(
+        &self,
+        ctx: &Context<'_>,
+        input: SetGodotPathInput,
+    ) -> GodotBinaryResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_set_godot_path(gql_ctx, &input)
     }
 
     async fn create_script(&self, ctx: &Context<'_>, input: CreateScriptInput) -> ScriptResult {
@@ -163,6 +667,235 @@ impl MutationRoot {
         resolver::create_script(gql_ctx, &input)
     }
 
+    /// Create a .tres instance of a custom Resource script, with values
+    /// checked against its exported vars
+    async fn create_custom_resource(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateCustomResourceInput,
+    ) -> CustomResourceResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_create_custom_resource(gql_ctx, &input)
+    }
+
+    /// Write the rows of an exported resource table back to .tres files,
+    /// skipping rows that do not validate
+    async fn import_resources_from_table(
+        &self,
+        ctx: &Context<'_>,
+        file: String,
+        target_directory: String,
+    ) -> ResourceTableImport {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_import_resources_from_table(gql_ctx, &file, &target_directory)
+    }
+
+    /// Record a design decision on a file or folder (.godot-mcp/decisions.json)
+    async fn record_decision(
+        &self,
+        ctx: &Context<'_>,
+        path: String,
+        decision: String,
+        rationale: String,
+        supersedes: Option<String>,
+    ) -> RecordDecisionResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_record_decision(
+            gql_ctx,
+            &path,
+            &decision,
+            &rationale,
+            supersedes.as_deref(),
+        )
+    }
+
+    async fn set_node_metadata(
+        &self,
+        ctx: &Context<'_>,
+        input: SetNodeMetadataInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::set_node_metadata(gql_ctx, &input).await
+    }
+
+    /// Report multiple roots, orphans and duplicate nodes, applying the given fixes
+    async fn repair_scene(
+        &self,
+        ctx: &Context<'_>,
+        path: String,
+        #[graphql(default)] fixes: Vec<SceneRepairFix>,
+        policy: Option<SceneWritePolicy>,
+    ) -> SceneRepairResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::repair_scene(
+            gql_ctx,
+            &path,
+            &fixes,
+            policy.unwrap_or(SceneWritePolicy::Refuse),
+        )
+        .await
+    }
+
+    /// Validate a declarative patch of one scene and apply all of its operations, or none
+    async fn apply_scene_patch(
+        &self,
+        ctx: &Context<'_>,
+        path: String,
+        patch: async_graphql::Json<serde_json::Value>,
+        #[graphql(default)] dry_run: bool,
+        policy: Option<SceneWritePolicy>,
+    ) -> ApplyScenePatchResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::apply_scene_patch(
+            gql_ctx,
+            &path,
+            &patch.0,
+            dry_run,
+            policy.unwrap_or(SceneWritePolicy::Refuse),
+        )
+        .await
+    }
+
+    /// Add a signal connection to a scene file
+    async fn connect_signal_in_scene(
+        &self,
+        ctx: &Context<'_>,
+        input: ConnectSignalInSceneInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::connect_signal_in_scene(gql_ctx, &input).await
+    }
+
+    /// Move a node and its descendants under a different parent in a scene file
+    async fn reparent_node_in_scene(
+        &self,
+        ctx: &Context<'_>,
+        input: ReparentNodeInSceneInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::reparent_node_in_scene(gql_ctx, &input).await
+    }
+
+    /// Rename a node in a scene file, updating child paths, NodePaths and connections
+    async fn rename_node(&self, ctx: &Context<'_>, input: RenameNodeInput) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::rename_node(gql_ctx, &input).await
+    }
+
+    /// Duplicate a node and its descendants in a scene file
+    async fn duplicate_node_in_scene(
+        &self,
+        ctx: &Context<'_>,
+        input: DuplicateNodeInSceneInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::duplicate_node_in_scene(gql_ctx, &input).await
+    }
+
+    /// Paste a subtree copied by copySubtree under a node of a scene file
+    async fn paste_subtree(&self, ctx: &Context<'_>, input: PasteSubtreeInput) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::paste_subtree(gql_ctx, &input).await
+    }
+
+    /// Rewrite animation track paths of an AnimationPlayer (scene file, or live when no scenePath)
+    async fn remap_animation_tracks(
+        &self,
+        ctx: &Context<'_>,
+        input: RemapAnimationTracksInput,
+    ) -> RemapAnimationTracksResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_remap_animation_tracks(gql_ctx, &input).await
+    }
+
+    /// Evaluate an expression over node properties and write the result to a node
+    async fn compute_property(
+        &self,
+        ctx: &Context<'_>,
+        input: ComputePropertyInput,
+        policy: Option<SceneWritePolicy>,
+    ) -> ComputePropertyResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::compute_property(gql_ctx, &input, policy.unwrap_or(SceneWritePolicy::Refuse))
+            .await
+    }
+
+    /// Add one templated node or scene instance per row of a CSV/JSON data table
+    async fn populate_from_data(
+        &self,
+        ctx: &Context<'_>,
+        input: PopulateFromDataInput,
+    ) -> PopulateFromDataResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::populate_from_data(gql_ctx, &input).await
+    }
+
+    /// Add a CollisionShape fitted to a MeshInstance3D's mesh or a Sprite2D's texture
+    async fn generate_collision_shape(
+        &self,
+        ctx: &Context<'_>,
+        input: GenerateCollisionShapeInput,
+    ) -> GenerateCollisionShapeResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::generate_collision_shape(gql_ctx, &input).await
+    }
+
+    /// Make two physics layers collide (or not) by editing the masks of the
+    /// objects on them
+    async fn set_layer_collision(
+        &self,
+        ctx: &Context<'_>,
+        input: SetLayerCollisionInput,
+    ) -> SetLayerCollisionResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_set_layer_collision(gql_ctx, &input).await
+    }
+
+    /// Snapshot scenes, scripts and resources for later `projectAt` queries
+    async fn create_checkpoint(
+        &self,
+        ctx: &Context<'_>,
+        label: Option<String>,
+    ) -> CreateCheckpointResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_create_checkpoint(gql_ctx, label)
+    }
+
+    /// Restore the files of a backup, deleting files its operation created
+    async fn restore_backup(&self, ctx: &Context<'_>, id: String) -> RestoreBackupResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_restore_backup(gql_ctx, &id)
+    }
+
+    /// Find unassigned exported node references and wire them to matching nodes
+    async fn wire_exported_references(
+        &self,
+        ctx: &Context<'_>,
+        input: WireExportedReferencesInput,
+    ) -> WireExportedReferencesResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_wire_exported_references(gql_ctx, &input).await
+    }
+
+    /// Queue an activity item for the editor dock panel
+    async fn notify_editor(
+        &self,
+        ctx: &Context<'_>,
+        message: String,
+        level: Option<NotifyLevel>,
+        kind: Option<ActivityKind>,
+        detail: Option<String>,
+    ) -> NotifyEditorResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_notify_editor(
+            gql_ctx,
+            &message,
+            level.unwrap_or(NotifyLevel::Info),
+            kind.unwrap_or(ActivityKind::Message),
+            detail,
+        )
+    }
+
     // ========== Live operations ==========
 
     async fn add_node(&self, ctx: &Context<'_>, input: AddNodeInput) -> NodeResult {
@@ -170,6 +903,17 @@ impl MutationRoot {
         live_resolver::resolve_add_node(gql_ctx, input).await
     }
 
+    /// Add a node (or scene instance) in front of the 3D editor camera or at
+    /// the center of the 2D viewport
+    async fn add_node_at_cursor(
+        &self,
+        ctx: &Context<'_>,
+        input: AddNodeAtCursorInput,
+    ) -> NodeResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_add_node_at_cursor(gql_ctx, input).await
+    }
+
     async fn remove_node(&self, ctx: &Context<'_>, path: String) -> OperationResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         live_resolver::resolve_remove_node(gql_ctx, path).await
@@ -274,13 +1018,163 @@ impl MutationRoot {
         live_resolver::resolve_open_scene(gql_ctx, path).await
     }
 
+    /// Run the game outside the editor with output captured for
+    /// lastRunReport, like run_project; timeoutSeconds and autoStop keep it
+    /// from outliving the run or the server
+    async fn run_project(&self, ctx: &Context<'_>, input: RunProjectInput) -> RunProjectResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_run_project(gql_ctx, &input)
+    }
+
+    /// Run the project from the editor, like the Play button (F5)
+    async fn play_main_scene(&self, ctx: &Context<'_>) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_play_main_scene(gql_ctx).await
+    }
+
+    /// Run the scene being edited, like the Play Scene button (F6)
+    async fn play_current_scene(&self, ctx: &Context<'_>) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_play_current_scene(gql_ctx).await
+    }
+
+    /// Stop the game run by the editor, like the Stop button (F8)
+    async fn stop_playing_scene(&self, ctx: &Context<'_>) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_stop_playing_scene(gql_ctx).await
+    }
+
+    /// Press (true), release (false) or tap (omitted) an InputMap action in the running game (live)
+    async fn send_input_action(
+        &self,
+        ctx: &Context<'_>,
+        action: String,
+        pressed: Option<bool>,
+        #[graphql(default = 1.0)] strength: f64,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_send_input_action(gql_ctx, action, pressed, strength).await
+    }
+
+    /// Press (true), release (false) or tap (omitted) a key in the running game (live)
+    async fn send_key(
+        &self,
+        ctx: &Context<'_>,
+        key: String,
+        pressed: Option<bool>,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_send_key(gql_ctx, key, pressed).await
+    }
+
+    /// Click at a position of the running game's viewport (live)
+    async fn send_mouse_click(
+        &self,
+        ctx: &Context<'_>,
+        x: f64,
+        y: f64,
+        #[graphql(default = 1)] button: i32,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_send_mouse_click(gql_ctx, x, y, button).await
+    }
+
+    /// Send a scripted sequence of inputs to the running game, for simple playtests (live)
+    async fn play_input_sequence(
+        &self,
+        ctx: &Context<'_>,
+        steps: Vec<InputStepInput>,
+    ) -> InputSequenceResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_play_input_sequence(gql_ctx, steps).await
+    }
+
+    /// Clear and start the editor's script profiler (live)
+    async fn start_profiling(&self, ctx: &Context<'_>) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_start_profiling(gql_ctx).await
+    }
+
+    /// Stop the script profiler; profileReport keeps the recorded data (live)
+    async fn stop_profiling(&self, ctx: &Context<'_>) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_stop_profiling(gql_ctx).await
+    }
+
+    /// Evaluate a GDScript expression on a node of the running game (live)
+    async fn eval_expression(
+        &self,
+        ctx: &Context<'_>,
+        node_path: String,
+        expression: String,
+    ) -> EvalResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_eval_expression(gql_ctx, node_path, expression).await
+    }
+
+    /// Select a node and point the 2D or 3D editor view at it (live)
+    async fn frame_node(&self, ctx: &Context<'_>, node_path: String) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_frame_node(gql_ctx, node_path).await
+    }
+
+    /// Save the 2D/3D editor viewport or the running game to a PNG (live)
+    async fn capture_viewport(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "ScreenshotViewport::Viewport2D")] viewport: ScreenshotViewport,
+        #[graphql(default = false)] inline: bool,
+    ) -> ViewportCapture {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_capture_viewport(gql_ctx, viewport, inline).await
+    }
+
+    /// Place the 3D editor camera at a position, facing a point (live)
+    async fn set_editor_camera(
+        &self,
+        ctx: &Context<'_>,
+        input: SetEditorCameraInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_set_editor_camera(gql_ctx, input).await
+    }
+
+    /// Switch the editor's main screen: 2D, 3D, Script or AssetLib (live)
+    async fn set_main_screen(&self, ctx: &Context<'_>, screen: String) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_set_main_screen(gql_ctx, screen).await
+    }
+
+    /// Change an editor setting, e.g. the external editor or autosave interval (live)
+    async fn set_editor_setting(
+        &self,
+        ctx: &Context<'_>,
+        input: SetEditorSettingInput,
+    ) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_set_editor_setting(gql_ctx, input).await
+    }
+
     // ========== Development / Testing ==========
 
+    /// Run GdUnit4 or GUT tests with a headless Godot and return the suites
+    /// and cases; runs can be narrowed to a script and test name
     async fn run_tests(&self, ctx: &Context<'_>, input: RunTestsInput) -> TestExecutionResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
         resolver::resolve_run_tests(gql_ctx, &input).await
     }
 
+    /// Line coverage of GDScript files under GUT: runs the tests with the
+    /// scripts instrumented and reports covered and total lines per script
+    async fn run_test_coverage(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] input: TestCoverageInput,
+    ) -> TestCoverageResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_run_test_coverage(gql_ctx, &input)
+    }
+
     // ========== Phase 2.2: Project Settings & Input Map ==========
 
     /// Add an input action to the InputMap
@@ -293,6 +1187,12 @@ impl MutationRoot {
         resolver::resolve_add_input_action(gql_ctx, &input)
     }
 
+    /// Remove an input action from the InputMap
+    async fn remove_input_action(&self, ctx: &Context<'_>, action_name: String) -> OperationResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_remove_input_action(gql_ctx, &action_name)
+    }
+
     /// Set a project setting
     async fn set_project_setting(
         &self,
@@ -303,6 +1203,62 @@ impl MutationRoot {
         resolver::resolve_set_project_setting(gql_ctx, &input)
     }
 
+    /// Add an export preset from a platform template or as a copy of another preset
+    async fn create_export_preset(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateExportPresetInput,
+    ) -> ExportPresetResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_create_export_preset(gql_ctx, &input)
+    }
+
+    /// Change an export preset's name, path, features, filters or options
+    async fn update_export_preset(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateExportPresetInput,
+    ) -> ExportPresetResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_update_export_preset(gql_ctx, &input)
+    }
+
+    /// Set the loop mode and loop points in an audio file's import settings
+    async fn set_audio_loop(
+        &self,
+        ctx: &Context<'_>,
+        input: SetAudioLoopInput,
+    ) -> AudioImportResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_set_audio_loop(gql_ctx, &input)
+    }
+
+    /// Apply import settings to every audio file in a folder
+    async fn apply_audio_import_preset(
+        &self,
+        ctx: &Context<'_>,
+        input: ApplyAudioImportPresetInput,
+    ) -> AudioImportResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_apply_audio_import_preset(gql_ctx, &input)
+    }
+
+    /// Install (or update) a feature pack from a local directory or git URL
+    async fn install_feature_pack(
+        &self,
+        ctx: &Context<'_>,
+        input: InstallFeaturePackInput,
+    ) -> InstallFeaturePackResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_install_feature_pack(gql_ctx, &input)
+    }
+
+    /// Download a remote asset (allowlisted hosts only, size-capped, with a license file)
+    async fn fetch_asset(&self, ctx: &Context<'_>, input: FetchAssetInput) -> FetchAssetResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_fetch_asset(gql_ctx, &input).await
+    }
+
     // ========== Debugging Operations (Phase 2) ==========
 
     async fn pause(&self, ctx: &Context<'_>) -> OperationResult {
@@ -352,27 +1308,28 @@ impl MutationRoot {
 
     async fn apply_mutation(&self, ctx: &Context<'_>, input: ApplyMutationInput) -> ApplyResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
-        resolver::apply_mutation(gql_ctx, &input)
+        resolver::apply_mutation(gql_ctx, &input).await
     }
 
     // ========== Transaction operations ==========
 
-    /// Begin a transaction - groups subsequent operations into a single Undo action
+    /// Begin a transaction - stages subsequent file writes in memory and groups
+    /// live editor operations into a single Undo action
     async fn begin_transaction(&self, ctx: &Context<'_>, name: String) -> TransactionResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
-        live_resolver::resolve_begin_transaction(gql_ctx, name).await
+        resolver::resolve_begin_transaction(gql_ctx, name).await
     }
 
-    /// Commit the current transaction
+    /// Commit the current transaction - writes all staged files, or none if one fails
     async fn commit_transaction(&self, ctx: &Context<'_>) -> TransactionResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
-        live_resolver::resolve_commit_transaction(gql_ctx).await
+        resolver::resolve_commit_transaction(gql_ctx).await
     }
 
     /// Rollback the current transaction - discards all changes
     async fn rollback_transaction(&self, ctx: &Context<'_>) -> TransactionResult {
         let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
-        live_resolver::resolve_rollback_transaction(gql_ctx).await
+        resolver::resolve_rollback_transaction(gql_ctx).await
     }
 
     // ========== Phase 3: Refactoring ==========
@@ -397,6 +1354,19 @@ impl MutationRoot {
         refactoring_resolver::resolve_extract_function(gql_ctx, &input)
     }
 
+    /// Format scripts with gdformat (requires gdtoolkit)
+    async fn gdformat(&self, ctx: &Context<'_>, input: GdformatInput) -> GdformatResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_gdformat(gql_ctx, &input)
+    }
+
+    /// Parse scripts with Godot itself (`--headless --check-only`); path is a
+    /// script or folder
+    async fn check_script(&self, ctx: &Context<'_>, path: String) -> ScriptCheckResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_check_script(gql_ctx, &path)
+    }
+
     /// Move node to a new scene
     async fn move_node_to_scene(
         &self,
@@ -407,6 +1377,39 @@ impl MutationRoot {
         live_resolver::resolve_move_node_to_scene(gql_ctx, input).await
     }
 
+    /// Move a file or directory and update all res:// references
+    async fn move_file(&self, ctx: &Context<'_>, input: MoveFileInput) -> MoveFileResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        refactoring_resolver::resolve_move_file(gql_ctx, &input)
+    }
+
+    /// Rename a file in place and update all res:// references
+    async fn rename_file(&self, ctx: &Context<'_>, input: RenameFileInput) -> MoveFileResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        refactoring_resolver::resolve_rename_file(gql_ctx, &input)
+    }
+
+    /// Reorganize the project folders in checkpointed batches
+    async fn apply_project_layout(
+        &self,
+        ctx: &Context<'_>,
+        input: ApplyProjectLayoutInput,
+    ) -> ApplyProjectLayoutResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_apply_project_layout(gql_ctx, &input)
+    }
+
+    /// Move .import/.uid files whose source asset is gone to the trash and list
+    /// assets missing a .import file
+    async fn clean_import_artifacts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] dry_run: bool,
+    ) -> CleanImportArtifactsResult {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        resolver::resolve_clean_import_artifacts(gql_ctx, dry_run)
+    }
+
     // ========== Phase 3: Code Generation ==========
 
     /// Generate input handler code
@@ -467,19 +1470,56 @@ impl MutationRoot {
     }
 }
 
+/// Subscription root
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Watched project files (.tscn/.gd/.tres) created, modified or deleted on disk,
+    /// including saves from the Godot editor
+    async fn file_changed(
+        &self,
+        ctx: &Context<'_>,
+        extensions: Option<Vec<String>>,
+    ) -> async_graphql::Result<impl Stream<Item = FileChangedEvent>> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        Ok(resolver::resolve_file_changed(gql_ctx, extensions)?)
+    }
+
+    /// Scene files changed on disk, re-parsed after each save
+    async fn scene_changed(
+        &self,
+        ctx: &Context<'_>,
+        path: Option<String>,
+    ) -> async_graphql::Result<impl Stream<Item = SceneChangedEvent>> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        Ok(resolver::resolve_scene_changed(gql_ctx, path)?)
+    }
+
+    /// New editor log lines with parsed severity, polled from the plugin (live)
+    async fn editor_log(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 500)] interval_ms: i32,
+    ) -> impl Stream<Item = LogEntry> {
+        let gql_ctx = ctx.data::<GqlContext>().expect("GqlContext not found");
+        live_resolver::resolve_editor_log(gql_ctx, interval_ms.max(0) as u64)
+    }
+}
+
 /// GQL Schema type alias
-pub type GqlSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+pub type GqlSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 
 /// Build the GraphQL schema with context
 pub fn build_schema_with_context(ctx: GqlContext) -> GqlSchema {
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(ctx)
         .finish()
 }
 
 /// Build the GraphQL schema (for tests without context)
 pub fn build_schema() -> GqlSchema {
-    Schema::build(QueryRoot, MutationRoot, EmptySubscription).finish()
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot).finish()
 }
 
 #[cfg(test)]
@@ -0,0 +1,267 @@
+//! Schema Version Resolver
+//!
+//! schemaVersion and changelog: the schema's semver version and the changes
+//! released under each version, so clients can detect breaking changes and
+//! move off deprecated fields before they are removed.
+//!
+//! Every schema change adds an entry to [`RELEASES`]; deprecations here
+//! mirror the `deprecation` attributes on the fields themselves.
+
+use super::types::*;
+
+/// Current schema version; always the last entry of [`RELEASES`]
+pub const SCHEMA_VERSION: &str = "1.6.0";
+
+/// Major version deprecated fields are removed in
+const NEXT_MAJOR: &str = "2.0.0";
+
+struct Release {
+    version: &'static str,
+    date: &'static str,
+    changes: &'static [Change],
+}
+
+struct Change {
+    kind: SchemaChangeKind,
+    target: &'static str,
+    description: &'static str,
+    breaking: bool,
+    replacement: Option<&'static str>,
+}
+
+/// Schema releases, oldest first
+const RELEASES: &[Release] = &[Release {
+    version: "1.0.0",
+    date: "2026-10-17",
+    changes: &[
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "Query.schemaVersion",
+            description: "Schema version and the deprecated fields still served",
+            breaking: false,
+            replacement: None,
+        },
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "Query.changelog",
+            description: "Schema changes released after a given version",
+            breaking: false,
+            replacement: None,
+        },
+        Change {
+            kind: SchemaChangeKind::Deprecated,
+            target: "Query.logs",
+            description:
+                "Returns the plugin's latest lines on every call, without sequence numbers",
+            breaking: false,
+            replacement: Some("Subscription.editorLog"),
+        },
+        Change {
+            kind: SchemaChangeKind::Deprecated,
+            target: "Query.parseErrors",
+            description: "Needs the editor and returns nothing when it is not connected",
+            breaking: false,
+            replacement: Some("Mutation.checkScript"),
+        },
+    ],
+}, Release {
+    version: "1.1.0",
+    date: "2026-10-17",
+    changes: &[
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "Mutation.runProject",
+            description: "Run the game outside the editor with a timeout and auto-stop",
+            breaking: false,
+            replacement: None,
+        },
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "RunReport.timedOut",
+            description: "Watchdog limits of the run (timeoutSeconds, timedOut, autoStop) and its CPU and memory use while running (cpuPercent, memoryBytes)",
+            breaking: false,
+            replacement: None,
+        },
+    ],
+}, Release {
+    version: "1.2.0",
+    date: "2026-10-17",
+    changes: &[Change {
+        kind: SchemaChangeKind::Added,
+        target: "Mutation.generateExampleProject",
+        description: "Playable example project generated through the mutation pipeline, with its projectHealth",
+        breaking: false,
+        replacement: None,
+    }],
+}, Release {
+    version: "1.3.0",
+    date: "2026-10-17",
+    changes: &[
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "RunTestsInput.framework",
+            description: "GUT runs (framework, scriptName and testName filters), with per-script suites and cases",
+            breaking: false,
+            replacement: None,
+        },
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "TestExecutionResult.error",
+            description: "Framework used (framework), why tests could not run (error) and pending cases (TestCaseResult.skipped)",
+            breaking: false,
+            replacement: None,
+        },
+    ],
+}, Release {
+    version: "1.4.0",
+    date: "2026-10-17",
+    changes: &[Change {
+        kind: SchemaChangeKind::Added,
+        target: "Query.testCoverage",
+        description: "Per-script line coverage of a GUT run, with the lines left uncovered",
+        breaking: false,
+        replacement: None,
+    }],
+}, Release {
+    version: "1.5.0",
+    date: "2026-10-17",
+    changes: &[
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "Mutation.generateThumbnails",
+            description: "PNG thumbnails of scenes rendered by Godot",
+            breaking: false,
+            replacement: None,
+        },
+        Change {
+            kind: SchemaChangeKind::Added,
+            target: "Scene.thumbnail",
+            description: "Thumbnail rendered by generateThumbnails",
+            breaking: false,
+            replacement: None,
+        },
+    ],
+}, Release {
+    version: "1.6.0",
+    date: "2026-10-17",
+    changes: &[Change {
+        kind: SchemaChangeKind::Changed,
+        target: "Query.projectHealth",
+        description: "record defaults to false; the query only records a checkpoint when asked to",
+        breaking: true,
+        replacement: None,
+    }, Change {
+        kind: SchemaChangeKind::Added,
+        target: "Mutation.runTestCoverage",
+        description: "Line coverage of GUT runs, moved from Query.testCoverage since it writes instrumented scripts and launches Godot",
+        breaking: false,
+        replacement: None,
+    }, Change {
+        kind: SchemaChangeKind::Removed,
+        target: "Query.testCoverage",
+        description: "Replaced by Mutation.runTestCoverage",
+        breaking: true,
+        replacement: Some("Mutation.runTestCoverage"),
+    }],
+}];
+
+/// Resolve schemaVersion query
+pub fn resolve_schema_version() -> SchemaVersion {
+    let (major, minor, patch) = parse_version(SCHEMA_VERSION).unwrap_or_default();
+    let removed: Vec<&str> = all_changes()
+        .filter(|change| change.kind == SchemaChangeKind::Removed)
+        .map(|change| change.target)
+        .collect();
+    SchemaVersion {
+        version: SCHEMA_VERSION.to_string(),
+        major: major as i32,
+        minor: minor as i32,
+        patch: patch as i32,
+        deprecations: all_changes()
+            .filter(|change| {
+                change.kind == SchemaChangeKind::Deprecated && !removed.contains(&change.target)
+            })
+            .map(to_schema_change)
+            .collect(),
+    }
+}
+
+/// Resolve changelog query: releases after `since_version` (all when None)
+pub fn resolve_changelog(since_version: Option<&str>) -> SchemaChangelog {
+    let since = match since_version.map(parse_version) {
+        None => None,
+        Some(Some(since)) => Some(since),
+        Some(None) => {
+            return SchemaChangelog {
+                success: false,
+                current_version: SCHEMA_VERSION.to_string(),
+                releases: Vec::new(),
+                has_breaking_changes: false,
+                error: Some(
+                    GqlStructuredError::new(
+                        "INVALID_VERSION",
+                        GqlErrorCategory::Validation,
+                        format!(
+                            "Invalid schema version: {}",
+                            since_version.unwrap_or_default()
+                        ),
+                    )
+                    .with_suggestion("MAJOR.MINOR.PATCH 形式で指定してください（例: 1.0.0）"),
+                ),
+            };
+        }
+    };
+
+    let releases: Vec<SchemaRelease> = RELEASES
+        .iter()
+        .filter(|release| is_after(release, since))
+        .map(|release| SchemaRelease {
+            version: release.version.to_string(),
+            date: release.date.to_string(),
+            changes: release.changes.iter().map(to_schema_change).collect(),
+        })
+        .collect();
+    SchemaChangelog {
+        success: true,
+        current_version: SCHEMA_VERSION.to_string(),
+        has_breaking_changes: releases
+            .iter()
+            .flat_map(|release| &release.changes)
+            .any(|change| change.breaking),
+        releases,
+        error: None,
+    }
+}
+
+/// `MAJOR[.MINOR[.PATCH]]`, missing parts being 0
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+fn is_after(release: &Release, since: Option<(u32, u32, u32)>) -> bool {
+    since.is_none_or(|since| parse_version(release.version).is_some_and(|v| v > since))
+}
+
+/// Every change ever released, oldest first
+fn all_changes() -> impl Iterator<Item = &'static Change> {
+    RELEASES.iter().flat_map(|release| release.changes)
+}
+
+fn to_schema_change(change: &Change) -> SchemaChange {
+    SchemaChange {
+        kind: change.kind,
+        target: change.target.to_string(),
+        description: change.description.to_string(),
+        breaking: change.breaking,
+        replacement: change.replacement.map(str::to_string),
+        removal_version: (change.kind == SchemaChangeKind::Deprecated)
+            .then(|| NEXT_MAJOR.to_string()),
+    }
+}
@@ -0,0 +1,336 @@
+//! Screenshot Resolver
+//!
+//! Captures an editor viewport through the plugin and highlights the requested
+//! nodes, using the screen-space bounds the plugin computes from the live scene
+//! (2D rects, projected 3D AABBs). Boxes default to the editor accent color.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::Value;
+
+use crate::path_utils;
+use crate::screenshot::{parse_hex_color, Canvas, Rect};
+
+use super::context::GqlContext;
+use super::live_resolver::{execute_live_command, GodotLiveCommand};
+use super::types::*;
+
+/// Where annotated screenshots are saved
+pub const SCREENSHOT_DIR: &str = "res://.godot-mcp/screenshots";
+
+/// Screenshots kept in SCREENSHOT_DIR; older ones are deleted
+const MAX_SCREENSHOTS: usize = 20;
+
+/// Godot's default accent color, used when the plugin reports no theme
+const DEFAULT_ACCENT: [u8; 3] = [0x70, 0xba, 0xfa];
+
+/// Resolve editorScreenshot query
+pub async fn resolve_editor_screenshot(
+    ctx: &GqlContext,
+    input: &EditorScreenshotInput,
+) -> EditorScreenshotResult {
+    if input.viewport == ScreenshotViewport::Game {
+        return failure(
+            GqlStructuredError::new(
+                "INVALID_INPUT",
+                GqlErrorCategory::Validation,
+                "editorScreenshot cannot capture the running game",
+            )
+            .with_suggestion("実行中のゲームは captureViewport(viewport: GAME) で撮影してください"),
+        );
+    }
+    let command = GodotLiveCommand::CaptureScreenshot {
+        viewport: viewport_name(input.viewport).to_string(),
+        node_paths: input
+            .annotations
+            .iter()
+            .map(|a| a.node_path.clone())
+            .collect(),
+    };
+
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return failure(e.to_structured_error()),
+    };
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return failure(GqlStructuredError::new(
+            "SCREENSHOT_FAILED",
+            GqlErrorCategory::Godot,
+            message,
+        ));
+    }
+
+    annotate_capture(ctx, input, &value).unwrap_or_else(|e| {
+        failure(GqlStructuredError::new(
+            "SCREENSHOT_INVALID",
+            GqlErrorCategory::Godot,
+            format!("{:#}", e),
+        ))
+    })
+}
+
+/// Resolve captureViewport mutation
+///
+/// Editor viewports come back in the plugin's response. The running game
+/// writes the PNG itself when the plugin forwards the request over the
+/// debugger session, so that file is awaited on disk. The PNG is also
+/// attached to the MCP tool result as an image.
+pub async fn resolve_capture_viewport(
+    ctx: &GqlContext,
+    viewport: ScreenshotViewport,
+    inline: bool,
+) -> ViewportCapture {
+    let failure = |error: GqlStructuredError| ViewportCapture {
+        success: false,
+        viewport,
+        path: None,
+        width: 0,
+        height: 0,
+        image_base64: None,
+        error: Some(error),
+    };
+    let invalid = |e: anyhow::Error| {
+        GqlStructuredError::new(
+            "SCREENSHOT_INVALID",
+            GqlErrorCategory::Godot,
+            format!("{:#}", e),
+        )
+    };
+
+    let (res_path, fs_path) = match next_screenshot_path(ctx) {
+        Ok(paths) => paths,
+        Err(e) => return failure(invalid(e)),
+    };
+    let command = GodotLiveCommand::CaptureViewport {
+        viewport: viewport_name(viewport).to_string(),
+        path: res_path.clone(),
+    };
+    let value = match execute_live_command(ctx, command).await {
+        Ok(value) => value,
+        Err(e) => return failure(e.to_structured_error()),
+    };
+    if let Some(message) = value.get("error").and_then(|e| e.as_str()) {
+        return failure(
+            GqlStructuredError::new("SCREENSHOT_FAILED", GqlErrorCategory::Godot, message)
+                .with_suggestion(
+                    "GAME はエディターからゲームを実行中（playMainScene / playCurrentScene）のみ撮影できます",
+                ),
+        );
+    }
+
+    let png = match value["image"].as_str() {
+        Some(encoded) => BASE64
+            .decode(encoded)
+            .context("Invalid base64 image")
+            .and_then(|png| {
+                fs::write(&fs_path, &png).context("Failed to write screenshot")?;
+                Ok(png)
+            }),
+        None => wait_for_capture(&fs_path, Duration::from_millis(ctx.timeout_ms)).await,
+    };
+    let (canvas, png) = match png.and_then(|png| Ok((Canvas::decode_png(&png)?, png))) {
+        Ok(decoded) => decoded,
+        Err(e) => return failure(invalid(e)),
+    };
+    let _ = prune_screenshots(ctx);
+
+    let encoded = BASE64.encode(&png);
+    ctx.attach_image("image/png", encoded.clone());
+    ViewportCapture {
+        success: true,
+        viewport,
+        path: Some(res_path),
+        width: canvas.width as i32,
+        height: canvas.height as i32,
+        image_base64: inline.then_some(encoded),
+        error: None,
+    }
+}
+
+/// Wait for the game to write its capture (it renames the finished file into place)
+async fn wait_for_capture(path: &Path, timeout: Duration) -> anyhow::Result<Vec<u8>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if path.is_file() {
+            return fs::read(path).context("Failed to read the game's capture");
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!("The game did not save the capture within {:?}", timeout);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+/// Draw the annotations onto the captured image and save it
+fn annotate_capture(
+    ctx: &GqlContext,
+    input: &EditorScreenshotInput,
+    capture: &Value,
+) -> anyhow::Result<EditorScreenshotResult> {
+    let encoded = capture["image"]
+        .as_str()
+        .context("Capture response has no image")?;
+    let png = BASE64.decode(encoded).context("Invalid base64 image")?;
+    let mut canvas = Canvas::decode_png(&png)?;
+
+    let accent = capture["theme"]["accent"]
+        .as_str()
+        .and_then(parse_hex_color)
+        .unwrap_or(DEFAULT_ACCENT);
+    // A thin edge in the theme's background tone keeps boxes readable
+    let outline = if capture["theme"]["dark"].as_bool().unwrap_or(true) {
+        [0, 0, 0]
+    } else {
+        [255, 255, 255]
+    };
+
+    let nodes = capture["nodes"].as_array().cloned().unwrap_or_default();
+    let mut annotations = Vec::new();
+    for request in &input.annotations {
+        let node = nodes
+            .iter()
+            .find(|n| n["node_path"].as_str() == Some(request.node_path.as_str()))
+            .unwrap_or(&Value::Null);
+        let found = node["found"].as_bool().unwrap_or(false);
+        let label = request.label.clone().unwrap_or_else(|| {
+            node["name"]
+                .as_str()
+                .unwrap_or_else(|| request.node_path.rsplit('/').next().unwrap_or_default())
+                .to_string()
+        });
+
+        let rect = node_rect(node);
+        let visible = rect.is_some_and(|r| {
+            r.x < canvas.width as i32
+                && r.y < canvas.height as i32
+                && r.x + r.width > 0
+                && r.y + r.height > 0
+        });
+        if let (Some(rect), true) = (rect, visible) {
+            let color = request
+                .color
+                .as_deref()
+                .and_then(parse_hex_color)
+                .unwrap_or(accent);
+            canvas.annotate(rect, &label, color, outline);
+        }
+
+        let rect = rect.unwrap_or(Rect {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        });
+        annotations.push(ScreenshotAnnotation {
+            node_path: request.node_path.clone(),
+            label,
+            found,
+            visible,
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        });
+    }
+
+    let png = canvas.encode_png()?;
+    let res_path = save_screenshot(ctx, &png)?;
+
+    Ok(EditorScreenshotResult {
+        success: true,
+        path: Some(res_path),
+        width: canvas.width as i32,
+        height: canvas.height as i32,
+        annotations,
+        image_base64: input.inline.then(|| BASE64.encode(&png)),
+        error: None,
+    })
+}
+
+/// `rect: [x, y, width, height]` reported by the plugin
+fn node_rect(node: &Value) -> Option<Rect> {
+    let values: Vec<f64> = node["rect"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_f64)
+        .collect();
+    let [x, y, width, height] = values[..] else {
+        return None;
+    };
+    Some(Rect {
+        x: x.round() as i32,
+        y: y.round() as i32,
+        width: width.round().max(1.0) as i32,
+        height: height.round().max(1.0) as i32,
+    })
+}
+
+/// Write the PNG under SCREENSHOT_DIR and prune old captures
+fn save_screenshot(ctx: &GqlContext, png: &[u8]) -> anyhow::Result<String> {
+    let (res_path, fs_path) = next_screenshot_path(ctx)?;
+    fs::write(&fs_path, png).context("Failed to write screenshot")?;
+    prune_screenshots(ctx)?;
+    Ok(res_path)
+}
+
+/// res:// and filesystem path for a new capture, creating SCREENSHOT_DIR
+fn next_screenshot_path(ctx: &GqlContext) -> anyhow::Result<(String, PathBuf)> {
+    let dir = path_utils::to_fs_path_unchecked(&ctx.project_path, SCREENSHOT_DIR);
+    fs::create_dir_all(&dir).context("Failed to create screenshot directory")?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let file_name = format!("screenshot-{}.png", millis);
+    Ok((
+        format!("{}/{}", SCREENSHOT_DIR, file_name),
+        dir.join(file_name),
+    ))
+}
+
+/// Keep the newest MAX_SCREENSHOTS captures
+fn prune_screenshots(ctx: &GqlContext) -> anyhow::Result<()> {
+    let dir = path_utils::to_fs_path_unchecked(&ctx.project_path, SCREENSHOT_DIR);
+    let mut existing: Vec<_> = fs::read_dir(&dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("screenshot-") && n.ends_with(".png"))
+        })
+        .collect();
+    existing.sort();
+    let excess = existing.len().saturating_sub(MAX_SCREENSHOTS);
+    for old in &existing[..excess] {
+        let _ = fs::remove_file(old);
+    }
+    Ok(())
+}
+
+fn viewport_name(viewport: ScreenshotViewport) -> &'static str {
+    match viewport {
+        ScreenshotViewport::Viewport2D => "2d",
+        ScreenshotViewport::Viewport3D => "3d",
+        ScreenshotViewport::Editor => "editor",
+        ScreenshotViewport::Game => "game",
+    }
+}
+
+fn failure(error: GqlStructuredError) -> EditorScreenshotResult {
+    EditorScreenshotResult {
+        success: false,
+        path: None,
+        width: 0,
+        height: 0,
+        annotations: vec![],
+        image_base64: None,
+        error: Some(error),
+    }
+}
@@ -2,19 +2,24 @@
 //!
 //! Handles script parsing, conversion, and creation.
 
-use std::fs;
-
-use crate::godot::gdscript::GDScript;
+use crate::godot::csharp::{CSharpField, CSharpParameter, CSharpScript};
+use crate::godot::gdscript::ast::ClassDecl;
+use crate::godot::gdscript::{render_parameter, GDScript};
 use crate::path_utils;
+use crate::telemetry;
 
+use super::audit;
 use super::context::GqlContext;
 use super::types::*;
 
 /// Resolve script from file path
 pub fn resolve_script(ctx: &GqlContext, res_path: &str) -> Option<Script> {
     let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
-    let content = fs::read_to_string(&file_path).ok()?;
-    let gdscript = GDScript::parse(&content);
+    if res_path.ends_with(".cs") {
+        let script = ctx.index.csharp_script(&file_path)?;
+        return Some(convert_csharp_to_gql(&script, res_path));
+    }
+    let gdscript = ctx.index.script(&file_path)?;
 
     Some(convert_gdscript_to_gql(&gdscript, res_path))
 }
@@ -23,16 +28,10 @@ pub fn resolve_script(ctx: &GqlContext, res_path: &str) -> Option<Script> {
 pub fn convert_gdscript_to_gql(script: &GDScript, path: &str) -> Script {
     Script {
         path: path.to_string(),
+        language: ScriptLanguage::Gdscript,
         extends: script.extends.clone().unwrap_or_else(|| "Node".to_string()),
         class_name: script.class_name.clone(),
-        functions: script
-            .functions
-            .iter()
-            .map(|f| Function {
-                name: f.name.clone(),
-                arguments: f.params.iter().map(|p| p.name.clone()).collect(),
-            })
-            .collect(),
+        functions: convert_functions(&script.ast),
         variables: script
             .variables
             .iter()
@@ -64,9 +63,142 @@ pub fn convert_gdscript_to_gql(script: &GDScript, path: &str) -> Script {
                 default_value: e.default_value.clone(),
             })
             .collect(),
+        constants: convert_constants(&script.ast),
+        enums: convert_enums(&script.ast),
+        inner_classes: script.ast.classes().map(convert_inner_class).collect(),
+        syntax_errors: script
+            .errors
+            .iter()
+            .map(|e| ScriptSyntaxError {
+                line: e.line as i32,
+                message: e.message.clone(),
+            })
+            .collect(),
     }
 }
 
+/// Convert a scanned C# script to GraphQL Script
+///
+/// Signal arguments are rendered `name: Type` like GDScript's. Nested types
+/// are not scanned, so `innerClasses` is always empty.
+pub fn convert_csharp_to_gql(script: &CSharpScript, path: &str) -> Script {
+    let variable = |f: &CSharpField| Variable {
+        name: f.name.clone(),
+        var_type: f.field_type.clone(),
+        default_value: f.default_value.clone(),
+    };
+    let argument = |p: &CSharpParameter| format!("{}: {}", p.name, p.param_type);
+
+    Script {
+        path: path.to_string(),
+        language: ScriptLanguage::Csharp,
+        extends: script
+            .base_class
+            .clone()
+            .unwrap_or_else(|| "Node".to_string()),
+        class_name: script.class_name.clone(),
+        functions: script
+            .methods
+            .iter()
+            .map(|m| Function {
+                name: m.name.clone(),
+                arguments: m.parameters.iter().map(|p| p.name.clone()).collect(),
+                return_type: Some(m.return_type.clone()),
+                line: m.line as i32,
+            })
+            .collect(),
+        variables: script.fields.iter().map(variable).collect(),
+        signals: script
+            .signals
+            .iter()
+            .map(|s| SignalDefinition {
+                name: s.name.clone(),
+                arguments: s.parameters.iter().map(argument).collect(),
+            })
+            .collect(),
+        exports: script.exports.iter().map(variable).collect(),
+        constants: script
+            .constants
+            .iter()
+            .map(|c| Constant {
+                name: c.name.clone(),
+                const_type: Some(c.field_type.clone()),
+                value: c.default_value.clone(),
+            })
+            .collect(),
+        enums: script
+            .enums
+            .iter()
+            .map(|e| EnumDefinition {
+                name: Some(e.name.clone()),
+                values: e.values.iter().map(|v| v.name.clone()).collect(),
+            })
+            .collect(),
+        inner_classes: Vec::new(),
+        syntax_errors: Vec::new(),
+    }
+}
+
+fn convert_inner_class(class: &ClassDecl) -> InnerClass {
+    InnerClass {
+        name: class.name.clone().unwrap_or_default(),
+        extends: class.extends.clone(),
+        line: class.line as i32,
+        functions: convert_functions(class),
+        variables: class
+            .variables()
+            .map(|v| Variable {
+                name: v.name.clone(),
+                var_type: v.type_hint.clone().unwrap_or_else(|| "Variant".to_string()),
+                default_value: v.value_source.clone(),
+            })
+            .collect(),
+        signals: class
+            .signals()
+            .map(|s| SignalDefinition {
+                name: s.name.clone(),
+                arguments: s.parameters.iter().map(render_parameter).collect(),
+            })
+            .collect(),
+        constants: convert_constants(class),
+        enums: convert_enums(class),
+        inner_classes: class.classes().map(convert_inner_class).collect(),
+    }
+}
+
+fn convert_functions(class: &ClassDecl) -> Vec<Function> {
+    class
+        .functions()
+        .map(|f| Function {
+            name: f.name.clone(),
+            arguments: f.parameters.iter().map(|p| p.name.clone()).collect(),
+            return_type: f.return_type.clone(),
+            line: f.line as i32,
+        })
+        .collect()
+}
+
+fn convert_constants(class: &ClassDecl) -> Vec<Constant> {
+    class
+        .constants()
+        .map(|c| Constant {
+            name: c.name.clone(),
+            const_type: c.type_hint.clone(),
+            value: c.value_source.clone(),
+        })
+        .collect()
+}
+
+fn convert_enums(class: &ClassDecl) -> Vec<EnumDefinition> {
+    class
+        .enums()
+        .map(|e| EnumDefinition {
+            name: e.name.clone(),
+            values: e.values.iter().map(|v| v.name.clone()).collect(),
+        })
+        .collect()
+}
+
 /// Parse signal definition string
 pub fn parse_signal_definition(signal_str: &str) -> (String, Vec<String>) {
     if let Some(paren_start) = signal_str.find('(') {
@@ -85,6 +217,29 @@ pub fn parse_signal_definition(signal_str: &str) -> (String, Vec<String>) {
     }
 }
 
+/// GDScript written by createScript
+pub(super) fn script_content(input: &CreateScriptInput) -> String {
+    let class_name_line = input
+        .class_name
+        .as_ref()
+        .map(|name| format!("class_name {}\n", name))
+        .unwrap_or_default();
+
+    format!(
+        r#"{}extends {}
+
+
+func _ready() -> void:
+	pass
+
+
+func _process(delta: float) -> void:
+	pass
+"#,
+        class_name_line, input.extends
+    )
+}
+
 /// Create a new GDScript file
 pub fn create_script(ctx: &GqlContext, input: &CreateScriptInput) -> ScriptResult {
     let project_path = &ctx.project_path;
@@ -93,7 +248,7 @@ pub fn create_script(ctx: &GqlContext, input: &CreateScriptInput) -> ScriptResul
     let file_path = path_utils::to_fs_path_unchecked(project_path, &input.path);
 
     // Check if file already exists
-    if file_path.exists() {
+    if ctx.fs.exists(&file_path) {
         return ScriptResult {
             success: false,
             script: None,
@@ -103,7 +258,7 @@ pub fn create_script(ctx: &GqlContext, input: &CreateScriptInput) -> ScriptResul
 
     // Ensure parent directory exists
     if let Some(parent) = file_path.parent() {
-        if let Err(e) = fs::create_dir_all(parent) {
+        if let Err(e) = ctx.fs.create_dir_all(parent) {
             return ScriptResult {
                 success: false,
                 script: None,
@@ -112,35 +267,17 @@ pub fn create_script(ctx: &GqlContext, input: &CreateScriptInput) -> ScriptResul
         }
     }
 
-    // Generate GDScript content
-    let class_name_line = input
-        .class_name
-        .as_ref()
-        .map(|name| format!("class_name {}\n", name))
-        .unwrap_or_default();
-
-    let script_content = format!(
-        r#"{}extends {}
-
-
-func _ready() -> void:
-	pass
-
-
-func _process(delta: float) -> void:
-	pass
-"#,
-        class_name_line, input.extends
-    );
+    let script_content = script_content(input);
 
     // Write file
-    if let Err(e) = fs::write(&file_path, script_content) {
+    if let Err(e) = audit::write_file(ctx, "createScript", &file_path, script_content) {
         return ScriptResult {
             success: false,
             script: None,
             message: Some(format!("Failed to write script: {}", e)),
         };
     }
+    telemetry::record_files_written(1);
 
     ScriptResult {
         success: true,
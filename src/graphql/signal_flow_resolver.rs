@@ -0,0 +1,399 @@
+//! Signal Flow Resolver
+//!
+//! Renders the signal network of a scene (or the whole project) as a DOT or
+//! MERMAID diagram: scene nodes are vertices and `[connection]` entries are
+//! edges labeled `signal → method`. Autoloads that scripts emit into or
+//! subscribe to (`Events.died.emit()`, `Events.died.connect(_on_died)`) show
+//! up as event bus vertices, so traffic routed through them is visible too.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::godot::gdscript::lexer::{tokenize, Token, TokenKind};
+use crate::godot::tscn::{node_path, GodotScene};
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::dependency_resolver::{
+    render_dot, render_mermaid, DiagramEdge, DiagramShape, DiagramStyle, DiagramVertex,
+};
+use super::project_resolver::collect_project_files;
+use super::refactoring_resolver::resolve_autoloads;
+use super::types::*;
+use super::wiring_resolver::attached_script;
+
+/// Build the signal flow graph of one scene, or of every scene when `scene_path` is `None`
+pub fn resolve_signal_flow_graph(
+    ctx: &GqlContext,
+    scene_path: Option<&str>,
+    format: DiagramFormat,
+) -> SignalFlowGraph {
+    let mut graph = SignalGraph::default();
+
+    // Scripts to scan for event bus usage, with the vertices they are attached to
+    let mut script_owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    let scenes = match scene_path {
+        Some(path) => match load_scene(ctx, path) {
+            Ok(scene) => vec![(path.to_string(), scene)],
+            Err(error) => {
+                return SignalFlowGraph {
+                    vertices: Vec::new(),
+                    edges: Vec::new(),
+                    exported_data: String::new(),
+                    error: Some(*error),
+                }
+            }
+        },
+        None => {
+            let (scenes, scripts) = collect_project_files(ctx);
+            for script in scripts {
+                script_owners.entry(script.path).or_default();
+            }
+            scenes
+                .into_iter()
+                .filter_map(|s| load_scene(ctx, &s.path).ok().map(|scene| (s.path, scene)))
+                .collect()
+        }
+    };
+
+    for (path, scene) in &scenes {
+        for node in &scene.nodes {
+            let id = node_id(path, &node_path(node));
+            let node_type = Some(node.node_type.clone()).filter(|t| !t.is_empty());
+            graph.add_vertex(SignalFlowVertex {
+                label: match &node_type {
+                    Some(t) => format!("{} ({})", node.name, t),
+                    None => node.name.clone(),
+                },
+                id: id.clone(),
+                kind: SignalVertexKind::Node,
+                scene: Some(path.clone()),
+                node_type,
+            });
+            if let Some(script) = attached_script(scene, node) {
+                script_owners.entry(script).or_default().push(id);
+            }
+        }
+
+        for connection in &scene.connections {
+            let from = graph.scene_node(path, &connection.from);
+            let to = graph.scene_node(path, &connection.to);
+            graph.add_edge(SignalFlowEdge {
+                from,
+                to,
+                signal: connection.signal.clone(),
+                method: Some(connection.method.clone()),
+                source: SignalEdgeSource::Scene,
+            });
+        }
+    }
+
+    let buses: Vec<String> = resolve_autoloads(ctx)
+        .autoloads
+        .into_iter()
+        .map(|a| a.name)
+        .collect();
+    if !buses.is_empty() {
+        for (script, owners) in &script_owners {
+            let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, script);
+            let Some(source) = ctx.index.read_to_string(&file_path) else {
+                continue;
+            };
+            let uses = find_bus_usage(&source, &buses);
+            if uses.is_empty() {
+                continue;
+            }
+
+            // Scripts not attached in the scanned scenes get a vertex of their own
+            let owners = if owners.is_empty() {
+                graph.add_vertex(SignalFlowVertex {
+                    id: script.clone(),
+                    label: script.rsplit('/').next().unwrap_or(script).to_string(),
+                    kind: SignalVertexKind::Script,
+                    scene: None,
+                    node_type: None,
+                });
+                vec![script.clone()]
+            } else {
+                owners.clone()
+            };
+
+            for usage in uses {
+                let bus = format!("autoload:{}", usage.bus);
+                graph.add_vertex(SignalFlowVertex {
+                    id: bus.clone(),
+                    label: usage.bus.clone(),
+                    kind: SignalVertexKind::EventBus,
+                    scene: None,
+                    node_type: None,
+                });
+                for owner in &owners {
+                    let (from, to) = match usage.handler {
+                        Some(_) => (bus.clone(), owner.clone()),
+                        None => (owner.clone(), bus.clone()),
+                    };
+                    graph.add_edge(SignalFlowEdge {
+                        from,
+                        to,
+                        signal: usage.signal.clone(),
+                        method: usage.handler.clone(),
+                        source: SignalEdgeSource::Script,
+                    });
+                }
+            }
+        }
+    }
+
+    let exported_data = export(&graph, format);
+    SignalFlowGraph {
+        vertices: graph.vertices,
+        edges: graph.edges,
+        exported_data,
+        error: None,
+    }
+}
+
+fn load_scene(
+    ctx: &GqlContext,
+    res_path: &str,
+) -> Result<std::sync::Arc<GodotScene>, Box<GqlStructuredError>> {
+    let file_path = path_utils::to_fs_path(&ctx.project_path, res_path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "INVALID_PATH",
+            GqlErrorCategory::Validation,
+            e.to_string(),
+        ))
+    })?;
+    if !ctx.fs.is_file(&file_path) {
+        return Err(Box::new(GqlStructuredError::new(
+            "SCENE_NOT_FOUND",
+            GqlErrorCategory::FileSystem,
+            format!("Scene not found: {}", res_path),
+        )));
+    }
+    ctx.index.scene(&file_path).map_err(|e| {
+        Box::new(GqlStructuredError::new(
+            "SCENE_PARSE_ERROR",
+            GqlErrorCategory::Validation,
+            format!("Failed to parse scene: {}", e),
+        ))
+    })
+}
+
+fn node_id(scene: &str, node_path: &str) -> String {
+    format!("{}::{}", scene, node_path)
+}
+
+/// Vertices and edges in insertion order, without duplicates
+#[derive(Default)]
+struct SignalGraph {
+    vertices: Vec<SignalFlowVertex>,
+    edges: Vec<SignalFlowEdge>,
+    ids: HashSet<String>,
+}
+
+impl SignalGraph {
+    fn add_vertex(&mut self, vertex: SignalFlowVertex) {
+        if self.ids.insert(vertex.id.clone()) {
+            self.vertices.push(vertex);
+        }
+    }
+
+    /// Vertex id of a node path in a scene; nodes inside instanced scenes are added on demand
+    fn scene_node(&mut self, scene: &str, node_path: &str) -> String {
+        let id = node_id(scene, node_path);
+        if !self.ids.contains(&id) {
+            self.add_vertex(SignalFlowVertex {
+                id: id.clone(),
+                label: node_path
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(node_path)
+                    .to_string(),
+                kind: SignalVertexKind::Node,
+                scene: Some(scene.to_string()),
+                node_type: None,
+            });
+        }
+        id
+    }
+
+    fn add_edge(&mut self, edge: SignalFlowEdge) {
+        let duplicate = self.edges.iter().any(|e| {
+            e.from == edge.from
+                && e.to == edge.to
+                && e.signal == edge.signal
+                && e.method == edge.method
+        });
+        if !duplicate {
+            self.edges.push(edge);
+        }
+    }
+}
+
+fn export(graph: &SignalGraph, format: DiagramFormat) -> String {
+    let labels: Vec<String> = graph
+        .edges
+        .iter()
+        .map(|e| match &e.method {
+            Some(method) => format!("{} → {}", e.signal, method),
+            None => e.signal.clone(),
+        })
+        .collect();
+    let vertices: Vec<DiagramVertex> = graph
+        .vertices
+        .iter()
+        .map(|v| DiagramVertex {
+            id: &v.id,
+            label: &v.label,
+            shape: match v.kind {
+                SignalVertexKind::Node => DiagramShape::Box,
+                SignalVertexKind::Script => DiagramShape::Ellipse,
+                SignalVertexKind::EventBus => DiagramShape::Diamond,
+            },
+        })
+        .collect();
+    let edges: Vec<DiagramEdge> = graph
+        .edges
+        .iter()
+        .zip(&labels)
+        .map(|(e, label)| DiagramEdge {
+            from: &e.from,
+            to: &e.to,
+            label: Some(label),
+            style: match e.source {
+                SignalEdgeSource::Scene => DiagramStyle::Solid,
+                SignalEdgeSource::Script => DiagramStyle::Dashed,
+            },
+        })
+        .collect();
+
+    match format {
+        DiagramFormat::Dot => render_dot("SignalFlow", &vertices, &edges),
+        DiagramFormat::Mermaid => render_mermaid(&vertices, &edges),
+    }
+}
+
+/// One emission into (no handler) or subscription to (with handler) an event bus signal
+#[derive(Debug, PartialEq)]
+struct BusUsage {
+    bus: String,
+    signal: String,
+    handler: Option<String>,
+}
+
+/// Event bus signals a script emits or connects to
+///
+/// Recognises `Bus.sig.emit(...)`, `Bus.sig.connect(handler)`,
+/// `Bus.emit_signal("sig", ...)` and `Bus.connect("sig", handler)`.
+fn find_bus_usage(source: &str, buses: &[String]) -> Vec<BusUsage> {
+    let (tokens, _) = tokenize(source);
+    let ident = |i: usize| match tokens.get(i).map(|t| &t.kind) {
+        Some(TokenKind::Identifier(name)) => Some(name.as_str()),
+        _ => None,
+    };
+    let punct = |i: usize, p: &str| matches!(tokens.get(i).map(|t| &t.kind), Some(TokenKind::Punct(q)) if *q == p);
+    let string = |i: usize| match tokens.get(i).map(|t| &t.kind) {
+        Some(TokenKind::String { value, .. }) => Some(value.clone()),
+        _ => None,
+    };
+
+    let mut uses = Vec::new();
+    for i in 0..tokens.len() {
+        let Some(bus) = ident(i).filter(|name| buses.iter().any(|b| b == name)) else {
+            continue;
+        };
+        // Skip member access on something else (`other.Events`)
+        if i > 0 && punct(i - 1, ".") {
+            continue;
+        }
+        if !punct(i + 1, ".") {
+            continue;
+        }
+        let Some(member) = ident(i + 2) else {
+            continue;
+        };
+
+        let usage = match member {
+            "emit_signal" if punct(i + 3, "(") => string(i + 4).map(|signal| (signal, None)),
+            "connect" if punct(i + 3, "(") => string(i + 4)
+                .filter(|_| punct(i + 5, ","))
+                .map(|signal| (signal, Some(handler_name(&tokens, i + 6)))),
+            signal if punct(i + 3, ".") && punct(i + 5, "(") => match ident(i + 4) {
+                Some("emit") => Some((signal.to_string(), None)),
+                Some("connect") => Some((signal.to_string(), Some(handler_name(&tokens, i + 6)))),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some((signal, handler)) = usage {
+            uses.push(BusUsage {
+                bus: bus.to_string(),
+                signal,
+                handler,
+            });
+        }
+    }
+    uses
+}
+
+/// Method named by a callable argument: `_on_died`, `self._on_died`,
+/// `Callable(self, "_on_died")` or `_on_died.bind(1)`
+fn handler_name(tokens: &[Token], start: usize) -> String {
+    let mut depth = 0usize;
+    let mut name = None;
+    for token in &tokens[start.min(tokens.len())..] {
+        match &token.kind {
+            TokenKind::Punct("(") | TokenKind::Punct("[") => depth += 1,
+            TokenKind::Punct(")") | TokenKind::Punct("]") if depth == 0 => break,
+            TokenKind::Punct(")") | TokenKind::Punct("]") => depth -= 1,
+            TokenKind::Punct(",") if depth == 0 => break,
+            TokenKind::Identifier(id) if name.is_none() && id != "Callable" && id != "self" => {
+                name = Some(id.clone())
+            }
+            TokenKind::String { value, .. } if name.is_none() => name = Some(value.clone()),
+            _ => {}
+        }
+        if name.is_some() && depth == 0 {
+            break;
+        }
+    }
+    name.unwrap_or_else(|| "<callable>".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_bus_usage() {
+        let source = r#"extends Node
+
+func _ready():
+    Events.player_died.connect(_on_player_died)
+    Events.connect("score_changed", Callable(self, "_on_score"))
+    other.Events.ignored.emit()
+
+func die():
+    Events.player_died.emit(self)
+    Events.emit_signal("score_changed", 10)
+    Unknown.thing.emit()
+"#;
+        let buses = vec!["Events".to_string()];
+        let usage = |signal: &str, handler: Option<&str>| BusUsage {
+            bus: "Events".to_string(),
+            signal: signal.to_string(),
+            handler: handler.map(str::to_string),
+        };
+
+        assert_eq!(
+            find_bus_usage(source, &buses),
+            vec![
+                usage("player_died", Some("_on_player_died")),
+                usage("score_changed", Some("_on_score")),
+                usage("player_died", None),
+                usage("score_changed", None),
+            ]
+        );
+    }
+}
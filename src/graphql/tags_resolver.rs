@@ -0,0 +1,220 @@
+//! Tags Resolver
+//!
+//! Owners and area tags of project files, so queries can be scoped to a slice
+//! of a large project ("only UI team files"). Tags come from
+//! `res://.godot-mcp/owners.toml`:
+//!
+//! ```toml
+//! [[area]]
+//! paths = ["ui/**", "scenes/menus/*.tscn"]
+//! tags = ["ui"]
+//! owners = ["ui-team"]
+//! ```
+//!
+//! and, for scenes, from agent metadata on the root node (`mcp_tags` as a
+//! comma separated list and `mcp_owner`, see `setNodeMetadata`).
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::contracts_resolver::glob_match;
+use super::project_resolver::to_res_path;
+use super::scene_resolver::{unquote_godot_string, MCP_METADATA_PREFIX};
+use super::types::*;
+
+/// Owners file read by fileTags and the tag filters
+pub const OWNERS_PATH: &str = "res://.godot-mcp/owners.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OwnersFile {
+    #[serde(default)]
+    area: Vec<Area>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Area {
+    /// Globs over res:// paths ("ui/**")
+    paths: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    owners: Vec<String>,
+}
+
+/// Tags of project files, for matching them against a filter
+pub(super) struct TagScope {
+    areas: Vec<Area>,
+    filter: FileTagFilter,
+}
+
+impl TagScope {
+    /// Scope for a query's filter; None when the query is not filtered
+    pub(super) fn new(
+        ctx: &GqlContext,
+        filter: Option<&FileTagFilter>,
+    ) -> Result<Option<Self>, Box<GqlStructuredError>> {
+        let Some(filter) = filter else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            areas: load_areas(ctx)?,
+            filter: filter.clone(),
+        }))
+    }
+
+    /// Whether the file at `res_path` passes the filter
+    pub(super) fn includes(&self, ctx: &GqlContext, res_path: &str) -> bool {
+        let tags = file_tags(ctx, &self.areas, res_path);
+        self.filter.matches(&tags)
+    }
+}
+
+impl FileTagFilter {
+    fn matches(&self, file: &FileTags) -> bool {
+        self.tags
+            .as_ref()
+            .is_none_or(|tags| tags.iter().any(|t| file.tags.contains(t)))
+            && self
+                .owners
+                .as_ref()
+                .is_none_or(|owners| owners.iter().any(|o| file.owners.contains(o)))
+    }
+}
+
+/// Resolve fileTags query: tagged scenes, scripts and resources
+pub fn resolve_file_tags(ctx: &GqlContext, filter: Option<&FileTagFilter>) -> FileTagsResult {
+    let areas = match load_areas(ctx) {
+        Ok(areas) => areas,
+        Err(error) => {
+            return FileTagsResult {
+                files: Vec::new(),
+                error: Some(*error),
+            }
+        }
+    };
+
+    let mut files: Vec<FileTags> = ctx
+        .index
+        .files(&ctx.project_path)
+        .iter()
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("tscn" | "scn" | "gd" | "cs" | "tres" | "res" | "gdshader")
+            )
+        })
+        .map(|path| file_tags(ctx, &areas, &to_res_path(&ctx.project_path, path)))
+        .filter(|file| !file.tags.is_empty() || !file.owners.is_empty())
+        .filter(|file| filter.is_none_or(|f| f.matches(file)))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    FileTagsResult { files, error: None }
+}
+
+/// Limit a project's scene and script lists (and their counts) to the filter
+pub fn filter_project(
+    ctx: &GqlContext,
+    mut project: Project,
+    filter: Option<&FileTagFilter>,
+) -> Project {
+    let scope = match TagScope::new(ctx, filter) {
+        Ok(Some(scope)) => scope,
+        Ok(None) => return project,
+        Err(error) => {
+            tracing::warn!("Ignoring {}: {}", OWNERS_PATH, error.message);
+            TagScope {
+                areas: Vec::new(),
+                filter: filter.cloned().unwrap_or_default(),
+            }
+        }
+    };
+    project
+        .scenes
+        .retain(|scene| scope.includes(ctx, &scene.path));
+    project
+        .scripts
+        .retain(|script| scope.includes(ctx, &script.path));
+    project.stats.scene_count = project.scenes.len() as i32;
+    project.stats.script_count = project.scripts.len() as i32;
+    project
+}
+
+fn file_tags(ctx: &GqlContext, areas: &[Area], res_path: &str) -> FileTags {
+    let relative = path_utils::strip_res_prefix(res_path);
+    let mut tags = BTreeSet::new();
+    let mut owners = BTreeSet::new();
+    for area in areas.iter().filter(|a| {
+        a.paths
+            .iter()
+            .any(|p| glob_match(path_utils::strip_res_prefix(p), relative))
+    }) {
+        tags.extend(area.tags.iter().cloned());
+        owners.extend(area.owners.iter().cloned());
+    }
+
+    if res_path.ends_with(".tscn") {
+        let path = path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+        if let Some(root) = ctx
+            .index
+            .scene(&path)
+            .ok()
+            .and_then(|s| s.nodes.first().cloned())
+        {
+            let metadata = |key: &str| {
+                root.properties
+                    .get(&format!("{}{}", MCP_METADATA_PREFIX, key))
+                    .map(|v| unquote_godot_string(v))
+            };
+            if let Some(value) = metadata("tags") {
+                tags.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(String::from),
+                );
+            }
+            if let Some(owner) = metadata("owner").filter(|o| !o.is_empty()) {
+                owners.insert(owner);
+            }
+        }
+    }
+
+    FileTags {
+        path: res_path.to_string(),
+        tags: tags.into_iter().collect(),
+        owners: owners.into_iter().collect(),
+    }
+}
+
+/// Areas of the owners file; none when the file does not exist
+fn load_areas(ctx: &GqlContext) -> Result<Vec<Area>, Box<GqlStructuredError>> {
+    let path = path_utils::to_fs_path_unchecked(&ctx.project_path, OWNERS_PATH);
+    let Ok(content) = ctx.fs.read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+
+    let file: OwnersFile = toml::from_str(&content).map_err(|e| {
+        let mut error = GqlStructuredError::new(
+            "OWNERS_INVALID",
+            GqlErrorCategory::Validation,
+            e.message().to_string(),
+        );
+        error.location = Some(GqlErrorLocation {
+            file: Some(OWNERS_PATH.to_string()),
+            line: e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() as i32 + 1),
+            column: None,
+        });
+        Box::new(error)
+    })?;
+    Ok(file.area)
+}
@@ -1,59 +1,405 @@
 //! Test Resolver
 //!
-//! Handles GdUnit4 test execution and result parsing.
+//! Runs GdUnit4 or GUT tests with a headless Godot and parses the results
+//! (GdUnit4's JUnit XML report, GUT's console output) into suites and cases. The framework is the one asked for, else the installed
+//! addon (GdUnit4 when both are).
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::path_utils;
 
 use super::context::GqlContext;
+use super::godot_binary;
+use super::health_resolver::record_test_run;
+use super::junit;
+use super::project_resolver::collect_project_files;
 use super::types::*;
 
-/// Run GdUnit4 tests and return structured results
+/// GUT's command line runner
+const GUT_CMDLN: &str = "res://addons/gut/gut_cmdln.gd";
+
+/// GUT settings read by gut_cmdln.gd when no directory is given
+const GUT_CONFIG: &str = "res://.gutconfig.json";
+
+const GDUNIT4_ADDON: &str = "res://addons/gdUnit4";
+
+/// GdUnit4's command line runner
+const GDUNIT4_CMD_TOOL: &str = "res://addons/gdUnit4/bin/GdUnitCmdTool.gd";
+
+/// Where GdUnit4 writes `report_N/results.xml`; cleared before each run
+const GDUNIT4_REPORT_DIR: &str = "res://.godot-mcp/test_reports";
+
+pub(crate) const DEFAULT_TEST_PATH: &str = "res://tests/";
+
+/// A whole test run, project import included
+const TEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Output lines quoted when a run fails without results
+const OUTPUT_TAIL: usize = 20;
+
+/// Run the project's tests and return structured results
 pub async fn resolve_run_tests(ctx: &GqlContext, input: &RunTestsInput) -> TestExecutionResult {
-    let project_path = &ctx.project_path;
-    let test_path = input.test_path.as_deref().unwrap_or("res://tests/");
-
-    // Determine Godot executable path
-    // 1. Check environment variable GODOT_BIN
-    // 2. Default to "godot"
-    let godot_bin = std::env::var("GODOT_BIN").unwrap_or_else(|_| "godot".to_string());
-
-    // Execute GdUnit4 CLI
-    // For now, we use a simple command-line invocation.
-    // In a full implementation, we would use the GdUnit4 CLI tool or the specific addon script.
-
-    let mut command = std::process::Command::new(&godot_bin);
-    command.arg("--headless");
-    command.arg("--path");
-    command.arg(project_path);
-    command.arg("-s");
-    command.arg("res://addons/gdUnit4/bin/GdUnitCopyAndRun.gd");
-    command.arg("--add");
-    command.arg(test_path);
-
-    // Additional args for GdUnit4
-    // command.arg("--continue"); // Don't stop on first failure
-
-    let output = match command.output() {
-        Ok(out) => out,
-        Err(_e) => {
-            return TestExecutionResult {
-                success: false,
-                total_count: 0,
-                passed_count: 0,
-                failed_count: 0,
-                error_count: 1,
-                skipped_count: 0,
-                duration_ms: 0,
-                suites: vec![],
-            };
+    let framework = input.framework.unwrap_or_else(|| detect_framework(ctx));
+    let result = match framework {
+        TestFramework::GdUnit4 => run_gdunit4(ctx, input),
+        TestFramework::Gut => run_gut(ctx, input, &[]),
+        TestFramework::Custom => failure(
+            framework,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "runTests runs GdUnit4 and GUT tests",
+            ),
+        ),
+    };
+    if result.error.is_none() {
+        record_test_run(ctx, &result);
+    }
+    result
+}
+
+/// GUT when only GUT is installed, GdUnit4 otherwise
+fn detect_framework(ctx: &GqlContext) -> TestFramework {
+    let exists = |res_path: &str| {
+        ctx.fs.exists(&path_utils::to_fs_path_unchecked(
+            &ctx.project_path,
+            res_path,
+        ))
+    };
+    if exists(GUT_CMDLN) && !exists(GDUNIT4_ADDON) {
+        TestFramework::Gut
+    } else {
+        TestFramework::GdUnit4
+    }
+}
+
+/// Run `GdUnitCmdTool.gd` on the test path, or on the suites and tests
+/// scriptName and testName select, and read its JUnit report
+fn run_gdunit4(ctx: &GqlContext, input: &RunTestsInput) -> TestExecutionResult {
+    let fs_path = |res_path: &str| path_utils::to_fs_path_unchecked(&ctx.project_path, res_path);
+    if !ctx.fs.exists(&fs_path(GDUNIT4_CMD_TOOL)) {
+        return failure(
+            TestFramework::GdUnit4,
+            GqlStructuredError::new(
+                "GDUNIT4_NOT_INSTALLED",
+                GqlErrorCategory::FileSystem,
+                format!("{} not found", GDUNIT4_CMD_TOOL),
+            )
+            .with_suggestion("GdUnit4 を addons/gdUnit4 にインストールしてください"),
+        );
+    }
+    let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(binary) => binary,
+        Err(message) => return failure(
+            TestFramework::GdUnit4,
+            GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                .with_suggestion(
+                "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+            ),
+        ),
+    };
+
+    let test_path = input.test_path.as_deref().unwrap_or(DEFAULT_TEST_PATH);
+    let selections = match select_gdunit4_tests(ctx, test_path, input) {
+        Ok(selections) => selections,
+        Err(error) => return failure(TestFramework::GdUnit4, *error),
+    };
+
+    // Only this run's report may be read back
+    let report_dir = fs_path(GDUNIT4_REPORT_DIR);
+    if ctx.fs.exists(&report_dir) {
+        if let Err(e) = ctx.fs.remove_dir_all(&report_dir) {
+            return failure(
+                TestFramework::GdUnit4,
+                GqlStructuredError::new(
+                    "TEST_RUN_FAILED",
+                    GqlErrorCategory::FileSystem,
+                    format!("Cannot clear {}: {}", GDUNIT4_REPORT_DIR, e),
+                ),
+            );
+        }
+    }
+
+    let report_arg = report_dir.to_string_lossy().to_string();
+    let mut args = vec![
+        "-s",
+        "-d",
+        GDUNIT4_CMD_TOOL,
+        "--ignoreHeadlessMode",
+        "-rd",
+        report_arg.as_str(),
+    ];
+    for selection in &selections {
+        args.extend(["-a", selection.as_str()]);
+    }
+    let (status, output) = match godot_binary::run_headless(
+        &binary.path,
+        &ctx.project_path,
+        &args,
+        TEST_TIMEOUT,
+        "running GdUnit4 tests",
+    ) {
+        Ok(run) => run,
+        Err(message) => {
+            return failure(
+                TestFramework::GdUnit4,
+                GqlStructuredError::new("TEST_RUN_FAILED", GqlErrorCategory::Godot, message),
+            )
         }
     };
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let result = match latest_report(ctx, &report_dir) {
+        Some(xml) => match parse_junit_xml(&xml) {
+            Ok(mut result) => {
+                locate_suites(ctx, &mut result.suites);
+                result
+            }
+            Err(message) => {
+                return failure(
+                    TestFramework::GdUnit4,
+                    GqlStructuredError::new(
+                        "TEST_RUN_FAILED",
+                        GqlErrorCategory::Godot,
+                        format!("Cannot parse the GdUnit4 report: {}", message),
+                    ),
+                )
+            }
+        },
+        // Older GdUnit4 versions only print a summary
+        None => parse_test_output(&output, ""),
+    };
+    if result.total_count == 0 && !status.success() {
+        return failure(
+            TestFramework::GdUnit4,
+            GqlStructuredError::new(
+                "TEST_RUN_FAILED",
+                GqlErrorCategory::Godot,
+                format!(
+                    "GdUnit4 exited with {} without running tests:\n{}",
+                    status,
+                    output_tail(&output)
+                ),
+            ),
+        );
+    }
+    result
+}
 
-    // Parse the output
-    // For Phase 1, we'll implement a basic parser that looks for the summary line.
-    // Future phases will use JUnit XML for more detail.
-    parse_test_output(&stdout, &stderr)
+/// `-a` arguments: the test path itself, or with scriptName the test
+/// scripts under it whose path contains the text, each narrowed to its
+/// matching `test*` functions (`path:function`) with testName
+fn select_gdunit4_tests(
+    ctx: &GqlContext,
+    test_path: &str,
+    input: &RunTestsInput,
+) -> Result<Vec<String>, Box<GqlStructuredError>> {
+    if input.script_name.is_none() && input.test_name.is_none() {
+        return Ok(vec![test_path.to_string()]);
+    }
+    let folder = format!("{}/", test_path.trim_end_matches('/'));
+    let scripts: Vec<String> = collect_project_files(ctx)
+        .1
+        .into_iter()
+        .map(|script| script.path)
+        .filter(|path| path.ends_with(".gd") && (path == test_path || path.starts_with(&folder)))
+        .filter(|path| {
+            input
+                .script_name
+                .as_deref()
+                .is_none_or(|name| path.contains(name))
+        })
+        .collect();
+
+    let mut selections = Vec::new();
+    for script in scripts {
+        let Some(test_name) = input.test_name.as_deref() else {
+            selections.push(script);
+            continue;
+        };
+        let Some(parsed) = ctx.index.script(&path_utils::to_fs_path_unchecked(
+            &ctx.project_path,
+            &script,
+        )) else {
+            continue;
+        };
+        selections.extend(
+            parsed
+                .functions
+                .iter()
+                .filter(|f| f.name.starts_with("test") && f.name.contains(test_name))
+                .map(|f| format!("{}:{}", script, f.name)),
+        );
+    }
+    if selections.is_empty() {
+        return Err(Box::new(GqlStructuredError::new(
+            "NO_MATCHING_TESTS",
+            GqlErrorCategory::Validation,
+            format!(
+                "No tests under {} match scriptName {:?} and testName {:?}",
+                test_path, input.script_name, input.test_name
+            ),
+        )));
+    }
+    Ok(selections)
+}
+
+/// `results.xml` of the highest-numbered `report_N` folder
+fn latest_report(ctx: &GqlContext, report_dir: &Path) -> Option<String> {
+    let latest = ctx
+        .fs
+        .read_dir(report_dir)
+        .ok()?
+        .into_iter()
+        .filter_map(|dir| {
+            let n = dir
+                .file_name()?
+                .to_str()?
+                .strip_prefix("report_")?
+                .parse::<u32>()
+                .ok()?;
+            Some((n, dir))
+        })
+        .max_by_key(|(n, _)| *n)?
+        .1;
+    ctx.fs.read_to_string(&latest.join("results.xml")).ok()
+}
+
+/// Point suites whose guessed `res://{package}/{name}.gd` does not exist at
+/// the test script with that file name
+fn locate_suites(ctx: &GqlContext, suites: &mut [TestSuiteResult]) {
+    let exists = |res_path: &str| {
+        ctx.fs.exists(&path_utils::to_fs_path_unchecked(
+            &ctx.project_path,
+            res_path,
+        ))
+    };
+    if suites.iter().all(|suite| exists(&suite.path)) {
+        return;
+    }
+    let scripts = collect_project_files(ctx).1;
+    for suite in suites.iter_mut().filter(|suite| !exists(&suite.path)) {
+        let file_name = format!("/{}.gd", suite.name);
+        if let Some(script) = scripts.iter().find(|s| s.path.ends_with(&file_name)) {
+            suite.path = script.path.clone();
+        }
+    }
+}
+
+/// Run `gut_cmdln.gd`: a `.gd` test path runs that script, a folder its
+/// scripts and subfolders; scriptName and testName narrow the run.
+/// `extra_args` are passed on to gut_cmdln.gd.
+pub(crate) fn run_gut(
+    ctx: &GqlContext,
+    input: &RunTestsInput,
+    extra_args: &[&str],
+) -> TestExecutionResult {
+    let exists = |res_path: &str| {
+        ctx.fs.exists(&path_utils::to_fs_path_unchecked(
+            &ctx.project_path,
+            res_path,
+        ))
+    };
+    if !exists(GUT_CMDLN) {
+        return failure(
+            TestFramework::Gut,
+            GqlStructuredError::new(
+                "GUT_NOT_INSTALLED",
+                GqlErrorCategory::FileSystem,
+                format!("{} not found", GUT_CMDLN),
+            )
+            .with_suggestion("GUT を addons/gut にインストールしてください"),
+        );
+    }
+    let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+        Ok(binary) => binary,
+        Err(message) => return failure(
+            TestFramework::Gut,
+            GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                .with_suggestion(
+                "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+            ),
+        ),
+    };
+
+    let mut args = vec![
+        "-s".to_string(),
+        GUT_CMDLN.to_string(),
+        "-gexit".to_string(),
+        "-gdisable_colors".to_string(),
+    ];
+    match input.test_path.as_deref() {
+        Some(path) if path.ends_with(".gd") => args.push(format!("-gtest={}", path)),
+        Some(path) => args.extend([format!("-gdir={}", path), "-ginclude_subdirs".to_string()]),
+        // gut_cmdln.gd reads its directories from .gutconfig.json
+        None if exists(GUT_CONFIG) => {}
+        None => args.extend([
+            format!("-gdir={}", DEFAULT_TEST_PATH),
+            "-ginclude_subdirs".to_string(),
+        ]),
+    }
+    if let Some(script) = &input.script_name {
+        args.push(format!("-gselect={}", script));
+    }
+    if let Some(test) = &input.test_name {
+        args.push(format!("-gunit_test_name={}", test));
+    }
+
+    args.extend(extra_args.iter().map(|arg| arg.to_string()));
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (status, output) = match godot_binary::run_headless(
+        &binary.path,
+        &ctx.project_path,
+        &args,
+        TEST_TIMEOUT,
+        "running GUT tests",
+    ) {
+        Ok(run) => run,
+        Err(message) => {
+            return failure(
+                TestFramework::Gut,
+                GqlStructuredError::new("TEST_RUN_FAILED", GqlErrorCategory::Godot, message),
+            )
+        }
+    };
+
+    let result = parse_gut_output(&output);
+    if result.total_count == 0 && !status.success() {
+        return failure(
+            TestFramework::Gut,
+            GqlStructuredError::new(
+                "TEST_RUN_FAILED",
+                GqlErrorCategory::Godot,
+                format!(
+                    "GUT exited with {} without running tests:\n{}",
+                    status,
+                    output_tail(&output)
+                ),
+            ),
+        );
+    }
+    result
+}
+
+fn output_tail(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    lines[lines.len().saturating_sub(OUTPUT_TAIL)..].join("\n")
+}
+
+fn failure(framework: TestFramework, error: GqlStructuredError) -> TestExecutionResult {
+    TestExecutionResult {
+        success: false,
+        framework,
+        total_count: 0,
+        passed_count: 0,
+        failed_count: 0,
+        error_count: 0,
+        skipped_count: 0,
+        duration_ms: 0,
+        suites: vec![],
+        error: Some(error),
+    }
 }
 
 /// Simple parser for GdUnit4 output
@@ -90,6 +436,7 @@ pub fn parse_test_output(stdout: &str, _stderr: &str) -> TestExecutionResult {
 
     TestExecutionResult {
         success: failed_count == 0 && error_count == 0 && total_count > 0,
+        framework: TestFramework::GdUnit4,
         total_count,
         passed_count,
         failed_count,
@@ -97,6 +444,7 @@ pub fn parse_test_output(stdout: &str, _stderr: &str) -> TestExecutionResult {
         skipped_count,
         duration_ms: 0, // TODO: Extract duration
         suites: vec![], // TODO: Parse individual suites
+        error: None,
     }
 }
 
@@ -107,6 +455,233 @@ fn extract_value(line: &str, key: &str) -> Option<i32> {
     rest[..end].trim().parse().ok()
 }
 
+/// Parse GUT's console output: a `res://...gd[.InnerClass]` heading per
+/// script, `* test_name` per test, then `[Failed]:`, `[Pending]:` or
+/// `[ERROR]:` lines with `at line N` for the test above. Parsing stops at the
+/// run summary, which repeats the failures; its `Time` gives the duration.
+pub fn parse_gut_output(output: &str) -> TestExecutionResult {
+    let mut suites: Vec<TestSuiteResult> = Vec::new();
+    let mut error_count = 0;
+    let mut duration_ms = 0;
+    let mut in_summary = false;
+
+    for line in output.lines() {
+        let line = strip_ansi(line);
+        let line = line.trim();
+        if line.starts_with("= Run Summary") {
+            in_summary = true;
+            continue;
+        }
+        if in_summary {
+            if let Some(time) = line.strip_prefix("Time") {
+                let seconds = time.trim().trim_end_matches('s').trim();
+                if let Ok(seconds) = seconds.parse::<f64>() {
+                    duration_ms = (seconds * 1000.0).round() as i32;
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with("res://") && !line.contains(char::is_whitespace) {
+            if let Some(end) = line.find(".gd") {
+                let path = &line[..end + 3];
+                let file_name = path.rsplit('/').next().unwrap_or(path);
+                let name = match line[end + 3..].strip_prefix('.') {
+                    Some(inner) => format!("{}.{}", file_name, inner),
+                    None => file_name.to_string(),
+                };
+                suites.push(TestSuiteResult {
+                    name,
+                    path: path.to_string(),
+                    success: true,
+                    passed_count: 0,
+                    failed_count: 0,
+                    skipped_count: 0,
+                    cases: Vec::new(),
+                });
+                continue;
+            }
+        }
+        let Some(suite) = suites.last_mut() else {
+            continue;
+        };
+        if let Some(name) = line.strip_prefix("* ") {
+            suite.cases.push(TestCaseResult {
+                name: name.trim().to_string(),
+                success: true,
+                skipped: false,
+                line: None,
+                message: None,
+                stack_overflow: None,
+            });
+            continue;
+        }
+        let Some(case) = suite.cases.last_mut() else {
+            continue;
+        };
+        if let Some(message) = line.strip_prefix("[Failed]:") {
+            case.success = false;
+            append_message(case, message);
+        } else if let Some(message) = line.strip_prefix("[ERROR]:") {
+            case.success = false;
+            error_count += 1;
+            append_message(case, message);
+        } else if let Some(message) = line.strip_prefix("[Pending]:") {
+            case.skipped = true;
+            append_message(case, message);
+        } else if let Some(number) = line.strip_prefix("at line ") {
+            if case.line.is_none() {
+                case.line = number.trim().parse().ok();
+            }
+        }
+    }
+
+    summarize(TestFramework::Gut, suites, error_count, duration_ms)
+}
+
+/// Parse a JUnit XML report (GdUnit4's `results.xml`). A case's message is
+/// its failure text, its line the one in the `FAILED: res://...gd:N` message
+/// (else a leading `line N:`). Suite paths come from that message, else from
+/// `res://{package}/{name}.gd`.
+pub fn parse_junit_xml(xml: &str) -> Result<TestExecutionResult, String> {
+    let report = junit::parse(xml)?;
+    let mut error_count = 0;
+    let mut suite_time = 0.0;
+    let suites = report
+        .suites
+        .into_iter()
+        .map(|suite| {
+            suite_time += suite.time.unwrap_or(0.0);
+            let mut path = None;
+            let cases = suite
+                .cases
+                .into_iter()
+                .map(|case| {
+                    let location = case
+                        .message
+                        .as_deref()
+                        .and_then(|message| message.split_once("res://"))
+                        .and_then(|(_, rest)| rest.rsplit_once(':'))
+                        .map(|(file, line)| (format!("res://{}", file), line.trim().parse().ok()));
+                    let (file, mut line) = location.unzip();
+                    if path.is_none() {
+                        path = file;
+                    }
+                    let details = case.details.as_deref().map(str::trim);
+                    if line.flatten().is_none() {
+                        line = Some(details.and_then(|details| {
+                            let rest = details.strip_prefix("line ")?;
+                            rest[..rest.find(':')?].trim().parse().ok()
+                        }));
+                    }
+                    if case.outcome == junit::Outcome::Error {
+                        error_count += 1;
+                    }
+                    TestCaseResult {
+                        name: case.name,
+                        success: matches!(
+                            case.outcome,
+                            junit::Outcome::Passed | junit::Outcome::Skipped
+                        ),
+                        skipped: case.outcome == junit::Outcome::Skipped,
+                        line: line.flatten(),
+                        message: details.map(String::from).or(case.message),
+                        stack_overflow: None,
+                    }
+                })
+                .collect();
+            let path = path.unwrap_or_else(|| {
+                match suite
+                    .package
+                    .as_deref()
+                    .map(|p| p.trim_start_matches("res://").trim_matches('/'))
+                {
+                    Some(package) if !package.is_empty() => {
+                        format!("res://{}/{}.gd", package, suite.name)
+                    }
+                    _ => format!("res://{}.gd", suite.name),
+                }
+            });
+            TestSuiteResult {
+                name: suite.name,
+                path,
+                success: true,
+                passed_count: 0,
+                failed_count: 0,
+                skipped_count: 0,
+                cases,
+            }
+        })
+        .collect();
+    let seconds = report.time.unwrap_or(suite_time);
+    Ok(summarize(
+        TestFramework::GdUnit4,
+        suites,
+        error_count,
+        (seconds * 1000.0).round() as i32,
+    ))
+}
+
+/// Count each suite's cases and total them
+fn summarize(
+    framework: TestFramework,
+    mut suites: Vec<TestSuiteResult>,
+    error_count: i32,
+    duration_ms: i32,
+) -> TestExecutionResult {
+    for suite in &mut suites {
+        suite.failed_count = suite.cases.iter().filter(|c| !c.success).count() as i32;
+        suite.skipped_count = suite
+            .cases
+            .iter()
+            .filter(|c| c.success && c.skipped)
+            .count() as i32;
+        suite.passed_count = suite.cases.len() as i32 - suite.failed_count - suite.skipped_count;
+        suite.success = suite.failed_count == 0;
+    }
+    let total_count = suites.iter().map(|s| s.cases.len() as i32).sum();
+    let failed_count = suites.iter().map(|s| s.failed_count).sum();
+    TestExecutionResult {
+        success: failed_count == 0 && total_count > 0,
+        framework,
+        total_count,
+        passed_count: suites.iter().map(|s| s.passed_count).sum(),
+        failed_count,
+        error_count,
+        skipped_count: suites.iter().map(|s| s.skipped_count).sum(),
+        duration_ms,
+        suites,
+        error: None,
+    }
+}
+
+/// First message of a case; later ones on their own lines
+fn append_message(case: &mut TestCaseResult, message: &str) {
+    let message = message.trim();
+    case.message = Some(match case.message.take() {
+        Some(previous) => format!("{}\n{}", previous, message),
+        None => message.to_string(),
+    });
+}
+
+/// Drop terminal color codes (`ESC [ ... m`)
+fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +698,157 @@ mod tests {
         assert_eq!(result.skipped_count, 0);
         assert!(!result.success);
     }
+
+    const GDUNIT4_REPORT: &str = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<testsuites id="2026-10-17" name="report_1" tests="4" failures="2" skipped="1" time="0.25">
+	<testsuite id="0" name="test_player" package="tests" tests="3" failures="1" errors="0" skipped="1" time="0.2">
+		<testcase name="test_moves" classname="test_player" time="0.02"/>
+		<testcase name="test_jumps" classname="test_player" time="0.03">
+			<failure message="FAILED: res://tests/unit/test_player.gd:14" type="FAILURE"><![CDATA[
+Expecting:
+ '-460'
+ but was
+ '-400'
+]]></failure>
+		</testcase>
+		<testcase name="test_dash" classname="test_player" time="0.0">
+			<skipped message="SKIPPED: res://tests/unit/test_player.gd:20"/>
+		</testcase>
+	</testsuite>
+	<testsuite id="1" name="test_inventory" package="tests" tests="1" failures="0" errors="1" skipped="0" time="0.05">
+		<testcase name="test_add_item" classname="test_inventory" time="0.05">
+			<error message="ERROR:" type="ABORT">line 31: Invalid call. Nonexistent function 'add' in base 'Nil'.</error>
+		</testcase>
+	</testsuite>
+</testsuites>
+"#;
+
+    #[test]
+    fn test_parse_junit_xml() {
+        let result = parse_junit_xml(GDUNIT4_REPORT).unwrap();
+
+        assert_eq!(result.framework, TestFramework::GdUnit4);
+        assert_eq!(result.total_count, 4);
+        assert_eq!(result.passed_count, 1);
+        assert_eq!(result.failed_count, 2);
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.skipped_count, 1);
+        assert_eq!(result.duration_ms, 250);
+        assert!(!result.success);
+
+        let player = &result.suites[0];
+        assert_eq!(player.name, "test_player");
+        // From the failure message rather than the package
+        assert_eq!(player.path, "res://tests/unit/test_player.gd");
+        let failed = &player.cases[1];
+        assert!(!failed.success);
+        assert_eq!(failed.line, Some(14));
+        assert!(failed.message.as_deref().unwrap().starts_with("Expecting:"));
+        assert!(player.cases[2].skipped);
+        assert!(player.cases[2].success);
+
+        let inventory = &result.suites[1];
+        assert_eq!(inventory.path, "res://tests/test_inventory.gd");
+        assert!(!inventory.success);
+        assert_eq!(inventory.cases[0].line, Some(31));
+    }
+
+    #[test]
+    fn test_parse_junit_xml_malformed() {
+        assert!(parse_junit_xml("<testsuites><testsuite name=\"x\">").is_err());
+    }
+
+    const GUT_OUTPUT: &str = "\
+---  GUT  ---
+[INFO]:  using [res://.gut_editor_config.json] for options
+
+
+res://tests/test_player.gd
+* test_starts_on_floor
+* test_jump_velocity
+    [Failed]:  [-400] expected to equal [-460]:
+          at line 14
+* test_double_jump
+    [Pending]:  not implemented yet
+
+
+res://tests/test_player.gd.TestInventory
+* test_add_item
+* test_remove_missing
+    \u{1b}[31m[ERROR]:  Invalid call. Nonexistent function 'remove' in base 'Nil'.\u{1b}[0m
+          at line 31
+
+
+
+==============================================
+= Run Summary
+==============================================
+
+res://tests/test_player.gd
+- test_jump_velocity
+    [Failed]:  [-400] expected to equal [-460]:
+          at line 14
+
+---- Totals ----
+Scripts               2
+Tests                 5
+  Passing             2
+  Failing             2
+  Pending             1
+Asserts               6
+Time                  0.138s
+";
+
+    #[test]
+    fn test_parse_gut_output() {
+        let result = parse_gut_output(GUT_OUTPUT);
+
+        assert_eq!(result.framework, TestFramework::Gut);
+        assert_eq!(result.total_count, 5);
+        assert_eq!(result.passed_count, 2);
+        assert_eq!(result.failed_count, 2);
+        assert_eq!(result.error_count, 1);
+        assert_eq!(result.skipped_count, 1);
+        assert_eq!(result.duration_ms, 138);
+        assert!(!result.success);
+
+        assert_eq!(result.suites.len(), 2);
+        let player = &result.suites[0];
+        assert_eq!(player.name, "test_player.gd");
+        assert_eq!(player.path, "res://tests/test_player.gd");
+        assert_eq!(
+            (
+                player.passed_count,
+                player.failed_count,
+                player.skipped_count
+            ),
+            (1, 1, 1)
+        );
+        let failed = &player.cases[1];
+        assert_eq!(failed.name, "test_jump_velocity");
+        assert!(!failed.success);
+        assert_eq!(failed.line, Some(14));
+        assert_eq!(
+            failed.message.as_deref(),
+            Some("[-400] expected to equal [-460]:")
+        );
+        assert!(player.cases[2].skipped);
+
+        let inner = &result.suites[1];
+        assert_eq!(inner.name, "test_player.gd.TestInventory");
+        assert_eq!(inner.path, "res://tests/test_player.gd");
+        assert_eq!(inner.cases[1].line, Some(31));
+        assert!(inner.cases[1]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("Invalid call."));
+    }
+
+    #[test]
+    fn test_parse_gut_output_without_tests() {
+        let result = parse_gut_output("---  GUT  ---\nNo tests ran\n");
+        assert_eq!(result.total_count, 0);
+        assert!(!result.success);
+    }
 }
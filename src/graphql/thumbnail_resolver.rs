@@ -0,0 +1,185 @@
+//! Thumbnail Resolver
+//!
+//! generateThumbnails: renders scenes to PNG thumbnails with Godot (see
+//! `thumbnails`), all scenes of the project when none are given, and
+//! Scene.thumbnail: the thumbnail of a scene, if one was rendered.
+
+use std::time::Duration;
+
+use crate::path_utils;
+
+use super::context::GqlContext;
+use super::godot_binary;
+use super::project_resolver::collect_project_files;
+use super::thumbnails;
+use super::types::*;
+
+/// Project import on the first run, then loading and drawing each scene
+const RENDER_TIMEOUT: Duration = Duration::from_secs(120);
+const RENDER_TIMEOUT_PER_SCENE: Duration = Duration::from_secs(10);
+
+const MIN_SIZE: i32 = 16;
+const MAX_SIZE: i32 = 1024;
+
+/// Resolve generateThumbnails mutation
+pub fn resolve_generate_thumbnails(
+    ctx: &GqlContext,
+    scenes: Option<&[String]>,
+    size: i32,
+) -> GenerateThumbnailsResult {
+    let failure = |godot: Option<String>, error: GqlStructuredError| GenerateThumbnailsResult {
+        success: false,
+        godot,
+        rendered_count: 0,
+        thumbnails: Vec::new(),
+        error: Some(error),
+    };
+
+    if ctx.fs.is_virtual() {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "NOT_SUPPORTED",
+                GqlErrorCategory::Validation,
+                "generateThumbnails needs the project on disk",
+            ),
+        );
+    }
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+        return failure(
+            None,
+            GqlStructuredError::new(
+                "INVALID_SIZE",
+                GqlErrorCategory::Validation,
+                format!("size must be {} to {} pixels", MIN_SIZE, MAX_SIZE),
+            ),
+        );
+    }
+    let scenes: Vec<String> = match scenes {
+        Some(scenes) => scenes.to_vec(),
+        None => collect_project_files(ctx)
+            .0
+            .into_iter()
+            .map(|scene| scene.path)
+            .collect(),
+    };
+
+    // Scenes that can't be rendered keep their error; no stale thumbnail survives
+    let mut outcomes: Vec<Option<Result<(), String>>> = Vec::with_capacity(scenes.len());
+    for scene in &scenes {
+        let outcome = match path_utils::to_fs_path(&ctx.project_path, scene) {
+            Err(e) => Some(Err(e.to_string())),
+            Ok(path) if !ctx.fs.is_file(&path) => Some(Err("Scene not found".to_string())),
+            Ok(_) => None,
+        };
+        let thumbnail =
+            path_utils::to_fs_path_unchecked(&ctx.project_path, &thumbnails::thumbnail_path(scene));
+        if outcome.is_none() && ctx.fs.exists(&thumbnail) {
+            if let Err(e) = ctx.fs.remove_file(&thumbnail) {
+                return failure(
+                    None,
+                    GqlStructuredError::new(
+                        "WRITE_FAILED",
+                        GqlErrorCategory::FileSystem,
+                        format!("Cannot replace the thumbnail of {}: {}", scene, e),
+                    ),
+                );
+            }
+        }
+        outcomes.push(outcome);
+    }
+
+    let to_render: Vec<String> = scenes
+        .iter()
+        .zip(&outcomes)
+        .filter(|(_, outcome)| outcome.is_none())
+        .map(|(scene, _)| scene.clone())
+        .collect();
+    let mut godot = None;
+    if !to_render.is_empty() {
+        let binary = match godot_binary::discover(Some(&ctx.project_path)) {
+            Ok(binary) => binary,
+            Err(message) => return failure(
+                None,
+                GqlStructuredError::new("GODOT_NOT_FOUND", GqlErrorCategory::FileSystem, message)
+                    .with_suggestion(
+                    "setGodotPath で Godot 実行ファイルを指定するか、GODOT 環境変数を設定してください",
+                ),
+            ),
+        };
+        godot = Some(binary.path.display().to_string());
+        let timeout = RENDER_TIMEOUT + RENDER_TIMEOUT_PER_SCENE * to_render.len() as u32;
+        let rendered = match thumbnails::render(
+            &binary.path,
+            &ctx.project_path,
+            &to_render,
+            size as u32,
+            timeout,
+        ) {
+            Ok(rendered) => rendered,
+            Err(message) => {
+                return failure(
+                    godot,
+                    GqlStructuredError::new(
+                        "GODOT_RENDER_FAILED",
+                        GqlErrorCategory::Godot,
+                        message,
+                    ),
+                )
+            }
+        };
+        let mut rendered = rendered.into_iter();
+        for outcome in outcomes.iter_mut().filter(|outcome| outcome.is_none()) {
+            *outcome = rendered.next();
+        }
+    }
+
+    let thumbnails: Vec<SceneThumbnail> = scenes
+        .into_iter()
+        .zip(outcomes)
+        .map(|(scene_path, outcome)| {
+            // Godot reporting success is not enough; the file has to be there
+            let outcome = outcome
+                .unwrap_or_else(|| Err("Not rendered".to_string()))
+                .and_then(|_| {
+                    resolve_scene_thumbnail(ctx, &scene_path)
+                        .ok_or_else(|| "Godot saved no thumbnail".to_string())
+                });
+            match outcome {
+                Ok(thumbnail) => SceneThumbnail {
+                    scene_path,
+                    thumbnail_path: Some(thumbnail),
+                    error: None,
+                },
+                Err(error) => SceneThumbnail {
+                    scene_path,
+                    thumbnail_path: None,
+                    error: Some(error),
+                },
+            }
+        })
+        .collect();
+    let rendered_count = thumbnails
+        .iter()
+        .filter(|thumbnail| thumbnail.thumbnail_path.is_some())
+        .count() as i32;
+
+    GenerateThumbnailsResult {
+        success: rendered_count as usize == thumbnails.len(),
+        godot,
+        rendered_count,
+        thumbnails,
+        error: None,
+    }
+}
+
+/// Resolve Scene.thumbnail: the thumbnail's `res://` path when it exists
+pub fn resolve_scene_thumbnail(ctx: &GqlContext, scene_path: &str) -> Option<String> {
+    let thumbnail = thumbnails::thumbnail_path(scene_path);
+    ctx.fs
+        .is_file(&path_utils::to_fs_path_unchecked(
+            &ctx.project_path,
+            &thumbnail,
+        ))
+        .then_some(thumbnail)
+}
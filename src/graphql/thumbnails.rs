@@ -0,0 +1,180 @@
+//! Scene thumbnails rendered by Godot
+//!
+//! Instantiates each scene in a SubViewport of a short-lived Godot running an
+//! embedded SceneTree script and saves what it draws, scaled to fit the
+//! requested size, as `THUMBNAIL_DIR/<scene path>.png`. 3D scenes without a
+//! current camera get one looking at the origin. The viewport has the
+//! project's window size, so 2D scenes are framed as in the game.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::path_utils;
+
+use super::godot_binary;
+
+/// Where the capture script is written for Godot to run
+pub const SCRIPT_PATH: &str = "res://.godot-mcp/tools/thumbnails.gd";
+
+/// Thumbnails mirror the scene's path below this folder
+pub const THUMBNAIL_DIR: &str = "res://.godot-mcp/thumbnails";
+
+const DONE_MARKER: &str = "THUMBNAIL:";
+const ERROR_MARKER: &str = "THUMBNAIL_ERROR:";
+
+/// Renders the scenes given after `--` (thumbnail folder, size, scenes)
+const SCRIPT: &str = r#"extends SceneTree
+## Written by godot-mcp-rs: renders each scene given after `--` to
+## <folder>/<scene path>.png, printing THUMBNAIL or THUMBNAIL_ERROR per scene.
+
+
+func _initialize() -> void:
+	var args := OS.get_cmdline_user_args()
+	if args.size() < 2:
+		printerr("THUMBNAIL_ERROR: Expected <folder> <size> <scene>...")
+		quit(1)
+		return
+	var folder: String = args[0]
+	var size := int(args[1])
+	for scene_path in args.slice(2):
+		var error: String = await _render(scene_path, folder, size)
+		if error.is_empty():
+			print("THUMBNAIL: ", scene_path)
+		else:
+			print("THUMBNAIL_ERROR: ", scene_path, ": ", error)
+	quit()
+
+
+func _render(scene_path: String, folder: String, size: int) -> String:
+	var scene = load(scene_path)
+	if not scene is PackedScene:
+		return "Cannot load scene"
+	var viewport := SubViewport.new()
+	viewport.size = Vector2i(
+		ProjectSettings.get_setting("display/window/size/viewport_width", 1152),
+		ProjectSettings.get_setting("display/window/size/viewport_height", 648)
+	)
+	viewport.own_world_3d = true
+	viewport.render_target_update_mode = SubViewport.UPDATE_ALWAYS
+	get_root().add_child(viewport)
+	var root: Node = scene.instantiate()
+	viewport.add_child(root)
+	if root is Node3D and viewport.get_camera_3d() == null:
+		var camera := Camera3D.new()
+		viewport.add_child(camera)
+		camera.look_at_from_position(Vector3(0, 2, 5), Vector3.ZERO)
+		camera.current = true
+	# Containers, themes and first animation frames settle before drawing
+	for i in 3:
+		await process_frame
+	await RenderingServer.frame_post_draw
+	var image := viewport.get_texture().get_image()
+	viewport.queue_free()
+	if image == null or image.is_empty():
+		return "Godot rendered no image"
+	var factor := float(size) / max(image.get_width(), image.get_height())
+	image.resize(
+		max(1, int(image.get_width() * factor)),
+		max(1, int(image.get_height() * factor)),
+		Image.INTERPOLATE_LANCZOS
+	)
+	var target := folder.path_join(scene_path.trim_prefix("res://") + ".png")
+	DirAccess.make_dir_recursive_absolute(target.get_base_dir())
+	var error := image.save_png(target)
+	if error != OK:
+		return "Cannot save %s (error %d)" % [target, error]
+	return ""
+"#;
+
+/// `res://` path of a scene's thumbnail
+pub fn thumbnail_path(scene_path: &str) -> String {
+    format!(
+        "{}/{}.png",
+        THUMBNAIL_DIR,
+        scene_path.trim_start_matches("res://")
+    )
+}
+
+/// Render `scenes` with Godot; per scene, in order, whether its thumbnail
+/// was saved or why not
+pub fn render(
+    binary: &Path,
+    project_root: &Path,
+    scenes: &[String],
+    size: u32,
+    timeout: Duration,
+) -> Result<Vec<Result<(), String>>, String> {
+    let script = path_utils::to_fs_path_unchecked(project_root, SCRIPT_PATH);
+    if fs::read_to_string(&script).ok().as_deref() != Some(SCRIPT) {
+        if let Some(parent) = script.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&script, SCRIPT)
+            .map_err(|e| format!("Failed to write {}: {}", SCRIPT_PATH, e))?;
+    }
+    let size = size.to_string();
+    let mut args = vec!["--script", SCRIPT_PATH, "--", THUMBNAIL_DIR, &size];
+    args.extend(scenes.iter().map(String::as_str));
+    let (_, output) =
+        godot_binary::run_rendering(binary, project_root, &args, timeout, "rendering thumbnails")?;
+    Ok(parse_output(&output, scenes))
+}
+
+/// Outcome per scene from the markers; scenes Godot never reported get the
+/// first error it printed
+pub fn parse_output(output: &str, scenes: &[String]) -> Vec<Result<(), String>> {
+    let lines: Vec<&str> = output.lines().map(str::trim).collect();
+    let godot_error = lines
+        .iter()
+        .find(|line| line.starts_with("SCRIPT ERROR:") || line.starts_with("ERROR:"))
+        .map(|line| line.to_string())
+        .unwrap_or_else(|| "Godot stopped before rendering the scene".to_string());
+    scenes
+        .iter()
+        .map(|scene| {
+            for line in &lines {
+                if line.strip_prefix(DONE_MARKER).map(str::trim) == Some(scene.as_str()) {
+                    return Ok(());
+                }
+                if let Some(error) = line
+                    .strip_prefix(ERROR_MARKER)
+                    .and_then(|rest| rest.trim().strip_prefix(scene.as_str()))
+                    .and_then(|rest| rest.strip_prefix(':'))
+                {
+                    return Err(error.trim().to_string());
+                }
+            }
+            Err(godot_error.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_path() {
+        assert_eq!(
+            thumbnail_path("res://levels/main.tscn"),
+            "res://.godot-mcp/thumbnails/levels/main.tscn.png"
+        );
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let scenes: Vec<String> = ["res://a.tscn", "res://b.tscn", "res://c.tscn"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let output = "Godot Engine v4.3.stable\n\
+            THUMBNAIL: res://a.tscn\n\
+            THUMBNAIL_ERROR: res://b.tscn: Cannot load scene\n\
+            ERROR: Condition \"!is_inside_tree()\" is true.\n";
+        let results = parse_output(output, &scenes);
+        assert_eq!(results[0], Ok(()));
+        assert_eq!(results[1], Err("Cannot load scene".to_string()));
+        assert!(results[2].as_ref().unwrap_err().starts_with("ERROR:"));
+    }
+}
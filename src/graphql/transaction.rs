@@ -0,0 +1,615 @@
+//! File Transactions
+//!
+//! While a transaction is open (`beginTransaction`), every project file the
+//! server writes, appends to, moves or deletes is staged in memory by
+//! [`TransactionFs`] instead of reaching the filesystem below it. Reads see
+//! the staged changes, so later operations of the transaction build on
+//! earlier ones. `commitTransaction` applies the staged changes as one unit:
+//! when a write fails, the files already written are restored and nothing of
+//! the transaction remains on disk. `rollbackTransaction` discards them.
+//! The changes are journaled before they are applied (see `journal`), so a
+//! commit interrupted by a crash is completed when the project is next opened.
+//!
+//! Folders can be created inside a transaction, but not removed or moved.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::project_registry::ProjectRegistry;
+
+use super::journal::{self, Journal, JournalChange};
+use super::vfs::{is_a_directory, normalize, not_found, Metadata, OsFs, ProjectFs};
+
+/// Filesystem that stages changes while a transaction is open
+pub struct TransactionFs {
+    inner: Arc<dyn ProjectFs>,
+    state: RwLock<Option<Transaction>>,
+    /// Project whose journal commits are recorded in (None: not journaled)
+    journal_root: Option<PathBuf>,
+}
+
+struct Transaction {
+    id: String,
+    name: String,
+    files: BTreeMap<PathBuf, StagedFile>,
+    /// Folders created in the transaction
+    dirs: BTreeMap<PathBuf, SystemTime>,
+    /// Folders whose entries changed, with their new modification time
+    touched: HashMap<PathBuf, SystemTime>,
+    last_modified: SystemTime,
+}
+
+enum StagedFile {
+    Written { data: Vec<u8>, modified: SystemTime },
+    Removed,
+}
+
+/// Open (or just finished) transaction and the files it changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStatus {
+    pub id: String,
+    pub name: String,
+    /// Written or deleted files, sorted
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum TransactionError {
+    /// No transaction is open
+    NotOpen,
+    /// Another transaction (its id) is already open
+    AlreadyOpen(String),
+    /// Applying a staged change failed; the commit was undone
+    Write { path: PathBuf, source: io::Error },
+    /// Journaling the changes failed; nothing was applied
+    Journal(io::Error),
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionError::NotOpen => write!(f, "No transaction is open"),
+            TransactionError::AlreadyOpen(id) => write!(f, "Transaction {} is already open", id),
+            TransactionError::Write { path, source } => {
+                write!(f, "Failed to write {}: {}", path.display(), source)
+            }
+            TransactionError::Journal(source) => {
+                write!(f, "Failed to journal the commit: {}", source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+impl Transaction {
+    fn status(&self) -> TransactionStatus {
+        TransactionStatus {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            paths: self.files.keys().cloned().collect(),
+        }
+    }
+
+    /// Next modification time, later than every earlier one
+    fn tick(&mut self) -> SystemTime {
+        let now = SystemTime::now();
+        self.last_modified = if now > self.last_modified {
+            now
+        } else {
+            self.last_modified + Duration::from_nanos(1)
+        };
+        self.last_modified
+    }
+
+    /// Update a folder's modification time after its entries changed
+    fn touch_parent(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            let modified = self.tick();
+            self.touched.insert(parent.to_path_buf(), modified);
+        }
+    }
+}
+
+impl TransactionFs {
+    pub fn new(inner: Arc<dyn ProjectFs>) -> Self {
+        Self {
+            inner,
+            state: RwLock::new(None),
+            journal_root: None,
+        }
+    }
+
+    /// Transaction layer over the disk shared by every context of
+    /// `project_root`, so a transaction spans requests
+    ///
+    /// Creating it (once per project and process) recovers the operations a
+    /// crash interrupted in the project.
+    pub fn shared(project_root: &Path) -> Arc<Self> {
+        static LAYERS: ProjectRegistry<Arc<TransactionFs>> = ProjectRegistry::new();
+
+        LAYERS
+            .lock()
+            .entry(project_root.to_path_buf())
+            .or_insert_with(|| {
+                journal::recover(project_root);
+                Arc::new(Self {
+                    journal_root: Some(project_root.to_path_buf()),
+                    ..Self::new(Arc::new(OsFs))
+                })
+            })
+            .clone()
+    }
+
+    /// The open transaction, if any
+    pub fn status(&self) -> Option<TransactionStatus> {
+        self.read_state().as_ref().map(Transaction::status)
+    }
+
+    /// Open a transaction; returns its id
+    pub fn begin(&self, name: &str) -> Result<String, TransactionError> {
+        let mut state = self.write_state();
+        if let Some(open) = state.as_ref() {
+            return Err(TransactionError::AlreadyOpen(open.id.clone()));
+        }
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let id = format!("tx-{}", millis);
+        *state = Some(Transaction {
+            id: id.clone(),
+            name: name.to_string(),
+            files: BTreeMap::new(),
+            dirs: BTreeMap::new(),
+            touched: HashMap::new(),
+            last_modified: SystemTime::now(),
+        });
+        Ok(id)
+    }
+
+    /// Apply the staged changes and close the transaction
+    ///
+    /// On the first failing write, every change already applied is undone
+    /// and the transaction stays open, so it can be committed again or
+    /// rolled back.
+    pub fn commit(&self) -> Result<TransactionStatus, TransactionError> {
+        let mut state = self.write_state();
+        let transaction = state.as_ref().ok_or(TransactionError::NotOpen)?;
+        let journal = self
+            .journal(transaction)
+            .map_err(TransactionError::Journal)?;
+
+        let mut created_dirs = Vec::new();
+        let mut applied: Vec<(&Path, Option<Vec<u8>>)> = Vec::new();
+        let mut failure = None;
+        for dir in transaction.dirs.keys() {
+            if self.inner.is_dir(dir) {
+                continue;
+            }
+            match self.inner.create_dir_all(dir) {
+                Ok(()) => created_dirs.push(dir.as_path()),
+                Err(source) => {
+                    failure = Some((dir.clone(), source));
+                    break;
+                }
+            }
+        }
+        if failure.is_none() {
+            for (path, file) in &transaction.files {
+                let previous = self.inner.read(path).ok();
+                let result = match file {
+                    StagedFile::Written { data, .. } => self.inner.write(path, data),
+                    StagedFile::Removed if previous.is_some() => self.inner.remove_file(path),
+                    StagedFile::Removed => Ok(()),
+                };
+                if let Err(source) = result {
+                    failure = Some((path.clone(), source));
+                    break;
+                }
+                applied.push((path, previous));
+            }
+        }
+
+        if let Some((path, source)) = failure {
+            // Best effort: put back what was there before the commit
+            for (path, previous) in applied.into_iter().rev() {
+                let _ = match previous {
+                    Some(data) => self.inner.write(path, &data),
+                    None => self.inner.remove_file(path),
+                };
+            }
+            for dir in created_dirs.into_iter().rev() {
+                let _ = self.inner.remove_dir(dir);
+            }
+            if let Some(journal) = journal {
+                journal.finish();
+            }
+            return Err(TransactionError::Write { path, source });
+        }
+
+        let status = transaction.status();
+        *state = None;
+        if let Some(journal) = journal {
+            journal.finish();
+        }
+        Ok(status)
+    }
+
+    /// Record the changes a commit is about to apply
+    fn journal(&self, transaction: &Transaction) -> io::Result<Option<Journal>> {
+        let Some(root) = self.journal_root.as_deref() else {
+            return Ok(None);
+        };
+        if self.inner.is_virtual() {
+            return Ok(None);
+        }
+        let dirs = transaction
+            .dirs
+            .keys()
+            .map(|path| JournalChange::CreateDir { path });
+        let files = transaction.files.iter().map(|(path, file)| match file {
+            StagedFile::Written { data, .. } => JournalChange::Write { path, data },
+            StagedFile::Removed => JournalChange::Remove { path },
+        });
+        let changes: Vec<JournalChange> = dirs.chain(files).collect();
+        Journal::begin(
+            root,
+            &format!("commitTransaction ({})", transaction.name),
+            &changes,
+        )
+        .map(Some)
+    }
+
+    /// Discard the staged changes and close the transaction
+    pub fn rollback(&self) -> Result<TransactionStatus, TransactionError> {
+        self.write_state()
+            .take()
+            .map(|transaction| transaction.status())
+            .ok_or(TransactionError::NotOpen)
+    }
+
+    fn read_state(&self) -> RwLockReadGuard<'_, Option<Transaction>> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_state(&self) -> RwLockWriteGuard<'_, Option<Transaction>> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Metadata as seen through a transaction
+    fn staged_metadata(&self, transaction: &Transaction, path: &Path) -> io::Result<Metadata> {
+        match transaction.files.get(path) {
+            Some(StagedFile::Written { data, modified }) => {
+                return Ok(Metadata {
+                    is_dir: false,
+                    len: data.len() as u64,
+                    modified: *modified,
+                })
+            }
+            Some(StagedFile::Removed) => return Err(not_found(path)),
+            None => {}
+        }
+        let mut metadata = match transaction.dirs.get(path) {
+            Some(created) => Metadata {
+                is_dir: true,
+                len: 0,
+                modified: *created,
+            },
+            None => self.inner.metadata(path)?,
+        };
+        if let Some(modified) = transaction.touched.get(path) {
+            metadata.modified = *modified;
+        }
+        Ok(metadata)
+    }
+
+    /// Stage a file write, checking what the real write would check
+    fn stage_write(
+        &self,
+        transaction: &mut Transaction,
+        path: &Path,
+        data: Vec<u8>,
+    ) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            if !self.staged_metadata(transaction, parent)?.is_dir {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotADirectory,
+                    format!("Not a directory: {}", parent.display()),
+                ));
+            }
+        }
+        let created = match self.staged_metadata(transaction, path) {
+            Ok(metadata) if metadata.is_dir => return Err(is_a_directory(path)),
+            Ok(_) => false,
+            Err(_) => true,
+        };
+        let modified = transaction.tick();
+        transaction
+            .files
+            .insert(path.to_path_buf(), StagedFile::Written { data, modified });
+        if created {
+            transaction.touch_parent(path);
+        }
+        Ok(())
+    }
+
+    fn stage_remove(&self, transaction: &mut Transaction, path: &Path) -> io::Result<()> {
+        if self.staged_metadata(transaction, path)?.is_dir {
+            return Err(is_a_directory(path));
+        }
+        transaction
+            .files
+            .insert(path.to_path_buf(), StagedFile::Removed);
+        transaction.touch_parent(path);
+        Ok(())
+    }
+}
+
+impl ProjectFs for TransactionFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let state = self.read_state();
+        let Some(transaction) = state.as_ref() else {
+            return self.inner.read(path);
+        };
+        match transaction.files.get(&normalize(path)) {
+            Some(StagedFile::Written { data, .. }) => Ok(data.clone()),
+            Some(StagedFile::Removed) => Err(not_found(path)),
+            None => self.inner.read(path),
+        }
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        if self.read_state().is_none() {
+            return self.inner.read_to_string(path);
+        }
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut state = self.write_state();
+        match state.as_mut() {
+            Some(transaction) => self.stage_write(transaction, &normalize(path), contents.to_vec()),
+            None => self.inner.write(path, contents),
+        }
+    }
+
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut state = self.write_state();
+        let Some(transaction) = state.as_mut() else {
+            return self.inner.append(path, contents);
+        };
+        let path = normalize(path);
+        let mut data = match transaction.files.get(&path) {
+            Some(StagedFile::Written { data, .. }) => data.clone(),
+            Some(StagedFile::Removed) => Vec::new(),
+            None => match self.inner.read(&path) {
+                Ok(data) => data,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(e),
+            },
+        };
+        data.extend_from_slice(contents);
+        self.stage_write(transaction, &path, data)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.write_state();
+        let Some(transaction) = state.as_mut() else {
+            return self.inner.create_dir_all(path);
+        };
+        let path = normalize(path);
+        for ancestor in path.ancestors().collect::<Vec<_>>().into_iter().rev() {
+            match self.staged_metadata(transaction, ancestor) {
+                Ok(metadata) if metadata.is_dir => {}
+                Ok(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AlreadyExists,
+                        format!("File exists: {}", ancestor.display()),
+                    ))
+                }
+                Err(_) => {
+                    let modified = transaction.tick();
+                    transaction.dirs.insert(ancestor.to_path_buf(), modified);
+                    transaction.touch_parent(ancestor);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let state = self.read_state();
+        let Some(transaction) = state.as_ref() else {
+            return self.inner.read_dir(path);
+        };
+        let path = normalize(path);
+        if !self.staged_metadata(transaction, &path)?.is_dir {
+            return Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!("Not a directory: {}", path.display()),
+            ));
+        }
+
+        let mut entries: BTreeSet<PathBuf> = if transaction.dirs.contains_key(&path) {
+            BTreeSet::new()
+        } else {
+            self.inner.read_dir(&path)?.into_iter().collect()
+        };
+        for (file, staged) in &transaction.files {
+            if file.parent() != Some(path.as_path()) {
+                continue;
+            }
+            match staged {
+                StagedFile::Written { .. } => entries.insert(file.clone()),
+                StagedFile::Removed => entries.remove(file),
+            };
+        }
+        entries.extend(
+            transaction
+                .dirs
+                .keys()
+                .filter(|dir| dir.parent() == Some(path.as_path()))
+                .cloned(),
+        );
+        Ok(entries.into_iter().collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let state = self.read_state();
+        match state.as_ref() {
+            Some(transaction) => self.staged_metadata(transaction, &normalize(path)),
+            None => self.inner.metadata(path),
+        }
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.write_state();
+        match state.as_mut() {
+            Some(transaction) => self.stage_remove(transaction, &normalize(path)),
+            None => self.inner.remove_file(path),
+        }
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        if self.read_state().is_some() {
+            return Err(folder_change_unsupported(path));
+        }
+        self.inner.remove_dir(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        if self.read_state().is_some() {
+            return Err(folder_change_unsupported(path));
+        }
+        self.inner.remove_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut state = self.write_state();
+        let Some(transaction) = state.as_mut() else {
+            return self.inner.rename(from, to);
+        };
+        let (from, to) = (normalize(from), normalize(to));
+        if self.staged_metadata(transaction, &from)?.is_dir {
+            return Err(folder_change_unsupported(&from));
+        }
+        let data = match transaction.files.get(&from) {
+            Some(StagedFile::Written { data, .. }) => data.clone(),
+            _ => self.inner.read(&from)?,
+        };
+        self.stage_write(transaction, &to, data)?;
+        self.stage_remove(transaction, &from)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        if self.read_state().is_none() {
+            return self.inner.copy(from, to);
+        }
+        let contents = self.read(from)?;
+        self.write(to, &contents)?;
+        Ok(contents.len() as u64)
+    }
+
+    /// Staged files are not on disk yet, so external tools cannot see them
+    fn is_virtual(&self) -> bool {
+        self.inner.is_virtual() || self.read_state().is_some()
+    }
+}
+
+impl fmt::Debug for TransactionFs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionFs")
+            .field("inner", &self.inner)
+            .field("transaction", &self.status())
+            .finish()
+    }
+}
+
+fn folder_change_unsupported(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "Folders cannot be removed or moved inside a transaction: {}",
+            path.display()
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::vfs::MemoryFs;
+
+    fn staged_fs(root: &Path) -> TransactionFs {
+        let fs = MemoryFs::from_json(
+            root,
+            &serde_json::json!({ "a.gd": "extends Node\n", "b.gd": "extends Node2D\n" }),
+        )
+        .unwrap();
+        TransactionFs::new(Arc::new(fs))
+    }
+
+    #[test]
+    fn test_staged_changes_are_visible_until_rollback() {
+        let root = Path::new("/project");
+        let fs = staged_fs(root);
+        fs.begin("edit").unwrap();
+        assert!(matches!(
+            fs.begin("again"),
+            Err(TransactionError::AlreadyOpen(_))
+        ));
+
+        fs.write(&root.join("a.gd"), b"extends Control\n").unwrap();
+        fs.create_dir_all(&root.join("ui")).unwrap();
+        fs.write(&root.join("ui/hud.gd"), b"extends Control\n")
+            .unwrap();
+        fs.rename(&root.join("b.gd"), &root.join("ui/b.gd"))
+            .unwrap();
+        assert_eq!(
+            fs.read_to_string(&root.join("a.gd")).unwrap(),
+            "extends Control\n"
+        );
+        assert!(!fs.exists(&root.join("b.gd")));
+        let mut entries = fs.read_dir(&root.join("ui")).unwrap();
+        entries.sort();
+        assert_eq!(entries, vec![root.join("ui/b.gd"), root.join("ui/hud.gd")]);
+        assert!(fs.remove_dir_all(&root.join("ui")).is_err());
+
+        let status = fs.rollback().unwrap();
+        assert_eq!(status.paths.len(), 4);
+        assert_eq!(
+            fs.read_to_string(&root.join("a.gd")).unwrap(),
+            "extends Node\n"
+        );
+        assert!(fs.exists(&root.join("b.gd")));
+        assert!(!fs.exists(&root.join("ui")));
+        assert!(matches!(fs.rollback(), Err(TransactionError::NotOpen)));
+    }
+
+    #[test]
+    fn test_commit_applies_staged_changes() {
+        let root = Path::new("/project");
+        let fs = staged_fs(root);
+        fs.begin("edit").unwrap();
+        fs.append(&root.join("a.gd"), b"var x\n").unwrap();
+        fs.remove_file(&root.join("b.gd")).unwrap();
+        fs.create_dir_all(&root.join("ui")).unwrap();
+        fs.write(&root.join("ui/hud.gd"), b"extends Control\n")
+            .unwrap();
+        fs.commit().unwrap();
+
+        assert!(fs.status().is_none());
+        assert_eq!(
+            fs.inner.read_to_string(&root.join("a.gd")).unwrap(),
+            "extends Node\nvar x\n"
+        );
+        assert!(!fs.inner.exists(&root.join("b.gd")));
+        assert!(fs.inner.is_file(&root.join("ui/hud.gd")));
+    }
+}
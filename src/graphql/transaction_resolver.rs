@@ -0,0 +1,110 @@
+//! Transaction Resolver
+//!
+//! beginTransaction / commitTransaction / rollbackTransaction over the file
+//! staging layer (see `transaction`). When the editor plugin is connected,
+//! its live changes are grouped into one undo action alongside.
+
+use super::context::GqlContext;
+use super::live_resolver;
+use super::project_resolver::to_res_path;
+use super::transaction::{TransactionError, TransactionStatus};
+use super::types::*;
+
+/// Resolve beginTransaction mutation
+pub async fn resolve_begin_transaction(ctx: &GqlContext, name: String) -> TransactionResult {
+    match ctx.transaction.begin(&name) {
+        Ok(id) => {
+            // The editor groups its own changes; without an editor only files are staged
+            let _ = live_resolver::resolve_begin_transaction(ctx, name).await;
+            TransactionResult::ok(id)
+        }
+        Err(e) => failure(ctx, e),
+    }
+}
+
+/// Resolve commitTransaction mutation: write every staged file or none
+pub async fn resolve_commit_transaction(ctx: &GqlContext) -> TransactionResult {
+    match ctx.transaction.commit() {
+        Ok(status) => {
+            let _ = live_resolver::resolve_commit_transaction(ctx).await;
+            finished(ctx, status, "Transaction committed")
+        }
+        Err(e) => failure(ctx, e),
+    }
+}
+
+/// Resolve rollbackTransaction mutation: discard every staged file
+pub async fn resolve_rollback_transaction(ctx: &GqlContext) -> TransactionResult {
+    match ctx.transaction.rollback() {
+        Ok(status) => {
+            let _ = live_resolver::resolve_rollback_transaction(ctx).await;
+            finished(ctx, status, "Transaction rolled back")
+        }
+        Err(e) => failure(ctx, e),
+    }
+}
+
+fn finished(ctx: &GqlContext, status: TransactionStatus, message: &str) -> TransactionResult {
+    TransactionResult {
+        success: true,
+        transaction_id: Some(status.id),
+        message: Some(format!("{} ({} files)", message, status.paths.len())),
+        files: status
+            .paths
+            .iter()
+            .map(|path| to_res_path(&ctx.project_path, path))
+            .collect(),
+        error: None,
+    }
+}
+
+fn failure(ctx: &GqlContext, error: TransactionError) -> TransactionResult {
+    let message = error.to_string();
+    let structured = match &error {
+        TransactionError::NotOpen => GqlStructuredError::new(
+            "NO_TRANSACTION",
+            GqlErrorCategory::Validation,
+            message.clone(),
+        )
+        .with_suggestion("beginTransaction でトランザクションを開始してください"),
+        TransactionError::AlreadyOpen(_) => GqlStructuredError::new(
+            "TRANSACTION_ALREADY_OPEN",
+            GqlErrorCategory::Validation,
+            message.clone(),
+        )
+        .with_suggestion(
+            "開いているトランザクションを commitTransaction または rollbackTransaction で終了してください",
+        ),
+        TransactionError::Write { path, .. } => {
+            let mut structured = GqlStructuredError::new(
+                "COMMIT_FAILED",
+                GqlErrorCategory::FileSystem,
+                message.clone(),
+            )
+            .with_suggestion(
+                "変更は取り消され、トランザクションは開いたままです。原因を解消して再度コミットするか、ロールバックしてください",
+            );
+            structured.location = Some(GqlErrorLocation {
+                file: Some(to_res_path(&ctx.project_path, path)),
+                line: None,
+                column: None,
+            });
+            structured
+        }
+        TransactionError::Journal(_) => GqlStructuredError::new(
+            "JOURNAL_FAILED",
+            GqlErrorCategory::FileSystem,
+            message.clone(),
+        )
+        .with_suggestion(
+            "変更は適用されていません。res://.godot-mcp/journal に書き込めるか確認して再度コミットしてください",
+        ),
+    };
+    TransactionResult {
+        success: false,
+        transaction_id: ctx.transaction.status().map(|status| status.id),
+        message: Some(message),
+        files: Vec::new(),
+        error: Some(structured),
+    }
+}
@@ -3,7 +3,7 @@
 //! These types correspond to `docs/gql/schema.graphql`.
 //! Keep in sync with the SDL.
 
-use async_graphql::{Enum, InputObject, Object, SimpleObject};
+use async_graphql::{ComplexObject, Context, Enum, InputObject, Object, SimpleObject};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -148,6 +148,8 @@ pub struct Scene {
     pub root: SceneNode,
     pub all_nodes: Vec<SceneNode>,
     pub external_resources: Vec<ExternalResource>,
+    pub sub_resources: Vec<SubResource>,
+    pub inherits_from: Option<String>,
 }
 
 #[Object]
@@ -167,6 +169,81 @@ impl Scene {
     async fn external_resources(&self) -> &[ExternalResource] {
         &self.external_resources
     }
+
+    async fn sub_resources(&self) -> &[SubResource] {
+        &self.sub_resources
+    }
+
+    /// Base scene of an inherited scene (root node instancing another scene)
+    async fn inherits_from(&self) -> Option<&str> {
+        self.inherits_from.as_deref()
+    }
+
+    /// PNG rendered by generateThumbnails, if there is one
+    async fn thumbnail(&self, ctx: &Context<'_>) -> Option<String> {
+        let gql_ctx = ctx.data::<super::context::GqlContext>().ok()?;
+        super::thumbnail_resolver::resolve_scene_thumbnail(gql_ctx, &self.path)
+    }
+}
+
+/// Resource embedded in a scene (`[sub_resource]`), e.g. shapes, materials, curves
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SubResource {
+    pub id: String,
+    #[graphql(name = "type")]
+    pub resource_type: String,
+    pub properties: Vec<Property>,
+}
+
+/// Resource file (.tres) structure
+#[derive(Debug, Clone)]
+pub struct Resource {
+    pub path: String,
+    pub resource_type: String,
+    pub uid: Option<String>,
+    pub script_class: Option<String>,
+    pub external_resources: Vec<ExternalResource>,
+    pub sub_resources: Vec<SubResource>,
+    pub properties: Vec<Property>,
+}
+
+#[Object]
+impl Resource {
+    async fn path(&self) -> &str {
+        &self.path
+    }
+
+    #[graphql(name = "type")]
+    async fn resource_type(&self) -> &str {
+        &self.resource_type
+    }
+
+    /// `uid://...` from the file header
+    async fn uid(&self) -> Option<&str> {
+        self.uid.as_deref()
+    }
+
+    /// `class_name` of the attached script, for custom resources
+    async fn script_class(&self) -> Option<&str> {
+        self.script_class.as_deref()
+    }
+
+    async fn external_resources(&self) -> &[ExternalResource] {
+        &self.external_resources
+    }
+
+    async fn sub_resources(&self) -> &[SubResource] {
+        &self.sub_resources
+    }
+
+    /// Properties of the `[resource]` section
+    async fn properties(&self) -> &[Property] {
+        &self.properties
+    }
+
+    async fn property(&self, name: String) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
 }
 
 /// Scene node from file analysis
@@ -180,6 +257,8 @@ pub struct SceneNode {
     pub script: Option<Script>,
     pub groups: Vec<String>,
     pub signals: Vec<SignalConnection>,
+    /// Path of the scene this node instances
+    pub instance_path: Option<String>,
 }
 
 #[Object]
@@ -220,6 +299,11 @@ impl SceneNode {
     async fn signals(&self) -> &[SignalConnection] {
         &self.signals
     }
+
+    /// Path of the scene this node instances (`instance=ExtResource(...)`), if any
+    async fn instance_path(&self) -> Option<&str> {
+        self.instance_path.as_deref()
+    }
 }
 
 /// Live scene from editor
@@ -300,16 +384,29 @@ impl LiveNode {
     }
 }
 
+/// Language of a script file
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ScriptLanguage {
+    Gdscript,
+    /// C# script of a Godot .NET project
+    Csharp,
+}
+
 /// Script analysis result
 #[derive(Debug, Clone)]
 pub struct Script {
     pub path: String,
+    pub language: ScriptLanguage,
     pub extends: String,
     pub class_name: Option<String>,
     pub functions: Vec<Function>,
     pub variables: Vec<Variable>,
     pub signals: Vec<SignalDefinition>,
     pub exports: Vec<Variable>,
+    pub constants: Vec<Constant>,
+    pub enums: Vec<EnumDefinition>,
+    pub inner_classes: Vec<InnerClass>,
+    pub syntax_errors: Vec<ScriptSyntaxError>,
 }
 
 #[Object]
@@ -318,6 +415,10 @@ impl Script {
         &self.path
     }
 
+    async fn language(&self) -> ScriptLanguage {
+        self.language
+    }
+
     async fn extends(&self) -> &str {
         &self.extends
     }
@@ -341,6 +442,24 @@ impl Script {
     async fn exports(&self) -> &[Variable] {
         &self.exports
     }
+
+    async fn constants(&self) -> &[Constant] {
+        &self.constants
+    }
+
+    async fn enums(&self) -> &[EnumDefinition] {
+        &self.enums
+    }
+
+    /// Inner `class` declarations, nested as written
+    async fn inner_classes(&self) -> &[InnerClass] {
+        &self.inner_classes
+    }
+
+    /// Syntax errors found while parsing; the other fields cover what could be parsed
+    async fn syntax_errors(&self) -> &[ScriptSyntaxError] {
+        &self.syntax_errors
+    }
 }
 
 // ======================
@@ -378,6 +497,13 @@ pub struct Vector3 {
     pub z: f64,
 }
 
+#[derive(Debug, Clone, InputObject)]
+pub struct Vector3Input {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
 // ======================
 // Signals / Scripts
 // ======================
@@ -386,6 +512,46 @@ pub struct Vector3 {
 pub struct Function {
     pub name: String,
     pub arguments: Vec<String>,
+    pub return_type: Option<String>,
+    /// Line of the `func` keyword (1-based)
+    pub line: i32,
+}
+
+/// Inner class declared with `class Name:` inside a script
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InnerClass {
+    pub name: String,
+    pub extends: Option<String>,
+    pub line: i32,
+    pub functions: Vec<Function>,
+    /// All member variables, exported or not
+    pub variables: Vec<Variable>,
+    pub signals: Vec<SignalDefinition>,
+    pub constants: Vec<Constant>,
+    pub enums: Vec<EnumDefinition>,
+    pub inner_classes: Vec<InnerClass>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Constant {
+    pub name: String,
+    #[graphql(name = "type")]
+    pub const_type: Option<String>,
+    /// Value expression as written
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EnumDefinition {
+    /// `None` for anonymous enums
+    pub name: Option<String>,
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScriptSyntaxError {
+    pub line: i32,
+    pub message: String,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
@@ -417,6 +583,7 @@ pub struct SignalConnection {
 }
 
 #[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
 pub struct ExternalResource {
     pub id: i32,
     #[graphql(name = "type")]
@@ -424,6 +591,40 @@ pub struct ExternalResource {
     pub path: String,
 }
 
+#[ComplexObject]
+impl ExternalResource {
+    /// Header-derived metadata of binary assets (images, audio, meshes)
+    async fn preview(&self, ctx: &Context<'_>) -> Option<ResourcePreview> {
+        let gql_ctx = ctx.data::<super::context::GqlContext>().ok()?;
+        super::project_resolver::resolve_resource_preview(gql_ctx, &self.path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ResourcePreviewKind {
+    Image,
+    Audio,
+    Mesh,
+}
+
+/// Metadata read from the headers of a binary asset
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ResourcePreview {
+    pub path: String,
+    pub kind: ResourcePreviewKind,
+    /// Container/codec (PNG, JPEG, WebP, WAV, Ogg Vorbis, Ogg Opus, OBJ, glTF, GLB)
+    pub format: String,
+    pub file_size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub channels: Option<i32>,
+    pub sample_rate: Option<i32>,
+    pub duration_sec: Option<f64>,
+    pub vertex_count: Option<i64>,
+    /// Triangles (glTF) or faces (OBJ)
+    pub face_count: Option<i64>,
+}
+
 // ======================
 // Node Type Metadata
 // ======================
@@ -457,6 +658,30 @@ pub struct AddNodeInput {
     pub groups: Option<Vec<String>>,
 }
 
+/// Node created where the user is looking in the editor
+#[derive(Debug, Clone, InputObject)]
+pub struct AddNodeAtCursorInput {
+    /// Node class to create (exclusive with scenePath)
+    #[graphql(name = "type")]
+    pub node_type: Option<String>,
+    /// Scene to instance (exclusive with type)
+    pub scene_path: Option<String>,
+    #[graphql(default = ".")]
+    pub parent: String,
+    /// Defaults to the class or scene root name
+    pub name: Option<String>,
+    /// 3D only: distance in front of the editor camera (default 5)
+    pub distance: Option<f64>,
+}
+
+/// Placement of the 3D editor camera
+#[derive(Debug, Clone, InputObject)]
+pub struct SetEditorCameraInput {
+    pub position: Vector3Input,
+    /// Point the camera faces
+    pub look_at: Vector3Input,
+}
+
 #[derive(Debug, Clone, InputObject)]
 pub struct SetPropertyInput {
     pub node_path: String,
@@ -472,6 +697,75 @@ pub struct ConnectSignalInput {
     pub method: String,
 }
 
+/// Signal connection written to a scene file (node paths relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct ConnectSignalInSceneInput {
+    pub scene_path: String,
+    pub from_node: String,
+    pub signal: String,
+    pub to_node: String,
+    pub method: String,
+    /// ConnectFlags bitmask (e.g. 4 = ONE_SHOT)
+    pub flags: Option<i32>,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+/// Node moved under a different parent in a scene file (paths relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct ReparentNodeInSceneInput {
+    pub scene_path: String,
+    pub node_path: String,
+    /// "." for the root node
+    pub new_parent: String,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+/// Node renamed in a scene file (paths relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct RenameNodeInput {
+    pub scene_path: String,
+    pub node_path: String,
+    pub new_name: String,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+/// Node duplicated in a scene file (paths relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct DuplicateNodeInSceneInput {
+    pub scene_path: String,
+    pub node_path: String,
+    /// Name of the copy (default: "Name2", "Name3", ...)
+    pub new_name: Option<String>,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+/// Subtree copied out of a scene file by copySubtree
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CopiedSubtree {
+    /// Self-contained token to pass to pasteSubtree (empty on error)
+    pub token: String,
+    pub node_count: i32,
+    /// res:// paths of the external resources the subtree uses
+    pub ext_resources: Vec<String>,
+    pub sub_resource_count: i32,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Subtree pasted into a scene file (parent path relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct PasteSubtreeInput {
+    pub target_scene: String,
+    pub parent: String,
+    /// Token returned by copySubtree
+    pub token: String,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
 #[derive(Debug, Clone, InputObject)]
 pub struct DisconnectSignalInput {
     pub from_node: String,
@@ -483,6 +777,7 @@ pub struct DisconnectSignalInput {
 #[derive(Debug, Clone, SimpleObject)]
 pub struct OperationResult {
     pub success: bool,
+    /// What the operation did on success; the error's message on failure
     pub message: Option<String>,
     /// Structured error for AI-friendly error handling
     pub error: Option<GqlStructuredError>,
@@ -529,6 +824,9 @@ pub struct TransactionResult {
     /// Unique identifier for the transaction
     pub transaction_id: Option<String>,
     pub message: Option<String>,
+    /// Files staged by the transaction (written on commit, discarded on rollback)
+    pub files: Vec<String>,
+    pub error: Option<GqlStructuredError>,
 }
 
 impl TransactionResult {
@@ -538,6 +836,8 @@ impl TransactionResult {
             success: true,
             transaction_id: Some(transaction_id.into()),
             message: None,
+            files: Vec::new(),
+            error: None,
         }
     }
 
@@ -547,6 +847,8 @@ impl TransactionResult {
             success: true,
             transaction_id: None,
             message: Some(message.into()),
+            files: Vec::new(),
+            error: None,
         }
     }
 
@@ -556,10 +858,50 @@ impl TransactionResult {
             success: false,
             transaction_id: None,
             message: Some(message.into()),
+            files: Vec::new(),
+            error: None,
         }
     }
 }
 
+/// State of the server process (see serverDiagnostics)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ServerDiagnostics {
+    pub version: String,
+    /// Session file writes of this process are attributed to
+    pub session_id: String,
+    /// Operations a crash interrupted, recovered when this process opened the project
+    pub journal_recoveries: Vec<JournalRecovery>,
+}
+
+/// Interrupted multi-file operation found in the write-ahead journal
+#[derive(Debug, Clone, SimpleObject)]
+pub struct JournalRecovery {
+    pub id: String,
+    /// Operation that was interrupted (e.g. `moveFile`); null when its
+    /// journal was never completed
+    pub operation: Option<String>,
+    /// Start time (Unix epoch milliseconds)
+    pub started_at: Option<String>,
+    pub outcome: JournalRecoveryOutcome,
+    /// Files and folders the operation changes
+    pub files: Vec<String>,
+    pub message: Option<String>,
+}
+
+/// What recovery did with an interrupted operation
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum JournalRecoveryOutcome {
+    /// Every change was applied from the journal's staged copies
+    RolledForward,
+    /// Rolling forward failed; the files were restored to their previous content
+    Restored,
+    /// The journal was incomplete, so no file had been changed
+    Discarded,
+    /// Neither worked; the journal is kept and retried on the next start
+    Failed,
+}
+
 #[derive(Debug, Clone, SimpleObject)]
 pub struct NodeResult {
     pub success: bool,
@@ -618,8 +960,19 @@ pub struct CreateSceneInput {
 
 #[derive(Debug, Clone, InputObject)]
 pub struct TemplateSceneInput {
+    /// Template name (file stem in res://.godot-mcp/templates)
     pub template: String,
     pub path: String,
+    /// Values for the template's parameters; declared defaults fill the rest
+    pub params: Option<Vec<TemplateParamInput>>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct TemplateParamInput {
+    pub name: String,
+    /// Value in Godot syntax (e.g., `32`, `Color(1, 0, 0, 1)`); String
+    /// parameters also take unquoted text
+    pub value: String,
 }
 
 #[derive(Debug, Clone, InputObject)]
@@ -629,343 +982,2611 @@ pub struct CreateScriptInput {
     pub class_name: Option<String>,
 }
 
-// ======================
-// Phase 2.2: Input Action & Project Settings
-// ======================
-
-/// Input for adding an input action to the InputMap
+/// Input for createCustomResource
 #[derive(Debug, Clone, InputObject)]
-pub struct AddInputActionInput {
-    /// Action name (e.g., "jump", "move_left")
-    pub action_name: String,
-    /// Events to associate with this action
-    pub events: Vec<InputEventInput>,
+pub struct CreateCustomResourceInput {
+    /// Script extending Resource (res://...)
+    pub script_path: String,
+    /// .tres file to create (res://...)
+    pub output_path: String,
+    /// Field name to value; Godot literals as strings for vectors, colors and such
+    pub values: async_graphql::Json<serde_json::Value>,
 }
 
-/// Input event definition
-#[derive(Debug, Clone, InputObject)]
-pub struct InputEventInput {
-    /// Event type
+/// Exported var of a custom Resource script
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CustomResourceField {
+    pub name: String,
+    /// Declared type, or the type of the default value for untyped vars
     #[graphql(name = "type")]
-    pub event_type: InputEventType,
-    /// Key name (for KEY type)
-    pub key: Option<String>,
-    /// Button number (for MOUSE_BUTTON / JOY_BUTTON)
-    pub button: Option<i32>,
-    /// Device number (for gamepad)
-    pub device: Option<i32>,
+    pub field_type: String,
+    pub default_value: Option<String>,
 }
 
-/// Input event type enumeration
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CustomResourceResult {
+    pub success: bool,
+    pub path: Option<String>,
+    pub resource: Option<Resource>,
+    /// Valid fields of the script (also on failure)
+    pub fields: Vec<CustomResourceField>,
+    /// INVALID_VALUES lists each rejected field in its context
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Table format of exportResourcesToTable
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum InputEventType {
-    Key,
-    MouseButton,
-    JoyButton,
-    JoyAxis,
+pub enum ResourceTableFormat {
+    /// Header row of columns; arrays and dictionaries as JSON text
+    Csv,
+    /// Array of row objects
+    Json,
 }
 
-/// Input for setting a project setting
-#[derive(Debug, Clone, InputObject)]
-pub struct SetProjectSettingInput {
-    /// Setting path (e.g., "application/config/name", "display/window/size/width")
-    pub path: String,
-    /// Setting value (GDScript format string)
-    pub value: String,
-    /// Optional type hint
-    #[graphql(name = "type")]
-    pub value_type: Option<String>,
+/// Custom resources of a directory as one table
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ResourceTable {
+    pub success: bool,
+    pub format: ResourceTableFormat,
+    /// `path` (relative to the directory), `script`, then every exported field
+    pub columns: Vec<String>,
+    pub row_count: i32,
+    pub content: String,
+    /// .tres files without a custom Resource script
+    pub skipped: Vec<String>,
+    pub error: Option<GqlStructuredError>,
 }
 
-// ======================
-// Safe change flow
-// ======================
+/// Rejected row of importResourcesFromTable
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ResourceTableRowError {
+    /// 1-based data row (the CSV header is not counted)
+    pub row: i32,
+    pub path: Option<String>,
+    /// Column the error is about, when it is about one
+    pub field: Option<String>,
+    pub message: String,
+}
 
-#[derive(Debug, Clone, InputObject)]
-pub struct MutationPlanInput {
-    pub operations: Vec<PlannedOperation>,
+/// Result of importResourcesFromTable; rows with errors are not written
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ResourceTableImport {
+    /// false when any row was rejected
+    pub success: bool,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub row_errors: Vec<ResourceTableRowError>,
+    pub error: Option<GqlStructuredError>,
 }
 
+/// Input for annotating a node with agent metadata (stored as metadata/mcp_<key>)
 #[derive(Debug, Clone, InputObject)]
-pub struct PlannedOperation {
-    #[graphql(name = "type")]
-    pub operation_type: OperationType,
-    pub args: async_graphql::Json<serde_json::Value>,
+pub struct SetNodeMetadataInput {
+    pub scene_path: String,
+    pub node_path: String,
+    /// Key without the mcp_ prefix (letters, digits, underscore)
+    pub key: String,
+    /// New value; null removes the entry
+    pub value: Option<String>,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
 }
 
+/// What to do when a scene about to be written is open in the editor with unsaved changes
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum OperationType {
-    AddNode,
-    RemoveNode,
-    SetProperty,
-    SetProperties,
-    ConnectSignal,
-    DisconnectSignal,
-    AddToGroup,
-    RemoveFromGroup,
-    ReparentNode,
-    DuplicateNode,
-    CreateScript,
-    AttachScript,
+pub enum SceneWritePolicy {
+    /// Do not write the file
+    Refuse,
+    /// Save the editor's changes first, then write and reload
+    ForceSave,
+    /// Write anyway and reload the scene, discarding the editor's changes
+    ReloadAfterWrite,
 }
 
+/// Editor-side state of a scene file
 #[derive(Debug, Clone, SimpleObject)]
-pub struct MutationValidationResult {
-    pub is_valid: bool,
-    pub errors: Vec<MutationValidationError>,
-    pub warnings: Vec<MutationValidationWarning>,
-    pub validation_time_ms: i32,
+pub struct SceneLockStatus {
+    pub path: String,
+    /// false when the editor plugin could not be reached (open/unsaved/locked are unknown)
+    pub editor_connected: bool,
+    pub open: bool,
+    pub unsaved: bool,
+    /// A server write is in progress
+    pub locked: bool,
 }
 
+/// A node found on only one side of a liveSceneDrift comparison
 #[derive(Debug, Clone, SimpleObject)]
-pub struct MutationValidationError {
-    pub operation_index: i32,
-    pub code: String,
-    pub message: String,
-    pub suggestion: Option<String>,
+pub struct DriftNode {
+    /// Path relative to the scene root
+    pub path: String,
+    /// Node class, or the instanced scene's path for scene instances
+    pub node_type: String,
 }
 
+/// A property whose value differs between the scene file and the editor
 #[derive(Debug, Clone, SimpleObject)]
-pub struct MutationValidationWarning {
-    pub operation_index: i32,
-    pub message: String,
+pub struct PropertyDrift {
+    pub node_path: String,
+    pub property: String,
+    /// Value in the file; None when only the editor sets it
+    pub disk_value: Option<String>,
+    /// Value in the editor; None when it is only set in the file
+    pub live_value: Option<String>,
 }
 
+/// Differences between the scene being edited and its file on disk
 #[derive(Debug, Clone, SimpleObject)]
-pub struct PreviewResult {
-    pub success: bool,
+pub struct LiveSceneDrift {
+    pub path: String,
+    /// false when the editor plugin could not be reached
+    pub editor_connected: bool,
+    /// The scene is the one being edited (only that one can be compared)
+    pub open: bool,
+    /// Scene being edited instead, when it is another one
+    pub edited_scene: Option<String>,
+    /// The editor's version differs from the file (unsaved edits)
+    pub drifted: bool,
+    /// Nodes in the editor but not in the file
+    pub added_nodes: Vec<DriftNode>,
+    /// Nodes in the file but no longer in the editor
+    pub removed_nodes: Vec<DriftNode>,
+    pub changed_properties: Vec<PropertyDrift>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Transport a live command went over
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Enum)]
+pub enum LiveTransport {
+    /// Persistent WebSocket channel
+    Websocket,
+    /// One HTTP request per command (fallback)
+    Http,
+}
+
+/// Latency of one live command over one transport, since the server started
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LiveCommandLatency {
+    pub command: String,
+    pub transport: LiveTransport,
+    pub calls: i32,
+    pub errors: i32,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+}
+
+/// State of the persistent connection to the editor plugin
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LiveChannelStatus {
+    /// None when live commands go over HTTP only
+    pub websocket_url: Option<String>,
+    pub connected: bool,
+    /// Connections opened again after one dropped
+    pub reconnects: i32,
+    /// Commands sent and waiting for their response
+    pub in_flight: i32,
+    pub commands: Vec<LiveCommandLatency>,
+}
+
+/// An editor liveInstancesQuery runs against
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EditorInstance {
+    /// "current" for the server's own editor, otherwise the `[[editors]]` name
+    pub name: String,
+    pub project_path: String,
+    /// Plugin HTTP port
+    pub port: i32,
+    /// Plugin WebSocket port; None when commands go over HTTP only
+    pub ws_port: Option<i32>,
+}
+
+/// Response of one editor instance to a liveInstancesQuery
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InstanceQueryResult {
+    pub instance: EditorInstance,
+    pub success: bool,
+    /// The query's `data`, as it would be returned for this instance alone
+    pub data: Option<async_graphql::Json<serde_json::Value>>,
+    pub errors: Vec<String>,
+    pub duration_ms: f64,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LiveInstancesResult {
+    /// True when every instance answered without errors
+    pub success: bool,
+    pub results: Vec<InstanceQueryResult>,
+    /// Set when the query was refused (not read-only, unknown instance)
+    pub error: Option<GqlStructuredError>,
+}
+
+/// State of the game run by the editor (Play / Play Scene)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PlayState {
+    /// false when the editor plugin could not be reached
+    pub editor_connected: bool,
+    pub playing: bool,
+    /// Scene being played, while playing
+    pub scene: Option<String>,
+}
+
+/// One step of playInputSequence; set exactly one of action, key and mouseClick
+#[derive(Debug, Clone, InputObject)]
+pub struct InputStepInput {
+    /// InputMap action (e.g., "jump")
+    pub action: Option<String>,
+    /// Key name as in the editor (e.g., "Space", "A", "Escape")
+    pub key: Option<String>,
+    pub mouse_click: Option<MouseClickInput>,
+    /// true holds the action/key, false releases it; omitted taps it
+    pub pressed: Option<bool>,
+    /// Milliseconds to wait after this step
+    #[graphql(default = 0)]
+    pub wait_ms: i32,
+}
+
+/// Click at a position of the game's viewport
+#[derive(Debug, Clone, InputObject)]
+pub struct MouseClickInput {
+    pub x: f64,
+    pub y: f64,
+    /// Button number (1 = left, 2 = right, 3 = middle)
+    #[graphql(default = 1)]
+    pub button: i32,
+}
+
+/// Result of playInputSequence
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InputSequenceResult {
+    pub success: bool,
+    /// Steps delivered to the game before it finished or failed
+    pub steps_sent: i32,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Script functions recorded by the editor's profiler
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProfileReport {
+    /// false when the editor plugin could not be reached
+    pub editor_connected: bool,
+    /// Whether the profiler is still recording
+    pub profiling: bool,
+    /// Slowest first
+    pub functions: Vec<ProfiledFunction>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// One script function in a profile
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProfiledFunction {
+    pub name: String,
+    /// res:// path of the script
+    pub script: String,
+    pub line: Option<i32>,
+    /// Average time per frame spent in the function itself
+    pub time_ms: f64,
+    /// Share of the frame, when the panel shows percentages instead of times
+    pub frame_percent: Option<f64>,
+    /// Calls per frame
+    pub calls: i32,
+}
+
+/// Result of evalExpression, evaluated in the running game
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EvalResult {
+    pub success: bool,
+    /// The result as GDScript source (var_to_str), e.g. "Vector2(10, 20)"
+    pub value: Option<String>,
+    /// Variant type name of the result, e.g. "Vector2"
+    pub value_type: Option<String>,
+    /// The result as JSON, for numbers, strings, arrays and dictionaries
+    pub json: Option<async_graphql::Json<serde_json::Value>>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Performance monitors of the editor (Performance.get_monitor); rates are
+/// averaged over the samples, memory and object counts are the latest values
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PerformanceMetrics {
+    /// false when the editor plugin could not be reached
+    pub editor_connected: bool,
+    pub sample_count: i32,
+    pub fps: f64,
+    pub min_fps: f64,
+    /// Process (idle) time per frame
+    pub frame_time_ms: f64,
+    pub max_frame_time_ms: f64,
+    pub physics_time_ms: f64,
+    pub draw_calls: f64,
+    pub objects_in_frame: f64,
+    pub static_memory_mb: f64,
+    pub video_memory_mb: f64,
+    pub object_count: i32,
+    pub node_count: i32,
+}
+
+/// Editor settings read through the editor plugin (Editor > Editor Settings)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EditorSettings {
+    /// false when the editor plugin could not be reached
+    pub editor_connected: bool,
+    pub settings: Vec<EditorSetting>,
+}
+
+/// A single editor setting
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EditorSetting {
+    /// Setting path (e.g., "text_editor/external/exec_path")
+    pub name: String,
+    /// Value in GDScript format (e.g., `true`, `"code"`, `2`)
+    pub value: String,
+    /// Variant type name (e.g., "bool", "String", "int")
+    #[graphql(name = "type")]
+    pub value_type: String,
+}
+
+/// Editor icons and categories of node classes, for clients drawing scene trees
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeClassIcons {
+    /// false when the icons come from the bundled set
+    pub editor_connected: bool,
+    pub icons: Vec<NodeClassIcon>,
+}
+
+/// Icon and editor metadata of one node class
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeClassIcon {
+    pub type_name: String,
+    /// Parent class (None for Node and unknown classes)
+    pub base_class: Option<String>,
+    pub category: NodeCategory,
+    /// Whether the class is known to the editor (or the bundled class list)
+    pub known: bool,
+    /// Icon color of the category as #rrggbb, as in the Scene dock
+    pub color: String,
+    /// "image/png" for editor icons, "image/svg+xml" for bundled ones
+    pub mime_type: String,
+    pub icon_base64: String,
+    pub source: IconSource,
+}
+
+/// Branch of the class tree a node class belongs to, which sets its icon color
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum NodeCategory {
+    #[graphql(name = "NODE_2D")]
+    Node2D,
+    #[graphql(name = "NODE_3D")]
+    Node3D,
+    Control,
+    /// Any other Node (AnimationPlayer, Timer, ...)
+    Node,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum IconSource {
+    /// The editor theme's icon for the class
+    Editor,
+    /// Generic icon bundled with the server, in the category color
+    Bundled,
+}
+
+/// Input for changing an editor setting
+#[derive(Debug, Clone, InputObject)]
+pub struct SetEditorSettingInput {
+    /// Setting path (e.g., "text_editor/external/use_external_editor")
+    pub name: String,
+    /// New value (GDScript format string; String settings also take unquoted text)
+    pub value: String,
+}
+
+/// Agent metadata entry stored on a scene node
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeMetadataEntry {
+    pub node_path: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// Input for wiring exported node references of scripts attached in a scene
+#[derive(Debug, Clone, InputObject)]
+pub struct WireExportedReferencesInput {
+    pub scene_path: String,
+    /// Report the proposed assignments without writing the scene
+    #[graphql(default = false)]
+    pub dry_run: bool,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ReferenceWireStatus {
+    /// Already set in the scene
+    Assigned,
+    /// Set to the single best candidate (proposed in dry-run)
+    Wired,
+    /// Several equally good candidates; pick one with setProperty
+    Ambiguous,
+    NoCandidate,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ReferenceCandidate {
+    /// Scene-relative path of the candidate node
+    pub node_path: String,
+    /// NodePath from the scripted node, as stored in the scene
+    pub relative_path: String,
+    pub node_type: String,
+    pub score: i32,
+}
+
+/// Exported NodePath / Node property of an attached script
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExportedReference {
+    pub node_path: String,
+    pub script_path: String,
+    pub property: String,
+    /// Declared type (NodePath, Node class or class_name)
+    pub export_type: String,
+    pub status: ReferenceWireStatus,
+    /// Current or newly assigned value
+    pub value: Option<String>,
+    /// Best candidates first
+    pub candidates: Vec<ReferenceCandidate>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WireExportedReferencesResult {
+    pub success: bool,
+    pub dry_run: bool,
+    pub references: Vec<ExportedReference>,
+    pub wired_count: i32,
+    pub unresolved_count: i32,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum SceneStructureIssueKind {
+    /// No nodes, or the first node has a parent
+    MissingRoot,
+    /// Parentless node after the root
+    ExtraRoot,
+    /// Parent path matches no node
+    Orphan,
+    /// Same path as an earlier node
+    Duplicate,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneStructureIssue {
+    pub kind: SceneStructureIssueKind,
+    pub node_path: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum SceneRepairFix {
+    /// Move orphans and extra roots under the root node
+    ReattachOrphans,
+    /// Remove orphans (with their children) and extra roots
+    DropOrphans,
+    /// Remove nodes duplicating an earlier node path
+    DropDuplicates,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneRepairResult {
+    pub success: bool,
+    /// Issues found before repairing
+    pub issues: Vec<SceneStructureIssue>,
+    /// Changes made to the scene
+    pub applied: Vec<String>,
+    /// Issues left after repairing
+    pub remaining: Vec<SceneStructureIssue>,
+    /// Whether the scene file was rewritten
+    pub written: bool,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Scene Patch Types
+// ======================
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ApplyScenePatchResult {
+    pub success: bool,
+    pub dry_run: bool,
+    /// Operations in the patch (all of them are applied, or none)
+    pub operation_count: i32,
+    /// Unified diff of the scene file
+    pub diff: String,
+    /// Operations that were rejected
+    pub errors: Vec<ScenePatchError>,
+    /// The scene could not be read, locked or written
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScenePatchError {
+    /// Null when the patch itself is malformed
+    pub operation_index: Option<i32>,
+    /// INVALID_PATCH, SELECTOR_NO_MATCH, CONFLICT, ...
+    pub code: String,
+    pub message: String,
+}
+
+// ======================
+// Computed Property Types
+// ======================
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ComputePropertyInput {
+    /// Scene file path (res://...)
+    pub scene_path: String,
+    /// Node to write, relative to the scene root ("." for the root)
+    pub node_path: String,
+    /// Property to set
+    pub property: String,
+    /// Expression, e.g. `$"../Door".position + Vector2(32, 0)`
+    pub expression: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ComputePropertyResult {
+    pub success: bool,
+    /// Written value as stored in the scene
+    pub value: Option<String>,
+    /// Value before the write (None when the property was unset)
+    pub previous: Option<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Data Population Types
+// ======================
+
+#[derive(Debug, Clone, InputObject)]
+pub struct PopulateFromDataInput {
+    /// Scene to add nodes to (res://...)
+    pub scene_path: String,
+    /// Scene instanced once per row (res://...)
+    pub template_scene: Option<String>,
+    /// Node type created per row when no template scene is given
+    pub node_type: Option<String>,
+    /// Data table (res://....csv with a header line, or .json array of objects)
+    pub data_file: String,
+    /// Property expressions per row; bare identifiers name columns
+    #[graphql(default)]
+    pub mapping: Vec<DataPropertyMapping>,
+    /// Column used for node names (default: template root name / type + row number)
+    pub name_column: Option<String>,
+    /// Parent node path (default: scene root)
+    pub parent: Option<String>,
+    /// Report the nodes without writing the scene
+    #[graphql(default = false)]
+    pub dry_run: bool,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct DataPropertyMapping {
+    /// Property to set on each node
+    pub property: String,
+    /// Expression over the row's columns, e.g. `Vector2(x, y) * 16`
+    pub expression: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PopulateFromDataResult {
+    pub success: bool,
+    /// Data rows read
+    pub rows: i32,
+    /// Paths of the created nodes
+    pub created: Vec<String>,
+    /// Whether the scene file was rewritten
+    pub written: bool,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Collision Shape Types
+// ======================
+
+/// How generateCollisionShape fits the shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum CollisionShapeStrategy {
+    /// ConvexPolygonShape (mesh vertices / opaque sprite pixels)
+    Convex,
+    /// ConcavePolygonShape3D with the mesh triangles
+    Trimesh,
+    /// Capsule around the bounding box
+    CapsuleFit,
+    /// RectangleShape2D of the sprite frame
+    RectFromSprite,
+}
+
+/// CollisionShape generated for a MeshInstance3D or Sprite2D (paths relative to the scene root)
+#[derive(Debug, Clone, InputObject)]
+pub struct GenerateCollisionShapeInput {
+    pub scene_path: String,
+    pub node_path: String,
+    pub strategy: CollisionShapeStrategy,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GenerateCollisionShapeResult {
+    pub success: bool,
+    /// Path of the added CollisionShape2D/3D node
+    pub node_path: Option<String>,
+    /// Resource type of the shape, e.g. "CapsuleShape3D"
+    pub shape_type: Option<String>,
+    pub warnings: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Physics Layer Types
+// ======================
+
+/// Physics engine a collision layer belongs to (2D and 3D layers are separate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum PhysicsSpace {
+    TwoD,
+    ThreeD,
+}
+
+/// A physics layer in use or named in project.godot
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PhysicsLayer {
+    pub space: PhysicsSpace,
+    /// 1-32
+    pub layer: i32,
+    /// Name from project.godot's [layer_names]
+    pub name: Option<String>,
+    /// Collision objects on this layer
+    pub objects: i32,
+    /// Collision objects whose mask includes this layer
+    pub scanned_by: i32,
+}
+
+/// Two layers whose objects collide (A <= B; A == B for a layer colliding with itself)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LayerCollision {
+    pub space: PhysicsSpace,
+    pub layer_a: i32,
+    pub layer_b: i32,
+    pub name_a: Option<String>,
+    pub name_b: Option<String>,
+    /// Some object on layer A has layer B in its mask
+    pub a_detects_b: bool,
+    /// Some object on layer B has layer A in its mask
+    pub b_detects_a: bool,
+}
+
+/// Physics body, area, GridMap or colliding CSG shape in a scene file
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CollisionObject {
+    pub scene_path: String,
+    pub node_path: String,
+    pub node_type: String,
+    pub space: PhysicsSpace,
+    /// Layers the object is on (collision_layer)
+    pub layers: Vec<i32>,
+    /// Layers the object scans (collision_mask)
+    pub mask: Vec<i32>,
+}
+
+/// Which layers collide with which, aggregated from every scene
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CollisionMatrix {
+    pub layers: Vec<PhysicsLayer>,
+    pub pairs: Vec<LayerCollision>,
+    pub objects: Vec<CollisionObject>,
+    pub scenes_scanned: i32,
+}
+
+/// Make objects on two layers collide (or stop colliding) by editing their masks
+#[derive(Debug, Clone, InputObject)]
+pub struct SetLayerCollisionInput {
+    /// Layer number (1-32) or name from [layer_names]
+    pub layer_a: String,
+    pub layer_b: String,
+    pub enabled: bool,
+    /// Default: TWO_D
+    pub space: Option<PhysicsSpace>,
+    /// Only edit scenes matching this glob (e.g. "res://levels/**")
+    pub scene_pattern: Option<String>,
+    /// Report the changes without writing
+    #[graphql(default)]
+    pub dry_run: bool,
+    /// Applied when a scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SetLayerCollisionResult {
+    pub success: bool,
+    /// Objects whose mask changed, with the new mask
+    pub changed: Vec<CollisionObject>,
+    pub scenes_written: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Checkpoint Types
+// ======================
+
+/// Read-only snapshot of the project's scenes, scripts and resources
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    pub id: String,
+    pub label: Option<String>,
+    /// Creation time (Unix epoch milliseconds)
+    pub timestamp: String,
+    /// Files captured in the snapshot
+    pub file_count: i32,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CreateCheckpointResult {
+    pub success: bool,
+    pub checkpoint: Option<Checkpoint>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Project state at a checkpoint, resolved with the regular project/scene/script resolvers
+#[derive(Debug, Clone)]
+pub struct ProjectSnapshot {
+    pub checkpoint: Checkpoint,
+    /// Context rooted at the snapshot directory
+    pub context: super::context::GqlContext,
+}
+
+#[Object]
+impl ProjectSnapshot {
+    async fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+
+    /// Project information as of the checkpoint
+    async fn project(&self) -> Project {
+        super::project_resolver::resolve_project(&self.context)
+    }
+
+    /// Scene file contents as of the checkpoint
+    async fn scene(&self, path: String) -> Option<Scene> {
+        super::scene_resolver::resolve_scene(&self.context, &path)
+    }
+
+    /// Script file contents as of the checkpoint
+    async fn script(&self, path: String) -> Option<Script> {
+        super::script_resolver::resolve_script(&self.context, &path)
+    }
+}
+
+/// Project state of a git branch or commit, read without checking it out
+#[derive(Debug, Clone)]
+pub struct BranchView {
+    pub git_ref: String,
+    /// Commit the ref resolved to
+    pub commit: Option<String>,
+    /// Scenes, scripts and resources read from the commit
+    pub file_count: i32,
+    pub error: Option<GqlStructuredError>,
+    /// Context over the in-memory copy; None when the ref could not be read
+    pub context: Option<super::context::GqlContext>,
+}
+
+#[Object]
+impl BranchView {
+    async fn git_ref(&self) -> &str {
+        &self.git_ref
+    }
+
+    async fn commit(&self) -> Option<&str> {
+        self.commit.as_deref()
+    }
+
+    async fn file_count(&self) -> i32 {
+        self.file_count
+    }
+
+    async fn error(&self) -> Option<&GqlStructuredError> {
+        self.error.as_ref()
+    }
+
+    /// Project information on the branch
+    async fn project(&self) -> Option<Project> {
+        Some(super::project_resolver::resolve_project(
+            self.context.as_ref()?,
+        ))
+    }
+
+    /// Scene file contents on the branch
+    async fn scene(&self, path: String) -> Option<Scene> {
+        super::scene_resolver::resolve_scene(self.context.as_ref()?, &path)
+    }
+
+    /// Script file contents on the branch
+    async fn script(&self, path: String) -> Option<Script> {
+        super::script_resolver::resolve_script(self.context.as_ref()?, &path)
+    }
+
+    /// .tres resource contents on the branch
+    async fn resource(&self, path: String) -> Option<Resource> {
+        super::scene_resolver::resolve_resource(self.context.as_ref()?, &path)
+    }
+}
+
+// ======================
+// Backup Types
+// ======================
+
+/// Previous contents of the files one operation wrote
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Backup {
+    pub id: String,
+    /// Creation time (Unix epoch milliseconds)
+    pub timestamp: String,
+    pub session_id: String,
+    /// GraphQL field whose writes were backed up (e.g. `addNode`)
+    pub operation: String,
+    pub description: Option<String>,
+    pub files: Vec<BackupFile>,
+}
+
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFile {
+    pub path: String,
+    /// The file did not exist before (restoring deletes it)
+    pub created: bool,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RestoreBackupResult {
+    pub success: bool,
+    pub backup: Option<Backup>,
+    /// Files written back to their backed up content
+    pub restored: Vec<String>,
+    /// Files created by the operation and deleted again
+    pub deleted: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Session Report Types
+// ======================
+
+/// Changelog of the file edits one session recorded in the audit log
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SessionReport {
+    pub session_id: String,
+    /// First and last change (Unix epoch milliseconds)
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    /// Recorded writes and moves
+    pub change_count: i32,
+    /// Files touched per operation, in first-use order
+    pub features: Vec<SessionFeatureChange>,
+    /// Changes per file with diffs, by path
+    pub files: Vec<SessionFileChange>,
+    /// The report rendered as Markdown (e.g. for a PR description)
+    pub markdown: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SessionFeatureChange {
+    /// GraphQL operation, e.g. `computeProperty`
+    pub operation: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SessionFileChange {
+    pub path: String,
+    pub operations: Vec<String>,
+    /// The session created the file
+    pub created: bool,
+    pub moved_from: Option<String>,
+    pub change_count: i32,
+    /// Unified diffs of each change, in order
+    pub diff: String,
+}
+
+// ======================
+// Animation Types
+// ======================
+
+/// Track path rewrite: `from` matches a track's node path or one of its
+/// ancestors, or a full `Node:property` path when it contains ':'
+#[derive(Debug, Clone, InputObject, Serialize, Deserialize)]
+pub struct TrackPathMappingInput {
+    pub from: String,
+    pub to: String,
+}
+
+/// Input for rewriting the track paths of an AnimationPlayer's animations
+#[derive(Debug, Clone, InputObject)]
+pub struct RemapAnimationTracksInput {
+    /// Scene file to edit; the scene open in the editor is remapped live when omitted
+    pub scene_path: Option<String>,
+    /// AnimationPlayer path relative to the scene root
+    pub player: String,
+    pub mapping: Vec<TrackPathMappingInput>,
+    /// Only remap these animations (default: all)
+    pub animations: Option<Vec<String>>,
+    /// Report the rewritten paths without changing anything
+    #[graphql(default = false)]
+    pub dry_run: bool,
+    /// Applied when the scene is open in the editor with unsaved changes (default: REFUSE)
+    pub policy: Option<SceneWritePolicy>,
+}
+
+#[derive(Debug, Clone, SimpleObject, Deserialize)]
+pub struct RemappedTrack {
+    pub animation: String,
+    pub track: i32,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RemapAnimationTracksResult {
+    pub success: bool,
+    pub dry_run: bool,
+    pub remapped: Vec<RemappedTrack>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Audio Import Types
+// ======================
+
+/// Loop mode of an audio import. Ogg Vorbis and MP3 only loop forward or not at all.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum AudioLoopMode {
+    /// Use the loop points stored in the WAV file
+    Detect,
+    Disabled,
+    Forward,
+    PingPong,
+    Backward,
+}
+
+/// WAV compression (`compress/mode`)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum AudioCompression {
+    Pcm,
+    ImaAdpcm,
+    Qoa,
+}
+
+/// `key=value` entry of an import file's [params] section
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ImportParam {
+    pub key: String,
+    pub value: String,
+}
+
+/// Audio file with its import settings
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AudioAsset {
+    pub path: String,
+    /// WAV, Ogg Vorbis or MP3
+    pub format: String,
+    pub importer: Option<String>,
+    /// Whether a .import file exists
+    pub imported: bool,
+    pub loop_mode: Option<AudioLoopMode>,
+    /// Ogg Vorbis / MP3 loop start in seconds
+    pub loop_offset: Option<f64>,
+    /// WAV loop start in frames
+    pub loop_begin: Option<i32>,
+    /// WAV loop end in frames (-1 for the end of the file)
+    pub loop_end: Option<i32>,
+    pub compression: Option<AudioCompression>,
+    pub normalize: Option<bool>,
+    pub force_mono: Option<bool>,
+    pub duration_sec: Option<f64>,
+    pub params: Vec<ImportParam>,
+}
+
+/// Loop settings written to one audio file's import
+#[derive(Debug, Clone, InputObject)]
+pub struct SetAudioLoopInput {
+    pub path: String,
+    pub mode: AudioLoopMode,
+    /// Ogg Vorbis / MP3 loop start in seconds
+    pub loop_offset: Option<f64>,
+    /// WAV loop start in frames
+    pub loop_begin: Option<i32>,
+    /// WAV loop end in frames (-1 for the end of the file)
+    pub loop_end: Option<i32>,
+}
+
+/// Import settings applied to every audio file they support; unset fields are left alone
+#[derive(Debug, Clone, InputObject)]
+pub struct AudioImportPresetInput {
+    /// WAV only
+    pub compression: Option<AudioCompression>,
+    /// WAV only
+    pub normalize: Option<bool>,
+    /// WAV only
+    pub force_mono: Option<bool>,
+    /// WAV only
+    pub trim: Option<bool>,
+    pub loop_mode: Option<AudioLoopMode>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ApplyAudioImportPresetInput {
+    /// Folder containing the sounds (res:// path)
+    pub folder: String,
+    pub preset: AudioImportPresetInput,
+    #[graphql(default = true)]
+    pub recursive: bool,
+    #[graphql(default = false)]
+    pub dry_run: bool,
+}
+
+/// Import settings changed in one audio file
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AudioImportChange {
+    pub path: String,
+    pub changed: Vec<ImportParam>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AudioImportResult {
+    pub success: bool,
+    pub dry_run: bool,
+    pub changes: Vec<AudioImportChange>,
+    /// Audio files without a .import file (not imported by Godot yet)
+    pub skipped: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Feature Pack Types
+// ======================
+
+/// A feature pack installed into the project (recorded for later updates)
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledFeaturePack {
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    /// Local directory or git URL the pack was installed from
+    pub source: String,
+    /// Folder the pack's files were installed under (res:// path)
+    pub prefix: String,
+    /// Unix epoch milliseconds
+    pub installed_at: String,
+    /// Installed files (res:// paths)
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct InstallFeaturePackInput {
+    /// Local directory (absolute or relative to the project) or git URL
+    pub source: String,
+    /// Branch or tag to check out when `source` is a git URL
+    pub git_ref: Option<String>,
+    /// Install folder (res:// path); defaults to the previous install location,
+    /// then the manifest's `prefix`, then `res://features/<name>`
+    pub prefix: Option<String>,
+    /// Replace existing files that don't belong to this pack
+    #[graphql(default = false)]
+    pub overwrite: bool,
+    #[graphql(default = false)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum FeaturePackFileAction {
+    Create,
+    Update,
+    Unchanged,
+    /// Installed by a previous version but no longer part of the pack
+    Remove,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FeaturePackFileChange {
+    pub path: String,
+    pub action: FeaturePackFileAction,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InstallFeaturePackResult {
+    pub success: bool,
+    pub dry_run: bool,
+    pub pack: Option<InstalledFeaturePack>,
+    pub files: Vec<FeaturePackFileChange>,
+    /// Existing files the pack would overwrite (install refused unless `overwrite`)
+    pub conflicts: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct FetchAssetInput {
+    /// https:// URL of the file; its host must be on the `[assets]` allowlist
+    pub url: String,
+    /// Target res:// file path, or a folder ending in `/` to keep the URL's file name
+    pub destination: String,
+    /// License file to download into the same folder (e.g. the pack's License.txt)
+    pub license_url: Option<String>,
+    /// Replace an existing file
+    #[graphql(default = false)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FetchAssetResult {
+    pub success: bool,
+    /// Written file (res:// path)
+    pub path: Option<String>,
+    /// Downloaded bytes
+    pub size: i32,
+    pub content_type: Option<String>,
+    /// License file covering the asset's folder (res:// path)
+    pub license_path: Option<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// File Watch Types
+// ======================
+
+/// A watched project file (.tscn/.gd/.tres) changed on disk
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FileChangedEvent {
+    pub path: String,
+    pub change_type: FileChangeType,
+    /// Unix timestamp (ms)
+    pub timestamp: String,
+}
+
+/// A scene file changed on disk
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneChangedEvent {
+    pub path: String,
+    pub change_type: FileChangeType,
+    /// Re-parsed scene (null when deleted or unparseable)
+    pub scene: Option<Scene>,
+    /// Unix timestamp (ms)
+    pub timestamp: String,
+}
+
+// ======================
+// Phase 2.2: Input Action & Project Settings
+// ======================
+
+/// Input for adding an input action to the InputMap
+#[derive(Debug, Clone, InputObject)]
+pub struct AddInputActionInput {
+    /// Action name (e.g., "jump", "move_left")
+    pub action_name: String,
+    /// Events to associate with this action
+    pub events: Vec<InputEventInput>,
+    /// Analog deadzone (default: 0.5)
+    pub deadzone: Option<f64>,
+}
+
+/// Input event definition
+#[derive(Debug, Clone, InputObject)]
+pub struct InputEventInput {
+    /// Event type
+    #[graphql(name = "type")]
+    pub event_type: InputEventType,
+    /// Key name (for KEY type), with modifiers if any: "Space", "W", "F5", "Ctrl+S"
+    pub key: Option<String>,
+    /// Button number (for MOUSE_BUTTON / JOY_BUTTON), axis number (for JOY_AXIS)
+    pub button: Option<i32>,
+    /// Axis direction (for JOY_AXIS): negative or positive (default: 1.0)
+    pub axis_value: Option<f64>,
+    /// Device number (for gamepad; default: all devices)
+    pub device: Option<i32>,
+}
+
+/// Input event type enumeration
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum InputEventType {
+    Key,
+    MouseButton,
+    JoyButton,
+    JoyAxis,
+}
+
+/// An action of the project's InputMap ([input] in project.godot)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InputAction {
+    pub name: String,
+    pub deadzone: f64,
+    pub events: Vec<InputActionEvent>,
+}
+
+/// An event bound to an input action
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InputActionEvent {
+    /// Null for event classes other than keys, mouse buttons and gamepads
+    #[graphql(name = "type")]
+    pub event_type: Option<InputEventType>,
+    /// Godot class, e.g. "InputEventKey"
+    pub class_name: String,
+    /// Key with modifiers (KEY), e.g. "Ctrl+S"
+    pub key: Option<String>,
+    /// Button number (MOUSE_BUTTON / JOY_BUTTON) or axis number (JOY_AXIS)
+    pub button: Option<i32>,
+    /// Axis direction (JOY_AXIS)
+    pub axis_value: Option<f64>,
+    /// Gamepad device; -1 for all devices
+    pub device: i32,
+    /// Readable binding, e.g. "Space", "Mouse Button 1", "Joypad Axis 0-"
+    pub description: String,
+}
+
+/// Input for setting a project setting
+#[derive(Debug, Clone, InputObject)]
+pub struct SetProjectSettingInput {
+    /// Setting path (e.g., "application/config/name", "display/window/size/width")
+    pub path: String,
+    /// Setting value (GDScript format string)
+    pub value: String,
+    /// Optional type hint (int, float, bool, string): the value is checked,
+    /// and string values are quoted when needed
+    #[graphql(name = "type")]
+    pub value_type: Option<String>,
+}
+
+/// A project.godot setting
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectSetting {
+    /// Setting path (e.g., "application/config/name")
+    pub path: String,
+    /// Key within the section (e.g., "config/name")
+    pub key: String,
+    /// Value as written in project.godot
+    pub value: String,
+}
+
+/// A section of project.godot
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectSettingsSection {
+    /// Section name; empty for the keys before the first section (config_version)
+    pub name: String,
+    pub settings: Vec<ProjectSetting>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectSettingsResult {
+    pub sections: Vec<ProjectSettingsSection>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Export Preset Types
+// ======================
+
+/// An export preset in export_presets.cfg
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExportPreset {
+    /// N of `[preset.N]`
+    pub index: i32,
+    pub name: String,
+    pub platform: String,
+    /// Used by one-click deploy (one preset per platform)
+    pub runnable: bool,
+    /// Exported file, relative to the project or absolute
+    pub export_path: String,
+    pub custom_features: Vec<String>,
+    /// all_resources, scenes, resources, exclude or customized
+    pub export_filter: String,
+    pub include_filter: String,
+    pub exclude_filter: String,
+    /// Platform exporter options (`[preset.N.options]`)
+    pub options: Vec<ExportPresetOption>,
+}
+
+/// An exporter option, with its value as written in export_presets.cfg
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExportPresetOption {
+    /// Option path (e.g., "binary_format/embed_pck")
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExportPresetsResult {
+    pub presets: Vec<ExportPreset>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExportPresetResult {
+    pub success: bool,
+    pub preset: Option<ExportPreset>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateExportPresetInput {
+    pub name: String,
+    /// Platform to start from its template (e.g., "Windows Desktop", "Web")
+    pub platform: Option<String>,
+    /// Existing preset to copy instead of a platform template
+    pub copy_from: Option<String>,
+    /// Defaults to build/<preset name>.<platform extension>
+    pub export_path: Option<String>,
+    pub custom_features: Option<Vec<String>>,
+    /// Defaults to true for the platform's first preset
+    pub runnable: Option<bool>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct UpdateExportPresetInput {
+    /// Preset to change
+    pub name: String,
+    pub new_name: Option<String>,
+    pub export_path: Option<String>,
+    /// Replaces the preset's custom features
+    pub custom_features: Option<Vec<String>>,
+    /// Making a preset runnable clears the other presets of its platform
+    pub runnable: Option<bool>,
+    /// Extra files to export (e.g., "*.json, data/*")
+    pub include_filter: Option<String>,
+    /// Files left out of the export
+    pub exclude_filter: Option<String>,
+    pub options: Option<Vec<ExportPresetOptionInput>>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ExportPresetOptionInput {
+    pub key: String,
+    /// Value in Godot syntax (e.g., `true`, `"x86_64"`)
+    pub value: String,
+}
+
+// ======================
+// Godot Binary
+// ======================
+
+/// Where a Godot executable was found
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum GodotBinarySource {
+    /// Set with setGodotPath or --godot
+    Override,
+    /// GODOT, GODOT_PATH or GODOT_BIN environment variable
+    Environment,
+    /// On PATH
+    Path,
+    /// A common install location
+    InstallDir,
+}
+
+/// A Godot executable
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GodotBinaryInfo {
+    pub path: String,
+    pub source: GodotBinarySource,
+    /// `--version` output (e.g., "4.3.stable.official.77dcf97d8"); null when it could not be run
+    pub version: Option<String>,
+    /// Whether the version satisfies .godot-version; null when no version is pinned
+    pub matches_pin: Option<bool>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GodotBinaryResult {
+    /// Executable editor tools run for this project
+    pub binary: Option<GodotBinaryInfo>,
+    /// Version pinned in .godot-version
+    pub pinned_version: Option<String>,
+    /// Every executable found, in order of precedence
+    pub candidates: Vec<GodotBinaryInfo>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct SetGodotPathInput {
+    /// Godot executable for this session; null or empty goes back to discovery
+    pub path: Option<String>,
+    /// Pin the executable's version in .godot-version
+    pub pin: Option<bool>,
+}
+
+// ======================
+// Node Bounds
+// ======================
+
+/// Bounds kind: `Rect2` for 2D nodes and controls, `AABB` for 3D nodes
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum BoundsKind {
+    #[graphql(name = "RECT2")]
+    Rect2,
+    Aabb,
+}
+
+/// Global bounds of a node, measured by Godot
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeBounds {
+    /// Relative to the scene root (`.` for the root)
+    pub path: String,
+    #[graphql(name = "type")]
+    pub node_type: String,
+    pub kind: BoundsKind,
+    /// Two components for RECT2, three for AABB
+    pub position: Vec<f64>,
+    pub size: Vec<f64>,
+}
+
+/// Two nodes whose bounds intersect (neither is an ancestor of the other)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeBoundsOverlap {
+    pub a: String,
+    pub b: String,
+    /// Area (RECT2) or volume (AABB) of the intersection
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NodeBoundsResult {
+    pub success: bool,
+    pub scene_path: String,
+    /// Godot executable used
+    pub godot: Option<String>,
+    /// Served from res://.godot-mcp/node_bounds.json without running Godot
+    pub cached: bool,
+    /// Nodes Godot could measure, in tree order
+    pub nodes: Vec<NodeBounds>,
+    pub overlaps: Vec<NodeBoundsOverlap>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Scene Thumbnails
+// ======================
+
+/// Thumbnail of one scene
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneThumbnail {
+    pub scene_path: String,
+    /// PNG under res://.godot-mcp/thumbnails; None when rendering failed
+    pub thumbnail_path: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GenerateThumbnailsResult {
+    /// Every scene was rendered
+    pub success: bool,
+    /// Godot executable used
+    pub godot: Option<String>,
+    pub rendered_count: i32,
+    /// In the order the scenes were given (project order for all scenes)
+    pub thumbnails: Vec<SceneThumbnail>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Schema Versioning
+// ======================
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum SchemaChangeKind {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+}
+
+/// One change to the schema
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SchemaChange {
+    pub kind: SchemaChangeKind,
+    /// Type or field changed, e.g. `Query.logs` or `Mutation.runTestCoverage`
+    pub target: String,
+    pub description: String,
+    /// Queries valid before this change may fail after it
+    pub breaking: bool,
+    /// For deprecations: field or argument to use instead
+    pub replacement: Option<String>,
+    /// For deprecations: schema version the target is removed in
+    pub removal_version: Option<String>,
+}
+
+/// Changes released together under one schema version
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SchemaRelease {
+    pub version: String,
+    /// Release date (YYYY-MM-DD)
+    pub date: String,
+    pub changes: Vec<SchemaChange>,
+}
+
+/// Current schema version (semver: breaking changes bump the major version)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SchemaVersion {
+    pub version: String,
+    pub major: i32,
+    pub minor: i32,
+    pub patch: i32,
+    /// Deprecated fields still served, with their replacements
+    pub deprecations: Vec<SchemaChange>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SchemaChangelog {
+    pub success: bool,
+    pub current_version: String,
+    /// Releases after sinceVersion, oldest first
+    pub releases: Vec<SchemaRelease>,
+    /// Some listed change is breaking
+    pub has_breaking_changes: bool,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Scene Templates
+// ======================
+
+/// A user-defined scene template in res://.godot-mcp/templates
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneTemplate {
+    pub name: String,
+    /// res:// path of the template scene
+    pub path: String,
+    pub description: Option<String>,
+    pub params: Vec<SceneTemplateParam>,
+}
+
+/// A parameter declared in a template's .toml sidecar
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneTemplateParam {
+    pub name: String,
+    /// Parameter type (e.g., "float", "Color"); any value when not declared
+    #[graphql(name = "type")]
+    pub param_type: Option<String>,
+    /// Default value or expression in Godot syntax
+    pub default: Option<String>,
+    /// Whether createSceneFromTemplate needs a value (no default)
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneTemplatesResult {
+    pub templates: Vec<SceneTemplate>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// A problem in a template or its parameter declarations
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneTemplateLintIssue {
+    pub template: String,
+    /// 1-based line in the template (or in its .toml for INVALID_DECLARATIONS)
+    pub line: Option<i32>,
+    pub severity: ErrorSeverity,
+    /// UNDECLARED_PARAMETER, UNUSED_PARAMETER, UNKNOWN_TYPE, INVALID_DEFAULT,
+    /// INVALID_PLACEHOLDER, INVALID_EXPRESSION, INVALID_SCENE or INVALID_DECLARATIONS
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SceneTemplateLintResult {
+    /// No issues of Error severity
+    pub valid: bool,
+    pub templates_checked: i32,
+    pub issues: Vec<SceneTemplateLintIssue>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Safe change flow
+// ======================
+
+#[derive(Debug, Clone, InputObject)]
+pub struct MutationPlanInput {
+    pub operations: Vec<PlannedOperation>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct PlannedOperation {
+    #[graphql(name = "type")]
+    pub operation_type: OperationType,
+    pub args: async_graphql::Json<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum OperationType {
+    AddNode,
+    RemoveNode,
+    SetProperty,
+    SetProperties,
+    ConnectSignal,
+    DisconnectSignal,
+    AddToGroup,
+    RemoveFromGroup,
+    ReparentNode,
+    DuplicateNode,
+    CreateScript,
+    AttachScript,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MutationValidationResult {
+    pub is_valid: bool,
+    pub errors: Vec<MutationValidationError>,
+    pub warnings: Vec<MutationValidationWarning>,
+    pub validation_time_ms: i32,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MutationValidationError {
+    pub operation_index: i32,
+    pub code: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MutationValidationWarning {
+    pub operation_index: i32,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PreviewResult {
+    pub success: bool,
+    /// Unified diff per affected file
     pub diff: String,
     pub affected_files: Vec<AffectedFile>,
     pub summary: ChangeSummary,
+    /// Operations that would fail when applied
+    pub errors: Vec<ApplyError>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AffectedFile {
+    pub path: String,
+    pub change_type: FileChangeType,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum FileChangeType {
+    Created,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Default, SimpleObject)]
+pub struct ChangeSummary {
+    pub nodes_added: i32,
+    pub nodes_removed: i32,
+    pub properties_changed: i32,
+    pub signals_connected: i32,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct ApplyMutationInput {
+    pub operations: Vec<PlannedOperation>,
+    pub create_backup: Option<bool>,
+    pub backup_description: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ApplyResult {
+    pub success: bool,
+    pub applied_count: i32,
+    pub backup_path: Option<String>,
+    pub errors: Vec<ApplyError>,
+    pub undo_action_id: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ApplyError {
+    pub operation_index: i32,
+    pub message: String,
+}
+
+// ======================
+// Project validation (static)
+// ======================
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectValidationResult {
+    pub is_valid: bool,
+    pub errors: Vec<ProjectValidationError>,
+    pub warnings: Vec<ProjectValidationWarning>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectValidationError {
+    pub file: String,
+    pub line: Option<i32>,
+    pub message: String,
+    pub severity: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectValidationWarning {
+    pub file: Option<String>,
+    pub line: Option<i32>,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+// ======================
+// gatherContext
+// ======================
+
+#[derive(Debug, Clone, InputObject)]
+pub struct GatherContextInput {
+    pub entry_point: String,
+    pub depth: Option<i32>,
+    pub include: Option<Vec<FileType>>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum FileType {
+    Scene,
+    Script,
+    Resource,
+    Shader,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GatheredContext {
+    pub entry_point: String,
+    pub main: ContextItem,
+    pub dependencies: Vec<ContextItem>,
+    pub dependents: Vec<ContextItem>,
+    pub resources: Vec<ResourceInfo>,
+    pub summary: ContextSummary,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ContextItem {
+    pub path: String,
+    #[graphql(name = "type")]
+    pub file_type: FileType,
+    pub scene: Option<Scene>,
+    pub script: Option<Script>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(complex)]
+pub struct ResourceInfo {
+    pub path: String,
+    #[graphql(name = "type")]
+    pub resource_type: Option<String>,
+}
+
+#[ComplexObject]
+impl ResourceInfo {
+    /// Header-derived metadata of binary assets (images, audio, meshes)
+    async fn preview(&self, ctx: &Context<'_>) -> Option<ResourcePreview> {
+        let gql_ctx = ctx.data::<super::context::GqlContext>().ok()?;
+        super::project_resolver::resolve_resource_preview(gql_ctx, &self.path)
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ContextSummary {
+    pub total_files: i32,
+    pub total_functions: i32,
+}
+
+// ======================
+// packContext
+// ======================
+
+/// Which file packContext lowers next while over budget
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum PackStrategy {
+    /// Summarize every file before leaving any out, entry points last
+    Balanced,
+    /// Keep as many files as possible, lowering all of them evenly
+    Breadth,
+    /// Keep the files closest to the entry points whole, dropping the farthest first
+    Depth,
+}
+
+/// Why a file is part of a packed context
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ContextRole {
+    EntryPoint,
+    Dependency,
+    Dependent,
+}
+
+/// Detail a file was packed at, most detailed first
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum PackDetail {
+    Full,
+    /// Without comments and blank lines
+    Stripped,
+    /// Stripped, with long function bodies collapsed
+    Collapsed,
+    /// Declarations referenced by the other files (scripts) or the node tree (scenes)
+    Summary,
+    Omitted,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PackedContext {
+    /// Packed files as Markdown sections, entry points first
+    pub bundle: String,
+    /// Estimated size of the bundle (4 characters per token), at most tokenBudget
+    pub estimated_tokens: i32,
+    pub token_budget: i32,
+    pub strategy: PackStrategy,
+    /// Every file considered, with the detail it was packed at
+    pub files: Vec<PackedFile>,
+    /// Files left out of the bundle
+    pub omitted: Vec<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PackedFile {
+    pub path: String,
+    #[graphql(name = "type")]
+    pub file_type: FileType,
+    pub role: ContextRole,
+    pub detail: PackDetail,
+    /// Estimated tokens of the file's section in the bundle (0 when omitted)
+    pub estimated_tokens: i32,
+    /// Estimated tokens of the file at full detail
+    pub full_tokens: i32,
+}
+
+// ======================
+// dependencyGraph
+// ======================
+
+#[derive(Debug, Clone, InputObject)]
+pub struct DependencyGraphInput {
+    pub directory: Option<String>,
+    pub file_types: Option<Vec<FileType>>,
+    pub format: Option<GraphFormat>,
+    /// Only nodes of matching files, with their outgoing edges
+    pub tags: Option<FileTagFilter>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum GraphFormat {
+    Json,
+    Graphml,
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct GraphNodeFilter {
+    pub is_unused: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    pub stats: GraphStats,
+    pub exported_data: Option<String>,
+    /// Compare with `dependencyGraphVersion` to tell whether this graph is stale
+    pub version: String,
+    pub cycle_break_suggestions: Vec<CycleBreakSuggestion>,
+}
+
+#[Object]
+impl DependencyGraph {
+    async fn nodes(
+        &self,
+        filter: Option<GraphNodeFilter>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Vec<&GraphNode> {
+        let offset = offset.unwrap_or(0) as usize;
+        let limit = limit.map(|l| l as usize);
+
+        let filtered: Vec<&GraphNode> = self
+            .nodes
+            .iter()
+            .filter(|n| {
+                if let Some(ref f) = filter {
+                    if let Some(is_unused) = f.is_unused {
+                        return n.is_unused == is_unused;
+                    }
+                }
+                true
+            })
+            .skip(offset)
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+
+        filtered
+    }
+
+    async fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    async fn stats(&self) -> &GraphStats {
+        &self.stats
+    }
+
+    async fn exported_data(&self) -> Option<&str> {
+        self.exported_data.as_deref()
+    }
+
+    /// Compare with `dependencyGraphVersion` to tell whether this graph is stale
+    async fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// References whose removal breaks the detected cycles, best first
+    async fn cycle_break_suggestions(&self) -> &[CycleBreakSuggestion] {
+        &self.cycle_break_suggestions
+    }
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct AffectedFile {
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    #[graphql(name = "type")]
+    pub node_type: FileType,
+    pub in_degree: i32,
+    pub out_degree: i32,
+    pub is_unused: bool,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub reference_type: ReferenceType,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ReferenceType {
+    /// A node instances the scene (`instance=ExtResource(...)`)
+    Instantiates,
+    AttachesScript,
+    UsesResource,
+    Preloads,
+    Loads,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GraphStats {
+    pub node_count: i32,
+    pub edge_count: i32,
+    pub unused_count: i32,
+    pub has_cycles: bool,
+    pub cycle_paths: Option<Vec<Vec<String>>>,
+}
+
+/// Refactor that removes a reference from a dependency cycle
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum CycleBreakStrategy {
+    /// preload() → load() where the value is used
+    PreloadToLoad,
+    /// Replace the direct reference with a signal or an event bus autoload
+    SignalDecoupling,
+    /// Instance the scene from code instead of in the scene file
+    RuntimeInstantiation,
+    /// Assign the resource from code instead of in the scene file
+    RuntimeLoad,
+}
+
+/// A reference whose removal breaks one or more dependency cycles
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CycleBreakSuggestion {
+    pub from: String,
+    pub to: String,
+    pub reference_type: ReferenceType,
+    /// Line in `from` that holds the reference (1-based)
+    pub line: Option<i32>,
+    pub strategy: CycleBreakStrategy,
+    /// Detected cycles that run through this reference
+    pub cycles_broken: i32,
+    /// Relative refactoring effort; lower is less invasive
+    pub impact: i32,
+    pub description: String,
+}
+
+// ======================
+// signalFlowGraph
+// ======================
+
+/// Text diagram format
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum DiagramFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum SignalVertexKind {
+    /// Node of a scene
+    Node,
+    /// Script not attached in the scanned scenes
+    Script,
+    /// Autoload used as an event bus
+    EventBus,
+}
+
+/// Where a connection was found
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum SignalEdgeSource {
+    /// `[connection]` in a scene file
+    Scene,
+    /// `connect()` / `emit()` in a script
+    Script,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SignalFlowVertex {
+    /// `res://scene.tscn::NodePath`, script path or `autoload:Name`
+    pub id: String,
+    pub label: String,
+    pub kind: SignalVertexKind,
+    pub scene: Option<String>,
+    pub node_type: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SignalFlowEdge {
+    pub from: String,
+    pub to: String,
+    pub signal: String,
+    /// Handler method; `None` for emissions into an event bus
+    pub method: Option<String>,
+    pub source: SignalEdgeSource,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SignalFlowGraph {
+    pub vertices: Vec<SignalFlowVertex>,
+    pub edges: Vec<SignalFlowEdge>,
+    /// The graph rendered in the requested format
+    pub exported_data: String,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Preload Analysis Types
+// ======================
+
+/// preload() (load-time) vs load() (runtime) references of a single script
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScriptLoadReferences {
     pub path: String,
-    pub change_type: FileChangeType,
+    /// Resolved when the script itself is loaded
+    pub preloads: Vec<String>,
+    /// Resolved only when the code runs
+    pub loads: Vec<String>,
+}
+
+/// A file pulled in while loading the entry scene
+#[derive(Debug, Clone, SimpleObject)]
+pub struct StartupFile {
+    pub path: String,
+    #[graphql(name = "type")]
+    pub file_type: FileType,
+    pub size_bytes: i32,
+    /// Shortest reference distance from the entry point
+    pub depth: i32,
+    /// File that first pulled this one into the closure
+    pub loaded_by: Option<String>,
+    pub reference_type: Option<ReferenceType>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PreloadAnalysis {
+    pub entry_point: Option<String>,
+    pub scripts: Vec<ScriptLoadReferences>,
+    pub startup_files: Vec<StartupFile>,
+    pub total_startup_bytes: i32,
+    pub preload_count: i32,
+    pub load_count: i32,
+}
+
+// ======================
+// Project Manifest Types
+// ======================
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectManifest {
+    /// Unix timestamp (ms) to pass as `since` to manifestDelta
+    pub generated_at: String,
+    pub scene_count: i32,
+    pub script_count: i32,
+    /// Manifest document (scenes, scripts, autoloads, inputActions, dependencies, settings)
+    pub json: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ManifestDelta {
+    pub since: String,
+    pub generated_at: String,
+    pub changed_files: Vec<String>,
+    pub removed_files: Vec<String>,
+    /// project.godot changed; project-level sections are included in `json`
+    pub project_changed: bool,
+    pub json: Option<String>,
+    pub message: Option<String>,
 }
 
+// ======================
+// Project Layout Types
+// ======================
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum FileChangeType {
-    Created,
-    Modified,
-    Deleted,
+pub enum LayoutConvention {
+    /// One folder per scene with the scripts/resources only it uses
+    ByFeature,
+    /// scenes/, scripts/, resources/, shaders/, assets/*
+    ByType,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ChangeSummary {
-    pub nodes_added: i32,
-    pub nodes_removed: i32,
-    pub properties_changed: i32,
-    pub signals_connected: i32,
+pub struct PlannedMove {
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProjectLayoutPlan {
+    pub convention: LayoutConvention,
+    pub moves: Vec<PlannedMove>,
+    /// Target folders introduced by the plan
+    pub folders: Vec<String>,
+    pub unchanged_count: i32,
 }
 
 #[derive(Debug, Clone, InputObject)]
-pub struct ApplyMutationInput {
-    pub operations: Vec<PlannedOperation>,
-    pub create_backup: Option<bool>,
-    pub backup_description: Option<String>,
+pub struct ApplyProjectLayoutInput {
+    pub convention: LayoutConvention,
+    /// Moves per batch; a checkpoint is written after each batch
+    #[graphql(default = 20)]
+    pub batch_size: i32,
+    #[graphql(default = false)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ApplyResult {
+pub struct ApplyProjectLayoutResult {
     pub success: bool,
-    pub applied_count: i32,
-    pub backup_path: Option<String>,
-    pub errors: Vec<ApplyError>,
-    pub undo_action_id: Option<String>,
+    pub dry_run: bool,
+    pub moves_applied: i32,
+    pub batches_completed: i32,
+    pub total_batches: i32,
+    pub references_updated: i32,
+    /// res:// path of the checkpoint log (completed moves, in order)
+    pub checkpoint_path: Option<String>,
+    pub failed_move: Option<PlannedMove>,
+    pub message: Option<String>,
 }
 
+// ======================
+// Import Artifact Cleanup Types
+// ======================
+
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ApplyError {
-    pub operation_index: i32,
-    pub message: String,
+pub struct CleanImportArtifactsResult {
+    pub success: bool,
+    pub dry_run: bool,
+    /// .import/.uid files whose source asset no longer exists (moved to the trash unless dryRun)
+    pub removed: Vec<String>,
+    /// Imported assets without a .import file; reimport them in the editor
+    pub missing_imports: Vec<String>,
+    /// res:// folder the removed files were moved to
+    pub trash_path: Option<String>,
+    pub error: Option<GqlStructuredError>,
 }
 
 // ======================
-// Project validation (static)
+// Editor Activity Types
 // ======================
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    /// Free-form message
+    Message,
+    /// Operation the agent is currently running
+    Operation,
+    /// Change waiting for a human decision
+    Approval,
+    /// Recent change, `detail` holds the diff
+    Diff,
+}
+
+/// Item in the activity queue shown by the editor dock
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityItem {
+    /// Increasing sequence number; the dock polls for ids above the last one seen
+    pub id: i32,
+    /// Unix timestamp (ms)
+    pub timestamp: String,
+    pub level: NotifyLevel,
+    pub kind: ActivityKind,
+    pub message: String,
+    pub detail: Option<String>,
+}
+
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ProjectValidationResult {
-    pub is_valid: bool,
-    pub errors: Vec<ProjectValidationError>,
-    pub warnings: Vec<ProjectValidationWarning>,
+pub struct NotifyEditorResult {
+    pub success: bool,
+    pub item: Option<ActivityItem>,
+    pub message: Option<String>,
 }
 
+// ======================
+// Scene Contract Types
+// ======================
+
+/// Broken invariant declared in `.godot-mcp/contracts.toml`
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ProjectValidationError {
-    pub file: String,
-    pub line: Option<i32>,
+pub struct ContractViolation {
+    /// Contract name
+    pub contract: String,
+    pub scene: String,
+    /// Offending nodes (empty when a required node is missing)
+    pub node_paths: Vec<String>,
     pub message: String,
-    pub severity: Option<String>,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ProjectValidationWarning {
-    pub file: Option<String>,
-    pub message: String,
+pub struct ContractCheckResult {
+    /// True when the contracts file was read and every contract holds
+    pub passed: bool,
+    pub contracts_checked: i32,
+    pub scenes_checked: i32,
+    pub violations: Vec<ContractViolation>,
+    /// Set when the contracts file is missing or invalid
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// File Tag Types
+// ======================
+
+/// Scope a query to files with any of the tags and any of the owners
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct FileTagFilter {
+    pub tags: Option<Vec<String>>,
+    pub owners: Option<Vec<String>>,
+}
+
+/// Owners and area tags of a file (owners.toml areas and scene root metadata)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FileTags {
+    pub path: String,
+    pub tags: Vec<String>,
+    pub owners: Vec<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FileTagsResult {
+    pub files: Vec<FileTags>,
+    /// Set when the owners file is invalid
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Decision Log Types
+// ======================
+
+/// A design decision recorded on a file or folder, for later sessions
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Decision {
+    pub id: String,
+    /// File or folder it applies to (res:// path; "res://" for the whole project)
+    pub path: String,
+    pub decision: String,
+    pub rationale: String,
+    /// Session that recorded it
+    pub session_id: String,
+    /// Unix epoch milliseconds
+    pub recorded_at: String,
+    /// Earlier decision this one replaces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+    /// Later decision that replaced this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RecordDecisionResult {
+    pub success: bool,
+    pub decision: Option<Decision>,
+    pub error: Option<GqlStructuredError>,
+}
+
+// ======================
+// Property Search Types
+// ======================
+
+/// Node whose property matched a findPropertyValues search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PropertyMatch {
+    pub scene_path: String,
+    /// Scene-relative node path ("." for the root)
+    pub node_path: String,
+    pub node_name: String,
+    pub node_type: String,
+    pub property: String,
+    /// Value as written in the scene file
+    pub value: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PropertySearchResult {
+    pub success: bool,
+    pub matches: Vec<PropertyMatch>,
+    pub scenes_scanned: i32,
+    /// Set when the value pattern is invalid
+    pub error: Option<GqlStructuredError>,
 }
 
 // ======================
-// gatherContext
+// Performance Audit Types
 // ======================
 
+/// Budgets of performanceAudit (defaults: 4 shadow lights, 10000 GPU particles per scene)
 #[derive(Debug, Clone, InputObject)]
-pub struct GatherContextInput {
-    pub entry_point: String,
-    pub depth: Option<i32>,
-    pub include: Option<Vec<FileType>>,
+pub struct PerformanceAuditOptions {
+    pub max_shadow_lights: Option<i32>,
+    pub max_gpu_particles: Option<i32>,
+    /// `visibility_range_end` suggested for meshes without one (default 150.0)
+    pub visibility_range_end: Option<f64>,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum FileType {
-    Scene,
-    Script,
-    Resource,
-    Shader,
+pub enum PerformanceIssueKind {
+    MissingVisibilityRange,
+    TooManyShadowLights,
+    TooManyParticles,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct GatheredContext {
-    pub entry_point: String,
-    pub main: ContextItem,
-    pub dependencies: Vec<ContextItem>,
-    pub dependents: Vec<ContextItem>,
-    pub resources: Vec<ResourceInfo>,
-    pub summary: ContextSummary,
+pub struct PerformanceIssue {
+    pub kind: PerformanceIssueKind,
+    pub scene_path: String,
+    /// Null for scene-wide issues (particle budget)
+    pub node_path: Option<String>,
+    pub message: String,
 }
 
+/// 3D rendering load of one scene
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ContextItem {
-    pub path: String,
-    #[graphql(name = "type")]
-    pub file_type: FileType,
-    pub scene: Option<Scene>,
-    pub script: Option<Script>,
+pub struct ScenePerformance {
+    pub scene_path: String,
+    pub meshes: i32,
+    pub meshes_without_visibility_range: i32,
+    pub shadow_lights: i32,
+    pub particle_emitters: i32,
+    /// Sum of `amount` over GPU particle emitters
+    pub gpu_particles: i32,
 }
 
+/// Planned operation in the shape of `PlannedOperation`
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ResourceInfo {
-    pub path: String,
+pub struct SuggestedOperation {
     #[graphql(name = "type")]
-    pub resource_type: Option<String>,
+    pub operation_type: OperationType,
+    pub args: async_graphql::Json<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct ContextSummary {
-    pub total_files: i32,
-    pub total_functions: i32,
+pub struct PerformanceAudit {
+    pub success: bool,
+    /// Scenes with meshes, shadow-casting lights or GPU particles
+    pub scenes: Vec<ScenePerformance>,
+    pub issues: Vec<PerformanceIssue>,
+    /// Fixes for the issues, ready for previewMutation / applyMutation
+    pub suggested_plan: Vec<SuggestedOperation>,
+    /// Set when the options are invalid
+    pub error: Option<GqlStructuredError>,
 }
 
 // ======================
-// dependencyGraph
+// Lint / Format Types
 // ======================
 
-#[derive(Debug, Clone, InputObject)]
-pub struct DependencyGraphInput {
-    pub directory: Option<String>,
-    pub file_types: Option<Vec<FileType>>,
-    pub format: Option<GraphFormat>,
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LintResult {
+    pub success: bool,
+    /// `gdlint` when gdtoolkit is installed, `builtin` otherwise
+    pub tool: String,
+    pub files_checked: i32,
+    pub diagnostics: Vec<LintDiagnostic>,
+    /// Why an installed gdlint was not used
+    pub message: Option<String>,
+    pub error: Option<GqlStructuredError>,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum GraphFormat {
-    Json,
-    Graphml,
-    Dot,
-    Mermaid,
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LintDiagnostic {
+    pub file: String,
+    /// `None` for file-wide problems
+    pub line: Option<i32>,
+    pub severity: HealthSeverity,
+    /// Check name, e.g. `function-name` or `syntax-error`
+    pub code: Option<String>,
+    pub message: String,
 }
 
 #[derive(Debug, Clone, InputObject)]
-pub struct GraphNodeFilter {
-    pub is_unused: Option<bool>,
+pub struct GdformatInput {
+    /// Script or folder (`res://`); the whole project when omitted
+    pub path: Option<String>,
+    /// Only report the files gdformat would change
+    pub check: Option<bool>,
 }
 
-#[derive(Debug, Clone)]
-pub struct DependencyGraph {
-    pub nodes: Vec<GraphNode>,
-    pub edges: Vec<GraphEdge>,
-    pub stats: GraphStats,
-    pub exported_data: Option<String>,
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GdformatResult {
+    /// Every script was formatted (or checked) without errors
+    pub success: bool,
+    pub check: bool,
+    pub files_checked: i32,
+    /// Scripts reformatted, or that would be with `check`
+    pub changed_files: Vec<String>,
+    /// Scripts gdformat rejected, e.g. for syntax errors
+    pub failed_files: Vec<FormatFailure>,
+    pub error: Option<GqlStructuredError>,
 }
 
-#[Object]
-impl DependencyGraph {
-    async fn nodes(
-        &self,
-        filter: Option<GraphNodeFilter>,
-        limit: Option<i32>,
-        offset: Option<i32>,
-    ) -> Vec<&GraphNode> {
-        let offset = offset.unwrap_or(0) as usize;
-        let limit = limit.map(|l| l as usize);
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FormatFailure {
+    pub path: String,
+    pub message: String,
+}
 
-        let filtered: Vec<&GraphNode> = self
-            .nodes
-            .iter()
-            .filter(|n| {
-                if let Some(ref f) = filter {
-                    if let Some(is_unused) = f.is_unused {
-                        return n.is_unused == is_unused;
-                    }
-                }
-                true
-            })
-            .skip(offset)
-            .take(limit.unwrap_or(usize::MAX))
-            .collect();
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScriptCheckResult {
+    /// Godot ran on every script
+    pub success: bool,
+    /// No parse errors
+    pub valid: bool,
+    /// Godot executable used
+    pub godot: Option<String>,
+    pub scripts_checked: i32,
+    /// Parse errors and warnings, code `parse-error` or `parse-warning`
+    pub diagnostics: Vec<LintDiagnostic>,
+    pub error: Option<GqlStructuredError>,
+}
 
-        filtered
-    }
+// ======================
+// Project Health Types
+// ======================
 
-    async fn edges(&self) -> &[GraphEdge] {
-        &self.edges
-    }
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+    /// No data for this category (e.g. tests never run, no budgets file)
+    Skipped,
+}
 
-    async fn stats(&self) -> &GraphStats {
-        &self.stats
-    }
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum HealthSeverity {
+    Error,
+    Warning,
+    Info,
+}
 
-    async fn exported_data(&self) -> Option<&str> {
-        self.exported_data.as_deref()
-    }
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HealthIssue {
+    pub severity: HealthSeverity,
+    pub file: Option<String>,
+    pub message: String,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct GraphNode {
-    pub id: String,
-    pub label: String,
-    #[graphql(name = "type")]
-    pub node_type: FileType,
-    pub in_degree: i32,
-    pub out_degree: i32,
-    pub is_unused: bool,
+pub struct HealthCategory {
+    /// validation, lint, dependencies, unused, tests, contracts, budgets
+    pub name: String,
+    pub status: HealthStatus,
+    /// 0-100, absent when skipped
+    pub score: Option<i32>,
+    pub summary: String,
+    pub issue_count: i32,
+    /// Most severe issues first (truncated)
+    pub issues: Vec<HealthIssue>,
+}
+
+/// Recorded overall score
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckpoint {
+    /// Unix timestamp (ms)
+    pub timestamp: String,
+    pub score: i32,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
-pub struct GraphEdge {
-    pub from: String,
-    pub to: String,
-    pub reference_type: ReferenceType,
+pub struct ProjectHealth {
+    /// 0-100, mean of the scored categories
+    pub score: i32,
+    pub categories: Vec<HealthCategory>,
+    /// Lowest-scoring category with issues; the suggested next thing to work on
+    pub focus: Option<String>,
+    pub previous_score: Option<i32>,
+    pub delta: Option<i32>,
+    /// Recorded checkpoints, oldest first (including this one when recorded)
+    pub history: Vec<HealthCheckpoint>,
 }
 
+// ======================
+// Example Project Types
+// ======================
+
+/// Project generated by generateExampleProject
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
-pub enum ReferenceType {
-    Instantiates,
-    AttachesScript,
-    UsesResource,
-    Preloads,
-    Loads,
+pub enum ExampleProjectKind {
+    /// Side-scrolling platformer: player, platforms and coins to collect
+    #[graphql(name = "PLATFORMER_2D")]
+    Platformer2d,
+    /// First-person controller shooting targets
+    #[graphql(name = "FPS_3D")]
+    Fps3d,
+    /// Menu with buttons, a slider and a dialog scene
+    UiDemo,
 }
 
+/// One call into the scene, script, resource or settings subsystem
 #[derive(Debug, Clone, SimpleObject)]
-pub struct GraphStats {
-    pub node_count: i32,
-    pub edge_count: i32,
-    pub unused_count: i32,
-    pub has_cycles: bool,
-    pub cycle_paths: Option<Vec<Vec<String>>>,
+pub struct ExampleProjectStep {
+    /// Operation used, e.g. "createScene", "applyMutation", "addInputAction"
+    pub name: String,
+    /// File, action or setting it applied to
+    pub target: String,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExampleProjectResult {
+    pub success: bool,
+    pub kind: ExampleProjectKind,
+    /// Folder of the generated project
+    pub path: String,
+    pub main_scene: Option<String>,
+    /// Generated files (res:// paths)
+    pub files: Vec<String>,
+    pub input_actions: Vec<String>,
+    /// Steps run, in order, up to the first failure
+    pub steps: Vec<ExampleProjectStep>,
+    /// projectHealth of the generated project
+    pub health: Option<ProjectHealth>,
+    pub error: Option<GqlStructuredError>,
 }
 
 // ======================
@@ -974,13 +3595,22 @@ pub struct GraphStats {
 
 #[derive(Debug, Clone, InputObject)]
 pub struct RunTestsInput {
+    /// Test folder or script (default: res://tests/, or .gutconfig.json's folders for GUT)
     pub test_path: Option<String>,
     pub retries: Option<i32>,
+    /// GdUnit4 or GUT; the installed addon when omitted (GdUnit4 when both are)
+    pub framework: Option<TestFramework>,
+    /// Only test scripts whose path contains this text (GUT: the first one)
+    pub script_name: Option<String>,
+    /// Only tests whose name contains this text
+    pub test_name: Option<String>,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
 pub struct TestExecutionResult {
     pub success: bool,
+    /// Framework the tests ran with
+    pub framework: TestFramework,
     pub total_count: i32,
     pub passed_count: i32,
     pub failed_count: i32,
@@ -988,6 +3618,8 @@ pub struct TestExecutionResult {
     pub skipped_count: i32,
     pub duration_ms: i32,
     pub suites: Vec<TestSuiteResult>,
+    /// Why the tests could not run (missing addon or Godot, no results)
+    pub error: Option<GqlStructuredError>,
 }
 
 #[derive(Debug, Clone, SimpleObject)]
@@ -1005,11 +3637,133 @@ pub struct TestSuiteResult {
 pub struct TestCaseResult {
     pub name: String,
     pub success: bool,
+    /// Pending (GUT) or skipped test
+    pub skipped: bool,
     pub line: Option<i32>,
     pub message: Option<String>,
     pub stack_overflow: Option<bool>,
 }
 
+// ======================
+// testCoverage Types
+// ======================
+
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct TestCoverageInput {
+    /// Test folder or script (default: res://tests/, or .gutconfig.json's folders)
+    pub test_path: Option<String>,
+    /// Folder or script to measure (default: every script outside the tests)
+    pub path: Option<String>,
+    /// Only the first test script whose path contains this text
+    pub script_name: Option<String>,
+    /// Only tests whose name contains this text
+    pub test_name: Option<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TestCoverageResult {
+    /// Whether coverage was measured (failing tests still count)
+    pub success: bool,
+    pub covered_lines: i32,
+    pub total_lines: i32,
+    /// covered / total, 0-100
+    pub percent: f64,
+    /// Least covered first
+    pub scripts: Vec<ScriptCoverage>,
+    /// The GUT run coverage was measured on
+    pub tests: Option<TestExecutionResult>,
+    pub error: Option<GqlStructuredError>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScriptCoverage {
+    pub path: String,
+    pub covered_lines: i32,
+    /// Executable lines (statements in function bodies)
+    pub total_lines: i32,
+    pub percent: f64,
+    pub uncovered_lines: Vec<i32>,
+}
+
+// ======================
+// Editor Screenshot Types
+// ======================
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum ScreenshotViewport {
+    /// 2D editor viewport
+    #[graphql(name = "VIEWPORT_2D")]
+    Viewport2D,
+    /// First 3D editor viewport
+    #[graphql(name = "VIEWPORT_3D")]
+    Viewport3D,
+    /// Whole editor window (annotations are not supported)
+    Editor,
+    /// Game run from the editor (captureViewport only)
+    Game,
+}
+
+/// Node to highlight on the screenshot
+#[derive(Debug, Clone, InputObject)]
+pub struct ScreenshotAnnotationInput {
+    pub node_path: String,
+    /// Label drawn above the box (default: node name)
+    pub label: Option<String>,
+    /// Box color as #rrggbb (default: editor accent color)
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub struct EditorScreenshotInput {
+    #[graphql(default_with = "ScreenshotViewport::Viewport2D")]
+    pub viewport: ScreenshotViewport,
+    #[graphql(default)]
+    pub annotations: Vec<ScreenshotAnnotationInput>,
+    /// Also return the annotated PNG as base64
+    #[graphql(default = false)]
+    pub inline: bool,
+}
+
+/// Screen-space bounds of an annotated node
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScreenshotAnnotation {
+    pub node_path: String,
+    pub label: String,
+    pub found: bool,
+    /// Bounds intersect the captured image
+    pub visible: bool,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EditorScreenshotResult {
+    pub success: bool,
+    /// res:// path of the annotated PNG
+    pub path: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    pub annotations: Vec<ScreenshotAnnotation>,
+    pub image_base64: Option<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Viewport saved to a PNG by captureViewport
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ViewportCapture {
+    pub success: bool,
+    pub viewport: ScreenshotViewport,
+    /// res:// path of the PNG
+    pub path: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    /// The PNG as base64, when requested with `inline`
+    pub image_base64: Option<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
 // ======================
 // Debugging Types (Phase 2)
 // ======================
@@ -1037,6 +3791,73 @@ pub struct LogEntry {
     pub line: Option<i32>,
 }
 
+/// Last game run started by run_project (see lastRunReport)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RunReport {
+    pub pid: u32,
+    pub scene: Option<String>,
+    /// Start time (Unix epoch milliseconds)
+    pub started_at: String,
+    /// End time (Unix epoch milliseconds); null while running
+    pub finished_at: Option<String>,
+    pub running: bool,
+    pub exit_code: Option<i32>,
+    /// Signal that terminated the game (Unix)
+    pub signal: Option<i32>,
+    /// Ended by stop_project rather than on its own
+    pub stopped: bool,
+    /// Watchdog timeout the game was started with
+    pub timeout_seconds: Option<i32>,
+    /// Killed by the watchdog when the timeout passed
+    pub timed_out: bool,
+    /// Stopped when the server that started it exits
+    pub auto_stop: bool,
+    /// CPU use (percent of one core) while running; null on Windows
+    pub cpu_percent: Option<f64>,
+    /// Resident memory while running
+    pub memory_bytes: Option<i64>,
+    pub crashed: bool,
+    /// Crash handler line, signal or exception code the crash was detected by
+    pub crash_reason: Option<String>,
+    pub stdout_tail: Vec<String>,
+    pub stderr_tail: Vec<String>,
+    pub stack_traces: Vec<RunStackTrace>,
+    /// Engine backtrace printed by Godot's crash handler
+    pub native_backtrace: Vec<String>,
+}
+
+/// Start the game outside the editor, like run_project
+#[derive(Debug, Clone, InputObject)]
+pub struct RunProjectInput {
+    /// Scene to run; the main scene when omitted
+    pub scene: Option<String>,
+    /// Kill the game after this many seconds
+    pub timeout_seconds: Option<i32>,
+    /// Stop the game when the server exits
+    #[graphql(default = false)]
+    pub auto_stop: bool,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RunProjectResult {
+    pub success: bool,
+    pub pid: Option<u32>,
+    /// A game started earlier was still running and was stopped first
+    pub restarted: bool,
+    /// Godot executable used
+    pub godot: Option<String>,
+    pub error: Option<GqlStructuredError>,
+}
+
+/// Script error or warning printed during a run, with its GDScript frames
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RunStackTrace {
+    pub message: String,
+    pub is_error: bool,
+    /// Most recent call first
+    pub frames: Vec<StackFrame>,
+}
+
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct GodotObject {
     pub id: String,
@@ -1137,6 +3958,47 @@ pub struct AutoloadsResult {
     pub count: i32,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+pub enum DeadCodeKind {
+    /// Function never called, connected or referenced by name
+    UnusedFunction,
+    /// Signal never emitted nor connected
+    UnusedSignal,
+    /// Signal connected somewhere but never emitted
+    SignalNeverEmitted,
+    /// Signal emitted but never connected
+    SignalNeverConnected,
+}
+
+/// How sure deadCode is that an item is unused (ordered low to high)
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Enum)]
+pub enum DeadCodeConfidence {
+    /// Referenced indirectly (passed around, named in a string)
+    Low,
+    /// May be called by the engine or an addon
+    Medium,
+    High,
+}
+
+/// A function or signal deadCode considers unused
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DeadCodeItem {
+    pub kind: DeadCodeKind,
+    pub name: String,
+    /// Script declaring it (res:// path)
+    pub file: String,
+    pub line: i32,
+    pub confidence: DeadCodeConfidence,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DeadCodeReport {
+    /// Most confident first, then by file and line
+    pub items: Vec<DeadCodeItem>,
+    pub scripts_scanned: i32,
+}
+
 // ======================
 // Phase 3: Refactoring Types
 // ======================
@@ -1203,6 +4065,40 @@ pub struct MoveNodeToSceneResult {
     pub message: Option<String>,
 }
 
+/// Move file input
+#[derive(Debug, Clone, InputObject)]
+pub struct MoveFileInput {
+    pub from: String,
+    pub to: String,
+    /// Report changes without touching the filesystem
+    #[graphql(default = false)]
+    pub dry_run: bool,
+}
+
+/// Rename file input (same directory, new file name)
+#[derive(Debug, Clone, InputObject)]
+pub struct RenameFileInput {
+    pub path: String,
+    pub new_name: String,
+    #[graphql(default = false)]
+    pub dry_run: bool,
+}
+
+/// Move/rename file result
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MoveFileResult {
+    pub success: bool,
+    pub from: String,
+    pub to: String,
+    pub dry_run: bool,
+    /// Files moved on disk, including .uid/.import sidecars
+    pub moved_files: Vec<String>,
+    /// Files whose res:// references were rewritten
+    pub files_changed: Vec<FileChange>,
+    pub references_updated: i32,
+    pub message: Option<String>,
+}
+
 // ======================
 // Phase 3: Code Generation Types
 // ======================
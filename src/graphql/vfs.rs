@@ -0,0 +1,591 @@
+//! Project Filesystem
+//!
+//! Every project file the resolvers read or write goes through [`ProjectFs`]
+//! (`GqlContext::fs`). [`OsFs`] is the real disk; [`MemoryFs`] holds a whole
+//! project in memory, built from a JSON fixture, so that queries and
+//! mutations can run without touching disk — for fast tests of agent
+//! workflows and for the `--sandbox` demo mode, where edits are discarded
+//! when the server exits. Contexts wrap either one in a
+//! `transaction::TransactionFs`, which stages writes while a transaction is
+//! open.
+//!
+//! Features that need a real project on disk (the live editor plugin, the
+//! Godot test runner, the file watcher, gdtoolkit) keep using the OS.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use serde_json::Value;
+
+/// Project path the `--sandbox` fixture is mounted at (never on disk)
+pub const SANDBOX_ROOT: &str = "/godot-mcp-sandbox";
+
+/// Metadata of a file or folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub is_dir: bool,
+    /// Size in bytes (0 for folders)
+    pub len: u64,
+    pub modified: SystemTime,
+}
+
+/// File access used by the resolvers
+pub trait ProjectFs: fmt::Debug + Send + Sync {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+    /// Append to a file, creating it when missing
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+    /// Paths of the entries of a folder, in no particular order
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+    fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    /// Remove an empty folder
+    fn remove_dir(&self, path: &Path) -> io::Result<()>;
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    /// Whether files live outside the real disk (tools that run external
+    /// processes on project files cannot be used)
+    fn is_virtual(&self) -> bool {
+        false
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        let contents = self.read(from)?;
+        self.write(to, &contents)?;
+        Ok(contents.len() as u64)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|m| !m.is_dir)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|m| m.is_dir)
+    }
+}
+
+/// The real filesystem
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsFs;
+
+impl ProjectFs for OsFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        fs::write(path, contents)
+    }
+
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(contents)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        Ok(fs::read_dir(path)?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let metadata = fs::metadata(path)?;
+        Ok(Metadata {
+            is_dir: metadata.is_dir(),
+            len: metadata.len(),
+            modified: metadata.modified()?,
+        })
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir(path)
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        fs::remove_dir_all(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        fs::copy(from, to)
+    }
+}
+
+/// A project held entirely in memory
+///
+/// Every change advances the modification time of the file and its folder
+/// by at least a nanosecond, so the project index notices it even when two
+/// writes happen within the clock's resolution.
+pub struct MemoryFs {
+    state: RwLock<MemoryState>,
+}
+
+struct MemoryState {
+    entries: BTreeMap<PathBuf, Entry>,
+    last_modified: SystemTime,
+}
+
+enum Entry {
+    File { data: Vec<u8>, modified: SystemTime },
+    Dir { modified: SystemTime },
+}
+
+impl Entry {
+    fn metadata(&self) -> Metadata {
+        match self {
+            Entry::File { data, modified } => Metadata {
+                is_dir: false,
+                len: data.len() as u64,
+                modified: *modified,
+            },
+            Entry::Dir { modified } => Metadata {
+                is_dir: true,
+                len: 0,
+                modified: *modified,
+            },
+        }
+    }
+}
+
+impl MemoryFs {
+    /// Empty filesystem containing only the folder `root`
+    pub fn new(root: &Path) -> Self {
+        let fs = Self {
+            state: RwLock::new(MemoryState {
+                entries: BTreeMap::new(),
+                last_modified: SystemTime::UNIX_EPOCH,
+            }),
+        };
+        fs.write_state().create_dir_all(&normalize(root));
+        fs
+    }
+
+    /// Project below `root` from a JSON fixture
+    ///
+    /// The fixture maps project-relative paths (optionally `res://`) to file
+    /// contents: a string for text, `{"base64": "..."}` for binary files and
+    /// `null` for an empty folder.
+    pub fn from_json(root: &Path, fixture: &Value) -> Result<Self, String> {
+        let files = fixture
+            .as_object()
+            .ok_or("Fixture must be an object mapping paths to file contents")?;
+        let fs = Self::new(root);
+        {
+            let mut state = fs.write_state();
+            for (path, content) in files {
+                let relative = path.strip_prefix("res://").unwrap_or(path);
+                let escapes = relative.starts_with(['/', '\\'])
+                    || Path::new(relative).is_absolute()
+                    || relative.split(['/', '\\']).any(|part| part == "..");
+                if escapes {
+                    return Err(format!("Fixture path leaves the project: {}", path));
+                }
+                let full_path = normalize(&root.join(relative));
+                match content {
+                    Value::String(text) => state.write(&full_path, text.as_bytes().to_vec()),
+                    Value::Null => {
+                        state.create_dir_all(&full_path);
+                        Ok(())
+                    }
+                    Value::Object(object) => {
+                        let encoded = object
+                            .get("base64")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| format!("Unsupported fixture entry: {}", path))?;
+                        let data = base64::engine::general_purpose::STANDARD
+                            .decode(encoded)
+                            .map_err(|e| format!("Invalid base64 for {}: {}", path, e))?;
+                        state.write(&full_path, data)
+                    }
+                    _ => return Err(format!("Unsupported fixture entry: {}", path)),
+                }
+                .map_err(|e| format!("Cannot add {}: {}", path, e))?;
+            }
+        }
+        Ok(fs)
+    }
+
+    /// Load a JSON fixture file (see [`MemoryFs::from_json`])
+    pub fn from_fixture_file(root: &Path, fixture: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(fixture)
+            .map_err(|e| format!("Cannot read {}: {}", fixture.display(), e))?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid fixture {}: {}", fixture.display(), e))?;
+        Self::from_json(root, &value)
+    }
+
+    /// Text files below `root` as a fixture (binary files are base64 encoded)
+    pub fn to_json(&self, root: &Path) -> Value {
+        let root = normalize(root);
+        let state = self.read_state();
+        let files = state
+            .entries
+            .iter()
+            .filter_map(|(path, entry)| {
+                let Entry::File { data, .. } = entry else {
+                    return None;
+                };
+                let relative = path.strip_prefix(&root).ok()?;
+                let content = match std::str::from_utf8(data) {
+                    Ok(text) => Value::String(text.to_string()),
+                    Err(_) => serde_json::json!({
+                        "base64": base64::engine::general_purpose::STANDARD.encode(data)
+                    }),
+                };
+                Some((relative.to_string_lossy().replace('\\', "/"), content))
+            })
+            .collect();
+        Value::Object(files)
+    }
+
+    fn read_state(&self) -> RwLockReadGuard<'_, MemoryState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_state(&self) -> RwLockWriteGuard<'_, MemoryState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl MemoryState {
+    /// Next modification time, later than every earlier one
+    fn tick(&mut self) -> SystemTime {
+        let now = SystemTime::now();
+        self.last_modified = if now > self.last_modified {
+            now
+        } else {
+            self.last_modified + Duration::from_nanos(1)
+        };
+        self.last_modified
+    }
+
+    fn create_dir_all(&mut self, path: &Path) {
+        for ancestor in path.ancestors().collect::<Vec<_>>().into_iter().rev() {
+            if !self.entries.contains_key(ancestor) {
+                let modified = self.tick();
+                self.entries
+                    .insert(ancestor.to_path_buf(), Entry::Dir { modified });
+                self.touch_parent(ancestor);
+            }
+        }
+    }
+
+    fn write(&mut self, path: &Path, data: Vec<u8>) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            self.create_dir_all(parent);
+        }
+        let created = match self.entries.get(path) {
+            Some(Entry::Dir { .. }) => return Err(is_a_directory(path)),
+            Some(Entry::File { .. }) => false,
+            None => true,
+        };
+        let modified = self.tick();
+        self.entries
+            .insert(path.to_path_buf(), Entry::File { data, modified });
+        if created {
+            self.touch_parent(path);
+        }
+        Ok(())
+    }
+
+    /// Update a folder's modification time after its entries changed
+    fn touch_parent(&mut self, path: &Path) {
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        let modified = self.tick();
+        if let Some(Entry::Dir { modified: m }) = self.entries.get_mut(parent) {
+            *m = modified;
+        }
+    }
+
+    fn dir_exists(&self, path: &Path) -> io::Result<()> {
+        match self.entries.get(path) {
+            Some(Entry::Dir { .. }) => Ok(()),
+            Some(Entry::File { .. }) => Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                format!("Not a directory: {}", path.display()),
+            )),
+            None => Err(not_found(path)),
+        }
+    }
+
+    /// Paths at or below `path`
+    fn subtree(&self, path: &Path) -> Vec<PathBuf> {
+        self.entries
+            .range(path.to_path_buf()..)
+            .map(|(p, _)| p)
+            .take_while(|p| p.starts_with(path))
+            .cloned()
+            .collect()
+    }
+}
+
+impl ProjectFs for MemoryFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.read_state().entries.get(&normalize(path)) {
+            Some(Entry::File { data, .. }) => Ok(data.clone()),
+            Some(Entry::Dir { .. }) => Err(is_a_directory(path)),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        if let Some(parent) = path.parent() {
+            state.dir_exists(parent)?;
+        }
+        state.write(&path, contents.to_vec())
+    }
+
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        let mut data = match state.entries.get(&path) {
+            Some(Entry::File { data, .. }) => data.clone(),
+            Some(Entry::Dir { .. }) => return Err(is_a_directory(&path)),
+            None => {
+                if let Some(parent) = path.parent() {
+                    state.dir_exists(parent)?;
+                }
+                Vec::new()
+            }
+        };
+        data.extend_from_slice(contents);
+        state.write(&path, data)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        if let Some(file) = path
+            .ancestors()
+            .find(|a| matches!(state.entries.get(*a), Some(Entry::File { .. })))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("File exists: {}", file.display()),
+            ));
+        }
+        state.create_dir_all(&path);
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let path = normalize(path);
+        let state = self.read_state();
+        state.dir_exists(&path)?;
+        Ok(state
+            .subtree(&path)
+            .into_iter()
+            .filter(|p| p.parent() == Some(path.as_path()))
+            .collect())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.read_state()
+            .entries
+            .get(&normalize(path))
+            .map(Entry::metadata)
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        match state.entries.get(&path) {
+            Some(Entry::File { .. }) => {
+                state.entries.remove(&path);
+                state.touch_parent(&path);
+                Ok(())
+            }
+            Some(Entry::Dir { .. }) => Err(is_a_directory(&path)),
+            None => Err(not_found(&path)),
+        }
+    }
+
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        state.dir_exists(&path)?;
+        if state.subtree(&path).len() > 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::DirectoryNotEmpty,
+                format!("Directory not empty: {}", path.display()),
+            ));
+        }
+        state.entries.remove(&path);
+        state.touch_parent(&path);
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let path = normalize(path);
+        let mut state = self.write_state();
+        state.dir_exists(&path)?;
+        for entry in state.subtree(&path) {
+            state.entries.remove(&entry);
+        }
+        state.touch_parent(&path);
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let (from, to) = (normalize(from), normalize(to));
+        let mut state = self.write_state();
+        if !state.entries.contains_key(&from) {
+            return Err(not_found(&from));
+        }
+        if let Some(parent) = to.parent() {
+            state.dir_exists(parent)?;
+        }
+        if to.starts_with(&from) && to != from {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Cannot move {} into itself", from.display()),
+            ));
+        }
+        for old in state.subtree(&from) {
+            let entry = state.entries.remove(&old).expect("listed entry");
+            let new = to.join(old.strip_prefix(&from).expect("subtree path"));
+            state.entries.insert(new, entry);
+        }
+        state.touch_parent(&from);
+        state.touch_parent(&to);
+        Ok(())
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for MemoryFs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryFs")
+            .field("entries", &self.read_state().entries.len())
+            .finish()
+    }
+}
+
+/// Drop `.` components and trailing separators so equal paths compare equal
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+pub(super) fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("No such file or directory: {}", path.display()),
+    )
+}
+
+pub(super) fn is_a_directory(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::IsADirectory,
+        format!("Is a directory: {}", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_fs_from_fixture() {
+        let root = Path::new("/sandbox");
+        let fs = MemoryFs::from_json(
+            root,
+            &serde_json::json!({
+                "project.godot": "config_version=5\n",
+                "res://scenes/main.tscn": "[node name=\"Main\" type=\"Node\"]\n",
+                "icon.png": { "base64": "iVBORw==" },
+                "empty": null
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs.read_to_string(&root.join("project.godot")).unwrap(),
+            "config_version=5\n"
+        );
+        assert!(fs.is_dir(&root.join("scenes")));
+        assert!(fs.is_dir(&root.join("empty")));
+        assert_eq!(fs.read(&root.join("icon.png")).unwrap(), b"\x89PNG");
+        let mut entries = fs.read_dir(root).unwrap();
+        entries.sort();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            fs.to_json(root)["scenes/main.tscn"],
+            "[node name=\"Main\" type=\"Node\"]\n"
+        );
+
+        assert!(MemoryFs::from_json(root, &serde_json::json!({ "../x": "" })).is_err());
+        assert!(MemoryFs::from_json(root, &serde_json::json!({ "/etc/x": "" })).is_err());
+    }
+
+    #[test]
+    fn test_memory_fs_changes_advance_modified() {
+        let root = Path::new("/sandbox");
+        let fs = MemoryFs::new(root);
+        let dir = root.join("scripts");
+        fs.create_dir_all(&dir).unwrap();
+        let listed = fs.metadata(&dir).unwrap().modified;
+
+        fs.write(&dir.join("a.gd"), b"extends Node\n").unwrap();
+        let written = fs.metadata(&dir.join("a.gd")).unwrap().modified;
+        assert!(fs.metadata(&dir).unwrap().modified > listed);
+        fs.append(&dir.join("a.gd"), b"var x\n").unwrap();
+        assert!(fs.metadata(&dir.join("a.gd")).unwrap().modified > written);
+
+        fs.rename(&dir, &root.join("src")).unwrap();
+        assert!(!fs.exists(&dir.join("a.gd")));
+        assert_eq!(
+            fs.read_to_string(&root.join("src/a.gd")).unwrap(),
+            "extends Node\nvar x\n"
+        );
+        assert!(fs.remove_dir(&root.join("src")).is_err());
+        fs.remove_dir_all(&root.join("src")).unwrap();
+        assert!(fs.read_dir(root).unwrap().is_empty());
+        assert!(fs.write(&root.join("missing/a.gd"), b"").is_err());
+    }
+}
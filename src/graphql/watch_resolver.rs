@@ -0,0 +1,89 @@
+//! File Watch Resolvers
+//!
+//! Streams behind the `fileChanged` / `sceneChanged` subscriptions, fed by the
+//! project file watcher in `crate::watch`.
+
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio::sync::broadcast;
+
+use super::context::GqlContext;
+use super::scene_resolver::resolve_scene;
+use super::types::*;
+use crate::watch::{self, FileEvent, FileEventKind};
+
+/// Changes to watched files, optionally limited to some extensions
+/// (without the dot, e.g. `["gd"]`)
+pub fn resolve_file_changed(
+    ctx: &GqlContext,
+    extensions: Option<Vec<String>>,
+) -> Result<impl Stream<Item = FileChangedEvent>, String> {
+    let receiver = watch::subscribe(&ctx.project_path)?;
+    Ok(file_events(receiver).filter_map(move |event| {
+        let wanted = extensions.as_ref().is_none_or(|exts| {
+            exts.iter()
+                .any(|e| e.trim_start_matches('.') == event.extension())
+        });
+        let item = wanted.then(|| FileChangedEvent {
+            path: event.path,
+            change_type: change_type(event.kind),
+            timestamp: event.timestamp.to_string(),
+        });
+        async move { item }
+    }))
+}
+
+/// Changes to scene files, with the scene re-parsed after each save.
+/// When `path` is given, only that scene is reported.
+pub fn resolve_scene_changed(
+    ctx: &GqlContext,
+    path: Option<String>,
+) -> Result<impl Stream<Item = SceneChangedEvent>, String> {
+    let receiver = watch::subscribe(&ctx.project_path)?;
+    let ctx = ctx.clone();
+    Ok(file_events(receiver).filter_map(move |event| {
+        let wanted = event.extension() == "tscn"
+            && path
+                .as_deref()
+                .is_none_or(|p| same_res_path(p, &event.path));
+        let item = wanted.then(|| {
+            let scene = match event.kind {
+                FileEventKind::Deleted => None,
+                _ => resolve_scene(&ctx, &event.path),
+            };
+            SceneChangedEvent {
+                path: event.path,
+                change_type: change_type(event.kind),
+                scene,
+                timestamp: event.timestamp.to_string(),
+            }
+        });
+        async move { item }
+    }))
+}
+
+/// Receiver as a stream; events missed by a slow subscriber are skipped
+fn file_events(receiver: broadcast::Receiver<FileEvent>) -> impl Stream<Item = FileEvent> {
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((event, receiver)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("File watch subscriber lagged; skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+fn change_type(kind: FileEventKind) -> FileChangeType {
+    match kind {
+        FileEventKind::Created => FileChangeType::Created,
+        FileEventKind::Modified => FileChangeType::Modified,
+        FileEventKind::Deleted => FileChangeType::Deleted,
+    }
+}
+
+fn same_res_path(a: &str, b: &str) -> bool {
+    a.strip_prefix("res://").unwrap_or(a) == b.strip_prefix("res://").unwrap_or(b)
+}
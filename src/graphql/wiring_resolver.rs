@@ -0,0 +1,466 @@
+//! Wiring Resolver
+//!
+//! Audits exported node references (`@export var x: NodePath`, `@export var
+//! x: Camera2D`, `@export_node_path(...)`) of the scripts attached in a scene,
+//! and assigns unset ones to the best matching node by type and name — a
+//! frequent source of null references at runtime.
+
+use crate::godot::gdscript::GDScript;
+use crate::godot::tscn::{node_path, GodotScene, SceneNode};
+use crate::path_utils;
+use crate::telemetry;
+
+use super::audit;
+use super::context::GqlContext;
+use super::scene_lock;
+use super::types::*;
+
+/// Candidates reported per reference
+const MAX_CANDIDATES: usize = 5;
+
+/// Engine node types recognised as export types without a matching node in the scene
+const KNOWN_NODE_TYPES: &[&str] = &[
+    "Node",
+    "Node2D",
+    "Node3D",
+    "CanvasItem",
+    "CanvasLayer",
+    "Control",
+    "Camera2D",
+    "Camera3D",
+    "AnimationPlayer",
+    "AnimationTree",
+    "AudioStreamPlayer",
+    "AudioStreamPlayer2D",
+    "AudioStreamPlayer3D",
+    "Area2D",
+    "Area3D",
+    "CharacterBody2D",
+    "CharacterBody3D",
+    "RigidBody2D",
+    "RigidBody3D",
+    "StaticBody2D",
+    "StaticBody3D",
+    "CollisionShape2D",
+    "CollisionShape3D",
+    "Sprite2D",
+    "Sprite3D",
+    "AnimatedSprite2D",
+    "AnimatedSprite3D",
+    "MeshInstance3D",
+    "Marker2D",
+    "Marker3D",
+    "RayCast2D",
+    "RayCast3D",
+    "Timer",
+    "NavigationAgent2D",
+    "NavigationAgent3D",
+    "Path2D",
+    "Path3D",
+    "PathFollow2D",
+    "PathFollow3D",
+    "TileMap",
+    "TileMapLayer",
+    "GPUParticles2D",
+    "GPUParticles3D",
+    "Label",
+    "Button",
+    "TextureRect",
+    "ColorRect",
+    "ProgressBar",
+    "LineEdit",
+    "TextEdit",
+    "RichTextLabel",
+    "Panel",
+    "SubViewport",
+];
+
+/// Control subclasses without a 2D/3D suffix
+const CONTROL_TYPES: &[&str] = &[
+    "Control",
+    "Label",
+    "Button",
+    "TextureRect",
+    "ColorRect",
+    "ProgressBar",
+    "LineEdit",
+    "TextEdit",
+    "RichTextLabel",
+    "Panel",
+    "TextureButton",
+    "CheckBox",
+    "OptionButton",
+    "ItemList",
+    "Tree",
+    "TabContainer",
+];
+
+/// Exported reference declared by a script
+struct ExportDecl {
+    name: String,
+    export_type: String,
+    /// Allowed node types from `@export_node_path(...)`
+    node_path_hints: Vec<String>,
+}
+
+/// Unassigned reference resolved to a single candidate
+struct Assignment {
+    node_index: usize,
+    property: String,
+    value: String,
+}
+
+/// Resolve wireExportedReferences mutation
+pub async fn resolve_wire_exported_references(
+    ctx: &GqlContext,
+    input: &WireExportedReferencesInput,
+) -> WireExportedReferencesResult {
+    if input.dry_run {
+        return wire_exported_references(ctx, input);
+    }
+
+    let policy = input.policy.unwrap_or(SceneWritePolicy::Refuse);
+    let lock = match scene_lock::lock_scene(ctx, &input.scene_path, policy).await {
+        Ok(lock) => lock,
+        Err(e) => return failure(input.dry_run, e),
+    };
+    let result = wire_exported_references(ctx, input);
+    scene_lock::unlock_scene(ctx, lock, result.success && result.wired_count > 0).await;
+    result
+}
+
+fn wire_exported_references(
+    ctx: &GqlContext,
+    input: &WireExportedReferencesInput,
+) -> WireExportedReferencesResult {
+    let file_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &input.scene_path);
+    let Ok(content) = ctx.fs.read_to_string(&file_path) else {
+        return failure(
+            input.dry_run,
+            GqlStructuredError::new(
+                "SCENE_NOT_FOUND",
+                GqlErrorCategory::FileSystem,
+                format!("Scene not found: {}", input.scene_path),
+            ),
+        );
+    };
+    let mut scene = match GodotScene::parse(&content) {
+        Ok(scene) => scene,
+        Err(e) => {
+            return failure(
+                input.dry_run,
+                GqlStructuredError::new(
+                    "SCENE_PARSE_ERROR",
+                    GqlErrorCategory::Validation,
+                    format!("Failed to parse scene: {}", e),
+                ),
+            )
+        }
+    };
+
+    // Script path and source of every scripted node
+    let scripts: Vec<Option<(String, String)>> = scene
+        .nodes
+        .iter()
+        .map(|node| {
+            let path = attached_script(&scene, node)?;
+            let fs_path = path_utils::to_fs_path_unchecked(&ctx.project_path, &path);
+            Some((path, ctx.fs.read_to_string(&fs_path).ok()?))
+        })
+        .collect();
+    let classes: Vec<Option<String>> = scripts
+        .iter()
+        .map(|s| {
+            s.as_ref()
+                .and_then(|(_, src)| GDScript::parse(src).class_name)
+        })
+        .collect();
+
+    let mut references = Vec::new();
+    let mut assignments = Vec::new();
+
+    for (index, node) in scene.nodes.iter().enumerate() {
+        let Some((script_path, source)) = &scripts[index] else {
+            continue;
+        };
+        let owner_path = node_path(node);
+
+        for export in exported_references(source, &scene, &classes) {
+            if let Some(value) = node.properties.get(&export.name) {
+                references.push(ExportedReference {
+                    node_path: owner_path.clone(),
+                    script_path: script_path.clone(),
+                    property: export.name,
+                    export_type: export.export_type,
+                    status: ReferenceWireStatus::Assigned,
+                    value: Some(value.clone()),
+                    candidates: vec![],
+                });
+                continue;
+            }
+
+            let mut candidates: Vec<ReferenceCandidate> = scene
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != index)
+                .filter_map(|(i, target)| {
+                    let score = candidate_score(&export, target, classes[i].as_deref())?;
+                    let target_path = node_path(target);
+                    Some(ReferenceCandidate {
+                        relative_path: relative_node_path(&owner_path, &target_path),
+                        node_path: target_path,
+                        node_type: target.node_type.clone(),
+                        score,
+                    })
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.score.cmp(&a.score).then(a.node_path.cmp(&b.node_path)));
+            candidates.truncate(MAX_CANDIDATES);
+
+            let (status, value) = match candidates.as_slice() {
+                [] => (ReferenceWireStatus::NoCandidate, None),
+                [first, second, ..] if first.score == second.score => {
+                    (ReferenceWireStatus::Ambiguous, None)
+                }
+                [best, ..] => {
+                    let value = format!("NodePath(\"{}\")", best.relative_path);
+                    assignments.push(Assignment {
+                        node_index: index,
+                        property: export.name.clone(),
+                        value: value.clone(),
+                    });
+                    (ReferenceWireStatus::Wired, Some(value))
+                }
+            };
+
+            references.push(ExportedReference {
+                node_path: owner_path.clone(),
+                script_path: script_path.clone(),
+                property: export.name,
+                export_type: export.export_type,
+                status,
+                value,
+                candidates,
+            });
+        }
+    }
+
+    let wired_count = assignments.len() as i32;
+    let unresolved_count = references
+        .iter()
+        .filter(|r| {
+            matches!(
+                r.status,
+                ReferenceWireStatus::Ambiguous | ReferenceWireStatus::NoCandidate
+            )
+        })
+        .count() as i32;
+
+    if !input.dry_run && !assignments.is_empty() {
+        for assignment in assignments {
+            scene.nodes[assignment.node_index]
+                .properties
+                .insert(assignment.property, assignment.value);
+        }
+        if let Err(e) =
+            audit::write_file(ctx, "wireExportedReferences", &file_path, scene.to_tscn())
+        {
+            return failure(
+                input.dry_run,
+                GqlStructuredError::new(
+                    "FILE_WRITE_ERROR",
+                    GqlErrorCategory::FileSystem,
+                    format!("Failed to write scene: {}", e),
+                ),
+            );
+        }
+        telemetry::record_files_written(1);
+    }
+
+    WireExportedReferencesResult {
+        success: true,
+        dry_run: input.dry_run,
+        references,
+        wired_count,
+        unresolved_count,
+        error: None,
+    }
+}
+
+fn failure(dry_run: bool, error: GqlStructuredError) -> WireExportedReferencesResult {
+    WireExportedReferencesResult {
+        success: false,
+        dry_run,
+        references: vec![],
+        wired_count: 0,
+        unresolved_count: 0,
+        error: Some(error),
+    }
+}
+
+/// res:// path of the script attached with `script = ExtResource("id")`
+pub(super) fn attached_script(scene: &GodotScene, node: &SceneNode) -> Option<String> {
+    let value = node.properties.get("script")?;
+    let id = value
+        .strip_prefix("ExtResource(")?
+        .trim_end_matches(')')
+        .trim_matches('"');
+    scene
+        .ext_resources
+        .iter()
+        .find(|r| r.id == id)
+        .map(|r| r.path.clone())
+}
+
+/// Exported variables of a script that hold node references
+fn exported_references(
+    source: &str,
+    scene: &GodotScene,
+    classes: &[Option<String>],
+) -> Vec<ExportDecl> {
+    let is_node_type = |t: &str| {
+        KNOWN_NODE_TYPES.contains(&t)
+            || scene.nodes.iter().any(|n| n.node_type == t)
+            || classes.iter().flatten().any(|c| c == t)
+    };
+
+    GDScript::parse(source)
+        .ast
+        .variables()
+        .filter(|var| var.is_exported())
+        .filter_map(|var| {
+            let export_type = var.type_hint.clone()?;
+            if export_type != "NodePath" && !is_node_type(&export_type) {
+                return None;
+            }
+            // Allowed types of `@export_node_path("A", "B")`
+            let node_path_hints = var
+                .annotations
+                .iter()
+                .filter(|a| a.name == "export_node_path")
+                .flat_map(|a| a.string_arguments())
+                .collect();
+            Some(ExportDecl {
+                name: var.name.clone(),
+                export_type,
+                node_path_hints,
+            })
+        })
+        .collect()
+}
+
+/// Score a node as the target of an export; `None` when it does not qualify
+fn candidate_score(export: &ExportDecl, node: &SceneNode, class: Option<&str>) -> Option<i32> {
+    let name = name_score(&export.name, &node.name);
+
+    let type_score = if export.export_type == "NodePath" {
+        if export.node_path_hints.is_empty() {
+            // Untyped NodePath: only the name can tell
+            if name == 0 {
+                return None;
+            }
+            0
+        } else {
+            export
+                .node_path_hints
+                .iter()
+                .map(|hint| type_score(hint, node, class))
+                .max()
+                .unwrap_or(0)
+        }
+    } else {
+        type_score(&export.export_type, node, class)
+    };
+
+    let typed = export.export_type != "NodePath" || !export.node_path_hints.is_empty();
+    if typed && type_score == 0 {
+        return None;
+    }
+    Some(type_score + name)
+}
+
+/// 2 for the exact type or class_name, 1 for a compatible base type
+fn type_score(expected: &str, node: &SceneNode, class: Option<&str>) -> i32 {
+    let node_type = node.node_type.as_str();
+    if expected == node_type || Some(expected) == class {
+        return 2;
+    }
+    let compatible = match expected {
+        "Node" => true,
+        "Node2D" => node_type.ends_with("2D"),
+        "Node3D" => node_type.ends_with("3D"),
+        "CanvasItem" => node_type.ends_with("2D") || is_control(node_type),
+        "Control" => is_control(node_type),
+        _ => false,
+    };
+    i32::from(compatible)
+}
+
+fn is_control(node_type: &str) -> bool {
+    CONTROL_TYPES.contains(&node_type)
+        || node_type.ends_with("Container")
+        || node_type.ends_with("Button")
+}
+
+/// 3 when names match ignoring case and underscores, 1 when one contains the other
+fn name_score(property: &str, node_name: &str) -> i32 {
+    let normalize = |s: &str| s.replace('_', "").to_lowercase();
+    let property = normalize(property);
+    let node_name = normalize(node_name);
+    if property == node_name {
+        3
+    } else if property.contains(&node_name) || node_name.contains(&property) {
+        1
+    } else {
+        0
+    }
+}
+
+/// NodePath from `from` to `to`, both relative to the scene root
+fn relative_node_path(from: &str, to: &str) -> String {
+    let segments = |path: &str| -> Vec<String> {
+        path.split('/')
+            .filter(|s| !s.is_empty() && *s != ".")
+            .map(str::to_string)
+            .collect()
+    };
+    let from = segments(from);
+    let to = segments(to);
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().cloned());
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_node_path() {
+        assert_eq!(relative_node_path(".", "Camera"), "Camera");
+        assert_eq!(relative_node_path("Player", "Camera"), "../Camera");
+        assert_eq!(relative_node_path("Player", "Player/Sprite"), "Sprite");
+        assert_eq!(relative_node_path("A/B", "C/D"), "../../C/D");
+        assert_eq!(relative_node_path("A", "."), "..");
+    }
+
+    #[test]
+    fn test_node_path_hints() {
+        let scene = GodotScene::new("Root", "Node2D");
+        let exports = exported_references(
+            "@export_node_path(\"Camera2D\", \"Camera3D\") var camera: NodePath\n@export var speed: float",
+            &scene,
+            &[],
+        );
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].name, "camera");
+        assert_eq!(exports[0].node_path_hints, vec!["Camera2D", "Camera3D"]);
+    }
+}
@@ -0,0 +1,395 @@
+//! GraphQL over HTTP
+//!
+//! Serves the GQL schema to local tools that don't speak MCP, such as agents
+//! running inside the Godot editor (EditorScript / GDScript LLM clients):
+//!
+//! - `POST /graphql` with `{"query", "variables", "operationName"}` and
+//!   `Authorization: Bearer <token>`
+//! - `POST /graphql/stream` with the same body, answered as server-sent events
+//!   (one `next` event per response) for subscriptions such as `fileChanged`
+//! - `GET /health` (no auth) for readiness checks
+//!
+//! CORS preflight is answered for the configured origins. Tokens are scoped:
+//! read tokens may only run queries, and not the query fields that write files
+//! or launch Godot (`WRITE_QUERY_FIELDS`). For remote boxes the endpoint serves HTTPS
+//! (rustls) and refuses plain HTTP beyond localhost unless explicitly allowed.
+
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use async_graphql::parser::types::{ExecutableDocument, OperationType, Selection, SelectionSet};
+use axum::extract::{Extension, Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::StreamExt;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls;
+use tokio_rustls::TlsAcceptor;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::config::{HttpConfig, HttpToken, TlsConfig, TokenScope};
+use crate::graphql::GqlSchema;
+use crate::path_utils;
+
+/// Written when the server generates its own token, so in-editor clients can find it
+pub const ENDPOINT_FILE: &str = "res://.godot-mcp/http.json";
+
+#[derive(Clone)]
+struct AppState {
+    schema: GqlSchema,
+    tokens: Arc<Vec<HttpToken>>,
+}
+
+/// Build the HTTP router for a schema, accepting any of `tokens`
+pub fn router(schema: GqlSchema, tokens: Vec<HttpToken>, cors_origins: &[String]) -> Router {
+    let state = AppState {
+        schema,
+        tokens: Arc::new(tokens),
+    };
+
+    let graphql = Router::new()
+        .route("/graphql", post(graphql_handler))
+        .route("/graphql/stream", post(graphql_stream_handler))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .merge(graphql)
+        .route("/health", get(|| async { "ok" }))
+        .layer(cors_layer(cors_origins))
+        .with_state(state)
+}
+
+/// Serve until the process is stopped
+///
+/// A generated token is written to the project's endpoint file, or only
+/// logged when there is no project on disk (`project_path` is `None`).
+pub async fn serve(
+    schema: GqlSchema,
+    config: &HttpConfig,
+    project_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    let tls = config.tls.as_ref().map(tls_acceptor).transpose()?;
+    let listener = TcpListener::bind(&config.bind)
+        .await
+        .with_context(|| format!("Failed to bind {}", config.bind))?;
+    let addr = listener.local_addr()?;
+    if !addr.ip().is_loopback() && tls.is_none() {
+        if !config.allow_insecure {
+            anyhow::bail!(
+                "Refusing plain HTTP on {}: configure [http.tls] or set allow_insecure = true",
+                addr
+            );
+        }
+        tracing::warn!("Serving plain HTTP beyond localhost: {}", addr);
+    }
+
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let url = format!("{}://{}/graphql", scheme, addr);
+    let mut tokens = config.tokens.clone();
+    if tokens.is_empty() {
+        let token = generate_token();
+        match project_path {
+            Some(project_path) => {
+                write_endpoint_file(project_path, &url, &token)?;
+                tracing::info!(
+                    "No HTTP token configured; generated one and wrote it to {}",
+                    ENDPOINT_FILE
+                );
+            }
+            None => tracing::info!("No HTTP token configured; generated token {}", token),
+        }
+        tokens.push(HttpToken::write(token));
+    }
+    tracing::info!("GraphQL HTTP endpoint listening on {}", url);
+
+    serve_listener(listener, router(schema, tokens, &config.cors_origins), tls).await
+}
+
+/// Serve `app` on `listener`, over TLS when an acceptor is given
+pub async fn serve_listener(
+    listener: TcpListener,
+    app: Router,
+    tls: Option<TlsAcceptor>,
+) -> anyhow::Result<()> {
+    let Some(acceptor) = tls else {
+        axum::serve(listener, app).await?;
+        return Ok(());
+    };
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let acceptor = acceptor.clone();
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            let stream = match acceptor.accept(stream).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::debug!("TLS handshake with {} failed: {}", peer, e);
+                    return;
+                }
+            };
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("HTTPS connection with {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Load the PEM certificate chain and private key
+pub fn tls_acceptor(config: &TlsConfig) -> anyhow::Result<TlsAcceptor> {
+    let open = |path: &Path| {
+        File::open(path)
+            .map(BufReader::new)
+            .with_context(|| format!("Failed to open {}", path.display()))
+    };
+    let certs = rustls_pemfile::certs(&mut open(&config.cert)?)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid certificate {}", config.cert.display()))?;
+    let key = rustls_pemfile::private_key(&mut open(&config.key)?)?
+        .with_context(|| format!("No private key in {}", config.key.display()))?;
+
+    let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()?
+    .with_no_client_auth()
+    .with_single_cert(certs, key)
+    .context("Certificate and key don't match")?;
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+async fn graphql_handler(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TokenScope>,
+    Json(request): Json<async_graphql::Request>,
+) -> Response {
+    if scope == TokenScope::Read && is_write(&request) {
+        return error_response(StatusCode::FORBIDDEN, "This token is read-only");
+    }
+    Json(state.schema.execute(request).await).into_response()
+}
+
+async fn graphql_stream_handler(
+    State(state): State<AppState>,
+    Extension(scope): Extension<TokenScope>,
+    Json(request): Json<async_graphql::Request>,
+) -> Response {
+    if scope == TokenScope::Read && is_write(&request) {
+        return error_response(StatusCode::FORBIDDEN, "This token is read-only");
+    }
+    let events = state
+        .schema
+        .execute_stream(request)
+        .map(|response| Event::default().event("next").json_data(response));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Query fields with side effects: they record checkpoints, write files into
+/// the project or launch Godot. The argument, if any, is what turns them on
+const WRITE_QUERY_FIELDS: &[(&str, Option<&str>)] = &[
+    ("editorScreenshot", None),
+    ("nodeBounds", None),
+    ("projectHealth", Some("record")),
+];
+
+/// Whether the operation to run (any operation when unnamed) is a mutation or
+/// selects a query field with side effects
+fn is_write(request: &async_graphql::Request) -> bool {
+    let Ok(document) = async_graphql::parser::parse_query(&request.query) else {
+        // Left to the executor, which reports the syntax error
+        return false;
+    };
+    document.operations.iter().any(|(name, operation)| {
+        let selected = match (&request.operation_name, name) {
+            (Some(wanted), Some(name)) => wanted == name.as_str(),
+            _ => true,
+        };
+        selected
+            && (operation.node.ty == OperationType::Mutation
+                || selects_write_field(&document, &operation.node.selection_set.node, 0))
+    })
+}
+
+/// Root fields of a selection set, including those reached through fragments
+fn selects_write_field(document: &ExecutableDocument, set: &SelectionSet, depth: usize) -> bool {
+    // Fragment cycles are rejected by the executor; stop following them here
+    if depth > 16 {
+        return false;
+    }
+    set.items.iter().any(|item| match &item.node {
+        Selection::Field(field) => WRITE_QUERY_FIELDS.iter().any(|(name, argument)| {
+            field.node.name.node == *name
+                && argument.is_none_or(|argument| field.node.get_argument(argument).is_some())
+        }),
+        Selection::FragmentSpread(spread) => document
+            .fragments
+            .get(&spread.node.fragment_name.node)
+            .is_some_and(|fragment| {
+                selects_write_field(document, &fragment.node.selection_set.node, depth + 1)
+            }),
+        Selection::InlineFragment(fragment) => {
+            selects_write_field(document, &fragment.node.selection_set.node, depth + 1)
+        }
+    })
+}
+
+/// Reject requests without a valid `Authorization: Bearer` token
+async fn require_token(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    let scope = presented.and_then(|presented| {
+        state
+            .tokens
+            .iter()
+            .find(|t| constant_time_eq(t.token(), presented))
+            .map(HttpToken::scope)
+    });
+    match scope {
+        Some(scope) => {
+            let mut request = request;
+            request.extensions_mut().insert(scope);
+            next.run(request).await
+        }
+        None => {
+            let mut response =
+                error_response(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token");
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            response
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        Json(serde_json::json!({ "errors": [{ "message": message }] })),
+    )
+        .into_response()
+}
+
+fn cors_layer(origins: &[String]) -> CorsLayer {
+    let allow_origin = if origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            origins
+                .iter()
+                .filter_map(|o| HeaderValue::from_str(o).ok())
+                .collect::<Vec<_>>(),
+        )
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+}
+
+/// Compare without short-circuiting on the first differing byte
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// 128-bit hex token from the OS-seeded keys of `RandomState`
+fn generate_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    (0..2)
+        .map(|_| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+fn write_endpoint_file(project_path: &Path, url: &str, token: &str) -> anyhow::Result<()> {
+    let path = path_utils::to_fs_path_unchecked(project_path, ENDPOINT_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::json!({
+        "url": url,
+        "token": token,
+    });
+    std::fs::write(&path, serde_json::to_string_pretty(&content)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("secret", "secret"));
+        assert!(!constant_time_eq("secret", "secreT"));
+        assert!(!constant_time_eq("secret", "secret2"));
+    }
+
+    #[test]
+    fn test_is_write() {
+        let request = |query: &str| async_graphql::Request::new(query);
+        assert!(!is_write(&request("{ project { name } }")));
+        assert!(is_write(&request("mutation { saveScene { success } }")));
+        assert!(!is_write(
+            &request("query A { project { name } } mutation B { saveScene { success } }")
+                .operation_name("A")
+        ));
+        assert!(is_write(&request(
+            "query A { project { name } } mutation B { saveScene { success } }"
+        )));
+
+        // Query fields with side effects, also behind aliases and fragments
+        assert!(is_write(&request(
+            "{ health: projectHealth(record: true) { score } }"
+        )));
+        assert!(!is_write(&request("{ projectHealth { score } }")));
+        assert!(is_write(&request("{ editorScreenshot { success } }")));
+        assert!(is_write(&request(
+            "{ ...Bounds } fragment Bounds on QueryRoot { nodeBounds(path: \"res://a.tscn\") { success } }"
+        )));
+        assert!(is_write(&request(
+            "{ ... on QueryRoot { nodeBounds(path: \"res://a.tscn\") { success } } }"
+        )));
+        assert!(!is_write(&request(
+            "{ ...Project } fragment Project on QueryRoot { project { name } }"
+        )));
+    }
+
+    #[test]
+    fn test_generated_tokens_differ() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert_ne!(token, generate_token());
+    }
+}
@@ -2,8 +2,16 @@
 //!
 //! MCP server for LLM to interact with Godot projects.
 
+pub mod config;
 pub mod godot;
 pub mod graphql;
+pub mod http;
 pub mod path_utils;
+pub mod project_registry;
+pub mod replay;
+pub mod screenshot;
+pub mod telemetry;
+mod time_utils;
 pub mod tools;
+pub mod watch;
 pub mod ws;
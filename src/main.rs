@@ -7,7 +7,12 @@ mod cli;
 mod server;
 
 // Re-export from lib for internal use
+use godot_mcp_rs::config::{HttpToken, ServerConfig};
 use godot_mcp_rs::{godot, tools};
+use godot_mcp_rs::{graphql, telemetry::Telemetry};
+
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use anyhow::Result;
 use clap::Parser;
@@ -17,6 +22,9 @@ use cli::{Cli, Commands};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let config = ServerConfig::load()?;
+    let telemetry = Telemetry::init(&config.telemetry)?;
+
     // Initialize logging (output to stderr, stdout is reserved for MCP communication)
     tracing_subscriber::registry()
         .with(
@@ -24,22 +32,112 @@ async fn main() -> Result<()> {
                 .unwrap_or_else(|_| "godot_mcp_rs=info".into()),
         )
         .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(
+            telemetry
+                .as_ref()
+                .map(|t| tracing_opentelemetry::layer().with_tracer(t.tracer())),
+        )
         .init();
 
+    graphql::project_index::set_jobs(config.index.jobs);
+    graphql::gdtoolkit::set_venv(config.gdtoolkit.venv.clone());
+    graphql::asset_fetch::set_config(config.assets.clone());
+    graphql::live_resolver::set_editor_instances(config.editors.clone());
+
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
 
     // If no arguments or "serve" command, start in MCP server mode.
     if args.len() == 1 {
         tracing::info!("Godot MCP Server starting (MCP mode)...");
-        server::run().await?;
+        server::run(&config.replay, &config.index).await?;
     } else {
         // CLI mode
         let cli = Cli::parse();
+        if let Some(jobs) = cli.jobs {
+            graphql::project_index::set_jobs(jobs);
+        }
+        if let Some(godot) = cli.godot {
+            if !godot.is_file() {
+                anyhow::bail!("Godot executable not found: {}", godot.display());
+            }
+            graphql::godot_binary::set_override(Some(godot));
+        }
         match cli.command {
             Commands::Serve => {
                 tracing::info!("Godot MCP Server starting (MCP mode)...");
-                server::run().await?;
+                server::run(&config.replay, &config.index).await?;
+            }
+            Commands::Http {
+                project,
+                bind,
+                token,
+                godot_port,
+                sandbox,
+            } => {
+                let mut http = config.http.clone();
+                if let Some(bind) = bind {
+                    http.bind = bind;
+                }
+                http.tokens.extend(token.map(HttpToken::write));
+                if let Some(fixture) = sandbox {
+                    let root = PathBuf::from(graphql::vfs::SANDBOX_ROOT);
+                    let fs = graphql::vfs::MemoryFs::from_fixture_file(&root, &fixture)
+                        .map_err(anyhow::Error::msg)?;
+                    tracing::info!("Serving sandbox project from {}", fixture.display());
+                    let ctx = graphql::GqlContext::new(root)
+                        .with_port(godot_port)
+                        .with_fs(Arc::new(fs));
+                    let schema = graphql::build_schema_with_context(ctx);
+                    godot_mcp_rs::http::serve(schema, &http, None).await?;
+                } else {
+                    let ctx = graphql::GqlContext::new(project.clone()).with_port(godot_port);
+                    if config.index.warm_open_scenes {
+                        graphql::scene_warmup::spawn(ctx.clone());
+                    }
+                    let schema = graphql::build_schema_with_context(ctx);
+                    godot_mcp_rs::http::serve(schema, &http, Some(&project)).await?;
+                }
+            }
+            Commands::Replay {
+                file,
+                project,
+                sandbox,
+                keep,
+                check,
+            } => {
+                let entries = godot_mcp_rs::replay::read_replay_file(&file)?;
+                let (ctx, copy) = if let Some(fixture) = sandbox {
+                    let root = PathBuf::from(graphql::vfs::SANDBOX_ROOT);
+                    let fs = graphql::vfs::MemoryFs::from_fixture_file(&root, &fixture)
+                        .map_err(anyhow::Error::msg)?;
+                    (graphql::GqlContext::new(root).with_fs(Arc::new(fs)), None)
+                } else {
+                    let copy = godot_mcp_rs::replay::scratch_dir();
+                    godot_mcp_rs::replay::copy_project(&project, &copy)?;
+                    (graphql::GqlContext::new(copy.clone()), Some(copy))
+                };
+
+                let calls = godot_mcp_rs::replay::replay(&ctx, &entries).await;
+                let differing: Vec<_> = calls.iter().filter(|c| !c.matches).collect();
+                let report = serde_json::json!({
+                    "calls": calls.len(),
+                    "matching": calls.len() - differing.len(),
+                    "differing": differing,
+                    "project": if keep { copy.as_ref().map(|c| c.display().to_string()) } else { None },
+                });
+                println!("{}", serde_json::to_string_pretty(&report)?);
+
+                if let Some(copy) = copy.filter(|_| !keep) {
+                    let _ = std::fs::remove_dir_all(copy);
+                }
+                if check && !differing.is_empty() {
+                    anyhow::bail!(
+                        "{} of {} replayed calls differ",
+                        differing.len(),
+                        calls.len()
+                    );
+                }
             }
             Commands::Tool(tool_cmd) => {
                 cli::run_cli(tool_cmd).await?;
@@ -47,5 +145,12 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Games run with auto_stop end with the server or CLI that started them
+    graphql::run_report::stop_auto_stopped();
+
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown();
+    }
+
     Ok(())
 }
@@ -137,8 +137,19 @@ pub fn to_res_path(project_root: &Path, file_path: &Path) -> Result<String, Path
 ///
 /// This uses path canonicalization when possible to detect symlink attacks
 /// and other path tricks. For non-existent paths, it validates the existing
-/// ancestor directory.
+/// ancestor directory. A project root that is not on disk (an in-memory
+/// sandbox project) cannot contain symlinks and is compared lexically.
 pub fn validate_within_project(project_root: &Path, target: &Path) -> Result<(), PathError> {
+    if !project_root.exists() {
+        let escapes = target
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir));
+        if escapes || !target.starts_with(project_root) {
+            return Err(PathError::OutsideProject(target.display().to_string()));
+        }
+        return Ok(());
+    }
+
     // Try to canonicalize both paths
     let canonical_root = project_root.canonicalize().map_err(|e| {
         PathError::FilesystemError(format!("Cannot canonicalize project root: {}", e))
@@ -276,4 +287,15 @@ mod tests {
         // We just check it doesn't panic - result depends on filesystem state
         let _ = result;
     }
+
+    #[test]
+    fn test_validate_within_virtual_project() {
+        let root = PathBuf::from("/godot-mcp-sandbox/does-not-exist");
+        assert!(validate_within_project(&root, &root.join("scenes/main.tscn")).is_ok());
+        assert!(matches!(
+            validate_within_project(&root, Path::new("/etc/passwd")),
+            Err(PathError::OutsideProject(_))
+        ));
+        assert!(validate_within_project(&root, &root.join("a/../../x")).is_err());
+    }
 }
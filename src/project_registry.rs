@@ -0,0 +1,60 @@
+//! Project Registry
+//!
+//! Values kept per project for the life of the process (file watchers,
+//! caches, transaction layers), so that requests which each build their own
+//! context share them.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+/// Values keyed by project root, meant to be declared as a `static`
+pub struct ProjectRegistry<T> {
+    entries: OnceLock<Mutex<HashMap<PathBuf, T>>>,
+}
+
+impl<T> ProjectRegistry<T> {
+    pub const fn new() -> Self {
+        Self {
+            entries: OnceLock::new(),
+        }
+    }
+
+    /// Lock the registry, first dropping projects (e.g. temporary checkouts)
+    /// that have since been deleted
+    pub fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, T>> {
+        let mut entries = self
+            .entries
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        entries.retain(|root, _| root.exists());
+        entries
+    }
+}
+
+impl<T> Default for ProjectRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_drops_deleted_projects() {
+        static REGISTRY: ProjectRegistry<u32> = ProjectRegistry::new();
+        let kept = tempfile::tempdir().unwrap();
+        let deleted = tempfile::tempdir().unwrap();
+        REGISTRY.lock().insert(kept.path().to_path_buf(), 1);
+        REGISTRY.lock().insert(deleted.path().to_path_buf(), 2);
+
+        let deleted_root = deleted.path().to_path_buf();
+        drop(deleted);
+        let entries = REGISTRY.lock();
+        assert_eq!(entries.get(kept.path()), Some(&1));
+        assert!(!entries.contains_key(&deleted_root));
+    }
+}
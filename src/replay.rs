@@ -0,0 +1,220 @@
+//! Tool call replay
+//!
+//! With `[replay] file = "..."` in the config file, every MCP tool call is
+//! appended to that file as one JSON line holding the tool name, its
+//! arguments and the result (or error) the agent received.
+//! `godot-mcp-rs replay <file>` runs the recorded calls again, in order,
+//! against a copy of the project or an in-memory sandbox, and reports the
+//! calls whose result differs from the recording.
+
+use std::fs::{self, File, OpenOptions};
+use std::future::Future;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use anyhow::Context;
+use rmcp::{model::CallToolResult, ErrorData as McpError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::config::ReplayConfig;
+use crate::graphql::GqlContext;
+use crate::time_utils::now_millis;
+use crate::tools::gql_tools;
+
+/// One recorded tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    /// Position of the call within the recording session, from 1
+    pub seq: u64,
+    /// Unix timestamp (ms) when the call started
+    pub timestamp: u64,
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Map<String, Value>>,
+    /// The `CallToolResult` returned to the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// The error returned instead of a result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    pub duration_ms: u64,
+}
+
+/// Appends tool calls to a replay file
+pub struct ReplayRecorder {
+    file: Mutex<File>,
+    next_seq: AtomicU64,
+}
+
+static RECORDER: OnceLock<ReplayRecorder> = OnceLock::new();
+
+impl ReplayRecorder {
+    /// Open `path` for appending, creating it and its folder if needed
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open replay file {}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+            next_seq: AtomicU64::new(1),
+        })
+    }
+
+    /// Run a tool call and append it with its outcome
+    pub async fn record<F>(
+        &self,
+        tool: &str,
+        arguments: Option<Map<String, Value>>,
+        call: F,
+    ) -> Result<CallToolResult, McpError>
+    where
+        F: Future<Output = Result<CallToolResult, McpError>>,
+    {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let timestamp = now_millis();
+        let start = Instant::now();
+        let outcome = call.await;
+
+        let (result, error) = outcome_values(&outcome);
+        let entry = ReplayEntry {
+            seq,
+            timestamp,
+            tool: tool.to_string(),
+            arguments,
+            result,
+            error,
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
+        if let Err(e) = self.append(&entry) {
+            tracing::warn!("Failed to record tool call {}: {}", seq, e);
+        }
+        outcome
+    }
+
+    fn append(&self, entry: &ReplayEntry) -> anyhow::Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Start recording when `[replay].file` is set
+pub fn init(config: &ReplayConfig) -> anyhow::Result<()> {
+    if let Some(path) = &config.file {
+        let recorder = ReplayRecorder::open(path)?;
+        tracing::info!("Recording tool calls to {}", path.display());
+        let _ = RECORDER.set(recorder);
+    }
+    Ok(())
+}
+
+/// Record a tool call if recording is enabled, otherwise just run it
+pub async fn record_tool_call<F>(
+    tool: &str,
+    arguments: Option<Map<String, Value>>,
+    call: F,
+) -> Result<CallToolResult, McpError>
+where
+    F: Future<Output = Result<CallToolResult, McpError>>,
+{
+    match RECORDER.get() {
+        Some(recorder) => recorder.record(tool, arguments, call).await,
+        None => call.await,
+    }
+}
+
+/// Read a replay file; blank lines are skipped
+pub fn read_replay_file(path: &Path) -> anyhow::Result<Vec<ReplayEntry>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open replay file {}", path.display()))?;
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .with_context(|| format!("{}:{}: invalid replay entry", path.display(), index + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Outcome of one replayed call
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayedCall {
+    pub seq: u64,
+    pub tool: String,
+    /// Whether the result (or error) equals the recorded one
+    pub matches: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+
+/// Run recorded calls again, in order, against `ctx`
+pub async fn replay(ctx: &GqlContext, entries: &[ReplayEntry]) -> Vec<ReplayedCall> {
+    let mut calls = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let outcome =
+            gql_tools::call_gql_tool(ctx.clone(), &entry.tool, entry.arguments.clone()).await;
+        let (result, error) = outcome_values(&outcome);
+        calls.push(ReplayedCall {
+            seq: entry.seq,
+            tool: entry.tool.clone(),
+            matches: result == entry.result && error == entry.error,
+            result,
+            error,
+        });
+    }
+    calls
+}
+
+/// Copy a project folder (without `.godot` and `.git`) for a replay to modify
+pub fn copy_project(from: &Path, to: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(to).with_context(|| format!("Failed to create {}", to.display()))?;
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == ".godot" || name == ".git" {
+            continue;
+        }
+        let target = to.join(&name);
+        if entry.file_type()?.is_dir() {
+            copy_project(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Scratch folder for a replay's project copy
+pub fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "godot-mcp-replay-{}-{}",
+        std::process::id(),
+        now_millis()
+    ))
+}
+
+fn outcome_values(outcome: &Result<CallToolResult, McpError>) -> (Option<Value>, Option<Value>) {
+    match outcome {
+        Ok(result) => (serde_json::to_value(result).ok(), None),
+        Err(error) => (None, serde_json::to_value(error).ok()),
+    }
+}
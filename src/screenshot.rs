@@ -0,0 +1,348 @@
+//! Screenshot annotation
+//!
+//! Decodes PNG screenshots captured by the editor plugin and draws node
+//! highlight boxes and labels onto them. Labels use a built-in 5x7 bitmap
+//! font (digits, letters, a few symbols; lowercase is drawn as uppercase).
+
+use anyhow::Context;
+
+/// Scale of the label font (5x7 glyphs become 10x14 pixels)
+const FONT_SCALE: i32 = 2;
+const GLYPH_WIDTH: i32 = 5;
+const GLYPH_HEIGHT: i32 = 7;
+const LABEL_PADDING: i32 = 3;
+const BOX_THICKNESS: i32 = 3;
+
+/// Axis-aligned rectangle in image pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// RGBA8 image
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Decode a PNG of any color type into RGBA8
+    pub fn decode_png(data: &[u8]) -> anyhow::Result<Self> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().context("Invalid PNG header")?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).context("Invalid PNG data")?;
+        let buf = &buf[..info.buffer_size()];
+
+        let pixels = match info.color_type {
+            png::ColorType::Rgba => buf.to_vec(),
+            png::ColorType::Rgb => buf
+                .chunks_exact(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buf
+                .chunks_exact(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::Indexed => anyhow::bail!("Unexpanded palette PNG"),
+        };
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    pub fn encode_png(&self) -> anyhow::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(out)
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Fill a rectangle, clipped to the image
+    pub fn fill_rect(&mut self, rect: Rect, color: [u8; 3]) {
+        let x0 = rect.x.clamp(0, self.width as i32);
+        let y0 = rect.y.clamp(0, self.height as i32);
+        let x1 = (rect.x + rect.width).clamp(0, self.width as i32);
+        let y1 = (rect.y + rect.height).clamp(0, self.height as i32);
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let i = (y as usize * self.width as usize + x as usize) * 4;
+                self.pixels[i..i + 3].copy_from_slice(&color);
+                self.pixels[i + 3] = 255;
+            }
+        }
+    }
+
+    /// Outline a rectangle with the given line thickness (drawn inwards)
+    pub fn stroke_rect(&mut self, rect: Rect, color: [u8; 3], thickness: i32) {
+        let t = thickness.min(rect.width).min(rect.height).max(1);
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+        self.fill_rect(
+            Rect {
+                x,
+                y,
+                width,
+                height: t,
+            },
+            color,
+        );
+        self.fill_rect(
+            Rect {
+                x,
+                y: y + height - t,
+                width,
+                height: t,
+            },
+            color,
+        );
+        self.fill_rect(
+            Rect {
+                x,
+                y,
+                width: t,
+                height,
+            },
+            color,
+        );
+        self.fill_rect(
+            Rect {
+                x: x + width - t,
+                y,
+                width: t,
+                height,
+            },
+            color,
+        );
+    }
+
+    /// Draw text with its top-left corner at (x, y)
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: [u8; 3]) {
+        for (i, c) in text.chars().enumerate() {
+            let origin = x + i as i32 * (GLYPH_WIDTH + 1) * FONT_SCALE;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        self.fill_rect(
+                            Rect {
+                                x: origin + col * FONT_SCALE,
+                                y: y + row as i32 * FONT_SCALE,
+                                width: FONT_SCALE,
+                                height: FONT_SCALE,
+                            },
+                            color,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Highlight a rectangle and attach a label badge above it (or inside
+    /// when there is no room above). `outline` separates the box from
+    /// backgrounds of the same hue.
+    pub fn annotate(&mut self, rect: Rect, label: &str, color: [u8; 3], outline: [u8; 3]) {
+        self.stroke_rect(
+            Rect {
+                x: rect.x - 1,
+                y: rect.y - 1,
+                width: rect.width + 2,
+                height: rect.height + 2,
+            },
+            outline,
+            1,
+        );
+        self.stroke_rect(rect, color, BOX_THICKNESS);
+
+        if label.is_empty() {
+            return;
+        }
+        let (text_width, text_height) = text_size(label);
+        let badge_height = text_height + LABEL_PADDING * 2;
+        let badge = Rect {
+            x: rect.x.clamp(0, (self.width as i32 - text_width).max(0)),
+            y: if rect.y >= badge_height {
+                rect.y - badge_height
+            } else {
+                rect.y.max(0)
+            },
+            width: text_width + LABEL_PADDING * 2,
+            height: badge_height,
+        };
+        self.fill_rect(badge, color);
+        self.draw_text(
+            badge.x + LABEL_PADDING,
+            badge.y + LABEL_PADDING,
+            label,
+            contrast_color(color),
+        );
+    }
+}
+
+/// Pixel size of a label
+pub fn text_size(text: &str) -> (i32, i32) {
+    let chars = text.chars().count() as i32;
+    let width = if chars == 0 {
+        0
+    } else {
+        (chars * (GLYPH_WIDTH + 1) - 1) * FONT_SCALE
+    };
+    (width, GLYPH_HEIGHT * FONT_SCALE)
+}
+
+/// Parse `#rrggbb` / `rrggbb`
+pub fn parse_hex_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Black or white, whichever reads better on `color`
+pub fn contrast_color(color: [u8; 3]) -> [u8; 3] {
+    let [r, g, b] = color.map(f32::from);
+    if 0.299 * r + 0.587 * g + 0.114 * b > 150.0 {
+        [0, 0, 0]
+    } else {
+        [255, 255, 255]
+    }
+}
+
+/// 5x7 glyph rows, most significant of the low 5 bits leftmost
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0; 7],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' => [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        ',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+        ':' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '(' => [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02],
+        ')' => [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08],
+        '!' => [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04],
+        _ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_round_trip_and_annotate() {
+        let mut canvas = Canvas::new(64, 48);
+        canvas.fill_rect(
+            Rect {
+                x: 0,
+                y: 0,
+                width: 64,
+                height: 48,
+            },
+            [40, 40, 40],
+        );
+        let rect = Rect {
+            x: 10,
+            y: 30,
+            width: 20,
+            height: 10,
+        };
+        canvas.annotate(rect, "A1", [255, 200, 0], [0, 0, 0]);
+
+        let decoded = Canvas::decode_png(&canvas.encode_png().unwrap()).unwrap();
+        assert_eq!((decoded.width, decoded.height), (64, 48));
+        // Box edge, interior and outer outline
+        assert_eq!(decoded.pixel(10, 35), [255, 200, 0, 255]);
+        assert_eq!(decoded.pixel(20, 35), [40, 40, 40, 255]);
+        assert_eq!(decoded.pixel(9, 35), [0, 0, 0, 255]);
+        // Badge sits above the box
+        assert_eq!(decoded.pixel(11, 12), [255, 200, 0, 255]);
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#ff8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex_color("00FF00"), Some([0, 255, 0]));
+        assert_eq!(parse_hex_color("#fff"), None);
+    }
+
+    #[test]
+    fn test_contrast_color() {
+        assert_eq!(contrast_color([255, 255, 0]), [0, 0, 0]);
+        assert_eq!(contrast_color([40, 60, 160]), [255, 255, 255]);
+    }
+}
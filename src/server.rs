@@ -1,12 +1,21 @@
 //! MCP Server implementation
 
+use std::path::PathBuf;
+
 use crate::tools::GodotTools;
 use anyhow::Result;
+use godot_mcp_rs::config::{IndexConfig, ReplayConfig};
+use godot_mcp_rs::graphql::{scene_warmup, GqlContext};
 use rmcp::{transport::stdio, ServiceExt};
 
-/// Start the MCP server
-pub async fn run() -> Result<()> {
+/// Start the MCP server, recording tool calls when `[replay].file` is set
+pub async fn run(replay: &ReplayConfig, index: &IndexConfig) -> Result<()> {
+    godot_mcp_rs::replay::init(replay)?;
     let tools = GodotTools::new();
+    if index.warm_open_scenes {
+        // Tools run on the working directory's project
+        scene_warmup::spawn(GqlContext::new(PathBuf::from(".")));
+    }
 
     let transport = stdio();
 
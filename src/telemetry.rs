@@ -0,0 +1,224 @@
+//! Telemetry
+//!
+//! Optional OTLP export configured by the `[telemetry]` section of the config
+//! file. Every MCP tool call becomes a span carrying the GraphQL operation,
+//! the number of files written and its duration, and feeds the call count,
+//! duration and files-written metrics. Without an exporter the spans only
+//! reach the regular log output.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use opentelemetry::metrics::{Counter, Histogram};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use opentelemetry_sdk::Resource;
+use tracing::field::Empty;
+use tracing::Instrument;
+
+use crate::config::TelemetryConfig;
+
+/// Instrumentation scope of the exported spans and metrics
+const SCOPE: &str = "godot-mcp-rs";
+
+tokio::task_local! {
+    static FILES_WRITTEN: Cell<u64>;
+}
+
+/// Installed OTLP pipeline; flush it with `shutdown` before exiting
+pub struct Telemetry {
+    tracer_provider: SdkTracerProvider,
+    meter_provider: Option<SdkMeterProvider>,
+}
+
+impl Telemetry {
+    /// Build the exporters; returns `None` when telemetry is disabled
+    pub fn init(config: &TelemetryConfig) -> anyhow::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let resource = Resource::builder()
+            .with_service_name(config.service_name.clone())
+            .build();
+
+        let mut span_exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_headers(config.headers.clone());
+        if let Some(endpoint) = &config.endpoint {
+            span_exporter = span_exporter.with_endpoint(signal_endpoint(endpoint, "traces"));
+        }
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter.build()?)
+            .with_resource(resource.clone())
+            .build();
+
+        let meter_provider = if config.metrics {
+            let mut metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+                .with_http()
+                .with_headers(config.headers.clone());
+            if let Some(endpoint) = &config.endpoint {
+                metric_exporter =
+                    metric_exporter.with_endpoint(signal_endpoint(endpoint, "metrics"));
+            }
+            let provider = SdkMeterProvider::builder()
+                .with_periodic_exporter(metric_exporter.build()?)
+                .with_resource(resource)
+                .build();
+            opentelemetry::global::set_meter_provider(provider.clone());
+            Some(provider)
+        } else {
+            None
+        };
+
+        Ok(Some(Self {
+            tracer_provider,
+            meter_provider,
+        }))
+    }
+
+    /// Tracer for the `tracing-opentelemetry` layer
+    pub fn tracer(&self) -> Tracer {
+        self.tracer_provider.tracer(SCOPE)
+    }
+
+    /// Flush pending spans and metrics
+    pub fn shutdown(&self) {
+        if let Err(e) = self.tracer_provider.shutdown() {
+            eprintln!("Failed to flush traces: {}", e);
+        }
+        if let Some(provider) = &self.meter_provider {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush metrics: {}", e);
+            }
+        }
+    }
+}
+
+struct ToolMetrics {
+    calls: Counter<u64>,
+    duration: Histogram<f64>,
+    files_written: Counter<u64>,
+}
+
+/// Instruments are created on first use, after the meter provider is installed
+fn metrics() -> &'static ToolMetrics {
+    static METRICS: OnceLock<ToolMetrics> = OnceLock::new();
+    METRICS.get_or_init(|| {
+        let meter = opentelemetry::global::meter(SCOPE);
+        ToolMetrics {
+            calls: meter
+                .u64_counter("godot_mcp.tool.calls")
+                .with_description("MCP tool calls")
+                .build(),
+            duration: meter
+                .f64_histogram("godot_mcp.tool.duration")
+                .with_unit("ms")
+                .with_description("MCP tool call duration")
+                .build(),
+            files_written: meter
+                .u64_counter("godot_mcp.files.written")
+                .with_description("Project files written by tool calls")
+                .build(),
+        }
+    })
+}
+
+/// Run a tool call inside its span and record the call metrics
+pub async fn instrument_tool_call<F, T, E>(tool: &str, call: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let span = tracing::info_span!(
+        "tool_call",
+        otel.name = %format!("tool {}", tool),
+        otel.status_code = Empty,
+        mcp.tool = tool,
+        graphql.operation.type = Empty,
+        graphql.operation.name = Empty,
+        godot.files_written = Empty,
+        duration_ms = Empty,
+    );
+
+    let start = Instant::now();
+    let (result, files_written) = FILES_WRITTEN
+        .scope(Cell::new(0), async {
+            let result = call.await;
+            (result, FILES_WRITTEN.with(Cell::get))
+        })
+        .instrument(span.clone())
+        .await;
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    span.record("godot.files_written", files_written);
+    span.record("duration_ms", duration_ms);
+    if result.is_err() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    let attributes = [
+        KeyValue::new("tool", tool.to_string()),
+        KeyValue::new("success", result.is_ok()),
+    ];
+    let metrics = metrics();
+    metrics.calls.add(1, &attributes);
+    metrics.duration.record(duration_ms, &attributes);
+    if files_written > 0 {
+        metrics
+            .files_written
+            .add(files_written, &[KeyValue::new("tool", tool.to_string())]);
+    }
+
+    result
+}
+
+/// Attach the GraphQL operation to the current tool call span
+pub fn record_graphql_operation(operation_type: &str, operation_name: Option<&str>) {
+    let span = tracing::Span::current();
+    span.record("graphql.operation.type", operation_type);
+    if let Some(name) = operation_name {
+        span.record("graphql.operation.name", name);
+    }
+}
+
+/// Count project files written by the current tool call (no-op outside one)
+pub fn record_files_written(count: u64) {
+    let _ = FILES_WRITTEN.try_with(|files| files.set(files.get() + count));
+}
+
+/// Per-signal OTLP/HTTP endpoint under a collector base URL
+fn signal_endpoint(base: &str, signal: &str) -> String {
+    format!("{}/v1/{}", base.trim_end_matches('/'), signal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_files_written_scoped_to_tool_call() {
+        // Outside a tool call this is a no-op
+        record_files_written(1);
+
+        let result: Result<u64, ()> = instrument_tool_call("godot_mutate", async {
+            record_files_written(2);
+            record_files_written(1);
+            Ok(FILES_WRITTEN.with(Cell::get))
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn test_signal_endpoint() {
+        assert_eq!(
+            signal_endpoint("http://localhost:4318/", "traces"),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+}
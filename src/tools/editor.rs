@@ -2,9 +2,11 @@
 
 use rmcp::{model::CallToolResult, model::Content, ErrorData as McpError};
 use std::fs;
-use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::graphql::{godot_binary, run_report};
 
 use super::{
     GetDebugOutputRequest, GetGodotVersionRequest, GetRunningStatusRequest, GodotTools,
@@ -13,7 +15,7 @@ use super::{
 
 /// Get the PID file path
 fn get_pid_file_path(project_root: &std::path::Path) -> PathBuf {
-    project_root.join(".godot_mcp_pid")
+    project_root.join(run_report::PID_FILE)
 }
 
 impl GodotTools {
@@ -26,65 +28,11 @@ impl GodotTools {
             }
         }
 
-        // 2. Environment variable GODOT_PATH
-        if let Ok(env_path) = std::env::var("GODOT_PATH") {
-            let path = PathBuf::from(&env_path);
-            if path.exists() {
-                return Ok(path);
-            }
-        }
-
-        // 3. Search in PATH (Windows)
-        #[cfg(windows)]
-        {
-            if let Ok(output) = Command::new("where").arg("godot").output() {
-                if output.status.success() {
-                    if let Ok(path_str) = String::from_utf8(output.stdout) {
-                        if let Some(first_line) = path_str.lines().next() {
-                            let path = PathBuf::from(first_line.trim());
-                            if path.exists() {
-                                return Ok(path);
-                            }
-                        }
-                    }
-                }
-            }
-            // Also try godot4
-            if let Ok(output) = Command::new("where").arg("godot4").output() {
-                if output.status.success() {
-                    if let Ok(path_str) = String::from_utf8(output.stdout) {
-                        if let Some(first_line) = path_str.lines().next() {
-                            let path = PathBuf::from(first_line.trim());
-                            if path.exists() {
-                                return Ok(path);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // 4. Default paths (Windows)
-        #[cfg(windows)]
-        {
-            let default_paths = [
-                r"C:\Program Files\Godot\Godot.exe",
-                r"C:\Program Files (x86)\Godot\Godot.exe",
-                r"C:\Godot\Godot.exe",
-            ];
-            for path_str in &default_paths {
-                let path = PathBuf::from(path_str);
-                if path.exists() {
-                    return Ok(path);
-                }
-            }
-        }
-
-        Err(McpError::internal_error(
-            "Godot executable not found. Set GODOT_PATH environment variable or add Godot to PATH."
-                .to_string(),
-            None,
-        ))
+        // 2. Session override, environment, PATH, install locations
+        //    (honoring the project's .godot-version)
+        godot_binary::discover(Some(self.get_base_path()))
+            .map(|binary| binary.path)
+            .map_err(|message| McpError::internal_error(message, None))
     }
 
     /// get_godot_version - Get Godot version
@@ -127,101 +75,40 @@ impl GodotTools {
 
         let godot_path = self.resolve_godot_path()?;
         let project_root = self.get_base_path();
-        let pid_file = get_pid_file_path(project_root);
-
-        // If already running, stop it first (auto-restart behavior)
-        let mut stopped_previous = false;
-        if pid_file.exists() {
-            if let Ok(pid_str) = fs::read_to_string(&pid_file) {
-                if let Ok(pid) = pid_str.trim().parse::<u32>() {
-                    // Check if process still exists
-                    #[cfg(windows)]
-                    {
-                        let check = Command::new("tasklist")
-                            .args(["/FI", &format!("PID eq {}", pid)])
-                            .output();
-                        if let Ok(out) = check {
-                            let output_str = String::from_utf8_lossy(&out.stdout);
-                            if output_str.contains(&pid.to_string()) {
-                                // Stop the running project automatically
-                                let _ = Command::new("taskkill")
-                                    .args(["/PID", &pid.to_string(), "/F"])
-                                    .output();
-                                stopped_previous = true;
-                                // Brief wait for process to terminate
-                                std::thread::sleep(std::time::Duration::from_millis(500));
-                            }
-                        }
-                    }
-                    // Clean up PID file
-                    fs::remove_file(&pid_file).ok();
-                }
-            }
-        }
 
-        let mut cmd = Command::new(&godot_path);
-        cmd.arg("--path").arg(project_root);
-
-        if let Some(ref scene) = req.scene {
-            let scene_path = scene.strip_prefix("res://").unwrap_or(scene);
-            cmd.arg(scene_path);
-        }
-
-        cmd.arg("--debug");
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| McpError::internal_error(format!("Failed to start Godot: {}", e), None))?;
-
-        let pid = child.id();
-
-        // Save PID to file
-        fs::write(&pid_file, pid.to_string())
-            .map_err(|e| McpError::internal_error(format!("Failed to save PID: {}", e), None))?;
-
-        // Create output capture file
-        let output_file = project_root.join(".godot_mcp_output");
-        fs::write(&output_file, "").ok();
-
-        // Start thread to asynchronously write output to file
-        let output_file_clone = output_file.clone();
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
-
-        std::thread::spawn(move || {
-            let mut all_output = String::new();
-
-            if let Some(stdout) = stdout {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().filter_map(|l| l.ok()) {
-                    all_output.push_str(&line);
-                    all_output.push('\n');
-                    fs::write(&output_file_clone, &all_output).ok();
-                }
-            }
-            if let Some(stderr) = stderr {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().filter_map(|l| l.ok()) {
-                    all_output.push_str("ERROR: ");
-                    all_output.push_str(&line);
-                    all_output.push('\n');
-                    fs::write(&output_file_clone, &all_output).ok();
-                }
-            }
-        });
+        // A game still running is stopped first (auto-restart behavior)
+        let limits = run_report::RunLimits {
+            timeout: req.timeout_seconds.map(Duration::from_secs),
+            auto_stop: req.auto_stop.unwrap_or(false),
+        };
+        let run = run_report::start(&godot_path, project_root, req.scene.clone(), limits)
+            .map_err(|e| McpError::internal_error(e, None))?;
+        let (pid, stopped_previous) = (run.pid, run.restarted);
 
         let message = if stopped_previous {
-            format!("Stopped previous instance and started project{}", req.scene.as_ref().map(|s| format!(" with scene: {}", s)).unwrap_or_default())
+            format!(
+                "Stopped previous instance and started project{}",
+                req.scene
+                    .as_ref()
+                    .map(|s| format!(" with scene: {}", s))
+                    .unwrap_or_default()
+            )
         } else {
-            format!("Project started{}", req.scene.as_ref().map(|s| format!(" with scene: {}", s)).unwrap_or_default())
+            format!(
+                "Project started{}",
+                req.scene
+                    .as_ref()
+                    .map(|s| format!(" with scene: {}", s))
+                    .unwrap_or_default()
+            )
         };
 
         let result = serde_json::json!({
             "success": true,
             "pid": pid,
             "restarted": stopped_previous,
+            "timeout_seconds": req.timeout_seconds,
+            "auto_stop": limits.auto_stop,
             "message": message,
         });
 
@@ -256,20 +143,16 @@ impl GodotTools {
             .parse()
             .map_err(|_| McpError::internal_error("Invalid PID in file".to_string(), None))?;
 
+        // Delete PID file first, so the run is recorded as stopped rather than crashed
+        fs::remove_file(&pid_file).ok();
+
         // Terminate process
-        #[cfg(windows)]
-        {
-            Command::new("taskkill")
-                .args(["/PID", &pid.to_string(), "/F"])
-                .output()
-                .map_err(|e| {
-                    McpError::internal_error(format!("Failed to kill process: {}", e), None)
-                })?;
+        if run_report::is_running(pid) {
+            run_report::kill(pid).map_err(|e| {
+                McpError::internal_error(format!("Failed to kill process: {}", e), None)
+            })?;
         }
 
-        // Delete PID file
-        fs::remove_file(&pid_file).ok();
-
         let result = serde_json::json!({
             "success": true,
             "pid": pid,
@@ -294,7 +177,7 @@ impl GodotTools {
         };
 
         let project_root = self.get_base_path();
-        let output_file = project_root.join(".godot_mcp_output");
+        let output_file = project_root.join(run_report::OUTPUT_FILE);
         let pid_file = get_pid_file_path(project_root);
 
         let running = pid_file.exists();
@@ -394,26 +277,27 @@ impl GodotTools {
         let pid: u32 = pid_str.trim().parse().unwrap_or(0);
 
         // Check if process still exists
-        let mut still_running = false;
-        #[cfg(windows)]
-        {
-            if let Ok(out) = Command::new("tasklist")
-                .args(["/FI", &format!("PID eq {}", pid)])
-                .output()
-            {
-                let output_str = String::from_utf8_lossy(&out.stdout);
-                still_running = output_str.contains(&pid.to_string());
-            }
-        }
+        let usage = run_report::process_usage(pid);
+        let still_running = usage.is_some();
 
         if !still_running {
             fs::remove_file(&pid_file).ok();
         }
 
-        let result = serde_json::json!({
+        let mut result = serde_json::json!({
             "running": still_running,
             "pid": if still_running { Some(pid) } else { None },
         });
+        if let Some(usage) = usage {
+            result["cpu_percent"] = serde_json::json!(usage.cpu_percent);
+            result["memory_bytes"] = serde_json::json!(usage.memory_bytes);
+        }
+        if let Some(record) = run_report::read(project_root, 0).map(|report| report.record) {
+            if still_running && record.pid == pid {
+                result["timeout_seconds"] = serde_json::json!(record.timeout_seconds);
+                result["auto_stop"] = serde_json::json!(record.auto_stop);
+            }
+        }
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&result).unwrap_or_default(),
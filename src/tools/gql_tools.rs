@@ -12,6 +12,7 @@ use std::path::Path;
 use std::sync::OnceLock;
 
 use crate::graphql::{build_schema, GqlContext, GqlSchema};
+use crate::telemetry;
 
 /// Global cached schema instance
 static SCHEMA: OnceLock<GqlSchema> = OnceLock::new();
@@ -46,36 +47,43 @@ pub struct GqlIntrospectRequest {
     pub format: Option<String>,
 }
 
+/// Run one of the GraphQL tools against `ctx`
+///
+/// The MCP server uses a context on the project root; replays use a copy of
+/// the project or an in-memory sandbox.
+pub async fn call_gql_tool(
+    ctx: GqlContext,
+    name: &str,
+    args: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<CallToolResult, McpError> {
+    match name {
+        "godot_query" => {
+            let request: GqlQueryRequest = parse_request(args)?;
+            Ok(execute_document(ctx, &request.query, request.variables).await)
+        }
+        "godot_mutate" => {
+            let request: GqlMutateRequest = parse_request(args)?;
+            Ok(execute_document(ctx, &request.mutation, request.variables).await)
+        }
+        "godot_introspect" => handle_godot_introspect(&ctx.project_path, args).await,
+        _ => Err(McpError::invalid_request(
+            format!("Unknown tool: {}", name),
+            None,
+        )),
+    }
+}
+
 /// Execute a GraphQL query
 pub async fn handle_godot_query(
     base_path: &Path,
     args: Option<serde_json::Map<String, serde_json::Value>>,
 ) -> Result<CallToolResult, McpError> {
-    let request: GqlQueryRequest = match args {
-        Some(map) => serde_json::from_value(serde_json::Value::Object(map)).map_err(|e| {
-            McpError::invalid_params(format!("Invalid request parameters: {}", e), None)
-        })?,
-        None => return Err(McpError::invalid_params("Missing request parameters", None)),
-    };
-
-    let schema = get_schema();
-    let ctx = GqlContext::new(base_path.to_path_buf());
-
-    // Build request with context and optional variables
-    let mut gql_request = async_graphql::Request::new(&request.query).data(ctx);
-    if let Some(vars) = request.variables {
-        gql_request = gql_request.variables(async_graphql::Variables::from_json(vars));
-    }
-
-    let response = schema.execute(gql_request).await;
-
-    // Serialize the response
-    let response_json = serde_json::to_string_pretty(&response)
-        .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize response: {}\"}}", e));
-
-    Ok(CallToolResult::success(vec![rmcp::model::Content::text(
-        response_json,
-    )]))
+    call_gql_tool(
+        GqlContext::new(base_path.to_path_buf()),
+        "godot_query",
+        args,
+    )
+    .await
 }
 
 /// Execute a GraphQL mutation
@@ -83,19 +91,38 @@ pub async fn handle_godot_mutate(
     base_path: &Path,
     args: Option<serde_json::Map<String, serde_json::Value>>,
 ) -> Result<CallToolResult, McpError> {
-    let request: GqlMutateRequest = match args {
+    call_gql_tool(
+        GqlContext::new(base_path.to_path_buf()),
+        "godot_mutate",
+        args,
+    )
+    .await
+}
+
+fn parse_request<T: serde::de::DeserializeOwned>(
+    args: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<T, McpError> {
+    match args {
         Some(map) => serde_json::from_value(serde_json::Value::Object(map)).map_err(|e| {
             McpError::invalid_params(format!("Invalid request parameters: {}", e), None)
-        })?,
-        None => return Err(McpError::invalid_params("Missing request parameters", None)),
-    };
+        }),
+        None => Err(McpError::invalid_params("Missing request parameters", None)),
+    }
+}
 
+/// Execute a query or mutation document and return the response as JSON text
+async fn execute_document(
+    ctx: GqlContext,
+    document: &str,
+    variables: Option<serde_json::Value>,
+) -> CallToolResult {
     let schema = get_schema();
-    let ctx = GqlContext::new(base_path.to_path_buf());
+    record_operation(document);
+    let attachments = ctx.clone();
 
     // Build request with context and optional variables
-    let mut gql_request = async_graphql::Request::new(&request.mutation).data(ctx);
-    if let Some(vars) = request.variables {
+    let mut gql_request = async_graphql::Request::new(document).data(ctx);
+    if let Some(vars) = variables {
         gql_request = gql_request.variables(async_graphql::Variables::from_json(vars));
     }
 
@@ -105,9 +132,34 @@ pub async fn handle_godot_mutate(
     let response_json = serde_json::to_string_pretty(&response)
         .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize response: {}\"}}", e));
 
-    Ok(CallToolResult::success(vec![rmcp::model::Content::text(
-        response_json,
-    )]))
+    // Images attached by resolvers (e.g. captureViewport) follow the JSON
+    let mut content = vec![rmcp::model::Content::text(response_json)];
+    content.extend(
+        attachments
+            .take_images()
+            .into_iter()
+            .map(|image| rmcp::model::Content::image(image.data_base64, image.mime_type)),
+    );
+    CallToolResult::success(content)
+}
+
+/// Attach the operation type and name of a GraphQL document to the tool call span
+fn record_operation(document: &str) {
+    use async_graphql::parser::types::DocumentOperations;
+
+    let Ok(doc) = async_graphql::parser::parse_query(document) else {
+        return;
+    };
+    match &doc.operations {
+        DocumentOperations::Single(op) => {
+            telemetry::record_graphql_operation(&op.node.ty.to_string(), None)
+        }
+        DocumentOperations::Multiple(ops) => {
+            if let Some((name, op)) = ops.iter().next() {
+                telemetry::record_graphql_operation(&op.node.ty.to_string(), Some(name.as_str()));
+            }
+        }
+    }
 }
 
 /// Get the GraphQL schema
@@ -142,6 +194,8 @@ pub async fn handle_godot_introspect(
                             fields(includeDeprecated: true) {
                                 name
                                 description
+                                isDeprecated
+                                deprecationReason
                                 args {
                                     name
                                     description
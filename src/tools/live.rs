@@ -45,21 +45,26 @@ impl GodotTools {
         self.execute_live_http(base_port, &command).await
     }
 
-    /// Execute command via WebSocket
+    /// Execute command via the shared WebSocket channel
     async fn execute_live_ws(
         &self,
         port: u16,
         command: &GodotCommand,
     ) -> Result<CallToolResult, McpError> {
-        use crate::ws::WsClient;
+        use crate::ws::LiveChannel;
 
-        let client = WsClient::new(port);
-        let response = client
-            .send_command(command)
+        let command = serde_json::to_value(command)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let response = LiveChannel::shared(port)
+            .request(&command, std::time::Duration::from_secs(5))
             .await
             .map_err(|e| McpError::internal_error(format!("WebSocket error: {}", e), None))?;
 
-        Ok(CallToolResult::success(vec![Content::text(response)]))
+        let text = match response {
+            serde_json::Value::String(text) => text,
+            value => value.to_string(),
+        };
+        Ok(CallToolResult::success(vec![Content::text(text)]))
     }
 
     /// Execute command via HTTP (legacy fallback)
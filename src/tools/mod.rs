@@ -4,14 +4,20 @@ mod editor;
 pub mod gql_tools;
 mod live;
 mod project;
+mod prompts;
 mod resource;
+mod resources;
 mod scene;
 mod script;
 
 use gql_tools::{GqlIntrospectRequest, GqlMutateRequest, GqlQueryRequest};
 
 use rmcp::{
-    model::{CallToolRequestParam, CallToolResult, ListToolsResult, PaginatedRequestParam, Tool},
+    model::{
+        CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult,
+        ListPromptsResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+        ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo, Tool,
+    },
     service::{RequestContext, RoleServer},
     ErrorData as McpError, ServerHandler,
 };
@@ -78,6 +84,28 @@ pub struct RemoveNodeRequest {
     pub node_path: String,
 }
 
+/// Request to rename a node
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct RenameNodeRequest {
+    /// Scene file path
+    pub scene_path: String,
+    /// Path of the node to rename
+    pub node_path: String,
+    /// New node name
+    pub new_name: String,
+}
+
+/// Request to move a node under a different parent
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct ReparentNodeRequest {
+    /// Scene file path
+    pub scene_path: String,
+    /// Path of the node to move
+    pub node_path: String,
+    /// New parent node path ("." for root)
+    pub new_parent: String,
+}
+
 /// Request to set a node property
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct SetNodePropertyRequest {
@@ -96,6 +124,32 @@ pub struct SetNodePropertyRequest {
 pub struct GetNodeTreeRequest {
     /// Scene file path
     pub path: String,
+    /// Output style (default: indent)
+    #[serde(default)]
+    pub style: NodeTreeStyle,
+    /// Show node types (default: true)
+    pub include_types: Option<bool>,
+    /// Show attached scripts (default: false)
+    pub include_scripts: Option<bool>,
+    /// Show groups (default: false)
+    pub include_groups: Option<bool>,
+    /// Levels shown below the subtree root (default: all)
+    pub max_depth: Option<u32>,
+    /// Node path to start from ("." for the scene root, default)
+    pub root: Option<String>,
+}
+
+/// Rendering of get_node_tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeTreeStyle {
+    /// Two spaces per level
+    #[default]
+    Indent,
+    /// Box-drawing tree (├── └──)
+    Ascii,
+    /// Nested Markdown list
+    Markdown,
 }
 
 /// Request to create a script
@@ -385,6 +439,10 @@ pub struct GetGodotVersionRequest {}
 pub struct RunProjectRequest {
     /// Scene to run (optional, default is main scene)
     pub scene: Option<String>,
+    /// Kill the game after this many seconds (optional, default no limit)
+    pub timeout_seconds: Option<u64>,
+    /// Stop the game when the server or CLI that started it exits (optional, default false)
+    pub auto_stop: Option<bool>,
 }
 
 /// Request to stop the project
@@ -719,6 +777,17 @@ impl GodotTools {
 // ============================================================
 
 impl ServerHandler for GodotTools {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
+            ..Default::default()
+        }
+    }
+
     fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
@@ -757,7 +826,9 @@ impl ServerHandler for GodotTools {
         request: CallToolRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> impl Future<Output = Result<CallToolResult, McpError>> + Send + '_ {
-        async move {
+        let tool = request.name.to_string();
+        let arguments = request.arguments.clone();
+        let call = async move {
             match request.name.as_ref() {
                 // GraphQL Tools
                 "godot_query" => {
@@ -778,6 +849,50 @@ impl ServerHandler for GodotTools {
                     None,
                 )),
             }
+        };
+        async move {
+            let call = crate::replay::record_tool_call(&tool, arguments, call);
+            crate::telemetry::instrument_tool_call(&tool, call).await
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: self.list_project_resources(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        self.read_project_resource(&request.uri)
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: self.list_workflow_prompts(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        self.get_workflow_prompt(&request.name, request.arguments.as_ref())
+    }
 }
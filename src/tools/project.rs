@@ -8,6 +8,7 @@ use super::{
     SearchInProjectRequest, ValidateProjectRequest,
 };
 use crate::godot::tscn::GodotScene;
+use crate::graphql::project_index::{parallel_map, ProjectIndex};
 
 impl GodotTools {
     /// list_project_files - List project files
@@ -279,14 +280,15 @@ impl GodotTools {
             files
         }
 
-        for file_path in find_tscn_files_stat(base) {
-            if let Ok(content) = std::fs::read_to_string(&file_path) {
-                if let Ok(scene) = GodotScene::parse(&content) {
-                    total_nodes += scene.nodes.len();
-                    for node in &scene.nodes {
-                        *node_type_counts.entry(node.node_type.clone()).or_insert(0) += 1;
-                    }
-                }
+        let index = ProjectIndex::shared(base);
+        let scene_files = find_tscn_files_stat(base);
+        for scene in parallel_map(&scene_files, |path| index.scene(path))
+            .into_iter()
+            .flatten()
+        {
+            total_nodes += scene.nodes.len();
+            for node in &scene.nodes {
+                *node_type_counts.entry(node.node_type.clone()).or_insert(0) += 1;
             }
         }
 
@@ -0,0 +1,204 @@
+//! MCP Prompts - Canned Godot workflows
+//!
+//! Each prompt expands into a user message that walks the agent through a
+//! sequence of `godot_query` / `godot_mutate` calls, with the GraphQL
+//! documents already filled in from the prompt arguments.
+
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+use rmcp::ErrorData as McpError;
+
+use super::GodotTools;
+
+/// A workflow prompt and its arguments as (name, description, required, default)
+struct Workflow {
+    name: &'static str,
+    description: &'static str,
+    arguments: &'static [(&'static str, &'static str, bool, Option<&'static str>)],
+    /// Message text; `{argument}` placeholders are replaced by argument values
+    template: &'static str,
+}
+
+const WORKFLOWS: &[Workflow] = &[
+    Workflow {
+        name: "create_3d_player_controller",
+        description: "Create a CharacterBody3D player scene with a movement script and WASD/jump input actions",
+        arguments: &[
+            (
+                "scene_path",
+                "Scene to create",
+                false,
+                Some("res://scenes/player.tscn"),
+            ),
+            (
+                "script_path",
+                "Script to create",
+                false,
+                Some("res://scripts/player.gd"),
+            ),
+        ],
+        template: r#"Create a 3D player controller in this Godot project. Run these steps in order and stop to report if one fails.
+
+1. Create the scene with godot_mutate:
+mutation { createScene(input: {path: "{scene_path}", rootName: "Player", rootType: "CharacterBody3D"}) { success message } }
+
+2. Create the script with godot_mutate:
+mutation { createScript(input: {path: "{script_path}", extends: "CharacterBody3D", className: "Player"}) { success message } }
+
+3. Register the input actions with godot_mutate (skip any that `project` already lists):
+mutation {
+  forward: addInputAction(input: {actionName: "move_forward", events: [{type: KEY, key: "W"}]}) { success message }
+  back: addInputAction(input: {actionName: "move_back", events: [{type: KEY, key: "S"}]}) { success message }
+  left: addInputAction(input: {actionName: "move_left", events: [{type: KEY, key: "A"}]}) { success message }
+  right: addInputAction(input: {actionName: "move_right", events: [{type: KEY, key: "D"}]}) { success message }
+  jump: addInputAction(input: {actionName: "jump", events: [{type: KEY, key: "Space"}]}) { success message }
+}
+
+4. Generate the movement handler with godot_mutate:
+mutation { generateInputHandler(input: {scriptPath: "{script_path}", actions: ["move_forward", "move_back", "move_left", "move_right", "jump"], handlerType: PHYSICS_PROCESS}) { success path message } }
+Then read the script with `query { script(path: "{script_path}") { functions { name } } }` and fill in velocity, gravity and move_and_slide() where the generated handler leaves TODOs.
+
+5. If the Godot editor is running with the plugin, add the child nodes and attach the script with godot_mutate:
+mutation { openScene(path: "{scene_path}") { success message } }
+mutation {
+  shape: addNode(input: {parent: ".", name: "CollisionShape3D", type: "CollisionShape3D"}) { success message }
+  mesh: addNode(input: {parent: ".", name: "MeshInstance3D", type: "MeshInstance3D"}) { success message }
+  camera: addNode(input: {parent: ".", name: "Camera3D", type: "Camera3D"}) { success message }
+  script: setProperty(input: {nodePath: ".", property: "script", value: "load(\"{script_path}\")"}) { success message }
+  save: saveScene { success message }
+}
+Otherwise, tell the user which nodes to add (CollisionShape3D, MeshInstance3D, Camera3D) and that the script must be attached to the root.
+
+6. Verify with godot_query:
+query {
+  scene(path: "{scene_path}") { root { name type children { name type } script { path } } }
+  parseErrors(scriptPath: "{script_path}") { line message severity }
+}"#,
+    },
+    Workflow {
+        name: "debug_failing_scene",
+        description: "Diagnose a scene that fails to load or run: structure, scripts, dependencies and runtime errors",
+        arguments: &[(
+            "scene_path",
+            "Scene that fails (res://...)",
+            true,
+            None,
+        )],
+        template: r#"The scene {scene_path} is failing. Find the cause before changing anything. Run queries with godot_query and mutations with godot_mutate.
+
+1. Check the file structure (repairScene without fixes only reports and writes nothing):
+mutation { repairScene(path: "{scene_path}") { issues { kind nodePath message } } }
+
+2. Load the scene and its attached scripts:
+query { scene(path: "{scene_path}") { allNodes { path type script { path } } externalResources { id type path } } }
+
+3. Check every attached script for parse errors:
+query { parseErrors(scriptPath: "<script path from step 2>") { line column message severity } }
+
+4. Check references in and out of the scene; look for missing files and cycles:
+query {
+  gatherContext(input: {entryPoint: "{scene_path}", depth: 2}) { dependencies { path type } dependents { path type } resources { path type } }
+  dependencyGraph { stats { hasCycles cyclePaths } cycleBreakSuggestions { from to strategy line description } }
+}
+
+5. If the game is running in the editor, read the runtime errors:
+query { debuggerErrors { message stackInfo { file line function } } logs(limit: 50) { message severity file line } }
+
+6. Check scene contracts:
+query { checkContracts { passed violations { contract scene message } } }
+
+Summarize the most likely cause with the file and line, then propose the smallest fix. Create a checkpoint (mutation { createCheckpoint(label: "before fixing {scene_path}") { success } }) before applying it."#,
+    },
+    Workflow {
+        name: "audit_unused_assets",
+        description: "Find scenes, scripts and assets nothing references, and plan a safe cleanup",
+        arguments: &[(
+            "folder",
+            "Only report files below this folder",
+            false,
+            Some("res://"),
+        )],
+        template: r#"Audit unused files below {folder} in this Godot project. Do not delete anything without the user's confirmation.
+
+1. List files nothing references, with godot_query:
+query { dependencyGraph { nodes(filter: {isUnused: true}) { id type inDegree } stats { nodeCount unusedCount } } }
+Keep only ids below {folder}.
+
+2. Rule out entry points: files referenced from project.godot (main scene, autoloads) are used even without incoming edges:
+query { project { name } autoloads { autoloads { name path } } preloadAnalysis { entryPoint } }
+
+3. For each remaining script, confirm its class_name is not used elsewhere:
+query { findReferences(symbol: "<class_name>") { totalCount references { file line } } }
+
+4. Cross-check with the health report:
+query { projectHealth(record: false) { categories { name summary issues { severity file message } } } }
+
+5. Report the candidates grouped by type with their size, and ask which to remove. Before any removal, create a checkpoint:
+mutation { createCheckpoint(label: "before unused asset cleanup") { success checkpoint { id } } }"#,
+    },
+];
+
+impl Workflow {
+    fn prompt(&self) -> Prompt {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|(name, description, required, default)| PromptArgument {
+                name: name.to_string(),
+                title: None,
+                description: Some(match default {
+                    Some(default) => format!("{} (default: {})", description, default),
+                    None => description.to_string(),
+                }),
+                required: Some(*required),
+            })
+            .collect();
+        Prompt::new(self.name, Some(self.description), Some(arguments))
+    }
+
+    fn render(&self, arguments: Option<&JsonObject>) -> Result<String, McpError> {
+        let mut text = self.template.to_string();
+        for (name, _, _, default) in self.arguments {
+            let value = arguments
+                .and_then(|args| args.get(*name))
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .or(*default);
+            let Some(value) = value else {
+                return Err(McpError::invalid_params(
+                    format!("Prompt '{}' requires argument '{}'", self.name, name),
+                    None,
+                ));
+            };
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        Ok(text)
+    }
+}
+
+impl GodotTools {
+    /// prompts/list - Available workflow prompts
+    pub fn list_workflow_prompts(&self) -> Vec<Prompt> {
+        WORKFLOWS.iter().map(Workflow::prompt).collect()
+    }
+
+    /// prompts/get - Workflow prompt with its arguments filled in
+    pub fn get_workflow_prompt(
+        &self,
+        name: &str,
+        arguments: Option<&JsonObject>,
+    ) -> Result<GetPromptResult, McpError> {
+        let workflow = WORKFLOWS
+            .iter()
+            .find(|w| w.name == name)
+            .ok_or_else(|| McpError::invalid_params(format!("Unknown prompt: {}", name), None))?;
+        Ok(GetPromptResult {
+            description: Some(workflow.description.to_string()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                workflow.render(arguments)?,
+            )],
+        })
+    }
+}
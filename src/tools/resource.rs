@@ -119,9 +119,8 @@ impl GodotTools {
         let resource = GodotResource::new(&req.resource_type);
         let content = resource.to_tres();
 
-        std::fs::write(&full_path, &content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write file: {}", e), None)
-        })?;
+        std::fs::write(&full_path, &content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write file: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Created resource '{}' at {}",
@@ -152,9 +151,8 @@ impl GodotTools {
         resource.set_property(&req.property, &req.value);
 
         let new_content = resource.to_tres();
-        std::fs::write(&full_path, &new_content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write file: {}", e), None)
-        })?;
+        std::fs::write(&full_path, &new_content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write file: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Set property '{}' = {} on {}",
@@ -186,9 +184,8 @@ impl GodotTools {
         resource.add_ext_resource(&req.id, &req.resource_type, &req.resource_path);
 
         let new_content = resource.to_tres();
-        std::fs::write(&full_path, &new_content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write file: {}", e), None)
-        })?;
+        std::fs::write(&full_path, &new_content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write file: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Added ext_resource id='{}' type='{}' path='{}' to {}",
@@ -228,9 +225,8 @@ impl GodotTools {
         }
 
         let new_content = resource.to_tres();
-        std::fs::write(&full_path, &new_content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write file: {}", e), None)
-        })?;
+        std::fs::write(&full_path, &new_content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write file: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Added sub_resource id='{}' type='{}' to {}",
@@ -270,7 +266,10 @@ impl GodotTools {
         if let Some(color) = req.albedo_color {
             resource.set_property(
                 "albedo_color",
-                &format!("Color({}, {}, {}, {})", color[0], color[1], color[2], color[3]),
+                &format!(
+                    "Color({}, {}, {}, {})",
+                    color[0], color[1], color[2], color[3]
+                ),
             );
         }
 
@@ -283,9 +282,8 @@ impl GodotTools {
         }
 
         let content = resource.to_tres();
-        std::fs::write(&full_path, &content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write file: {}", e), None)
-        })?;
+        std::fs::write(&full_path, &content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write file: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Created StandardMaterial3D at {}",
@@ -312,7 +310,10 @@ impl GodotTools {
                 .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
 
         let base = self.get_base_path();
-        let scene_path = req.scene_path.strip_prefix("res://").unwrap_or(&req.scene_path);
+        let scene_path = req
+            .scene_path
+            .strip_prefix("res://")
+            .unwrap_or(&req.scene_path);
         let full_scene_path = base.join(scene_path);
 
         let content = std::fs::read_to_string(&full_scene_path)
@@ -336,6 +337,7 @@ impl GodotTools {
                 id: material_id.clone(),
                 resource_type: "Material".to_string(),
                 path: req.material_path.clone(),
+                uid: None,
             });
         }
 
@@ -382,9 +384,8 @@ impl GodotTools {
         }
 
         let new_content = scene.to_tscn();
-        std::fs::write(&full_scene_path, &new_content).map_err(|e| {
-            McpError::internal_error(format!("Failed to write scene: {}", e), None)
-        })?;
+        std::fs::write(&full_scene_path, &new_content)
+            .map_err(|e| McpError::internal_error(format!("Failed to write scene: {}", e), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Assigned material '{}' to node '{}' in {}",
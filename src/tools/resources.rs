@@ -0,0 +1,88 @@
+//! MCP Resources - Scenes, scripts and project.godot as attachable files
+//!
+//! Resources are addressed by their `res://` path, so the URI a client reads
+//! is the same path GraphQL queries and mutations use.
+
+use rmcp::model::{AnnotateAble, RawResource, ReadResourceResult, Resource, ResourceContents};
+use rmcp::ErrorData as McpError;
+
+use super::GodotTools;
+use crate::graphql::project_index::ProjectIndex;
+use crate::path_utils;
+
+/// project.godot, which is listed alongside scenes and scripts
+const PROJECT_FILE: &str = "res://project.godot";
+
+/// MIME type of a listed file; None for files that are not exposed
+fn mime_type(res_path: &str) -> Option<&'static str> {
+    if res_path == PROJECT_FILE {
+        return Some("text/x-godot-project");
+    }
+    match res_path.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("tscn") => Some("text/x-godot-scene"),
+        Some("gd") => Some("text/x-gdscript"),
+        _ => None,
+    }
+}
+
+impl GodotTools {
+    /// resources/list - project.godot, then every scene and script by path
+    pub fn list_project_resources(&self) -> Vec<Resource> {
+        let base = self.get_base_path();
+        let index = ProjectIndex::shared(base);
+
+        let mut resources = Vec::new();
+        for path in index.files(base) {
+            let Ok(res_path) = path_utils::to_res_path(base, &path) else {
+                continue;
+            };
+            let Some(mime_type) = mime_type(&res_path) else {
+                continue;
+            };
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut resource = RawResource::new(res_path.as_str(), name);
+            resource.mime_type = Some(mime_type.to_string());
+            resource.size = std::fs::metadata(&path)
+                .ok()
+                .and_then(|m| u32::try_from(m.len()).ok());
+            resources.push(resource.no_annotation());
+        }
+        // project.godot first, the rest by path
+        resources.sort_by(|a, b| {
+            (a.uri != PROJECT_FILE)
+                .cmp(&(b.uri != PROJECT_FILE))
+                .then_with(|| a.uri.cmp(&b.uri))
+        });
+        resources
+    }
+
+    /// resources/read - Text of a listed scene, script or project.godot
+    pub fn read_project_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
+        let base = self.get_base_path();
+        let Some(mime_type) = mime_type(uri) else {
+            return Err(McpError::resource_not_found(
+                format!("Not a project resource: {}", uri),
+                None,
+            ));
+        };
+        let fs_path = path_utils::to_fs_path(base, uri)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        let text = ProjectIndex::shared(base)
+            .read_to_string(&fs_path)
+            .ok_or_else(|| {
+                McpError::resource_not_found(format!("Resource not found: {}", uri), None)
+            })?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime_type.to_string()),
+                text: text.to_string(),
+                meta: None,
+            }],
+        })
+    }
+}
@@ -1,17 +1,40 @@
 //! Scene-related tools - Creation, editing, and analysis
 
+use indexmap::IndexMap;
 use rmcp::{model::CallToolResult, model::Content, ErrorData as McpError};
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::{
     AddNodeRequest, BatchAddNodesRequest, CompareScenesRequest, CopySceneRequest,
     CreateSceneFromTemplateRequest, CreateSceneRequest, ExportSceneAsJsonRequest,
-    GetNodeTreeRequest, GetSceneMetadataRequest, GodotTools, ReadSceneRequest, RemoveNodeRequest,
-    SetNodePropertyRequest, ValidateTscnRequest,
+    GetNodeTreeRequest, GetSceneMetadataRequest, GodotTools, NodeTreeStyle, ReadSceneRequest,
+    RemoveNodeRequest, RenameNodeRequest, ReparentNodeRequest, SetNodePropertyRequest,
+    ValidateTscnRequest,
 };
 use crate::godot::tscn::{GodotScene, SceneNode};
+use crate::graphql::{scene_lock, GqlContext, SceneWritePolicy};
+use crate::path_utils;
 
 impl GodotTools {
+    /// Write a scene under the editor plugin's scene lock, like the GraphQL
+    /// scene mutations; unsaved changes in the editor refuse the write
+    async fn write_scene(
+        &self,
+        path: &str,
+        full_path: &Path,
+        content: &str,
+    ) -> Result<(), McpError> {
+        let ctx = GqlContext::new(self.get_base_path().to_path_buf());
+        let res_path = format!("res://{}", path_utils::strip_res_prefix(path));
+        let lock = scene_lock::lock_scene(&ctx, &res_path, SceneWritePolicy::Refuse)
+            .await
+            .map_err(|e| McpError::internal_error(e.message, None))?;
+        let written = std::fs::write(full_path, content);
+        scene_lock::unlock_scene(&ctx, lock, written.is_ok()).await;
+        written.map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
     /// create_scene - Create a scene
     pub async fn handle_create_scene(
         &self,
@@ -39,8 +62,7 @@ impl GodotTools {
             std::fs::create_dir_all(parent)
                 .map_err(|e| McpError::internal_error(e.to_string(), None))?;
         }
-        std::fs::write(&full_path, &content)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(&req.path, &full_path, &content).await?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Created scene: {}",
@@ -107,11 +129,15 @@ impl GodotTools {
             name: req.name.clone(),
             node_type: req.node_type.clone(),
             parent: Some(req.parent.clone()),
-            properties: HashMap::new(),
+            instance: None,
+            groups: Vec::new(),
+            extra_attrs: Vec::new(),
+            attr_order: Vec::new(),
+            properties: IndexMap::new(),
         });
 
-        std::fs::write(&full_path, scene.to_tscn())
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(&req.scene_path, &full_path, &scene.to_tscn())
+            .await?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Added node '{}' (type: {}) under '{}'",
@@ -141,8 +167,8 @@ impl GodotTools {
             .remove_node(&req.node_path)
             .map_err(|e| McpError::internal_error(e, None))?;
 
-        std::fs::write(&full_path, scene.to_tscn())
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(&req.scene_path, &full_path, &scene.to_tscn())
+            .await?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Removed node '{}'",
@@ -150,6 +176,68 @@ impl GodotTools {
         ))]))
     }
 
+    /// rename_node - Rename a node, updating the references to it
+    pub async fn handle_rename_node(
+        &self,
+        args: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<CallToolResult, McpError> {
+        let req: RenameNodeRequest =
+            serde_json::from_value(serde_json::Value::Object(args.unwrap_or_default()))
+                .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let base = self.get_base_path();
+        let full_path = base.join(&req.scene_path);
+
+        let content = std::fs::read_to_string(&full_path)
+            .map_err(|e| McpError::internal_error(format!("Failed to read scene: {}", e), None))?;
+
+        let mut scene = GodotScene::parse(&content)
+            .map_err(|e| McpError::internal_error(format!("Failed to parse scene: {}", e), None))?;
+
+        let new_path = scene
+            .rename_node(&req.node_path, &req.new_name)
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        self.write_scene(&req.scene_path, &full_path, &scene.to_tscn())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Renamed node '{}' to '{}'",
+            req.node_path, new_path
+        ))]))
+    }
+
+    /// reparent_node - Move a node (and its children) under a different parent
+    pub async fn handle_reparent_node(
+        &self,
+        args: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<CallToolResult, McpError> {
+        let req: ReparentNodeRequest =
+            serde_json::from_value(serde_json::Value::Object(args.unwrap_or_default()))
+                .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+        let base = self.get_base_path();
+        let full_path = base.join(&req.scene_path);
+
+        let content = std::fs::read_to_string(&full_path)
+            .map_err(|e| McpError::internal_error(format!("Failed to read scene: {}", e), None))?;
+
+        let mut scene = GodotScene::parse(&content)
+            .map_err(|e| McpError::internal_error(format!("Failed to parse scene: {}", e), None))?;
+
+        let new_path = scene
+            .reparent_node(&req.node_path, &req.new_parent)
+            .map_err(|e| McpError::internal_error(e, None))?;
+
+        self.write_scene(&req.scene_path, &full_path, &scene.to_tscn())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Moved node '{}' to '{}'",
+            req.node_path, new_path
+        ))]))
+    }
+
     /// set_node_property - Set a node property
     pub async fn handle_set_node_property(
         &self,
@@ -168,12 +256,23 @@ impl GodotTools {
         let mut scene = GodotScene::parse(&content)
             .map_err(|e| McpError::internal_error(format!("Failed to parse scene: {}", e), None))?;
 
-        scene
-            .set_property(&req.node_path, &req.property, &req.value)
-            .map_err(|e| McpError::internal_error(e, None))?;
+        // Nodes inherited from a base scene get an override section
+        let load = |res_path: &str| {
+            let content = std::fs::read_to_string(path_utils::to_fs_path_unchecked(base, res_path));
+            GodotScene::parse(&content.ok()?).ok()
+        };
+        if let Err(e) = scene.set_property(&req.node_path, &req.property, &req.value) {
+            if scene.inherited_node(&req.node_path, load).is_none() {
+                return Err(McpError::internal_error(e, None));
+            }
+            scene
+                .add_inherited_override(&req.node_path)
+                .and_then(|_| scene.set_property(&req.node_path, &req.property, &req.value))
+                .map_err(|e| McpError::internal_error(e, None))?;
+        }
 
-        std::fs::write(&full_path, scene.to_tscn())
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(&req.scene_path, &full_path, &scene.to_tscn())
+            .await?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Set {}.{} = {}",
@@ -199,21 +298,7 @@ impl GodotTools {
         let scene = GodotScene::parse(&content)
             .map_err(|e| McpError::internal_error(format!("Failed to parse scene: {}", e), None))?;
 
-        let mut tree = String::new();
-        for node in &scene.nodes {
-            let depth = if node.parent.is_none() {
-                0
-            } else if node.parent.as_deref() == Some(".") {
-                1
-            } else {
-                node.parent
-                    .as_ref()
-                    .map(|p| p.matches('/').count() + 2)
-                    .unwrap_or(1)
-            };
-            let indent = "  ".repeat(depth);
-            tree.push_str(&format!("{}{} ({})\n", indent, node.name, node.node_type));
-        }
+        let tree = render_node_tree(&scene, &req).map_err(|e| McpError::invalid_params(e, None))?;
 
         Ok(CallToolResult::success(vec![Content::text(tree)]))
     }
@@ -235,24 +320,11 @@ impl GodotTools {
 
         match GodotScene::parse(&content) {
             Ok(scene) => {
-                let mut issues: Vec<String> = Vec::new();
-
-                if scene.nodes.is_empty() {
-                    issues.push("Root node missing".to_string());
-                }
-
-                for node in &scene.nodes {
-                    if let Some(ref parent) = node.parent {
-                        if parent != "."
-                            && !scene
-                                .nodes
-                                .iter()
-                                .any(|n| n.name == parent.split('/').next().unwrap_or(""))
-                        {
-                            // For future validation
-                        }
-                    }
-                }
+                let issues: Vec<String> = scene
+                    .structure_issues()
+                    .iter()
+                    .map(|issue| issue.message())
+                    .collect();
 
                 let result = if issues.is_empty() {
                     serde_json::json!({
@@ -565,13 +637,16 @@ impl GodotTools {
                 name: entry.name.clone(),
                 node_type: entry.node_type.clone(),
                 parent: Some(entry.parent.clone()),
-                properties: std::collections::HashMap::new(),
+                instance: None,
+                groups: Vec::new(),
+                extra_attrs: Vec::new(),
+                attr_order: Vec::new(),
+                properties: IndexMap::new(),
             });
             added.push(format!("{} ({})", entry.name, entry.node_type));
         }
 
-        std::fs::write(&full_path, scene.to_tscn())
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(path, &full_path, &scene.to_tscn()).await?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
             "Added {} nodes: {}",
@@ -666,7 +741,11 @@ impl GodotTools {
                 name: node_name.to_string(),
                 node_type: node_type.to_string(),
                 parent: Some(".".to_string()),
-                properties: HashMap::new(),
+                instance: None,
+                groups: Vec::new(),
+                extra_attrs: Vec::new(),
+                attr_order: Vec::new(),
+                properties: IndexMap::new(),
             });
         }
 
@@ -674,8 +753,8 @@ impl GodotTools {
             std::fs::create_dir_all(parent)
                 .map_err(|e| McpError::internal_error(e.to_string(), None))?;
         }
-        std::fs::write(&full_path, scene.to_tscn())
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.write_scene(&req.path, &full_path, &scene.to_tscn())
+            .await?;
 
         let node_list: Vec<String> = nodes
             .iter()
@@ -693,3 +772,152 @@ impl GodotTools {
         ))]))
     }
 }
+
+/// Render the node tree of `scene` as requested by get_node_tree
+fn render_node_tree(scene: &GodotScene, req: &GetNodeTreeRequest) -> Result<String, String> {
+    // Children of every node path, in file order
+    let mut children: HashMap<String, Vec<&SceneNode>> = HashMap::new();
+    for node in &scene.nodes {
+        if let Some(parent) = &node.parent {
+            children.entry(parent.clone()).or_default().push(node);
+        }
+    }
+
+    let root_path = req.root.as_deref().unwrap_or(".");
+    let root = scene
+        .nodes
+        .iter()
+        .find(|node| tree_node_path(node) == root_path)
+        .ok_or_else(|| format!("Node not found: {}", root_path))?;
+
+    let renderer = TreeRenderer {
+        scene,
+        children,
+        style: req.style,
+        include_types: req.include_types.unwrap_or(true),
+        include_scripts: req.include_scripts.unwrap_or(false),
+        include_groups: req.include_groups.unwrap_or(false),
+        max_depth: req.max_depth.map(|d| d as usize),
+    };
+    let mut out = String::new();
+    renderer.render(root, 0, "", true, &mut out);
+    Ok(out)
+}
+
+/// Path of a node relative to the scene root ("." for the root)
+fn tree_node_path(node: &SceneNode) -> String {
+    match node.parent.as_deref() {
+        None => ".".to_string(),
+        Some(".") => node.name.clone(),
+        Some(parent) => format!("{}/{}", parent, node.name),
+    }
+}
+
+struct TreeRenderer<'a> {
+    scene: &'a GodotScene,
+    children: HashMap<String, Vec<&'a SceneNode>>,
+    style: NodeTreeStyle,
+    include_types: bool,
+    include_scripts: bool,
+    include_groups: bool,
+    max_depth: Option<usize>,
+}
+
+impl TreeRenderer<'_> {
+    /// Write `node` and, depth permitting, its subtree; `prefix` carries the
+    /// ASCII guide lines of the ancestors
+    fn render(&self, node: &SceneNode, depth: usize, prefix: &str, last: bool, out: &mut String) {
+        let children = self.children_of(node);
+        let mut label = self.label(node);
+        let truncated = self.max_depth.is_some_and(|max| depth >= max);
+        if truncated && !children.is_empty() {
+            let hidden = self.count_descendants(node);
+            label.push_str(&format!(" [+{} more]", hidden));
+        }
+
+        match self.style {
+            NodeTreeStyle::Indent => {
+                out.push_str(&format!("{}{}\n", "  ".repeat(depth), label));
+            }
+            NodeTreeStyle::Markdown => {
+                out.push_str(&format!("{}- {}\n", "  ".repeat(depth), label));
+            }
+            NodeTreeStyle::Ascii if depth == 0 => {
+                out.push_str(&format!("{}\n", label));
+            }
+            NodeTreeStyle::Ascii => {
+                let branch = if last { "└── " } else { "├── " };
+                out.push_str(&format!("{}{}{}\n", prefix, branch, label));
+            }
+        }
+        if truncated {
+            return;
+        }
+
+        let child_prefix = match (self.style, depth) {
+            (NodeTreeStyle::Ascii, 0) => String::new(),
+            (NodeTreeStyle::Ascii, _) => {
+                format!("{}{}", prefix, if last { "    " } else { "│   " })
+            }
+            _ => String::new(),
+        };
+        for (i, child) in children.iter().enumerate() {
+            self.render(
+                child,
+                depth + 1,
+                &child_prefix,
+                i + 1 == children.len(),
+                out,
+            );
+        }
+    }
+
+    fn children_of(&self, node: &SceneNode) -> &[&SceneNode] {
+        self.children
+            .get(&tree_node_path(node))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn count_descendants(&self, node: &SceneNode) -> usize {
+        self.children_of(node)
+            .iter()
+            .map(|child| 1 + self.count_descendants(child))
+            .sum()
+    }
+
+    fn label(&self, node: &SceneNode) -> String {
+        let mut label = match self.style {
+            NodeTreeStyle::Markdown => format!("**{}**", node.name),
+            _ => node.name.clone(),
+        };
+        if self.include_types {
+            label.push_str(&format!(" ({})", node.node_type));
+        }
+        if self.include_scripts {
+            if let Some(script) = self.script_path(node) {
+                label.push_str(&format!(" script={}", script));
+            }
+        }
+        if self.include_groups && !node.groups.is_empty() {
+            label.push_str(&format!(" groups=[{}]", node.groups.join(", ")));
+        }
+        label
+    }
+
+    /// res:// path of the script attached via `script = ExtResource("id")`
+    fn script_path(&self, node: &SceneNode) -> Option<&str> {
+        let value = node.properties.get("script")?;
+        let id = value
+            .trim()
+            .strip_prefix("ExtResource(")?
+            .strip_suffix(')')?
+            .trim()
+            .trim_matches('"');
+        self.scene
+            .ext_resources
+            .iter()
+            .find(|ext| ext.id == id)
+            .map(|ext| ext.path.as_str())
+    }
+}
@@ -6,7 +6,9 @@ use super::{
     AddExportVarRequest, AddFunctionRequest, AnalyzeScriptRequest, AttachScriptRequest,
     CreateScriptRequest, FunctionParamInput, GodotTools, ReadScriptRequest,
 };
-use crate::godot::gdscript::{generate_template, ExportVar, Function, FunctionParam, GDScript};
+use crate::godot::gdscript::{
+    append_function, generate_template, insert_export, ExportVar, Function, FunctionParam, GDScript,
+};
 use crate::godot::tscn::GodotScene;
 
 impl GodotTools {
@@ -142,8 +144,6 @@ impl GodotTools {
         let content = std::fs::read_to_string(&full_path)
             .map_err(|e| McpError::internal_error(format!("Failed to read file: {}", e), None))?;
 
-        let mut script = GDScript::parse(&content);
-
         let params: Vec<FunctionParam> = req
             .params
             .unwrap_or_default()
@@ -155,14 +155,14 @@ impl GodotTools {
             })
             .collect();
 
-        script.add_function(Function {
+        let function = Function {
             name: req.name.clone(),
             params,
             return_type: req.return_type,
             body: req.body.unwrap_or_else(|| "pass".to_string()),
-        });
+        };
 
-        std::fs::write(&full_path, script.to_gdscript())
+        std::fs::write(&full_path, append_function(&content, &function))
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
@@ -187,15 +187,13 @@ impl GodotTools {
         let content = std::fs::read_to_string(&full_path)
             .map_err(|e| McpError::internal_error(format!("Failed to read file: {}", e), None))?;
 
-        let mut script = GDScript::parse(&content);
-
-        script.add_export(ExportVar {
+        let var = ExportVar {
             name: req.name.clone(),
             var_type: req.var_type,
             default_value: req.default_value,
-        });
+        };
 
-        std::fs::write(&full_path, script.to_gdscript())
+        std::fs::write(&full_path, insert_export(&content, &var))
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(format!(
@@ -0,0 +1,324 @@
+//! Project file watcher
+//!
+//! Watches the project directory for `.tscn`, `.gd` and `.tres` changes,
+//! including saves made from the Godot editor, and broadcasts them so that
+//! GraphQL subscriptions can react without polling.
+//!
+//! One watcher is started lazily per project root and shared by every
+//! subscriber; it stops when the process exits.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::broadcast;
+
+use crate::path_utils;
+use crate::project_registry::ProjectRegistry;
+use crate::time_utils::now_millis;
+
+/// Extensions of the files that are watched
+pub const WATCHED_EXTENSIONS: &[&str] = &["tscn", "gd", "tres"];
+
+/// Events buffered per subscriber before the oldest are dropped
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Identical events for a path within this window are reported once
+/// (a single save often produces several raw notifications)
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// What happened to a watched file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileEventKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// A change to a watched file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEvent {
+    /// Resource path (res://...)
+    pub path: String,
+    pub kind: FileEventKind,
+    /// Unix timestamp (ms)
+    pub timestamp: u64,
+}
+
+impl FileEvent {
+    /// File extension without the dot (e.g. "tscn")
+    pub fn extension(&self) -> &str {
+        self.path.rsplit_once('.').map_or("", |(_, ext)| ext)
+    }
+}
+
+/// Watches one project root and broadcasts its file events
+pub struct ProjectWatcher {
+    sender: broadcast::Sender<FileEvent>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ProjectWatcher {
+    /// Start watching `project_root` recursively
+    pub fn start(project_root: &Path) -> Result<Self, String> {
+        let root = project_root
+            .canonicalize()
+            .map_err(|e| format!("Cannot watch {}: {}", project_root.display(), e))?;
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let mut state = EventFilter::new(&root);
+        let tx = sender.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(event) => {
+                    for file_event in state.process(&event) {
+                        // No subscribers is fine; events are not replayed
+                        let _ = tx.send(file_event);
+                    }
+                }
+                Err(e) => tracing::warn!("File watcher error: {}", e),
+            }
+        })
+        .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| format!("Cannot watch {}: {}", root.display(), e))?;
+        tracing::info!("Watching {} for file changes", root.display());
+
+        Ok(Self {
+            sender,
+            _watcher: watcher,
+        })
+    }
+
+    /// Receive events from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<FileEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Subscribe to file events of `project_root`, starting its watcher on first use
+pub fn subscribe(project_root: &Path) -> Result<broadcast::Receiver<FileEvent>, String> {
+    static WATCHERS: ProjectRegistry<Arc<ProjectWatcher>> = ProjectRegistry::new();
+
+    let key = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    let mut watchers = WATCHERS.lock();
+    if let Some(watcher) = watchers.get(&key) {
+        return Ok(watcher.subscribe());
+    }
+    let watcher = Arc::new(ProjectWatcher::start(&key)?);
+    let receiver = watcher.subscribe();
+    watchers.insert(key, watcher);
+    Ok(receiver)
+}
+
+/// Turns raw notify events into debounced `FileEvent`s for watched files
+struct EventFilter {
+    root: PathBuf,
+    /// Watched files known to exist, so that a save which replaces the file
+    /// (write to a temp file, then rename) is reported as a modification
+    known: HashSet<PathBuf>,
+    last: HashMap<PathBuf, (FileEventKind, Instant)>,
+}
+
+impl EventFilter {
+    fn new(root: &Path) -> Self {
+        let mut known = HashSet::new();
+        collect_watched_files(root, &mut known);
+        Self {
+            root: root.to_path_buf(),
+            known,
+            last: HashMap::new(),
+        }
+    }
+
+    fn process(&mut self, event: &notify::Event) -> Vec<FileEvent> {
+        let changes: Vec<(&PathBuf, bool)> = match event.kind {
+            EventKind::Create(_) => event.paths.iter().map(|p| (p, true)).collect(),
+            EventKind::Remove(_) => event.paths.iter().map(|p| (p, false)).collect(),
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                event.paths.iter().map(|p| (p, false)).collect()
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                event.paths.iter().map(|p| (p, true)).collect()
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => match event.paths.as_slice() {
+                [from, to] => vec![(from, false), (to, true)],
+                _ => Vec::new(),
+            },
+            // Metadata-only changes (permissions, access times) are not edits
+            EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+            EventKind::Modify(_) => event.paths.iter().map(|p| (p, true)).collect(),
+            _ => Vec::new(),
+        };
+
+        let mut events = Vec::new();
+        for (path, exists) in self.expand_directories(changes) {
+            if !self.is_watched(&path) {
+                continue;
+            }
+            let kind = if !exists {
+                if !self.known.remove(&path) {
+                    continue;
+                }
+                FileEventKind::Deleted
+            } else if self.known.insert(path.clone()) {
+                FileEventKind::Created
+            } else {
+                FileEventKind::Modified
+            };
+
+            let now = Instant::now();
+            if let Some((last_kind, at)) = self.last.get(&path) {
+                if *last_kind == kind && now.duration_since(*at) < DEBOUNCE {
+                    continue;
+                }
+            }
+            self.last.insert(path.clone(), (kind, now));
+
+            let Ok(res_path) = path_utils::to_res_path(&self.root, &path) else {
+                continue;
+            };
+            events.push(FileEvent {
+                path: res_path,
+                kind,
+                timestamp: now_millis(),
+            });
+        }
+        events
+    }
+
+    /// A moved or deleted directory only reports itself; stand in its files
+    fn expand_directories(&self, changes: Vec<(&PathBuf, bool)>) -> Vec<(PathBuf, bool)> {
+        let mut expanded = Vec::new();
+        for (path, exists) in changes {
+            if exists && path.is_dir() {
+                let mut files = HashSet::new();
+                collect_watched_files(path, &mut files);
+                expanded.extend(files.into_iter().map(|f| (f, true)));
+            } else if !exists && !self.known.contains(path) {
+                expanded.extend(
+                    self.known
+                        .iter()
+                        .filter(|f| f.starts_with(path))
+                        .map(|f| (f.clone(), false)),
+                );
+            } else {
+                expanded.push((path.clone(), exists));
+            }
+        }
+        expanded
+    }
+
+    /// Watched extension, and not inside a hidden directory such as `.godot/`
+    fn is_watched(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        is_watched_file(relative)
+    }
+}
+
+fn is_watched_file(relative: &Path) -> bool {
+    let hidden = relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    let watched_ext = relative
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| WATCHED_EXTENSIONS.contains(&e));
+    !hidden && watched_ext
+}
+
+fn collect_watched_files(dir: &Path, files: &mut HashSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_watched_files(&path, files);
+        } else if is_watched_file(Path::new(&entry.file_name())) {
+            files.insert(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, DataChange, RemoveKind};
+
+    fn event(kind: EventKind, paths: &[&Path]) -> notify::Event {
+        notify::Event {
+            kind,
+            paths: paths.iter().map(|p| p.to_path_buf()).collect(),
+            attrs: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_filter_classifies_and_skips_unwatched() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("main.tscn"), "").unwrap();
+        let mut filter = EventFilter::new(root);
+
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        let events = filter.process(&event(modify, &[&root.join("main.tscn")]));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "res://main.tscn");
+        assert_eq!(events[0].kind, FileEventKind::Modified);
+        assert_eq!(events[0].extension(), "tscn");
+
+        // Same change again right away is debounced
+        assert!(filter
+            .process(&event(modify, &[&root.join("main.tscn")]))
+            .is_empty());
+
+        // Safe save: temp file renamed over the original
+        std::thread::sleep(DEBOUNCE);
+        let rename = EventKind::Modify(ModifyKind::Name(RenameMode::Both));
+        let events = filter.process(&event(
+            rename,
+            &[&root.join("main.tscn.tmp"), &root.join("main.tscn")],
+        ));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, FileEventKind::Modified);
+
+        let create = EventKind::Create(CreateKind::File);
+        let events = filter.process(&event(create, &[&root.join("player.gd")]));
+        assert_eq!(events[0].kind, FileEventKind::Created);
+
+        let remove = EventKind::Remove(RemoveKind::File);
+        let events = filter.process(&event(remove, &[&root.join("player.gd")]));
+        assert_eq!(events[0].kind, FileEventKind::Deleted);
+
+        // Folder moved away: its files are gone
+        fs::create_dir(root.join("enemies")).unwrap();
+        fs::write(root.join("enemies/slime.tscn"), "").unwrap();
+        let events = filter.process(&event(create, &[&root.join("enemies")]));
+        assert_eq!(events[0].path, "res://enemies/slime.tscn");
+        assert_eq!(events[0].kind, FileEventKind::Created);
+        let moved = EventKind::Modify(ModifyKind::Name(RenameMode::From));
+        let events = filter.process(&event(moved, &[&root.join("enemies")]));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "res://enemies/slime.tscn");
+        assert_eq!(events[0].kind, FileEventKind::Deleted);
+
+        for ignored in ["icon.png", ".godot/editor/main.tscn", "main.tscn.tmp"] {
+            assert!(filter
+                .process(&event(create, &[&root.join(ignored)]))
+                .is_empty());
+        }
+    }
+}
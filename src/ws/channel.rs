@@ -0,0 +1,257 @@
+//! Persistent WebSocket channel to the Godot plugin
+//!
+//! One connection per port is opened on first use and shared by every caller.
+//! Requests carry an `id` that the plugin echoes back, so several can be in
+//! flight at once and each response reaches the request it answers. A dropped
+//! connection fails the requests waiting on it and is re-established by the
+//! next request; after a failed connect, requests fail fast for a short
+//! backoff so callers can fall back to HTTP.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout, Instant};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use super::WsError;
+
+/// How long requests fail fast after a connect attempt failed
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(2);
+
+type Waiting = BTreeMap<u64, oneshot::Sender<Result<Value, WsError>>>;
+
+/// Shared, multiplexed WebSocket connection to the plugin on one port
+pub struct LiveChannel {
+    url: String,
+    next_id: AtomicU64,
+    state: tokio::sync::Mutex<ChannelState>,
+}
+
+#[derive(Default)]
+struct ChannelState {
+    connection: Option<Arc<Connection>>,
+    /// When the last connect attempt failed
+    failed_at: Option<Instant>,
+    reconnects: u32,
+}
+
+/// Connection state reported by [`LiveChannel::status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelStatus {
+    pub url: String,
+    pub connected: bool,
+    /// Connections opened after the first one
+    pub reconnects: u32,
+    /// Requests waiting for a response
+    pub in_flight: usize,
+}
+
+impl LiveChannel {
+    /// Create a channel to `ws://localhost:<port>`; it connects on first request
+    pub fn new(port: u16) -> Self {
+        Self {
+            url: format!("ws://localhost:{}", port),
+            next_id: AtomicU64::new(1),
+            state: tokio::sync::Mutex::new(ChannelState::default()),
+        }
+    }
+
+    /// The channel for `port` shared by the whole process
+    pub fn shared(port: u16) -> Arc<Self> {
+        static CHANNELS: OnceLock<Mutex<HashMap<u16, Arc<LiveChannel>>>> = OnceLock::new();
+        let mut channels = CHANNELS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        channels
+            .entry(port)
+            .or_insert_with(|| Arc::new(Self::new(port)))
+            .clone()
+    }
+
+    /// Send a command (a JSON object) and wait for its response
+    ///
+    /// `ConnectionFailed` and `SendFailed` mean the command never reached the
+    /// plugin; after any other error it may have run.
+    pub async fn request(
+        &self,
+        command: &Value,
+        timeout_duration: Duration,
+    ) -> Result<Value, WsError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut message = command.clone();
+        let Some(fields) = message.as_object_mut() else {
+            return Err(WsError::SerializationError(
+                "Command must be a JSON object".to_string(),
+            ));
+        };
+        fields.insert("id".to_string(), Value::from(id));
+        let text = message.to_string();
+
+        // A connection that dropped since the last request is replaced once
+        let mut receiver = None;
+        for _ in 0..2 {
+            let connection = self.connection(timeout_duration).await?;
+            if let Some(rx) = connection.send(id, text.clone()) {
+                receiver = Some(rx);
+                break;
+            }
+        }
+        let Some(receiver) = receiver else {
+            return Err(WsError::SendFailed("Connection closed".to_string()));
+        };
+
+        match timeout(timeout_duration, receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(WsError::ReceiveFailed("Connection closed".to_string())),
+            Err(_) => {
+                if let Some(connection) = &self.state.lock().await.connection {
+                    connection.waiting().remove(&id);
+                }
+                Err(WsError::Timeout)
+            }
+        }
+    }
+
+    /// Current connection state
+    pub async fn status(&self) -> ChannelStatus {
+        let state = self.state.lock().await;
+        let open = state.connection.as_ref().filter(|c| !c.is_closed());
+        ChannelStatus {
+            url: self.url.clone(),
+            connected: open.is_some(),
+            reconnects: state.reconnects,
+            in_flight: open.map_or(0, |c| c.waiting().len()),
+        }
+    }
+
+    /// The open connection, connecting when there is none
+    async fn connection(&self, timeout_duration: Duration) -> Result<Arc<Connection>, WsError> {
+        let mut state = self.state.lock().await;
+        if let Some(connection) = state.connection.as_ref().filter(|c| !c.is_closed()) {
+            return Ok(connection.clone());
+        }
+        if state
+            .failed_at
+            .is_some_and(|at| at.elapsed() < RECONNECT_BACKOFF)
+        {
+            return Err(WsError::ConnectionFailed(format!(
+                "{} was unreachable moments ago",
+                self.url
+            )));
+        }
+
+        let stream = match timeout(timeout_duration, connect_async(&self.url)).await {
+            Ok(Ok((stream, _))) => stream,
+            Ok(Err(e)) => {
+                state.failed_at = Some(Instant::now());
+                return Err(WsError::ConnectionFailed(e.to_string()));
+            }
+            Err(_) => {
+                state.failed_at = Some(Instant::now());
+                return Err(WsError::ConnectionFailed("Connect timed out".to_string()));
+            }
+        };
+        if state.connection.is_some() {
+            state.reconnects += 1;
+        }
+        state.failed_at = None;
+        let connection = Connection::spawn(stream);
+        state.connection = Some(connection.clone());
+        Ok(connection)
+    }
+}
+
+/// One WebSocket connection with a writer and a reader task
+struct Connection {
+    outgoing: mpsc::UnboundedSender<Message>,
+    waiting: Arc<Mutex<Waiting>>,
+    closed: Arc<AtomicBool>,
+}
+
+impl Connection {
+    fn spawn<S>(stream: tokio_tungstenite::WebSocketStream<S>) -> Arc<Self>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, mut source) = stream.split();
+        let (outgoing, mut queue) = mpsc::unbounded_channel::<Message>();
+        let waiting: Arc<Mutex<Waiting>> = Arc::default();
+        let closed = Arc::new(AtomicBool::new(false));
+
+        // One writer keeps requests in the order they were sent
+        tokio::spawn(async move {
+            while let Some(message) = queue.recv().await {
+                if sink.send(message).await.is_err() {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let reader_waiting = waiting.clone();
+        let reader_closed = closed.clone();
+        tokio::spawn(async move {
+            while let Some(message) = source.next().await {
+                let text = match message {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Binary(data)) => String::from_utf8_lossy(&data).into_owned(),
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                let value: Value = serde_json::from_str(&text).unwrap_or(Value::String(text));
+                // A reply without an id can't be matched to its request, so
+                // it is dropped and the request times out instead
+                let Some(id) = value.get("id").and_then(Value::as_u64) else {
+                    tracing::debug!("Ignoring WebSocket message without an id");
+                    continue;
+                };
+                let sender = reader_waiting
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(&id);
+                if let Some(sender) = sender {
+                    let _ = sender.send(Ok(value.get("result").cloned().unwrap_or(Value::Null)));
+                }
+            }
+            reader_closed.store(true, Ordering::SeqCst);
+            let waiting =
+                std::mem::take(&mut *reader_waiting.lock().unwrap_or_else(|e| e.into_inner()));
+            for (_, sender) in waiting {
+                let _ = sender.send(Err(WsError::ReceiveFailed("Connection closed".to_string())));
+            }
+        });
+
+        Arc::new(Self {
+            outgoing,
+            waiting,
+            closed,
+        })
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst) || self.outgoing.is_closed()
+    }
+
+    fn waiting(&self) -> std::sync::MutexGuard<'_, Waiting> {
+        self.waiting.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Queue a request; None when the connection is already closed
+    fn send(&self, id: u64, text: String) -> Option<oneshot::Receiver<Result<Value, WsError>>> {
+        let (sender, receiver) = oneshot::channel();
+        self.waiting().insert(id, sender);
+        // Checked after registering: the reader sets `closed` before failing
+        // the waiting requests, so none is left behind
+        if self.is_closed() || self.outgoing.send(Message::Text(text)).is_err() {
+            self.waiting().remove(&id);
+            return None;
+        }
+        Some(receiver)
+    }
+}
@@ -1,4 +1,6 @@
 //! WebSocket client module for Godot plugin communication
 
+pub mod channel;
 pub mod client;
+pub use channel::{ChannelStatus, LiveChannel};
 pub use client::{WsClient, WsError};
@@ -4,14 +4,23 @@ extends Node
 ## Command Handler - Router
 ## Routes commands to domain-specific handlers.
 ## Decomposed from monolithic command_handler.gd into:
-## - node_handler: add, remove, duplicate, rename, reparent
-## - scene_handler: save, open, instantiate, get_tree
+## - node_handler: add, add_node_at_cursor, remove, duplicate, rename, reparent
+## - scene_handler: save, open, instantiate, get_tree, get_scene_events
 ## - signal_handler: connect, disconnect, list_signals
 ## - property_handler: set_property, get_properties
-## - animation_handler: create, add_track, add_key, play, stop, list
-## - debug_handler: logs, errors, pause, resume, step, breakpoints
+## - animation_handler: create, add_track, add_key, play, stop, list, remap_tracks
+## - debug_handler: logs, errors, pause, resume, step, breakpoints, eval_expression,
+##   get_performance_metrics
 ## - group_handler: add_to_group, remove_from_group, list_groups, get_group_nodes
 ## - shader_handler: create_visual_shader_node, validate_shader_live
+## - lock_handler: get_scene_status, lock_scene, unlock_scene
+## - screenshot_handler: capture_screenshot, capture_viewport
+## - play_handler: get_play_state, play_main_scene, play_current_scene, stop_playing_scene,
+##   send_input_action, send_key, send_mouse_click
+## - editor_settings_handler: get_editor_settings, set_editor_setting
+## - camera_handler: frame_node, set_editor_camera, set_main_screen
+## - profiler_handler: start_profiling, stop_profiling, get_profile_report
+## - introspect_handler: get_type_info, list_all_types, get_class_icons
 
 var plugin: EditorPlugin
 
@@ -26,6 +35,12 @@ var _group_handler
 var _shader_handler
 var _introspect_handler
 var _transaction_handler
+var _lock_handler
+var _screenshot_handler
+var _play_handler
+var _editor_settings_handler
+var _camera_handler
+var _profiler_handler
 
 # Command to handler mapping
 var _command_handlers: Dictionary = {}
@@ -45,6 +60,12 @@ func _load_handlers() -> void:
 	var ShaderHandler = load("res://addons/godot_mcp/handlers/shader_handler.gd")
 	var IntrospectHandler = load("res://addons/godot_mcp/handlers/introspect_handler.gd")
 	var TransactionHandler = load("res://addons/godot_mcp/handlers/transaction_handler.gd")
+	var LockHandler = load("res://addons/godot_mcp/handlers/lock_handler.gd")
+	var ScreenshotHandler = load("res://addons/godot_mcp/handlers/screenshot_handler.gd")
+	var PlayHandler = load("res://addons/godot_mcp/handlers/play_handler.gd")
+	var EditorSettingsHandler = load("res://addons/godot_mcp/handlers/editor_settings_handler.gd")
+	var CameraHandler = load("res://addons/godot_mcp/handlers/camera_handler.gd")
+	var ProfilerHandler = load("res://addons/godot_mcp/handlers/profiler_handler.gd")
 	
 	_node_handler = NodeHandler.new(plugin)
 	_scene_handler = SceneHandler.new(plugin)
@@ -56,10 +77,17 @@ func _load_handlers() -> void:
 	_shader_handler = ShaderHandler.new(plugin)
 	_introspect_handler = IntrospectHandler.new(plugin)
 	_transaction_handler = TransactionHandler.new(plugin)
+	_lock_handler = LockHandler.new(plugin)
+	_screenshot_handler = ScreenshotHandler.new(plugin)
+	_play_handler = PlayHandler.new(plugin)
+	_editor_settings_handler = EditorSettingsHandler.new(plugin)
+	_camera_handler = CameraHandler.new(plugin)
+	_profiler_handler = ProfilerHandler.new(plugin)
 
 func _build_command_map() -> void:
 	# Node operations
 	_command_handlers["add_node"] = _node_handler
+	_command_handlers["add_node_at_cursor"] = _node_handler
 	_command_handlers["remove_node"] = _node_handler
 	_command_handlers["duplicate_node"] = _node_handler
 	_command_handlers["rename_node"] = _node_handler
@@ -70,6 +98,7 @@ func _build_command_map() -> void:
 	_command_handlers["open_scene"] = _scene_handler
 	_command_handlers["instantiate_scene"] = _scene_handler
 	_command_handlers["get_tree"] = _scene_handler
+	_command_handlers["get_scene_events"] = _scene_handler
 	
 	# Signal operations
 	_command_handlers["connect_signal"] = _signal_handler
@@ -87,6 +116,7 @@ func _build_command_map() -> void:
 	_command_handlers["play_animation"] = _animation_handler
 	_command_handlers["stop_animation"] = _animation_handler
 	_command_handlers["list_animations"] = _animation_handler
+	_command_handlers["remap_animation_tracks"] = _animation_handler
 	
 	# Debug operations
 	_command_handlers["get_editor_log"] = _debug_handler
@@ -113,15 +143,53 @@ func _build_command_map() -> void:
 	# Phase 3: Debug Enhanced
 	_command_handlers["get_parse_errors"] = _debug_handler
 	_command_handlers["get_stack_frame_vars"] = _debug_handler
+	_command_handlers["get_performance_metrics"] = _debug_handler
+	
+	# Remote evaluation in the running game (answered asynchronously)
+	_command_handlers["eval_expression"] = _debug_handler
+	_command_handlers["get_eval_result"] = _debug_handler
 	
 	# Introspect operations (Phase 1: Dynamic Type Discovery)
 	_command_handlers["get_type_info"] = _introspect_handler
 	_command_handlers["list_all_types"] = _introspect_handler
+	_command_handlers["get_class_icons"] = _introspect_handler
 	
 	# Transaction operations (Phase 1: Undo/Redo grouping)
 	_command_handlers["begin_transaction"] = _transaction_handler
 	_command_handlers["commit_transaction"] = _transaction_handler
 	_command_handlers["rollback_transaction"] = _transaction_handler
+	
+	# Scene lock operations (file-based writes from the server)
+	_command_handlers["get_scene_status"] = _lock_handler
+	_command_handlers["lock_scene"] = _lock_handler
+	_command_handlers["unlock_scene"] = _lock_handler
+	
+	# Screenshot operations (annotated by the server)
+	_command_handlers["capture_screenshot"] = _screenshot_handler
+	_command_handlers["capture_viewport"] = _screenshot_handler
+	
+	# Play-in-editor operations (Play / Play Scene / Stop buttons)
+	_command_handlers["get_play_state"] = _play_handler
+	_command_handlers["play_main_scene"] = _play_handler
+	_command_handlers["play_current_scene"] = _play_handler
+	_command_handlers["stop_playing_scene"] = _play_handler
+	_command_handlers["send_input_action"] = _play_handler
+	_command_handlers["send_key"] = _play_handler
+	_command_handlers["send_mouse_click"] = _play_handler
+	
+	# Editor settings (Editor > Editor Settings)
+	_command_handlers["get_editor_settings"] = _editor_settings_handler
+	_command_handlers["set_editor_setting"] = _editor_settings_handler
+	
+	# Editor camera (point the viewports at what was changed)
+	_command_handlers["frame_node"] = _camera_handler
+	_command_handlers["set_editor_camera"] = _camera_handler
+	_command_handlers["set_main_screen"] = _camera_handler
+	
+	# Script profiler (the editor's Profiler panel)
+	_command_handlers["start_profiling"] = _profiler_handler
+	_command_handlers["stop_profiling"] = _profiler_handler
+	_command_handlers["get_profile_report"] = _profiler_handler
 
 func handle_command(data: Dictionary) -> Dictionary:
 	var command = data.get("command", "")
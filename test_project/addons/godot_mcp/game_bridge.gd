@@ -0,0 +1,110 @@
+extends Node
+
+## MCP Game Bridge
+## Autoload added by the plugin. Answers requests the editor sends to the
+## running game over the debugger session ("mcp:" messages):
+## capture_viewport saves the next rendered frame to a PNG, input injects
+## simulated actions, keys and mouse clicks, eval evaluates an expression
+## on a node and sends the result back ("mcp_debug:eval_result").
+
+## Frames a tapped action or key stays pressed, so is_action_just_pressed sees it
+const TAP_FRAMES = 2
+
+func _ready() -> void:
+	if EngineDebugger.is_active():
+		EngineDebugger.register_message_capture("mcp", _on_message)
+
+func _exit_tree() -> void:
+	if EngineDebugger.has_capture("mcp"):
+		EngineDebugger.unregister_message_capture("mcp")
+
+func _on_message(message: String, data: Array) -> bool:
+	match message:
+		"capture_viewport":
+			_capture_viewport(str(data[0]) if data.size() > 0 else "")
+			return true
+		"input":
+			if data.size() > 0 and data[0] is Dictionary:
+				_simulate_input(data[0])
+			return true
+		"eval":
+			if data.size() >= 3:
+				_eval(int(data[0]), str(data[1]), str(data[2]))
+			return true
+	return false
+
+func _capture_viewport(path: String) -> void:
+	if path.is_empty():
+		return
+	await RenderingServer.frame_post_draw
+	var image = get_viewport().get_texture().get_image()
+	if image == null or image.is_empty():
+		push_error("MCP: viewport capture failed")
+		return
+
+	DirAccess.make_dir_recursive_absolute(path.get_base_dir())
+	# Write then rename, so the server never reads a half-written file
+	var tmp_path = path + ".tmp"
+	if image.save_png(tmp_path) != OK:
+		push_error("MCP: failed to save " + tmp_path)
+		return
+	DirAccess.rename_absolute(tmp_path, path)
+
+func _eval(id: int, node_path: String, source: String) -> void:
+	EngineDebugger.send_message("mcp_debug:eval_result", [id, _evaluate(node_path, source)])
+
+## Absolute paths start at the root viewport, others at the current scene
+func _evaluate(node_path: String, source: String) -> Dictionary:
+	var base = get_tree().current_scene
+	if node_path.begins_with("/"):
+		base = get_tree().root
+	var node = base.get_node_or_null(node_path) if base else null
+	if node == null:
+		return {"error": "Node not found: " + node_path}
+
+	var expression = Expression.new()
+	if expression.parse(source) != OK:
+		return {"error": "Parse error: " + expression.get_error_text()}
+	var value = expression.execute([], node, false)
+	if expression.has_execute_failed():
+		return {"error": "Execution failed: " + expression.get_error_text()}
+
+	var result = {"success": true, "value": var_to_str(value), "type": type_string(typeof(value))}
+	if value == null or value is bool or value is int or value is float or value is String \
+			or value is StringName or value is Array or value is Dictionary:
+		result["json"] = JSON.stringify(value)
+	return result
+
+## Send the event; without "pressed" it is a tap: press, then release a few frames later
+func _simulate_input(params: Dictionary) -> void:
+	var pressed = params.get("pressed")
+	if pressed != null:
+		Input.parse_input_event(_input_event(params, pressed))
+		return
+
+	Input.parse_input_event(_input_event(params, true))
+	for i in TAP_FRAMES:
+		await get_tree().process_frame
+	Input.parse_input_event(_input_event(params, false))
+
+func _input_event(params: Dictionary, pressed: bool) -> InputEvent:
+	match params.get("type"):
+		"action":
+			var event = InputEventAction.new()
+			event.action = params["action"]
+			event.pressed = pressed
+			event.strength = float(params.get("strength", 1.0)) if pressed else 0.0
+			return event
+		"key":
+			var event = InputEventKey.new()
+			event.keycode = OS.find_keycode_from_string(params["key"])
+			event.physical_keycode = event.keycode
+			event.pressed = pressed
+			return event
+		_:
+			var event = InputEventMouseButton.new()
+			event.button_index = int(params.get("button", MOUSE_BUTTON_LEFT))
+			event.position = Vector2(float(params.get("x", 0.0)), float(params.get("y", 0.0)))
+			event.global_position = event.position
+			event.pressed = pressed
+			return event
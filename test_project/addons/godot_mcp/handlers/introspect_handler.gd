@@ -1,7 +1,8 @@
 @tool
 extends RefCounted
 ## Introspect Handler
-## Handles type introspection operations using ClassDB
+## Handles type introspection operations using ClassDB,
+## and class icons for clients drawing scene trees (get_class_icons)
 
 var plugin: EditorPlugin
 
@@ -14,6 +15,8 @@ func handle(command: String, params: Dictionary) -> Dictionary:
 			return _get_type_info(params)
 		"list_all_types":
 			return _list_all_types(params)
+		"get_class_icons":
+			return _get_class_icons(params)
 		_:
 			return {"error": "Unknown introspect command: " + command}
 
@@ -74,6 +77,63 @@ func _list_all_types(params: Dictionary) -> Dictionary:
 	}
 
 
+func _get_class_icons(params: Dictionary) -> Dictionary:
+	# Script classes (class_name) with their base class and @icon path
+	var script_classes = {}
+	for entry in ProjectSettings.get_global_class_list():
+		script_classes[str(entry["class"])] = entry
+
+	var theme = EditorInterface.get_editor_theme()
+	var icons = {}
+	for type_name in params.get("types", []):
+		type_name = str(type_name)
+		var base = ""
+		var icon: Texture2D = null
+		if script_classes.has(type_name):
+			var entry = script_classes[type_name]
+			base = str(entry["base"])
+			if str(entry.get("icon", "")) != "":
+				icon = load(entry["icon"])
+		elif ClassDB.class_exists(type_name):
+			base = ClassDB.get_parent_class(type_name)
+		else:
+			icons[type_name] = {"known": false}
+			continue
+
+		# Classes without an icon of their own show the nearest ancestor's
+		var native = type_name
+		while script_classes.has(native):
+			native = str(script_classes[native]["base"])
+		var ancestor = native
+		while icon == null and ancestor != "":
+			if theme.has_icon(ancestor, "EditorIcons"):
+				icon = theme.get_icon(ancestor, "EditorIcons")
+			ancestor = ClassDB.get_parent_class(ancestor)
+
+		var png = ""
+		if icon:
+			var image = icon.get_image()
+			if image and not image.is_empty():
+				png = Marshalls.raw_to_base64(image.save_png_to_buffer())
+		icons[type_name] = {
+			"known": true,
+			"base": base,
+			"category": _class_category(native),
+			"icon": png,
+		}
+	return {"success": true, "icons": icons}
+
+
+func _class_category(native: String) -> String:
+	if ClassDB.is_parent_class(native, "Node2D"):
+		return "2d"
+	if ClassDB.is_parent_class(native, "Node3D"):
+		return "3d"
+	if ClassDB.is_parent_class(native, "Control"):
+		return "control"
+	return "node"
+
+
 func _type_to_string(type: int) -> String:
 	match type:
 		TYPE_NIL: return "Nil"
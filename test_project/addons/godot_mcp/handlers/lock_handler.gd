@@ -0,0 +1,108 @@
+@tool
+extends RefCounted
+## Lock Handler
+## Coordinates file-based scene writes from the MCP server with scenes open in the editor:
+## get_scene_status, lock_scene, unlock_scene
+
+var plugin: EditorPlugin
+var _locks: Dictionary = {}
+var _dirty_scenes: Dictionary = {}
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+	plugin.scene_saved.connect(_on_scene_saved)
+	plugin.get_undo_redo().version_changed.connect(_on_version_changed)
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	match command:
+		"get_scene_status":
+			return _handle_get_scene_status(params)
+		"lock_scene":
+			return _handle_lock_scene(params)
+		"unlock_scene":
+			return _handle_unlock_scene(params)
+		_:
+			return {"error": "Unknown lock command: " + command}
+
+func _handle_get_scene_status(params: Dictionary) -> Dictionary:
+	var scene_path = params.get("scene_path", "")
+	if scene_path == "":
+		return {"error": "Scene path required"}
+
+	var open = _is_open(scene_path)
+	return {
+		"success": true,
+		"open": open,
+		"unsaved": open and _dirty_scenes.has(scene_path),
+		"locked": _locks.has(scene_path),
+	}
+
+func _handle_lock_scene(params: Dictionary) -> Dictionary:
+	var scene_path = params.get("scene_path", "")
+	var policy = params.get("policy", "refuse")
+	if scene_path == "":
+		return {"error": "Scene path required"}
+	if _locks.has(scene_path):
+		return {"error": "Scene is already locked: " + scene_path, "code": "SCENE_LOCKED"}
+
+	var open = _is_open(scene_path)
+	var unsaved = open and _dirty_scenes.has(scene_path)
+	var saved = false
+	if unsaved:
+		match policy:
+			"refuse":
+				return {
+					"error": "Scene has unsaved changes in the editor: " + scene_path,
+					"code": "SCENE_UNSAVED",
+				}
+			"force_save":
+				var err = _save_open_scene(scene_path)
+				if err != OK:
+					return {"error": "Failed to save scene: " + str(err), "code": "SCENE_SAVE_FAILED"}
+				saved = true
+			"reload_after_write":
+				# Editor changes are discarded when the scene is reloaded on unlock
+				pass
+
+	_locks[scene_path] = policy
+	return {"success": true, "open": open, "unsaved": unsaved, "saved": saved}
+
+func _handle_unlock_scene(params: Dictionary) -> Dictionary:
+	var scene_path = params.get("scene_path", "")
+	if not _locks.has(scene_path):
+		return {"error": "Scene is not locked: " + scene_path}
+	_locks.erase(scene_path)
+
+	var reloaded = false
+	if params.get("reload", false) and _is_open(scene_path):
+		EditorInterface.reload_scene_from_path(scene_path)
+		_dirty_scenes.erase(scene_path)
+		reloaded = true
+
+	return {"success": true, "reloaded": reloaded}
+
+func _is_open(scene_path: String) -> bool:
+	return scene_path in EditorInterface.get_open_scenes()
+
+func _save_open_scene(scene_path: String) -> int:
+	var root = EditorInterface.get_edited_scene_root()
+	var previous = root.scene_file_path if root else ""
+	if previous != scene_path:
+		EditorInterface.open_scene_from_path(scene_path)
+
+	var err = EditorInterface.save_scene()
+	if previous != "" and previous != scene_path:
+		EditorInterface.open_scene_from_path(previous)
+	if err == OK:
+		_dirty_scenes.erase(scene_path)
+	return err
+
+func _on_version_changed() -> void:
+	var root = EditorInterface.get_edited_scene_root()
+	if root and root.scene_file_path != "":
+		_dirty_scenes[root.scene_file_path] = true
+
+func _on_scene_saved(filepath: String) -> void:
+	_dirty_scenes.erase(filepath)
+	if _locks.has(filepath):
+		push_warning("Godot MCP: %s was saved while the MCP server is writing it" % filepath)
@@ -1,7 +1,10 @@
 @tool
 extends RefCounted
 ## Node Handler
-## Handles node operations: add, remove, duplicate, rename, reparent
+## Handles node operations: add, add at the editor's view, remove, duplicate, rename, reparent
+
+## Distance in front of the 3D editor camera when none is given
+const DEFAULT_CURSOR_DISTANCE = 5.0
 
 var plugin: EditorPlugin
 
@@ -12,6 +15,8 @@ func handle(command: String, params: Dictionary) -> Dictionary:
 	match command:
 		"add_node":
 			return _handle_add_node(params)
+		"add_node_at_cursor":
+			return _handle_add_node_at_cursor(params)
 		"remove_node":
 			return _handle_remove_node(params)
 		"duplicate_node":
@@ -53,6 +58,76 @@ func _handle_add_node(params: Dictionary) -> Dictionary:
 	
 	return {"success": true, "node_path": str(new_node.get_path())}
 
+func _handle_add_node_at_cursor(params: Dictionary) -> Dictionary:
+	var root = EditorInterface.get_edited_scene_root()
+	if not root:
+		return {"error": "No scene is open"}
+	
+	var parent_path = params.get("parent", ".")
+	var parent = root.get_node_or_null(parent_path) if parent_path != "." else root
+	if not parent:
+		return {"error": "Parent node not found: " + parent_path}
+	
+	var new_node = null
+	var scene_path = params.get("scene_path")
+	var node_type = params.get("node_type")
+	if scene_path:
+		var packed_scene = load(scene_path)
+		if not packed_scene is PackedScene:
+			return {"error": "Failed to load scene: " + scene_path}
+		new_node = packed_scene.instantiate(PackedScene.GEN_EDIT_STATE_INSTANCE)
+	elif node_type:
+		new_node = ClassDB.instantiate(node_type)
+		if new_node is Node:
+			new_node.name = node_type
+	if not new_node is Node:
+		return {"error": "Failed to create node: " + str(scene_path if scene_path else node_type)}
+	if params.get("name"):
+		new_node.name = params["name"]
+	
+	var position = null
+	if new_node is Node3D:
+		var camera = EditorInterface.get_editor_viewport_3d(0).get_camera_3d()
+		if not camera:
+			new_node.free()
+			return {"error": "3D editor camera not available"}
+		var distance = params.get("distance")
+		if distance == null:
+			distance = DEFAULT_CURSOR_DISTANCE
+		var target = camera.global_position - camera.global_transform.basis.z * float(distance)
+		new_node.position = parent.global_transform.affine_inverse() * target if parent is Node3D else target
+		position = [target.x, target.y, target.z]
+	elif new_node is Node2D or new_node is Control:
+		# Visible center in canvas coordinates, undoing the editor's pan and zoom
+		var viewport = EditorInterface.get_editor_viewport_2d()
+		var center = viewport.global_canvas_transform.affine_inverse() * (Vector2(viewport.size) / 2.0)
+		new_node.position = parent.get_global_transform().affine_inverse() * center if parent is CanvasItem else center
+		position = [center.x, center.y]
+	
+	# Undo/Redo support
+	var ur = plugin.get_undo_redo()
+	ur.create_action("Add Node at View via LLM: " + new_node.name)
+	ur.add_do_method(parent, "add_child", new_node, true)
+	ur.add_do_property(new_node, "owner", root)
+	ur.add_do_reference(new_node)
+	ur.add_undo_method(parent, "remove_child", new_node)
+	ur.commit_action()
+	
+	# Select it, as a drop from the FileSystem dock would
+	var selection = EditorInterface.get_selection()
+	selection.clear()
+	selection.add_node(new_node)
+	
+	var result = {
+		"success": true,
+		"node_path": str(root.get_path_to(new_node)),
+		"name": String(new_node.name),
+		"type": new_node.get_class(),
+	}
+	if position != null:
+		result["position"] = position
+	return result
+
 func _handle_remove_node(params: Dictionary) -> Dictionary:
 	var root = EditorInterface.get_edited_scene_root()
 	if not root:
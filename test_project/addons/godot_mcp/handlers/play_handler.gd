@@ -0,0 +1,97 @@
+@tool
+extends RefCounted
+## Play Handler
+## Controls the game run by the editor (Play / Play Scene / Stop buttons):
+## get_play_state, play_main_scene, play_current_scene, stop_playing_scene
+## Simulated input for playtests, forwarded to the game (game_bridge.gd):
+## send_input_action, send_key, send_mouse_click
+
+var plugin: EditorPlugin
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	match command:
+		"get_play_state":
+			return _handle_get_play_state(params)
+		"play_main_scene":
+			return _handle_play_main_scene(params)
+		"play_current_scene":
+			return _handle_play_current_scene(params)
+		"stop_playing_scene":
+			return _handle_stop_playing_scene(params)
+		"send_input_action":
+			return _handle_send_input_action(params)
+		"send_key":
+			return _handle_send_key(params)
+		"send_mouse_click":
+			return _handle_send_mouse_click(params)
+		_:
+			return {"error": "Unknown play command: " + command}
+
+func _handle_get_play_state(_params: Dictionary) -> Dictionary:
+	var playing = EditorInterface.is_playing_scene()
+	return {
+		"success": true,
+		"playing": playing,
+		"scene": EditorInterface.get_playing_scene() if playing else "",
+	}
+
+func _handle_play_main_scene(_params: Dictionary) -> Dictionary:
+	var main_scene = ProjectSettings.get_setting("application/run/main_scene", "")
+	if main_scene == "":
+		return {"error": "No main scene is set (application/run/main_scene)"}
+	
+	EditorInterface.play_main_scene()
+	return {"success": true, "scene": main_scene}
+
+func _handle_play_current_scene(_params: Dictionary) -> Dictionary:
+	var root = EditorInterface.get_edited_scene_root()
+	if not root:
+		return {"error": "No scene is open"}
+	if root.scene_file_path == "":
+		return {"error": "The current scene has not been saved"}
+	
+	EditorInterface.play_current_scene()
+	return {"success": true, "scene": root.scene_file_path}
+
+func _handle_stop_playing_scene(_params: Dictionary) -> Dictionary:
+	if not EditorInterface.is_playing_scene():
+		return {"success": true, "message": "Not playing"}
+	
+	EditorInterface.stop_playing_scene()
+	return {"success": true}
+
+func _handle_send_input_action(params: Dictionary) -> Dictionary:
+	var action = str(params.get("action", ""))
+	# Project actions (including the built-in ui_* ones) live in ProjectSettings
+	if not ProjectSettings.has_setting("input/" + action):
+		return {"error": "Unknown input action: " + action}
+	return _send_to_game({
+		"type": "action",
+		"action": action,
+		"pressed": params.get("pressed"),
+		"strength": params.get("strength", 1.0),
+	})
+
+func _handle_send_key(params: Dictionary) -> Dictionary:
+	var key = str(params.get("key", ""))
+	if OS.find_keycode_from_string(key) == KEY_NONE:
+		return {"error": "Unknown key: " + key}
+	return _send_to_game({"type": "key", "key": key, "pressed": params.get("pressed")})
+
+func _handle_send_mouse_click(params: Dictionary) -> Dictionary:
+	return _send_to_game({
+		"type": "mouse_click",
+		"x": params.get("x", 0.0),
+		"y": params.get("y", 0.0),
+		"button": params.get("button", MOUSE_BUTTON_LEFT),
+	})
+
+func _send_to_game(event: Dictionary) -> Dictionary:
+	var session = plugin.debugger_plugin.get_active_session()
+	if session == null or not session.is_active():
+		return {"error": "No game is running from the editor"}
+	session.send_message("mcp:input", [event])
+	return {"success": true}
@@ -0,0 +1,106 @@
+@tool
+extends RefCounted
+## Profiler Handler
+## Drives the editor's own Profiler panel (Debugger > Profiler), which receives
+## the script profiler's data from the running game:
+## start_profiling, stop_profiling, get_profile_report
+## The panel is not exposed to scripts; it is found by class name and its
+## function tree is read as displayed (average time, self).
+
+var plugin: EditorPlugin
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	var profiler = _find_profiler()
+	if not profiler:
+		return {"error": "Profiler panel not found in the editor"}
+	match command:
+		"start_profiling":
+			return _handle_start_profiling(profiler, params)
+		"stop_profiling":
+			return _handle_stop_profiling(profiler, params)
+		"get_profile_report":
+			return _handle_get_profile_report(profiler, params)
+		_:
+			return {"error": "Unknown profiler command: " + command}
+
+func _handle_start_profiling(profiler: Node, _params: Dictionary) -> Dictionary:
+	var start = _start_button(profiler)
+	if not start:
+		return {"error": "Profiler start button not found"}
+	# Average over the recorded frames, counting each function's own time
+	_select_option(profiler, "Average Time")
+	_select_option(profiler, "Self")
+	_press(profiler, "Clear")
+	if not start.button_pressed:
+		start.button_pressed = true
+	return {"success": true, "game_running": _game_running()}
+
+func _handle_stop_profiling(profiler: Node, _params: Dictionary) -> Dictionary:
+	var start = _start_button(profiler)
+	if not start:
+		return {"error": "Profiler start button not found"}
+	if start.button_pressed:
+		start.button_pressed = false
+	return {"success": true}
+
+func _handle_get_profile_report(profiler: Node, _params: Dictionary) -> Dictionary:
+	var start = _start_button(profiler)
+	var tree: Tree = _first_child_of_type(profiler, "Tree")
+	if not tree:
+		return {"error": "Profiler function list not found"}
+
+	var functions = []
+	var root = tree.get_root()
+	if root:
+		for category in root.get_children():
+			for item in category.get_children():
+				var script = item.get_metadata(1)
+				functions.append({
+					"category": category.get_text(0),
+					"name": item.get_text(0),
+					"script": script if script is String else "",
+					"line": int(item.get_metadata(2)) if item.get_metadata(2) != null else 0,
+					"time": item.get_text(1),
+					"calls": int(item.get_text(2)),
+				})
+	return {
+		"success": true,
+		"profiling": start != null and start.button_pressed,
+		"functions": functions,
+	}
+
+func _find_profiler() -> Node:
+	var found = EditorInterface.get_base_control().find_children("*", "EditorProfiler", true, false)
+	return found[0] if not found.is_empty() else null
+
+func _game_running() -> bool:
+	var session = plugin.debugger_plugin.get_active_session() if plugin.debugger_plugin else null
+	return session != null and session.is_active()
+
+## The Start/Stop toggle is the panel's only toggle button
+func _start_button(profiler: Node) -> Button:
+	for button in profiler.find_children("*", "Button", true, false):
+		if button.toggle_mode and not button is OptionButton and not button is CheckBox:
+			return button
+	return null
+
+func _press(profiler: Node, text: String) -> void:
+	for button in profiler.find_children("*", "Button", true, false):
+		if not button.toggle_mode and button.text == text:
+			button.pressed.emit()
+			return
+
+func _select_option(profiler: Node, text: String) -> void:
+	for option in profiler.find_children("*", "OptionButton", true, false):
+		for i in option.item_count:
+			if option.get_item_text(i) == text and option.selected != i:
+				option.select(i)
+				option.item_selected.emit(i)
+				return
+
+func _first_child_of_type(node: Node, type: String) -> Node:
+	var found = node.find_children("*", type, true, false)
+	return found[0] if not found.is_empty() else null
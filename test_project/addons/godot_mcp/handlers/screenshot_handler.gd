@@ -0,0 +1,147 @@
+@tool
+extends RefCounted
+## Screenshot Handler
+## Captures editor viewports with the screen-space bounds of requested nodes
+## (2D rects, projected 3D AABBs) for server-side annotation: capture_screenshot
+## Plain captures of an editor viewport or the running game: capture_viewport
+
+## Half size of the marker box around nodes without their own extent
+const MARKER_HALF_SIZE = 8.0
+
+var plugin: EditorPlugin
+
+func _init(p: EditorPlugin) -> void:
+	plugin = p
+
+func handle(command: String, params: Dictionary) -> Dictionary:
+	match command:
+		"capture_screenshot":
+			return _handle_capture_screenshot(params)
+		"capture_viewport":
+			return _handle_capture_viewport(params)
+		_:
+			return {"error": "Unknown screenshot command: " + command}
+
+func _handle_capture_screenshot(params: Dictionary) -> Dictionary:
+	var mode = params.get("viewport", "2d")
+	var viewport: Viewport
+	match mode:
+		"2d":
+			viewport = EditorInterface.get_editor_viewport_2d()
+		"3d":
+			viewport = EditorInterface.get_editor_viewport_3d(0)
+		"editor":
+			viewport = EditorInterface.get_base_control().get_viewport()
+		_:
+			return {"error": "Unknown viewport: " + str(mode)}
+
+	var image = viewport.get_texture().get_image()
+	if image == null or image.is_empty():
+		return {"error": "Viewport has not been rendered yet"}
+	image.convert(Image.FORMAT_RGBA8)
+
+	var root = EditorInterface.get_edited_scene_root()
+	var nodes = []
+	for node_path in params.get("node_paths", []):
+		nodes.append(_node_bounds(root, str(node_path), viewport, mode))
+
+	var theme = EditorInterface.get_editor_theme()
+	var accent = theme.get_color("accent_color", "Editor")
+	var base = theme.get_color("base_color", "Editor")
+	return {
+		"success": true,
+		"width": image.get_width(),
+		"height": image.get_height(),
+		"image": Marshalls.raw_to_base64(image.save_png_to_buffer()),
+		"theme": {"accent": "#" + accent.to_html(false), "dark": base.get_luminance() < 0.5},
+		"nodes": nodes,
+	}
+
+func _handle_capture_viewport(params: Dictionary) -> Dictionary:
+	var mode = params.get("viewport", "2d")
+	if mode == "game":
+		# The game saves the PNG itself (game_bridge.gd); the server waits for the file
+		var session = plugin.debugger_plugin.get_active_session()
+		if session == null or not session.is_active():
+			return {"error": "No game is running from the editor"}
+		session.send_message("mcp:capture_viewport", [params.get("path", "")])
+		return {"success": true, "pending": true}
+
+	var viewport: Viewport
+	match mode:
+		"2d":
+			viewport = EditorInterface.get_editor_viewport_2d()
+		"3d":
+			viewport = EditorInterface.get_editor_viewport_3d(0)
+		"editor":
+			viewport = EditorInterface.get_base_control().get_viewport()
+		_:
+			return {"error": "Unknown viewport: " + str(mode)}
+
+	var image = viewport.get_texture().get_image()
+	if image == null or image.is_empty():
+		return {"error": "Viewport has not been rendered yet"}
+	return {
+		"success": true,
+		"width": image.get_width(),
+		"height": image.get_height(),
+		"image": Marshalls.raw_to_base64(image.save_png_to_buffer()),
+	}
+
+func _node_bounds(root: Node, node_path: String, viewport: Viewport, mode: String) -> Dictionary:
+	var result = {"node_path": node_path, "found": false}
+	if root == null:
+		return result
+	var node = root if node_path == "." else root.get_node_or_null(node_path)
+	if node == null:
+		return result
+	result["found"] = true
+	result["name"] = String(node.name)
+
+	var rect = null
+	if mode == "2d" and node is CanvasItem:
+		rect = _canvas_item_rect(node)
+	elif mode == "3d" and node is Node3D:
+		rect = _projected_aabb(node, viewport)
+	if rect != null:
+		result["rect"] = [rect.position.x, rect.position.y, rect.size.x, rect.size.y]
+	return result
+
+func _canvas_item_rect(node: CanvasItem) -> Rect2:
+	var local: Rect2
+	if node is Control:
+		local = Rect2(Vector2.ZERO, node.size)
+	elif node.has_method("get_rect"):
+		local = node.get_rect()
+	else:
+		local = Rect2(-Vector2.ONE * MARKER_HALF_SIZE, Vector2.ONE * MARKER_HALF_SIZE * 2.0)
+
+	# Includes the editor's pan and zoom of the 2D viewport
+	var xform = node.get_global_transform_with_canvas()
+	var corners = [
+		local.position,
+		Vector2(local.end.x, local.position.y),
+		local.end,
+		Vector2(local.position.x, local.end.y),
+	]
+	var bounds = Rect2(xform * corners[0], Vector2.ZERO)
+	for corner in corners:
+		bounds = bounds.expand(xform * corner)
+	return bounds
+
+func _projected_aabb(node: Node3D, viewport: Viewport):
+	var camera = viewport.get_camera_3d()
+	if camera == null:
+		return null
+	var aabb = AABB(-Vector3.ONE * 0.5, Vector3.ONE)
+	if node is VisualInstance3D:
+		aabb = node.get_aabb()
+
+	var bounds = null
+	for i in 8:
+		var world = node.global_transform * aabb.get_endpoint(i)
+		if camera.is_position_behind(world):
+			continue
+		var point = camera.unproject_position(world)
+		bounds = Rect2(point, Vector2.ZERO) if bounds == null else bounds.expand(point)
+	return bounds
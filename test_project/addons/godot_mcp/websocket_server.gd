@@ -136,11 +136,16 @@ func _handle_message(peer_id: int, ws_peer: WebSocketPeer, message: String) -> v
 	var response_body: String
 	if parse_result == OK:
 		var data = json.data
+		var result: Dictionary
 		if command_handler:
-			var result = command_handler.handle_command(data)
-			response_body = JSON.stringify(result)
+			result = command_handler.handle_command(data)
+		else:
+			result = {"error": "Command handler not available"}
+		# Requests with an id get it back so the client can match responses
+		if data is Dictionary and data.has("id"):
+			response_body = JSON.stringify({"id": data["id"], "result": result})
 		else:
-			response_body = JSON.stringify({"error": "Command handler not available"})
+			response_body = JSON.stringify(result)
 	else:
 		response_body = JSON.stringify({"error": "Invalid JSON"})
 	
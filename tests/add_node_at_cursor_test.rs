@@ -0,0 +1,111 @@
+//! Add Node At Cursor Tests
+//!
+//! Tests for addNodeAtCursor, using a stub plugin that records the command it
+//! receives and answers with the placed node.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+
+use common::start_recording_stub_plugin;
+
+fn schema(port: u16) -> godot_mcp_rs::graphql::GqlSchema {
+    let dir = std::env::temp_dir();
+    build_schema_with_context(GqlContext::new(dir).with_port(port).with_timeout(2000))
+}
+
+const MUTATION: &str = r#"mutation($input: AddNodeAtCursorInput!) {
+    addNodeAtCursor(input: $input) {
+        success
+        node { name type path globalPosition { x y z } globalPosition2D { x y } }
+        error { code }
+    }
+}"#;
+
+async fn add_node_at_cursor(port: u16, input: serde_json::Value) -> serde_json::Value {
+    let request = async_graphql::Request::new(MUTATION).variables(
+        async_graphql::Variables::from_json(serde_json::json!({ "input": input })),
+    );
+    let result = schema(port).execute(request).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()["addNodeAtCursor"].clone()
+}
+
+#[tokio::test]
+async fn test_add_node_at_cursor_3d() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let response = serde_json::json!({
+        "success": true,
+        "node_path": "Props/Crate",
+        "name": "Crate",
+        "type": "MeshInstance3D",
+        "position": [1.0, 2.0, -5.0]
+    });
+    let port = start_recording_stub_plugin(response.to_string(), received.clone()).await;
+
+    let result = add_node_at_cursor(
+        port,
+        serde_json::json!({ "type": "MeshInstance3D", "name": "Crate", "parent": "Props", "distance": 3.5 }),
+    )
+    .await;
+    assert_eq!(result["success"], true);
+    assert_eq!(result["node"]["path"], "Props/Crate");
+    assert_eq!(result["node"]["type"], "MeshInstance3D");
+    assert_eq!(result["node"]["globalPosition"]["z"], -5.0);
+    assert!(result["node"]["globalPosition2D"].is_null());
+
+    let sent: serde_json::Value = serde_json::from_str(&received.lock().unwrap()[0]).unwrap();
+    assert_eq!(sent["command"], "add_node_at_cursor");
+    assert_eq!(sent["params"]["node_type"], "MeshInstance3D");
+    assert_eq!(sent["params"]["parent"], "Props");
+    assert_eq!(sent["params"]["distance"], 3.5);
+    assert!(sent["params"]["scene_path"].is_null());
+}
+
+#[tokio::test]
+async fn test_add_scene_at_cursor_2d() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let response = serde_json::json!({
+        "success": true,
+        "node_path": "Enemy",
+        "name": "Enemy",
+        "type": "CharacterBody2D",
+        "position": [320.0, 180.0]
+    });
+    let port = start_recording_stub_plugin(response.to_string(), received.clone()).await;
+
+    let result = add_node_at_cursor(
+        port,
+        serde_json::json!({ "scenePath": "res://enemies/enemy.tscn" }),
+    )
+    .await;
+    assert_eq!(result["node"]["globalPosition2D"]["x"], 320.0);
+    assert!(result["node"]["globalPosition"].is_null());
+
+    let sent: serde_json::Value = serde_json::from_str(&received.lock().unwrap()[0]).unwrap();
+    assert_eq!(sent["params"]["scene_path"], "res://enemies/enemy.tscn");
+    assert_eq!(sent["params"]["parent"], ".");
+}
+
+#[tokio::test]
+async fn test_add_node_at_cursor_errors() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let response = serde_json::json!({ "error": "No scene is open" });
+    let port = start_recording_stub_plugin(response.to_string(), received.clone()).await;
+
+    // type and scenePath are exclusive; nothing is sent to the plugin
+    let result = add_node_at_cursor(
+        port,
+        serde_json::json!({ "type": "Node3D", "scenePath": "res://a.tscn" }),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "INVALID_INPUT");
+    assert!(received.lock().unwrap().is_empty());
+
+    let result = add_node_at_cursor(port, serde_json::json!({ "type": "Node3D" })).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "NODE_ADD_FAILED");
+}
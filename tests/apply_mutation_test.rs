@@ -0,0 +1,287 @@
+//! Apply Mutation Tests
+//!
+//! Tests for applyMutation executing planned operations on scene and script
+//! files, previewMutation diffing the same edits and validateMutation
+//! checking them against the scenes.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Hud" type="CanvasLayer" parent="."]
+
+[node name="Old" type="Node2D" parent="."]
+"#;
+
+#[tokio::test]
+async fn test_apply_mutation_edits_scene_and_scripts() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation { applyMutation(input: { operations: [
+            { type: CREATE_SCRIPT, args: { path: "res://player.gd", extends: "CharacterBody2D" } },
+            { type: ADD_NODE, args: { scenePath: "res://level.tscn", parent: ".", name: "Player", type: "CharacterBody2D" } },
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Player", property: "position", value: "Vector2(16, 32)" } },
+            { type: ATTACH_SCRIPT, args: { scenePath: "res://level.tscn", nodePath: "Player", scriptPath: "res://player.gd" } },
+            { type: ADD_TO_GROUP, args: { scenePath: "res://level.tscn", nodePath: "Player", group: "players" } },
+            { type: CONNECT_SIGNAL, args: { scenePath: "res://level.tscn", from: "Player", signal: "ready", to: ".", method: "_on_player_ready" } },
+            { type: REPARENT_NODE, args: { scenePath: "res://level.tscn", nodePath: "Hud", newParent: "Player" } },
+            { type: REMOVE_NODE, args: { scenePath: "res://level.tscn", path: "Old" } }
+        ] }) { success appliedCount errors { operationIndex message } } }"#,
+    )
+    .await;
+    let result = &data["applyMutation"];
+    assert_eq!(result["errors"], serde_json::json!([]));
+    assert_eq!(result["success"], true);
+    assert_eq!(result["appliedCount"], 8);
+
+    let script = fs::read_to_string(dir.path().join("player.gd")).unwrap();
+    assert!(script.starts_with("extends CharacterBody2D"));
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains(r#"[ext_resource type="Script" path="res://player.gd" id="1"]"#));
+    assert!(content
+        .contains(r#"[node name="Player" type="CharacterBody2D" parent="." groups=["players"]]"#));
+    assert!(content.contains("position = Vector2(16, 32)"));
+    assert!(content.contains(r#"script = ExtResource("1")"#));
+    assert!(content.contains(r#"[node name="Hud" type="CanvasLayer" parent="Player"]"#));
+    assert!(content
+        .contains(r#"[connection signal="ready" from="Player" to="." method="_on_player_ready"]"#));
+    assert!(!content.contains("Old"));
+}
+
+#[tokio::test]
+async fn test_apply_mutation_reports_failed_operations() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation { applyMutation(input: { operations: [
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Missing", property: "visible", value: "false" } },
+            { type: SET_PROPERTIES, args: { scenePath: "res://level.tscn", nodePath: "Hud", properties: { layer: "2", visible: "false" } } },
+            { type: ADD_NODE, args: { parent: ".", name: "Orphan", type: "Node" } },
+            { type: ADD_NODE, args: { scenePath: "res://missing.tscn", parent: ".", name: "Lost", type: "Node" } }
+        ] }) { success appliedCount errors { operationIndex message } } }"#,
+    )
+    .await;
+    let result = &data["applyMutation"];
+    assert_eq!(result["success"], false);
+    assert_eq!(result["appliedCount"], 1);
+    let indexes: Vec<i64> = result["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["operationIndex"].as_i64().unwrap())
+        .collect();
+    assert_eq!(indexes, vec![0, 2, 3]);
+    assert_eq!(
+        result["errors"][1]["message"],
+        "Missing required argument: scenePath"
+    );
+
+    // The successful operation is still written
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains("layer = 2"));
+    assert!(content.contains("visible = false"));
+    assert!(!dir.path().join("missing.tscn").exists());
+}
+
+#[tokio::test]
+async fn test_preview_mutation_diffs_files() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation { previewMutation(input: { operations: [
+            { type: ADD_NODE, args: { scenePath: "res://level.tscn", parent: ".", name: "Player", type: "CharacterBody2D" } },
+            { type: SET_PROPERTIES, args: { scenePath: "res://level.tscn", nodePath: "Hud", properties: { layer: "2", visible: "false" } } },
+            { type: REMOVE_NODE, args: { scenePath: "res://level.tscn", path: "Missing" } },
+            { type: CREATE_SCRIPT, args: { path: "res://player.gd", extends: "CharacterBody2D" } }
+        ] }) {
+            success diff
+            affectedFiles { path changeType }
+            summary { nodesAdded nodesRemoved propertiesChanged signalsConnected }
+            errors { operationIndex }
+        } }"#,
+    )
+    .await;
+    let preview = &data["previewMutation"];
+    assert_eq!(preview["success"], false);
+    assert_eq!(
+        preview["errors"],
+        serde_json::json!([{ "operationIndex": 2 }])
+    );
+    assert_eq!(
+        preview["affectedFiles"],
+        serde_json::json!([
+            { "path": "res://level.tscn", "changeType": "MODIFIED" },
+            { "path": "res://player.gd", "changeType": "CREATED" }
+        ])
+    );
+    assert_eq!(
+        preview["summary"],
+        serde_json::json!({
+            "nodesAdded": 1,
+            "nodesRemoved": 0,
+            "propertiesChanged": 2,
+            "signalsConnected": 0
+        })
+    );
+
+    let diff = preview["diff"].as_str().unwrap();
+    assert!(diff.contains("--- res://level.tscn\n+++ res://level.tscn\n@@"));
+    assert!(diff.contains("+[node name=\"Player\" type=\"CharacterBody2D\" parent=\".\"]"));
+    assert!(diff.contains("+layer = 2"));
+    assert!(diff.contains("+++ res://player.gd\n@@ -0,0 +1,"));
+    assert!(diff.contains("+extends CharacterBody2D"));
+
+    // Nothing is written
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+    assert!(!dir.path().join("player.gd").exists());
+}
+
+#[tokio::test]
+async fn test_preview_mutation_keeps_property_order() {
+    let level = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+y_sort_enabled = true
+position = Vector2(10, 20)
+
+[node name="Player" type="CharacterBody2D" parent="."]
+position = Vector2(100, 200)
+rotation = 0.5
+scale = Vector2(2, 2)
+visible = false
+z_index = 3
+
+"#;
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), level).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let operations = r#"operations: [
+        { type: ADD_NODE, args: { scenePath: "res://level.tscn", parent: ".", name: "Hud", type: "CanvasLayer" } }
+    ]"#;
+
+    let data = execute(
+        &schema,
+        &format!(
+            "mutation {{ previewMutation(input: {{ {} }}) {{ diff }} }}",
+            operations
+        ),
+    )
+    .await;
+    let diff = data["previewMutation"]["diff"].as_str().unwrap();
+    let changed: Vec<&str> = diff
+        .lines()
+        .filter(|line| !line.starts_with("---") && !line.starts_with("+++"))
+        .filter(|line| line.starts_with('+') || line.starts_with('-'))
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            "+[node name=\"Hud\" type=\"CanvasLayer\" parent=\".\"]",
+            "+"
+        ],
+        "{}",
+        diff
+    );
+
+    execute(
+        &schema,
+        &format!(
+            "mutation {{ applyMutation(input: {{ {} }}) {{ success }} }}",
+            operations
+        ),
+    )
+    .await;
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        format!(
+            "{}[node name=\"Hud\" type=\"CanvasLayer\" parent=\".\"]\n\n",
+            level
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_validate_mutation_checks_scene() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("level.tscn"),
+        LEVEL.replace(
+            "[node name=\"Old\" type=\"Node2D\" parent=\".\"]",
+            "[node name=\"Old\" type=\"Node2D\" parent=\".\"]\nposition = Vector2(4, 8)\n\n[node name=\"Sprite\" type=\"Sprite2D\" parent=\"Old\"]",
+        ),
+    )
+    .unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation { validateMutation(input: { operations: [
+            { type: ADD_NODE, args: { scenePath: "res://level.tscn", parent: ".", name: "Player", type: "CharacterBody2D" } },
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Player", property: "visible", value: "false" } },
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Old", property: "position", value: "Vector3(1, 2, 3)" } },
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Old", property: "position", value: "Vector2(1, 2)" } },
+            { type: SET_PROPERTY, args: { scenePath: "res://level.tscn", nodePath: "Sprite", property: "visible", value: "false" } },
+            { type: ADD_NODE, args: { scenePath: "res://level.tscn", parent: ".", name: "Hud", type: "Node" } },
+            { type: REPARENT_NODE, args: { scenePath: "res://level.tscn", nodePath: "Old/Sprite", newParent: "Player" } },
+            { type: REMOVE_NODE, args: { scenePath: "res://missing.tscn", path: "Old" } },
+            { type: SET_PROPERTY, args: { nodePath: ".", property: "visible", value: "false" } }
+        ] }) { isValid errors { operationIndex code suggestion } warnings { operationIndex } } }"#,
+    )
+    .await;
+    let validation = &data["validateMutation"];
+    assert_eq!(validation["isValid"], false);
+    let errors: Vec<(i64, &str)> = validation["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["operationIndex"].as_i64().unwrap(),
+                e["code"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (2, "TYPE_MISMATCH"),
+            (4, "NODE_NOT_FOUND"),
+            (5, "DUPLICATE_NAME"),
+            (7, "SCENE_NOT_FOUND"),
+        ]
+    );
+    assert_eq!(
+        validation["errors"][1]["suggestion"],
+        "Did you mean 'Old/Sprite'?"
+    );
+    assert_eq!(
+        validation["warnings"],
+        serde_json::json!([{ "operationIndex": 8 }])
+    );
+
+    // Validation leaves the files alone
+    assert!(!fs::read_to_string(dir.path().join("level.tscn"))
+        .unwrap()
+        .contains("Player"));
+}
@@ -0,0 +1,213 @@
+//! Audio Import Tests
+//!
+//! Tests for audioAssets, setAudioLoop and applyAudioImportPreset: reading and
+//! editing the .import settings of WAV, Ogg Vorbis and MP3 files.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+use common::execute;
+
+const WAV_IMPORT: &str = r#"[remap]
+
+importer="wav"
+type="AudioStreamWAV"
+uid="uid://c1hit"
+path="res://.godot/imported/hit.wav-1c2d.sample"
+
+[deps]
+
+source_file="res://sfx/hit.wav"
+dest_files=["res://.godot/imported/hit.wav-1c2d.sample"]
+
+[params]
+
+force/8_bit=false
+force/mono=false
+edit/trim=false
+edit/normalize=false
+edit/loop_mode=0
+edit/loop_begin=0
+edit/loop_end=-1
+compress/mode=0
+"#;
+
+const OGG_IMPORT: &str = r#"[remap]
+
+importer="oggvorbisstr"
+type="AudioStreamOggVorbis"
+uid="uid://c2theme"
+path="res://.godot/imported/theme.ogg-9f8e.oggvorbisstr"
+
+[deps]
+
+source_file="res://music/theme.ogg"
+dest_files=["res://.godot/imported/theme.ogg-9f8e.oggvorbisstr"]
+
+[params]
+
+loop=false
+loop_offset=0
+bpm=0
+beat_count=0
+bar_beats=4
+"#;
+
+/// One second of 8 kHz mono 16-bit silence
+fn wav_bytes() -> Vec<u8> {
+    let data_len: u32 = 16000;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&8000u32.to_le_bytes());
+    wav.extend_from_slice(&16000u32.to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+fn setup(root: &Path) {
+    fs::create_dir_all(root.join("sfx")).unwrap();
+    fs::create_dir_all(root.join("music")).unwrap();
+    fs::write(root.join("sfx/hit.wav"), wav_bytes()).unwrap();
+    fs::write(root.join("sfx/hit.wav.import"), WAV_IMPORT).unwrap();
+    fs::write(root.join("sfx/new.wav"), wav_bytes()).unwrap();
+    fs::write(root.join("music/theme.ogg"), b"OggS").unwrap();
+    fs::write(root.join("music/theme.ogg.import"), OGG_IMPORT).unwrap();
+}
+
+#[tokio::test]
+async fn test_audio_assets_report_import_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        "{ audioAssets { path format importer imported loopMode loopEnd compression normalize durationSec } }",
+    )
+    .await;
+    let assets = data["audioAssets"].as_array().unwrap();
+    let paths: Vec<&str> = assets.iter().map(|a| a["path"].as_str().unwrap()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "res://music/theme.ogg",
+            "res://sfx/hit.wav",
+            "res://sfx/new.wav"
+        ]
+    );
+    assert_eq!(assets[0]["format"], "Ogg Vorbis");
+    assert_eq!(assets[0]["loopMode"], "DISABLED");
+    assert_eq!(assets[1]["importer"], "wav");
+    assert_eq!(assets[1]["loopMode"], "DETECT");
+    assert_eq!(assets[1]["loopEnd"], -1);
+    assert_eq!(assets[1]["compression"], "PCM");
+    assert_eq!(assets[1]["normalize"], false);
+    assert_eq!(assets[1]["durationSec"], 1.0);
+    assert_eq!(assets[2]["imported"], false);
+
+    let data = execute(
+        &schema,
+        r#"{ audioAssets(folder: "res://music") { path } }"#,
+    )
+    .await;
+    assert_eq!(data["audioAssets"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_set_audio_loop_writes_import_params() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            music: setAudioLoop(input: { path: "res://music/theme.ogg", mode: FORWARD, loopOffset: 2.5 }) {
+                success changes { path changed { key value } }
+            }
+            sfx: setAudioLoop(input: { path: "res://sfx/hit.wav", mode: PING_PONG, loopBegin: 400, loopEnd: 7600 }) {
+                success
+            }
+            invalid: setAudioLoop(input: { path: "res://music/theme.ogg", mode: BACKWARD }) {
+                success error { code }
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["music"]["success"], true);
+    assert_eq!(
+        data["music"]["changes"][0]["changed"],
+        serde_json::json!([
+            { "key": "loop", "value": "true" },
+            { "key": "loop_offset", "value": "2.5" },
+        ])
+    );
+    assert_eq!(data["sfx"]["success"], true);
+    assert_eq!(data["invalid"]["success"], false);
+    assert_eq!(data["invalid"]["error"]["code"], "AUDIO_LOOP_UNSUPPORTED");
+
+    let ogg = fs::read_to_string(dir.path().join("music/theme.ogg.import")).unwrap();
+    assert!(ogg.contains("[params]\n\nloop=true\nloop_offset=2.5\nbpm=0"));
+    assert!(ogg.starts_with("[remap]\n\nimporter=\"oggvorbisstr\""));
+    let wav = fs::read_to_string(dir.path().join("sfx/hit.wav.import")).unwrap();
+    assert!(wav.contains("edit/loop_mode=3\nedit/loop_begin=400\nedit/loop_end=7600\n"));
+}
+
+#[tokio::test]
+async fn test_apply_audio_import_preset_to_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    setup(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let mutation = |dry_run: bool| {
+        format!(
+            r#"mutation {{
+                applyAudioImportPreset(input: {{
+                    folder: "res://",
+                    preset: {{ compression: QOA, normalize: true, loopMode: DISABLED }},
+                    dryRun: {}
+                }}) {{ success dryRun changes {{ path changed {{ key value }} }} skipped }}
+            }}"#,
+            dry_run
+        )
+    };
+
+    let data = execute(&schema, &mutation(true)).await;
+    let result = &data["applyAudioImportPreset"];
+    assert_eq!(result["dryRun"], true);
+    assert_eq!(result["skipped"], serde_json::json!(["res://sfx/new.wav"]));
+    // The Ogg file already doesn't loop, so only the WAV changes
+    let changes = result["changes"].as_array().unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0]["path"], "res://sfx/hit.wav");
+    assert_eq!(
+        changes[0]["changed"],
+        serde_json::json!([
+            { "key": "compress/mode", "value": "2" },
+            { "key": "edit/normalize", "value": "true" },
+            { "key": "edit/loop_mode", "value": "1" },
+        ])
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("sfx/hit.wav.import")).unwrap(),
+        WAV_IMPORT
+    );
+
+    let data = execute(&schema, &mutation(false)).await;
+    assert_eq!(data["applyAudioImportPreset"]["success"], true);
+    let wav = fs::read_to_string(dir.path().join("sfx/hit.wav.import")).unwrap();
+    assert!(wav.contains("edit/normalize=true\nedit/loop_mode=1\n"));
+    assert!(wav.contains("compress/mode=2\n"));
+    assert!(!dir.path().join("sfx/new.wav.import").exists());
+}
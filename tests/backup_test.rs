@@ -0,0 +1,92 @@
+//! Backup Tests
+//!
+//! Tests for the backups taken before file writes, listBackups and
+//! restoreBackup.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::path::Path;
+
+use common::execute;
+
+fn schema(root: &Path) -> GqlSchema {
+    build_schema_with_context(GqlContext::new(root.to_path_buf()).with_session("s-backup"))
+}
+
+const BACKUPS: &str = "{ listBackups { id operation description files { path created } } }";
+
+#[tokio::test]
+async fn test_writes_are_backed_up_and_restored() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = schema(dir.path());
+    execute(
+        &schema,
+        r#"mutation {
+            createScene(input: { path: "res://player.tscn", rootName: "Player", rootType: "Node2D" }) { success }
+        }"#,
+    )
+    .await;
+    let created = std::fs::read_to_string(dir.path().join("player.tscn")).unwrap();
+    execute(
+        &schema,
+        r#"mutation {
+            setNodeMetadata(input: { scenePath: "res://player.tscn", nodePath: ".", key: "todo", value: "add sprite" }) { success }
+        }"#,
+    )
+    .await;
+
+    let data = execute(&schema, BACKUPS).await;
+    let backups = data["listBackups"].as_array().unwrap();
+    assert_eq!(backups.len(), 2);
+    assert_eq!(backups[0]["operation"], "createScene");
+    assert_eq!(backups[0]["files"][0]["path"], "res://player.tscn");
+    assert_eq!(backups[0]["files"][0]["created"], true);
+    assert_eq!(backups[1]["operation"], "setNodeMetadata");
+    assert_eq!(backups[1]["files"][0]["created"], false);
+
+    // Undo the metadata edit
+    let id = backups[1]["id"].as_str().unwrap();
+    let data = execute(
+        &schema,
+        &format!(
+            r#"mutation {{ restoreBackup(id: "{}") {{ success restored deleted backup {{ id }} }} }}"#,
+            id
+        ),
+    )
+    .await;
+    let restore = &data["restoreBackup"];
+    assert_eq!(restore["success"], true);
+    assert_eq!(restore["restored"][0], "res://player.tscn");
+    assert_eq!(restore["backup"]["id"], id);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("player.tscn")).unwrap(),
+        created
+    );
+
+    // Undo the scene creation
+    let id = backups[0]["id"].as_str().unwrap();
+    let data = execute(
+        &schema,
+        &format!(
+            r#"mutation {{ restoreBackup(id: "{}") {{ success deleted }} }}"#,
+            id
+        ),
+    )
+    .await;
+    assert_eq!(data["restoreBackup"]["success"], true);
+    assert_eq!(data["restoreBackup"]["deleted"][0], "res://player.tscn");
+    assert!(!dir.path().join("player.tscn").exists());
+}
+
+#[tokio::test]
+async fn test_restore_unknown_backup_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = execute(
+        &schema(dir.path()),
+        r#"mutation { restoreBackup(id: "bk-0") { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["restoreBackup"]["success"], false);
+    assert_eq!(data["restoreBackup"]["error"]["code"], "BACKUP_NOT_FOUND");
+}
@@ -0,0 +1,217 @@
+//! Branch View Tests
+//!
+//! Tests for branchView: a temporary git repository whose project folder has
+//! committed scenes on `main` and uncommitted changes on a work branch.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use common::write;
+
+const MAIN_SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://scripts/player.gd" id="1"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1")
+
+[node name="Sprite" type="Sprite2D" parent="."]
+"#;
+
+fn git_available() -> bool {
+    Command::new("git").arg("--version").output().is_ok()
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+}
+
+/// Repository with the Godot project in `game/`; `main` holds the committed
+/// state and the checked out `work` branch has uncommitted edits
+fn repository() -> tempfile::TempDir {
+    let repo = tempfile::tempdir().unwrap();
+    let root = repo.path();
+    let game = root.join("game");
+    write(root, "README.md", "# Game\n");
+    write(&game, "project.godot", "config_version=5\n");
+    write(&game, "scenes/player.tscn", MAIN_SCENE);
+    write(
+        &game,
+        "scripts/player.gd",
+        "extends CharacterBody2D\n\nvar speed = 100\n",
+    );
+    write(
+        &game,
+        "data/stats.tres",
+        "[gd_resource type=\"Resource\" format=3]\n\n[resource]\nhealth = 10\n",
+    );
+    write(&game, ".godot/editor/cache.cfg", "[cache]\n");
+    git(root, &["init", "--quiet", "--initial-branch=main"]);
+    git(root, &["add", "."]);
+    git(root, &["commit", "--quiet", "-m", "initial"]);
+    git(root, &["tag", "v1"]);
+    git(root, &["checkout", "--quiet", "-b", "work"]);
+
+    write(
+        &game,
+        "scenes/player.tscn",
+        &format!(
+            "{}\n[node name=\"Hitbox\" type=\"Area2D\" parent=\".\"]\n",
+            MAIN_SCENE
+        ),
+    );
+    write(
+        &game,
+        "scripts/player.gd",
+        "extends CharacterBody2D\n\nvar speed = 250\n",
+    );
+    write(
+        &game,
+        "scenes/enemy.tscn",
+        "[gd_scene format=3]\n\n[node name=\"Enemy\" type=\"Node2D\"]\n",
+    );
+    repo
+}
+
+async fn query(project: &Path, query: &str) -> Value {
+    let schema = build_schema_with_context(GqlContext::new(project.to_path_buf()));
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn test_branch_view_reads_committed_scene() {
+    if !git_available() {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    let repo = repository();
+    let game = repo.path().join("game");
+
+    let data = query(
+        &game,
+        r#"{ branchView(gitRef: "main") {
+            gitRef commit fileCount error { code }
+            scene(path: "res://scenes/player.tscn") { allNodes { name } }
+            script(path: "res://scripts/player.gd") { variables { name defaultValue } }
+            resource(path: "res://data/stats.tres") { type }
+        } }"#,
+    )
+    .await;
+    let view = &data["branchView"];
+    assert!(view["error"].is_null(), "{}", view);
+    assert_eq!(view["gitRef"], "main");
+    assert_eq!(view["commit"].as_str().unwrap().len(), 40);
+    // project.godot, the scene, the script and the resource; not README.md
+    // (outside the project) or .godot/
+    assert_eq!(view["fileCount"], 4);
+
+    let nodes: Vec<_> = view["scene"]["allNodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(nodes, ["Player", "Sprite"]);
+    assert_eq!(view["script"]["variables"][0]["defaultValue"], "100");
+    assert_eq!(view["resource"]["type"], "Resource");
+
+    // The working directory keeps its edits
+    let data = query(
+        &game,
+        r#"{ scene(path: "res://scenes/player.tscn") { allNodes { name } } }"#,
+    )
+    .await;
+    assert_eq!(data["scene"]["allNodes"].as_array().unwrap().len(), 3);
+    assert!(fs::read_to_string(game.join("scripts/player.gd"))
+        .unwrap()
+        .contains("250"));
+    assert!(!game.join(".godot-mcp").exists());
+}
+
+#[tokio::test]
+async fn test_branch_view_project_listing() {
+    if !git_available() {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    let repo = repository();
+
+    let data = query(
+        &repo.path().join("game"),
+        r#"{ branchView(gitRef: "v1") {
+            project { scenes { path } }
+            scene(path: "res://scenes/enemy.tscn") { path }
+        } }"#,
+    )
+    .await;
+    let scenes = data["branchView"]["project"]["scenes"].as_array().unwrap();
+    assert_eq!(scenes.len(), 1);
+    assert_eq!(scenes[0]["path"], "res://scenes/player.tscn");
+    // Created after the tag, never committed
+    assert!(data["branchView"]["scene"].is_null());
+}
+
+#[tokio::test]
+async fn test_branch_view_unknown_ref() {
+    if !git_available() {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    let repo = repository();
+
+    let data = query(
+        &repo.path().join("game"),
+        r#"{ branchView(gitRef: "no-such-branch") {
+            commit fileCount error { code } project { name }
+        } }"#,
+    )
+    .await;
+    let view = &data["branchView"];
+    assert_eq!(view["error"]["code"], "GIT_REF_NOT_FOUND");
+    assert!(view["commit"].is_null());
+    assert_eq!(view["fileCount"], 0);
+    assert!(view["project"].is_null());
+}
+
+#[tokio::test]
+async fn test_branch_view_rejects_option_like_ref() {
+    let project = tempfile::tempdir().unwrap();
+    let data = query(
+        project.path(),
+        r#"{ branchView(gitRef: "--output=x") { error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["branchView"]["error"]["code"], "INVALID_INPUT");
+}
+
+#[tokio::test]
+async fn test_branch_view_outside_repository() {
+    if !git_available() {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    let project = tempfile::tempdir().unwrap();
+    write(project.path(), "project.godot", "config_version=5\n");
+    // Keep git from finding a repository further up
+    std::env::set_var("GIT_CEILING_DIRECTORIES", std::env::temp_dir());
+
+    let data = query(
+        project.path(),
+        r#"{ branchView(gitRef: "main") { error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["branchView"]["error"]["code"], "GIT_FAILED");
+}
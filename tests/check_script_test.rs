@@ -0,0 +1,113 @@
+//! checkScript Tests
+//!
+//! Tests for the checkScript mutation against a stand-in Godot executable
+//! that reports parse errors the way Godot 4 prints them.
+
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::Path;
+
+use common::{fake_godot, write};
+
+/// Fails scripts containing `foo(` with a parse error on the line it's on,
+/// and warns about `var unused`
+const FAKE_GODOT: &str = r#"#!/bin/sh
+project=""
+script=""
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --path) project="$2"; shift ;;
+    --script) script="$2"; shift ;;
+  esac
+  shift
+done
+file="$project/${script#res://}"
+echo "Godot Engine v4.3.stable.official.77dcf97d8 - https://godotengine.org"
+warning=$(grep -n "var unused" "$file" | cut -d: -f1)
+if [ -n "$warning" ]; then
+  echo "SCRIPT WARNING: The local variable \"unused\" is declared but never used in the block." >&2
+  echo "          at: GDScript::reload ($script:$warning)" >&2
+fi
+error=$(grep -n "foo(" "$file" | cut -d: -f1)
+if [ -n "$error" ]; then
+  echo "SCRIPT ERROR: Parse Error: Function \"foo()\" not found in base self." >&2
+  echo "          at: GDScript::reload ($script:$error)" >&2
+  echo "ERROR: Failed to load script \"$script\" with error \"Parse error\"." >&2
+  echo "   at: load (modules/gdscript/gdscript.cpp:2936)" >&2
+  exit 1
+fi
+exit 0
+"#;
+
+async fn check_script(root: &Path, path: &str) -> serde_json::Value {
+    fake_godot(FAKE_GODOT);
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!(
+        r#"mutation {{ checkScript(path: "{}") {{
+            success valid godot scriptsChecked
+            diagnostics {{ file line severity code message }}
+            error {{ code message }}
+        }} }}"#,
+        path
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["checkScript"].clone()
+}
+
+#[tokio::test]
+async fn test_check_script_reports_parse_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(
+        dir.path(),
+        "scripts/player.gd",
+        "extends Node\n\nfunc _ready():\n\tfoo()\n",
+    );
+    write(
+        dir.path(),
+        "scripts/enemy.gd",
+        "extends Node\n\nfunc _ready():\n\tvar unused = 1\n",
+    );
+
+    let result = check_script(dir.path(), "res://scripts/player.gd").await;
+    assert_eq!(result["success"], true, "{}", result["error"]);
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["scriptsChecked"], 1);
+    assert_eq!(
+        result["godot"],
+        fake_godot(FAKE_GODOT).display().to_string()
+    );
+    assert_eq!(
+        result["diagnostics"],
+        serde_json::json!([{
+            "file": "res://scripts/player.gd",
+            "line": 4,
+            "severity": "ERROR",
+            "code": "parse-error",
+            "message": "Function \"foo()\" not found in base self.",
+        }])
+    );
+
+    // A folder checks every script in it; warnings don't fail the check
+    write(dir.path(), "scripts/player.gd", "extends Node\n");
+    let result = check_script(dir.path(), "res://scripts").await;
+    assert_eq!(result["valid"], true);
+    assert_eq!(result["scriptsChecked"], 2);
+    assert_eq!(result["diagnostics"][0]["file"], "res://scripts/enemy.gd");
+    assert_eq!(result["diagnostics"][0]["line"], 4);
+    assert_eq!(result["diagnostics"][0]["severity"], "WARNING");
+}
+
+#[tokio::test]
+async fn test_check_script_missing_script() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+
+    let result = check_script(dir.path(), "res://missing.gd").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "SCRIPT_NOT_FOUND");
+}
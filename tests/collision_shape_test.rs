@@ -0,0 +1,182 @@
+//! Collision Shape Tests
+//!
+//! Tests for generateCollisionShape: shapes fitted to primitive meshes, OBJ
+//! files and sprite textures, placed next to the source node.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use godot_mcp_rs::screenshot::{Canvas, Rect};
+use std::fs;
+
+use common::execute;
+
+const PLAYER: &str = r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="ArrayMesh" path="res://crate.obj" id="1_crate"]
+
+[sub_resource type="CapsuleMesh" id="CapsuleMesh_a"]
+radius = 0.4
+height = 1.8
+
+[node name="Player" type="CharacterBody3D"]
+
+[node name="Body" type="MeshInstance3D" parent="."]
+transform = Transform3D(1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0.9, 0)
+mesh = SubResource("CapsuleMesh_a")
+
+[node name="Crate" type="MeshInstance3D" parent="."]
+mesh = ExtResource("1_crate")
+"#;
+
+const CRATE_OBJ: &str = "v 0 0 0\nv 2 0 0\nv 2 1 0\nv 0 1 0\nf 1 2 3 4\n";
+
+const COIN: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Texture2D" path="res://coin.png" id="1_coin"]
+
+[node name="Coin" type="Area2D"]
+
+[node name="Sprite" type="Sprite2D" parent="."]
+position = Vector2(10, 0)
+texture = ExtResource("1_coin")
+centered = false
+
+[node name="Label" type="Label" parent="."]
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("player.tscn"), PLAYER).unwrap();
+    fs::write(dir.path().join("crate.obj"), CRATE_OBJ).unwrap();
+    fs::write(dir.path().join("coin.tscn"), COIN).unwrap();
+
+    // 8x4 texture with an opaque 4x2 block
+    let mut image = Canvas::new(8, 4);
+    image.fill_rect(
+        Rect {
+            x: 2,
+            y: 1,
+            width: 4,
+            height: 2,
+        },
+        [255, 200, 0],
+    );
+    fs::write(dir.path().join("coin.png"), image.encode_png().unwrap()).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_mesh_collision_shapes() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            capsule: generateCollisionShape(input: { scenePath: "res://player.tscn", nodePath: "Body", strategy: CAPSULE_FIT }) {
+                success nodePath shapeType warnings
+            }
+            trimesh: generateCollisionShape(input: { scenePath: "res://player.tscn", nodePath: "Crate", strategy: TRIMESH }) {
+                success nodePath shapeType warnings
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(
+        data["capsule"],
+        serde_json::json!({
+            "success": true,
+            "nodePath": "CollisionShape3D",
+            "shapeType": "CapsuleShape3D",
+            "warnings": []
+        })
+    );
+    assert_eq!(data["trimesh"]["nodePath"], "CollisionShape3D2");
+    assert_eq!(data["trimesh"]["shapeType"], "ConcavePolygonShape3D");
+
+    let tscn = fs::read_to_string(dir.path().join("player.tscn")).unwrap();
+    assert!(tscn.contains("[sub_resource type=\"CapsuleShape3D\" id=\"CapsuleShape3D_1\"]"));
+    assert!(tscn.contains("radius = 0.4"));
+    assert!(tscn.contains("height = 1.8"));
+    // The capsule is centered on the mesh, so it takes the mesh's transform as is
+    assert_eq!(
+        tscn.matches("transform = Transform3D(1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0.9, 0)")
+            .count(),
+        2
+    );
+    assert!(tscn.contains(
+        "data = PackedVector3Array(0, 0, 0, 2, 0, 0, 2, 1, 0, 0, 0, 0, 2, 1, 0, 0, 1, 0)"
+    ));
+    assert!(
+        tscn.contains("[node name=\"CollisionShape3D2\" type=\"CollisionShape3D\" parent=\".\"]")
+    );
+}
+
+#[tokio::test]
+async fn test_sprite_collision_shapes() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            rect: generateCollisionShape(input: { scenePath: "res://coin.tscn", nodePath: "Sprite", strategy: RECT_FROM_SPRITE }) {
+                success nodePath shapeType
+            }
+            convex: generateCollisionShape(input: { scenePath: "res://coin.tscn", nodePath: "Sprite", strategy: CONVEX }) {
+                success nodePath shapeType
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["rect"]["nodePath"], "CollisionShape2D");
+    assert_eq!(data["rect"]["shapeType"], "RectangleShape2D");
+    assert_eq!(data["convex"]["nodePath"], "CollisionShape2D2");
+
+    let tscn = fs::read_to_string(dir.path().join("coin.tscn")).unwrap();
+    assert!(tscn.contains("size = Vector2(8, 4)"));
+    // Not centered: the rectangle's center is half the frame away from the sprite's origin
+    assert!(tscn.contains("position = Vector2(14, 2)"));
+    // The outline covers the opaque block only, in the sprite's space
+    assert!(tscn.contains("points = PackedVector2Array(2, 1, 6, 1, 6, 3, 2, 3)"));
+    assert_eq!(tscn.matches("position = Vector2(10, 0)").count(), 2);
+}
+
+#[tokio::test]
+async fn test_collision_shape_errors_and_warnings() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("loose.tscn"),
+        "[gd_scene format=3]\n\n[sub_resource type=\"BoxMesh\" id=\"BoxMesh_a\"]\n\n[node name=\"Root\" type=\"Node3D\"]\n\n[node name=\"Box\" type=\"MeshInstance3D\" parent=\".\"]\nmesh = SubResource(\"BoxMesh_a\")\n",
+    )
+    .unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            label: generateCollisionShape(input: { scenePath: "res://coin.tscn", nodePath: "Label", strategy: CONVEX }) { success error { code } }
+            trimesh: generateCollisionShape(input: { scenePath: "res://coin.tscn", nodePath: "Sprite", strategy: TRIMESH }) { success error { code } }
+            missing: generateCollisionShape(input: { scenePath: "res://coin.tscn", nodePath: "Missing", strategy: CONVEX }) { success error { code } }
+            loose: generateCollisionShape(input: { scenePath: "res://loose.tscn", nodePath: "Box", strategy: CONVEX }) { success shapeType warnings }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["label"]["error"]["code"], "UNSUPPORTED_NODE_TYPE");
+    assert_eq!(data["trimesh"]["error"]["code"], "INVALID_STRATEGY");
+    assert_eq!(data["missing"]["error"]["code"], "NODE_NOT_FOUND");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("coin.tscn")).unwrap(),
+        COIN
+    );
+
+    assert_eq!(data["loose"]["success"], true);
+    assert_eq!(data["loose"]["shapeType"], "ConvexPolygonShape3D");
+    assert_eq!(
+        data["loose"]["warnings"],
+        serde_json::json!([
+            "'.' is a Node3D, not a physics body or area; move the CollisionShape3D under one"
+        ])
+    );
+}
@@ -0,0 +1,158 @@
+//! Helpers shared by the integration tests
+//!
+//! Each test crate compiles its own copy and uses only some of them.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Run `query` against `schema`, failing on GraphQL errors; returns its data
+pub async fn execute(schema: &GqlSchema, query: &str) -> Value {
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+/// Run `query` against a new schema for the project at `root`
+pub async fn execute_in(root: &Path, query: &str) -> Value {
+    execute(
+        &build_schema_with_context(GqlContext::new(root.to_path_buf())),
+        query,
+    )
+    .await
+}
+
+/// Start a stub plugin answering every command with `response`; returns its port
+pub async fn start_stub_plugin(response: impl Into<String>) -> u16 {
+    let response = response.into();
+    start_stub_plugin_with(move |_| response.clone()).await
+}
+
+/// Start a stub plugin answering every command with `response`; request
+/// bodies are pushed to `received`. Returns its port
+pub async fn start_recording_stub_plugin(
+    response: impl Into<String>,
+    received: Arc<Mutex<Vec<String>>>,
+) -> u16 {
+    let response = response.into();
+    start_stub_plugin_with(move |body| {
+        received.lock().unwrap().push(body.to_string());
+        response.clone()
+    })
+    .await
+}
+
+/// Start a stub plugin answering each command with `respond(body)`; returns its port
+pub async fn start_stub_plugin_with<F>(mut respond: F) -> u16
+where
+    F: FnMut(&str) -> String + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let mut body = String::new();
+            // Read headers and body
+            loop {
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        body = text[header_end + 4..].to_string();
+                        break;
+                    }
+                }
+            }
+
+            let response = respond(&body);
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                response.len(),
+                response
+            );
+            let _ = socket.write_all(reply.as_bytes()).await;
+        }
+    });
+
+    port
+}
+
+/// Start a stub plugin serving the WebSocket channel: `respond` gets each
+/// request and returns the replies to send, and whether to close the
+/// connection after them. Returns its port
+pub async fn start_ws_stub_plugin<F>(mut respond: F) -> u16
+where
+    F: FnMut(Value) -> (Vec<Value>, bool) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let (replies, close) = respond(serde_json::from_str(&text).unwrap());
+                for reply in replies {
+                    ws.send(Message::Text(reply.to_string())).await.unwrap();
+                }
+                if close {
+                    let _ = ws.close(None).await;
+                    break;
+                }
+            }
+        }
+    });
+
+    port
+}
+
+/// Write `content` to `path` under `root`, creating its folders
+pub fn write(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Install `script` as the stand-in Godot shared by every test in the
+/// calling file; later calls return the same executable
+#[cfg(unix)]
+pub fn fake_godot(script: &str) -> &'static Path {
+    use godot_mcp_rs::graphql::godot_binary;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+
+    static GODOT: OnceLock<PathBuf> = OnceLock::new();
+    GODOT.get_or_init(|| {
+        let dir = tempfile::tempdir().unwrap().keep();
+        let path = dir.join("godot");
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        godot_binary::set_override(Some(path.clone()));
+        path
+    })
+}
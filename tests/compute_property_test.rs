@@ -0,0 +1,92 @@
+//! Compute Property Tests
+//!
+//! Tests for computeProperty: expressions over node properties written as concrete values.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Door" type="Node2D" parent="."]
+position = Vector2(100, 40)
+
+[node name="Player" type="CharacterBody2D" parent="."]
+speed = 2.5
+
+[node name="Key" type="Sprite2D" parent="Player"]
+"#;
+
+async fn compute(
+    root: &Path,
+    node_path: &str,
+    property: &str,
+    expression: &str,
+) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let request = async_graphql::Request::new(
+        r#"mutation($input: ComputePropertyInput!) {
+            computeProperty(input: $input) {
+                success value previous
+                error { code message }
+            }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(serde_json::json!({
+        "input": {
+            "scenePath": "res://level.tscn",
+            "nodePath": node_path,
+            "property": property,
+            "expression": expression,
+        }
+    })));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["computeProperty"].clone()
+}
+
+#[tokio::test]
+async fn test_compute_property_relative_placement() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let result = compute(
+        dir.path(),
+        "Player",
+        "position",
+        r#"$"../Door".position + Vector2(32, 0) * speed"#,
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["value"], "Vector2(180, 40)");
+    assert!(result["previous"].is_null());
+
+    // Unset transform properties fall back to engine defaults
+    let result = compute(dir.path(), "Player/Key", "scale", "$\"..\".scale * 2").await;
+    assert_eq!(result["value"], "Vector2(2, 2)");
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains("position = Vector2(180, 40)"));
+    assert!(content.contains("scale = Vector2(2, 2)"));
+}
+
+#[tokio::test]
+async fn test_compute_property_reports_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let result = compute(dir.path(), "Player", "position", "$Missing.position").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "EXPRESSION_ERROR");
+
+    let result = compute(dir.path(), "Player", "position", "Vector2(1, 2) +").await;
+    assert_eq!(result["error"]["code"], "EXPRESSION_ERROR");
+
+    let result = compute(dir.path(), "Nobody", "position", "1").await;
+    assert_eq!(result["error"]["code"], "VALIDATION_NODE_NOT_FOUND");
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert_eq!(content, LEVEL);
+}
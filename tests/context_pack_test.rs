@@ -0,0 +1,174 @@
+//! Context Pack Tests
+//!
+//! Tests for packContext: gatherContext output packed into one bundle under
+//! a token budget, lowering file detail as needed.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+use std::path::Path;
+
+const PLAYER_SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1")
+
+[node name="Sprite" type="Sprite2D" parent="."]
+
+[node name="Hitbox" type="Area2D" parent="." groups=["hurtbox"]]
+
+[connection signal="body_entered" from="Hitbox" to="." method="_on_hit"]
+"#;
+
+const PLAYER_SCRIPT: &str = r#"extends CharacterBody2D
+## The player character
+
+const Weapon = preload("res://weapon.gd")
+
+# Movement speed in pixels per second
+@export var speed := 200.0
+var weapon := Weapon.new()
+
+func _physics_process(delta: float) -> void:
+	# Read input
+	var direction := Input.get_vector("left", "right", "up", "down")
+	velocity = direction * speed
+	if Input.is_action_just_pressed("fire"):
+		weapon.fire()
+	move_and_slide()
+	# Keep inside the level
+	position = position.clamp(Vector2.ZERO, Vector2(1024, 768))
+
+func _on_hit(body: Node2D) -> void:
+	queue_free()
+"#;
+
+const WEAPON_SCRIPT: &str = r#"extends RefCounted
+## A weapon that fires bullets
+
+signal fired
+signal emptied
+
+var ammo := 10
+var _cooldown := 0.0
+
+func fire() -> void:
+	# Spend one bullet
+	if ammo <= 0:
+		emptied.emit()
+		return
+	ammo -= 1
+	_cooldown = 0.5
+	fired.emit()
+
+func _tick_cooldown(delta: float) -> void:
+	_cooldown = max(_cooldown - delta, 0.0)
+"#;
+
+fn setup_project(root: &Path) {
+    fs::write(root.join("player.tscn"), PLAYER_SCENE).unwrap();
+    fs::write(root.join("player.gd"), PLAYER_SCRIPT).unwrap();
+    fs::write(root.join("weapon.gd"), WEAPON_SCRIPT).unwrap();
+}
+
+async fn pack(schema: &GqlSchema, budget: i32, strategy: &str) -> serde_json::Value {
+    let query = format!(
+        r#"{{ packContext(entryPoints: ["res://player.tscn"], tokenBudget: {}, strategy: {}) {{
+            bundle estimatedTokens omitted
+            files {{ path role detail estimatedTokens fullTokens }}
+            error {{ code }}
+        }} }}"#,
+        budget, strategy
+    );
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["packContext"].clone()
+}
+
+fn detail(packed: &serde_json::Value, path: &str) -> String {
+    packed["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["path"] == path)
+        .map(|f| f["detail"].as_str().unwrap().to_string())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_pack_context_within_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let packed = pack(&schema, 10_000, "BALANCED").await;
+    let roles: Vec<(&str, &str)> = packed["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["path"].as_str().unwrap(), f["role"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        roles,
+        vec![
+            ("res://player.tscn", "ENTRY_POINT"),
+            ("res://player.gd", "DEPENDENCY"),
+            ("res://weapon.gd", "DEPENDENCY"),
+        ]
+    );
+    assert!(packed["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|f| f["detail"] == "FULL"));
+    let bundle = packed["bundle"].as_str().unwrap();
+    assert!(bundle.starts_with("## res://player.tscn (full)\n"));
+    assert!(bundle.contains("# Movement speed in pixels per second"));
+
+    // Every budget is honored, down to leaving everything out
+    let full = packed["estimatedTokens"].as_i64().unwrap();
+    for strategy in ["BALANCED", "BREADTH", "DEPTH"] {
+        for budget in (1..=full).rev().step_by(7) {
+            let packed = pack(&schema, budget as i32, strategy).await;
+            let tokens = packed["estimatedTokens"].as_i64().unwrap();
+            assert!(tokens <= budget, "{} {}: {}", strategy, budget, tokens);
+            let sections: i64 = packed["files"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| f["estimatedTokens"].as_i64().unwrap())
+                .sum();
+            assert!(sections >= tokens);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_pack_context_strategies() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    // Summaries keep only the members the other files use
+    let packed = pack(&schema, 200, "BALANCED").await;
+    assert_eq!(detail(&packed, "res://weapon.gd"), "SUMMARY");
+    assert_eq!(detail(&packed, "res://player.tscn"), "FULL");
+    let bundle = packed["bundle"].as_str().unwrap();
+    assert!(bundle.contains("func fire() -> void:"), "{}", bundle);
+    assert!(!bundle.contains("_tick_cooldown"));
+    assert!(!bundle.contains("## A weapon"));
+
+    // Depth leaves the farthest file out before touching the others
+    let packed = pack(&schema, 200, "DEPTH").await;
+    assert_eq!(packed["omitted"], serde_json::json!(["res://weapon.gd"]));
+    assert_eq!(detail(&packed, "res://player.tscn"), "FULL");
+
+    // Breadth lowers every file evenly
+    let packed = pack(&schema, 200, "BREADTH").await;
+    assert!(packed["omitted"].as_array().unwrap().is_empty());
+    assert_ne!(detail(&packed, "res://player.tscn"), "FULL");
+
+    let packed = pack(&schema, 0, "BALANCED").await;
+    assert_eq!(packed["error"]["code"], "INVALID_BUDGET");
+}
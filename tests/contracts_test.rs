@@ -0,0 +1,145 @@
+//! Scene Contract Tests
+//!
+//! Tests for checkContracts against invariants in .godot-mcp/contracts.toml.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::Path;
+
+use common::write;
+
+const CONTRACTS: &str = r#"
+[[contract]]
+name = "level-spawn"
+scenes = "levels/**/*.tscn"
+
+[[contract.nodes]]
+name = "PlayerSpawn"
+group = "spawn"
+count = 1
+
+[[contract]]
+name = "ui-root"
+scenes = "ui/*.tscn"
+root_type = "Control"
+"#;
+
+const QUERY: &str = r#"{
+    checkContracts {
+        passed contractsChecked scenesChecked
+        violations { contract scene nodePaths message }
+        error { code location { line } }
+    }
+}"#;
+
+fn setup_project(root: &Path) {
+    write(root, ".godot-mcp/contracts.toml", CONTRACTS);
+    write(
+        root,
+        "levels/level_1.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Level1" type="Node2D"]
+
+[node name="PlayerSpawn" type="Marker2D" parent="." groups=["spawn"]]
+"#,
+    );
+    write(
+        root,
+        "levels/world2/level_2.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Level2" type="Node2D"]
+
+[node name="Points" type="Node2D" parent="."]
+
+[node name="PlayerSpawn" type="Marker2D" parent="Points" groups=["spawn"]]
+
+[node name="PlayerSpawn2" type="Marker2D" parent="Points"]
+"#,
+    );
+    write(
+        root,
+        "ui/menu.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Menu" type="Node2D"]
+"#,
+    );
+}
+
+#[tokio::test]
+async fn test_contracts_pass_and_fail() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema.execute(QUERY).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let check = &data["checkContracts"];
+
+    assert_eq!(check["passed"], false);
+    assert_eq!(check["contractsChecked"], 2);
+    assert_eq!(check["scenesChecked"], 3);
+
+    // Only the UI root is broken; both levels have exactly one spawn in the group
+    let violations = check["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1, "{:?}", violations);
+    assert_eq!(violations[0]["contract"], "ui-root");
+    assert_eq!(violations[0]["scene"], "res://ui/menu.tscn");
+    assert_eq!(violations[0]["nodePaths"][0], ".");
+}
+
+#[tokio::test]
+async fn test_contract_reports_offending_nodes() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    write(
+        dir.path(),
+        "levels/level_3.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Level3" type="Node2D"]
+
+[node name="PlayerSpawn" type="Marker2D" parent="." groups=["spawn"]]
+
+[node name="Sub" type="Node2D" parent="."]
+
+[node name="PlayerSpawn" type="Marker2D" parent="Sub" groups=["spawn"]]
+"#,
+    );
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = schema.execute(QUERY).await.data.into_json().unwrap();
+    let violations = data["checkContracts"]["violations"].as_array().unwrap();
+    let level = violations
+        .iter()
+        .find(|v| v["scene"] == "res://levels/level_3.tscn")
+        .expect("level_3 violation");
+
+    assert_eq!(level["contract"], "level-spawn");
+    assert_eq!(
+        level["nodePaths"],
+        serde_json::json!(["PlayerSpawn", "Sub/PlayerSpawn"])
+    );
+    assert!(level["message"].as_str().unwrap().contains("exactly 1"));
+}
+
+#[tokio::test]
+async fn test_invalid_contracts_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        ".godot-mcp/contracts.toml",
+        "[[contract]]\nname = \"x\"\nroot_typ = \"Control\"\n",
+    );
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = schema.execute(QUERY).await.data.into_json().unwrap();
+    let check = &data["checkContracts"];
+    assert_eq!(check["passed"], false);
+    assert_eq!(check["error"]["code"], "CONTRACTS_INVALID");
+    assert_eq!(check["error"]["location"]["line"], 3);
+}
@@ -0,0 +1,176 @@
+//! C# Script Tests
+//!
+//! Tests for Godot .NET `.cs` scripts in the project resolvers: the script
+//! query, project stats, class hierarchy, symbol search and the dependency graph.
+
+mod common;
+
+use common::{execute_in, write};
+
+const ACTOR: &str = r#"using Godot;
+
+public partial class Actor : CharacterBody2D
+{
+    [Export] public int MaxHealth { get; set; } = 100;
+}
+"#;
+
+const PLAYER: &str = r#"using Godot;
+
+namespace Game;
+
+public partial class Player : Actor
+{
+    [Signal]
+    public delegate void DiedEventHandler(int score);
+
+    [Export] public float Speed = 300.0f;
+    private PackedScene _bullet = GD.Load<PackedScene>("res://bullet.tscn");
+
+    public override void _Ready()
+    {
+        // Speed is read here
+        GD.Print(Speed);
+    }
+}
+"#;
+
+const MAIN: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://Player.cs" id="1_player"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1_player")
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "project.godot",
+        "[application]\nconfig/name=\"Mono\"\n",
+    );
+    write(dir.path(), "Actor.cs", ACTOR);
+    write(dir.path(), "Player.cs", PLAYER);
+    write(dir.path(), "main.tscn", MAIN);
+    write(
+        dir.path(),
+        "bullet.tscn",
+        "[gd_scene format=3]\n\n[node name=\"Bullet\" type=\"Area2D\"]\n",
+    );
+    write(dir.path(), "hud.gd", "extends CanvasLayer\n");
+    dir
+}
+
+#[tokio::test]
+async fn test_csharp_script_query() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{
+            script(path: "res://Player.cs") {
+                language extends className
+                functions { name returnType line }
+                signals { name arguments }
+                exports { name type defaultValue }
+                variables { name type }
+            }
+            project { stats { scriptCount } scripts { path } }
+        }"#,
+    )
+    .await;
+
+    assert_eq!(
+        data["script"],
+        serde_json::json!({
+            "language": "CSHARP",
+            "extends": "Actor",
+            "className": "Player",
+            "functions": [{ "name": "_Ready", "returnType": "void", "line": 13 }],
+            "signals": [{ "name": "Died", "arguments": ["score: int"] }],
+            "exports": [{ "name": "Speed", "type": "float", "defaultValue": "300.0f" }],
+            "variables": [{ "name": "_bullet", "type": "PackedScene" }]
+        })
+    );
+    assert_eq!(data["project"]["stats"]["scriptCount"], 3);
+    assert_eq!(
+        data["project"]["scripts"],
+        serde_json::json!([
+            { "path": "res://Actor.cs" },
+            { "path": "res://Player.cs" },
+            { "path": "res://hud.gd" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_csharp_hierarchy_and_references() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{
+            classHierarchy(scriptPath: "res://Player.cs") {
+                className depth extendsChain { name scriptPath isBuiltin }
+            }
+            findReferences(symbol: "Speed") {
+                definition { file line }
+                references { file line }
+            }
+        }"#,
+    )
+    .await;
+
+    assert_eq!(
+        data["classHierarchy"],
+        serde_json::json!({
+            "className": "Player",
+            "depth": 2,
+            "extendsChain": [
+                { "name": "Actor", "scriptPath": "res://Actor.cs", "isBuiltin": false },
+                { "name": "CharacterBody2D", "scriptPath": null, "isBuiltin": true }
+            ]
+        })
+    );
+    // The comment mentioning Speed is not a reference
+    assert_eq!(
+        data["findReferences"],
+        serde_json::json!({
+            "definition": { "file": "res://Player.cs", "line": 10 },
+            "references": [{ "file": "res://Player.cs", "line": 16 }]
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_csharp_dependency_graph() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ dependencyGraph { nodes { id type isUnused } edges { from to referenceType } } }"#,
+    )
+    .await;
+    let graph = &data["dependencyGraph"];
+
+    let edges = graph["edges"].as_array().unwrap();
+    assert!(edges.contains(&serde_json::json!({
+        "from": "res://main.tscn",
+        "to": "res://Player.cs",
+        "referenceType": "ATTACHES_SCRIPT"
+    })));
+    assert!(edges.contains(&serde_json::json!({
+        "from": "res://Player.cs",
+        "to": "res://bullet.tscn",
+        "referenceType": "LOADS"
+    })));
+    let player = graph["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == "res://Player.cs")
+        .unwrap();
+    assert_eq!(player["type"], "SCRIPT");
+    assert_eq!(player["isUnused"], false);
+}
@@ -0,0 +1,175 @@
+//! Custom Resource Tests
+//!
+//! Tests for createCustomResource: .tres instances of custom Resource
+//! scripts, with values checked against the script's exported vars.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+use std::path::Path;
+
+const BASE_SCRIPT: &str = r#"class_name BaseItem
+extends Resource
+
+@export var display_name: String = ""
+"#;
+
+const ITEM_SCRIPT: &str = r#"class_name Item
+extends BaseItem
+
+enum Rarity { COMMON, RARE = 5, EPIC }
+
+@export var rarity: Rarity = Rarity.COMMON
+@export var price := 10
+@export var weight: float = 1.0
+@export var tint: Color = Color(1, 1, 1, 1)
+@export var tags: Array[String] = []
+@export var icon: Texture2D
+@export var upgrade: Item
+var internal_id := 0
+"#;
+
+fn setup_project(root: &Path) {
+    fs::write(root.join("base_item.gd"), BASE_SCRIPT).unwrap();
+    fs::write(root.join("item.gd"), ITEM_SCRIPT).unwrap();
+    fs::write(root.join("sword.png"), b"png").unwrap();
+    fs::write(
+        root.join("node.gd"),
+        "extends Node\n\n@export var speed := 1\n",
+    )
+    .unwrap();
+}
+
+async fn create(
+    schema: &GqlSchema,
+    script: &str,
+    output: &str,
+    values: serde_json::Value,
+) -> serde_json::Value {
+    let request = async_graphql::Request::new(
+        r#"mutation($input: CreateCustomResourceInput!) {
+            createCustomResource(input: $input) {
+                success path
+                fields { name type defaultValue }
+                resource { scriptClass properties { name value } }
+                error { code message context }
+            }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(serde_json::json!({
+        "input": { "scriptPath": script, "outputPath": output, "values": values }
+    })));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["createCustomResource"].clone()
+}
+
+#[tokio::test]
+async fn test_create_custom_resource_writes_tres() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = create(
+        &schema,
+        "res://item.gd",
+        "res://items/sword.tres",
+        serde_json::json!({
+            "display_name": "Sword \"of Dawn\"",
+            "rarity": "EPIC",
+            "price": 250,
+            "weight": 3,
+            "tint": "#ff8000",
+            "tags": ["melee", "starter"],
+            "icon": "res://sword.png",
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["resource"]["scriptClass"], "Item");
+    let fields: Vec<(&str, &str)> = result["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| (f["name"].as_str().unwrap(), f["type"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("display_name", "String"),
+            ("rarity", "Rarity"),
+            ("price", "int"),
+            ("weight", "float"),
+            ("tint", "Color"),
+            ("tags", "Array[String]"),
+            ("icon", "Texture2D"),
+            ("upgrade", "Item"),
+        ]
+    );
+
+    let tres = fs::read_to_string(dir.path().join("items/sword.tres")).unwrap();
+    assert!(tres
+        .starts_with(r#"[gd_resource type="Resource" script_class="Item" load_steps=3 format=3]"#));
+    assert!(tres.contains(r#"[ext_resource type="Script" path="res://item.gd" id="1"]"#));
+    assert!(tres.contains(r#"[ext_resource type="Texture2D" path="res://sword.png" id="2"]"#));
+    for line in [
+        r#"script = ExtResource("1")"#,
+        r#"display_name = "Sword \"of Dawn\"""#,
+        "rarity = 6",
+        "price = 250",
+        "weight = 3.0",
+        "tint = Color(1, 0.502, 0, 1)",
+        r#"tags = Array[String](["melee", "starter"])"#,
+        r#"icon = ExtResource("2")"#,
+    ] {
+        assert!(tres.contains(line), "{} in {}", line, tres);
+    }
+}
+
+#[tokio::test]
+async fn test_create_custom_resource_rejects_invalid_values() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = create(
+        &schema,
+        "res://item.gd",
+        "res://bad.tres",
+        serde_json::json!({
+            "price": "cheap",
+            "rarity": "LEGENDARY",
+            "icon": "res://missing.png",
+            "internal_id": 3,
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "INVALID_VALUES");
+    let context = result["error"]["context"].as_object().unwrap();
+    let mut invalid: Vec<&String> = context.keys().collect();
+    invalid.sort();
+    assert_eq!(invalid, vec!["icon", "internal_id", "price", "rarity"]);
+    assert!(context["rarity"]
+        .as_str()
+        .unwrap()
+        .contains("COMMON, RARE, EPIC"));
+    assert!(!dir.path().join("bad.tres").exists());
+
+    let result = create(
+        &schema,
+        "res://node.gd",
+        "res://n.tres",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "NOT_A_RESOURCE");
+
+    let result = create(
+        &schema,
+        "res://nope.gd",
+        "res://n.tres",
+        serde_json::json!({}),
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "SCRIPT_NOT_FOUND");
+}
@@ -0,0 +1,148 @@
+//! Dead Code Tests
+//!
+//! Tests for the deadCode query: functions never called and signals never
+//! emitted or connected, with scene connections and dynamic calls counted.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+
+const PLAYER: &str = r#"extends CharacterBody2D
+
+signal died
+signal health_changed(value)
+signal leveled_up
+signal unused
+
+var health := 3
+
+func _ready():
+	died.connect(_on_died)
+	call("heal")
+
+func _process(delta):
+	take_damage(1)
+
+func take_damage(amount):
+	health -= amount
+	health_changed.emit(health)
+	if health <= 0:
+		emit_signal("died")
+
+func heal():
+	health += 1
+
+func countdown(n):
+	if n > 0:
+		countdown(n - 1)
+
+func _on_died():
+	queue_free()
+
+func _on_button_pressed():
+	pass
+
+func _on_timer_timeout():
+	pass
+
+func _custom_virtual():
+	pass
+
+class Inner:
+	func helper():
+		pass
+"#;
+
+const HUD: &str = r#"extends Control
+
+func _ready():
+	await $Player.leveled_up
+"#;
+
+const MAIN: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1")
+
+[node name="Button" type="Button" parent="."]
+
+[connection signal="pressed" from="Button" to="." method="_on_button_pressed"]
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.path().join("player.gd"), PLAYER).unwrap();
+    fs::write(dir.path().join("hud.gd"), HUD).unwrap();
+    fs::write(dir.path().join("main.tscn"), MAIN).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+async fn dead_code(schema: &GqlSchema, arguments: &str) -> serde_json::Value {
+    let query = format!(
+        "{{ deadCode{} {{ items {{ kind name file line confidence }} scriptsScanned }} }}",
+        arguments
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["deadCode"].clone()
+}
+
+fn names(report: &serde_json::Value) -> Vec<(String, String, String)> {
+    report["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            (
+                item["kind"].as_str().unwrap().to_string(),
+                item["name"].as_str().unwrap().to_string(),
+                item["confidence"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+fn entry(kind: &str, name: &str, confidence: &str) -> (String, String, String) {
+    (kind.to_string(), name.to_string(), confidence.to_string())
+}
+
+#[tokio::test]
+async fn test_dead_code_flags_unused_functions_and_signals() {
+    let (_dir, schema) = setup();
+
+    let report = dead_code(&schema, "").await;
+    assert_eq!(report["scriptsScanned"], 2);
+    let items = names(&report);
+    assert_eq!(
+        items,
+        vec![
+            entry("SIGNAL_NEVER_EMITTED", "leveled_up", "HIGH"),
+            entry("UNUSED_SIGNAL", "unused", "HIGH"),
+            entry("UNUSED_FUNCTION", "countdown", "HIGH"),
+            entry("UNUSED_FUNCTION", "_on_timer_timeout", "HIGH"),
+            entry("UNUSED_FUNCTION", "helper", "HIGH"),
+            entry("SIGNAL_NEVER_CONNECTED", "health_changed", "MEDIUM"),
+            entry("UNUSED_FUNCTION", "_custom_virtual", "MEDIUM"),
+        ]
+    );
+    assert_eq!(report["items"][0]["file"], "res://player.gd");
+    assert_eq!(report["items"][0]["line"], 5);
+}
+
+#[tokio::test]
+async fn test_dead_code_filters() {
+    let (_dir, schema) = setup();
+
+    let report = dead_code(&schema, "(minConfidence: MEDIUM, scope: \"res://hud\")").await;
+    assert_eq!(report["scriptsScanned"], 1);
+    assert!(names(&report).is_empty());
+
+    let report = dead_code(&schema, "(minConfidence: HIGH)").await;
+    assert_eq!(report["scriptsScanned"], 2);
+    let items = names(&report);
+    assert_eq!(items.len(), 5);
+    assert!(items.iter().all(|(_, _, confidence)| confidence == "HIGH"));
+}
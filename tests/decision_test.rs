@@ -0,0 +1,161 @@
+//! Decision Log Tests
+//!
+//! Tests for recordDecision and the decisions query
+//! (`.godot-mcp/decisions.json`).
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+
+use common::execute;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::create_dir_all(dir.path().join("scenes/enemies")).unwrap();
+    fs::write(
+        dir.path().join("scenes/enemies/bat.tscn"),
+        "[gd_scene format=3]\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("scenes/main.tscn"), "[gd_scene format=3]\n").unwrap();
+    let ctx = GqlContext::new(dir.path().to_path_buf()).with_session("session-a");
+    (dir, build_schema_with_context(ctx))
+}
+
+async fn record(schema: &GqlSchema, path: &str, decision: &str) -> serde_json::Value {
+    let query = format!(
+        r#"mutation {{
+            recordDecision(path: "{}", decision: "{}", rationale: "Agreed in review") {{
+                success decision {{ id path decision rationale sessionId recordedAt }} error {{ code }}
+            }}
+        }}"#,
+        path, decision
+    );
+    execute(schema, &query).await["recordDecision"].clone()
+}
+
+#[tokio::test]
+async fn test_decisions_apply_to_files_inside_folders() {
+    let (dir, schema) = setup();
+
+    let project = record(
+        &schema,
+        "res://",
+        "Collision layer 3 is reserved for projectiles",
+    )
+    .await;
+    assert_eq!(project["success"], true);
+    assert_eq!(project["decision"]["path"], "res://");
+    assert_eq!(project["decision"]["sessionId"], "session-a");
+    assert!(project["decision"]["id"]
+        .as_str()
+        .unwrap()
+        .starts_with("dec-"));
+    record(&schema, "res://scenes/enemies/", "Enemies extend BaseEnemy").await;
+    record(
+        &schema,
+        "res://scenes/main.tscn",
+        "HUD is instanced, not inlined",
+    )
+    .await;
+
+    let data = execute(
+        &schema,
+        r#"{ decisions(path: "res://scenes/enemies/bat.tscn") { path decision } }"#,
+    )
+    .await;
+    let decisions: Vec<&str> = data["decisions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["decision"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        decisions,
+        vec![
+            "Collision layer 3 is reserved for projectiles",
+            "Enemies extend BaseEnemy"
+        ]
+    );
+    assert_eq!(data["decisions"][1]["path"], "res://scenes/enemies");
+
+    let data = execute(&schema, "{ decisions { decision } }").await;
+    assert_eq!(data["decisions"].as_array().unwrap().len(), 3);
+
+    // Kept in the project for later sessions
+    let log = fs::read_to_string(dir.path().join(".godot-mcp/decisions.json")).unwrap();
+    assert!(log.contains("\"rationale\": \"Agreed in review\""));
+    let later = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let data = execute(
+        &later,
+        r#"{ decisions(path: "res://scenes/main.tscn") { decision } }"#,
+    )
+    .await;
+    assert_eq!(data["decisions"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_superseded_decisions_are_hidden() {
+    let (_dir, schema) = setup();
+
+    let first = record(&schema, "res://", "Layer 3 for projectiles").await;
+    let id = first["decision"]["id"].as_str().unwrap().to_string();
+    let data = execute(
+        &schema,
+        &format!(
+            r#"mutation {{
+                recordDecision(path: "res://", decision: "Layer 5 for projectiles", rationale: "Layer 3 went to pickups", supersedes: "{}") {{
+                    success decision {{ id supersedes }}
+                }}
+            }}"#,
+            id
+        ),
+    )
+    .await;
+    assert_eq!(data["recordDecision"]["success"], true);
+    assert_eq!(
+        data["recordDecision"]["decision"]["supersedes"],
+        id.as_str()
+    );
+
+    let data = execute(&schema, "{ decisions { decision } }").await;
+    assert_eq!(data["decisions"][0]["decision"], "Layer 5 for projectiles");
+    assert_eq!(data["decisions"].as_array().unwrap().len(), 1);
+
+    let data = execute(
+        &schema,
+        "{ decisions(includeSuperseded: true) { decision supersededBy } }",
+    )
+    .await;
+    assert_eq!(data["decisions"][0]["decision"], "Layer 3 for projectiles");
+    assert!(data["decisions"][0]["supersededBy"].is_string());
+}
+
+#[tokio::test]
+async fn test_record_decision_errors() {
+    let (dir, schema) = setup();
+
+    let missing = record(&schema, "res://scenes/boss.tscn", "Boss has two phases").await;
+    assert_eq!(missing["success"], false);
+    assert_eq!(missing["error"]["code"], "FILE_NOT_FOUND");
+
+    let empty = record(&schema, "res://scenes/main.tscn", " ").await;
+    assert_eq!(empty["error"]["code"], "INVALID_INPUT");
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            recordDecision(path: "res://", decision: "x", rationale: "y", supersedes: "dec-1") {
+                success error { code }
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(
+        data["recordDecision"]["error"]["code"],
+        "DECISION_NOT_FOUND"
+    );
+    assert!(!dir.path().join(".godot-mcp/decisions.json").exists());
+}
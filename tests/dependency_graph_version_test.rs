@@ -0,0 +1,133 @@
+//! Dependency Graph Version Tests
+//!
+//! Tests for incremental dependency graph updates: the graph follows files
+//! changed on disk (via the file watcher) and by the server's own writes, and
+//! `dependencyGraphVersion` changes only when the graph does.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use common::execute_in;
+
+const MAIN: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://main.gd" id="1"]
+
+[node name="Main" type="Node2D"]
+script = ExtResource("1")
+"#;
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("project.godot"),
+        "[application]\nconfig/name=\"Graph Test\"\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("main.tscn"), MAIN).unwrap();
+    fs::write(dir.path().join("main.gd"), "extends Node2D\n").unwrap();
+    fs::write(dir.path().join("enemy.gd"), "extends Node\n").unwrap();
+    dir
+}
+
+async fn graph(root: &Path) -> serde_json::Value {
+    let data = execute_in(
+        root,
+        "{ dependencyGraph { version nodes { id } edges { from to referenceType } } }",
+    )
+    .await;
+    data["dependencyGraph"].clone()
+}
+
+async fn version(root: &Path) -> String {
+    let data = execute_in(root, "{ dependencyGraphVersion }").await;
+    data["dependencyGraphVersion"].as_str().unwrap().to_string()
+}
+
+/// Poll until the version moves on from `previous` (file watcher delivery is async)
+async fn wait_for_new_version(root: &Path, previous: &str) -> String {
+    for _ in 0..50 {
+        let current = version(root).await;
+        if current != previous {
+            return current;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("dependencyGraphVersion stayed at {}", previous);
+}
+
+fn has_edge(graph: &serde_json::Value, from: &str, to: &str) -> bool {
+    graph["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|e| e["from"] == from && e["to"] == to)
+}
+
+fn has_node(graph: &serde_json::Value, id: &str) -> bool {
+    graph["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|n| n["id"] == id)
+}
+
+#[tokio::test]
+async fn test_version_is_stable_without_changes() {
+    let dir = project();
+    let first = graph(dir.path()).await;
+    assert!(has_edge(&first, "res://main.tscn", "res://main.gd"));
+    assert_eq!(first["nodes"].as_array().unwrap().len(), 3);
+
+    // Non-graph files don't change the graph
+    fs::write(dir.path().join("notes.txt"), "todo").unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(version(dir.path()).await, first["version"]);
+    assert_eq!(graph(dir.path()).await["version"], first["version"]);
+}
+
+#[tokio::test]
+async fn test_graph_follows_files_changed_on_disk() {
+    let dir = project();
+    let v1 = graph(dir.path()).await["version"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    fs::write(
+        dir.path().join("main.gd"),
+        "extends Node2D\n\nconst Enemy = preload(\"res://enemy.gd\")\n",
+    )
+    .unwrap();
+    let v2 = wait_for_new_version(dir.path(), &v1).await;
+    let updated = graph(dir.path()).await;
+    assert_eq!(updated["version"], v2);
+    assert!(has_edge(&updated, "res://main.gd", "res://enemy.gd"));
+
+    fs::remove_file(dir.path().join("enemy.gd")).unwrap();
+    wait_for_new_version(dir.path(), &v2).await;
+    let updated = graph(dir.path()).await;
+    assert!(!has_node(&updated, "res://enemy.gd"));
+    // The reference itself is still there
+    assert!(has_edge(&updated, "res://main.gd", "res://enemy.gd"));
+}
+
+#[tokio::test]
+async fn test_server_writes_update_graph_immediately() {
+    let dir = project();
+    let v1 = version(dir.path()).await;
+
+    let data = execute_in(
+        dir.path(),
+        r#"mutation { createScene(input: { path: "res://level.tscn", rootType: "Node2D", rootName: "Level" }) { success } }"#,
+    )
+    .await;
+    assert_eq!(data["createScene"]["success"], true);
+
+    let updated = graph(dir.path()).await;
+    assert_ne!(updated["version"], v1.as_str());
+    assert!(has_node(&updated, "res://level.tscn"));
+}
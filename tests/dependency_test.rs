@@ -283,6 +283,231 @@ async fn test_dependency_graph_filter_unused() {
     }
 }
 
+/// Test: cycles come with ranked suggestions pointing at the referencing line
+#[tokio::test]
+async fn test_dependency_graph_cycle_break_suggestions() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("player.gd"),
+        "extends Node\n\nconst Hud = preload(\"res://hud.gd\")\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("hud.gd"),
+        "extends Node\n\nfunc _ready():\n    var player = load(\"res://player.gd\")\n",
+    )
+    .unwrap();
+
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = r#"
+        query {
+            dependencyGraph {
+                stats { hasCycles }
+                cycleBreakSuggestions {
+                    from to referenceType line strategy cyclesBroken impact description
+                }
+            }
+        }
+    "#;
+
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+
+    let data = result.data.into_json().unwrap();
+    let graph = &data["dependencyGraph"];
+    assert_eq!(graph["stats"]["hasCycles"], true);
+
+    let suggestions = graph["cycleBreakSuggestions"].as_array().unwrap();
+    assert_eq!(suggestions.len(), 2);
+    // preload -> load is the least invasive fix
+    assert_eq!(suggestions[0]["from"], "res://player.gd");
+    assert_eq!(suggestions[0]["to"], "res://hud.gd");
+    assert_eq!(suggestions[0]["strategy"], "PRELOAD_TO_LOAD");
+    assert_eq!(suggestions[0]["line"], 3);
+    assert_eq!(suggestions[0]["cyclesBroken"], 1);
+    assert!(suggestions[0]["description"]
+        .as_str()
+        .unwrap()
+        .contains("load(\"res://hud.gd\")"));
+
+    assert_eq!(suggestions[1]["from"], "res://hud.gd");
+    assert_eq!(suggestions[1]["strategy"], "SIGNAL_DECOUPLING");
+    assert_eq!(suggestions[1]["line"], 4);
+    assert!(suggestions[1]["impact"].as_i64() > suggestions[0]["impact"].as_i64());
+}
+
+/// Test: resources and shaders are nodes, so references to them count
+#[tokio::test]
+async fn test_dependency_graph_resources_and_shaders() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("main.tscn"),
+        "[gd_scene load_steps=2 format=3]\n\n\
+         [ext_resource type=\"Material\" path=\"res://glow.tres\" id=\"1\"]\n\n\
+         [node name=\"Main\" type=\"Sprite2D\"]\n\
+         material = ExtResource(\"1\")\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("glow.tres"),
+        "[gd_resource type=\"ShaderMaterial\" load_steps=2 format=3]\n\n\
+         [ext_resource type=\"Shader\" path=\"res://glow.gdshader\" id=\"1\"]\n\n\
+         [resource]\n\
+         shader = ExtResource(\"1\")\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("glow.gdshader"),
+        "shader_type canvas_item;\n#include \"res://noise.gdshaderinc\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("noise.gdshaderinc"),
+        "float noise(vec2 uv) { return 0.0; }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("unused.tres"),
+        "[gd_resource type=\"Resource\" format=3]\n\n[resource]\n",
+    )
+    .unwrap();
+
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = r#"
+        query {
+            dependencyGraph {
+                nodes { id type inDegree outDegree isUnused }
+                edges { from to referenceType }
+                stats { nodeCount edgeCount unusedCount }
+            }
+        }
+    "#;
+
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+
+    let data = result.data.into_json().unwrap();
+    let graph = &data["dependencyGraph"];
+    let node = |id: &str| {
+        graph["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["id"] == id)
+            .cloned()
+            .unwrap_or_else(|| panic!("no node {}", id))
+    };
+
+    let material = node("res://glow.tres");
+    assert_eq!(material["type"], "RESOURCE");
+    assert_eq!(material["inDegree"], 1);
+    assert_eq!(material["outDegree"], 1);
+    assert_eq!(material["isUnused"], false);
+
+    let shader = node("res://glow.gdshader");
+    assert_eq!(shader["type"], "SHADER");
+    assert_eq!(shader["inDegree"], 1);
+    assert_eq!(shader["isUnused"], false);
+    assert_eq!(node("res://noise.gdshaderinc")["isUnused"], false);
+    assert_eq!(node("res://unused.tres")["isUnused"], true);
+
+    assert_eq!(graph["stats"]["nodeCount"], 5);
+    assert_eq!(graph["stats"]["edgeCount"], 3);
+    // main.tscn and unused.tres
+    assert_eq!(graph["stats"]["unusedCount"], 2);
+}
+
+// ======================
+// preloadAnalysis Tests
+// ======================
+
+/// Test: preloadAnalysis separates preload/load and follows only load-time references
+#[tokio::test]
+async fn test_preload_analysis_startup_closure() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("project.godot"),
+        "[application]\n\nrun/main_scene=\"res://main.tscn\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("main.tscn"),
+        r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://main.gd" id="1"]
+
+[node name="Main" type="Node"]
+script = ExtResource("1")
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("main.gd"),
+        r#"extends Node
+
+const Enemy = preload("res://enemy.tscn")
+
+func _ready():
+    var level = load("res://level.tscn")
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("enemy.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Enemy\" type=\"Node2D\"]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("level.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Level\" type=\"Node2D\"]\n",
+    )
+    .unwrap();
+
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = r#"
+        query {
+            preloadAnalysis {
+                entryPoint
+                scripts { path preloads loads }
+                startupFiles { path type sizeBytes depth loadedBy referenceType }
+                totalStartupBytes
+                preloadCount
+                loadCount
+            }
+        }
+    "#;
+
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+
+    let data = result.data.into_json().unwrap();
+    let analysis = &data["preloadAnalysis"];
+
+    assert_eq!(analysis["entryPoint"], "res://main.tscn");
+    assert_eq!(analysis["preloadCount"], 1);
+    assert_eq!(analysis["loadCount"], 1);
+    assert_eq!(analysis["scripts"][0]["loads"][0], "res://level.tscn");
+
+    let files = analysis["startupFiles"].as_array().unwrap();
+    let paths: Vec<&str> = files.iter().map(|f| f["path"].as_str().unwrap()).collect();
+    assert_eq!(
+        paths,
+        vec!["res://main.tscn", "res://main.gd", "res://enemy.tscn"]
+    );
+
+    let enemy = &files[2];
+    assert_eq!(enemy["depth"], 2);
+    assert_eq!(enemy["loadedBy"], "res://main.gd");
+    assert_eq!(enemy["referenceType"], "PRELOADS");
+
+    let total: i64 = files.iter().map(|f| f["sizeBytes"].as_i64().unwrap()).sum();
+    assert!(total > 0);
+    assert_eq!(analysis["totalStartupBytes"], total);
+}
+
 // ======================
 // Dependency Resolver Unit Tests
 // ======================
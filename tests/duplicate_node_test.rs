@@ -0,0 +1,99 @@
+//! Duplicate Node Tests
+//!
+//! Tests for duplicating a node subtree in a scene file, through
+//! duplicateNodeInScene and DUPLICATE_NODE operations in applyMutation.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Enemy" type="CharacterBody2D" parent="."]
+position = Vector2(64, 0)
+
+[node name="Sprite" type="Sprite2D" parent="Enemy"]
+
+[node name="Hud" type="CanvasLayer" parent="."]
+
+[connection signal="ready" from="Enemy" to="." method="_on_enemy_ready"]
+"#;
+
+#[tokio::test]
+async fn test_duplicate_node_in_scene_copies_subtree() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            first: duplicateNodeInScene(input: { scenePath: "res://level.tscn", nodePath: "Enemy" }) { success message }
+            named: duplicateNodeInScene(input: { scenePath: "res://level.tscn", nodePath: "Enemy", newName: "Boss" }) { success message }
+            root: duplicateNodeInScene(input: { scenePath: "res://level.tscn", nodePath: "." }) { success error { code } }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["first"]["message"], "Duplicated to 'Enemy2'");
+    assert_eq!(data["named"]["message"], "Duplicated to 'Boss'");
+    assert_eq!(data["root"]["success"], false);
+    assert_eq!(data["root"]["error"]["code"], "NODE_DUPLICATE_FAILED");
+
+    let tscn = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(tscn.contains(
+        "[node name=\"Enemy2\" type=\"CharacterBody2D\" parent=\".\"]\nposition = Vector2(64, 0)"
+    ));
+    assert!(tscn.contains("[node name=\"Sprite\" type=\"Sprite2D\" parent=\"Enemy2\"]"));
+    assert!(tscn.contains("[node name=\"Sprite\" type=\"Sprite2D\" parent=\"Boss\"]"));
+    assert!(tscn.contains("from=\"Enemy2\" to=\".\" method=\"_on_enemy_ready\""));
+    // Copies follow the original subtree, before the next sibling
+    let order: Vec<usize> = ["\"Enemy\"", "\"Boss\"", "\"Enemy2\"", "\"Hud\""]
+        .iter()
+        .map(|name| tscn.find(&format!("name={}", name)).unwrap())
+        .collect();
+    assert!(order.windows(2).all(|w| w[0] < w[1]), "{}", tscn);
+}
+
+#[tokio::test]
+async fn test_apply_mutation_duplicates_node() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            previewMutation(input: { operations: [
+                { type: DUPLICATE_NODE, args: { scenePath: "res://level.tscn", nodePath: "Enemy" } }
+            ] }) { summary { nodesAdded } affectedFiles { path changeType } }
+            applyMutation(input: { operations: [
+                { type: DUPLICATE_NODE, args: { scenePath: "res://level.tscn", nodePath: "Enemy" } },
+                { type: DUPLICATE_NODE, args: { scenePath: "res://level.tscn", nodePath: "Missing" } },
+                { type: DUPLICATE_NODE, args: { nodePath: "Enemy" } }
+            ] }) { success appliedCount errors { operationIndex message } }
+        }"#,
+    )
+    .await;
+    // Enemy and its Sprite
+    assert_eq!(data["previewMutation"]["summary"]["nodesAdded"], 2);
+    assert_eq!(
+        data["previewMutation"]["affectedFiles"],
+        serde_json::json!([{ "path": "res://level.tscn", "changeType": "MODIFIED" }])
+    );
+
+    let apply = &data["applyMutation"];
+    assert_eq!(apply["success"], false);
+    assert_eq!(apply["appliedCount"], 1);
+    assert_eq!(apply["errors"][0]["operationIndex"], 1);
+    assert_eq!(apply["errors"][0]["message"], "Node not found: Missing");
+    assert_eq!(apply["errors"][1]["operationIndex"], 2);
+
+    let tscn = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(tscn.contains("[node name=\"Enemy2\" type=\"CharacterBody2D\" parent=\".\"]"));
+    assert!(!tscn.contains("Enemy3"));
+}
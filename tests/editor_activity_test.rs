@@ -0,0 +1,66 @@
+//! Editor Activity Tests
+//!
+//! Tests for notifyEditor mutation and editorActivity query.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+#[tokio::test]
+async fn test_notify_editor_queues_items() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(r#"mutation { notifyEditor(message: "Adding enemies") { success item { id level kind } } }"#)
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["notifyEditor"]["success"], true);
+    assert_eq!(data["notifyEditor"]["item"]["id"], 1);
+    assert_eq!(data["notifyEditor"]["item"]["level"], "INFO");
+    assert_eq!(data["notifyEditor"]["item"]["kind"], "MESSAGE");
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                notifyEditor(
+                    message: "Review level layout",
+                    level: WARNING,
+                    kind: APPROVAL,
+                    detail: "+ [node name=\"Spawn\"]"
+                ) { success item { id } }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["notifyEditor"]["item"]["id"], 2);
+
+    // The plugin polls this file
+    let queue = fs::read_to_string(dir.path().join(".godot-mcp/activity.json")).unwrap();
+    let queue: serde_json::Value = serde_json::from_str(&queue).unwrap();
+    assert_eq!(queue["nextId"], 3);
+    assert_eq!(queue["items"][1]["level"], "warning");
+    assert_eq!(queue["items"][1]["kind"], "approval");
+
+    let result = schema
+        .execute(r#"{ editorActivity(since: 1) { id message detail } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    let items = data["editorActivity"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["message"], "Review level layout");
+    assert_eq!(items[0]["detail"], "+ [node name=\"Spawn\"]");
+}
+
+#[tokio::test]
+async fn test_notify_editor_rejects_empty_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(r#"mutation { notifyEditor(message: "  ") { success message } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["notifyEditor"]["success"], false);
+    assert!(!dir.path().join(".godot-mcp/activity.json").exists());
+}
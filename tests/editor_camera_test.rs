@@ -0,0 +1,114 @@
+//! Editor Camera Tests
+//!
+//! Tests for frameNode, setEditorCamera and setMainScreen, using a stub plugin
+//! that records the commands it receives.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+
+use common::start_recording_stub_plugin;
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+#[tokio::test]
+async fn test_frame_node_and_set_editor_camera() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+    let schema = schema(port);
+
+    let result = schema
+        .execute(r#"mutation { frameNode(nodePath: "Level/Player") { success } }"#)
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    assert_eq!(
+        result.data.into_json().unwrap()["frameNode"]["success"],
+        true
+    );
+
+    let result = schema
+        .execute(
+            r#"mutation { setEditorCamera(input: {
+                position: { x: 0, y: 5, z: 10 }, lookAt: { x: 0, y: 1, z: 0 }
+            }) { success } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    assert_eq!(
+        result.data.into_json().unwrap()["setEditorCamera"]["success"],
+        true
+    );
+
+    let sent: Vec<serde_json::Value> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| serde_json::from_str(body).unwrap())
+        .collect();
+    assert_eq!(
+        sent[0],
+        serde_json::json!({ "command": "frame_node", "params": { "node_path": "Level/Player" } })
+    );
+    assert_eq!(
+        sent[1],
+        serde_json::json!({
+            "command": "set_editor_camera",
+            "params": { "position": [0.0, 5.0, 10.0], "look_at": [0.0, 1.0, 0.0] }
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_set_main_screen() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+    let schema = schema(port);
+
+    let result = schema
+        .execute(r#"mutation { setMainScreen(screen: "script") { success } }"#)
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    assert_eq!(
+        result.data.into_json().unwrap()["setMainScreen"]["success"],
+        true
+    );
+
+    // Unknown screens are rejected before reaching the plugin
+    let result = schema
+        .execute(r#"mutation { setMainScreen(screen: "4D") { success error { code } } }"#)
+        .await;
+    let data = &result.data.into_json().unwrap()["setMainScreen"];
+    assert_eq!(data["success"], false);
+    assert_eq!(data["error"]["code"], "INVALID_INPUT");
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    let sent: serde_json::Value = serde_json::from_str(&received[0]).unwrap();
+    assert_eq!(sent["params"]["screen"], "Script");
+}
+
+#[tokio::test]
+async fn test_camera_error_from_plugin() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port =
+        start_recording_stub_plugin(r#"{"error": "Node not found: Missing"}"#, received).await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation { frameNode(nodePath: "Missing") { success message error { code } } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = &result.data.into_json().unwrap()["frameNode"];
+    assert_eq!(data["success"], false);
+    assert_eq!(data["message"], "Node not found: Missing");
+    assert_eq!(data["error"]["code"], "CAMERA_FAILED");
+}
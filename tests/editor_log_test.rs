@@ -0,0 +1,105 @@
+//! Editor Log Tests
+//!
+//! Tests for the `editorLog` subscription, polling a stub plugin over its
+//! WebSocket channel, and for the severity parsing of plugin log lines.
+
+mod common;
+
+use futures_util::StreamExt;
+use godot_mcp_rs::graphql::live_resolver::parse_log_lines;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Start a stub plugin whose log has one old line, and gains `new_lines`
+/// after it has been polled once
+async fn start_stub_plugin(new_lines: Vec<&'static str>) -> u16 {
+    let mut log = vec!["[2026-01-01T10:00:00] Received: old request".to_string()];
+    let mut polls = 0;
+    common::start_ws_stub_plugin(move |request| {
+        assert_eq!(request["command"], "get_logs");
+        polls += 1;
+        if polls == 2 {
+            log.extend(new_lines.iter().map(|line| line.to_string()));
+        }
+        let since = request["params"]["since"].as_u64().unwrap_or(0) as usize;
+        let response = json!({
+            "id": request["id"],
+            "result": {
+                "success": true,
+                "logs": log[since.min(log.len())..],
+                "last_seq": log.len(),
+            },
+        });
+        (vec![response], false)
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_editor_log_streams_new_lines_only() {
+    let port = start_stub_plugin(vec![
+        "[2026-01-01T10:00:01] SCRIPT ERROR: Invalid call. Nonexistent function 'jump'.",
+        "[2026-01-01T10:00:01]    at: _physics_process (res://player.gd:42)",
+        "[2026-01-01T10:00:02] Player spawned",
+    ])
+    .await;
+    let schema = build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(19996)
+            .with_ws_port(Some(port)),
+    );
+
+    let mut stream = schema.execute_stream(
+        "subscription { editorLog(intervalMs: 20) { message severity timestamp file line } }",
+    );
+    let mut entries = vec![];
+    for _ in 0..2 {
+        let response = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .expect("log line within 5s")
+            .unwrap();
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        entries.push(response.data.into_json().unwrap()["editorLog"].clone());
+    }
+
+    assert_eq!(
+        entries[0],
+        json!({
+            "message": "SCRIPT ERROR: Invalid call. Nonexistent function 'jump'.",
+            "severity": "ERROR",
+            "timestamp": "2026-01-01T10:00:01",
+            "file": "res://player.gd",
+            "line": 42,
+        })
+    );
+    assert_eq!(entries[1]["message"], "Player spawned");
+    assert_eq!(entries[1]["severity"], "INFO");
+    assert_eq!(entries[1]["file"], Value::Null);
+}
+
+#[test]
+fn test_parse_log_lines_severity() {
+    let lines: Vec<String> = [
+        "[t] ERROR: Condition \"!is_inside_tree()\" is true.",
+        "[t] USER WARNING: Deprecated call",
+        "[t] WARNING: res://enemy.gd:7 - unused variable",
+        "[t] Parse Error: Expected end of statement",
+        "[t] Received: {\"command\":\"ping\"}",
+        "no timestamp",
+    ]
+    .iter()
+    .map(|line| line.to_string())
+    .collect();
+
+    let entries = parse_log_lines(&lines);
+    let severities: Vec<&str> = entries.iter().map(|e| e.severity.as_str()).collect();
+    assert_eq!(
+        severities,
+        ["ERROR", "WARNING", "WARNING", "ERROR", "INFO", "INFO"]
+    );
+    assert_eq!(entries[2].file.as_deref(), Some("res://enemy.gd"));
+    assert_eq!(entries[2].line, Some(7));
+    assert_eq!(entries[5].timestamp, "");
+    assert_eq!(entries[5].message, "no timestamp");
+}
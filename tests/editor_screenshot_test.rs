@@ -0,0 +1,185 @@
+//! Editor Screenshot Tests
+//!
+//! Tests for editorScreenshot annotation and captureViewport, using a stub
+//! plugin that returns a capture with node bounds.
+
+mod common;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use godot_mcp_rs::screenshot::{Canvas, Rect};
+use godot_mcp_rs::tools::gql_tools::call_gql_tool;
+use serde_json::json;
+
+use common::start_stub_plugin;
+
+fn capture_response() -> String {
+    let mut canvas = Canvas::new(200, 120);
+    canvas.fill_rect(
+        Rect {
+            x: 0,
+            y: 0,
+            width: 200,
+            height: 120,
+        },
+        [30, 30, 30],
+    );
+    serde_json::json!({
+        "success": true,
+        "width": 200,
+        "height": 120,
+        "image": BASE64.encode(canvas.encode_png().unwrap()),
+        "theme": { "accent": "#ff8800", "dark": true },
+        "nodes": [
+            { "node_path": "Player", "found": true, "name": "Player", "rect": [40.0, 50.0, 30.0, 40.0] },
+            { "node_path": "Offscreen", "found": true, "name": "Offscreen", "rect": [500.0, 500.0, 10.0, 10.0] },
+            { "node_path": "Missing", "found": false }
+        ]
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn test_editor_screenshot_annotates_nodes() {
+    let dir = tempfile::tempdir().unwrap();
+    let port = start_stub_plugin(capture_response()).await;
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(port));
+
+    let response = schema
+        .execute(
+            r#"{
+                editorScreenshot(input: {
+                    annotations: [
+                        { nodePath: "Player", label: "Hero" },
+                        { nodePath: "Offscreen" },
+                        { nodePath: "Missing" }
+                    ]
+                    inline: true
+                }) {
+                    success path width height imageBase64
+                    annotations { nodePath label found visible x y width height }
+                    error { code message }
+                }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let result = &data["editorScreenshot"];
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["width"], 200);
+
+    let annotations = result["annotations"].as_array().unwrap();
+    assert_eq!(annotations[0]["label"], "Hero");
+    assert_eq!(annotations[0]["visible"], true);
+    assert_eq!(annotations[0]["x"], 40);
+    assert_eq!(annotations[0]["height"], 40);
+    assert_eq!(annotations[1]["visible"], false);
+    assert_eq!(annotations[2]["found"], false);
+    assert_eq!(annotations[2]["label"], "Missing");
+
+    // Saved file matches the inline image and carries the accent-colored box
+    let path = result["path"].as_str().unwrap();
+    assert!(path.starts_with("res://.godot-mcp/screenshots/screenshot-"));
+    let saved = std::fs::read(dir.path().join(path.trim_start_matches("res://"))).unwrap();
+    let inline = BASE64
+        .decode(result["imageBase64"].as_str().unwrap())
+        .unwrap();
+    assert_eq!(saved, inline);
+
+    let image = Canvas::decode_png(&saved).unwrap();
+    assert_eq!(image.pixel(41, 70), [0xff, 0x88, 0x00, 255]);
+    assert_eq!(image.pixel(55, 70), [30, 30, 30, 255]);
+}
+
+#[tokio::test]
+async fn test_editor_screenshot_without_editor() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(19999));
+
+    let response = schema
+        .execute(r#"{ editorScreenshot(input: {}) { success error { code } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["editorScreenshot"]["success"], false);
+    assert_eq!(data["editorScreenshot"]["error"]["code"], "CONN_REFUSED");
+}
+
+#[tokio::test]
+async fn test_capture_viewport_attaches_image() {
+    let dir = tempfile::tempdir().unwrap();
+    let port = start_stub_plugin(capture_response()).await;
+    let ctx = GqlContext::new(dir.path().to_path_buf()).with_port(port);
+
+    let arguments = json!({
+        "mutation": "mutation { captureViewport(viewport: VIEWPORT_3D) { success viewport path width height imageBase64 } }"
+    });
+    let result = call_gql_tool(ctx, "godot_mutate", arguments.as_object().cloned())
+        .await
+        .unwrap();
+    let content = serde_json::to_value(&result.content).unwrap();
+    assert_eq!(content.as_array().unwrap().len(), 2, "{}", content);
+
+    let data: serde_json::Value =
+        serde_json::from_str(content[0]["text"].as_str().unwrap()).unwrap();
+    let capture = &data["data"]["captureViewport"];
+    assert_eq!(capture["success"], true, "{}", data);
+    assert_eq!(capture["viewport"], "VIEWPORT_3D");
+    assert_eq!(capture["width"], 200);
+    assert_eq!(capture["height"], 120);
+    assert_eq!(capture["imageBase64"], serde_json::Value::Null);
+
+    // The PNG is saved unannotated and attached as an MCP image
+    assert_eq!(content[1]["type"], "image");
+    assert_eq!(content[1]["mimeType"], "image/png");
+    let attached = BASE64.decode(content[1]["data"].as_str().unwrap()).unwrap();
+    let path = capture["path"].as_str().unwrap();
+    let saved = std::fs::read(dir.path().join(path.trim_start_matches("res://"))).unwrap();
+    assert_eq!(saved, attached);
+    assert_eq!(
+        Canvas::decode_png(&saved).unwrap().pixel(41, 70),
+        [30, 30, 30, 255]
+    );
+}
+
+#[tokio::test]
+async fn test_capture_viewport_without_running_game() {
+    let dir = tempfile::tempdir().unwrap();
+    let port = start_stub_plugin(r#"{"error": "No game is running from the editor"}"#).await;
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(port));
+
+    let response = schema
+        .execute(
+            "mutation { captureViewport(viewport: GAME) { success path error { code message } } }",
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let capture = &data["captureViewport"];
+    assert_eq!(capture["success"], false);
+    assert_eq!(capture["path"], serde_json::Value::Null);
+    assert_eq!(capture["error"]["code"], "SCREENSHOT_FAILED");
+    assert_eq!(
+        capture["error"]["message"],
+        "No game is running from the editor"
+    );
+}
+
+#[tokio::test]
+async fn test_editor_screenshot_rejects_game() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(19999));
+
+    let response = schema
+        .execute(r#"{ editorScreenshot(input: { viewport: GAME }) { success error { code } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["editorScreenshot"]["error"]["code"], "INVALID_INPUT");
+}
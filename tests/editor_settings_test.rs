@@ -0,0 +1,139 @@
+//! Editor Settings Tests
+//!
+//! Tests for editorSettings and setEditorSetting, using a stub plugin that
+//! records the commands it receives.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+
+use common::start_recording_stub_plugin;
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+fn sent(received: &Arc<Mutex<Vec<String>>>, index: usize) -> serde_json::Value {
+    serde_json::from_str(&received.lock().unwrap()[index]).unwrap()
+}
+
+#[tokio::test]
+async fn test_editor_settings_query() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "settings": [
+            {"name": "text_editor/external/use_external_editor", "value": "false", "type": "bool"},
+            {"name": "text_editor/external/exec_path", "value": "\"\"", "type": "String"}
+        ]}"#,
+        received.clone(),
+    )
+    .await;
+    let schema = schema(port);
+
+    let result = schema
+        .execute("{ editorSettings { editorConnected settings { name value type } } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let settings = &result.data.into_json().unwrap()["editorSettings"];
+    assert_eq!(settings["editorConnected"], true);
+    assert_eq!(
+        settings["settings"][0],
+        serde_json::json!({
+            "name": "text_editor/external/use_external_editor",
+            "value": "false",
+            "type": "bool"
+        })
+    );
+
+    // Without names or a prefix, the common settings are requested
+    let request = sent(&received, 0);
+    assert_eq!(request["command"], "get_editor_settings");
+    let names = request["params"]["names"].as_array().unwrap();
+    assert!(names.contains(&serde_json::json!("text_editor/external/exec_path")));
+    assert!(names.contains(&serde_json::json!(
+        "text_editor/behavior/files/autosave_interval_secs"
+    )));
+
+    let result = schema
+        .execute(r#"{ editorSettings(prefix: "text_editor/external/") { editorConnected } }"#)
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let request = sent(&received, 1);
+    assert_eq!(request["params"]["prefix"], "text_editor/external/");
+    assert_eq!(request["params"]["names"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_editor_settings_without_editor() {
+    let result = schema(19997)
+        .execute("{ editorSettings { editorConnected settings { name } } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let settings = &result.data.into_json().unwrap()["editorSettings"];
+    assert_eq!(settings["editorConnected"], false);
+    assert_eq!(settings["settings"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_set_editor_setting() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "previous": "false", "value": "true"}"#,
+        received.clone(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation { setEditorSetting(input: {
+                name: "text_editor/external/use_external_editor", value: "true"
+            }) { success message } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = &result.data.into_json().unwrap()["setEditorSetting"];
+    assert_eq!(data["success"], true);
+    assert_eq!(
+        data["message"],
+        "text_editor/external/use_external_editor = true (was false)"
+    );
+
+    let request = sent(&received, 0);
+    assert_eq!(request["command"], "set_editor_setting");
+    assert_eq!(
+        request["params"],
+        serde_json::json!({ "name": "text_editor/external/use_external_editor", "value": "true" })
+    );
+}
+
+#[tokio::test]
+async fn test_set_editor_setting_error_from_plugin() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"error": "Unknown editor setting: text_editor/missing"}"#,
+        received,
+    )
+    .await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation { setEditorSetting(input: { name: "text_editor/missing", value: "1" }) {
+                success message error { code }
+            } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = &result.data.into_json().unwrap()["setEditorSetting"];
+    assert_eq!(data["success"], false);
+    assert_eq!(
+        data["message"],
+        "Unknown editor setting: text_editor/missing"
+    );
+    assert_eq!(data["error"]["code"], "EDITOR_SETTING_FAILED");
+}
@@ -0,0 +1,157 @@
+//! Eval Expression Tests
+//!
+//! Tests for evalExpression, using a stub plugin that answers the first
+//! poll for the result as still pending, as the game does.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use serde_json::Value;
+
+/// Start a stub plugin answering each command with `respond(command)`;
+/// commands are pushed to `received`. Returns its port
+async fn start_stub_plugin<F>(respond: F, received: Arc<Mutex<Vec<Value>>>) -> u16
+where
+    F: Fn(&Value) -> String + Send + Sync + 'static,
+{
+    common::start_stub_plugin_with(move |body| {
+        let command: Value = serde_json::from_str(body).unwrap_or_default();
+        let response = respond(&command);
+        received.lock().unwrap().push(command);
+        response
+    })
+    .await
+}
+
+/// Stub whose game answers with `result` on the second poll
+fn game_answering(result: &'static str) -> impl Fn(&Value) -> String + Send + Sync {
+    let polls = AtomicUsize::new(0);
+    move |command| match command["command"].as_str() {
+        Some("eval_expression") => r#"{"success": true, "pending": true, "id": 7}"#.to_string(),
+        Some("get_eval_result") if polls.fetch_add(1, Ordering::SeqCst) == 0 => {
+            r#"{"success": true, "pending": true}"#.to_string()
+        }
+        Some("get_eval_result") => result.to_string(),
+        _ => r#"{"error": "Unknown command"}"#.to_string(),
+    }
+}
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+const EVAL: &str = r#"mutation {
+    evalExpression(nodePath: "Player", expression: "velocity.length() * 2") {
+        success value valueType json error { code message }
+    }
+}"#;
+
+#[tokio::test]
+async fn test_eval_expression() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        game_answering(r#"{"success": true, "value": "240.0", "type": "float", "json": "240"}"#),
+        received.clone(),
+    )
+    .await;
+
+    let result = schema(port).execute(EVAL).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let eval = &data["evalExpression"];
+    assert_eq!(eval["success"], true);
+    assert_eq!(eval["value"], "240.0");
+    assert_eq!(eval["valueType"], "float");
+    assert_eq!(eval["json"], 240);
+
+    let received = received.lock().unwrap();
+    assert_eq!(received[0]["command"], "eval_expression");
+    assert_eq!(received[0]["params"]["node_path"], "Player");
+    assert_eq!(received[0]["params"]["expression"], "velocity.length() * 2");
+    // Polled until the game answered
+    assert_eq!(received.len(), 3);
+    assert!(received[1..]
+        .iter()
+        .all(|c| c["command"] == "get_eval_result" && c["params"]["id"] == 7));
+}
+
+#[tokio::test]
+async fn test_eval_expression_error_from_game() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        game_answering(r#"{"error": "Execution failed: Invalid named index 'velocity'"}"#),
+        received,
+    )
+    .await;
+
+    let result = schema(port).execute(EVAL).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let eval = &data["evalExpression"];
+    assert_eq!(eval["success"], false);
+    assert!(eval["value"].is_null());
+    assert_eq!(eval["error"]["code"], "EVAL_FAILED");
+    assert!(eval["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Invalid named index"));
+}
+
+#[tokio::test]
+async fn test_eval_expression_without_game() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        |_: &Value| r#"{"error": "No game is running from the editor"}"#.to_string(),
+        received.clone(),
+    )
+    .await;
+
+    let result = schema(port).execute(EVAL).await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["evalExpression"]["error"]["code"], "EVAL_FAILED");
+    // Nothing to poll for
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_eval_expression_rejects_empty_expression() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(game_answering("{}"), received.clone()).await;
+
+    let result = schema(port)
+        .execute(r#"mutation { evalExpression(nodePath: ".", expression: "  ") { success error { code } } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["evalExpression"]["success"], false);
+    assert_eq!(data["evalExpression"]["error"]["code"], "INVALID_INPUT");
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_eval_expression_times_out() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        |command: &Value| match command["command"].as_str() {
+            Some("eval_expression") => r#"{"success": true, "pending": true, "id": 1}"#.to_string(),
+            _ => r#"{"success": true, "pending": true}"#.to_string(),
+        },
+        received,
+    )
+    .await;
+
+    let schema = build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(300),
+    );
+    let result = schema.execute(EVAL).await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["evalExpression"]["error"]["code"], "EVAL_TIMEOUT");
+}
@@ -0,0 +1,148 @@
+//! Example Project Tests
+//!
+//! generateExampleProject builds each kind of project end to end through the
+//! scene, script, resource and settings resolvers; the generated project must
+//! come out healthy.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+async fn generate(project: &Path, kind: &str, path: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(project.to_path_buf()));
+    let query = format!(
+        r#"mutation {{ generateExampleProject(kind: {}, path: "{}") {{
+            success kind path mainScene files inputActions
+            steps {{ name target success message }}
+            health {{ categories {{ name issueCount issues {{ file message }} }} }}
+            error {{ code message }}
+        }} }}"#,
+        kind,
+        path.replace('\\', "\\\\")
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["generateExampleProject"].clone()
+}
+
+async fn assert_generates(kind: &str, scenes: &[&str], actions: &[&str]) {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("example");
+    let result = generate(dir.path(), kind, target.to_str().unwrap()).await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["kind"], kind);
+    assert_eq!(result["mainScene"], "res://main.tscn");
+    assert!(result["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|step| step["success"] == true));
+
+    // Every subsystem took part
+    let steps: Vec<&str> = result["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["name"].as_str().unwrap())
+        .collect();
+    for name in [
+        "createScene",
+        "applyMutation",
+        "createCustomResource",
+        "addInputAction",
+        "setProjectSetting",
+    ] {
+        assert!(steps.contains(&name), "{} missing from {:?}", name, steps);
+    }
+
+    for scene in scenes {
+        let content = fs::read_to_string(target.join(scene)).unwrap();
+        assert!(
+            content.contains("type=\"Script\""),
+            "{}: {}",
+            scene,
+            content
+        );
+    }
+    for file in result["files"].as_array().unwrap() {
+        let file = file.as_str().unwrap().trim_start_matches("res://");
+        assert!(target.join(file).is_file(), "{} not generated", file);
+    }
+
+    let project = fs::read_to_string(target.join("project.godot")).unwrap();
+    assert!(
+        project.contains("run/main_scene=\"res://main.tscn\""),
+        "{}",
+        project
+    );
+    assert_eq!(
+        result["inputActions"],
+        serde_json::json!(actions),
+        "{}",
+        project
+    );
+    for action in actions {
+        assert!(project.contains(&format!("{}={{", action)), "{}", project);
+    }
+
+    // The generated project passes the same checks as any other
+    for category in result["health"]["categories"].as_array().unwrap() {
+        if ["validation", "lint", "dependencies", "unused"]
+            .contains(&category["name"].as_str().unwrap())
+        {
+            assert_eq!(category["issueCount"], 0, "{}", category);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_generate_platformer_2d() {
+    assert_generates(
+        "PLATFORMER_2D",
+        &["main.tscn", "coin.tscn"],
+        &["move_left", "move_right", "jump"],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_generate_fps_3d() {
+    assert_generates(
+        "FPS_3D",
+        &["main.tscn", "target.tscn"],
+        &[
+            "move_forward",
+            "move_back",
+            "move_left",
+            "move_right",
+            "jump",
+            "shoot",
+        ],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_generate_ui_demo() {
+    assert_generates(
+        "UI_DEMO",
+        &["main.tscn", "dialog.tscn"],
+        &["toggle_fullscreen"],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_generate_example_project_refuses_non_empty_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::create_dir(dir.path().join("taken")).unwrap();
+    fs::write(dir.path().join("taken/notes.txt"), "keep").unwrap();
+
+    // Relative to the current project's parent folder
+    let name = dir.path().file_name().unwrap().to_str().unwrap();
+    let result = generate(dir.path(), "UI_DEMO", &format!("{}/taken", name)).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "TARGET_NOT_EMPTY");
+    assert_eq!(fs::read_dir(dir.path().join("taken")).unwrap().count(), 1);
+}
@@ -0,0 +1,179 @@
+//! Export Preset Tests
+//!
+//! Tests for exportPresets, createExportPreset and updateExportPreset, which
+//! edit export_presets.cfg.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+
+use common::execute;
+
+const PRESETS: &str = r#"[preset.0]
+
+name="Windows Desktop"
+platform="Windows Desktop"
+runnable=true
+dedicated_server=false
+custom_features="steam"
+export_filter="all_resources"
+include_filter=""
+exclude_filter=""
+export_path="build/game.exe"
+
+[preset.0.options]
+
+custom_template/debug=""
+binary_format/embed_pck=false
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.path().join("export_presets.cfg"), PRESETS).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+#[tokio::test]
+async fn test_list_export_presets() {
+    let (_dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        "{ exportPresets { presets { index name platform runnable exportPath customFeatures exportFilter options { key value } } error { code } } }",
+    )
+    .await;
+    let preset = &data["exportPresets"]["presets"][0];
+    assert_eq!(preset["name"], "Windows Desktop");
+    assert_eq!(preset["runnable"], true);
+    assert_eq!(preset["exportPath"], "build/game.exe");
+    assert_eq!(preset["customFeatures"], serde_json::json!(["steam"]));
+    assert_eq!(preset["exportFilter"], "all_resources");
+    assert_eq!(preset["options"][1]["key"], "binary_format/embed_pck");
+    assert_eq!(preset["options"][1]["value"], "false");
+
+    // No export_presets.cfg yet
+    let dir = tempfile::tempdir().unwrap();
+    let empty = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let data = execute(
+        &empty,
+        "{ exportPresets { presets { name } error { code } } }",
+    )
+    .await;
+    assert_eq!(data["exportPresets"]["presets"], serde_json::json!([]));
+    assert!(data["exportPresets"]["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_create_export_preset_from_template() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            createExportPreset(input: { name: "Web Demo", platform: "web", customFeatures: ["demo", " web "] }) {
+                success preset { index platform runnable exportPath customFeatures } error { code }
+            }
+        }"#,
+    )
+    .await;
+    let result = &data["createExportPreset"];
+    assert_eq!(result["success"], true);
+    assert_eq!(result["preset"]["index"], 1);
+    assert_eq!(result["preset"]["platform"], "Web");
+    assert_eq!(result["preset"]["runnable"], true);
+    assert_eq!(result["preset"]["exportPath"], "build/web_demo.html");
+    assert_eq!(
+        result["preset"]["customFeatures"],
+        serde_json::json!(["demo", "web"])
+    );
+
+    let content = fs::read_to_string(dir.path().join("export_presets.cfg")).unwrap();
+    assert!(content.starts_with(PRESETS));
+    assert!(content.contains(
+        "\n[preset.1]\n\nname=\"Web Demo\"\nplatform=\"Web\"\nrunnable=true\nadvanced_options=false\n"
+    ));
+    assert!(content.contains("custom_features=\"demo,web\"\n"));
+    assert!(content.contains("\n[preset.1.options]\n\ncustom_template/debug=\"\"\n"));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            createExportPreset(input: { name: "Switch", platform: "Switch" }) { success error { code } }
+        }"#,
+    )
+    .await;
+    assert_eq!(
+        data["createExportPreset"]["error"]["code"],
+        "UNKNOWN_PLATFORM"
+    );
+}
+
+#[tokio::test]
+async fn test_copy_and_update_export_preset() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            createExportPreset(input: { name: "Windows Demo", copyFrom: "Windows Desktop" }) {
+                success preset { index platform runnable exportPath customFeatures options { key } }
+            }
+        }"#,
+    )
+    .await;
+    let preset = &data["createExportPreset"]["preset"];
+    assert_eq!(preset["platform"], "Windows Desktop");
+    // The original stays the platform's runnable preset
+    assert_eq!(preset["runnable"], false);
+    assert_eq!(preset["exportPath"], "build/windows_demo.exe");
+    assert_eq!(preset["customFeatures"], serde_json::json!(["steam"]));
+    assert_eq!(preset["options"].as_array().unwrap().len(), 2);
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            updateExportPreset(input: {
+                name: "Windows Demo",
+                newName: "Windows Trial",
+                exportPath: "dist/trial.exe",
+                customFeatures: ["demo"],
+                runnable: true,
+                options: [{ key: "binary_format/embed_pck", value: "true" }]
+            }) { success preset { name runnable exportPath customFeatures } error { code } }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["updateExportPreset"]["success"], true);
+    assert_eq!(
+        data["updateExportPreset"]["preset"]["name"],
+        "Windows Trial"
+    );
+
+    let data = execute(
+        &schema,
+        "{ exportPresets { presets { name runnable exportPath customFeatures } } }",
+    )
+    .await;
+    let presets = &data["exportPresets"]["presets"];
+    assert_eq!(presets[0]["runnable"], false);
+    assert_eq!(presets[1]["runnable"], true);
+    assert_eq!(presets[1]["exportPath"], "dist/trial.exe");
+    assert_eq!(presets[1]["customFeatures"], serde_json::json!(["demo"]));
+    let content = fs::read_to_string(dir.path().join("export_presets.cfg")).unwrap();
+    assert!(content.ends_with(
+        "[preset.1.options]\n\ncustom_template/debug=\"\"\nbinary_format/embed_pck=true\n"
+    ));
+
+    let data = execute(
+        &schema,
+        r#"mutation { updateExportPreset(input: { name: "Linux", exportPath: "x" }) { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["updateExportPreset"]["error"]["code"],
+        "EXPORT_PRESET_NOT_FOUND"
+    );
+}
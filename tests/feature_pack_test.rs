@@ -0,0 +1,344 @@
+//! Feature Pack Tests
+//!
+//! Tests for installFeaturePack: path-prefix rewriting, conflict detection,
+//! install records and updates, and git sources.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use common::{execute_in, write};
+
+const MANIFEST: &str = r#"name = "dialogue_box"
+version = "1.0.0"
+description = "Typewriter dialogue box"
+"#;
+
+const SCENE: &str = r#"[gd_scene load_steps=2 format=3 uid="uid://c1box"]
+
+[ext_resource type="Script" uid="uid://c1script" path="res://scripts/dialogue_box.gd" id="1_abc"]
+
+[node name="DialogueBox" type="Control"]
+script = ExtResource("1_abc")
+"#;
+
+const SCRIPT: &str = "extends Control\n\nconst PORTRAITS := \"res://portraits/\"\n";
+
+fn pack() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "feature_pack.toml", MANIFEST);
+    write(dir.path(), "scenes/dialogue_box.tscn", SCENE);
+    write(dir.path(), "scenes/dialogue_box.tscn.import", "[remap]\n");
+    write(dir.path(), "scripts/dialogue_box.gd", SCRIPT);
+    write(dir.path(), "portraits/hero.png", "\u{89}PNG fake");
+    dir
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "project.godot",
+        "[application]\nconfig/name=\"Pack Test\"\n",
+    );
+    dir
+}
+
+async fn install(root: &Path, args: &str) -> serde_json::Value {
+    let data = execute_in(
+        root,
+        &format!(
+            r#"mutation {{ installFeaturePack(input: {{ {} }}) {{
+                success dryRun conflicts
+                pack {{ name version prefix files }}
+                files {{ path action }}
+                error {{ code message }}
+            }} }}"#,
+            args
+        ),
+    )
+    .await;
+    data["installFeaturePack"].clone()
+}
+
+fn source_arg(dir: &Path) -> String {
+    format!(
+        r#"source: "{}""#,
+        dir.display().to_string().replace('\\', "/")
+    )
+}
+
+#[tokio::test]
+async fn test_install_rewrites_paths_and_records_pack() {
+    let pack = pack();
+    let project = project();
+
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["pack"]["prefix"], "res://features/dialogue_box");
+    assert_eq!(result["pack"]["files"].as_array().unwrap().len(), 3);
+
+    let installed = project.path().join("features/dialogue_box");
+    let scene = fs::read_to_string(installed.join("scenes/dialogue_box.tscn")).unwrap();
+    assert!(scene.starts_with("[gd_scene load_steps=2 format=3]\n"));
+    assert!(scene.contains(
+        r#"[ext_resource type="Script" path="res://features/dialogue_box/scripts/dialogue_box.gd" id="1_abc"]"#
+    ));
+    let script = fs::read_to_string(installed.join("scripts/dialogue_box.gd")).unwrap();
+    assert!(script.contains("\"res://features/dialogue_box/portraits/\""));
+    assert_eq!(
+        fs::read(installed.join("portraits/hero.png")).unwrap(),
+        "\u{89}PNG fake".as_bytes()
+    );
+    assert!(!installed.join("feature_pack.toml").exists());
+    assert!(!installed.join("scenes/dialogue_box.tscn.import").exists());
+
+    let data = execute_in(
+        project.path(),
+        "{ featurePacks { name version description source prefix installedAt } }",
+    )
+    .await;
+    let packs = data["featurePacks"].as_array().unwrap();
+    assert_eq!(packs.len(), 1);
+    assert_eq!(packs[0]["name"], "dialogue_box");
+    assert_eq!(packs[0]["version"], "1.0.0");
+    assert_eq!(packs[0]["description"], "Typewriter dialogue box");
+}
+
+#[tokio::test]
+async fn test_install_detects_conflicts_with_existing_files() {
+    let pack = pack();
+    let project = project();
+    write(
+        project.path(),
+        "ui/scripts/dialogue_box.gd",
+        "extends Control # mine\n",
+    );
+
+    let args = format!(r#"{}, prefix: "res://ui""#, source_arg(pack.path()));
+    let result = install(project.path(), &args).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "FEATURE_PACK_CONFLICT");
+    assert_eq!(result["conflicts"][0], "res://ui/scripts/dialogue_box.gd");
+    // Nothing written, nothing recorded
+    assert!(!project.path().join("ui/scenes").exists());
+    let data = execute_in(project.path(), "{ featurePacks { name } }").await;
+    assert!(data["featurePacks"].as_array().unwrap().is_empty());
+
+    let result = install(project.path(), &format!("{}, overwrite: true", args)).await;
+    assert_eq!(result["success"], true, "{}", result);
+    let script = fs::read_to_string(project.path().join("ui/scripts/dialogue_box.gd")).unwrap();
+    assert!(script.contains("res://ui/portraits/"));
+}
+
+#[tokio::test]
+async fn test_reinstall_updates_pack_files() {
+    let pack = pack();
+    let project = project();
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], true, "{}", result);
+
+    // Version 1.1: script changed, portrait dropped, new theme added
+    write(
+        pack.path(),
+        "feature_pack.toml",
+        &MANIFEST.replace("1.0.0", "1.1.0"),
+    );
+    write(
+        pack.path(),
+        "scripts/dialogue_box.gd",
+        "extends Control\n\nconst THEME := \"res://dialogue_theme.tres\"\n",
+    );
+    fs::remove_dir_all(pack.path().join("portraits")).unwrap();
+    write(
+        pack.path(),
+        "dialogue_theme.tres",
+        "[gd_resource type=\"Theme\" format=3]\n",
+    );
+
+    let result = install(
+        project.path(),
+        &format!("{}, dryRun: true", source_arg(pack.path())),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    let action = |path: &str| {
+        result["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["path"] == path)
+            .map(|f| f["action"].as_str().unwrap().to_string())
+    };
+    let prefix = "res://features/dialogue_box";
+    assert_eq!(
+        action(&format!("{}/scenes/dialogue_box.tscn", prefix)).as_deref(),
+        Some("UNCHANGED")
+    );
+    assert_eq!(
+        action(&format!("{}/scripts/dialogue_box.gd", prefix)).as_deref(),
+        Some("UPDATE")
+    );
+    assert_eq!(
+        action(&format!("{}/dialogue_theme.tres", prefix)).as_deref(),
+        Some("CREATE")
+    );
+    assert_eq!(
+        action(&format!("{}/portraits/hero.png", prefix)).as_deref(),
+        Some("REMOVE")
+    );
+    assert!(project
+        .path()
+        .join("features/dialogue_box/portraits/hero.png")
+        .exists());
+
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], true, "{}", result);
+    let installed = project.path().join("features/dialogue_box");
+    assert!(!installed.join("portraits/hero.png").exists());
+    let script = fs::read_to_string(installed.join("scripts/dialogue_box.gd")).unwrap();
+    assert!(script.contains("res://features/dialogue_box/dialogue_theme.tres"));
+
+    let data = execute_in(project.path(), "{ featurePacks { version files } }").await;
+    let packs = data["featurePacks"].as_array().unwrap();
+    assert_eq!(packs.len(), 1);
+    assert_eq!(packs[0]["version"], "1.1.0");
+    assert_eq!(packs[0]["files"].as_array().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_install_from_git_url() {
+    if Command::new("git").arg("--version").output().is_err() {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    let pack = pack();
+    let repo = tempfile::tempdir().unwrap();
+    let repo_dir = repo.path().join("dialogue_box.git");
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(&repo_dir)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "{:?}", status);
+    };
+    fs::create_dir(&repo_dir).unwrap();
+    for rel in [
+        "feature_pack.toml",
+        "scenes/dialogue_box.tscn",
+        "scripts/dialogue_box.gd",
+    ] {
+        write(
+            &repo_dir,
+            rel,
+            &fs::read_to_string(pack.path().join(rel)).unwrap(),
+        );
+    }
+    git(&["init", "--quiet"]);
+    git(&["add", "."]);
+    git(&["commit", "--quiet", "-m", "v1"]);
+    git(&["tag", "v1.0.0"]);
+
+    let project = project();
+    let source = repo_dir.display().to_string().replace('\\', "/");
+    let result = install(
+        project.path(),
+        &format!(r#"source: "{}", gitRef: "v1.0.0""#, source),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert!(project
+        .path()
+        .join("features/dialogue_box/scripts/dialogue_box.gd")
+        .exists());
+
+    let result = install(
+        project.path(),
+        r#"source: "https://invalid.invalid/pack.git""#,
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "FEATURE_PACK_FETCH_FAILED");
+}
+
+#[tokio::test]
+async fn test_install_rejects_option_like_sources() {
+    let project = project();
+    let marker = project.path().join("pwned");
+    let result = install(
+        project.path(),
+        &format!(
+            r#"source: "--upload-pack=touch {};.git""#,
+            marker.display().to_string().replace('\\', "/")
+        ),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "INVALID_INPUT");
+    assert!(!marker.exists());
+
+    let result = install(
+        project.path(),
+        r#"source: "https://example.com/pack.git", gitRef: "--help""#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "INVALID_INPUT");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_install_rejects_symlinks_in_pack() {
+    let secret = tempfile::tempdir().unwrap();
+    write(secret.path(), "id_ed25519", "PRIVATE KEY");
+    let pack = pack();
+    std::os::unix::fs::symlink(
+        secret.path().join("id_ed25519"),
+        pack.path().join("scripts/key.txt"),
+    )
+    .unwrap();
+    let project = project();
+
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "FEATURE_PACK_READ_FAILED");
+    assert!(!project.path().join("features").exists());
+
+    // A link back up the tree must not make the walk loop
+    fs::remove_file(pack.path().join("scripts/key.txt")).unwrap();
+    std::os::unix::fs::symlink(pack.path(), pack.path().join("scripts/loop")).unwrap();
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["error"]["code"], "FEATURE_PACK_READ_FAILED");
+}
+
+#[tokio::test]
+async fn test_install_keeps_files_outside_project_named_in_record() {
+    let outside = tempfile::tempdir().unwrap();
+    write(outside.path(), "keep.txt", "keep");
+    let pack = pack();
+    let project = project();
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], true, "{}", result);
+
+    // Tampered record: the pack "owns" a file outside the project
+    let record = project.path().join(".godot-mcp/feature_packs.json");
+    let mut packs: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&record).unwrap()).unwrap();
+    let escape = format!(
+        "res://../{}/keep.txt",
+        outside.path().file_name().unwrap().to_string_lossy()
+    );
+    packs[0]["files"]
+        .as_array_mut()
+        .unwrap()
+        .push(escape.into());
+    fs::write(&record, packs.to_string()).unwrap();
+
+    let result = install(project.path(), &source_arg(pack.path())).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "FEATURE_PACK_INVALID");
+    assert!(outside.path().join("keep.txt").exists());
+}
@@ -0,0 +1,286 @@
+//! Fetch Asset Tests
+//!
+//! Tests for fetchAsset against a stub HTTP server on loopback: license
+//! handling, the domain allowlist, size caps, redirects and rate limiting.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use godot_mcp_rs::config::AssetsConfig;
+use godot_mcp_rs::graphql::asset_fetch;
+use godot_mcp_rs::graphql::vfs::{MemoryFs, ProjectFs};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Largest download accepted in these tests
+const MAX_BYTES: u64 = 1024;
+/// Spacing between requests to the stub
+const INTERVAL_MS: u64 = 100;
+
+/// Start a stub asset server; requested paths are pushed to `requested`.
+/// Returns its base URL
+async fn start_stub_server(requested: Arc<Mutex<Vec<String>>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+                if String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                    break;
+                }
+            }
+            let text = String::from_utf8_lossy(&request);
+            let path = text.split(' ').nth(1).unwrap_or_default().to_string();
+            requested.lock().unwrap().push(path.clone());
+
+            let (status, headers, body): (&str, String, Vec<u8>) = match path.as_str() {
+                "/art/tiles.png" => (
+                    "200 OK",
+                    "Content-Type: image/png\r\n".into(),
+                    b"\x89PNG tiles".to_vec(),
+                ),
+                "/art/License.txt" => ("200 OK", String::new(), b"CC0 1.0 Universal".to_vec()),
+                "/art/big.png" => ("200 OK", String::new(), vec![0; 2048]),
+                "/art/moved.png" => (
+                    "302 Found",
+                    "Location: http://example.com/tiles.png\r\n".into(),
+                    Vec::new(),
+                ),
+                _ => ("404 Not Found", String::new(), Vec::new()),
+            };
+            let mut reply = format!(
+                "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                headers,
+                body.len()
+            )
+            .into_bytes();
+            reply.extend_from_slice(&body);
+            let _ = socket.write_all(&reply).await;
+        }
+    });
+
+    format!("http://127.0.0.1:{}", port)
+}
+
+fn configure() {
+    asset_fetch::set_config(AssetsConfig {
+        allowed_domains: vec!["127.0.0.1".to_string()],
+        max_bytes: MAX_BYTES,
+        min_interval_ms: INTERVAL_MS,
+        require_license: true,
+    });
+}
+
+fn project(name: &str, files: Value) -> (GqlContext, Arc<MemoryFs>, PathBuf) {
+    configure();
+    let root =
+        std::env::temp_dir().join(format!("godot-mcp-assets-{}-{}", name, std::process::id()));
+    let fs = Arc::new(MemoryFs::from_json(&root, &files).unwrap());
+    (GqlContext::new(root.clone()).with_fs(fs.clone()), fs, root)
+}
+
+async fn fetch_asset(ctx: GqlContext, input: &str) -> Value {
+    let schema = build_schema_with_context(ctx);
+    let result = schema
+        .execute(format!(
+            "mutation {{ fetchAsset(input: {{ {} }}) {{ success path size contentType licensePath error {{ code message }} }} }}",
+            input
+        ))
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()["fetchAsset"].clone()
+}
+
+#[tokio::test]
+async fn test_fetch_asset_with_license() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested.clone()).await;
+    let (ctx, fs, root) = project("license", json!({"project.godot": ""}));
+
+    let result = fetch_asset(
+        ctx,
+        &format!(
+            r#"url: "{0}/art/tiles.png", destination: "res://assets/kenney/tiles.png", licenseUrl: "{0}/art/License.txt""#,
+            base
+        ),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["path"], "res://assets/kenney/tiles.png");
+    assert_eq!(result["size"], 10);
+    assert_eq!(result["contentType"], "image/png");
+    assert_eq!(result["licensePath"], "res://assets/kenney/License.txt");
+    assert_eq!(
+        fs.read(&root.join("assets/kenney/tiles.png")).unwrap(),
+        b"\x89PNG tiles"
+    );
+    assert_eq!(
+        fs.read_to_string(&root.join("assets/kenney/License.txt"))
+            .unwrap(),
+        "CC0 1.0 Universal"
+    );
+    assert_eq!(
+        *requested.lock().unwrap(),
+        ["/art/License.txt", "/art/tiles.png"]
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_asset_into_folder_with_license() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested).await;
+    let (ctx, fs, root) = project("folder", json!({"project.godot": "", "art/LICENSE": "CC0"}));
+
+    let result = fetch_asset(
+        ctx,
+        &format!(
+            r#"url: "{}/art/tiles.png", destination: "res://art/""#,
+            base
+        ),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["path"], "res://art/tiles.png");
+    assert_eq!(result["licensePath"], "res://art/LICENSE");
+    assert!(fs.exists(&root.join("art/tiles.png")));
+}
+
+#[tokio::test]
+async fn test_fetch_asset_requires_license() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested.clone()).await;
+    let (ctx, fs, root) = project("nolicense", json!({"project.godot": ""}));
+
+    let result = fetch_asset(
+        ctx,
+        &format!(
+            r#"url: "{}/art/tiles.png", destination: "res://art/tiles.png""#,
+            base
+        ),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "ASSET_LICENSE_MISSING");
+    assert!(!fs.exists(&root.join("art/tiles.png")));
+    // Refused before anything was downloaded
+    assert!(requested.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_asset_rejects_unlisted_domain() {
+    let (ctx, _, _) = project("domain", json!({"project.godot": "", "art/LICENSE": "CC0"}));
+    let result = fetch_asset(
+        ctx,
+        r#"url: "https://example.com/tiles.png", destination: "res://art/tiles.png""#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "ASSET_URL_NOT_ALLOWED");
+}
+
+#[tokio::test]
+async fn test_fetch_asset_redirect_must_stay_allowed() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested).await;
+    let (ctx, fs, root) = project(
+        "redirect",
+        json!({"project.godot": "", "art/LICENSE": "CC0"}),
+    );
+
+    let result = fetch_asset(
+        ctx,
+        &format!(
+            r#"url: "{}/art/moved.png", destination: "res://art/moved.png""#,
+            base
+        ),
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "ASSET_FETCH_FAILED");
+    assert!(result["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("example.com"));
+    assert!(!fs.exists(&root.join("art/moved.png")));
+}
+
+#[tokio::test]
+async fn test_fetch_asset_size_cap() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested).await;
+    let (ctx, fs, root) = project("big", json!({"project.godot": "", "art/LICENSE": "CC0"}));
+
+    let result = fetch_asset(
+        ctx,
+        &format!(
+            r#"url: "{}/art/big.png", destination: "res://art/big.png""#,
+            base
+        ),
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "ASSET_TOO_LARGE");
+    assert!(!fs.exists(&root.join("art/big.png")));
+}
+
+#[tokio::test]
+async fn test_fetch_asset_validates_destination() {
+    let (ctx, _, _) = project(
+        "destination",
+        json!({"project.godot": "", "art/LICENSE": "CC0", "art/tiles.png": "old"}),
+    );
+
+    // Only asset file types
+    let result = fetch_asset(
+        ctx.clone(),
+        r#"url: "http://127.0.0.1:1/player.gd", destination: "res://art/player.gd""#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "ASSET_TYPE_NOT_ALLOWED");
+
+    let result = fetch_asset(
+        ctx.clone(),
+        r#"url: "http://127.0.0.1:1/tiles.png", destination: "res://../tiles.png""#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "INVALID_INPUT");
+
+    let result = fetch_asset(
+        ctx,
+        r#"url: "http://127.0.0.1:1/tiles.png", destination: "res://art/tiles.png""#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "ASSET_EXISTS");
+}
+
+#[tokio::test]
+async fn test_fetch_asset_rate_limited_per_host() {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let base = start_stub_server(requested.clone()).await;
+    let (ctx, _, _) = project("rate", json!({"project.godot": "", "art/LICENSE": "CC0"}));
+
+    let start = Instant::now();
+    for name in ["a", "b", "c"] {
+        let result = fetch_asset(
+            ctx.clone(),
+            &format!(
+                r#"url: "{}/art/tiles.png", destination: "res://art/{}.png""#,
+                base, name
+            ),
+        )
+        .await;
+        assert_eq!(result["success"], true, "{}", result);
+    }
+    // Three requests to one host: at least two intervals apart in total
+    assert!(start.elapsed() >= Duration::from_millis(2 * INTERVAL_MS));
+    assert_eq!(requested.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,262 @@
+//! File Move/Rename Tests
+//!
+//! Tests for moveFile and renameFile mutations with reference updates.
+
+use godot_mcp_rs::graphql::vfs::{Metadata, OsFs, ProjectFs};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+fn write_sample_project(root: &Path) {
+    fs::create_dir_all(root.join("scripts")).unwrap();
+    fs::write(
+        root.join("project.godot"),
+        "[autoload]\n\nGame=\"*res://scripts/game.gd\"\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("main.tscn"),
+        r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" uid="uid://abc123" path="res://scripts/game.gd" id="1"]
+
+[node name="Main" type="Node"]
+script = ExtResource("1")
+"#,
+    )
+    .unwrap();
+    fs::write(root.join("scripts/game.gd"), "extends Node\n").unwrap();
+    fs::write(root.join("scripts/game.gd.uid"), "uid://abc123\n").unwrap();
+    fs::write(
+        root.join("scripts/loader.gd"),
+        "extends Node\nconst G = preload(\"res://scripts/game.gd\")\nconst S = preload(\"res://scripts/game.gdshader\")\n",
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_move_file_dry_run_leaves_project_untouched() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                moveFile(input: { from: "res://scripts/game.gd", to: "res://core/game.gd", dryRun: true }) {
+                    success dryRun movedFiles referencesUpdated filesChanged { path changesCount }
+                }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let moved = &data["moveFile"];
+
+    assert_eq!(moved["success"], true);
+    assert_eq!(moved["dryRun"], true);
+    assert_eq!(moved["referencesUpdated"], 3);
+    assert_eq!(
+        moved["movedFiles"],
+        serde_json::json!(["res://scripts/game.gd", "res://scripts/game.gd.uid"])
+    );
+    assert_eq!(moved["filesChanged"].as_array().unwrap().len(), 3);
+    assert!(dir.path().join("scripts/game.gd").exists());
+    assert!(!dir.path().join("core").exists());
+}
+
+#[tokio::test]
+async fn test_move_file_rewrites_references() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                moveFile(input: { from: "res://scripts/game.gd", to: "res://core/game.gd" }) {
+                    success message
+                }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["moveFile"]["success"], true, "{:?}", data);
+
+    assert!(dir.path().join("core/game.gd").exists());
+    assert!(dir.path().join("core/game.gd.uid").exists());
+    assert!(!dir.path().join("scripts/game.gd").exists());
+
+    let scene = fs::read_to_string(dir.path().join("main.tscn")).unwrap();
+    assert!(scene.contains(r#"uid="uid://abc123" path="res://core/game.gd""#));
+    let project = fs::read_to_string(dir.path().join("project.godot")).unwrap();
+    assert!(project.contains("\"*res://core/game.gd\""));
+    let loader = fs::read_to_string(dir.path().join("scripts/loader.gd")).unwrap();
+    assert!(loader.contains("preload(\"res://core/game.gd\")"));
+    // Paths sharing a prefix must not be touched
+    assert!(loader.contains("preload(\"res://scripts/game.gdshader\")"));
+}
+
+#[tokio::test]
+async fn test_move_file_rewrites_single_quoted_references() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("lib")).unwrap();
+    fs::write(dir.path().join("project.godot"), "[application]\n").unwrap();
+    fs::write(dir.path().join("old.gd"), "extends Node\n").unwrap();
+    fs::write(dir.path().join("lib/util.gd"), "extends Node\n").unwrap();
+    fs::write(
+        dir.path().join("user.gd"),
+        "extends Node\nconst Old = preload('res://old.gd')\nconst Util = preload('res://lib/util.gd')\nvar odd = \"res://old.gd'\n",
+    )
+    .unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                file: moveFile(input: { from: "res://old.gd", to: "res://new.gd" }) { success referencesUpdated }
+                dir: moveFile(input: { from: "res://lib", to: "res://common" }) { success referencesUpdated }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["file"]["success"], true, "{:?}", data);
+    assert_eq!(data["file"]["referencesUpdated"], 1);
+    assert_eq!(data["dir"]["referencesUpdated"], 1);
+
+    let user = fs::read_to_string(dir.path().join("user.gd")).unwrap();
+    assert!(user.contains("preload('res://new.gd')"));
+    assert!(user.contains("preload('res://common/util.gd')"));
+    // Mismatched quotes are not a string literal of the path
+    assert!(user.contains("\"res://old.gd'"));
+}
+
+#[tokio::test]
+async fn test_rename_file_and_directory_move() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                renameFile(input: { path: "res://scripts/game.gd", newName: "game_state.gd" }) {
+                    success to
+                }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["renameFile"]["success"], true);
+    assert_eq!(data["renameFile"]["to"], "res://scripts/game_state.gd");
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                moveFile(input: { from: "res://scripts", to: "res://src" }) {
+                    success filesChanged { path }
+                }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["moveFile"]["success"], true);
+
+    let scene = fs::read_to_string(dir.path().join("main.tscn")).unwrap();
+    assert!(scene.contains("path=\"res://src/game_state.gd\""));
+    let loader = fs::read_to_string(dir.path().join("src/loader.gd")).unwrap();
+    assert!(loader.contains("preload(\"res://src/game_state.gd\")"));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                moveFile(input: { from: "res://missing.gd", to: "res://other.gd" }) { success message }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["moveFile"]["success"], false);
+}
+
+/// Real filesystem refusing every write to `project.godot`
+#[derive(Debug)]
+struct ReadOnlySettingsFs;
+
+impl ProjectFs for ReadOnlySettingsFs {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        OsFs.read(path)
+    }
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        if path.ends_with("project.godot") {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only"));
+        }
+        OsFs.write(path, contents)
+    }
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        OsFs.append(path, contents)
+    }
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        OsFs.create_dir_all(path)
+    }
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        OsFs.read_dir(path)
+    }
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        OsFs.metadata(path)
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        OsFs.remove_file(path)
+    }
+    fn remove_dir(&self, path: &Path) -> io::Result<()> {
+        OsFs.remove_dir(path)
+    }
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        OsFs.remove_dir_all(path)
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        OsFs.rename(from, to)
+    }
+}
+
+#[tokio::test]
+async fn test_failed_move_is_undone() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let before: Vec<(&str, String)> = ["main.tscn", "project.godot", "scripts/loader.gd"]
+        .into_iter()
+        .map(|path| (path, fs::read_to_string(dir.path().join(path)).unwrap()))
+        .collect();
+    let schema = build_schema_with_context(
+        GqlContext::new(dir.path().to_path_buf()).with_fs(Arc::new(ReadOnlySettingsFs)),
+    );
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                moveFile(input: { from: "res://scripts/game.gd", to: "res://core/game.gd" }) {
+                    success message
+                }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["moveFile"]["success"], false, "{:?}", data);
+    assert!(data["moveFile"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("project.godot"));
+
+    // Moves and rewrites already applied are put back, and nothing is left
+    // for recovery to roll forward
+    assert!(dir.path().join("scripts/game.gd").exists());
+    assert!(dir.path().join("scripts/game.gd.uid").exists());
+    assert!(!dir.path().join("core").exists());
+    for (path, content) in before {
+        assert_eq!(fs::read_to_string(dir.path().join(path)).unwrap(), content);
+    }
+    let journal = dir.path().join(".godot-mcp/journal");
+    assert!(fs::read_dir(journal).map_or(true, |mut entries| entries.next().is_none()));
+}
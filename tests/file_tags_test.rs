@@ -0,0 +1,173 @@
+//! File Tag Tests
+//!
+//! Tests for fileTags (owners.toml areas and scene root metadata) and the tag
+//! filters of project, findPropertyValues, gdlint and dependencyGraph.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::Path;
+
+use common::{execute, write};
+
+fn setup_project(root: &Path) {
+    write(
+        root,
+        ".godot-mcp/owners.toml",
+        r#"
+[[area]]
+paths = ["ui/**"]
+tags = ["ui"]
+owners = ["ui-team"]
+
+[[area]]
+paths = ["levels/**", "enemies/*.gd"]
+tags = ["gameplay"]
+owners = ["level-team"]
+"#,
+    );
+    write(
+        root,
+        "ui/hud.tscn",
+        r#"[gd_scene format=3]
+
+[ext_resource type="Script" path="res://ui/hud.gd" id="1"]
+
+[node name="Hud" type="Control"]
+script = ExtResource("1")
+visible = false
+"#,
+    );
+    write(root, "ui/hud.gd", "extends Control\n# TODO: score\n");
+    write(
+        root,
+        "levels/cave.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Cave" type="Node3D"]
+metadata/mcp_tags = "caves, lighting"
+visible = false
+"#,
+    );
+    write(root, "enemies/slime.gd", "extends Node\n# TODO: jump\n");
+    write(
+        root,
+        "menus/title.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Title" type="Control"]
+metadata/mcp_owner = "ui-team"
+visible = false
+"#,
+    );
+}
+
+fn strings(value: &serde_json::Value, key: &str) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v[key].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_file_tags_from_owners_file_and_scene_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        "{ fileTags { files { path tags owners } error { code } } }",
+    )
+    .await;
+    let files = &data["fileTags"]["files"];
+    assert_eq!(
+        strings(files, "path"),
+        vec![
+            "res://enemies/slime.gd",
+            "res://levels/cave.tscn",
+            "res://menus/title.tscn",
+            "res://ui/hud.gd",
+            "res://ui/hud.tscn",
+        ]
+    );
+    assert_eq!(
+        files[1]["tags"],
+        serde_json::json!(["caves", "gameplay", "lighting"])
+    );
+    assert_eq!(files[2]["owners"], serde_json::json!(["ui-team"]));
+
+    let data = execute(
+        &schema,
+        r#"{ fileTags(filter: { owners: ["ui-team"] }) { files { path } } }"#,
+    )
+    .await;
+    assert_eq!(
+        strings(&data["fileTags"]["files"], "path"),
+        vec![
+            "res://menus/title.tscn",
+            "res://ui/hud.gd",
+            "res://ui/hud.tscn"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_queries_scoped_by_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{
+            project(tags: { owners: ["ui-team"] }) { scenes { path } scripts { path } stats { sceneCount } }
+            findPropertyValues(property: "visible", tags: { tags: ["gameplay"] }) { matches { scenePath } }
+            gdlint(tags: { tags: ["ui"] }) { filesChecked diagnostics { file } }
+            dependencyGraph(input: { tags: { tags: ["ui"] } }) { nodes { id } edges { from to } }
+        }"#,
+    )
+    .await;
+
+    assert_eq!(
+        strings(&data["project"]["scenes"], "path"),
+        vec!["res://menus/title.tscn", "res://ui/hud.tscn"]
+    );
+    assert_eq!(
+        strings(&data["project"]["scripts"], "path"),
+        vec!["res://ui/hud.gd"]
+    );
+    assert_eq!(data["project"]["stats"]["sceneCount"], 2);
+
+    assert_eq!(
+        strings(&data["findPropertyValues"]["matches"], "scenePath"),
+        vec!["res://levels/cave.tscn"]
+    );
+
+    assert_eq!(data["gdlint"]["filesChecked"], 1);
+    assert_eq!(data["gdlint"]["diagnostics"][0]["file"], "res://ui/hud.gd");
+
+    let mut nodes = strings(&data["dependencyGraph"]["nodes"], "id");
+    nodes.sort();
+    assert_eq!(nodes, vec!["res://ui/hud.gd", "res://ui/hud.tscn"]);
+    assert_eq!(
+        data["dependencyGraph"]["edges"][0]["from"],
+        "res://ui/hud.tscn"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_owners_file_is_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        ".godot-mcp/owners.toml",
+        "[[area]]\ntags = [\"ui\"]\n",
+    );
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(&schema, "{ fileTags { files { path } error { code } } }").await;
+    assert_eq!(data["fileTags"]["error"]["code"], "OWNERS_INVALID");
+}
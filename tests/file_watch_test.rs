@@ -0,0 +1,141 @@
+//! File Watch Subscription Tests
+//!
+//! Tests for the `fileChanged` / `sceneChanged` subscriptions fed by the
+//! project file watcher.
+
+use futures_util::{Stream, StreamExt};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const MAIN: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+"#;
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("project.godot"),
+        "[application]\nconfig/name=\"Watch Test\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+/// Start a subscription and wait until its watcher is running
+async fn subscribe(
+    root: &Path,
+    query: &str,
+) -> impl Stream<Item = async_graphql::Response> + Unpin {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let mut stream = schema.execute_stream(query.to_string());
+    // The first poll runs the resolver, which starts the watcher
+    let started = tokio::time::timeout(Duration::from_millis(200), stream.next()).await;
+    assert!(started.is_err(), "no event expected yet: {:?}", started);
+    stream
+}
+
+async fn next_event(
+    stream: &mut (impl Stream<Item = async_graphql::Response> + Unpin),
+) -> serde_json::Value {
+    let response = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for a file event")
+        .expect("subscription ended");
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn test_file_changed_reports_watched_files_only() {
+    let dir = project();
+    fs::create_dir(dir.path().join("scripts")).unwrap();
+    let mut stream = subscribe(
+        dir.path(),
+        "subscription { fileChanged { path changeType timestamp } }",
+    )
+    .await;
+
+    fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+    fs::create_dir_all(dir.path().join(".godot/editor")).unwrap();
+    fs::write(dir.path().join(".godot/editor/cache.tres"), "ignored").unwrap();
+    fs::write(dir.path().join("scripts/player.gd"), "extends Node\n").unwrap();
+
+    let event = next_event(&mut stream).await;
+    assert_eq!(event["fileChanged"]["path"], "res://scripts/player.gd");
+    assert_eq!(event["fileChanged"]["changeType"], "CREATED");
+    assert!(event["fileChanged"]["timestamp"]
+        .as_str()
+        .unwrap()
+        .parse::<u64>()
+        .is_ok());
+
+    fs::remove_file(dir.path().join("scripts/player.gd")).unwrap();
+    let event = loop {
+        let event = next_event(&mut stream).await;
+        // The create may be followed by a content write notification
+        if event["fileChanged"]["changeType"] != "MODIFIED" {
+            break event;
+        }
+    };
+    assert_eq!(event["fileChanged"]["path"], "res://scripts/player.gd");
+    assert_eq!(event["fileChanged"]["changeType"], "DELETED");
+}
+
+#[tokio::test]
+async fn test_file_changed_filters_by_extension() {
+    let dir = project();
+    let mut stream = subscribe(
+        dir.path(),
+        r#"subscription { fileChanged(extensions: ["tres"]) { path } }"#,
+    )
+    .await;
+
+    fs::write(dir.path().join("player.gd"), "extends Node\n").unwrap();
+    fs::write(
+        dir.path().join("theme.tres"),
+        "[gd_resource type=\"Theme\" format=3]\n",
+    )
+    .unwrap();
+
+    let event = next_event(&mut stream).await;
+    assert_eq!(event["fileChanged"]["path"], "res://theme.tres");
+}
+
+#[tokio::test]
+async fn test_scene_changed_reparses_scene() {
+    let dir = project();
+    fs::write(dir.path().join("other.tscn"), MAIN).unwrap();
+    fs::write(dir.path().join("main.tscn"), MAIN).unwrap();
+    let mut stream = subscribe(
+        dir.path(),
+        r#"subscription { sceneChanged(path: "res://main.tscn") {
+            path changeType scene { root { name } allNodes { name } }
+        } }"#,
+    )
+    .await;
+
+    fs::write(dir.path().join("other.tscn"), MAIN.replace("Main", "Other")).unwrap();
+    let edited = format!(
+        "{}\n[node name=\"Camera\" type=\"Camera2D\" parent=\"Player\"]\n",
+        MAIN
+    );
+    fs::write(dir.path().join("main.tscn"), edited).unwrap();
+
+    let event = next_event(&mut stream).await;
+    let changed = &event["sceneChanged"];
+    assert_eq!(changed["path"], "res://main.tscn");
+    assert_eq!(changed["changeType"], "MODIFIED");
+    assert_eq!(changed["scene"]["root"]["name"], "Main");
+    let names: Vec<&str> = changed["scene"]["allNodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|n| n["name"].as_str())
+        .collect();
+    assert!(names.contains(&"Camera"), "{:?}", names);
+}
@@ -0,0 +1,207 @@
+//! GDScript AST Tests
+//!
+//! Tests for the script query and the refactoring tools on top of the
+//! GDScript parser: inner classes, match, lambdas and comment-aware renames.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{execute_in, write};
+
+const PLAYER: &str = r#"class_name Player
+extends "res://actors/actor.gd"
+
+signal health_changed(old: int, new: int)
+
+enum State { IDLE, RUN }
+const MAX_HEALTH := 100
+
+@export_range(0, 100) var health: int = MAX_HEALTH
+var state := State.IDLE
+
+class Inventory:
+	var items: Array = []
+
+	class Slot:
+		const SIZE = 4
+
+	func add(item) -> void:
+		items.append(item)
+
+func take_damage(amount: int) -> void:
+	# health is clamped below
+	var old = health
+	health = max(health - amount, 0)
+	health_changed.emit(old, health)
+	match state:
+		State.IDLE, State.RUN:
+			pass
+		_:
+			pass
+	get_tree().create_timer(1.0).timeout.connect(func():
+		print("health:", health)
+	)
+"#;
+
+fn setup_project(root: &Path) {
+    write(root, "actors/player.gd", PLAYER);
+    write(root, "actors/actor.gd", "extends CharacterBody2D\n");
+    write(
+        root,
+        "ui/hud.gd",
+        "extends Control\n\n# Shows the player's health\nfunc _on_changed(_old, health):\n\t$Label.text = str(health)\n",
+    );
+}
+
+#[tokio::test]
+async fn test_script_query_exposes_ast_members() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ script(path: "res://actors/player.gd") {
+            extends className
+            functions { name arguments returnType line }
+            exports { name type defaultValue }
+            signals { name arguments }
+            constants { name value }
+            enums { name values }
+            innerClasses { name functions { name } variables { name } innerClasses { name constants { name value } } }
+            syntaxErrors { line message }
+        } }"#,
+    )
+    .await;
+    let script = &data["script"];
+
+    assert_eq!(script["extends"], "res://actors/actor.gd");
+    assert_eq!(script["className"], "Player");
+    assert_eq!(script["syntaxErrors"], serde_json::json!([]));
+
+    // Only top-level functions; the lambda and inner class methods are not members
+    assert_eq!(
+        script["functions"],
+        serde_json::json!([{
+            "name": "take_damage",
+            "arguments": ["amount"],
+            "returnType": "void",
+            "line": 21
+        }])
+    );
+    assert_eq!(script["exports"][0]["name"], "health");
+    assert_eq!(script["exports"][0]["defaultValue"], "MAX_HEALTH");
+    assert_eq!(
+        script["signals"][0]["arguments"],
+        serde_json::json!(["old: int", "new: int"])
+    );
+    assert_eq!(script["constants"][0]["value"], "100");
+    assert_eq!(
+        script["enums"][0]["values"],
+        serde_json::json!(["IDLE", "RUN"])
+    );
+
+    let inventory = &script["innerClasses"][0];
+    assert_eq!(inventory["name"], "Inventory");
+    assert_eq!(inventory["functions"][0]["name"], "add");
+    assert_eq!(inventory["variables"][0]["name"], "items");
+    assert_eq!(inventory["innerClasses"][0]["name"], "Slot");
+    assert_eq!(inventory["innerClasses"][0]["constants"][0]["value"], "4");
+}
+
+#[tokio::test]
+async fn test_script_query_reports_syntax_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "broken.gd",
+        "extends Node\n\nfunc broken(:\n\tpass\n\nfunc ok():\n\tpass\n",
+    );
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ script(path: "res://broken.gd") { functions { name } syntaxErrors { line } } }"#,
+    )
+    .await;
+
+    assert_eq!(
+        data["script"]["syntaxErrors"],
+        serde_json::json!([{ "line": 3 }])
+    );
+    assert_eq!(
+        data["script"]["functions"],
+        serde_json::json!([{ "name": "ok" }])
+    );
+}
+
+#[tokio::test]
+async fn test_find_references_skips_comments() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ findReferences(symbol: "health") {
+            definition { file line column }
+            references { file line }
+            totalCount
+        } }"#,
+    )
+    .await;
+    let refs = &data["findReferences"];
+
+    assert_eq!(
+        refs["definition"],
+        serde_json::json!({ "file": "res://actors/player.gd", "line": 9, "column": 26 })
+    );
+    let lines: Vec<(String, i64)> = refs["references"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["file"].as_str().unwrap().to_string(),
+                r["line"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    // Comments on player.gd:22 and hud.gd:3 are skipped; "health:" is not the symbol
+    assert!(!lines.contains(&("res://actors/player.gd".to_string(), 22)));
+    assert!(!lines.contains(&("res://ui/hud.gd".to_string(), 3)));
+    assert!(lines.contains(&("res://actors/player.gd".to_string(), 32)));
+    assert!(lines.contains(&("res://ui/hud.gd".to_string(), 5)));
+    assert_eq!(refs["totalCount"], 8);
+}
+
+#[tokio::test]
+async fn test_rename_symbol_rewrites_code_and_signal_names() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    write(
+        dir.path(),
+        "ui/bar.gd",
+        "extends Node\n\nfunc _ready():\n\t# health_changed fires on damage\n\tplayer.connect(\"health_changed\", _on_changed)\n\tplayer.health_changed.connect(_on_changed)\n",
+    );
+
+    let data = execute_in(
+        dir.path(),
+        r#"mutation { renameSymbol(input: { symbol: "health_changed", newName: "hp_changed" }) {
+            success occurrencesReplaced filesChanged { path changesCount }
+        } }"#,
+    )
+    .await;
+    let result = &data["renameSymbol"];
+
+    assert_eq!(result["success"], true);
+    assert_eq!(result["occurrencesReplaced"], 4);
+
+    let bar = fs::read_to_string(dir.path().join("ui/bar.gd")).unwrap();
+    assert!(bar.contains("# health_changed fires on damage"));
+    assert!(bar.contains("player.connect(\"hp_changed\", _on_changed)"));
+    assert!(bar.contains("player.hp_changed.connect(_on_changed)"));
+
+    let player = fs::read_to_string(dir.path().join("actors/player.gd")).unwrap();
+    assert!(player.contains("signal hp_changed(old: int, new: int)"));
+    assert!(player.contains("\thp_changed.emit(old, health)"));
+}
@@ -0,0 +1,184 @@
+//! gdtoolkit Tests
+//!
+//! Tests for gdlint/gdformat against stand-in executables in a fake
+//! virtualenv, including the fallback to the built-in lint.
+
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::gdtoolkit;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use common::{execute_in, write};
+
+/// Reports `func Foo` as an error and fails like a crash on `CRASH`
+const FAKE_GDLINT: &str = r#"#!/bin/sh
+status=0
+for f in "$@"; do
+  if grep -q CRASH "$f"; then
+    echo "Traceback: unexpected token" >&2
+    exit 2
+  fi
+  if grep -q "func Foo" "$f"; then
+    echo "$f:3: Error: Function name \"Foo\" is not valid (function-name)" >&2
+    status=1
+  fi
+done
+if [ $status -ne 0 ]; then echo "Failure: problems found" >&2; fi
+exit $status
+"#;
+
+/// Turns four-space indentation into tabs; rejects `BROKEN`
+const FAKE_GDFORMAT: &str = r#"#!/bin/sh
+input=$(cat)
+case "$input" in
+  *BROKEN*) echo "Unexpected token at line 1" >&2; exit 1 ;;
+esac
+printf '%s\n' "$input" | sed 's/^    /	/'
+"#;
+
+/// Virtualenv shared by every test in this file
+fn fake_venv() -> &'static Path {
+    static VENV: OnceLock<PathBuf> = OnceLock::new();
+    VENV.get_or_init(|| {
+        let venv = tempfile::tempdir().unwrap().keep();
+        let bin = venv.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for (name, script) in [("gdlint", FAKE_GDLINT), ("gdformat", FAKE_GDFORMAT)] {
+            let path = bin.join(name);
+            fs::write(&path, script).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        venv
+    })
+}
+
+async fn execute(root: &Path, query: &str) -> serde_json::Value {
+    gdtoolkit::set_venv(Some(fake_venv().to_path_buf()));
+    execute_in(root, query).await
+}
+
+#[tokio::test]
+async fn test_gdlint_parses_diagnostics() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "player.gd",
+        "extends Node\n\nfunc Foo():\n\tpass\n",
+    );
+    write(dir.path(), "ui/hud.gd", "extends Control\n");
+
+    let data = execute(
+        dir.path(),
+        r#"{ gdlint { success tool filesChecked diagnostics { file line severity code message } } }"#,
+    )
+    .await;
+    let result = &data["gdlint"];
+
+    assert_eq!(result["tool"], "gdlint");
+    assert_eq!(result["filesChecked"], 2);
+    assert_eq!(
+        result["diagnostics"],
+        serde_json::json!([{
+            "file": "res://player.gd",
+            "line": 3,
+            "severity": "ERROR",
+            "code": "function-name",
+            "message": "Function name \"Foo\" is not valid"
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_gdlint_falls_back_to_builtin_when_tool_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "crash.gd",
+        "extends Node\n# CRASH\nfunc broken(:\n\tpass\n",
+    );
+
+    let data = execute(
+        dir.path(),
+        r#"{ gdlint(path: "res://crash.gd") { tool message diagnostics { line code } } }"#,
+    )
+    .await;
+    let result = &data["gdlint"];
+
+    assert_eq!(result["tool"], "builtin");
+    assert!(result["message"]
+        .as_str()
+        .unwrap()
+        .contains("Traceback: unexpected token"));
+    assert_eq!(
+        result["diagnostics"],
+        serde_json::json!([{ "line": 3, "code": "syntax-error" }])
+    );
+}
+
+#[tokio::test]
+async fn test_gdformat_check_and_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let messy = "extends Node\n\nfunc _ready():\n    pass\n";
+    write(dir.path(), "scripts/messy.gd", messy);
+    write(dir.path(), "scripts/clean.gd", "extends Node\n");
+    write(dir.path(), "scripts/broken.gd", "BROKEN\n");
+    write(dir.path(), "other/untouched.gd", messy);
+
+    let query = |check: bool| {
+        format!(
+            r#"mutation {{ gdformat(input: {{ path: "res://scripts", check: {} }}) {{
+                success check filesChecked changedFiles failedFiles {{ path message }}
+            }} }}"#,
+            check
+        )
+    };
+
+    let data = execute(dir.path(), &query(true)).await;
+    let result = &data["gdformat"];
+    assert_eq!(result["success"], false);
+    assert_eq!(result["filesChecked"], 3);
+    assert_eq!(
+        result["changedFiles"],
+        serde_json::json!(["res://scripts/messy.gd"])
+    );
+    assert_eq!(
+        result["failedFiles"],
+        serde_json::json!([{
+            "path": "res://scripts/broken.gd",
+            "message": "Unexpected token at line 1"
+        }])
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("scripts/messy.gd")).unwrap(),
+        messy
+    );
+
+    execute(dir.path(), &query(false)).await;
+    assert_eq!(
+        fs::read_to_string(dir.path().join("scripts/messy.gd")).unwrap(),
+        "extends Node\n\nfunc _ready():\n\tpass\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("other/untouched.gd")).unwrap(),
+        messy
+    );
+}
+
+#[tokio::test]
+async fn test_gdformat_missing_script() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let data = execute(
+        dir.path(),
+        r#"mutation { gdformat(input: { path: "res://missing.gd" }) { success error { code } } }"#,
+    )
+    .await;
+
+    assert_eq!(data["gdformat"]["success"], false);
+    assert_eq!(data["gdformat"]["error"]["code"], "SCRIPT_NOT_FOUND");
+}
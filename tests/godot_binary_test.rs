@@ -0,0 +1,76 @@
+//! Godot Binary Tests
+//!
+//! Tests for the godotBinary query and the setGodotPath mutation: a session
+//! override reported with its version, and pinning it in .godot-version.
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+use common::execute;
+
+const FIELDS: &str =
+    "binary { path source version matchesPin } pinnedVersion candidates { path } error { code }";
+
+async fn set_godot_path(schema: &GqlSchema, arguments: &str) -> serde_json::Value {
+    let query = format!(
+        "mutation {{ setGodotPath(input: {{ {} }}) {{ {} }} }}",
+        arguments, FIELDS
+    );
+    execute(schema, &query).await["setGodotPath"].clone()
+}
+
+// The override is process-wide, so one test covers the whole flow
+#[tokio::test]
+async fn test_set_godot_path_and_pin() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("project");
+    fs::create_dir(&project).unwrap();
+    fs::write(project.join("project.godot"), "config_version=5\n").unwrap();
+    let godot = dir.path().join("my-godot");
+    fs::write(
+        &godot,
+        "#!/bin/sh\necho 'WARNING: no audio driver'\necho 4.3.stable.official.77dcf97d8\n",
+    )
+    .unwrap();
+    fs::set_permissions(&godot, fs::Permissions::from_mode(0o755)).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(project.clone()));
+    let godot_path = godot.display().to_string();
+
+    // A missing executable is refused
+    let result = set_godot_path(&schema, r#"path: "/nonexistent/godot""#).await;
+    assert_eq!(result["error"]["code"], "GODOT_NOT_FOUND");
+
+    let result = set_godot_path(&schema, &format!(r#"path: "{}", pin: true"#, godot_path)).await;
+    assert!(result["error"].is_null(), "{}", result["error"]);
+    assert_eq!(result["binary"]["path"], godot_path.as_str());
+    assert_eq!(result["binary"]["source"], "OVERRIDE");
+    assert_eq!(result["binary"]["version"], "4.3.stable.official.77dcf97d8");
+    assert_eq!(result["binary"]["matchesPin"], true);
+    assert_eq!(result["pinnedVersion"], "4.3.stable");
+    assert_eq!(result["candidates"][0]["path"], godot_path.as_str());
+    assert_eq!(
+        fs::read_to_string(project.join(".godot-version")).unwrap(),
+        "4.3.stable\n"
+    );
+
+    let query = format!("{{ godotBinary {{ {} }} }}", FIELDS);
+    let data = execute(&schema, &query).await;
+    assert_eq!(data["godotBinary"]["binary"]["source"], "OVERRIDE");
+
+    // A pin the override doesn't satisfy is reported, not enforced
+    fs::write(project.join(".godot-version"), "4.2\n").unwrap();
+    let data = execute(&schema, &query).await;
+    assert_eq!(data["godotBinary"]["binary"]["path"], godot_path.as_str());
+    assert_eq!(data["godotBinary"]["binary"]["matchesPin"], false);
+
+    let result = set_godot_path(&schema, "path: null").await;
+    assert!(result["candidates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|c| c["path"] != godot_path.as_str()));
+}
@@ -0,0 +1,235 @@
+//! GraphQL over HTTP Tests
+//!
+//! Tests for the HTTP endpoint mode: bearer token auth and scopes, CORS, TLS
+//! and query execution.
+
+use godot_mcp_rs::config::{HttpToken, TlsConfig, TokenScope};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+const TOKEN: &str = "test-token";
+const READ_TOKEN: &str = "read-token";
+
+/// Start the router on an ephemeral port and return its base URL
+async fn start(project: &std::path::Path, tls: Option<TlsConfig>) -> String {
+    let schema = build_schema_with_context(GqlContext::new(project.to_path_buf()));
+    let app = godot_mcp_rs::http::router(
+        schema,
+        vec![
+            HttpToken::write(TOKEN),
+            HttpToken::Scoped {
+                token: READ_TOKEN.to_string(),
+                scope: TokenScope::Read,
+            },
+        ],
+        &["http://localhost:3000".to_string()],
+    );
+    let acceptor = tls.map(|tls| godot_mcp_rs::http::tls_acceptor(&tls).unwrap());
+    let scheme = if acceptor.is_some() { "https" } else { "http" };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(godot_mcp_rs::http::serve_listener(listener, app, acceptor));
+    format!("{}://{}", scheme, addr)
+}
+
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("project.godot"),
+        "[application]\nconfig/name=\"Http Test\"\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_graphql_requires_bearer_token() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({ "query": "{ project { name } }" });
+
+    let response = client
+        .post(format!("{}/graphql", base))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    let response = client
+        .post(format!("{}/graphql", base))
+        .bearer_auth("wrong")
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    let health = client.get(format!("{}/health", base)).send().await.unwrap();
+    assert_eq!(health.status(), 200);
+}
+
+#[tokio::test]
+async fn test_graphql_executes_query_with_token() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(format!("{}/graphql", base))
+        .bearer_auth(TOKEN)
+        .json(&serde_json::json!({
+            "query": "query($path: String!) { scene(path: $path) { path } project { name } }",
+            "variables": { "path": "res://missing.tscn" }
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["data"]["project"]["name"], "Http Test");
+    assert!(response["data"]["scene"].is_null());
+}
+
+#[tokio::test]
+async fn test_stream_endpoint_sends_subscription_events() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+
+    let mut response = reqwest::Client::new()
+        .post(format!("{}/graphql/stream", base))
+        .bearer_auth(READ_TOKEN)
+        .json(&serde_json::json!({ "query": "subscription { fileChanged { path } }" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    // Keep touching the script until the watcher (started on first poll) sees it
+    let script = dir.path().join("player.gd");
+    let writer = tokio::spawn(async move {
+        loop {
+            fs::write(&script, "extends Node\n").unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    });
+    let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), response.chunk())
+        .await
+        .expect("timed out waiting for an event")
+        .unwrap()
+        .unwrap();
+    writer.abort();
+    let text = String::from_utf8_lossy(&chunk);
+    assert!(text.starts_with("event: next\n"), "{}", text);
+    assert!(
+        text.contains(r#"{"data":{"fileChanged":{"path":"res://player.gd"}}}"#),
+        "{}",
+        text
+    );
+}
+
+#[tokio::test]
+async fn test_cors_preflight_allows_configured_origin() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+
+    let response = reqwest::Client::new()
+        .request(reqwest::Method::OPTIONS, format!("{}/graphql", base))
+        .header("Origin", "http://localhost:3000")
+        .header("Access-Control-Request-Method", "POST")
+        .header(
+            "Access-Control-Request-Headers",
+            "authorization, content-type",
+        )
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "http://localhost:3000"
+    );
+}
+
+#[tokio::test]
+async fn test_read_token_cannot_mutate() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+    let client = reqwest::Client::new();
+
+    let query = client
+        .post(format!("{}/graphql", base))
+        .bearer_auth(READ_TOKEN)
+        .json(&serde_json::json!({ "query": "{ project { name } }" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(query.status(), 200);
+
+    let mutation = client
+        .post(format!("{}/graphql", base))
+        .bearer_auth(READ_TOKEN)
+        .json(&serde_json::json!({
+            "query": "mutation { createScene(input: { path: \"res://a.tscn\", rootType: \"Node\", rootName: \"A\" }) { success } }"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(mutation.status(), 403);
+    assert!(!dir.path().join("a.tscn").exists());
+}
+
+#[tokio::test]
+async fn test_read_token_cannot_take_editor_screenshot() {
+    let dir = project();
+    let base = start(dir.path(), None).await;
+    let client = reqwest::Client::new();
+
+    // The query saves the capture into the project and prunes older ones
+    let screenshot = client
+        .post(format!("{}/graphql", base))
+        .bearer_auth(READ_TOKEN)
+        .json(&serde_json::json!({ "query": "{ editorScreenshot(input: {}) { success path } }" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(screenshot.status(), 403);
+    assert!(!dir.path().join(".godot-mcp/screenshots").exists());
+}
+
+#[tokio::test]
+async fn test_serves_https_with_configured_certificate() {
+    let dir = project();
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_path = dir.path().join("cert.pem");
+    let key_path = dir.path().join("key.pem");
+    fs::write(&cert_path, cert.cert.pem()).unwrap();
+    fs::write(&key_path, cert.key_pair.serialize_pem()).unwrap();
+
+    let base = start(
+        dir.path(),
+        Some(TlsConfig {
+            cert: cert_path,
+            key: key_path,
+        }),
+    )
+    .await;
+    assert!(base.starts_with("https://"));
+
+    let response: serde_json::Value = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+        .post(format!("{}/graphql", base))
+        .bearer_auth(TOKEN)
+        .json(&serde_json::json!({ "query": "{ project { name } }" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(response["data"]["project"]["name"], "Http Test");
+}
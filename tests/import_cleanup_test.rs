@@ -0,0 +1,79 @@
+//! Import Artifact Cleanup Tests
+//!
+//! Tests for cleanImportArtifacts: stale .import/.uid sidecars moved to the
+//! trash and assets missing a .import file reported.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+
+use common::{execute, write};
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "[application]\n");
+    write(dir.path(), "icon.png", "png");
+    write(dir.path(), "icon.png.import", "[remap]\n");
+    write(dir.path(), "player.gd", "extends Node\n");
+    write(dir.path(), "player.gd.uid", "uid://b1player\n");
+    // Sources moved away by an agent
+    write(dir.path(), "art/old.png.import", "[remap]\n");
+    write(dir.path(), "enemy.gd.uid", "uid://c2enemy\n");
+    // Moved source without its sidecar
+    write(dir.path(), "sounds/hit.wav", "wav");
+    dir
+}
+
+#[tokio::test]
+async fn test_clean_import_artifacts_dry_run() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        "mutation { cleanImportArtifacts(dryRun: true) { success dryRun removed missingImports trashPath } }",
+    )
+    .await;
+    assert_eq!(
+        data["cleanImportArtifacts"],
+        serde_json::json!({
+            "success": true,
+            "dryRun": true,
+            "removed": ["res://art/old.png.import", "res://enemy.gd.uid"],
+            "missingImports": ["res://sounds/hit.wav"],
+            "trashPath": null
+        })
+    );
+    assert!(dir.path().join("art/old.png.import").exists());
+    assert!(dir.path().join("enemy.gd.uid").exists());
+}
+
+#[tokio::test]
+async fn test_clean_import_artifacts_moves_to_trash() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        "mutation { cleanImportArtifacts { success removed trashPath } }",
+    )
+    .await;
+    let result = &data["cleanImportArtifacts"];
+    assert_eq!(result["success"], true);
+    let trash = result["trashPath"].as_str().unwrap();
+    assert!(trash.starts_with("res://.godot-mcp/trash/"));
+
+    assert!(!dir.path().join("art/old.png.import").exists());
+    assert!(!dir.path().join("enemy.gd.uid").exists());
+    let trash_dir = dir.path().join(trash.strip_prefix("res://").unwrap());
+    assert!(trash_dir.join("art/old.png.import").is_file());
+    assert!(trash_dir.join("enemy.gd.uid").is_file());
+    // Sidecars of existing sources stay
+    assert!(dir.path().join("icon.png.import").exists());
+    assert!(dir.path().join("player.gd.uid").exists());
+
+    // The moves are in the audit log
+    let data = execute(&schema, "{ sessionReport { files { path } } }").await;
+    let files = data["sessionReport"]["files"].to_string();
+    assert!(files.contains("enemy.gd.uid"), "{}", files);
+}
@@ -0,0 +1,100 @@
+//! Inherited Scene Tests
+//!
+//! Tests for scenes inheriting from a base scene (root with `instance=...`):
+//! `inheritsFrom`, node types taken from the base scene and property overrides
+//! of inherited nodes.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const ENEMY: &str = r#"[gd_scene format=3]
+
+[node name="Enemy" type="CharacterBody2D"]
+
+[node name="Sprite" type="Sprite2D" parent="."]
+
+[node name="Body" type="Node2D" parent="."]
+
+[node name="Shape" type="CollisionShape2D" parent="Body"]
+"#;
+
+const BOSS: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="PackedScene" path="res://enemy.tscn" id="1_enemy"]
+
+[node name="Boss" instance=ExtResource("1_enemy")]
+speed = 50
+
+[node name="Sprite" parent="."]
+scale = Vector2(2, 2)
+
+[node name="Crown" type="Sprite2D" parent="Sprite"]
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("enemy.tscn"), ENEMY).unwrap();
+    fs::write(dir.path().join("boss.tscn"), BOSS).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_inherited_scene_query() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{
+            boss: scene(path: "res://boss.tscn") { inheritsFrom root { type } allNodes { path type } }
+            enemy: scene(path: "res://enemy.tscn") { inheritsFrom }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["boss"]["inheritsFrom"], "res://enemy.tscn");
+    assert_eq!(data["boss"]["root"]["type"], "CharacterBody2D");
+    assert_eq!(
+        data["boss"]["allNodes"],
+        serde_json::json!([
+            { "path": ".", "type": "CharacterBody2D" },
+            { "path": "Sprite", "type": "Sprite2D" },
+            { "path": "Sprite/Crown", "type": "Sprite2D" }
+        ])
+    );
+    assert!(data["enemy"]["inheritsFrom"].is_null());
+}
+
+#[tokio::test]
+async fn test_override_inherited_node_property() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            shape: computeProperty(input: {
+                scenePath: "res://boss.tscn", nodePath: "Body/Shape", property: "disabled", expression: "true"
+            }) { success value }
+            missing: computeProperty(input: {
+                scenePath: "res://boss.tscn", nodePath: "Body/Missing", property: "visible", expression: "false"
+            }) { success error { code } }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["shape"]["success"], true);
+    assert_eq!(
+        data["missing"]["error"]["code"],
+        "VALIDATION_NODE_NOT_FOUND"
+    );
+
+    let tscn = fs::read_to_string(dir.path().join("boss.tscn")).unwrap();
+    assert!(tscn.contains("[node name=\"Shape\" parent=\"Body\"]\ndisabled = true"));
+    // Existing overrides and the inherited root stay without a type
+    assert!(tscn.contains("[node name=\"Boss\" instance=ExtResource(\"1_enemy\")]"));
+    assert!(tscn.contains("[node name=\"Sprite\" parent=\".\"]\nscale = Vector2(2, 2)"));
+    assert!(tscn.contains("[node name=\"Crown\" type=\"Sprite2D\" parent=\"Sprite\"]"));
+}
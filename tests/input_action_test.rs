@@ -4,42 +4,23 @@
 
 use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
 use std::path::PathBuf;
+use tempfile::TempDir;
 
 fn test_project_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_project")
 }
 
-fn build_test_schema() -> godot_mcp_rs::graphql::GqlSchema {
-    let ctx = GqlContext::new(test_project_path());
-    build_schema_with_context(ctx)
-}
-
-/// Backup of original project.godot for cleanup
-const ORIGINAL_PROJECT_GODOT: &str = r#"; Engine configuration file.
-; It's best edited using the editor UI and not directly,
-; since the parameters that go here are not all obvious.
-;
-; Format:
-;   [section] ; section goes between []
-;   param=value ; assign values to parameters
-
-config_version=5
-
-[application]
-
-config/name="TestProject"
-run/main_scene="res://scenes/main.tscn"
-config/features=PackedStringArray("4.5", "Forward Plus")
-
-[editor_plugins]
-
-enabled=PackedStringArray("res://addons/godot_mcp/plugin.cfg")
-"#;
-
-/// Reset project.godot to original state after tests
-fn cleanup_test_project() {
-    let project_godot = test_project_path().join("project.godot");
-    std::fs::write(&project_godot, ORIGINAL_PROJECT_GODOT).ok();
+/// Schema over a temporary copy of test_project's project.godot, so the
+/// mutations never touch the checked-in file
+fn build_test_schema() -> (TempDir, godot_mcp_rs::graphql::GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::copy(
+        test_project_path().join("project.godot"),
+        dir.path().join("project.godot"),
+    )
+    .unwrap();
+    let ctx = GqlContext::new(dir.path().to_path_buf());
+    (dir, build_schema_with_context(ctx))
 }
 
 // ======================
@@ -49,7 +30,7 @@ fn cleanup_test_project() {
 /// Test: addInputAction with valid key event
 #[tokio::test]
 async fn test_add_input_action_valid_key() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     // Use a unique action name that doesn't exist in test_project
     let query = r#"
         mutation {
@@ -73,15 +54,12 @@ async fn test_add_input_action_valid_key() {
         "Expected success but got: {:?}",
         op_result
     );
-
-    // Cleanup: Remove the added action from project.godot
-    cleanup_test_project();
 }
 
 /// Test: addInputAction with multiple events
 #[tokio::test]
 async fn test_add_input_action_multiple_events() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     // Use a unique action name
     let query = r#"
         mutation {
@@ -109,15 +87,12 @@ async fn test_add_input_action_multiple_events() {
         "Expected success but got: {:?}",
         op_result
     );
-
-    // Cleanup
-    cleanup_test_project();
 }
 
 /// Test: addInputAction with empty action name should fail
 #[tokio::test]
 async fn test_add_input_action_empty_name() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     let query = r#"
         mutation {
             addInputAction(input: {
@@ -145,7 +120,7 @@ async fn test_add_input_action_empty_name() {
 /// Test: setProjectSetting with valid path
 #[tokio::test]
 async fn test_set_project_setting_valid() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     let query = r#"
         mutation {
             setProjectSetting(input: {
@@ -169,7 +144,7 @@ async fn test_set_project_setting_valid() {
 /// Test: setProjectSetting with integer value
 #[tokio::test]
 async fn test_set_project_setting_integer() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     let query = r#"
         mutation {
             setProjectSetting(input: {
@@ -194,7 +169,7 @@ async fn test_set_project_setting_integer() {
 /// Test: setProjectSetting with empty path should fail
 #[tokio::test]
 async fn test_set_project_setting_empty_path() {
-    let schema = build_test_schema();
+    let (_dir, schema) = build_test_schema();
     let query = r#"
         mutation {
             setProjectSetting(input: {
@@ -0,0 +1,169 @@
+//! Input Map Tests
+//!
+//! Tests for listInputActions, addInputAction and removeInputAction, which
+//! rewrite the [input] section of project.godot.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use serde_json::json;
+use std::fs;
+
+use common::execute;
+
+const PROJECT: &str = r#"; Engine configuration file.
+
+config_version=5
+
+[application]
+
+config/name="Demo"
+
+[input]
+
+jump={
+"deadzone": 0.2,
+"events": [Object(InputEventKey,"resource_local_to_scene":false,"resource_name":"","device":-1,"window_id":0,"alt_pressed":false,"shift_pressed":false,"ctrl_pressed":false,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":32,"key_label":0,"unicode":32,"location":0,"echo":false,"script":null)
+, Object(InputEventJoypadButton,"resource_local_to_scene":false,"resource_name":"","device":-1,"button_index":0,"pressure":0.0,"pressed":true,"script":null)
+]
+}
+
+[rendering]
+
+textures/canvas_textures/default_texture_filter=0
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), PROJECT).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+#[tokio::test]
+async fn test_list_input_actions() {
+    let (_dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"{ listInputActions { name deadzone events { type className key button device description } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["listInputActions"],
+        json!([{
+            "name": "jump",
+            "deadzone": 0.2,
+            "events": [
+                {"type": "KEY", "className": "InputEventKey", "key": "Space", "button": null, "device": -1, "description": "Space"},
+                {"type": "JOY_BUTTON", "className": "InputEventJoypadButton", "key": null, "button": 0, "device": -1, "description": "Joypad Button 0"}
+            ]
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_add_input_action_serializes_events() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            addInputAction(input: {
+                actionName: "save",
+                deadzone: 0.3,
+                events: [
+                    { type: KEY, key: "Ctrl+S" },
+                    { type: MOUSE_BUTTON, button: 2 },
+                    { type: JOY_AXIS, button: 1, axisValue: -0.8 }
+                ]
+            }) { success error { code } }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["addInputAction"]["success"], true);
+
+    let content = fs::read_to_string(dir.path().join("project.godot")).unwrap();
+    // Added after the existing action, before the next section
+    assert!(content.contains("}\nsave={\n\"deadzone\": 0.3,\n\"events\": [Object(InputEventKey,"));
+    assert!(content.contains(r#""ctrl_pressed":true,"meta_pressed":false,"pressed":false,"keycode":0,"physical_keycode":83,"key_label":0,"unicode":115"#));
+    assert!(content.contains("\n, Object(InputEventMouseButton,"));
+    assert!(content.contains(r#""device":-1,"axis":1,"axis_value":-1.0,"script":null)]"#));
+    assert!(content.contains("]\n}\n\n[rendering]"));
+
+    let data = execute(
+        &schema,
+        r#"{ listInputActions { name deadzone events { type key button axisValue description } } }"#,
+    )
+    .await;
+    let save = &data["listInputActions"][1];
+    assert_eq!(save["name"], "save");
+    assert_eq!(save["deadzone"], 0.3);
+    assert_eq!(
+        save["events"],
+        json!([
+            {"type": "KEY", "key": "Ctrl+S", "button": null, "axisValue": null, "description": "Ctrl+S"},
+            {"type": "MOUSE_BUTTON", "key": null, "button": 2, "axisValue": null, "description": "Mouse Button 2"},
+            {"type": "JOY_AXIS", "key": null, "button": 1, "axisValue": -1.0, "description": "Joypad Axis 1-"}
+        ])
+    );
+
+    // The same name again is rejected
+    let data = execute(
+        &schema,
+        r#"mutation { addInputAction(input: { actionName: "save", events: [] }) { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["addInputAction"]["success"], false);
+    assert_eq!(
+        data["addInputAction"]["error"]["code"],
+        "INPUT_ACTION_EXISTS"
+    );
+}
+
+#[tokio::test]
+async fn test_add_input_action_rejects_unknown_key() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            addInputAction(input: { actionName: "dash", events: [{ type: KEY, key: "Hyper+D" }] }) {
+                success error { code message }
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["addInputAction"]["success"], false);
+    assert_eq!(data["addInputAction"]["error"]["code"], "INVALID_INPUT");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("project.godot")).unwrap(),
+        PROJECT
+    );
+}
+
+#[tokio::test]
+async fn test_remove_input_action() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation { removeInputAction(actionName: "jump") { success } }"#,
+    )
+    .await;
+    assert_eq!(data["removeInputAction"]["success"], true);
+    let content = fs::read_to_string(dir.path().join("project.godot")).unwrap();
+    assert!(!content.contains("jump"));
+    assert!(content.contains("[input]\n\n[rendering]"));
+
+    let data = execute(
+        &schema,
+        r#"mutation { removeInputAction(actionName: "jump") { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["removeInputAction"]["success"], false);
+    assert_eq!(
+        data["removeInputAction"]["error"]["code"],
+        "INPUT_ACTION_NOT_FOUND"
+    );
+}
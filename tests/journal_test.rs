@@ -0,0 +1,126 @@
+//! Journal Tests
+//!
+//! Tests for the write-ahead journal: an operation interrupted mid-way is
+//! completed when the project is opened and reported by serverDiagnostics,
+//! and finished operations leave no journal behind.
+
+mod common;
+
+use godot_mcp_rs::graphql::journal::{Journal, JournalChange};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+use common::{execute, write};
+
+fn journal_entries(root: &Path) -> usize {
+    fs::read_dir(root.join(".godot-mcp/journal"))
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_interrupted_rename_is_recovered_on_open() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(root, "project.godot", "config_version=5\n");
+    write(root, "player.gd", "func jump():\n\tpass\n");
+    write(root, "enemy.gd", "func _ready():\n\t$Player.jump()\n");
+
+    // The crash hit after the first of two files was written
+    let (player, enemy) = (root.join("player.gd"), root.join("enemy.gd"));
+    Journal::begin(
+        root,
+        "renameSymbol",
+        &[
+            JournalChange::Write {
+                path: &player,
+                data: b"func leap():\n\tpass\n",
+            },
+            JournalChange::Write {
+                path: &enemy,
+                data: b"func _ready():\n\t$Player.leap()\n",
+            },
+        ],
+    )
+    .unwrap();
+    fs::write(&player, "func leap():\n\tpass\n").unwrap();
+
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let data = execute(
+        &schema,
+        "{ serverDiagnostics { version journalRecoveries { operation outcome files message } } }",
+    )
+    .await;
+    let diagnostics = &data["serverDiagnostics"];
+    assert_eq!(diagnostics["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        diagnostics["journalRecoveries"],
+        serde_json::json!([{
+            "operation": "renameSymbol",
+            "outcome": "ROLLED_FORWARD",
+            "files": ["res://player.gd", "res://enemy.gd"],
+            "message": null,
+        }])
+    );
+    assert_eq!(
+        fs::read_to_string(&enemy).unwrap(),
+        "func _ready():\n\t$Player.leap()\n"
+    );
+    assert_eq!(journal_entries(root), 0);
+}
+
+#[tokio::test]
+async fn test_finished_operations_leave_no_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(root, "project.godot", "config_version=5\n");
+    write(root, "scripts/player.gd", "func jump():\n\tpass\n");
+    write(
+        root,
+        "main.tscn",
+        "[gd_scene format=3]\n\n[ext_resource type=\"Script\" path=\"res://scripts/player.gd\" id=\"1\"]\n\n[node name=\"Main\" type=\"Node2D\"]\nscript = ExtResource(\"1\")\n",
+    );
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation { renameSymbol(input: { symbol: "jump", newName: "leap" }) { success } }"#,
+    )
+    .await;
+    assert_eq!(data["renameSymbol"]["success"], true);
+    let data = execute(
+        &schema,
+        r#"mutation { moveFile(input: { from: "res://scripts", to: "res://src/scripts" }) { success } }"#,
+    )
+    .await;
+    assert_eq!(data["moveFile"]["success"], true);
+    assert!(fs::read_to_string(root.join("main.tscn"))
+        .unwrap()
+        .contains("res://src/scripts/player.gd"));
+
+    execute(
+        &schema,
+        r#"mutation { beginTransaction(name: "edit") { success } }"#,
+    )
+    .await;
+    execute(
+        &schema,
+        r#"mutation { createScript(input: { path: "res://src/enemy.gd", extends: "Node" }) { success } }"#,
+    )
+    .await;
+    let data = execute(&schema, "mutation { commitTransaction { success } }").await;
+    assert_eq!(data["commitTransaction"]["success"], true);
+    assert!(root.join("src/enemy.gd").exists());
+
+    assert_eq!(journal_entries(root), 0);
+    let data = execute(
+        &schema,
+        "{ serverDiagnostics { journalRecoveries { id } } }",
+    )
+    .await;
+    assert_eq!(
+        data["serverDiagnostics"]["journalRecoveries"],
+        serde_json::json!([])
+    );
+}
@@ -0,0 +1,149 @@
+//! Project Layout Tests
+//!
+//! Tests for suggestProjectLayout and applyProjectLayout.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+fn write_sample_project(root: &Path) {
+    fs::write(root.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(
+        root.join("player.tscn"),
+        r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1"]
+[ext_resource type="Texture2D" path="res://shared.png" id="2"]
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1")
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("enemy.tscn"),
+        r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Texture2D" path="res://shared.png" id="1"]
+
+[node name="Enemy" type="Node2D"]
+"#,
+    )
+    .unwrap();
+    fs::write(root.join("player.gd"), "extends CharacterBody2D\n").unwrap();
+    fs::write(root.join("shared.png"), [0u8; 4]).unwrap();
+    fs::write(root.join("README.md"), "notes\n").unwrap();
+}
+
+fn move_target<'a>(moves: &'a [serde_json::Value], from: &str) -> Option<&'a str> {
+    moves
+        .iter()
+        .find(|m| m["from"] == from)
+        .and_then(|m| m["to"].as_str())
+}
+
+#[tokio::test]
+async fn test_suggest_layout_by_feature() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute("{ suggestProjectLayout(convention: BY_FEATURE) { moves { from to reason } folders unchangedCount } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let plan = &data["suggestProjectLayout"];
+    let moves = plan["moves"].as_array().unwrap();
+
+    assert_eq!(
+        move_target(moves, "res://player.tscn"),
+        Some("res://features/player/player.tscn")
+    );
+    assert_eq!(
+        move_target(moves, "res://player.gd"),
+        Some("res://features/player/player.gd")
+    );
+    assert_eq!(
+        move_target(moves, "res://shared.png"),
+        Some("res://common/assets/textures/shared.png")
+    );
+    // README.md has no target folder
+    assert_eq!(plan["unchangedCount"], 1);
+}
+
+#[tokio::test]
+async fn test_suggest_layout_by_type() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute("{ suggestProjectLayout(convention: BY_TYPE) { moves { from to } folders } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    let plan = &data["suggestProjectLayout"];
+    let moves = plan["moves"].as_array().unwrap();
+
+    assert_eq!(
+        move_target(moves, "res://enemy.tscn"),
+        Some("res://scenes/enemy.tscn")
+    );
+    assert_eq!(
+        move_target(moves, "res://player.gd"),
+        Some("res://scripts/player.gd")
+    );
+    assert!(plan["folders"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("res://assets/textures")));
+}
+
+#[tokio::test]
+async fn test_apply_layout_in_batches() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                applyProjectLayout(input: { convention: BY_TYPE, batchSize: 2 }) {
+                    success movesApplied batchesCompleted totalBatches referencesUpdated checkpointPath
+                }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let applied = &data["applyProjectLayout"];
+
+    assert_eq!(applied["success"], true);
+    assert_eq!(applied["movesApplied"], 4);
+    assert_eq!(applied["totalBatches"], 2);
+    assert_eq!(applied["batchesCompleted"], 2);
+    assert_eq!(applied["referencesUpdated"], 3);
+    assert_eq!(
+        applied["checkpointPath"],
+        "res://.godot-mcp/layout_checkpoint.json"
+    );
+
+    let player = fs::read_to_string(dir.path().join("scenes/player.tscn")).unwrap();
+    assert!(player.contains("path=\"res://scripts/player.gd\""));
+    assert!(player.contains("path=\"res://assets/textures/shared.png\""));
+
+    let checkpoint =
+        fs::read_to_string(dir.path().join(".godot-mcp/layout_checkpoint.json")).unwrap();
+    let checkpoint: serde_json::Value = serde_json::from_str(&checkpoint).unwrap();
+    assert_eq!(checkpoint["moves"].as_array().unwrap().len(), 4);
+
+    // Re-running finds nothing left to do
+    let result = schema
+        .execute("{ suggestProjectLayout(convention: BY_TYPE) { moves { from } } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert!(data["suggestProjectLayout"]["moves"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
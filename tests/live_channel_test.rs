@@ -0,0 +1,156 @@
+//! Live Channel Tests
+//!
+//! Tests for the persistent WebSocket channel to the editor plugin, using a
+//! stub WebSocket server: response matching by id, reconnection, and the
+//! per-command latency reported by liveChannel.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use godot_mcp_rs::ws::LiveChannel;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Start a stub plugin that collects `batch` requests, answers them in
+/// reverse order, then closes the connection when `close_after_reply`
+async fn start_stub_plugin(batch: usize, close_after_reply: bool) -> u16 {
+    let mut requests: Vec<Value> = vec![];
+    common::start_ws_stub_plugin(move |request| {
+        requests.push(request);
+        if requests.len() < batch {
+            return (vec![], false);
+        }
+        let responses = requests
+            .drain(..)
+            .rev()
+            .map(|request| {
+                json!({
+                    "id": request["id"],
+                    "result": {
+                        "playing": true,
+                        "scene": request["params"]["scene"].as_str().unwrap_or("res://main.tscn"),
+                    },
+                })
+            })
+            .collect();
+        (responses, close_after_reply)
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_channel_matches_out_of_order_responses() {
+    let port = start_stub_plugin(2, false).await;
+    let channel = LiveChannel::new(port);
+    let timeout = Duration::from_secs(5);
+
+    let first = json!({ "command": "get_play_state", "params": { "scene": "res://a.tscn" } });
+    let second = json!({ "command": "get_play_state", "params": { "scene": "res://b.tscn" } });
+    let (a, b) = tokio::join!(
+        channel.request(&first, timeout),
+        channel.request(&second, timeout)
+    );
+    assert_eq!(a.unwrap()["scene"], "res://a.tscn");
+    assert_eq!(b.unwrap()["scene"], "res://b.tscn");
+
+    let status = channel.status().await;
+    assert!(status.connected);
+    assert_eq!(status.in_flight, 0);
+    assert_eq!(status.reconnects, 0);
+}
+
+#[tokio::test]
+async fn test_channel_drops_responses_without_id() {
+    // Each request gets a stray reply without an id before its own
+    let port = common::start_ws_stub_plugin(|request| {
+        let stray = json!({ "result": { "scene": "res://stray.tscn" } });
+        let reply =
+            json!({ "id": request["id"], "result": { "scene": request["params"]["scene"] } });
+        let answered = request["params"]["scene"] != "res://unanswered.tscn";
+        (
+            if answered {
+                vec![stray, reply]
+            } else {
+                vec![stray]
+            },
+            false,
+        )
+    })
+    .await;
+    let channel = LiveChannel::new(port);
+
+    let first = json!({ "command": "get_play_state", "params": { "scene": "res://a.tscn" } });
+    let result = channel.request(&first, Duration::from_secs(5)).await;
+    assert_eq!(result.unwrap()["scene"], "res://a.tscn");
+
+    let unanswered =
+        json!({ "command": "get_play_state", "params": { "scene": "res://unanswered.tscn" } });
+    let result = channel
+        .request(&unanswered, Duration::from_millis(200))
+        .await;
+    assert!(result.is_err(), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_live_commands_reconnect_and_report_latency() {
+    let port = start_stub_plugin(1, true).await;
+    let schema = build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(19998)
+            .with_ws_port(Some(port)),
+    );
+
+    for _ in 0..2 {
+        let result = schema
+            .execute("{ playState { editorConnected playing scene } }")
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let data = result.data.into_json().unwrap();
+        assert_eq!(data["playState"]["editorConnected"], true);
+        assert_eq!(data["playState"]["scene"], "res://main.tscn");
+        // Let the stub's close reach the channel
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let result = schema
+        .execute(
+            "{ liveChannel { websocketUrl connected reconnects inFlight
+                commands { command transport calls errors avgMs maxMs lastMs } } }",
+        )
+        .await;
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let channel = result.data.into_json().unwrap()["liveChannel"].clone();
+    assert_eq!(channel["websocketUrl"], format!("ws://localhost:{}", port));
+    assert_eq!(channel["connected"], false);
+    assert_eq!(channel["reconnects"], 1);
+    let commands = channel["commands"].as_array().unwrap();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0]["command"], "get_play_state");
+    assert_eq!(commands[0]["transport"], "WEBSOCKET");
+    assert_eq!(commands[0]["calls"], 2);
+    assert_eq!(commands[0]["errors"], 0);
+    assert!(commands[0]["maxMs"].as_f64().unwrap() >= commands[0]["avgMs"].as_f64().unwrap());
+}
+
+#[tokio::test]
+async fn test_live_commands_fall_back_to_http() {
+    let schema = build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(19997)
+            .with_ws_port(None),
+    );
+
+    let result = schema.execute("{ playState { editorConnected } }").await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["playState"]["editorConnected"], false);
+
+    let result = schema
+        .execute("{ liveChannel { websocketUrl connected commands { command transport calls errors } } }")
+        .await;
+    let channel = result.data.into_json().unwrap()["liveChannel"].clone();
+    assert_eq!(channel["websocketUrl"], Value::Null);
+    assert_eq!(
+        channel["commands"],
+        json!([{ "command": "get_play_state", "transport": "HTTP", "calls": 1, "errors": 1 }])
+    );
+}
@@ -0,0 +1,162 @@
+//! Live Instances Tests
+//!
+//! Tests for editorInstances and liveInstancesQuery, fanning a query out to
+//! stub plugins standing in for two open editors.
+
+mod common;
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::config::EditorInstanceConfig;
+use godot_mcp_rs::graphql::live_resolver::set_editor_instances;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use common::execute;
+
+/// Start a stub plugin answering every command with `response`; commands are
+/// pushed to `received`. Returns its port
+async fn start_stub_plugin(response: Value, received: Arc<Mutex<Vec<Value>>>) -> u16 {
+    common::start_stub_plugin_with(move |body| {
+        received
+            .lock()
+            .unwrap()
+            .push(serde_json::from_str(body).unwrap_or_default());
+        response.to_string()
+    })
+    .await
+}
+
+fn project(name: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("project.godot"),
+        format!(
+            "config_version=5\n\n[application]\n\nconfig/name=\"{}\"\n",
+            name
+        ),
+    )
+    .unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_live_instances_query() {
+    let client = project("Client");
+    let server = project("Server");
+    let client_port = start_stub_plugin(
+        json!({"playing": true, "scene": "res://main.tscn"}),
+        Arc::default(),
+    )
+    .await;
+    let server_commands = Arc::new(Mutex::new(Vec::new()));
+    let server_port = start_stub_plugin(
+        json!({"playing": false, "scene": ""}),
+        server_commands.clone(),
+    )
+    .await;
+    // Nothing listens on the closed editor's port
+    let closed_port = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    set_editor_instances(vec![
+        EditorInstanceConfig {
+            name: "server".to_string(),
+            project: server.path().to_path_buf(),
+            port: server_port,
+            ws_port: None,
+        },
+        EditorInstanceConfig {
+            name: "closed".to_string(),
+            project: server.path().to_path_buf(),
+            port: closed_port,
+            ws_port: None,
+        },
+    ]);
+    let schema = build_schema_with_context(
+        GqlContext::new(client.path().to_path_buf())
+            .with_port(client_port)
+            .with_ws_port(None)
+            .with_timeout(2000),
+    );
+
+    let data = execute(&schema, "{ editorInstances { name port wsPort } }").await;
+    assert_eq!(
+        data["editorInstances"],
+        json!([
+            {"name": "current", "port": client_port, "wsPort": null},
+            {"name": "server", "port": server_port, "wsPort": null},
+            {"name": "closed", "port": closed_port, "wsPort": null}
+        ])
+    );
+
+    let data = execute(
+        &schema,
+        r#"{
+            liveInstancesQuery(query: "{ project { name } playState { editorConnected playing scene } }") {
+                success results { instance { name } success data errors } error { code }
+            }
+        }"#,
+    )
+    .await;
+    let result = &data["liveInstancesQuery"];
+    assert_eq!(result["success"], true);
+    let results = result["results"].as_array().unwrap();
+    assert_eq!(
+        results[0]["data"],
+        json!({
+            "project": {"name": "Client"},
+            "playState": {"editorConnected": true, "playing": true, "scene": "res://main.tscn"}
+        })
+    );
+    assert_eq!(results[1]["instance"]["name"], "server");
+    assert_eq!(
+        results[1]["data"],
+        json!({
+            "project": {"name": "Server"},
+            "playState": {"editorConnected": true, "playing": false, "scene": null}
+        })
+    );
+    assert_eq!(results[2]["data"]["playState"]["editorConnected"], false);
+    assert_eq!(
+        server_commands.lock().unwrap()[0]["command"],
+        "get_play_state"
+    );
+
+    // Only the named instances
+    let data = execute(
+        &schema,
+        r#"{ liveInstancesQuery(query: "{ project { name } }", instances: ["server"]) { results { instance { name } data } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["liveInstancesQuery"]["results"],
+        json!([{"instance": {"name": "server"}, "data": {"project": {"name": "Server"}}}])
+    );
+
+    // Refused: mutations, unknown instances
+    let data = execute(
+        &schema,
+        r#"{ liveInstancesQuery(query: "mutation { stopPlayingScene { success } }") { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["liveInstancesQuery"]["error"]["code"],
+        "READ_ONLY_QUERY_REQUIRED"
+    );
+    let data = execute(
+        &schema,
+        r#"{ liveInstancesQuery(query: "{ playState { playing } }", instances: ["editor2"]) { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["liveInstancesQuery"]["success"], false);
+    assert_eq!(
+        data["liveInstancesQuery"]["error"]["code"],
+        "INSTANCE_NOT_FOUND"
+    );
+}
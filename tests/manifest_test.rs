@@ -0,0 +1,117 @@
+//! Project Manifest Tests
+//!
+//! Tests for projectManifest and manifestDelta queries.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+fn write_sample_project(root: &Path) {
+    fs::write(
+        root.join("project.godot"),
+        r#"config_version=5
+
+[application]
+
+config/name="ManifestProject"
+run/main_scene="res://main.tscn"
+
+[autoload]
+
+Game="*res://game.gd"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": []
+}
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("main.tscn"),
+        r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://game.gd" id="1"]
+
+[node name="Main" type="Node2D"]
+script = ExtResource("1")
+"#,
+    )
+    .unwrap();
+    fs::write(
+        root.join("game.gd"),
+        "class_name Game\nextends Node\n\nfunc _ready():\n    pass\n",
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_project_manifest_contents() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let result = schema
+        .execute("{ projectManifest { generatedAt sceneCount scriptCount json } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+
+    let data = result.data.into_json().unwrap();
+    let manifest = &data["projectManifest"];
+    assert_eq!(manifest["sceneCount"], 1);
+    assert_eq!(manifest["scriptCount"], 1);
+
+    let doc: serde_json::Value = serde_json::from_str(manifest["json"].as_str().unwrap()).unwrap();
+    assert_eq!(doc["project"]["name"], "ManifestProject");
+    assert_eq!(doc["project"]["mainScene"], "res://main.tscn");
+    assert_eq!(doc["scenes"][0]["rootType"], "Node2D");
+    assert_eq!(doc["scripts"][0]["className"], "Game");
+    assert_eq!(doc["autoloads"][0]["name"], "Game");
+    assert_eq!(doc["inputActions"], serde_json::json!(["jump"]));
+    assert_eq!(doc["dependencies"][0]["to"], "res://game.gd");
+    assert_eq!(doc["dependencies"][0]["type"], "ATTACHES_SCRIPT");
+}
+
+#[tokio::test]
+async fn test_manifest_delta_reports_changes_and_removals() {
+    let dir = tempfile::tempdir().unwrap();
+    write_sample_project(dir.path());
+
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    // Far-future timestamp: nothing has changed since
+    let result = schema
+        .execute(
+            r#"{ manifestDelta(since: "99999999999999", knownPaths: ["res://main.tscn", "res://old.gd"]) {
+                changedFiles removedFiles projectChanged
+            } }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let delta = &data["manifestDelta"];
+    assert_eq!(delta["changedFiles"], serde_json::json!([]));
+    assert_eq!(delta["removedFiles"], serde_json::json!(["res://old.gd"]));
+    assert_eq!(delta["projectChanged"], false);
+
+    // Epoch: everything counts as changed
+    let result = schema
+        .execute(r#"{ manifestDelta(since: "0") { changedFiles projectChanged json } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    let delta = &data["manifestDelta"];
+    assert_eq!(delta["changedFiles"].as_array().unwrap().len(), 2);
+    assert_eq!(delta["projectChanged"], true);
+    let doc: serde_json::Value = serde_json::from_str(delta["json"].as_str().unwrap()).unwrap();
+    assert_eq!(doc["scripts"][0]["path"], "res://game.gd");
+
+    // Invalid timestamp
+    let result = schema
+        .execute(r#"{ manifestDelta(since: "yesterday") { json message } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert!(data["manifestDelta"]["json"].is_null());
+    assert!(data["manifestDelta"]["message"].is_string());
+}
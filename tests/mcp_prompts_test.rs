@@ -0,0 +1,121 @@
+//! MCP Prompts Tests
+//!
+//! Tests for prompts/list and prompts/get, and that the GraphQL documents the
+//! workflows suggest are valid against the schema.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use godot_mcp_rs::tools::GodotTools;
+use rmcp::model::PromptMessageContent;
+
+fn prompt_text(tools: &GodotTools, name: &str, args: serde_json::Value) -> String {
+    let args = args.as_object().cloned();
+    let result = tools.get_workflow_prompt(name, args.as_ref()).unwrap();
+    match &result.messages[0].content {
+        PromptMessageContent::Text { text } => text.clone(),
+        other => panic!("Expected text, got {:?}", other),
+    }
+}
+
+/// Every `query { ... }` / `mutation { ... }` in the text, brace-balanced
+fn graphql_documents(text: &str) -> Vec<String> {
+    let mut documents = Vec::new();
+    for keyword in ["query {", "mutation {"] {
+        for (start, _) in text.match_indices(keyword) {
+            let mut depth = 0;
+            for (offset, c) in text[start..].char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            documents.push(text[start..=start + offset].to_string());
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    documents
+}
+
+/// Test: the workflows are listed with their arguments
+#[test]
+fn test_list_workflow_prompts() {
+    let tools = GodotTools::new();
+    let prompts = tools.list_workflow_prompts();
+    let names: Vec<&str> = prompts.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "create_3d_player_controller",
+            "debug_failing_scene",
+            "audit_unused_assets"
+        ]
+    );
+
+    let debug = &prompts[1];
+    let args = debug.arguments.as_ref().unwrap();
+    assert_eq!(args[0].name, "scene_path");
+    assert_eq!(args[0].required, Some(true));
+}
+
+/// Test: arguments and defaults are filled in; missing required arguments fail
+#[test]
+fn test_get_workflow_prompt() {
+    let tools = GodotTools::new();
+
+    let text = prompt_text(
+        &tools,
+        "create_3d_player_controller",
+        serde_json::json!({ "scene_path": "res://actors/hero.tscn" }),
+    );
+    assert!(text.contains(r#"createScene(input: {path: "res://actors/hero.tscn""#));
+    assert!(text.contains(r#"createScript(input: {path: "res://scripts/player.gd""#));
+    assert!(!text.contains("{scene_path}"));
+
+    assert!(tools
+        .get_workflow_prompt("debug_failing_scene", None)
+        .is_err());
+    assert!(tools.get_workflow_prompt("unknown", None).is_err());
+}
+
+/// Test: every suggested query and mutation passes schema validation
+#[tokio::test]
+async fn test_workflow_documents_match_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let ctx = GqlContext::new(dir.path().to_path_buf())
+        .with_port(1)
+        .with_timeout(100);
+    let schema = build_schema_with_context(ctx);
+    let tools = GodotTools::new();
+
+    for prompt in tools.list_workflow_prompts() {
+        let text = prompt_text(
+            &tools,
+            &prompt.name,
+            serde_json::json!({ "scene_path": "res://main.tscn" }),
+        );
+        let documents = graphql_documents(&text);
+        assert!(!documents.is_empty(), "{} has no documents", prompt.name);
+
+        for document in documents {
+            let response = schema.execute(document.as_str()).await;
+            // Resolver failures (no editor, missing files) carry a path;
+            // parse and validation errors do not
+            let invalid: Vec<_> = response
+                .errors
+                .iter()
+                .filter(|e| e.path.is_empty())
+                .collect();
+            assert!(
+                invalid.is_empty(),
+                "{}: {}\n{:?}",
+                prompt.name,
+                document,
+                invalid
+            );
+        }
+    }
+}
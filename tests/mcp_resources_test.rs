@@ -0,0 +1,75 @@
+//! MCP Resources Tests
+//!
+//! Tests for resources/list and resources/read over project files.
+
+use godot_mcp_rs::tools::GodotTools;
+use rmcp::model::ResourceContents;
+
+fn create_project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("scenes")).unwrap();
+    std::fs::create_dir_all(root.join("addons/plugin")).unwrap();
+    std::fs::write(root.join("project.godot"), "config_version=5\n").unwrap();
+    std::fs::write(
+        root.join("scenes/main.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"]\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("player.gd"), "extends Node\n").unwrap();
+    std::fs::write(root.join("icon.png"), [0u8; 4]).unwrap();
+    std::fs::write(root.join("addons/plugin/plugin.gd"), "extends Node\n").unwrap();
+    dir
+}
+
+/// Test: scenes, scripts and project.godot are listed with their res:// URIs
+#[test]
+fn test_list_project_resources() {
+    let dir = create_project();
+    let tools = GodotTools::with_project(dir.path().to_path_buf());
+
+    let resources = tools.list_project_resources();
+    let uris: Vec<&str> = resources.iter().map(|r| r.uri.as_str()).collect();
+    assert_eq!(
+        uris,
+        vec![
+            "res://project.godot",
+            "res://player.gd",
+            "res://scenes/main.tscn"
+        ]
+    );
+    assert_eq!(resources[2].name, "main.tscn");
+    assert_eq!(
+        resources[2].mime_type.as_deref(),
+        Some("text/x-godot-scene")
+    );
+    assert_eq!(resources[1].size, Some(13));
+}
+
+/// Test: resources/read returns the file text; other paths are rejected
+#[test]
+fn test_read_project_resource() {
+    let dir = create_project();
+    let tools = GodotTools::with_project(dir.path().to_path_buf());
+
+    let result = tools
+        .read_project_resource("res://scenes/main.tscn")
+        .unwrap();
+    match &result.contents[0] {
+        ResourceContents::TextResourceContents {
+            uri,
+            mime_type,
+            text,
+            ..
+        } => {
+            assert_eq!(uri, "res://scenes/main.tscn");
+            assert_eq!(mime_type.as_deref(), Some("text/x-godot-scene"));
+            assert!(text.contains("[node name=\"Main\""));
+        }
+        other => panic!("Expected text contents, got {:?}", other),
+    }
+
+    assert!(tools.read_project_resource("res://icon.png").is_err());
+    assert!(tools.read_project_resource("res://missing.gd").is_err());
+    assert!(tools.read_project_resource("res://../outside.gd").is_err());
+}
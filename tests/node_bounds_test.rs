@@ -0,0 +1,127 @@
+//! Node Bounds Tests
+//!
+//! Tests for the nodeBounds query against a stand-in Godot executable that
+//! prints bounds the way the measuring script does.
+
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+use common::{fake_godot, write};
+
+/// Counts its runs in the project's `measured` file and prints two
+/// overlapping sprites under a root control, or fails for a missing scene
+const FAKE_GODOT: &str = r#"#!/bin/sh
+project=""
+scene=""
+while [ $# -gt 0 ]; do
+  case "$1" in
+    --path) project="$2"; shift ;;
+    --) scene="$2"; shift ;;
+  esac
+  shift
+done
+echo "Godot Engine v4.3.stable.official.77dcf97d8 - https://godotengine.org"
+echo run >> "$project/measured"
+if [ ! -f "$project/${scene#res://}" ]; then
+  echo "NODE_BOUNDS_ERROR: Cannot load scene [\"$scene\"]" >&2
+  exit 1
+fi
+echo NODE_BOUNDS_BEGIN
+echo '[{"path":".","type":"Control","kind":"RECT2","position":[0,0],"size":[1152,648]},{"path":"Player","type":"Sprite2D","kind":"RECT2","position":[100,100],"size":[64,64]},{"path":"Enemy","type":"Sprite2D","kind":"RECT2","position":[132,132],"size":[64,64]}]'
+echo NODE_BOUNDS_END
+"#;
+
+fn runs(root: &Path) -> usize {
+    fs::read_to_string(root.join("measured"))
+        .unwrap_or_default()
+        .lines()
+        .count()
+}
+
+async fn node_bounds(root: &Path, arguments: &str) -> serde_json::Value {
+    fake_godot(FAKE_GODOT);
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!(
+        r#"{{ nodeBounds({}) {{
+            success scenePath godot cached
+            nodes {{ path type kind position size }}
+            overlaps {{ a b amount }}
+            error {{ code message }}
+        }} }}"#,
+        arguments
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["nodeBounds"].clone()
+}
+
+const SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Texture2D" path="res://icon.svg" id="1"]
+
+[node name="Hud" type="Control"]
+
+[node name="Player" type="Sprite2D" parent="."]
+texture = ExtResource("1")
+
+[node name="Enemy" type="Sprite2D" parent="."]
+texture = ExtResource("1")
+"#;
+
+#[tokio::test]
+async fn test_node_bounds_measures_and_caches() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "icon.svg", "<svg/>");
+    write(dir.path(), "hud.tscn", SCENE);
+
+    let result = node_bounds(dir.path(), r#"path: "res://hud.tscn""#).await;
+    assert_eq!(result["success"], true, "{}", result["error"]);
+    assert_eq!(result["cached"], false);
+    assert_eq!(
+        result["godot"],
+        fake_godot(FAKE_GODOT).display().to_string()
+    );
+    assert_eq!(result["nodes"].as_array().unwrap().len(), 3);
+    assert_eq!(result["nodes"][1]["type"], "Sprite2D");
+    assert_eq!(result["nodes"][1]["kind"], "RECT2");
+    assert_eq!(result["nodes"][1]["size"], serde_json::json!([64.0, 64.0]));
+    // The root contains both sprites, so only the sprites overlap
+    assert_eq!(
+        result["overlaps"],
+        serde_json::json!([{ "a": "Player", "b": "Enemy", "amount": 1024.0 }])
+    );
+    assert!(dir.path().join(".godot-mcp/node_bounds.json").is_file());
+    assert_eq!(runs(dir.path()), 1);
+
+    // Unchanged: served from the cache without running Godot
+    let result = node_bounds(dir.path(), r#"path: "res://hud.tscn""#).await;
+    assert_eq!(result["cached"], true);
+    assert_eq!(result["overlaps"][0]["amount"], 1024.0);
+    assert_eq!(runs(dir.path()), 1);
+
+    // A changed texture invalidates the entry
+    write(dir.path(), "icon.svg", "<svg width=\"32\"/>");
+    let result = node_bounds(dir.path(), r#"path: "res://hud.tscn""#).await;
+    assert_eq!(result["cached"], false);
+    assert_eq!(runs(dir.path()), 2);
+
+    let result = node_bounds(dir.path(), r#"path: "res://hud.tscn", refresh: true"#).await;
+    assert_eq!(result["cached"], false);
+    assert_eq!(runs(dir.path()), 3);
+}
+
+#[tokio::test]
+async fn test_node_bounds_missing_scene() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+
+    let result = node_bounds(dir.path(), r#"path: "res://missing.tscn""#).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "SCENE_NOT_FOUND");
+}
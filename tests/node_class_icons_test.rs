@@ -0,0 +1,93 @@
+//! Node Class Icon Tests
+//!
+//! Tests for nodeClassIcons, with a stub plugin reporting editor icons and
+//! without an editor (bundled icons).
+
+mod common;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+
+use common::start_stub_plugin;
+
+const QUERY: &str = r#"{
+    nodeClassIcons(types: ["CharacterBody2D", "Label", "Player", "Nope"]) {
+        editorConnected
+        icons { typeName baseClass category known color mimeType iconBase64 source }
+    }
+}"#;
+
+async fn query(port: u16) -> Value {
+    let schema = build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_ws_port(None),
+    );
+    let response = schema.execute(QUERY).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["nodeClassIcons"].clone()
+}
+
+#[tokio::test]
+async fn test_node_class_icons_from_editor() {
+    let response = json!({
+        "success": true,
+        "icons": {
+            "CharacterBody2D": { "known": true, "base": "PhysicsBody2D", "category": "2d", "icon": "cG5n" },
+            "Label": { "known": true, "base": "Control", "category": "control", "icon": "" },
+            "Player": { "known": true, "base": "CharacterBody2D", "category": "2d", "icon": "aWNvbg==" },
+            "Nope": { "known": false }
+        }
+    });
+    let port = start_stub_plugin(response.to_string()).await;
+    let result = query(port).await;
+    assert_eq!(result["editorConnected"], true);
+
+    let icons = result["icons"].as_array().unwrap();
+    assert_eq!(
+        icons[0],
+        json!({
+            "typeName": "CharacterBody2D",
+            "baseClass": "PhysicsBody2D",
+            "category": "NODE_2D",
+            "known": true,
+            "color": "#8da5f3",
+            "mimeType": "image/png",
+            "iconBase64": "cG5n",
+            "source": "EDITOR",
+        })
+    );
+    // No editor icon: generic SVG in the category color
+    assert_eq!(icons[1]["category"], "CONTROL");
+    assert_eq!(icons[1]["source"], "BUNDLED");
+    assert_eq!(icons[1]["mimeType"], "image/svg+xml");
+    // Script class reported by the editor
+    assert_eq!(icons[2]["baseClass"], "CharacterBody2D");
+    assert_eq!(icons[2]["iconBase64"], "aWNvbg==");
+    assert_eq!(icons[3]["known"], false);
+    assert_eq!(icons[3]["category"], "NODE");
+}
+
+#[tokio::test]
+async fn test_node_class_icons_without_editor() {
+    let result = query(19998).await;
+    assert_eq!(result["editorConnected"], false);
+
+    let icons = result["icons"].as_array().unwrap();
+    assert!(icons.iter().all(|icon| icon["source"] == "BUNDLED"));
+    assert_eq!(icons[0]["category"], "NODE_2D");
+    assert_eq!(icons[0]["baseClass"], "PhysicsBody2D");
+    assert_eq!(icons[1]["category"], "CONTROL");
+    assert_eq!(icons[1]["color"], "#8eef97");
+    assert_eq!(icons[2]["known"], false);
+    assert_eq!(icons[2]["baseClass"], Value::Null);
+
+    let svg = BASE64
+        .decode(icons[1]["iconBase64"].as_str().unwrap())
+        .unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.starts_with("<svg"), "{}", svg);
+    assert!(svg.contains("#8eef97"), "{}", svg);
+}
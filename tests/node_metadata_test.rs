@@ -0,0 +1,106 @@
+//! Node Metadata Tests
+//!
+//! Tests for setNodeMetadata mutation and nodeMetadata query.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+const SCENE: &str = r#"[gd_scene format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+"#;
+
+#[tokio::test]
+async fn test_set_and_read_node_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), SCENE).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn",
+                    nodePath: "Player",
+                    key: "generated_by",
+                    value: "scaffold \"platformer\" on 2024-05-01"
+                }) { success message }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["setNodeMetadata"]["success"], true);
+
+    let content = fs::read_to_string(dir.path().join("main.tscn")).unwrap();
+    assert!(content.contains("metadata/mcp_generated_by = "));
+
+    let result = schema
+        .execute(r#"{ nodeMetadata(scenePath: "res://main.tscn") { nodePath key value } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    let entries = data["nodeMetadata"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["nodePath"], "Player");
+    assert_eq!(entries[0]["key"], "generated_by");
+    assert_eq!(entries[0]["value"], "scaffold \"platformer\" on 2024-05-01");
+
+    // null value removes the entry
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn", nodePath: "Player", key: "generated_by"
+                }) { success }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["setNodeMetadata"]["success"], true);
+
+    let result = schema
+        .execute(r#"{ nodeMetadata(scenePath: "res://main.tscn", nodePath: "Player") { key } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert!(data["nodeMetadata"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_set_node_metadata_rejects_bad_input() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), SCENE).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn", nodePath: "Missing", key: "note", value: "x"
+                }) { success error { code } }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["setNodeMetadata"]["success"], false);
+    assert_eq!(
+        data["setNodeMetadata"]["error"]["code"],
+        "VALIDATION_NODE_NOT_FOUND"
+    );
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn", nodePath: ".", key: "bad key", value: "x"
+                }) { success error { code } }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(
+        data["setNodeMetadata"]["error"]["code"],
+        "VALIDATION_INVALID_KEY"
+    );
+}
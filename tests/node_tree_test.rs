@@ -0,0 +1,83 @@
+//! Node Tree Rendering Tests
+//!
+//! Tests for the get_node_tree tool's output styles and filters.
+
+use godot_mcp_rs::tools::GodotTools;
+
+const SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1_abc"]
+
+[node name="Player" type="CharacterBody2D" groups=["players"]]
+script = ExtResource("1_abc")
+
+[node name="Sprite" type="Sprite2D" parent="."]
+
+[node name="Body" type="Node2D" parent="."]
+
+[node name="Arm" type="Node2D" parent="Body"]
+
+[node name="Hand" type="Node2D" parent="Body/Arm"]
+"#;
+
+async fn node_tree(args: serde_json::Value) -> String {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("player.tscn"), SCENE).unwrap();
+    let tools = GodotTools::with_project(dir.path().to_path_buf());
+
+    let mut map = args.as_object().unwrap().clone();
+    map.insert("path".to_string(), "player.tscn".into());
+    let result = tools.handle_get_node_tree(Some(map)).await.unwrap();
+    result.content[0].as_text().unwrap().text.clone()
+}
+
+/// Test: default output keeps two-space indentation with types
+#[tokio::test]
+async fn test_node_tree_default_indent() {
+    let tree = node_tree(serde_json::json!({})).await;
+    assert_eq!(
+        tree,
+        "Player (CharacterBody2D)\n  Sprite (Sprite2D)\n  Body (Node2D)\n    Arm (Node2D)\n      Hand (Node2D)\n"
+    );
+}
+
+/// Test: ASCII style draws box-drawing branches
+#[tokio::test]
+async fn test_node_tree_ascii() {
+    let tree = node_tree(serde_json::json!({ "style": "ascii", "include_types": false })).await;
+    assert_eq!(
+        tree,
+        "Player\n├── Sprite\n└── Body\n    └── Arm\n        └── Hand\n"
+    );
+}
+
+/// Test: Markdown style with scripts and groups, limited depth
+#[tokio::test]
+async fn test_node_tree_markdown_with_details() {
+    let tree = node_tree(serde_json::json!({
+        "style": "markdown",
+        "include_scripts": true,
+        "include_groups": true,
+        "max_depth": 1
+    }))
+    .await;
+    assert_eq!(
+        tree,
+        "- **Player** (CharacterBody2D) script=res://player.gd groups=[players]\n  - **Sprite** (Sprite2D)\n  - **Body** (Node2D) [+2 more]\n"
+    );
+}
+
+/// Test: rendering can start from a subtree; unknown roots are rejected
+#[tokio::test]
+async fn test_node_tree_subtree_root() {
+    let tree = node_tree(serde_json::json!({ "root": "Body/Arm" })).await;
+    assert_eq!(tree, "Arm (Node2D)\n  Hand (Node2D)\n");
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("player.tscn"), SCENE).unwrap();
+    let tools = GodotTools::with_project(dir.path().to_path_buf());
+    let mut map = serde_json::Map::new();
+    map.insert("path".to_string(), "player.tscn".into());
+    map.insert("root".to_string(), "Missing".into());
+    assert!(tools.handle_get_node_tree(Some(map)).await.is_err());
+}
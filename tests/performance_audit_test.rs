@@ -0,0 +1,120 @@
+//! Performance Audit Tests
+//!
+//! Tests for performanceAudit: visibility ranges, shadow light and GPU
+//! particle budgets, and the suggested mutation plan.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node3D"]
+
+[node name="Sun" type="DirectionalLight3D" parent="."]
+shadow_enabled = true
+
+[node name="Lamp" type="OmniLight3D" parent="."]
+shadow_enabled = true
+
+[node name="Torch" type="SpotLight3D" parent="."]
+shadow_enabled = true
+
+[node name="Rock" type="MeshInstance3D" parent="."]
+
+[node name="Tree" type="MeshInstance3D" parent="."]
+visibility_range_end = 80.0
+
+[node name="Smoke" type="GPUParticles3D" parent="."]
+amount = 300
+
+[node name="Sparks" type="GPUParticles3D" parent="."]
+amount = 100
+"#;
+
+#[tokio::test]
+async fn test_performance_audit_suggests_plan() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    fs::write(
+        dir.path().join("menu.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Menu\" type=\"Control\"]\n",
+    )
+    .unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ performanceAudit(options: { maxShadowLights: 1, maxGpuParticles: 200 }) {
+            success
+            scenes { scenePath meshes meshesWithoutVisibilityRange shadowLights particleEmitters gpuParticles }
+            issues { kind nodePath }
+            suggestedPlan { type args }
+        } }"#,
+    )
+    .await;
+    let audit = &data["performanceAudit"];
+    assert_eq!(audit["success"], true);
+    assert_eq!(
+        audit["scenes"],
+        serde_json::json!([{
+            "scenePath": "res://level.tscn",
+            "meshes": 2,
+            "meshesWithoutVisibilityRange": 1,
+            "shadowLights": 3,
+            "particleEmitters": 2,
+            "gpuParticles": 400,
+        }])
+    );
+    let issues: Vec<(&str, Option<&str>)> = audit["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| (i["kind"].as_str().unwrap(), i["nodePath"].as_str()))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            ("MISSING_VISIBILITY_RANGE", Some("Rock")),
+            ("TOO_MANY_SHADOW_LIGHTS", Some("Lamp")),
+            ("TOO_MANY_SHADOW_LIGHTS", Some("Torch")),
+            ("TOO_MANY_PARTICLES", None),
+        ]
+    );
+
+    // The plan applies as-is
+    let request = async_graphql::Request::new(
+        "mutation($operations: [PlannedOperation!]!) { applyMutation(input: { operations: $operations }) { success appliedCount } }",
+    )
+    .variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "operations": audit["suggestedPlan"] }),
+    ));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["applyMutation"]["success"], true);
+    assert_eq!(data["applyMutation"]["appliedCount"], 5);
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains("visibility_range_end = 150.0"));
+    assert!(content.contains("amount = 150"));
+    assert!(content.contains("amount = 50"));
+    assert_eq!(content.matches("shadow_enabled = true").count(), 1);
+
+    let data = execute(
+        &schema,
+        "{ performanceAudit(options: { maxShadowLights: 1, maxGpuParticles: 200 }) { issues { kind } } }",
+    )
+    .await;
+    assert_eq!(data["performanceAudit"]["issues"], serde_json::json!([]));
+
+    let data = execute(
+        &schema,
+        "{ performanceAudit(options: { visibilityRangeEnd: 0 }) { success error { code } } }",
+    )
+    .await;
+    assert_eq!(data["performanceAudit"]["error"]["code"], "INVALID_OPTIONS");
+}
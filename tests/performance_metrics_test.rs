@@ -0,0 +1,103 @@
+//! Performance Metrics Tests
+//!
+//! Tests for performanceMetrics, using a stub plugin whose monitors change
+//! between readings.
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+
+/// Start a stub plugin answering with the readings in turn (the last one
+/// repeats); `count` is incremented per request. Returns its port
+async fn start_stub_plugin(readings: &'static [&'static str], count: Arc<AtomicUsize>) -> u16 {
+    common::start_stub_plugin_with(move |_| {
+        let index = count.fetch_add(1, Ordering::SeqCst);
+        readings[index.min(readings.len() - 1)].to_string()
+    })
+    .await
+}
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+const SMOOTH: &str = r#"{"success": true, "monitors": {"fps": 60.0, "process_time": 0.004,
+    "physics_process_time": 0.001, "draw_calls": 120.0, "objects_in_frame": 80.0,
+    "static_memory": 104857600.0, "video_memory": 52428800.0, "object_count": 900.0,
+    "node_count": 150.0}}"#;
+
+const HITCH: &str = r#"{"success": true, "monitors": {"fps": 30.0, "process_time": 0.02,
+    "physics_process_time": 0.003, "draw_calls": 200.0, "objects_in_frame": 100.0,
+    "static_memory": 110100480.0, "video_memory": 52428800.0, "object_count": 950.0,
+    "node_count": 160.0}}"#;
+
+const QUERY: &str = "performanceMetrics { editorConnected sampleCount fps minFps frameTimeMs \
+    maxFrameTimeMs physicsTimeMs drawCalls objectsInFrame staticMemoryMb videoMemoryMb \
+    objectCount nodeCount }";
+
+#[tokio::test]
+async fn test_performance_metrics_single_sample() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let port = start_stub_plugin(&[SMOOTH], count.clone()).await;
+
+    let result = schema(port).execute(format!("{{ {} }}", QUERY)).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let metrics = &data["performanceMetrics"];
+    assert_eq!(metrics["editorConnected"], true);
+    assert_eq!(metrics["sampleCount"], 1);
+    assert_eq!(metrics["fps"], 60.0);
+    assert_eq!(metrics["frameTimeMs"], 4.0);
+    assert_eq!(metrics["physicsTimeMs"], 1.0);
+    assert_eq!(metrics["drawCalls"], 120.0);
+    assert_eq!(metrics["staticMemoryMb"], 100.0);
+    assert_eq!(metrics["videoMemoryMb"], 50.0);
+    assert_eq!(metrics["nodeCount"], 150);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_performance_metrics_averaged_over_duration() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let port = start_stub_plugin(&[SMOOTH, HITCH, SMOOTH, HITCH], count.clone()).await;
+
+    let result = schema(port)
+        .execute(
+            QUERY.replacen(
+                "performanceMetrics",
+                "{ performanceMetrics(durationMs: 300, intervalMs: 100)",
+                1,
+            ) + " }",
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let metrics = &data["performanceMetrics"];
+    assert_eq!(metrics["sampleCount"], 4);
+    assert_eq!(metrics["fps"], 45.0);
+    assert_eq!(metrics["minFps"], 30.0);
+    assert_eq!(metrics["frameTimeMs"], 12.0);
+    assert_eq!(metrics["maxFrameTimeMs"], 20.0);
+    assert_eq!(metrics["drawCalls"], 160.0);
+    // Levels come from the latest reading
+    assert_eq!(metrics["staticMemoryMb"], 105.0);
+    assert_eq!(metrics["objectCount"], 950);
+}
+
+#[tokio::test]
+async fn test_performance_metrics_without_editor() {
+    let result = schema(19997).execute(format!("{{ {} }}", QUERY)).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let metrics = &data["performanceMetrics"];
+    assert_eq!(metrics["editorConnected"], false);
+    assert_eq!(metrics["sampleCount"], 0);
+    assert_eq!(metrics["fps"], 0.0);
+}
@@ -0,0 +1,211 @@
+//! Physics Layer Tests
+//!
+//! Tests for collisionMatrix (layers and masks aggregated from scenes, named
+//! from project.godot) and setLayerCollision (mask edits on both layers).
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::json;
+use std::fs;
+
+use common::execute;
+
+const PROJECT: &str = r#"config_version=5
+
+[application]
+
+config/name="Layers"
+
+[layer_names]
+
+2d_physics/layer_1="World"
+2d_physics/layer_2="Player"
+2d_physics/layer_3="Enemies"
+3d_physics/layer_1="Terrain"
+"#;
+
+/// Player on layer 2 scanning the world; its hurtbox scans enemies
+const PLAYER: &str = r#"[gd_scene format=3]
+
+[node name="Player" type="CharacterBody2D"]
+collision_layer = 2
+
+[node name="Hurtbox" type="Area2D" parent="."]
+collision_layer = 2
+collision_mask = 4
+
+[node name="Sprite" type="Sprite2D" parent="."]
+"#;
+
+/// Enemy on layer 3 scanning the world only: it walks through the player
+const ENEMY: &str = r#"[gd_scene format=3]
+
+[node name="Enemy" type="CharacterBody2D"]
+collision_layer = 4
+"#;
+
+/// World geometry with defaults (layer 1, mask 1), and a 3D floor
+const LEVEL: &str = r#"[gd_scene format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Walls" type="StaticBody2D" parent="."]
+
+[node name="Floor" type="CSGBox3D" parent="."]
+use_collision = true
+
+[node name="Decor" type="CSGBox3D" parent="."]
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), PROJECT).unwrap();
+    fs::write(dir.path().join("player.tscn"), PLAYER).unwrap();
+    fs::write(dir.path().join("enemy.tscn"), ENEMY).unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_collision_matrix() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ collisionMatrix(space: TWO_D) {
+            layers { layer name objects scannedBy }
+            pairs { layerA layerB nameA nameB aDetectsB bDetectsA }
+            objects { scenePath nodePath nodeType layers mask }
+            scenesScanned
+        } }"#,
+    )
+    .await;
+    let matrix = &data["collisionMatrix"];
+    assert_eq!(matrix["scenesScanned"], 3);
+    assert_eq!(
+        matrix["layers"],
+        json!([
+            {"layer": 1, "name": "World", "objects": 1, "scannedBy": 3},
+            {"layer": 2, "name": "Player", "objects": 2, "scannedBy": 0},
+            {"layer": 3, "name": "Enemies", "objects": 1, "scannedBy": 1},
+        ])
+    );
+    assert_eq!(
+        matrix["pairs"],
+        json!([
+            {"layerA": 1, "layerB": 1, "nameA": "World", "nameB": "World", "aDetectsB": true, "bDetectsA": true},
+            {"layerA": 1, "layerB": 2, "nameA": "World", "nameB": "Player", "aDetectsB": false, "bDetectsA": true},
+            {"layerA": 1, "layerB": 3, "nameA": "World", "nameB": "Enemies", "aDetectsB": false, "bDetectsA": true},
+            {"layerA": 2, "layerB": 3, "nameA": "Player", "nameB": "Enemies", "aDetectsB": true, "bDetectsA": false},
+        ])
+    );
+    let hurtbox = matrix["objects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|o| o["nodePath"] == "Hurtbox")
+        .unwrap();
+    assert_eq!(
+        hurtbox,
+        &json!({"scenePath": "res://player.tscn", "nodePath": "Hurtbox", "nodeType": "Area2D", "layers": [2], "mask": [3]})
+    );
+
+    // CSG shapes count only with use_collision
+    let data = execute(
+        &schema,
+        r#"{ collisionMatrix(space: THREE_D) { layers { layer name objects } objects { nodePath } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["collisionMatrix"]["objects"],
+        json!([{"nodePath": "Floor"}])
+    );
+    assert_eq!(
+        data["collisionMatrix"]["layers"],
+        json!([{"layer": 1, "name": "Terrain", "objects": 1}])
+    );
+}
+
+#[tokio::test]
+async fn test_set_layer_collision() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    // Enemies and the player body/hurtbox scan each other
+    let data = execute(
+        &schema,
+        r#"mutation { setLayerCollision(input: { layerA: "player", layerB: "3", enabled: true }) {
+            success changed { scenePath nodePath mask } scenesWritten error { code }
+        } }"#,
+    )
+    .await;
+    let result = &data["setLayerCollision"];
+    assert_eq!(result["success"], true);
+    assert_eq!(
+        result["changed"],
+        json!([
+            {"scenePath": "res://enemy.tscn", "nodePath": ".", "mask": [1, 2]},
+            {"scenePath": "res://player.tscn", "nodePath": ".", "mask": [1, 3]},
+        ])
+    );
+    assert_eq!(
+        result["scenesWritten"],
+        json!(["res://enemy.tscn", "res://player.tscn"])
+    );
+    assert!(fs::read_to_string(dir.path().join("enemy.tscn"))
+        .unwrap()
+        .contains("collision_mask = 3"));
+    assert!(fs::read_to_string(dir.path().join("player.tscn"))
+        .unwrap()
+        .contains("collision_mask = 5"));
+
+    // Disabling brings the enemy back to the default mask, which Godot omits
+    let data = execute(
+        &schema,
+        r#"mutation { setLayerCollision(input: { layerA: "Player", layerB: "Enemies", enabled: false }) {
+            success scenesWritten
+        } }"#,
+    )
+    .await;
+    assert_eq!(data["setLayerCollision"]["success"], true);
+    let enemy = fs::read_to_string(dir.path().join("enemy.tscn")).unwrap();
+    assert!(!enemy.contains("collision_mask"), "{}", enemy);
+    let player = fs::read_to_string(dir.path().join("player.tscn")).unwrap();
+    assert!(!player.contains("collision_mask = 5"));
+    // The hurtbox scanned enemies on its own and no longer does
+    assert!(!player.contains("collision_mask = 4"));
+}
+
+#[tokio::test]
+async fn test_set_layer_collision_dry_run_and_errors() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            preview: setLayerCollision(input: { layerA: "World", layerB: "Enemies", enabled: true, dryRun: true }) {
+                success changed { nodePath mask } scenesWritten
+            }
+            unknown: setLayerCollision(input: { layerA: "Bullets", layerB: "1", enabled: true }) {
+                success error { code }
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["preview"]["success"], true);
+    assert_eq!(
+        data["preview"]["changed"],
+        json!([{"nodePath": "Walls", "mask": [1, 3]}])
+    );
+    assert_eq!(data["preview"]["scenesWritten"], json!([]));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+
+    assert_eq!(data["unknown"]["success"], false);
+    assert_eq!(data["unknown"]["error"]["code"], "LAYER_NOT_FOUND");
+}
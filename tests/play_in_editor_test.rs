@@ -0,0 +1,237 @@
+//! Play In Editor Tests
+//!
+//! Tests for playState, the play/stop mutations and input simulation, using a
+//! stub plugin that records the commands it receives.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+
+use common::start_recording_stub_plugin;
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+#[tokio::test]
+async fn test_play_state() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "playing": true, "scene": "res://levels/level_1.tscn"}"#,
+        received.clone(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute("{ playState { editorConnected playing scene } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let state = &result.data.into_json().unwrap()["playState"];
+    assert_eq!(state["editorConnected"], true);
+    assert_eq!(state["playing"], true);
+    assert_eq!(state["scene"], "res://levels/level_1.tscn");
+
+    let sent: serde_json::Value = serde_json::from_str(&received.lock().unwrap()[0]).unwrap();
+    assert_eq!(sent["command"], "get_play_state");
+}
+
+#[tokio::test]
+async fn test_play_state_without_editor() {
+    let result = schema(19998)
+        .execute("{ playState { editorConnected playing scene } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let state = &result.data.into_json().unwrap()["playState"];
+    assert_eq!(state["editorConnected"], false);
+    assert_eq!(state["playing"], false);
+    assert!(state["scene"].is_null());
+}
+
+#[tokio::test]
+async fn test_play_and_stop_commands() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+    let schema = schema(port);
+
+    for mutation in ["playMainScene", "playCurrentScene", "stopPlayingScene"] {
+        let result = schema
+            .execute(format!("mutation {{ {} {{ success }} }}", mutation))
+            .await;
+        assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+        assert_eq!(result.data.into_json().unwrap()[mutation]["success"], true);
+    }
+
+    let commands: Vec<String> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| {
+            let sent: serde_json::Value = serde_json::from_str(body).unwrap();
+            sent["command"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(
+        commands,
+        [
+            "play_main_scene",
+            "play_current_scene",
+            "stop_playing_scene"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_play_error_from_plugin() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"error": "No scene is open"}"#, received).await;
+
+    let result = schema(port)
+        .execute("mutation { playCurrentScene { success message error { code } } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = &result.data.into_json().unwrap()["playCurrentScene"];
+    assert_eq!(data["success"], false);
+    assert_eq!(data["message"], "No scene is open");
+    assert_eq!(data["error"]["code"], "PLAY_FAILED");
+}
+
+fn sent_commands(received: &Mutex<Vec<String>>) -> Vec<serde_json::Value> {
+    received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| serde_json::from_str(body).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_send_input_commands() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation {
+                sendInputAction(action: "jump", pressed: true) { success }
+                sendKey(key: "Space") { success }
+                sendMouseClick(x: 120, y: 48.5) { success }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+
+    let sent = sent_commands(&received);
+    assert_eq!(sent[0]["command"], "send_input_action");
+    assert_eq!(
+        sent[0]["params"],
+        serde_json::json!({ "action": "jump", "pressed": true, "strength": 1.0 })
+    );
+    // Without pressed, the key is tapped
+    assert_eq!(
+        sent[1]["params"],
+        serde_json::json!({ "key": "Space", "pressed": null })
+    );
+    assert_eq!(
+        sent[2]["params"],
+        serde_json::json!({ "x": 120.0, "y": 48.5, "button": 1 })
+    );
+}
+
+#[tokio::test]
+async fn test_play_input_sequence() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+
+    let start = Instant::now();
+    let result = schema(port)
+        .execute(
+            r#"mutation {
+                playInputSequence(steps: [
+                    { action: "move_right", pressed: true, waitMs: 200 }
+                    { action: "move_right", pressed: false }
+                    { mouseClick: { x: 10, y: 20, button: 2 } }
+                ]) { success stepsSent error { code } }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    let data = &result.data.into_json().unwrap()["playInputSequence"];
+    assert_eq!(data["success"], true);
+    assert_eq!(data["stepsSent"], 3);
+
+    let sent = sent_commands(&received);
+    let commands: Vec<&str> = sent
+        .iter()
+        .map(|c| c["command"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        commands,
+        ["send_input_action", "send_input_action", "send_mouse_click"]
+    );
+    assert_eq!(sent[1]["params"]["pressed"], false);
+    assert_eq!(sent[2]["params"]["button"], 2);
+}
+
+#[tokio::test]
+async fn test_play_input_sequence_checks_steps_first() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(r#"{"success": true}"#, received.clone()).await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation {
+                playInputSequence(steps: [
+                    { key: "A" }
+                    { key: "B", action: "jump" }
+                ]) { success stepsSent error { code message } }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = &result.data.into_json().unwrap()["playInputSequence"];
+    assert_eq!(data["success"], false);
+    assert_eq!(data["stepsSent"], 0);
+    assert_eq!(data["error"]["code"], "INVALID_INPUT");
+    assert!(data["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("steps[1]:"));
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_input_error_from_plugin() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"error": "No game is running from the editor"}"#,
+        received,
+    )
+    .await;
+
+    let result = schema(port)
+        .execute(
+            r#"mutation {
+                sendKey(key: "Escape") { success message error { code } }
+                playInputSequence(steps: [{ key: "A" }, { key: "B" }]) { success stepsSent error { code } }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["sendKey"]["success"], false);
+    assert_eq!(
+        data["sendKey"]["message"],
+        "No game is running from the editor"
+    );
+    assert_eq!(data["sendKey"]["error"]["code"], "INPUT_FAILED");
+    assert_eq!(data["playInputSequence"]["stepsSent"], 0);
+    assert_eq!(data["playInputSequence"]["error"]["code"], "INPUT_FAILED");
+}
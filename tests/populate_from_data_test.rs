@@ -0,0 +1,135 @@
+//! Populate From Data Tests
+//!
+//! Tests for populateFromData: one templated node per CSV/JSON row.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Items" type="Node2D" parent="."]
+"#;
+
+const COIN: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Coin" type="Area2D"]
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    fs::write(dir.path().join("coin.tscn"), COIN).unwrap();
+    fs::write(
+        dir.path().join("spawns.csv"),
+        "x,y,type\n1,2,gold\n3,4,silver\n",
+    )
+    .unwrap();
+    dir
+}
+
+async fn populate(root: &Path, input: serde_json::Value) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let request = async_graphql::Request::new(
+        r#"mutation($input: PopulateFromDataInput!) {
+            populateFromData(input: $input) {
+                success rows created written
+                error { code message }
+            }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "input": input }),
+    ));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["populateFromData"].clone()
+}
+
+#[tokio::test]
+async fn test_populate_instances_template_per_csv_row() {
+    let dir = setup();
+    let result = populate(
+        dir.path(),
+        serde_json::json!({
+            "scenePath": "res://level.tscn",
+            "templateScene": "res://coin.tscn",
+            "dataFile": "res://spawns.csv",
+            "parent": "Items",
+            "mapping": [
+                { "property": "position", "expression": "Vector2(x, y) * 16" },
+                { "property": "metadata/kind", "expression": "type" }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["rows"], 2);
+    assert_eq!(
+        result["created"],
+        serde_json::json!(["Items/Coin1", "Items/Coin2"])
+    );
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(
+        content.contains(r#"[ext_resource type="PackedScene" path="res://coin.tscn" id="1_coin"]"#)
+    );
+    assert!(
+        content.contains(r#"[node name="Coin2" parent="Items" instance=ExtResource("1_coin")]"#)
+    );
+    assert!(content.contains("position = Vector2(48, 64)"));
+    assert!(content.contains(r#"metadata/kind = "silver""#));
+}
+
+#[tokio::test]
+async fn test_populate_plain_nodes_from_json_with_names() {
+    let dir = setup();
+    fs::write(
+        dir.path().join("lights.json"),
+        r#"[{"id": "Lamp", "energy": 1.5}, {"id": "Lamp", "energy": 2}]"#,
+    )
+    .unwrap();
+
+    let input = serde_json::json!({
+        "scenePath": "res://level.tscn",
+        "nodeType": "PointLight2D",
+        "dataFile": "res://lights.json",
+        "nameColumn": "id",
+        "mapping": [{ "property": "energy", "expression": "energy" }],
+        "dryRun": true
+    });
+    let result = populate(dir.path(), input).await;
+    assert_eq!(result["created"], serde_json::json!(["Lamp", "Lamp2"]));
+    assert_eq!(result["written"], false);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+}
+
+#[tokio::test]
+async fn test_populate_rejects_bad_rows_without_writing() {
+    let dir = setup();
+    let result = populate(
+        dir.path(),
+        serde_json::json!({
+            "scenePath": "res://level.tscn",
+            "nodeType": "Marker2D",
+            "dataFile": "res://spawns.csv",
+            "mapping": [{ "property": "position", "expression": "Vector2(x, z)" }]
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "DATA_ROW_ERROR");
+    assert!(result["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Row 1:"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+}
@@ -0,0 +1,187 @@
+//! Script Profiler Tests
+//!
+//! Tests for startProfiling / stopProfiling and profileReport, using a stub
+//! plugin that records the commands it receives.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+
+use common::start_recording_stub_plugin;
+
+fn schema(port: u16) -> GqlSchema {
+    build_schema_with_context(
+        GqlContext::new(std::env::temp_dir())
+            .with_port(port)
+            .with_timeout(2000),
+    )
+}
+
+fn sent_commands(received: &Mutex<Vec<String>>) -> Vec<serde_json::Value> {
+    received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| serde_json::from_str(body).unwrap())
+        .collect()
+}
+
+/// Rows of the Profiler panel: engine categories have no script
+const PROFILE: &str = r#"{"success": true, "profiling": false, "functions": [
+    {"category": "Frame Time", "name": "Process Time", "script": "", "line": 0, "time": "8.20 ms", "calls": 1},
+    {"category": "Script Functions", "name": "_process", "script": "res://scripts/player.gd", "line": 12, "time": "0.45 ms", "calls": 1},
+    {"category": "Script Functions", "name": "find_path", "script": "res://scripts/enemy.gd", "line": 40, "time": "3.10 ms", "calls": 24},
+    {"category": "Script Functions", "name": "_draw_gizmos", "script": "res://addons/debug_draw/draw.gd", "line": 7, "time": "5.00 ms", "calls": 300},
+    {"category": "Script Functions", "name": "update_hud", "script": "res://ui/hud.gd", "line": 3, "time": "0.45 ms", "calls": 2}
+]}"#;
+
+#[tokio::test]
+async fn test_profile_report() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(PROFILE, received.clone()).await;
+
+    let result = schema(port)
+        .execute(
+            "{ profileReport { editorConnected profiling \
+             functions { name script line timeMs framePercent calls } } }",
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let report = &data["profileReport"];
+    assert_eq!(report["editorConnected"], true);
+    assert_eq!(report["profiling"], false);
+
+    // User scripts only, slowest first; ties go to the function called more
+    let functions = report["functions"].as_array().unwrap();
+    let names: Vec<_> = functions
+        .iter()
+        .map(|f| f["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["find_path", "update_hud", "_process"]);
+    assert_eq!(functions[0]["script"], "res://scripts/enemy.gd");
+    assert_eq!(functions[0]["line"], 40);
+    assert_eq!(functions[0]["timeMs"], 3.1);
+    assert!(functions[0]["framePercent"].is_null());
+    assert_eq!(functions[0]["calls"], 24);
+
+    assert_eq!(sent_commands(&received)[0]["command"], "get_profile_report");
+}
+
+#[tokio::test]
+async fn test_profile_report_with_addons_and_limit() {
+    let port = start_recording_stub_plugin(PROFILE, Arc::default()).await;
+
+    let result = schema(port)
+        .execute("{ profileReport(limit: 2, includeAddons: true) { functions { name } } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    let names: Vec<_> = data["profileReport"]["functions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, ["_draw_gizmos", "find_path"]);
+}
+
+#[tokio::test]
+async fn test_profile_report_in_frame_percent() {
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "profiling": true, "functions": [
+            {"category": "Script Functions", "name": "_process", "script": "res://player.gd", "line": 5, "time": "12.50 %", "calls": 1}
+        ]}"#,
+        Arc::default(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute("{ profileReport { profiling functions { timeMs framePercent } } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    let report = &data["profileReport"];
+    assert_eq!(report["profiling"], true);
+    assert_eq!(report["functions"][0]["timeMs"], 0.0);
+    assert_eq!(report["functions"][0]["framePercent"], 12.5);
+}
+
+#[tokio::test]
+async fn test_profile_report_without_editor() {
+    let result = schema(19995)
+        .execute("{ profileReport { editorConnected functions { name } error { code } } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["profileReport"]["editorConnected"], false);
+    assert!(data["profileReport"]["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_start_and_stop_profiling() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "game_running": true}"#,
+        received.clone(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute("mutation { startProfiling { success message } stopProfiling { success } }")
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["startProfiling"]["success"], true);
+    assert!(data["startProfiling"]["message"].is_null());
+    assert_eq!(data["stopProfiling"]["success"], true);
+
+    let commands: Vec<_> = sent_commands(&received)
+        .iter()
+        .map(|c| c["command"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(commands, ["start_profiling", "stop_profiling"]);
+}
+
+#[tokio::test]
+async fn test_start_profiling_before_the_game_runs() {
+    let port = start_recording_stub_plugin(
+        r#"{"success": true, "game_running": false}"#,
+        Arc::default(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute("mutation { startProfiling { success message } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["startProfiling"]["success"], true);
+    assert!(data["startProfiling"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("when the game is run"));
+}
+
+#[tokio::test]
+async fn test_profiler_error_from_plugin() {
+    let port = start_recording_stub_plugin(
+        r#"{"error": "Profiler panel not found in the editor"}"#,
+        Arc::default(),
+    )
+    .await;
+
+    let result = schema(port)
+        .execute("mutation { startProfiling { success error { code message } } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["startProfiling"]["success"], false);
+    assert_eq!(data["startProfiling"]["error"]["code"], "PROFILER_FAILED");
+
+    let result = schema(port)
+        .execute("{ profileReport { editorConnected error { code } } }")
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["profileReport"]["editorConnected"], true);
+    assert_eq!(data["profileReport"]["error"]["code"], "PROFILER_FAILED");
+}
@@ -0,0 +1,137 @@
+//! Project At Tests
+//!
+//! Tests for createCheckpoint / checkpoints / projectAt: read-only queries
+//! against a snapshot of the project instead of the working tree.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+
+use common::execute;
+
+const PLAYER: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Player" type="CharacterBody2D"]
+
+[node name="Sprite" type="Sprite2D" parent="."]
+"#;
+
+const PLAYER_SCRIPT: &str = "extends CharacterBody2D\n\nfunc jump():\n\tpass\n";
+
+/// Same selection against the snapshot and the working tree
+const SELECTION: &str = r#"
+    scene(path: "res://scenes/player.tscn") { allNodes { name } }
+    script(path: "res://scenes/player.gd") { functions { name } }
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("project.godot"),
+        "[application]\nconfig/name=\"Snapshots\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.path().join("scenes")).unwrap();
+    fs::write(dir.path().join("scenes/player.tscn"), PLAYER).unwrap();
+    fs::write(dir.path().join("scenes/player.gd"), PLAYER_SCRIPT).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+async fn create_checkpoint(schema: &GqlSchema, label: &str) -> String {
+    let data = execute(
+        schema,
+        &format!(
+            r#"mutation {{ createCheckpoint(label: "{}") {{
+                success checkpoint {{ id fileCount }} error {{ message }}
+            }} }}"#,
+            label
+        ),
+    )
+    .await;
+    let result = &data["createCheckpoint"];
+    assert_eq!(result["success"], true, "{}", result);
+    // project.godot, scene and script
+    assert_eq!(result["checkpoint"]["fileCount"], 3);
+    result["checkpoint"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_project_at_reads_snapshot_not_working_tree() {
+    let (dir, schema) = setup();
+    let id = create_checkpoint(&schema, "before").await;
+
+    // Edits after the checkpoint
+    fs::write(
+        dir.path().join("scenes/player.tscn"),
+        format!(
+            "{}\n[node name=\"Hitbox\" type=\"Area2D\" parent=\".\"]\n",
+            PLAYER
+        ),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("scenes/player.gd"),
+        format!("{}\nfunc dash():\n\tpass\n", PLAYER_SCRIPT),
+    )
+    .unwrap();
+
+    let before = execute(
+        &schema,
+        &format!(
+            r#"{{ projectAt(checkpointId: "{}") {{
+                checkpoint {{ label }}
+                project {{ name }}
+                {}
+            }} }}"#,
+            id, SELECTION
+        ),
+    )
+    .await;
+    let after = execute(&schema, &format!("{{ {} }}", SELECTION)).await;
+
+    let snapshot = &before["projectAt"];
+    assert_eq!(snapshot["checkpoint"]["label"], "before");
+    assert_eq!(snapshot["project"]["name"], "Snapshots");
+    assert_eq!(snapshot["scene"]["allNodes"].as_array().unwrap().len(), 2);
+    assert_eq!(snapshot["script"]["functions"].as_array().unwrap().len(), 1);
+    assert_eq!(after["scene"]["allNodes"].as_array().unwrap().len(), 3);
+    assert_eq!(after["script"]["functions"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_checkpoints_listed_and_unknown_id_is_null() {
+    let (_dir, schema) = setup();
+    let first = create_checkpoint(&schema, "one").await;
+    let second = create_checkpoint(&schema, "two").await;
+    assert_ne!(first, second);
+
+    let data = execute(
+        &schema,
+        r#"{
+            checkpoints { id label }
+            projectAt(checkpointId: "../../etc") { project { name } }
+        }"#,
+    )
+    .await;
+    let labels: Vec<&str> = data["checkpoints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, vec!["one", "two"]);
+    assert!(data["projectAt"].is_null());
+}
+
+#[tokio::test]
+async fn test_snapshots_are_not_part_of_the_project() {
+    let (_dir, schema) = setup();
+    create_checkpoint(&schema, "first").await;
+
+    // The first snapshot must not be copied into the second
+    create_checkpoint(&schema, "second").await;
+    let data = execute(&schema, "{ project { scenes { path } } }").await;
+    assert_eq!(data["project"]["scenes"].as_array().unwrap().len(), 1);
+}
@@ -0,0 +1,167 @@
+//! Project Health Tests
+//!
+//! Tests for the aggregated projectHealth report and checkpoint history.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::Path;
+
+use common::write;
+
+const QUERY: &str = r#"{
+    projectHealth {
+        score focus previousScore delta
+        categories { name status score issueCount issues { severity file message } }
+        history { score }
+    }
+}"#;
+
+fn category<'a>(data: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    data["projectHealth"]["categories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap()
+}
+
+fn setup_project(root: &Path) {
+    write(
+        root,
+        "project.godot",
+        "[application]\nconfig/name=\"Health\"\nrun/main_scene=\"res://main.tscn\"\n",
+    );
+    write(
+        root,
+        "main.tscn",
+        r#"[gd_scene format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1"]
+[ext_resource type="Texture2D" path="res://missing.png" id="2"]
+
+[node name="Main" type="Node2D"]
+"#,
+    );
+    write(
+        root,
+        "player.gd",
+        "extends Node2D\n\nfunc _ready():\n\tpass\n",
+    );
+    write(root, "old.gd", "extends Node\n# TODO: remove\n");
+}
+
+#[tokio::test]
+async fn test_project_health_categories() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let result = schema.execute(QUERY).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+
+    let validation = category(&data, "validation");
+    assert_eq!(validation["status"], "FAIL");
+    assert!(validation["issues"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("res://missing.png"));
+
+    // The main scene is an entry point; only the orphan script is unused
+    let unused = category(&data, "unused");
+    assert_eq!(unused["issueCount"], 1);
+    assert_eq!(unused["issues"][0]["file"], "res://old.gd");
+
+    assert_eq!(category(&data, "lint")["issues"][0]["severity"], "INFO");
+    assert_eq!(category(&data, "tests")["status"], "SKIPPED");
+    assert!(category(&data, "tests")["score"].is_null());
+    assert_eq!(data["projectHealth"]["focus"], "validation");
+}
+
+#[tokio::test]
+async fn test_project_health_unused_resources() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    // Referenced from project settings only
+    write(
+        dir.path(),
+        "project.godot",
+        "[application]\nrun/main_scene=\"res://main.tscn\"\n\n[gui]\ntheme/custom=\"res://ui/theme.tres\"\n",
+    );
+    write(
+        dir.path(),
+        "ui/theme.tres",
+        "[gd_resource type=\"Theme\" format=3]\n\n[resource]\n",
+    );
+    write(
+        dir.path(),
+        "fx/dissolve.gdshader",
+        "shader_type canvas_item;\n",
+    );
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = schema.execute(QUERY).await.data.into_json().unwrap();
+    let unused = category(&data, "unused");
+    let files: Vec<&str> = unused["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, ["res://fx/dissolve.gdshader", "res://old.gd"]);
+}
+
+#[tokio::test]
+async fn test_project_health_budgets() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    write(
+        dir.path(),
+        ".godot-mcp/budgets.toml",
+        "[[budget]]\nname = \"textures\"\nfiles = \"**/*.png\"\nmax_file_kb = 1\n",
+    );
+    write(dir.path(), "art/big.png", &"x".repeat(4096));
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = schema.execute(QUERY).await.data.into_json().unwrap();
+    let budgets = category(&data, "budgets");
+    assert_eq!(budgets["status"], "WARN");
+    assert_eq!(budgets["issues"][0]["file"], "res://art/big.png");
+}
+
+#[tokio::test]
+async fn test_project_health_trend() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let recording = QUERY.replace("projectHealth {", "projectHealth(record: true) {");
+
+    // A plain query records nothing
+    let plain = schema.execute(QUERY).await.data.into_json().unwrap();
+    assert_eq!(plain["projectHealth"]["history"], serde_json::json!([]));
+    assert!(!dir.path().join(".godot-mcp/health_history.json").exists());
+
+    let first = schema
+        .execute(recording.as_str())
+        .await
+        .data
+        .into_json()
+        .unwrap();
+    assert!(first["projectHealth"]["previousScore"].is_null());
+    let first_score = first["projectHealth"]["score"].as_i64().unwrap();
+
+    // Fix the broken reference and check the trend
+    write(dir.path(), "missing.png", "png");
+    let second = schema
+        .execute(recording.as_str())
+        .await
+        .data
+        .into_json()
+        .unwrap();
+    let health = &second["projectHealth"];
+    assert_eq!(health["previousScore"], first_score);
+    assert!(health["delta"].as_i64().unwrap() > 0);
+    assert_eq!(health["history"].as_array().unwrap().len(), 2);
+}
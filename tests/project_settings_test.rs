@@ -0,0 +1,124 @@
+//! Project Settings Tests
+//!
+//! Tests for the projectSettings query and setProjectSetting writes through
+//! the structured project.godot editor.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use serde_json::json;
+use std::fs;
+
+use common::execute;
+
+const PROJECT: &str = r#"; Engine configuration file.
+
+config_version=5
+
+[application]
+
+config/name="Demo"
+run/main_scene="res://main.tscn"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": []
+}
+
+[rendering]
+
+textures/canvas_textures/default_texture_filter=0
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), PROJECT).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+#[tokio::test]
+async fn test_project_settings_by_section() {
+    let (_dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"{ projectSettings { sections { name settings { path key value } } error { code } } }"#,
+    )
+    .await;
+    let sections = &data["projectSettings"]["sections"];
+    assert_eq!(
+        sections[0],
+        json!({"name": "", "settings": [{"path": "config_version", "key": "config_version", "value": "5"}]})
+    );
+    assert_eq!(
+        sections[1]["settings"][0],
+        json!({"path": "application/config/name", "key": "config/name", "value": "\"Demo\""})
+    );
+    // Multi-line values stay whole
+    assert_eq!(
+        sections[2]["settings"][0]["value"],
+        "{\n\"deadzone\": 0.5,\n\"events\": []\n}"
+    );
+    assert_eq!(sections.as_array().unwrap().len(), 4);
+
+    let data = execute(
+        &schema,
+        r#"{ projectSettings(prefix: "application/run") { sections { name settings { path } } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["projectSettings"]["sections"],
+        json!([{"name": "application", "settings": [{"path": "application/run/main_scene"}]}])
+    );
+}
+
+#[tokio::test]
+async fn test_set_project_setting_keeps_layout() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            name: setProjectSetting(input: { path: "application/config/name", value: "My Game", type: "string" }) { success }
+            width: setProjectSetting(input: { path: "display/window/size/viewport_width", value: "1920", type: "int" }) { success }
+            filter: setProjectSetting(input: { path: "rendering/textures/canvas_textures/default_texture_filter", value: "1" }) { success }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["name"]["success"], true);
+    assert_eq!(data["width"]["success"], true);
+    assert_eq!(data["filter"]["success"], true);
+
+    let content = fs::read_to_string(dir.path().join("project.godot")).unwrap();
+    assert_eq!(
+        content,
+        PROJECT
+            .replace("\"Demo\"", "\"My Game\"")
+            .replace("default_texture_filter=0", "default_texture_filter=1")
+            + "\n[display]\n\nwindow/size/viewport_width=1920\n"
+    );
+}
+
+#[tokio::test]
+async fn test_set_project_setting_rejects_mistyped_values() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation {
+            setProjectSetting(input: { path: "display/window/size/viewport_width", value: "wide", type: "int" }) {
+                success error { code }
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["setProjectSetting"]["success"], false);
+    assert_eq!(data["setProjectSetting"]["error"]["code"], "INVALID_INPUT");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("project.godot")).unwrap(),
+        PROJECT
+    );
+}
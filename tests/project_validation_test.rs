@@ -0,0 +1,110 @@
+//! Project Validation Tests
+//!
+//! Tests for the naming collision checks reported by `project { validation }`:
+//! handlers on missing autoload methods, autoloads hidden by a class_name and
+//! group names that differ only by case.
+
+use godot_mcp_rs::graphql::vfs::MemoryFs;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+async fn validate(files: Value) -> Value {
+    let root = std::env::temp_dir().join(format!("godot-mcp-validation-{}", std::process::id()));
+    let fs = MemoryFs::from_json(&root, &files).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(root).with_fs(Arc::new(fs)));
+    let response = schema
+        .execute(
+            "{ project { validation { isValid errors { file line message suggestion } warnings { file line message suggestion } } } }",
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["project"]["validation"].clone()
+}
+
+const PROJECT_GODOT: &str = "config_version=5\n\n[autoload]\n\nEvents=\"*res://autoload/events.gd\"\nGame=\"*res://autoload/game.tscn\"\n";
+
+#[tokio::test]
+async fn test_clean_project_is_valid() {
+    let validation = validate(json!({
+        "project.godot": PROJECT_GODOT,
+        "autoload/events.gd": "extends Node\n\nsignal died\n\nfunc on_died():\n\tpass\n",
+        "autoload/game.tscn": "[gd_scene format=3]\n\n[node name=\"Game\" type=\"Node\"]\n",
+        "player.gd": "extends Node\n\nsignal died\n\nfunc _ready():\n\tdied.connect(Events.on_died)\n\tadd_to_group(\"players\")\n",
+    }))
+    .await;
+    assert_eq!(validation["isValid"], true, "{}", validation);
+    assert_eq!(validation["errors"], json!([]));
+    assert_eq!(validation["warnings"], json!([]));
+}
+
+#[tokio::test]
+async fn test_handler_on_missing_autoload_method() {
+    let validation = validate(json!({
+        "project.godot": PROJECT_GODOT,
+        "autoload/base_events.gd": "class_name BaseEvents\nextends Node\n\nfunc on_reset():\n\tpass\n",
+        "autoload/events.gd": "extends BaseEvents\n\nfunc On_Died():\n\tpass\n",
+        "autoload/game.tscn": "[gd_scene format=3]\n\n[ext_resource type=\"Script\" path=\"res://autoload/game.gd\" id=\"1\"]\n\n[node name=\"Game\" type=\"Node\"]\nscript = ExtResource(\"1\")\n",
+        "autoload/game.gd": "extends Node\n\nfunc pause():\n\tpass\n",
+        "player.gd": "extends Node\n\nsignal died\n\nfunc _ready():\n\tdied.connect(Events.on_died)\n\tdied.connect(Events.on_reset)\n\tdied.connect(Callable(Game, \"unpause\"))\n\tdied.connect(Game.pause)\n",
+    }))
+    .await;
+    assert_eq!(validation["isValid"], false);
+
+    let errors = validation["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2, "{:#}", validation);
+    assert_eq!(errors[0]["file"], "res://player.gd");
+    assert_eq!(errors[0]["line"], 6);
+    assert!(errors[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("autoload Events (res://autoload/events.gd) has no method on_died"));
+    assert_eq!(errors[0]["suggestion"], "Did you mean Events.On_Died?");
+    // Autoload scenes are checked through their root node's script
+    assert_eq!(errors[1]["line"], 8);
+    assert!(errors[1]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Game.unpause is connected"));
+}
+
+#[tokio::test]
+async fn test_class_name_hides_autoload() {
+    let validation = validate(json!({
+        "project.godot": PROJECT_GODOT,
+        "autoload/events.gd": "extends Node\n",
+        "autoload/game.tscn": "[gd_scene format=3]\n\n[node name=\"Game\" type=\"Node\"]\n",
+        "ui/events.gd": "@tool\nclass_name Events\nextends Control\n",
+    }))
+    .await;
+
+    let errors = validation["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1, "{:#}", validation);
+    assert_eq!(errors[0]["file"], "res://ui/events.gd");
+    assert_eq!(errors[0]["line"], 2);
+    assert_eq!(
+        errors[0]["message"],
+        "class_name Events hides the autoload Events (project.godot line 5)"
+    );
+}
+
+#[tokio::test]
+async fn test_group_names_differing_by_case() {
+    let validation = validate(json!({
+        "project.godot": "config_version=5\n",
+        "level.tscn": "[gd_scene format=3]\n\n[node name=\"Level\" type=\"Node2D\"]\n\n[node name=\"Slime\" type=\"Node2D\" parent=\".\" groups=[\"Enemies\"]]\n\n[node name=\"Bat\" type=\"Node2D\" parent=\".\" groups=[\"Enemies\", \"flying\"]]\n",
+        "spawner.gd": "extends Node\n\nfunc count():\n\treturn get_tree().get_nodes_in_group(\"enemies\").size()\n",
+    }))
+    .await;
+    assert_eq!(validation["isValid"], true);
+
+    let warnings = validation["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1, "{:#}", validation);
+    assert_eq!(warnings[0]["file"], "res://spawner.gd");
+    assert_eq!(warnings[0]["line"], 4);
+    assert_eq!(warnings[0]["suggestion"], "Use \"Enemies\"");
+    assert!(warnings[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Group \"enemies\" differs only by case from \"Enemies\" (used 2 time(s))"));
+}
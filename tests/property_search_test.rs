@@ -0,0 +1,153 @@
+//! Property Search Tests
+//!
+//! Tests for findPropertyValues across the scenes of a project.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::Path;
+
+use common::write;
+
+fn setup_project(root: &Path) {
+    write(
+        root,
+        "levels/cave.tscn",
+        r#"[gd_scene format=3]
+
+[node name="Cave" type="Node3D"]
+
+[node name="Lights" type="Node3D" parent="."]
+
+[node name="Torch" type="OmniLight3D" parent="Lights"]
+light_energy = 8.0
+
+[node name="Glow" type="OmniLight3D" parent="Lights"]
+light_energy = 2.5
+visible = false
+
+[node name="Sun" type="DirectionalLight3D" parent="."]
+light_energy = 12.0
+"#,
+    );
+    write(
+        root,
+        "ui/hud.tscn",
+        r#"[gd_scene format=3]
+
+[node name="HUD" type="Control"]
+visible = false
+
+[node name="Title" type="Label" parent="."]
+text = "Score"
+"#,
+    );
+}
+
+async fn search(root: &Path, args: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!(
+        "{{ findPropertyValues({}) {{ success scenesScanned matches {{ scenePath nodePath nodeType value }} error {{ code }} }} }}",
+        args
+    );
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()["findPropertyValues"].clone()
+}
+
+fn node_paths(result: &serde_json::Value) -> Vec<String> {
+    result["matches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            format!(
+                "{}:{}",
+                m["scenePath"].as_str().unwrap(),
+                m["nodePath"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_find_by_equality_across_scenes() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = search(
+        dir.path(),
+        r#"property: "visible", valuePattern: "== false""#,
+    )
+    .await;
+    assert_eq!(result["success"], true);
+    assert_eq!(result["scenesScanned"], 2);
+    assert_eq!(
+        node_paths(&result),
+        vec!["res://levels/cave.tscn:Lights/Glow", "res://ui/hud.tscn:."]
+    );
+}
+
+#[tokio::test]
+async fn test_find_by_numeric_comparison_and_type() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = search(
+        dir.path(),
+        r#"property: "light_energy", valuePattern: "> 5""#,
+    )
+    .await;
+    assert_eq!(
+        node_paths(&result),
+        vec![
+            "res://levels/cave.tscn:Lights/Torch",
+            "res://levels/cave.tscn:Sun"
+        ]
+    );
+
+    let result = search(
+        dir.path(),
+        r#"property: "light_energy", valuePattern: ">= 2.5", nodeType: "Omni*""#,
+    )
+    .await;
+    assert_eq!(
+        node_paths(&result),
+        vec![
+            "res://levels/cave.tscn:Lights/Torch",
+            "res://levels/cave.tscn:Lights/Glow"
+        ]
+    );
+    assert_eq!(result["matches"][1]["value"], "2.5");
+}
+
+#[tokio::test]
+async fn test_find_by_regex_within_scene_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = search(
+        dir.path(),
+        r#"property: "visible", scenePattern: "res://levels/**""#,
+    )
+    .await;
+    assert_eq!(result["scenesScanned"], 1);
+    assert_eq!(
+        node_paths(&result),
+        vec!["res://levels/cave.tscn:Lights/Glow"]
+    );
+
+    let result = search(dir.path(), r#"property: "text", valuePattern: "^\"Sc""#).await;
+    assert_eq!(node_paths(&result), vec!["res://ui/hud.tscn:Title"]);
+    assert_eq!(result["matches"][0]["nodeType"], "Label");
+}
+
+#[tokio::test]
+async fn test_invalid_value_pattern() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = search(dir.path(), r#"property: "text", valuePattern: "(unclosed""#).await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "VALUE_PATTERN_INVALID");
+}
@@ -0,0 +1,141 @@
+//! Remap Animation Tracks Tests
+//!
+//! Tests for remapAnimationTracks on scene files: rewriting the track paths of
+//! animations embedded in an AnimationPlayer's libraries.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const HERO: &str = r#"[gd_scene load_steps=4 format=3]
+
+[sub_resource type="Animation" id="Animation_walk"]
+resource_name = "walk"
+length = 1.0
+tracks/0/type = "value"
+tracks/0/path = NodePath("Body/Sprite:frame")
+tracks/1/type = "value"
+tracks/1/path = NodePath("Body:position")
+tracks/2/type = "method"
+tracks/2/path = NodePath(".")
+
+[sub_resource type="Animation" id="Animation_hit"]
+resource_name = "hit"
+tracks/0/type = "value"
+tracks/0/path = NodePath("Body/Sprite:modulate")
+
+[sub_resource type="AnimationLibrary" id="AnimationLibrary_main"]
+_data = {
+&"hit": SubResource("Animation_hit"),
+&"walk": SubResource("Animation_walk")
+}
+
+[node name="Hero" type="CharacterBody2D"]
+
+[node name="Rig" type="Node2D" parent="."]
+
+[node name="Body" type="Node2D" parent="Rig"]
+
+[node name="Sprite" type="Sprite2D" parent="Rig/Body"]
+
+[node name="AnimationPlayer" type="AnimationPlayer" parent="."]
+libraries = {
+&"": SubResource("AnimationLibrary_main")
+}
+"#;
+
+async fn remap(root: &Path, input: serde_json::Value) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let request = async_graphql::Request::new(
+        r#"mutation($input: RemapAnimationTracksInput!) {
+            remapAnimationTracks(input: $input) {
+                success dryRun remapped { animation track from to } error { code message }
+            }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "input": input }),
+    ));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["remapAnimationTracks"].clone()
+}
+
+#[tokio::test]
+async fn test_remap_rewrites_tracks_below_moved_node() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("hero.tscn"), HERO).unwrap();
+    let input = |dry_run: bool| {
+        serde_json::json!({
+            "scenePath": "res://hero.tscn",
+            "player": "AnimationPlayer",
+            "mapping": [{ "from": "Body", "to": "Rig/Body" }],
+            "dryRun": dry_run,
+        })
+    };
+
+    let preview = remap(dir.path(), input(true)).await;
+    assert_eq!(preview["success"], true, "{}", preview);
+    assert_eq!(preview["remapped"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("hero.tscn")).unwrap(),
+        HERO
+    );
+
+    let result = remap(dir.path(), input(false)).await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(
+        result["remapped"][0],
+        serde_json::json!({
+            "animation": "hit",
+            "track": 0,
+            "from": "Body/Sprite:modulate",
+            "to": "Rig/Body/Sprite:modulate",
+        })
+    );
+    assert_eq!(result["remapped"][2]["track"], 1);
+    assert_eq!(result["remapped"][2]["to"], "Rig/Body:position");
+
+    let tscn = fs::read_to_string(dir.path().join("hero.tscn")).unwrap();
+    assert!(tscn.contains("tracks/0/path = NodePath(\"Rig/Body/Sprite:frame\")"));
+    assert!(tscn.contains("tracks/1/path = NodePath(\"Rig/Body:position\")"));
+    assert!(tscn.contains("tracks/2/path = NodePath(\".\")"));
+    assert!(!tscn.contains("NodePath(\"Body"));
+}
+
+#[tokio::test]
+async fn test_remap_filters_animations_and_reports_missing_player() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("hero.tscn"), HERO).unwrap();
+
+    let result = remap(
+        dir.path(),
+        serde_json::json!({
+            "scenePath": "res://hero.tscn",
+            "player": "AnimationPlayer",
+            "mapping": [{ "from": "Body/Sprite:frame", "to": "Rig/Body/Sprite:frame" }],
+            "animations": ["walk"],
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["remapped"].as_array().unwrap().len(), 1);
+    let tscn = fs::read_to_string(dir.path().join("hero.tscn")).unwrap();
+    assert!(tscn.contains("NodePath(\"Body/Sprite:modulate\")"));
+
+    let result = remap(
+        dir.path(),
+        serde_json::json!({
+            "scenePath": "res://hero.tscn",
+            "player": "Rig",
+            "mapping": [],
+        }),
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "TRACK_REMAP_FAILED");
+    assert_eq!(
+        result["error"]["message"],
+        "'Rig' is not an AnimationPlayer"
+    );
+}
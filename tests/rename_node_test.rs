@@ -0,0 +1,79 @@
+//! Rename Node Tests
+//!
+//! Tests for renameNode: renaming a node in a scene file along with the child
+//! parent paths, NodePath properties and connections that refer to it.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Door" type="Area2D" parent="."]
+target = NodePath("../Spawn")
+
+[node name="Shape" type="CollisionShape2D" parent="Door"]
+
+[node name="Spawn" type="Marker2D" parent="."]
+door = NodePath("../Door")
+shape = NodePath("../Door/Shape:shape")
+
+[connection signal="body_entered" from="Door" to="." method="_on_door_entered"]
+"#;
+
+async fn rename(root: &Path, node_path: &str, new_name: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let request = async_graphql::Request::new(
+        r#"mutation($input: RenameNodeInput!) {
+            renameNode(input: $input) { success message error { code } }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(serde_json::json!({
+        "input": {
+            "scenePath": "res://level.tscn",
+            "nodePath": node_path,
+            "newName": new_name,
+        }
+    })));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["renameNode"].clone()
+}
+
+#[tokio::test]
+async fn test_rename_updates_children_node_paths_and_connections() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let result = rename(dir.path(), "Door", "Gate").await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["message"], "Renamed to 'Gate'");
+
+    let tscn = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(tscn.contains(
+        "[node name=\"Gate\" type=\"Area2D\" parent=\".\"]\ntarget = NodePath(\"../Spawn\")"
+    ));
+    assert!(tscn.contains("[node name=\"Shape\" type=\"CollisionShape2D\" parent=\"Gate\"]"));
+    assert!(tscn.contains("door = NodePath(\"../Gate\")"));
+    assert!(tscn.contains("shape = NodePath(\"../Gate/Shape:shape\")"));
+    assert!(tscn.contains("from=\"Gate\""));
+    assert!(!tscn.contains("Door"));
+}
+
+#[tokio::test]
+async fn test_rename_rejects_sibling_names_and_invalid_characters() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    for new_name in ["Spawn", "Door:2"] {
+        let result = rename(dir.path(), "Door", new_name).await;
+        assert_eq!(result["success"], false);
+        assert_eq!(result["error"]["code"], "NODE_RENAME_FAILED");
+    }
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+}
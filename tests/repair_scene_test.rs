@@ -0,0 +1,98 @@
+//! Scene Repair Tests
+//!
+//! Tests for repairScene: structural validation and auto-fix of .tscn files.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const MALFORMED: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+
+[node name="Enemy" type="Node2D" parent="Enemies"]
+
+[node name="Sprite" type="Sprite2D" parent="Enemies/Enemy"]
+
+[node name="Stray" type="Node"]
+"#;
+
+async fn repair(root: &Path, fixes: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let mutation = format!(
+        r#"mutation {{
+            repairScene(path: "res://level.tscn", fixes: [{}]) {{
+                success written applied
+                issues {{ kind nodePath message }}
+                remaining {{ kind nodePath }}
+                error {{ code }}
+            }}
+        }}"#,
+        fixes
+    );
+    let response = schema.execute(&mutation).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["repairScene"].clone()
+}
+
+#[tokio::test]
+async fn test_repair_scene_reports_without_fixes() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), MALFORMED).unwrap();
+
+    let result = repair(dir.path(), "").await;
+    assert_eq!(result["success"], true);
+    assert_eq!(result["written"], false);
+
+    let kinds: Vec<&str> = result["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, vec!["DUPLICATE", "ORPHAN", "EXTRA_ROOT"]);
+    assert_eq!(result["issues"][1]["nodePath"], "Enemies/Enemy");
+    assert_eq!(result["remaining"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        MALFORMED
+    );
+}
+
+#[tokio::test]
+async fn test_repair_scene_reattaches_and_drops_duplicates() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), MALFORMED).unwrap();
+
+    let result = repair(dir.path(), "REATTACH_ORPHANS, DROP_DUPLICATES").await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["written"], true);
+    assert_eq!(result["applied"].as_array().unwrap().len(), 3);
+    assert!(result["remaining"].as_array().unwrap().is_empty());
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains(r#"[node name="Enemy" type="Node2D" parent="."]"#));
+    assert!(content.contains(r#"[node name="Sprite" type="Sprite2D" parent="Enemy"]"#));
+    assert!(content.contains(r#"[node name="Stray" type="Node" parent="."]"#));
+    assert_eq!(content.matches(r#"name="Player""#).count(), 1);
+}
+
+#[tokio::test]
+async fn test_repair_scene_drops_orphans() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), MALFORMED).unwrap();
+
+    let result = repair(dir.path(), "DROP_ORPHANS").await;
+    assert_eq!(result["written"], true);
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(!content.contains("Enemy"));
+    assert!(!content.contains("Sprite"));
+    assert!(!content.contains("Stray"));
+    // Duplicates are left alone unless requested
+    assert_eq!(result["remaining"][0]["kind"], "DUPLICATE");
+}
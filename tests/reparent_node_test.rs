@@ -0,0 +1,73 @@
+//! Reparent Node Tests
+//!
+//! Tests for reparentNodeInScene: moving a node and its descendants under a
+//! different parent in a scene file.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const LEVEL: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Door" type="Area2D" parent="."]
+position = Vector2(64, 0)
+
+[node name="Shape" type="CollisionShape2D" parent="Door"]
+
+[node name="House" type="Node2D" parent="."]
+
+[connection signal="body_entered" from="Door" to="." method="_on_door_entered"]
+"#;
+
+async fn reparent(root: &Path, node_path: &str, new_parent: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let request = async_graphql::Request::new(
+        r#"mutation($input: ReparentNodeInSceneInput!) {
+            reparentNodeInScene(input: $input) { success message error { code } }
+        }"#,
+    )
+    .variables(async_graphql::Variables::from_json(serde_json::json!({
+        "input": {
+            "scenePath": "res://level.tscn",
+            "nodePath": node_path,
+            "newParent": new_parent,
+        }
+    })));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["reparentNodeInScene"].clone()
+}
+
+#[tokio::test]
+async fn test_reparent_keeps_properties_children_and_connections() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let result = reparent(dir.path(), "Door", "House").await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["message"], "Moved to 'House/Door'");
+
+    let tscn = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(tscn.contains(
+        "[node name=\"Door\" type=\"Area2D\" parent=\"House\"]\nposition = Vector2(64, 0)"
+    ));
+    assert!(tscn.contains("[node name=\"Shape\" type=\"CollisionShape2D\" parent=\"House/Door\"]"));
+    assert!(tscn.contains("from=\"House/Door\""));
+    assert!(tscn.find("name=\"House\"").unwrap() < tscn.find("name=\"Door\"").unwrap());
+}
+
+#[tokio::test]
+async fn test_reparent_below_itself_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let result = reparent(dir.path(), "Door", "Door/Shape").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "NODE_REPARENT_FAILED");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+}
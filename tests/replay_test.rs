@@ -0,0 +1,111 @@
+//! Replay Tests
+//!
+//! Tests for recording tool calls to a replay file and running them again
+//! against an in-memory copy of the project.
+
+use godot_mcp_rs::graphql::vfs::MemoryFs;
+use godot_mcp_rs::graphql::GqlContext;
+use godot_mcp_rs::replay::{read_replay_file, replay, ReplayRecorder};
+use godot_mcp_rs::tools::gql_tools::call_gql_tool;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const FIXTURE: &str = include_str!("fixtures/sandbox_project.json");
+
+/// Project root that does not exist on disk
+fn virtual_root(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("godot-mcp-replay-{}-{}", name, std::process::id()))
+}
+
+fn sandbox(root: &Path, fixture: &str) -> GqlContext {
+    let fixture: Value = serde_json::from_str(fixture).unwrap();
+    let fs = MemoryFs::from_json(root, &fixture).unwrap();
+    GqlContext::new(root.to_path_buf()).with_fs(Arc::new(fs))
+}
+
+fn args(value: Value) -> Option<Map<String, Value>> {
+    value.as_object().cloned()
+}
+
+/// Calls an agent might make: create a script, read it back, then a typo'd tool
+fn session() -> Vec<(&'static str, Option<Map<String, Value>>)> {
+    vec![
+        (
+            "godot_mutate",
+            args(json!({
+                "mutation": r#"mutation { createScript(input: { path: "res://scripts/enemy.gd", extends: "Node2D" }) { success } }"#
+            })),
+        ),
+        (
+            "godot_query",
+            args(json!({ "query": r#"{ project { stats { sceneCount scriptCount } } }"# })),
+        ),
+        (
+            "godot_qurey",
+            args(json!({ "query": "{ project { name } }" })),
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_recorded_session_replays_identically() {
+    let dir = tempfile::tempdir().unwrap();
+    let replay_file = dir.path().join("logs/replay.jsonl");
+    let recorder = ReplayRecorder::open(&replay_file).unwrap();
+
+    let ctx = sandbox(&virtual_root("record"), FIXTURE);
+    for (tool, arguments) in session() {
+        let call = call_gql_tool(ctx.clone(), tool, arguments.clone());
+        let _ = recorder.record(tool, arguments, call).await;
+    }
+
+    let entries = read_replay_file(&replay_file).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries.iter().map(|e| e.seq).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(entries[0].tool, "godot_mutate");
+    let stats = entries[1].result.as_ref().unwrap()["content"][0]["text"]
+        .as_str()
+        .unwrap();
+    assert!(stats.contains("\"scriptCount\": 3"), "{}", stats);
+    assert!(entries[2].result.is_none());
+    assert!(entries[2].error.as_ref().unwrap()["message"]
+        .as_str()
+        .unwrap()
+        .contains("Unknown tool: godot_qurey"));
+
+    // A fresh copy of the same project gives the same results
+    let calls = replay(&sandbox(&virtual_root("same"), FIXTURE), &entries).await;
+    assert_eq!(calls.len(), 3);
+    assert!(calls.iter().all(|c| c.matches), "{:?}", calls);
+}
+
+#[tokio::test]
+async fn test_replay_reports_changed_results() {
+    let dir = tempfile::tempdir().unwrap();
+    let replay_file = dir.path().join("replay.jsonl");
+    let recorder = ReplayRecorder::open(&replay_file).unwrap();
+
+    let ctx = sandbox(&virtual_root("before"), FIXTURE);
+    for (tool, arguments) in session().into_iter().take(2) {
+        let call = call_gql_tool(ctx.clone(), tool, arguments.clone());
+        let _ = recorder.record(tool, arguments, call).await;
+    }
+
+    // The project has one script fewer than when the session was recorded
+    let mut fixture: Value = serde_json::from_str(FIXTURE).unwrap();
+    fixture.as_object_mut().unwrap().remove("scripts/events.gd");
+    let entries = read_replay_file(&replay_file).unwrap();
+    let calls = replay(
+        &sandbox(&virtual_root("after"), &fixture.to_string()),
+        &entries,
+    )
+    .await;
+
+    assert!(calls[0].matches);
+    assert!(!calls[1].matches);
+    let text = calls[1].result.as_ref().unwrap()["content"][0]["text"]
+        .as_str()
+        .unwrap();
+    assert!(text.contains("\"scriptCount\": 2"), "{}", text);
+}
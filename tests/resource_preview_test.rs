@@ -0,0 +1,103 @@
+//! Resource Preview Tests
+//!
+//! Tests for header-derived asset metadata via resourcePreview and
+//! Scene.externalResources.preview.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    data.extend_from_slice(&width.to_be_bytes());
+    data.extend_from_slice(&height.to_be_bytes());
+    data.extend_from_slice(&[8, 2, 0, 0, 0]);
+    data
+}
+
+fn wav(seconds: u32) -> Vec<u8> {
+    let sample_rate = 8_000u32;
+    let mut data = b"RIFF\0\0\0\0WAVEfmt \x10\0\0\0".to_vec();
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&sample_rate.to_le_bytes());
+    data.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend_from_slice(&16u16.to_le_bytes());
+    data.extend_from_slice(b"data");
+    let size = sample_rate * 2 * seconds;
+    data.extend_from_slice(&size.to_le_bytes());
+    data.resize(data.len() + size as usize, 0);
+    data
+}
+
+fn setup_project(root: &Path) {
+    fs::create_dir_all(root.join("art")).unwrap();
+    fs::write(root.join("art/hero.png"), png(128, 64)).unwrap();
+    fs::write(root.join("art/jump.wav"), wav(2)).unwrap();
+    fs::write(
+        root.join("hero.tscn"),
+        r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Texture2D" path="res://art/hero.png" id="1"]
+[ext_resource type="AudioStream" path="res://art/jump.wav" id="2"]
+
+[node name="Hero" type="Sprite2D"]
+texture = ExtResource("1")
+"#,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_resource_preview_image_and_audio() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let response = schema
+        .execute(
+            r#"{
+                image: resourcePreview(path: "res://art/hero.png") { kind format width height channels }
+                audio: resourcePreview(path: "res://art/jump.wav") { kind format sampleRate channels durationSec }
+                missing: resourcePreview(path: "res://art/none.png") { kind }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+
+    assert_eq!(data["image"]["kind"], "IMAGE");
+    assert_eq!(data["image"]["format"], "PNG");
+    assert_eq!(data["image"]["width"], 128);
+    assert_eq!(data["image"]["height"], 64);
+    assert_eq!(data["image"]["channels"], 3);
+
+    assert_eq!(data["audio"]["kind"], "AUDIO");
+    assert_eq!(data["audio"]["sampleRate"], 8000);
+    assert_eq!(data["audio"]["durationSec"], 2.0);
+
+    assert!(data["missing"].is_null());
+}
+
+#[tokio::test]
+async fn test_scene_external_resource_preview() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let response = schema
+        .execute(
+            r#"{ scene(path: "res://hero.tscn") {
+                externalResources { path preview { format width durationSec } }
+            } }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let resources = &data["scene"]["externalResources"];
+
+    assert_eq!(resources[0]["preview"]["width"], 128);
+    assert_eq!(resources[1]["preview"]["format"], "WAV");
+    assert_eq!(resources[1]["preview"]["durationSec"], 2.0);
+}
@@ -0,0 +1,111 @@
+//! Resource Query Tests
+//!
+//! Tests for the `resource` query: .tres files parsed into their type,
+//! external/sub-resources and `[resource]` properties.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const SWORD: &str = r#"[gd_resource type="Resource" script_class="ItemData" load_steps=4 format=3 uid="uid://b1sword"]
+
+[ext_resource type="Script" uid="uid://c2item" path="res://item_data.gd" id="1_item"]
+[ext_resource type="Texture2D" path="res://sword.png" id="2_icon"]
+
+[sub_resource type="Curve" id="Curve_a"]
+_data = [Vector2(0, 0), 0.0, 0.0, 0, 0, Vector2(1, 1), 0.0, 0.0, 0, 0]
+point_count = 2
+
+[resource]
+script = ExtResource("1_item")
+name = "Sword"
+icon = ExtResource("2_icon")
+damage_curve = SubResource("Curve_a")
+stats = {
+"attack": 5,
+"weight": 2.5
+}
+"#;
+
+#[tokio::test]
+async fn test_resource_query() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("sword.tres"), SWORD).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ resource(path: "res://sword.tres") {
+            path type uid scriptClass
+            externalResources { type path }
+            subResources { id type properties { name value } }
+            properties { name }
+            stats: property(name: "stats") { value }
+            missing: property(name: "missing") { value }
+        } }"#,
+    )
+    .await;
+    let resource = &data["resource"];
+
+    assert_eq!(resource["path"], "res://sword.tres");
+    assert_eq!(resource["type"], "Resource");
+    assert_eq!(resource["uid"], "uid://b1sword");
+    assert_eq!(resource["scriptClass"], "ItemData");
+    assert_eq!(
+        resource["externalResources"],
+        serde_json::json!([
+            { "type": "Script", "path": "res://item_data.gd" },
+            { "type": "Texture2D", "path": "res://sword.png" }
+        ])
+    );
+    assert_eq!(
+        resource["subResources"],
+        serde_json::json!([{
+            "id": "Curve_a",
+            "type": "Curve",
+            "properties": [
+                {
+                    "name": "_data",
+                    "value": "[Vector2(0, 0), 0.0, 0.0, 0, 0, Vector2(1, 1), 0.0, 0.0, 0, 0]"
+                },
+                { "name": "point_count", "value": "2" }
+            ]
+        }])
+    );
+    assert_eq!(
+        resource["properties"],
+        serde_json::json!([
+            { "name": "damage_curve" },
+            { "name": "icon" },
+            { "name": "name" },
+            { "name": "script" },
+            { "name": "stats" }
+        ])
+    );
+    assert_eq!(
+        resource["stats"]["value"],
+        "{\n\"attack\": 5,\n\"weight\": 2.5\n}"
+    );
+    assert!(resource["missing"].is_null());
+}
+
+#[tokio::test]
+async fn test_resource_query_missing_or_invalid() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("broken.tres"), "[resource]\nvalue = 1\n").unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{
+            missing: resource(path: "res://missing.tres") { type }
+            broken: resource(path: "res://broken.tres") { type }
+        }"#,
+    )
+    .await;
+    assert!(data["missing"].is_null());
+    assert!(data["broken"].is_null());
+}
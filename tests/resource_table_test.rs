@@ -0,0 +1,207 @@
+//! Resource Table Tests
+//!
+//! Tests for exportResourcesToTable / importResourcesFromTable: custom
+//! resources of a directory round-tripped through a CSV or JSON table.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+use std::path::Path;
+
+const ITEM_SCRIPT: &str = r#"class_name Item
+extends Resource
+
+enum Rarity { COMMON, RARE = 5, EPIC }
+
+@export var display_name: String = ""
+@export var rarity: Rarity = Rarity.COMMON
+@export var price := 10
+@export var offset: Vector2 = Vector2.ZERO
+@export var tags: Array[String] = []
+@export var icon: Texture2D
+"#;
+
+const SWORD: &str = r#"[gd_resource type="Resource" script_class="Item" load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://item.gd" id="1_item"]
+[ext_resource type="Texture2D" path="res://sword.png" id="2_icon"]
+
+[resource]
+script = ExtResource("1_item")
+display_name = "Sword, \"Dawn\""
+rarity = 6
+price = 250
+offset = Vector2(1, -2)
+tags = Array[String](["melee", "iron"])
+icon = ExtResource("2_icon")
+"#;
+
+const SHIELD: &str = r#"[gd_resource type="Resource" script_class="Item" load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://item.gd" id="1"]
+
+[resource]
+script = ExtResource("1")
+display_name = "Shield"
+"#;
+
+fn setup_project(root: &Path) {
+    fs::write(root.join("item.gd"), ITEM_SCRIPT).unwrap();
+    fs::write(root.join("sword.png"), b"png").unwrap();
+    fs::create_dir_all(root.join("items")).unwrap();
+    fs::write(root.join("items/sword.tres"), SWORD).unwrap();
+    fs::write(root.join("items/shield.tres"), SHIELD).unwrap();
+    fs::write(
+        root.join("items/theme.tres"),
+        "[gd_resource type=\"Theme\" format=3]\n\n[resource]\n",
+    )
+    .unwrap();
+}
+
+async fn export(schema: &GqlSchema, format: &str) -> serde_json::Value {
+    let query = format!(
+        r#"{{ exportResourcesToTable(directory: "res://items", format: {}) {{
+            success columns rowCount content skipped
+        }} }}"#,
+        format
+    );
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["exportResourcesToTable"].clone()
+}
+
+async fn import(schema: &GqlSchema, file: &str) -> serde_json::Value {
+    let query = format!(
+        r#"mutation {{ importResourcesFromTable(file: "{}", targetDirectory: "res://items") {{
+            success created updated unchanged
+            rowErrors {{ row path field message }}
+            error {{ code }}
+        }} }}"#,
+        file
+    );
+    let response = schema.execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["importResourcesFromTable"].clone()
+}
+
+#[tokio::test]
+async fn test_resource_table_csv_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let table = export(&schema, "CSV").await;
+    assert_eq!(table["success"], true);
+    assert_eq!(table["rowCount"], 2);
+    assert_eq!(
+        table["skipped"],
+        serde_json::json!(["res://items/theme.tres"])
+    );
+    let content = table["content"].as_str().unwrap();
+    assert_eq!(
+        content,
+        "path,script,display_name,rarity,price,offset,tags,icon\n\
+         shield.tres,res://item.gd,Shield,,,,,\n\
+         sword.tres,res://item.gd,\"Sword, \"\"Dawn\"\"\",EPIC,250,\"Vector2(1, -2)\",\"[\"\"melee\"\",\"\"iron\"\"]\",res://sword.png\n"
+    );
+
+    // Unedited, the table changes nothing
+    fs::write(dir.path().join("items.csv"), content).unwrap();
+    let result = import(&schema, "res://items.csv").await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(
+        result["unchanged"],
+        serde_json::json!(["res://items/shield.tres", "res://items/sword.tres"])
+    );
+
+    // Edited rows are written back and new rows create files; bad rows are reported
+    let edited = content
+        .replace("Shield,,", "Shield,RARE,")
+        .replace(",250,", ",300,")
+        + "bow.tres,res://item.gd,Bow,COMMON,120,,\"[\"\"ranged\"\"]\",\n\
+           axe.tres,res://item.gd,Axe,LEGENDARY,cheap,,,\n\
+           orb.tres,,Orb,,,,,\n";
+    fs::write(dir.path().join("items.csv"), edited).unwrap();
+    let result = import(&schema, "res://items.csv").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(
+        result["created"],
+        serde_json::json!(["res://items/bow.tres"])
+    );
+    assert_eq!(
+        result["updated"],
+        serde_json::json!(["res://items/shield.tres", "res://items/sword.tres"])
+    );
+    let errors: Vec<(i64, &str, &str)> = result["rowErrors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["row"].as_i64().unwrap(),
+                e["path"].as_str().unwrap(),
+                e["field"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (4, "res://items/axe.tres", "price"),
+            (4, "res://items/axe.tres", "rarity"),
+            (5, "res://items/orb.tres", "script"),
+        ]
+    );
+    assert!(!dir.path().join("items/axe.tres").exists());
+
+    let shield = fs::read_to_string(dir.path().join("items/shield.tres")).unwrap();
+    assert!(shield.contains("rarity = 5\n"));
+    let sword = fs::read_to_string(dir.path().join("items/sword.tres")).unwrap();
+    assert!(sword.contains("price = 300\n"));
+    assert!(sword.contains("icon = ExtResource(\"2_icon\")\n"));
+    let bow = fs::read_to_string(dir.path().join("items/bow.tres")).unwrap();
+    assert!(bow.starts_with("[gd_resource type=\"Resource\" script_class=\"Item\""));
+    assert!(bow.contains("tags = Array[String]([\"ranged\"])\n"));
+    assert!(bow.contains("price = 120\n"));
+}
+
+#[tokio::test]
+async fn test_resource_table_json() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let table = export(&schema, "JSON").await;
+    let rows: serde_json::Value = serde_json::from_str(table["content"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        rows[1],
+        serde_json::json!({
+            "path": "sword.tres",
+            "script": "res://item.gd",
+            "display_name": "Sword, \"Dawn\"",
+            "rarity": "EPIC",
+            "price": 250,
+            "offset": "Vector2(1, -2)",
+            "tags": ["melee", "iron"],
+            "icon": "res://sword.png",
+        })
+    );
+
+    let rows = serde_json::json!([
+        { "path": "sword.tres", "tags": ["melee"], "offset": [3, 4] },
+        { "path": "res://items/shield.tres", "price": "free" },
+    ]);
+    fs::write(dir.path().join("items.json"), rows.to_string()).unwrap();
+    let result = import(&schema, "res://items.json").await;
+    assert_eq!(
+        result["updated"],
+        serde_json::json!(["res://items/sword.tres"])
+    );
+    assert_eq!(result["rowErrors"][0]["row"], 2);
+    assert_eq!(result["rowErrors"][0]["field"], "price");
+    let sword = fs::read_to_string(dir.path().join("items/sword.tres")).unwrap();
+    assert!(sword.contains("tags = Array[String]([\"melee\"])\n"));
+    assert!(sword.contains("offset = Vector2(3, 4)\n"));
+
+    let result = import(&schema, "res://missing.csv").await;
+    assert_eq!(result["error"]["code"], "FILE_NOT_FOUND");
+}
@@ -0,0 +1,172 @@
+//! Run Report Tests
+//!
+//! Tests for the lastRunReport query over the files run_project leaves in
+//! the project: the run record and the combined output. runProject is run
+//! against a stand-in Godot that never exits on its own.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, godot_binary, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const FIELDS: &str = "pid scene finishedAt running exitCode signal stopped crashed crashReason
+    timeoutSeconds timedOut autoStop cpuPercent memoryBytes
+    stdoutTail stderrTail stackTraces { message isError frames { function file line } }
+    nativeBacktrace";
+
+async fn last_run_report(root: &Path, arguments: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!("{{ lastRunReport{} {{ {} }} }}", arguments, FIELDS);
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["lastRunReport"].clone()
+}
+
+/// Output of a run whose script error was followed by a segfault
+const CRASH_OUTPUT: &str = "\
+Godot Engine v4.3.stable.official.77dcf97d8 - https://godotengine.org
+Level loaded
+ERROR: SCRIPT ERROR: Invalid access to property or key 'health' on a base object of type 'Nil'.
+ERROR:           at: take_damage (res://scripts/enemy.gd:18)
+ERROR: ================================================================
+ERROR: handle_crash: Program crashed with signal 11
+ERROR: Engine version: Godot Engine v4.3.stable.official (77dcf97d8)
+ERROR: Dumping the backtrace. Please include this when reporting the bug to the project developer.
+ERROR: [1] /lib/x86_64-linux-gnu/libc.so.6(+0x42520) [0x7f3a2c842520] (??:0)
+ERROR: [2] godot(+0x2f1a3b4) [0x55d1c7b1a3b4] (??:0)
+ERROR: -- END OF BACKTRACE --
+ERROR: ================================================================
+";
+
+#[tokio::test]
+async fn test_last_run_report_flags_crash() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(
+        dir.path().join(".godot_mcp_run.json"),
+        r#"{ "pid": 4242, "scene": "res://levels/forest.tscn", "startedAt": 1700000000000,
+            "finishedAt": 1700000004000, "exitCode": null, "signal": 11, "stopped": false }"#,
+    )
+    .unwrap();
+    fs::write(dir.path().join(".godot_mcp_output"), CRASH_OUTPUT).unwrap();
+
+    let report = last_run_report(dir.path(), "(tailLines: 2)").await;
+    assert_eq!(report["pid"], 4242);
+    assert_eq!(report["scene"], "res://levels/forest.tscn");
+    assert_eq!(report["running"], false);
+    assert_eq!(report["signal"], 11);
+    assert_eq!(report["crashed"], true);
+    assert_eq!(
+        report["crashReason"],
+        "handle_crash: Program crashed with signal 11"
+    );
+    assert_eq!(
+        report["stdoutTail"],
+        serde_json::json!([
+            "Godot Engine v4.3.stable.official.77dcf97d8 - https://godotengine.org",
+            "Level loaded"
+        ])
+    );
+    assert_eq!(
+        report["stderrTail"],
+        serde_json::json!([
+            "-- END OF BACKTRACE --",
+            "================================================================"
+        ])
+    );
+    assert_eq!(
+        report["stackTraces"],
+        serde_json::json!([{
+            "message": "Invalid access to property or key 'health' on a base object of type 'Nil'.",
+            "isError": true,
+            "frames": [{ "function": "take_damage", "file": "res://scripts/enemy.gd", "line": 18 }],
+        }])
+    );
+    assert_eq!(report["nativeBacktrace"].as_array().unwrap().len(), 2);
+
+    // A game ended with stop_project isn't a crash
+    fs::write(
+        dir.path().join(".godot_mcp_run.json"),
+        r#"{ "pid": 4242, "startedAt": 1700000000000, "finishedAt": 1700000004000,
+            "exitCode": null, "signal": 9, "stopped": true }"#,
+    )
+    .unwrap();
+    fs::write(dir.path().join(".godot_mcp_output"), "Level loaded\n").unwrap();
+    let report = last_run_report(dir.path(), "").await;
+    assert_eq!(report["stopped"], true);
+    assert_eq!(report["crashed"], false);
+    assert!(report["crashReason"].is_null());
+}
+
+#[tokio::test]
+async fn test_last_run_report_without_run() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    assert!(last_run_report(dir.path(), "").await.is_null());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_project_timeout() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.path().join("main.tscn"), "[gd_scene format=3]\n").unwrap();
+    let godot = dir.path().join("godot");
+    fs::write(
+        &godot,
+        "#!/bin/sh\necho \"Godot Engine v4.3\"\nexec sleep 30\n",
+    )
+    .unwrap();
+    fs::set_permissions(&godot, fs::Permissions::from_mode(0o755)).unwrap();
+    godot_binary::set_override(Some(godot));
+
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let run = |input: &str| {
+        format!(
+            "mutation {{ runProject(input: {{ {} }}) {{ success pid restarted error {{ code }} }} }}",
+            input
+        )
+    };
+    let response = schema
+        .execute(run(r#"scene: "res://main.tscn", timeoutSeconds: 1"#).as_str())
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let result = response.data.into_json().unwrap()["runProject"].clone();
+    assert_eq!(result["success"], true, "{}", result["error"]);
+    assert_eq!(result["restarted"], false);
+
+    let report = last_run_report(dir.path(), "").await;
+    assert_eq!(report["pid"], result["pid"]);
+    assert_eq!(report["timeoutSeconds"], 1);
+    assert_eq!(report["autoStop"], false);
+    if report["running"] == true {
+        assert!(report["memoryBytes"].as_i64().unwrap() > 0);
+    }
+
+    let mut report = report;
+    for _ in 0..100 {
+        if report["running"] == false {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        report = last_run_report(dir.path(), "").await;
+    }
+    assert_eq!(report["running"], false);
+    assert_eq!(report["timedOut"], true);
+    assert_eq!(report["crashed"], false);
+    assert!(report["memoryBytes"].is_null());
+    assert_eq!(
+        report["stdoutTail"],
+        serde_json::json!(["Godot Engine v4.3"])
+    );
+
+    let response = schema.execute(run("timeoutSeconds: 0").as_str()).await;
+    let result = response.data.into_json().unwrap()["runProject"].clone();
+    assert_eq!(result["error"]["code"], "INVALID_INPUT");
+    let response = schema
+        .execute(run(r#"scene: "res://missing.tscn""#).as_str())
+        .await;
+    let result = response.data.into_json().unwrap()["runProject"].clone();
+    assert_eq!(result["error"]["code"], "SCENE_NOT_FOUND");
+}
@@ -0,0 +1,242 @@
+//! runTests Tests
+//!
+//! GdUnit4 and GUT run against a stand-in Godot executable that records its
+//! arguments and writes GdUnit4's JUnit report or prints GUT's console
+//! output.
+
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+use common::{fake_godot, write};
+
+/// Writes its arguments to the project's `args` file and reports a run with
+/// one passing and one failing test
+const FAKE_GODOT: &str = r#"#!/bin/sh
+project=""
+reports=""
+for arg in "$@"; do
+  if [ "$prev" = "--path" ]; then project="$arg"; fi
+  if [ "$prev" = "-rd" ]; then reports="$arg"; fi
+  prev="$arg"
+done
+printf '%s\n' "$@" > "$project/args"
+if [ -n "$reports" ]; then
+  mkdir -p "$reports/report_1" "$reports/report_2"
+  echo '<testsuites/>' > "$reports/report_1/results.xml"
+  cat > "$reports/report_2/results.xml" <<'EOF'
+<?xml version="1.0" encoding="UTF-8" ?>
+<testsuites name="report_2" tests="2" failures="1" time="0.052">
+	<testsuite name="test_player" package="tests" tests="2" failures="1" time="0.052">
+		<testcase name="test_moves" classname="test_player" time="0.01"/>
+		<testcase name="test_jumps" classname="test_player" time="0.04">
+			<failure message="FAILED: res://tests/test_player.gd:9" type="FAILURE"><![CDATA[Expecting: '0' to be greater than '0']]></failure>
+		</testcase>
+	</testsuite>
+</testsuites>
+EOF
+  exit 100
+fi
+cat <<'EOF'
+---  GUT  ---
+
+res://tests/test_player.gd
+* test_moves
+* test_jumps
+    [Failed]:  [0] expected to be > than [0]:
+          at line 9
+
+==============================================
+= Run Summary
+==============================================
+
+---- Totals ----
+Tests                 2
+Time                  0.052s
+EOF
+exit 1
+"#;
+
+async fn run_tests(root: &Path, input: &str) -> serde_json::Value {
+    fake_godot(FAKE_GODOT);
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!(
+        r#"mutation {{ runTests(input: {}) {{
+            success framework totalCount passedCount failedCount durationMs
+            suites {{ name path passedCount failedCount cases {{ name success skipped line message }} }}
+            error {{ code message }}
+        }} }}"#,
+        input
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["runTests"].clone()
+}
+
+fn args(root: &Path) -> Vec<String> {
+    fs::read_to_string(root.join("args"))
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_run_gut_tests() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "addons/gut/gut_cmdln.gd", "extends SceneTree\n");
+
+    // Detected from the installed addon
+    let result = run_tests(dir.path(), "{}").await;
+    assert_eq!(result["framework"], "GUT", "{}", result);
+    assert_eq!(result["success"], false);
+    assert_eq!(result["totalCount"], 2);
+    assert_eq!(result["passedCount"], 1);
+    assert_eq!(result["failedCount"], 1);
+    assert_eq!(result["durationMs"], 52);
+    let suite = &result["suites"][0];
+    assert_eq!(suite["name"], "test_player.gd");
+    assert_eq!(suite["path"], "res://tests/test_player.gd");
+    assert_eq!(suite["cases"][1]["name"], "test_jumps");
+    assert_eq!(suite["cases"][1]["success"], false);
+    assert_eq!(suite["cases"][1]["line"], 9);
+
+    let args = args(dir.path());
+    assert!(args.contains(&"res://addons/gut/gut_cmdln.gd".to_string()));
+    assert!(args.contains(&"-gexit".to_string()));
+    assert!(args.contains(&"-gdir=res://tests/".to_string()));
+    assert!(args.contains(&"-ginclude_subdirs".to_string()));
+
+    // The run is recorded for projectHealth
+    assert!(dir.path().join(".godot-mcp/test_results.json").is_file());
+}
+
+#[tokio::test]
+async fn test_run_gut_tests_filtered() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "addons/gut/gut_cmdln.gd", "extends SceneTree\n");
+
+    let result = run_tests(
+        dir.path(),
+        r#"{ framework: GUT, testPath: "res://tests/test_player.gd", scriptName: "player", testName: "jump" }"#,
+    )
+    .await;
+    assert_eq!(result["error"], serde_json::Value::Null);
+    let args = args(dir.path());
+    assert!(args.contains(&"-gtest=res://tests/test_player.gd".to_string()));
+    assert!(args.contains(&"-gselect=player".to_string()));
+    assert!(args.contains(&"-gunit_test_name=jump".to_string()));
+    assert!(!args.iter().any(|arg| arg.starts_with("-gdir")));
+}
+
+#[tokio::test]
+async fn test_run_gut_tests_without_addon() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+
+    let result = run_tests(dir.path(), "{ framework: GUT }").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "GUT_NOT_INSTALLED");
+    assert!(!dir.path().join(".godot-mcp/test_results.json").exists());
+}
+
+#[tokio::test]
+async fn test_run_gdunit4_tests() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(
+        dir.path(),
+        "addons/gdUnit4/bin/GdUnitCmdTool.gd",
+        "extends SceneTree\n",
+    );
+    // A report left by an earlier run is cleared first
+    write(
+        dir.path(),
+        ".godot-mcp/test_reports/report_9/results.xml",
+        "<testsuites/>",
+    );
+
+    let result = run_tests(dir.path(), "{}").await;
+    assert_eq!(result["framework"], "GD_UNIT_4", "{}", result);
+    assert_eq!(result["success"], false);
+    assert_eq!(result["totalCount"], 2);
+    assert_eq!(result["failedCount"], 1);
+    assert_eq!(result["durationMs"], 52);
+    let suite = &result["suites"][0];
+    assert_eq!(suite["path"], "res://tests/test_player.gd");
+    assert_eq!(suite["cases"][1]["success"], false);
+    assert_eq!(suite["cases"][1]["line"], 9);
+    assert_eq!(
+        suite["cases"][1]["message"],
+        "Expecting: '0' to be greater than '0'"
+    );
+
+    let args = args(dir.path());
+    assert!(args.contains(&"res://addons/gdUnit4/bin/GdUnitCmdTool.gd".to_string()));
+    assert!(args.contains(&"--ignoreHeadlessMode".to_string()));
+    assert!(args.contains(&"res://tests/".to_string()));
+    assert!(dir.path().join(".godot-mcp/test_results.json").is_file());
+}
+
+#[tokio::test]
+async fn test_run_gdunit4_tests_filtered() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(
+        dir.path(),
+        "addons/gdUnit4/bin/GdUnitCmdTool.gd",
+        "extends SceneTree\n",
+    );
+    write(
+        dir.path(),
+        "tests/test_player.gd",
+        "extends GdUnitTestSuite\n\nfunc test_moves():\n\tpass\n\nfunc test_jumps():\n\tpass\n\nfunc test_double_jump():\n\tpass\n",
+    );
+    write(
+        dir.path(),
+        "tests/test_inventory.gd",
+        "extends GdUnitTestSuite\n\nfunc test_jump_items():\n\tpass\n",
+    );
+
+    let result = run_tests(
+        dir.path(),
+        r#"{ framework: GD_UNIT_4, scriptName: "player", testName: "jump" }"#,
+    )
+    .await;
+    assert_eq!(result["error"], serde_json::Value::Null, "{}", result);
+    let selections: Vec<String> = args(dir.path())
+        .windows(2)
+        .filter(|pair| pair[0] == "-a")
+        .map(|pair| pair[1].clone())
+        .collect();
+    assert_eq!(
+        selections,
+        vec![
+            "res://tests/test_player.gd:test_jumps",
+            "res://tests/test_player.gd:test_double_jump",
+        ]
+    );
+
+    let result = run_tests(
+        dir.path(),
+        r#"{ framework: GD_UNIT_4, testName: "missing" }"#,
+    )
+    .await;
+    assert_eq!(result["error"]["code"], "NO_MATCHING_TESTS");
+}
+
+#[tokio::test]
+async fn test_run_gdunit4_tests_without_addon() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+
+    let result = run_tests(dir.path(), "{ framework: GD_UNIT_4 }").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "GDUNIT4_NOT_INSTALLED");
+}
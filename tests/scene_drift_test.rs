@@ -0,0 +1,224 @@
+//! Scene Drift Tests
+//!
+//! Tests for liveSceneDrift, using a stub plugin that returns the edited
+//! scene as Godot would save it.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::vfs::MemoryFs;
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::{json, Value};
+
+/// Start a stub plugin answering every command with `response`; commands are
+/// pushed to `received`. Returns its port
+async fn start_stub_plugin(response: Value, received: Arc<Mutex<Vec<Value>>>) -> u16 {
+    common::start_stub_plugin_with(move |body| {
+        received
+            .lock()
+            .unwrap()
+            .push(serde_json::from_str(body).unwrap_or_default());
+        response.to_string()
+    })
+    .await
+}
+
+/// The file on disk, as written by hand (ids and number formats differ from
+/// what the editor writes)
+const DISK_SCENE: &str = r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://scripts/player.gd" id="1"]
+
+[sub_resource type="RectangleShape2D" id="1"]
+size = Vector2(32.0, 32.0)
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1")
+speed = 200.0
+
+[node name="Shape" type="CollisionShape2D" parent="."]
+shape = SubResource("1")
+
+[node name="Sprite" type="Sprite2D" parent="."]
+position = Vector2(0, -8)
+"#;
+
+/// The same scene saved by the editor, without edits
+const SAVED_SCENE: &str = r#"[gd_scene load_steps=3 format=3 uid="uid://b1x"]
+
+[ext_resource type="Script" uid="uid://c2y" path="res://scripts/player.gd" id="1_abcde"]
+
+[sub_resource type="RectangleShape2D" id="RectangleShape2D_k3j2h"]
+size = Vector2(32, 32)
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1_abcde")
+speed = 200
+
+[node name="Shape" type="CollisionShape2D" parent="."]
+shape = SubResource("RectangleShape2D_k3j2h")
+
+[node name="Sprite" type="Sprite2D" parent="."]
+position = Vector2(0, -8)
+"#;
+
+/// The editor's scene after the user resized the shape, changed the sprite's
+/// type and added a camera
+const EDITED_SCENE: &str = r#"[gd_scene load_steps=3 format=3 uid="uid://b1x"]
+
+[ext_resource type="Script" uid="uid://c2y" path="res://scripts/player.gd" id="1_abcde"]
+
+[sub_resource type="RectangleShape2D" id="RectangleShape2D_k3j2h"]
+size = Vector2(48, 32)
+
+[node name="Player" type="CharacterBody2D"]
+script = ExtResource("1_abcde")
+speed = 200
+
+[node name="Shape" type="CollisionShape2D" parent="."]
+shape = SubResource("RectangleShape2D_k3j2h")
+
+[node name="Sprite" type="AnimatedSprite2D" parent="."]
+
+[node name="Camera" type="Camera2D" parent="."]
+zoom = Vector2(2, 2)
+"#;
+
+fn project(name: &str, port: u16) -> GqlContext {
+    let root: PathBuf =
+        std::env::temp_dir().join(format!("godot-mcp-drift-{}-{}", name, std::process::id()));
+    let fs = MemoryFs::from_json(
+        &root,
+        &json!({
+            "project.godot": "config_version=5\n",
+            "scenes/player.tscn": DISK_SCENE,
+            "scripts/player.gd": "extends CharacterBody2D\n\n@export var speed = 100.0\n",
+        }),
+    )
+    .unwrap();
+    GqlContext::new(root)
+        .with_fs(Arc::new(fs))
+        .with_port(port)
+        .with_ws_port(None)
+}
+
+const QUERY: &str = r#"{ liveSceneDrift(path: "res://scenes/player.tscn") {
+    path editorConnected open editedScene drifted
+    addedNodes { path nodeType }
+    removedNodes { path nodeType }
+    changedProperties { nodePath property diskValue liveValue }
+    error { code }
+} }"#;
+
+async fn drift(ctx: GqlContext) -> Value {
+    let result = build_schema_with_context(ctx).execute(QUERY).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()["liveSceneDrift"].clone()
+}
+
+fn tree_response(scene_path: &str, tscn: &str) -> Value {
+    json!({"success": true, "tree": [], "root_name": "Player", "scene_path": scene_path, "tscn": tscn})
+}
+
+#[tokio::test]
+async fn test_saved_scene_has_no_drift() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        tree_response("res://scenes/player.tscn", SAVED_SCENE),
+        received.clone(),
+    )
+    .await;
+
+    let drift = drift(project("saved", port)).await;
+    assert_eq!(drift["editorConnected"], true);
+    assert_eq!(drift["open"], true);
+    assert_eq!(drift["drifted"], false, "{}", drift);
+    assert!(drift["error"].is_null());
+
+    let received = received.lock().unwrap();
+    assert_eq!(received[0]["command"], "get_tree");
+    assert_eq!(received[0]["params"]["serialize"], true);
+}
+
+#[tokio::test]
+async fn test_unsaved_edits_are_reported() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        tree_response("res://scenes/player.tscn", EDITED_SCENE),
+        received,
+    )
+    .await;
+
+    let drift = drift(project("edited", port)).await;
+    assert_eq!(drift["drifted"], true);
+    // The camera is new; the sprite changed type, so it was replaced
+    assert_eq!(
+        drift["addedNodes"],
+        json!([
+            {"path": "Sprite", "nodeType": "AnimatedSprite2D"},
+            {"path": "Camera", "nodeType": "Camera2D"},
+        ])
+    );
+    assert_eq!(
+        drift["removedNodes"],
+        json!([{"path": "Sprite", "nodeType": "Sprite2D"}])
+    );
+    // The shape's sub-resource is compared by content
+    assert_eq!(
+        drift["changedProperties"],
+        json!([{
+            "nodePath": "Shape",
+            "property": "shape",
+            "diskValue": "SubResource(\"1\")",
+            "liveValue": "SubResource(\"RectangleShape2D_k3j2h\")",
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_other_scene_being_edited() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        tree_response("res://scenes/menu.tscn", SAVED_SCENE),
+        received,
+    )
+    .await;
+
+    let drift = drift(project("other", port)).await;
+    assert_eq!(drift["editorConnected"], true);
+    assert_eq!(drift["open"], false);
+    assert_eq!(drift["editedScene"], "res://scenes/menu.tscn");
+    assert_eq!(drift["drifted"], false);
+}
+
+#[tokio::test]
+async fn test_no_scene_open() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(json!({"error": "No scene is open"}), received).await;
+
+    let drift = drift(project("none", port)).await;
+    assert_eq!(drift["editorConnected"], true);
+    assert_eq!(drift["open"], false);
+    assert!(drift["editedScene"].is_null());
+    assert!(drift["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_without_editor() {
+    let drift = drift(project("offline", 19995)).await;
+    assert_eq!(drift["editorConnected"], false);
+    assert_eq!(drift["drifted"], false);
+    assert!(drift["error"].is_null());
+}
+
+#[tokio::test]
+async fn test_missing_scene_file() {
+    let ctx = project("missing", 19995);
+    let result = build_schema_with_context(ctx)
+        .execute(r#"{ liveSceneDrift(path: "res://scenes/nope.tscn") { error { code } } }"#)
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["liveSceneDrift"]["error"]["code"], "SCENE_NOT_FOUND");
+}
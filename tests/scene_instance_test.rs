@@ -0,0 +1,88 @@
+//! Scene Instance Tests
+//!
+//! Tests for nodes that instance other scenes (`instance=ExtResource(...)`):
+//! `instancePath` on scene nodes and INSTANTIATES edges in the dependency graph.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const LEVEL: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="PackedScene" path="res://coin.tscn" id="1_coin"]
+[ext_resource type="PackedScene" path="res://bullet.tscn" id="2_bullet"]
+[ext_resource type="Script" path="res://level.gd" id="3_script"]
+
+[node name="Level" type="Node2D"]
+script = ExtResource("3_script")
+bullet_scene = ExtResource("2_bullet")
+
+[node name="Coins" type="Node2D" parent="."]
+
+[node name="Coin" parent="Coins" instance=ExtResource("1_coin")]
+position = Vector2(32, 0)
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    fs::write(
+        dir.path().join("coin.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Coin\" type=\"Area2D\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("bullet.tscn"),
+        "[gd_scene format=3]\n\n[node name=\"Bullet\" type=\"Area2D\"]\n",
+    )
+    .unwrap();
+    fs::write(dir.path().join("level.gd"), "extends Node2D\n").unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_scene_node_instance_path() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ scene(path: "res://level.tscn") { allNodes { path instancePath } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["scene"]["allNodes"],
+        serde_json::json!([
+            { "path": ".", "instancePath": null },
+            { "path": "Coins", "instancePath": null },
+            { "path": "Coins/Coin", "instancePath": "res://coin.tscn" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_instances_are_instantiates_edges() {
+    let dir = setup();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ dependencyGraph { edges { from to referenceType } } }"#,
+    )
+    .await;
+    let edges = data["dependencyGraph"]["edges"].as_array().unwrap();
+    let edge = |to: &str| {
+        edges
+            .iter()
+            .find(|e| e["from"] == "res://level.tscn" && e["to"] == to)
+            .map(|e| e["referenceType"].clone())
+    };
+
+    assert_eq!(edge("res://coin.tscn"), Some("INSTANTIATES".into()));
+    // Held in a property, not instanced by a node
+    assert_eq!(edge("res://bullet.tscn"), Some("USES_RESOURCE".into()));
+    assert_eq!(edge("res://level.gd"), Some("ATTACHES_SCRIPT".into()));
+}
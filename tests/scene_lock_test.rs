@@ -0,0 +1,118 @@
+//! Scene Lock Tests
+//!
+//! Tests for editor coordination of file-based scene writes, using a stub plugin.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use godot_mcp_rs::tools::GodotTools;
+use std::fs;
+
+use common::start_stub_plugin;
+
+const SCENE: &str = r#"[gd_scene format=3]
+
+[node name="Main" type="Node2D"]
+"#;
+
+#[tokio::test]
+async fn test_write_proceeds_without_editor() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), SCENE).unwrap();
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(19999));
+
+    let result = schema
+        .execute(r#"{ sceneLockStatus(path: "res://main.tscn") { editorConnected open } }"#)
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["sceneLockStatus"]["editorConnected"], false);
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn", nodePath: ".", key: "note", value: "x"
+                }) { success }
+            }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["setNodeMetadata"]["success"], true);
+}
+
+#[tokio::test]
+async fn test_unsaved_scene_refuses_write() {
+    let port = start_stub_plugin(
+        r#"{"error": "Scene has unsaved changes in the editor: res://main.tscn", "code": "SCENE_UNSAVED"}"#,
+    )
+    .await;
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), SCENE).unwrap();
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(port));
+
+    let result = schema
+        .execute(
+            r#"mutation {
+                setNodeMetadata(input: {
+                    scenePath: "res://main.tscn", nodePath: ".", key: "note", value: "x"
+                }) { success error { code suggestion } }
+            }"#,
+        )
+        .await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    let data = result.data.into_json().unwrap();
+    assert_eq!(data["setNodeMetadata"]["success"], false);
+    assert_eq!(data["setNodeMetadata"]["error"]["code"], "SCENE_UNSAVED");
+
+    // File untouched
+    assert_eq!(
+        fs::read_to_string(dir.path().join("main.tscn")).unwrap(),
+        SCENE
+    );
+}
+
+#[tokio::test]
+async fn test_scene_lock_status_from_editor() {
+    let port =
+        start_stub_plugin(r#"{"success": true, "open": true, "unsaved": true, "locked": false}"#)
+            .await;
+    let dir = tempfile::tempdir().unwrap();
+    let schema =
+        build_schema_with_context(GqlContext::new(dir.path().to_path_buf()).with_port(port));
+
+    let result = schema
+        .execute(
+            r#"{ sceneLockStatus(path: "res://main.tscn") { editorConnected open unsaved locked } }"#,
+        )
+        .await;
+    let data = result.data.into_json().unwrap();
+    let status = &data["sceneLockStatus"];
+    assert_eq!(status["editorConnected"], true);
+    assert_eq!(status["open"], true);
+    assert_eq!(status["unsaved"], true);
+    assert_eq!(status["locked"], false);
+}
+
+#[tokio::test]
+async fn test_scene_tool_writes_through_lock_without_editor() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), SCENE).unwrap();
+    let tools = GodotTools::with_project(dir.path().to_path_buf());
+
+    let args = serde_json::json!({
+        "scene_path": "main.tscn",
+        "parent": ".",
+        "name": "Sprite",
+        "node_type": "Sprite2D",
+    });
+    tools
+        .handle_add_node(args.as_object().cloned())
+        .await
+        .unwrap();
+
+    let scene = fs::read_to_string(dir.path().join("main.tscn")).unwrap();
+    assert!(scene.contains("[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"]"));
+}
@@ -0,0 +1,118 @@
+//! Scene Patch Tests
+//!
+//! Tests for applyScenePatch: declarative patches of one scene, applied
+//! atomically or previewed with dryRun.
+
+use async_graphql::{Request, Variables};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+const LEVEL: &str = r#"[gd_scene format=3]
+
+[node name="Level" type="Node2D"]
+
+[node name="Enemies" type="Node2D" parent="."]
+
+[node name="Slime" type="CharacterBody2D" parent="Enemies" groups=["enemies"]]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+"#;
+
+async fn apply_patch(root: &Path, patch: serde_json::Value, dry_run: bool) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let mutation = r#"mutation($patch: JSON!, $dryRun: Boolean!) {
+        applyScenePatch(path: "res://level.tscn", patch: $patch, dryRun: $dryRun) {
+            success dryRun operationCount diff
+            errors { operationIndex code message }
+            error { code }
+        }
+    }"#;
+    let request = Request::new(mutation).variables(Variables::from_json(
+        json!({ "patch": patch, "dryRun": dry_run }),
+    ));
+    let response = schema.execute(request).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["applyScenePatch"].clone()
+}
+
+fn patch() -> serde_json::Value {
+    json!({ "operations": [
+        { "op": "addNode", "parent": "Enemies/Bats", "name": "Bat", "type": "CharacterBody2D",
+          "groups": ["enemies"] },
+        { "op": "addNode", "parent": "Enemies", "name": "Bats", "type": "Node2D" },
+        { "op": "setProperty", "select": { "group": "enemies" }, "property": "speed", "value": 40 },
+        { "op": "connectSignal", "from": "Player", "signal": "died", "to": ".", "method": "_on_player_died" },
+        { "op": "renameNode", "select": { "path": "Player" }, "newName": "Hero" }
+    ]})
+}
+
+#[tokio::test]
+async fn test_apply_scene_patch() {
+    let dir = tempfile::tempdir().unwrap();
+    let scene_path = dir.path().join("level.tscn");
+    fs::write(&scene_path, LEVEL).unwrap();
+
+    // Dry run leaves the file alone
+    let preview = apply_patch(dir.path(), patch(), true).await;
+    assert_eq!(preview["success"], true, "{}", preview);
+    assert_eq!(preview["dryRun"], true);
+    assert_eq!(preview["operationCount"], 5);
+    assert!(preview["diff"]
+        .as_str()
+        .unwrap()
+        .contains("+[node name=\"Bat\" type=\"CharacterBody2D\" parent=\"Enemies/Bats\""));
+    assert_eq!(fs::read_to_string(&scene_path).unwrap(), LEVEL);
+
+    let result = apply_patch(dir.path(), patch(), false).await;
+    assert_eq!(result["success"], true, "{}", result);
+    let content = fs::read_to_string(&scene_path).unwrap();
+    assert!(content.contains("[node name=\"Hero\" type=\"CharacterBody2D\" parent=\".\"]"));
+    assert!(content.contains("[node name=\"Bats\" type=\"Node2D\" parent=\"Enemies\"]"));
+    assert_eq!(content.matches("speed = 40").count(), 2);
+    // The connection follows the rename
+    assert!(
+        content.contains(
+            "[connection signal=\"died\" from=\"Hero\" to=\".\" method=\"_on_player_died\"]"
+        ),
+        "{}",
+        content
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_patch_leaves_scene_untouched() {
+    let dir = tempfile::tempdir().unwrap();
+    let scene_path = dir.path().join("level.tscn");
+    fs::write(&scene_path, LEVEL).unwrap();
+
+    let result = apply_patch(
+        dir.path(),
+        json!({ "operations": [
+            { "op": "addNode", "parent": ".", "name": "Hud", "type": "CanvasLayer" },
+            { "op": "removeNode", "select": { "path": "Player" } },
+            { "op": "setProperty", "select": { "path": "Player" }, "property": "visible", "value": false },
+            { "op": "reparentNode", "select": { "path": "Player" }, "newParent": "Hud" }
+        ]}),
+        false,
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["errors"][0]["operationIndex"], 3);
+    assert_eq!(result["errors"][0]["code"], "CONFLICT");
+    assert_eq!(fs::read_to_string(&scene_path).unwrap(), LEVEL);
+
+    let result = apply_patch(
+        dir.path(),
+        json!({ "operations": [{ "op": "addNode" }] }),
+        false,
+    )
+    .await;
+    assert_eq!(result["success"], false);
+    assert_eq!(
+        result["errors"][0]["operationIndex"],
+        serde_json::Value::Null
+    );
+    assert_eq!(result["errors"][0]["code"], "INVALID_PATCH");
+}
@@ -0,0 +1,84 @@
+//! Scene Signal Connection Tests
+//!
+//! Tests for file-based signal connections: `[connection]` parsing exposed via
+//! `scene { allNodes { signals } }` and the connectSignalInScene mutation.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::execute_in;
+
+const MAIN: &str = r#"[gd_scene load_steps=1 format=3]
+
+[node name="Main" type="Node2D"]
+
+[node name="Button" type="Button" parent="."]
+
+[node name="Timer" type="Timer" parent="."]
+
+[connection signal="pressed" from="Button" to="." method="_on_button_pressed"]
+"#;
+
+async fn signals_of(root: &Path, node_path: &str) -> Vec<serde_json::Value> {
+    let data = execute_in(
+        root,
+        r#"{ scene(path: "res://main.tscn") {
+            allNodes { path signals { fromNode signal toNode method } }
+        } }"#,
+    )
+    .await;
+    data["scene"]["allNodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["path"] == node_path)
+        .map(|n| n["signals"].as_array().unwrap().clone())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_scene_exposes_file_connections() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), MAIN).unwrap();
+
+    let signals = signals_of(dir.path(), "Button").await;
+    assert_eq!(signals.len(), 1);
+    assert_eq!(signals[0]["signal"], "pressed");
+    assert_eq!(signals[0]["toNode"], ".");
+    assert_eq!(signals[0]["method"], "_on_button_pressed");
+    assert!(signals_of(dir.path(), "Timer").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_connect_signal_in_scene() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("main.tscn"), MAIN).unwrap();
+
+    let mutation = r#"mutation {
+        connectSignalInScene(input: {
+            scenePath: "res://main.tscn", fromNode: "Timer", signal: "timeout",
+            toNode: ".", method: "_on_timer_timeout", flags: 4
+        }) { success error { code } }
+    }"#;
+    let data = execute_in(dir.path(), mutation).await;
+    assert_eq!(data["connectSignalInScene"]["success"], true);
+
+    let content = fs::read_to_string(dir.path().join("main.tscn")).unwrap();
+    assert!(content.contains(
+        r#"[connection signal="pressed" from="Button" to="." method="_on_button_pressed"]"#
+    ));
+    assert!(content.contains(
+        r#"[connection signal="timeout" from="Timer" to="." method="_on_timer_timeout" flags=4]"#
+    ));
+    assert_eq!(signals_of(dir.path(), "Timer").await.len(), 1);
+
+    // The same connection twice is rejected
+    let data = execute_in(dir.path(), mutation).await;
+    assert_eq!(data["connectSignalInScene"]["success"], false);
+    assert_eq!(
+        data["connectSignalInScene"]["error"]["code"],
+        "SIGNAL_CONNECT_FAILED"
+    );
+}
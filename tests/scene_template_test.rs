@@ -0,0 +1,185 @@
+//! Scene Template Tests
+//!
+//! Tests for user-defined scene templates: listing with their parameters,
+//! instantiation with parameter expressions and defaults, and linting.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::fs;
+
+use common::execute;
+
+const PLATFORM: &str = r#"[gd_scene format=3]
+
+[node name="Platform" type="Sprite2D"]
+scale = Vector2({{size * 2}}, {{size}})
+modulate = {{color|default:'Color(1, 1, 1, 1)'}}
+
+[node name="Label" type="Label" parent="."]
+text = "{{label}}"
+"#;
+
+const PLATFORM_PARAMS: &str = r#"description = "Moving platform"
+
+[params.size]
+type = "float"
+default = "16"
+
+[params.color]
+type = "Color"
+
+[params.label]
+type = "String"
+default = "\"Platform\""
+description = "Text shown above the platform"
+"#;
+
+const BROKEN: &str = r#"[gd_scene format=3]
+
+[node name="Enemy" type="Node2D"]
+position = Vector2({{speed}}, {{height}})
+visible = {{hidden * 2}}
+"#;
+
+const BROKEN_PARAMS: &str = r#"[params.speed]
+type = "float"
+
+[params.hidden]
+type = "bool"
+
+[params.unused]
+type = "int"
+default = "1.5"
+"#;
+
+fn setup() -> (tempfile::TempDir, GqlSchema) {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("project.godot"), "config_version=5\n").unwrap();
+    let templates = dir.path().join(".godot-mcp/templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(templates.join("platform.tscn"), PLATFORM).unwrap();
+    fs::write(templates.join("platform.toml"), PLATFORM_PARAMS).unwrap();
+    fs::write(templates.join("enemy.tscn"), BROKEN).unwrap();
+    fs::write(templates.join("enemy.toml"), BROKEN_PARAMS).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    (dir, schema)
+}
+
+#[tokio::test]
+async fn test_scene_templates_lists_parameters() {
+    let (_dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        "{ sceneTemplates { templates { name path description params { name type default required } } error { code } } }",
+    )
+    .await;
+    let templates = &data["sceneTemplates"]["templates"];
+    assert_eq!(templates[0]["name"], "enemy");
+    assert_eq!(templates[1]["name"], "platform");
+    assert_eq!(
+        templates[1]["path"],
+        "res://.godot-mcp/templates/platform.tscn"
+    );
+    assert_eq!(templates[1]["description"], "Moving platform");
+    let params = templates[1]["params"].as_array().unwrap();
+    let names: Vec<&str> = params.iter().map(|p| p["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["color", "label", "size"]);
+    // color is covered by its placeholder's default filter
+    assert_eq!(params[0]["required"], false);
+    assert_eq!(templates[0]["params"][1]["name"], "speed");
+    assert_eq!(templates[0]["params"][1]["required"], true);
+    assert_eq!(params[0]["type"], "Color");
+    assert_eq!(params[2]["default"], "16");
+}
+
+#[tokio::test]
+async fn test_create_scene_from_template_evaluates_expressions() {
+    let (dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        r#"mutation { createSceneFromTemplate(input: {
+            template: "platform", path: "res://levels/platform_a.tscn",
+            params: [{ name: "size", value: "24" }, { name: "label", value: "Lift" }]
+        }) { success message } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["createSceneFromTemplate"]["success"], true,
+        "{}",
+        data["createSceneFromTemplate"]["message"]
+    );
+    let scene = fs::read_to_string(dir.path().join("levels/platform_a.tscn")).unwrap();
+    assert!(scene.contains("scale = Vector2(48, 24)\n"));
+    assert!(scene.contains("modulate = Color(1, 1, 1, 1)\n"));
+    assert!(scene.contains("text = \"Lift\"\n"));
+
+    // Declared types are checked, and unknown parameters rejected
+    for (params, expected) in [
+        (r#"[{ name: "size", value: "true" }]"#, "is not a float"),
+        (r#"[{ name: "speed", value: "1" }]"#, "no parameter 'speed'"),
+    ] {
+        let query = format!(
+            r#"mutation {{ createSceneFromTemplate(input: {{
+                template: "platform", path: "res://levels/platform_b.tscn", params: {}
+            }}) {{ success message }} }}"#,
+            params
+        );
+        let data = execute(&schema, &query).await;
+        assert_eq!(data["createSceneFromTemplate"]["success"], false);
+        let message = data["createSceneFromTemplate"]["message"].as_str().unwrap();
+        assert!(message.contains(expected), "{}", message);
+    }
+    assert!(!dir.path().join("levels/platform_b.tscn").exists());
+}
+
+#[tokio::test]
+async fn test_lint_scene_templates() {
+    let (_dir, schema) = setup();
+
+    let data = execute(
+        &schema,
+        "{ lintSceneTemplates { valid templatesChecked issues { template line severity code message } } }",
+    )
+    .await;
+    let result = &data["lintSceneTemplates"];
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["templatesChecked"], 2);
+    let issues: Vec<(&str, &str, &str)> = result["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| {
+            (
+                i["template"].as_str().unwrap(),
+                i["code"].as_str().unwrap(),
+                i["severity"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            ("enemy", "INVALID_DEFAULT", "ERROR"),
+            ("enemy", "UNDECLARED_PARAMETER", "ERROR"),
+            ("enemy", "INVALID_EXPRESSION", "ERROR"),
+            ("enemy", "UNUSED_PARAMETER", "WARNING"),
+        ]
+    );
+    assert_eq!(result["issues"][1]["line"], 4);
+    assert!(result["issues"][1]["message"]
+        .as_str()
+        .unwrap()
+        .contains("'height'"));
+
+    let data = execute(
+        &schema,
+        r#"{ lintSceneTemplates(name: "platform") { valid templatesChecked issues { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["lintSceneTemplates"]["valid"], true);
+    assert_eq!(data["lintSceneTemplates"]["templatesChecked"], 1);
+    assert_eq!(data["lintSceneTemplates"]["issues"], serde_json::json!([]));
+}
@@ -0,0 +1,133 @@
+//! Scene Warm-up Tests
+//!
+//! Tests for warming the caches of scenes opened in the editor, using a stub
+//! plugin that reports scene events.
+
+mod common;
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use godot_mcp_rs::graphql::scene_warmup::{warm_opened_scenes, warm_scene, SceneEventCursor};
+use godot_mcp_rs::graphql::vfs::MemoryFs;
+use godot_mcp_rs::graphql::GqlContext;
+use serde_json::{json, Value};
+
+/// Start a stub plugin answering with the responses in turn (the last one
+/// repeats); commands are pushed to `received`. Returns its port
+async fn start_stub_plugin(responses: Vec<Value>, received: Arc<Mutex<Vec<Value>>>) -> u16 {
+    common::start_stub_plugin_with(move |body| {
+        let mut received = received.lock().unwrap();
+        received.push(serde_json::from_str(body).unwrap_or_default());
+        responses[(received.len() - 1).min(responses.len() - 1)].to_string()
+    })
+    .await
+}
+
+/// Project root that does not exist on disk
+fn virtual_root(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("godot-mcp-warmup-{}-{}", name, std::process::id()))
+}
+
+/// main.tscn uses player.gd and instances enemy.tscn, whose script preloads bullet.tscn
+fn project(name: &str) -> GqlContext {
+    let root = virtual_root(name);
+    let fixture = json!({
+        "project.godot": "config_version=5\n",
+        "scenes/main.tscn": r#"[gd_scene load_steps=3 format=3]
+
+[ext_resource type="Script" path="res://scripts/player.gd" id="1"]
+[ext_resource type="PackedScene" path="res://scenes/enemy.tscn" id="2"]
+
+[node name="Main" type="Node2D"]
+script = ExtResource("1")
+
+[node name="Enemy" parent="." instance=ExtResource("2")]
+"#,
+        "scenes/enemy.tscn": r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://scripts/enemy.gd" id="1"]
+
+[node name="Enemy" type="CharacterBody2D"]
+script = ExtResource("1")
+"#,
+        "scenes/bullet.tscn": "[gd_scene format=3]\n\n[node name=\"Bullet\" type=\"Area2D\"]\n",
+        "scenes/menu.tscn": "[gd_scene format=3]\n\n[node name=\"Menu\" type=\"Control\"]\n",
+        "scripts/player.gd": "extends Node2D\n",
+        "scripts/enemy.gd": "extends CharacterBody2D\n\nconst BULLET = preload(\"res://scenes/bullet.tscn\")\n",
+    });
+    let fs = MemoryFs::from_json(&root, &fixture).unwrap();
+    GqlContext::new(root).with_fs(Arc::new(fs))
+}
+
+#[test]
+fn test_warm_scene_follows_dependencies() {
+    let ctx = project("deps");
+    let warmed = warm_scene(&ctx, "res://scenes/main.tscn");
+
+    assert_eq!(warmed.path, "res://scenes/main.tscn");
+    let mut files = warmed.files.clone();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            "res://scenes/bullet.tscn",
+            "res://scenes/enemy.tscn",
+            "res://scenes/main.tscn",
+            "res://scripts/enemy.gd",
+            "res://scripts/player.gd",
+        ]
+    );
+    // The opened scene comes first
+    assert_eq!(warmed.files[0], "res://scenes/main.tscn");
+}
+
+#[tokio::test]
+async fn test_warm_opened_scenes_from_plugin_events() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let port = start_stub_plugin(
+        vec![
+            json!({"success": true, "events": [], "last_seq": 4, "current_scene": "res://scenes/menu.tscn"}),
+            json!({"success": true, "last_seq": 7, "current_scene": "res://scenes/main.tscn", "events": [
+                {"seq": 5, "event": "opened", "path": "res://scenes/main.tscn"},
+                {"seq": 6, "event": "closed", "path": "res://scenes/menu.tscn"},
+                {"seq": 7, "event": "opened", "path": "res://scenes/main.tscn"},
+            ]}),
+            json!({"success": true, "events": [], "last_seq": 7, "current_scene": "res://scenes/main.tscn"}),
+        ],
+        received.clone(),
+    )
+    .await;
+    let ctx = project("events").with_port(port).with_ws_port(None);
+    let mut cursor = SceneEventCursor::default();
+
+    // The scene already being edited when the server connects
+    let warmed = warm_opened_scenes(&ctx, &mut cursor).await.unwrap();
+    assert_eq!(warmed.len(), 1);
+    assert_eq!(warmed[0].files, ["res://scenes/menu.tscn"]);
+
+    // Scenes opened since; switching back to a tab reports it again
+    let warmed = warm_opened_scenes(&ctx, &mut cursor).await.unwrap();
+    assert_eq!(warmed.len(), 1);
+    assert_eq!(warmed[0].path, "res://scenes/main.tscn");
+    assert_eq!(warmed[0].files.len(), 5);
+
+    // Nothing new
+    assert!(warm_opened_scenes(&ctx, &mut cursor)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let received = received.lock().unwrap();
+    assert!(received.iter().all(|c| c["command"] == "get_scene_events"));
+    assert!(received[0]["params"].get("since").is_none());
+    assert_eq!(received[1]["params"]["since"], 4);
+    assert_eq!(received[2]["params"]["since"], 7);
+}
+
+#[tokio::test]
+async fn test_warm_opened_scenes_without_editor() {
+    let ctx = project("offline").with_port(19996).with_ws_port(None);
+    let mut cursor = SceneEventCursor::default();
+    assert!(warm_opened_scenes(&ctx, &mut cursor).await.is_none());
+}
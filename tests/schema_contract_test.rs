@@ -168,8 +168,9 @@ fn test_schema_matches_source_of_truth() {
     // Known type mappings (schema.graphql name -> Rust name)
     // async-graphql generates different names for root types
     let type_mappings: HashSet<_> = [
-        "Query",    // -> QueryRoot in async-graphql
-        "Mutation", // -> MutationRoot in async-graphql
+        "Query",        // -> QueryRoot in async-graphql
+        "Mutation",     // -> MutationRoot in async-graphql
+        "Subscription", // -> SubscriptionRoot in async-graphql
     ]
     .iter()
     .map(|s| s.to_string())
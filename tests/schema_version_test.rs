@@ -0,0 +1,125 @@
+//! Schema Version Tests
+//!
+//! Tests for the schemaVersion and changelog queries, and that the
+//! deprecations they report match the `@deprecated` fields in the schema.
+
+use godot_mcp_rs::graphql::build_schema;
+
+async fn query(query: &str) -> serde_json::Value {
+    let response = build_schema().execute(query).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn test_schema_version_deprecations_match_schema() {
+    let result = query(
+        "{ schemaVersion { version major minor patch
+            deprecations { kind target replacement removalVersion } } }",
+    )
+    .await["schemaVersion"]
+        .clone();
+    let version = result["version"].as_str().unwrap();
+    assert_eq!(
+        version,
+        format!(
+            "{}.{}.{}",
+            result["major"], result["minor"], result["patch"]
+        )
+    );
+
+    let deprecations = result["deprecations"].as_array().unwrap();
+    assert!(!deprecations.is_empty());
+    for deprecation in deprecations {
+        assert_eq!(deprecation["kind"], "DEPRECATED");
+        assert!(deprecation["replacement"].is_string());
+        assert_eq!(
+            deprecation["removalVersion"],
+            format!("{}.0.0", result["major"].as_i64().unwrap() + 1)
+        );
+    }
+
+    // Every deprecated field in the schema is listed, and nothing else
+    let types = query(
+        "{ __schema { types { name fields(includeDeprecated: true) { name isDeprecated } } } }",
+    )
+    .await;
+    let mut deprecated: Vec<String> = types["__schema"]["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|t| {
+            let name = t["name"].as_str().unwrap().to_string();
+            t["fields"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|f| f["isDeprecated"] == true)
+                .map(move |f| {
+                    let root = match name.as_str() {
+                        "QueryRoot" => "Query",
+                        "MutationRoot" => "Mutation",
+                        "SubscriptionRoot" => "Subscription",
+                        other => other,
+                    };
+                    format!("{}.{}", root, f["name"].as_str().unwrap())
+                })
+        })
+        .collect();
+    let mut listed: Vec<String> = deprecations
+        .iter()
+        .map(|d| d["target"].as_str().unwrap().to_string())
+        .collect();
+    deprecated.sort();
+    listed.sort();
+    assert_eq!(deprecated, listed);
+}
+
+#[tokio::test]
+async fn test_changelog_since_version() {
+    const FIELDS: &str = "success currentVersion hasBreakingChanges
+        releases { version date changes { kind target breaking } }
+        error { code }";
+
+    let all = query(&format!("{{ changelog {{ {} }} }}", FIELDS)).await["changelog"].clone();
+    assert_eq!(all["success"], true);
+    let releases = all["releases"].as_array().unwrap();
+    assert_eq!(
+        releases.last().unwrap()["version"],
+        all["currentVersion"],
+        "the current version is the latest release"
+    );
+    assert!(releases[0]["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["kind"] == "ADDED" && c["target"] == "Query.changelog"));
+
+    // Nothing released after the current version
+    let current = all["currentVersion"].as_str().unwrap();
+    let since = query(&format!(
+        r#"{{ changelog(sinceVersion: "{}") {{ {} }} }}"#,
+        current, FIELDS
+    ))
+    .await["changelog"]
+        .clone();
+    assert_eq!(since["releases"], serde_json::json!([]));
+    assert_eq!(since["hasBreakingChanges"], false);
+
+    let since = query(&format!(
+        r#"{{ changelog(sinceVersion: "0.9") {{ {} }} }}"#,
+        FIELDS
+    ))
+    .await["changelog"]
+        .clone();
+    assert_eq!(since["releases"], all["releases"]);
+
+    let invalid = query(&format!(
+        r#"{{ changelog(sinceVersion: "latest") {{ {} }} }}"#,
+        FIELDS
+    ))
+    .await["changelog"]
+        .clone();
+    assert_eq!(invalid["success"], false);
+    assert_eq!(invalid["error"]["code"], "INVALID_VERSION");
+}
@@ -0,0 +1,127 @@
+//! Session Report Tests
+//!
+//! Tests for the audit log and sessionReport: a session's file edits grouped
+//! by operation and file, with diffs and a Markdown rendering.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext, GqlSchema};
+use std::path::Path;
+
+use common::execute;
+
+fn schema(root: &Path, session: &str) -> GqlSchema {
+    build_schema_with_context(GqlContext::new(root.to_path_buf()).with_session(session))
+}
+
+const REPORT: &str = r#"
+    sessionId changeCount
+    features { operation files }
+    files { path operations created changeCount diff }
+    markdown
+"#;
+
+async fn edit_session(schema: &GqlSchema) {
+    let data = execute(
+        schema,
+        r#"mutation {
+            createScene(input: { path: "res://player.tscn", rootName: "Player", rootType: "Node2D" }) { success }
+            setNodeMetadata(input: { scenePath: "res://player.tscn", nodePath: ".", key: "todo", value: "add sprite" }) { success }
+            createScript(input: { path: "res://player.gd", extends: "Node2D" }) { success }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["createScene"]["success"], true);
+    assert_eq!(data["setNodeMetadata"]["success"], true);
+    assert_eq!(data["createScript"]["success"], true);
+}
+
+#[tokio::test]
+async fn test_session_report_groups_changes_with_diffs() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = schema(dir.path(), "s-edit");
+    edit_session(&schema).await;
+
+    let data = execute(
+        &schema,
+        &format!(
+            r#"{{ sessionReport(sessionId: "s-edit") {{ {} }} }}"#,
+            REPORT
+        ),
+    )
+    .await;
+    let report = &data["sessionReport"];
+    assert_eq!(report["sessionId"], "s-edit");
+    assert_eq!(report["changeCount"], 3);
+
+    let operations: Vec<&str> = report["features"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["operation"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        operations,
+        vec!["createScene", "setNodeMetadata", "createScript"]
+    );
+
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    let scene = &files[1];
+    assert_eq!(scene["path"], "res://player.tscn");
+    assert_eq!(scene["created"], true);
+    assert_eq!(scene["changeCount"], 2);
+    let diff = scene["diff"].as_str().unwrap();
+    assert!(diff.contains("+++ res://player.tscn"), "{}", diff);
+    assert!(
+        diff.contains("+metadata/mcp_todo = \"add sprite\""),
+        "{}",
+        diff
+    );
+
+    let markdown = report["markdown"].as_str().unwrap();
+    assert!(
+        markdown.contains("3 change(s) to 2 file(s)"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("#### `res://player.tscn` (new)"),
+        "{}",
+        markdown
+    );
+    assert!(markdown.contains("```diff"), "{}", markdown);
+}
+
+#[tokio::test]
+async fn test_session_report_defaults_to_latest_session() {
+    let dir = tempfile::tempdir().unwrap();
+    edit_session(&schema(dir.path(), "s-first")).await;
+    let second = schema(dir.path(), "s-second");
+    execute(
+        &second,
+        r#"mutation { setNodeMetadata(input: { scenePath: "res://player.tscn", nodePath: ".", key: "todo" }) { success } }"#,
+    )
+    .await;
+
+    let data = execute(&second, &format!("{{ sessionReport {{ {} }} }}", REPORT)).await;
+    let report = &data["sessionReport"];
+    assert_eq!(report["sessionId"], "s-second");
+    assert_eq!(report["changeCount"], 1);
+    assert_eq!(report["files"][0]["created"], false);
+    assert!(report["files"][0]["diff"]
+        .as_str()
+        .unwrap()
+        .contains("-metadata/mcp_todo"));
+
+    let data = execute(
+        &second,
+        r#"{ sessionReport(sessionId: "s-unknown") { changeCount markdown } }"#,
+    )
+    .await;
+    assert_eq!(data["sessionReport"]["changeCount"], 0);
+    assert!(data["sessionReport"]["markdown"]
+        .as_str()
+        .unwrap()
+        .contains("No file changes recorded"));
+}
@@ -0,0 +1,157 @@
+//! Signal Flow Graph Tests
+//!
+//! Tests for signalFlowGraph: scene connections as labeled edges, autoload
+//! event bus traffic from scripts, and the DOT/MERMAID exports.
+
+mod common;
+
+use common::{execute_in, write};
+
+const PROJECT: &str = r#"[application]
+config/name="Signals"
+
+[autoload]
+Events="*res://events.gd"
+"#;
+
+const MAIN: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://hud.gd" id="1_hud"]
+
+[node name="Main" type="Node2D"]
+
+[node name="Button" type="Button" parent="."]
+
+[node name="Hud" type="CanvasLayer" parent="."]
+script = ExtResource("1_hud")
+
+[connection signal="pressed" from="Button" to="Hud" method="_on_button_pressed"]
+"#;
+
+const HUD: &str = r#"extends CanvasLayer
+
+func _ready():
+	Events.player_died.connect(_on_player_died)
+
+func _on_button_pressed():
+	pass
+
+func _on_player_died():
+	pass
+"#;
+
+const PLAYER: &str = r#"extends Node
+
+func die():
+	Events.player_died.emit()
+"#;
+
+fn setup() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", PROJECT);
+    write(dir.path(), "main.tscn", MAIN);
+    write(dir.path(), "hud.gd", HUD);
+    write(dir.path(), "player.gd", PLAYER);
+    write(
+        dir.path(),
+        "events.gd",
+        "extends Node\n\nsignal player_died\n",
+    );
+    dir
+}
+
+#[tokio::test]
+async fn test_scene_connections_and_event_bus() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ signalFlowGraph(scenePath: "res://main.tscn", format: DOT) {
+            vertices { id kind }
+            edges { from to signal method source }
+            exportedData
+            error { code }
+        } }"#,
+    )
+    .await;
+    let graph = &data["signalFlowGraph"];
+
+    assert!(graph["error"].is_null());
+    assert_eq!(
+        graph["edges"],
+        serde_json::json!([
+            {
+                "from": "res://main.tscn::Button",
+                "to": "res://main.tscn::Hud",
+                "signal": "pressed",
+                "method": "_on_button_pressed",
+                "source": "SCENE"
+            },
+            {
+                "from": "autoload:Events",
+                "to": "res://main.tscn::Hud",
+                "signal": "player_died",
+                "method": "_on_player_died",
+                "source": "SCRIPT"
+            }
+        ])
+    );
+    let kinds: Vec<_> = graph["vertices"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["NODE", "NODE", "NODE", "EVENT_BUS"]);
+
+    let dot = graph["exportedData"].as_str().unwrap();
+    assert!(dot.starts_with("digraph SignalFlow {"));
+    assert!(dot.contains(
+        r#""res://main.tscn::Button" -> "res://main.tscn::Hud" [label="pressed → _on_button_pressed" style=solid];"#
+    ));
+    assert!(dot.contains(r#""autoload:Events" [label="Events" shape=diamond];"#));
+}
+
+#[tokio::test]
+async fn test_project_graph_includes_unattached_scripts() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ signalFlowGraph(format: MERMAID) { edges { from to signal method } exportedData } }"#,
+    )
+    .await;
+    let graph = &data["signalFlowGraph"];
+
+    assert!(graph["edges"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!({
+            "from": "res://player.gd",
+            "to": "autoload:Events",
+            "signal": "player_died",
+            "method": null
+        })));
+
+    let mermaid = graph["exportedData"].as_str().unwrap();
+    assert!(mermaid.starts_with("graph LR\n"));
+    assert!(mermaid.contains("    player_gd((player.gd))\n"));
+    assert!(mermaid.contains("    autoload_Events{Events}\n"));
+    assert!(mermaid.contains("    player_gd -.->|player_died| autoload_Events\n"));
+    assert!(mermaid
+        .contains("    main_tscn__Button -->|\"pressed → _on_button_pressed\"| main_tscn__Hud\n"));
+}
+
+#[tokio::test]
+async fn test_missing_scene() {
+    let dir = setup();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ signalFlowGraph(scenePath: "res://missing.tscn", format: DOT) { exportedData error { code } } }"#,
+    )
+    .await;
+
+    assert_eq!(data["signalFlowGraph"]["error"]["code"], "SCENE_NOT_FOUND");
+    assert_eq!(data["signalFlowGraph"]["exportedData"], "");
+}
@@ -0,0 +1,63 @@
+//! Scene Sub-Resource Tests
+//!
+//! Tests for `[sub_resource]` support: exposure via `scene.subResources` and
+//! preservation when a mutation rewrites the scene.
+
+mod common;
+
+use std::fs;
+
+use common::execute_in;
+
+const LEVEL: &str = r#"[gd_scene load_steps=2 format=3]
+
+[sub_resource type="RectangleShape2D" id="RectangleShape2D_x7"]
+size = Vector2(32, 16)
+
+[node name="Level" type="Node2D"]
+
+[node name="Wall" type="StaticBody2D" parent="."]
+
+[node name="Shape" type="CollisionShape2D" parent="Wall"]
+shape = SubResource("RectangleShape2D_x7")
+"#;
+
+#[tokio::test]
+async fn test_scene_exposes_sub_resources() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let data = execute_in(
+        dir.path(),
+        r#"{ scene(path: "res://level.tscn") { subResources { id type properties { name value } } } }"#,
+    )
+    .await;
+    let subs = &data["scene"]["subResources"];
+    assert_eq!(subs.as_array().unwrap().len(), 1);
+    assert_eq!(subs[0]["id"], "RectangleShape2D_x7");
+    assert_eq!(subs[0]["type"], "RectangleShape2D");
+    assert_eq!(subs[0]["properties"][0]["name"], "size");
+    assert_eq!(subs[0]["properties"][0]["value"], "Vector2(32, 16)");
+}
+
+#[tokio::test]
+async fn test_sub_resources_survive_scene_rewrite() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+
+    let data = execute_in(
+        dir.path(),
+        r#"mutation { computeProperty(input: {
+            scenePath: "res://level.tscn", nodePath: "Wall",
+            property: "position", expression: "Vector2(64, 0)"
+        }) { success } }"#,
+    )
+    .await;
+    assert_eq!(data["computeProperty"]["success"], true);
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains("[sub_resource type=\"RectangleShape2D\" id=\"RectangleShape2D_x7\"]"));
+    assert!(content.contains("size = Vector2(32, 16)"));
+    assert!(content.contains("shape = SubResource(\"RectangleShape2D_x7\")"));
+    assert!(content.starts_with("[gd_scene load_steps=2 format=3]"));
+}
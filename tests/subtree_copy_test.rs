@@ -0,0 +1,149 @@
+//! Subtree Copy/Paste Tests
+//!
+//! Tests for copySubtree and pasteSubtree: copying a node subtree out of one
+//! scene file and pasting it, with its resources and connections, into another.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const ENEMY: &str = r#"[gd_scene load_steps=4 format=3]
+
+[ext_resource type="Texture2D" path="res://enemy.png" id="1_tex"]
+[ext_resource type="Script" path="res://main.gd" id="2_main"]
+
+[sub_resource type="CircleShape2D" id="CircleShape2D_a"]
+radius = 8.0
+
+[node name="Main" type="Node2D"]
+script = ExtResource("2_main")
+
+[node name="Enemy" type="Area2D" parent="."]
+position = Vector2(64, 0)
+
+[node name="Sprite" type="Sprite2D" parent="Enemy"]
+texture = ExtResource("1_tex")
+
+[node name="Shape" type="CollisionShape2D" parent="Enemy"]
+shape = SubResource("CircleShape2D_a")
+
+[connection signal="body_entered" from="Enemy" to="Enemy/Sprite" method="_on_hit"]
+[connection signal="body_entered" from="Enemy" to="." method="_on_enemy_hit"]
+"#;
+
+const LEVEL: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Texture2D" path="res://enemy.png" id="1_enemy"]
+
+[node name="Level" type="Node2D"]
+
+[node name="Enemies" type="Node2D" parent="."]
+
+[node name="Enemy" type="Sprite2D" parent="Enemies"]
+texture = ExtResource("1_enemy")
+"#;
+
+#[tokio::test]
+async fn test_copy_and_paste_subtree_across_scenes() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("enemy.tscn"), ENEMY).unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{ copySubtree(scenePath: "res://enemy.tscn", nodePath: "Enemy") {
+            token nodeCount extResources subResourceCount error { code }
+        } }"#,
+    )
+    .await;
+    let copied = &data["copySubtree"];
+    assert!(copied["error"].is_null());
+    assert_eq!(copied["nodeCount"], 3);
+    assert_eq!(
+        copied["extResources"],
+        serde_json::json!(["res://enemy.png"])
+    );
+    assert_eq!(copied["subResourceCount"], 1);
+    let token = copied["token"].as_str().unwrap();
+
+    let data = execute(
+        &schema,
+        &format!(
+            r#"mutation {{ pasteSubtree(input: {{
+                targetScene: "res://level.tscn", parent: "Enemies", token: "{}"
+            }}) {{ success message }} }}"#,
+            token
+        ),
+    )
+    .await;
+    assert_eq!(
+        data["pasteSubtree"]["message"],
+        "Pasted to 'Enemies/Enemy2'"
+    );
+
+    let tscn = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(tscn.contains(
+        "[node name=\"Enemy2\" type=\"Area2D\" parent=\"Enemies\"]\nposition = Vector2(64, 0)"
+    ));
+    // The texture already loaded by the level is reused
+    assert_eq!(tscn.matches("[ext_resource").count(), 1);
+    assert!(tscn.contains(
+        "[node name=\"Sprite\" type=\"Sprite2D\" parent=\"Enemies/Enemy2\"]\ntexture = ExtResource(\"1_enemy\")"
+    ));
+    assert!(
+        tscn.contains("[sub_resource type=\"CircleShape2D\" id=\"CircleShape2D_a\"]\nradius = 8.0")
+    );
+    assert!(tscn.contains("shape = SubResource(\"CircleShape2D_a\")"));
+    // Only connections inside the subtree come along
+    assert!(tscn.contains(
+        "[connection signal=\"body_entered\" from=\"Enemies/Enemy2\" to=\"Enemies/Enemy2/Sprite\" method=\"_on_hit\"]"
+    ));
+    assert!(!tscn.contains("_on_enemy_hit"));
+    assert!(tscn.starts_with("[gd_scene load_steps=3 format=3]"));
+}
+
+#[tokio::test]
+async fn test_copy_and_paste_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("enemy.tscn"), ENEMY).unwrap();
+    fs::write(dir.path().join("level.tscn"), LEVEL).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    let data = execute(
+        &schema,
+        r#"{
+            node: copySubtree(scenePath: "res://enemy.tscn", nodePath: "Missing") { token error { code } }
+            scene: copySubtree(scenePath: "res://missing.tscn", nodePath: "Enemy") { error { code } }
+            root: copySubtree(scenePath: "res://enemy.tscn", nodePath: ".") { token nodeCount }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["node"]["error"]["code"], "NODE_NOT_FOUND");
+    assert_eq!(data["node"]["token"], "");
+    assert_eq!(data["scene"]["error"]["code"], "SCENE_NOT_FOUND");
+    assert_eq!(data["root"]["nodeCount"], 4);
+    let token = data["root"]["token"].as_str().unwrap();
+
+    let data = execute(
+        &schema,
+        &format!(
+            r#"mutation {{
+                parent: pasteSubtree(input: {{ targetScene: "res://level.tscn", parent: "Missing", token: "{}" }}) {{ success error {{ code }} }}
+                token: pasteSubtree(input: {{ targetScene: "res://level.tscn", parent: ".", token: "not a token" }}) {{ success error {{ code }} }}
+            }}"#,
+            token
+        ),
+    )
+    .await;
+    assert_eq!(data["parent"]["success"], false);
+    assert_eq!(data["parent"]["error"]["code"], "NODE_PASTE_FAILED");
+    assert_eq!(data["token"]["error"]["code"], "INVALID_SUBTREE_TOKEN");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("level.tscn")).unwrap(),
+        LEVEL
+    );
+}
@@ -0,0 +1,136 @@
+//! runTestCoverage Tests
+//!
+//! GUT runs against a stand-in Godot executable that keeps the coverage plan
+//! the pre-run hook would read and writes the hits the post-run hook would.
+
+#![cfg(unix)]
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+use common::{fake_godot, write};
+
+/// Copies the plan to the project's `plan.json`, reports line 5 of the first
+/// script as hit and prints a passing run
+const FAKE_GODOT: &str = r#"#!/bin/sh
+project=""
+hooks=""
+for arg in "$@"; do
+  if [ "$prev" = "--path" ]; then project="$arg"; fi
+  case "$arg" in -gpost_run_script=*) hooks="yes" ;; esac
+  prev="$arg"
+done
+if [ -n "$hooks" ]; then
+  cp "$project/.godot-mcp/coverage_plan.json" "$project/plan.json"
+  printf '{"0":[5]}' > "$project/.godot-mcp/coverage_hits.json"
+fi
+cat <<'EOF'
+res://tests/test_player.gd
+* test_heal
+
+==============================================
+= Run Summary
+==============================================
+Time                  0.010s
+EOF
+"#;
+
+const PLAYER: &str = "extends Node
+
+var health := 10
+
+func heal(amount: int) -> void:
+\thealth += amount
+\tif health > 100:
+\t\thealth = 100
+";
+
+async fn test_coverage(root: &Path, input: &str) -> serde_json::Value {
+    fake_godot(FAKE_GODOT);
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let query = format!(
+        r#"mutation {{ runTestCoverage(input: {}) {{
+            success coveredLines totalLines percent
+            scripts {{ path coveredLines totalLines percent uncoveredLines }}
+            tests {{ totalCount }}
+            error {{ code message }}
+        }} }}"#,
+        input
+    );
+    let response = schema.execute(query.as_str()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["runTestCoverage"].clone()
+}
+
+#[tokio::test]
+async fn test_test_coverage() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "addons/gut/gut_cmdln.gd", "extends SceneTree\n");
+    write(dir.path(), "player.gd", PLAYER);
+    write(
+        dir.path(),
+        "tests/test_player.gd",
+        "extends GutTest\n\nfunc test_heal():\n\tassert_true(true)\n",
+    );
+
+    let result = test_coverage(dir.path(), "{}").await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["tests"]["totalCount"], 1);
+    // Test scripts are not measured
+    assert_eq!(result["scripts"].as_array().unwrap().len(), 1);
+    let player = &result["scripts"][0];
+    assert_eq!(player["path"], "res://player.gd");
+    assert_eq!(player["totalLines"], 3);
+    assert_eq!(player["coveredLines"], 0);
+    assert_eq!(player["uncoveredLines"], serde_json::json!([6, 7, 8]));
+
+    // The hook received an instrumented copy of the script
+    let plan: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("plan.json")).unwrap()).unwrap();
+    assert_eq!(plan[0]["path"], "res://player.gd");
+    let source = plan[0]["source"].as_str().unwrap();
+    assert!(
+        source
+            .contains("\tEngine.get_meta(\"godot_mcp_coverage\")[0][6] = true\n\thealth += amount"),
+        "{}",
+        source
+    );
+    assert!(!dir.path().join(".godot-mcp/coverage_plan.json").exists());
+}
+
+#[tokio::test]
+async fn test_test_coverage_counts_hits() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "addons/gut/gut_cmdln.gd", "extends SceneTree\n");
+    // Line 5 is the first statement here
+    write(
+        dir.path(),
+        "src/counter.gd",
+        "extends Node\n\n\nfunc count() -> int:\n\treturn 1\n",
+    );
+    write(dir.path(), "other.gd", PLAYER);
+
+    let result = test_coverage(dir.path(), r#"{ path: "res://src" }"#).await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["scripts"].as_array().unwrap().len(), 1);
+    assert_eq!(result["scripts"][0]["path"], "res://src/counter.gd");
+    assert_eq!(result["coveredLines"], 1);
+    assert_eq!(result["totalLines"], 1);
+    assert_eq!(result["percent"], 100.0);
+}
+
+#[tokio::test]
+async fn test_test_coverage_without_gut() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "player.gd", PLAYER);
+
+    let result = test_coverage(dir.path(), "{}").await;
+    assert_eq!(result["success"], false);
+    assert_eq!(result["error"]["code"], "GUT_NOT_INSTALLED");
+}
@@ -0,0 +1,140 @@
+//! generateThumbnails Tests
+//!
+//! Runs against a stand-in Godot executable that records its arguments and
+//! saves a PNG for every scene after `--` except those named `broken`.
+
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use common::{execute_in, fake_godot, write};
+
+const FAKE_GODOT: &str = r#"#!/bin/sh
+project=""
+while [ $# -gt 0 ]; do
+  if [ "$1" = "--path" ]; then project="$2"; fi
+  if [ "$1" = "--" ]; then shift; break; fi
+  echo "$1" >> "$project/args"
+  shift
+done
+folder="$project/${1#res://}"
+echo "size=$2" >> "$project/args"
+shift 2
+for scene in "$@"; do
+  case "$scene" in
+    *broken*) echo "THUMBNAIL_ERROR: $scene: Cannot load scene" ;;
+    *)
+      target="$folder/${scene#res://}.png"
+      mkdir -p "$(dirname "$target")"
+      printf 'PNG' > "$target"
+      echo "THUMBNAIL: $scene"
+      ;;
+  esac
+done
+"#;
+
+const SCENE: &str = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"]\n";
+
+async fn execute(root: &Path, query: &str) -> serde_json::Value {
+    fake_godot(FAKE_GODOT);
+    execute_in(root, query).await
+}
+
+#[tokio::test]
+async fn test_generate_thumbnails_for_all_scenes() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "main.tscn", SCENE);
+    write(dir.path(), "levels/level_1.tscn", SCENE);
+
+    let data = execute(
+        dir.path(),
+        r#"mutation { generateThumbnails(size: 128) {
+            success renderedCount thumbnails { scenePath thumbnailPath error } error { code }
+        } }"#,
+    )
+    .await;
+    let result = &data["generateThumbnails"];
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["renderedCount"], 2);
+    assert_eq!(
+        result["thumbnails"][0]["thumbnailPath"],
+        "res://.godot-mcp/thumbnails/levels/level_1.tscn.png"
+    );
+    assert!(dir
+        .path()
+        .join(".godot-mcp/thumbnails/main.tscn.png")
+        .is_file());
+
+    // Godot draws with a window; headless mode renders nothing
+    let args = fs::read_to_string(dir.path().join("args")).unwrap();
+    assert!(!args.lines().any(|arg| arg == "--headless"), "{}", args);
+    assert!(args.lines().any(|arg| arg == "size=128"), "{}", args);
+
+    // The Scene type exposes the thumbnail
+    let data = execute(
+        dir.path(),
+        r#"{ scene(path: "res://main.tscn") { thumbnail } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["scene"]["thumbnail"],
+        "res://.godot-mcp/thumbnails/main.tscn.png"
+    );
+}
+
+#[tokio::test]
+async fn test_generate_thumbnails_reports_failures() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+    write(dir.path(), "main.tscn", SCENE);
+    write(dir.path(), "broken.tscn", SCENE);
+    // Left from an earlier run; removed before rendering again
+    write(dir.path(), ".godot-mcp/thumbnails/broken.tscn.png", "old");
+
+    let data = execute(
+        dir.path(),
+        r#"mutation { generateThumbnails(scenes: ["res://broken.tscn", "res://missing.tscn", "res://main.tscn"]) {
+            success renderedCount thumbnails { scenePath thumbnailPath error }
+        } }"#,
+    )
+    .await;
+    let result = &data["generateThumbnails"];
+    assert_eq!(result["success"], false);
+    assert_eq!(result["renderedCount"], 1);
+    let thumbnails = result["thumbnails"].as_array().unwrap();
+    assert_eq!(thumbnails[0]["error"], "Cannot load scene");
+    assert_eq!(thumbnails[0]["thumbnailPath"], serde_json::Value::Null);
+    assert_eq!(thumbnails[1]["error"], "Scene not found");
+    assert_eq!(
+        thumbnails[2]["thumbnailPath"],
+        "res://.godot-mcp/thumbnails/main.tscn.png"
+    );
+    assert!(!dir
+        .path()
+        .join(".godot-mcp/thumbnails/broken.tscn.png")
+        .exists());
+
+    let data = execute(
+        dir.path(),
+        r#"{ scene(path: "res://broken.tscn") { thumbnail } }"#,
+    )
+    .await;
+    assert_eq!(data["scene"]["thumbnail"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_generate_thumbnails_rejects_size() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "project.godot", "config_version=5\n");
+
+    let data = execute(
+        dir.path(),
+        r#"mutation { generateThumbnails(size: 4096) { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(data["generateThumbnails"]["error"]["code"], "INVALID_SIZE");
+}
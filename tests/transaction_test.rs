@@ -0,0 +1,110 @@
+//! Transaction Tests
+//!
+//! Tests for beginTransaction/commitTransaction/rollbackTransaction: file
+//! writes staged in memory until commit, and discarded on rollback.
+
+mod common;
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+
+use common::execute;
+
+const ADD_ENEMY: &str = r#"mutation {
+    applyScenePatch(path: "res://scenes/main.tscn", patch: { operations: [
+        { op: "addNode", parent: ".", name: "Enemy", type: "CharacterBody2D" }
+    ] }) { success }
+}"#;
+
+#[tokio::test]
+async fn test_commit_writes_staged_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let scene_path = dir.path().join("scenes/main.tscn");
+
+    let data = execute(
+        &schema,
+        r#"mutation { beginTransaction(name: "level") { success transactionId } }"#,
+    )
+    .await;
+    assert_eq!(data["beginTransaction"]["success"], true);
+    assert!(data["beginTransaction"]["transactionId"]
+        .as_str()
+        .unwrap()
+        .starts_with("tx-"));
+
+    execute(
+        &schema,
+        r#"mutation { createScene(input: { path: "res://scenes/main.tscn", rootName: "Main", rootType: "Node2D" }) { success } }"#,
+    )
+    .await;
+    let data = execute(&schema, ADD_ENEMY).await;
+    assert_eq!(data["applyScenePatch"]["success"], true);
+
+    // Reads see the staged scene, the disk does not
+    let data = execute(
+        &schema,
+        r#"{ scene(path: "res://scenes/main.tscn") { allNodes { path } } }"#,
+    )
+    .await;
+    assert_eq!(data["scene"]["allNodes"][1]["path"], "Enemy");
+    assert!(!scene_path.exists());
+
+    let data = execute(
+        &schema,
+        r#"mutation { beginTransaction(name: "again") { success error { code } } }"#,
+    )
+    .await;
+    assert_eq!(
+        data["beginTransaction"]["error"]["code"],
+        "TRANSACTION_ALREADY_OPEN"
+    );
+
+    let data = execute(&schema, "mutation { commitTransaction { success files } }").await;
+    assert_eq!(data["commitTransaction"]["success"], true);
+    assert!(data["commitTransaction"]["files"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("res://scenes/main.tscn")));
+    let content = fs::read_to_string(&scene_path).unwrap();
+    assert!(content.contains("[node name=\"Enemy\" type=\"CharacterBody2D\" parent=\".\"]"));
+}
+
+#[tokio::test]
+async fn test_rollback_discards_staged_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let scene_path = dir.path().join("scenes/main.tscn");
+    fs::create_dir_all(scene_path.parent().unwrap()).unwrap();
+    let original = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node2D\"]\n";
+    fs::write(&scene_path, original).unwrap();
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+
+    execute(
+        &schema,
+        r#"mutation { beginTransaction(name: "try") { success } }"#,
+    )
+    .await;
+    execute(&schema, ADD_ENEMY).await;
+    let data = execute(
+        &schema,
+        "mutation { rollbackTransaction { success files } }",
+    )
+    .await;
+    assert_eq!(data["rollbackTransaction"]["success"], true);
+    assert_eq!(fs::read_to_string(&scene_path).unwrap(), original);
+    assert!(!dir.path().join(".godot-mcp").exists());
+
+    let data = execute(
+        &schema,
+        r#"{ scene(path: "res://scenes/main.tscn") { allNodes { path } } }"#,
+    )
+    .await;
+    assert_eq!(data["scene"]["allNodes"].as_array().unwrap().len(), 1);
+
+    let data = execute(
+        &schema,
+        "mutation { commitTransaction { success error { code } } }",
+    )
+    .await;
+    assert_eq!(data["commitTransaction"]["error"]["code"], "NO_TRANSACTION");
+}
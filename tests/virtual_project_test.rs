@@ -0,0 +1,154 @@
+//! Virtual Project Tests
+//!
+//! Tests for running queries and mutations against an in-memory project
+//! built from a JSON fixture, without touching disk.
+
+use godot_mcp_rs::graphql::vfs::{MemoryFs, ProjectFs};
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const FIXTURE: &str = include_str!("fixtures/sandbox_project.json");
+
+/// Project root that does not exist on disk
+fn virtual_root(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("godot-mcp-virtual-{}-{}", name, std::process::id()))
+}
+
+fn load_fixture(root: &Path) -> Arc<MemoryFs> {
+    let fixture: serde_json::Value = serde_json::from_str(FIXTURE).unwrap();
+    Arc::new(MemoryFs::from_json(root, &fixture).unwrap())
+}
+
+async fn execute(ctx: &GqlContext, query: &str) -> serde_json::Value {
+    let schema = build_schema_with_context(ctx.clone());
+    let result = schema.execute(query).await;
+    assert!(result.errors.is_empty(), "Errors: {:?}", result.errors);
+    result.data.into_json().unwrap()
+}
+
+#[tokio::test]
+async fn test_queries_read_the_fixture() {
+    let root = virtual_root("queries");
+    let ctx = GqlContext::new(root.clone()).with_fs(load_fixture(&root));
+
+    let data = execute(
+        &ctx,
+        r#"{
+            project { name stats { sceneCount scriptCount } }
+            scene(path: "res://scenes/main.tscn") { root { name type } allNodes { name } }
+            script(path: "res://scripts/player.gd") { className functions { name } exports { name } }
+            dependencyGraph { edges { from to referenceType } }
+            autoloads { autoloads { name path } }
+        }"#,
+    )
+    .await;
+
+    assert_eq!(data["project"]["name"], "Sandbox");
+    assert_eq!(
+        data["project"]["stats"],
+        serde_json::json!({ "sceneCount": 1, "scriptCount": 2 })
+    );
+    assert_eq!(data["scene"]["root"]["type"], "Node2D");
+    assert_eq!(data["scene"]["allNodes"].as_array().unwrap().len(), 2);
+    assert_eq!(data["script"]["className"], "Player");
+    assert_eq!(data["script"]["functions"][0]["name"], "take_damage");
+    assert_eq!(data["script"]["exports"][0]["name"], "speed");
+    assert_eq!(
+        data["dependencyGraph"]["edges"],
+        serde_json::json!([{
+            "from": "res://scenes/main.tscn",
+            "to": "res://scripts/player.gd",
+            "referenceType": "ATTACHES_SCRIPT"
+        }])
+    );
+    assert_eq!(
+        data["autoloads"]["autoloads"][0]["path"],
+        "res://scripts/events.gd"
+    );
+    assert!(!root.exists());
+}
+
+#[tokio::test]
+async fn test_mutations_stay_in_memory() {
+    let root = virtual_root("mutations");
+    let fs = load_fixture(&root);
+    let ctx = GqlContext::new(root.clone()).with_fs(fs.clone());
+
+    let data = execute(
+        &ctx,
+        r#"mutation {
+            createScene(input: { path: "res://scenes/level.tscn", rootName: "Level", rootType: "Node3D" }) { success }
+            createScript(input: { path: "res://scripts/enemy.gd", extends: "Node3D" }) { success }
+            moveFile(input: { from: "res://scripts/player.gd", to: "res://actors/player.gd" }) {
+                success referencesUpdated
+            }
+        }"#,
+    )
+    .await;
+    assert_eq!(data["createScene"]["success"], true);
+    assert_eq!(data["createScript"]["success"], true);
+    assert_eq!(data["moveFile"]["success"], true);
+    assert_eq!(data["moveFile"]["referencesUpdated"], 1);
+
+    // Later queries see the edits, including the file listing
+    let data = execute(
+        &ctx,
+        r#"{
+            project { stats { sceneCount scriptCount } }
+            scene(path: "res://scenes/main.tscn") { externalResources { path } }
+            sessionReport { changeCount }
+        }"#,
+    )
+    .await;
+    assert_eq!(
+        data["project"]["stats"],
+        serde_json::json!({ "sceneCount": 2, "scriptCount": 3 })
+    );
+    assert_eq!(
+        data["scene"]["externalResources"][0]["path"],
+        "res://actors/player.gd"
+    );
+    assert!(data["sessionReport"]["changeCount"].as_i64().unwrap() >= 4);
+
+    assert!(fs.is_file(&root.join("actors/player.gd")));
+    assert!(!fs.exists(&root.join("scripts/player.gd")));
+    assert!(fs.is_file(&root.join(".godot-mcp/audit.jsonl")));
+    let snapshot = fs.to_json(&root);
+    assert!(snapshot["scenes/level.tscn"]
+        .as_str()
+        .unwrap()
+        .contains("type=\"Node3D\""));
+    assert!(!root.exists());
+}
+
+#[tokio::test]
+async fn test_checkpoint_of_virtual_project() {
+    let root = virtual_root("checkpoint");
+    let ctx = GqlContext::new(root.clone()).with_fs(load_fixture(&root));
+
+    let data = execute(
+        &ctx,
+        r#"mutation { createCheckpoint(label: "before") { success checkpoint { id fileCount } } }"#,
+    )
+    .await;
+    let checkpoint = &data["createCheckpoint"]["checkpoint"];
+    assert_eq!(checkpoint["fileCount"], 4);
+
+    execute(
+        &ctx,
+        r#"mutation { moveFile(input: { from: "res://scripts/player.gd", to: "res://player.gd" }) { success } }"#,
+    )
+    .await;
+
+    let data = execute(
+        &ctx,
+        &format!(
+            r#"{{ projectAt(checkpointId: "{}") {{ script(path: "res://scripts/player.gd") {{ className }} }} }}"#,
+            checkpoint["id"].as_str().unwrap()
+        ),
+    )
+    .await;
+    assert_eq!(data["projectAt"]["script"]["className"], "Player");
+    assert!(!root.exists());
+}
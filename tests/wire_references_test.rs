@@ -0,0 +1,185 @@
+//! Exported Reference Wiring Tests
+//!
+//! Tests for wireExportedReferences: audit of exported node references in
+//! attached scripts and assignment of the matching scene nodes.
+
+use godot_mcp_rs::graphql::{build_schema_with_context, GqlContext};
+use std::fs;
+use std::path::Path;
+
+const SCRIPT: &str = r#"extends CharacterBody2D
+
+@export var speed: float = 300.0
+@export var camera: Camera2D
+@export var hurt_box: Area2D
+@export_node_path("Sprite2D") var body_sprite: NodePath
+@export var health_bar: NodePath
+@export var marker: Marker2D
+"#;
+
+const SCENE: &str = r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://player.gd" id="1_abc"]
+
+[node name="Level" type="Node2D"]
+
+[node name="Player" type="CharacterBody2D" parent="."]
+script = ExtResource("1_abc")
+marker = NodePath("../Spawn")
+
+[node name="Sprite" type="Sprite2D" parent="Player"]
+
+[node name="HurtBox" type="Area2D" parent="Player"]
+
+[node name="Camera" type="Camera2D" parent="."]
+
+[node name="Spawn" type="Marker2D" parent="."]
+
+[node name="HealthBar" type="ProgressBar" parent="."]
+
+[node name="HealthBarBackup" type="ProgressBar" parent="."]
+"#;
+
+fn setup_project(root: &Path) {
+    fs::write(root.join("project.godot"), "[application]\n").unwrap();
+    fs::write(root.join("player.gd"), SCRIPT).unwrap();
+    fs::write(root.join("level.tscn"), SCENE).unwrap();
+}
+
+async fn wire(root: &Path, dry_run: bool) -> serde_json::Value {
+    let schema = build_schema_with_context(GqlContext::new(root.to_path_buf()));
+    let mutation = format!(
+        r#"mutation {{
+            wireExportedReferences(input: {{ scenePath: "res://level.tscn", dryRun: {} }}) {{
+                success dryRun wiredCount unresolvedCount
+                references {{
+                    nodePath property exportType status value
+                    candidates {{ nodePath relativePath score }}
+                }}
+                error {{ code }}
+            }}
+        }}"#,
+        dry_run
+    );
+    let response = schema.execute(&mutation).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()["wireExportedReferences"].clone()
+}
+
+fn reference<'a>(result: &'a serde_json::Value, property: &str) -> &'a serde_json::Value {
+    result["references"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["property"] == property)
+        .unwrap_or_else(|| panic!("missing reference {}", property))
+}
+
+#[tokio::test]
+async fn test_wire_exported_references_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = wire(dir.path(), true).await;
+    assert_eq!(result["success"], true);
+    assert_eq!(result["dryRun"], true);
+
+    // Non-node exports are ignored
+    assert_eq!(result["references"].as_array().unwrap().len(), 5);
+
+    let camera = reference(&result, "camera");
+    assert_eq!(camera["status"], "WIRED");
+    assert_eq!(camera["value"], "NodePath(\"../Camera\")");
+
+    let hurt_box = reference(&result, "hurt_box");
+    assert_eq!(hurt_box["status"], "WIRED");
+    assert_eq!(hurt_box["value"], "NodePath(\"HurtBox\")");
+
+    let sprite = reference(&result, "body_sprite");
+    assert_eq!(sprite["status"], "WIRED");
+    assert_eq!(sprite["value"], "NodePath(\"Sprite\")");
+
+    let health_bar = reference(&result, "health_bar");
+    assert_eq!(health_bar["status"], "WIRED");
+    assert_eq!(health_bar["candidates"][0]["nodePath"], "HealthBar");
+    assert_eq!(health_bar["candidates"][0]["score"], 3);
+    assert_eq!(health_bar["candidates"][1]["score"], 1);
+
+    let marker = reference(&result, "marker");
+    assert_eq!(marker["status"], "ASSIGNED");
+    assert_eq!(marker["value"], "NodePath(\"../Spawn\")");
+
+    assert_eq!(result["wiredCount"], 4);
+    assert_eq!(result["unresolvedCount"], 0);
+
+    // Dry run leaves the scene untouched
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(!content.contains("camera ="));
+}
+
+#[tokio::test]
+async fn test_wire_exported_references_writes_scene() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_project(dir.path());
+
+    let result = wire(dir.path(), false).await;
+    assert_eq!(result["success"], true, "{}", result);
+    assert_eq!(result["wiredCount"], 4);
+
+    let content = fs::read_to_string(dir.path().join("level.tscn")).unwrap();
+    assert!(content.contains("camera = NodePath(\"../Camera\")"));
+    assert!(content.contains("body_sprite = NodePath(\"Sprite\")"));
+
+    // Second pass finds everything assigned
+    let result = wire(dir.path(), true).await;
+    assert_eq!(result["wiredCount"], 0);
+    assert_eq!(reference(&result, "camera")["status"], "ASSIGNED");
+}
+
+#[tokio::test]
+async fn test_wire_exported_references_ambiguous() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("turret.gd"),
+        "extends Node2D\n\n@export var target: Marker2D\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("turret.tscn"),
+        r#"[gd_scene load_steps=2 format=3]
+
+[ext_resource type="Script" path="res://turret.gd" id="1"]
+
+[node name="Turret" type="Node2D"]
+script = ExtResource("1")
+
+[node name="Left" type="Marker2D" parent="."]
+
+[node name="Right" type="Marker2D" parent="."]
+"#,
+    )
+    .unwrap();
+
+    let schema = build_schema_with_context(GqlContext::new(dir.path().to_path_buf()));
+    let response = schema
+        .execute(
+            r#"mutation {
+                wireExportedReferences(input: { scenePath: "res://turret.tscn", dryRun: true }) {
+                    unresolvedCount
+                    references { status candidates { relativePath } }
+                }
+            }"#,
+        )
+        .await;
+    let data = response.data.into_json().unwrap();
+    let result = &data["wireExportedReferences"];
+    assert_eq!(result["unresolvedCount"], 1);
+    assert_eq!(result["references"][0]["status"], "AMBIGUOUS");
+    assert_eq!(
+        result["references"][0]["candidates"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}